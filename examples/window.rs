@@ -424,6 +424,9 @@ impl ApplicationHandler for Application {
                 window.modifiers = modifiers.state();
                 info!("Modifiers changed to {:?}", window.modifiers);
             },
+            WindowEvent::KeyboardLayoutChanged(layout) => {
+                info!("Keyboard layout changed to {}", layout.name);
+            },
             WindowEvent::MouseWheel { delta, .. } => match delta {
                 MouseScrollDelta::LineDelta(x, y) => {
                     info!("Mouse wheel Line Delta: ({x},{y})");
@@ -484,8 +487,16 @@ impl ApplicationHandler for Application {
                 Ime::Commit(text) => {
                     info!("Committed: {}", text);
                 },
+                Ime::SurroundingTextRequest => info!("IME requested the surrounding text"),
+                Ime::Candidates { candidates, selected, .. } => {
+                    info!("IME candidates: {candidates:?}, selected: {selected:?}");
+                },
                 Ime::Disabled => info!("IME disabled for Window={window_id:?}"),
             },
+            WindowEvent::Compose(compose) => info!("Compose: {compose:?}"),
+            WindowEvent::VirtualKeyboardGeometryChanged { position, size } => {
+                info!("Virtual keyboard covers {size:?} at {position:?}");
+            },
             WindowEvent::PinchGesture { delta, .. } => {
                 window.zoom += delta;
                 let zoom = window.zoom;
@@ -513,13 +524,25 @@ impl ApplicationHandler for Application {
                 info!("Smart zoom");
             },
             WindowEvent::TouchpadPressure { .. }
+            | WindowEvent::PenInput { .. }
             | WindowEvent::HoveredFileCancelled
             | WindowEvent::KeyboardInput { .. }
             | WindowEvent::PointerEntered { .. }
             | WindowEvent::DroppedFile(_)
             | WindowEvent::HoveredFile(_)
+            | WindowEvent::DragEntered { .. }
+            | WindowEvent::DragMoved { .. }
+            | WindowEvent::DragLeft
+            | WindowEvent::DragDropped { .. }
+            | WindowEvent::DragEnded { .. }
             | WindowEvent::Destroyed
+            | WindowEvent::AccentColorChanged(_)
+            | WindowEvent::ColorProfileChanged
+            | WindowEvent::CursorGrabChanged(_)
             | WindowEvent::Moved(_) => (),
+            #[cfg(feature = "dialogs")]
+            WindowEvent::ModalInputRejected => (),
+            _ => (),
         }
     }
 
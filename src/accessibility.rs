@@ -0,0 +1,53 @@
+//! Accessibility of the content of windows with [AccessKit].
+//!
+//! The content of a window is exposed to the assistive technologies, like screen readers, once the
+//! source of its accessibility tree was set with [`Window::set_accesskit_tree_source()`]. The
+//! window then owns the platform adapter of AccessKit, which follows the focus and the bounds of
+//! the window, and is dropped with it.
+//!
+//! The tree is made of the types of the [`accesskit`] crate, which is re-exported here so that its
+//! version matches. The source is asked for the initial tree once an assistive technology starts
+//! using the window, and the changes are pushed afterwards with
+//! [`Window::update_accesskit_tree()`]. The actions the assistive technologies request, like
+//! focusing or clicking an element, are delivered as [`WindowEvent::AccessKitActionRequested`].
+//!
+//! ```no_run
+//! use winit::accessibility::accesskit::{ActivationHandler, Node, NodeId, Role, Tree, TreeUpdate};
+//! # use winit::window::Window;
+//!
+//! struct Source;
+//!
+//! impl ActivationHandler for Source {
+//!     fn request_initial_tree(&mut self) -> Option<TreeUpdate> {
+//!         let root = NodeId(0);
+//!         Some(TreeUpdate {
+//!             nodes: vec![(root, Node::new(Role::Window))],
+//!             tree: Some(Tree::new(root)),
+//!             focus: root,
+//!         })
+//!     }
+//! }
+//!
+//! # fn scope(window: &dyn Window) {
+//! window.set_accesskit_tree_source(Box::new(Source)).unwrap();
+//! # }
+//! ```
+//!
+//! ## Platform-specific
+//!
+//! - **Windows:** The window answers the `WM_GETOBJECT` messages with UI Automation. The source is
+//!   called on the thread of the window.
+//! - **macOS:** The view of the window implements the `NSAccessibility` methods. The source is
+//!   called on the main thread.
+//! - **Wayland / X11:** The window is registered with AT-SPI over D-Bus. The source is called on a
+//!   thread of the adapter.
+//! - **iOS / Android / Web / Orbital:** Unsupported.
+//!
+//! This module is only available with the `accesskit` cargo feature.
+//!
+//! [AccessKit]: https://accesskit.dev
+//! [`Window::set_accesskit_tree_source()`]: crate::window::Window::set_accesskit_tree_source()
+//! [`Window::update_accesskit_tree()`]: crate::window::Window::update_accesskit_tree()
+//! [`WindowEvent::AccessKitActionRequested`]: crate::event::WindowEvent::AccessKitActionRequested
+
+pub use accesskit;
@@ -1,10 +1,21 @@
 //! End user application handling.
 
-use crate::event::{DeviceEvent, DeviceId, StartCause, WindowEvent};
-use crate::event_loop::ActiveEventLoop;
+use std::path::PathBuf;
+
+use crate::clipboard::ClipboardData;
+#[cfg(feature = "dialogs")]
+use crate::dialogs::{FileDialogResult, MessageBoxResult};
+use crate::event::{
+    DeviceEvent, DeviceId, DeviceInfo, LanguageTag, PowerEvent, StartCause, SystemPreferences,
+    WindowEvent,
+};
+use crate::event_loop::{ActiveEventLoop, AsyncRequestSerial, ExitResponse};
+use crate::monitor::MonitorHandle;
 #[cfg(any(docsrs, macos_platform))]
 use crate::platform::macos::ApplicationHandlerExtMacOS;
-use crate::window::WindowId;
+#[cfg(any(docsrs, windows_platform))]
+use crate::platform::windows::ApplicationHandlerExtWindows;
+use crate::window::{CursorThemeInfo, WindowId};
 
 /// The handler of the application events.
 pub trait ApplicationHandler {
@@ -212,6 +223,110 @@ pub trait ApplicationHandler {
         let _ = (event_loop, device_id, event);
     }
 
+    /// Emitted when an input device was connected.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Only emitted for mice and keyboards, while their device events are captured,
+    ///   and for gamepads. See [`ActiveEventLoop::listen_device_events()`].
+    /// - **Wayland / Web:** Only emitted for gamepads, with the `gamepad` cargo feature.
+    /// - **macOS / iOS / Android / Orbital:** Unsupported.
+    fn input_device_added(&mut self, event_loop: &dyn ActiveEventLoop, device: DeviceInfo) {
+        let _ = (event_loop, device);
+    }
+
+    /// Emitted when an input device was disconnected.
+    ///
+    /// ## Platform-specific
+    ///
+    /// See [`input_device_added()`][Self::input_device_added()].
+    fn input_device_removed(&mut self, event_loop: &dyn ActiveEventLoop, device_id: DeviceId) {
+        let _ = (event_loop, device_id);
+    }
+
+    /// Emitted when a monitor was connected.
+    ///
+    /// The application can offer it for fullscreen, without polling
+    /// [`ActiveEventLoop::available_monitors()`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Emitted in response to `WM_DISPLAYCHANGE`.
+    /// - **macOS:** Emitted in response to `NSApplicationDidChangeScreenParametersNotification`.
+    /// - **X11:** Emitted in response to the RandR screen change notifications.
+    /// - **Wayland:** Emitted when the compositor advertises a new `wl_output`.
+    /// - **iOS / Android / Web / Orbital:** Unsupported.
+    fn monitor_added(&mut self, event_loop: &dyn ActiveEventLoop, monitor: MonitorHandle) {
+        let _ = (event_loop, monitor);
+    }
+
+    /// Emitted when a monitor was disconnected.
+    ///
+    /// The handle can't be queried anymore, but compares equal to the one of the monitor the
+    /// application knew, so it can move its windows off it.
+    ///
+    /// ## Platform-specific
+    ///
+    /// See [`monitor_added()`][Self::monitor_added()].
+    fn monitor_removed(&mut self, event_loop: &dyn ActiveEventLoop, monitor: MonitorHandle) {
+        let _ = (event_loop, monitor);
+    }
+
+    /// Emitted when the [color capabilities][MonitorHandle::color_capabilities] of a monitor
+    /// changed, for example when HDR was turned on or off.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Emitted when the display configuration changes.
+    /// - **macOS:** Emitted when the screen parameters change.
+    /// - **Wayland:** Emitted when the image description of the output changes.
+    /// - **X11 / iOS / Android / Web / Orbital:** Unsupported.
+    fn color_capabilities_changed(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        monitor: MonitorHandle,
+    ) {
+        let _ = (event_loop, monitor);
+    }
+
+    /// Emitted when the [orientation][MonitorHandle::orientation] of a monitor changed, for
+    /// example when a tablet was rotated.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Emitted when the display configuration changes.
+    /// - **macOS:** Emitted when the screen parameters change.
+    /// - **X11:** Emitted in response to the RandR screen change notifications.
+    /// - **Wayland:** Emitted when the transform of the output changes.
+    /// - **iOS / Android / Web / Orbital:** Unsupported.
+    fn monitor_orientation_changed(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        monitor: MonitorHandle,
+    ) {
+        let _ = (event_loop, monitor);
+    }
+
+    /// Emitted when the refresh rate of the [current video mode] of a monitor changed, for example
+    /// when the user changed it in the display settings.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Emitted when the display configuration changes.
+    /// - **macOS:** Emitted when the screen parameters change.
+    /// - **X11:** Emitted in response to the RandR screen change notifications.
+    /// - **Wayland:** Emitted when the current mode of the output changes.
+    /// - **iOS / Android / Web / Orbital:** Unsupported.
+    ///
+    /// [current video mode]: MonitorHandle::current_video_mode
+    fn monitor_refresh_rate_changed(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        monitor: MonitorHandle,
+    ) {
+        let _ = (event_loop, monitor);
+    }
+
     /// Emitted when the event loop is about to block and wait for new events.
     ///
     /// Most applications shouldn't need to hook into this event since there is no real relationship
@@ -310,6 +425,124 @@ pub trait ApplicationHandler {
         let _ = event_loop;
     }
 
+    /// Emitted when the user asks to reopen the running application.
+    ///
+    /// This happens when clicking on the dock icon of the application, or when launching it again
+    /// while it's running. Applications which keep running after their last window was closed
+    /// should usually create a new window here.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Emitted on the reopen Apple Event, which is what
+    ///   `applicationShouldHandleReopen:hasVisibleWindows:` is usually based upon.
+    /// - **Windows:** Only emitted with [`EventLoopBuilderExtWindows::with_single_instance`], when
+    ///   the application is launched again without files, e.g. from its pinned taskbar button once
+    ///   its last window was closed. Clicking on the taskbar button of a running window only
+    ///   activates that window.
+    /// - **iOS / Android / Web / Wayland / X11 / Orbital:** Unsupported.
+    ///
+    #[cfg_attr(
+        any(windows_platform, docsrs),
+        doc = "[`EventLoopBuilderExtWindows::with_single_instance`]: \
+               crate::platform::windows::EventLoopBuilderExtWindows::with_single_instance"
+    )]
+    #[cfg_attr(
+        not(any(windows_platform, docsrs)),
+        doc = "[`EventLoopBuilderExtWindows::with_single_instance`]: #only-available-on-windows"
+    )]
+    fn reopen_requested(&mut self, event_loop: &dyn ActiveEventLoop) {
+        let _ = event_loop;
+    }
+
+    /// Emitted when files are opened with the application.
+    ///
+    /// This happens when the user opens documents associated with the application, e.g. from the
+    /// file manager, or drops them on the application icon. Files the application was launched
+    /// with are delivered after [`can_create_surfaces()`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Emitted on the open documents Apple Event. The document types must be declared
+    ///   with `CFBundleDocumentTypes` in the application bundle.
+    /// - **iOS:** Emitted when `application:openURL:options:` is sent with a file URL, e.g. from
+    ///   "Open in". This needs the application delegate of winit, which is only installed when the
+    ///   application doesn't have its own delegate, and an application without scenes, as the
+    ///   windows of winit aren't attached to scenes.
+    /// - **Android:** Emitted for the `file` URI of the `ACTION_VIEW` intent the activity was
+    ///   started with. The documents of other applications usually come as `content` URIs, which
+    ///   are delivered with [`open_urls()`] instead. The intents sent to the running activity
+    ///   aren't forwarded by `android-activity`, so they're only seen when the activity is started
+    ///   again.
+    /// - **Windows:** The shell passes the files as command line arguments, so they're delivered at
+    ///   launch when all the arguments are existing paths. The files opened while the application
+    ///   is running are only delivered to it with
+    ///   [`EventLoopBuilderExtWindows::with_single_instance`], as they launch a new process
+    ///   otherwise.
+    /// - **Web / Wayland / X11 / Orbital:** Unsupported, the files are usually passed as command
+    ///   line arguments instead.
+    ///
+    /// [`can_create_surfaces()`]: Self::can_create_surfaces()
+    /// [`open_urls()`]: Self::open_urls()
+    #[cfg_attr(
+        any(windows_platform, docsrs),
+        doc = "[`EventLoopBuilderExtWindows::with_single_instance`]: \
+               crate::platform::windows::EventLoopBuilderExtWindows::with_single_instance"
+    )]
+    #[cfg_attr(
+        not(any(windows_platform, docsrs)),
+        doc = "[`EventLoopBuilderExtWindows::with_single_instance`]: #only-available-on-windows"
+    )]
+    fn open_files(&mut self, event_loop: &dyn ActiveEventLoop, paths: Vec<PathBuf>) {
+        let _ = (event_loop, paths);
+    }
+
+    /// Emitted when URLs are opened with the application.
+    ///
+    /// This happens when opening a URL with a scheme handled by the application, e.g. from a web
+    /// browser. URLs the application was launched with are delivered after
+    /// [`can_create_surfaces()`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Emitted on the get URL Apple Event. The schemes must be declared with
+    ///   `CFBundleURLTypes` in the application bundle.
+    /// - **iOS:** Emitted when `application:openURL:options:` is sent, including after launching
+    ///   for the URL the application was launched with. The schemes must be declared with
+    ///   `CFBundleURLTypes`, and the same limitations as for [`open_files()`] apply.
+    /// - **Android:** Emitted for the URI of the `ACTION_VIEW` intent the activity was started
+    ///   with, unless it's a `file` URI, see [`open_files()`].
+    /// - **Web / Windows / Wayland / X11 / Orbital:** Unsupported.
+    ///
+    /// [`can_create_surfaces()`]: Self::can_create_surfaces()
+    /// [`open_files()`]: Self::open_files()
+    fn open_urls(&mut self, event_loop: &dyn ActiveEventLoop, urls: Vec<String>) {
+        let _ = (event_loop, urls);
+    }
+
+    /// Emitted when the system asks the application to quit.
+    ///
+    /// This happens when the user quits the application from the system UI, when the user session
+    /// is ending, or when the process is asked to terminate. Applications with unsaved work could
+    /// return [`ExitResponse::Cancel`] to keep running, and call [`ActiveEventLoop::exit()`] once
+    /// they're ready to quit.
+    ///
+    /// The default implementation returns [`ExitResponse::Exit`], which exits the event loop.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Emitted when the application is terminated, e.g. with `Cmd+Q` from the
+    ///   application menu or when the user logs out.
+    /// - **Windows:** Emitted on `WM_QUERYENDSESSION`, when the user session is ending.
+    /// - **Wayland / X11:** Emitted on `SIGTERM`, unless the application installed its own signal
+    ///   handler before creating the event loop.
+    /// - **iOS / Android / Web / Orbital:** Unsupported.
+    ///
+    /// [`ActiveEventLoop::exit()`]: crate::event_loop::ActiveEventLoop::exit()
+    fn exit_requested(&mut self, event_loop: &dyn ActiveEventLoop) -> ExitResponse {
+        let _ = event_loop;
+        ExitResponse::Exit
+    }
+
     /// Emitted when the event loop is being shut down.
     ///
     /// This is irreversible - if this method is called, it is guaranteed that the event loop
@@ -346,6 +579,184 @@ pub trait ApplicationHandler {
         let _ = event_loop;
     }
 
+    /// Emitted when the system is about to sleep, when it wakes up, and when the power source or
+    /// the battery state changes.
+    ///
+    /// Unlike [`suspended()`] and [`resumed()`], which are about the application's lifecycle,
+    /// this is about the whole system. A game can use it to pause before the system sleeps, or to
+    /// lower its frame rate while on battery. The current state can be queried with
+    /// [`ActiveEventLoop::power_state()`].
+    ///
+    /// [`suspended()`]: Self::suspended()
+    /// [`resumed()`]: Self::resumed()
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Emitted in response to `WM_POWERBROADCAST`.
+    /// - **macOS:** `Suspending` and `Resumed` are emitted in response to the `NSWorkspace`
+    ///   `willSleep` and `didWake` notifications. `PowerStateChanged` is emitted in response to
+    ///   IOKit power source notifications.
+    /// - **Wayland / X11:** `Suspending` and `Resumed` are emitted in response to the
+    ///   `PrepareForSleep` signal of `systemd-logind`. No sleep inhibitor is taken, so the system
+    ///   may already be asleep when `Suspending` is handled. `PowerStateChanged` is unsupported.
+    /// - **iOS / Android / Orbital / Web:** Unsupported.
+    fn power_event(&mut self, event_loop: &dyn ActiveEventLoop, event: PowerEvent) {
+        let _ = (event_loop, event);
+    }
+
+    /// Emitted when the desktop session is locked, for example by a screen locker.
+    ///
+    /// The windows of the application aren't visible while the session is locked, so media
+    /// applications can pause their playback, and stop rendering until
+    /// [`session_unlocked()`][Self::session_unlocked()] is emitted.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Emitted in response to `WM_WTSSESSION_CHANGE` with `WTS_SESSION_LOCK`.
+    /// - **macOS:** Emitted in response to the `com.apple.screenIsLocked` distributed notification.
+    /// - **Wayland / X11:** Emitted when `systemd-logind` reports the session as locked, which
+    ///   needs a screen locker setting its `LockedHint`.
+    /// - **iOS / Android / Orbital / Web:** Unsupported.
+    fn session_locked(&mut self, event_loop: &dyn ActiveEventLoop) {
+        let _ = event_loop;
+    }
+
+    /// Emitted when the desktop session is unlocked, after
+    /// [`session_locked()`][Self::session_locked()].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Emitted in response to `WM_WTSSESSION_CHANGE` with `WTS_SESSION_UNLOCK`.
+    /// - **macOS:** Emitted in response to the `com.apple.screenIsUnlocked` distributed
+    ///   notification.
+    /// - **Wayland / X11:** Emitted when `systemd-logind` reports the session as unlocked.
+    /// - **iOS / Android / Orbital / Web:** Unsupported.
+    fn session_unlocked(&mut self, event_loop: &dyn ActiveEventLoop) {
+        let _ = event_loop;
+    }
+
+    /// Emitted when the user changed the locale returned by [`ActiveEventLoop::locale()`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Emitted in response to `WM_SETTINGCHANGE` for the `intl` settings.
+    /// - **macOS / iOS:** Emitted in response to `NSCurrentLocaleDidChangeNotification`.
+    /// - **Android:** Emitted when the configuration changes to another language or country.
+    /// - **Web:** Emitted in response to the `languagechange` event.
+    /// - **Wayland / X11 / Orbital:** Unsupported.
+    fn locale_changed(&mut self, event_loop: &dyn ActiveEventLoop, locale: LanguageTag) {
+        let _ = (event_loop, locale);
+    }
+
+    /// Emitted when the user changed the cursor theme returned by
+    /// [`ActiveEventLoop::cursor_theme()`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Emitted in response to `WM_SETTINGCHANGE` for `SPI_SETCURSORS`.
+    /// - **X11:** Emitted when the XSETTINGS or the resources change.
+    /// - **macOS / iOS / Android / Web / Wayland / Orbital:** Unsupported.
+    fn cursor_theme_changed(&mut self, event_loop: &dyn ActiveEventLoop, theme: CursorThemeInfo) {
+        let _ = (event_loop, theme);
+    }
+
+    /// Emitted when the user changed the accessibility preferences returned by
+    /// [`ActiveEventLoop::system_preferences()`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Emitted in response to `WM_SETTINGCHANGE`.
+    /// - **macOS:** Emitted in response to
+    ///   `NSWorkspaceAccessibilityDisplayOptionsDidChangeNotification`.
+    /// - **iOS:** Emitted in response to the status change notifications of `UIAccessibility`.
+    /// - **Wayland / X11:** Emitted in response to the `SettingChanged` signal of the portal.
+    /// - **Web:** Emitted in response to the `change` events of the media queries.
+    /// - **Android / Orbital:** Unsupported.
+    fn system_preferences_changed(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        preferences: SystemPreferences,
+    ) {
+        let _ = (event_loop, preferences);
+    }
+
+    /// Emitted when the user changed the text scale factor returned by
+    /// [`ActiveEventLoop::text_scale_factor()`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Emitted in response to `WM_SETTINGCHANGE`.
+    /// - **iOS:** Emitted in response to `UIContentSizeCategoryDidChangeNotification`.
+    /// - **Wayland / X11:** Emitted in response to the `SettingChanged` signal of the portal.
+    /// - **macOS / Android / Web / Orbital:** Unsupported.
+    fn text_scale_factor_changed(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        text_scale_factor: f64,
+    ) {
+        let _ = (event_loop, text_scale_factor);
+    }
+
+    /// Emitted with the content read from the clipboard with [`Clipboard::get()`].
+    ///
+    /// The `serial` is the one returned by [`Clipboard::get()`], and `data` is `None` if the
+    /// clipboard doesn't contain the requested format.
+    ///
+    /// [`Clipboard::get()`]: crate::clipboard::Clipboard::get()
+    fn clipboard_data(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        serial: AsyncRequestSerial,
+        data: Option<ClipboardData>,
+    ) {
+        let _ = (event_loop, serial, data);
+    }
+
+    /// Emitted when the content of the clipboard changed, by this application or another one.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Emitted in response to `WM_CLIPBOARDUPDATE`.
+    /// - **macOS:** The change count of the pasteboard is checked in each iteration of the event
+    ///   loop, so changes are noticed once the event loop wakes up.
+    /// - **iOS:** Only the changes made while the application is in the foreground.
+    /// - **X11:** Requires the XFIXES extension.
+    /// - **Wayland:** Requires the `wlr-data-control` protocol.
+    /// - **Web / Android / Orbital:** Unsupported.
+    fn clipboard_changed(&mut self, event_loop: &dyn ActiveEventLoop) {
+        let _ = event_loop;
+    }
+
+    /// Emitted when a file dialog shown with [`ActiveEventLoop::pick_file()`],
+    /// [`pick_folder()`] or [`save_file()`] is closed.
+    ///
+    /// The `serial` is the one returned when the dialog was shown.
+    ///
+    /// [`pick_folder()`]: ActiveEventLoop::pick_folder()
+    /// [`save_file()`]: ActiveEventLoop::save_file()
+    #[cfg(feature = "dialogs")]
+    fn file_dialog_done(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        serial: AsyncRequestSerial,
+        result: FileDialogResult,
+    ) {
+        let _ = (event_loop, serial, result);
+    }
+
+    /// Emitted when a message box shown with [`ActiveEventLoop::show_message_box()`] is closed.
+    ///
+    /// The `serial` is the one returned when the message box was shown.
+    #[cfg(feature = "dialogs")]
+    fn message_box_done(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        serial: AsyncRequestSerial,
+        result: MessageBoxResult,
+    ) {
+        let _ = (event_loop, serial, result);
+    }
+
     /// The macOS-specific handler.
     ///
     /// The return value from this should not change at runtime.
@@ -354,6 +765,15 @@ pub trait ApplicationHandler {
     fn macos_handler(&mut self) -> Option<&mut dyn ApplicationHandlerExtMacOS> {
         None
     }
+
+    /// The Windows-specific handler.
+    ///
+    /// The return value from this should not change at runtime.
+    #[cfg(any(docsrs, windows_platform))]
+    #[inline(always)]
+    fn windows_handler(&mut self) -> Option<&mut dyn ApplicationHandlerExtWindows> {
+        None
+    }
 }
 
 #[deny(clippy::missing_trait_methods)]
@@ -398,6 +818,53 @@ impl<A: ?Sized + ApplicationHandler> ApplicationHandler for &mut A {
         (**self).device_event(event_loop, device_id, event);
     }
 
+    #[inline]
+    fn input_device_added(&mut self, event_loop: &dyn ActiveEventLoop, device: DeviceInfo) {
+        (**self).input_device_added(event_loop, device);
+    }
+
+    #[inline]
+    fn input_device_removed(&mut self, event_loop: &dyn ActiveEventLoop, device_id: DeviceId) {
+        (**self).input_device_removed(event_loop, device_id);
+    }
+
+    #[inline]
+    fn monitor_added(&mut self, event_loop: &dyn ActiveEventLoop, monitor: MonitorHandle) {
+        (**self).monitor_added(event_loop, monitor);
+    }
+
+    #[inline]
+    fn monitor_removed(&mut self, event_loop: &dyn ActiveEventLoop, monitor: MonitorHandle) {
+        (**self).monitor_removed(event_loop, monitor);
+    }
+
+    #[inline]
+    fn color_capabilities_changed(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        monitor: MonitorHandle,
+    ) {
+        (**self).color_capabilities_changed(event_loop, monitor);
+    }
+
+    #[inline]
+    fn monitor_orientation_changed(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        monitor: MonitorHandle,
+    ) {
+        (**self).monitor_orientation_changed(event_loop, monitor);
+    }
+
+    #[inline]
+    fn monitor_refresh_rate_changed(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        monitor: MonitorHandle,
+    ) {
+        (**self).monitor_refresh_rate_changed(event_loop, monitor);
+    }
+
     #[inline]
     fn about_to_wait(&mut self, event_loop: &dyn ActiveEventLoop) {
         (**self).about_to_wait(event_loop);
@@ -413,6 +880,26 @@ impl<A: ?Sized + ApplicationHandler> ApplicationHandler for &mut A {
         (**self).destroy_surfaces(event_loop);
     }
 
+    #[inline]
+    fn reopen_requested(&mut self, event_loop: &dyn ActiveEventLoop) {
+        (**self).reopen_requested(event_loop);
+    }
+
+    #[inline]
+    fn open_files(&mut self, event_loop: &dyn ActiveEventLoop, paths: Vec<PathBuf>) {
+        (**self).open_files(event_loop, paths);
+    }
+
+    #[inline]
+    fn open_urls(&mut self, event_loop: &dyn ActiveEventLoop, urls: Vec<String>) {
+        (**self).open_urls(event_loop, urls);
+    }
+
+    #[inline]
+    fn exit_requested(&mut self, event_loop: &dyn ActiveEventLoop) -> ExitResponse {
+        (**self).exit_requested(event_loop)
+    }
+
     #[inline]
     fn exiting(&mut self, event_loop: &dyn ActiveEventLoop) {
         (**self).exiting(event_loop);
@@ -423,11 +910,97 @@ impl<A: ?Sized + ApplicationHandler> ApplicationHandler for &mut A {
         (**self).memory_warning(event_loop);
     }
 
+    #[inline]
+    fn power_event(&mut self, event_loop: &dyn ActiveEventLoop, event: PowerEvent) {
+        (**self).power_event(event_loop, event);
+    }
+
+    #[inline]
+    fn session_locked(&mut self, event_loop: &dyn ActiveEventLoop) {
+        (**self).session_locked(event_loop);
+    }
+
+    #[inline]
+    fn session_unlocked(&mut self, event_loop: &dyn ActiveEventLoop) {
+        (**self).session_unlocked(event_loop);
+    }
+
+    #[inline]
+    fn locale_changed(&mut self, event_loop: &dyn ActiveEventLoop, locale: LanguageTag) {
+        (**self).locale_changed(event_loop, locale);
+    }
+
+    #[inline]
+    fn cursor_theme_changed(&mut self, event_loop: &dyn ActiveEventLoop, theme: CursorThemeInfo) {
+        (**self).cursor_theme_changed(event_loop, theme);
+    }
+
+    #[inline]
+    fn system_preferences_changed(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        preferences: SystemPreferences,
+    ) {
+        (**self).system_preferences_changed(event_loop, preferences);
+    }
+
+    #[inline]
+    fn text_scale_factor_changed(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        text_scale_factor: f64,
+    ) {
+        (**self).text_scale_factor_changed(event_loop, text_scale_factor);
+    }
+
+    #[inline]
+    fn clipboard_data(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        serial: AsyncRequestSerial,
+        data: Option<ClipboardData>,
+    ) {
+        (**self).clipboard_data(event_loop, serial, data);
+    }
+
+    #[inline]
+    fn clipboard_changed(&mut self, event_loop: &dyn ActiveEventLoop) {
+        (**self).clipboard_changed(event_loop);
+    }
+
+    #[cfg(feature = "dialogs")]
+    #[inline]
+    fn file_dialog_done(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        serial: AsyncRequestSerial,
+        result: FileDialogResult,
+    ) {
+        (**self).file_dialog_done(event_loop, serial, result);
+    }
+
+    #[cfg(feature = "dialogs")]
+    #[inline]
+    fn message_box_done(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        serial: AsyncRequestSerial,
+        result: MessageBoxResult,
+    ) {
+        (**self).message_box_done(event_loop, serial, result);
+    }
+
     #[cfg(any(docsrs, macos_platform))]
     #[inline]
     fn macos_handler(&mut self) -> Option<&mut dyn ApplicationHandlerExtMacOS> {
         (**self).macos_handler()
     }
+
+    #[cfg(any(docsrs, windows_platform))]
+    #[inline]
+    fn windows_handler(&mut self) -> Option<&mut dyn ApplicationHandlerExtWindows> {
+        (**self).windows_handler()
+    }
 }
 
 #[deny(clippy::missing_trait_methods)]
@@ -472,6 +1045,53 @@ impl<A: ?Sized + ApplicationHandler> ApplicationHandler for Box<A> {
         (**self).device_event(event_loop, device_id, event);
     }
 
+    #[inline]
+    fn input_device_added(&mut self, event_loop: &dyn ActiveEventLoop, device: DeviceInfo) {
+        (**self).input_device_added(event_loop, device);
+    }
+
+    #[inline]
+    fn input_device_removed(&mut self, event_loop: &dyn ActiveEventLoop, device_id: DeviceId) {
+        (**self).input_device_removed(event_loop, device_id);
+    }
+
+    #[inline]
+    fn monitor_added(&mut self, event_loop: &dyn ActiveEventLoop, monitor: MonitorHandle) {
+        (**self).monitor_added(event_loop, monitor);
+    }
+
+    #[inline]
+    fn monitor_removed(&mut self, event_loop: &dyn ActiveEventLoop, monitor: MonitorHandle) {
+        (**self).monitor_removed(event_loop, monitor);
+    }
+
+    #[inline]
+    fn color_capabilities_changed(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        monitor: MonitorHandle,
+    ) {
+        (**self).color_capabilities_changed(event_loop, monitor);
+    }
+
+    #[inline]
+    fn monitor_orientation_changed(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        monitor: MonitorHandle,
+    ) {
+        (**self).monitor_orientation_changed(event_loop, monitor);
+    }
+
+    #[inline]
+    fn monitor_refresh_rate_changed(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        monitor: MonitorHandle,
+    ) {
+        (**self).monitor_refresh_rate_changed(event_loop, monitor);
+    }
+
     #[inline]
     fn about_to_wait(&mut self, event_loop: &dyn ActiveEventLoop) {
         (**self).about_to_wait(event_loop);
@@ -487,6 +1107,26 @@ impl<A: ?Sized + ApplicationHandler> ApplicationHandler for Box<A> {
         (**self).destroy_surfaces(event_loop);
     }
 
+    #[inline]
+    fn reopen_requested(&mut self, event_loop: &dyn ActiveEventLoop) {
+        (**self).reopen_requested(event_loop);
+    }
+
+    #[inline]
+    fn open_files(&mut self, event_loop: &dyn ActiveEventLoop, paths: Vec<PathBuf>) {
+        (**self).open_files(event_loop, paths);
+    }
+
+    #[inline]
+    fn open_urls(&mut self, event_loop: &dyn ActiveEventLoop, urls: Vec<String>) {
+        (**self).open_urls(event_loop, urls);
+    }
+
+    #[inline]
+    fn exit_requested(&mut self, event_loop: &dyn ActiveEventLoop) -> ExitResponse {
+        (**self).exit_requested(event_loop)
+    }
+
     #[inline]
     fn exiting(&mut self, event_loop: &dyn ActiveEventLoop) {
         (**self).exiting(event_loop);
@@ -497,9 +1137,95 @@ impl<A: ?Sized + ApplicationHandler> ApplicationHandler for Box<A> {
         (**self).memory_warning(event_loop);
     }
 
+    #[inline]
+    fn power_event(&mut self, event_loop: &dyn ActiveEventLoop, event: PowerEvent) {
+        (**self).power_event(event_loop, event);
+    }
+
+    #[inline]
+    fn session_locked(&mut self, event_loop: &dyn ActiveEventLoop) {
+        (**self).session_locked(event_loop);
+    }
+
+    #[inline]
+    fn session_unlocked(&mut self, event_loop: &dyn ActiveEventLoop) {
+        (**self).session_unlocked(event_loop);
+    }
+
+    #[inline]
+    fn locale_changed(&mut self, event_loop: &dyn ActiveEventLoop, locale: LanguageTag) {
+        (**self).locale_changed(event_loop, locale);
+    }
+
+    #[inline]
+    fn cursor_theme_changed(&mut self, event_loop: &dyn ActiveEventLoop, theme: CursorThemeInfo) {
+        (**self).cursor_theme_changed(event_loop, theme);
+    }
+
+    #[inline]
+    fn system_preferences_changed(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        preferences: SystemPreferences,
+    ) {
+        (**self).system_preferences_changed(event_loop, preferences);
+    }
+
+    #[inline]
+    fn text_scale_factor_changed(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        text_scale_factor: f64,
+    ) {
+        (**self).text_scale_factor_changed(event_loop, text_scale_factor);
+    }
+
+    #[inline]
+    fn clipboard_data(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        serial: AsyncRequestSerial,
+        data: Option<ClipboardData>,
+    ) {
+        (**self).clipboard_data(event_loop, serial, data);
+    }
+
+    #[inline]
+    fn clipboard_changed(&mut self, event_loop: &dyn ActiveEventLoop) {
+        (**self).clipboard_changed(event_loop);
+    }
+
+    #[cfg(feature = "dialogs")]
+    #[inline]
+    fn file_dialog_done(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        serial: AsyncRequestSerial,
+        result: FileDialogResult,
+    ) {
+        (**self).file_dialog_done(event_loop, serial, result);
+    }
+
+    #[cfg(feature = "dialogs")]
+    #[inline]
+    fn message_box_done(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        serial: AsyncRequestSerial,
+        result: MessageBoxResult,
+    ) {
+        (**self).message_box_done(event_loop, serial, result);
+    }
+
     #[cfg(any(docsrs, macos_platform))]
     #[inline]
     fn macos_handler(&mut self) -> Option<&mut dyn ApplicationHandlerExtMacOS> {
         (**self).macos_handler()
     }
+
+    #[cfg(any(docsrs, windows_platform))]
+    #[inline]
+    fn windows_handler(&mut self) -> Option<&mut dyn ApplicationHandlerExtWindows> {
+        (**self).windows_handler()
+    }
 }
@@ -0,0 +1,328 @@
+//! Reading and writing the clipboard of the system.
+//!
+//! The clipboard is reached with [`ActiveEventLoop::clipboard()`]. On X11 and Wayland, the
+//! content of the clipboard stays with the application which copied it, which has to send it to
+//! the applications pasting it for as long as it owns the clipboard. The event loop does this
+//! while it's running, which is why the clipboard belongs to it.
+//!
+//! The clipboard holds [`ClipboardData`] in several formats at once, such as rich text as HTML
+//! along with its plain text, so each application pastes the format it understands best.
+//! Reading the clipboard may have to wait for the application owning it, so the content is
+//! delivered to [`ApplicationHandler::clipboard_data()`] with the serial returned by
+//! [`Clipboard::get()`].
+//!
+//! Applications can exchange their own objects through [custom formats][ClipboardFormat::Custom],
+//! and be notified of the changes of the clipboard, including the ones of other applications,
+//! with [`ApplicationHandler::clipboard_changed()`].
+//!
+//! On X11 and Wayland, the text selected with the mouse forms the [primary
+//! selection][Selection::Primary], pasted with a middle click, which is reached like the clipboard
+//! with [`Clipboard::set_selection()`] and [`Clipboard::get_selection()`].
+//!
+//! ```no_run
+//! # use winit::clipboard::{ClipboardData, ClipboardFormat};
+//! # use winit::event_loop::{ActiveEventLoop, AsyncRequestSerial};
+//! # fn scope(event_loop: &dyn ActiveEventLoop) -> AsyncRequestSerial {
+//! event_loop
+//!     .clipboard()
+//!     .set(vec![
+//!         ClipboardData::Html("<b>Hello</b> world".into()),
+//!         ClipboardData::Text("Hello world".into()),
+//!     ])
+//!     .unwrap();
+//!
+//! // The image is then delivered to `ApplicationHandler::clipboard_data()`.
+//! event_loop.clipboard().get(ClipboardFormat::Image).unwrap()
+//! # }
+//! ```
+//!
+//! [`ActiveEventLoop::clipboard()`]: crate::event_loop::ActiveEventLoop::clipboard()
+//! [`ApplicationHandler::clipboard_data()`]: crate::application::ApplicationHandler::clipboard_data()
+//! [`ApplicationHandler::clipboard_changed()`]: crate::application::ApplicationHandler::clipboard_changed()
+
+use std::borrow::Cow;
+use std::path::PathBuf;
+
+use crate::error::{NotSupportedError, RequestError};
+use crate::event_loop::AsyncRequestSerial;
+use crate::utils::AsAny;
+
+/// The clipboard of the system, returned by [`ActiveEventLoop::clipboard()`].
+///
+/// [`ActiveEventLoop::clipboard()`]: crate::event_loop::ActiveEventLoop::clipboard()
+pub trait Clipboard: AsAny {
+    /// Replace the content of the clipboard with `data`, offering each of its formats.
+    ///
+    /// When `data` contains the same format more than once, the first one is used.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** Requires a recent input event of one of the application's windows, such as a
+    ///   key press or a click, otherwise the compositor ignores it.
+    /// - **Web:** Requires a recent user interaction with the page and a secure context. Files and
+    ///   URIs are unsupported.
+    /// - **iOS:** Files are unsupported.
+    /// - **Android / Orbital:** Unsupported.
+    fn set(&self, data: Vec<ClipboardData>) -> Result<(), RequestError>;
+
+    /// Request the content of the clipboard in the `format`.
+    ///
+    /// The content is delivered to [`ApplicationHandler::clipboard_data()`] with the returned
+    /// serial, or `None` if the clipboard doesn't contain this format.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** The browser may ask the user for the permission to read the clipboard. Files and
+    ///   URIs are unsupported.
+    /// - **iOS:** Files are unsupported.
+    /// - **Android / Orbital:** Unsupported.
+    ///
+    /// [`ApplicationHandler::clipboard_data()`]: crate::application::ApplicationHandler::clipboard_data()
+    fn get(&self, format: ClipboardFormat) -> Result<AsyncRequestSerial, RequestError>;
+
+    /// Replace the content of the clipboard with `text`.
+    ///
+    /// See [`set()`][Self::set()] for the details.
+    fn set_text(&self, text: String) -> Result<(), RequestError> {
+        self.set(vec![ClipboardData::Text(text)])
+    }
+
+    /// Request the text in the clipboard.
+    ///
+    /// See [`get()`][Self::get()] for the details.
+    fn get_text(&self) -> Result<AsyncRequestSerial, RequestError> {
+        self.get(ClipboardFormat::Text)
+    }
+
+    /// Replace the content of the `selection` with `data`.
+    ///
+    /// [`Selection::Clipboard`] is the same as [`set()`][Self::set()]. Applications usually set
+    /// the [primary selection][Selection::Primary] each time the user selects text.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** The primary selection requires the `zwp_primary_selection_device_manager_v1`
+    ///   protocol.
+    /// - **Windows / macOS / iOS / Web / Android / Orbital:** The primary selection is unsupported.
+    fn set_selection(
+        &self,
+        selection: Selection,
+        data: Vec<ClipboardData>,
+    ) -> Result<(), RequestError> {
+        match selection {
+            Selection::Clipboard => self.set(data),
+            Selection::Primary => {
+                Err(NotSupportedError::new("the primary selection is not supported").into())
+            },
+        }
+    }
+
+    /// Request the content of the `selection` in the `format`.
+    ///
+    /// [`Selection::Clipboard`] is the same as [`get()`][Self::get()], and the content is
+    /// delivered the same way.
+    ///
+    /// ## Platform-specific
+    ///
+    /// See [`set_selection()`][Self::set_selection()].
+    fn get_selection(
+        &self,
+        selection: Selection,
+        format: ClipboardFormat,
+    ) -> Result<AsyncRequestSerial, RequestError> {
+        match selection {
+            Selection::Clipboard => self.get(format),
+            Selection::Primary => {
+                Err(NotSupportedError::new("the primary selection is not supported").into())
+            },
+        }
+    }
+}
+
+/// The selections of the system which hold content to paste.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Selection {
+    /// The clipboard, set when copying explicitly and pasted with a shortcut or a menu.
+    #[default]
+    Clipboard,
+    /// The primary selection of X11 and Wayland, set when selecting text and pasted with a
+    /// middle click.
+    Primary,
+}
+
+/// The content of the clipboard in one format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClipboardData {
+    /// Plain text.
+    Text(String),
+    /// A fragment of HTML, such as rich text copied from a web page or an editor.
+    Html(String),
+    /// An image, such as a screenshot.
+    Image(ClipboardImage),
+    /// Paths of files, such as the ones copied in a file manager.
+    Files(Vec<PathBuf>),
+    /// URIs, such as the address of a link dragged out of a web browser.
+    Uris(Vec<String>),
+    /// The bytes of a [custom format][ClipboardFormat::Custom].
+    Custom {
+        /// The name of the format.
+        mime_type: &'static str,
+        /// The content, in the encoding of the format.
+        data: Vec<u8>,
+    },
+}
+
+impl ClipboardData {
+    /// The format of the data.
+    pub fn format(&self) -> ClipboardFormat {
+        match self {
+            ClipboardData::Text(_) => ClipboardFormat::Text,
+            ClipboardData::Html(_) => ClipboardFormat::Html,
+            ClipboardData::Image(_) => ClipboardFormat::Image,
+            ClipboardData::Files(_) => ClipboardFormat::Files,
+            ClipboardData::Uris(_) => ClipboardFormat::Uris,
+            ClipboardData::Custom { mime_type, .. } => ClipboardFormat::Custom(mime_type),
+        }
+    }
+}
+
+/// The formats of [`ClipboardData`], to request with [`Clipboard::get()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ClipboardFormat {
+    /// [`ClipboardData::Text`].
+    Text,
+    /// [`ClipboardData::Html`].
+    Html,
+    /// [`ClipboardData::Image`].
+    Image,
+    /// [`ClipboardData::Files`].
+    Files,
+    /// [`ClipboardData::Uris`].
+    Uris,
+    /// [`ClipboardData::Custom`] of the format with this name, such as
+    /// `application/x-myapp-node`.
+    ///
+    /// The name is the type of the format on the system, registered when it's first used. Other
+    /// instances of the application, and the applications knowing the format, can read it.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** The name is registered with `RegisterClipboardFormatW`.
+    /// - **macOS / iOS:** The name is the pasteboard type, which other applications expect to be a
+    ///   uniform type identifier such as `com.example.myapp.node`.
+    /// - **Web:** The format is exchanged as `web <name>`, which few browsers support.
+    Custom(&'static str),
+}
+
+/// An image in the clipboard.
+///
+/// Images are read in the encoding the platform stores them in, which is PNG everywhere but on
+/// Windows, where applications which don't offer PNG images are read as [`Rgba`][Self::Rgba].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClipboardImage {
+    /// An image encoded in PNG.
+    Png(Vec<u8>),
+    /// Unencoded pixels, with 8 bits per channel and the rows from top to bottom.
+    Rgba { width: u32, height: u32, pixels: Vec<u8> },
+}
+
+impl ClipboardImage {
+    /// The image encoded in PNG, encoding the pixels without compression if needed.
+    #[allow(dead_code)]
+    pub(crate) fn to_png(&self) -> Cow<'_, [u8]> {
+        match self {
+            ClipboardImage::Png(png) => Cow::Borrowed(png),
+            ClipboardImage::Rgba { width, height, pixels } => {
+                Cow::Owned(encode_png(*width, *height, pixels))
+            },
+        }
+    }
+}
+
+/// Encode RGBA pixels in a PNG, in stored deflate blocks.
+fn encode_png(width: u32, height: u32, pixels: &[u8]) -> Vec<u8> {
+    fn chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+        png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        let start = png.len();
+        png.extend_from_slice(kind);
+        png.extend_from_slice(data);
+        let crc = crc32(&png[start..]);
+        png.extend_from_slice(&crc.to_be_bytes());
+    }
+
+    // Each row starts with its filter type, which is none.
+    let row_len = width as usize * 4;
+    let mut raw = Vec::with_capacity((row_len + 1) * height as usize);
+    for row in pixels.chunks(row_len.max(1)).take(height as usize) {
+        raw.push(0);
+        raw.extend_from_slice(row);
+    }
+
+    // A zlib stream of stored blocks, which are at most 65535 bytes long.
+    let mut zlib = vec![0x78, 0x01];
+    let mut blocks = raw.chunks(0xffff).peekable();
+    if blocks.peek().is_none() {
+        zlib.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let len = block.len() as u16;
+        zlib.push(blocks.peek().is_none() as u8);
+        zlib.extend_from_slice(&len.to_le_bytes());
+        zlib.extend_from_slice(&(!len).to_le_bytes());
+        zlib.extend_from_slice(block);
+    }
+    zlib.extend_from_slice(&adler32(&raw).to_be_bytes());
+
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&width.to_be_bytes());
+    header.extend_from_slice(&height.to_be_bytes());
+    // 8 bits per channel, RGBA, deflate, adaptive filtering and no interlacing.
+    header.extend_from_slice(&[8, 6, 0, 0, 0]);
+
+    let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+    chunk(&mut png, b"IHDR", &header);
+    chunk(&mut png, b"IDAT", &zlib);
+    chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xedb8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_png_checksums() {
+        // The check values of the algorithms.
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn png_of_rgba() {
+        let pixels: Vec<u8> = (0..2 * 3 * 4).collect();
+        let image = ClipboardImage::Rgba { width: 2, height: 3, pixels: pixels.clone() };
+        let decoded = image::load_from_memory(&image.to_png()).unwrap().into_rgba8();
+        assert_eq!(decoded.dimensions(), (2, 3));
+        assert_eq!(decoded.into_raw(), pixels);
+    }
+}
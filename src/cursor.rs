@@ -7,6 +7,8 @@ use cursor_icon::CursorIcon;
 
 use crate::platform_impl::{PlatformCustomCursor, PlatformCustomCursorSource};
 
+pub(crate) mod decode;
+
 /// The maximum width and height for a cursor when using [`CustomCursor::from_rgba`].
 pub const MAX_CURSOR_SIZE: u16 = 2048;
 
@@ -37,6 +39,19 @@ impl From<CustomCursor> for Cursor {
     }
 }
 
+/// The cursor theme configured by the user, see
+/// [`ActiveEventLoop::cursor_theme()`][crate::event_loop::ActiveEventLoop::cursor_theme].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CursorThemeInfo {
+    /// The name of the theme, `None` when the default cursors are used.
+    pub name: Option<String>,
+    /// The nominal size of the cursors, in pixels at a scale factor of `1.0`.
+    ///
+    /// Software cursors should be drawn at this size, multiplied by the scale factor.
+    pub size: u32,
+}
+
 /// Use a custom image as a cursor (mouse pointer).
 ///
 /// Is guaranteed to be cheap to clone.
@@ -102,6 +117,38 @@ impl CustomCursor {
             )?,
         })
     }
+
+    /// Creates a new cursor from the bytes of a Windows `.cur` file.
+    ///
+    /// The file can have images of several sizes, the one closest to `size` is used, the larger
+    /// one on ties. `.ico` files are also accepted, with their hotspot in the top-left corner.
+    ///
+    /// The PNG images of the file need the `png` feature, otherwise only the bitmap images are
+    /// used.
+    pub fn from_cur(bytes: &[u8], size: u16) -> Result<CustomCursorSource, BadCursorFile> {
+        Self::from_decoded(decode::cur(bytes, size)?)
+    }
+
+    /// Creates a new cursor from the bytes of a Windows `.ani` animated cursor file.
+    ///
+    /// Only the first frame is used, with the image closest to `size` as in
+    /// [`CustomCursor::from_cur`].
+    pub fn from_ani(bytes: &[u8], size: u16) -> Result<CustomCursorSource, BadCursorFile> {
+        Self::from_decoded(decode::ani(bytes, size)?)
+    }
+
+    /// Creates a new cursor from the bytes of an X11 cursor file, as found in cursor themes.
+    ///
+    /// The file can have images of several nominal sizes, the one closest to `size` is used, the
+    /// larger one on ties. Only the first frame of animated cursors is used.
+    pub fn from_xcursor(bytes: &[u8], size: u16) -> Result<CustomCursorSource, BadCursorFile> {
+        Self::from_decoded(decode::xcursor(bytes, size)?)
+    }
+
+    fn from_decoded(image: decode::DecodedImage) -> Result<CustomCursorSource, BadCursorFile> {
+        let decode::DecodedImage { rgba, width, height, hotspot_x, hotspot_y } = image;
+        Self::from_rgba(rgba, width, height, hotspot_x, hotspot_y).map_err(BadCursorFile::BadImage)
+    }
 }
 
 /// Source for [`CustomCursor`].
@@ -114,6 +161,36 @@ pub struct CustomCursorSource {
     pub(crate) inner: PlatformCustomCursorSource,
 }
 
+impl CustomCursorSource {
+    /// Adds an image of the cursor for a higher scale factor, like a `2x` image for HiDPI
+    /// monitors, so the cursor isn't blurry or tiny there.
+    ///
+    /// The scale factor of an image is its width relative to the width of the first image, which
+    /// is used at a scale factor of `1.0`, and its hotspot is expected to be scaled likewise.
+    /// Images must be added in increasing size. The image closest to the scale factor of the
+    /// window is used, the larger one on ties, and it is selected again when the scale factor
+    /// changes, like when the window moves to another monitor.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** The system chooses the image for the screen showing the cursor.
+    /// - **Wayland:** The image is chosen for the scale of the surface of the cursor.
+    /// - **Web:** Only the first image is used, the browser scales it with the device pixel ratio.
+    ///   Returns [`BadImage::NotAnImage`] for URLs and animations.
+    /// - **iOS / Android / Orbital:** Unsupported.
+    pub fn with_scaled_rgba(
+        mut self,
+        rgba: impl Into<Vec<u8>>,
+        width: u16,
+        height: u16,
+        hotspot_x: u16,
+        hotspot_y: u16,
+    ) -> Result<Self, BadImage> {
+        self.inner.push_scaled_rgba(rgba.into(), width, height, hotspot_x, hotspot_y)?;
+        Ok(self)
+    }
+}
+
 /// An error produced when using [`CustomCursor::from_rgba`] with invalid arguments.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -130,6 +207,12 @@ pub enum BadImage {
     DimensionsVsPixelCount { width: u16, height: u16, width_x_height: u64, pixel_count: u64 },
     /// Produced when the hotspot is outside the image bounds
     HotspotOutOfBounds { width: u16, height: u16, hotspot_x: u16, hotspot_y: u16 },
+    /// Produced by [`CustomCursorSource::with_scaled_rgba`] when the image isn't larger than the
+    /// previous image of the cursor.
+    ScaledImageTooSmall { width: u16, height: u16, previous_width: u16, previous_height: u16 },
+    /// Produced by [`CustomCursorSource::with_scaled_rgba`] when the cursor isn't made of
+    /// images, like the URL cursors on Web.
+    NotAnImage,
 }
 
 impl fmt::Display for BadImage {
@@ -158,17 +241,62 @@ impl fmt::Display for BadImage {
                 "The specified hotspot ({hotspot_x:?}, {hotspot_y:?}) is outside the image bounds \
                  ({width:?}x{height:?}).",
             ),
+            BadImage::ScaledImageTooSmall { width, height, previous_width, previous_height } => {
+                write!(
+                    f,
+                    "The specified dimensions ({width:?}x{height:?}) aren't larger than the \
+                     previous image of the cursor ({previous_width:?}x{previous_height:?}).",
+                )
+            },
+            BadImage::NotAnImage => write!(f, "The cursor isn't made of images."),
         }
     }
 }
 
 impl Error for BadImage {}
 
+/// An error produced when decoding a cursor file, like with [`CustomCursor::from_cur`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BadCursorFile {
+    /// Produced when the bytes are truncated or aren't a file of the expected format.
+    Malformed,
+    /// Produced when the file uses a feature of the format that isn't supported, like PNG images
+    /// in `.cur` files.
+    Unsupported,
+    /// Produced when the file has no image.
+    NoImage,
+    /// Produced when the image of the file is invalid.
+    BadImage(BadImage),
+}
+
+impl fmt::Display for BadCursorFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BadCursorFile::Malformed => write!(f, "The cursor file is malformed."),
+            BadCursorFile::Unsupported => {
+                write!(f, "The cursor file uses a feature that isn't supported.")
+            },
+            BadCursorFile::NoImage => write!(f, "The cursor file has no image."),
+            BadCursorFile::BadImage(error) => error.fmt(f),
+        }
+    }
+}
+
+impl Error for BadCursorFile {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            BadCursorFile::BadImage(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
 /// Platforms export this directly as `PlatformCustomCursorSource` if they need to only work with
 /// images.
 #[allow(dead_code)]
 #[derive(Debug, Clone, Eq, Hash, PartialEq)]
-pub(crate) struct OnlyCursorImageSource(pub(crate) CursorImage);
+pub(crate) struct OnlyCursorImageSource(pub(crate) CursorImages);
 
 #[allow(dead_code)]
 impl OnlyCursorImageSource {
@@ -179,14 +307,27 @@ impl OnlyCursorImageSource {
         hotspot_x: u16,
         hotspot_y: u16,
     ) -> Result<Self, BadImage> {
-        CursorImage::from_rgba(rgba, width, height, hotspot_x, hotspot_y).map(Self)
+        CursorImage::from_rgba(rgba, width, height, hotspot_x, hotspot_y)
+            .map(CursorImages::new)
+            .map(Self)
+    }
+
+    pub(crate) fn push_scaled_rgba(
+        &mut self,
+        rgba: Vec<u8>,
+        width: u16,
+        height: u16,
+        hotspot_x: u16,
+        hotspot_y: u16,
+    ) -> Result<(), BadImage> {
+        self.0.push_scaled(CursorImage::from_rgba(rgba, width, height, hotspot_x, hotspot_y)?)
     }
 }
 
 /// Platforms export this directly as `PlatformCustomCursor` if they don't implement caching.
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
-pub(crate) struct OnlyCursorImage(pub(crate) Arc<CursorImage>);
+pub(crate) struct OnlyCursorImage(pub(crate) Arc<CursorImages>);
 
 impl Hash for OnlyCursorImage {
     fn hash<H: Hasher>(&self, state: &mut H) {
@@ -247,6 +388,69 @@ impl CursorImage {
     }
 }
 
+/// The images of a cursor, the first one for a scale factor of `1.0` and the next ones for
+/// increasingly higher scale factors.
+#[derive(Debug, Clone, Eq, Hash, PartialEq)]
+#[allow(dead_code)]
+pub(crate) struct CursorImages(Vec<CursorImage>);
+
+#[allow(dead_code)]
+impl CursorImages {
+    pub(crate) fn new(image: CursorImage) -> Self {
+        Self(vec![image])
+    }
+
+    pub(crate) fn push_scaled(&mut self, image: CursorImage) -> Result<(), BadImage> {
+        let previous = self.0.last().expect("no base image");
+        if image.width <= previous.width || image.height <= previous.height {
+            return Err(BadImage::ScaledImageTooSmall {
+                width: image.width,
+                height: image.height,
+                previous_width: previous.width,
+                previous_height: previous.height,
+            });
+        }
+        self.0.push(image);
+        Ok(())
+    }
+
+    /// The image for a scale factor of `1.0`.
+    pub(crate) fn base(&self) -> &CursorImage {
+        &self.0[0]
+    }
+
+    /// All the images, in increasing size.
+    pub(crate) fn images(&self) -> &[CursorImage] {
+        &self.0
+    }
+
+    pub(crate) fn for_scale_factor(&self, scale_factor: f64) -> &CursorImage {
+        closest_to_scale_factor(&self.0, |image| image.width, scale_factor)
+    }
+}
+
+/// The item closest to `scale_factor`, the larger one on ties, among items of increasing size.
+///
+/// The scale of an item is its `width` relative to the width of the first one.
+#[allow(dead_code)]
+pub(crate) fn closest_to_scale_factor<T>(
+    items: &[T],
+    width: impl Fn(&T) -> u16,
+    scale_factor: f64,
+) -> &T {
+    let base_width = f64::from(width(&items[0]));
+    let mut closest = &items[0];
+    let mut closest_distance = f64::INFINITY;
+    for item in items {
+        let distance = (f64::from(width(item)) / base_width - scale_factor).abs();
+        if distance <= closest_distance {
+            closest = item;
+            closest_distance = distance;
+        }
+    }
+    closest
+}
+
 // Platforms that don't support cursors will export this as `PlatformCustomCursor`.
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub(crate) struct NoCustomCursor;
@@ -263,4 +467,51 @@ impl NoCustomCursor {
         CursorImage::from_rgba(rgba, width, height, hotspot_x, hotspot_y)?;
         Ok(Self)
     }
+
+    pub(crate) fn push_scaled_rgba(
+        &mut self,
+        rgba: Vec<u8>,
+        width: u16,
+        height: u16,
+        hotspot_x: u16,
+        hotspot_y: u16,
+    ) -> Result<(), BadImage> {
+        CursorImage::from_rgba(rgba, width, height, hotspot_x, hotspot_y)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn image(size: u16) -> CursorImage {
+        let rgba = vec![0; size as usize * size as usize * PIXEL_SIZE];
+        CursorImage::from_rgba(rgba, size, size, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn picks_closest_scaled_image() {
+        let mut images = CursorImages::new(image(16));
+        images.push_scaled(image(24)).unwrap();
+        images.push_scaled(image(32)).unwrap();
+        assert_eq!(
+            images.push_scaled(image(32)),
+            Err(BadImage::ScaledImageTooSmall {
+                width: 32,
+                height: 32,
+                previous_width: 32,
+                previous_height: 32
+            })
+        );
+
+        let width = |scale_factor| images.for_scale_factor(scale_factor).width;
+        assert_eq!(width(1.0), 16);
+        assert_eq!(width(1.2), 16);
+        // Ties go to the larger image.
+        assert_eq!(width(1.25), 24);
+        assert_eq!(width(1.5), 24);
+        assert_eq!(width(2.0), 32);
+        assert_eq!(width(3.0), 32);
+    }
 }
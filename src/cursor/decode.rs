@@ -0,0 +1,410 @@
+//! Decoders of the cursor file formats of Windows and X11, also used for `.ico` icons.
+
+use super::{BadCursorFile, MAX_CURSOR_SIZE};
+
+/// A cursor image decoded from a file, with non-premultiplied RGBA pixels.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct DecodedImage {
+    pub(crate) rgba: Vec<u8>,
+    pub(crate) width: u16,
+    pub(crate) height: u16,
+    pub(crate) hotspot_x: u16,
+    pub(crate) hotspot_y: u16,
+}
+
+/// The signature at the start of PNG images.
+pub(crate) const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+fn slice(bytes: &[u8], offset: usize, len: usize) -> Result<&[u8], BadCursorFile> {
+    offset.checked_add(len).and_then(|end| bytes.get(offset..end)).ok_or(BadCursorFile::Malformed)
+}
+
+fn u16_at(bytes: &[u8], offset: usize) -> Result<u16, BadCursorFile> {
+    slice(bytes, offset, 2).map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+fn u32_at(bytes: &[u8], offset: usize) -> Result<u32, BadCursorFile> {
+    slice(bytes, offset, 4)
+        .map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+/// The index of the size closest to `size`, the larger one on ties.
+fn closest_size(sizes: impl Iterator<Item = u32>, size: u16) -> Option<usize> {
+    sizes
+        .enumerate()
+        .min_by_key(|&(_, candidate)| (candidate.abs_diff(size as u32), u32::MAX - candidate))
+        .map(|(index, _)| index)
+}
+
+/// Check the size of an image before allocating its pixels.
+fn checked_size(width: u32, height: u32) -> Result<(u16, u16), BadCursorFile> {
+    if width > MAX_CURSOR_SIZE as u32 || height > MAX_CURSOR_SIZE as u32 {
+        let clamp = |value: u32| value.min(u16::MAX as u32) as u16;
+        return Err(BadCursorFile::BadImage(super::BadImage::TooLarge {
+            width: clamp(width),
+            height: clamp(height),
+        }));
+    }
+    Ok((width as u16, height as u16))
+}
+
+/// Decode the image closest to `size` in a Windows `.cur` or `.ico` file.
+///
+/// The images of `.ico` files have no hotspot, it's their top-left corner.
+pub(crate) fn cur(bytes: &[u8], size: u16) -> Result<DecodedImage, BadCursorFile> {
+    const ICON: u16 = 1;
+    const CURSOR: u16 = 2;
+
+    let kind = u16_at(bytes, 2)?;
+    if u16_at(bytes, 0)? != 0 || !matches!(kind, ICON | CURSOR) {
+        return Err(BadCursorFile::Malformed);
+    }
+
+    // The directory entries are 16 bytes long after the 6 bytes of the header, a size of 0 means
+    // 256 pixels.
+    let count = u16_at(bytes, 4)? as usize;
+    if count == 0 {
+        return Err(BadCursorFile::NoImage);
+    }
+    let entries =
+        (0..count).map(|index| slice(bytes, 6 + 16 * index, 16)).collect::<Result<Vec<_>, _>>()?;
+    let entry_size = |entry: &[u8]| match entry[0].max(entry[1]) {
+        0 => 256,
+        size => size as u32,
+    };
+    // PNG images need the PNG decoder, otherwise only the bitmaps are used.
+    let images: Vec<_> = entries
+        .into_iter()
+        .filter(|entry| {
+            let offset = u32::from_le_bytes([entry[12], entry[13], entry[14], entry[15]]);
+            cfg!(feature = "png")
+                || !slice(bytes, offset as usize, PNG_SIGNATURE.len())
+                    .is_ok_and(|signature| signature == PNG_SIGNATURE)
+        })
+        .collect();
+    let index = closest_size(images.iter().map(|entry| entry_size(entry)), size)
+        .ok_or(BadCursorFile::Unsupported)?;
+    let entry = images[index];
+
+    let (hotspot_x, hotspot_y) = match kind {
+        CURSOR => (u16_at(entry, 4)?, u16_at(entry, 6)?),
+        _ => (0, 0),
+    };
+    let length = u32_at(entry, 8)? as usize;
+    let offset = u32_at(entry, 12)? as usize;
+    let image = slice(bytes, offset, length)?;
+    let (rgba, width, height) =
+        if image.starts_with(PNG_SIGNATURE) { png(image)? } else { bitmap(image)? };
+    Ok(DecodedImage { rgba, width, height, hotspot_x, hotspot_y })
+}
+
+/// Decode a PNG image to non-premultiplied RGBA pixels.
+///
+/// The size in the header of the image is checked before its pixels are allocated.
+#[cfg(feature = "png")]
+pub(crate) fn png(bytes: &[u8]) -> Result<(Vec<u8>, u16, u16), BadCursorFile> {
+    let mut decoder = png::Decoder::new(std::io::Cursor::new(bytes));
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info().map_err(|_| BadCursorFile::Malformed)?;
+    let (width, height) = checked_size(reader.info().width, reader.info().height)?;
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buffer).map_err(|_| BadCursorFile::Malformed)?;
+    buffer.truncate(info.buffer_size());
+
+    let rgba = match info.color_type {
+        png::ColorType::Rgba => buffer,
+        png::ColorType::Rgb => {
+            buffer.chunks_exact(3).flat_map(|rgb| [rgb[0], rgb[1], rgb[2], u8::MAX]).collect()
+        },
+        png::ColorType::GrayscaleAlpha => {
+            buffer.chunks_exact(2).flat_map(|la| [la[0], la[0], la[0], la[1]]).collect()
+        },
+        png::ColorType::Grayscale => buffer.iter().flat_map(|&l| [l, l, l, u8::MAX]).collect(),
+        // Palettes are expanded to RGB.
+        png::ColorType::Indexed => return Err(BadCursorFile::Unsupported),
+    };
+    Ok((rgba, width, height))
+}
+
+/// Without the PNG decoder, PNG images are unsupported.
+#[cfg(not(feature = "png"))]
+pub(crate) fn png(_: &[u8]) -> Result<(Vec<u8>, u16, u16), BadCursorFile> {
+    Err(BadCursorFile::Unsupported)
+}
+
+/// Decode a device-independent bitmap with its AND mask, as stored in `.cur` and `.ico` files.
+fn bitmap(bytes: &[u8]) -> Result<(Vec<u8>, u16, u16), BadCursorFile> {
+    const BI_RGB: u32 = 0;
+
+    let header_size = u32_at(bytes, 0)? as usize;
+    let width = u32_at(bytes, 4)? as i32;
+    // The height covers both the color bitmap and the mask.
+    let height = u32_at(bytes, 8)? as i32 / 2;
+    let bit_count = u16_at(bytes, 14)?;
+    let compression = u32_at(bytes, 16)?;
+    let colors_used = u32_at(bytes, 32)?;
+    if width <= 0 || height <= 0 {
+        return Err(BadCursorFile::Malformed);
+    }
+    if compression != BI_RGB || !matches!(bit_count, 1 | 4 | 8 | 24 | 32) {
+        return Err(BadCursorFile::Unsupported);
+    }
+    let (width, height) = checked_size(width as u32, height as u32)?;
+    let (width, height) = (width as usize, height as usize);
+
+    let palette_len = match (bit_count, colors_used) {
+        (24 | 32, _) => 0,
+        (_, 0) => 1 << bit_count,
+        (_, colors_used) => colors_used as usize,
+    };
+    let palette = slice(bytes, header_size, palette_len * 4)?;
+
+    // The rows are padded to 4 bytes, and stored from the bottom up.
+    let stride = |bits: usize| (width * bits).div_ceil(32) * 4;
+    let colors_offset = header_size + palette.len();
+    let colors = slice(bytes, colors_offset, stride(bit_count as usize) * height)?;
+    let mask = slice(bytes, colors_offset + colors.len(), stride(1) * height)?;
+
+    let mut rgba = Vec::with_capacity(width * height * 4);
+    for y in (0..height).rev() {
+        let row = &colors[y * stride(bit_count as usize)..];
+        let mask_row = &mask[y * stride(1)..];
+        for x in 0..width {
+            let [blue, green, red, alpha] = match bit_count {
+                32 => [row[x * 4], row[x * 4 + 1], row[x * 4 + 2], row[x * 4 + 3]],
+                24 => [row[x * 3], row[x * 3 + 1], row[x * 3 + 2], 0],
+                _ => {
+                    let bits = bit_count as usize;
+                    let shift = 8 - bits - (x * bits) % 8;
+                    let index = (row[x * bits / 8] >> shift) as usize & ((1 << bits) - 1);
+                    let color = palette.get(index * 4..index * 4 + 4).unwrap_or(&[0; 4]);
+                    [color[0], color[1], color[2], 0]
+                },
+            };
+            rgba.extend_from_slice(&[red, green, blue, alpha]);
+        }
+        // Older bitmaps use the mask for transparency, its set bits are transparent when the
+        // color is black and invert the screen otherwise, which is approximated as black.
+        if bit_count != 32 {
+            let pixels = rgba.len() - width * 4;
+            for (x, pixel) in rgba[pixels..].chunks_exact_mut(4).enumerate() {
+                let masked = mask_row[x / 8] & (0x80 >> (x % 8)) != 0;
+                if !masked {
+                    pixel[3] = u8::MAX;
+                } else if pixel[..3] != [0, 0, 0] {
+                    pixel.copy_from_slice(&[0, 0, 0, u8::MAX]);
+                }
+            }
+        }
+    }
+
+    // Some 32 bits bitmaps have no alpha channel, and use the mask instead.
+    if bit_count == 32 && rgba.chunks_exact(4).all(|pixel| pixel[3] == 0) {
+        for (index, pixel) in rgba.chunks_exact_mut(4).enumerate() {
+            let (x, y) = (index % width, height - 1 - index / width);
+            let masked = mask[y * stride(1) + x / 8] & (0x80 >> (x % 8)) != 0;
+            pixel[3] = if masked { 0 } else { u8::MAX };
+        }
+    }
+
+    Ok((rgba, width as u16, height as u16))
+}
+
+/// Decode the first frame of a Windows `.ani` file, with the image closest to `size`.
+pub(crate) fn ani(bytes: &[u8], size: u16) -> Result<DecodedImage, BadCursorFile> {
+    /// The frames are `.cur` or `.ico` files, rather than raw bitmaps.
+    const AF_ICON: u32 = 1;
+
+    if slice(bytes, 0, 4)? != b"RIFF" || slice(bytes, 8, 4)? != b"ACON" {
+        return Err(BadCursorFile::Malformed);
+    }
+
+    let mut flags = None;
+    let mut first_frame = 0;
+    let mut frames = Vec::new();
+    let mut chunks = vec![slice(bytes, 12, bytes.len() - 12)?];
+    while let Some(mut data) = chunks.pop() {
+        while data.len() >= 8 {
+            let id = &data[..4];
+            let len = u32_at(data, 4)? as usize;
+            let chunk = slice(data, 8, len)?;
+            match id {
+                b"anih" => flags = Some(u32_at(chunk, 32)?),
+                b"seq " => first_frame = u32_at(chunk, 0)? as usize,
+                b"icon" => frames.push(chunk),
+                b"LIST" if chunk.starts_with(b"fram") => chunks.push(&chunk[4..]),
+                _ => (),
+            }
+            // The chunks are aligned to 2 bytes.
+            data = data.get(8 + len + len % 2..).unwrap_or_default();
+        }
+    }
+
+    match flags {
+        None => Err(BadCursorFile::Malformed),
+        Some(flags) if flags & AF_ICON == 0 => Err(BadCursorFile::Unsupported),
+        Some(_) => cur(frames.get(first_frame).ok_or(BadCursorFile::NoImage)?, size),
+    }
+}
+
+/// Decode the first frame of the nominal size closest to `size` in an X11 cursor file.
+pub(crate) fn xcursor(bytes: &[u8], size: u16) -> Result<DecodedImage, BadCursorFile> {
+    const IMAGE_TYPE: u32 = 0xfffd0002;
+
+    if slice(bytes, 0, 4)? != b"Xcur" {
+        return Err(BadCursorFile::Malformed);
+    }
+    let header_size = u32_at(bytes, 4)? as usize;
+    let count = u32_at(bytes, 12)? as usize;
+
+    // The table of contents has the type, nominal size and position of every chunk.
+    let mut images = Vec::new();
+    for index in 0..count.min(bytes.len() / 12) {
+        let entry = slice(bytes, header_size + 12 * index, 12)?;
+        if u32_at(entry, 0)? == IMAGE_TYPE {
+            images.push((u32_at(entry, 4)?, u32_at(entry, 8)? as usize));
+        }
+    }
+    let index =
+        closest_size(images.iter().map(|&(size, _)| size), size).ok_or(BadCursorFile::NoImage)?;
+    let position = images[index].1;
+
+    let width = u32_at(bytes, position + 16)?;
+    let height = u32_at(bytes, position + 20)?;
+    let hotspot_x = u32_at(bytes, position + 24)?;
+    let hotspot_y = u32_at(bytes, position + 28)?;
+    let (width, height) = checked_size(width, height)?;
+    if hotspot_x >= width as u32 || hotspot_y >= height as u32 {
+        return Err(BadCursorFile::Malformed);
+    }
+
+    // The pixels are premultiplied ARGB, as little endian 32 bits integers.
+    let pixels = slice(bytes, position + 36, width as usize * height as usize * 4)?;
+    let mut rgba = Vec::with_capacity(pixels.len());
+    for pixel in pixels.chunks_exact(4) {
+        let [blue, green, red, alpha] = [pixel[0], pixel[1], pixel[2], pixel[3]];
+        let unpremultiply = |channel: u8| match alpha {
+            0 => 0,
+            alpha => (channel as u32 * 255 / alpha as u32).min(255) as u8,
+        };
+        rgba.extend_from_slice(&[
+            unpremultiply(red),
+            unpremultiply(green),
+            unpremultiply(blue),
+            alpha,
+        ]);
+    }
+
+    Ok(DecodedImage {
+        rgba,
+        width,
+        height,
+        hotspot_x: hotspot_x as u16,
+        hotspot_y: hotspot_y as u16,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A `.cur` file with a single 2x2 image of 32 bits.
+    fn cur_file(hotspot: (u16, u16)) -> Vec<u8> {
+        let mut file = Vec::new();
+        file.extend_from_slice(&[0, 0, 2, 0, 1, 0]);
+        file.extend_from_slice(&[2, 2, 0, 0]);
+        file.extend_from_slice(&hotspot.0.to_le_bytes());
+        file.extend_from_slice(&hotspot.1.to_le_bytes());
+        file.extend_from_slice(&(40u32 + 16 + 8).to_le_bytes());
+        file.extend_from_slice(&22u32.to_le_bytes());
+
+        let mut header = [0; 40];
+        header[0] = 40;
+        header[4] = 2;
+        header[8] = 4;
+        header[12] = 1;
+        header[14] = 32;
+        file.extend_from_slice(&header);
+        // The bottom row, then the top row, in BGRA.
+        file.extend_from_slice(&[0, 0, 255, 255, 0, 255, 0, 255]);
+        file.extend_from_slice(&[255, 0, 0, 255, 0, 0, 0, 0]);
+        file.extend_from_slice(&[0; 8]);
+        file
+    }
+
+    #[test]
+    fn decode_cur() {
+        let image = cur(&cur_file((1, 0)), 32).unwrap();
+        assert_eq!((image.width, image.height), (2, 2));
+        assert_eq!((image.hotspot_x, image.hotspot_y), (1, 0));
+        assert_eq!(image.rgba, [0, 0, 255, 255, 0, 0, 0, 0, 255, 0, 0, 255, 0, 255, 0, 255]);
+
+        assert_eq!(cur(&cur_file((0, 0))[..30], 32), Err(BadCursorFile::Malformed));
+        assert_eq!(cur(b"not a cursor", 32), Err(BadCursorFile::Malformed));
+    }
+
+    #[test]
+    fn decode_ani() {
+        let frame = cur_file((1, 1));
+        let mut anih = vec![0; 36];
+        anih[0] = 36;
+        anih[32] = 1;
+
+        let mut list = b"fram".to_vec();
+        list.extend_from_slice(b"icon");
+        list.extend_from_slice(&(frame.len() as u32).to_le_bytes());
+        list.extend_from_slice(&frame);
+
+        let mut body = b"ACON".to_vec();
+        body.extend_from_slice(b"anih");
+        body.extend_from_slice(&36u32.to_le_bytes());
+        body.extend_from_slice(&anih);
+        body.extend_from_slice(b"LIST");
+        body.extend_from_slice(&(list.len() as u32).to_le_bytes());
+        body.extend_from_slice(&list);
+
+        let mut file = b"RIFF".to_vec();
+        file.extend_from_slice(&(body.len() as u32).to_le_bytes());
+        file.extend_from_slice(&body);
+
+        assert_eq!(ani(&file, 32), cur(&frame, 32));
+    }
+
+    #[test]
+    fn decode_xcursor() {
+        let image = |nominal_size: u32, alpha: u8| {
+            let mut chunk = Vec::new();
+            for value in [36, 0xfffd0002, nominal_size, 1, 1, 1, 0, 0, 50] {
+                chunk.extend_from_slice(&u32::to_le_bytes(value));
+            }
+            chunk.extend_from_slice(&[0, 0, alpha / 2, alpha]);
+            chunk
+        };
+        let mut file = b"Xcur".to_vec();
+        for value in [16, 0x10000, 2, 0xfffd0002, 24, 40, 0xfffd0002, 48, 80] {
+            file.extend_from_slice(&u32::to_le_bytes(value));
+        }
+        file.extend_from_slice(&image(24, 200));
+        file.extend_from_slice(&image(48, 100));
+
+        let small = xcursor(&file, 20).unwrap();
+        assert_eq!(small.rgba, [127, 0, 0, 200]);
+        let large = xcursor(&file, 64).unwrap();
+        assert_eq!(large.rgba, [127, 0, 0, 100]);
+        assert_eq!(xcursor(&file[..50], 64), Err(BadCursorFile::Malformed));
+    }
+
+    #[test]
+    #[cfg(feature = "png")]
+    fn png_too_large() {
+        // Only the header and an empty chunk of image data, as the size is rejected before.
+        let mut file = Vec::new();
+        let encoder = png::Encoder::new(&mut file, 100_000, 100_000);
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_chunk(png::chunk::IDAT, &[]).unwrap();
+        drop(writer);
+
+        let too_large = crate::cursor::BadImage::TooLarge { width: u16::MAX, height: u16::MAX };
+        assert_eq!(png(&file), Err(BadCursorFile::BadImage(too_large)));
+    }
+}
@@ -0,0 +1,463 @@
+//! Native file and folder picker dialogs, and message boxes.
+//!
+//! Dialog crates usually run their own event loop, or need to own the main thread, which conflicts
+//! with winit on several platforms. The dialogs of this module are driven by winit's event loop
+//! instead: [`ActiveEventLoop::pick_file()`], [`pick_folder()`] and [`save_file()`] return right
+//! away, and the choice of the user is delivered later to
+//! [`ApplicationHandler::file_dialog_done()`], along with the serial returned when the dialog was
+//! shown.
+//!
+//! Message boxes work the same way with [`ActiveEventLoop::show_message_box()`] and
+//! [`ApplicationHandler::message_box_done()`]. They don't depend on the application's own UI, so
+//! they can report errors which happen before it's ready.
+//!
+//! ```no_run
+//! # use winit::dialogs::{FileDialogOptions, FileFilter};
+//! # use winit::event_loop::ActiveEventLoop;
+//! # fn scope(event_loop: &dyn ActiveEventLoop) {
+//! let options = FileDialogOptions::new()
+//!     .with_title("Open image")
+//!     .with_filter(FileFilter::new("Images", &["png", "jpg"]));
+//! let serial = event_loop.pick_file(options).expect("failed to show the file picker");
+//! # }
+//! ```
+//!
+//! ```no_run
+//! # use winit::dialogs::{MessageBoxConfig, MessageBoxLevel};
+//! # use winit::event_loop::ActiveEventLoop;
+//! # fn scope(event_loop: &dyn ActiveEventLoop) {
+//! let config = MessageBoxConfig::new("The GPU doesn't support Vulkan 1.2.")
+//!     .with_title("Startup failed")
+//!     .with_level(MessageBoxLevel::Error);
+//! let serial = event_loop.show_message_box(config).expect("failed to show the message box");
+//! # }
+//! ```
+//!
+//! While a dialog is shown, the windows it blocks don't receive input, see [`Modality`]. Pressing
+//! a key or a button on them is reported with [`WindowEvent::ModalInputRejected`] instead.
+//!
+//! This module is only available with the `dialogs` cargo feature.
+//!
+//! [`ActiveEventLoop::pick_file()`]: crate::event_loop::ActiveEventLoop::pick_file()
+//! [`pick_folder()`]: crate::event_loop::ActiveEventLoop::pick_folder()
+//! [`save_file()`]: crate::event_loop::ActiveEventLoop::save_file()
+//! [`ApplicationHandler::file_dialog_done()`]: crate::application::ApplicationHandler::file_dialog_done()
+//! [`ActiveEventLoop::show_message_box()`]: crate::event_loop::ActiveEventLoop::show_message_box()
+//! [`ApplicationHandler::message_box_done()`]: crate::application::ApplicationHandler::message_box_done()
+//! [`WindowEvent::ModalInputRejected`]: crate::event::WindowEvent::ModalInputRejected
+
+use std::path::PathBuf;
+
+#[cfg(any(x11_platform, wayland_platform))]
+use crate::event::{ElementState, KeyEvent, WindowEvent};
+#[cfg(any(x11_platform, wayland_platform))]
+use crate::event_loop::AsyncRequestSerial;
+use crate::window::WindowId;
+
+/// Attributes of a file dialog.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FileDialogOptions {
+    pub(crate) title: Option<String>,
+    pub(crate) directory: Option<PathBuf>,
+    pub(crate) file_name: Option<String>,
+    pub(crate) filters: Vec<FileFilter>,
+    pub(crate) multiple: bool,
+    pub(crate) parent: Option<WindowId>,
+    pub(crate) modality: Modality,
+}
+
+impl FileDialogOptions {
+    /// Options with the platform defaults.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the title of the dialog.
+    #[inline]
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets the directory the dialog starts in.
+    #[inline]
+    pub fn with_directory(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.directory = Some(directory.into());
+        self
+    }
+
+    /// Sets the file name suggested by the save dialog.
+    ///
+    /// Ignored by the pickers.
+    #[inline]
+    pub fn with_file_name(mut self, file_name: impl Into<String>) -> Self {
+        self.file_name = Some(file_name.into());
+        self
+    }
+
+    /// Adds a filter the user can choose from to restrict the files shown.
+    ///
+    /// The first filter is selected by default. Ignored by the folder picker.
+    #[inline]
+    pub fn with_filter(mut self, filter: FileFilter) -> Self {
+        self.filters.push(filter);
+        self
+    }
+
+    /// Sets whether the user can pick multiple files or folders.
+    ///
+    /// The default is `false`. Ignored by the save dialog.
+    #[inline]
+    pub fn with_multiple(mut self, multiple: bool) -> Self {
+        self.multiple = multiple;
+        self
+    }
+
+    /// Sets the window the dialog belongs to.
+    ///
+    /// The dialog is shown on top of it, and is modal to it on platforms supporting that.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** Unsupported.
+    #[inline]
+    pub fn with_parent(mut self, parent: WindowId) -> Self {
+        self.parent = Some(parent);
+        self
+    }
+
+    /// Sets the windows blocked while the dialog is shown.
+    ///
+    /// The default is [`Modality::Window`].
+    #[inline]
+    pub fn with_modality(mut self, modality: Modality) -> Self {
+        self.modality = modality;
+        self
+    }
+}
+
+/// A set of file extensions, shown with a name in the file dialogs.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FileFilter {
+    /// The name shown to the user, like `"Images"`.
+    pub name: String,
+
+    /// The extensions of the files matching the filter, without the leading dot.
+    pub extensions: Vec<String>,
+}
+
+impl FileFilter {
+    /// Creates a filter from its name and extensions.
+    pub fn new(name: impl Into<String>, extensions: &[&str]) -> Self {
+        Self {
+            name: name.into(),
+            extensions: extensions.iter().map(|extension| extension.to_string()).collect(),
+        }
+    }
+}
+
+/// The outcome of a file dialog, delivered to [`ApplicationHandler::file_dialog_done()`].
+///
+/// [`ApplicationHandler::file_dialog_done()`]: crate::application::ApplicationHandler::file_dialog_done()
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum FileDialogResult {
+    /// The paths chosen by the user.
+    ///
+    /// Contains a single path, unless multiple selection was allowed.
+    Picked(Vec<PathBuf>),
+
+    /// The user dismissed the dialog.
+    Cancelled,
+
+    /// The dialog couldn't be shown, with a description of the error.
+    Failed(String),
+}
+
+/// Attributes of a message box.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MessageBoxConfig {
+    pub(crate) title: String,
+    pub(crate) text: String,
+    pub(crate) level: MessageBoxLevel,
+    pub(crate) buttons: MessageBoxButtons,
+    pub(crate) parent: Option<WindowId>,
+    pub(crate) modality: Modality,
+}
+
+impl MessageBoxConfig {
+    /// A message box showing `text`, with a single "OK" button.
+    pub fn new(text: impl Into<String>) -> Self {
+        Self { text: text.into(), ..Default::default() }
+    }
+
+    /// Sets the title of the message box.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** The title is shown in bold above the text, as alerts don't have a title bar.
+    #[inline]
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Sets the severity of the message, which picks the icon of the message box.
+    ///
+    /// The default is [`MessageBoxLevel::Info`].
+    #[inline]
+    pub fn with_level(mut self, level: MessageBoxLevel) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Sets the buttons the user can choose from.
+    ///
+    /// The default is [`MessageBoxButtons::Ok`].
+    #[inline]
+    pub fn with_buttons(mut self, buttons: MessageBoxButtons) -> Self {
+        self.buttons = buttons;
+        self
+    }
+
+    /// Sets the window the message box belongs to.
+    ///
+    /// The message box is shown on top of it, and is modal to it on platforms supporting that.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** The message box isn't shown on top of it, but still blocks it.
+    #[inline]
+    pub fn with_parent(mut self, parent: WindowId) -> Self {
+        self.parent = Some(parent);
+        self
+    }
+
+    /// Sets the windows blocked while the message box is shown.
+    ///
+    /// The default is [`Modality::Window`].
+    #[inline]
+    pub fn with_modality(mut self, modality: Modality) -> Self {
+        self.modality = modality;
+        self
+    }
+}
+
+/// The windows blocked by a dialog while it's shown.
+///
+/// The blocked windows don't receive input, and pressing a key or a button on them is reported with
+/// [`WindowEvent::ModalInputRejected`].
+///
+/// ## Platform-specific
+///
+/// - **macOS:** Window-modal dialogs are shown as sheets, application-modal dialogs are run with
+///   `runModal`.
+///
+/// [`WindowEvent::ModalInputRejected`]: crate::event::WindowEvent::ModalInputRejected
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Modality {
+    /// Only the parent window is blocked, the other windows keep working.
+    ///
+    /// Without a parent window, no window is blocked.
+    #[default]
+    Window,
+
+    /// All the windows of the application are blocked.
+    Application,
+}
+
+/// The severity of a message box.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MessageBoxLevel {
+    /// An informative message.
+    #[default]
+    Info,
+
+    /// A warning, the user may want to reconsider.
+    Warning,
+
+    /// An error.
+    Error,
+}
+
+/// The buttons of a message box.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MessageBoxButtons {
+    /// A single "OK" button.
+    #[default]
+    Ok,
+
+    /// "OK" and "Cancel".
+    OkCancel,
+
+    /// "Yes" and "No".
+    YesNo,
+
+    /// "Yes", "No" and "Cancel".
+    YesNoCancel,
+}
+
+impl MessageBoxButtons {
+    /// The button chosen when the message box is dismissed without pressing a button, like with
+    /// the escape key.
+    #[cfg(any(windows_platform, macos_platform, x11_platform, wayland_platform))]
+    pub(crate) fn dismissed(self) -> MessageBoxButton {
+        match self {
+            Self::Ok => MessageBoxButton::Ok,
+            Self::YesNo => MessageBoxButton::No,
+            Self::OkCancel | Self::YesNoCancel => MessageBoxButton::Cancel,
+        }
+    }
+}
+
+/// A button of a message box.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MessageBoxButton {
+    Ok,
+    Cancel,
+    Yes,
+    No,
+}
+
+/// The outcome of a message box, delivered to [`ApplicationHandler::message_box_done()`].
+///
+/// [`ApplicationHandler::message_box_done()`]: crate::application::ApplicationHandler::message_box_done()
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MessageBoxResult {
+    /// The button pressed by the user.
+    ///
+    /// Dismissing the message box counts as pressing "Cancel". Without a "Cancel" button, it
+    /// counts as pressing "No", or "OK" when it's the only button.
+    Pressed(MessageBoxButton),
+
+    /// The message box couldn't be shown, with a description of the error.
+    Failed(String),
+}
+
+/// The kind of a file dialog.
+#[cfg(any(windows_platform, macos_platform, x11_platform, wayland_platform))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum FileDialogKind {
+    PickFile,
+    PickFolder,
+    SaveFile,
+}
+
+/// The windows blocked by the dialogs which are shown, for the backends which don't block them
+/// natively.
+#[cfg(any(x11_platform, wayland_platform))]
+#[derive(Debug, Default)]
+pub(crate) struct ModalBlocks {
+    /// The dialogs with the window they block, or `None` when they block all of them.
+    dialogs: Vec<(AsyncRequestSerial, Option<WindowId>)>,
+}
+
+#[cfg(any(x11_platform, wayland_platform))]
+impl ModalBlocks {
+    /// Block the windows for the dialog shown with `serial`, until it's unblocked.
+    pub fn block(
+        &mut self,
+        serial: AsyncRequestSerial,
+        modality: Modality,
+        parent: Option<WindowId>,
+    ) {
+        match (modality, parent) {
+            (Modality::Application, _) => self.dialogs.push((serial, None)),
+            (Modality::Window, Some(parent)) => self.dialogs.push((serial, Some(parent))),
+            (Modality::Window, None) => (),
+        }
+    }
+
+    /// Release the windows blocked by the dialog shown with `serial`, once it's dismissed.
+    pub fn unblock(&mut self, serial: AsyncRequestSerial) {
+        self.dialogs.retain(|&(dialog, _)| dialog != serial);
+    }
+
+    pub fn is_blocked(&self, window_id: WindowId) -> bool {
+        self.dialogs
+            .iter()
+            .any(|&(_, blocked)| blocked.map_or(true, |blocked| blocked == window_id))
+    }
+
+    /// The event to deliver in place of `event`, if any.
+    ///
+    /// The input of the blocked windows is dropped, and the presses are turned into
+    /// [`WindowEvent::ModalInputRejected`].
+    pub fn filter(&self, window_id: WindowId, event: WindowEvent) -> Option<WindowEvent> {
+        if !self.is_blocked(window_id) {
+            return Some(event);
+        }
+
+        match event {
+            WindowEvent::PointerButton { state: ElementState::Pressed, .. }
+            | WindowEvent::KeyboardInput {
+                event: KeyEvent { state: ElementState::Pressed, repeat: false, .. },
+                is_synthetic: false,
+                ..
+            } => Some(WindowEvent::ModalInputRejected),
+            WindowEvent::KeyboardInput { .. }
+            | WindowEvent::PointerButton { .. }
+            | WindowEvent::PointerMoved { .. }
+            | WindowEvent::PenInput { .. }
+            | WindowEvent::MouseWheel { .. }
+            | WindowEvent::Ime(_)
+            | WindowEvent::PinchGesture { .. }
+            | WindowEvent::PanGesture { .. }
+            | WindowEvent::DoubleTapGesture { .. }
+            | WindowEvent::RotationGesture { .. }
+            | WindowEvent::TouchpadPressure { .. } => None,
+            event => Some(event),
+        }
+    }
+}
+
+#[cfg(all(test, any(x11_platform, wayland_platform)))]
+mod tests {
+    use super::*;
+    use crate::dpi::PhysicalPosition;
+    use crate::event::{ButtonSource, MouseButton};
+
+    fn button(state: ElementState) -> WindowEvent {
+        WindowEvent::PointerButton {
+            device_id: None,
+            state,
+            position: PhysicalPosition::new(0.0, 0.0),
+            button: ButtonSource::Mouse(MouseButton::Left),
+        }
+    }
+
+    #[test]
+    fn modal_blocks() {
+        let parent = WindowId::from_raw(1);
+        let other = WindowId::from_raw(2);
+        let window_modal = AsyncRequestSerial::get();
+        let app_modal = AsyncRequestSerial::get();
+        let mut blocks = ModalBlocks::default();
+
+        blocks.block(window_modal, Modality::Window, None);
+        assert!(!blocks.is_blocked(parent));
+
+        blocks.block(window_modal, Modality::Window, Some(parent));
+        assert!(blocks.is_blocked(parent));
+        assert!(!blocks.is_blocked(other));
+        assert_eq!(
+            blocks.filter(parent, button(ElementState::Pressed)),
+            Some(WindowEvent::ModalInputRejected)
+        );
+        assert_eq!(blocks.filter(parent, button(ElementState::Released)), None);
+        assert_eq!(
+            blocks.filter(parent, WindowEvent::Focused(true)),
+            Some(WindowEvent::Focused(true))
+        );
+        assert_eq!(
+            blocks.filter(other, button(ElementState::Pressed)),
+            Some(button(ElementState::Pressed))
+        );
+
+        blocks.block(app_modal, Modality::Application, Some(parent));
+        assert!(blocks.is_blocked(other));
+
+        blocks.unblock(app_modal);
+        blocks.unblock(window_modal);
+        assert!(!blocks.is_blocked(parent));
+    }
+}
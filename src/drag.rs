@@ -0,0 +1,518 @@
+//! Drag and drop between the windows of the application.
+//!
+//! [`ActiveEventLoop::start_drag()`] starts dragging [`DragData`] from one of the application's
+//! windows, usually once the pointer moved a few pixels with a button held. As long as the pointer
+//! stays over the application's windows, the drag never goes through the system: the windows it
+//! moves over receive [`WindowEvent::DragEntered`], [`WindowEvent::DragMoved`] and
+//! [`WindowEvent::DragLeft`], and the window it's released over receives
+//! [`WindowEvent::DragDropped`] along with the data itself. The data isn't serialized, so
+//! [`DragData::Custom`] can carry any Rust value. The window the drag started from then receives
+//! [`WindowEvent::DragEnded`].
+//!
+//! While the drag is in progress, the window it started from receives
+//! [`WindowEvent::DragSourceMoved`] with the window under the pointer, and can change the image
+//! following the pointer with [`ActiveEventLoop::set_drag_image()`]. If the drag is cancelled, the
+//! window should restore whatever it changed when it started.
+//!
+//! When the pointer leaves the application's windows, files and text are handed over to the drag
+//! and drop of the system where it's supported, so they can be dropped in other applications.
+//!
+//! Data dragged from other applications is delivered with [`WindowEvent::DataDropped`] as
+//! [`DropData`], which is read in the [formats][ClipboardFormat] of the clipboard. While it's
+//! dragged over a window, [`WindowEvent::DataHovered`] lets the window choose the [`DragEffect`]
+//! of dropping it at each position, or reject it.
+//!
+//! ```no_run
+//! # use winit::drag::DragData;
+//! # use winit::event::WindowEvent;
+//! # use winit::event_loop::ActiveEventLoop;
+//! # use winit::window::WindowId;
+//! struct Tab {
+//!     title: String,
+//! }
+//!
+//! # fn scope(event_loop: &dyn ActiveEventLoop, source: WindowId, event: WindowEvent) {
+//! // When the user starts dragging a tab out of the tab bar.
+//! let tab = Tab { title: "README.md".into() };
+//! event_loop.start_drag(source, DragData::Custom(Box::new(tab))).unwrap();
+//!
+//! // In the window it's dropped on.
+//! if let WindowEvent::DragDropped { data, .. } = event {
+//!     if let Some(tab) = data.take_custom::<Tab>() {
+//!         println!("Moved {} to another window", tab.title);
+//!     }
+//! }
+//! # }
+//! ```
+//!
+//! [`ActiveEventLoop::start_drag()`]: crate::event_loop::ActiveEventLoop::start_drag()
+//! [`ActiveEventLoop::set_drag_image()`]: crate::event_loop::ActiveEventLoop::set_drag_image()
+//! [`WindowEvent::DragEntered`]: crate::event::WindowEvent::DragEntered
+//! [`WindowEvent::DragMoved`]: crate::event::WindowEvent::DragMoved
+//! [`WindowEvent::DragLeft`]: crate::event::WindowEvent::DragLeft
+//! [`WindowEvent::DragDropped`]: crate::event::WindowEvent::DragDropped
+//! [`WindowEvent::DragEnded`]: crate::event::WindowEvent::DragEnded
+//! [`WindowEvent::DragSourceMoved`]: crate::event::WindowEvent::DragSourceMoved
+//! [`WindowEvent::DataDropped`]: crate::event::WindowEvent::DataDropped
+//! [`WindowEvent::DataHovered`]: crate::event::WindowEvent::DataHovered
+
+use std::any::Any;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, Weak};
+use std::thread::{self, ThreadId};
+
+#[cfg(any(windows_platform, macos_platform, x11_platform))]
+use dpi::PhysicalPosition;
+
+use crate::clipboard::{ClipboardData, ClipboardFormat};
+use crate::error::RequestError;
+#[cfg(any(windows_platform, macos_platform, x11_platform))]
+use crate::event::WindowEvent;
+#[cfg(any(windows_platform, macos_platform, x11_platform))]
+use crate::window::Cursor;
+use crate::window::WindowId;
+
+/// The data dragged with [`ActiveEventLoop::start_drag()`].
+///
+/// [`ActiveEventLoop::start_drag()`]: crate::event_loop::ActiveEventLoop::start_drag()
+pub enum DragData {
+    /// Paths of files, which other applications can receive as well.
+    Files(Vec<PathBuf>),
+    /// Plain text, which other applications can receive as well.
+    Text(String),
+    /// Any value, which can only be dropped on the application's own windows.
+    Custom(Box<dyn Any + Send>),
+}
+
+impl DragData {
+    /// Returns a reference to the custom value if it's a `T`.
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        match self {
+            Self::Custom(value) => value.downcast_ref(),
+            _ => None,
+        }
+    }
+
+    /// Whether the data can be handed over to the drag and drop of the system.
+    #[allow(dead_code)] // Not used on every platform
+    pub(crate) fn is_shareable(&self) -> bool {
+        !matches!(self, Self::Custom(_))
+    }
+}
+
+impl fmt::Debug for DragData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Files(paths) => f.debug_tuple("Files").field(paths).finish(),
+            Self::Text(text) => f.debug_tuple("Text").field(text).finish(),
+            Self::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+/// The data delivered with [`WindowEvent::DragDropped`].
+///
+/// Cloning the payload doesn't clone the data, which can only be taken once.
+///
+/// [`WindowEvent::DragDropped`]: crate::event::WindowEvent::DragDropped
+#[derive(Clone)]
+pub struct DragPayload {
+    data: Arc<Mutex<Option<DragData>>>,
+}
+
+impl DragPayload {
+    #[cfg(any(windows_platform, macos_platform, x11_platform, test))]
+    pub(crate) fn new(data: DragData) -> Self {
+        Self { data: Arc::new(Mutex::new(Some(data))) }
+    }
+
+    /// Take the data, or `None` if it was already taken.
+    pub fn take(&self) -> Option<DragData> {
+        self.data.lock().unwrap().take()
+    }
+
+    /// Take the custom value if it's a `T`, leaving the data in place otherwise.
+    pub fn take_custom<T: Any>(&self) -> Option<T> {
+        let mut data = self.data.lock().unwrap();
+        data.as_ref()?.downcast_ref::<T>()?;
+        match data.take() {
+            Some(DragData::Custom(value)) => value.downcast().ok().map(|value| *value),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Debug for DragPayload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DragPayload").field("data", &*self.data.lock().unwrap()).finish()
+    }
+}
+
+impl PartialEq for DragPayload {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.data, &other.data)
+    }
+}
+
+impl Eq for DragPayload {}
+
+/// The data dropped from another application, delivered with [`WindowEvent::DataDropped`].
+///
+/// The data is only transferred in the formats requested with [`get()`][Self::get()], so large
+/// payloads the application doesn't need aren't copied. The other application only provides it
+/// during the drop, so it can be read while the event is handled, on the thread of the event loop.
+/// Afterwards, [`get()`][Self::get()] returns `None`.
+///
+/// ```no_run
+/// # use winit::clipboard::{ClipboardData, ClipboardFormat};
+/// # use winit::event::WindowEvent;
+/// # fn scope(event: WindowEvent) {
+/// if let WindowEvent::DataDropped { data, .. } = event {
+///     if let Some(ClipboardData::Uris(uris)) = data.get(ClipboardFormat::Uris) {
+///         println!("Open {uris:?}");
+///     } else if let Some(ClipboardData::Text(text)) = data.get(ClipboardFormat::Text) {
+///         println!("Insert {text}");
+///     }
+/// }
+/// # }
+/// ```
+///
+/// [`WindowEvent::DataDropped`]: crate::event::WindowEvent::DataDropped
+#[derive(Clone)]
+pub struct DropData {
+    inner: Arc<DropDataInner>,
+}
+
+/// Reads the data in a format, while the drop is in progress.
+type DropReader = Box<dyn FnMut(ClipboardFormat) -> Option<ClipboardData> + Send>;
+
+struct DropDataInner {
+    formats: Vec<ClipboardFormat>,
+    thread: ThreadId,
+    reader: Mutex<Option<DropReader>>,
+}
+
+impl DropData {
+    /// The data in the `formats`, read by the `reader` on the current thread.
+    #[allow(dead_code)] // Not used on every platform
+    pub(crate) fn new(
+        formats: Vec<ClipboardFormat>,
+        reader: impl FnMut(ClipboardFormat) -> Option<ClipboardData> + Send + 'static,
+    ) -> Self {
+        let reader = Mutex::new(Some(Box::new(reader) as DropReader));
+        Self { inner: Arc::new(DropDataInner { formats, thread: thread::current().id(), reader }) }
+    }
+
+    /// The formats the data can be read in, the ones the other application prefers first.
+    ///
+    /// [Custom formats][ClipboardFormat::Custom] aren't listed, but can be read with
+    /// [`get()`][Self::get()].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** The custom formats of the `DropFormatHandler` of the window are listed.
+    pub fn formats(&self) -> &[ClipboardFormat] {
+        &self.inner.formats
+    }
+
+    /// Read the data in the `format`, or `None` if it isn't available.
+    pub fn get(&self, format: ClipboardFormat) -> Option<ClipboardData> {
+        let listed =
+            matches!(format, ClipboardFormat::Custom(_)) || self.formats().contains(&format);
+        if !listed || thread::current().id() != self.inner.thread {
+            return None;
+        }
+        let mut reader = self.inner.reader.lock().unwrap();
+        (reader.as_mut()?)(format)
+    }
+
+    /// The drop is done, the data can't be read anymore.
+    #[allow(dead_code)] // Not used on every platform
+    pub(crate) fn expire(&self) {
+        self.inner.reader.lock().unwrap().take();
+    }
+}
+
+impl fmt::Debug for DropData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DropData").field("formats", &self.inner.formats).finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for DropData {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl Eq for DropData {}
+
+/// How a drag ended, reported with [`WindowEvent::DragEnded`].
+///
+/// [`WindowEvent::DragEnded`]: crate::event::WindowEvent::DragEnded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DragOutcome {
+    /// The data was dropped on one of the application's windows.
+    Dropped(WindowId),
+    /// The data was handed over to the drag and drop of the system, and dropped in another
+    /// application with the effect it chose.
+    DroppedOutside(DragEffect),
+    /// The drag was released outside of any window accepting it, or cancelled by the user.
+    Cancelled,
+}
+
+/// What another application did with the data dropped on it, reported with
+/// [`DragOutcome::DroppedOutside`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DragEffect {
+    /// The data was copied, the source keeps it.
+    Copy,
+    /// The data was moved, the source should remove it.
+    Move,
+    /// The application created a link to the data, like a shortcut to the files.
+    Link,
+}
+
+/// Handle to choose the effect of dropping the data hovered over the window, from
+/// [`WindowEvent::DataHovered`].
+///
+/// [`WindowEvent::DataHovered`]: crate::event::WindowEvent::DataHovered
+#[derive(Debug, Clone)]
+pub struct DropEffectWriter {
+    effect: Weak<Mutex<Option<DragEffect>>>,
+}
+
+impl DropEffectWriter {
+    /// Ask the application for the effect of dropping the hovered data, starting with `default`.
+    ///
+    /// The writer is only valid while `emit` runs, and the effect is read once it returns.
+    #[allow(dead_code)] // Not used on every platform
+    pub(crate) fn negotiate(
+        default: Option<DragEffect>,
+        emit: impl FnOnce(DropEffectWriter),
+    ) -> Option<DragEffect> {
+        let effect = Arc::new(Mutex::new(default));
+        emit(Self { effect: Arc::downgrade(&effect) });
+        let effect = *effect.lock().unwrap();
+        effect
+    }
+
+    /// Set the effect of dropping the data at the position of the event, or `None` to reject the
+    /// drop there.
+    pub fn set_effect(&mut self, effect: Option<DragEffect>) -> Result<(), RequestError> {
+        if let Some(inner) = self.effect.upgrade() {
+            *inner.lock().unwrap() = effect;
+            Ok(())
+        } else {
+            Err(RequestError::Ignored)
+        }
+    }
+}
+
+impl PartialEq for DropEffectWriter {
+    fn eq(&self, other: &Self) -> bool {
+        self.effect.as_ptr() == other.effect.as_ptr()
+    }
+}
+
+impl Eq for DropEffectWriter {}
+
+/// The state of a drag between the application's windows, shared by the backends.
+///
+/// The backends report where the pointer is, and the session turns it into the events of the
+/// windows it moves over.
+#[cfg(any(windows_platform, macos_platform, x11_platform))]
+#[derive(Debug)]
+pub(crate) struct DragSession {
+    source: WindowId,
+    data: DragData,
+    target: Option<WindowId>,
+    image: Option<Cursor>,
+}
+
+#[cfg(any(windows_platform, macos_platform, x11_platform))]
+impl DragSession {
+    pub(crate) fn new(source: WindowId, data: DragData) -> Self {
+        Self { source, data, target: None, image: None }
+    }
+
+    #[allow(dead_code)] // Not used on every platform
+    pub(crate) fn source(&self) -> WindowId {
+        self.source
+    }
+
+    #[allow(dead_code)] // Not used on every platform
+    pub(crate) fn data(&self) -> &DragData {
+        &self.data
+    }
+
+    /// The image following the pointer, or `None` for the cursor of the source window.
+    #[allow(dead_code)] // Not used on every platform
+    pub(crate) fn image(&self) -> Option<&Cursor> {
+        self.image.as_ref()
+    }
+
+    #[allow(dead_code)] // Not used on every platform
+    pub(crate) fn set_image(&mut self, image: Option<Cursor>) {
+        self.image = image;
+    }
+
+    /// The pointer moved over `target`, the window under it with the position in that window, or
+    /// `None` outside of the application's windows.
+    pub(crate) fn moved(
+        &mut self,
+        target: Option<(WindowId, PhysicalPosition<f64>)>,
+        mut emit: impl FnMut(WindowId, WindowEvent),
+    ) {
+        self.move_to(target, &mut emit);
+        let target = self.target;
+        emit(self.source, WindowEvent::DragSourceMoved { target });
+    }
+
+    fn move_to(
+        &mut self,
+        target: Option<(WindowId, PhysicalPosition<f64>)>,
+        mut emit: impl FnMut(WindowId, WindowEvent),
+    ) {
+        let new_target = target.map(|(window_id, _)| window_id);
+        if self.target != new_target {
+            if let Some(window_id) = self.target.take() {
+                emit(window_id, WindowEvent::DragLeft);
+            }
+        }
+
+        if let Some((window_id, position)) = target {
+            if self.target == Some(window_id) {
+                emit(window_id, WindowEvent::DragMoved { position });
+            } else {
+                self.target = Some(window_id);
+                emit(window_id, WindowEvent::DragEntered { source: self.source, position });
+            }
+        }
+    }
+
+    /// The pointer was released over `target`, ending the drag.
+    pub(crate) fn released(
+        mut self,
+        target: Option<(WindowId, PhysicalPosition<f64>)>,
+        mut emit: impl FnMut(WindowId, WindowEvent),
+    ) {
+        self.move_to(target, &mut emit);
+
+        let outcome = match target {
+            Some((window_id, position)) => {
+                let data = DragPayload::new(self.data);
+                emit(window_id, WindowEvent::DragDropped { source: self.source, position, data });
+                DragOutcome::Dropped(window_id)
+            },
+            None => DragOutcome::Cancelled,
+        };
+        emit(self.source, WindowEvent::DragEnded { outcome });
+    }
+
+    /// The drag ended without being dropped on the application's windows.
+    ///
+    /// `effect` is what another application did with the data when the system dropped it there.
+    #[allow(dead_code)] // Not used on every platform
+    pub(crate) fn ended(
+        mut self,
+        effect: Option<DragEffect>,
+        mut emit: impl FnMut(WindowId, WindowEvent),
+    ) {
+        self.move_to(None, &mut emit);
+        let outcome = effect.map_or(DragOutcome::Cancelled, DragOutcome::DroppedOutside);
+        emit(self.source, WindowEvent::DragEnded { outcome });
+    }
+}
+
+#[cfg(all(test, any(windows_platform, macos_platform, x11_platform)))]
+mod tests {
+    use super::*;
+
+    fn events(f: impl FnOnce(&mut dyn FnMut(WindowId, WindowEvent))) -> Vec<(usize, WindowEvent)> {
+        let mut events = Vec::new();
+        f(&mut |window_id, event| events.push((window_id.into_raw(), event)));
+        events
+    }
+
+    #[test]
+    fn drag_between_windows() {
+        let (a, b) = (WindowId::from_raw(1), WindowId::from_raw(2));
+        let position = PhysicalPosition::new(1.0, 2.0);
+        let mut session = DragSession::new(a, DragData::Custom(Box::new(42u32)));
+
+        let moved = events(|emit| {
+            session.moved(Some((a, position)), &mut *emit);
+            session.moved(Some((a, position)), &mut *emit);
+            session.moved(None, &mut *emit);
+            session.moved(Some((b, position)), &mut *emit);
+        });
+        assert_eq!(moved, vec![
+            (1, WindowEvent::DragEntered { source: a, position }),
+            (1, WindowEvent::DragSourceMoved { target: Some(a) }),
+            (1, WindowEvent::DragMoved { position }),
+            (1, WindowEvent::DragSourceMoved { target: Some(a) }),
+            (1, WindowEvent::DragLeft),
+            (1, WindowEvent::DragSourceMoved { target: None }),
+            (2, WindowEvent::DragEntered { source: a, position }),
+            (1, WindowEvent::DragSourceMoved { target: Some(b) }),
+        ]);
+
+        let released = events(|emit| session.released(Some((b, position)), emit));
+        assert_eq!(released.len(), 3);
+        assert_eq!(released[0], (2, WindowEvent::DragMoved { position }));
+        let (2, WindowEvent::DragDropped { data, .. }) = &released[1] else {
+            panic!("expected a drop, got {:?}", released[1]);
+        };
+        assert_eq!(data.take_custom::<String>(), None);
+        assert_eq!(data.take_custom::<u32>(), Some(42));
+        assert!(data.take().is_none());
+        assert_eq!(released[2], (1, WindowEvent::DragEnded { outcome: DragOutcome::Dropped(b) }));
+    }
+
+    #[test]
+    fn drop_data_expires() {
+        let data = DropData::new(vec![ClipboardFormat::Text], |format| {
+            (format == ClipboardFormat::Text).then(|| ClipboardData::Text("text".into()))
+        });
+        assert_eq!(data.get(ClipboardFormat::Html), None);
+        assert_eq!(data.get(ClipboardFormat::Text), Some(ClipboardData::Text("text".into())));
+
+        let clone = data.clone();
+        let other_thread = std::thread::spawn(move || clone.get(ClipboardFormat::Text));
+        assert_eq!(other_thread.join().unwrap(), None);
+
+        data.expire();
+        assert_eq!(data.get(ClipboardFormat::Text), None);
+    }
+
+    #[test]
+    fn drop_effect_negotiation() {
+        let mut writer = None;
+        let effect = DropEffectWriter::negotiate(Some(DragEffect::Copy), |mut effect| {
+            effect.set_effect(Some(DragEffect::Link)).unwrap();
+            writer = Some(effect);
+        });
+        assert_eq!(effect, Some(DragEffect::Link));
+        assert!(writer.unwrap().set_effect(None).is_err());
+    }
+
+    #[test]
+    fn drag_released_outside() {
+        let a = WindowId::from_raw(1);
+        let position = PhysicalPosition::new(0.0, 0.0);
+        let mut session = DragSession::new(a, DragData::Text("text".into()));
+        assert!(session.data().is_shareable());
+
+        let events = events(|emit| {
+            session.moved(Some((a, position)), &mut *emit);
+            session.ended(Some(DragEffect::Move), emit);
+        });
+        let outcome = DragOutcome::DroppedOutside(DragEffect::Move);
+        assert_eq!(events[2..], [
+            (1, WindowEvent::DragLeft),
+            (1, WindowEvent::DragEnded { outcome }),
+        ]);
+    }
+}
@@ -34,8 +34,11 @@
 //!
 //! [`EventLoop::run_app(...)`]: crate::event_loop::EventLoop::run_app
 //! [`ControlFlow::WaitUntil`]: crate::event_loop::ControlFlow::WaitUntil
+use std::any::Any;
+use std::fmt;
+use std::ops::Range;
 use std::path::PathBuf;
-use std::sync::{Mutex, Weak};
+use std::sync::{Arc, Mutex, Weak};
 #[cfg(not(web_platform))]
 use std::time::Instant;
 
@@ -45,14 +48,17 @@ use smol_str::SmolStr;
 #[cfg(web_platform)]
 use web_time::Instant;
 
+use crate::clipboard::{ClipboardData, ClipboardFormat};
 use crate::dpi::{PhysicalPosition, PhysicalSize};
+use crate::drag::{DragEffect, DragOutcome, DragPayload, DropData, DropEffectWriter};
 use crate::error::RequestError;
 use crate::event_loop::AsyncRequestSerial;
-use crate::keyboard::{self, ModifiersKeyState, ModifiersKeys, ModifiersState};
+use crate::keyboard::{self, KeyboardLayout, ModifiersKeyState, ModifiersKeys, ModifiersState};
+use crate::monitor::MonitorHandle;
 use crate::platform_impl;
 #[cfg(doc)]
 use crate::window::Window;
-use crate::window::{ActivationToken, Theme, WindowId};
+use crate::window::{ActivationToken, CursorGrabMode, CursorThemeInfo, Rgba, Theme, WindowId};
 
 // TODO: Remove once the backends can call `ApplicationHandler` methods directly. For now backends
 // like Windows and Web require `Event` to wire user events, otherwise each backend will have to
@@ -100,6 +106,11 @@ pub(crate) enum Event {
     /// [`ApplicationHandler::about_to_wait()`]: crate::application::ApplicationHandler::about_to_wait()
     AboutToWait,
 
+    /// See [`ApplicationHandler::exit_requested()`] for details.
+    ///
+    /// [`ApplicationHandler::exit_requested()`]: crate::application::ApplicationHandler::exit_requested()
+    ExitRequested,
+
     /// See [`ApplicationHandler::exiting()`] for details.
     ///
     /// [`ApplicationHandler::exiting()`]: crate::application::ApplicationHandler::exiting()
@@ -110,6 +121,125 @@ pub(crate) enum Event {
     /// [`ApplicationHandler::memory_warning()`]: crate::application::ApplicationHandler::memory_warning()
     MemoryWarning,
 
+    /// See [`ApplicationHandler::power_event()`] for details.
+    ///
+    /// [`ApplicationHandler::power_event()`]: crate::application::ApplicationHandler::power_event()
+    PowerEvent(PowerEvent),
+
+    /// See [`ApplicationHandler::reopen_requested()`] for details.
+    ///
+    /// [`ApplicationHandler::reopen_requested()`]: crate::application::ApplicationHandler::reopen_requested()
+    ReopenRequested,
+
+    /// See [`ApplicationHandler::open_files()`] for details.
+    ///
+    /// [`ApplicationHandler::open_files()`]: crate::application::ApplicationHandler::open_files()
+    OpenFiles(Vec<PathBuf>),
+
+    /// See [`ApplicationHandler::open_urls()`] for details.
+    ///
+    /// [`ApplicationHandler::open_urls()`]: crate::application::ApplicationHandler::open_urls()
+    OpenUrls(Vec<String>),
+
+    /// See [`ApplicationHandler::locale_changed()`] for details.
+    ///
+    /// [`ApplicationHandler::locale_changed()`]: crate::application::ApplicationHandler::locale_changed()
+    LocaleChanged(LanguageTag),
+
+    /// See [`ApplicationHandler::cursor_theme_changed()`] for details.
+    ///
+    /// [`ApplicationHandler::cursor_theme_changed()`]: crate::application::ApplicationHandler::cursor_theme_changed()
+    CursorThemeChanged(CursorThemeInfo),
+
+    /// See [`ApplicationHandler::system_preferences_changed()`] for details.
+    ///
+    /// [`ApplicationHandler::system_preferences_changed()`]: crate::application::ApplicationHandler::system_preferences_changed()
+    SystemPreferencesChanged(SystemPreferences),
+
+    /// See [`ApplicationHandler::text_scale_factor_changed()`] for details.
+    ///
+    /// [`ApplicationHandler::text_scale_factor_changed()`]: crate::application::ApplicationHandler::text_scale_factor_changed()
+    TextScaleFactorChanged(f64),
+
+    /// See [`ApplicationHandlerExtWindows::jump_list_task()`] for details.
+    ///
+    /// [`ApplicationHandlerExtWindows::jump_list_task()`]: crate::platform::windows::ApplicationHandlerExtWindows::jump_list_task()
+    #[cfg(windows_platform)]
+    JumpListTask(Vec<String>),
+
+    /// See [`ApplicationHandlerExtWindows::thumbbar_button_clicked()`] for details.
+    ///
+    /// [`ApplicationHandlerExtWindows::thumbbar_button_clicked()`]: crate::platform::windows::ApplicationHandlerExtWindows::thumbbar_button_clicked()
+    #[cfg(windows_platform)]
+    ThumbbarButtonClicked { window_id: WindowId, id: u32 },
+
+    /// See [`ApplicationHandler::input_device_added()`] for details.
+    ///
+    /// [`ApplicationHandler::input_device_added()`]: crate::application::ApplicationHandler::input_device_added()
+    InputDeviceAdded(DeviceInfo),
+
+    /// See [`ApplicationHandler::input_device_removed()`] for details.
+    ///
+    /// [`ApplicationHandler::input_device_removed()`]: crate::application::ApplicationHandler::input_device_removed()
+    InputDeviceRemoved(DeviceId),
+
+    /// See [`ApplicationHandler::monitor_added()`] for details.
+    ///
+    /// [`ApplicationHandler::monitor_added()`]: crate::application::ApplicationHandler::monitor_added()
+    MonitorAdded(MonitorHandle),
+
+    /// See [`ApplicationHandler::monitor_removed()`] for details.
+    ///
+    /// [`ApplicationHandler::monitor_removed()`]: crate::application::ApplicationHandler::monitor_removed()
+    MonitorRemoved(MonitorHandle),
+
+    /// See [`ApplicationHandler::color_capabilities_changed()`] for details.
+    ///
+    /// [`ApplicationHandler::color_capabilities_changed()`]: crate::application::ApplicationHandler::color_capabilities_changed()
+    ColorCapabilitiesChanged(MonitorHandle),
+
+    /// See [`ApplicationHandler::monitor_orientation_changed()`] for details.
+    ///
+    /// [`ApplicationHandler::monitor_orientation_changed()`]: crate::application::ApplicationHandler::monitor_orientation_changed()
+    MonitorOrientationChanged(MonitorHandle),
+
+    /// See [`ApplicationHandler::monitor_refresh_rate_changed()`] for details.
+    ///
+    /// [`ApplicationHandler::monitor_refresh_rate_changed()`]: crate::application::ApplicationHandler::monitor_refresh_rate_changed()
+    MonitorRefreshRateChanged(MonitorHandle),
+
+    /// See [`ApplicationHandler::session_locked()`] for details.
+    ///
+    /// [`ApplicationHandler::session_locked()`]: crate::application::ApplicationHandler::session_locked()
+    SessionLocked,
+
+    /// See [`ApplicationHandler::session_unlocked()`] for details.
+    ///
+    /// [`ApplicationHandler::session_unlocked()`]: crate::application::ApplicationHandler::session_unlocked()
+    SessionUnlocked,
+
+    /// See [`ApplicationHandler::clipboard_data()`] for details.
+    ///
+    /// [`ApplicationHandler::clipboard_data()`]: crate::application::ApplicationHandler::clipboard_data()
+    ClipboardData { serial: AsyncRequestSerial, data: Option<ClipboardData> },
+
+    /// See [`ApplicationHandler::clipboard_changed()`] for details.
+    ///
+    /// [`ApplicationHandler::clipboard_changed()`]: crate::application::ApplicationHandler::clipboard_changed()
+    ClipboardChanged,
+
+    /// See [`ApplicationHandler::file_dialog_done()`] for details.
+    ///
+    /// [`ApplicationHandler::file_dialog_done()`]: crate::application::ApplicationHandler::file_dialog_done()
+    #[cfg(feature = "dialogs")]
+    FileDialogDone { serial: AsyncRequestSerial, result: crate::dialogs::FileDialogResult },
+
+    /// See [`ApplicationHandler::message_box_done()`] for details.
+    ///
+    /// [`ApplicationHandler::message_box_done()`]: crate::application::ApplicationHandler::message_box_done()
+    #[cfg(feature = "dialogs")]
+    MessageBoxDone { serial: AsyncRequestSerial, result: crate::dialogs::MessageBoxResult },
+
     /// User requested a wake up.
     UserWakeUp,
 }
@@ -139,6 +269,12 @@ pub enum StartCause {
 }
 
 /// Describes an event from a [`Window`].
+///
+/// New events are added without a breaking change, and the data which is specific to a backend
+/// is available from [`ActiveEventLoop::event_platform_data`].
+///
+/// [`ActiveEventLoop::event_platform_data`]: crate::event_loop::ActiveEventLoop::event_platform_data
+#[non_exhaustive]
 #[derive(Debug, Clone, PartialEq)]
 pub enum WindowEvent {
     /// The activation token was delivered back and now could be used.
@@ -175,6 +311,44 @@ pub enum WindowEvent {
     /// separately.
     DroppedFile(PathBuf),
 
+    /// Data dragged from another application was dropped on the window.
+    ///
+    /// It's delivered along with [`WindowEvent::DroppedFile`] when files are dropped, and carries
+    /// the other formats of the data as well, such as text, URIs, HTML and images. `effect` is the
+    /// one chosen with the last [`WindowEvent::DataHovered`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / Android / Web / Wayland / Orbital:** Unsupported.
+    DataDropped { position: PhysicalPosition<f64>, data: DropData, effect: DragEffect },
+
+    /// Data dragged from another application entered the window, or moved over it.
+    ///
+    /// `formats` are the ones the data can be read in once it's dropped, as with
+    /// [`DropData::formats()`], and `modifiers` the modifiers held, since the window usually
+    /// doesn't have the keyboard focus during the drag. With `effect`, the window chooses what
+    /// dropping the data at `position` would do, which the system shows with the cursor, or
+    /// rejects the drop there. The effect defaults to the one the other application suggests,
+    /// usually following the modifiers, and the choice only applies while the event is handled.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** The modifiers are the ones of the pointer, as reported by the server.
+    /// - **iOS / Android / Web / Wayland / Orbital:** Unsupported.
+    DataHovered {
+        position: PhysicalPosition<f64>,
+        formats: Vec<ClipboardFormat>,
+        modifiers: ModifiersState,
+        effect: DropEffectWriter,
+    },
+
+    /// Data dragged from another application left the window without being dropped.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / Android / Web / Wayland / Orbital:** Unsupported.
+    DataHoverCancelled,
+
     /// A file is being hovered over the window.
     ///
     /// When the user hovers multiple files at once, this event will be emitted for each file
@@ -187,11 +361,66 @@ pub enum WindowEvent {
     /// hovered.
     HoveredFileCancelled,
 
+    /// A drag started with [`ActiveEventLoop::start_drag()`] entered the window.
+    ///
+    /// `source` is the window the drag started from, which can be this window.
+    ///
+    /// [`ActiveEventLoop::start_drag()`]: crate::event_loop::ActiveEventLoop::start_drag()
+    DragEntered { source: WindowId, position: PhysicalPosition<f64> },
+
+    /// The drag which entered the window moved over it.
+    DragMoved { position: PhysicalPosition<f64> },
+
+    /// The drag which entered the window left it, or ended without being dropped on it.
+    DragLeft,
+
+    /// The drag which entered the window was dropped on it.
+    ///
+    /// The window the drag started from receives [`WindowEvent::DragEnded`] afterwards.
+    DragDropped { source: WindowId, position: PhysicalPosition<f64>, data: DragPayload },
+
+    /// The drag started from the window with [`ActiveEventLoop::start_drag()`] moved.
+    ///
+    /// `target` is the application's window under the pointer, or `None` outside of its windows.
+    /// The window can use it to pick the image following the pointer with
+    /// [`ActiveEventLoop::set_drag_image()`].
+    ///
+    /// [`ActiveEventLoop::start_drag()`]: crate::event_loop::ActiveEventLoop::start_drag()
+    /// [`ActiveEventLoop::set_drag_image()`]: crate::event_loop::ActiveEventLoop::set_drag_image()
+    DragSourceMoved { target: Option<WindowId> },
+
+    /// The drag started from the window with [`ActiveEventLoop::start_drag()`] ended.
+    ///
+    /// [`ActiveEventLoop::start_drag()`]: crate::event_loop::ActiveEventLoop::start_drag()
+    DragEnded { outcome: DragOutcome },
+
     /// The window gained or lost focus.
     ///
     /// The parameter is true if the window has gained focus, and false if it has lost focus.
     Focused(bool),
 
+    /// The user pressed a key or a button on the window while it's blocked by a modal dialog.
+    ///
+    /// The input itself isn't delivered. Applications may flash the window or ring the bell, to
+    /// hint at the dialog. See [`Modality`] for the windows blocked by a dialog.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Unsupported, the system plays the alert sound itself.
+    /// - **iOS / Android / Web / Orbital:** Unsupported.
+    ///
+    /// [`Modality`]: crate::dialogs::Modality
+    #[cfg(feature = "dialogs")]
+    ModalInputRejected,
+
+    /// The cursor grab was released or established again by Winit, following the
+    /// [`CursorGrabPolicy`] of the window.
+    ///
+    /// The parameter is the grab mode now in effect.
+    ///
+    /// [`CursorGrabPolicy`]: crate::window::CursorGrabPolicy
+    CursorGrabChanged(CursorGrabMode),
+
     /// An event from the keyboard has been received.
     ///
     /// ## Platform-specific
@@ -215,8 +444,30 @@ pub enum WindowEvent {
     },
 
     /// The keyboard modifiers have changed.
+    ///
+    /// When caused by pressing or releasing a modifier key, the event is delivered after the
+    /// [`KeyboardInput`] event of that key by default. This can be changed with
+    /// [`EventLoopBuilder::with_modifiers_order()`].
+    ///
+    /// [`KeyboardInput`]: Self::KeyboardInput
+    /// [`EventLoopBuilder::with_modifiers_order()`]: crate::event_loop::EventLoopBuilder::with_modifiers_order
     ModifiersChanged(Modifiers),
 
+    /// The user switched to another keyboard layout.
+    ///
+    /// Keys may produce other characters afterwards, so the bindings shown to the user should be
+    /// looked up again.
+    ///
+    /// The event is sent to the window with the keyboard focus.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Emitted in response to `WM_INPUTLANGCHANGE`.
+    /// - **macOS:** Emitted when the selected keyboard input source changes.
+    /// - **Wayland / X11:** Emitted when the keymap or its active layout changes.
+    /// - **iOS / Android / Web / Orbital:** Unsupported.
+    KeyboardLayoutChanged(KeyboardLayout),
+
     /// An event from an input method.
     ///
     /// **Note:** You have to explicitly enable this event using [`Window::set_ime_allowed`].
@@ -226,6 +477,32 @@ pub enum WindowEvent {
     /// - **iOS / Android / Web / Orbital:** Unsupported.
     Ime(Ime),
 
+    /// A dead key or Compose key sequence started, continued or ended.
+    ///
+    /// This allows showing the pending characters, like the accent of a dead key, as text fields
+    /// do. The event is delivered before the [`KeyboardInput`] event of the key.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Unsupported, the dead keys are reported with [`Ime::Preedit`] events.
+    /// - **iOS / Android / Web / Orbital:** Unsupported.
+    ///
+    /// [`KeyboardInput`]: Self::KeyboardInput
+    Compose(Compose),
+
+    /// The on-screen keyboard moved, was resized, shown or hidden.
+    ///
+    /// The area covered by the keyboard is in the coordinates of the surface of the window, and
+    /// its size is zero when the keyboard is hidden.
+    ///
+    /// See [`Window::set_virtual_keyboard_visible`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS:** Delivered when the keyboard frame changes.
+    /// - **Android / Web / Windows / macOS / Wayland / X11 / Orbital:** Unsupported.
+    VirtualKeyboardGeometryChanged { position: PhysicalPosition<i32>, size: PhysicalSize<u32> },
+
     /// The pointer has moved on the window.
     PointerMoved {
         device_id: Option<DeviceId>,
@@ -288,7 +565,39 @@ pub enum WindowEvent {
     },
 
     /// A mouse wheel movement or touchpad scroll occurred.
-    MouseWheel { device_id: Option<DeviceId>, delta: MouseScrollDelta, phase: TouchPhase },
+    ///
+    /// Touchpads and other precise devices report a [`MouseScrollDelta::PixelDelta`], and wrap
+    /// each scroll in a [`TouchPhase::Started`] and a [`TouchPhase::Ended`] event, so that the
+    /// application can start its own kinetic scrolling when the scroll ended. Mouse wheels report
+    /// a [`MouseScrollDelta::LineDelta`] with [`TouchPhase::Moved`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11 / Windows:** The end of a scroll isn't reported by the system, so
+    ///   [`TouchPhase::Ended`] is emitted once the pointer moves again. On X11, touchpads are
+    ///   detected by their name.
+    /// - **Wayland:** The compositor doesn't do kinetic scrolling, so `momentum` is always
+    ///   `false`.
+    /// - **Windows:** The system scrolls kinetically by itself, and its momentum scroll isn't
+    ///   distinguished from the scroll of the fingers, so `momentum` is always `false`. Precise
+    ///   deltas are only recognized once they aren't multiples of a wheel notch.
+    /// - **Web / Orbital:** Always emits [`TouchPhase::Moved`] and `momentum` is always `false`.
+    MouseWheel {
+        device_id: Option<DeviceId>,
+        delta: MouseScrollDelta,
+        phase: TouchPhase,
+
+        /// Whether the scroll is the momentum scroll the system continues with after the fingers
+        /// left the touchpad.
+        ///
+        /// The momentum scroll has its own [`TouchPhase::Started`] and [`TouchPhase::Ended`]
+        /// events, and is cancelled by the system once the user touches the touchpad again.
+        ///
+        /// ## Platform-specific
+        ///
+        /// Only **macOS** reports the momentum scroll.
+        momentum: bool,
+    },
 
     /// An mouse button press has been received.
     PointerButton {
@@ -310,12 +619,54 @@ pub enum WindowEvent {
         button: ButtonSource,
     },
 
+    /// A pen or stylus moved over the window, or its state changed.
+    ///
+    /// It's emitted along with the pointer events of the pen, which keep reporting its position
+    /// and the presses of its tip. This event adds the state which drawing applications need, like
+    /// the pressure and the tilt of the pen.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Requires Windows 8.
+    /// - **X11:** The twist isn't reported.
+    /// - **iOS / Android / Web / Orbital:** Unsupported.
+    PenInput {
+        device_id: Option<DeviceId>,
+
+        /// The position of the pen, relative to the top-left corner of the window.
+        position: PhysicalPosition<f64>,
+
+        /// Whether the tip, or the eraser, touches the surface.
+        contact: bool,
+
+        /// How hard the pen is pressed, from `0.0` to `1.0`.
+        ///
+        /// It's `0.0` while the pen hovers over the surface. Pens without pressure sensitivity
+        /// report `1.0` while they touch it.
+        pressure: f32,
+
+        /// The tilt of the pen, or `None` if the pen doesn't report it.
+        tilt: Option<PenTilt>,
+
+        /// The clockwise rotation of the pen around its own axis, in degrees from `0.0` to
+        /// `360.0`, or `None` if the pen doesn't report it.
+        twist: Option<f32>,
+
+        /// Whether the pen is used with its eraser end.
+        inverted: bool,
+
+        /// The buttons on the barrel of the pen which are held.
+        barrel_buttons: PenButtons,
+    },
+
     /// Two-finger pinch gesture, often used for magnification.
     ///
     /// ## Platform-specific
     ///
-    /// - Only available on **macOS** and **iOS**.
     /// - On iOS, not recognized by default. It must be enabled when needed.
+    /// - **Windows:** Recognized from two fingers on a touchscreen.
+    /// - **X11:** Requires XInput 2.4.
+    /// - **Android / Web / Orbital:** Unsupported.
     PinchGesture {
         device_id: Option<DeviceId>,
         /// Positive values indicate magnification (zooming in) and  negative
@@ -366,8 +717,10 @@ pub enum WindowEvent {
     ///
     /// ## Platform-specific
     ///
-    /// - Only available on **macOS** and **iOS**.
     /// - On iOS, not recognized by default. It must be enabled when needed.
+    /// - **Windows:** Recognized from two fingers on a touchscreen.
+    /// - **X11:** Requires XInput 2.4.
+    /// - **Android / Web / Orbital:** Unsupported.
     RotationGesture {
         device_id: Option<DeviceId>,
         /// change in rotation in degrees
@@ -414,6 +767,34 @@ pub enum WindowEvent {
     /// - **iOS / Android / X11 / Wayland / Orbital:** Unsupported.
     ThemeChanged(Theme),
 
+    /// The accent color of the system has changed.
+    ///
+    /// See [`ActiveEventLoop::system_accent_color`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / Android / Web / X11 / Wayland / Orbital:** Unsupported.
+    ///
+    /// [`ActiveEventLoop::system_accent_color`]: crate::event_loop::ActiveEventLoop::system_accent_color
+    AccentColorChanged(Rgba),
+
+    /// The color profile of the monitor the window is on has changed, or the window has moved to a
+    /// monitor with a different color profile.
+    ///
+    /// Color-managed applications should query [`MonitorHandle::icc_profile`] of the
+    /// [current monitor] again and transform their output accordingly.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** The profile is checked again when the display settings change.
+    /// - **X11:** Only reported when the profile of a RandR output changes, not when the window
+    ///   moves to another monitor.
+    /// - **iOS / Android / Web / Wayland / Orbital:** Unsupported.
+    ///
+    /// [`MonitorHandle::icc_profile`]: crate::monitor::MonitorHandle::icc_profile
+    /// [current monitor]: crate::window::Window::current_monitor
+    ColorProfileChanged,
+
     /// The window has been occluded (completely hidden from view).
     ///
     /// This is different to window visibility as it depends on whether the window is closed,
@@ -443,6 +824,13 @@ pub enum WindowEvent {
     /// [`transform`]: https://developer.mozilla.org/en-US/docs/Web/CSS/transform
     Occluded(bool),
 
+    /// An assistive technology requested an action on an element of the accessibility tree, like
+    /// focusing or clicking it.
+    ///
+    /// Only delivered to the windows given a source with [`Window::set_accesskit_tree_source`].
+    #[cfg(feature = "accesskit")]
+    AccessKitActionRequested(accesskit::ActionRequest),
+
     /// Emitted when a window should be redrawn.
     ///
     /// This gets triggered in two scenarios:
@@ -497,9 +885,12 @@ pub enum PointerSource {
     /// touch, such as when the window loses focus, or on mobile devices if the user moves the
     /// device against their face.
     ///
-    /// The [`FingerId`] may be reused by the system after a [`WindowEvent::PointerLeft`] event.
-    /// The user should assume that a new [`WindowEvent::PointerEntered`] event received with the
-    /// same ID has nothing to do with the old finger and is a new finger.
+    /// The [`FingerId`] is stable for the lifetime of the contact: it's the same in every event
+    /// from the [`WindowEvent::PointerEntered`] to the [`WindowEvent::PointerLeft`] of the
+    /// finger, and no other finger touching the screen at the same time has it. It may be reused
+    /// by the system after a [`WindowEvent::PointerLeft`] event, so the user should assume that a
+    /// new [`WindowEvent::PointerEntered`] event received with the same ID has nothing to do with
+    /// the old finger and is a new finger.
     ///
     /// ## Platform-specific
     ///
@@ -512,13 +903,26 @@ pub enum PointerSource {
         ///
         /// ## Platform-specific
         ///
-        /// - **MacOS / Orbital / Wayland / X11:** Always emits [`None`].
+        /// - **MacOS / Orbital / Wayland:** Always emits [`None`].
+        /// - **X11:** Only emitted by the devices with a pressure valuator.
         /// - **Android:** Will never be [`None`]. If the device doesn't support pressure
         ///   sensitivity, force will either be 0.0 or 1.0. Also see the
         ///   [android documentation](https://developer.android.com/reference/android/view/MotionEvent#AXIS_PRESSURE).#[derive(Debug, Clone, Copy, PartialEq)]
         /// - **Web:** Will never be [`None`]. If the device doesn't support pressure sensitivity,
         ///   force will be 0.5 when a button is pressed or 0.0 otherwise.
         force: Option<Force>,
+
+        /// The area of the screen touched by the finger. May be [`None`] if the hardware doesn't
+        /// report it.
+        ///
+        /// ## Platform-specific
+        ///
+        /// - **macOS / Orbital:** Always emits [`None`].
+        /// - **X11:** Only emitted by the devices with touch major valuators.
+        /// - **iOS:** The contact is a circle.
+        /// - **Windows:** The contact is the bounding box of the area, its orientation is always
+        ///   `0.0`.
+        contact: Option<TouchContact>,
     },
     Unknown,
 }
@@ -533,6 +937,32 @@ impl From<PointerSource> for PointerKind {
     }
 }
 
+/// The area of the screen touched by a finger, reported by [`PointerSource::Touch`] and
+/// [`ButtonSource::Touch`].
+///
+/// The area is an ellipse centered on the position of the event.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TouchContact {
+    /// The length of the major axis of the ellipse, in physical pixels.
+    pub major: f64,
+
+    /// The length of the minor axis of the ellipse, in physical pixels.
+    ///
+    /// The same as `major` when the system only reports the size of the contact.
+    pub minor: f64,
+
+    /// The angle of the major axis from the vertical axis of the window, in radians clockwise.
+    pub orientation: f64,
+}
+
+impl TouchContact {
+    /// The contact of a finger whose major axis is vertical.
+    #[allow(dead_code)]
+    pub(crate) fn new(major: f64, minor: f64) -> Self {
+        Self { major, minor, orientation: 0.0 }
+    }
+}
+
 /// Represents the pointer type of a [`WindowEvent::PointerButton`].
 ///
 /// **Wayland/X11:** [`Unknown`](Self::Unknown) device types are converted to known variants by the
@@ -548,6 +978,7 @@ pub enum ButtonSource {
     Touch {
         finger_id: FingerId,
         force: Option<Force>,
+        contact: Option<TouchContact>,
     },
     Unknown(u16),
 }
@@ -605,6 +1036,43 @@ impl DeviceId {
     }
 }
 
+/// Information about an input device, returned by [`ActiveEventLoop::input_devices()`].
+///
+/// [`ActiveEventLoop::input_devices()`]: crate::event_loop::ActiveEventLoop::input_devices()
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DeviceInfo {
+    /// The identifier of the device, the same as in the events it emits.
+    pub id: DeviceId,
+
+    /// What kind of device it is.
+    pub kind: DeviceKind,
+
+    /// The name of the device, like `Logitech USB Receiver`.
+    pub name: Option<String>,
+
+    /// The USB vendor ID of the device.
+    pub vendor_id: Option<u16>,
+
+    /// The USB product ID of the device.
+    pub product_id: Option<u16>,
+}
+
+/// The kind of an input device.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DeviceKind {
+    Mouse,
+    Keyboard,
+    Touchpad,
+    Touchscreen,
+    /// A pen or stylus, including the drawing tablets.
+    Pen,
+    Gamepad,
+    /// A device of another kind, or whose kind isn't known.
+    Other,
+}
+
 /// Identifier of a finger in a touch event.
 ///
 /// Whenever a touch event is received it contains a `FingerId` which uniquely identifies the finger
@@ -627,6 +1095,7 @@ impl FingerId {
 /// (corresponding to GUI pointers and keyboard focus) the device IDs may not match.
 ///
 /// Note that these events are delivered regardless of input focus.
+#[non_exhaustive]
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum DeviceEvent {
     /// Change in physical position of a pointing device.
@@ -667,6 +1136,10 @@ pub enum DeviceEvent {
     },
 
     Key(RawKeyEvent),
+
+    /// Input of a gamepad, see the [`gamepad`][crate::gamepad] module.
+    #[cfg(feature = "gamepad")]
+    Gamepad(crate::gamepad::GamepadEvent),
 }
 
 /// Describes a keyboard input as a raw device event.
@@ -959,6 +1432,35 @@ pub enum Ime {
     /// Right before this event winit will send empty [`Self::Preedit`] event.
     Commit(String),
 
+    /// Notifies when the IME needs the text around the cursor.
+    ///
+    /// The text should be provided with [`Window::set_ime_surrounding_text`], and allows the IME
+    /// to reconvert the selected text and to predict from the context. This is requested after
+    /// [`Enabled`][Self::Enabled] and [`Commit`][Self::Commit].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** Requires the `text-input-v3` protocol.
+    /// - **iOS / Android / Web / X11 / Orbital:** Unsupported.
+    SurroundingTextRequest,
+
+    /// Notifies when the candidates of the IME changed, for the applications drawing them.
+    ///
+    /// Only delivered after [`Window::set_ime_candidates_drawn_by_app`]. The candidates are empty
+    /// when the candidate window is closed.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS / Wayland / X11 / iOS / Android / Web / Orbital:** Unsupported.
+    Candidates {
+        /// All the candidates of the IME.
+        candidates: Vec<String>,
+        /// The index of the candidate the IME selected.
+        selected: Option<usize>,
+        /// The range of the candidates the IME shows on its current page.
+        page: Range<usize>,
+    },
+
     /// Notifies when the IME was disabled.
     ///
     /// After receiving this event you won't get any more [`Preedit`][Self::Preedit] or
@@ -968,6 +1470,33 @@ pub enum Ime {
     Disabled,
 }
 
+/// The state of a dead key or Compose key sequence, see [`WindowEvent::Compose`].
+///
+/// Pressing the dead key of the acute accent and then the `E` key gives:
+///
+/// ```ignore
+/// // Press the dead key.
+/// Compose::Started("´")
+/// // Press "E" key.
+/// Compose::Ended(Some("é"))
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Compose {
+    /// A sequence started, with the pending characters.
+    ///
+    /// The pending characters are empty when the sequence started with the Compose key.
+    Started(String),
+
+    /// A key continued the sequence, with all the pending characters.
+    Updated(String),
+
+    /// The sequence ended, with the composed text, or `None` when it was cancelled.
+    ///
+    /// The composed text is also the text of the [`KeyEvent`] ending the sequence.
+    Ended(Option<String>),
+}
+
 /// Describes touch-screen input state.
 #[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -1020,6 +1549,33 @@ impl Force {
     }
 }
 
+/// The tilt of a pen, reported by [`WindowEvent::PenInput`].
+///
+/// A pen perpendicular to the surface has no tilt.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PenTilt {
+    /// The angle between the pen and the vertical plane along the Y axis, in degrees from `-90.0`
+    /// to `90.0`. It's positive when the pen leans to the right.
+    pub x: f32,
+
+    /// The angle between the pen and the vertical plane along the X axis, in degrees from `-90.0`
+    /// to `90.0`. It's positive when the pen leans towards the bottom of the screen.
+    pub y: f32,
+}
+
+bitflags::bitflags! {
+    /// The buttons on the barrel of a pen, reported by [`WindowEvent::PenInput`].
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub struct PenButtons: u8 {
+        /// The button closest to the tip, which usually acts as a right click.
+        const PRIMARY = 1 << 0;
+        /// The second button, on pens which have two.
+        const SECONDARY = 1 << 1;
+    }
+}
+
 /// Identifier for a specific analog axis on some device.
 pub type AxisId = u32;
 
@@ -1085,6 +1641,133 @@ pub enum MouseScrollDelta {
     PixelDelta(PhysicalPosition<f64>),
 }
 
+/// Describes a change in the power state of the system.
+///
+/// See [`ApplicationHandler::power_event()`] for details.
+///
+/// [`ApplicationHandler::power_event()`]: crate::application::ApplicationHandler::power_event()
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PowerEvent {
+    /// The system is about to sleep.
+    ///
+    /// The application should stop its work and save its state, as it might never resume.
+    Suspending,
+
+    /// The system woke up from sleep.
+    Resumed,
+
+    /// The power source or the battery state changed.
+    PowerStateChanged(PowerState),
+}
+
+/// The power state of the system, as returned by [`ActiveEventLoop::power_state()`].
+///
+/// [`ActiveEventLoop::power_state()`]: crate::event_loop::ActiveEventLoop::power_state()
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PowerState {
+    /// The source the system is currently drawing power from.
+    pub source: PowerSource,
+
+    /// Whether the battery is charging.
+    pub charging: bool,
+
+    /// The remaining charge of the battery in percent, if the system has a battery.
+    pub battery_level: Option<u8>,
+}
+
+/// The source the system is drawing power from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PowerSource {
+    /// The system is plugged in to an external power supply.
+    External,
+
+    /// The system is running on battery.
+    Battery,
+}
+
+/// The accessibility preferences of the user, as returned by
+/// [`ActiveEventLoop::system_preferences()`].
+///
+/// Applications should honor them live: stop the animations when the motion should be reduced,
+/// and switch to an opaque, high contrast palette when requested.
+///
+/// [`ActiveEventLoop::system_preferences()`]: crate::event_loop::ActiveEventLoop::system_preferences()
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SystemPreferences {
+    /// Whether the user asked to reduce the motion of the interface, like the animations.
+    pub reduced_motion: bool,
+
+    /// Whether the user asked for a high contrast, or forced the colors of the interface.
+    pub high_contrast: bool,
+
+    /// Whether the user asked to replace the transparent and blurred surfaces with opaque ones.
+    pub reduced_transparency: bool,
+}
+
+/// A [BCP 47] language tag identifying the locale of the user, like `en-US` or `zh-Hant-TW`.
+///
+/// Returned by [`ActiveEventLoop::locale()`].
+///
+/// [BCP 47]: https://www.rfc-editor.org/info/bcp47
+/// [`ActiveEventLoop::locale()`]: crate::event_loop::ActiveEventLoop::locale()
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LanguageTag(String);
+
+impl LanguageTag {
+    /// Creates a tag from its string representation, which isn't validated.
+    pub fn new(tag: impl Into<String>) -> Self {
+        Self(tag.into())
+    }
+
+    /// The tag of an undetermined language, `und`.
+    pub fn undetermined() -> Self {
+        Self::new("und")
+    }
+
+    /// The tag as a string, like `en-US`.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The primary language subtag, like `en` for `en-US`.
+    pub fn language(&self) -> &str {
+        self.0.split('-').next().unwrap_or_default()
+    }
+
+    /// Converts a POSIX locale like `en_US.UTF-8@euro` or a `en_US` locale identifier.
+    ///
+    /// Returns `None` for the `C` and `POSIX` locales, which don't name a language.
+    #[cfg(any(x11_platform, wayland_platform, orbital_platform, macos_platform, ios_platform))]
+    pub(crate) fn from_posix(locale: &str) -> Option<Self> {
+        let locale = locale.split(['.', '@']).next().unwrap_or_default();
+        match locale {
+            "" | "C" | "POSIX" => None,
+            locale => Some(Self::new(locale.replace('_', "-"))),
+        }
+    }
+
+    /// The locale of the messages, from the `LC_ALL`, `LC_MESSAGES` and `LANG` variables.
+    #[cfg(any(x11_platform, wayland_platform, orbital_platform))]
+    pub(crate) fn from_env() -> Self {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .into_iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|locale| !locale.is_empty())
+            .and_then(|locale| Self::from_posix(&locale))
+            .unwrap_or_else(Self::undetermined)
+    }
+}
+
+impl fmt::Display for LanguageTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
 /// Handle to synchronously change the size of the window from the [`WindowEvent`].
 #[derive(Debug, Clone)]
 pub struct SurfaceSizeWriter {
@@ -1119,6 +1802,38 @@ impl PartialEq for SurfaceSizeWriter {
 
 impl Eq for SurfaceSizeWriter {}
 
+/// Data of the event being delivered which is specific to the backend.
+///
+/// It exposes what the system reported along with the event, without new fields in the events
+/// themselves. See [`ActiveEventLoop::event_platform_data`], and downcast it to the type of the
+/// backend:
+///
+/// - **Wayland:** [`EventData`][crate::platform::wayland::EventData].
+/// - **Windows:** [`MessageData`][crate::platform::windows::MessageData].
+///
+/// [`ActiveEventLoop::event_platform_data`]: crate::event_loop::ActiveEventLoop::event_platform_data
+#[cfg_attr(not(docsrs), allow(rustdoc::broken_intra_doc_links))]
+#[derive(Clone)]
+pub struct PlatformData(Arc<dyn Any + Send + Sync>);
+
+impl PlatformData {
+    #[cfg(any(wayland_platform, windows_platform))]
+    pub(crate) fn new<T: Any + Send + Sync>(data: T) -> Self {
+        Self(Arc::new(data))
+    }
+
+    /// Returns the data if it's of the type `T`.
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.0.downcast_ref()
+    }
+}
+
+impl fmt::Debug for PlatformData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PlatformData").finish_non_exhaustive()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::{BTreeSet, HashSet};
@@ -1157,15 +1872,48 @@ mod tests {
                 with_window_event(Moved((0, 0).into()));
                 with_window_event(SurfaceResized((0, 0).into()));
                 with_window_event(DroppedFile("x.txt".into()));
+                with_window_event(DataDropped {
+                    position: (0, 0).into(),
+                    data: crate::drag::DropData::new(Vec::new(), |_| None),
+                    effect: crate::drag::DragEffect::Copy,
+                });
+                crate::drag::DropEffectWriter::negotiate(None, |effect| {
+                    with_window_event(DataHovered {
+                        position: (0, 0).into(),
+                        formats: Vec::new(),
+                        modifiers: crate::keyboard::ModifiersState::empty(),
+                        effect,
+                    })
+                });
+                with_window_event(DataHoverCancelled);
                 with_window_event(HoveredFile("x.txt".into()));
                 with_window_event(HoveredFileCancelled);
+                with_window_event(DragEntered { source: wid, position: (0, 0).into() });
+                with_window_event(DragMoved { position: (0, 0).into() });
+                with_window_event(DragLeft);
+                with_window_event(DragDropped {
+                    source: wid,
+                    position: (0, 0).into(),
+                    data: crate::drag::DragPayload::new(crate::drag::DragData::Text("x".into())),
+                });
+                with_window_event(DragSourceMoved { target: None });
+                with_window_event(DragEnded { outcome: crate::drag::DragOutcome::Cancelled });
                 with_window_event(Ime(Enabled));
+                with_window_event(Compose(event::Compose::Started("´".into())));
+                with_window_event(VirtualKeyboardGeometryChanged {
+                    position: (0, 0).into(),
+                    size: (0, 0).into(),
+                });
                 with_window_event(PointerMoved {
                     device_id: None,
                     position: (0, 0).into(),
                     source: PointerSource::Mouse,
                 });
                 with_window_event(ModifiersChanged(event::Modifiers::default()));
+                with_window_event(KeyboardLayoutChanged(crate::keyboard::KeyboardLayout {
+                    id: "00000409".into(),
+                    name: "English (United States)".into(),
+                }));
                 with_window_event(PointerEntered {
                     device_id: None,
                     position: (0, 0).into(),
@@ -1180,6 +1928,7 @@ mod tests {
                     device_id: None,
                     delta: event::MouseScrollDelta::LineDelta(0.0, 0.0),
                     phase: event::TouchPhase::Started,
+                    momentum: false,
                 });
                 with_window_event(PointerButton {
                     device_id: None,
@@ -1194,6 +1943,7 @@ mod tests {
                     button: event::ButtonSource::Touch {
                         finger_id: fid,
                         force: Some(event::Force::Normalized(0.0)),
+                        contact: Some(event::TouchContact::new(1.0, 1.0)),
                     },
                 });
                 with_window_event(PinchGesture {
@@ -1214,7 +1964,15 @@ mod tests {
                 });
                 with_window_event(TouchpadPressure { device_id: None, pressure: 0.0, stage: 0 });
                 with_window_event(ThemeChanged(crate::window::Theme::Light));
+                with_window_event(AccentColorChanged(crate::window::Rgba::new(0, 0, 0, 255)));
+                with_window_event(ColorProfileChanged);
                 with_window_event(Occluded(true));
+                #[cfg(feature = "accesskit")]
+                with_window_event(AccessKitActionRequested(accesskit::ActionRequest {
+                    action: accesskit::Action::Click,
+                    target: accesskit::NodeId(0),
+                    data: None,
+                }));
             }
 
             #[allow(deprecated)]
@@ -1276,4 +2034,16 @@ mod tests {
 
         let _ = event::Force::Calibrated { force: 0.0, max_possible_force: 0.0 }.clone();
     }
+
+    #[test]
+    #[cfg(any(x11_platform, wayland_platform, orbital_platform, macos_platform, ios_platform))]
+    fn language_tag_from_posix() {
+        let tag = |locale| event::LanguageTag::from_posix(locale).map(|tag| tag.to_string());
+        assert_eq!(tag("en_US.UTF-8"), Some("en-US".into()));
+        assert_eq!(tag("de_DE@euro"), Some("de-DE".into()));
+        assert_eq!(tag("fr"), Some("fr".into()));
+        assert_eq!(tag("C.UTF-8"), None);
+        assert_eq!(tag("POSIX"), None);
+        assert_eq!(event::LanguageTag::new("zh-Hant-TW").language(), "zh");
+    }
 }
@@ -8,23 +8,42 @@
 //!
 //! See the root-level documentation for information on how to create and use an event loop to
 //! handle events.
-use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::marker::PhantomData;
 #[cfg(any(x11_platform, wayland_platform))]
 use std::os::unix::io::{AsFd, AsRawFd, BorrowedFd, RawFd};
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
 #[cfg(not(web_platform))]
 use std::time::{Duration, Instant};
+use std::{fmt, panic, thread};
 
+use bitflags::bitflags;
 #[cfg(web_platform)]
 use web_time::{Duration, Instant};
 
 use crate::application::ApplicationHandler;
+use crate::clipboard::Clipboard;
+#[cfg(feature = "dialogs")]
+use crate::dialogs::{FileDialogOptions, MessageBoxConfig};
+use crate::dpi::{PhysicalPosition, PhysicalSize};
+use crate::drag::DragData;
+#[cfg(feature = "winit-test-harness")]
+use crate::error::NotSupportedError;
 use crate::error::{EventLoopError, RequestError};
-use crate::monitor::MonitorHandle;
+#[cfg(feature = "winit-test-harness")]
+use crate::event::DeviceEvent;
+#[cfg(any(feature = "gamepad", feature = "winit-test-harness"))]
+use crate::event::DeviceId;
+use crate::event::{DeviceInfo, LanguageTag, PlatformData, PowerState, SystemPreferences};
+use crate::keyboard::{Key, KeyCode, KeyboardLayout, Led};
+use crate::monitor::{self, MonitorHandle};
 use crate::platform_impl;
 use crate::utils::AsAny;
-use crate::window::{CustomCursor, CustomCursorSource, Theme, Window, WindowAttributes};
+use crate::window::{
+    Cursor, CursorThemeInfo, CustomCursor, CustomCursorSource, Rgba, Theme, Window,
+    WindowAttributes, WindowId,
+};
 
 /// Provides a way to retrieve events from the system and from the windows that were registered to
 /// the events loop.
@@ -55,6 +74,7 @@ pub struct EventLoop {
 #[derive(Default, PartialEq, Eq, Hash)]
 pub struct EventLoopBuilder {
     pub(crate) platform_specific: platform_impl::PlatformSpecificEventLoopAttributes,
+    pub(crate) panic_handler: Option<PanicHandler>,
 }
 
 static EVENT_LOOP_CREATED: AtomicBool = AtomicBool::new(false);
@@ -103,10 +123,65 @@ impl EventLoopBuilder {
 
         // Certain platforms accept a mutable reference in their API.
         #[allow(clippy::unnecessary_mut_passed)]
-        Ok(EventLoop {
-            event_loop: platform_impl::EventLoop::new(&mut self.platform_specific)?,
-            _marker: PhantomData,
-        })
+        let event_loop = platform_impl::EventLoop::new(&mut self.platform_specific)?;
+
+        if let Some(handler) = self.panic_handler.clone() {
+            handler.install();
+        }
+
+        Ok(EventLoop { event_loop, _marker: PhantomData })
+    }
+
+    /// Set the order of the [`ModifiersChanged`] events relative to the [`KeyboardInput`] events
+    /// of the modifier keys.
+    ///
+    /// The default is [`ModifiersOrder::AfterKeyboardInput`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / Android:** Unsupported, `ModifiersChanged` isn't emitted.
+    ///
+    /// [`ModifiersChanged`]: crate::event::WindowEvent::ModifiersChanged
+    /// [`KeyboardInput`]: crate::event::WindowEvent::KeyboardInput
+    #[inline]
+    pub fn with_modifiers_order(&mut self, order: ModifiersOrder) -> &mut Self {
+        #[cfg(not(any(ios_platform, android_platform)))]
+        {
+            self.platform_specific.modifiers_order = order;
+        }
+        #[cfg(any(ios_platform, android_platform))]
+        let _ = order;
+        self
+    }
+
+    /// Set a handler that is called when the thread running the event loop panics.
+    ///
+    /// Before the handler is called, the state the application changed outside of its windows
+    /// is restored: the video mode of exclusive fullscreen windows, and the grabs and
+    /// visibility of the cursor. The handler receives the message of the panic, and returns
+    /// whether to show it in a native error dialog. The previous panic hook runs afterwards,
+    /// and the panic continues unwinding.
+    ///
+    /// The handler is installed with [`std::panic::set_hook`] when the event loop is built,
+    /// panics of other threads are passed directly to the previous hook.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Only the grabs of the pointer and keyboard are released, the video modes aren't
+    ///   restored. The dialog is shown with `zenity` or `kdialog` when installed, and isn't
+    ///   attached to any window.
+    /// - **Wayland:** Nothing to restore, the compositor releases the grabs of the application. The
+    ///   dialog is shown with `zenity` or `kdialog` when installed, and isn't attached to any
+    ///   window.
+    /// - **Web:** The pointer lock and fullscreen are exited, and the dialog is an alert.
+    /// - **iOS / Android / Orbital:** Nothing is restored, and the dialog isn't shown.
+    #[inline]
+    pub fn with_panic_handler<F>(&mut self, handler: F) -> &mut Self
+    where
+        F: Fn(&str) -> PanicAction + Send + Sync + 'static,
+    {
+        self.panic_handler = Some(PanicHandler(Arc::new(handler)));
+        self
     }
 
     #[cfg(web_platform)]
@@ -121,6 +196,66 @@ impl fmt::Debug for EventLoopBuilder {
     }
 }
 
+/// What to do after the handler set with [`EventLoopBuilder::with_panic_handler`] returns.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PanicAction {
+    /// Continue with the previous panic hook.
+    #[default]
+    Continue,
+
+    /// Show the message of the panic in a native error dialog, and continue with the previous
+    /// panic hook once the user closed it.
+    ShowDialog,
+}
+
+#[derive(Clone)]
+pub(crate) struct PanicHandler(Arc<dyn Fn(&str) -> PanicAction + Send + Sync>);
+
+impl PanicHandler {
+    fn install(self) {
+        let event_loop_thread = thread::current().id();
+        let previous_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if thread::current().id() == event_loop_thread {
+                platform_impl::restore_after_panic();
+
+                let payload = info.payload();
+                let payload = match payload.downcast_ref::<&str>() {
+                    Some(message) => *message,
+                    None => match payload.downcast_ref::<String>() {
+                        Some(message) => message.as_str(),
+                        None => "Box<dyn Any>",
+                    },
+                };
+                let message = match info.location() {
+                    Some(location) => format!("panicked at {location}:\n{payload}"),
+                    None => payload.to_owned(),
+                };
+
+                if (self.0)(&message) == PanicAction::ShowDialog {
+                    platform_impl::show_panic_dialog(&message);
+                }
+            }
+
+            previous_hook(info);
+        }));
+    }
+}
+
+impl PartialEq for PanicHandler {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for PanicHandler {}
+
+impl Hash for PanicHandler {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (Arc::as_ptr(&self.0) as *const ()).hash(state);
+    }
+}
+
 impl fmt::Debug for EventLoop {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("EventLoop").finish_non_exhaustive()
@@ -188,7 +323,7 @@ impl EventLoop {
     /// To get the actual event loop, call [`build`][EventLoopBuilder::build] on that.
     #[inline]
     pub fn builder() -> EventLoopBuilder {
-        EventLoopBuilder { platform_specific: Default::default() }
+        EventLoopBuilder { platform_specific: Default::default(), panic_handler: None }
     }
 }
 
@@ -255,6 +390,20 @@ impl EventLoop {
         self.event_loop.window_target().listen_device_events(allowed)
     }
 
+    /// Change from which classes of devices [`DeviceEvent`]s are captured.
+    ///
+    /// See [`ActiveEventLoop::listen_device_classes`] for details.
+    ///
+    /// [`DeviceEvent`]: crate::event::DeviceEvent
+    pub fn listen_device_classes(&self, classes: DeviceClass) {
+        let _span = tracing::debug_span!(
+            "winit::EventLoop::listen_device_classes",
+            classes = ?classes
+        )
+        .entered();
+        self.event_loop.window_target().listen_device_classes(classes)
+    }
+
     /// Sets the [`ControlFlow`].
     pub fn set_control_flow(&self, control_flow: ControlFlow) {
         self.event_loop.window_target().set_control_flow(control_flow);
@@ -363,6 +512,25 @@ pub trait ActiveEventLoop: AsAny {
     #[cfg_attr(not(any(web_platform, docsrs)), doc = "  detailed monitor permissions.")]
     fn primary_monitor(&self) -> Option<MonitorHandle>;
 
+    /// Returns the monitor containing `position`, in desktop coordinates.
+    ///
+    /// Returns `None` if no known monitor contains the point.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** Only finds the current monitor without
+    #[cfg_attr(
+        any(web_platform, docsrs),
+        doc = "  [detailed monitor permissions][crate::platform::web::ActiveEventLoopExtWeb::request_detailed_monitor_permission]."
+    )]
+    #[cfg_attr(not(any(web_platform, docsrs)), doc = "  detailed monitor permissions.")]
+    fn monitor_at(&self, position: PhysicalPosition<i32>) -> Option<MonitorHandle> {
+        let point = (position, PhysicalSize::new(1, 1));
+        self.available_monitors().find(|monitor| {
+            monitor.bounds().is_some_and(|bounds| monitor::intersection_area(bounds, point) != 0)
+        })
+    }
+
     /// Change if or when [`DeviceEvent`]s are captured.
     ///
     /// Since the [`DeviceEvent`] capture can lead to high CPU usage for unfocused windows, winit
@@ -376,6 +544,193 @@ pub trait ActiveEventLoop: AsAny {
     /// [`DeviceEvent`]: crate::event::DeviceEvent
     fn listen_device_events(&self, allowed: DeviceEvents);
 
+    /// Change from which classes of devices [`DeviceEvent`]s are captured, all of them by
+    /// default.
+    ///
+    /// This narrows down [`ActiveEventLoop::listen_device_events`], so for example a game can
+    /// receive the raw motion of the mouse without capturing the keyboard globally. Where
+    /// possible, the events of the other classes aren't even requested from the system.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland / macOS / iOS / Android / Orbital:** Unsupported.
+    ///
+    /// [`DeviceEvent`]: crate::event::DeviceEvent
+    fn listen_device_classes(&self, classes: DeviceClass);
+
+    /// Returns the input devices connected to the system.
+    ///
+    /// [`ApplicationHandler::input_device_added()`] and
+    /// [`ApplicationHandler::input_device_removed()`] are emitted when devices are connected and
+    /// disconnected.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** The names are read from the HID devices, so the ones of other devices are
+    ///   `None`.
+    /// - **X11:** Includes the virtual core pointer and keyboard, which aggregate the input of the
+    ///   other devices. The vendor and product IDs are only known for the devices handled by the
+    ///   `libinput` and `evdev` drivers.
+    /// - **Wayland / Web:** Only returns the gamepads, with the `gamepad` cargo feature.
+    /// - **macOS / iOS / Android / Orbital:** Unsupported, always returns an empty list.
+    ///
+    /// [`ApplicationHandler::input_device_added()`]: crate::application::ApplicationHandler::input_device_added()
+    /// [`ApplicationHandler::input_device_removed()`]: crate::application::ApplicationHandler::input_device_removed()
+    fn input_devices(&self) -> Vec<DeviceInfo>;
+
+    /// Make a gamepad vibrate, or stop it when both magnitudes are `0.0`.
+    ///
+    /// The magnitudes of the strong, low frequency, and of the weak, high frequency motors range
+    /// from `0.0` to `1.0`. The gamepad vibrates until this is called again.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland / X11:** Requires the permission to write to the `evdev` device of the gamepad.
+    /// - **Web:** Requires the support of the `vibrationActuator` of the gamepad, which then
+    ///   vibrates for at most five seconds.
+    /// - **macOS / iOS / Android / Orbital:** Unsupported.
+    ///
+    /// See the [`gamepad`][crate::gamepad] module for the supported gamepads.
+    #[cfg(feature = "gamepad")]
+    fn set_gamepad_rumble(
+        &self,
+        device_id: DeviceId,
+        strong: f32,
+        weak: f32,
+    ) -> Result<(), RequestError>;
+
+    /// Returns the identifiers of the windows of the application which are still open.
+    ///
+    /// This lets libraries, like debug overlays and accessibility adapters, enumerate the windows
+    /// without the application keeping track of them.
+    ///
+    /// There is no `window(WindowId) -> Option<&dyn Window>` counterpart: the application owns its
+    /// windows as `Box<dyn Window>`, and the backends only keep weak or internal references to
+    /// them, so the event loop has no window it could lend out.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Orbital:** Unsupported, always returns an empty list.
+    fn windows(&self) -> Vec<WindowId>;
+
+    /// Run a native modal dialog, such as a file picker or a message box created by another crate.
+    ///
+    /// While `f` is running, winit releases the cursor grabs held by its windows and stops
+    /// capturing [`DeviceEvent`]s, so that the dialog receives input like any other window.
+    /// Everything is restored once `f` returns.
+    ///
+    /// ```no_run
+    /// # use winit::event_loop::ActiveEventLoop;
+    /// # fn open_file_dialog() -> Option<std::path::PathBuf> { None }
+    /// # fn scope(event_loop: &dyn ActiveEventLoop) {
+    /// let mut path = None;
+    /// event_loop.with_native_modal_scope(&mut || path = open_file_dialog());
+    /// # }
+    /// ```
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** With [`CursorGrabPolicy::ReGrabOnFocus`], the cursor is only locked again once
+    ///   the window is focused.
+    /// - **iOS / Android / Web / Orbital:** `f` is called directly.
+    ///
+    /// [`DeviceEvent`]: crate::event::DeviceEvent
+    /// [`CursorGrabPolicy::ReGrabOnFocus`]: crate::window::CursorGrabPolicy::ReGrabOnFocus
+    fn with_native_modal_scope(&self, f: &mut dyn FnMut());
+
+    /// Start dragging `data` from the window `source`.
+    ///
+    /// This should be called while a mouse button is held over the window, and the drag follows
+    /// the pointer until the button is released. See the [`drag`] module for the events
+    /// describing the drag.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Files and text are handed over to OLE drag and drop when the pointer leaves
+    ///   the application's windows, custom data stays within the application.
+    /// - **macOS / X11:** The data stays within the application, releasing it outside of its
+    ///   windows cancels the drag.
+    /// - **iOS / Android / Web / Orbital / Wayland:** Unsupported.
+    ///
+    /// [`drag`]: crate::drag
+    fn start_drag(&self, source: WindowId, data: DragData) -> Result<(), RequestError>;
+
+    /// Show `image` instead of the cursor while the drag started with [`start_drag()`] is in
+    /// progress, or the cursor of the source window again with `None`.
+    ///
+    /// The image is a [`Cursor`], so it can be a preview of the data made with
+    /// [`create_custom_cursor()`], or a named icon. The cursor is restored when the drag ends.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** The image isn't shown once the drag was handed over to OLE drag and drop.
+    /// - **iOS / Android / Web / Orbital / Wayland:** Unsupported.
+    ///
+    /// [`start_drag()`]: Self::start_drag()
+    /// [`create_custom_cursor()`]: Self::create_custom_cursor()
+    fn set_drag_image(&self, image: Option<Cursor>) -> Result<(), RequestError>;
+
+    /// Returns the clipboard of the system.
+    ///
+    /// See the [`clipboard`] module for details.
+    ///
+    /// [`clipboard`]: crate::clipboard
+    fn clipboard(&self) -> &dyn Clipboard;
+
+    /// Show a native dialog to pick a file to open.
+    ///
+    /// The dialog is shown asynchronously, and the choice of the user is delivered to
+    /// [`ApplicationHandler::file_dialog_done()`] with the returned serial.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Uses `IFileOpenDialog`.
+    /// - **macOS:** Uses `NSOpenPanel`.
+    /// - **Wayland / X11:** Uses the `FileChooser` portal of `xdg-desktop-portal`.
+    /// - **iOS / Android / Web / Orbital:** Unsupported.
+    ///
+    /// [`ApplicationHandler::file_dialog_done()`]: crate::application::ApplicationHandler::file_dialog_done()
+    #[cfg(feature = "dialogs")]
+    fn pick_file(&self, options: FileDialogOptions) -> Result<AsyncRequestSerial, RequestError>;
+
+    /// Show a native dialog to pick a folder.
+    ///
+    /// See [`pick_file()`] for details.
+    ///
+    /// [`pick_file()`]: Self::pick_file()
+    #[cfg(feature = "dialogs")]
+    fn pick_folder(&self, options: FileDialogOptions) -> Result<AsyncRequestSerial, RequestError>;
+
+    /// Show a native dialog to choose where to save a file.
+    ///
+    /// The dialog asks the user to confirm overwriting an existing file. See [`pick_file()`] for
+    /// details.
+    ///
+    /// [`pick_file()`]: Self::pick_file()
+    #[cfg(feature = "dialogs")]
+    fn save_file(&self, options: FileDialogOptions) -> Result<AsyncRequestSerial, RequestError>;
+
+    /// Show a native message box, like an error or a confirmation.
+    ///
+    /// The message box is shown asynchronously, and the button pressed by the user is delivered to
+    /// [`ApplicationHandler::message_box_done()`] with the returned serial.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Uses `MessageBoxW`.
+    /// - **macOS:** Uses `NSAlert`. Without a parent window, the alert is run modally before this
+    ///   returns, and its result is still delivered afterwards.
+    /// - **Wayland / X11:** Runs `zenity`, or `kdialog` when it's not installed. On Wayland, it
+    ///   can't be attached to the parent window.
+    /// - **iOS / Android / Web / Orbital:** Unsupported.
+    ///
+    /// [`ApplicationHandler::message_box_done()`]: crate::application::ApplicationHandler::message_box_done()
+    #[cfg(feature = "dialogs")]
+    fn show_message_box(
+        &self,
+        config: MessageBoxConfig,
+    ) -> Result<AsyncRequestSerial, RequestError>;
+
     /// Returns the current system theme.
     ///
     /// Returns `None` if it cannot be determined on the current platform.
@@ -385,6 +740,233 @@ pub trait ActiveEventLoop: AsAny {
     /// - **iOS / Android / Wayland / x11 / Orbital:** Unsupported.
     fn system_theme(&self) -> Option<Theme>;
 
+    /// Returns the accent color chosen by the user for the system.
+    ///
+    /// Custom-drawn decorations and selection highlights can use it to match the rest of the
+    /// system. Changes are reported with [`WindowEvent::AccentColorChanged`].
+    ///
+    /// Returns `None` if it cannot be determined on the current platform.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Returns the DWM colorization color.
+    /// - **macOS:** Returns `controlAccentColor`, requires macOS 10.14.
+    /// - **iOS / Android / Web / Wayland / X11 / Orbital:** Unsupported.
+    ///
+    /// [`WindowEvent::AccentColorChanged`]: crate::event::WindowEvent::AccentColorChanged
+    fn system_accent_color(&self) -> Option<Rgba>;
+
+    /// Returns the cursor theme configured by the user.
+    ///
+    /// Applications drawing their own cursor can use it to match the size of the system cursor.
+    /// Changes are reported with [`ApplicationHandler::cursor_theme_changed()`].
+    ///
+    /// Returns `None` if it cannot be determined on the current platform.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** The name is the cursor scheme, the size is the accessibility cursor size.
+    /// - **X11:** Read from the XSETTINGS, then the `Xcursor.theme` and `Xcursor.size` resources,
+    ///   then the `XCURSOR_THEME` and `XCURSOR_SIZE` environment variables.
+    /// - **Wayland:** Read from the `XCURSOR_THEME` and `XCURSOR_SIZE` environment variables, which
+    ///   are also used to load the cursors.
+    /// - **macOS / iOS / Android / Web / Orbital:** Unsupported.
+    ///
+    /// [`ApplicationHandler::cursor_theme_changed()`]: crate::application::ApplicationHandler::cursor_theme_changed()
+    fn cursor_theme(&self) -> Option<CursorThemeInfo>;
+
+    /// Returns the accessibility preferences of the user.
+    ///
+    /// Changes are reported with [`ApplicationHandler::system_preferences_changed()`].
+    ///
+    /// Returns `None` if they cannot be determined on the current platform.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** The motion is reduced when the animations of the client area are disabled,
+    ///   and the transparency when the transparency effects are disabled.
+    /// - **Wayland / X11:** Read from the `Settings` portal of `xdg-desktop-portal`, with the GNOME
+    ///   settings as fallback, so this is `None` until the portal replied. The transparency
+    ///   preference isn't available.
+    /// - **Web:** Read from the `prefers-reduced-motion`, `prefers-contrast`, `forced-colors` and
+    ///   `prefers-reduced-transparency` media queries.
+    /// - **Android / Orbital:** Unsupported.
+    ///
+    /// [`ApplicationHandler::system_preferences_changed()`]: crate::application::ApplicationHandler::system_preferences_changed()
+    fn system_preferences(&self) -> Option<SystemPreferences>;
+
+    /// Returns the factor by which the user asked to scale the text, `1.0` being the default size.
+    ///
+    /// This is independent from the [scale factor] of the monitors, which applies to all the
+    /// content: the size of the text should be multiplied by both.
+    ///
+    /// Changes are reported with [`ApplicationHandler::text_scale_factor_changed()`].
+    ///
+    /// Returns `None` if it cannot be determined on the current platform.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** The "Make text bigger" setting of the accessibility settings.
+    /// - **iOS:** The scaling of the body text by the Dynamic Type content size category.
+    /// - **Wayland / X11:** The `text-scaling-factor` of GNOME, read from the `Settings` portal of
+    ///   `xdg-desktop-portal`. This is `None` until the portal replied, or on other desktops.
+    /// - **macOS / Android / Web / Orbital:** Unsupported. On Android, the font scale of the
+    ///   configuration isn't exposed by the NDK.
+    ///
+    /// [scale factor]: crate::window::Window::scale_factor()
+    /// [`ApplicationHandler::text_scale_factor_changed()`]: crate::application::ApplicationHandler::text_scale_factor_changed()
+    fn text_scale_factor(&self) -> Option<f64>;
+
+    /// Returns the current power state of the system.
+    ///
+    /// Changes are reported with [`PowerEvent::PowerStateChanged`].
+    ///
+    /// Returns `None` if it cannot be determined on the current platform, or if the system has
+    /// no battery and doesn't report its power supply.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland / X11:** Read from `/sys/class/power_supply`, changes aren't reported.
+    /// - **iOS / Android / Web / Orbital:** Unsupported.
+    ///
+    /// [`PowerEvent::PowerStateChanged`]: crate::event::PowerEvent::PowerStateChanged
+    fn power_state(&self) -> Option<PowerState>;
+
+    /// Returns the locale the user chose for the user interface.
+    ///
+    /// Changes are reported with [`ApplicationHandler::locale_changed()`], so the application can
+    /// be re-localized while it runs. Returns [`LanguageTag::undetermined()`] if it cannot be
+    /// determined.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Returns the default locale of the user.
+    /// - **macOS / iOS:** Returns the first of the preferred languages of the user.
+    /// - **Android:** Returns the language and country of the current configuration.
+    /// - **Web:** Returns `navigator.language`.
+    /// - **Wayland / X11 / Orbital:** Read from the `LC_ALL`, `LC_MESSAGES` and `LANG` environment
+    ///   variables, changes aren't reported.
+    ///
+    /// [`ApplicationHandler::locale_changed()`]: crate::application::ApplicationHandler::locale_changed()
+    fn locale(&self) -> LanguageTag;
+
+    /// Returns the keyboard layout currently chosen by the user.
+    ///
+    /// Returns `None` if it cannot be determined on the current platform. See
+    /// [`WindowEvent::KeyboardLayoutChanged`] to be notified when it changes.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland / X11:** The layout is only known once the keymap was received.
+    /// - **iOS / Android / Web / Orbital:** Unsupported.
+    ///
+    /// [`WindowEvent::KeyboardLayoutChanged`]: crate::event::WindowEvent::KeyboardLayoutChanged
+    fn keyboard_layout(&self) -> Option<KeyboardLayout>;
+
+    /// Returns the label of a physical key in the current keyboard layout.
+    ///
+    /// This is the character the key produces without modifiers, in uppercase like on the
+    /// keycaps, e.g. `"Z"` for [`KeyCode::KeyZ`] with a QWERTY layout and `"W"` with an AZERTY
+    /// layout. Applications binding actions to physical keys can use it to show the key to press.
+    ///
+    /// Returns `None` for keys which don't produce a visible character, like the modifiers or the
+    /// arrows, which have the same name in every layout.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland / X11:** The labels are only known once the keymap was received. The dead keys
+    ///   have no label.
+    /// - **iOS / Android / Web / Orbital:** Unsupported.
+    fn key_label(&self, key_code: KeyCode) -> Option<String>;
+
+    /// Returns the logical key a physical key produces without modifiers in the current keyboard
+    /// layout.
+    ///
+    /// Returns `None` if the key produces nothing, or if the layout cannot be determined.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland / X11:** The keys are only known once the keymap was received.
+    /// - **iOS / Android / Web / Orbital:** Unsupported.
+    fn key_for_keycode(&self, key_code: KeyCode) -> Option<Key>;
+
+    /// Returns the physical key producing a logical key without modifiers in the current keyboard
+    /// layout.
+    ///
+    /// This resolves a shortcut like `Ctrl+Z` to the key which produces `z`, e.g.
+    /// [`KeyCode::KeyZ`] with a QWERTY layout and [`KeyCode::KeyW`] with an AZERTY layout.
+    /// Characters are compared ignoring their case. When several keys produce it, the key of
+    /// the main block is preferred over the numpad.
+    ///
+    /// Returns `None` if no key produces it without modifiers.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland / X11:** The keys are only known once the keymap was received.
+    /// - **iOS / Android / Web / Orbital:** Unsupported.
+    fn keycode_for_key(&self, key: &Key) -> Option<KeyCode>;
+
+    /// Turns a lock of the keyboard on or off, along with its LED.
+    ///
+    /// The lock itself changes, not only its light, so the keys follow it like when the user
+    /// presses the lock key. This lets emulators mirror the lock state of the system they run.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** The lock key is pressed and released with `SendInput` when it needs to
+    ///   change.
+    /// - **X11:** The Num Lock is expected to be the `Mod2` modifier, like on most keymaps.
+    /// - **macOS / Wayland / iOS / Android / Web / Orbital:** Unsupported.
+    fn set_keyboard_led(&self, led: Led, on: bool) -> Result<(), RequestError>;
+
+    /// Returns whether a lock of the keyboard is on, or `None` if it cannot be determined.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Only the Caps Lock is supported.
+    /// - **Wayland:** The Scroll Lock isn't supported, and the locks are only known once the keymap
+    ///   was received.
+    /// - **iOS / Android / Web / Orbital:** Unsupported.
+    fn keyboard_led(&self, led: Led) -> Option<bool>;
+
+    /// Deliver a device event as if it came from the system, to test applications end-to-end.
+    ///
+    /// The event is delivered to [`ApplicationHandler::device_event()`] on the next iteration of
+    /// the event loop, regardless of [`listen_device_events()`]. See [`Window::inject_event()`]
+    /// for the window events.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows / macOS / iOS / Android / Web / Orbital:** Unsupported.
+    ///
+    /// [`listen_device_events()`]: Self::listen_device_events()
+    /// [`Window::inject_event()`]: crate::window::Window::inject_event()
+    #[cfg(feature = "winit-test-harness")]
+    fn inject_device_event(
+        &self,
+        device_id: Option<DeviceId>,
+        event: DeviceEvent,
+    ) -> Result<(), RequestError> {
+        let _ = (device_id, event);
+        Err(NotSupportedError::new("inject_device_event is not supported").into())
+    }
+
+    /// Returns the data specific to the backend of the event being delivered.
+    ///
+    /// It's only available while the application handles the event, and gives access to what
+    /// the system reported along with it, like the serials of the input events on Wayland.
+    /// Returns `None` when the event has no such data.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** The pointer, keyboard and touch events which carry a serial have an
+    ///   [`EventData`][crate::platform::wayland::EventData].
+    /// - **Windows:** The events created from a window message have a
+    ///   [`MessageData`][crate::platform::windows::MessageData]. The raw device events don't.
+    /// - **macOS / iOS / Android / Web / X11 / Orbital:** Unsupported.
+    #[cfg_attr(not(docsrs), allow(rustdoc::broken_intra_doc_links))]
+    fn event_platform_data(&self) -> Option<PlatformData>;
+
     /// Sets the [`ControlFlow`].
     fn set_control_flow(&self, control_flow: ControlFlow);
 
@@ -489,6 +1071,38 @@ impl fmt::Debug for EventLoopProxy {
     }
 }
 
+/// The response to [`ApplicationHandler::exit_requested()`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ExitResponse {
+    /// Exit the event loop, the same way [`ActiveEventLoop::exit()`] does.
+    #[default]
+    Exit,
+
+    /// Keep the application running.
+    ///
+    /// The application could still exit later on, for example after asking the user to save
+    /// their work, by calling [`ActiveEventLoop::exit()`].
+    Cancel,
+}
+
+/// The order of a [`ModifiersChanged`] event relative to the [`KeyboardInput`] event of the
+/// modifier key which caused it.
+///
+/// Set with [`EventLoopBuilder::with_modifiers_order()`].
+///
+/// [`ModifiersChanged`]: crate::event::WindowEvent::ModifiersChanged
+/// [`KeyboardInput`]: crate::event::WindowEvent::KeyboardInput
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ModifiersOrder {
+    /// Deliver the key press or release first, the modifiers then reflect the state after it.
+    #[default]
+    AfterKeyboardInput,
+
+    /// Deliver the new modifiers first, so they're already up to date when the key press or
+    /// release is handled.
+    BeforeKeyboardInput,
+}
+
 /// Control when device events are captured.
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -502,6 +1116,36 @@ pub enum DeviceEvents {
     Never,
 }
 
+bitflags! {
+    /// Classes of devices whose [`DeviceEvent`]s are captured, see
+    /// [`ActiveEventLoop::listen_device_classes`].
+    ///
+    /// [`DeviceEvent`]: crate::event::DeviceEvent
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct DeviceClass: u8 {
+        /// Mice and other pointing devices: [`DeviceEvent::PointerMotion`],
+        /// [`DeviceEvent::MouseWheel`] and [`DeviceEvent::Button`].
+        ///
+        /// [`DeviceEvent::PointerMotion`]: crate::event::DeviceEvent::PointerMotion
+        /// [`DeviceEvent::MouseWheel`]: crate::event::DeviceEvent::MouseWheel
+        /// [`DeviceEvent::Button`]: crate::event::DeviceEvent::Button
+        const MOUSE = 1 << 0;
+        /// Keyboards: [`DeviceEvent::Key`].
+        ///
+        /// [`DeviceEvent::Key`]: crate::event::DeviceEvent::Key
+        const KEYBOARD = 1 << 1;
+        /// Other human interface devices, like gamepads and their `DeviceEvent::Gamepad`.
+        const HID = 1 << 2;
+    }
+}
+
+impl Default for DeviceClass {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
 /// A unique identifier of the winit's async request.
 ///
 /// This could be used to identify the async request once it's done
@@ -517,8 +1161,8 @@ pub struct AsyncRequestSerial {
 }
 
 impl AsyncRequestSerial {
-    // TODO(kchibisov): Remove `cfg` when the clipboard will be added.
-    #[allow(dead_code)]
+    // Android and Orbital have no asynchronous requests yet.
+    #[cfg_attr(any(android_platform, orbital_platform), allow(dead_code))]
     pub(crate) fn get() -> Self {
         static CURRENT_SERIAL: AtomicUsize = AtomicUsize::new(0);
         // NOTE: We rely on wrap around here, while the user may just request
@@ -0,0 +1,167 @@
+//! Input of gamepads and game controllers.
+//!
+//! Gamepads are input devices like the others: they're reported by
+//! [`ApplicationHandler::input_device_added()`] and [`input_device_removed()`] with the
+//! [`DeviceKind::Gamepad`] kind when they're connected and disconnected, and their input is
+//! delivered as [`DeviceEvent::Gamepad`] to [`ApplicationHandler::device_event()`].
+//!
+//! The buttons and axes are named after their position on the standard gamepad layout, with four
+//! face buttons, two shoulder buttons and triggers, a directional pad and two thumb sticks.
+//!
+//! ```no_run
+//! # use winit::event::DeviceEvent;
+//! # use winit::gamepad::{GamepadAxis, GamepadButton, GamepadEvent};
+//! # fn scope(event: DeviceEvent) {
+//! if let DeviceEvent::Gamepad(event) = event {
+//!     match event {
+//!         GamepadEvent::Button { button: GamepadButton::South, state } => {
+//!             println!("Jump button {state:?}")
+//!         },
+//!         GamepadEvent::Axis { axis: GamepadAxis::LeftStickX, value } => {
+//!             println!("Steer {value}")
+//!         },
+//!         _ => (),
+//!     }
+//! }
+//! # }
+//! ```
+//!
+//! The gamepads can be made to vibrate with [`ActiveEventLoop::set_gamepad_rumble()`].
+//!
+//! ## Platform-specific
+//!
+//! - **Windows:** Uses XInput, which supports up to four Xbox compatible controllers.
+//! - **Wayland / X11:** Reads the `evdev` devices of `/dev/input`, which requires the permission to
+//!   read them. It's usually granted to the user of the active session.
+//! - **Web:** Uses the Gamepad API. Browsers only report gamepads once a button was pressed on
+//!   them, and rumble requires support of the `vibrationActuator`.
+//! - **macOS / iOS / Android / Orbital:** Unsupported.
+//!
+//! This module is only available with the `gamepad` cargo feature.
+//!
+//! [`ApplicationHandler::input_device_added()`]: crate::application::ApplicationHandler::input_device_added()
+//! [`input_device_removed()`]: crate::application::ApplicationHandler::input_device_removed()
+//! [`ApplicationHandler::device_event()`]: crate::application::ApplicationHandler::device_event()
+//! [`DeviceKind::Gamepad`]: crate::event::DeviceKind::Gamepad
+//! [`DeviceEvent::Gamepad`]: crate::event::DeviceEvent::Gamepad
+//! [`ActiveEventLoop::set_gamepad_rumble()`]: crate::event_loop::ActiveEventLoop::set_gamepad_rumble()
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::event::ElementState;
+
+/// The input of a gamepad.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum GamepadEvent {
+    /// A button was pressed or released.
+    Button { button: GamepadButton, state: ElementState },
+
+    /// An axis moved.
+    Axis {
+        axis: GamepadAxis,
+        /// The position of the axis.
+        ///
+        /// It ranges from `-1.0` to `1.0` for the sticks, where positive values point right and
+        /// up, and from `0.0` to `1.0` for the triggers.
+        value: f64,
+    },
+
+    /// The battery level of a wireless gamepad changed.
+    ///
+    /// It's also emitted right after the gamepad was connected, when the level is known.
+    Battery { level: BatteryLevel, charging: bool },
+}
+
+/// A button of a gamepad.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum GamepadButton {
+    /// The bottom face button, like A on Xbox controllers and Cross on PlayStation controllers.
+    South,
+    /// The right face button, like B on Xbox controllers and Circle on PlayStation controllers.
+    East,
+    /// The top face button, like Y on Xbox controllers and Triangle on PlayStation controllers.
+    North,
+    /// The left face button, like X on Xbox controllers and Square on PlayStation controllers.
+    West,
+    LeftShoulder,
+    RightShoulder,
+    /// The left trigger, for the gamepads which report it as a button.
+    ///
+    /// The analog position is reported by [`GamepadAxis::LeftTrigger`].
+    LeftTrigger,
+    /// The right trigger, for the gamepads which report it as a button.
+    ///
+    /// The analog position is reported by [`GamepadAxis::RightTrigger`].
+    RightTrigger,
+    /// The left center button, like Back or View on Xbox controllers.
+    Select,
+    /// The right center button, like Start or Menu on Xbox controllers.
+    Start,
+    /// The button with the logo of the vendor, like the Xbox button.
+    Mode,
+    /// Pressing the left stick.
+    LeftStick,
+    /// Pressing the right stick.
+    RightStick,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+    /// A button outside of the standard layout, with its platform-specific code.
+    Other(u16),
+}
+
+/// An axis of a gamepad.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum GamepadAxis {
+    LeftStickX,
+    LeftStickY,
+    RightStickX,
+    RightStickY,
+    LeftTrigger,
+    RightTrigger,
+    /// An axis outside of the standard layout, with its platform-specific code.
+    Other(u16),
+}
+
+/// The approximate charge of the battery of a gamepad.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum BatteryLevel {
+    Empty,
+    Low,
+    Medium,
+    Full,
+}
+
+impl BatteryLevel {
+    /// The level of a battery charged at `percent`, between 0 and 100.
+    pub fn from_percent(percent: u8) -> Self {
+        match percent {
+            0..=5 => Self::Empty,
+            6..=40 => Self::Low,
+            41..=80 => Self::Medium,
+            _ => Self::Full,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn battery_level_from_percent() {
+        assert_eq!(BatteryLevel::from_percent(0), BatteryLevel::Empty);
+        assert_eq!(BatteryLevel::from_percent(20), BatteryLevel::Low);
+        assert_eq!(BatteryLevel::from_percent(60), BatteryLevel::Medium);
+        assert_eq!(BatteryLevel::from_percent(100), BatteryLevel::Full);
+        assert_eq!(BatteryLevel::from_percent(255), BatteryLevel::Full);
+    }
+}
@@ -0,0 +1,527 @@
+//! Recognition of high-level gestures from the pointer events.
+//!
+//! Not every platform recognizes gestures natively, and those which do often only report a subset
+//! of them. The [`GestureRecognizer`] consumes the [`WindowEvent`]s of a window and turns the
+//! touch and mouse input into [`Gesture`]s, which behave the same on every platform.
+//!
+//! ```no_run
+//! # use winit::event::WindowEvent;
+//! # use winit::gestures::{Gesture, GestureRecognizer};
+//! # fn scope(recognizer: &mut GestureRecognizer, event: WindowEvent) {
+//! recognizer.handle_event(&event);
+//! for gesture in recognizer.gestures() {
+//!     match gesture {
+//!         Gesture::Tap { position } => println!("Tapped at {position:?}"),
+//!         Gesture::Pinch { delta, .. } => println!("Zoomed by {delta}"),
+//!         _ => (),
+//!     }
+//! }
+//! # }
+//! ```
+//!
+//! This module is only available with the `gestures` cargo feature.
+
+use std::collections::VecDeque;
+#[cfg(not(web_platform))]
+use std::time::{Duration, Instant};
+
+use bitflags::bitflags;
+use dpi::PhysicalPosition;
+#[cfg(web_platform)]
+use web_time::{Duration, Instant};
+
+use crate::event::{ButtonSource, ElementState, MouseButton, PointerKind, TouchPhase, WindowEvent};
+
+bitflags! {
+    /// The set of gestures a [`GestureRecognizer`] looks for.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct Gestures: u8 {
+        /// See [`Gesture::Tap`].
+        const TAP = 1 << 0;
+        /// See [`Gesture::DoubleTap`].
+        const DOUBLE_TAP = 1 << 1;
+        /// See [`Gesture::LongPress`].
+        const LONG_PRESS = 1 << 2;
+        /// See [`Gesture::Pan`].
+        const PAN = 1 << 3;
+        /// See [`Gesture::Pinch`].
+        const PINCH = 1 << 4;
+        /// See [`Gesture::Rotation`].
+        const ROTATION = 1 << 5;
+    }
+}
+
+impl Default for Gestures {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+/// A gesture recognized by a [`GestureRecognizer`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Gesture {
+    /// A single pointer was pressed and released quickly, without moving.
+    Tap { position: PhysicalPosition<f64> },
+
+    /// Two taps happened in a quick succession at the same place.
+    ///
+    /// The first tap is still reported as [`Gesture::Tap`].
+    DoubleTap { position: PhysicalPosition<f64> },
+
+    /// A single pointer was held down without moving.
+    LongPress { position: PhysicalPosition<f64> },
+
+    /// The pointers moved together.
+    ///
+    /// With multiple pointers, the delta is the movement of their center.
+    Pan {
+        /// Change in pixels from the last update.
+        delta: PhysicalPosition<f32>,
+        phase: TouchPhase,
+    },
+
+    /// Two pointers moved away from, or towards, each other.
+    ///
+    /// Same as [`WindowEvent::PinchGesture`].
+    Pinch {
+        /// Positive values indicate magnification (zooming in) and negative values indicate
+        /// shrinking (zooming out).
+        delta: f64,
+        phase: TouchPhase,
+    },
+
+    /// Two pointers rotated around each other.
+    ///
+    /// Same as [`WindowEvent::RotationGesture`].
+    Rotation {
+        /// Change in rotation in degrees, positive values indicate rotation counterclockwise.
+        delta: f32,
+        phase: TouchPhase,
+    },
+}
+
+/// A pointer being held down.
+#[derive(Debug, Clone, Copy)]
+struct Pointer {
+    kind: PointerKind,
+    position: PhysicalPosition<f64>,
+}
+
+/// The single pointer which could become a tap or a long press.
+#[derive(Debug, Clone, Copy)]
+struct Press {
+    start: Instant,
+    position: PhysicalPosition<f64>,
+    long_pressed: bool,
+}
+
+/// Turns the pointer events of a window into [`Gesture`]s.
+///
+/// One recognizer should be used per window, by passing it every [`WindowEvent`] of that window
+/// with [`handle_event()`], and then taking the recognized gestures with [`gestures()`].
+///
+/// Touches and the left mouse button are both considered as pointers, so that a mouse could be
+/// used to tap, long press and pan.
+///
+/// [`handle_event()`]: Self::handle_event
+/// [`gestures()`]: Self::gestures
+#[derive(Debug, Clone)]
+pub struct GestureRecognizer {
+    enabled: Gestures,
+    tap_slop: f64,
+    tap_duration: Duration,
+    double_tap_interval: Duration,
+    long_press_duration: Duration,
+
+    pointers: Vec<Pointer>,
+    press: Option<Press>,
+    last_tap: Option<(Instant, PhysicalPosition<f64>)>,
+    panning: bool,
+    pinching: bool,
+    rotating: bool,
+    pending: VecDeque<Gesture>,
+}
+
+impl Default for GestureRecognizer {
+    fn default() -> Self {
+        Self {
+            enabled: Gestures::default(),
+            tap_slop: 10.,
+            tap_duration: Duration::from_millis(300),
+            double_tap_interval: Duration::from_millis(300),
+            long_press_duration: Duration::from_millis(500),
+            pointers: Vec::new(),
+            press: None,
+            last_tap: None,
+            panning: false,
+            pinching: false,
+            rotating: false,
+            pending: VecDeque::new(),
+        }
+    }
+}
+
+impl GestureRecognizer {
+    /// Create a recognizer looking for all the [`Gestures`], with the default thresholds.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the gestures to recognize.
+    ///
+    /// The default is [`Gestures::all()`].
+    #[inline]
+    pub fn with_gestures(mut self, gestures: Gestures) -> Self {
+        self.enabled = gestures;
+        self
+    }
+
+    /// Sets how far, in physical pixels, a pointer can move before it's no longer considered a
+    /// tap or a long press.
+    ///
+    /// The default is `10` pixels.
+    #[inline]
+    pub fn with_tap_slop(mut self, slop: f64) -> Self {
+        self.tap_slop = slop;
+        self
+    }
+
+    /// Sets how long a pointer could be held down to be considered a tap.
+    ///
+    /// The default is 300 milliseconds.
+    #[inline]
+    pub fn with_tap_duration(mut self, duration: Duration) -> Self {
+        self.tap_duration = duration;
+        self
+    }
+
+    /// Sets the maximum time between two taps to be considered a double tap.
+    ///
+    /// The default is 300 milliseconds.
+    #[inline]
+    pub fn with_double_tap_interval(mut self, interval: Duration) -> Self {
+        self.double_tap_interval = interval;
+        self
+    }
+
+    /// Sets how long a pointer must be held down to be considered a long press.
+    ///
+    /// The default is 500 milliseconds.
+    #[inline]
+    pub fn with_long_press_duration(mut self, duration: Duration) -> Self {
+        self.long_press_duration = duration;
+        self
+    }
+
+    /// Feed a [`WindowEvent`] of the window to the recognizer.
+    ///
+    /// The events not related to the pointers are ignored.
+    pub fn handle_event(&mut self, event: &WindowEvent) {
+        self.handle_event_at(event, Instant::now());
+    }
+
+    /// Recognize the gestures depending on the elapsed time, such as [`Gesture::LongPress`].
+    ///
+    /// This should be called when [`deadline()`] is reached, e.g. by using
+    /// [`ControlFlow::WaitUntil`].
+    ///
+    /// [`deadline()`]: Self::deadline
+    /// [`ControlFlow::WaitUntil`]: crate::event_loop::ControlFlow::WaitUntil
+    pub fn update(&mut self) {
+        self.update_at(Instant::now());
+    }
+
+    /// The time at which [`update()`] must be called for a pending gesture to be recognized.
+    ///
+    /// [`update()`]: Self::update
+    pub fn deadline(&self) -> Option<Instant> {
+        match self.press {
+            Some(press) if !press.long_pressed && self.enabled.contains(Gestures::LONG_PRESS) => {
+                Some(press.start + self.long_press_duration)
+            },
+            _ => None,
+        }
+    }
+
+    /// Take the gestures recognized so far.
+    pub fn gestures(&mut self) -> impl Iterator<Item = Gesture> + '_ {
+        self.pending.drain(..)
+    }
+
+    fn handle_event_at(&mut self, event: &WindowEvent, now: Instant) {
+        self.update_at(now);
+
+        match event {
+            WindowEvent::PointerButton { state, position, button, .. } => {
+                let kind = match button {
+                    ButtonSource::Touch { finger_id, .. } => PointerKind::Touch(*finger_id),
+                    ButtonSource::Mouse(MouseButton::Left) => PointerKind::Mouse,
+                    _ => return,
+                };
+
+                match state {
+                    ElementState::Pressed => self.pointer_down(kind, *position, now),
+                    ElementState::Released => self.pointer_up(kind, now),
+                }
+            },
+            WindowEvent::PointerMoved { position, source, .. } => {
+                self.pointer_moved(PointerKind::from(source.clone()), *position)
+            },
+            // The touch was cancelled.
+            WindowEvent::PointerLeft { kind: kind @ PointerKind::Touch(_), .. } => {
+                self.press = None;
+                self.pointer_up(*kind, now);
+            },
+            WindowEvent::Focused(false) => {
+                self.press = None;
+                while let Some(pointer) = self.pointers.last() {
+                    self.pointer_up(pointer.kind, now);
+                }
+            },
+            _ => (),
+        }
+    }
+
+    fn update_at(&mut self, now: Instant) {
+        if let Some(deadline) = self.deadline() {
+            if now >= deadline {
+                let press = self.press.as_mut().unwrap();
+                press.long_pressed = true;
+                self.pending.push_back(Gesture::LongPress { position: press.position });
+            }
+        }
+    }
+
+    fn pointer_down(&mut self, kind: PointerKind, position: PhysicalPosition<f64>, now: Instant) {
+        if self.pointers.iter().any(|pointer| pointer.kind == kind) {
+            return;
+        }
+
+        self.end_multi_touch();
+        self.pointers.push(Pointer { kind, position });
+        self.press = if self.pointers.len() == 1 {
+            Some(Press { start: now, position, long_pressed: false })
+        } else {
+            None
+        };
+    }
+
+    fn pointer_up(&mut self, kind: PointerKind, now: Instant) {
+        let Some(index) = self.pointers.iter().position(|pointer| pointer.kind == kind) else {
+            return;
+        };
+
+        self.end_multi_touch();
+        if self.panning && self.pointers.len() == 1 {
+            self.panning = false;
+            self.pending.push_back(Gesture::Pan {
+                delta: PhysicalPosition::new(0., 0.),
+                phase: TouchPhase::Ended,
+            });
+        }
+
+        self.pointers.remove(index);
+        if let Some(press) = self.press.take() {
+            if !press.long_pressed && now.duration_since(press.start) <= self.tap_duration {
+                self.tap(press.position, now);
+            }
+        }
+    }
+
+    fn pointer_moved(&mut self, kind: PointerKind, position: PhysicalPosition<f64>) {
+        let Some(index) = self.pointers.iter().position(|pointer| pointer.kind == kind) else {
+            return;
+        };
+
+        let old_center = self.center();
+        let old_span = self.span();
+        self.pointers[index].position = position;
+        let new_center = self.center();
+
+        if let Some(press) = self.press {
+            if distance(press.position, position) <= self.tap_slop {
+                return;
+            }
+            self.press = None;
+        }
+
+        if self.enabled.contains(Gestures::PAN) {
+            let phase = if self.panning { TouchPhase::Moved } else { TouchPhase::Started };
+            self.panning = true;
+            self.pending.push_back(Gesture::Pan {
+                delta: PhysicalPosition::new(
+                    (new_center.x - old_center.x) as f32,
+                    (new_center.y - old_center.y) as f32,
+                ),
+                phase,
+            });
+        }
+
+        if let (Some((old_distance, old_angle)), Some((new_distance, new_angle))) =
+            (old_span, self.span())
+        {
+            if self.enabled.contains(Gestures::PINCH) && old_distance > 0. {
+                let phase = if self.pinching { TouchPhase::Moved } else { TouchPhase::Started };
+                self.pinching = true;
+                self.pending
+                    .push_back(Gesture::Pinch { delta: new_distance / old_distance - 1., phase });
+            }
+
+            if self.enabled.contains(Gestures::ROTATION) {
+                let phase = if self.rotating { TouchPhase::Moved } else { TouchPhase::Started };
+                self.rotating = true;
+                // The Y axis points down, so counterclockwise rotations decrease the angle.
+                let mut delta = (old_angle - new_angle).to_degrees();
+                if delta > 180. {
+                    delta -= 360.;
+                } else if delta <= -180. {
+                    delta += 360.;
+                }
+                self.pending.push_back(Gesture::Rotation { delta: delta as f32, phase });
+            }
+        }
+    }
+
+    fn tap(&mut self, position: PhysicalPosition<f64>, now: Instant) {
+        if self.enabled.contains(Gestures::TAP) {
+            self.pending.push_back(Gesture::Tap { position });
+        }
+
+        match self.last_tap.take() {
+            Some((time, last_position))
+                if self.enabled.contains(Gestures::DOUBLE_TAP)
+                    && now.duration_since(time) <= self.double_tap_interval
+                    && distance(last_position, position) <= self.tap_slop =>
+            {
+                self.pending.push_back(Gesture::DoubleTap { position });
+            },
+            _ => self.last_tap = Some((now, position)),
+        }
+    }
+
+    /// End the gestures tracking exactly two pointers, since their number is about to change.
+    fn end_multi_touch(&mut self) {
+        if self.pinching {
+            self.pinching = false;
+            self.pending.push_back(Gesture::Pinch { delta: 0., phase: TouchPhase::Ended });
+        }
+
+        if self.rotating {
+            self.rotating = false;
+            self.pending.push_back(Gesture::Rotation { delta: 0., phase: TouchPhase::Ended });
+        }
+    }
+
+    fn center(&self) -> PhysicalPosition<f64> {
+        let count = self.pointers.len().max(1) as f64;
+        let (x, y) = self
+            .pointers
+            .iter()
+            .fold((0., 0.), |(x, y), pointer| (x + pointer.position.x, y + pointer.position.y));
+        PhysicalPosition::new(x / count, y / count)
+    }
+
+    /// The distance and angle between the two pointers, if there are exactly two of them.
+    fn span(&self) -> Option<(f64, f64)> {
+        match self.pointers.as_slice() {
+            [first, second] => {
+                let dx = second.position.x - first.position.x;
+                let dy = second.position.y - first.position.y;
+                Some((dx.hypot(dy), dy.atan2(dx)))
+            },
+            _ => None,
+        }
+    }
+}
+
+fn distance(a: PhysicalPosition<f64>, b: PhysicalPosition<f64>) -> f64 {
+    (b.x - a.x).hypot(b.y - a.y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{FingerId, PointerSource};
+
+    // The mouse and a finger, to have two distinct pointers.
+    const MOUSE: bool = false;
+    const FINGER: bool = true;
+
+    fn button(state: ElementState, touch: bool, x: f64, y: f64) -> WindowEvent {
+        let button = if touch {
+            ButtonSource::Touch { finger_id: FingerId::dummy(), force: None, contact: None }
+        } else {
+            ButtonSource::Mouse(MouseButton::Left)
+        };
+        WindowEvent::PointerButton {
+            device_id: None,
+            state,
+            position: PhysicalPosition::new(x, y),
+            button,
+        }
+    }
+
+    fn moved(touch: bool, x: f64, y: f64) -> WindowEvent {
+        let source = if touch {
+            PointerSource::Touch { finger_id: FingerId::dummy(), force: None, contact: None }
+        } else {
+            PointerSource::Mouse
+        };
+        WindowEvent::PointerMoved { device_id: None, position: PhysicalPosition::new(x, y), source }
+    }
+
+    #[test]
+    fn tap_and_double_tap() {
+        let mut recognizer = GestureRecognizer::new();
+        let start = Instant::now();
+        let at = |millis| start + Duration::from_millis(millis);
+
+        recognizer.handle_event_at(&button(ElementState::Pressed, FINGER, 5., 5.), at(0));
+        recognizer.handle_event_at(&button(ElementState::Released, FINGER, 5., 5.), at(50));
+        recognizer.handle_event_at(&button(ElementState::Pressed, FINGER, 6., 5.), at(150));
+        recognizer.handle_event_at(&button(ElementState::Released, FINGER, 6., 5.), at(200));
+
+        assert_eq!(recognizer.gestures().collect::<Vec<_>>(), [
+            Gesture::Tap { position: PhysicalPosition::new(5., 5.) },
+            Gesture::Tap { position: PhysicalPosition::new(6., 5.) },
+            Gesture::DoubleTap { position: PhysicalPosition::new(6., 5.) },
+        ]);
+    }
+
+    #[test]
+    fn long_press() {
+        let mut recognizer = GestureRecognizer::new();
+        let start = Instant::now();
+        let at = |millis| start + Duration::from_millis(millis);
+
+        recognizer.handle_event_at(&button(ElementState::Pressed, MOUSE, 5., 5.), at(0));
+        assert_eq!(recognizer.deadline(), Some(at(500)));
+        recognizer.update_at(at(600));
+        recognizer.handle_event_at(&button(ElementState::Released, MOUSE, 5., 5.), at(1000));
+
+        assert_eq!(recognizer.gestures().collect::<Vec<_>>(), [Gesture::LongPress {
+            position: PhysicalPosition::new(5., 5.)
+        }]);
+        assert_eq!(recognizer.deadline(), None);
+    }
+
+    #[test]
+    fn pinch_and_rotate() {
+        let mut recognizer =
+            GestureRecognizer::new().with_gestures(Gestures::PINCH | Gestures::ROTATION);
+        let now = Instant::now();
+
+        recognizer.handle_event_at(&button(ElementState::Pressed, MOUSE, 0., 0.), now);
+        recognizer.handle_event_at(&button(ElementState::Pressed, FINGER, 100., 0.), now);
+        // Move the finger upwards, twice as far.
+        recognizer.handle_event_at(&moved(FINGER, 0., -200.), now);
+        recognizer.handle_event_at(&button(ElementState::Released, FINGER, 0., -200.), now);
+
+        assert_eq!(recognizer.gestures().collect::<Vec<_>>(), [
+            Gesture::Pinch { delta: 1., phase: TouchPhase::Started },
+            Gesture::Rotation { delta: 90., phase: TouchPhase::Started },
+            Gesture::Pinch { delta: 0., phase: TouchPhase::Ended },
+            Gesture::Rotation { delta: 0., phase: TouchPhase::Ended },
+        ]);
+    }
+}
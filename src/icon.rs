@@ -1,8 +1,16 @@
 use std::error::Error;
-use std::{fmt, io, mem};
+use std::path::Path;
+use std::{fmt, fs, io, mem};
 
 use crate::platform_impl::PlatformIcon;
 
+mod decode;
+#[cfg(feature = "svg")]
+mod svg;
+
+#[cfg(feature = "svg")]
+pub(crate) use self::svg::SvgIcon;
+
 #[repr(C)]
 #[derive(Debug)]
 pub(crate) struct Pixel {
@@ -15,7 +23,7 @@ pub(crate) struct Pixel {
 pub(crate) const PIXEL_SIZE: usize = mem::size_of::<Pixel>();
 
 #[derive(Debug)]
-/// An error produced when using [`Icon::from_rgba`] with invalid arguments.
+/// An error produced when creating an [`Icon`] from invalid arguments or image data.
 pub enum BadIcon {
     /// Produced when the length of the `rgba` argument isn't divisible by 4, thus `rgba` can't be
     /// safely interpreted as 32bpp RGBA pixels.
@@ -23,8 +31,17 @@ pub enum BadIcon {
     /// Produced when the number of pixels (`rgba.len() / 4`) isn't equal to `width * height`.
     /// At least one of your arguments is incorrect.
     DimensionsVsPixelCount { width: u32, height: u32, width_x_height: usize, pixel_count: usize },
-    /// Produced when underlying OS functionality failed to create the icon
+    /// Produced when underlying OS functionality failed to create the icon, or when the icon
+    /// file couldn't be read.
     OsError(io::Error),
+    /// Produced when the image data is truncated or otherwise invalid.
+    Malformed,
+    /// Produced when the image data uses a format or encoding that isn't supported, for example
+    /// PNG without the `png` feature, JPEG 2000 in `.icns` files, or images larger than
+    /// [`MAX_CURSOR_SIZE`][crate::cursor::MAX_CURSOR_SIZE] in the decoded files.
+    Unsupported,
+    /// Produced when the icon file contains no image.
+    NoImage,
 }
 
 impl fmt::Display for BadIcon {
@@ -44,6 +61,9 @@ impl fmt::Display for BadIcon {
                 )
             },
             BadIcon::OsError(e) => write!(f, "OS error when instantiating the icon: {e:?}"),
+            BadIcon::Malformed => write!(f, "The icon image data is malformed."),
+            BadIcon::Unsupported => write!(f, "The icon image format or encoding isn't supported."),
+            BadIcon::NoImage => write!(f, "The icon file contains no image."),
         }
     }
 }
@@ -57,6 +77,10 @@ pub(crate) struct RgbaIcon {
     pub(crate) height: u32,
 }
 
+/// The images of an icon, in increasing size.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct RgbaIcons(pub(crate) Vec<RgbaIcon>);
+
 /// For platforms which don't have window icons (e.g. Web)
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub(crate) struct NoIcon;
@@ -84,12 +108,52 @@ mod constructors {
         }
     }
 
+    impl RgbaIcons {
+        pub fn from_rgba(rgba: Vec<u8>, width: u32, height: u32) -> Result<Self, BadIcon> {
+            Ok(RgbaIcons(vec![RgbaIcon::from_rgba(rgba, width, height)?]))
+        }
+
+        pub fn from_rgba_set(images: Vec<RgbaIcon>) -> Result<Self, BadIcon> {
+            Ok(RgbaIcons(images))
+        }
+
+        #[cfg(feature = "svg")]
+        pub fn from_svg(svg: SvgIcon) -> Result<Self, BadIcon> {
+            Ok(svg.rasterize_set())
+        }
+    }
+
     impl NoIcon {
         pub fn from_rgba(rgba: Vec<u8>, width: u32, height: u32) -> Result<Self, BadIcon> {
             // Create the rgba icon anyway to validate the input
             let _ = RgbaIcon::from_rgba(rgba, width, height)?;
             Ok(NoIcon)
         }
+
+        pub fn from_rgba_set(_images: Vec<RgbaIcon>) -> Result<Self, BadIcon> {
+            Ok(NoIcon)
+        }
+
+        #[cfg(feature = "svg")]
+        pub fn from_svg(_svg: SvgIcon) -> Result<Self, BadIcon> {
+            Ok(NoIcon)
+        }
+    }
+}
+
+/// An image of an icon with several sizes, see [`Icon::from_rgba_set`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IconImage {
+    pub(crate) inner: RgbaIcon,
+}
+
+impl IconImage {
+    /// Creates an icon image from 32bpp RGBA data.
+    ///
+    /// The length of `rgba` must be divisible by 4, and `width * height` must equal
+    /// `rgba.len() / 4`. Otherwise, this will return a `BadIcon` error.
+    pub fn from_rgba(rgba: Vec<u8>, width: u32, height: u32) -> Result<Self, BadIcon> {
+        Ok(IconImage { inner: RgbaIcon::from_rgba(rgba, width, height)? })
     }
 }
 
@@ -115,4 +179,85 @@ impl Icon {
 
         Ok(Icon { inner: PlatformIcon::from_rgba(rgba, width, height)? })
     }
+
+    /// Creates an icon from images of several sizes.
+    ///
+    /// The system picks the image of the size it needs for each place the icon is shown, like the
+    /// titlebar, the taskbar or the task switcher, instead of scaling a single image. Returns
+    /// [`BadIcon::NoImage`] if `images` is empty.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** The image closest to the size of the small and big icons at the scale factor
+    ///   of the window is used, and changed when the scale factor changes.
+    /// - **X11:** All the images are given to the window manager.
+    pub fn from_rgba_set(images: &[IconImage]) -> Result<Self, BadIcon> {
+        let _span =
+            tracing::debug_span!("winit::Icon::from_rgba_set", count = images.len()).entered();
+
+        if images.is_empty() {
+            return Err(BadIcon::NoImage);
+        }
+        let mut images: Vec<_> = images.iter().map(|image| image.inner.clone()).collect();
+        images.sort_by_key(|image| image.width);
+        Ok(Icon { inner: PlatformIcon::from_rgba_set(images)? })
+    }
+
+    /// Creates an icon from the content of a PNG file.
+    ///
+    /// Images larger than [`MAX_CURSOR_SIZE`][crate::cursor::MAX_CURSOR_SIZE] in either
+    /// dimension are rejected with [`BadIcon::Unsupported`] before being decoded.
+    #[cfg(feature = "png")]
+    pub fn from_png(bytes: &[u8]) -> Result<Self, BadIcon> {
+        let icon = decode::png(bytes)?;
+        Self::from_rgba(icon.rgba, icon.width, icon.height)
+    }
+
+    /// Creates an icon from the largest image of a Windows `.ico` file.
+    ///
+    /// The images compressed with PNG require the `png` feature.
+    pub fn from_ico(bytes: &[u8]) -> Result<Self, BadIcon> {
+        let icon = decode::ico(bytes)?;
+        Self::from_rgba(icon.rgba, icon.width, icon.height)
+    }
+
+    /// Creates an icon from the largest supported image of a macOS `.icns` file.
+    ///
+    /// The images compressed with PNG require the `png` feature, and the ones compressed with
+    /// JPEG 2000 aren't supported.
+    pub fn from_icns(bytes: &[u8]) -> Result<Self, BadIcon> {
+        let icon = decode::icns(bytes)?;
+        Self::from_rgba(icon.rgba, icon.width, icon.height)
+    }
+
+    /// Creates an icon from an SVG document, optionally gzip compressed.
+    ///
+    /// The icon is rasterized at the sizes the system needs.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** The icon is rasterized at the size of the small and big icons at the scale
+    ///   factor of the window, and again when the scale factor changes.
+    /// - **X11:** The icon is rasterized at the usual icon sizes, from 16 to 256 pixels, and the
+    ///   window manager picks one of them.
+    #[cfg(feature = "svg")]
+    pub fn from_svg(bytes: &[u8]) -> Result<Self, BadIcon> {
+        let _span = tracing::debug_span!("winit::Icon::from_svg").entered();
+
+        Ok(Icon { inner: PlatformIcon::from_svg(SvgIcon::new(bytes)?)? })
+    }
+
+    /// Creates an icon from a PNG, `.ico`, `.icns` or, with the `svg` feature, SVG file.
+    ///
+    /// The format is recognized from the content of the file rather than from its extension.
+    /// See [`Icon::from_ico`] and [`Icon::from_icns`] for the supported images.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, BadIcon> {
+        let bytes = fs::read(path).map_err(BadIcon::OsError)?;
+        #[cfg(feature = "svg")]
+        if svg::is_svg(&bytes) {
+            return Self::from_svg(&bytes);
+        }
+        let icon = decode::any(&bytes)?;
+        Self::from_rgba(icon.rgba, icon.width, icon.height)
+    }
 }
@@ -0,0 +1,248 @@
+//! Decoders of the icon file formats of Windows and macOS, picking their largest image.
+
+use super::BadIcon;
+use crate::cursor::decode::{self as cursor_decode, PNG_SIGNATURE};
+use crate::cursor::{BadCursorFile, BadImage};
+
+/// An icon image decoded from a file, with non-premultiplied RGBA pixels.
+#[derive(Debug)]
+pub(crate) struct DecodedIcon {
+    pub(crate) rgba: Vec<u8>,
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+}
+
+impl From<BadCursorFile> for BadIcon {
+    fn from(error: BadCursorFile) -> Self {
+        match error {
+            BadCursorFile::Malformed => BadIcon::Malformed,
+            BadCursorFile::Unsupported | BadCursorFile::BadImage(BadImage::TooLarge { .. }) => {
+                BadIcon::Unsupported
+            },
+            BadCursorFile::NoImage => BadIcon::NoImage,
+            BadCursorFile::BadImage(_) => BadIcon::Malformed,
+        }
+    }
+}
+
+/// Decode a file in any of the supported formats, recognized from its first bytes.
+pub(crate) fn any(bytes: &[u8]) -> Result<DecodedIcon, BadIcon> {
+    if bytes.starts_with(PNG_SIGNATURE) {
+        png(bytes)
+    } else if bytes.starts_with(b"icns") {
+        icns(bytes)
+    } else if bytes.starts_with(&[0, 0, 1, 0]) || bytes.starts_with(&[0, 0, 2, 0]) {
+        ico(bytes)
+    } else {
+        Err(BadIcon::Unsupported)
+    }
+}
+
+pub(crate) fn png(bytes: &[u8]) -> Result<DecodedIcon, BadIcon> {
+    let (rgba, width, height) = cursor_decode::png(bytes)?;
+    Ok(DecodedIcon { rgba, width: width.into(), height: height.into() })
+}
+
+/// Decode the largest image of a Windows `.ico` file.
+pub(crate) fn ico(bytes: &[u8]) -> Result<DecodedIcon, BadIcon> {
+    let image = cursor_decode::cur(bytes, u16::MAX)?;
+    Ok(DecodedIcon { rgba: image.rgba, width: image.width.into(), height: image.height.into() })
+}
+
+/// The size of the images of the `.icns` element types, or `None` for the other elements.
+fn icns_size(kind: &[u8]) -> Option<u32> {
+    Some(match kind {
+        b"is32" | b"ic04" | b"icp4" => 16,
+        b"il32" | b"ic05" | b"icp5" | b"ic11" => 32,
+        b"ih32" => 48,
+        b"icp6" | b"ic12" => 64,
+        b"it32" | b"ic07" => 128,
+        b"ic08" => 256,
+        b"ic09" | b"ic13" => 512,
+        b"ic10" | b"ic14" => 1024,
+        _ => return None,
+    })
+}
+
+/// The mask with the alpha channel of the legacy RGB images.
+fn icns_mask(kind: &[u8]) -> Option<&'static [u8; 4]> {
+    match kind {
+        b"is32" => Some(b"s8mk"),
+        b"il32" => Some(b"l8mk"),
+        b"ih32" => Some(b"h8mk"),
+        b"it32" => Some(b"t8mk"),
+        _ => None,
+    }
+}
+
+/// Decode the largest supported image of a macOS `.icns` file.
+///
+/// The images are PNG, JPEG 2000, or RGB and ARGB compressed with a run-length encoding. JPEG
+/// 2000 isn't supported.
+pub(crate) fn icns(bytes: &[u8]) -> Result<DecodedIcon, BadIcon> {
+    let u32_at = |bytes: &[u8], offset: usize| {
+        bytes
+            .get(offset..offset + 4)
+            .map(|bytes| u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+            .ok_or(BadIcon::Malformed)
+    };
+
+    if !bytes.starts_with(b"icns") {
+        return Err(BadIcon::Malformed);
+    }
+    let len = (u32_at(bytes, 4)? as usize).min(bytes.len());
+
+    // The elements have a type and a length including their 8 bytes header.
+    let mut elements = Vec::new();
+    let mut offset = 8;
+    while offset + 8 <= len {
+        let element_len = u32_at(bytes, offset + 4)? as usize;
+        let data = element_len
+            .checked_sub(8)
+            .and_then(|data_len| bytes.get(offset + 8..offset + 8 + data_len))
+            .ok_or(BadIcon::Malformed)?;
+        elements.push((&bytes[offset..offset + 4], data));
+        offset += element_len;
+    }
+
+    let mut images: Vec<_> =
+        elements.iter().filter_map(|&(kind, data)| Some((icns_size(kind)?, kind, data))).collect();
+    images.sort_by_key(|&(size, ..)| u32::MAX - size);
+    if images.is_empty() {
+        return Err(BadIcon::NoImage);
+    }
+
+    let mask = |kind| {
+        let mask = icns_mask(kind)?;
+        elements.iter().find(|&&(mask_kind, _)| mask_kind == mask).map(|&(_, data)| data)
+    };
+    let mut last_error = BadIcon::Unsupported;
+    for (size, kind, data) in images {
+        let result = if data.starts_with(PNG_SIGNATURE) {
+            png(data)
+        } else if let Some(argb) = data.strip_prefix(b"ARGB") {
+            icns_argb(argb, size)
+        } else if icns_mask(kind).is_some() {
+            icns_rgb(data, mask(kind), kind == b"it32", size)
+        } else {
+            // JPEG 2000.
+            Err(BadIcon::Unsupported)
+        };
+        match result {
+            Ok(icon) => return Ok(icon),
+            Err(error) => last_error = error,
+        }
+    }
+    Err(last_error)
+}
+
+/// Decode the channels of an image compressed with the run-length encoding of `.icns` files,
+/// every channel after the other.
+fn icns_rle(data: &[u8], len: usize) -> Result<Vec<u8>, BadIcon> {
+    let mut channels = Vec::with_capacity(len);
+    let mut data = data.iter().copied();
+    let mut next = || data.next().ok_or(BadIcon::Malformed);
+    while channels.len() < len {
+        let header = next()?;
+        if header < 0x80 {
+            // Copy the next bytes.
+            for _ in 0..=header {
+                channels.push(next()?);
+            }
+        } else {
+            // Repeat the next byte.
+            let byte = next()?;
+            channels.extend(std::iter::repeat(byte).take(header as usize - 125));
+        }
+    }
+    channels.truncate(len);
+    Ok(channels)
+}
+
+fn icns_argb(data: &[u8], size: u32) -> Result<DecodedIcon, BadIcon> {
+    let pixels = (size * size) as usize;
+    let channels = icns_rle(data, pixels * 4)?;
+    let rgba = (0..pixels)
+        .flat_map(|i| {
+            let [a, r, g, b] = [0, 1, 2, 3].map(|channel| channels[channel * pixels + i]);
+            [r, g, b, a]
+        })
+        .collect();
+    Ok(DecodedIcon { rgba, width: size, height: size })
+}
+
+fn icns_rgb(
+    data: &[u8],
+    mask: Option<&[u8]>,
+    padded: bool,
+    size: u32,
+) -> Result<DecodedIcon, BadIcon> {
+    let pixels = (size * size) as usize;
+    // The 128x128 images start with 4 zero bytes.
+    let data = if padded { data.get(4..).ok_or(BadIcon::Malformed)? } else { data };
+    let rgb: Vec<_> = if data.len() == pixels * 4 {
+        // Uncompressed, as ARGB with an unused alpha.
+        data.chunks_exact(4).flat_map(|argb| [argb[1], argb[2], argb[3]]).collect()
+    } else {
+        let channels = icns_rle(data, pixels * 3)?;
+        (0..pixels).flat_map(|i| [0, 1, 2].map(|channel| channels[channel * pixels + i])).collect()
+    };
+    let alpha = |i: usize| mask.and_then(|mask| mask.get(i).copied()).unwrap_or(u8::MAX);
+    let rgba = rgb
+        .chunks_exact(3)
+        .enumerate()
+        .flat_map(|(i, rgb)| [rgb[0], rgb[1], rgb[2], alpha(i)])
+        .collect();
+    Ok(DecodedIcon { rgba, width: size, height: size })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn icns_file(elements: &[(&[u8; 4], &[u8])]) -> Vec<u8> {
+        let mut body = Vec::new();
+        for (kind, data) in elements {
+            body.extend_from_slice(*kind);
+            body.extend_from_slice(&(data.len() as u32 + 8).to_be_bytes());
+            body.extend_from_slice(data);
+        }
+        let mut file = b"icns".to_vec();
+        file.extend_from_slice(&(body.len() as u32 + 8).to_be_bytes());
+        file.extend(body);
+        file
+    }
+
+    #[test]
+    fn decode_rle() {
+        // A copy of 2 bytes, then a run of 3 bytes.
+        assert_eq!(icns_rle(&[1, 10, 20, 0x80, 30], 5).unwrap(), [10, 20, 30, 30, 30]);
+        assert!(matches!(icns_rle(&[1, 10], 2), Err(BadIcon::Malformed)));
+    }
+
+    #[test]
+    fn decode_icns_rgb_with_mask() {
+        // 16x16 red pixels, with runs of 128 and 128 for every channel.
+        let channel = |value| [0xfd, value, 0xfd, value];
+        let data: Vec<u8> = [channel(255), channel(0), channel(0)].concat();
+        let mask = [128; 256];
+        let icon = icns(&icns_file(&[(b"is32", &data), (b"s8mk", &mask)])).unwrap();
+        assert_eq!((icon.width, icon.height), (16, 16));
+        assert_eq!(&icon.rgba[..4], [255, 0, 0, 128]);
+        assert_eq!(icon.rgba.len(), 16 * 16 * 4);
+    }
+
+    #[test]
+    fn icns_largest_supported_image() {
+        let channel = |value| [0xfd, value, 0xfd, value];
+        let argb = [&b"ARGB"[..], &channel(255), &channel(0), &channel(0), &channel(255)].concat();
+        // The JPEG 2000 image is skipped.
+        let file = icns_file(&[(b"ic05", b"\0\0\0\x0cjP  "), (b"ic04", &argb)]);
+        let icon = icns(&file).unwrap();
+        assert_eq!((icon.width, icon.height), (16, 16));
+        assert_eq!(&icon.rgba[..4], [0, 0, 255, 255]);
+
+        assert!(matches!(icns(&icns_file(&[(b"info", b"")])), Err(BadIcon::NoImage)));
+        assert!(matches!(any(b"GIF89a"), Err(BadIcon::Unsupported)));
+    }
+}
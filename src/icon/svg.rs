@@ -0,0 +1,104 @@
+//! Rasterization of SVG icons.
+
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use resvg::{tiny_skia, usvg};
+
+use super::{BadIcon, RgbaIcon, RgbaIcons};
+
+/// The sizes SVG icons are rasterized at for the platforms taking all the images of an icon.
+const SIZES: [u32; 8] = [16, 24, 32, 48, 64, 96, 128, 256];
+
+/// Whether the data looks like an SVG document, or a gzip compressed one.
+pub(crate) fn is_svg(bytes: &[u8]) -> bool {
+    let start = bytes.iter().position(|byte| !byte.is_ascii_whitespace()).unwrap_or(bytes.len());
+    let bytes = &bytes[start..];
+    bytes.starts_with(b"<svg") || bytes.starts_with(b"<?xml") || bytes.starts_with(&[0x1f, 0x8b])
+}
+
+/// A parsed SVG icon, rasterized at the size the platform needs.
+#[derive(Clone)]
+pub(crate) struct SvgIcon {
+    data: Arc<[u8]>,
+    tree: Arc<usvg::Tree>,
+}
+
+impl SvgIcon {
+    pub(crate) fn new(data: &[u8]) -> Result<Self, BadIcon> {
+        let tree = usvg::Tree::from_data(data, &usvg::Options::default())
+            .map_err(|_| BadIcon::Malformed)?;
+        Ok(Self { data: data.into(), tree: Arc::new(tree) })
+    }
+
+    /// Rasterize the icon in a square image, keeping its aspect ratio.
+    pub(crate) fn rasterize(&self, size: u32) -> RgbaIcon {
+        let mut pixmap = tiny_skia::Pixmap::new(size, size).expect("icon size is zero");
+        let tree_size = self.tree.size();
+        let scale = size as f32 / tree_size.width().max(tree_size.height());
+        let transform = tiny_skia::Transform::from_scale(scale, scale).post_translate(
+            (size as f32 - tree_size.width() * scale) / 2.,
+            (size as f32 - tree_size.height() * scale) / 2.,
+        );
+        resvg::render(&self.tree, transform, &mut pixmap.as_mut());
+
+        let rgba = pixmap
+            .pixels()
+            .iter()
+            .flat_map(|pixel| {
+                let color = pixel.demultiply();
+                [color.red(), color.green(), color.blue(), color.alpha()]
+            })
+            .collect();
+        RgbaIcon { rgba, width: size, height: size }
+    }
+
+    /// Rasterize the icon at the usual icon sizes.
+    pub(crate) fn rasterize_set(&self) -> RgbaIcons {
+        RgbaIcons(SIZES.iter().map(|&size| self.rasterize(size)).collect())
+    }
+}
+
+impl PartialEq for SvgIcon {
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data
+    }
+}
+
+impl Eq for SvgIcon {}
+
+impl Hash for SvgIcon {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.data.hash(state);
+    }
+}
+
+impl fmt::Debug for SvgIcon {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let size = self.tree.size();
+        f.debug_struct("SvgIcon")
+            .field("width", &size.width())
+            .field("height", &size.height())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rasterize_keeps_aspect_ratio() {
+        let svg = br#"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="10">
+            <rect width="20" height="10" fill="red"/>
+        </svg>"#;
+        assert!(is_svg(svg));
+        let icon = SvgIcon::new(svg).unwrap().rasterize(16);
+        assert_eq!((icon.width, icon.height), (16, 16));
+        let pixel = |x: usize, y: usize| &icon.rgba[(y * 16 + x) * 4..][..4];
+        // The rectangle is centered vertically.
+        assert_eq!(pixel(0, 0), [0, 0, 0, 0]);
+        assert_eq!(pixel(8, 8), [255, 0, 0, 255]);
+    }
+}
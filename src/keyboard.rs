@@ -69,11 +69,17 @@
 //
 // --------- END OF W3C SHORT NOTICE ---------------------------------------------------------------
 
+use std::error::Error;
+use std::str::FromStr;
+use std::{fmt, iter};
+
 use bitflags::bitflags;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 pub use smol_str::SmolStr;
 
+use crate::event::{ElementState, KeyEvent};
+
 /// Contains the platform-native physical key identifier
 ///
 /// The exact values vary from platform to platform (which is part of why this is a per-platform
@@ -1614,6 +1620,38 @@ impl Key {
             _ => None,
         }
     }
+
+    /// Whether a key producing `unmodified` without modifiers produces this key, comparing the
+    /// characters ignoring their case like shortcuts do.
+    #[allow(dead_code)] // Not used on every platform
+    pub(crate) fn is_produced_by(&self, unmodified: &Key) -> bool {
+        match (self, unmodified) {
+            (Key::Character(key), Key::Character(unmodified)) => {
+                key.to_lowercase() == unmodified.to_lowercase()
+            },
+            (Key::Character(key), Key::Dead(Some(unmodified))) => {
+                key.chars().eq(iter::once(*unmodified))
+            },
+            (key, unmodified) => key == unmodified,
+        }
+    }
+
+    /// The label of a key producing this logical key without modifiers, written in uppercase
+    /// like on the keycaps.
+    ///
+    /// Returns `None` for keys which don't produce a visible character.
+    #[allow(dead_code)] // Not used on every platform
+    pub(crate) fn to_label(&self) -> Option<String> {
+        let label = match self {
+            Key::Character(ch) => ch.to_string(),
+            Key::Dead(Some(ch)) => ch.to_string(),
+            _ => return None,
+        };
+        if label.trim().is_empty() || label.chars().any(char::is_control) {
+            return None;
+        }
+        Some(label.to_uppercase())
+    }
 }
 
 /// The location of the key on the keyboard.
@@ -1746,6 +1784,429 @@ pub enum ModifiersKeyState {
     Unknown,
 }
 
+/// A keyboard layout, or input source, chosen by the user.
+///
+/// Returned by [`ActiveEventLoop::keyboard_layout()`], and sent with
+/// [`WindowEvent::KeyboardLayoutChanged`] when the user switches to another one.
+///
+/// [`ActiveEventLoop::keyboard_layout()`]: crate::event_loop::ActiveEventLoop::keyboard_layout()
+/// [`WindowEvent::KeyboardLayoutChanged`]: crate::event::WindowEvent::KeyboardLayoutChanged
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct KeyboardLayout {
+    /// An identifier of the layout, which doesn't change across sessions.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** The name of the keyboard layout identifier, like `00000409`.
+    /// - **macOS:** The identifier of the input source, like `com.apple.keylayout.US`.
+    /// - **Wayland / X11:** The same as the name, as keymaps don't carry a shorter identifier.
+    pub id: String,
+
+    /// The name of the layout, meant to be shown to the user.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** The localized name of the language of the layout, like `English (United
+    ///   States)`.
+    /// - **macOS:** The localized name of the input source, like `U.S.`.
+    /// - **Wayland / X11:** The name of the layout in the keymap, like `English (US)`.
+    pub name: String,
+}
+
+/// A lock of the keyboard with a LED, see [`ActiveEventLoop::set_keyboard_led()`].
+///
+/// [`ActiveEventLoop::set_keyboard_led()`]: crate::event_loop::ActiveEventLoop::set_keyboard_led()
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Led {
+    /// The Caps Lock.
+    CapsLock,
+    /// The Num Lock.
+    NumLock,
+    /// The Scroll Lock.
+    ScrollLock,
+}
+
+/// A keyboard shortcut, like `Ctrl+Shift+P`: a key pressed while holding modifiers.
+///
+/// Shortcuts can be parsed from strings, matched against the [`KeyEvent`]s of
+/// [`WindowEvent::KeyboardInput`] with [`Shortcut::matches()`], and shown in menus the way the
+/// platform does with [`Shortcut::to_display_string()`].
+///
+/// ```
+/// use winit::keyboard::{Key, ModifiersState, Shortcut};
+///
+/// let shortcut: Shortcut = "Ctrl+Shift+P".parse().unwrap();
+/// assert_eq!(shortcut.modifiers, ModifiersState::CONTROL | ModifiersState::SHIFT);
+/// assert_eq!(shortcut.key, Key::Character("p".into()));
+/// assert_eq!(shortcut.to_string(), "Ctrl+Shift+P");
+/// ```
+///
+/// The modifiers are `Ctrl`, `Alt`, `Shift` and `Super`, with the aliases `Control`, `Option`,
+/// `Cmd`, `Command`, `Meta` and `Win`. `CmdOrCtrl` is `Super` on macOS and iOS, and `Ctrl`
+/// elsewhere. The key is either a single character, or one of `Enter`, `Tab`, `Space`,
+/// `Backspace`, `Delete`, `Escape`, `Insert`, `Home`, `End`, `PageUp`, `PageDown`, `Up`, `Down`,
+/// `Left`, `Right`, `Plus` and `F1` to `F24`. Names are case-insensitive.
+///
+/// [`KeyEvent`]: crate::event::KeyEvent
+/// [`WindowEvent::KeyboardInput`]: crate::event::WindowEvent::KeyboardInput
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Shortcut {
+    /// The modifiers held while pressing the key.
+    pub modifiers: ModifiersState,
+    /// The key, with characters in lowercase.
+    pub key: Key,
+}
+
+impl Shortcut {
+    /// Creates a shortcut, lowercasing the character of `key`.
+    pub fn new(modifiers: ModifiersState, key: Key) -> Self {
+        let key = match key {
+            Key::Character(character) => Key::Character(character.to_lowercase().into()),
+            key => key,
+        };
+        Self { modifiers, key }
+    }
+
+    /// Returns `true` if `event` triggers the shortcut while `modifiers` are held.
+    ///
+    /// Only presses match, repeated ones included. Keys are matched the way users expect
+    /// shortcuts to follow their keyboard layout:
+    ///
+    /// - Characters are compared ignoring their case.
+    /// - Shift is ignored when the shortcut doesn't use it and the character has no case, as it's
+    ///   often needed to type the character: `Ctrl++` matches Ctrl+Shift+= on a US layout.
+    /// - When the layout doesn't produce a Latin character, like Cyrillic layouts or Option on
+    ///   macOS do, letters and digits match the key at their position on a US layout, so `Ctrl+C`
+    ///   keeps working.
+    pub fn matches(&self, event: &KeyEvent, modifiers: ModifiersState) -> bool {
+        event.state == ElementState::Pressed
+            && self.matches_key(&event.logical_key, event.physical_key, modifiers)
+    }
+
+    fn matches_key(
+        &self,
+        logical_key: &Key,
+        physical_key: PhysicalKey,
+        modifiers: ModifiersState,
+    ) -> bool {
+        let mut modifiers = modifiers & ModifiersState::all();
+        let Key::Character(expected) = &self.key else {
+            return self.key == *logical_key && modifiers == self.modifiers;
+        };
+
+        let expected = expected.to_lowercase();
+        let actual = match logical_key {
+            Key::Character(actual) => Some(actual.as_str()),
+            _ => None,
+        };
+        let matches = match actual {
+            Some(actual) if actual.is_ascii() || !expected.is_ascii() => {
+                actual.to_lowercase() == expected
+            },
+            _ => us_layout_character(physical_key)
+                .is_some_and(|c| expected.chars().eq(iter::once(c))),
+        };
+
+        let caseless = actual.map_or(true, |actual| actual.to_lowercase() == actual.to_uppercase());
+        if caseless && !self.modifiers.shift_key() {
+            modifiers.remove(ModifiersState::SHIFT);
+        }
+        matches && modifiers == self.modifiers
+    }
+
+    /// The shortcut the way the platform shows it in menus, like `⇧⌘P` on macOS and iOS, and
+    /// `Ctrl+Shift+P` elsewhere.
+    ///
+    /// Unlike the [`Display`][fmt::Display] implementation, which can be parsed back, the super
+    /// key is named after the platform, like `Win` on Windows.
+    pub fn to_display_string(&self) -> String {
+        let style = if cfg!(any(macos_platform, ios_platform)) {
+            ShortcutStyle::Apple
+        } else if cfg!(windows_platform) {
+            ShortcutStyle::Windows
+        } else {
+            ShortcutStyle::Default
+        };
+        self.format(style)
+    }
+
+    fn format(&self, style: ShortcutStyle) -> String {
+        let mut string = String::new();
+        if style == ShortcutStyle::Apple {
+            // The order of the Apple Human Interface Guidelines.
+            for (modifier, symbol) in [
+                (ModifiersState::CONTROL, "⌃"),
+                (ModifiersState::ALT, "⌥"),
+                (ModifiersState::SHIFT, "⇧"),
+                (ModifiersState::SUPER, "⌘"),
+            ] {
+                if self.modifiers.contains(modifier) {
+                    string.push_str(symbol);
+                }
+            }
+        } else {
+            let super_name = if style == ShortcutStyle::Windows { "Win" } else { "Super" };
+            for (modifier, name) in [
+                (ModifiersState::CONTROL, "Ctrl"),
+                (ModifiersState::ALT, "Alt"),
+                (ModifiersState::SHIFT, "Shift"),
+                (ModifiersState::SUPER, super_name),
+            ] {
+                if self.modifiers.contains(modifier) {
+                    string.push_str(name);
+                    string.push('+');
+                }
+            }
+        }
+
+        match &self.key {
+            Key::Character(character) if character == "+" && style != ShortcutStyle::Apple => {
+                string.push_str("Plus")
+            },
+            Key::Character(character) => string.push_str(&character.to_uppercase()),
+            Key::Named(named) => {
+                let symbol = APPLE_KEY_SYMBOLS
+                    .iter()
+                    .filter(|_| style == ShortcutStyle::Apple)
+                    .chain(NAMED_KEYS)
+                    .find(|(key, _)| key == named)
+                    .map(|(_, name)| name.to_string());
+                let function_key = F_KEYS
+                    .iter()
+                    .position(|key| key == named)
+                    .map(|index| format!("F{}", index + 1));
+                string.push_str(&symbol.or(function_key).unwrap_or_else(|| format!("{named:?}")));
+            },
+            key => string.push_str(&format!("{key:?}")),
+        }
+        string
+    }
+}
+
+impl fmt::Display for Shortcut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.format(ShortcutStyle::Default))
+    }
+}
+
+impl FromStr for Shortcut {
+    type Err = ParseShortcutError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        // A trailing `+` is the key itself, like in `Ctrl++`.
+        let (modifiers, key) = if s == "+" {
+            ("", "+")
+        } else if let Some(modifiers) = s.strip_suffix("++") {
+            (modifiers, "+")
+        } else {
+            s.rsplit_once('+').unwrap_or(("", s))
+        };
+
+        let mut state = ModifiersState::empty();
+        if !modifiers.is_empty() {
+            for name in modifiers.split('+') {
+                state |= parse_modifier(name.trim())?;
+            }
+        }
+        Ok(Self::new(state, parse_key(key.trim())?))
+    }
+}
+
+/// An error produced when parsing a [`Shortcut`] from a string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseShortcutError {
+    /// The string doesn't end with a key.
+    MissingKey,
+    /// A modifier isn't one of the known names.
+    UnknownModifier(String),
+    /// The key is neither a single character nor one of the known names.
+    UnknownKey(String),
+}
+
+impl fmt::Display for ParseShortcutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingKey => f.write_str("The shortcut doesn't end with a key."),
+            Self::UnknownModifier(name) => write!(f, "Unknown modifier {name:?} in the shortcut."),
+            Self::UnknownKey(name) => write!(f, "Unknown key {name:?} in the shortcut."),
+        }
+    }
+}
+
+impl Error for ParseShortcutError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ShortcutStyle {
+    Default,
+    Windows,
+    Apple,
+}
+
+const NAMED_KEYS: &[(NamedKey, &str)] = &[
+    (NamedKey::Enter, "Enter"),
+    (NamedKey::Tab, "Tab"),
+    (NamedKey::Space, "Space"),
+    (NamedKey::Backspace, "Backspace"),
+    (NamedKey::Delete, "Delete"),
+    (NamedKey::Escape, "Escape"),
+    (NamedKey::Insert, "Insert"),
+    (NamedKey::Home, "Home"),
+    (NamedKey::End, "End"),
+    (NamedKey::PageUp, "PageUp"),
+    (NamedKey::PageDown, "PageDown"),
+    (NamedKey::ArrowUp, "Up"),
+    (NamedKey::ArrowDown, "Down"),
+    (NamedKey::ArrowLeft, "Left"),
+    (NamedKey::ArrowRight, "Right"),
+];
+
+const APPLE_KEY_SYMBOLS: &[(NamedKey, &str)] = &[
+    (NamedKey::Enter, "↩"),
+    (NamedKey::Tab, "⇥"),
+    (NamedKey::Backspace, "⌫"),
+    (NamedKey::Delete, "⌦"),
+    (NamedKey::Escape, "⎋"),
+    (NamedKey::Home, "↖"),
+    (NamedKey::End, "↘"),
+    (NamedKey::PageUp, "⇞"),
+    (NamedKey::PageDown, "⇟"),
+    (NamedKey::ArrowUp, "↑"),
+    (NamedKey::ArrowDown, "↓"),
+    (NamedKey::ArrowLeft, "←"),
+    (NamedKey::ArrowRight, "→"),
+];
+
+const F_KEYS: [NamedKey; 24] = [
+    NamedKey::F1,
+    NamedKey::F2,
+    NamedKey::F3,
+    NamedKey::F4,
+    NamedKey::F5,
+    NamedKey::F6,
+    NamedKey::F7,
+    NamedKey::F8,
+    NamedKey::F9,
+    NamedKey::F10,
+    NamedKey::F11,
+    NamedKey::F12,
+    NamedKey::F13,
+    NamedKey::F14,
+    NamedKey::F15,
+    NamedKey::F16,
+    NamedKey::F17,
+    NamedKey::F18,
+    NamedKey::F19,
+    NamedKey::F20,
+    NamedKey::F21,
+    NamedKey::F22,
+    NamedKey::F23,
+    NamedKey::F24,
+];
+
+fn parse_modifier(name: &str) -> Result<ModifiersState, ParseShortcutError> {
+    Ok(match name.to_ascii_lowercase().as_str() {
+        "ctrl" | "control" => ModifiersState::CONTROL,
+        "alt" | "option" => ModifiersState::ALT,
+        "shift" => ModifiersState::SHIFT,
+        "super" | "cmd" | "command" | "meta" | "win" => ModifiersState::SUPER,
+        "cmdorctrl" if cfg!(any(macos_platform, ios_platform)) => ModifiersState::SUPER,
+        "cmdorctrl" => ModifiersState::CONTROL,
+        _ => return Err(ParseShortcutError::UnknownModifier(name.to_owned())),
+    })
+}
+
+fn parse_key(name: &str) -> Result<Key, ParseShortcutError> {
+    let mut chars = name.chars();
+    match (chars.next(), chars.next()) {
+        (None, _) => return Err(ParseShortcutError::MissingKey),
+        (Some(_), None) => return Ok(Key::Character(name.into())),
+        _ => (),
+    }
+
+    let lowercase = name.to_ascii_lowercase();
+    let named = match lowercase.as_str() {
+        "plus" => return Ok(Key::Character("+".into())),
+        "return" => Some(NamedKey::Enter),
+        "del" => Some(NamedKey::Delete),
+        "esc" => Some(NamedKey::Escape),
+        "ins" => Some(NamedKey::Insert),
+        "pgup" => Some(NamedKey::PageUp),
+        "pgdn" => Some(NamedKey::PageDown),
+        "arrowup" => Some(NamedKey::ArrowUp),
+        "arrowdown" => Some(NamedKey::ArrowDown),
+        "arrowleft" => Some(NamedKey::ArrowLeft),
+        "arrowright" => Some(NamedKey::ArrowRight),
+        _ => NAMED_KEYS.iter().find(|(_, key)| key.eq_ignore_ascii_case(name)).map(|(key, _)| *key),
+    };
+    let function_key = || {
+        let number: usize = lowercase.strip_prefix('f')?.parse().ok()?;
+        F_KEYS.get(number.checked_sub(1)?).copied()
+    };
+    named
+        .or_else(function_key)
+        .map(Key::Named)
+        .ok_or_else(|| ParseShortcutError::UnknownKey(name.to_owned()))
+}
+
+/// The character of the key at the position of `physical_key` on a US layout, for the letters and
+/// digits.
+fn us_layout_character(physical_key: PhysicalKey) -> Option<char> {
+    const LETTERS: [KeyCode; 26] = [
+        KeyCode::KeyA,
+        KeyCode::KeyB,
+        KeyCode::KeyC,
+        KeyCode::KeyD,
+        KeyCode::KeyE,
+        KeyCode::KeyF,
+        KeyCode::KeyG,
+        KeyCode::KeyH,
+        KeyCode::KeyI,
+        KeyCode::KeyJ,
+        KeyCode::KeyK,
+        KeyCode::KeyL,
+        KeyCode::KeyM,
+        KeyCode::KeyN,
+        KeyCode::KeyO,
+        KeyCode::KeyP,
+        KeyCode::KeyQ,
+        KeyCode::KeyR,
+        KeyCode::KeyS,
+        KeyCode::KeyT,
+        KeyCode::KeyU,
+        KeyCode::KeyV,
+        KeyCode::KeyW,
+        KeyCode::KeyX,
+        KeyCode::KeyY,
+        KeyCode::KeyZ,
+    ];
+    const DIGITS: [KeyCode; 10] = [
+        KeyCode::Digit0,
+        KeyCode::Digit1,
+        KeyCode::Digit2,
+        KeyCode::Digit3,
+        KeyCode::Digit4,
+        KeyCode::Digit5,
+        KeyCode::Digit6,
+        KeyCode::Digit7,
+        KeyCode::Digit8,
+        KeyCode::Digit9,
+    ];
+
+    let PhysicalKey::Code(code) = physical_key else {
+        return None;
+    };
+    if let Some(index) = LETTERS.iter().position(|key| *key == code) {
+        Some((b'a' + index as u8) as char)
+    } else {
+        DIGITS.iter().position(|key| *key == code).map(|index| (b'0' + index as u8) as char)
+    }
+}
+
 // NOTE: the exact modifier key is not used to represent modifiers state in the
 // first place due to a fact that modifiers state could be changed without any
 // key being pressed and on some platforms like Wayland/X11 which key resulted
@@ -1768,3 +2229,108 @@ bitflags! {
         const RSUPER   = 0b1000_0000;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shortcut(s: &str) -> Shortcut {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn key_labels() {
+        assert_eq!(Key::Character("z".into()).to_label().as_deref(), Some("Z"));
+        assert_eq!(Key::Character("é".into()).to_label().as_deref(), Some("É"));
+        assert_eq!(Key::Dead(Some('^')).to_label().as_deref(), Some("^"));
+        assert_eq!(Key::Character("\r".into()).to_label(), None);
+        assert_eq!(Key::Character(" ".into()).to_label(), None);
+        assert_eq!(Key::Named(NamedKey::Shift).to_label(), None);
+    }
+
+    #[test]
+    fn keys_produced_without_modifiers() {
+        assert!(Key::Character("Z".into()).is_produced_by(&Key::Character("z".into())));
+        assert!(Key::Character("^".into()).is_produced_by(&Key::Dead(Some('^'))));
+        assert!(Key::Named(NamedKey::Enter).is_produced_by(&Key::Named(NamedKey::Enter)));
+        assert!(!Key::Character("z".into()).is_produced_by(&Key::Character("w".into())));
+    }
+
+    #[test]
+    fn parse_shortcut() {
+        assert_eq!(
+            shortcut("Ctrl+Shift+P"),
+            Shortcut::new(
+                ModifiersState::CONTROL | ModifiersState::SHIFT,
+                Key::Character("p".into())
+            )
+        );
+        assert_eq!(shortcut(" cmd + alt + f12 "), Shortcut {
+            modifiers: ModifiersState::SUPER | ModifiersState::ALT,
+            key: Key::Named(NamedKey::F12),
+        });
+        assert_eq!(shortcut("Ctrl++").key, Key::Character("+".into()));
+        assert_eq!(shortcut("Ctrl+Plus"), shortcut("Ctrl++"));
+        assert_eq!(
+            shortcut("+"),
+            Shortcut::new(ModifiersState::empty(), Key::Character("+".into()))
+        );
+        assert_eq!(shortcut("Esc").key, Key::Named(NamedKey::Escape));
+
+        assert_eq!("".parse::<Shortcut>(), Err(ParseShortcutError::MissingKey));
+        assert_eq!("Ctrl+".parse::<Shortcut>(), Err(ParseShortcutError::MissingKey));
+        assert_eq!(
+            "Hyper+P".parse::<Shortcut>(),
+            Err(ParseShortcutError::UnknownModifier("Hyper".into()))
+        );
+        assert_eq!(
+            "Ctrl+F25".parse::<Shortcut>(),
+            Err(ParseShortcutError::UnknownKey("F25".into()))
+        );
+    }
+
+    #[test]
+    fn match_shortcut() {
+        let key = |c: &str| Key::Character(c.into());
+        let code = PhysicalKey::Code;
+        let ctrl = ModifiersState::CONTROL;
+        let shift = ModifiersState::SHIFT;
+
+        let save = shortcut("Ctrl+S");
+        assert!(save.matches_key(&key("s"), code(KeyCode::KeyS), ctrl));
+        assert!(!save.matches_key(&key("S"), code(KeyCode::KeyS), ctrl | shift));
+        assert!(!save.matches_key(&key("s"), code(KeyCode::KeyS), ModifiersState::empty()));
+        // A Cyrillic layout.
+        assert!(save.matches_key(&key("ы"), code(KeyCode::KeyS), ctrl));
+        // An AZERTY layout.
+        assert!(shortcut("Ctrl+A").matches_key(&key("a"), code(KeyCode::KeyQ), ctrl));
+        assert!(!shortcut("Ctrl+Q").matches_key(&key("a"), code(KeyCode::KeyQ), ctrl));
+
+        assert!(shortcut("Ctrl+Shift+P").matches_key(&key("P"), code(KeyCode::KeyP), ctrl | shift));
+        assert!(shortcut("Ctrl++").matches_key(&key("+"), code(KeyCode::Equal), ctrl | shift));
+        assert!(shortcut("Shift+Tab").matches_key(
+            &Key::Named(NamedKey::Tab),
+            code(KeyCode::Tab),
+            shift
+        ));
+        assert!(!shortcut("Tab").matches_key(
+            &Key::Named(NamedKey::Tab),
+            code(KeyCode::Tab),
+            shift
+        ));
+    }
+
+    #[test]
+    fn display_shortcut() {
+        let word_left = shortcut("Super+Shift+Ctrl+Left");
+        assert_eq!(word_left.to_string(), "Ctrl+Shift+Super+Left");
+        assert_eq!(word_left.format(ShortcutStyle::Windows), "Ctrl+Shift+Win+Left");
+        assert_eq!(word_left.format(ShortcutStyle::Apple), "⌃⇧⌘←");
+
+        let zoom = shortcut("Ctrl++");
+        assert_eq!(zoom.to_string(), "Ctrl+Plus");
+        assert_eq!(zoom.format(ShortcutStyle::Apple), "⌃+");
+        assert_eq!(shortcut("Alt+F4").format(ShortcutStyle::Apple), "⌥F4");
+        assert_eq!(shortcut("Alt+p").to_string(), "Alt+P");
+    }
+}
@@ -143,6 +143,17 @@
 //! * `rwh_06`: Implement `raw-window-handle v0.6` traits.
 //! * `serde`: Enables serialization/deserialization of certain types with [Serde](https://crates.io/crates/serde).
 //! * `mint`: Enables mint (math interoperability standard types) conversions.
+//! * `gestures`: Enables the `gestures` module, recognizing gestures from the pointer events.
+//! * `dialogs`: Enables the `dialogs` module, showing native file and folder pickers.
+//! * `gamepad`: Enables the `gamepad` module, reporting the input of gamepads as device events.
+//! * `png`: Enables decoding PNG images, with `Icon::from_png` and in `.ico`, `.icns` and `.cur`
+//!   files.
+//! * `svg`: Enables SVG icons with `Icon::from_svg`, rasterized at the sizes the system needs.
+//! * `accesskit`: Enables the `accessibility` module, exposing the content of windows to the
+//!   assistive technologies with AccessKit.
+//! * `winit-test-harness`: On X11 and Wayland, enables the `test_harness` module, running the event
+//!   loop against virtual displays in tests, and `Window::inject_event` and
+//!   `ActiveEventLoop::inject_device_event`, delivering events as if they came from the system.
 //!
 //! See the [`platform`] module for documentation on platform-specific cargo
 //! features.
@@ -237,14 +248,24 @@ pub use dpi;
 #[cfg(feature = "rwh_06")]
 pub use rwh_06 as raw_window_handle;
 
+#[cfg(feature = "accesskit")]
+pub mod accessibility;
 pub mod application;
 #[cfg(any(doc, doctest, test))]
 pub mod changelog;
+pub mod clipboard;
+#[cfg(feature = "dialogs")]
+pub mod dialogs;
 #[macro_use]
 pub mod error;
 mod cursor;
+pub mod drag;
 pub mod event;
 pub mod event_loop;
+#[cfg(feature = "gamepad")]
+pub mod gamepad;
+#[cfg(feature = "gestures")]
+pub mod gestures;
 mod icon;
 pub mod keyboard;
 pub mod monitor;
@@ -253,3 +274,5 @@ mod utils;
 pub mod window;
 
 pub mod platform;
+#[cfg(all(feature = "winit-test-harness", any(x11_platform, wayland_platform)))]
+pub mod test_harness;
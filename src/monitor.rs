@@ -5,9 +5,11 @@
 //! methods, which return an iterator of [`MonitorHandle`]:
 //! - [`ActiveEventLoop::available_monitors`][crate::event_loop::ActiveEventLoop::available_monitors].
 //! - [`Window::available_monitors`][crate::window::Window::available_monitors].
+use std::cmp::Reverse;
 use std::num::{NonZeroU16, NonZeroU32};
 
 use crate::dpi::{PhysicalPosition, PhysicalSize};
+use crate::error::RequestError;
 use crate::platform_impl;
 
 /// Describes a fullscreen video mode of a monitor.
@@ -64,6 +66,16 @@ impl VideoModeHandle {
     }
 
     /// Returns the refresh rate of this video mode in mHz.
+    ///
+    /// Fractional rates are kept, so a 59.94 Hz mode reports `59940`.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Only the [current video mode] has a fractional rate, the other modes are
+    ///   rounded down to whole hertz.
+    /// - **iOS:** Always a whole number of hertz.
+    ///
+    /// [current video mode]: MonitorHandle::current_video_mode
     #[inline]
     pub fn refresh_rate_millihertz(&self) -> Option<NonZeroU32> {
         self.video_mode.refresh_rate_millihertz()
@@ -90,6 +102,270 @@ impl std::fmt::Display for VideoModeHandle {
     }
 }
 
+/// The desired properties of a video mode, see [`MonitorHandle::best_video_mode`].
+///
+/// The properties left to `None` don't matter.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VideoModeRequest {
+    /// The desired resolution.
+    pub size: Option<PhysicalSize<u32>>,
+    /// The desired refresh rate, in mHz.
+    pub refresh_rate_millihertz: Option<NonZeroU32>,
+    /// The desired bit depth.
+    pub bit_depth: Option<NonZeroU16>,
+}
+
+impl VideoModeRequest {
+    /// How far a mode is from the request, the best mode has the lowest score.
+    fn score(
+        &self,
+        size: PhysicalSize<u32>,
+        refresh_rate_millihertz: Option<NonZeroU32>,
+        bit_depth: Option<NonZeroU16>,
+    ) -> impl Ord {
+        // A property the mode doesn't report is as far as possible from the requested one.
+        let distance = |requested: Option<u32>, actual: Option<u32>| match (requested, actual) {
+            (None, _) => 0,
+            (Some(requested), Some(actual)) => requested.abs_diff(actual),
+            (Some(_), None) => u32::MAX,
+        };
+        let size_distance = self.size.map_or(0, |requested| {
+            requested.width.abs_diff(size.width) as u64
+                + requested.height.abs_diff(size.height) as u64
+        });
+        let refresh_rate = refresh_rate_millihertz.map(NonZeroU32::get);
+        let bit_depth = bit_depth.map(|bit_depth| bit_depth.get() as u32);
+        (
+            size_distance,
+            distance(self.refresh_rate_millihertz.map(NonZeroU32::get), refresh_rate),
+            Reverse(refresh_rate),
+            distance(self.bit_depth.map(|bit_depth| bit_depth.get() as u32), bit_depth),
+            Reverse(bit_depth),
+            Reverse(size.width as u64 * size.height as u64),
+        )
+    }
+}
+
+/// Static HDR10 metadata describing the content, as defined by SMPTE ST 2086 and CTA-861.3.
+///
+/// Used to request HDR output in exclusive fullscreen, see
+/// [`Window::set_fullscreen_hdr`][crate::window::Window::set_fullscreen_hdr].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HdrMetadata {
+    /// The CIE 1931 `xy` chromaticity coordinates of the red, green and blue primaries of the
+    /// mastering display.
+    pub primaries: [(f32, f32); 3],
+    /// The CIE 1931 `xy` chromaticity coordinates of the white point of the mastering display.
+    pub white_point: (f32, f32),
+    /// The maximum luminance of the mastering display, in nits.
+    pub max_mastering_luminance: f32,
+    /// The minimum luminance of the mastering display, in nits.
+    pub min_mastering_luminance: f32,
+    /// The maximum content light level (MaxCLL), in nits.
+    pub max_content_light_level: f32,
+    /// The maximum frame-average light level (MaxFALL), in nits.
+    pub max_frame_average_light_level: f32,
+}
+
+/// The color capabilities of a monitor, see [`MonitorHandle::color_capabilities`].
+///
+/// The values the monitor doesn't report are `None`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ColorCapabilities {
+    /// Whether the monitor currently shows HDR content, as opposed to only being capable of it.
+    pub hdr: bool,
+    /// How many times brighter than SDR white the monitor can currently show, `1.0` without HDR.
+    pub headroom: Option<f32>,
+    /// The CIE 1931 `xy` chromaticity coordinates of the red, green and blue primaries of the
+    /// monitor.
+    pub primaries: Option<[(f32, f32); 3]>,
+    /// The CIE 1931 `xy` chromaticity coordinates of the white point of the monitor.
+    pub white_point: Option<(f32, f32)>,
+    /// The minimum luminance of the monitor, in nits.
+    pub min_luminance: Option<f32>,
+    /// The maximum luminance of the monitor on a small part of the screen, in nits.
+    pub max_luminance: Option<f32>,
+    /// The maximum luminance of the monitor on the whole screen, in nits.
+    pub max_full_frame_luminance: Option<f32>,
+    /// The luminance of SDR white, in nits.
+    pub sdr_white_luminance: Option<f32>,
+}
+
+/// The identity of the physical display behind a monitor, see [`MonitorHandle::identity`].
+///
+/// Unlike the name of the monitor, it stays the same when the display is connected through
+/// another cable or port, so it can be used to remember settings per display.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MonitorIdentity {
+    /// The manufacturer of the display, usually its three-letter PNP ID like `DEL` or `SAM`.
+    pub manufacturer: String,
+    /// The code the manufacturer gave to the model of the display.
+    pub product_code: Option<u16>,
+    /// The name of the model of the display.
+    pub model: Option<String>,
+    /// The serial number of the display.
+    pub serial: Option<String>,
+}
+
+impl MonitorIdentity {
+    /// Parse the identity from the base block of an EDID.
+    #[allow(dead_code)] // Not used on every platform
+    pub(crate) fn from_edid(edid: &[u8]) -> Option<Self> {
+        const HEADER: [u8; 8] = [0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00];
+        if edid.len() < 128 || edid[..8] != HEADER {
+            return None;
+        }
+
+        let manufacturer = pnp_id(u16::from_be_bytes([edid[8], edid[9]]))?;
+        let product_code = u16::from_le_bytes([edid[10], edid[11]]);
+        let serial_number = u32::from_le_bytes([edid[12], edid[13], edid[14], edid[15]]);
+
+        // The display descriptors have a zero pixel clock, then the tag of their text.
+        let descriptor = |tag: u8| {
+            edid[54..126].chunks_exact(18).find_map(|descriptor| {
+                if descriptor[..3] != [0, 0, 0] || descriptor[3] != tag {
+                    return None;
+                }
+                let text = &descriptor[5..];
+                let text = &text[..text.iter().position(|&c| c == b'\n').unwrap_or(text.len())];
+                let text = String::from_utf8_lossy(text).trim().to_owned();
+                (!text.is_empty()).then_some(text)
+            })
+        };
+
+        Some(Self {
+            manufacturer,
+            product_code: Some(product_code),
+            model: descriptor(0xfc),
+            serial: descriptor(0xff)
+                .or_else(|| (serial_number != 0).then(|| serial_number.to_string())),
+        })
+    }
+}
+
+/// Decode the three letters of a PNP ID, packed in five bits each.
+#[allow(dead_code)] // Not used on every platform
+pub(crate) fn pnp_id(id: u16) -> Option<String> {
+    [10, 5, 0]
+        .into_iter()
+        .map(|shift| match (id >> shift) & 0x1f {
+            letter @ 1..=26 => Some(char::from(b'A' + letter as u8 - 1)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The orientation of a monitor, see [`MonitorHandle::orientation`].
+///
+/// Expressed as the clockwise rotation of the image shown by the monitor.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Orientation {
+    /// The image isn't rotated.
+    #[default]
+    Landscape,
+    /// The image is rotated by 90 degrees.
+    Portrait,
+    /// The image is rotated by 180 degrees.
+    LandscapeFlipped,
+    /// The image is rotated by 270 degrees.
+    PortraitFlipped,
+}
+
+impl Orientation {
+    /// Whether the image is rotated by a quarter turn, so that its width and height are swapped.
+    pub fn is_portrait(self) -> bool {
+        matches!(self, Self::Portrait | Self::PortraitFlipped)
+    }
+
+    /// The orientation closest to a clockwise rotation in degrees.
+    #[allow(dead_code)] // Not used on every platform
+    pub(crate) fn from_degrees(degrees: f64) -> Self {
+        match (degrees / 90.0).round().rem_euclid(4.0) as u8 {
+            1 => Self::Portrait,
+            2 => Self::LandscapeFlipped,
+            3 => Self::PortraitFlipped,
+            _ => Self::Landscape,
+        }
+    }
+}
+
+/// The gamma ramp of a monitor, used to adjust the gamma and the brightness of its output, see
+/// [`Window::set_gamma_ramp`][crate::window::Window::set_gamma_ramp].
+///
+/// Each channel is a lookup table mapping evenly spaced input intensities, from black to full
+/// intensity, to the intensity sent to the monitor, from `0` to `u16::MAX`. The tables are
+/// resampled to the size the monitor supports.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GammaRamp {
+    red: Vec<u16>,
+    green: Vec<u16>,
+    blue: Vec<u16>,
+}
+
+impl GammaRamp {
+    /// Creates a gamma ramp from the tables of the red, green and blue channels.
+    ///
+    /// Returns `None` unless the tables have the same length, of at least two entries.
+    pub fn new(red: Vec<u16>, green: Vec<u16>, blue: Vec<u16>) -> Option<Self> {
+        (red.len() >= 2 && red.len() == green.len() && red.len() == blue.len()).then_some(Self {
+            red,
+            green,
+            blue,
+        })
+    }
+
+    /// Creates a gamma ramp raising the intensities of all channels to the power of
+    /// `1.0 / gamma`, so that a `gamma` above `1.0` brightens the output.
+    pub fn from_gamma(gamma: f64) -> Self {
+        let table: Vec<u16> = (0..256)
+            .map(|i| ((i as f64 / 255.0).powf(1.0 / gamma) * u16::MAX as f64).round() as u16)
+            .collect();
+        Self { red: table.clone(), green: table.clone(), blue: table }
+    }
+
+    /// The table of the red channel.
+    pub fn red(&self) -> &[u16] {
+        &self.red
+    }
+
+    /// The table of the green channel.
+    pub fn green(&self) -> &[u16] {
+        &self.green
+    }
+
+    /// The table of the blue channel.
+    pub fn blue(&self) -> &[u16] {
+        &self.blue
+    }
+
+    /// The tables of the red, green and blue channels, linearly resampled to `size` entries.
+    #[allow(dead_code)] // Not used on every platform
+    pub(crate) fn resample(&self, size: usize) -> [Vec<u16>; 3] {
+        let resample = |table: &[u16]| -> Vec<u16> {
+            if table.len() == size {
+                return table.to_vec();
+            }
+            let last = (table.len() - 1) as f64;
+            (0..size)
+                .map(|i| {
+                    let position = i as f64 * last / (size - 1).max(1) as f64;
+                    let index = (position as usize).min(table.len() - 2);
+                    let fraction = position - index as f64;
+                    let (low, high) = (table[index] as f64, table[index + 1] as f64);
+                    (low + (high - low) * fraction).round() as u16
+                })
+                .collect()
+        };
+        [resample(&self.red), resample(&self.green), resample(&self.blue)]
+    }
+}
+
 /// Handle to a monitor.
 ///
 /// Allows you to retrieve information about a given monitor and can be used in [`Window`] creation.
@@ -181,6 +457,94 @@ impl MonitorHandle {
         self.inner.scale_factor()
     }
 
+    /// Returns the physical size of the monitor in millimeters, as `(width, height)`.
+    ///
+    /// Together with the size of its video mode, this gives the true density of the monitor, for
+    /// example to show content at its actual size, independently of the scale factor.
+    ///
+    /// Returns `None` when the monitor doesn't report its size, as is common with projectors and
+    /// virtual displays.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Reported by RandR, usually from the EDID of the monitor.
+    /// - **iOS / Android / Web / Orbital:** Unsupported, always returns `None`.
+    #[inline]
+    pub fn physical_size_mm(&self) -> Option<(u32, u32)> {
+        self.inner.physical_size_mm()
+    }
+
+    /// Returns the identity of the physical display behind the monitor.
+    ///
+    /// Returns `None` if the display doesn't identify itself, as is common with virtual displays.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows / X11:** Read from the EDID of the display.
+    /// - **macOS:** The numbers reported by CoreGraphics, there's no model name.
+    /// - **Wayland:** The manufacturer and model are the make and model of the output reported by
+    ///   the compositor, there's no product code nor serial.
+    /// - **iOS / Android / Web / Orbital:** Unsupported, always returns `None`.
+    #[inline]
+    pub fn identity(&self) -> Option<MonitorIdentity> {
+        self.inner.identity()
+    }
+
+    /// Returns the orientation of the monitor, which kiosk and tablet applications can follow to
+    /// lay out their content.
+    ///
+    /// [`ApplicationHandler::monitor_orientation_changed()`] is called when it changes.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / Android / Web / Orbital:** Unsupported, always returns [`Orientation::Landscape`].
+    ///
+    /// [`ApplicationHandler::monitor_orientation_changed()`]: crate::application::ApplicationHandler::monitor_orientation_changed
+    #[inline]
+    pub fn orientation(&self) -> Orientation {
+        self.inner.orientation()
+    }
+
+    /// Returns the HDR and wide gamut capabilities of the monitor, to choose the format of a
+    /// swapchain and tone-map its content.
+    ///
+    /// [`ApplicationHandler::color_capabilities_changed()`] is called when they change.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Reported by DXGI, the SDR white level by the display configuration.
+    /// - **macOS:** Only [`hdr`][ColorCapabilities::hdr] and
+    ///   [`headroom`][ColorCapabilities::headroom] are reported, from the extended dynamic range of
+    ///   the screen.
+    /// - **Wayland:** Requires the `wp-color-management-v1` protocol.
+    /// - **X11 / iOS / Android / Web / Orbital:** Unsupported.
+    ///
+    /// [`ApplicationHandler::color_capabilities_changed()`]: crate::application::ApplicationHandler::color_capabilities_changed
+    #[inline]
+    pub fn color_capabilities(&self) -> Result<ColorCapabilities, RequestError> {
+        self.inner.color_capabilities()
+    }
+
+    /// Returns the ICC profile assigned to the monitor, to transform colors for this specific
+    /// display.
+    ///
+    /// [`WindowEvent::ColorProfileChanged`] is emitted when the profile of the monitor a window is
+    /// on changes.
+    ///
+    /// Returns `None` when no profile is assigned to the monitor.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Read from the `_ICC_PROFILE` property of the RandR output, falling back to the
+    ///   one of the root window for the primary monitor.
+    /// - **iOS / Android / Web / Wayland / Orbital:** Unsupported, always returns `None`.
+    ///
+    /// [`WindowEvent::ColorProfileChanged`]: crate::event::WindowEvent::ColorProfileChanged
+    #[inline]
+    pub fn icc_profile(&self) -> Option<Vec<u8>> {
+        self.inner.icc_profile()
+    }
+
     /// Returns the currently active video mode of this monitor.
     #[inline]
     pub fn current_video_mode(&self) -> Option<VideoModeHandle> {
@@ -192,4 +556,181 @@ impl MonitorHandle {
     pub fn video_modes(&self) -> impl Iterator<Item = VideoModeHandle> {
         self.inner.video_modes().map(|video_mode| VideoModeHandle { video_mode })
     }
+
+    /// Returns the video mode of this monitor closest to `desired`, to use with
+    /// [`Fullscreen::Exclusive`].
+    ///
+    /// The modes are compared by, in order:
+    ///
+    /// 1. The distance of their size to the requested one, the sum of the differences of their
+    ///    widths and heights.
+    /// 2. The distance of their refresh rate to the requested one, then the highest refresh rate.
+    /// 3. The distance of their bit depth to the requested one, then the highest bit depth.
+    /// 4. The largest area.
+    ///
+    /// The properties that aren't requested only count for picking the highest values, so the
+    /// default request picks the mode with the highest refresh rate, then bit depth, then size.
+    ///
+    /// Returns `None` if the monitor has no video modes.
+    ///
+    /// [`Fullscreen::Exclusive`]: crate::window::Fullscreen::Exclusive
+    pub fn best_video_mode(&self, desired: VideoModeRequest) -> Option<VideoModeHandle> {
+        self.video_modes().min_by_key(|mode| {
+            desired.score(mode.size(), mode.refresh_rate_millihertz(), mode.bit_depth())
+        })
+    }
+
+    /// Returns the brightness of the monitor, from `0.0` for the darkest to `1.0` for the
+    /// brightest.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Linux:** Uses the backlight of built-in panels, and DDC/CI for external monitors. DDC/CI
+    ///   needs access to the `/dev/i2c-*` devices, which usually requires being in the `i2c` group.
+    /// - **Windows:** Uses DDC/CI, so built-in panels aren't supported.
+    /// - **macOS:** Only supported for built-in panels.
+    /// - **iOS:** Only supported for the main screen.
+    /// - **Android / Orbital / Web:** Unsupported.
+    #[inline]
+    pub fn brightness(&self) -> Result<f64, RequestError> {
+        self.inner.brightness()
+    }
+
+    /// Sets the brightness of the monitor, from `0.0` for the darkest to `1.0` for the brightest.
+    ///
+    /// Values outside of this range are clamped.
+    ///
+    /// ## Platform-specific
+    ///
+    /// See [`MonitorHandle::brightness()`]. On Linux, the backlight is set through
+    /// `systemd-logind` when the process can't write to sysfs.
+    #[inline]
+    pub fn set_brightness(&self, brightness: f64) -> Result<(), RequestError> {
+        self.inner.set_brightness(brightness)
+    }
+
+    /// The area covered by the monitor in the desktop, if it's known.
+    pub(crate) fn bounds(&self) -> Option<(PhysicalPosition<i32>, PhysicalSize<u32>)> {
+        Some((self.position()?, self.current_video_mode()?.size()))
+    }
+}
+
+/// The area of the intersection of two rectangles, in square pixels.
+pub(crate) fn intersection_area(
+    (a_position, a_size): (PhysicalPosition<i32>, PhysicalSize<u32>),
+    (b_position, b_size): (PhysicalPosition<i32>, PhysicalSize<u32>),
+) -> u64 {
+    let overlap = |a_start: i32, a_len: u32, b_start: i32, b_len: u32| {
+        let start = a_start.max(b_start) as i64;
+        let end = (a_start as i64 + a_len as i64).min(b_start as i64 + b_len as i64);
+        (end - start).max(0) as u64
+    };
+    overlap(a_position.x, a_size.width, b_position.x, b_size.width)
+        * overlap(a_position.y, a_size.height, b_position.y, b_size.height)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rectangle_intersection_area() {
+        let rect =
+            |x, y, width, height| (PhysicalPosition::new(x, y), PhysicalSize::new(width, height));
+        assert_eq!(intersection_area(rect(0, 0, 100, 100), rect(50, 50, 100, 100)), 2500);
+        assert_eq!(intersection_area(rect(-50, 0, 100, 10), rect(0, 0, 1920, 1080)), 500);
+        assert_eq!(intersection_area(rect(0, 0, 100, 100), rect(100, 0, 100, 100)), 0);
+        assert_eq!(intersection_area(rect(0, 0, 100, 100), rect(200, 200, 10, 10)), 0);
+    }
+
+    #[test]
+    fn gamma_ramp_resample() {
+        let ramp = GammaRamp::new(vec![0, 1000], vec![0, 2000], vec![1000, 0]).unwrap();
+        let [red, green, blue] = ramp.resample(3);
+        assert_eq!(red, [0, 500, 1000]);
+        assert_eq!(green, [0, 1000, 2000]);
+        assert_eq!(blue, [1000, 500, 0]);
+
+        let identity = GammaRamp::from_gamma(1.0);
+        assert_eq!(identity.resample(256)[0], identity.red());
+        assert_eq!(identity.resample(2)[0], [0, u16::MAX]);
+
+        assert!(GammaRamp::new(vec![0], vec![0], vec![0]).is_none());
+        assert!(GammaRamp::new(vec![0, 1], vec![0, 1], vec![0]).is_none());
+    }
+
+    #[test]
+    fn video_mode_request_score() {
+        let mode = |width, height, refresh_rate, bit_depth| {
+            (
+                PhysicalSize::new(width, height),
+                NonZeroU32::new(refresh_rate),
+                NonZeroU16::new(bit_depth),
+            )
+        };
+        let modes = [
+            mode(1920, 1080, 60000, 32),
+            mode(1920, 1080, 59940, 32),
+            mode(1920, 1080, 144000, 32),
+            mode(2560, 1440, 60000, 32),
+            mode(1280, 720, 60000, 32),
+            mode(1920, 1200, 60000, 32),
+        ];
+        let best = |request: VideoModeRequest| {
+            modes
+                .iter()
+                .min_by_key(|(size, refresh_rate, bit_depth)| {
+                    request.score(*size, *refresh_rate, *bit_depth)
+                })
+                .copied()
+                .unwrap()
+        };
+
+        let request = VideoModeRequest {
+            size: Some(PhysicalSize::new(1920, 1080)),
+            refresh_rate_millihertz: NonZeroU32::new(60000),
+            bit_depth: None,
+        };
+        assert_eq!(best(request), modes[0]);
+        let request =
+            VideoModeRequest { refresh_rate_millihertz: NonZeroU32::new(59000), ..request };
+        assert_eq!(best(request), modes[1]);
+        let request = VideoModeRequest { size: Some(PhysicalSize::new(1900, 1150)), ..request };
+        assert_eq!(best(request), modes[5]);
+        assert_eq!(best(VideoModeRequest::default()), modes[2]);
+    }
+
+    #[test]
+    fn monitor_identity_from_edid() {
+        let mut edid = vec![0; 128];
+        edid[..8].copy_from_slice(&[0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00]);
+        edid[8..16].copy_from_slice(&[0x10, 0xac, 0xf4, 0x40, 0x78, 0x56, 0x34, 0x12]);
+        edid[54..59].copy_from_slice(&[0, 0, 0, 0xfc, 0]);
+        edid[59..72].copy_from_slice(b"DELL U2720Q\n ");
+        edid[72..77].copy_from_slice(&[0, 0, 0, 0x10, 0]);
+
+        let identity = MonitorIdentity::from_edid(&edid).unwrap();
+        assert_eq!(identity.manufacturer, "DEL");
+        assert_eq!(identity.product_code, Some(0x40f4));
+        assert_eq!(identity.model.as_deref(), Some("DELL U2720Q"));
+        assert_eq!(identity.serial.as_deref(), Some("305419896"));
+
+        edid[90..95].copy_from_slice(&[0, 0, 0, 0xff, 0]);
+        edid[95..108].copy_from_slice(b"ABC123\n      ");
+        let identity = MonitorIdentity::from_edid(&edid).unwrap();
+        assert_eq!(identity.serial.as_deref(), Some("ABC123"));
+
+        edid[0] = 0xff;
+        assert_eq!(MonitorIdentity::from_edid(&edid), None);
+    }
+
+    #[test]
+    fn orientation_from_degrees() {
+        assert_eq!(Orientation::from_degrees(0.0), Orientation::Landscape);
+        assert_eq!(Orientation::from_degrees(90.0), Orientation::Portrait);
+        assert_eq!(Orientation::from_degrees(180.0), Orientation::LandscapeFlipped);
+        assert_eq!(Orientation::from_degrees(270.0), Orientation::PortraitFlipped);
+        assert_eq!(Orientation::from_degrees(-90.0), Orientation::PortraitFlipped);
+        assert_eq!(Orientation::from_degrees(360.0), Orientation::Landscape);
+    }
 }
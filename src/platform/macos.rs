@@ -14,9 +14,10 @@
 //!
 //! [reopen]: https://developer.apple.com/documentation/appkit/nsapplicationdelegate/1428638-applicationshouldhandlereopen?language=objc
 //!
-//! Instead, Winit guarantees that it will not register an application delegate, so the solution is
-//! to register your own application delegate, as outlined in the following example (see
-//! `objc2-app-kit` for more detailed information).
+//! Instead, Winit only registers its own application delegate when the application doesn't have
+//! one when it's launched, so the solution is to register your own application delegate, as
+//! outlined in the following example (see `objc2-app-kit` for more detailed information). The
+//! Dock menu and the user activities continued with Handoff are then left to your delegate.
 #![cfg_attr(target_os = "macos", doc = "```")]
 #![cfg_attr(not(target_os = "macos"), doc = "```ignore")]
 //! use objc2::rc::Retained;
@@ -70,15 +71,20 @@
 //! }
 //! ```
 
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
 use std::os::raw::c_void;
+use std::path::{Path, PathBuf};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use crate::application::ApplicationHandler;
-use crate::event_loop::{ActiveEventLoop, EventLoopBuilder};
+use crate::dpi::LogicalPosition;
+use crate::event_loop::{ActiveEventLoop, EventLoop, EventLoopBuilder};
+use crate::keyboard::ModifiersState;
 use crate::monitor::MonitorHandle;
-use crate::window::{Window, WindowAttributes, WindowId};
+use crate::window::{Window, WindowAttributes, WindowButtons, WindowId};
 
 /// Additional methods on [`Window`] that are specific to MacOS.
 pub trait WindowExtMacOS {
@@ -122,6 +128,18 @@ pub trait WindowExtMacOS {
     /// Get the number of tabs in the window tab group.
     fn num_tabs(&self) -> usize;
 
+    /// Moves the tab of the window out of its tab group, into a new window.
+    ///
+    /// Will no-op when the window is the only tab of its group.
+    fn move_tab_to_new_window(&self);
+
+    /// Sets the title shown in the tab of the window, or shows the title of the window with
+    /// `None`.
+    fn set_tab_title(&self, title: Option<&str>);
+
+    /// Sets the tooltip of the tab of the window, or shows the title of the window with `None`.
+    fn set_tab_tooltip(&self, tooltip: Option<&str>);
+
     /// Get the window's edit state.
     ///
     /// # Examples
@@ -164,6 +182,129 @@ pub trait WindowExtMacOS {
 
     /// Getter for the [`WindowExtMacOS::set_unified_titlebar`].
     fn unified_titlebar(&self) -> bool;
+
+    /// Sets the items shown in the Touch Bar while the window is focused, or removes them with an
+    /// empty description.
+    ///
+    /// Their interactions are delivered with [`ApplicationHandlerExtMacOS::touch_bar_event`].
+    fn set_touch_bar(&self, touch_bar: TouchBarDescription);
+
+    /// Sets the file represented by the window, or removes it with `None`.
+    ///
+    /// The icon of the file is shown before the title, and can be dragged like the file, while
+    /// Command-clicking the title shows the folders containing it, like the windows of document
+    /// based applications.
+    fn set_represented_url(&self, path: Option<&Path>);
+
+    /// Getter for the [`WindowExtMacOS::set_represented_url`].
+    fn represented_url(&self) -> Option<PathBuf>;
+
+    /// Presents the other window as a sheet attached to the titlebar of this window, making this
+    /// one unresponsive until the sheet is ended.
+    ///
+    /// The sheet is ended with [`WindowExtMacOS::end_sheet`], which hides it and is reported with
+    /// [`ApplicationHandlerExtMacOS::sheet_ended`].
+    fn begin_sheet(&self, sheet: &dyn Window);
+
+    /// Ends the sheet presented with [`WindowExtMacOS::begin_sheet`].
+    fn end_sheet(&self, sheet: &dyn Window);
+
+    /// Moves the traffic lights, the close, minimize and zoom buttons, so the top left corner of
+    /// the close button is at the position relative to the top left corner of the window, or
+    /// restores their default position with `None`.
+    ///
+    /// The titlebar is enlarged to keep the buttons vertically centered in it, which is useful
+    /// with a custom titlebar drawn with [`WindowAttributesExtMacOS::with_fullsize_content_view`].
+    /// The position is kept when the window is resized or exits fullscreen.
+    fn set_traffic_light_inset(&self, inset: Option<LogicalPosition<f64>>);
+
+    /// Getter for the [`WindowExtMacOS::set_traffic_light_inset`].
+    fn traffic_light_inset(&self) -> Option<LogicalPosition<f64>>;
+
+    /// Hides the traffic lights of the buttons, showing the others.
+    ///
+    /// This is kept across resizes and fullscreen transitions, unlike hiding the buttons of the
+    /// `NSWindow` directly. The buttons can be disabled instead with
+    /// [`Window::set_enabled_buttons`].
+    fn set_hidden_traffic_lights(&self, buttons: WindowButtons);
+
+    /// Getter for the [`WindowExtMacOS::set_hidden_traffic_lights`].
+    fn hidden_traffic_lights(&self) -> WindowButtons;
+
+    /// Shows the translucent material behind the content of the window, like the sidebars and
+    /// HUD windows of the system, replacing the previous one.
+    ///
+    /// The material is drawn by an `NSVisualEffectView` behind the view of the window, resized
+    /// with it. It's only visible through the transparent parts of the content, so the window
+    /// should be [transparent][crate::window::WindowAttributes::with_transparent].
+    fn set_vibrancy(
+        &self,
+        material: VibrancyMaterial,
+        blending_mode: VibrancyBlendingMode,
+        state: VibrancyState,
+    );
+
+    /// Removes the material shown with [`WindowExtMacOS::set_vibrancy`].
+    fn remove_vibrancy(&self);
+
+    /// Sets the options of the system applied while the window is in borderless or simple
+    /// fullscreen, like hiding the Dock and the menu bar or disabling Command-Tab, or restores
+    /// the default ones with `None`.
+    ///
+    /// The options are applied when the window enters fullscreen, or immediately if it's already
+    /// in fullscreen. In exclusive fullscreen, the Dock and the menu bar are always hidden.
+    fn set_fullscreen_presentation_options(&self, options: Option<FullscreenPresentationOptions>);
+
+    /// Getter for the [`WindowExtMacOS::set_fullscreen_presentation_options`].
+    fn fullscreen_presentation_options(&self) -> Option<FullscreenPresentationOptions>;
+
+    /// Forces the appearance of the window, or makes it follow the appearance of the application
+    /// with `None`.
+    ///
+    /// This overrides the [theme][Window::set_theme] of the window, with the appearances that
+    /// don't have an equivalent [`Theme`][crate::window::Theme], like the high contrast ones.
+    /// [`WindowEvent::ThemeChanged`][crate::event::WindowEvent::ThemeChanged] isn't delivered
+    /// while the appearance is forced.
+    fn set_appearance(&self, appearance: Option<AppearanceName>);
+
+    /// Getter for the [`WindowExtMacOS::set_appearance`], also returning the appearance set with
+    /// [`Window::set_theme`].
+    fn appearance(&self) -> Option<AppearanceName>;
+
+    /// Sets whether the secure keyboard entry is enabled while the window is focused, like in the
+    /// password fields of the system.
+    ///
+    /// The keyboard events can't be read by the other processes, like keyloggers, and the input
+    /// sources not suited to passwords are disabled.
+    fn set_secure_input(&self, enabled: bool);
+
+    /// Getter for the [`WindowExtMacOS::set_secure_input`].
+    fn is_secure_input(&self) -> bool;
+
+    /// Sets how the window behaves with Spaces, Mission Control and the fullscreen windows.
+    ///
+    /// For example, an overlay following the user across Spaces, without switching Space when
+    /// it's shown, joins all Spaces and is an auxiliary window of the fullscreen windows.
+    fn set_collection_behavior(&self, behavior: CollectionBehavior);
+
+    /// Getter for the [`WindowExtMacOS::set_collection_behavior`].
+    fn collection_behavior(&self) -> CollectionBehavior;
+
+    /// Returns the Space of the window, or `None` when it's hidden or on all Spaces.
+    ///
+    /// This uses a private API.
+    fn space(&self) -> Option<SpaceId>;
+
+    /// Moves the window to the Space, like the one of another window.
+    ///
+    /// This uses a private API, which is restricted on recent versions of macOS, where the window
+    /// may not be moved.
+    fn move_to_space(&self, space: SpaceId);
+
+    /// Advertises the activity of the user in the window, to be continued on the other devices of
+    /// the user with Handoff while the window is the main window, or stops advertising it with
+    /// `None`.
+    fn set_user_activity(&self, activity: Option<UserActivity>);
 }
 
 impl WindowExtMacOS for dyn Window + '_ {
@@ -227,6 +368,24 @@ impl WindowExtMacOS for dyn Window + '_ {
         window.maybe_wait_on_main(|w| w.num_tabs())
     }
 
+    #[inline]
+    fn move_tab_to_new_window(&self) {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
+        window.maybe_wait_on_main(|w| w.move_tab_to_new_window());
+    }
+
+    #[inline]
+    fn set_tab_title(&self, title: Option<&str>) {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
+        window.maybe_wait_on_main(|w| w.set_tab_title(title));
+    }
+
+    #[inline]
+    fn set_tab_tooltip(&self, tooltip: Option<&str>) {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
+        window.maybe_wait_on_main(|w| w.set_tab_tooltip(tooltip));
+    }
+
     #[inline]
     fn is_document_edited(&self) -> bool {
         let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
@@ -274,6 +433,243 @@ impl WindowExtMacOS for dyn Window + '_ {
         let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
         window.maybe_wait_on_main(|w| w.unified_titlebar())
     }
+
+    #[inline]
+    fn set_touch_bar(&self, touch_bar: TouchBarDescription) {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
+        window.maybe_wait_on_main(|w| w.set_touch_bar(touch_bar))
+    }
+
+    #[inline]
+    fn set_represented_url(&self, path: Option<&Path>) {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
+        window.maybe_wait_on_main(|w| w.set_represented_url(path))
+    }
+
+    #[inline]
+    fn represented_url(&self) -> Option<PathBuf> {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
+        window.maybe_wait_on_main(|w| w.represented_url())
+    }
+
+    #[inline]
+    fn begin_sheet(&self, sheet: &dyn Window) {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
+        window.maybe_wait_on_main(|w| w.begin_sheet(sheet))
+    }
+
+    #[inline]
+    fn end_sheet(&self, sheet: &dyn Window) {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
+        window.maybe_wait_on_main(|w| w.end_sheet(sheet))
+    }
+
+    #[inline]
+    fn set_traffic_light_inset(&self, inset: Option<LogicalPosition<f64>>) {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
+        window.maybe_wait_on_main(|w| w.set_traffic_light_inset(inset))
+    }
+
+    #[inline]
+    fn traffic_light_inset(&self) -> Option<LogicalPosition<f64>> {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
+        window.maybe_wait_on_main(|w| w.traffic_light_inset())
+    }
+
+    #[inline]
+    fn set_hidden_traffic_lights(&self, buttons: WindowButtons) {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
+        window.maybe_wait_on_main(|w| w.set_hidden_traffic_lights(buttons))
+    }
+
+    #[inline]
+    fn hidden_traffic_lights(&self) -> WindowButtons {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
+        window.maybe_wait_on_main(|w| w.hidden_traffic_lights())
+    }
+
+    #[inline]
+    fn set_vibrancy(
+        &self,
+        material: VibrancyMaterial,
+        blending_mode: VibrancyBlendingMode,
+        state: VibrancyState,
+    ) {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
+        window.maybe_wait_on_main(|w| w.set_vibrancy(material, blending_mode, state))
+    }
+
+    #[inline]
+    fn remove_vibrancy(&self) {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
+        window.maybe_wait_on_main(|w| w.remove_vibrancy())
+    }
+
+    #[inline]
+    fn set_fullscreen_presentation_options(&self, options: Option<FullscreenPresentationOptions>) {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
+        window.maybe_wait_on_main(|w| w.set_fullscreen_presentation_options(options))
+    }
+
+    #[inline]
+    fn fullscreen_presentation_options(&self) -> Option<FullscreenPresentationOptions> {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
+        window.maybe_wait_on_main(|w| w.fullscreen_presentation_options())
+    }
+
+    #[inline]
+    fn set_appearance(&self, appearance: Option<AppearanceName>) {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
+        window.maybe_wait_on_main(|w| w.set_appearance(appearance))
+    }
+
+    #[inline]
+    fn appearance(&self) -> Option<AppearanceName> {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
+        window.maybe_wait_on_main(|w| w.appearance())
+    }
+
+    #[inline]
+    fn set_secure_input(&self, enabled: bool) {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
+        window.maybe_wait_on_main(|w| w.set_secure_input(enabled))
+    }
+
+    #[inline]
+    fn is_secure_input(&self) -> bool {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
+        window.maybe_wait_on_main(|w| w.is_secure_input())
+    }
+
+    #[inline]
+    fn set_collection_behavior(&self, behavior: CollectionBehavior) {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
+        window.maybe_wait_on_main(|w| w.set_collection_behavior(behavior))
+    }
+
+    #[inline]
+    fn collection_behavior(&self) -> CollectionBehavior {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
+        window.maybe_wait_on_main(|w| w.collection_behavior())
+    }
+
+    #[inline]
+    fn space(&self) -> Option<SpaceId> {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
+        window.maybe_wait_on_main(|w| w.space())
+    }
+
+    #[inline]
+    fn move_to_space(&self, space: SpaceId) {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
+        window.maybe_wait_on_main(|w| w.move_to_space(space))
+    }
+
+    #[inline]
+    fn set_user_activity(&self, activity: Option<UserActivity>) {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
+        window.maybe_wait_on_main(|w| w.set_user_activity(activity))
+    }
+}
+
+/// The items of the Touch Bar of a window, set with [`WindowExtMacOS::set_touch_bar`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TouchBarDescription {
+    pub(crate) items: Vec<TouchBarItem>,
+}
+
+impl TouchBarDescription {
+    /// Creates a description without items.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an item, shown from left to right in the order they were added.
+    #[inline]
+    pub fn with_item(mut self, item: TouchBarItem) -> Self {
+        self.items.push(item);
+        self
+    }
+}
+
+/// An item of the Touch Bar.
+///
+/// The identifiers of the items of a window should be unique.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TouchBarItem {
+    /// A button, delivering [`TouchBarEvent::ButtonPressed`].
+    Button {
+        /// The identifier delivered with the events.
+        id: u32,
+        /// The title of the button.
+        title: String,
+    },
+    /// A slider, delivering [`TouchBarEvent::SliderChanged`].
+    Slider {
+        /// The identifier delivered with the events.
+        id: u32,
+        /// The label shown before the slider, may be empty.
+        label: String,
+        /// The range of the values of the slider.
+        range: RangeInclusive<f64>,
+        /// The initial value of the slider.
+        value: f64,
+    },
+    /// A strip of candidates, like the ones suggested when typing, delivering
+    /// [`TouchBarEvent::CandidateSelected`].
+    Candidates {
+        /// The identifier delivered with the events.
+        id: u32,
+        /// The candidates, from left to right.
+        candidates: Vec<String>,
+    },
+}
+
+impl TouchBarItem {
+    pub(crate) fn id(&self) -> u32 {
+        match self {
+            Self::Button { id, .. } | Self::Slider { id, .. } | Self::Candidates { id, .. } => *id,
+        }
+    }
+}
+
+/// An interaction with an item of the Touch Bar, delivered with
+/// [`ApplicationHandlerExtMacOS::touch_bar_event`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TouchBarEvent {
+    /// The [button][TouchBarItem::Button] was pressed.
+    ButtonPressed {
+        /// The identifier of the button.
+        id: u32,
+    },
+    /// The value of the [slider][TouchBarItem::Slider] changed.
+    SliderChanged {
+        /// The identifier of the slider.
+        id: u32,
+        /// The new value, in the range of the slider.
+        value: f64,
+    },
+    /// A candidate of the [strip][TouchBarItem::Candidates] was selected.
+    CandidateSelected {
+        /// The identifier of the strip.
+        id: u32,
+        /// The index of the candidate.
+        index: usize,
+    },
+}
+
+/// A change of the tab of a window, delivered with [`ApplicationHandlerExtMacOS::tab_event`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TabEvent {
+    /// The tab of the window was selected in its tab group.
+    TabSelected,
+    /// The tabs of the tab group of the window were reordered, or a tab was added or removed.
+    TabOrderChanged {
+        /// The new index of the tab of the window in its tab group.
+        index: usize,
+    },
 }
 
 /// Corresponds to `NSApplicationActivationPolicy`.
@@ -329,6 +725,11 @@ pub trait WindowAttributesExtMacOS {
     fn with_borderless_game(self, borderless_game: bool) -> Self;
     /// See [`WindowExtMacOS::set_unified_titlebar`] for details on what this means if set.
     fn with_unified_titlebar(self, unified_titlebar: bool) -> Self;
+    /// Creates the window as an `NSPanel`, for palettes and launchers.
+    ///
+    /// A panel is never the main window of the application, and stays visible when the
+    /// application is deactivated.
+    fn with_panel(self, style: PanelStyle) -> Self;
 }
 
 impl WindowAttributesExtMacOS for WindowAttributes {
@@ -409,6 +810,12 @@ impl WindowAttributesExtMacOS for WindowAttributes {
         self.platform_specific.unified_titlebar = unified_titlebar;
         self
     }
+
+    #[inline]
+    fn with_panel(mut self, style: PanelStyle) -> Self {
+        self.platform_specific.panel = Some(style);
+        self
+    }
 }
 
 pub trait EventLoopBuilderExtMacOS {
@@ -464,6 +871,20 @@ pub trait EventLoopBuilderExtMacOS {
     ///
     /// The default behavior is to ignore other applications and activate when launched.
     fn with_activate_ignoring_other_apps(&mut self, ignore: bool) -> &mut Self;
+
+    /// Makes the application the default handler of the URL schemes, like the redirection of a
+    /// login flow, delivering the URLs with [`ApplicationHandler::open_urls`].
+    ///
+    /// The application must be bundled, with the schemes declared with `CFBundleURLTypes` for the
+    /// system to launch it when it isn't running.
+    fn with_url_schemes(&mut self, schemes: &[&str]) -> &mut Self;
+
+    /// Handles the Services of the application, delivering their invocations with
+    /// [`ApplicationHandlerExtMacOS::service_invoked`].
+    ///
+    /// The names are the `NSMessage` of the services declared with `NSServices` in the bundle of
+    /// the application.
+    fn with_services(&mut self, names: &[&str]) -> &mut Self;
 }
 
 impl EventLoopBuilderExtMacOS for EventLoopBuilder {
@@ -484,6 +905,227 @@ impl EventLoopBuilderExtMacOS for EventLoopBuilder {
         self.platform_specific.activate_ignoring_other_apps = ignore;
         self
     }
+
+    #[inline]
+    fn with_url_schemes(&mut self, schemes: &[&str]) -> &mut Self {
+        self.platform_specific.url_schemes =
+            schemes.iter().map(|scheme| scheme.to_string()).collect();
+        self
+    }
+
+    #[inline]
+    fn with_services(&mut self, names: &[&str]) -> &mut Self {
+        self.platform_specific.services = names.iter().map(|name| name.to_string()).collect();
+        self
+    }
+}
+
+/// Additional methods on [`EventLoop`] that are specific to macOS.
+pub trait EventLoopExtMacOS {
+    /// Sets the main menu of the application, shown in the menu bar.
+    ///
+    /// This replaces the default menu, see [`EventLoopBuilderExtMacOS::with_default_menu`].
+    /// The activation of the [custom items][MenuItem::Custom] is delivered with
+    /// [`ApplicationHandlerExtMacOS::menu_item_activated`], and the actions of the
+    /// [standard items][MenuItem::Standard] of the Edit menu, like `"copy:"`, with
+    /// [`ApplicationHandlerExtMacOS::standard_key_binding`] to the focused window.
+    fn set_main_menu(&self, menu: MenuDescription);
+
+    /// Sets the menu shown above the standard items when right-clicking on the icon of the
+    /// application in the Dock, or removes it with `None`.
+    ///
+    /// The title of the menu isn't shown. The activation of its [custom items][MenuItem::Custom]
+    /// is delivered with [`ApplicationHandlerExtMacOS::menu_item_activated`], like the ones of the
+    /// main menu.
+    ///
+    /// The menu is provided by the delegate of `NSApplication`, and isn't shown if the
+    /// application set its own delegate.
+    fn set_dock_menu(&self, menu: Option<Menu>);
+
+    /// Sets the badge shown over the icon of the application in the Dock, like the number of
+    /// unread messages, or removes it with `None`.
+    fn set_dock_badge(&self, badge: Option<&str>);
+
+    /// Sets the progress bar shown over the icon of the application in the Dock, from `0.0` to
+    /// `1.0`, or removes it with `None`.
+    fn set_dock_progress(&self, progress: Option<f64>);
+}
+
+impl EventLoopExtMacOS for EventLoop {
+    #[inline]
+    fn set_main_menu(&self, menu: MenuDescription) {
+        self.event_loop.set_main_menu(menu)
+    }
+
+    #[inline]
+    fn set_dock_menu(&self, menu: Option<Menu>) {
+        self.event_loop.set_dock_menu(menu)
+    }
+
+    #[inline]
+    fn set_dock_badge(&self, badge: Option<&str>) {
+        self.event_loop.set_dock_badge(badge)
+    }
+
+    #[inline]
+    fn set_dock_progress(&self, progress: Option<f64>) {
+        self.event_loop.set_dock_progress(progress)
+    }
+}
+
+/// The main menu of the application, set with [`EventLoopExtMacOS::set_main_menu`].
+///
+/// The application menu, named after the application, is always first, with the About,
+/// Services, Hide and Quit items.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct MenuDescription {
+    pub(crate) app_items: Vec<MenuItem>,
+    pub(crate) menus: Vec<Menu>,
+}
+
+impl MenuDescription {
+    /// A main menu with only the application menu.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an item to the application menu after the About item, like `Settings…`.
+    #[inline]
+    pub fn with_app_item(mut self, item: MenuItem) -> Self {
+        self.app_items.push(item);
+        self
+    }
+
+    /// Adds a menu after the application menu, shown in the order they were added.
+    #[inline]
+    pub fn with_menu(mut self, menu: Menu) -> Self {
+        self.menus.push(menu);
+        self
+    }
+}
+
+/// A menu of the [`MenuDescription`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Menu {
+    pub(crate) title: String,
+    pub(crate) items: Vec<MenuItem>,
+    pub(crate) is_window_menu: bool,
+}
+
+impl Menu {
+    /// An empty menu.
+    pub fn new(title: impl Into<String>) -> Self {
+        Self { title: title.into(), items: Vec::new(), is_window_menu: false }
+    }
+
+    /// The standard Edit menu, with the Undo, Redo, Cut, Copy, Paste, Delete and Select All
+    /// items.
+    pub fn edit() -> Self {
+        Self::new("Edit")
+            .with_item(MenuItem::Standard(StandardAction::Undo))
+            .with_item(MenuItem::Standard(StandardAction::Redo))
+            .with_item(MenuItem::Separator)
+            .with_item(MenuItem::Standard(StandardAction::Cut))
+            .with_item(MenuItem::Standard(StandardAction::Copy))
+            .with_item(MenuItem::Standard(StandardAction::Paste))
+            .with_item(MenuItem::Standard(StandardAction::Delete))
+            .with_item(MenuItem::Standard(StandardAction::SelectAll))
+    }
+
+    /// The standard Window menu, with the Minimize, Zoom and Bring All to Front items, which
+    /// the system completes with the list of the windows.
+    pub fn window() -> Self {
+        let mut menu = Self::new("Window")
+            .with_item(MenuItem::Standard(StandardAction::Minimize))
+            .with_item(MenuItem::Standard(StandardAction::Zoom))
+            .with_item(MenuItem::Separator)
+            .with_item(MenuItem::Standard(StandardAction::BringAllToFront));
+        menu.is_window_menu = true;
+        menu
+    }
+
+    /// Adds an item, shown in the order they were added.
+    #[inline]
+    pub fn with_item(mut self, item: MenuItem) -> Self {
+        self.items.push(item);
+        self
+    }
+}
+
+/// An item of a [`Menu`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MenuItem {
+    /// An item delivered with [`ApplicationHandlerExtMacOS::menu_item_activated`].
+    Custom(CustomMenuItem),
+
+    /// An item performing a standard action of the system.
+    Standard(StandardAction),
+
+    /// A line separating the items.
+    Separator,
+
+    /// An item showing another menu.
+    Submenu(Menu),
+}
+
+/// A [custom item][MenuItem::Custom] of a [`Menu`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CustomMenuItem {
+    /// The identifier delivered with [`ApplicationHandlerExtMacOS::menu_item_activated`].
+    pub id: u32,
+
+    /// The title of the item.
+    pub title: String,
+
+    /// The character of the keyboard shortcut of the item, like `"n"`, or an empty string for
+    /// none.
+    pub key_equivalent: String,
+
+    /// The modifiers of the keyboard shortcut of the item.
+    pub modifiers: ModifiersState,
+}
+
+impl CustomMenuItem {
+    /// Creates an item from its identifier and title, without a keyboard shortcut.
+    pub fn new(id: u32, title: impl Into<String>) -> Self {
+        Self {
+            id,
+            title: title.into(),
+            key_equivalent: String::new(),
+            modifiers: ModifiersState::SUPER,
+        }
+    }
+}
+
+/// A [standard item][MenuItem::Standard] of a [`Menu`], with its usual title and keyboard
+/// shortcut.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum StandardAction {
+    /// `undo:`, with `Cmd+Z`.
+    Undo,
+    /// `redo:`, with `Cmd+Shift+Z`.
+    Redo,
+    /// `cut:`, with `Cmd+X`.
+    Cut,
+    /// `copy:`, with `Cmd+C`.
+    Copy,
+    /// `paste:`, with `Cmd+V`.
+    Paste,
+    /// `delete:`.
+    Delete,
+    /// `selectAll:`, with `Cmd+A`.
+    SelectAll,
+    /// Minimizes the key window, with `Cmd+M`.
+    Minimize,
+    /// Zooms the key window.
+    Zoom,
+    /// Closes the key window, with `Cmd+W`.
+    Close,
+    /// Toggles the fullscreen of the key window, with `Cmd+Ctrl+F`.
+    ToggleFullScreen,
+    /// Brings all the windows of the application to the front.
+    BringAllToFront,
 }
 
 /// Additional methods on [`MonitorHandle`] that are specific to MacOS.
@@ -521,6 +1163,14 @@ pub trait ActiveEventLoopExtMacOS {
     fn set_allows_automatic_window_tabbing(&self, enabled: bool);
     /// Returns whether the system can automatically organize windows into tabs.
     fn allows_automatic_window_tabbing(&self) -> bool;
+    /// Sets the main menu of the application, see [`EventLoopExtMacOS::set_main_menu`].
+    fn set_main_menu(&self, menu: MenuDescription);
+    /// Sets the menu of the Dock, see [`EventLoopExtMacOS::set_dock_menu`].
+    fn set_dock_menu(&self, menu: Option<Menu>);
+    /// Sets the badge of the Dock, see [`EventLoopExtMacOS::set_dock_badge`].
+    fn set_dock_badge(&self, badge: Option<&str>);
+    /// Sets the progress bar of the Dock, see [`EventLoopExtMacOS::set_dock_progress`].
+    fn set_dock_progress(&self, progress: Option<f64>);
 }
 
 impl ActiveEventLoopExtMacOS for dyn ActiveEventLoop + '_ {
@@ -555,6 +1205,38 @@ impl ActiveEventLoopExtMacOS for dyn ActiveEventLoop + '_ {
             .expect("non macOS event loop on macOS");
         event_loop.allows_automatic_window_tabbing()
     }
+
+    fn set_main_menu(&self, menu: MenuDescription) {
+        let event_loop = self
+            .as_any()
+            .downcast_ref::<crate::platform_impl::ActiveEventLoop>()
+            .expect("non macOS event loop on macOS");
+        event_loop.set_main_menu(menu);
+    }
+
+    fn set_dock_menu(&self, menu: Option<Menu>) {
+        let event_loop = self
+            .as_any()
+            .downcast_ref::<crate::platform_impl::ActiveEventLoop>()
+            .expect("non macOS event loop on macOS");
+        event_loop.set_dock_menu(menu);
+    }
+
+    fn set_dock_badge(&self, badge: Option<&str>) {
+        let event_loop = self
+            .as_any()
+            .downcast_ref::<crate::platform_impl::ActiveEventLoop>()
+            .expect("non macOS event loop on macOS");
+        event_loop.set_dock_badge(badge);
+    }
+
+    fn set_dock_progress(&self, progress: Option<f64>) {
+        let event_loop = self
+            .as_any()
+            .downcast_ref::<crate::platform_impl::ActiveEventLoop>()
+            .expect("non macOS event loop on macOS");
+        event_loop.set_dock_progress(progress);
+    }
 }
 
 /// Option as alt behavior.
@@ -577,6 +1259,239 @@ pub enum OptionAsAlt {
     None,
 }
 
+/// The material shown with [`WindowExtMacOS::set_vibrancy`], named after the parts of the
+/// interface of the system it's used for.
+///
+/// See [`NSVisualEffectMaterial`](https://developer.apple.com/documentation/appkit/nsvisualeffectview/material).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum VibrancyMaterial {
+    /// The titlebar of windows.
+    Titlebar,
+    /// The selection of lists and tables.
+    Selection,
+    /// Menus.
+    Menu,
+    /// Popovers.
+    Popover,
+    /// The sidebars of windows.
+    Sidebar,
+    /// The headers of lists and tables.
+    HeaderView,
+    /// Sheets.
+    Sheet,
+    /// The background of windows.
+    WindowBackground,
+    /// Heads-up display windows.
+    HudWindow,
+    /// The interface shown over fullscreen content.
+    FullScreenUi,
+    /// Tooltips.
+    ToolTip,
+    /// The background of opaque content.
+    ContentBackground,
+    /// The background behind the content of windows.
+    UnderWindowBackground,
+    /// The background behind document pages.
+    UnderPageBackground,
+}
+
+/// What the material shown with [`WindowExtMacOS::set_vibrancy`] blurs.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum VibrancyBlendingMode {
+    /// The windows and the desktop behind the window.
+    #[default]
+    BehindWindow,
+    /// The content of the window behind the material.
+    WithinWindow,
+}
+
+/// When the material shown with [`WindowExtMacOS::set_vibrancy`] looks active.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum VibrancyState {
+    /// Active while the window is focused.
+    #[default]
+    FollowsWindowActiveState,
+    /// Always active.
+    Active,
+    /// Always inactive.
+    Inactive,
+}
+
+/// The appearance of a window, set with [`WindowExtMacOS::set_appearance`].
+///
+/// See [`NSAppearance.Name`](https://developer.apple.com/documentation/appkit/nsappearance/name).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum AppearanceName {
+    /// The light appearance.
+    Aqua,
+    /// The dark appearance, available since macOS 10.14.
+    DarkAqua,
+    /// The light appearance of the vibrant materials.
+    VibrantLight,
+    /// The dark appearance of the vibrant materials.
+    VibrantDark,
+    /// The high contrast version of [`AppearanceName::Aqua`].
+    AccessibilityHighContrastAqua,
+    /// The high contrast version of [`AppearanceName::DarkAqua`].
+    AccessibilityHighContrastDarkAqua,
+    /// The high contrast version of [`AppearanceName::VibrantLight`].
+    AccessibilityHighContrastVibrantLight,
+    /// The high contrast version of [`AppearanceName::VibrantDark`].
+    AccessibilityHighContrastVibrantDark,
+}
+
+bitflags::bitflags! {
+    /// How a window behaves with Spaces, Mission Control and the fullscreen windows, set with
+    /// [`WindowExtMacOS::set_collection_behavior`].
+    ///
+    /// See [`NSWindow.CollectionBehavior`](https://developer.apple.com/documentation/appkit/nswindow/collectionbehavior).
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct CollectionBehavior: u32 {
+        /// The window is shown on all Spaces, like the menu bar.
+        const CAN_JOIN_ALL_SPACES = 1 << 0;
+        /// The window moves to the active Space when it's shown, instead of switching to its
+        /// Space.
+        const MOVE_TO_ACTIVE_SPACE = 1 << 1;
+        /// The window is managed by Mission Control, the default for normal windows.
+        const MANAGED = 1 << 2;
+        /// The window floats in the Spaces and is hidden by Mission Control.
+        const TRANSIENT = 1 << 3;
+        /// The window isn't affected by Mission Control, like the desktop.
+        const STATIONARY = 1 << 4;
+        /// The window is in the cycle of the windows of the application, with Command-`.
+        const PARTICIPATES_IN_CYCLE = 1 << 5;
+        /// The window isn't in the cycle of the windows of the application.
+        const IGNORES_CYCLE = 1 << 6;
+        /// The window can enter fullscreen in its own Space.
+        const FULLSCREEN_PRIMARY = 1 << 7;
+        /// The window can be shown in the Space of a fullscreen window.
+        const FULLSCREEN_AUXILIARY = 1 << 8;
+        /// The window can't enter fullscreen.
+        const FULLSCREEN_NONE = 1 << 9;
+    }
+}
+
+/// The style of a window created as a panel with [`WindowAttributesExtMacOS::with_panel`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PanelStyle {
+    /// The panel receives the clicks and the keyboard without activating the application, so the
+    /// active application keeps its focus, like Spotlight.
+    pub nonactivating: bool,
+    /// The panel floats above the normal windows.
+    pub floating: bool,
+    /// The panel has the dark translucent style of the heads-up displays.
+    pub hud: bool,
+}
+
+/// An activity of the user, advertised with [`WindowExtMacOS::set_user_activity`] and continued
+/// with [`ApplicationHandlerExtMacOS::continue_user_activity`].
+///
+/// The activity types must be declared with `NSUserActivityTypes` in the application bundle, and
+/// the activity is also suggested by the system, like by Siri.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UserActivity {
+    pub(crate) activity_type: String,
+    pub(crate) title: Option<String>,
+    pub(crate) user_info: HashMap<String, String>,
+    pub(crate) webpage_url: Option<String>,
+}
+
+impl UserActivity {
+    /// Creates an activity, with a reverse-DNS type like `com.example.editing`.
+    pub fn new(activity_type: &str) -> Self {
+        Self { activity_type: activity_type.to_owned(), ..Default::default() }
+    }
+
+    /// Sets the title shown to the user.
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_owned());
+        self
+    }
+
+    /// Adds the state needed to continue the activity, like the identifier of a document.
+    pub fn with_user_info(mut self, key: &str, value: &str) -> Self {
+        self.user_info.insert(key.to_owned(), value.to_owned());
+        self
+    }
+
+    /// Sets the web page continuing the activity on the devices without the application.
+    pub fn with_webpage_url(mut self, url: &str) -> Self {
+        self.webpage_url = Some(url.to_owned());
+        self
+    }
+
+    /// The type of the activity.
+    pub fn activity_type(&self) -> &str {
+        &self.activity_type
+    }
+
+    /// The title shown to the user.
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /// The state needed to continue the activity, only with the strings when it was advertised by
+    /// another application.
+    pub fn user_info(&self) -> &HashMap<String, String> {
+        &self.user_info
+    }
+
+    /// The web page continuing the activity.
+    pub fn webpage_url(&self) -> Option<&str> {
+        self.webpage_url.as_deref()
+    }
+}
+
+/// The identifier of a Space, returned by [`WindowExtMacOS::space`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SpaceId(pub(crate) u64);
+
+bitflags::bitflags! {
+    /// The options of the system applied while a window is in fullscreen, set with
+    /// [`WindowExtMacOS::set_fullscreen_presentation_options`].
+    ///
+    /// See [`NSApplication.PresentationOptions`] for the combinations allowed by AppKit, which
+    /// raises an exception on the others: the Dock must be hidden or auto-hidden when the menu
+    /// bar is, and hidden when the menu bar is hidden, and the process switching can only be
+    /// disabled with the Dock hidden or auto-hidden.
+    ///
+    /// [`NSApplication.PresentationOptions`]: https://developer.apple.com/documentation/appkit/nsapplication/presentationoptions
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct FullscreenPresentationOptions: u32 {
+        /// The Dock appears when the cursor moves to its edge of the screen.
+        const AUTO_HIDE_DOCK = 1 << 0;
+        /// The Dock is hidden.
+        const HIDE_DOCK = 1 << 1;
+        /// The menu bar appears when the cursor moves to the top of the screen.
+        const AUTO_HIDE_MENU_BAR = 1 << 2;
+        /// The menu bar is hidden.
+        const HIDE_MENU_BAR = 1 << 3;
+        /// Command-Tab and the application switcher are disabled.
+        const DISABLE_PROCESS_SWITCHING = 1 << 4;
+        /// The Force Quit panel, opened with Command-Option-Escape, is disabled.
+        const DISABLE_FORCE_QUIT = 1 << 5;
+        /// Restarting, shutting down and logging out are disabled.
+        const DISABLE_SESSION_TERMINATION = 1 << 6;
+        /// Hiding the application is disabled.
+        const DISABLE_HIDE_APPLICATION = 1 << 7;
+        /// The toolbar of the window is hidden with the menu bar.
+        const AUTO_HIDE_TOOLBAR = 1 << 8;
+        /// The options of a kiosk, which can't be left with the Dock, the menu bar or the
+        /// shortcuts of the system.
+        const KIOSK = Self::HIDE_DOCK.bits()
+            | Self::HIDE_MENU_BAR.bits()
+            | Self::DISABLE_PROCESS_SWITCHING.bits()
+            | Self::DISABLE_FORCE_QUIT.bits()
+            | Self::DISABLE_SESSION_TERMINATION.bits()
+            | Self::DISABLE_HIDE_APPLICATION.bits();
+    }
+}
+
 /// Additional events on [`ApplicationHandler`] that are specific to macOS.
 ///
 /// This can be registered with [`ApplicationHandler::macos_handler`].
@@ -587,7 +1502,9 @@ pub trait ApplicationHandlerExtMacOS: ApplicationHandler {
     /// [`NSStandardKeyBindingResponding`] for the full list of key bindings. They are often text
     /// editing related.
     ///
-    /// This corresponds to the [`doCommandBySelector:`] method on `NSTextInputClient`.
+    /// This corresponds to the [`doCommandBySelector:`] method on `NSTextInputClient`. The
+    /// [standard items][StandardAction] of the Edit menu set with
+    /// [`EventLoopExtMacOS::set_main_menu`] are also delivered here, e.g. `"copy:"`.
     ///
     /// The `action` parameter contains the string representation of the selector. Examples include
     /// `"insertBacktab:"`, `"indent:"` and `"noop:"`.
@@ -624,4 +1541,85 @@ pub trait ApplicationHandlerExtMacOS: ApplicationHandler {
         let _ = window_id;
         let _ = action;
     }
+
+    /// A [custom item][MenuItem::Custom] of the main menu set with
+    /// [`EventLoopExtMacOS::set_main_menu`], or of the Dock menu set with
+    /// [`EventLoopExtMacOS::set_dock_menu`], was activated, with its identifier.
+    fn menu_item_activated(&mut self, event_loop: &dyn ActiveEventLoop, id: u32) {
+        let _ = event_loop;
+        let _ = id;
+    }
+
+    /// An item of the Touch Bar set with [`WindowExtMacOS::set_touch_bar`] was interacted with.
+    fn touch_bar_event(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        window_id: WindowId,
+        event: TouchBarEvent,
+    ) {
+        let _ = event_loop;
+        let _ = window_id;
+        let _ = event;
+    }
+
+    /// The sheet presented on the window with [`WindowExtMacOS::begin_sheet`] was ended.
+    fn sheet_ended(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        window_id: WindowId,
+        sheet_id: WindowId,
+    ) {
+        let _ = event_loop;
+        let _ = window_id;
+        let _ = sheet_id;
+    }
+
+    /// The tab of the window changed in its tab group, with the tab bar or
+    /// [`WindowExtMacOS::select_tab_at_index`] for example.
+    ///
+    /// This is delivered to every window of the tab group whose tab changed, to synchronize the
+    /// documents shown in the windows with the tab bar.
+    fn tab_event(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        window_id: WindowId,
+        event: TabEvent,
+    ) {
+        let _ = event_loop;
+        let _ = window_id;
+        let _ = event;
+    }
+
+    /// Files were printed with the application, from the Finder for example, on the print
+    /// documents Apple Event.
+    ///
+    /// The document types must be declared with `CFBundleDocumentTypes` in the application
+    /// bundle.
+    fn print_files(&mut self, event_loop: &dyn ActiveEventLoop, paths: Vec<PathBuf>) {
+        let _ = event_loop;
+        let _ = paths;
+    }
+
+    /// A service declared with [`EventLoopBuilderExtMacOS::with_services`] was invoked, with its
+    /// name and the text selected by the user.
+    fn service_invoked(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        name: String,
+        text: Option<String>,
+    ) {
+        let _ = event_loop;
+        let _ = name;
+        let _ = text;
+    }
+
+    /// An activity advertised with [`WindowExtMacOS::set_user_activity`], on this device or
+    /// another one, is continued with Handoff.
+    ///
+    /// This isn't delivered when the application has its own delegate, see the
+    /// [module-level docs](self).
+    fn continue_user_activity(&mut self, event_loop: &dyn ActiveEventLoop, activity: UserActivity) {
+        let _ = event_loop;
+        let _ = activity;
+    }
 }
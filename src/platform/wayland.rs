@@ -110,3 +110,12 @@ impl MonitorHandleExtWayland for MonitorHandle {
         self.inner.native_identifier()
     }
 }
+
+/// The Wayland data of an event, see [`ActiveEventLoop::event_platform_data`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EventData {
+    /// The serial of the input event, which requests like popup grabs or interactive moves
+    /// pass back to the compositor.
+    pub serial: u32,
+}
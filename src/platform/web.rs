@@ -660,6 +660,9 @@ pub trait MonitorHandleExtWeb {
     fn is_internal(&self) -> Option<bool>;
 
     /// Returns screen orientation data for this monitor.
+    ///
+    /// [`MonitorHandle::orientation()`] takes precedence in method calls, call this one as
+    /// `MonitorHandleExtWeb::orientation(&monitor)`.
     fn orientation(&self) -> OrientationData;
 
     /// Lock the screen orientation. The returned [`OrientationLockFuture`] can be dropped without
@@ -687,7 +690,7 @@ impl MonitorHandleExtWeb for MonitorHandle {
     }
 
     fn orientation(&self) -> OrientationData {
-        self.inner.orientation()
+        self.inner.orientation_data()
     }
 
     fn request_lock(&self, orientation_lock: OrientationLock) -> OrientationLockFuture {
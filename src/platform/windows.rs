@@ -12,11 +12,14 @@ use serde::{Deserialize, Serialize};
 #[cfg(windows_platform)]
 use windows_sys::Win32::Foundation::HANDLE;
 
-use crate::dpi::PhysicalSize;
+use crate::application::ApplicationHandler;
+use crate::dpi::{PhysicalSize, Position, Size};
+use crate::error::RequestError;
 use crate::event::{DeviceId, FingerId};
-use crate::event_loop::EventLoopBuilder;
+use crate::event_loop::{ActiveEventLoop, EventLoop, EventLoopBuilder};
+use crate::icon::RgbaIcon;
 use crate::monitor::MonitorHandle;
-use crate::window::{BadIcon, Icon, Window, WindowAttributes};
+use crate::window::{BadIcon, Icon, Window, WindowAttributes, WindowId};
 
 /// Window Handle type used by Win32 API
 pub type HWND = isize;
@@ -25,6 +28,22 @@ pub type HMENU = isize;
 /// Monitor Handle type used by Win32 API
 pub type HMONITOR = isize;
 
+/// The window message of an event, see [`ActiveEventLoop::event_platform_data`].
+///
+/// [`ActiveEventLoop::event_platform_data`]: crate::event_loop::ActiveEventLoop::event_platform_data
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MessageData {
+    /// The window which received the message.
+    pub hwnd: HWND,
+    /// The identifier of the message, like `WM_KEYDOWN`.
+    pub msg: u32,
+    /// The `WPARAM` of the message.
+    pub wparam: usize,
+    /// The `LPARAM` of the message.
+    pub lparam: isize,
+}
+
 /// Describes a system-drawn backdrop material of a window.
 ///
 /// For a detailed explanation, see [`DWM_SYSTEMBACKDROP_TYPE docs`].
@@ -45,19 +64,39 @@ pub enum BackdropType {
     /// Corresponds to `DWMSBT_MAINWINDOW`.
     ///
     /// Draws the Mica backdrop material.
+    #[doc(alias = "Mica")]
     MainWindow = 2,
 
     /// Corresponds to `DWMSBT_TRANSIENTWINDOW`.
     ///
     /// Draws the Background Acrylic backdrop material.
+    #[doc(alias = "Acrylic")]
     TransientWindow = 3,
 
     /// Corresponds to `DWMSBT_TABBEDWINDOW`.
     ///
     /// Draws the Alt Mica backdrop material.
+    #[doc(alias = "MicaAlt")]
     TabbedWindow = 4,
 }
 
+/// The API a window receives its touch input from, set with
+/// [`WindowExtWindows::set_pointer_api`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PointerApi {
+    /// The touch input is received with `WM_TOUCH`, without the pressure and the coalesced
+    /// samples of the contacts.
+    #[default]
+    Legacy,
+
+    /// The touch input is received with `WM_POINTER`, like the pen input, including the
+    /// pressure and the coalesced samples of the contacts.
+    ///
+    /// Requires Windows 8 or later.
+    WmPointer,
+}
+
 /// Describes a color used by Windows
 #[repr(transparent)]
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
@@ -180,6 +219,29 @@ pub trait EventLoopBuilderExtWindows {
     /// ```
     fn with_dpi_aware(&mut self, dpi_aware: bool) -> &mut Self;
 
+    /// Sets the process-wide DPI awareness, instead of [`DpiAwareness::PerMonitorV2`].
+    ///
+    /// This is useful when embedding components not supporting a per monitor DPI. The awareness
+    /// can only be set once per process, so this has no effect if it's already set, like in the
+    /// manifest of the application. The awareness of the process can be queried with
+    /// [`EventLoopExtWindows::dpi_awareness`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use winit::event_loop::EventLoop;
+    /// #[cfg(target_os = "windows")]
+    /// use winit::platform::windows::{DpiAwareness, EventLoopBuilderExtWindows};
+    ///
+    /// let mut builder = EventLoop::builder();
+    /// #[cfg(target_os = "windows")]
+    /// builder.with_dpi_awareness(DpiAwareness::System);
+    /// # if false { // We can't test this part
+    /// let event_loop = builder.build();
+    /// # }
+    /// ```
+    fn with_dpi_awareness(&mut self, awareness: DpiAwareness) -> &mut Self;
+
     /// A callback to be executed before dispatching a win32 message to the window procedure.
     /// Return true to disable winit's internal message dispatching.
     ///
@@ -209,6 +271,29 @@ pub trait EventLoopBuilderExtWindows {
     fn with_msg_hook<F>(&mut self, callback: F) -> &mut Self
     where
         F: FnMut(*const c_void) -> bool + 'static;
+
+    /// Sets the [AppUserModelID] of the application when the event loop is created, before any
+    /// window exists.
+    ///
+    /// The identifier attributes the windows, the notifications and the [`JumpList`] to the
+    /// application, and groups its windows under one taskbar button. By default, the system
+    /// derives one from the executable, which breaks when it's launched through another one.
+    /// Windows can override it with [`WindowAttributesExtWindows::with_app_user_model_id`].
+    ///
+    /// [AppUserModelID]: https://learn.microsoft.com/en-us/windows/win32/shell/appids
+    fn with_app_user_model_id(&mut self, id: &str) -> &mut Self;
+
+    /// Whether to hand the launches of the application over to its running instance.
+    ///
+    /// When enabled and another instance of the same executable is running, creating the event
+    /// loop sends it the files this process was launched with, which it receives with
+    /// [`ApplicationHandler::open_files`], and exits the process. A launch without files is
+    /// received with [`ApplicationHandler::reopen_requested`] instead. This is what applications
+    /// associated with file types or staying in the background usually want, as the shell launches
+    /// a new process for each opened file and each click on a pinned taskbar button.
+    ///
+    /// The default is `false`.
+    fn with_single_instance(&mut self, single_instance: bool) -> &mut Self;
 }
 
 impl EventLoopBuilderExtWindows for EventLoopBuilder {
@@ -220,7 +305,13 @@ impl EventLoopBuilderExtWindows for EventLoopBuilder {
 
     #[inline]
     fn with_dpi_aware(&mut self, dpi_aware: bool) -> &mut Self {
-        self.platform_specific.dpi_aware = dpi_aware;
+        self.platform_specific.dpi_awareness = dpi_aware.then_some(DpiAwareness::PerMonitorV2);
+        self
+    }
+
+    #[inline]
+    fn with_dpi_awareness(&mut self, awareness: DpiAwareness) -> &mut Self {
+        self.platform_specific.dpi_awareness = Some(awareness);
         self
     }
 
@@ -232,6 +323,471 @@ impl EventLoopBuilderExtWindows for EventLoopBuilder {
         self.platform_specific.msg_hook = Some(Box::new(callback));
         self
     }
+
+    #[inline]
+    fn with_app_user_model_id(&mut self, id: &str) -> &mut Self {
+        self.platform_specific.app_user_model_id = Some(id.to_owned());
+        self
+    }
+
+    #[inline]
+    fn with_single_instance(&mut self, single_instance: bool) -> &mut Self {
+        self.platform_specific.single_instance = single_instance;
+        self
+    }
+}
+
+/// Additional methods on [`EventLoop`] that are specific to Windows.
+pub trait EventLoopExtWindows {
+    /// Sets the jump list of the application, shown when right-clicking its taskbar button.
+    ///
+    /// The jump list replaces the previous one and is kept by the system after the application
+    /// exits. Choosing a task launches the application with the arguments of the task, which are
+    /// then delivered to the running instance with
+    /// [`ApplicationHandlerExtWindows::jump_list_task`].
+    ///
+    /// A launch from a task is recognized when the event loop is created: if an instance is
+    /// running, the arguments are sent to it and the process exits. Otherwise the arguments are
+    /// delivered to the new instance after [`ApplicationHandler::can_create_surfaces`]. The
+    /// arguments are preceded by a `--winit-jump-list-task` marker in the command line of the
+    /// process, which applications parsing it before creating the event loop should skip.
+    fn set_jump_list(&self, jump_list: JumpList) -> Result<(), RequestError>;
+
+    /// Sets which devices are registered for Raw Input and how.
+    ///
+    /// Raw Input is what [`DeviceEvent`]s are made from. The devices are still only registered
+    /// as allowed by [`listen_device_events`] and [`listen_device_classes`], the mice and
+    /// keyboards being filtered by [`DeviceClass::MOUSE`] and [`DeviceClass::KEYBOARD`] and the
+    /// other devices by [`DeviceClass::HID`].
+    ///
+    /// [`DeviceEvent`]: crate::event::DeviceEvent
+    /// [`listen_device_events`]: ActiveEventLoop::listen_device_events
+    /// [`listen_device_classes`]: ActiveEventLoop::listen_device_classes
+    /// [`DeviceClass::MOUSE`]: crate::event_loop::DeviceClass::MOUSE
+    /// [`DeviceClass::KEYBOARD`]: crate::event_loop::DeviceClass::KEYBOARD
+    /// [`DeviceClass::HID`]: crate::event_loop::DeviceClass::HID
+    fn set_raw_input_config(&self, config: RawInputConfig);
+
+    /// Sets the quality of service of the process, which Windows uses to schedule it on the
+    /// efficiency or the performance cores, and to lower its timer resolution.
+    ///
+    /// The QoS is lowered to [`EcoQos::Eco`] while all the windows set with
+    /// [`WindowExtWindows::set_throttle_when_occluded`] are minimized.
+    ///
+    /// Requires Windows 10 version 1709 or later, [`EcoQos::Eco`] only lowering the QoS on
+    /// Windows 11.
+    fn set_process_power_throttling(&self, qos: EcoQos) -> Result<(), RequestError>;
+
+    /// Returns the DPI awareness of the process, set with
+    /// [`EventLoopBuilderExtWindows::with_dpi_awareness`] or in the manifest of the application.
+    fn dpi_awareness(&self) -> DpiAwareness;
+}
+
+impl EventLoopExtWindows for EventLoop {
+    #[inline]
+    fn set_jump_list(&self, jump_list: JumpList) -> Result<(), RequestError> {
+        self.event_loop.set_jump_list(&jump_list)
+    }
+
+    #[inline]
+    fn set_raw_input_config(&self, config: RawInputConfig) {
+        self.event_loop.set_raw_input_config(config)
+    }
+
+    #[inline]
+    fn set_process_power_throttling(&self, qos: EcoQos) -> Result<(), RequestError> {
+        self.event_loop.set_process_power_throttling(qos)
+    }
+
+    #[inline]
+    fn dpi_awareness(&self) -> DpiAwareness {
+        self.event_loop.dpi_awareness()
+    }
+}
+
+/// Additional methods on [`ActiveEventLoop`] that are specific to Windows.
+pub trait ActiveEventLoopExtWindows {
+    /// Sets the jump list of the application, see [`EventLoopExtWindows::set_jump_list`].
+    fn set_jump_list(&self, jump_list: JumpList) -> Result<(), RequestError>;
+
+    /// Sets which devices are registered for Raw Input and how, see
+    /// [`EventLoopExtWindows::set_raw_input_config`].
+    fn set_raw_input_config(&self, config: RawInputConfig);
+
+    /// Sets the quality of service of the process, see
+    /// [`EventLoopExtWindows::set_process_power_throttling`].
+    fn set_process_power_throttling(&self, qos: EcoQos) -> Result<(), RequestError>;
+
+    /// Returns the DPI awareness of the process, see [`EventLoopExtWindows::dpi_awareness`].
+    fn dpi_awareness(&self) -> DpiAwareness;
+}
+
+impl ActiveEventLoopExtWindows for dyn ActiveEventLoop + '_ {
+    #[inline]
+    fn set_jump_list(&self, jump_list: JumpList) -> Result<(), RequestError> {
+        let event_loop = self
+            .as_any()
+            .downcast_ref::<crate::platform_impl::ActiveEventLoop>()
+            .expect("non Windows event loop on Windows");
+        event_loop.set_jump_list(&jump_list)
+    }
+
+    #[inline]
+    fn set_raw_input_config(&self, config: RawInputConfig) {
+        let event_loop = self
+            .as_any()
+            .downcast_ref::<crate::platform_impl::ActiveEventLoop>()
+            .expect("non Windows event loop on Windows");
+        event_loop.set_raw_input_config(config)
+    }
+
+    #[inline]
+    fn set_process_power_throttling(&self, qos: EcoQos) -> Result<(), RequestError> {
+        let event_loop = self
+            .as_any()
+            .downcast_ref::<crate::platform_impl::ActiveEventLoop>()
+            .expect("non Windows event loop on Windows");
+        event_loop.set_process_power_throttling(qos)
+    }
+
+    #[inline]
+    fn dpi_awareness(&self) -> DpiAwareness {
+        let event_loop = self
+            .as_any()
+            .downcast_ref::<crate::platform_impl::ActiveEventLoop>()
+            .expect("non Windows event loop on Windows");
+        event_loop.dpi_awareness()
+    }
+}
+
+/// The jump list of the application, set with [`EventLoopExtWindows::set_jump_list`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct JumpList {
+    pub(crate) category: Option<JumpListCategory>,
+    pub(crate) tasks: Vec<JumpListTask>,
+}
+
+impl JumpList {
+    /// An empty jump list.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Shows the documents the application recently or frequently used.
+    ///
+    /// The documents are maintained by the system, from the files opened with the application
+    /// through its registered file types or added with `SHAddToRecentDocs`.
+    #[inline]
+    pub fn with_category(mut self, category: JumpListCategory) -> Self {
+        self.category = Some(category);
+        self
+    }
+
+    /// Adds a task, shown in the order they were added.
+    #[inline]
+    pub fn with_task(mut self, task: JumpListTask) -> Self {
+        self.tasks.push(task);
+        self
+    }
+}
+
+/// The documents shown in a [`JumpList`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum JumpListCategory {
+    /// Corresponds to `KDC_RECENT`.
+    Recent,
+
+    /// Corresponds to `KDC_FREQUENT`.
+    Frequent,
+}
+
+/// A task of a [`JumpList`], launching the application with its arguments.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct JumpListTask {
+    /// The title shown to the user, like `"New Window"`.
+    pub title: String,
+
+    /// The arguments delivered with [`ApplicationHandlerExtWindows::jump_list_task`].
+    pub arguments: Vec<String>,
+
+    /// The tooltip of the task.
+    pub description: Option<String>,
+}
+
+impl JumpListTask {
+    /// Creates a task from its title and arguments.
+    pub fn new(title: impl Into<String>, arguments: &[&str]) -> Self {
+        Self {
+            title: title.into(),
+            arguments: arguments.iter().map(|argument| argument.to_string()).collect(),
+            description: None,
+        }
+    }
+}
+
+/// The DPI awareness of the process, set with
+/// [`EventLoopBuilderExtWindows::with_dpi_awareness`].
+///
+/// For a detailed explanation, see [`DPI_AWARENESS_CONTEXT docs`].
+///
+/// [`DPI_AWARENESS_CONTEXT docs`]: https://learn.microsoft.com/en-us/windows/win32/hidpi/dpi-awareness-context
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DpiAwareness {
+    /// Corresponds to `DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2`.
+    ///
+    /// The windows are scaled by the application for the DPI of their monitor, their non-client
+    /// area and dialogs being scaled by the system. Falls back to [`DpiAwareness::PerMonitor`]
+    /// before Windows 10 version 1703.
+    #[default]
+    PerMonitorV2,
+
+    /// Corresponds to `DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE`.
+    ///
+    /// The windows are scaled by the application for the DPI of their monitor.
+    PerMonitor,
+
+    /// Corresponds to `DPI_AWARENESS_CONTEXT_SYSTEM_AWARE`.
+    ///
+    /// The windows are scaled by the application for the DPI of the primary monitor when the
+    /// user logged in, and stretched by the system on the monitors with another DPI.
+    System,
+
+    /// Corresponds to `DPI_AWARENESS_CONTEXT_UNAWARE`.
+    ///
+    /// The windows are always 96 DPI, and stretched by the system on the other monitors.
+    Unaware,
+}
+
+/// The quality of service of the process, set with
+/// [`EventLoopExtWindows::set_process_power_throttling`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum EcoQos {
+    /// Windows decides, usually from the visibility and the focus of the windows.
+    #[default]
+    Default,
+
+    /// The process is throttled, running on the efficiency cores.
+    ///
+    /// Corresponds to setting `PROCESS_POWER_THROTTLING_EXECUTION_SPEED`.
+    Eco,
+
+    /// The process is never throttled.
+    ///
+    /// Corresponds to clearing `PROCESS_POWER_THROTTLING_EXECUTION_SPEED`.
+    HighQos,
+}
+
+/// The Raw Input registration of the devices, set with
+/// [`EventLoopExtWindows::set_raw_input_config`].
+///
+/// By default, mice and keyboards are registered, only while the application is in the
+/// foreground unless [`DeviceEvents::Always`] is used, and the legacy mouse messages are kept.
+///
+/// [`DeviceEvents::Always`]: crate::event_loop::DeviceEvents::Always
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RawInputConfig {
+    pub(crate) usages: Vec<RawInputUsage>,
+    pub(crate) input_sink: bool,
+    pub(crate) no_legacy_mouse: bool,
+}
+
+impl Default for RawInputConfig {
+    fn default() -> Self {
+        Self {
+            usages: vec![RawInputUsage::MOUSE, RawInputUsage::KEYBOARD],
+            input_sink: false,
+            no_legacy_mouse: false,
+        }
+    }
+}
+
+impl RawInputConfig {
+    /// The default configuration.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the usages of the devices to register, replacing the default mice and keyboards.
+    ///
+    /// Only mice and keyboards produce [`DeviceEvent`]s from their input, the hotplug of the
+    /// other devices being delivered with [`ApplicationHandler::input_device_added`] and
+    /// [`ApplicationHandler::input_device_removed`].
+    ///
+    /// [`DeviceEvent`]: crate::event::DeviceEvent
+    #[inline]
+    pub fn with_usages(mut self, usages: &[RawInputUsage]) -> Self {
+        self.usages = usages.to_vec();
+        self
+    }
+
+    /// Receives the input while the application is in the background, whatever the
+    /// [`DeviceEvents`] set, except [`DeviceEvents::Never`].
+    ///
+    /// Corresponds to `RIDEV_INPUTSINK`.
+    ///
+    /// [`DeviceEvents`]: crate::event_loop::DeviceEvents
+    /// [`DeviceEvents::Never`]: crate::event_loop::DeviceEvents::Never
+    #[inline]
+    pub fn with_input_sink(mut self, input_sink: bool) -> Self {
+        self.input_sink = input_sink;
+        self
+    }
+
+    /// Suppresses the legacy mouse messages while the mice are registered, so the mouse input is
+    /// only delivered once, as [`DeviceEvent`]s.
+    ///
+    /// The windows then receive no [`WindowEvent::PointerMoved`] and
+    /// [`WindowEvent::PointerButton`] from the mouse, and can't be moved or resized with it. The
+    /// legacy keyboard messages are always kept, as the keyboard and text input of the windows
+    /// are made from them.
+    ///
+    /// Corresponds to `RIDEV_NOLEGACY`.
+    ///
+    /// [`DeviceEvent`]: crate::event::DeviceEvent
+    /// [`WindowEvent::PointerMoved`]: crate::event::WindowEvent::PointerMoved
+    /// [`WindowEvent::PointerButton`]: crate::event::WindowEvent::PointerButton
+    #[inline]
+    pub fn with_no_legacy_mouse(mut self, no_legacy_mouse: bool) -> Self {
+        self.no_legacy_mouse = no_legacy_mouse;
+        self
+    }
+}
+
+/// The HID usage page and usage of devices registered for Raw Input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RawInputUsage {
+    /// The usage page, like `0x01` for the generic desktop controls.
+    pub page: u16,
+
+    /// The usage in the page.
+    pub usage: u16,
+}
+
+impl RawInputUsage {
+    /// Mice, from the generic desktop controls.
+    pub const MOUSE: Self = Self::new(0x01, 0x02);
+    /// Joysticks, from the generic desktop controls.
+    pub const JOYSTICK: Self = Self::new(0x01, 0x04);
+    /// Gamepads, from the generic desktop controls.
+    pub const GAMEPAD: Self = Self::new(0x01, 0x05);
+    /// Keyboards, from the generic desktop controls.
+    pub const KEYBOARD: Self = Self::new(0x01, 0x06);
+
+    /// Creates a usage from its page and usage.
+    pub const fn new(page: u16, usage: u16) -> Self {
+        Self { page, usage }
+    }
+}
+
+/// The additional formats accepted by the drop target of a window, set with
+/// [`WindowExtWindows::set_drop_format_handler`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct DropFormatHandler {
+    pub(crate) formats: Vec<&'static str>,
+    pub(crate) virtual_files: bool,
+}
+
+impl DropFormatHandler {
+    /// A handler accepting no additional format.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accepts the drops offering the format registered with this name, which is listed in the
+    /// formats of the drop as [`ClipboardFormat::Custom`].
+    ///
+    /// [`ClipboardFormat::Custom`]: crate::clipboard::ClipboardFormat::Custom
+    #[inline]
+    pub fn with_format(mut self, name: &'static str) -> Self {
+        self.formats.push(name);
+        self
+    }
+
+    /// Accepts the files which only exist in the source, like the attachments dragged out of a
+    /// mail client, offered with `FileGroupDescriptorW` and `FileContents`.
+    ///
+    /// The files are listed in the formats of the drop as [`ClipboardFormat::Files`]. Reading
+    /// them saves them in a new temporary directory, which the application should move or delete
+    /// once it's done with them.
+    ///
+    /// [`ClipboardFormat::Files`]: crate::clipboard::ClipboardFormat::Files
+    #[inline]
+    pub fn with_virtual_files(mut self, virtual_files: bool) -> Self {
+        self.virtual_files = virtual_files;
+        self
+    }
+}
+
+/// A button of the toolbar in the taskbar thumbnail of a window, set with
+/// [`WindowExtWindows::set_thumbbar_buttons`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ThumbbarButton {
+    /// The identifier delivered with [`ApplicationHandlerExtWindows::thumbbar_button_clicked`].
+    pub id: u32,
+
+    /// The icon of the button, usually 16x16 pixels at 96 DPI.
+    pub icon: Icon,
+
+    /// The tooltip of the button.
+    pub tooltip: String,
+
+    /// Whether the button can be clicked, or is grayed out.
+    pub enabled: bool,
+
+    /// Whether clicking the button closes the thumbnail.
+    pub dismiss_on_click: bool,
+}
+
+impl ThumbbarButton {
+    /// Creates an enabled button from its identifier, icon and tooltip.
+    pub fn new(id: u32, icon: Icon, tooltip: impl Into<String>) -> Self {
+        Self { id, icon, tooltip: tooltip.into(), enabled: true, dismiss_on_click: false }
+    }
+}
+
+/// An image shown in the taskbar thumbnail of a window, set with
+/// [`WindowExtWindows::set_taskbar_preview`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TaskbarPreview {
+    pub(crate) image: RgbaIcon,
+}
+
+impl TaskbarPreview {
+    /// Creates a preview from 32bpp RGBA data.
+    ///
+    /// The length of `rgba` must be divisible by 4, and `width * height` must equal
+    /// `rgba.len() / 4`. Otherwise, this will return a `BadIcon` error.
+    pub fn from_rgba(rgba: Vec<u8>, width: u32, height: u32) -> Result<Self, BadIcon> {
+        Ok(Self { image: RgbaIcon::from_rgba(rgba, width, height)? })
+    }
+}
+
+/// Additional events on [`ApplicationHandler`] that are specific to Windows.
+///
+/// This can be registered with [`ApplicationHandler::windows_handler`].
+pub trait ApplicationHandlerExtWindows: ApplicationHandler {
+    /// The user chose a task of the [`JumpList`].
+    ///
+    /// The `arguments` are the ones of the [`JumpListTask`].
+    fn jump_list_task(&mut self, event_loop: &dyn ActiveEventLoop, arguments: Vec<String>) {
+        let _ = (event_loop, arguments);
+    }
+
+    /// A button set with [`WindowExtWindows::set_thumbbar_buttons`] was clicked.
+    ///
+    /// The `id` is the one of the [`ThumbbarButton`].
+    fn thumbbar_button_clicked(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        window_id: WindowId,
+        id: u32,
+    ) {
+        let _ = (event_loop, window_id, id);
+    }
 }
 
 /// Additional methods on `Window` that are specific to Windows.
@@ -264,12 +820,24 @@ pub trait WindowExtWindows {
 
     /// Sets system-drawn backdrop type.
     ///
-    /// Requires Windows 11 build 22523+.
+    /// The materials follow the theme of the window. On a [transparent] window, they're also
+    /// drawn behind its client area.
+    ///
+    /// Requires Windows 11 build 22523+. Before it, only [`BackdropType::MainWindow`] is
+    /// supported, from Windows 11 build 22000.
+    ///
+    /// [transparent]: crate::window::Window::set_transparent
     fn set_system_backdrop(&self, backdrop_type: BackdropType);
 
     /// Sets the color of the window border.
     ///
+    /// `None` removes the border, and [`Color::SYSTEM_DEFAULT`] restores the accent border. This
+    /// also applies to the border drawn around the undecorated windows which are resizable or
+    /// have a [shadow], and is kept when the decorations change.
+    ///
     /// Supported starting with Windows 11 Build 22000.
+    ///
+    /// [shadow]: WindowExtWindows::set_undecorated_shadow
     fn set_border_color(&self, color: Option<Color>);
 
     /// Sets the background color of the title bar.
@@ -287,6 +855,99 @@ pub trait WindowExtWindows {
     /// Supported starting with Windows 11 Build 22000.
     fn set_corner_preference(&self, preference: CornerPreference);
 
+    /// Sets a hook receiving the messages of the window before winit handles them, replacing the
+    /// previous one.
+    ///
+    /// The hook is called with the window handle, the message, its `WPARAM` and its `LPARAM`. When
+    /// it returns a result, winit doesn't handle the message and returns this result from the
+    /// window procedure. This allows observing or handling the messages winit doesn't expose, like
+    /// `WM_COPYDATA` or `WM_DEVICECHANGE`, without subclassing the window.
+    ///
+    /// The hook runs on the thread of the window. The messages sent to the window by the hook
+    /// itself aren't passed to it again.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use winit::window::Window;
+    /// # fn scope(window: &dyn Window) {
+    /// use winit::platform::windows::WindowExtWindows;
+    ///
+    /// const WM_COPYDATA: u32 = 0x004a;
+    ///
+    /// window.set_wndproc_hook(|_hwnd, msg, _wparam, _lparam| {
+    ///     if msg == WM_COPYDATA {
+    ///         // Read the `COPYDATASTRUCT` pointed to by the `LPARAM`.
+    ///         return Some(1);
+    ///     }
+    ///     None
+    /// });
+    /// # }
+    /// ```
+    fn set_wndproc_hook<F>(&self, hook: F)
+    where
+        F: FnMut(HWND, u32, usize, isize) -> Option<isize> + Send + 'static;
+
+    /// Removes the hook set with [`WindowExtWindows::set_wndproc_hook`].
+    fn remove_wndproc_hook(&self);
+
+    /// Sets the buttons of the toolbar shown in the taskbar thumbnail of the window, replacing the
+    /// previous ones.
+    ///
+    /// Clicks are delivered with [`ApplicationHandlerExtWindows::thumbbar_button_clicked`]. At
+    /// most 7 buttons are shown, the next ones are ignored.
+    fn set_thumbbar_buttons(&self, buttons: Vec<ThumbbarButton>);
+
+    /// Sets the image shown in the taskbar thumbnail of the window and when peeking at it, instead
+    /// of its content.
+    ///
+    /// This is useful when the content of the window doesn't make a good preview, or isn't
+    /// available while it's minimized. The image is scaled down to fit. `None` shows the content
+    /// of the window again.
+    fn set_taskbar_preview(&self, preview: Option<TaskbarPreview>);
+
+    /// Sets the area of the maximize button drawn by the application in its custom title bar,
+    /// relative to the surface of the window.
+    ///
+    /// The system then treats the area as the maximize button, showing the snap layouts flyout of
+    /// Windows 11 when hovering it. The mouse moves and left clicks in the area are still
+    /// delivered as [`WindowEvent`]s, so the application keeps drawing and handling its button.
+    /// The window must be maximizable. `None` removes the area.
+    ///
+    /// [`WindowEvent`]: crate::event::WindowEvent
+    fn set_snap_layout_button_rect(&self, rect: Option<(Position, Size)>);
+
+    /// Sets the API the window receives its touch input from.
+    ///
+    /// The pen input is always received with `WM_POINTER`. [`PointerApi::WmPointer`] also
+    /// enables the mouse input in the pointer messages for the whole process with
+    /// `EnableMouseInPointer`, which can't be undone, the mouse still being delivered as before
+    /// to all the windows.
+    ///
+    /// The default is [`PointerApi::Legacy`].
+    fn set_pointer_api(&self, api: PointerApi);
+
+    /// Sets whether the process is throttled while the window is occluded, which on Windows is
+    /// while it's minimized.
+    ///
+    /// The quality of service of the process is lowered to [`EcoQos::Eco`] while all the windows
+    /// with this set are minimized, and restored to the one set with
+    /// [`EventLoopExtWindows::set_process_power_throttling`] when one of them is shown again.
+    ///
+    /// The default is `false`.
+    fn set_throttle_when_occluded(&self, enabled: bool);
+
+    /// Sets the additional formats accepted when data is dropped on the window.
+    ///
+    /// The drops only offering these formats are then delivered with
+    /// [`WindowEvent::DataHovered`] and [`WindowEvent::DataDropped`] instead of being rejected.
+    /// `None` only accepts the formats of [`ClipboardFormat`].
+    ///
+    /// [`WindowEvent::DataHovered`]: crate::event::WindowEvent::DataHovered
+    /// [`WindowEvent::DataDropped`]: crate::event::WindowEvent::DataDropped
+    /// [`ClipboardFormat`]: crate::clipboard::ClipboardFormat
+    fn set_drop_format_handler(&self, handler: Option<DropFormatHandler>);
+
     /// Get the raw window handle for this [`Window`] without checking for thread affinity.
     ///
     /// Window handles in Win32 have a property called "thread affinity" that ties them to their
@@ -401,6 +1062,57 @@ impl WindowExtWindows for dyn Window + '_ {
         window.set_corner_preference(preference)
     }
 
+    #[inline]
+    fn set_wndproc_hook<F>(&self, hook: F)
+    where
+        F: FnMut(HWND, u32, usize, isize) -> Option<isize> + Send + 'static,
+    {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
+        window.set_wndproc_hook(Some(Box::new(hook)))
+    }
+
+    #[inline]
+    fn remove_wndproc_hook(&self) {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
+        window.set_wndproc_hook(None)
+    }
+
+    #[inline]
+    fn set_thumbbar_buttons(&self, buttons: Vec<ThumbbarButton>) {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
+        window.set_thumbbar_buttons(buttons)
+    }
+
+    #[inline]
+    fn set_taskbar_preview(&self, preview: Option<TaskbarPreview>) {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
+        window.set_taskbar_preview(preview)
+    }
+
+    #[inline]
+    fn set_snap_layout_button_rect(&self, rect: Option<(Position, Size)>) {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
+        window.set_snap_layout_button_rect(rect)
+    }
+
+    #[inline]
+    fn set_pointer_api(&self, api: PointerApi) {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
+        window.set_pointer_api(api)
+    }
+
+    #[inline]
+    fn set_throttle_when_occluded(&self, enabled: bool) {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
+        window.set_throttle_when_occluded(enabled)
+    }
+
+    #[inline]
+    fn set_drop_format_handler(&self, handler: Option<DropFormatHandler>) {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
+        window.set_drop_format_handler(handler)
+    }
+
     #[cfg(feature = "rwh_06")]
     unsafe fn window_handle_any_thread(
         &self,
@@ -491,7 +1203,23 @@ pub trait WindowAttributesExtWindows {
     /// This sets `ICON_BIG`. A good ceiling here is 256x256.
     fn with_taskbar_icon(self, taskbar_icon: Option<Icon>) -> Self;
 
-    /// This sets `WS_EX_NOREDIRECTIONBITMAP`.
+    /// Whether the window is created without a redirection bitmap, by setting
+    /// `WS_EX_NOREDIRECTIONBITMAP`.
+    ///
+    /// Such a window has no content drawn by GDI or presented to its redirection surface, so it
+    /// must be presented with DirectComposition or `Windows.UI.Composition`, by binding a visual
+    /// tree to its handle, for example with a swap chain created by
+    /// `IDXGIFactory2::CreateSwapChainForComposition`. This is the way to get a flicker-free
+    /// transparent window rendered by the GPU.
+    ///
+    /// When the window is also [transparent], it isn't made transparent with the blur behind
+    /// effect of the DWM, so that only the alpha of the composition surface is used. This is
+    /// implied by a [surface format hint] with alpha.
+    ///
+    /// The style can't be changed once the window is created.
+    ///
+    /// [transparent]: crate::window::WindowAttributes::with_transparent
+    /// [surface format hint]: crate::window::WindowAttributes::with_surface_format_hint
     fn with_no_redirection_bitmap(self, flag: bool) -> Self;
 
     /// Enables or disables drag and drop support (enabled by default). Will interfere with other
@@ -524,6 +1252,8 @@ pub trait WindowAttributesExtWindows {
 
     /// Sets the color of the window border.
     ///
+    /// See [`WindowExtWindows::set_border_color`] for the details.
+    ///
     /// Supported starting with Windows 11 Build 22000.
     fn with_border_color(self, color: Option<Color>) -> Self;
 
@@ -541,6 +1271,14 @@ pub trait WindowAttributesExtWindows {
     ///
     /// Supported starting with Windows 11 Build 22000.
     fn with_corner_preference(self, corners: CornerPreference) -> Self;
+
+    /// Sets the [AppUserModelID] of the window, overriding the one of the application.
+    ///
+    /// The taskbar groups the windows with the same identifier under one button. See
+    /// [`EventLoopBuilderExtWindows::with_app_user_model_id`] to set the one of the application.
+    ///
+    /// [AppUserModelID]: https://learn.microsoft.com/en-us/windows/win32/shell/appids
+    fn with_app_user_model_id(self, id: &str) -> Self;
 }
 
 impl WindowAttributesExtWindows for WindowAttributes {
@@ -627,6 +1365,12 @@ impl WindowAttributesExtWindows for WindowAttributes {
         self.platform_specific.corner_preference = Some(corners);
         self
     }
+
+    #[inline]
+    fn with_app_user_model_id(mut self, id: &str) -> Self {
+        self.platform_specific.app_user_model_id = Some(id.to_owned());
+        self
+    }
 }
 
 /// Additional methods on `MonitorHandle` that are specific to Windows.
@@ -686,15 +1430,17 @@ impl FingerIdExtWindows for FingerId {
 
 /// Additional methods on `Icon` that are specific to Windows.
 pub trait IconExtWindows: Sized {
-    /// Create an icon from a file path.
+    /// Create an icon from a file path, using the system's icon loader.
     ///
     /// Specify `size` to load a specific icon size from the file, or `None` to load the default
     /// icon size from the file.
     ///
     /// In cases where the specified size does not exist in the file, Windows may perform scaling
     /// to get an icon of the desired size.
-    fn from_path<P: AsRef<Path>>(path: P, size: Option<PhysicalSize<u32>>)
-        -> Result<Self, BadIcon>;
+    fn from_path_with_size<P: AsRef<Path>>(
+        path: P,
+        size: Option<PhysicalSize<u32>>,
+    ) -> Result<Self, BadIcon>;
 
     /// Create an icon from a resource embedded in this executable or library.
     ///
@@ -707,7 +1453,7 @@ pub trait IconExtWindows: Sized {
 }
 
 impl IconExtWindows for Icon {
-    fn from_path<P: AsRef<Path>>(
+    fn from_path_with_size<P: AsRef<Path>>(
         path: P,
         size: Option<PhysicalSize<u32>>,
     ) -> Result<Self, BadIcon> {
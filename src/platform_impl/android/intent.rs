@@ -0,0 +1,75 @@
+//! The data of the intent the activity was started with, read with JNI as `android-activity`
+//! doesn't expose it.
+
+use std::path::PathBuf;
+
+use android_activity::AndroidApp;
+use jni::errors::Result;
+use jni::objects::{JObject, JString};
+use jni::strings::JNIStr;
+use jni::{jni_sig, jni_str, Env, JavaVM};
+use tracing::warn;
+
+/// What the activity was started to view.
+pub(crate) enum ViewData {
+    File(PathBuf),
+    Url(String),
+}
+
+/// The data of the intent the activity was started with, when its action is `ACTION_VIEW`.
+pub(crate) fn view_data(app: &AndroidApp) -> Option<ViewData> {
+    // SAFETY: The pointers are valid as long as `app` is.
+    let vm = unsafe { JavaVM::from_raw(app.vm_as_ptr().cast()) };
+    let activity = app.activity_as_ptr() as jni::sys::jobject;
+    let result = vm.attach_current_thread(|env| -> Result<_> {
+        // SAFETY: The cast doesn't own the global reference of the activity, so it isn't deleted.
+        let activity = unsafe { env.as_cast_raw::<JObject<'_>>(&activity)? };
+        let intent = env
+            .call_method(
+                &*activity,
+                jni_str!("getIntent"),
+                jni_sig!(() -> android.content.Intent),
+                &[],
+            )?
+            .l()?;
+        if intent.is_null() {
+            return Ok(None);
+        }
+
+        let action = string(env, &intent, jni_str!("getAction"))?;
+        if action.as_deref() != Some("android.intent.action.VIEW") {
+            return Ok(None);
+        }
+
+        let uri = env
+            .call_method(&intent, jni_str!("getData"), jni_sig!(() -> android.net.Uri), &[])?
+            .l()?;
+        if uri.is_null() {
+            return Ok(None);
+        }
+
+        // Only the `file` URIs have a path, the documents of other applications come as `content`
+        // URIs, to be opened with the `ContentResolver`.
+        if string(env, &uri, jni_str!("getScheme"))?.as_deref() == Some("file") {
+            if let Some(path) = string(env, &uri, jni_str!("getPath"))? {
+                return Ok(Some(ViewData::File(path.into())));
+            }
+        }
+        Ok(string(env, &uri, jni_str!("toString"))?.map(ViewData::Url))
+    });
+
+    result.unwrap_or_else(|error| {
+        warn!("Failed to read the intent of the activity: {error}");
+        None
+    })
+}
+
+/// Call a method returning a string, which may be `null`.
+fn string(env: &mut Env<'_>, object: &JObject<'_>, method: &JNIStr) -> Result<Option<String>> {
+    let value = env.call_method(object, method, jni_sig!(() -> java.lang.String), &[])?.l()?;
+    let value = env.cast_local::<JString<'_>>(value)?;
+    if value.is_null() {
+        return Ok(None);
+    }
+    value.try_to_string(env).map(Some)
+}
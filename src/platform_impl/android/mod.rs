@@ -12,22 +12,35 @@ use android_activity::{
 use tracing::{debug, trace, warn};
 
 use crate::application::ApplicationHandler;
+use crate::clipboard::{Clipboard as RootClipboard, ClipboardData, ClipboardFormat};
 use crate::cursor::Cursor;
+#[cfg(feature = "dialogs")]
+use crate::dialogs::{FileDialogOptions, MessageBoxConfig};
 use crate::dpi::{PhysicalPosition, PhysicalSize, Position, Size};
+use crate::drag::DragData;
 use crate::error::{EventLoopError, NotSupportedError, RequestError};
-use crate::event::{self, DeviceId, Force, StartCause, SurfaceSizeWriter};
+use crate::event::{
+    self, DeviceId, DeviceInfo, Force, LanguageTag, PlatformData, PowerState, StartCause,
+    SurfaceSizeWriter, SystemPreferences, TouchContact,
+};
 use crate::event_loop::{
-    ActiveEventLoop as RootActiveEventLoop, ControlFlow, DeviceEvents,
-    EventLoopProxy as RootEventLoopProxy, OwnedDisplayHandle as RootOwnedDisplayHandle,
+    ActiveEventLoop as RootActiveEventLoop, AsyncRequestSerial, ControlFlow, DeviceClass,
+    DeviceEvents, EventLoopProxy as RootEventLoopProxy,
+    OwnedDisplayHandle as RootOwnedDisplayHandle,
+};
+use crate::keyboard::{Key, KeyCode, KeyboardLayout, Led};
+use crate::monitor::{
+    ColorCapabilities, GammaRamp, HdrMetadata, MonitorHandle as RootMonitorHandle, MonitorIdentity,
+    Orientation,
 };
-use crate::monitor::MonitorHandle as RootMonitorHandle;
 use crate::platform::pump_events::PumpStatus;
 use crate::window::{
-    self, CursorGrabMode, CustomCursor, CustomCursorSource, Fullscreen, ImePurpose,
-    ResizeDirection, Theme, Window as CoreWindow, WindowAttributes, WindowButtons, WindowId,
-    WindowLevel,
+    self, Coalescing, CursorGrabMode, CursorGrabPolicy, CursorThemeInfo, CustomCursor,
+    CustomCursorSource, Fullscreen, ImePurpose, ResizeDirection, Rgba, Theme, Window as CoreWindow,
+    WindowAttributes, WindowButtons, WindowId, WindowLevel,
 };
 
+mod intent;
 mod keycodes;
 
 pub(crate) use crate::cursor::{
@@ -35,6 +48,12 @@ pub(crate) use crate::cursor::{
 };
 pub(crate) use crate::icon::NoIcon as PlatformIcon;
 
+/// Nothing is grabbed by the application that would outlive a panic.
+pub(crate) fn restore_after_panic() {}
+
+/// There is no dialog that can be shown from the panic hook.
+pub(crate) fn show_panic_dialog(_message: &str) {}
+
 static HAS_FOCUS: AtomicBool = AtomicBool::new(true);
 
 /// Returns the minimum `Option<Duration>`, taking into account that `None`
@@ -79,12 +98,25 @@ impl SharedFlag {
 #[derive(Clone)]
 pub struct RedrawRequester {
     flag: SharedFlagSetter,
+    /// The time of the scheduled redraw, shared with the event loop.
+    redraw_at: Arc<Mutex<Option<Instant>>>,
     waker: AndroidAppWaker,
 }
 
 impl RedrawRequester {
-    fn new(flag: &SharedFlag, waker: AndroidAppWaker) -> Self {
-        RedrawRequester { flag: flag.setter(), waker }
+    fn new(
+        flag: &SharedFlag,
+        redraw_at: Arc<Mutex<Option<Instant>>>,
+        waker: AndroidAppWaker,
+    ) -> Self {
+        RedrawRequester { flag: flag.setter(), redraw_at, waker }
+    }
+
+    pub fn request_redraw_at(&self, instant: Instant) {
+        let mut redraw_at = self.redraw_at.lock().unwrap();
+        *redraw_at = Some(redraw_at.map_or(instant, |deadline| deadline.min(instant)));
+        // Wake up the main loop, so it waits for the new deadline.
+        self.waker.wake();
     }
 
     pub fn request_redraw(&self) {
@@ -103,12 +135,17 @@ pub struct EventLoop {
     pub(crate) android_app: AndroidApp,
     window_target: ActiveEventLoop,
     redraw_flag: SharedFlag,
+    redraw_at: Arc<Mutex<Option<Instant>>>,
     loop_running: bool, // Dispatched `NewEvents<Init>`
     running: bool,
     pending_redraw: bool,
     cause: StartCause,
     ignore_volume_keys: bool,
     combining_accent: Option<char>,
+    /// The locale of the configuration, to report its changes.
+    locale: LanguageTag,
+    /// What the activity was started to view, delivered once the surfaces can be created.
+    view_data: Option<intent::ViewData>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -137,6 +174,7 @@ impl EventLoop {
              Android",
         );
         let redraw_flag = SharedFlag::new();
+        let redraw_at = Arc::new(Mutex::new(None));
 
         Ok(Self {
             android_app: android_app.clone(),
@@ -144,16 +182,23 @@ impl EventLoop {
                 app: android_app.clone(),
                 control_flow: Cell::new(ControlFlow::default()),
                 exit: Cell::new(false),
-                redraw_requester: RedrawRequester::new(&redraw_flag, android_app.create_waker()),
+                redraw_requester: RedrawRequester::new(
+                    &redraw_flag,
+                    Arc::clone(&redraw_at),
+                    android_app.create_waker(),
+                ),
                 proxy_wake_up,
             },
             redraw_flag,
+            redraw_at,
             loop_running: false,
             running: false,
             pending_redraw: false,
             cause: StartCause::Init,
             ignore_volume_keys: attributes.ignore_volume_keys,
             combining_accent: None,
+            locale: locale(android_app),
+            view_data: intent::view_data(android_app),
         })
     }
 
@@ -180,6 +225,15 @@ impl EventLoop {
             match event {
                 MainEvent::InitWindow { .. } => {
                     app.can_create_surfaces(&self.window_target);
+                    match self.view_data.take() {
+                        Some(intent::ViewData::File(path)) => {
+                            app.open_files(&self.window_target, vec![path])
+                        },
+                        Some(intent::ViewData::Url(url)) => {
+                            app.open_urls(&self.window_target, vec![url])
+                        },
+                        None => (),
+                    }
                 },
                 MainEvent::TerminateWindow { .. } => {
                     app.destroy_surfaces(&self.window_target);
@@ -213,6 +267,12 @@ impl EventLoop {
 
                         app.window_event(&self.window_target, GLOBAL_WINDOW, event);
                     }
+
+                    let locale = locale(&self.android_app);
+                    if locale != self.locale {
+                        self.locale = locale.clone();
+                        app.locale_changed(&self.window_target, locale);
+                    }
                 },
                 MainEvent::LowMemory => {
                     app.memory_warning(&self.window_target);
@@ -291,7 +351,7 @@ impl EventLoop {
                 app.window_event(&self.window_target, GLOBAL_WINDOW, event);
             }
 
-            pending_redraw |= self.redraw_flag.get_and_reset();
+            pending_redraw |= self.redraw_flag.get_and_reset() | self.take_scheduled_redraw();
             if pending_redraw {
                 pending_redraw = false;
                 let event = event::WindowEvent::RedrawRequested;
@@ -344,6 +404,11 @@ impl EventLoop {
                         );
                         let finger_id = event::FingerId(FingerId(pointer.pointer_id()));
                         let force = Some(Force::Normalized(pointer.pressure() as f64));
+                        let contact = Some(TouchContact {
+                            major: pointer.touch_major() as f64,
+                            minor: pointer.touch_minor() as f64,
+                            orientation: pointer.orientation() as f64,
+                        });
 
                         match action {
                             MotionAction::Down | MotionAction::PointerDown => {
@@ -366,7 +431,7 @@ impl EventLoop {
                                     position,
                                     button: match tool_type {
                                         android_activity::input::ToolType::Finger => {
-                                            event::ButtonSource::Touch { finger_id, force }
+                                            event::ButtonSource::Touch { finger_id, force, contact }
                                         },
                                         // TODO mouse events
                                         android_activity::input::ToolType::Mouse => continue,
@@ -381,7 +446,11 @@ impl EventLoop {
                                     position,
                                     source: match tool_type {
                                         android_activity::input::ToolType::Finger => {
-                                            event::PointerSource::Touch { finger_id, force }
+                                            event::PointerSource::Touch {
+                                                finger_id,
+                                                force,
+                                                contact,
+                                            }
                                         },
                                         // TODO mouse events
                                         android_activity::input::ToolType::Mouse => continue,
@@ -398,7 +467,11 @@ impl EventLoop {
                                         position,
                                         button: match tool_type {
                                             android_activity::input::ToolType::Finger => {
-                                                event::ButtonSource::Touch { finger_id, force }
+                                                event::ButtonSource::Touch {
+                                                    finger_id,
+                                                    force,
+                                                    contact,
+                                                }
                                             },
                                             // TODO mouse events
                                             android_activity::input::ToolType::Mouse => continue,
@@ -542,7 +615,7 @@ impl EventLoop {
     ) {
         let start = Instant::now();
 
-        self.pending_redraw |= self.redraw_flag.get_and_reset();
+        self.pending_redraw |= self.redraw_flag.get_and_reset() | self.take_scheduled_redraw();
 
         timeout = if self.running
             && (self.pending_redraw || self.window_target.proxy_wake_up.load(Ordering::Relaxed))
@@ -558,7 +631,13 @@ impl EventLoop {
                 },
             };
 
-            min_timeout(control_flow_timeout, timeout)
+            let redraw_timeout = self
+                .redraw_at
+                .lock()
+                .unwrap()
+                .map(|deadline| deadline.saturating_duration_since(start));
+
+            min_timeout(min_timeout(control_flow_timeout, redraw_timeout), timeout)
         };
 
         let android_app = self.android_app.clone(); // Don't borrow self as part of poll expression
@@ -575,7 +654,8 @@ impl EventLoop {
                     // For now, user_events and redraw_requests are the only reasons to expect
                     // a wake up here so we can ignore the wake up if there are no events/requests.
                     // We also ignore wake ups while suspended.
-                    self.pending_redraw |= self.redraw_flag.get_and_reset();
+                    self.pending_redraw |=
+                        self.redraw_flag.get_and_reset() | self.take_scheduled_redraw();
                     if !self.running
                         || (!self.pending_redraw
                             && !self.window_target.proxy_wake_up.load(Ordering::Relaxed))
@@ -583,7 +663,9 @@ impl EventLoop {
                         return;
                     }
                 },
-                android_activity::PollEvent::Timeout => {},
+                android_activity::PollEvent::Timeout => {
+                    self.pending_redraw |= self.take_scheduled_redraw();
+                },
                 android_activity::PollEvent::Main(event) => {
                     main_event = Some(event);
                 },
@@ -615,6 +697,16 @@ impl EventLoop {
     fn exiting(&self) -> bool {
         self.window_target.exiting()
     }
+
+    /// Whether the scheduled redraw is due, clearing it if it is.
+    fn take_scheduled_redraw(&self) -> bool {
+        let mut redraw_at = self.redraw_at.lock().unwrap();
+        let due = redraw_at.is_some_and(|deadline| deadline <= Instant::now());
+        if due {
+            *redraw_at = None;
+        }
+        due
+    }
 }
 
 #[derive(Clone)]
@@ -644,6 +736,16 @@ impl ActiveEventLoop {
     }
 }
 
+impl RootClipboard for ActiveEventLoop {
+    fn set(&self, _data: Vec<ClipboardData>) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set is not supported").into())
+    }
+
+    fn get(&self, _format: ClipboardFormat) -> Result<AsyncRequestSerial, RequestError> {
+        Err(NotSupportedError::new("get is not supported").into())
+    }
+}
+
 impl RootActiveEventLoop for ActiveEventLoop {
     fn create_proxy(&self) -> RootEventLoopProxy {
         let event_loop_proxy = EventLoopProxy {
@@ -679,8 +781,120 @@ impl RootActiveEventLoop for ActiveEventLoop {
         None
     }
 
+    fn system_accent_color(&self) -> Option<Rgba> {
+        None
+    }
+
+    fn cursor_theme(&self) -> Option<CursorThemeInfo> {
+        None
+    }
+
+    fn system_preferences(&self) -> Option<SystemPreferences> {
+        None
+    }
+
+    fn text_scale_factor(&self) -> Option<f64> {
+        None
+    }
+
+    fn power_state(&self) -> Option<PowerState> {
+        None
+    }
+
+    fn locale(&self) -> LanguageTag {
+        locale(&self.app)
+    }
+
+    fn keyboard_layout(&self) -> Option<KeyboardLayout> {
+        None
+    }
+
+    fn key_label(&self, _key_code: KeyCode) -> Option<String> {
+        None
+    }
+
+    fn key_for_keycode(&self, _key_code: KeyCode) -> Option<Key> {
+        None
+    }
+
+    fn keycode_for_key(&self, _key: &Key) -> Option<KeyCode> {
+        None
+    }
+
+    fn set_keyboard_led(&self, _led: Led, _on: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_keyboard_led is not supported").into())
+    }
+
+    fn keyboard_led(&self, _led: Led) -> Option<bool> {
+        None
+    }
+
+    fn event_platform_data(&self) -> Option<PlatformData> {
+        None
+    }
+
     fn listen_device_events(&self, _allowed: DeviceEvents) {}
 
+    fn listen_device_classes(&self, _classes: DeviceClass) {}
+
+    fn input_devices(&self) -> Vec<DeviceInfo> {
+        Vec::new()
+    }
+
+    #[cfg(feature = "gamepad")]
+    fn set_gamepad_rumble(
+        &self,
+        _device_id: crate::event::DeviceId,
+        _strong: f32,
+        _weak: f32,
+    ) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("gamepads are not supported").into())
+    }
+
+    fn windows(&self) -> Vec<WindowId> {
+        // There is a single window, which exists as long as the native window does.
+        self.app.native_window().map(|_| GLOBAL_WINDOW).into_iter().collect()
+    }
+
+    fn with_native_modal_scope(&self, f: &mut dyn FnMut()) {
+        f()
+    }
+
+    fn start_drag(&self, _source: WindowId, _data: DragData) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("start_drag is not supported").into())
+    }
+
+    fn set_drag_image(&self, _image: Option<Cursor>) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_drag_image is not supported").into())
+    }
+
+    fn clipboard(&self) -> &dyn RootClipboard {
+        self
+    }
+
+    #[cfg(feature = "dialogs")]
+    fn pick_file(&self, _options: FileDialogOptions) -> Result<AsyncRequestSerial, RequestError> {
+        Err(NotSupportedError::new("pick_file is not supported").into())
+    }
+
+    #[cfg(feature = "dialogs")]
+    fn pick_folder(&self, _options: FileDialogOptions) -> Result<AsyncRequestSerial, RequestError> {
+        Err(NotSupportedError::new("pick_folder is not supported").into())
+    }
+
+    #[cfg(feature = "dialogs")]
+    fn save_file(&self, _options: FileDialogOptions) -> Result<AsyncRequestSerial, RequestError> {
+        Err(NotSupportedError::new("save_file is not supported").into())
+    }
+
+    #[cfg(feature = "dialogs")]
+    fn show_message_box(
+        &self,
+        _config: MessageBoxConfig,
+    ) -> Result<AsyncRequestSerial, RequestError> {
+        Err(NotSupportedError::new("show_message_box is not supported").into())
+    }
+
     fn set_control_flow(&self, control_flow: ControlFlow) {
         self.control_flow.set(control_flow)
     }
@@ -829,6 +1043,10 @@ impl CoreWindow for Window {
         self.redraw_requester.request_redraw()
     }
 
+    fn request_redraw_at(&self, instant: Instant) {
+        self.redraw_requester.request_redraw_at(instant)
+    }
+
     fn pre_present_notify(&self) {}
 
     fn inner_position(&self) -> Result<PhysicalPosition<i32>, RequestError> {
@@ -909,6 +1127,18 @@ impl CoreWindow for Window {
         None
     }
 
+    fn set_fullscreen_hdr(&self, _hdr: Option<HdrMetadata>) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_fullscreen_hdr is not supported").into())
+    }
+
+    fn fullscreen_hdr(&self) -> Option<HdrMetadata> {
+        None
+    }
+
+    fn set_gamma_ramp(&self, _ramp: Option<&GammaRamp>) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_gamma_ramp is not supported").into())
+    }
+
     fn set_decorations(&self, _decorations: bool) {}
 
     fn is_decorated(&self) -> bool {
@@ -921,14 +1151,41 @@ impl CoreWindow for Window {
 
     fn set_ime_cursor_area(&self, _position: Position, _size: Size) {}
 
+    fn set_ime_exclusion_area(&self, _area: Option<(Position, Size)>) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_ime_exclusion_area is not supported").into())
+    }
+
+    fn set_ime_candidates_drawn_by_app(&self, _drawn_by_app: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_ime_candidates_drawn_by_app is not supported").into())
+    }
+
     fn set_ime_allowed(&self, _allowed: bool) {}
 
+    fn set_virtual_keyboard_visible(&self, visible: bool) -> Result<(), RequestError> {
+        if visible {
+            self.app.show_soft_input(true);
+        } else {
+            self.app.hide_soft_input(false);
+        }
+        Ok(())
+    }
+
     fn set_ime_purpose(&self, _purpose: ImePurpose) {}
 
+    fn set_ime_surrounding_text(&self, _text: &str, _cursor: usize, _anchor: usize) {}
+
     fn focus_window(&self) {}
 
     fn request_user_attention(&self, _request_type: Option<window::UserAttentionType>) {}
 
+    fn announce(
+        &self,
+        _message: &str,
+        _politeness: window::Politeness,
+    ) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("announce is not supported").into())
+    }
+
     fn set_cursor(&self, _: Cursor) {}
 
     fn set_cursor_position(&self, _: Position) -> Result<(), RequestError> {
@@ -939,8 +1196,18 @@ impl CoreWindow for Window {
         Err(NotSupportedError::new("set_cursor_grab is not supported").into())
     }
 
+    fn set_cursor_grab_policy(&self, _policy: CursorGrabPolicy) {}
+
     fn set_cursor_visible(&self, _: bool) {}
 
+    fn set_cursor_for_device(&self, _: DeviceId, _: Cursor) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_cursor_for_device is not supported").into())
+    }
+
+    fn set_cursor_visible_for_device(&self, _: DeviceId, _: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_cursor_visible_for_device is not supported").into())
+    }
+
     fn drag_window(&self) -> Result<(), RequestError> {
         Err(NotSupportedError::new("drag_window is not supported").into())
     }
@@ -956,6 +1223,10 @@ impl CoreWindow for Window {
         Err(NotSupportedError::new("set_cursor_hittest is not supported").into())
     }
 
+    fn set_motion_coalescing(&self, _coalescing: Coalescing) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_motion_coalescing is not supported").into())
+    }
+
     fn set_theme(&self, _theme: Option<Theme>) {}
 
     fn theme(&self) -> Option<Theme> {
@@ -968,6 +1239,10 @@ impl CoreWindow for Window {
         HAS_FOCUS.load(Ordering::Relaxed)
     }
 
+    fn set_keyboard_capture(&self, _capture: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_keyboard_capture is not supported").into())
+    }
+
     fn title(&self) -> String {
         String::new()
     }
@@ -1011,6 +1286,22 @@ impl MonitorHandle {
         unreachable!()
     }
 
+    pub fn physical_size_mm(&self) -> Option<(u32, u32)> {
+        unreachable!()
+    }
+
+    pub fn identity(&self) -> Option<MonitorIdentity> {
+        unreachable!()
+    }
+
+    pub fn orientation(&self) -> Orientation {
+        unreachable!()
+    }
+
+    pub fn icc_profile(&self) -> Option<Vec<u8>> {
+        unreachable!()
+    }
+
     pub fn current_video_mode(&self) -> Option<VideoModeHandle> {
         unreachable!()
     }
@@ -1018,6 +1309,18 @@ impl MonitorHandle {
     pub fn video_modes(&self) -> std::iter::Empty<VideoModeHandle> {
         unreachable!()
     }
+
+    pub fn color_capabilities(&self) -> Result<ColorCapabilities, RequestError> {
+        unreachable!()
+    }
+
+    pub fn brightness(&self) -> Result<f64, RequestError> {
+        unreachable!()
+    }
+
+    pub fn set_brightness(&self, _brightness: f64) -> Result<(), RequestError> {
+        unreachable!()
+    }
 }
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
@@ -1052,3 +1355,12 @@ fn screen_size(app: &AndroidApp) -> PhysicalSize<u32> {
 fn scale_factor(app: &AndroidApp) -> f64 {
     app.config().density().map(|dpi| dpi as f64 / 160.0).unwrap_or(1.0)
 }
+
+fn locale(app: &AndroidApp) -> LanguageTag {
+    let config = app.config();
+    match (config.language(), config.country()) {
+        (Some(language), Some(country)) => LanguageTag::new(format!("{language}-{country}")),
+        (Some(language), None) => LanguageTag::new(language),
+        (None, _) => LanguageTag::undetermined(),
+    }
+}
@@ -0,0 +1,80 @@
+//! The AccessKit adapter of a view, backing its `NSAccessibility` methods.
+
+use std::cell::RefCell;
+use std::fmt;
+
+use accesskit::{ActionHandler, ActionRequest, ActivationHandler, TreeUpdate};
+use objc2_app_kit::NSView;
+use objc2_foundation::{NSArray, NSObject, NSPoint};
+
+/// The adapter of a view, with the source of its tree.
+pub(super) struct AccessKit {
+    adapter: accesskit_macos::Adapter,
+    source: Box<dyn ActivationHandler + Send>,
+}
+
+impl fmt::Debug for AccessKit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AccessKit").finish_non_exhaustive()
+    }
+}
+
+/// Queues the actions as window events.
+struct Actions<F>(F);
+
+impl<F: FnMut(ActionRequest)> ActionHandler for Actions<F> {
+    fn do_action(&mut self, request: ActionRequest) {
+        (self.0)(request)
+    }
+}
+
+impl AccessKit {
+    /// Create the adapter of the view, `on_action` being called on the main thread.
+    pub(super) fn new(
+        view: &NSView,
+        is_focused: bool,
+        source: Box<dyn ActivationHandler + Send>,
+        on_action: impl FnMut(ActionRequest) + 'static,
+    ) -> Self {
+        let view = view as *const NSView as *mut _;
+        // SAFETY: The view is valid, and the adapter only keeps a weak reference to it.
+        let adapter =
+            unsafe { accesskit_macos::Adapter::new(view, is_focused, Actions(on_action)) };
+        Self { adapter, source }
+    }
+
+    pub(super) fn children(&mut self) -> *mut NSArray<NSObject> {
+        self.adapter.view_children(&mut *self.source)
+    }
+
+    pub(super) fn focus(&mut self) -> *mut NSObject {
+        self.adapter.focus(&mut *self.source)
+    }
+
+    pub(super) fn hit_test(&mut self, point: NSPoint) -> *mut NSObject {
+        self.adapter.hit_test(point, &mut *self.source)
+    }
+}
+
+/// Tell the adapter whether the window of the view is key.
+pub(super) fn set_focused(accesskit: &RefCell<Option<AccessKit>>, is_focused: bool) {
+    let events = accesskit
+        .borrow_mut()
+        .as_mut()
+        .and_then(|accesskit| accesskit.adapter.update_view_focus_state(is_focused));
+    // Raising the events may ask the view for its accessibility elements again.
+    if let Some(events) = events {
+        events.raise();
+    }
+}
+
+/// Push a change of the tree, dropped while no assistive technology uses the view.
+pub(super) fn update(accesskit: &RefCell<Option<AccessKit>>, update: TreeUpdate) {
+    let events = accesskit
+        .borrow_mut()
+        .as_mut()
+        .and_then(|accesskit| accesskit.adapter.update_if_active(|| update));
+    if let Some(events) = events {
+        events.raise();
+    }
+}
@@ -1,13 +1,25 @@
 #![allow(clippy::unnecessary_cast)]
 
+use std::path::PathBuf;
 use std::rc::Rc;
 
-use objc2::{declare_class, msg_send, mutability, ClassType, DeclaredClass};
-use objc2_app_kit::{NSApplication, NSEvent, NSEventModifierFlags, NSEventType, NSResponder};
-use objc2_foundation::{MainThreadMarker, NSObject};
+use core_graphics::display::CGDisplay;
+use objc2::rc::Retained;
+use objc2::runtime::AnyObject;
+use objc2::{
+    class, declare_class, msg_send, msg_send_id, mutability, sel, ClassType, DeclaredClass,
+};
+use objc2_app_kit::{
+    NSApplication, NSApplicationPresentationOptions, NSEvent, NSEventModifierFlags, NSEventType,
+    NSMenuItem, NSResponder,
+};
+use objc2_foundation::{MainThreadMarker, NSBundle, NSObject, NSString, NSUInteger};
+use tracing::warn;
 
 use super::app_state::AppState;
+use super::ffi;
 use crate::event::{DeviceEvent, ElementState};
+use crate::event_loop::ExitResponse;
 
 declare_class!(
     pub(super) struct WinitApplication;
@@ -44,9 +56,185 @@ declare_class!(
                 unsafe { msg_send![super(self), sendEvent: event] }
             }
         }
+
+        // Give the application a chance to cancel the termination, e.g. on `Cmd+Q` or logout.
+        #[method(terminate:)]
+        fn terminate(&self, sender: Option<&AnyObject>) {
+            let app_state = AppState::get(MainThreadMarker::from(self));
+            if app_state.exit_requested() == ExitResponse::Exit {
+                unsafe { msg_send![super(self), terminate: sender] }
+            }
+        }
+
+        // The Apple Event sent when the user clicks on the dock icon of the running application,
+        // or launches it again.
+        #[method(handleReopenEvent:withReplyEvent:)]
+        fn handle_reopen_event(&self, _event: &AnyObject, _reply: &AnyObject) {
+            let app_state = AppState::get(MainThreadMarker::from(self));
+            app_state.maybe_queue_with_handler(|app, event_loop| app.reopen_requested(event_loop));
+        }
+
+        // Sent when documents are opened with the application, e.g. from the Finder.
+        #[method(handleOpenDocumentsEvent:withReplyEvent:)]
+        fn handle_open_documents_event(&self, event: &AnyObject, _reply: &AnyObject) {
+            let paths = direct_object_paths(event);
+            if !paths.is_empty() {
+                let app_state = AppState::get(MainThreadMarker::from(self));
+                app_state.open_files(paths);
+            }
+        }
+
+        // Sent when documents are printed with the application, e.g. from the Finder.
+        #[method(handlePrintDocumentsEvent:withReplyEvent:)]
+        fn handle_print_documents_event(&self, event: &AnyObject, _reply: &AnyObject) {
+            let paths = direct_object_paths(event);
+            if !paths.is_empty() {
+                let app_state = AppState::get(MainThreadMarker::from(self));
+                app_state.maybe_queue_with_handler(move |app, event_loop| {
+                    if let Some(handler) = app.macos_handler() {
+                        handler.print_files(event_loop, paths);
+                    }
+                });
+            }
+        }
+
+        // Sent when a URL with a scheme registered by the application is opened.
+        #[method(handleGetURLEvent:withReplyEvent:)]
+        fn handle_get_url_event(&self, event: &AnyObject, _reply: &AnyObject) {
+            let urls: Vec<String> = direct_object_items(event)
+                .into_iter()
+                .filter_map(|item| unsafe {
+                    let url: Option<Retained<NSString>> = msg_send_id![&item, stringValue];
+                    url.map(|url| url.to_string())
+                })
+                .collect();
+
+            if !urls.is_empty() {
+                let app_state = AppState::get(MainThreadMarker::from(self));
+                app_state.open_urls(urls);
+            }
+        }
+
+        // The action of the custom items of the main menu, identified by their tag.
+        #[method(winitMenuItemActivated:)]
+        fn menu_item_activated(&self, sender: &NSMenuItem) {
+            let id = unsafe { sender.tag() } as u32;
+            let app_state = AppState::get(MainThreadMarker::from(self));
+            app_state.maybe_queue_with_handler(move |app, event_loop| {
+                if let Some(handler) = app.macos_handler() {
+                    handler.menu_item_activated(event_loop, id);
+                }
+            });
+        }
     }
 );
 
+/// `kCoreEventClass`.
+const CORE_EVENT_CLASS: u32 = u32::from_be_bytes(*b"aevt");
+/// `kAEReopenApplication`.
+const REOPEN_APPLICATION_EVENT_ID: u32 = u32::from_be_bytes(*b"rapp");
+/// `kAEOpenDocuments`.
+const OPEN_DOCUMENTS_EVENT_ID: u32 = u32::from_be_bytes(*b"odoc");
+/// `kAEPrintDocuments`.
+const PRINT_DOCUMENTS_EVENT_ID: u32 = u32::from_be_bytes(*b"pdoc");
+/// `kInternetEventClass` and `kAEGetURL`.
+const GET_URL_EVENT: u32 = u32::from_be_bytes(*b"GURL");
+/// `keyDirectObject`.
+const DIRECT_OBJECT_KEYWORD: u32 = u32::from_be_bytes(*b"----");
+
+/// Handle the Apple Events which are usually forwarded to the application delegate, which isn't
+/// owned by Winit, in [`WinitApplication`].
+///
+/// This must be done once `NSApplication` installed its own handlers, and before it finished
+/// launching to receive the documents the application was launched with.
+pub(super) fn install_apple_event_handlers(app: &NSApplication) {
+    let handlers = [
+        (sel!(handleReopenEvent:withReplyEvent:), CORE_EVENT_CLASS, REOPEN_APPLICATION_EVENT_ID),
+        (sel!(handleOpenDocumentsEvent:withReplyEvent:), CORE_EVENT_CLASS, OPEN_DOCUMENTS_EVENT_ID),
+        (
+            sel!(handlePrintDocumentsEvent:withReplyEvent:),
+            CORE_EVENT_CLASS,
+            PRINT_DOCUMENTS_EVENT_ID,
+        ),
+        (sel!(handleGetURLEvent:withReplyEvent:), GET_URL_EVENT, GET_URL_EVENT),
+    ];
+
+    unsafe {
+        let manager: Retained<AnyObject> =
+            msg_send_id![class!(NSAppleEventManager), sharedAppleEventManager];
+        for (selector, event_class, event_id) in handlers {
+            let _: () = msg_send![
+                &manager,
+                setEventHandler: app,
+                andSelector: selector,
+                forEventClass: event_class,
+                andEventID: event_id,
+            ];
+        }
+    }
+}
+
+/// Make the application the default handler of the URL schemes, for the URLs to be delivered with
+/// the get URL Apple Event.
+pub(super) fn register_url_schemes(schemes: &[String]) {
+    if schemes.is_empty() {
+        return;
+    }
+
+    let bundle_id = unsafe { NSBundle::mainBundle().bundleIdentifier() };
+    let Some(bundle_id) = bundle_id else {
+        warn!("the application isn't bundled, the URL schemes can't be registered");
+        return;
+    };
+    for scheme in schemes {
+        let scheme = NSString::from_str(scheme);
+        let status = unsafe {
+            ffi::LSSetDefaultHandlerForURLScheme(
+                Retained::as_ptr(&scheme).cast(),
+                Retained::as_ptr(&bundle_id).cast(),
+            )
+        };
+        if status != 0 {
+            warn!(%scheme, status, "failed to register the URL scheme");
+        }
+    }
+}
+
+/// The paths of the file URLs of the direct object parameter of an `NSAppleEventDescriptor`.
+fn direct_object_paths(event: &AnyObject) -> Vec<PathBuf> {
+    direct_object_items(event)
+        .into_iter()
+        .filter_map(|item| unsafe {
+            let url: Option<Retained<AnyObject>> = msg_send_id![&item, fileURLValue];
+            let path: Option<Retained<NSString>> = msg_send_id![&url?, path];
+            path.map(|path| PathBuf::from(path.to_string()))
+        })
+        .collect()
+}
+
+/// The items of the direct object parameter of an `NSAppleEventDescriptor`.
+fn direct_object_items(event: &AnyObject) -> Vec<Retained<AnyObject>> {
+    unsafe {
+        let list: Option<Retained<AnyObject>> =
+            msg_send_id![event, paramDescriptorForKeyword: DIRECT_OBJECT_KEYWORD];
+        let Some(list) = list else {
+            return Vec::new();
+        };
+
+        // The list is one-indexed, and a single item is its own list.
+        let count: isize = msg_send![&list, numberOfItems];
+        if count == 0 {
+            return vec![list];
+        }
+
+        (1..=count)
+            .filter_map(|index| -> Option<Retained<AnyObject>> {
+                msg_send_id![&list, descriptorAtIndex: index]
+            })
+            .collect()
+    }
+}
+
 fn maybe_dispatch_device_event(app_state: &Rc<AppState>, event: &NSEvent) {
     let event_type = unsafe { event.r#type() };
     #[allow(non_upper_case_globals)]
@@ -87,3 +275,36 @@ fn maybe_dispatch_device_event(app_state: &Rc<AppState>, event: &NSEvent) {
         _ => (),
     }
 }
+
+/// Release the cursor and the captured displays after a panic of the event loop thread.
+pub(crate) fn restore_after_panic() {
+    let _ = CGDisplay::associate_mouse_and_mouse_cursor_position(true);
+    unsafe {
+        let _: () = msg_send![class!(NSCursor), unhide];
+        ffi::CGRestorePermanentDisplayConfiguration();
+        ffi::CGReleaseAllDisplays();
+    }
+    if let Some(mtm) = MainThreadMarker::new() {
+        let app = NSApplication::sharedApplication(mtm);
+        app.setPresentationOptions(
+            NSApplicationPresentationOptions::NSApplicationPresentationDefault,
+        );
+    }
+}
+
+/// Show the message of a panic of the event loop thread, until the user closes it.
+pub(crate) fn show_panic_dialog(message: &str) {
+    // See `NSAlert.h`.
+    const ALERT_STYLE_CRITICAL: NSUInteger = 2;
+
+    if MainThreadMarker::new().is_none() {
+        return;
+    }
+    let alert: Retained<AnyObject> = unsafe { msg_send_id![class!(NSAlert), new] };
+    unsafe {
+        let _: () = msg_send![&alert, setMessageText: &*NSString::from_str("Error")];
+        let _: () = msg_send![&alert, setInformativeText: &*NSString::from_str(message)];
+        let _: () = msg_send![&alert, setAlertStyle: ALERT_STYLE_CRITICAL];
+        let _: isize = msg_send![&alert, runModal];
+    }
+}
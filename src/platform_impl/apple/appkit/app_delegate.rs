@@ -0,0 +1,95 @@
+//! The delegate of `NSApplication`, for the features only available to the delegate. It's only
+//! installed when the application doesn't already have one.
+
+use std::cell::{OnceCell, RefCell};
+use std::ptr::NonNull;
+
+use block2::Block;
+use objc2::rc::Retained;
+use objc2::runtime::ProtocolObject;
+use objc2::{declare_class, msg_send_id, mutability, ClassType, DeclaredClass};
+use objc2_app_kit::{NSApplication, NSApplicationDelegate, NSMenu, NSUserActivityRestoring};
+use objc2_foundation::{MainThreadMarker, NSArray, NSObject, NSObjectProtocol, NSUserActivity};
+use tracing::warn;
+
+use super::app_state::AppState;
+use super::user_activity;
+
+declare_class!(
+    /// The delegate of `NSApplication`, providing the Dock menu and continuing the user
+    /// activities.
+    #[derive(Debug)]
+    pub(super) struct ApplicationDelegate;
+
+    unsafe impl ClassType for ApplicationDelegate {
+        type Super = NSObject;
+        type Mutability = mutability::MainThreadOnly;
+        const NAME: &'static str = "WinitApplicationDelegate";
+    }
+
+    impl DeclaredClass for ApplicationDelegate {
+        type Ivars = RefCell<Option<Retained<NSMenu>>>;
+    }
+
+    unsafe impl NSObjectProtocol for ApplicationDelegate {}
+
+    unsafe impl NSApplicationDelegate for ApplicationDelegate {
+        #[method_id(applicationDockMenu:)]
+        fn dock_menu(&self, _sender: &NSApplication) -> Option<Retained<NSMenu>> {
+            trace_scope!("applicationDockMenu:");
+            self.ivars().borrow().clone()
+        }
+
+        #[method(application:continueUserActivity:restorationHandler:)]
+        fn continue_user_activity(
+            &self,
+            _application: &NSApplication,
+            user_activity: &NSUserActivity,
+            _restoration_handler: &Block<
+                dyn Fn(NonNull<NSArray<ProtocolObject<dyn NSUserActivityRestoring>>>),
+            >,
+        ) -> bool {
+            trace_scope!("application:continueUserActivity:restorationHandler:");
+            let activity = user_activity::from_ns_user_activity(user_activity);
+            let app_state = AppState::get(MainThreadMarker::from(self));
+            app_state.maybe_queue_with_handler(move |app, event_loop| {
+                if let Some(handler) = app.macos_handler() {
+                    handler.continue_user_activity(event_loop, activity);
+                }
+            });
+            true
+        }
+    }
+);
+
+impl ApplicationDelegate {
+    fn new(mtm: MainThreadMarker) -> Retained<Self> {
+        let this = mtm.alloc().set_ivars(RefCell::new(None));
+        unsafe { msg_send_id![super(this), init] }
+    }
+
+    pub(super) fn set_dock_menu(&self, menu: Option<Retained<NSMenu>>) {
+        *self.ivars().borrow_mut() = menu;
+    }
+}
+
+/// Install the delegate, unless the application already has one, in which case the features of
+/// the delegate are unavailable.
+pub(super) fn install<'a>(
+    app: &NSApplication,
+    delegate: &'a OnceCell<Retained<ApplicationDelegate>>,
+) -> &'a ApplicationDelegate {
+    delegate.get_or_init(|| {
+        let delegate = ApplicationDelegate::new(MainThreadMarker::from(app));
+        if unsafe { app.delegate() }.is_some() {
+            warn!(
+                "the application already has a delegate, the Dock menu and the user activities \
+                 won't be handled"
+            );
+        } else {
+            // `NSApplication` doesn't retain its delegate.
+            app.setDelegate(Some(ProtocolObject::from_ref(&*delegate)));
+        }
+        delegate
+    })
+}
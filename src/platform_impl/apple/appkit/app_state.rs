@@ -1,21 +1,36 @@
 use std::cell::{Cell, OnceCell, RefCell};
 use std::mem;
+use std::num::NonZeroU32;
+use std::path::PathBuf;
 use std::rc::{Rc, Weak};
 use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
 use std::sync::Arc;
 use std::time::Instant;
 
-use objc2_app_kit::{NSApplication, NSApplicationActivationPolicy};
-use objc2_foundation::{MainThreadMarker, NSNotification};
+use dpi::{LogicalPosition, PhysicalPosition};
+use objc2::rc::Retained;
+use objc2_app_kit::{NSApplication, NSApplicationActivationPolicy, NSEvent, NSWindow};
+use objc2_foundation::{MainThreadMarker, NSInteger, NSNotification, NSObjectProtocol};
 
 use super::super::event_handler::EventHandler;
+use super::app::install_apple_event_handlers;
+use super::app_delegate::{self, ApplicationDelegate};
+use super::cursor::cursor_from_icon;
 use super::event_loop::{stop_app_immediately, ActiveEventLoop, PanicInfo};
-use super::menu;
+use super::monitor::{self, MonitorHandle};
 use super::observer::{EventLoopWaker, RunLoop};
+use super::window::WinitWindow;
+use super::{clipboard, dock, menu};
 use crate::application::ApplicationHandler;
-use crate::event::{StartCause, WindowEvent};
-use crate::event_loop::ControlFlow;
-use crate::window::WindowId;
+use crate::drag::{DragData, DragSession};
+use crate::error::RequestError;
+use crate::event::{LanguageTag, PowerEvent, StartCause, SystemPreferences, WindowEvent};
+use crate::event_loop::{ControlFlow, ExitResponse, ModifiersOrder};
+use crate::monitor::{ColorCapabilities, MonitorHandle as RootMonitorHandle, Orientation};
+use crate::platform::macos::{Menu, MenuDescription};
+use crate::platform_impl::coalescing::MotionCoalescer;
+use crate::utils::RedrawSchedule;
+use crate::window::{Coalescing, Cursor, WindowId};
 
 #[derive(Debug)]
 pub(super) struct AppState {
@@ -23,6 +38,7 @@ pub(super) struct AppState {
     activation_policy: Option<NSApplicationActivationPolicy>,
     default_menu: bool,
     activate_ignoring_other_apps: bool,
+    modifiers_order: ModifiersOrder,
     run_loop: RunLoop,
     proxy_wake_up: Arc<AtomicBool>,
     event_handler: EventHandler,
@@ -41,6 +57,23 @@ pub(super) struct AppState {
     start_time: Cell<Option<Instant>>,
     wait_timeout: Cell<Option<Instant>>,
     pending_redraw: RefCell<Vec<WindowId>>,
+    redraw_schedule: RefCell<RedrawSchedule>,
+    /// The pointer motion held back according to the coalescing of the windows.
+    motion_coalescer: RefCell<MotionCoalescer>,
+    /// Files and URLs opened with the application before it finished launching.
+    launch_files: RefCell<Vec<PathBuf>>,
+    launch_urls: RefCell<Vec<String>>,
+    /// The drag between the windows, driven by the mouse events of the views.
+    drag: RefCell<Option<DragSession>>,
+    /// The change count of the pasteboard when it was last checked.
+    pasteboard_change_count: Cell<Option<NSInteger>>,
+    /// The monitors known when the screen parameters last changed, with the properties whose
+    /// changes are reported.
+    monitors: RefCell<Vec<MonitorState>>,
+    /// The main menu set before the application finished launching.
+    main_menu: RefCell<Option<MenuDescription>>,
+    /// The delegate of the application, once it's installed.
+    delegate: OnceCell<Retained<ApplicationDelegate>>,
     // NOTE: This is strongly referenced by our `NSWindowDelegate` and our `NSView` subclass, and
     // as such should be careful to not add fields that, in turn, strongly reference those.
 }
@@ -68,6 +101,7 @@ impl AppState {
         activation_policy: Option<NSApplicationActivationPolicy>,
         default_menu: bool,
         activate_ignoring_other_apps: bool,
+        modifiers_order: ModifiersOrder,
     ) -> Rc<Self> {
         let this = Rc::new(AppState {
             mtm,
@@ -75,6 +109,7 @@ impl AppState {
             proxy_wake_up: Arc::new(AtomicBool::new(false)),
             default_menu,
             activate_ignoring_other_apps,
+            modifiers_order,
             run_loop: RunLoop::main(mtm),
             event_handler: EventHandler::new(),
             stop_on_launch: Cell::new(false),
@@ -89,6 +124,15 @@ impl AppState {
             start_time: Cell::new(None),
             wait_timeout: Cell::new(None),
             pending_redraw: RefCell::new(vec![]),
+            redraw_schedule: RefCell::new(RedrawSchedule::default()),
+            motion_coalescer: RefCell::new(MotionCoalescer::default()),
+            launch_files: RefCell::new(vec![]),
+            launch_urls: RefCell::new(vec![]),
+            drag: RefCell::new(None),
+            pasteboard_change_count: Cell::new(None),
+            monitors: RefCell::new(monitor_states()),
+            main_menu: RefCell::new(None),
+            delegate: OnceCell::new(),
         });
 
         GLOBAL.get(mtm).set(this.clone()).expect("application state can only be set once");
@@ -103,6 +147,23 @@ impl AppState {
             .clone()
     }
 
+    pub fn modifiers_order(&self) -> ModifiersOrder {
+        self.modifiers_order
+    }
+
+    pub fn will_finish_launching(self: &Rc<Self>, _notification: &NSNotification) {
+        trace_scope!("NSApplicationWillFinishLaunchingNotification");
+        // `NSApplication` installs its own Apple Event handlers while launching, ours must be
+        // installed before the documents the application was launched with are opened.
+        let app = NSApplication::sharedApplication(self.mtm);
+        install_apple_event_handlers(&app);
+        // The user activities the application is launched with are continued by the delegate,
+        // which is only installed when the user didn't set their own.
+        if unsafe { app.delegate() }.is_none() {
+            app_delegate::install(&app, &self.delegate);
+        }
+    }
+
     // NOTE: This notification will, globally, only be emitted once,
     // no matter how many `EventLoop`s the user creates.
     pub fn did_finish_launching(self: &Rc<Self>, _notification: &NSNotification) {
@@ -122,7 +183,9 @@ impl AppState {
         #[allow(deprecated)]
         app.activateIgnoringOtherApps(self.activate_ignoring_other_apps);
 
-        if self.default_menu {
+        if let Some(main_menu) = self.main_menu.take() {
+            menu::set_main_menu(&app, &main_menu);
+        } else if self.default_menu {
             // The menubar initialization should be before the `NewEvents` event, to allow
             // overriding of the default menu even if it's created
             menu::initialize(&app);
@@ -133,6 +196,15 @@ impl AppState {
         self.set_is_running(true);
         self.dispatch_init_events();
 
+        let files = mem::take(&mut *self.launch_files.borrow_mut());
+        if !files.is_empty() {
+            self.with_handler(|app, event_loop| app.open_files(event_loop, files));
+        }
+        let urls = mem::take(&mut *self.launch_urls.borrow_mut());
+        if !urls.is_empty() {
+            self.with_handler(|app, event_loop| app.open_urls(event_loop, urls));
+        }
+
         // If the application is being launched via `EventLoop::pump_app_events()` then we'll
         // want to stop the app once it is launched (and return to the external loop)
         //
@@ -155,6 +227,18 @@ impl AppState {
         self.internal_exit();
     }
 
+    /// Ask the application whether it wants to be terminated.
+    pub fn exit_requested(self: &Rc<Self>) -> ExitResponse {
+        trace_scope!("terminate:");
+        let mut response = ExitResponse::Exit;
+        if !self.event_handler.in_use() {
+            self.with_handler(|app, event_loop| {
+                response = app.exit_requested(event_loop);
+            });
+        }
+        response
+    }
+
     /// Place the event handler in the application state for the duration
     /// of the given closure.
     pub fn set_event_handler<R>(
@@ -244,7 +328,11 @@ impl AppState {
         // Redraw request might come out of order from the OS.
         // -> Don't go back into the event handler when our callstack originates from there
         if !self.event_handler.in_use() {
+            let pending = self.motion_coalescer.borrow_mut().drain_window(window_id);
             self.with_handler(|app, event_loop| {
+                for event in pending {
+                    app.window_event(event_loop, window_id, event);
+                }
                 app.window_event(event_loop, window_id, WindowEvent::RedrawRequested);
             });
 
@@ -266,6 +354,39 @@ impl AppState {
         self.run_loop.wakeup();
     }
 
+    pub fn schedule_redraw(&self, window_id: WindowId, instant: Instant) {
+        self.redraw_schedule.borrow_mut().schedule(window_id, instant);
+        // Wake up the run loop, so the waker is started for the new deadline.
+        self.run_loop.wakeup();
+    }
+
+    /// Queue an event of the window, holding back the pointer motion according to `coalescing`.
+    pub fn queue_window_event(
+        self: &Rc<Self>,
+        window_id: WindowId,
+        event: WindowEvent,
+        coalescing: Coalescing,
+    ) {
+        let Some(event) = self.motion_coalescer.borrow_mut().push(window_id, event, coalescing)
+        else {
+            return;
+        };
+
+        // The motion coalesced per frame is only delivered before the window is redrawn.
+        let mut events = if coalescing == Coalescing::PerFrame {
+            Vec::new()
+        } else {
+            self.motion_coalescer.borrow_mut().drain_window(window_id)
+        };
+        events.push(event);
+
+        self.maybe_queue_with_handler(move |app, event_loop| {
+            for event in events {
+                app.window_event(event_loop, window_id, event);
+            }
+        });
+    }
+
     #[track_caller]
     pub fn maybe_queue_with_handler(
         self: &Rc<Self>,
@@ -288,6 +409,179 @@ impl AppState {
         }
     }
 
+    /// Set the main menu, once the application finished launching.
+    pub fn set_main_menu(&self, main_menu: MenuDescription) {
+        if self.is_launched() {
+            menu::set_main_menu(&NSApplication::sharedApplication(self.mtm), &main_menu);
+        } else {
+            *self.main_menu.borrow_mut() = Some(main_menu);
+        }
+    }
+
+    pub fn set_dock_menu(&self, menu: Option<Menu>) {
+        let app = NSApplication::sharedApplication(self.mtm);
+        dock::set_menu(&app, &self.delegate, menu.as_ref());
+    }
+
+    pub fn set_dock_badge(&self, badge: Option<&str>) {
+        dock::set_badge(&NSApplication::sharedApplication(self.mtm), badge);
+    }
+
+    pub fn set_dock_progress(&self, progress: Option<f64>) {
+        dock::set_progress(&NSApplication::sharedApplication(self.mtm), progress);
+    }
+
+    /// Deliver the files opened with the application, once it finished launching.
+    pub fn open_files(self: &Rc<Self>, paths: Vec<PathBuf>) {
+        if self.is_launched() {
+            self.maybe_queue_with_handler(|app, event_loop| app.open_files(event_loop, paths));
+        } else {
+            self.launch_files.borrow_mut().extend(paths);
+        }
+    }
+
+    /// Deliver the URLs opened with the application, once it finished launching.
+    pub fn open_urls(self: &Rc<Self>, urls: Vec<String>) {
+        if self.is_launched() {
+            self.maybe_queue_with_handler(|app, event_loop| app.open_urls(event_loop, urls));
+        } else {
+            self.launch_urls.borrow_mut().extend(urls);
+        }
+    }
+
+    pub fn power_event(self: &Rc<Self>, event: PowerEvent) {
+        self.maybe_queue_with_handler(move |app, event_loop| app.power_event(event_loop, event));
+    }
+
+    pub fn session_lock_changed(self: &Rc<Self>, locked: bool) {
+        self.maybe_queue_with_handler(move |app, event_loop| {
+            if locked {
+                app.session_locked(event_loop)
+            } else {
+                app.session_unlocked(event_loop)
+            }
+        });
+    }
+
+    pub fn start_drag(&self, source: WindowId, data: DragData) -> Result<(), RequestError> {
+        if ns_window(self.mtm, source).is_none() || self.drag.borrow().is_some() {
+            return Err(RequestError::Ignored);
+        }
+        self.drag.replace(Some(DragSession::new(source, data)));
+        Ok(())
+    }
+
+    pub fn set_drag_image(&self, image: Option<Cursor>) -> Result<(), RequestError> {
+        let mut drag = self.drag.borrow_mut();
+        let drag = drag.as_mut().ok_or(RequestError::Ignored)?;
+        if image.is_none() && drag.image().is_some() {
+            restore_cursor(self.mtm, drag.source());
+        }
+        drag.set_image(image);
+        Ok(())
+    }
+
+    /// Move the drag between the windows to the mouse, or drop it there when `released`.
+    ///
+    /// The view the drag started from keeps receiving the mouse events while a button is held.
+    pub fn update_drag(self: &Rc<Self>, released: bool) {
+        let Some(mut drag) = self.drag.take() else { return };
+
+        let target = window_under_mouse(self.mtm);
+        let queue_event = |window_id, event| {
+            self.maybe_queue_with_handler(move |app, event_loop| {
+                app.window_event(event_loop, window_id, event)
+            })
+        };
+        if released {
+            if drag.image().is_some() {
+                restore_cursor(self.mtm, drag.source());
+            }
+            drag.released(target, queue_event);
+        } else {
+            // The cursor rectangles of the views aren't applied while a mouse button is held.
+            if let Some(image) = drag.image() {
+                let cursor = match image {
+                    Cursor::Icon(icon) => cursor_from_icon(*icon),
+                    Cursor::Custom(cursor) => cursor.inner.0.clone(),
+                };
+                unsafe { cursor.set() };
+            }
+            drag.moved(target, queue_event);
+            self.drag.replace(Some(drag));
+        }
+    }
+
+    /// Report the new keyboard layout to the key window.
+    pub fn keyboard_layout_changed(self: &Rc<Self>) {
+        let Some(layout) = super::event::keyboard_layout() else { return };
+        let Some(window) = NSApplication::sharedApplication(self.mtm).keyWindow() else { return };
+        let window_id = WindowId::from_raw(Retained::as_ptr(&window) as usize);
+        self.maybe_queue_with_handler(move |app, event_loop| {
+            app.window_event(event_loop, window_id, WindowEvent::KeyboardLayoutChanged(layout))
+        });
+    }
+
+    /// Report the monitors connected and disconnected, and the ones whose colors, orientation or
+    /// refresh rate changed, since the screens last changed.
+    pub fn screens_changed(self: &Rc<Self>) {
+        let monitors = monitor_states();
+        let previous = self.monitors.replace(monitors.clone());
+        for state in &previous {
+            if !monitors.iter().any(|known| known.monitor == state.monitor) {
+                let monitor = RootMonitorHandle { inner: state.monitor.clone() };
+                self.maybe_queue_with_handler(move |app, event_loop| {
+                    app.monitor_removed(event_loop, monitor)
+                });
+            }
+        }
+        for state in monitors {
+            let known = previous.iter().find(|known| known.monitor == state.monitor);
+            let added = known.is_none();
+            let color_capabilities_changed =
+                known.is_some_and(|known| known.color_capabilities != state.color_capabilities);
+            let orientation_changed =
+                known.is_some_and(|known| known.orientation != state.orientation);
+            let refresh_rate_changed = known.is_some_and(|known| {
+                known.refresh_rate_millihertz != state.refresh_rate_millihertz
+            });
+            if !added
+                && !color_capabilities_changed
+                && !orientation_changed
+                && !refresh_rate_changed
+            {
+                continue;
+            }
+            let monitor = RootMonitorHandle { inner: state.monitor };
+            self.maybe_queue_with_handler(move |app, event_loop| {
+                if added {
+                    app.monitor_added(event_loop, monitor.clone())
+                }
+                if color_capabilities_changed {
+                    app.color_capabilities_changed(event_loop, monitor.clone())
+                }
+                if orientation_changed {
+                    app.monitor_orientation_changed(event_loop, monitor.clone())
+                }
+                if refresh_rate_changed {
+                    app.monitor_refresh_rate_changed(event_loop, monitor)
+                }
+            });
+        }
+    }
+
+    pub fn locale_changed(self: &Rc<Self>, locale: LanguageTag) {
+        self.maybe_queue_with_handler(move |app, event_loop| {
+            app.locale_changed(event_loop, locale)
+        });
+    }
+
+    pub fn system_preferences_changed(self: &Rc<Self>, preferences: SystemPreferences) {
+        self.maybe_queue_with_handler(move |app, event_loop| {
+            app.system_preferences_changed(event_loop, preferences)
+        });
+    }
+
     #[track_caller]
     fn with_handler(
         self: &Rc<Self>,
@@ -354,13 +648,39 @@ impl AppState {
             self.with_handler(|app, event_loop| app.proxy_wake_up(event_loop));
         }
 
+        for window_id in self.redraw_schedule.borrow_mut().take_due(Instant::now()) {
+            let mut pending_redraw = self.pending_redraw.borrow_mut();
+            if !pending_redraw.contains(&window_id) {
+                pending_redraw.push(window_id);
+            }
+        }
+
+        // The pasteboard doesn't notify its changes, so they're checked in each iteration.
+        let change_count = clipboard::change_count();
+        if self
+            .pasteboard_change_count
+            .replace(Some(change_count))
+            .is_some_and(|c| c != change_count)
+        {
+            self.with_handler(|app, event_loop| app.clipboard_changed(event_loop));
+        }
+
         let redraw = mem::take(&mut *self.pending_redraw.borrow_mut());
         for window_id in redraw {
+            let pending = self.motion_coalescer.borrow_mut().drain_window(window_id);
             self.with_handler(|app, event_loop| {
+                for event in pending {
+                    app.window_event(event_loop, window_id, event);
+                }
                 app.window_event(event_loop, window_id, WindowEvent::RedrawRequested);
             });
         }
+        // Deliver the motion of the windows which weren't redrawn.
+        let pending = self.motion_coalescer.borrow_mut().drain();
         self.with_handler(|app, event_loop| {
+            for (window_id, event) in pending {
+                app.window_event(event_loop, window_id, event);
+            }
             app.about_to_wait(event_loop);
         });
 
@@ -380,7 +700,10 @@ impl AppState {
             ControlFlow::Poll => Some(Instant::now()),
             ControlFlow::WaitUntil(instant) => Some(instant),
         };
-        self.waker.borrow_mut().start_at(min_timeout(wait_timeout, app_timeout));
+        let redraw_timeout = self.redraw_schedule.borrow().next_deadline();
+        self.waker
+            .borrow_mut()
+            .start_at(min_timeout(min_timeout(wait_timeout, app_timeout), redraw_timeout));
     }
 }
 
@@ -390,3 +713,57 @@ impl AppState {
 fn min_timeout(a: Option<Instant>, b: Option<Instant>) -> Option<Instant> {
     a.map_or(b, |a_timeout| b.map_or(Some(a_timeout), |b_timeout| Some(a_timeout.min(b_timeout))))
 }
+
+/// The window of the application with the ID.
+fn ns_window(mtm: MainThreadMarker, window_id: WindowId) -> Option<Retained<NSWindow>> {
+    let windows = NSApplication::sharedApplication(mtm).windows().to_vec_retained();
+    windows.into_iter().find(|window| Retained::as_ptr(window) as usize == window_id.into_raw())
+}
+
+/// Show the cursor of the window's view again after the image of a drag.
+fn restore_cursor(mtm: MainThreadMarker, window_id: WindowId) {
+    let Some(window) = ns_window(mtm, window_id) else { return };
+    if let Some(view) = window.contentView() {
+        window.invalidateCursorRectsForView(&view);
+    }
+}
+
+/// The window of the application under the mouse, with the position of the mouse in its view.
+fn window_under_mouse(mtm: MainThreadMarker) -> Option<(WindowId, PhysicalPosition<f64>)> {
+    let point = unsafe { NSEvent::mouseLocation() };
+    let number =
+        unsafe { NSWindow::windowNumberAtPoint_belowWindowWithWindowNumber(point, 0, mtm) };
+    let window = unsafe { NSApplication::sharedApplication(mtm).windowWithWindowNumber(number) }?;
+    if !window.is_kind_of::<WinitWindow>() {
+        return None;
+    }
+
+    let view = window.contentView()?;
+    let view_point = view.convertPoint_fromView(window.convertPointFromScreen(point), None);
+    let position =
+        LogicalPosition::new(view_point.x, view_point.y).to_physical(window.backingScaleFactor());
+    Some((WindowId::from_raw(Retained::as_ptr(&window) as usize), position))
+}
+
+/// A monitor with the properties whose changes are reported.
+#[derive(Debug, Clone)]
+struct MonitorState {
+    monitor: MonitorHandle,
+    color_capabilities: Option<ColorCapabilities>,
+    orientation: Orientation,
+    refresh_rate_millihertz: Option<NonZeroU32>,
+}
+
+fn monitor_states() -> Vec<MonitorState> {
+    monitor::available_monitors()
+        .into_iter()
+        .map(|monitor| MonitorState {
+            color_capabilities: monitor.color_capabilities().ok(),
+            orientation: monitor.orientation(),
+            refresh_rate_millihertz: monitor
+                .current_video_mode()
+                .and_then(|mode| mode.refresh_rate_millihertz()),
+            monitor,
+        })
+        .collect()
+}
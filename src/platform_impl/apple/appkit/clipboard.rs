@@ -0,0 +1,151 @@
+//! The general pasteboard, which is the clipboard of the system.
+
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use objc2::rc::Retained;
+use objc2::{class, msg_send, msg_send_id};
+use objc2_app_kit::{
+    NSPasteboard, NSPasteboardType, NSPasteboardTypeHTML, NSPasteboardTypePNG,
+    NSPasteboardTypeString, NSPasteboardTypeURL,
+};
+use objc2_foundation::{NSArray, NSCopying, NSData, NSInteger, NSString};
+
+use super::app_state::AppState;
+use crate::clipboard::{ClipboardData, ClipboardFormat, ClipboardImage};
+use crate::error::RequestError;
+use crate::event_loop::AsyncRequestSerial;
+
+fn general_pasteboard() -> Retained<NSPasteboard> {
+    unsafe { msg_send_id![class!(NSPasteboard), generalPasteboard] }
+}
+
+/// The count of the changes of the pasteboard's content, which increases with each of them.
+pub(crate) fn change_count() -> NSInteger {
+    unsafe { msg_send![&general_pasteboard(), changeCount] }
+}
+
+/// The type of the paths of files, which is deprecated in favor of URLs but still the one
+/// holding all of them.
+fn filenames_type() -> Retained<NSString> {
+    NSString::from_str("NSFilenamesPboardType")
+}
+
+pub(crate) fn set(content: &[ClipboardData]) -> Result<(), RequestError> {
+    let pasteboard = general_pasteboard();
+    let _: NSInteger = unsafe { msg_send![&pasteboard, clearContents] };
+
+    let mut written = true;
+    for (i, data) in content.iter().enumerate() {
+        // The first data of each format is used.
+        if content[..i].iter().any(|previous| previous.format() == data.format()) {
+            continue;
+        }
+
+        let set_data = |bytes: &[u8], kind: &NSPasteboardType| -> bool {
+            let bytes = NSData::with_bytes(bytes);
+            unsafe { msg_send![&pasteboard, setData: &*bytes, forType: kind] }
+        };
+        written &= match data {
+            ClipboardData::Text(text) => {
+                let string = NSString::from_str(text);
+                unsafe {
+                    msg_send![&pasteboard, setString: &*string, forType: NSPasteboardTypeString]
+                }
+            },
+            ClipboardData::Html(html) => set_data(html.as_bytes(), unsafe { NSPasteboardTypeHTML }),
+            ClipboardData::Image(image) => {
+                set_data(&image.to_png(), unsafe { NSPasteboardTypePNG })
+            },
+            ClipboardData::Files(paths) => {
+                let paths: Vec<_> =
+                    paths.iter().map(|path| NSString::from_str(&path.to_string_lossy())).collect();
+                let paths = NSArray::from_vec(paths);
+                unsafe {
+                    msg_send![&pasteboard, setPropertyList: &*paths, forType: &*filenames_type()]
+                }
+            },
+            // The type holds a single URL.
+            ClipboardData::Uris(uris) => uris.first().map_or(true, |uri| {
+                let uri = NSString::from_str(uri);
+                unsafe { msg_send![&pasteboard, setString: &*uri, forType: NSPasteboardTypeURL] }
+            }),
+            ClipboardData::Custom { mime_type, data } => {
+                set_data(data, &NSString::from_str(mime_type))
+            },
+        };
+    }
+
+    if !written {
+        return Err(os_error!("Failed to write the pasteboard").into());
+    }
+    Ok(())
+}
+
+/// Read the `format` of the pasteboard, delivered once the current event is handled.
+pub(crate) fn get(app_state: &Rc<AppState>, format: ClipboardFormat) -> AsyncRequestSerial {
+    let serial = AsyncRequestSerial::get();
+    let data = read(&general_pasteboard(), format);
+    app_state.maybe_queue_with_handler(move |app, event_loop| {
+        app.clipboard_data(event_loop, serial, data)
+    });
+    serial
+}
+
+/// The type of the pasteboard holding the `format`.
+fn pasteboard_type(format: ClipboardFormat) -> Retained<NSPasteboardType> {
+    match format {
+        ClipboardFormat::Text => unsafe { NSPasteboardTypeString }.copy(),
+        ClipboardFormat::Html => unsafe { NSPasteboardTypeHTML }.copy(),
+        ClipboardFormat::Image => unsafe { NSPasteboardTypePNG }.copy(),
+        ClipboardFormat::Files => filenames_type(),
+        ClipboardFormat::Uris => unsafe { NSPasteboardTypeURL }.copy(),
+        ClipboardFormat::Custom(mime_type) => NSString::from_str(mime_type),
+    }
+}
+
+/// Whether the pasteboard holds the `format`.
+pub(crate) fn contains(pasteboard: &NSPasteboard, format: ClipboardFormat) -> bool {
+    let types: Option<Retained<NSArray<NSString>>> = unsafe { msg_send_id![pasteboard, types] };
+    let kind = pasteboard_type(format);
+    types.is_some_and(|types| types.iter().any(|t| *t == *kind))
+}
+
+/// Read the `format` of the pasteboard, such as the general pasteboard or the one of a drag.
+pub(crate) fn read(pasteboard: &NSPasteboard, format: ClipboardFormat) -> Option<ClipboardData> {
+    let kind = pasteboard_type(format);
+    let get_data = || -> Option<Vec<u8>> {
+        let data: Option<Retained<NSData>> =
+            unsafe { msg_send_id![pasteboard, dataForType: &*kind] };
+        data.map(|data| data.bytes().to_vec())
+    };
+    let get_string = || -> Option<String> {
+        let text: Option<Retained<NSString>> =
+            unsafe { msg_send_id![pasteboard, stringForType: &*kind] };
+        text.map(|text| text.to_string())
+    };
+
+    match format {
+        ClipboardFormat::Text => get_string().map(ClipboardData::Text),
+        ClipboardFormat::Html => {
+            get_data().map(|html| ClipboardData::Html(String::from_utf8_lossy(&html).into_owned()))
+        },
+        ClipboardFormat::Image => {
+            get_data().map(|png| ClipboardData::Image(ClipboardImage::Png(png)))
+        },
+        ClipboardFormat::Files => {
+            // The property list of this type is an array of strings.
+            let paths: Option<Retained<NSArray<NSString>>> =
+                unsafe { msg_send_id![pasteboard, propertyListForType: &*kind] };
+            paths.map(|paths| {
+                ClipboardData::Files(
+                    paths.iter().map(|path| PathBuf::from(path.to_string())).collect(),
+                )
+            })
+        },
+        ClipboardFormat::Uris => get_string().map(|uri| ClipboardData::Uris(vec![uri])),
+        ClipboardFormat::Custom(mime_type) => {
+            get_data().map(|data| ClipboardData::Custom { mime_type, data })
+        },
+    }
+}
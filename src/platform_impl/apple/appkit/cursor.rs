@@ -11,7 +11,7 @@ use objc2_foundation::{
     NSString,
 };
 
-use crate::cursor::{CursorImage, OnlyCursorImageSource};
+use crate::cursor::{CursorImage, CursorImages, OnlyCursorImageSource};
 use crate::error::RequestError;
 use crate::window::CursorIcon;
 
@@ -25,11 +25,31 @@ unsafe impl Sync for CustomCursor {}
 
 impl CustomCursor {
     pub(crate) fn new(cursor: OnlyCursorImageSource) -> Result<CustomCursor, RequestError> {
-        cursor_from_image(&cursor.0).map(Self)
+        cursor_from_images(&cursor.0).map(Self)
     }
 }
 
-pub(crate) fn cursor_from_image(cursor: &CursorImage) -> Result<Retained<NSCursor>, RequestError> {
+/// A cursor whose image has a representation for each scale, the system picks the one for the
+/// screen showing the cursor.
+pub(crate) fn cursor_from_images(
+    cursor: &CursorImages,
+) -> Result<Retained<NSCursor>, RequestError> {
+    let base = cursor.base();
+    // The size is in points, the size of the image at a scale factor of `1.0`.
+    let size = NSSize::new(base.width.into(), base.height.into());
+    let image = unsafe { NSImage::initWithSize(NSImage::alloc(), size) };
+    for cursor in cursor.images() {
+        let bitmap = bitmap_from_image(cursor)?;
+        unsafe { bitmap.setSize(size) };
+        unsafe { image.addRepresentation(&bitmap) };
+    }
+
+    let hotspot = NSPoint::new(base.hotspot_x as f64, base.hotspot_y as f64);
+
+    Ok(NSCursor::initWithImage_hotSpot(NSCursor::alloc(), &image, hotspot))
+}
+
+fn bitmap_from_image(cursor: &CursorImage) -> Result<Retained<NSBitmapImageRep>, RequestError> {
     let width = cursor.width;
     let height = cursor.height;
 
@@ -51,14 +71,7 @@ pub(crate) fn cursor_from_image(cursor: &CursorImage) -> Result<Retained<NSCurso
     let bitmap_data = unsafe { slice::from_raw_parts_mut(bitmap.bitmapData(), cursor.rgba.len()) };
     bitmap_data.copy_from_slice(&cursor.rgba);
 
-    let image = unsafe {
-        NSImage::initWithSize(NSImage::alloc(), NSSize::new(width.into(), height.into()))
-    };
-    unsafe { image.addRepresentation(&bitmap) };
-
-    let hotspot = NSPoint::new(cursor.hotspot_x as f64, cursor.hotspot_y as f64);
-
-    Ok(NSCursor::initWithImage_hotSpot(NSCursor::alloc(), &image, hotspot))
+    Ok(bitmap)
 }
 
 pub(crate) fn default_cursor() -> Retained<NSCursor> {
@@ -0,0 +1,208 @@
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use block2::RcBlock;
+use objc2::rc::Retained;
+use objc2::runtime::AnyObject;
+use objc2::{class, msg_send, msg_send_id};
+use objc2_app_kit::{NSApplication, NSModalResponse, NSOpenPanel, NSSavePanel, NSWindow};
+use objc2_foundation::{MainThreadMarker, NSArray, NSString, NSUInteger, NSURL};
+
+use super::app_state::AppState;
+use crate::dialogs::{
+    FileDialogKind, FileDialogOptions, FileDialogResult, MessageBoxButton, MessageBoxButtons,
+    MessageBoxConfig, MessageBoxLevel, MessageBoxResult, Modality,
+};
+use crate::event_loop::AsyncRequestSerial;
+use crate::window::WindowId;
+
+// See `NSApplication.h`.
+const MODAL_RESPONSE_OK: NSModalResponse = 1;
+
+// See `NSAlert.h`.
+const ALERT_FIRST_BUTTON_RETURN: NSModalResponse = 1000;
+const ALERT_STYLE_WARNING: NSUInteger = 0;
+const ALERT_STYLE_INFORMATIONAL: NSUInteger = 1;
+const ALERT_STYLE_CRITICAL: NSUInteger = 2;
+
+/// Show the panel, as a sheet of the parent window when there is one.
+///
+/// The panel runs on top of the event loop, and its result is delivered once it's dismissed.
+/// Application-modal panels are run modally instead, before this returns.
+pub fn show_file_dialog(
+    app_state: &Rc<AppState>,
+    mtm: MainThreadMarker,
+    kind: FileDialogKind,
+    options: FileDialogOptions,
+) -> AsyncRequestSerial {
+    let serial = AsyncRequestSerial::get();
+
+    let (panel, open_panel) = match kind {
+        FileDialogKind::SaveFile => (unsafe { NSSavePanel::savePanel(mtm) }, None),
+        FileDialogKind::PickFile | FileDialogKind::PickFolder => unsafe {
+            let panel = NSOpenPanel::openPanel(mtm);
+            let folder = kind == FileDialogKind::PickFolder;
+            panel.setCanChooseFiles(!folder);
+            panel.setCanChooseDirectories(folder);
+            panel.setAllowsMultipleSelection(options.multiple);
+            (Retained::into_super(panel.clone()), Some(panel))
+        },
+    };
+
+    if let Some(title) = &options.title {
+        // Open panels don't show a title since macOS 11, the message is shown instead.
+        let title = NSString::from_str(title);
+        unsafe {
+            panel.setTitle(Some(&title));
+            panel.setMessage(Some(&title));
+        }
+    }
+    if let Some(directory) = &options.directory {
+        let path = NSString::from_str(&directory.to_string_lossy());
+        let url = unsafe { NSURL::fileURLWithPath_isDirectory(&path, true) };
+        unsafe { panel.setDirectoryURL(Some(&url)) };
+    }
+    if let (FileDialogKind::SaveFile, Some(file_name)) = (kind, &options.file_name) {
+        unsafe { panel.setNameFieldStringValue(&NSString::from_str(file_name)) };
+    }
+    if kind != FileDialogKind::PickFolder && !options.filters.is_empty() {
+        // `NSSavePanel` doesn't have a menu of filters, so all the extensions are allowed at once.
+        let extensions: Vec<_> = options
+            .filters
+            .iter()
+            .flat_map(|filter| &filter.extensions)
+            .map(|extension| NSString::from_str(extension))
+            .collect();
+        let extensions = NSArray::from_vec(extensions);
+        // `setAllowedContentTypes:` requires the `UniformTypeIdentifiers` framework.
+        let _: () = unsafe { msg_send![&panel, setAllowedFileTypes: &*extensions] };
+    }
+
+    let completion_panel = panel.clone();
+    let app_state = Rc::clone(app_state);
+    let done = move |response: NSModalResponse| {
+        let result = if response == MODAL_RESPONSE_OK {
+            FileDialogResult::Picked(panel_paths(&completion_panel, open_panel.as_deref()))
+        } else {
+            FileDialogResult::Cancelled
+        };
+        app_state.maybe_queue_with_handler(move |app, event_loop| {
+            app.file_dialog_done(event_loop, serial, result)
+        });
+    };
+
+    match (options.modality, options.parent.and_then(|parent| find_window(mtm, parent))) {
+        (Modality::Application, _) => done(unsafe { msg_send![&panel, runModal] }),
+        (Modality::Window, Some(window)) => unsafe {
+            let handler = RcBlock::new(done);
+            let _: () = msg_send![
+                &panel,
+                beginSheetModalForWindow: &*window,
+                completionHandler: &*handler
+            ];
+        },
+        (Modality::Window, None) => unsafe {
+            let handler = RcBlock::new(done);
+            let _: () = msg_send![&panel, beginWithCompletionHandler: &*handler];
+        },
+    }
+
+    serial
+}
+
+/// Show an alert, as a sheet of the parent window when there is one.
+///
+/// Without a parent window, or when it's application-modal, the alert is run modally, and its
+/// result is delivered once the event handler returns.
+pub fn show_message_box(
+    app_state: &Rc<AppState>,
+    mtm: MainThreadMarker,
+    config: MessageBoxConfig,
+) -> AsyncRequestSerial {
+    let serial = AsyncRequestSerial::get();
+
+    // `NSAlert` doesn't have a title bar, so the title becomes the bold message.
+    let (message, informative) = if config.title.is_empty() {
+        (config.text.as_str(), "")
+    } else {
+        (config.title.as_str(), config.text.as_str())
+    };
+    let style = match config.level {
+        MessageBoxLevel::Info => ALERT_STYLE_INFORMATIONAL,
+        MessageBoxLevel::Warning => ALERT_STYLE_WARNING,
+        MessageBoxLevel::Error => ALERT_STYLE_CRITICAL,
+    };
+    let buttons: &'static [MessageBoxButton] = match config.buttons {
+        MessageBoxButtons::Ok => &[MessageBoxButton::Ok],
+        MessageBoxButtons::OkCancel => &[MessageBoxButton::Ok, MessageBoxButton::Cancel],
+        MessageBoxButtons::YesNo => &[MessageBoxButton::Yes, MessageBoxButton::No],
+        MessageBoxButtons::YesNoCancel => {
+            &[MessageBoxButton::Yes, MessageBoxButton::No, MessageBoxButton::Cancel]
+        },
+    };
+
+    let alert: Retained<AnyObject> = unsafe { msg_send_id![class!(NSAlert), new] };
+    unsafe {
+        let _: () = msg_send![&alert, setMessageText: &*NSString::from_str(message)];
+        let _: () = msg_send![&alert, setInformativeText: &*NSString::from_str(informative)];
+        let _: () = msg_send![&alert, setAlertStyle: style];
+    }
+    for button in buttons {
+        let title = NSString::from_str(match button {
+            MessageBoxButton::Ok => "OK",
+            MessageBoxButton::Cancel => "Cancel",
+            MessageBoxButton::Yes => "Yes",
+            MessageBoxButton::No => "No",
+        });
+        // The button titled "Cancel" is also pressed by the escape key.
+        let _: *mut AnyObject = unsafe { msg_send![&alert, addButtonWithTitle: &*title] };
+    }
+
+    let app_state = Rc::clone(app_state);
+    let dismissed = config.buttons.dismissed();
+    let done = move |response: NSModalResponse| {
+        let button = usize::try_from(response - ALERT_FIRST_BUTTON_RETURN)
+            .ok()
+            .and_then(|index| buttons.get(index).copied())
+            .unwrap_or(dismissed);
+        app_state.maybe_queue_with_handler(move |app, event_loop| {
+            app.message_box_done(event_loop, serial, MessageBoxResult::Pressed(button))
+        });
+    };
+
+    let parent = match config.modality {
+        Modality::Window => config.parent.and_then(|parent| find_window(mtm, parent)),
+        Modality::Application => None,
+    };
+    match parent {
+        Some(window) => {
+            let handler = RcBlock::new(done);
+            unsafe {
+                let _: () = msg_send![
+                    &alert,
+                    beginSheetModalForWindow: &*window,
+                    completionHandler: &*handler
+                ];
+            }
+        },
+        None => done(unsafe { msg_send![&alert, runModal] }),
+    }
+
+    serial
+}
+
+fn panel_paths(panel: &NSSavePanel, open_panel: Option<&NSOpenPanel>) -> Vec<PathBuf> {
+    let urls = match open_panel {
+        Some(open_panel) => unsafe { open_panel.URLs() }.to_vec_retained(),
+        None => unsafe { panel.URL() }.into_iter().collect(),
+    };
+    urls.iter()
+        .filter_map(|url| unsafe { url.path() })
+        .map(|path| PathBuf::from(path.to_string()))
+        .collect()
+}
+
+fn find_window(mtm: MainThreadMarker, id: WindowId) -> Option<Retained<NSWindow>> {
+    let windows = NSApplication::sharedApplication(mtm).windows().to_vec_retained();
+    windows.into_iter().find(|window| Retained::as_ptr(window) as usize == id.into_raw())
+}
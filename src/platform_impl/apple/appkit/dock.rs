@@ -0,0 +1,62 @@
+//! The menu, badge and progress bar of the icon of the application in the Dock.
+
+use std::cell::OnceCell;
+
+use objc2::rc::Retained;
+use objc2_app_kit::{NSApplication, NSImageView, NSProgressIndicator, NSProgressIndicatorStyle};
+use objc2_foundation::{MainThreadMarker, NSPoint, NSRect, NSSize, NSString};
+
+use super::app_delegate::{self, ApplicationDelegate};
+use super::menu;
+use crate::platform::macos::Menu;
+
+/// The height of the progress bar, relative to the icon.
+const PROGRESS_BAR_HEIGHT: f64 = 0.15;
+
+pub(super) fn set_menu(
+    app: &NSApplication,
+    delegate: &OnceCell<Retained<ApplicationDelegate>>,
+    menu: Option<&Menu>,
+) {
+    let delegate = app_delegate::install(app, delegate);
+    delegate.set_dock_menu(menu.map(|menu| menu::build_menu(app, menu)));
+}
+
+pub(super) fn set_badge(app: &NSApplication, badge: Option<&str>) {
+    let badge = badge.map(NSString::from_str);
+    unsafe { app.dockTile().setBadgeLabel(badge.as_deref()) };
+}
+
+/// Replace the icon with a view of the icon with the progress bar over it.
+pub(super) fn set_progress(app: &NSApplication, progress: Option<f64>) {
+    let mtm = MainThreadMarker::from(app);
+    let dock_tile = unsafe { app.dockTile() };
+    match progress {
+        Some(progress) => {
+            let size = unsafe { dock_tile.size() };
+            let view = unsafe {
+                NSImageView::initWithFrame(mtm.alloc(), NSRect::new(NSPoint::new(0.0, 0.0), size))
+            };
+            unsafe { view.setImage(app.applicationIconImage().as_deref()) };
+
+            let bar_size = NSSize::new(size.width, size.height * PROGRESS_BAR_HEIGHT);
+            let bar = unsafe {
+                NSProgressIndicator::initWithFrame(
+                    mtm.alloc(),
+                    NSRect::new(NSPoint::new(0.0, 0.0), bar_size),
+                )
+            };
+            unsafe {
+                bar.setStyle(NSProgressIndicatorStyle::Bar);
+                bar.setIndeterminate(false);
+                bar.setMinValue(0.0);
+                bar.setMaxValue(1.0);
+                bar.setDoubleValue(progress.clamp(0.0, 1.0));
+                view.addSubview(&bar);
+                dock_tile.setContentView(Some(&view));
+            }
+        },
+        None => unsafe { dock_tile.setContentView(None) },
+    }
+    unsafe { dock_tile.display() };
+}
@@ -1,7 +1,8 @@
 use std::ffi::c_void;
 
-use core_foundation::base::CFRelease;
+use core_foundation::base::{CFRelease, TCFType};
 use core_foundation::data::{CFDataGetBytePtr, CFDataRef};
+use core_foundation::string::{CFString, CFStringRef};
 use objc2::rc::Retained;
 use objc2_app_kit::{NSEvent, NSEventModifierFlags, NSEventSubtype, NSEventType};
 use objc2_foundation::{run_on_main, NSPoint};
@@ -10,8 +11,8 @@ use smol_str::SmolStr;
 use super::ffi;
 use crate::event::{ElementState, KeyEvent, Modifiers};
 use crate::keyboard::{
-    Key, KeyCode, KeyLocation, ModifiersKeys, ModifiersState, NamedKey, NativeKey, NativeKeyCode,
-    PhysicalKey,
+    Key, KeyCode, KeyLocation, KeyboardLayout, ModifiersKeys, ModifiersState, NamedKey, NativeKey,
+    NativeKeyCode, PhysicalKey,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -20,6 +21,29 @@ pub struct KeyEventExtra {
     pub key_without_modifiers: Key,
 }
 
+/// The keyboard input source currently selected.
+pub fn keyboard_layout() -> Option<KeyboardLayout> {
+    unsafe {
+        let input_source = ffi::TISCopyCurrentKeyboardLayoutInputSource();
+        if input_source.is_null() {
+            return None;
+        }
+
+        let string_property = |key| {
+            let value = ffi::TISGetInputSourceProperty(input_source, key) as CFStringRef;
+            (!value.is_null()).then(|| CFString::wrap_under_get_rule(value).to_string())
+        };
+        let layout = string_property(ffi::kTISPropertyInputSourceID).map(|id| {
+            let name =
+                string_property(ffi::kTISPropertyLocalizedName).unwrap_or_else(|| id.clone());
+            KeyboardLayout { id, name }
+        });
+
+        CFRelease(input_source as *mut c_void);
+        layout
+    }
+}
+
 /// Ignores ALL modifiers.
 pub fn get_modifierless_char(scancode: u16) -> Key {
     let mut string = [0; 16];
@@ -313,6 +337,30 @@ pub(super) fn ralt_pressed(event: &NSEvent) -> bool {
     unsafe { event.modifierFlags() }.contains(NX_DEVICERALTKEYMASK)
 }
 
+/// The modifiers currently held, for the events which don't come with an `NSEvent`.
+pub(super) fn current_mods() -> ModifiersState {
+    let flags = unsafe { NSEvent::modifierFlags_class() };
+    let mut state = ModifiersState::empty();
+    state
+        .set(ModifiersState::SHIFT, flags.contains(NSEventModifierFlags::NSEventModifierFlagShift));
+    state.set(
+        ModifiersState::CONTROL,
+        flags.contains(NSEventModifierFlags::NSEventModifierFlagControl),
+    );
+    state.set(ModifiersState::ALT, flags.contains(NSEventModifierFlags::NSEventModifierFlagOption));
+    state.set(
+        ModifiersState::SUPER,
+        flags.contains(NSEventModifierFlags::NSEventModifierFlagCommand),
+    );
+    state
+}
+
+/// Whether Caps Lock is currently on.
+pub(super) fn caps_lock() -> bool {
+    let flags = unsafe { NSEvent::modifierFlags_class() };
+    flags.contains(NSEventModifierFlags::NSEventModifierFlagCapsLock)
+}
+
 pub(super) fn event_mods(event: &NSEvent) -> Modifiers {
     let flags = unsafe { event.modifierFlags() };
     let mut state = ModifiersState::empty();
@@ -13,32 +13,57 @@ use core_foundation::runloop::{
     kCFRunLoopCommonModes, CFRunLoopAddSource, CFRunLoopGetMain, CFRunLoopSourceContext,
     CFRunLoopSourceCreate, CFRunLoopSourceRef, CFRunLoopSourceSignal, CFRunLoopWakeUp,
 };
+use core_graphics::display::CGDisplay;
 use objc2::rc::{autoreleasepool, Retained};
 use objc2::{msg_send_id, sel, ClassType};
 use objc2_app_kit::{
-    NSApplication, NSApplicationActivationPolicy, NSApplicationDidFinishLaunchingNotification,
-    NSApplicationWillTerminateNotification, NSWindow,
+    NSApplication, NSApplicationActivationPolicy,
+    NSApplicationDidChangeScreenParametersNotification,
+    NSApplicationDidFinishLaunchingNotification, NSApplicationWillFinishLaunchingNotification,
+    NSApplicationWillTerminateNotification, NSWindow, NSWorkspace,
+    NSWorkspaceAccessibilityDisplayOptionsDidChangeNotification, NSWorkspaceDidWakeNotification,
+    NSWorkspaceWillSleepNotification,
+};
+use objc2_foundation::{
+    ns_string, MainThreadMarker, NSCurrentLocaleDidChangeNotification,
+    NSDistributedNotificationCenter, NSNotificationCenter, NSObject, NSObjectProtocol,
 };
-use objc2_foundation::{MainThreadMarker, NSNotificationCenter, NSObject, NSObjectProtocol};
 
+use super::super::locale::current_locale;
 use super::super::notification_center::create_observer;
-use super::app::WinitApplication;
+use super::app::{register_url_schemes, WinitApplication};
 use super::app_state::AppState;
 use super::cursor::CustomCursor;
+#[cfg(feature = "dialogs")]
+use super::dialogs;
 use super::event::dummy_event;
-use super::monitor;
 use super::observer::setup_control_flow_observers;
+use super::power::{self, PowerSourceObserver};
+use super::window::WinitWindow;
+use super::{clipboard, monitor, services, window_delegate};
 use crate::application::ApplicationHandler;
-use crate::error::{EventLoopError, RequestError};
+use crate::clipboard::{Clipboard as RootClipboard, ClipboardData, ClipboardFormat};
+#[cfg(feature = "dialogs")]
+use crate::dialogs::{FileDialogKind, FileDialogOptions, MessageBoxConfig};
+use crate::drag::DragData;
+use crate::error::{EventLoopError, NotSupportedError, RequestError};
+use crate::event::{
+    DeviceInfo, LanguageTag, PlatformData, PowerEvent, PowerState, SystemPreferences,
+};
 use crate::event_loop::{
-    ActiveEventLoop as RootActiveEventLoop, ControlFlow, DeviceEvents,
-    EventLoopProxy as RootEventLoopProxy, OwnedDisplayHandle as RootOwnedDisplayHandle,
+    ActiveEventLoop as RootActiveEventLoop, AsyncRequestSerial, ControlFlow, DeviceClass,
+    DeviceEvents, EventLoopProxy as RootEventLoopProxy, ModifiersOrder,
+    OwnedDisplayHandle as RootOwnedDisplayHandle,
 };
+use crate::keyboard::{Key, KeyCode, KeyboardLayout, Led, PhysicalKey};
 use crate::monitor::MonitorHandle as RootMonitorHandle;
-use crate::platform::macos::ActivationPolicy;
+use crate::platform::macos::{ActivationPolicy, Menu, MenuDescription};
 use crate::platform::pump_events::PumpStatus;
 use crate::platform_impl::Window;
-use crate::window::{CustomCursor as RootCustomCursor, CustomCursorSource, Theme};
+use crate::window::{
+    Cursor, CursorThemeInfo, CustomCursor as RootCustomCursor, CustomCursorSource, Rgba, Theme,
+    WindowId,
+};
 
 #[derive(Default)]
 pub struct PanicInfo {
@@ -92,6 +117,32 @@ impl ActiveEventLoop {
     pub(crate) fn allows_automatic_window_tabbing(&self) -> bool {
         NSWindow::allowsAutomaticWindowTabbing(self.mtm)
     }
+
+    pub(crate) fn set_main_menu(&self, menu: MenuDescription) {
+        self.app_state.set_main_menu(menu)
+    }
+
+    pub(crate) fn set_dock_menu(&self, menu: Option<Menu>) {
+        self.app_state.set_dock_menu(menu)
+    }
+
+    pub(crate) fn set_dock_badge(&self, badge: Option<&str>) {
+        self.app_state.set_dock_badge(badge)
+    }
+
+    pub(crate) fn set_dock_progress(&self, progress: Option<f64>) {
+        self.app_state.set_dock_progress(progress)
+    }
+}
+
+impl RootClipboard for ActiveEventLoop {
+    fn set(&self, data: Vec<ClipboardData>) -> Result<(), RequestError> {
+        clipboard::set(&data)
+    }
+
+    fn get(&self, format: ClipboardFormat) -> Result<AsyncRequestSerial, RequestError> {
+        Ok(clipboard::get(&self.app_state, format))
+    }
 }
 
 impl RootActiveEventLoop for ActiveEventLoop {
@@ -125,6 +176,70 @@ impl RootActiveEventLoop for ActiveEventLoop {
 
     fn listen_device_events(&self, _allowed: DeviceEvents) {}
 
+    fn listen_device_classes(&self, _classes: DeviceClass) {}
+
+    fn input_devices(&self) -> Vec<DeviceInfo> {
+        Vec::new()
+    }
+
+    #[cfg(feature = "gamepad")]
+    fn set_gamepad_rumble(
+        &self,
+        _device_id: crate::event::DeviceId,
+        _strong: f32,
+        _weak: f32,
+    ) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("gamepads are not supported").into())
+    }
+
+    fn windows(&self) -> Vec<WindowId> {
+        NSApplication::sharedApplication(self.mtm)
+            .windows()
+            .iter()
+            .filter(|window| window.is_kind_of::<WinitWindow>())
+            .map(|window| WindowId::from_raw(window as *const NSWindow as usize))
+            .collect()
+    }
+
+    fn with_native_modal_scope(&self, f: &mut dyn FnMut()) {
+        // Modal panels run their own loop on top of ours, so only the cursor must be released.
+        let _ = CGDisplay::associate_mouse_and_mouse_cursor_position(true);
+        f();
+        window_delegate::restore_cursor_grabs(self.mtm);
+    }
+
+    fn clipboard(&self) -> &dyn RootClipboard {
+        self
+    }
+
+    #[cfg(feature = "dialogs")]
+    fn pick_file(&self, options: FileDialogOptions) -> Result<AsyncRequestSerial, RequestError> {
+        Ok(dialogs::show_file_dialog(&self.app_state, self.mtm, FileDialogKind::PickFile, options))
+    }
+
+    #[cfg(feature = "dialogs")]
+    fn pick_folder(&self, options: FileDialogOptions) -> Result<AsyncRequestSerial, RequestError> {
+        Ok(dialogs::show_file_dialog(
+            &self.app_state,
+            self.mtm,
+            FileDialogKind::PickFolder,
+            options,
+        ))
+    }
+
+    #[cfg(feature = "dialogs")]
+    fn save_file(&self, options: FileDialogOptions) -> Result<AsyncRequestSerial, RequestError> {
+        Ok(dialogs::show_file_dialog(&self.app_state, self.mtm, FileDialogKind::SaveFile, options))
+    }
+
+    #[cfg(feature = "dialogs")]
+    fn show_message_box(
+        &self,
+        config: MessageBoxConfig,
+    ) -> Result<AsyncRequestSerial, RequestError> {
+        Ok(dialogs::show_message_box(&self.app_state, self.mtm, config))
+    }
+
     fn system_theme(&self) -> Option<Theme> {
         let app = NSApplication::sharedApplication(self.mtm);
 
@@ -135,6 +250,83 @@ impl RootActiveEventLoop for ActiveEventLoop {
         }
     }
 
+    fn system_accent_color(&self) -> Option<Rgba> {
+        super::window_delegate::accent_color()
+    }
+
+    fn cursor_theme(&self) -> Option<CursorThemeInfo> {
+        None
+    }
+
+    fn system_preferences(&self) -> Option<SystemPreferences> {
+        Some(system_preferences())
+    }
+
+    fn text_scale_factor(&self) -> Option<f64> {
+        None
+    }
+
+    fn power_state(&self) -> Option<PowerState> {
+        power::power_state()
+    }
+
+    fn locale(&self) -> LanguageTag {
+        current_locale()
+    }
+
+    fn keyboard_layout(&self) -> Option<KeyboardLayout> {
+        super::event::keyboard_layout()
+    }
+
+    fn key_label(&self, key_code: KeyCode) -> Option<String> {
+        self.key_for_keycode(key_code)?.to_label()
+    }
+
+    fn key_for_keycode(&self, key_code: KeyCode) -> Option<Key> {
+        let physical_key = PhysicalKey::Code(key_code);
+        let scancode = super::event::physicalkey_to_scancode(physical_key)? as u16;
+        let key = match super::event::code_to_key(physical_key, scancode) {
+            Key::Unidentified(_) => super::event::get_modifierless_char(scancode),
+            key => key,
+        };
+        (!matches!(key, Key::Unidentified(_))).then_some(key)
+    }
+
+    fn keycode_for_key(&self, key: &Key) -> Option<KeyCode> {
+        // The virtual key codes of the keyboard, in which the numpad comes after the main block.
+        (0..0x80).find_map(|scancode| {
+            let PhysicalKey::Code(key_code) = super::event::scancode_to_physicalkey(scancode)
+            else {
+                return None;
+            };
+            let unmodified = self.key_for_keycode(key_code)?;
+            key.is_produced_by(&unmodified).then_some(key_code)
+        })
+    }
+
+    fn set_keyboard_led(&self, _led: Led, _on: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_keyboard_led is not supported").into())
+    }
+
+    fn keyboard_led(&self, led: Led) -> Option<bool> {
+        match led {
+            Led::CapsLock => Some(super::event::caps_lock()),
+            _ => None,
+        }
+    }
+
+    fn event_platform_data(&self) -> Option<PlatformData> {
+        None
+    }
+
+    fn start_drag(&self, source: WindowId, data: DragData) -> Result<(), RequestError> {
+        self.app_state.start_drag(source, data)
+    }
+
+    fn set_drag_image(&self, image: Option<Cursor>) -> Result<(), RequestError> {
+        self.app_state.set_drag_image(image)
+    }
+
     fn set_control_flow(&self, control_flow: ControlFlow) {
         self.app_state.set_control_flow(control_flow)
     }
@@ -184,20 +376,40 @@ pub struct EventLoop {
     // the system instead cleans it up next time it would have posted a notification to it.
     //
     // Though we do still need to keep the observers around to prevent them from being deallocated.
+    _will_finish_launching_observer: Retained<NSObject>,
     _did_finish_launching_observer: Retained<NSObject>,
     _will_terminate_observer: Retained<NSObject>,
+    _will_sleep_observer: Retained<NSObject>,
+    _did_wake_observer: Retained<NSObject>,
+    _power_source_observer: Option<PowerSourceObserver>,
+    _screen_locked_observer: Retained<NSObject>,
+    _screen_unlocked_observer: Retained<NSObject>,
+    _locale_observer: Retained<NSObject>,
+    _screen_parameters_observer: Retained<NSObject>,
+    _keyboard_layout_observer: Retained<NSObject>,
+    _accessibility_display_observer: Retained<NSObject>,
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub(crate) struct PlatformSpecificEventLoopAttributes {
     pub(crate) activation_policy: Option<ActivationPolicy>,
     pub(crate) default_menu: bool,
     pub(crate) activate_ignoring_other_apps: bool,
+    pub(crate) modifiers_order: ModifiersOrder,
+    pub(crate) url_schemes: Vec<String>,
+    pub(crate) services: Vec<String>,
 }
 
 impl Default for PlatformSpecificEventLoopAttributes {
     fn default() -> Self {
-        Self { activation_policy: None, default_menu: true, activate_ignoring_other_apps: true }
+        Self {
+            activation_policy: None,
+            default_menu: true,
+            activate_ignoring_other_apps: true,
+            modifiers_order: ModifiersOrder::default(),
+            url_schemes: Vec::new(),
+            services: Vec::new(),
+        }
     }
 }
 
@@ -230,10 +442,26 @@ impl EventLoop {
             activation_policy,
             attributes.default_menu,
             attributes.activate_ignoring_other_apps,
+            attributes.modifiers_order,
         );
 
+        register_url_schemes(&attributes.url_schemes);
+        services::set_services_provider(&app, &attributes.services);
+
         let center = unsafe { NSNotificationCenter::defaultCenter() };
 
+        let weak_app_state = Rc::downgrade(&app_state);
+        let _will_finish_launching_observer = create_observer(
+            &center,
+            // `applicationWillFinishLaunching:`
+            unsafe { NSApplicationWillFinishLaunchingNotification },
+            move |notification| {
+                if let Some(app_state) = weak_app_state.upgrade() {
+                    app_state.will_finish_launching(notification);
+                }
+            },
+        );
+
         let weak_app_state = Rc::downgrade(&app_state);
         let _did_finish_launching_observer = create_observer(
             &center,
@@ -258,6 +486,100 @@ impl EventLoop {
             },
         );
 
+        // Sleep and wake are only posted to the workspace's notification center.
+        let workspace_center = unsafe { NSWorkspace::sharedWorkspace().notificationCenter() };
+
+        let weak_app_state = Rc::downgrade(&app_state);
+        let _will_sleep_observer = create_observer(
+            &workspace_center,
+            unsafe { NSWorkspaceWillSleepNotification },
+            move |_| {
+                if let Some(app_state) = weak_app_state.upgrade() {
+                    app_state.power_event(PowerEvent::Suspending);
+                }
+            },
+        );
+
+        let weak_app_state = Rc::downgrade(&app_state);
+        let _did_wake_observer = create_observer(
+            &workspace_center,
+            unsafe { NSWorkspaceDidWakeNotification },
+            move |_| {
+                if let Some(app_state) = weak_app_state.upgrade() {
+                    app_state.power_event(PowerEvent::Resumed);
+                }
+            },
+        );
+
+        let _power_source_observer = PowerSourceObserver::new(mtm);
+
+        // The lock screen is only announced through distributed notifications.
+        let distributed_center = unsafe { NSDistributedNotificationCenter::defaultCenter() };
+
+        let weak_app_state = Rc::downgrade(&app_state);
+        let _screen_locked_observer = create_observer(
+            &distributed_center,
+            ns_string!("com.apple.screenIsLocked"),
+            move |_| {
+                if let Some(app_state) = weak_app_state.upgrade() {
+                    app_state.session_lock_changed(true);
+                }
+            },
+        );
+
+        let weak_app_state = Rc::downgrade(&app_state);
+        let _screen_unlocked_observer = create_observer(
+            &distributed_center,
+            ns_string!("com.apple.screenIsUnlocked"),
+            move |_| {
+                if let Some(app_state) = weak_app_state.upgrade() {
+                    app_state.session_lock_changed(false);
+                }
+            },
+        );
+
+        let weak_app_state = Rc::downgrade(&app_state);
+        let _locale_observer =
+            create_observer(&center, unsafe { NSCurrentLocaleDidChangeNotification }, move |_| {
+                if let Some(app_state) = weak_app_state.upgrade() {
+                    app_state.locale_changed(current_locale());
+                }
+            });
+
+        let weak_app_state = Rc::downgrade(&app_state);
+        let _screen_parameters_observer = create_observer(
+            &center,
+            unsafe { NSApplicationDidChangeScreenParametersNotification },
+            move |_| {
+                if let Some(app_state) = weak_app_state.upgrade() {
+                    app_state.screens_changed();
+                }
+            },
+        );
+
+        let weak_app_state = Rc::downgrade(&app_state);
+        let _keyboard_layout_observer = create_observer(
+            &distributed_center,
+            ns_string!("com.apple.Carbon.TISNotifySelectedKeyboardInputSourceChanged"),
+            move |_| {
+                if let Some(app_state) = weak_app_state.upgrade() {
+                    app_state.keyboard_layout_changed();
+                }
+            },
+        );
+
+        // The accessibility display options are also only posted to the workspace's center.
+        let weak_app_state = Rc::downgrade(&app_state);
+        let _accessibility_display_observer = create_observer(
+            &workspace_center,
+            unsafe { NSWorkspaceAccessibilityDisplayOptionsDidChangeNotification },
+            move |_| {
+                if let Some(app_state) = weak_app_state.upgrade() {
+                    app_state.system_preferences_changed(system_preferences());
+                }
+            },
+        );
+
         let panic_info: Rc<PanicInfo> = Default::default();
         setup_control_flow_observers(mtm, Rc::downgrade(&panic_info));
 
@@ -266,8 +588,18 @@ impl EventLoop {
             app_state: app_state.clone(),
             window_target: ActiveEventLoop { app_state, mtm },
             panic_info,
+            _will_finish_launching_observer,
             _did_finish_launching_observer,
             _will_terminate_observer,
+            _will_sleep_observer,
+            _did_wake_observer,
+            _power_source_observer,
+            _screen_locked_observer,
+            _screen_unlocked_observer,
+            _locale_observer,
+            _screen_parameters_observer,
+            _keyboard_layout_observer,
+            _accessibility_display_observer,
         })
     }
 
@@ -275,6 +607,22 @@ impl EventLoop {
         &self.window_target
     }
 
+    pub(crate) fn set_main_menu(&self, menu: MenuDescription) {
+        self.window_target.set_main_menu(menu)
+    }
+
+    pub(crate) fn set_dock_menu(&self, menu: Option<Menu>) {
+        self.window_target.set_dock_menu(menu)
+    }
+
+    pub(crate) fn set_dock_badge(&self, badge: Option<&str>) {
+        self.window_target.set_dock_badge(badge)
+    }
+
+    pub(crate) fn set_dock_progress(&self, progress: Option<f64>) {
+        self.window_target.set_dock_progress(progress)
+    }
+
     pub fn run_app<A: ApplicationHandler>(mut self, app: A) -> Result<(), EventLoopError> {
         self.run_app_on_demand(app)
     }
@@ -404,6 +752,18 @@ impl OwnedDisplayHandle {
     }
 }
 
+/// The accessibility display options of the workspace.
+fn system_preferences() -> SystemPreferences {
+    let workspace = unsafe { NSWorkspace::sharedWorkspace() };
+    unsafe {
+        SystemPreferences {
+            reduced_motion: workspace.accessibilityDisplayShouldReduceMotion(),
+            high_contrast: workspace.accessibilityDisplayShouldIncreaseContrast(),
+            reduced_transparency: workspace.accessibilityDisplayShouldReduceTransparency(),
+        }
+    }
+}
+
 pub(super) fn stop_app_immediately(app: &NSApplication) {
     autoreleasepool(|_| {
         app.stop(None);
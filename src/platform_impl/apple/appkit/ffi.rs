@@ -5,17 +5,22 @@
 use std::ffi::c_void;
 
 use core_foundation::array::CFArrayRef;
+use core_foundation::base::CFTypeRef;
 use core_foundation::dictionary::CFDictionaryRef;
+use core_foundation::runloop::CFRunLoopSourceRef;
 use core_foundation::string::CFStringRef;
 use core_foundation::uuid::CFUUIDRef;
 use core_graphics::base::CGError;
 use core_graphics::display::{CGDirectDisplayID, CGDisplayConfigRef};
-use objc2::ffi::NSInteger;
+use objc2::encode::{Encoding, RefEncode};
+use objc2::ffi::{NSInteger, NSUInteger};
 use objc2::runtime::AnyObject;
+use objc2_foundation::{NSDictionary, NSObject, NSString};
 
 pub type CGDisplayFadeInterval = f32;
 pub type CGDisplayReservationInterval = f32;
 pub type CGDisplayBlendFraction = f32;
+pub type CGGammaValue = f32;
 
 pub const kCGDisplayBlendNormal: f32 = 0.0;
 pub const kCGDisplayBlendSolidColor: f32 = 1.0;
@@ -58,6 +63,54 @@ pub const IO8BitOverlayPixels: &str = "O8";
 pub type CGWindowLevel = i32;
 pub type CGDisplayModeRef = *mut c_void;
 
+#[repr(C)]
+pub struct CGColorSpace {
+    _priv: [u8; 0],
+}
+
+unsafe impl RefEncode for CGColorSpace {
+    const ENCODING_REF: Encoding = Encoding::Pointer(&Encoding::Struct("CGColorSpace", &[]));
+}
+
+pub type CGColorSpaceRef = *mut CGColorSpace;
+
+#[repr(C)]
+pub struct CGEvent {
+    _priv: [u8; 0],
+}
+
+unsafe impl RefEncode for CGEvent {
+    const ENCODING_REF: Encoding = Encoding::Pointer(&Encoding::Struct("__CGEvent", &[]));
+}
+
+pub type CGEventRef = *mut CGEvent;
+pub type CGEventMask = u64;
+pub type CGEventFlags = u64;
+pub type CGEventType = u32;
+pub type CGEventTapProxy = *mut c_void;
+pub type CGEventTapCallBack = unsafe extern "C" fn(
+    proxy: CGEventTapProxy,
+    kind: CGEventType,
+    event: CGEventRef,
+    user_info: *mut c_void,
+) -> CGEventRef;
+pub type CFMachPortRef = *mut c_void;
+
+pub const kCGSessionEventTap: u32 = 1;
+pub const kCGHeadInsertEventTap: u32 = 0;
+pub const kCGEventTapOptionDefault: u32 = 0;
+
+pub const kCGEventKeyDown: CGEventType = 10;
+pub const kCGEventKeyUp: CGEventType = 11;
+pub const kCGEventTapDisabledByTimeout: CGEventType = 0xfffffffe;
+pub const kCGEventTapDisabledByUserInput: CGEventType = 0xffffffff;
+
+pub const kCGEventFlagMaskControl: CGEventFlags = 0x00040000;
+pub const kCGEventFlagMaskCommand: CGEventFlags = 0x00100000;
+
+// From `MTLPixelFormat`.
+pub const MTLPixelFormatRGBA16Float: NSUInteger = 115;
+
 // `CGDisplayCreateUUIDFromDisplayID` comes from the `ColorSync` framework.
 // However, that framework was only introduced "publicly" in macOS 10.13.
 //
@@ -75,6 +128,11 @@ extern "C" {
     pub fn CGRestorePermanentDisplayConfiguration();
     pub fn CGDisplayCapture(display: CGDirectDisplayID) -> CGError;
     pub fn CGDisplayRelease(display: CGDirectDisplayID) -> CGError;
+    pub fn CGDisplayRotation(display: CGDirectDisplayID) -> f64;
+    pub fn CGDisplayVendorNumber(display: CGDirectDisplayID) -> u32;
+    pub fn CGDisplayModelNumber(display: CGDirectDisplayID) -> u32;
+    pub fn CGDisplaySerialNumber(display: CGDirectDisplayID) -> u32;
+    pub fn CGReleaseAllDisplays() -> CGError;
     pub fn CGConfigureDisplayFadeEffect(
         config: CGDisplayConfigRef,
         fadeOutSeconds: CGDisplayFadeInterval,
@@ -114,6 +172,42 @@ extern "C" {
     pub fn CGDisplayModeCopyPixelEncoding(mode: CGDisplayModeRef) -> CFStringRef;
     pub fn CGDisplayModeRetain(mode: CGDisplayModeRef);
     pub fn CGDisplayModeRelease(mode: CGDisplayModeRef);
+    pub fn CGDisplayGammaTableCapacity(display: CGDirectDisplayID) -> u32;
+    pub fn CGGetDisplayTransferByTable(
+        display: CGDirectDisplayID,
+        capacity: u32,
+        redTable: *mut CGGammaValue,
+        greenTable: *mut CGGammaValue,
+        blueTable: *mut CGGammaValue,
+        sampleCount: *mut u32,
+    ) -> CGError;
+    pub fn CGSetDisplayTransferByTable(
+        display: CGDirectDisplayID,
+        tableSize: u32,
+        redTable: *const CGGammaValue,
+        greenTable: *const CGGammaValue,
+        blueTable: *const CGGammaValue,
+    ) -> CGError;
+
+    pub static kCGColorSpaceSRGB: CFStringRef;
+    pub static kCGColorSpaceDisplayP3: CFStringRef;
+    pub static kCGColorSpaceITUR_2020: CFStringRef;
+    pub static kCGColorSpaceExtendedLinearSRGB: CFStringRef;
+    pub static kCGColorSpaceExtendedLinearDisplayP3: CFStringRef;
+    pub static kCGColorSpaceExtendedLinearITUR_2020: CFStringRef;
+    pub fn CGColorSpaceCreateWithName(name: CFStringRef) -> CGColorSpaceRef;
+    pub fn CGColorSpaceRelease(space: CGColorSpaceRef);
+
+    pub fn CGEventTapCreate(
+        tap: u32,
+        place: u32,
+        options: u32,
+        eventsOfInterest: CGEventMask,
+        callback: CGEventTapCallBack,
+        userInfo: *mut c_void,
+    ) -> CFMachPortRef;
+    pub fn CGEventTapEnable(tap: CFMachPortRef, enable: bool);
+    pub fn CGEventGetFlags(event: CGEventRef) -> CGEventFlags;
 
     // Wildly used private APIs; Apple uses them for their Terminal.app.
     pub fn CGSMainConnectionID() -> *mut AnyObject;
@@ -122,6 +216,37 @@ extern "C" {
         window_id: NSInteger,
         radius: i64,
     ) -> i32;
+    pub fn CGSCopySpacesForWindows(
+        connection_id: *mut AnyObject,
+        mask: i32,
+        window_ids: CFArrayRef,
+    ) -> CFArrayRef;
+    pub fn CGSMoveWindowsToManagedSpace(
+        connection_id: *mut AnyObject,
+        window_ids: CFArrayRef,
+        space_id: u64,
+    );
+}
+
+/// The mask of `CGSCopySpacesForWindows` for the Spaces of all the displays.
+pub const kCGSAllSpacesMask: i32 = 0x7;
+
+#[link(name = "CoreServices", kind = "framework")]
+extern "C" {
+    pub fn LSSetDefaultHandlerForURLScheme(
+        in_url_scheme: CFStringRef,
+        in_handler_bundle_id: CFStringRef,
+    ) -> i32;
+}
+
+#[link(name = "CoreFoundation", kind = "framework")]
+extern "C" {
+    pub fn CFMachPortCreateRunLoopSource(
+        allocator: *const c_void,
+        port: CFMachPortRef,
+        order: isize,
+    ) -> CFRunLoopSourceRef;
+    pub fn CFMachPortInvalidate(port: CFMachPortRef);
 }
 
 mod core_video {
@@ -185,6 +310,8 @@ pub const kUCKeyTranslateNoDeadKeysMask: OptionBits = 1;
 #[link(name = "Carbon", kind = "framework")]
 extern "C" {
     pub static kTISPropertyUnicodeKeyLayoutData: CFStringRef;
+    pub static kTISPropertyInputSourceID: CFStringRef;
+    pub static kTISPropertyLocalizedName: CFStringRef;
 
     #[allow(non_snake_case)]
     pub fn TISGetInputSourceProperty(
@@ -194,6 +321,9 @@ extern "C" {
 
     pub fn TISCopyCurrentKeyboardLayoutInputSource() -> TISInputSourceRef;
 
+    pub fn EnableSecureEventInput() -> OSStatus;
+    pub fn DisableSecureEventInput() -> OSStatus;
+
     pub fn LMGetKbdType() -> u8;
 
     #[allow(non_snake_case)]
@@ -211,6 +341,42 @@ extern "C" {
     ) -> OSStatus;
 }
 
+pub const NSAccessibilityPriorityMedium: NSInteger = 50;
+pub const NSAccessibilityPriorityHigh: NSInteger = 90;
+
+// NSAccessibilityConstants.h
+#[link(name = "AppKit", kind = "framework")]
+extern "C" {
+    pub static NSAccessibilityAnnouncementRequestedNotification: &'static NSString;
+    pub static NSAccessibilityAnnouncementKey: &'static NSString;
+    pub static NSAccessibilityPriorityKey: &'static NSString;
+
+    pub fn NSAccessibilityPostNotificationWithUserInfo(
+        element: &AnyObject,
+        notification: &NSString,
+        user_info: &NSDictionary<NSString, NSObject>,
+    );
+}
+
+pub type IOPowerSourceCallbackType = extern "C" fn(context: *mut c_void);
+
+// IOPowerSources.h
+#[link(name = "IOKit", kind = "framework")]
+extern "C" {
+    pub fn IOPSCopyPowerSourcesInfo() -> CFTypeRef;
+
+    pub fn IOPSCopyPowerSourcesList(blob: CFTypeRef) -> CFArrayRef;
+
+    pub fn IOPSGetPowerSourceDescription(blob: CFTypeRef, ps: CFTypeRef) -> CFDictionaryRef;
+
+    pub fn IOPSGetProvidingPowerSourceType(snapshot: CFTypeRef) -> CFStringRef;
+
+    pub fn IOPSNotificationCreateRunLoopSource(
+        callback: IOPowerSourceCallbackType,
+        context: *mut c_void,
+    ) -> CFRunLoopSourceRef;
+}
+
 // CGWindowLevel.h
 //
 // Note: There are two different things at play in this header:
@@ -0,0 +1,130 @@
+//! Capture of the keyboard shortcuts of the system with an event tap.
+//!
+//! The tap sees the key events before the system handles its shortcuts, like
+//! <kbd>Cmd</kbd>+<kbd>Tab</kbd>. While the window is key, the key events with <kbd>Cmd</kbd> or
+//! <kbd>Ctrl</kbd> are sent to the application directly, and hidden from the system.
+
+use std::cell::Cell;
+use std::ffi::c_void;
+use std::ptr;
+
+use core_foundation::base::CFRelease;
+use core_foundation::runloop::{
+    kCFRunLoopCommonModes, CFRunLoopAddSource, CFRunLoopGetMain, CFRunLoopRemoveSource,
+    CFRunLoopSourceRef,
+};
+use objc2::rc::{Retained, WeakId};
+use objc2::{msg_send_id, ClassType};
+use objc2_app_kit::{NSApplication, NSEvent};
+use objc2_foundation::MainThreadMarker;
+
+use super::ffi;
+use super::window::WinitWindow;
+use crate::error::RequestError;
+
+#[derive(Debug)]
+struct TapState {
+    window: WeakId<WinitWindow>,
+    port: Cell<ffi::CFMachPortRef>,
+}
+
+/// An event tap capturing the keyboard shortcuts for a window, removed when dropped.
+#[derive(Debug)]
+pub(crate) struct KeyboardCapture {
+    state: Box<TapState>,
+    source: CFRunLoopSourceRef,
+}
+
+impl KeyboardCapture {
+    /// Install the event tap on the main run loop.
+    ///
+    /// This fails when the application doesn't have the accessibility permission.
+    pub(crate) fn new(window: &WinitWindow) -> Result<Self, RequestError> {
+        let state = Box::new(TapState {
+            window: WeakId::new(&window.retain()),
+            port: Cell::new(ptr::null_mut()),
+        });
+
+        let mask = (1 << ffi::kCGEventKeyDown) | (1 << ffi::kCGEventKeyUp);
+        let port = unsafe {
+            ffi::CGEventTapCreate(
+                ffi::kCGSessionEventTap,
+                ffi::kCGHeadInsertEventTap,
+                ffi::kCGEventTapOptionDefault,
+                mask,
+                tap_callback,
+                &*state as *const TapState as *mut c_void,
+            )
+        };
+        if port.is_null() {
+            return Err(os_error!(
+                "failed to create the event tap, the accessibility permission may be missing"
+            )
+            .into());
+        }
+        state.port.set(port);
+
+        let source = unsafe { ffi::CFMachPortCreateRunLoopSource(ptr::null(), port, 0) };
+        if source.is_null() {
+            unsafe {
+                ffi::CFMachPortInvalidate(port);
+                CFRelease(port);
+            }
+            return Err(os_error!("failed to create the run loop source of the event tap").into());
+        }
+        unsafe {
+            CFRunLoopAddSource(CFRunLoopGetMain(), source, kCFRunLoopCommonModes);
+            ffi::CGEventTapEnable(port, true);
+        }
+
+        Ok(Self { state, source })
+    }
+}
+
+impl Drop for KeyboardCapture {
+    fn drop(&mut self) {
+        let port = self.state.port.get();
+        unsafe {
+            ffi::CGEventTapEnable(port, false);
+            CFRunLoopRemoveSource(CFRunLoopGetMain(), self.source, kCFRunLoopCommonModes);
+            ffi::CFMachPortInvalidate(port);
+            CFRelease(self.source as _);
+            CFRelease(port);
+        }
+    }
+}
+
+unsafe extern "C" fn tap_callback(
+    _proxy: ffi::CGEventTapProxy,
+    kind: ffi::CGEventType,
+    event: ffi::CGEventRef,
+    user_info: *mut c_void,
+) -> ffi::CGEventRef {
+    let state = unsafe { &*(user_info as *const TapState) };
+
+    // The system disables the taps which take too long.
+    if kind == ffi::kCGEventTapDisabledByTimeout || kind == ffi::kCGEventTapDisabledByUserInput {
+        unsafe { ffi::CGEventTapEnable(state.port.get(), true) };
+        return event;
+    }
+
+    let Some(mtm) = MainThreadMarker::new() else { return event };
+    let Some(window) = state.window.load() else { return event };
+    let flags = unsafe { ffi::CGEventGetFlags(event) };
+    if !window.isKeyWindow()
+        || flags & (ffi::kCGEventFlagMaskCommand | ffi::kCGEventFlagMaskControl) == 0
+    {
+        return event;
+    }
+
+    let ns_event: Option<Retained<NSEvent>> =
+        unsafe { msg_send_id![NSEvent::class(), eventWithCGEvent: event] };
+    match ns_event {
+        Some(ns_event) => {
+            unsafe { NSApplication::sharedApplication(mtm).sendEvent(&ns_event) };
+            // Returning no event hides it from the system.
+            ptr::null_mut()
+        },
+        None => event,
+    }
+}
@@ -1,8 +1,11 @@
 use objc2::rc::Retained;
-use objc2::runtime::Sel;
+use objc2::runtime::{AnyObject, Sel};
 use objc2::sel;
 use objc2_app_kit::{NSApplication, NSEventModifierFlags, NSMenu, NSMenuItem};
-use objc2_foundation::{ns_string, MainThreadMarker, NSProcessInfo, NSString};
+use objc2_foundation::{ns_string, MainThreadMarker, NSInteger, NSProcessInfo, NSString};
+
+use crate::keyboard::ModifiersState;
+use crate::platform::macos::{Menu, MenuDescription, MenuItem, StandardAction};
 
 struct KeyEquivalent<'a> {
     key: &'a NSString,
@@ -10,6 +13,10 @@ struct KeyEquivalent<'a> {
 }
 
 pub fn initialize(app: &NSApplication) {
+    set_main_menu(app, &MenuDescription::default());
+}
+
+pub fn set_main_menu(app: &NSApplication, description: &MenuDescription) {
     let mtm = MainThreadMarker::from(app);
     let menubar = NSMenu::new(mtm);
     let app_menu_item = NSMenuItem::new(mtm);
@@ -73,6 +80,9 @@ pub fn initialize(app: &NSApplication) {
     );
 
     app_menu.addItem(&about_item);
+    for item in &description.app_items {
+        app_menu.addItem(&build_item(app, item));
+    }
     app_menu.addItem(&sep_first);
     app_menu.addItem(&services_item);
     app_menu.addItem(&hide_item);
@@ -82,10 +92,115 @@ pub fn initialize(app: &NSApplication) {
     app_menu.addItem(&quit_item);
     app_menu_item.setSubmenu(Some(&app_menu));
 
+    for menu in &description.menus {
+        let item = menu_item(mtm, &NSString::from_str(&menu.title), None, None);
+        item.setSubmenu(Some(&build_menu(app, menu)));
+        menubar.addItem(&item);
+    }
+
     unsafe { app.setServicesMenu(Some(&services_menu)) };
     app.setMainMenu(Some(&menubar));
 }
 
+pub(super) fn build_menu(app: &NSApplication, menu: &Menu) -> Retained<NSMenu> {
+    let mtm = MainThreadMarker::from(app);
+    let ns_menu = unsafe { NSMenu::initWithTitle(mtm.alloc(), &NSString::from_str(&menu.title)) };
+    for item in &menu.items {
+        ns_menu.addItem(&build_item(app, item));
+    }
+    if menu.is_window_menu {
+        // The system adds the windows to the menu.
+        unsafe { app.setWindowsMenu(Some(&ns_menu)) };
+    }
+    ns_menu
+}
+
+fn build_item(app: &NSApplication, item: &MenuItem) -> Retained<NSMenuItem> {
+    let mtm = MainThreadMarker::from(app);
+    match item {
+        MenuItem::Custom(custom) => {
+            let item = menu_item(
+                mtm,
+                &NSString::from_str(&custom.title),
+                Some(sel!(winitMenuItemActivated:)),
+                Some(KeyEquivalent {
+                    key: &NSString::from_str(&custom.key_equivalent),
+                    masks: Some(modifier_masks(custom.modifiers)),
+                }),
+            );
+            // Handled by `WinitApplication`, which reads the identifier from the tag.
+            let target: &AnyObject = app;
+            unsafe { item.setTarget(Some(target)) };
+            unsafe { item.setTag(custom.id as NSInteger) };
+            item
+        },
+        MenuItem::Standard(action) => {
+            let (title, selector, key, masks) = standard_item(*action);
+            menu_item(mtm, title, Some(selector), Some(KeyEquivalent { key, masks }))
+        },
+        MenuItem::Separator => NSMenuItem::separatorItem(mtm),
+        MenuItem::Submenu(menu) => {
+            let item = menu_item(mtm, &NSString::from_str(&menu.title), None, None);
+            item.setSubmenu(Some(&build_menu(app, menu)));
+            item
+        },
+    }
+}
+
+/// The title, action and keyboard shortcut of the standard item.
+fn standard_item(
+    action: StandardAction,
+) -> (&'static NSString, Sel, &'static NSString, Option<NSEventModifierFlags>) {
+    let command_shift = NSEventModifierFlags::NSEventModifierFlagCommand
+        | NSEventModifierFlags::NSEventModifierFlagShift;
+    let command_control = NSEventModifierFlags::NSEventModifierFlagCommand
+        | NSEventModifierFlags::NSEventModifierFlagControl;
+    match action {
+        StandardAction::Undo => (ns_string!("Undo"), sel!(undo:), ns_string!("z"), None),
+        StandardAction::Redo => {
+            (ns_string!("Redo"), sel!(redo:), ns_string!("z"), Some(command_shift))
+        },
+        StandardAction::Cut => (ns_string!("Cut"), sel!(cut:), ns_string!("x"), None),
+        StandardAction::Copy => (ns_string!("Copy"), sel!(copy:), ns_string!("c"), None),
+        StandardAction::Paste => (ns_string!("Paste"), sel!(paste:), ns_string!("v"), None),
+        StandardAction::Delete => (ns_string!("Delete"), sel!(delete:), ns_string!(""), None),
+        StandardAction::SelectAll => {
+            (ns_string!("Select All"), sel!(selectAll:), ns_string!("a"), None)
+        },
+        StandardAction::Minimize => {
+            (ns_string!("Minimize"), sel!(performMiniaturize:), ns_string!("m"), None)
+        },
+        StandardAction::Zoom => (ns_string!("Zoom"), sel!(performZoom:), ns_string!(""), None),
+        StandardAction::Close => (ns_string!("Close"), sel!(performClose:), ns_string!("w"), None),
+        StandardAction::ToggleFullScreen => (
+            ns_string!("Enter Full Screen"),
+            sel!(toggleFullScreen:),
+            ns_string!("f"),
+            Some(command_control),
+        ),
+        StandardAction::BringAllToFront => {
+            (ns_string!("Bring All to Front"), sel!(arrangeInFront:), ns_string!(""), None)
+        },
+    }
+}
+
+fn modifier_masks(modifiers: ModifiersState) -> NSEventModifierFlags {
+    let mut masks = NSEventModifierFlags::empty();
+    if modifiers.super_key() {
+        masks |= NSEventModifierFlags::NSEventModifierFlagCommand;
+    }
+    if modifiers.shift_key() {
+        masks |= NSEventModifierFlags::NSEventModifierFlagShift;
+    }
+    if modifiers.alt_key() {
+        masks |= NSEventModifierFlags::NSEventModifierFlagOption;
+    }
+    if modifiers.control_key() {
+        masks |= NSEventModifierFlags::NSEventModifierFlagControl;
+    }
+    masks
+}
+
 fn menu_item(
     mtm: MainThreadMarker,
     title: &NSString,
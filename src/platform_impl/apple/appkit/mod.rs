@@ -1,19 +1,32 @@
 #[macro_use]
 mod util;
 
+#[cfg(feature = "accesskit")]
+mod accessibility;
 mod app;
+mod app_delegate;
 mod app_state;
+mod clipboard;
 mod cursor;
+#[cfg(feature = "dialogs")]
+mod dialogs;
+mod dock;
 mod event;
 mod event_loop;
 mod ffi;
+mod keyboard_capture;
 mod menu;
 mod monitor;
 mod observer;
+mod power;
+mod services;
+mod touch_bar;
+mod user_activity;
 mod view;
 mod window;
 mod window_delegate;
 
+pub(crate) use self::app::{restore_after_panic, show_panic_dialog};
 pub(crate) use self::cursor::CustomCursor as PlatformCustomCursor;
 pub(crate) use self::event::{physicalkey_to_scancode, scancode_to_physicalkey, KeyEventExtra};
 pub(crate) use self::event_loop::{
@@ -1,12 +1,17 @@
 #![allow(clippy::unnecessary_cast)]
 
 use std::collections::VecDeque;
-use std::fmt;
+use std::ffi::c_void;
 use std::num::{NonZeroU16, NonZeroU32};
+use std::sync::OnceLock;
+use std::{fmt, mem};
 
 use core_foundation::array::{CFArrayGetCount, CFArrayGetValueAtIndex};
 use core_foundation::base::{CFRelease, TCFType};
+use core_foundation::bundle::CFBundle;
 use core_foundation::string::CFString;
+use core_foundation::url::CFURL;
+use core_graphics::base::CGError;
 use core_graphics::display::{
     CGDirectDisplayID, CGDisplay, CGDisplayBounds, CGDisplayCopyDisplayMode,
 };
@@ -17,6 +22,8 @@ use objc2_foundation::{ns_string, run_on_main, MainThreadMarker, NSNumber, NSPoi
 
 use super::ffi;
 use crate::dpi::{LogicalPosition, PhysicalPosition, PhysicalSize};
+use crate::error::{NotSupportedError, RequestError};
+use crate::monitor::{pnp_id, ColorCapabilities, MonitorIdentity, Orientation};
 
 #[derive(Clone)]
 pub struct VideoModeHandle {
@@ -240,6 +247,13 @@ impl MonitorHandle {
         refresh_rate_millihertz(self.0, &current_display_mode)
     }
 
+    pub fn physical_size_mm(&self) -> Option<(u32, u32)> {
+        // Zero when the display is invalid or doesn't report its size.
+        let size = CGDisplay::new(self.0).screen_size();
+        (size.width > 0.0 && size.height > 0.0)
+            .then(|| (size.width.round() as u32, size.height.round() as u32))
+    }
+
     pub fn current_video_mode(&self) -> Option<VideoModeHandle> {
         let mode = NativeDisplayMode(unsafe { CGDisplayCopyDisplayMode(self.0) } as _);
         let refresh_rate_millihertz = refresh_rate_millihertz(self.0, &mode);
@@ -267,12 +281,12 @@ impl MonitorHandle {
             };
 
             modes.into_iter().map(move |mode| {
-                let cg_refresh_rate_hertz = ffi::CGDisplayModeGetRefreshRate(mode).round() as i64;
+                let cg_refresh_rate_hertz = ffi::CGDisplayModeGetRefreshRate(mode);
 
                 // CGDisplayModeGetRefreshRate returns 0.0 for any display that
                 // isn't a CRT
-                let refresh_rate_millihertz = if cg_refresh_rate_hertz > 0 {
-                    NonZeroU32::new((cg_refresh_rate_hertz * 1000) as u32)
+                let refresh_rate_millihertz = if cg_refresh_rate_hertz > 0.0 {
+                    NonZeroU32::new((cg_refresh_rate_hertz * 1000.0).round() as u32)
                 } else {
                     refresh_rate_millihertz
                 };
@@ -296,6 +310,127 @@ impl MonitorHandle {
             uuid == other_uuid
         })
     }
+
+    pub fn identity(&self) -> Option<MonitorIdentity> {
+        // The numbers come from the EDID, unknown vendors don't fit in a PNP ID.
+        let vendor = unsafe { ffi::CGDisplayVendorNumber(self.0) };
+        let product_code = unsafe { ffi::CGDisplayModelNumber(self.0) };
+        let serial = unsafe { ffi::CGDisplaySerialNumber(self.0) };
+        Some(MonitorIdentity {
+            manufacturer: pnp_id(u16::try_from(vendor).ok()?)?,
+            product_code: u16::try_from(product_code).ok(),
+            model: None,
+            serial: (serial != 0).then(|| serial.to_string()),
+        })
+    }
+
+    pub fn orientation(&self) -> Orientation {
+        Orientation::from_degrees(unsafe { ffi::CGDisplayRotation(self.0) })
+    }
+
+    pub fn color_capabilities(&self) -> Result<ColorCapabilities, RequestError> {
+        run_on_main(|mtm| {
+            let screen = self
+                .ns_screen(mtm)
+                .ok_or_else(|| os_error!("the screen of the monitor was not found"))?;
+            // The extended dynamic range is expressed relative to SDR white.
+            let potential =
+                unsafe { screen.maximumPotentialExtendedDynamicRangeColorComponentValue() };
+            let current = unsafe { screen.maximumExtendedDynamicRangeColorComponentValue() };
+            Ok(ColorCapabilities {
+                hdr: potential > 1.0,
+                headroom: Some(current.max(1.0) as f32),
+                primaries: None,
+                white_point: None,
+                min_luminance: None,
+                max_luminance: None,
+                max_full_frame_luminance: None,
+                sdr_white_luminance: None,
+            })
+        })
+    }
+
+    pub fn icc_profile(&self) -> Option<Vec<u8>> {
+        run_on_main(|mtm| {
+            let color_space = unsafe { self.ns_screen(mtm)?.colorSpace() }?;
+            let data = unsafe { color_space.ICCProfileData() }?;
+            Some(data.bytes().to_vec())
+        })
+    }
+
+    pub fn brightness(&self) -> Result<f64, RequestError> {
+        let display_services = self.display_services()?;
+        let mut brightness = 0.0;
+        let status = unsafe { (display_services.get_brightness)(self.0, &mut brightness) };
+        if status != 0 {
+            return Err(os_error!(format!("DisplayServicesGetBrightness failed: {status}")).into());
+        }
+        Ok(brightness as f64)
+    }
+
+    pub fn set_brightness(&self, brightness: f64) -> Result<(), RequestError> {
+        let display_services = self.display_services()?;
+        let brightness = brightness.clamp(0.0, 1.0) as f32;
+        let status = unsafe { (display_services.set_brightness)(self.0, brightness) };
+        if status != 0 {
+            return Err(os_error!(format!("DisplayServicesSetBrightness failed: {status}")).into());
+        }
+        Ok(())
+    }
+
+    fn display_services(&self) -> Result<&'static DisplayServices, NotSupportedError> {
+        DisplayServices::get()
+            .filter(|display_services| unsafe { (display_services.can_change_brightness)(self.0) })
+            .ok_or_else(|| {
+                NotSupportedError::new("brightness is only supported for built-in panels")
+            })
+    }
+}
+
+/// The functions of the private `DisplayServices` framework, which controls the brightness of
+/// built-in panels.
+struct DisplayServices {
+    can_change_brightness: CanChangeBrightness,
+    get_brightness: GetBrightness,
+    set_brightness: SetBrightness,
+}
+
+type CanChangeBrightness = unsafe extern "C" fn(CGDirectDisplayID) -> bool;
+type GetBrightness = unsafe extern "C" fn(CGDirectDisplayID, *mut f32) -> i32;
+type SetBrightness = unsafe extern "C" fn(CGDirectDisplayID, f32) -> i32;
+
+impl DisplayServices {
+    fn get() -> Option<&'static Self> {
+        static DISPLAY_SERVICES: OnceLock<Option<DisplayServices>> = OnceLock::new();
+        DISPLAY_SERVICES.get_or_init(Self::load).as_ref()
+    }
+
+    fn load() -> Option<Self> {
+        let url =
+            CFURL::from_path("/System/Library/PrivateFrameworks/DisplayServices.framework", true)?;
+        let bundle = CFBundle::new(url)?;
+        let function = |name| {
+            let pointer = bundle.function_pointer_for_name(CFString::from_static_string(name));
+            (!pointer.is_null()).then_some(pointer)
+        };
+
+        let display_services = unsafe {
+            Self {
+                can_change_brightness: mem::transmute::<*const c_void, CanChangeBrightness>(
+                    function("DisplayServicesCanChangeBrightness")?,
+                ),
+                get_brightness: mem::transmute::<*const c_void, GetBrightness>(function(
+                    "DisplayServicesGetBrightness",
+                )?),
+                set_brightness: mem::transmute::<*const c_void, SetBrightness>(function(
+                    "DisplayServicesSetBrightness",
+                )?),
+            }
+        };
+        // Keep the framework loaded for as long as the functions are used.
+        mem::forget(bundle);
+        Some(display_services)
+    }
 }
 
 pub(crate) fn get_display_id(screen: &NSScreen) -> u32 {
@@ -360,9 +495,55 @@ fn refresh_rate_millihertz(id: CGDirectDisplayID, mode: &NativeDisplayMode) -> O
             return None;
         }
 
-        (time.time_scale as i64)
-            .checked_div(time.time_value)
-            .map(|v| (v * 1000) as u32)
-            .and_then(NonZeroU32::new)
+        // The period is `time_value / time_scale` seconds, keep the fraction of the rate.
+        (time.time_scale as i64 * 1000)
+            .checked_add(time.time_value / 2)
+            .and_then(|scaled| scaled.checked_div(time.time_value))
+            .and_then(|v| NonZeroU32::new(v as u32))
+    }
+}
+
+/// Get the gamma tables of the display, for the red, green and blue channels.
+pub(crate) fn display_transfer(display: CGDirectDisplayID) -> Result<[Vec<f32>; 3], CGError> {
+    let capacity = unsafe { ffi::CGDisplayGammaTableCapacity(display) };
+    let mut tables = [(); 3].map(|_| vec![0.0; capacity as usize]);
+    let [red, green, blue] = &mut tables;
+    let mut count = 0;
+    let status = unsafe {
+        ffi::CGGetDisplayTransferByTable(
+            display,
+            capacity,
+            red.as_mut_ptr(),
+            green.as_mut_ptr(),
+            blue.as_mut_ptr(),
+            &mut count,
+        )
+    };
+    if status != ffi::kCGErrorSuccess {
+        return Err(status);
+    }
+    for table in &mut tables {
+        table.truncate(count as usize);
+    }
+    Ok(tables)
+}
+
+/// Set the gamma tables of the display, for the red, green and blue channels.
+pub(crate) fn set_display_transfer(
+    display: CGDirectDisplayID,
+    [red, green, blue]: &[Vec<f32>; 3],
+) -> Result<(), CGError> {
+    let status = unsafe {
+        ffi::CGSetDisplayTransferByTable(
+            display,
+            red.len() as u32,
+            red.as_ptr(),
+            green.as_ptr(),
+            blue.as_ptr(),
+        )
+    };
+    if status != ffi::kCGErrorSuccess {
+        return Err(status);
     }
+    Ok(())
 }
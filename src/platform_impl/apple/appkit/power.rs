@@ -0,0 +1,117 @@
+use std::ffi::c_void;
+use std::ptr;
+
+use core_foundation::array::CFArray;
+use core_foundation::base::{CFType, TCFType};
+use core_foundation::boolean::CFBoolean;
+use core_foundation::dictionary::CFDictionary;
+use core_foundation::number::CFNumber;
+use core_foundation::runloop::{
+    kCFRunLoopCommonModes, CFRunLoop, CFRunLoopSource, CFRunLoopSourceInvalidate,
+};
+use core_foundation::string::CFString;
+use objc2_foundation::MainThreadMarker;
+
+use super::app_state::AppState;
+use super::ffi;
+use crate::event::{PowerEvent, PowerSource, PowerState};
+
+// See `IOPSKeys.h`.
+const PROVIDING_AC_POWER: &str = "AC Power";
+const TYPE_KEY: &str = "Type";
+const INTERNAL_BATTERY_TYPE: &str = "InternalBattery";
+const CURRENT_CAPACITY_KEY: &str = "Current Capacity";
+const MAX_CAPACITY_KEY: &str = "Max Capacity";
+const IS_CHARGING_KEY: &str = "Is Charging";
+
+pub fn power_state() -> Option<PowerState> {
+    let blob = unsafe { ffi::IOPSCopyPowerSourcesInfo() };
+    if blob.is_null() {
+        return None;
+    }
+    let blob = unsafe { CFType::wrap_under_create_rule(blob) };
+
+    let providing = unsafe { ffi::IOPSGetProvidingPowerSourceType(blob.as_CFTypeRef()) };
+    if providing.is_null() {
+        return None;
+    }
+    let providing = unsafe { CFString::wrap_under_get_rule(providing) };
+    let source = if providing == PROVIDING_AC_POWER {
+        PowerSource::External
+    } else {
+        PowerSource::Battery
+    };
+
+    let mut charging = false;
+    let mut battery_level = None;
+
+    let list = unsafe { ffi::IOPSCopyPowerSourcesList(blob.as_CFTypeRef()) };
+    if !list.is_null() {
+        let list = unsafe { CFArray::<CFType>::wrap_under_create_rule(list) };
+        for power_source in list.iter() {
+            let description = unsafe {
+                ffi::IOPSGetPowerSourceDescription(blob.as_CFTypeRef(), power_source.as_CFTypeRef())
+            };
+            if description.is_null() {
+                continue;
+            }
+            let description =
+                unsafe { CFDictionary::<CFString, CFType>::wrap_under_get_rule(description) };
+            let value = |key: &'static str| {
+                description.find(CFString::from_static_string(key)).map(|value| (*value).clone())
+            };
+
+            let is_internal_battery = value(TYPE_KEY)
+                .and_then(|kind| kind.downcast::<CFString>())
+                .is_some_and(|kind| kind == INTERNAL_BATTERY_TYPE);
+            if !is_internal_battery {
+                continue;
+            }
+
+            let number = |key| value(key).and_then(|n| n.downcast::<CFNumber>()?.to_i64());
+            if let (Some(current), Some(max)) =
+                (number(CURRENT_CAPACITY_KEY), number(MAX_CAPACITY_KEY))
+            {
+                if max > 0 {
+                    battery_level = Some((current * 100 / max).clamp(0, 100) as u8);
+                }
+            }
+            charging = value(IS_CHARGING_KEY)
+                .and_then(|is_charging| is_charging.downcast::<CFBoolean>())
+                .is_some_and(bool::from);
+            break;
+        }
+    }
+
+    Some(PowerState { source, charging, battery_level })
+}
+
+extern "C" fn power_sources_changed(_context: *mut c_void) {
+    let mtm = MainThreadMarker::new().expect("power source notification on the main thread");
+    if let Some(state) = power_state() {
+        AppState::get(mtm).power_event(PowerEvent::PowerStateChanged(state));
+    }
+}
+
+/// Reports the changes of the power sources to the application until dropped.
+pub struct PowerSourceObserver(CFRunLoopSource);
+
+impl PowerSourceObserver {
+    pub fn new(_mtm: MainThreadMarker) -> Option<Self> {
+        let source = unsafe {
+            ffi::IOPSNotificationCreateRunLoopSource(power_sources_changed, ptr::null_mut())
+        };
+        if source.is_null() {
+            return None;
+        }
+        let source = unsafe { CFRunLoopSource::wrap_under_create_rule(source) };
+        CFRunLoop::get_main().add_source(&source, unsafe { kCFRunLoopCommonModes });
+        Some(Self(source))
+    }
+}
+
+impl Drop for PowerSourceObserver {
+    fn drop(&mut self) {
+        unsafe { CFRunLoopSourceInvalidate(self.0.as_concrete_TypeRef()) };
+    }
+}
@@ -0,0 +1,56 @@
+//! The provider of the Services of the application, declared with `NSServices` in its bundle.
+
+use objc2::declare::ClassBuilder;
+use objc2::rc::Retained;
+use objc2::runtime::{AnyObject, Sel};
+use objc2::{msg_send_id, ClassType};
+use objc2_app_kit::{NSApplication, NSPasteboard, NSPasteboardTypeString};
+use objc2_foundation::{MainThreadMarker, NSObject, NSString};
+use tracing::warn;
+
+use super::app_state::AppState;
+
+/// The parameters following the name of the method of a service, its `NSMessage`.
+const SELECTOR_SUFFIX: &str = ":userData:error:";
+
+/// Set a provider with a method for each of the services as the services provider of the
+/// application.
+pub(super) fn set_services_provider(app: &NSApplication, services: &[String]) {
+    if services.is_empty() {
+        return;
+    }
+
+    let Some(mut builder) = ClassBuilder::new("WinitServicesProvider", NSObject::class()) else {
+        warn!("the services provider was already declared");
+        return;
+    };
+    for service in services {
+        let selector = Sel::register(&format!("{service}{SELECTOR_SUFFIX}"));
+        let method: extern "C" fn(_, _, _, _, _) = perform_service;
+        unsafe { builder.add_method(selector, method) };
+    }
+    let class = builder.register();
+
+    let provider: Retained<AnyObject> = unsafe { msg_send_id![class, new] };
+    unsafe { app.setServicesProvider(Some(&provider)) };
+}
+
+extern "C" fn perform_service(
+    _this: &AnyObject,
+    selector: Sel,
+    pasteboard: &NSPasteboard,
+    _user_data: &NSString,
+    _error: *mut *mut NSString,
+) {
+    let name = selector.name().strip_suffix(SELECTOR_SUFFIX).unwrap_or(selector.name()).to_owned();
+    let text =
+        unsafe { pasteboard.stringForType(NSPasteboardTypeString) }.map(|text| text.to_string());
+
+    let app_state =
+        AppState::get(MainThreadMarker::new().expect("services are performed on the main thread"));
+    app_state.maybe_queue_with_handler(move |app, event_loop| {
+        if let Some(handler) = app.macos_handler() {
+            handler.service_invoked(event_loop, name, text);
+        }
+    });
+}
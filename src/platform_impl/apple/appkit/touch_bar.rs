@@ -0,0 +1,105 @@
+//! The Touch Bar of the windows, whose items send their actions to the view.
+
+use objc2::rc::Retained;
+use objc2::runtime::{AnyObject, ProtocolObject};
+use objc2::sel;
+use objc2_app_kit::{
+    NSButton, NSCandidateListTouchBarItem, NSCustomTouchBarItem, NSSliderTouchBarItem, NSTouchBar,
+    NSTouchBarItem,
+};
+use objc2_foundation::{MainThreadMarker, NSArray, NSInteger, NSRange, NSSet, NSString};
+
+use super::view::WinitView;
+use crate::platform::macos::{TouchBarDescription, TouchBarItem};
+
+/// The prefix of the identifiers of the items, followed by the identifier given by the user.
+const IDENTIFIER_PREFIX: &str = "rs.winit.touch-bar.";
+
+pub(super) fn create_touch_bar(
+    view: &WinitView,
+    description: &TouchBarDescription,
+) -> Option<Retained<NSTouchBar>> {
+    if description.items.is_empty() {
+        return None;
+    }
+
+    let mtm = MainThreadMarker::from(view);
+    let mut identifiers = Vec::with_capacity(description.items.len());
+    let mut items = Vec::with_capacity(description.items.len());
+    for item in &description.items {
+        let identifier = NSString::from_str(&format!("{IDENTIFIER_PREFIX}{}", item.id()));
+        items.push(create_item(view, &identifier, item));
+        identifiers.push(identifier);
+    }
+
+    let touch_bar = unsafe { NSTouchBar::new(mtm) };
+    unsafe {
+        touch_bar.setDefaultItemIdentifiers(&NSArray::from_vec(identifiers));
+        // `NSSet::from_vec` requires a stable hash, which main thread only classes don't promise.
+        touch_bar.setTemplateItems(&NSSet::setWithArray(&NSArray::from_vec(items)));
+    }
+    Some(touch_bar)
+}
+
+/// The identifier given by the user of the item.
+pub(super) fn item_id(item: &NSTouchBarItem) -> Option<u32> {
+    let identifier = unsafe { item.identifier() }.to_string();
+    identifier.strip_prefix(IDENTIFIER_PREFIX)?.parse().ok()
+}
+
+fn create_item(
+    view: &WinitView,
+    identifier: &NSString,
+    item: &TouchBarItem,
+) -> Retained<NSTouchBarItem> {
+    let mtm = MainThreadMarker::from(view);
+    let target: &AnyObject = view;
+    match item {
+        TouchBarItem::Button { id, title } => {
+            let button = unsafe {
+                NSButton::buttonWithTitle_target_action(
+                    &NSString::from_str(title),
+                    Some(target),
+                    Some(sel!(winitTouchBarButtonPressed:)),
+                    mtm,
+                )
+            };
+            // The action reads the identifier from the tag of the button.
+            unsafe { button.setTag(*id as NSInteger) };
+            let item = unsafe { NSCustomTouchBarItem::initWithIdentifier(mtm.alloc(), identifier) };
+            unsafe { item.setView(&button) };
+            Retained::into_super(item)
+        },
+        TouchBarItem::Slider { label, range, value, .. } => {
+            let item = unsafe { NSSliderTouchBarItem::initWithIdentifier(mtm.alloc(), identifier) };
+            unsafe {
+                let slider = item.slider();
+                slider.setMinValue(*range.start());
+                slider.setMaxValue(*range.end());
+                item.setDoubleValue(value.clamp(*range.start(), *range.end()));
+                if !label.is_empty() {
+                    item.setLabel(Some(&NSString::from_str(label)));
+                }
+                item.setTarget(Some(target));
+                item.setAction(Some(sel!(winitTouchBarSliderChanged:)));
+            }
+            Retained::into_super(item)
+        },
+        TouchBarItem::Candidates { candidates, .. } => {
+            let item: Retained<NSCandidateListTouchBarItem<NSString>> =
+                unsafe { NSCandidateListTouchBarItem::initWithIdentifier(mtm.alloc(), identifier) };
+            let candidates: Vec<_> =
+                candidates.iter().map(|candidate| NSString::from_str(candidate)).collect();
+            unsafe {
+                item.setDelegate(Some(ProtocolObject::from_ref(view)));
+                item.setAllowsCollapsing(false);
+                item.setCandidates_forSelectedRange_inString(
+                    &NSArray::from_vec(candidates),
+                    NSRange::new(0, 0),
+                    None,
+                );
+            }
+            Retained::into_super(item)
+        },
+    }
+}
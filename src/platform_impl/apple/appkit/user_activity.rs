@@ -0,0 +1,71 @@
+//! The conversions of the user activities advertised and continued with Handoff.
+
+use std::collections::HashMap;
+
+use objc2::rc::Retained;
+use objc2::runtime::AnyObject;
+use objc2::{msg_send, ClassType};
+use objc2_foundation::{NSDictionary, NSString, NSUserActivity, NSURL};
+
+use crate::platform::macos::UserActivity;
+
+pub(super) fn create_ns_user_activity(activity: &UserActivity) -> Retained<NSUserActivity> {
+    let ns_activity = unsafe {
+        NSUserActivity::initWithActivityType(
+            NSUserActivity::alloc(),
+            &NSString::from_str(&activity.activity_type),
+        )
+    };
+    let title = activity.title.as_deref().map(NSString::from_str);
+    let keys: Vec<_> = activity.user_info.keys().map(|key| NSString::from_str(key)).collect();
+    let keys: Vec<&NSString> = keys.iter().map(|key| &**key).collect();
+    let values: Vec<_> =
+        activity.user_info.values().map(|value| NSString::from_str(value)).collect();
+    let user_info = NSDictionary::from_vec(&keys, values);
+    let webpage_url = activity
+        .webpage_url
+        .as_deref()
+        .and_then(|url| unsafe { NSURL::URLWithString(&NSString::from_str(url)) });
+    unsafe {
+        ns_activity.setTitle(title.as_deref());
+        // SAFETY: The dictionary of strings is a valid `userInfo`.
+        ns_activity.setUserInfo(Some(&Retained::cast::<NSDictionary>(user_info)));
+        ns_activity.setWebpageURL(webpage_url.as_deref());
+        ns_activity.setEligibleForHandoff(true);
+        // For the activity to be suggested by the system, like by Siri.
+        ns_activity.setEligibleForSearch(true);
+    }
+    ns_activity
+}
+
+pub(super) fn from_ns_user_activity(ns_activity: &NSUserActivity) -> UserActivity {
+    let mut user_info = HashMap::new();
+    if let Some(ns_user_info) = unsafe { ns_activity.userInfo() } {
+        let (keys, values) = ns_user_info.to_vecs();
+        // Only the strings are kept, the other applications may set any property list.
+        for (key, value) in keys.into_iter().zip(values) {
+            if let (Some(key), Some(value)) = (as_string(key), as_string(value)) {
+                user_info.insert(key, value);
+            }
+        }
+    }
+
+    UserActivity {
+        activity_type: unsafe { ns_activity.activityType() }.to_string(),
+        title: unsafe { ns_activity.title() }.map(|title| title.to_string()),
+        user_info,
+        webpage_url: unsafe { ns_activity.webpageURL() }
+            .and_then(|url| unsafe { url.absoluteString() })
+            .map(|url| url.to_string()),
+    }
+}
+
+fn as_string(object: &AnyObject) -> Option<String> {
+    let is_string: bool = unsafe { msg_send![object, isKindOfClass: NSString::class()] };
+    if !is_string {
+        return None;
+    }
+    // SAFETY: The object is an `NSString`.
+    let string = unsafe { &*(object as *const AnyObject as *const NSString) };
+    Some(string.to_string())
+}
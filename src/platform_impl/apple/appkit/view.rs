@@ -4,33 +4,42 @@ use std::collections::{HashMap, VecDeque};
 use std::ptr;
 use std::rc::Rc;
 
+#[cfg(feature = "accesskit")]
+use objc2::msg_send;
 use objc2::rc::{Retained, WeakId};
 use objc2::runtime::{AnyObject, Sel};
 use objc2::{declare_class, msg_send_id, mutability, sel, ClassType, DeclaredClass};
 use objc2_app_kit::{
-    NSApplication, NSCursor, NSEvent, NSEventPhase, NSResponder, NSTextInputClient,
+    NSAllRomanInputSourcesLocaleIdentifier, NSApplication, NSButton, NSCandidateListTouchBarItem,
+    NSCandidateListTouchBarItemDelegate, NSCursor, NSEvent, NSEventButtonMask, NSEventPhase,
+    NSEventSubtype, NSPointingDeviceType, NSResponder, NSSliderTouchBarItem, NSTextInputClient,
     NSTrackingRectTag, NSView, NSViewFrameDidChangeNotification,
 };
 use objc2_foundation::{
-    MainThreadMarker, NSArray, NSAttributedString, NSAttributedStringKey, NSCopying,
+    MainThreadMarker, NSArray, NSAttributedString, NSAttributedStringKey, NSCopying, NSInteger,
     NSMutableAttributedString, NSNotFound, NSNotificationCenter, NSObject, NSObjectProtocol,
     NSPoint, NSRange, NSRect, NSSize, NSString, NSUInteger,
 };
 
+#[cfg(feature = "accesskit")]
+use super::accessibility::{self, AccessKit};
 use super::app_state::AppState;
 use super::cursor::{default_cursor, invisible_cursor};
 use super::event::{
     code_to_key, code_to_location, create_key_event, event_mods, lalt_pressed, ralt_pressed,
     scancode_to_physicalkey,
 };
+use super::touch_bar;
 use super::window::WinitWindow;
 use crate::dpi::{LogicalPosition, LogicalSize};
 use crate::event::{
-    DeviceEvent, ElementState, Ime, Modifiers, MouseButton, MouseScrollDelta, PointerKind,
-    PointerSource, TouchPhase, WindowEvent,
+    DeviceEvent, ElementState, Ime, Modifiers, MouseButton, MouseScrollDelta, PenButtons, PenTilt,
+    PointerKind, PointerSource, TouchPhase, WindowEvent,
 };
+use crate::event_loop::ModifiersOrder;
 use crate::keyboard::{Key, KeyCode, KeyLocation, ModifiersState, NamedKey};
-use crate::platform::macos::OptionAsAlt;
+use crate::platform::macos::{OptionAsAlt, TouchBarEvent};
+use crate::window::{Coalescing, ImePurpose};
 
 #[derive(Debug)]
 struct CursorState {
@@ -132,6 +141,8 @@ pub struct ViewState {
     forward_key_to_app: Cell<bool>,
 
     marked_text: RefCell<Retained<NSMutableAttributedString>>,
+    /// The text around the cursor set by the application, with the selection in UTF-16.
+    surrounding_text: RefCell<Option<(Retained<NSString>, NSRange)>>,
     accepts_first_mouse: bool,
 
     // Weak reference because the window keeps a strong reference to the view
@@ -139,6 +150,16 @@ pub struct ViewState {
 
     /// The state of the `Option` as `Alt`.
     option_as_alt: Cell<OptionAsAlt>,
+
+    /// Whether the pen in proximity is used with its eraser end.
+    pen_inverted: Cell<bool>,
+
+    /// How the pointer motion of the window is coalesced.
+    motion_coalescing: Cell<Coalescing>,
+
+    /// The AccessKit adapter, set with `Window::set_accesskit_tree_source`.
+    #[cfg(feature = "accesskit")]
+    accesskit: RefCell<Option<AccessKit>>,
 }
 
 declare_class!(
@@ -240,6 +261,25 @@ declare_class!(
         }
     }
 
+    unsafe impl NSObjectProtocol for WinitView {}
+
+    unsafe impl NSCandidateListTouchBarItemDelegate for WinitView {
+        #[method(candidateListTouchBarItem:endSelectingCandidateAtIndex:)]
+        fn candidate_list_end_selecting(&self, item: &NSCandidateListTouchBarItem, index: NSInteger) {
+            trace_scope!("candidateListTouchBarItem:endSelectingCandidateAtIndex:");
+            // The selection was cancelled.
+            if index == NSNotFound {
+                return;
+            }
+            if let Some(id) = touch_bar::item_id(item) {
+                self.queue_touch_bar_event(TouchBarEvent::CandidateSelected {
+                    id,
+                    index: index as usize,
+                });
+            }
+        }
+    }
+
     unsafe impl NSTextInputClient for WinitView {
         #[method(hasMarkedText)]
         fn has_marked_text(&self) -> bool {
@@ -262,8 +302,11 @@ declare_class!(
         #[method(selectedRange)]
         fn selected_range(&self) -> NSRange {
             trace_scope!("selectedRange");
-            // Documented to return `{NSNotFound, 0}` if there is no selection.
-            NSRange::new(NSNotFound as NSUInteger, 0)
+            match &*self.ivars().surrounding_text.borrow() {
+                Some((_, selection)) if !unsafe { self.hasMarkedText() } => *selection,
+                // Documented to return `{NSNotFound, 0}` if there is no selection.
+                _ => NSRange::new(NSNotFound as NSUInteger, 0),
+            }
         }
 
         #[method(setMarkedText:selectedRange:replacementRange:)]
@@ -302,6 +345,7 @@ declare_class!(
             if self.ivars().ime_state.get() == ImeState::Disabled {
                 *self.ivars().input_source.borrow_mut() = self.current_input_source();
                 self.queue_event(WindowEvent::Ime(Ime::Enabled));
+                self.queue_event(WindowEvent::Ime(Ime::SurroundingTextRequest));
             }
 
             if unsafe { self.hasMarkedText() } {
@@ -354,11 +398,21 @@ declare_class!(
         #[method_id(attributedSubstringForProposedRange:actualRange:)]
         fn attributed_substring_for_proposed_range(
             &self,
-            _range: NSRange,
-            _actual_range: *mut NSRange,
+            range: NSRange,
+            actual_range: *mut NSRange,
         ) -> Option<Retained<NSAttributedString>> {
             trace_scope!("attributedSubstringForProposedRange:actualRange:");
-            None
+            let surrounding_text = self.ivars().surrounding_text.borrow();
+            surrounding_text.as_ref().map(|(text, _)| {
+                let length = text.length();
+                let start = range.location.min(length);
+                let range = NSRange::new(start, range.end().min(length) - start);
+                if !actual_range.is_null() {
+                    unsafe { *actual_range = range };
+                }
+                let substring = unsafe { text.substringWithRange(range) };
+                Retained::into_super(NSMutableAttributedString::from_nsstring(&substring))
+            })
         }
 
         #[method(characterIndexForPoint:)]
@@ -405,6 +459,7 @@ declare_class!(
             if unsafe { self.hasMarkedText() } && self.is_ime_enabled() && !is_control {
                 self.queue_event(WindowEvent::Ime(Ime::Preedit(String::new(), None)));
                 self.queue_event(WindowEvent::Ime(Ime::Commit(string)));
+                self.queue_event(WindowEvent::Ime(Ime::SurroundingTextRequest));
                 self.ivars().ime_state.set(ImeState::Committed);
             }
         }
@@ -574,6 +629,67 @@ declare_class!(
             });
         }
 
+        // The actions of the standard items of the Edit menu, sent to the first responder, and
+        // delivered like the standard key bindings.
+        #[method(undo:)]
+        fn undo(&self, _sender: Option<&AnyObject>) {
+            trace_scope!("undo:");
+            self.standard_action("undo:");
+        }
+
+        #[method(redo:)]
+        fn redo(&self, _sender: Option<&AnyObject>) {
+            trace_scope!("redo:");
+            self.standard_action("redo:");
+        }
+
+        #[method(cut:)]
+        fn cut(&self, _sender: Option<&AnyObject>) {
+            trace_scope!("cut:");
+            self.standard_action("cut:");
+        }
+
+        #[method(copy:)]
+        fn copy(&self, _sender: Option<&AnyObject>) {
+            trace_scope!("copy:");
+            self.standard_action("copy:");
+        }
+
+        #[method(paste:)]
+        fn paste(&self, _sender: Option<&AnyObject>) {
+            trace_scope!("paste:");
+            self.standard_action("paste:");
+        }
+
+        #[method(delete:)]
+        fn delete(&self, _sender: Option<&AnyObject>) {
+            trace_scope!("delete:");
+            self.standard_action("delete:");
+        }
+
+        #[method(selectAll:)]
+        fn select_all(&self, _sender: Option<&AnyObject>) {
+            trace_scope!("selectAll:");
+            self.standard_action("selectAll:");
+        }
+
+        // The actions of the items of the Touch Bar, see `touch_bar.rs`.
+        #[method(winitTouchBarButtonPressed:)]
+        fn touch_bar_button_pressed(&self, sender: &NSButton) {
+            trace_scope!("winitTouchBarButtonPressed:");
+            let id = unsafe { sender.tag() } as u32;
+            self.queue_touch_bar_event(TouchBarEvent::ButtonPressed { id });
+        }
+
+        #[method(winitTouchBarSliderChanged:)]
+        fn touch_bar_slider_changed(&self, sender: &NSSliderTouchBarItem) {
+            trace_scope!("winitTouchBarSliderChanged:");
+            if let Some(id) = touch_bar::item_id(sender) {
+                let value = unsafe { sender.doubleValue() };
+                self.queue_touch_bar_event(TouchBarEvent::SliderChanged { id, value });
+            }
+        }
+
         // In the past (?), `mouseMoved:` events were not generated when the
         // user hovered over a window from a separate window, and as such the
         // application might not know the location of the mouse in the event.
@@ -597,6 +713,7 @@ declare_class!(
             trace_scope!("mouseUp:");
             self.mouse_motion(event);
             self.mouse_click(event, ElementState::Released);
+            self.ivars().app_state.update_drag(true);
         }
 
         #[method(rightMouseDown:)]
@@ -611,6 +728,7 @@ declare_class!(
             trace_scope!("rightMouseUp:");
             self.mouse_motion(event);
             self.mouse_click(event, ElementState::Released);
+            self.ivars().app_state.update_drag(true);
         }
 
         #[method(otherMouseDown:)]
@@ -625,6 +743,7 @@ declare_class!(
             trace_scope!("otherMouseUp:");
             self.mouse_motion(event);
             self.mouse_click(event, ElementState::Released);
+            self.ivars().app_state.update_drag(true);
         }
 
         // No tracing on these because that would be overly verbose
@@ -632,21 +751,39 @@ declare_class!(
         #[method(mouseMoved:)]
         fn mouse_moved(&self, event: &NSEvent) {
             self.mouse_motion(event);
+            self.pen_input(event);
         }
 
         #[method(mouseDragged:)]
         fn mouse_dragged(&self, event: &NSEvent) {
             self.mouse_motion(event);
+            self.pen_input(event);
+            self.ivars().app_state.update_drag(false);
         }
 
         #[method(rightMouseDragged:)]
         fn right_mouse_dragged(&self, event: &NSEvent) {
             self.mouse_motion(event);
+            self.pen_input(event);
+            self.ivars().app_state.update_drag(false);
         }
 
         #[method(otherMouseDragged:)]
         fn other_mouse_dragged(&self, event: &NSEvent) {
             self.mouse_motion(event);
+            self.pen_input(event);
+            self.ivars().app_state.update_drag(false);
+        }
+
+        #[method(tabletProximity:)]
+        fn tablet_proximity(&self, event: &NSEvent) {
+            trace_scope!("tabletProximity:");
+
+            let inverted = unsafe {
+                event.isEnteringProximity()
+                    && event.pointingDeviceType() == NSPointingDeviceType::Eraser
+            };
+            self.ivars().pen_inverted.set(inverted);
         }
 
         #[method(mouseEntered:)]
@@ -695,10 +832,13 @@ declare_class!(
             // be mutually exclusive anyhow, which is why the API is rather incoherent). If no momentum
             // phase is recorded (or rather, the started/ended cases of the momentum phase) then we
             // report the touch phase.
+            let momentum_phase = unsafe { event.momentumPhase() };
+            let momentum = momentum_phase != NSEventPhase::None;
             #[allow(non_upper_case_globals)]
-            let phase = match unsafe { event.momentumPhase() } {
+            let phase = match momentum_phase {
                 NSEventPhase::MayBegin | NSEventPhase::Began => TouchPhase::Started,
                 NSEventPhase::Ended | NSEventPhase::Cancelled => TouchPhase::Ended,
+                _ if momentum => TouchPhase::Moved,
                 _ => match unsafe { event.phase() } {
                     NSEventPhase::MayBegin | NSEventPhase::Began => TouchPhase::Started,
                     NSEventPhase::Ended | NSEventPhase::Cancelled => TouchPhase::Ended,
@@ -715,6 +855,7 @@ declare_class!(
                 device_id: None,
                 delta,
                 phase,
+                momentum,
             });
         }
 
@@ -799,6 +940,37 @@ declare_class!(
             self.ivars().accepts_first_mouse
         }
     }
+
+    // The `NSAccessibility` methods answered by AccessKit, once the application gave the source
+    // of the tree.
+    unsafe impl WinitView {
+        #[cfg(feature = "accesskit")]
+        #[method(accessibilityChildren)]
+        fn accessibility_children(&self) -> *mut NSArray<NSObject> {
+            match self.ivars().accesskit.borrow_mut().as_mut() {
+                Some(accesskit) => accesskit.children(),
+                None => unsafe { msg_send![super(self), accessibilityChildren] },
+            }
+        }
+
+        #[cfg(feature = "accesskit")]
+        #[method(accessibilityFocusedUIElement)]
+        fn accessibility_focused_ui_element(&self) -> *mut NSObject {
+            match self.ivars().accesskit.borrow_mut().as_mut() {
+                Some(accesskit) => accesskit.focus(),
+                None => unsafe { msg_send![super(self), accessibilityFocusedUIElement] },
+            }
+        }
+
+        #[cfg(feature = "accesskit")]
+        #[method(accessibilityHitTest:)]
+        fn accessibility_hit_test(&self, point: NSPoint) -> *mut NSObject {
+            match self.ivars().accesskit.borrow_mut().as_mut() {
+                Some(accesskit) => accesskit.hit_test(point),
+                None => unsafe { msg_send![super(self), accessibilityHitTest: point] },
+            }
+        }
+    }
 );
 
 impl WinitView {
@@ -822,9 +994,14 @@ impl WinitView {
             ime_allowed: Default::default(),
             forward_key_to_app: Default::default(),
             marked_text: Default::default(),
+            surrounding_text: Default::default(),
             accepts_first_mouse,
             _ns_window: WeakId::new(&window.retain()),
             option_as_alt: Cell::new(option_as_alt),
+            pen_inverted: Cell::new(false),
+            motion_coalescing: Cell::new(Coalescing::None),
+            #[cfg(feature = "accesskit")]
+            accesskit: RefCell::new(None),
         });
         let this: Retained<Self> = unsafe { msg_send_id![super(this), init] };
 
@@ -844,6 +1021,24 @@ impl WinitView {
         this
     }
 
+    fn standard_action(&self, action: &'static str) {
+        let window_id = self.window().id();
+        self.ivars().app_state.maybe_queue_with_handler(move |app, event_loop| {
+            if let Some(handler) = app.macos_handler() {
+                handler.standard_key_binding(event_loop, window_id, action);
+            }
+        });
+    }
+
+    fn queue_touch_bar_event(&self, event: TouchBarEvent) {
+        let window_id = self.window().id();
+        self.ivars().app_state.maybe_queue_with_handler(move |app, event_loop| {
+            if let Some(handler) = app.macos_handler() {
+                handler.touch_bar_event(event_loop, window_id, event);
+            }
+        });
+    }
+
     fn window(&self) -> Retained<WinitWindow> {
         // TODO: Simply use `window` property on `NSView`.
         // That only returns a window _after_ the view has been attached though!
@@ -855,9 +1050,8 @@ impl WinitView {
 
     fn queue_event(&self, event: WindowEvent) {
         let window_id = self.window().id();
-        self.ivars().app_state.maybe_queue_with_handler(move |app, event_loop| {
-            app.window_event(event_loop, window_id, event);
-        });
+        let coalescing = self.ivars().motion_coalescing.get();
+        self.ivars().app_state.queue_window_event(window_id, event, coalescing);
     }
 
     fn scale_factor(&self) -> f64 {
@@ -916,6 +1110,30 @@ impl WinitView {
         }
     }
 
+    pub(super) fn set_ime_purpose(&self, purpose: ImePurpose) {
+        // Like the secure text fields, limit the text to the Roman input sources.
+        let locales = match purpose {
+            ImePurpose::Normal | ImePurpose::Terminal => None,
+            ImePurpose::Password | ImePurpose::Digits | ImePurpose::Email | ImePurpose::Url => {
+                Some(NSArray::from_vec(vec![
+                    unsafe { NSAllRomanInputSourcesLocaleIdentifier }.copy()
+                ]))
+            },
+        };
+        let input_context = self.inputContext().expect("input context");
+        unsafe { input_context.setAllowedInputSourceLocales(locales.as_deref()) };
+    }
+
+    pub(super) fn set_ime_surrounding_text(&self, text: &str, cursor: usize, anchor: usize) {
+        let utf16_index = |index| text.get(..index).map(|text| text.encode_utf16().count());
+        let (Some(cursor), Some(anchor)) = (utf16_index(cursor), utf16_index(anchor)) else {
+            tracing::warn!("IME surrounding text ignored, the selection isn't on char boundaries");
+            return;
+        };
+        let selection = NSRange::new(cursor.min(anchor), cursor.abs_diff(anchor));
+        *self.ivars().surrounding_text.borrow_mut() = Some((NSString::from_str(text), selection));
+    }
+
     pub(super) fn set_ime_cursor_area(&self, position: NSPoint, size: NSSize) {
         self.ivars().ime_position.set(position);
         self.ivars().ime_size.set(size);
@@ -939,6 +1157,39 @@ impl WinitView {
         self.ivars().option_as_alt.get()
     }
 
+    #[cfg(feature = "accesskit")]
+    pub(super) fn set_accesskit_tree_source(
+        &self,
+        source: Box<dyn accesskit::ActivationHandler + Send>,
+    ) {
+        let view = WeakId::new(self);
+        let accesskit = AccessKit::new(self, self.window().isKeyWindow(), source, move |request| {
+            if let Some(view) = view.load() {
+                view.queue_event(WindowEvent::AccessKitActionRequested(request));
+            }
+        });
+        *self.ivars().accesskit.borrow_mut() = Some(accesskit);
+    }
+
+    #[cfg(feature = "accesskit")]
+    pub(super) fn update_accesskit_tree(&self, update: accesskit::TreeUpdate) {
+        accessibility::update(&self.ivars().accesskit, update);
+    }
+
+    /// Tell the AccessKit adapter whether the window is key.
+    #[cfg(feature = "accesskit")]
+    pub(super) fn set_accesskit_focused(&self, is_focused: bool) {
+        accessibility::set_focused(&self.ivars().accesskit, is_focused);
+    }
+
+    pub(super) fn set_motion_coalescing(&self, coalescing: Coalescing) {
+        self.ivars().motion_coalescing.set(coalescing)
+    }
+
+    pub(super) fn motion_coalescing(&self) -> Coalescing {
+        self.ivars().motion_coalescing.get()
+    }
+
     /// Update modifiers if `event` has something different
     fn update_modifiers(&self, ns_event: &NSEvent, is_flags_changed_event: bool) {
         use ElementState::{Pressed, Released};
@@ -955,6 +1206,7 @@ impl WinitView {
         // event, thus we can't generate regular presses based on that. The `ModifiersChanged`
         // later will work though, since the flags are attached to the event and contain valid
         // information.
+        let mut events = VecDeque::with_capacity(2);
         'send_event: {
             if is_flags_changed_event && unsafe { ns_event.keyCode() } != 0 {
                 let scancode = unsafe { ns_event.keyCode() };
@@ -978,7 +1230,6 @@ impl WinitView {
                 let phys_mod = phys_mod_state.entry(key).or_insert(ModLocationMask::empty());
 
                 let is_active = current_modifiers.state().contains(event_modifier);
-                let mut events = VecDeque::with_capacity(2);
 
                 // There is no API for getting whether the button was pressed or released
                 // during this event. For this reason we have to do a bit of magic below
@@ -1039,18 +1290,20 @@ impl WinitView {
                 }
 
                 drop(phys_mod_state);
-
-                for event in events {
-                    self.queue_event(event);
-                }
             }
         }
 
-        if prev_modifiers == current_modifiers {
-            return;
+        if prev_modifiers != current_modifiers {
+            let modifiers_changed = WindowEvent::ModifiersChanged(self.ivars().modifiers.get());
+            match self.ivars().app_state.modifiers_order() {
+                ModifiersOrder::BeforeKeyboardInput => events.push_front(modifiers_changed),
+                ModifiersOrder::AfterKeyboardInput => events.push_back(modifiers_changed),
+            }
         }
 
-        self.queue_event(WindowEvent::ModifiersChanged(self.ivars().modifiers.get()));
+        for event in events {
+            self.queue_event(event);
+        }
     }
 
     fn mouse_click(&self, event: &NSEvent, button_state: ElementState) {
@@ -1065,6 +1318,7 @@ impl WinitView {
             position,
             button: button.into(),
         });
+        self.pen_input(event);
     }
 
     fn mouse_motion(&self, event: &NSEvent) {
@@ -1092,6 +1346,38 @@ impl WinitView {
         });
     }
 
+    /// Emit the state of the pen, for the mouse events coming from a tablet.
+    fn pen_input(&self, event: &NSEvent) {
+        if unsafe { event.subtype() } != NSEventSubtype::TabletPoint {
+            return;
+        }
+
+        let position = self.mouse_view_point(event).to_physical(self.scale_factor());
+        let button_mask = unsafe { event.buttonMask() };
+        let contact = button_mask.contains(NSEventButtonMask::PenTip);
+        // The tilt is reported from `-1.0` to `1.0`, with positive `y` leaning up.
+        let tilt = unsafe { event.tilt() };
+        // The rotation is reported counter-clockwise.
+        let twist = (-unsafe { event.rotation() }).rem_euclid(360.0);
+
+        let mut barrel_buttons = PenButtons::empty();
+        barrel_buttons
+            .set(PenButtons::PRIMARY, button_mask.contains(NSEventButtonMask::PenLowerSide));
+        barrel_buttons
+            .set(PenButtons::SECONDARY, button_mask.contains(NSEventButtonMask::PenUpperSide));
+
+        self.queue_event(WindowEvent::PenInput {
+            device_id: None,
+            position,
+            contact,
+            pressure: if contact { unsafe { event.pressure() } } else { 0.0 },
+            tilt: Some(PenTilt { x: tilt.x as f32 * 90.0, y: -tilt.y as f32 * 90.0 }),
+            twist: Some(twist),
+            inverted: self.ivars().pen_inverted.get(),
+            barrel_buttons,
+        });
+    }
+
     fn mouse_view_point(&self, event: &NSEvent) -> LogicalPosition<f64> {
         let window_point = unsafe { event.locationInWindow() };
         let view_point = self.convertPoint_fromView(window_point, None);
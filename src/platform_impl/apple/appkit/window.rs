@@ -1,18 +1,21 @@
 #![allow(clippy::unnecessary_cast)]
 
+use std::time::Instant;
+
 use dpi::{Position, Size};
 use objc2::rc::{autoreleasepool, Retained};
 use objc2::{declare_class, mutability, ClassType, DeclaredClass};
-use objc2_app_kit::{NSResponder, NSWindow};
+use objc2_app_kit::{NSPanel, NSResponder, NSWindow};
 use objc2_foundation::{MainThreadBound, MainThreadMarker, NSObject};
 
 use super::event_loop::ActiveEventLoop;
 use super::window_delegate::WindowDelegate;
-use crate::error::RequestError;
-use crate::monitor::MonitorHandle as CoreMonitorHandle;
+use crate::error::{NotSupportedError, RequestError};
+use crate::event::DeviceId;
+use crate::monitor::{GammaRamp, HdrMetadata, MonitorHandle as CoreMonitorHandle};
 use crate::window::{
-    Cursor, Fullscreen, Icon, ImePurpose, Theme, UserAttentionType, Window as CoreWindow,
-    WindowAttributes, WindowButtons, WindowId, WindowLevel,
+    Coalescing, Cursor, Fullscreen, Icon, ImePurpose, Politeness, Theme, UserAttentionType,
+    Window as CoreWindow, WindowAttributes, WindowButtons, WindowId, WindowLevel,
 };
 
 pub(crate) struct Window {
@@ -42,6 +45,10 @@ impl Window {
         self.delegate.get_on_main(|delegate| f(delegate))
     }
 
+    pub(super) fn ns_window(&self, mtm: MainThreadMarker) -> &WinitWindow {
+        self.window.get(mtm)
+    }
+
     #[cfg(feature = "rwh_06")]
     #[inline]
     pub(crate) fn raw_window_handle_rwh_06(
@@ -103,6 +110,10 @@ impl CoreWindow for Window {
         self.maybe_wait_on_main(|delegate| delegate.request_redraw());
     }
 
+    fn request_redraw_at(&self, instant: Instant) {
+        self.maybe_wait_on_main(|delegate| delegate.request_redraw_at(instant));
+    }
+
     fn pre_present_notify(&self) {
         self.maybe_wait_on_main(|delegate| delegate.pre_present_notify());
     }
@@ -211,6 +222,18 @@ impl CoreWindow for Window {
         self.maybe_wait_on_main(|delegate| delegate.fullscreen().map(Into::into))
     }
 
+    fn set_fullscreen_hdr(&self, _hdr: Option<HdrMetadata>) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_fullscreen_hdr is not supported").into())
+    }
+
+    fn fullscreen_hdr(&self) -> Option<HdrMetadata> {
+        None
+    }
+
+    fn set_gamma_ramp(&self, ramp: Option<&GammaRamp>) -> Result<(), RequestError> {
+        self.maybe_wait_on_main(|delegate| delegate.set_gamma_ramp(ramp))
+    }
+
     fn set_decorations(&self, decorations: bool) {
         self.maybe_wait_on_main(|delegate| delegate.set_decorations(decorations));
     }
@@ -231,14 +254,30 @@ impl CoreWindow for Window {
         self.maybe_wait_on_main(|delegate| delegate.set_ime_cursor_area(position, size));
     }
 
+    fn set_ime_exclusion_area(&self, _area: Option<(Position, Size)>) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_ime_exclusion_area is not supported").into())
+    }
+
+    fn set_ime_candidates_drawn_by_app(&self, _drawn_by_app: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_ime_candidates_drawn_by_app is not supported").into())
+    }
+
     fn set_ime_allowed(&self, allowed: bool) {
         self.maybe_wait_on_main(|delegate| delegate.set_ime_allowed(allowed));
     }
 
+    fn set_virtual_keyboard_visible(&self, _visible: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_virtual_keyboard_visible is not supported").into())
+    }
+
     fn set_ime_purpose(&self, purpose: ImePurpose) {
         self.maybe_wait_on_main(|delegate| delegate.set_ime_purpose(purpose));
     }
 
+    fn set_ime_surrounding_text(&self, text: &str, cursor: usize, anchor: usize) {
+        self.maybe_wait_on_main(|delegate| delegate.set_ime_surrounding_text(text, cursor, anchor));
+    }
+
     fn focus_window(&self) {
         self.maybe_wait_on_main(|delegate| delegate.focus_window());
     }
@@ -247,10 +286,19 @@ impl CoreWindow for Window {
         self.maybe_wait_on_main(|delegate| delegate.has_focus())
     }
 
+    fn set_keyboard_capture(&self, capture: bool) -> Result<(), RequestError> {
+        self.maybe_wait_on_main(|delegate| delegate.set_keyboard_capture(capture))
+    }
+
     fn request_user_attention(&self, request_type: Option<UserAttentionType>) {
         self.maybe_wait_on_main(|delegate| delegate.request_user_attention(request_type));
     }
 
+    fn announce(&self, message: &str, politeness: Politeness) -> Result<(), RequestError> {
+        self.maybe_wait_on_main(|delegate| delegate.announce(message, politeness));
+        Ok(())
+    }
+
     fn set_theme(&self, theme: Option<Theme>) {
         self.maybe_wait_on_main(|delegate| delegate.set_theme(theme));
     }
@@ -279,10 +327,22 @@ impl CoreWindow for Window {
         self.maybe_wait_on_main(|delegate| delegate.set_cursor_grab(mode))
     }
 
+    fn set_cursor_grab_policy(&self, policy: crate::window::CursorGrabPolicy) {
+        self.maybe_wait_on_main(|delegate| delegate.set_cursor_grab_policy(policy));
+    }
+
     fn set_cursor_visible(&self, visible: bool) {
         self.maybe_wait_on_main(|delegate| delegate.set_cursor_visible(visible))
     }
 
+    fn set_cursor_for_device(&self, _: DeviceId, _: Cursor) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_cursor_for_device is not supported").into())
+    }
+
+    fn set_cursor_visible_for_device(&self, _: DeviceId, _: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_cursor_visible_for_device is not supported").into())
+    }
+
     fn drag_window(&self) -> Result<(), RequestError> {
         self.maybe_wait_on_main(|delegate| delegate.drag_window())
     }
@@ -303,6 +363,25 @@ impl CoreWindow for Window {
         Ok(())
     }
 
+    #[cfg(feature = "accesskit")]
+    fn set_accesskit_tree_source(
+        &self,
+        source: Box<dyn accesskit::ActivationHandler + Send>,
+    ) -> Result<(), RequestError> {
+        self.maybe_wait_on_main(|delegate| delegate.set_accesskit_tree_source(source));
+        Ok(())
+    }
+
+    #[cfg(feature = "accesskit")]
+    fn update_accesskit_tree(&self, update: accesskit::TreeUpdate) {
+        self.maybe_wait_on_main(|delegate| delegate.update_accesskit_tree(update));
+    }
+
+    fn set_motion_coalescing(&self, coalescing: Coalescing) -> Result<(), RequestError> {
+        self.maybe_wait_on_main(|delegate| delegate.set_motion_coalescing(coalescing));
+        Ok(())
+    }
+
     fn current_monitor(&self) -> Option<CoreMonitorHandle> {
         self.maybe_wait_on_main(|delegate| {
             delegate.current_monitor().map(|inner| CoreMonitorHandle { inner })
@@ -362,6 +441,36 @@ declare_class!(
     }
 );
 
+declare_class!(
+    /// The window created instead of [`WinitWindow`] for the panels, with the same overrides.
+    #[derive(Debug)]
+    pub struct WinitPanel;
+
+    unsafe impl ClassType for WinitPanel {
+        #[inherits(NSWindow, NSResponder, NSObject)]
+        type Super = NSPanel;
+        type Mutability = mutability::MainThreadOnly;
+        const NAME: &'static str = "WinitPanel";
+    }
+
+    impl DeclaredClass for WinitPanel {}
+
+    unsafe impl WinitPanel {
+        // Panels aren't the main window, for the main window of the application to stay so.
+        #[method(canBecomeMainWindow)]
+        fn can_become_main_window(&self) -> bool {
+            trace_scope!("canBecomeMainWindow");
+            false
+        }
+
+        #[method(canBecomeKeyWindow)]
+        fn can_become_key_window(&self) -> bool {
+            trace_scope!("canBecomeKeyWindow");
+            true
+        }
+    }
+);
+
 impl WinitWindow {
     pub(super) fn id(&self) -> WindowId {
         WindowId::from_raw(self as *const Self as usize)
@@ -2,46 +2,66 @@
 use std::cell::{Cell, RefCell};
 use std::collections::VecDeque;
 use std::ffi::c_void;
+use std::path::{Path, PathBuf};
 use std::ptr;
 use std::rc::Rc;
 use std::sync::{Arc, Mutex};
+use std::time::Instant;
 
-use core_graphics::display::{CGDisplay, CGPoint};
+use block2::RcBlock;
+use core_graphics::base::CGError;
+use core_graphics::display::{CGDirectDisplayID, CGDisplay, CGPoint};
 use monitor::VideoModeHandle;
 use objc2::rc::{autoreleasepool, Retained};
 use objc2::runtime::{AnyObject, ProtocolObject};
-use objc2::{declare_class, msg_send_id, mutability, sel, ClassType, DeclaredClass};
+use objc2::{
+    class, declare_class, msg_send, msg_send_id, mutability, sel, ClassType, DeclaredClass,
+};
 use objc2_app_kit::{
     NSAppKitVersionNumber, NSAppKitVersionNumber10_12, NSAppearance, NSAppearanceCustomization,
-    NSAppearanceNameAqua, NSApplication, NSApplicationPresentationOptions, NSBackingStoreType,
-    NSColor, NSDraggingDestination, NSFilenamesPboardType, NSPasteboard,
-    NSRequestUserAttentionType, NSScreen, NSToolbar, NSView, NSWindowButton, NSWindowDelegate,
+    NSAppearanceNameAqua, NSApplication, NSApplicationPresentationOptions,
+    NSAutoresizingMaskOptions, NSBackingStoreType, NSColor, NSColorSpace, NSDragOperation,
+    NSDraggingDestination, NSFilenamesPboardType, NSModalResponse, NSPasteboard,
+    NSPasteboardTypeHTML, NSPasteboardTypePNG, NSPasteboardTypeString, NSPasteboardTypeURL,
+    NSRequestUserAttentionType, NSScreen, NSSystemColorsDidChangeNotification, NSToolbar, NSView,
+    NSVisualEffectBlendingMode, NSVisualEffectMaterial, NSVisualEffectState, NSVisualEffectView,
+    NSWindow, NSWindowButton, NSWindowCollectionBehavior, NSWindowDelegate,
     NSWindowFullScreenButton, NSWindowLevel, NSWindowOcclusionState, NSWindowOrderingMode,
     NSWindowSharingType, NSWindowStyleMask, NSWindowTabbingMode, NSWindowTitleVisibility,
     NSWindowToolbarStyle,
 };
 use objc2_foundation::{
     ns_string, CGFloat, MainThreadMarker, NSArray, NSCopying, NSDictionary, NSKeyValueChangeKey,
-    NSKeyValueChangeNewKey, NSKeyValueChangeOldKey, NSKeyValueObservingOptions, NSObject,
-    NSObjectNSDelayedPerforming, NSObjectNSKeyValueObserverRegistration, NSObjectProtocol, NSPoint,
-    NSRect, NSSize, NSString,
+    NSKeyValueChangeNewKey, NSKeyValueChangeOldKey, NSKeyValueObservingOptions,
+    NSNotificationCenter, NSNumber, NSObject, NSObjectNSDelayedPerforming,
+    NSObjectNSKeyValueObserverRegistration, NSObjectProtocol, NSPoint, NSRect, NSSize, NSString,
+    NSUInteger, NSURL,
 };
 use tracing::{trace, warn};
 
 use super::app_state::AppState;
 use super::cursor::cursor_from_icon;
+use super::keyboard_capture::KeyboardCapture;
 use super::monitor::{self, flip_window_screen_coordinates, get_display_id};
 use super::observer::RunLoop;
 use super::view::WinitView;
-use super::window::WinitWindow;
-use super::{ffi, Fullscreen, MonitorHandle};
+use super::window::{Window, WinitPanel, WinitWindow};
+use super::{clipboard, event, ffi, touch_bar, user_activity, Fullscreen, MonitorHandle};
+use crate::clipboard::{ClipboardData, ClipboardFormat};
 use crate::dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize, Position, Size};
+use crate::drag::{DragEffect, DropData, DropEffectWriter};
 use crate::error::{NotSupportedError, RequestError};
 use crate::event::{SurfaceSizeWriter, WindowEvent};
-use crate::platform::macos::{OptionAsAlt, WindowExtMacOS};
+use crate::monitor::GammaRamp;
+use crate::platform::macos::{
+    AppearanceName, CollectionBehavior, FullscreenPresentationOptions, OptionAsAlt, PanelStyle,
+    SpaceId, TabEvent, TouchBarDescription, UserActivity, VibrancyBlendingMode, VibrancyMaterial,
+    VibrancyState, WindowExtMacOS,
+};
 use crate::window::{
-    Cursor, CursorGrabMode, Icon, ImePurpose, ResizeDirection, Theme, UserAttentionType,
-    WindowAttributes, WindowButtons, WindowId, WindowLevel,
+    Coalescing, Cursor, CursorGrabMode, CursorGrabPolicy, Icon, ImePurpose, Politeness,
+    ResizeDirection, Rgba, SurfaceColorSpace, SurfaceFormatHint, Theme, UserAttentionType,
+    Window as CoreWindow, WindowAttributes, WindowButtons, WindowId, WindowLevel,
 };
 
 #[derive(Clone, Debug, PartialEq)]
@@ -59,6 +79,7 @@ pub struct PlatformSpecificWindowAttributes {
     pub option_as_alt: OptionAsAlt,
     pub borderless_game: bool,
     pub unified_titlebar: bool,
+    pub panel: Option<PanelStyle>,
 }
 
 impl Default for PlatformSpecificWindowAttributes {
@@ -78,6 +99,7 @@ impl Default for PlatformSpecificWindowAttributes {
             option_as_alt: Default::default(),
             borderless_game: false,
             unified_titlebar: false,
+            panel: None,
         }
     }
 }
@@ -127,6 +149,32 @@ pub(crate) struct State {
     is_simple_fullscreen: Cell<bool>,
     saved_style: Cell<Option<NSWindowStyleMask>>,
     is_borderless_game: Cell<bool>,
+    /// The cursor grab mode requested by the user.
+    cursor_grab: Cell<CursorGrabMode>,
+    cursor_grab_policy: Cell<CursorGrabPolicy>,
+    /// The last accent color of the system, used to prevent redundant events.
+    accent_color: Cell<Option<Rgba>>,
+    /// Whether data which can be dropped is dragged over the window.
+    drop_hovered: Cell<bool>,
+    /// The effect of dropping the hovered data, chosen by the application.
+    drop_effect: Cell<Option<DragEffect>>,
+    /// The gamma ramp applied in exclusive fullscreen.
+    gamma_ramp: RefCell<Option<GammaRamp>>,
+    /// Used to restore the gamma tables of the display when the window resigns key.
+    saved_gamma_ramp: RefCell<Option<(CGDirectDisplayID, [Vec<f32>; 3])>>,
+    /// The event tap capturing the keyboard shortcuts of the system.
+    keyboard_capture: RefCell<Option<KeyboardCapture>>,
+    /// The position of the close button, applied again when AppKit lays out the titlebar.
+    traffic_light_inset: Cell<Option<LogicalPosition<f64>>>,
+    hidden_traffic_lights: Cell<WindowButtons>,
+    /// The view drawing the material behind the content view.
+    vibrancy_view: RefCell<Option<Retained<NSVisualEffectView>>>,
+    /// The options applied in borderless and simple fullscreen, instead of the default ones.
+    fullscreen_presentation_options: Cell<Option<FullscreenPresentationOptions>>,
+    /// Whether the secure event input is requested, and whether it's enabled, while the window is
+    /// key.
+    secure_input: Cell<bool>,
+    secure_input_enabled: Cell<bool>,
 }
 
 declare_class!(
@@ -169,6 +217,7 @@ declare_class!(
             trace_scope!("windowDidResize:");
             // NOTE: WindowEvent::SurfaceResized is reported in frameDidChange.
             self.emit_move_event();
+            self.update_traffic_lights();
         }
 
         #[method(windowWillStartLiveResize:)]
@@ -214,6 +263,18 @@ declare_class!(
             // TODO: center the cursor if the window had mouse grab when it
             // lost focus
             self.queue_event(WindowEvent::Focused(true));
+            #[cfg(feature = "accesskit")]
+            self.view().set_accesskit_focused(true);
+            self.update_traffic_lights();
+            self.update_secure_input();
+
+            if let Err(status) = self.update_gamma_ramp() {
+                warn!("Failed to apply the gamma ramp: CGError {status}");
+            }
+
+            if let Some(mode) = self.focus_changed_cursor_grab(true) {
+                self.queue_event(WindowEvent::CursorGrabChanged(mode));
+            }
         }
 
         #[method(windowDidResignKey:)]
@@ -229,6 +290,17 @@ declare_class!(
             self.view().reset_modifiers();
 
             self.queue_event(WindowEvent::Focused(false));
+            #[cfg(feature = "accesskit")]
+            self.view().set_accesskit_focused(false);
+            self.update_secure_input();
+
+            if let Err(status) = self.update_gamma_ramp() {
+                warn!("Failed to restore the gamma ramp: CGError {status}");
+            }
+
+            if let Some(mode) = self.focus_changed_cursor_grab(false) {
+                self.queue_event(WindowEvent::CursorGrabChanged(mode));
+            }
         }
 
         /// Invoked when before enter fullscreen
@@ -272,23 +344,7 @@ declare_class!(
             proposed_options: NSApplicationPresentationOptions,
         ) -> NSApplicationPresentationOptions {
             trace_scope!("window:willUseFullScreenPresentationOptions:");
-            // Generally, games will want to disable the menu bar and the dock. Ideally,
-            // this would be configurable by the user. Unfortunately because of our
-            // `CGShieldingWindowLevel() + 1` hack (see `set_fullscreen`), our window is
-            // placed on top of the menu bar in exclusive fullscreen mode. This looks
-            // broken so we always disable the menu bar in exclusive fullscreen. We may
-            // still want to make this configurable for borderless fullscreen. Right now
-            // we don't, for consistency. If we do, it should be documented that the
-            // user-provided options are ignored in exclusive fullscreen.
-            let mut options = proposed_options;
-            let fullscreen = self.ivars().fullscreen.borrow();
-            if let Some(Fullscreen::Exclusive(_)) = &*fullscreen {
-                options = NSApplicationPresentationOptions::NSApplicationPresentationFullScreen
-                    | NSApplicationPresentationOptions::NSApplicationPresentationHideDock
-                    | NSApplicationPresentationOptions::NSApplicationPresentationHideMenuBar;
-            }
-
-            options
+            self.fullscreen_presentation_options_for(proposed_options)
         }
 
         /// Invoked when entered fullscreen
@@ -309,6 +365,7 @@ declare_class!(
 
             self.restore_state_from_fullscreen();
             self.ivars().in_fullscreen_transition.set(false);
+            self.update_traffic_lights();
             if let Some(target_fullscreen) = self.ivars().target_fullscreen.take() {
                 self.set_fullscreen(target_fullscreen);
             }
@@ -366,26 +423,39 @@ declare_class!(
                 }
             }
         }
+
+        #[method(windowDidChangeScreenProfile:)]
+        fn window_did_change_screen_profile(&self, _: Option<&AnyObject>) {
+            trace_scope!("windowDidChangeScreenProfile:");
+            self.queue_event(WindowEvent::ColorProfileChanged);
+        }
     }
 
     unsafe impl NSDraggingDestination for WindowDelegate {
         /// Invoked when the dragged image enters destination bounds or frame
         #[method(draggingEntered:)]
-        fn dragging_entered(&self, sender: &NSObject) -> bool {
+        fn dragging_entered(&self, sender: &NSObject) -> NSDragOperation {
             trace_scope!("draggingEntered:");
 
             use std::path::PathBuf;
 
             let pb: Retained<NSPasteboard> = unsafe { msg_send_id![sender, draggingPasteboard] };
-            let filenames = pb.propertyListForType(unsafe { NSFilenamesPboardType }).unwrap();
-            let filenames: Retained<NSArray<NSString>> = unsafe { Retained::cast(filenames) };
+            if let Some(filenames) = pb.propertyListForType(unsafe { NSFilenamesPboardType }) {
+                let filenames: Retained<NSArray<NSString>> = unsafe { Retained::cast(filenames) };
+                filenames.into_iter().for_each(|file| {
+                    let path = PathBuf::from(file.to_string());
+                    self.queue_event(WindowEvent::HoveredFile(path));
+                });
+            }
 
-            filenames.into_iter().for_each(|file| {
-                let path = PathBuf::from(file.to_string());
-                self.queue_event(WindowEvent::HoveredFile(path));
-            });
+            self.hover_drop(sender)
+        }
 
-            true
+        /// Invoked periodically as the image is held within the destination area
+        #[method(draggingUpdated:)]
+        fn dragging_updated(&self, sender: &NSObject) -> NSDragOperation {
+            trace_scope!("draggingUpdated:");
+            self.hover_drop(sender)
         }
 
         /// Invoked when the image is released
@@ -400,18 +470,15 @@ declare_class!(
         fn perform_drag_operation(&self, sender: &NSObject) -> bool {
             trace_scope!("performDragOperation:");
 
-            use std::path::PathBuf;
-
-            let pb: Retained<NSPasteboard> = unsafe { msg_send_id![sender, draggingPasteboard] };
-            let filenames = pb.propertyListForType(unsafe { NSFilenamesPboardType }).unwrap();
-            let filenames: Retained<NSArray<NSString>> = unsafe { Retained::cast(filenames) };
-
-            filenames.into_iter().for_each(|file| {
-                let path = PathBuf::from(file.to_string());
-                self.queue_event(WindowEvent::DroppedFile(path));
-            });
-
-            true
+            self.ivars().drop_hovered.set(false);
+            // Without an effect, the application rejected the drop where it was released.
+            match self.ivars().drop_effect.take() {
+                Some(effect) => {
+                    self.drop_data(sender, effect);
+                    true
+                },
+                None => false,
+            }
         }
 
         /// Invoked when the dragging operation is complete
@@ -425,6 +492,10 @@ declare_class!(
         fn dragging_exited(&self, _sender: Option<&NSObject>) {
             trace_scope!("draggingExited:");
             self.queue_event(WindowEvent::HoveredFileCancelled);
+            self.ivars().drop_effect.set(None);
+            if self.ivars().drop_hovered.replace(false) {
+                self.queue_event(WindowEvent::DataHoverCancelled);
+            }
         }
     }
 
@@ -473,17 +544,53 @@ declare_class!(
                 }
 
                 self.queue_event(WindowEvent::ThemeChanged(new));
+            } else if key_path == Some(ns_string!("tabGroup.selectedWindow")) {
+                let window: &NSWindow = self.window();
+                let selected = window.tabGroup().and_then(|group| unsafe { group.selectedWindow() });
+                if selected.is_some_and(|selected| ptr::eq(&*selected, window)) {
+                    self.queue_tab_event(TabEvent::TabSelected);
+                }
+            } else if key_path == Some(ns_string!("tabGroup.windows")) {
+                let window: &NSWindow = self.window();
+                let windows = window.tabGroup().map(|group| group.windows());
+                let index = windows.and_then(|windows| windows.iter().position(|tab| ptr::eq(tab, window)));
+                if let Some(index) = index {
+                    self.queue_tab_event(TabEvent::TabOrderChanged { index });
+                }
             } else {
                 panic!("unknown observed keypath {key_path:?}");
             }
         }
     }
+
+    // Notifications
+    unsafe impl WindowDelegate {
+        #[method(systemColorsDidChange:)]
+        fn system_colors_did_change(&self, _: Option<&AnyObject>) {
+            trace_scope!("NSSystemColorsDidChangeNotification");
+            let accent_color = accent_color();
+            if accent_color == self.ivars().accent_color.replace(accent_color) {
+                return;
+            }
+
+            if let Some(accent_color) = accent_color {
+                self.queue_event(WindowEvent::AccentColorChanged(accent_color));
+            }
+        }
+    }
 );
 
 impl Drop for WindowDelegate {
     fn drop(&mut self) {
         unsafe {
             self.window().removeObserver_forKeyPath(self, ns_string!("effectiveAppearance"));
+            self.window().removeObserver_forKeyPath(self, ns_string!("tabGroup.selectedWindow"));
+            self.window().removeObserver_forKeyPath(self, ns_string!("tabGroup.windows"));
+            NSNotificationCenter::defaultCenter().removeObserver(self);
+        }
+        // The secure event input is counted for the whole process.
+        if self.ivars().secure_input_enabled.get() {
+            unsafe { ffi::DisableSecureEventInput() };
         }
     }
 }
@@ -564,19 +671,51 @@ fn new_window(
             masks |= NSWindowStyleMask::FullSizeContentView;
         }
 
+        if let Some(panel) = attrs.platform_specific.panel {
+            if panel.nonactivating {
+                masks |= NSWindowStyleMask::NonactivatingPanel;
+            }
+            if panel.hud {
+                masks |= NSWindowStyleMask::HUDWindow | NSWindowStyleMask::UtilityWindow;
+            }
+        }
+
         // NOTE: This should only be created after the application has started launching,
         // (`applicationWillFinishLaunching:` at the earliest), otherwise you'll run into very
         // confusing issues with the window not being properly activated.
         //
         // Winit ensures this by not allowing access to `ActiveEventLoop` before handling events.
-        let window: Option<Retained<WinitWindow>> = unsafe {
-            msg_send_id![
-                super(mtm.alloc().set_ivars(())),
-                initWithContentRect: frame,
-                styleMask: masks,
-                backing: NSBackingStoreType::NSBackingStoreBuffered,
-                defer: false,
-            ]
+        let window: Option<Retained<WinitWindow>> = if let Some(style) =
+            attrs.platform_specific.panel
+        {
+            let panel: Option<Retained<WinitPanel>> = unsafe {
+                msg_send_id![
+                    super(mtm.alloc().set_ivars(())),
+                    initWithContentRect: frame,
+                    styleMask: masks,
+                    backing: NSBackingStoreType::NSBackingStoreBuffered,
+                    defer: false,
+                ]
+            };
+            panel.map(|panel| {
+                unsafe { panel.setFloatingPanel(style.floating) };
+                // Panels hide when the application is deactivated by default, which a panel shown
+                // without activating the application wouldn't survive.
+                unsafe { panel.setHidesOnDeactivate(false) };
+                // SAFETY: `WinitPanel` is an `NSWindow` with the same overrides as `WinitWindow`,
+                // and neither has instance variables.
+                unsafe { Retained::cast::<WinitWindow>(panel) }
+            })
+        } else {
+            unsafe {
+                msg_send_id![
+                    super(mtm.alloc().set_ivars(())),
+                    initWithContentRect: frame,
+                    styleMask: masks,
+                    backing: NSBackingStoreType::NSBackingStoreBuffered,
+                    defer: false,
+                ]
+            }
         };
         let window = window?;
 
@@ -647,6 +786,7 @@ fn new_window(
             attrs.platform_specific.accepts_first_mouse,
             attrs.platform_specific.option_as_alt,
         );
+        view.set_motion_coalescing(attrs.motion_coalescing);
 
         // The default value of `setWantsBestResolutionOpenGLSurface:` was `false` until
         // macos 10.14 and `true` after 10.15, we should set it to `YES` or `NO` to avoid
@@ -663,6 +803,10 @@ fn new_window(
             view.setWantsLayer(true);
         }
 
+        if let Some(hint) = attrs.surface_format_hint {
+            set_surface_format_hint(&view, hint);
+        }
+
         // Configure the new view as the "key view" for the window
         window.setContentView(Some(&view));
         window.setInitialFirstResponder(Some(&view));
@@ -674,15 +818,49 @@ fn new_window(
         }
 
         // register for drag and drop operations.
-        window
-            .registerForDraggedTypes(&NSArray::from_id_slice(&[
-                unsafe { NSFilenamesPboardType }.copy()
-            ]));
+        window.registerForDraggedTypes(&NSArray::from_id_slice(&[
+            unsafe { NSFilenamesPboardType }.copy(),
+            unsafe { NSPasteboardTypeURL }.copy(),
+            unsafe { NSPasteboardTypeString }.copy(),
+            unsafe { NSPasteboardTypeHTML }.copy(),
+            unsafe { NSPasteboardTypePNG }.copy(),
+        ]));
 
         Some(window)
     })
 }
 
+/// The formats read from the pasteboards of the drops, in this order.
+const DROP_FORMATS: [ClipboardFormat; 5] = [
+    ClipboardFormat::Files,
+    ClipboardFormat::Uris,
+    ClipboardFormat::Text,
+    ClipboardFormat::Html,
+    ClipboardFormat::Image,
+];
+
+fn drag_operation(effect: DragEffect) -> NSDragOperation {
+    match effect {
+        DragEffect::Copy => NSDragOperation::Copy,
+        DragEffect::Move => NSDragOperation::Move,
+        DragEffect::Link => NSDragOperation::Link,
+    }
+}
+
+/// The pasteboard of a drag.
+struct DragPasteboard(Retained<NSPasteboard>);
+
+// SAFETY: `DropData` only reads the pasteboard on the main thread, and releases it there when the
+// drop is done.
+unsafe impl Send for DragPasteboard {}
+
+impl DragPasteboard {
+    // A method, so that closures capture the whole `Send` wrapper and not only its field.
+    fn read(&self, format: ClipboardFormat) -> Option<ClipboardData> {
+        clipboard::read(&self.0, format)
+    }
+}
+
 impl WindowDelegate {
     pub(super) fn new(
         app_state: &Rc<AppState>,
@@ -748,6 +926,20 @@ impl WindowDelegate {
             is_simple_fullscreen: Cell::new(false),
             saved_style: Cell::new(None),
             is_borderless_game: Cell::new(attrs.platform_specific.borderless_game),
+            cursor_grab: Cell::new(CursorGrabMode::None),
+            cursor_grab_policy: Cell::new(CursorGrabPolicy::default()),
+            accent_color: Cell::new(accent_color()),
+            drop_hovered: Cell::new(false),
+            drop_effect: Cell::new(None),
+            gamma_ramp: RefCell::new(None),
+            saved_gamma_ramp: RefCell::new(None),
+            keyboard_capture: RefCell::new(None),
+            traffic_light_inset: Cell::new(None),
+            hidden_traffic_lights: Cell::new(WindowButtons::empty()),
+            vibrancy_view: RefCell::new(None),
+            fullscreen_presentation_options: Cell::new(None),
+            secure_input: Cell::new(false),
+            secure_input_enabled: Cell::new(false),
         });
         let delegate: Retained<WindowDelegate> = unsafe { msg_send_id![super(delegate), init] };
 
@@ -772,6 +964,32 @@ impl WindowDelegate {
             )
         };
 
+        // Listen for the selection and the order of the tabs.
+        //
+        // SAFETY: The observers are un-registered in the `Drop` of the delegate.
+        for key_path in [ns_string!("tabGroup.selectedWindow"), ns_string!("tabGroup.windows")] {
+            unsafe {
+                window.addObserver_forKeyPath_options_context(
+                    &delegate,
+                    key_path,
+                    NSKeyValueObservingOptions::empty(),
+                    ptr::null_mut(),
+                )
+            };
+        }
+
+        // Listen for accent color change event.
+        //
+        // SAFETY: The observer is un-registered in the `Drop` of the delegate.
+        unsafe {
+            NSNotificationCenter::defaultCenter().addObserver_selector_name_object(
+                &delegate,
+                sel!(systemColorsDidChange:),
+                Some(NSSystemColorsDidChangeNotification),
+                None,
+            )
+        };
+
         if attrs.blur {
             delegate.set_blur(attrs.blur);
         }
@@ -829,10 +1047,77 @@ impl WindowDelegate {
         self.window().id()
     }
 
-    pub(crate) fn queue_event(&self, event: WindowEvent) {
+    /// The position of the drag in the view.
+    fn drop_position(&self, sender: &NSObject) -> PhysicalPosition<f64> {
+        let location: NSPoint = unsafe { msg_send![sender, draggingLocation] };
+        let location = self.view().convertPoint_fromView(location, None);
+        LogicalPosition::new(location.x, location.y).to_physical(self.scale_factor())
+    }
+
+    /// Deliver the dropped data, with the effect the application chose.
+    fn drop_data(&self, sender: &NSObject, effect: DragEffect) {
+        let pb: Retained<NSPasteboard> = unsafe { msg_send_id![sender, draggingPasteboard] };
+        if let Some(filenames) = pb.propertyListForType(unsafe { NSFilenamesPboardType }) {
+            let filenames: Retained<NSArray<NSString>> = unsafe { Retained::cast(filenames) };
+            filenames.into_iter().for_each(|file| {
+                let path = PathBuf::from(file.to_string());
+                self.queue_event(WindowEvent::DroppedFile(path));
+            });
+        }
+
+        let position = self.drop_position(sender);
+        let formats =
+            DROP_FORMATS.into_iter().filter(|&format| clipboard::contains(&pb, format)).collect();
+        let pasteboard = DragPasteboard(pb);
+        let data = DropData::new(formats, move |format| pasteboard.read(format));
+
+        // The data is read while the event is handled, even when it's queued.
         let window_id = self.window().id();
         self.ivars().app_state.maybe_queue_with_handler(move |app, event_loop| {
+            let event = WindowEvent::DataDropped { position, data: data.clone(), effect };
             app.window_event(event_loop, window_id, event);
+            data.expire();
+        });
+    }
+
+    /// Ask the application for the effect of dropping the dragged data where it is.
+    fn hover_drop(&self, sender: &NSObject) -> NSDragOperation {
+        let pb: Retained<NSPasteboard> = unsafe { msg_send_id![sender, draggingPasteboard] };
+        let formats: Vec<_> =
+            DROP_FORMATS.into_iter().filter(|&format| clipboard::contains(&pb, format)).collect();
+        self.ivars().drop_hovered.set(!formats.is_empty());
+        if formats.is_empty() {
+            self.ivars().drop_effect.set(None);
+            return NSDragOperation::None;
+        }
+
+        // The source already restricts the operations according to the modifiers.
+        let allowed: NSDragOperation = unsafe { msg_send![sender, draggingSourceOperationMask] };
+        let is_allowed = |effect: &DragEffect| allowed.0 & drag_operation(*effect).0 != 0;
+        let default =
+            [DragEffect::Copy, DragEffect::Move, DragEffect::Link].into_iter().find(is_allowed);
+        let position = self.drop_position(sender);
+        let modifiers = event::current_mods();
+        let effect = DropEffectWriter::negotiate(default, |effect| {
+            self.queue_event(WindowEvent::DataHovered { position, formats, modifiers, effect });
+        });
+        let effect = effect.filter(is_allowed);
+        self.ivars().drop_effect.set(effect);
+        effect.map_or(NSDragOperation::None, drag_operation)
+    }
+
+    pub(crate) fn queue_event(&self, event: WindowEvent) {
+        let window_id = self.window().id();
+        let coalescing = self.view().motion_coalescing();
+        self.ivars().app_state.queue_window_event(window_id, event, coalescing);
+    }
+
+    fn queue_tab_event(&self, event: TabEvent) {
+        let window_id = self.window().id();
+        self.ivars().app_state.maybe_queue_with_handler(move |app, event_loop| {
+            if let Some(handler) = app.macos_handler() {
+                handler.tab_event(event_loop, window_id, event);
+            }
         });
     }
 
@@ -876,10 +1161,13 @@ impl WindowDelegate {
         // If we don't do this, key handling will break
         // (at least until the window is clicked again/etc.)
         let _ = self.window().makeFirstResponder(Some(&self.view()));
+        // The titlebar was laid out again.
+        self.update_traffic_lights();
     }
 
     pub fn set_title(&self, title: &str) {
-        self.window().setTitle(&NSString::from_str(title))
+        self.window().setTitle(&NSString::from_str(title));
+        self.update_traffic_lights();
     }
 
     pub fn set_transparent(&self, transparent: bool) {
@@ -932,6 +1220,10 @@ impl WindowDelegate {
         self.ivars().app_state.queue_redraw(self.window().id());
     }
 
+    pub fn request_redraw_at(&self, instant: Instant) {
+        self.ivars().app_state.schedule_redraw(self.window().id(), instant);
+    }
+
     #[inline]
     pub fn pre_present_notify(&self) {}
 
@@ -1146,7 +1438,48 @@ impl WindowDelegate {
 
         // TODO: Do this for real https://stackoverflow.com/a/40922095/5435443
         CGDisplay::associate_mouse_and_mouse_cursor_position(associate_mouse_cursor)
-            .map_err(|status| os_error!(format!("CGError {status}")).into())
+            .map_err(|status| RequestError::from(os_error!(format!("CGError {status}"))))?;
+        self.ivars().cursor_grab.set(mode);
+        Ok(())
+    }
+
+    #[inline]
+    pub fn set_cursor_grab_policy(&self, policy: CursorGrabPolicy) {
+        self.ivars().cursor_grab_policy.set(policy);
+    }
+
+    /// Release or establish again the cursor grab following the focus of the window, when the
+    /// policy asks for it.
+    fn focus_changed_cursor_grab(&self, focused: bool) -> Option<CursorGrabMode> {
+        let mode = self.ivars().cursor_grab.get();
+        if self.ivars().cursor_grab_policy.get() != CursorGrabPolicy::ReGrabOnFocus
+            || mode == CursorGrabMode::None
+        {
+            return None;
+        }
+
+        let mode = if focused { mode } else { CursorGrabMode::None };
+        match CGDisplay::associate_mouse_and_mouse_cursor_position(mode == CursorGrabMode::None) {
+            Ok(()) => Some(mode),
+            Err(status) => {
+                warn!("Failed to change the cursor grab: CGError {status}");
+                None
+            },
+        }
+    }
+
+    /// Lock the cursor again after it was released for a native modal dialog.
+    fn restore_cursor_grab(&self) {
+        if self.ivars().cursor_grab.get() != CursorGrabMode::Locked
+            || (self.ivars().cursor_grab_policy.get() == CursorGrabPolicy::ReGrabOnFocus
+                && !self.window().isKeyWindow())
+        {
+            return;
+        }
+
+        if let Err(status) = CGDisplay::associate_mouse_and_mouse_cursor_position(false) {
+            warn!("Failed to restore the cursor grab: CGError {status}");
+        }
     }
 
     #[inline]
@@ -1203,6 +1536,20 @@ impl WindowDelegate {
         self.window().setIgnoresMouseEvents(!hittest);
     }
 
+    pub fn set_motion_coalescing(&self, coalescing: Coalescing) {
+        self.view().set_motion_coalescing(coalescing);
+    }
+
+    #[cfg(feature = "accesskit")]
+    pub fn set_accesskit_tree_source(&self, source: Box<dyn accesskit::ActivationHandler + Send>) {
+        self.view().set_accesskit_tree_source(source);
+    }
+
+    #[cfg(feature = "accesskit")]
+    pub fn update_accesskit_tree(&self, update: accesskit::TreeUpdate) {
+        self.view().update_accesskit_tree(update);
+    }
+
     pub(crate) fn is_zoomed(&self) -> bool {
         // because `isZoomed` doesn't work if the window's borderless,
         // we make it resizable temporarily.
@@ -1330,6 +1677,15 @@ impl WindowDelegate {
             return;
         }
 
+        // The gamma ramp only applies in exclusive fullscreen.
+        if !matches!(fullscreen, Some(Fullscreen::Exclusive(_)))
+            && self.ivars().gamma_ramp.take().is_some()
+        {
+            if let Err(status) = self.update_gamma_ramp() {
+                warn!("Failed to restore the gamma ramp: CGError {status}");
+            }
+        }
+
         // If the fullscreen is on a different monitor, we must move the window
         // to that monitor before we toggle fullscreen (as `toggleFullScreen`
         // does not take a screen parameter, but uses the current screen)
@@ -1502,6 +1858,82 @@ impl WindowDelegate {
         };
     }
 
+    pub fn set_gamma_ramp(&self, ramp: Option<&GammaRamp>) -> Result<(), RequestError> {
+        if ramp.is_some()
+            && !matches!(*self.ivars().fullscreen.borrow(), Some(Fullscreen::Exclusive(_)))
+        {
+            return Err(RequestError::Ignored);
+        }
+        self.ivars().gamma_ramp.replace(ramp.cloned());
+        self.update_gamma_ramp()
+            .map_err(|status| RequestError::from(os_error!(format!("CGError {status}"))))
+    }
+
+    /// The array of the window number, for the private APIs of the Spaces.
+    fn window_ids(&self) -> Retained<NSArray<NSNumber>> {
+        let window_number = unsafe { self.window().windowNumber() };
+        NSArray::from_vec(vec![NSNumber::new_isize(window_number)])
+    }
+
+    /// Enable the secure event input while the window is key, balancing the calls to
+    /// `EnableSecureEventInput` and `DisableSecureEventInput`.
+    fn update_secure_input(&self) {
+        let enable = self.ivars().secure_input.get() && self.window().isKeyWindow();
+        if enable == self.ivars().secure_input_enabled.get() {
+            return;
+        }
+        let status = unsafe {
+            if enable {
+                ffi::EnableSecureEventInput()
+            } else {
+                ffi::DisableSecureEventInput()
+            }
+        };
+        if status != 0 {
+            warn!(status, enable, "failed to toggle the secure event input");
+            return;
+        }
+        self.ivars().secure_input_enabled.set(enable);
+    }
+
+    /// Apply the gamma ramp to the display while the window is key in exclusive fullscreen, and
+    /// restore the previous gamma tables of the display otherwise.
+    fn update_gamma_ramp(&self) -> Result<(), CGError> {
+        let target = match (&*self.ivars().fullscreen.borrow(), &*self.ivars().gamma_ramp.borrow())
+        {
+            (Some(Fullscreen::Exclusive(video_mode)), Some(ramp))
+                if self.window().isKeyWindow() =>
+            {
+                let tables = ramp.resample(ramp.red().len()).map(|table| {
+                    table
+                        .into_iter()
+                        .map(|value| value as f32 / u16::MAX as f32)
+                        .collect::<Vec<_>>()
+                });
+                Some((video_mode.monitor().native_identifier(), tables))
+            },
+            _ => None,
+        };
+
+        let mut saved_gamma_ramp = self.ivars().saved_gamma_ramp.borrow_mut();
+        if let Some((display, saved)) = saved_gamma_ramp.take() {
+            if target.as_ref().is_some_and(|(target, _)| *target == display) {
+                *saved_gamma_ramp = Some((display, saved));
+            } else {
+                monitor::set_display_transfer(display, &saved)?;
+            }
+        }
+
+        if let Some((display, tables)) = target {
+            if saved_gamma_ramp.is_none() {
+                *saved_gamma_ramp = Some((display, monitor::display_transfer(display)?));
+            }
+            monitor::set_display_transfer(display, &tables)?;
+        }
+
+        Ok(())
+    }
+
     #[inline]
     pub fn set_decorations(&self, decorations: bool) {
         if decorations == self.ivars().decorations.get() {
@@ -1581,7 +2013,14 @@ impl WindowDelegate {
     }
 
     #[inline]
-    pub fn set_ime_purpose(&self, _purpose: ImePurpose) {}
+    pub fn set_ime_purpose(&self, purpose: ImePurpose) {
+        self.view().set_ime_purpose(purpose);
+    }
+
+    #[inline]
+    pub fn set_ime_surrounding_text(&self, text: &str, cursor: usize, anchor: usize) {
+        self.view().set_ime_surrounding_text(text, cursor, anchor);
+    }
 
     #[inline]
     pub fn focus_window(&self) {
@@ -1608,6 +2047,77 @@ impl WindowDelegate {
         }
     }
 
+    pub fn announce(&self, message: &str, politeness: Politeness) {
+        let priority = match politeness {
+            Politeness::Polite => ffi::NSAccessibilityPriorityMedium,
+            Politeness::Assertive => ffi::NSAccessibilityPriorityHigh,
+        };
+        let message = NSString::from_str(message);
+        let priority = NSNumber::new_isize(priority);
+        unsafe {
+            let user_info = NSDictionary::from_vec(
+                &[ffi::NSAccessibilityAnnouncementKey, ffi::NSAccessibilityPriorityKey],
+                vec![
+                    Retained::into_super(message),
+                    Retained::into_super(Retained::into_super(priority)),
+                ],
+            );
+            ffi::NSAccessibilityPostNotificationWithUserInfo(
+                self.window(),
+                ffi::NSAccessibilityAnnouncementRequestedNotification,
+                &user_info,
+            );
+        }
+    }
+
+    /// Move and hide the traffic lights, which AppKit puts back when it lays out the titlebar,
+    /// e.g. on resize or when exiting fullscreen.
+    fn update_traffic_lights(&self) {
+        let window = self.window();
+        // The titlebar is in another window in fullscreen.
+        if window.styleMask().contains(NSWindowStyleMask::FullScreen) {
+            return;
+        }
+
+        let hidden = self.ivars().hidden_traffic_lights.get();
+        let buttons = [
+            (NSWindowButton::NSWindowCloseButton, WindowButtons::CLOSE),
+            (NSWindowButton::NSWindowMiniaturizeButton, WindowButtons::MINIMIZE),
+            (NSWindowButton::NSWindowZoomButton, WindowButtons::MAXIMIZE),
+        ]
+        .map(|(kind, flag)| (window.standardWindowButton(kind), flag));
+        for (button, flag) in &buttons {
+            if let (Some(button), true) = (button, hidden.contains(*flag)) {
+                button.setHidden(true);
+            }
+        }
+
+        let Some(inset) = self.ivars().traffic_light_inset.get() else { return };
+        let [(Some(close), _), (Some(miniaturize), _), _] = &buttons else { return };
+        // The buttons are in the titlebar view, itself in the titlebar container view.
+        let Some(container) =
+            (unsafe { close.superview() }).and_then(|titlebar| unsafe { titlebar.superview() })
+        else {
+            return;
+        };
+
+        // The titlebar is enlarged to keep the buttons vertically centered in it.
+        let close_frame = close.frame();
+        let titlebar_height = close_frame.size.height + 2.0 * inset.y;
+        let mut container_frame = container.frame();
+        container_frame.size.height = titlebar_height;
+        container_frame.origin.y = window.frame().size.height - titlebar_height;
+        unsafe { container.setFrame(container_frame) };
+
+        let spacing = miniaturize.frame().origin.x - close_frame.origin.x;
+        for (index, (button, _)) in buttons.iter().enumerate() {
+            if let Some(button) = button {
+                let origin = NSPoint::new(inset.x + index as f64 * spacing, inset.y);
+                unsafe { button.setFrameOrigin(origin) };
+            }
+        }
+    }
+
     #[inline]
     // Allow directly accessing the current monitor internally without unwrapping.
     pub(crate) fn current_monitor_inner(&self) -> Option<MonitorHandle> {
@@ -1641,6 +2151,51 @@ impl WindowDelegate {
         rwh_06::RawWindowHandle::AppKit(window_handle)
     }
 
+    /// The presentation options of the fullscreen mode of AppKit, in place of the proposed ones.
+    fn fullscreen_presentation_options_for(
+        &self,
+        proposed_options: NSApplicationPresentationOptions,
+    ) -> NSApplicationPresentationOptions {
+        // Generally, games will want to disable the menu bar and the dock. Unfortunately
+        // because of our `CGShieldingWindowLevel() + 1` hack (see `set_fullscreen`), our
+        // window is placed on top of the menu bar in exclusive fullscreen mode. This looks
+        // broken so we always disable the menu bar in exclusive fullscreen. The user may
+        // configure the other options with `set_fullscreen_presentation_options`.
+        let mut options = proposed_options;
+        let user_options = self.ivars().fullscreen_presentation_options.get();
+        let fullscreen = self.ivars().fullscreen.borrow();
+        if let Some(Fullscreen::Exclusive(_)) = &*fullscreen {
+            options = NSApplicationPresentationOptions::NSApplicationPresentationFullScreen
+                | NSApplicationPresentationOptions::NSApplicationPresentationHideDock
+                | NSApplicationPresentationOptions::NSApplicationPresentationHideMenuBar;
+            if let Some(user_options) = user_options {
+                options |= presentation_options(
+                    user_options
+                        - FullscreenPresentationOptions::AUTO_HIDE_DOCK
+                        - FullscreenPresentationOptions::AUTO_HIDE_MENU_BAR,
+                );
+            }
+        } else if let Some(user_options) = user_options {
+            options = NSApplicationPresentationOptions::NSApplicationPresentationFullScreen
+                | presentation_options(user_options);
+        }
+
+        options
+    }
+
+    fn simple_fullscreen_presentation_options(&self) -> NSApplicationPresentationOptions {
+        match self.ivars().fullscreen_presentation_options.get() {
+            // The toolbar can only be hidden in the fullscreen mode of AppKit.
+            Some(options) => {
+                presentation_options(options - FullscreenPresentationOptions::AUTO_HIDE_TOOLBAR)
+            },
+            None => {
+                NSApplicationPresentationOptions::NSApplicationPresentationAutoHideDock
+                    | NSApplicationPresentationOptions::NSApplicationPresentationAutoHideMenuBar
+            },
+        }
+    }
+
     fn toggle_style_mask(&self, mask: NSWindowStyleMask, on: bool) {
         let current_style_mask = self.window().styleMask();
         if on {
@@ -1655,6 +2210,16 @@ impl WindowDelegate {
         self.window().isKeyWindow()
     }
 
+    pub fn set_keyboard_capture(&self, capture: bool) -> Result<(), RequestError> {
+        let mut keyboard_capture = self.ivars().keyboard_capture.borrow_mut();
+        if !capture {
+            *keyboard_capture = None;
+        } else if keyboard_capture.is_none() {
+            *keyboard_capture = Some(KeyboardCapture::new(self.window())?);
+        }
+        Ok(())
+    }
+
     pub fn theme(&self) -> Option<Theme> {
         unsafe { self.window().appearance() }
             .map(|appearance| appearance_to_theme(&appearance))
@@ -1742,10 +2307,7 @@ impl WindowExtMacOS for WindowDelegate {
             self.ivars().is_simple_fullscreen.set(true);
 
             // Simulate pre-Lion fullscreen by hiding the dock and menu bar
-            let presentation_options =
-                NSApplicationPresentationOptions::NSApplicationPresentationAutoHideDock
-                    | NSApplicationPresentationOptions::NSApplicationPresentationAutoHideMenuBar;
-            app.setPresentationOptions(presentation_options);
+            app.setPresentationOptions(self.simple_fullscreen_presentation_options());
 
             // Hide the titlebar
             self.toggle_style_mask(NSWindowStyleMask::Titled, false);
@@ -1825,6 +2387,23 @@ impl WindowExtMacOS for WindowDelegate {
         unsafe { self.window().tabbedWindows() }.map(|windows| windows.len()).unwrap_or(1)
     }
 
+    #[inline]
+    fn move_tab_to_new_window(&self) {
+        unsafe { self.window().moveTabToNewWindow(None) }
+    }
+
+    #[inline]
+    fn set_tab_title(&self, title: Option<&str>) {
+        let title = title.map(NSString::from_str);
+        unsafe { self.window().tab().setTitle(title.as_deref()) }
+    }
+
+    #[inline]
+    fn set_tab_tooltip(&self, tooltip: Option<&str>) {
+        let tooltip = tooltip.map(NSString::from_str);
+        unsafe { self.window().tab().setToolTip(tooltip.as_deref()) }
+    }
+
     fn is_document_edited(&self) -> bool {
         self.window().isDocumentEdited()
     }
@@ -1876,6 +2455,312 @@ impl WindowExtMacOS for WindowDelegate {
             window.toolbar().is_some() && window.toolbarStyle() == NSWindowToolbarStyle::Unified
         }
     }
+
+    fn set_touch_bar(&self, touch_bar: TouchBarDescription) {
+        let touch_bar = touch_bar::create_touch_bar(&self.view(), &touch_bar);
+        unsafe { self.window().setTouchBar(touch_bar.as_deref()) };
+    }
+
+    fn set_represented_url(&self, path: Option<&Path>) {
+        let url = path.map(|path| unsafe {
+            NSURL::fileURLWithPath(&NSString::from_str(&path.to_string_lossy()))
+        });
+        unsafe { self.window().setRepresentedURL(url.as_deref()) };
+    }
+
+    fn represented_url(&self) -> Option<PathBuf> {
+        let url = unsafe { self.window().representedURL() }?;
+        let path = unsafe { url.path() }?;
+        Some(PathBuf::from(path.to_string()))
+    }
+
+    fn begin_sheet(&self, sheet: &dyn CoreWindow) {
+        let sheet = sheet_window(sheet, MainThreadMarker::from(self));
+        let app_state = Rc::clone(&self.ivars().app_state);
+        let window_id = self.window().id();
+        let sheet_id = sheet.id();
+        // Called once the sheet was ended, which also orders it out.
+        let handler = RcBlock::new(move |_response: NSModalResponse| {
+            app_state.maybe_queue_with_handler(move |app, event_loop| {
+                if let Some(handler) = app.macos_handler() {
+                    handler.sheet_ended(event_loop, window_id, sheet_id);
+                }
+            });
+        });
+        unsafe { self.window().beginSheet_completionHandler(sheet, Some(&handler)) };
+    }
+
+    fn end_sheet(&self, sheet: &dyn CoreWindow) {
+        let sheet = sheet_window(sheet, MainThreadMarker::from(self));
+        unsafe { self.window().endSheet(sheet) };
+    }
+
+    fn set_traffic_light_inset(&self, inset: Option<LogicalPosition<f64>>) {
+        self.ivars().traffic_light_inset.set(inset);
+        if inset.is_some() {
+            self.update_traffic_lights();
+        } else {
+            // Setting the style mask makes AppKit lay out the titlebar again.
+            self.set_style_mask(self.window().styleMask());
+        }
+    }
+
+    fn traffic_light_inset(&self) -> Option<LogicalPosition<f64>> {
+        self.ivars().traffic_light_inset.get()
+    }
+
+    fn set_hidden_traffic_lights(&self, buttons: WindowButtons) {
+        let previous = self.ivars().hidden_traffic_lights.replace(buttons);
+        for (kind, flag) in [
+            (NSWindowButton::NSWindowCloseButton, WindowButtons::CLOSE),
+            (NSWindowButton::NSWindowMiniaturizeButton, WindowButtons::MINIMIZE),
+            (NSWindowButton::NSWindowZoomButton, WindowButtons::MAXIMIZE),
+        ] {
+            if previous.contains(flag) && !buttons.contains(flag) {
+                if let Some(button) = self.window().standardWindowButton(kind) {
+                    button.setHidden(false);
+                }
+            }
+        }
+        self.update_traffic_lights();
+    }
+
+    fn hidden_traffic_lights(&self) -> WindowButtons {
+        self.ivars().hidden_traffic_lights.get()
+    }
+
+    fn set_vibrancy(
+        &self,
+        material: VibrancyMaterial,
+        blending_mode: VibrancyBlendingMode,
+        state: VibrancyState,
+    ) {
+        self.remove_vibrancy();
+
+        let mtm = MainThreadMarker::from(self);
+        let view = self.view();
+        let Some(superview) = (unsafe { view.superview() }) else { return };
+        let effect_view = unsafe { NSVisualEffectView::initWithFrame(mtm.alloc(), view.frame()) };
+        let material = match material {
+            VibrancyMaterial::Titlebar => NSVisualEffectMaterial::Titlebar,
+            VibrancyMaterial::Selection => NSVisualEffectMaterial::Selection,
+            VibrancyMaterial::Menu => NSVisualEffectMaterial::Menu,
+            VibrancyMaterial::Popover => NSVisualEffectMaterial::Popover,
+            VibrancyMaterial::Sidebar => NSVisualEffectMaterial::Sidebar,
+            VibrancyMaterial::HeaderView => NSVisualEffectMaterial::HeaderView,
+            VibrancyMaterial::Sheet => NSVisualEffectMaterial::Sheet,
+            VibrancyMaterial::WindowBackground => NSVisualEffectMaterial::WindowBackground,
+            VibrancyMaterial::HudWindow => NSVisualEffectMaterial::HUDWindow,
+            VibrancyMaterial::FullScreenUi => NSVisualEffectMaterial::FullScreenUI,
+            VibrancyMaterial::ToolTip => NSVisualEffectMaterial::ToolTip,
+            VibrancyMaterial::ContentBackground => NSVisualEffectMaterial::ContentBackground,
+            VibrancyMaterial::UnderWindowBackground => {
+                NSVisualEffectMaterial::UnderWindowBackground
+            },
+            VibrancyMaterial::UnderPageBackground => NSVisualEffectMaterial::UnderPageBackground,
+        };
+        let blending_mode = match blending_mode {
+            VibrancyBlendingMode::BehindWindow => NSVisualEffectBlendingMode::BehindWindow,
+            VibrancyBlendingMode::WithinWindow => NSVisualEffectBlendingMode::WithinWindow,
+        };
+        let state = match state {
+            VibrancyState::FollowsWindowActiveState => {
+                NSVisualEffectState::FollowsWindowActiveState
+            },
+            VibrancyState::Active => NSVisualEffectState::Active,
+            VibrancyState::Inactive => NSVisualEffectState::Inactive,
+        };
+        unsafe {
+            effect_view.setMaterial(material);
+            effect_view.setBlendingMode(blending_mode);
+            effect_view.setState(state);
+            effect_view.setAutoresizingMask(
+                NSAutoresizingMaskOptions::NSViewWidthSizable
+                    | NSAutoresizingMaskOptions::NSViewHeightSizable,
+            );
+            // A sibling below the view, which is the content view of the window.
+            superview.addSubview_positioned_relativeTo(
+                &effect_view,
+                NSWindowOrderingMode::NSWindowBelow,
+                Some(&view),
+            );
+        }
+        *self.ivars().vibrancy_view.borrow_mut() = Some(effect_view);
+    }
+
+    fn remove_vibrancy(&self) {
+        if let Some(effect_view) = self.ivars().vibrancy_view.take() {
+            unsafe { effect_view.removeFromSuperview() };
+        }
+    }
+
+    fn set_fullscreen_presentation_options(&self, options: Option<FullscreenPresentationOptions>) {
+        self.ivars().fullscreen_presentation_options.set(options);
+
+        let app = NSApplication::sharedApplication(MainThreadMarker::from(self));
+        if self.ivars().is_simple_fullscreen.get() {
+            app.setPresentationOptions(self.simple_fullscreen_presentation_options());
+        } else if matches!(*self.ivars().fullscreen.borrow(), Some(Fullscreen::Borderless(_)))
+            && !self.ivars().in_fullscreen_transition.get()
+        {
+            // AppKit only asks the delegate when entering fullscreen.
+            let options = self.fullscreen_presentation_options_for(app.presentationOptions());
+            app.setPresentationOptions(options);
+        }
+    }
+
+    fn fullscreen_presentation_options(&self) -> Option<FullscreenPresentationOptions> {
+        self.ivars().fullscreen_presentation_options.get()
+    }
+
+    fn set_appearance(&self, appearance: Option<AppearanceName>) {
+        let appearance = appearance.and_then(|name| {
+            let appearance = NSAppearance::appearanceNamed(appearance_name(name));
+            if appearance.is_none() {
+                warn!(?name, "could not find appearance");
+            }
+            appearance
+        });
+        unsafe { self.window().setAppearance(appearance.as_deref()) };
+    }
+
+    fn set_secure_input(&self, enabled: bool) {
+        self.ivars().secure_input.set(enabled);
+        self.update_secure_input();
+    }
+
+    fn is_secure_input(&self) -> bool {
+        self.ivars().secure_input.get()
+    }
+
+    fn set_collection_behavior(&self, behavior: CollectionBehavior) {
+        let behavior = NSWindowCollectionBehavior(behavior.bits() as NSUInteger);
+        unsafe { self.window().setCollectionBehavior(behavior) };
+    }
+
+    fn collection_behavior(&self) -> CollectionBehavior {
+        let behavior = unsafe { self.window().collectionBehavior() };
+        CollectionBehavior::from_bits_truncate(behavior.0 as u32)
+    }
+
+    fn space(&self) -> Option<SpaceId> {
+        let window_ids = self.window_ids();
+        let spaces = unsafe {
+            ffi::CGSCopySpacesForWindows(
+                ffi::CGSMainConnectionID(),
+                ffi::kCGSAllSpacesMask,
+                Retained::as_ptr(&window_ids).cast(),
+            )
+        };
+        // SAFETY: The array is created, and toll-free bridged with `NSArray`.
+        let spaces: Retained<NSArray<NSNumber>> =
+            unsafe { Retained::from_raw(spaces as *mut NSArray<NSNumber>) }?;
+        // The window is on no Space when it's hidden, and on several when it joins all of them.
+        if spaces.len() != 1 {
+            return None;
+        }
+        Some(SpaceId(spaces[0].unsignedLongLongValue()))
+    }
+
+    fn move_to_space(&self, space: SpaceId) {
+        let window_ids = self.window_ids();
+        unsafe {
+            ffi::CGSMoveWindowsToManagedSpace(
+                ffi::CGSMainConnectionID(),
+                Retained::as_ptr(&window_ids).cast(),
+                space.0,
+            )
+        };
+    }
+
+    fn set_user_activity(&self, activity: Option<UserActivity>) {
+        let activity = activity.map(|activity| user_activity::create_ns_user_activity(&activity));
+        unsafe { self.window().setUserActivity(activity.as_deref()) };
+    }
+
+    fn appearance(&self) -> Option<AppearanceName> {
+        let name = unsafe { self.window().appearance()?.name() };
+        [
+            AppearanceName::Aqua,
+            AppearanceName::DarkAqua,
+            AppearanceName::VibrantLight,
+            AppearanceName::VibrantDark,
+            AppearanceName::AccessibilityHighContrastAqua,
+            AppearanceName::AccessibilityHighContrastDarkAqua,
+            AppearanceName::AccessibilityHighContrastVibrantLight,
+            AppearanceName::AccessibilityHighContrastVibrantDark,
+        ]
+        .into_iter()
+        .find(|appearance| *appearance_name(*appearance) == *name)
+    }
+}
+
+fn presentation_options(
+    options: FullscreenPresentationOptions,
+) -> NSApplicationPresentationOptions {
+    let mut presentation_options = NSApplicationPresentationOptions::empty();
+    for (option, presentation_option) in [
+        (
+            FullscreenPresentationOptions::AUTO_HIDE_DOCK,
+            NSApplicationPresentationOptions::NSApplicationPresentationAutoHideDock,
+        ),
+        (
+            FullscreenPresentationOptions::HIDE_DOCK,
+            NSApplicationPresentationOptions::NSApplicationPresentationHideDock,
+        ),
+        (
+            FullscreenPresentationOptions::AUTO_HIDE_MENU_BAR,
+            NSApplicationPresentationOptions::NSApplicationPresentationAutoHideMenuBar,
+        ),
+        (
+            FullscreenPresentationOptions::HIDE_MENU_BAR,
+            NSApplicationPresentationOptions::NSApplicationPresentationHideMenuBar,
+        ),
+        (
+            FullscreenPresentationOptions::DISABLE_PROCESS_SWITCHING,
+            NSApplicationPresentationOptions::NSApplicationPresentationDisableProcessSwitching,
+        ),
+        (
+            FullscreenPresentationOptions::DISABLE_FORCE_QUIT,
+            NSApplicationPresentationOptions::NSApplicationPresentationDisableForceQuit,
+        ),
+        (
+            FullscreenPresentationOptions::DISABLE_SESSION_TERMINATION,
+            NSApplicationPresentationOptions::NSApplicationPresentationDisableSessionTermination,
+        ),
+        (
+            FullscreenPresentationOptions::DISABLE_HIDE_APPLICATION,
+            NSApplicationPresentationOptions::NSApplicationPresentationDisableHideApplication,
+        ),
+        (
+            FullscreenPresentationOptions::AUTO_HIDE_TOOLBAR,
+            NSApplicationPresentationOptions::NSApplicationPresentationAutoHideToolbar,
+        ),
+    ] {
+        if options.contains(option) {
+            presentation_options |= presentation_option;
+        }
+    }
+    presentation_options
+}
+
+fn sheet_window(sheet: &dyn CoreWindow, mtm: MainThreadMarker) -> &WinitWindow {
+    sheet.as_any().downcast_ref::<Window>().unwrap().ns_window(mtm)
+}
+
+/// Lock the cursor again for the windows which grabbed it before a native modal dialog.
+pub(super) fn restore_cursor_grabs(mtm: MainThreadMarker) {
+    for window in NSApplication::sharedApplication(mtm).windows().iter() {
+        if !window.is_kind_of::<WinitWindow>() {
+            continue;
+        }
+
+        // SAFETY: The delegate of our windows is always a `WindowDelegate`, until it's dropped.
+        if let Some(delegate) = unsafe { window.delegate() } {
+            let delegate: Retained<WindowDelegate> = unsafe { Retained::cast(delegate) };
+            delegate.restore_cursor_grab();
+        }
+    }
 }
 
 const DEFAULT_STANDARD_FRAME: NSRect =
@@ -1886,6 +2771,28 @@ fn dark_appearance_name() -> &'static NSString {
     ns_string!("NSAppearanceNameDarkAqua")
 }
 
+// Don't use the statics to allow linking on the versions of macOS without all the appearances.
+fn appearance_name(appearance: AppearanceName) -> &'static NSString {
+    match appearance {
+        AppearanceName::Aqua => ns_string!("NSAppearanceNameAqua"),
+        AppearanceName::DarkAqua => dark_appearance_name(),
+        AppearanceName::VibrantLight => ns_string!("NSAppearanceNameVibrantLight"),
+        AppearanceName::VibrantDark => ns_string!("NSAppearanceNameVibrantDark"),
+        AppearanceName::AccessibilityHighContrastAqua => {
+            ns_string!("NSAppearanceNameAccessibilityHighContrastAqua")
+        },
+        AppearanceName::AccessibilityHighContrastDarkAqua => {
+            ns_string!("NSAppearanceNameAccessibilityHighContrastDarkAqua")
+        },
+        AppearanceName::AccessibilityHighContrastVibrantLight => {
+            ns_string!("NSAppearanceNameAccessibilityHighContrastVibrantLight")
+        },
+        AppearanceName::AccessibilityHighContrastVibrantDark => {
+            ns_string!("NSAppearanceNameAccessibilityHighContrastVibrantDark")
+        },
+    }
+}
+
 pub fn appearance_to_theme(appearance: &NSAppearance) -> Theme {
     let best_match = appearance.bestMatchFromAppearancesWithNames(&NSArray::from_id_slice(&[
         unsafe { NSAppearanceNameAqua.copy() },
@@ -1904,6 +2811,25 @@ pub fn appearance_to_theme(appearance: &NSAppearance) -> Theme {
     }
 }
 
+pub fn accent_color() -> Option<Rgba> {
+    // `controlAccentColor` is only available since macOS 10.14.
+    if !NSColor::class().responds_to(sel!(controlAccentColor)) {
+        return None;
+    }
+
+    let color = unsafe { NSColor::controlAccentColor() };
+    let color = unsafe { color.colorUsingColorSpace(&NSColorSpace::sRGBColorSpace()) }?;
+    let channel = |value: CGFloat| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+    unsafe {
+        Some(Rgba::new(
+            channel(color.redComponent()),
+            channel(color.greenComponent()),
+            channel(color.blueComponent()),
+            channel(color.alphaComponent()),
+        ))
+    }
+}
+
 fn theme_to_appearance(theme: Option<Theme>) -> Option<Retained<NSAppearance>> {
     let appearance = match theme? {
         Theme::Light => unsafe { NSAppearance::appearanceNamed(NSAppearanceNameAqua) },
@@ -1917,3 +2843,38 @@ fn theme_to_appearance(theme: Option<Theme>) -> Option<Retained<NSAppearance>> {
         None
     }
 }
+
+/// Back the view with a `CAMetalLayer` configured for the format hint.
+///
+/// Renderers reuse the layer of the view when it's already a `CAMetalLayer`.
+fn set_surface_format_hint(view: &NSView, hint: SurfaceFormatHint) {
+    let layer: Retained<AnyObject> = unsafe { msg_send_id![class!(CAMetalLayer), new] };
+
+    let name = unsafe {
+        match (hint.color_space, hint.hdr) {
+            (SurfaceColorSpace::Srgb, false) => ffi::kCGColorSpaceSRGB,
+            (SurfaceColorSpace::DisplayP3, false) => ffi::kCGColorSpaceDisplayP3,
+            (SurfaceColorSpace::Bt2020, false) => ffi::kCGColorSpaceITUR_2020,
+            (SurfaceColorSpace::Srgb, true) => ffi::kCGColorSpaceExtendedLinearSRGB,
+            (SurfaceColorSpace::DisplayP3, true) => ffi::kCGColorSpaceExtendedLinearDisplayP3,
+            (SurfaceColorSpace::Bt2020, true) => ffi::kCGColorSpaceExtendedLinearITUR_2020,
+        }
+    };
+
+    unsafe {
+        let color_space = ffi::CGColorSpaceCreateWithName(name);
+        if !color_space.is_null() {
+            let _: () = msg_send![&layer, setColorspace: color_space];
+            ffi::CGColorSpaceRelease(color_space);
+        }
+
+        if hint.hdr {
+            let _: () = msg_send![&layer, setWantsExtendedDynamicRangeContent: true];
+            let _: () = msg_send![&layer, setPixelFormat: ffi::MTLPixelFormatRGBA16Float];
+        }
+        let _: () = msg_send![&layer, setOpaque: !hint.alpha];
+
+        let _: () = msg_send![view, setLayer: &*layer];
+    }
+    view.setWantsLayer(true);
+}
@@ -0,0 +1,15 @@
+use objc2_foundation::NSLocale;
+
+use crate::event::LanguageTag;
+
+/// The language the user prefers, falling back to the identifier of the current locale.
+pub fn current_locale() -> LanguageTag {
+    // Preferred languages are already BCP 47 tags, unlike locale identifiers.
+    let languages = unsafe { NSLocale::preferredLanguages() };
+    if let Some(language) = unsafe { languages.firstObject() } {
+        return LanguageTag::new(language.to_string());
+    }
+
+    let identifier = unsafe { NSLocale::currentLocale().localeIdentifier() };
+    LanguageTag::from_posix(&identifier.to_string()).unwrap_or_else(LanguageTag::undetermined)
+}
@@ -3,6 +3,7 @@
 #[cfg(target_os = "macos")]
 mod appkit;
 mod event_handler;
+mod locale;
 mod notification_center;
 #[cfg(not(target_os = "macos"))]
 mod uikit;
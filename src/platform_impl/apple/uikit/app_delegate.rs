@@ -0,0 +1,87 @@
+//! The delegate of `UIApplication`, for the features only available to the delegate. It's only
+//! installed when the application doesn't already have one.
+
+use std::path::PathBuf;
+
+use objc2::rc::Retained;
+use objc2::runtime::AnyObject;
+use objc2::{declare_class, msg_send, msg_send_id, mutability, ClassType, DeclaredClass};
+use objc2_foundation::{MainThreadMarker, NSDictionary, NSObject, NSObjectProtocol, NSURL};
+use objc2_ui_kit::UIApplication;
+use tracing::warn;
+
+use super::app_state::{self, EventWrapper};
+use crate::event::Event;
+
+declare_class!(
+    /// The delegate of `UIApplication`, opening the files and URLs.
+    #[derive(Debug)]
+    pub(crate) struct ApplicationDelegate;
+
+    unsafe impl ClassType for ApplicationDelegate {
+        type Super = NSObject;
+        type Mutability = mutability::MainThreadOnly;
+        const NAME: &'static str = "WinitUIApplicationDelegate";
+    }
+
+    impl DeclaredClass for ApplicationDelegate {}
+
+    unsafe impl NSObjectProtocol for ApplicationDelegate {}
+
+    unsafe impl ApplicationDelegate {
+        // Also sent after launching for the URL the application was launched with.
+        #[method(application:openURL:options:)]
+        fn open_url(
+            &self,
+            _application: &UIApplication,
+            url: &NSURL,
+            _options: &NSDictionary<AnyObject, AnyObject>,
+        ) -> bool {
+            let mtm = MainThreadMarker::from(self);
+            match open_event(url) {
+                Some(event) => {
+                    app_state::handle_nonuser_event(mtm, EventWrapper::StaticEvent(event));
+                    true
+                },
+                None => false,
+            }
+        }
+    }
+);
+
+/// The event delivering the opened file or URL.
+fn open_event(url: &NSURL) -> Option<Event> {
+    if unsafe { url.isFileURL() } {
+        let path = unsafe { url.path() }?;
+        Some(Event::OpenFiles(vec![PathBuf::from(path.to_string())]))
+    } else {
+        let url = unsafe { url.absoluteString() }?;
+        Some(Event::OpenUrls(vec![url.to_string()]))
+    }
+}
+
+impl ApplicationDelegate {
+    fn new(mtm: MainThreadMarker) -> Retained<Self> {
+        let this = mtm.alloc().set_ivars(());
+        unsafe { msg_send_id![super(this), init] }
+    }
+
+    /// Install the delegate, unless the application already has one, in which case the files and
+    /// URLs aren't delivered.
+    pub(crate) fn install(mtm: MainThreadMarker) -> Option<Retained<Self>> {
+        let app = UIApplication::sharedApplication(mtm);
+        let current: Option<Retained<AnyObject>> = unsafe { msg_send_id![&app, delegate] };
+        if current.is_some() {
+            warn!(
+                "the application already has a delegate, the opened files and URLs won't be \
+                 handled"
+            );
+            return None;
+        }
+
+        let delegate = Self::new(mtm);
+        // `UIApplication` doesn't retain its delegate.
+        let _: () = unsafe { msg_send![&app, setDelegate: &*delegate] };
+        Some(delegate)
+    }
+}
@@ -23,12 +23,15 @@ use objc2_foundation::{
 use objc2_ui_kit::{UIApplication, UICoordinateSpace, UIView, UIWindow};
 
 use super::super::event_handler::EventHandler;
+use super::app_delegate::ApplicationDelegate;
 use super::window::WinitUIWindow;
 use super::ActiveEventLoop;
 use crate::application::ApplicationHandler;
-use crate::dpi::PhysicalSize;
+use crate::dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize};
 use crate::event::{Event, StartCause, SurfaceSizeWriter, WindowEvent};
-use crate::event_loop::ControlFlow;
+use crate::event_loop::{ActiveEventLoop as _, ControlFlow, ExitResponse};
+use crate::utils::RedrawSchedule;
+use crate::window::WindowId;
 
 macro_rules! bug {
     ($($msg:tt)*) => {
@@ -78,8 +81,48 @@ fn handle_event(mtm: MainThreadMarker, event: Event) {
         Event::Resumed => app.resumed(event_loop),
         Event::CreateSurfaces => app.can_create_surfaces(event_loop),
         Event::AboutToWait => app.about_to_wait(event_loop),
+        Event::ExitRequested => {
+            if app.exit_requested(event_loop) == ExitResponse::Exit {
+                event_loop.exit();
+            }
+        },
         Event::LoopExiting => app.exiting(event_loop),
         Event::MemoryWarning => app.memory_warning(event_loop),
+        Event::PowerEvent(event) => app.power_event(event_loop, event),
+        Event::ReopenRequested => app.reopen_requested(event_loop),
+        Event::OpenFiles(paths) => app.open_files(event_loop, paths),
+        Event::OpenUrls(urls) => app.open_urls(event_loop, urls),
+        Event::SessionLocked => app.session_locked(event_loop),
+        Event::SessionUnlocked => app.session_unlocked(event_loop),
+        Event::ClipboardData { serial, data } => app.clipboard_data(event_loop, serial, data),
+        Event::ClipboardChanged => app.clipboard_changed(event_loop),
+        Event::InputDeviceAdded(device) => app.input_device_added(event_loop, device),
+        Event::InputDeviceRemoved(device_id) => app.input_device_removed(event_loop, device_id),
+        Event::LocaleChanged(locale) => app.locale_changed(event_loop, locale),
+        Event::CursorThemeChanged(theme) => app.cursor_theme_changed(event_loop, theme),
+        Event::SystemPreferencesChanged(preferences) => {
+            app.system_preferences_changed(event_loop, preferences)
+        },
+        Event::TextScaleFactorChanged(factor) => app.text_scale_factor_changed(event_loop, factor),
+        Event::MonitorAdded(monitor) => app.monitor_added(event_loop, monitor),
+        Event::MonitorRemoved(monitor) => app.monitor_removed(event_loop, monitor),
+        Event::ColorCapabilitiesChanged(monitor) => {
+            app.color_capabilities_changed(event_loop, monitor)
+        },
+        Event::MonitorOrientationChanged(monitor) => {
+            app.monitor_orientation_changed(event_loop, monitor)
+        },
+        Event::MonitorRefreshRateChanged(monitor) => {
+            app.monitor_refresh_rate_changed(event_loop, monitor)
+        },
+        #[cfg(feature = "dialogs")]
+        Event::FileDialogDone { serial, result } => {
+            app.file_dialog_done(event_loop, serial, result)
+        },
+        #[cfg(feature = "dialogs")]
+        Event::MessageBoxDone { serial, result } => {
+            app.message_box_done(event_loop, serial, result)
+        },
     })
 }
 
@@ -140,6 +183,11 @@ pub(crate) struct AppState {
     control_flow: ControlFlow,
     waker: EventLoopWaker,
     proxy_wake_up: Arc<AtomicBool>,
+    redraw_schedule: RedrawSchedule,
+    /// Wakes up the run loop when the next scheduled redraw is due.
+    redraw_waker: EventLoopWaker,
+    /// Our delegate of `UIApplication`, which doesn't retain it.
+    delegate: Option<Retained<ApplicationDelegate>>,
 }
 
 impl AppState {
@@ -165,6 +213,9 @@ impl AppState {
                     control_flow: ControlFlow::default(),
                     waker,
                     proxy_wake_up: Arc::new(AtomicBool::new(false)),
+                    redraw_schedule: RedrawSchedule::default(),
+                    redraw_waker: EventLoopWaker::new(unsafe { CFRunLoopGetMain() }),
+                    delegate: None,
                 });
             }
             init_guard(&mut guard);
@@ -380,6 +431,13 @@ impl AppState {
         self.proxy_wake_up.clone()
     }
 
+    fn update_redraw_waker(&mut self) {
+        match self.redraw_schedule.next_deadline() {
+            Some(deadline) => self.redraw_waker.start_at(deadline),
+            None => self.redraw_waker.stop(),
+        }
+    }
+
     pub(crate) fn set_control_flow(&mut self, control_flow: ControlFlow) {
         self.control_flow = control_flow;
     }
@@ -414,6 +472,7 @@ pub fn did_finish_launching(mtm: MainThreadMarker) {
     let mut this = AppState::get_mut(mtm);
 
     this.waker.start();
+    this.delegate = ApplicationDelegate::install(mtm);
 
     // have to drop RefMut because the window setup code below can trigger new events
     drop(this);
@@ -606,6 +665,59 @@ pub(crate) fn send_occluded_event_for_all_windows(application: &UIApplication, o
     handle_nonuser_events(mtm, events);
 }
 
+/// Report the frame of the on-screen keyboard, in the coordinates of the screen, to all windows.
+pub(crate) fn send_virtual_keyboard_event_for_all_windows(
+    application: &UIApplication,
+    keyboard_frame: CGRect,
+) {
+    let mtm = MainThreadMarker::from(application);
+
+    let mut events = Vec::new();
+    #[allow(deprecated)]
+    for window in application.windows().iter() {
+        if window.is_kind_of::<WinitUIWindow>() {
+            // SAFETY: We just checked that the window is a `winit` window
+            let window = unsafe {
+                let ptr: *const UIWindow = window;
+                let ptr: *const WinitUIWindow = ptr.cast();
+                &*ptr
+            };
+            let (view, _) = get_view_and_screen_frame(window);
+            let screen_space = window.screen().coordinateSpace();
+            let frame = view.convertRect_fromCoordinateSpace(keyboard_frame, &screen_space);
+
+            // Only the part of the keyboard over the view covers it.
+            let bounds = view.bounds();
+            let left = frame.origin.x.max(bounds.origin.x);
+            let top = frame.origin.y.max(bounds.origin.y);
+            let right =
+                (frame.origin.x + frame.size.width).min(bounds.origin.x + bounds.size.width);
+            let bottom =
+                (frame.origin.y + frame.size.height).min(bounds.origin.y + bounds.size.height);
+            let scale_factor = view.contentScaleFactor();
+            let (position, size) = if right > left && bottom > top {
+                let position = LogicalPosition::new(left, top);
+                let size = LogicalSize::new(right - left, bottom - top);
+                (position.to_physical(scale_factor), size.to_physical(scale_factor))
+            } else {
+                (PhysicalPosition::default(), PhysicalSize::default())
+            };
+
+            events.push(EventWrapper::StaticEvent(Event::WindowEvent {
+                window_id: window.id(),
+                event: WindowEvent::VirtualKeyboardGeometryChanged { position, size },
+            }));
+        }
+    }
+    handle_nonuser_events(mtm, events);
+}
+
+pub(crate) fn schedule_redraw(mtm: MainThreadMarker, window_id: WindowId, instant: Instant) {
+    let mut this = AppState::get_mut(mtm);
+    this.redraw_schedule.schedule(window_id, instant);
+    this.update_redraw_waker();
+}
+
 pub fn handle_main_events_cleared(mtm: MainThreadMarker) {
     let mut this = AppState::get_mut(mtm);
     if !this.has_launched() || this.has_terminated() {
@@ -620,17 +732,25 @@ pub fn handle_main_events_cleared(mtm: MainThreadMarker) {
     handle_user_events(mtm);
 
     let mut this = AppState::get_mut(mtm);
-    let redraw_events: Vec<EventWrapper> = this
-        .main_events_cleared_transition()
+    let mut window_ids: Vec<WindowId> =
+        this.main_events_cleared_transition().into_iter().map(|window| window.id()).collect();
+    for window_id in this.redraw_schedule.take_due(Instant::now()) {
+        if !window_ids.contains(&window_id) {
+            window_ids.push(window_id);
+        }
+    }
+    this.update_redraw_waker();
+    drop(this);
+
+    let redraw_events: Vec<EventWrapper> = window_ids
         .into_iter()
-        .map(|window| {
+        .map(|window_id| {
             EventWrapper::StaticEvent(Event::WindowEvent {
-                window_id: window.id(),
+                window_id,
                 event: WindowEvent::RedrawRequested,
             })
         })
         .collect();
-    drop(this);
 
     handle_nonuser_events(mtm, redraw_events);
     handle_nonuser_event(mtm, EventWrapper::StaticEvent(Event::AboutToWait));
@@ -11,35 +11,151 @@ use core_foundation::runloop::{
     CFRunLoopSourceInvalidate, CFRunLoopSourceRef, CFRunLoopSourceSignal, CFRunLoopWakeUp,
 };
 use objc2::rc::Retained;
-use objc2::{msg_send_id, ClassType};
-use objc2_foundation::{MainThreadMarker, NSNotificationCenter, NSObject};
+use objc2::runtime::Bool;
+use objc2::{class, msg_send, msg_send_id, ClassType};
+use objc2_foundation::{
+    CGFloat, CGRect, MainThreadMarker, NSArray, NSCurrentLocaleDidChangeNotification, NSData,
+    NSDictionary, NSNotificationCenter, NSObject, NSObjectProtocol, NSString,
+};
 use objc2_ui_kit::{
     UIApplication, UIApplicationDidBecomeActiveNotification,
     UIApplicationDidEnterBackgroundNotification, UIApplicationDidFinishLaunchingNotification,
     UIApplicationDidReceiveMemoryWarningNotification, UIApplicationMain,
     UIApplicationWillEnterForegroundNotification, UIApplicationWillResignActiveNotification,
-    UIApplicationWillTerminateNotification, UIScreen,
+    UIApplicationWillTerminateNotification, UIKeyboardFrameEndUserInfoKey,
+    UIKeyboardWillChangeFrameNotification, UIPasteboardChangedNotification, UIScreen, UIWindow,
 };
 
+use super::super::locale::current_locale;
 use super::super::notification_center::create_observer;
-use super::app_state::{send_occluded_event_for_all_windows, AppState, EventWrapper};
+use super::app_state::{
+    send_occluded_event_for_all_windows, send_virtual_keyboard_event_for_all_windows, AppState,
+    EventWrapper,
+};
+use super::window::WinitUIWindow;
 use super::{app_state, monitor, MonitorHandle};
 use crate::application::ApplicationHandler;
+use crate::clipboard::{
+    Clipboard as RootClipboard, ClipboardData, ClipboardFormat, ClipboardImage,
+};
+#[cfg(feature = "dialogs")]
+use crate::dialogs::{FileDialogOptions, MessageBoxConfig};
+use crate::drag::DragData;
 use crate::error::{EventLoopError, NotSupportedError, RequestError};
-use crate::event::Event;
+use crate::event::{DeviceInfo, Event, LanguageTag, PlatformData, PowerState, SystemPreferences};
 use crate::event_loop::{
-    ActiveEventLoop as RootActiveEventLoop, ControlFlow, DeviceEvents,
-    EventLoopProxy as RootEventLoopProxy, OwnedDisplayHandle as RootOwnedDisplayHandle,
+    ActiveEventLoop as RootActiveEventLoop, AsyncRequestSerial, ControlFlow, DeviceClass,
+    DeviceEvents, EventLoopProxy as RootEventLoopProxy,
+    OwnedDisplayHandle as RootOwnedDisplayHandle,
 };
+use crate::keyboard::{Key, KeyCode, KeyboardLayout, Led};
 use crate::monitor::MonitorHandle as RootMonitorHandle;
 use crate::platform_impl::Window;
-use crate::window::{CustomCursor, CustomCursorSource, Theme, Window as CoreWindow};
+use crate::window::{
+    Cursor, CursorThemeInfo, CustomCursor, CustomCursorSource, Rgba, Theme, Window as CoreWindow,
+    WindowId,
+};
 
 #[derive(Debug)]
 pub(crate) struct ActiveEventLoop {
     pub(super) mtm: MainThreadMarker,
 }
 
+impl RootClipboard for ActiveEventLoop {
+    fn set(&self, data: Vec<ClipboardData>) -> Result<(), RequestError> {
+        // A single item holding each of the formats, keyed by their uniform type identifier.
+        let mut types: Vec<Retained<NSString>> = Vec::new();
+        let mut values: Vec<Retained<NSObject>> = Vec::new();
+        for data in &data {
+            let (kind, value) = match data {
+                ClipboardData::Text(text) => {
+                    ("public.utf8-plain-text", Retained::into_super(NSString::from_str(text)))
+                },
+                ClipboardData::Html(html) => {
+                    ("public.html", Retained::into_super(NSData::with_bytes(html.as_bytes())))
+                },
+                ClipboardData::Image(image) => {
+                    ("public.png", Retained::into_super(NSData::with_bytes(&image.to_png())))
+                },
+                ClipboardData::Files(_) => {
+                    return Err(NotSupportedError::new("files are not supported on iOS").into())
+                },
+                ClipboardData::Uris(uris) => {
+                    // The item holds a single URL.
+                    let Some(uri) = uris.first() else { continue };
+                    let url: Option<Retained<NSObject>> = unsafe {
+                        msg_send_id![class!(NSURL), URLWithString: &*NSString::from_str(uri)]
+                    };
+                    let Some(url) = url else { continue };
+                    ("public.url", url)
+                },
+                ClipboardData::Custom { mime_type, data } => {
+                    (*mime_type, Retained::into_super(NSData::with_bytes(data)))
+                },
+            };
+            let kind = NSString::from_str(kind);
+            if !types.contains(&kind) {
+                types.push(kind);
+                values.push(value);
+            }
+        }
+
+        let types: Vec<&NSString> = types.iter().map(|kind| &**kind).collect();
+        let items = NSArray::from_vec(vec![NSDictionary::from_vec(&types, values)]);
+        let pasteboard: Retained<NSObject> =
+            unsafe { msg_send_id![class!(UIPasteboard), generalPasteboard] };
+        let _: () = unsafe { msg_send![&pasteboard, setItems: &*items] };
+        Ok(())
+    }
+
+    fn get(&self, format: ClipboardFormat) -> Result<AsyncRequestSerial, RequestError> {
+        let serial = AsyncRequestSerial::get();
+        let pasteboard: Retained<NSObject> =
+            unsafe { msg_send_id![class!(UIPasteboard), generalPasteboard] };
+        let get_data = |kind: &str| -> Option<Vec<u8>> {
+            let kind = NSString::from_str(kind);
+            let data: Option<Retained<NSData>> =
+                unsafe { msg_send_id![&pasteboard, dataForPasteboardType: &*kind] };
+            data.map(|data| data.bytes().to_vec())
+        };
+        let data = match format {
+            ClipboardFormat::Text => {
+                let text: Option<Retained<NSString>> = unsafe { msg_send_id![&pasteboard, string] };
+                text.map(|text| ClipboardData::Text(text.to_string()))
+            },
+            ClipboardFormat::Html => get_data("public.html")
+                .map(|html| ClipboardData::Html(String::from_utf8_lossy(&html).into_owned())),
+            ClipboardFormat::Image => {
+                get_data("public.png").map(|png| ClipboardData::Image(ClipboardImage::Png(png)))
+            },
+            ClipboardFormat::Files => {
+                return Err(NotSupportedError::new("files are not supported on iOS").into())
+            },
+            ClipboardFormat::Uris => {
+                let urls: Option<Retained<NSArray<NSObject>>> =
+                    unsafe { msg_send_id![&pasteboard, URLs] };
+                let uris: Vec<String> = urls
+                    .iter()
+                    .flat_map(|urls| urls.iter())
+                    .filter_map(|url| {
+                        let uri: Option<Retained<NSString>> =
+                            unsafe { msg_send_id![url, absoluteString] };
+                        uri.map(|uri| uri.to_string())
+                    })
+                    .collect();
+                (!uris.is_empty()).then_some(ClipboardData::Uris(uris))
+            },
+            ClipboardFormat::Custom(mime_type) => {
+                get_data(mime_type).map(|data| ClipboardData::Custom { mime_type, data })
+            },
+        };
+        let event = Event::ClipboardData { serial, data };
+        // Queued until the current event is handled.
+        app_state::handle_nonuser_event(self.mtm, EventWrapper::StaticEvent(event));
+        Ok(serial)
+    }
+}
+
 impl RootActiveEventLoop for ActiveEventLoop {
     fn create_proxy(&self) -> crate::event_loop::EventLoopProxy {
         let event_loop_proxy = EventLoopProxy::new(AppState::get_mut(self.mtm).proxy_wake_up());
@@ -72,6 +188,76 @@ impl RootActiveEventLoop for ActiveEventLoop {
 
     fn listen_device_events(&self, _allowed: DeviceEvents) {}
 
+    fn listen_device_classes(&self, _classes: DeviceClass) {}
+
+    fn input_devices(&self) -> Vec<DeviceInfo> {
+        Vec::new()
+    }
+
+    #[cfg(feature = "gamepad")]
+    fn set_gamepad_rumble(
+        &self,
+        _device_id: crate::event::DeviceId,
+        _strong: f32,
+        _weak: f32,
+    ) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("gamepads are not supported").into())
+    }
+
+    fn windows(&self) -> Vec<WindowId> {
+        let application = UIApplication::sharedApplication(self.mtm);
+        #[allow(deprecated)]
+        let windows = application.windows();
+        windows
+            .iter()
+            .filter(|window| window.is_kind_of::<WinitUIWindow>())
+            .map(|window| {
+                // SAFETY: We just checked that the window is a `winit` window
+                let window = unsafe { &*(window as *const UIWindow).cast::<WinitUIWindow>() };
+                window.id()
+            })
+            .collect()
+    }
+
+    fn with_native_modal_scope(&self, f: &mut dyn FnMut()) {
+        f()
+    }
+
+    fn start_drag(&self, _source: WindowId, _data: DragData) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("start_drag is not supported").into())
+    }
+
+    fn set_drag_image(&self, _image: Option<Cursor>) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_drag_image is not supported").into())
+    }
+
+    fn clipboard(&self) -> &dyn RootClipboard {
+        self
+    }
+
+    #[cfg(feature = "dialogs")]
+    fn pick_file(&self, _options: FileDialogOptions) -> Result<AsyncRequestSerial, RequestError> {
+        Err(NotSupportedError::new("pick_file is not supported").into())
+    }
+
+    #[cfg(feature = "dialogs")]
+    fn pick_folder(&self, _options: FileDialogOptions) -> Result<AsyncRequestSerial, RequestError> {
+        Err(NotSupportedError::new("pick_folder is not supported").into())
+    }
+
+    #[cfg(feature = "dialogs")]
+    fn save_file(&self, _options: FileDialogOptions) -> Result<AsyncRequestSerial, RequestError> {
+        Err(NotSupportedError::new("save_file is not supported").into())
+    }
+
+    #[cfg(feature = "dialogs")]
+    fn show_message_box(
+        &self,
+        _config: MessageBoxConfig,
+    ) -> Result<AsyncRequestSerial, RequestError> {
+        Err(NotSupportedError::new("show_message_box is not supported").into())
+    }
+
     fn set_control_flow(&self, control_flow: ControlFlow) {
         AppState::get_mut(self.mtm).set_control_flow(control_flow)
     }
@@ -80,6 +266,58 @@ impl RootActiveEventLoop for ActiveEventLoop {
         None
     }
 
+    fn system_accent_color(&self) -> Option<Rgba> {
+        None
+    }
+
+    fn cursor_theme(&self) -> Option<CursorThemeInfo> {
+        None
+    }
+
+    fn system_preferences(&self) -> Option<SystemPreferences> {
+        Some(system_preferences())
+    }
+
+    fn text_scale_factor(&self) -> Option<f64> {
+        Some(text_scale_factor())
+    }
+
+    fn power_state(&self) -> Option<PowerState> {
+        None
+    }
+
+    fn locale(&self) -> LanguageTag {
+        current_locale()
+    }
+
+    fn keyboard_layout(&self) -> Option<KeyboardLayout> {
+        None
+    }
+
+    fn key_label(&self, _key_code: KeyCode) -> Option<String> {
+        None
+    }
+
+    fn key_for_keycode(&self, _key_code: KeyCode) -> Option<Key> {
+        None
+    }
+
+    fn keycode_for_key(&self, _key: &Key) -> Option<KeyCode> {
+        None
+    }
+
+    fn set_keyboard_led(&self, _led: Led, _on: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_keyboard_led is not supported").into())
+    }
+
+    fn keyboard_led(&self, _led: Led) -> Option<bool> {
+        None
+    }
+
+    fn event_platform_data(&self) -> Option<PlatformData> {
+        None
+    }
+
     fn control_flow(&self) -> ControlFlow {
         AppState::get_mut(self.mtm).control_flow()
     }
@@ -140,11 +378,55 @@ pub struct EventLoop {
     _did_enter_background_observer: Retained<NSObject>,
     _will_terminate_observer: Retained<NSObject>,
     _did_receive_memory_warning_observer: Retained<NSObject>,
+    _locale_observer: Retained<NSObject>,
+    _pasteboard_observer: Retained<NSObject>,
+    _keyboard_frame_observer: Retained<NSObject>,
+    _preferences_observers: [Retained<NSObject>; 3],
+    _content_size_category_observer: Retained<NSObject>,
 }
 
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub(crate) struct PlatformSpecificEventLoopAttributes {}
 
+// UIAccessibility.h
+#[link(name = "UIKit", kind = "framework")]
+extern "C" {
+    static UIAccessibilityReduceMotionStatusDidChangeNotification: &'static NSString;
+    static UIAccessibilityDarkerSystemColorsStatusDidChangeNotification: &'static NSString;
+    static UIAccessibilityReduceTransparencyStatusDidChangeNotification: &'static NSString;
+
+    fn UIAccessibilityIsReduceMotionEnabled() -> Bool;
+    fn UIAccessibilityDarkerSystemColorsEnabled() -> Bool;
+    fn UIAccessibilityIsReduceTransparencyEnabled() -> Bool;
+}
+
+// UIApplication.h
+#[link(name = "UIKit", kind = "framework")]
+extern "C" {
+    static UIContentSizeCategoryDidChangeNotification: &'static NSString;
+}
+
+/// The accessibility settings that affect how content should be drawn.
+fn system_preferences() -> SystemPreferences {
+    unsafe {
+        SystemPreferences {
+            reduced_motion: UIAccessibilityIsReduceMotionEnabled().as_bool(),
+            high_contrast: UIAccessibilityDarkerSystemColorsEnabled().as_bool(),
+            reduced_transparency: UIAccessibilityIsReduceTransparencyEnabled().as_bool(),
+        }
+    }
+}
+
+/// The scaling of the body text by the Dynamic Type content size category.
+fn text_scale_factor() -> f64 {
+    // The body text style is 17 points at the default content size category.
+    const BODY_SIZE: CGFloat = 17.0;
+    let metrics: Retained<NSObject> =
+        unsafe { msg_send_id![class!(UIFontMetrics), defaultMetrics] };
+    let size: CGFloat = unsafe { msg_send![&metrics, scaledValueForValue: BODY_SIZE] };
+    (size / BODY_SIZE) as f64
+}
+
 impl EventLoop {
     pub(crate) fn new(
         _: &PlatformSpecificEventLoopAttributes,
@@ -242,6 +524,65 @@ impl EventLoop {
                 );
             },
         );
+        let _locale_observer =
+            create_observer(&center, unsafe { NSCurrentLocaleDidChangeNotification }, move |_| {
+                app_state::handle_nonuser_event(
+                    mtm,
+                    EventWrapper::StaticEvent(Event::LocaleChanged(current_locale())),
+                );
+            });
+        let _pasteboard_observer =
+            create_observer(&center, unsafe { UIPasteboardChangedNotification }, move |_| {
+                app_state::handle_nonuser_event(
+                    mtm,
+                    EventWrapper::StaticEvent(Event::ClipboardChanged),
+                );
+            });
+        let _keyboard_frame_observer = create_observer(
+            &center,
+            unsafe { UIKeyboardWillChangeFrameNotification },
+            move |notification| {
+                let user_info: Option<Retained<NSDictionary>> =
+                    unsafe { msg_send_id![notification, userInfo] };
+                let frame: Option<Retained<NSObject>> = user_info.and_then(|user_info| unsafe {
+                    msg_send_id![&user_info, objectForKey: UIKeyboardFrameEndUserInfoKey]
+                });
+                let Some(frame) = frame else { return };
+                // SAFETY: The frame is documented to be a `NSValue` with a `CGRect`.
+                let frame: CGRect = unsafe { msg_send![&frame, CGRectValue] };
+                let app = UIApplication::sharedApplication(mtm);
+                send_virtual_keyboard_event_for_all_windows(&app, frame);
+            },
+        );
+
+        let _preferences_observers = unsafe {
+            [
+                UIAccessibilityReduceMotionStatusDidChangeNotification,
+                UIAccessibilityDarkerSystemColorsStatusDidChangeNotification,
+                UIAccessibilityReduceTransparencyStatusDidChangeNotification,
+            ]
+        }
+        .map(|name| {
+            create_observer(&center, name, move |_| {
+                app_state::handle_nonuser_event(
+                    mtm,
+                    EventWrapper::StaticEvent(
+                        Event::SystemPreferencesChanged(system_preferences()),
+                    ),
+                );
+            })
+        });
+
+        let _content_size_category_observer = create_observer(
+            &center,
+            unsafe { UIContentSizeCategoryDidChangeNotification },
+            move |_| {
+                app_state::handle_nonuser_event(
+                    mtm,
+                    EventWrapper::StaticEvent(Event::TextScaleFactorChanged(text_scale_factor())),
+                );
+            },
+        );
 
         Ok(EventLoop {
             mtm,
@@ -253,6 +594,11 @@ impl EventLoop {
             _did_enter_background_observer,
             _will_terminate_observer,
             _did_receive_memory_warning_observer,
+            _locale_observer,
+            _pasteboard_observer,
+            _keyboard_frame_observer,
+            _preferences_observers,
+            _content_size_category_observer,
         })
     }
 
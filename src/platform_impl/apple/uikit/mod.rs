@@ -1,5 +1,6 @@
 #![allow(clippy::let_unit_value)]
 
+mod app_delegate;
 mod app_state;
 mod event_loop;
 mod monitor;
@@ -42,3 +43,9 @@ impl fmt::Display for OsError {
         write!(f, "os error")
     }
 }
+
+/// Nothing is grabbed by the application that would outlive a panic.
+pub(crate) fn restore_after_panic() {}
+
+/// There is no dialog that can be shown from the panic hook.
+pub(crate) fn show_panic_dialog(_message: &str) {}
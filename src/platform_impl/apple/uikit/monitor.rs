@@ -12,7 +12,10 @@ use objc2_ui_kit::{UIScreen, UIScreenMode};
 
 use super::app_state;
 use crate::dpi::{PhysicalPosition, PhysicalSize};
-use crate::monitor::VideoModeHandle as RootVideoModeHandle;
+use crate::error::{NotSupportedError, RequestError};
+use crate::monitor::{
+    ColorCapabilities, MonitorIdentity, Orientation, VideoModeHandle as RootVideoModeHandle,
+};
 
 // Workaround for `MainThreadBound` implementing almost no traits
 #[derive(Debug)]
@@ -170,6 +173,22 @@ impl MonitorHandle {
         self.ui_screen.get_on_main(|ui_screen| ui_screen.nativeScale()) as f64
     }
 
+    pub fn physical_size_mm(&self) -> Option<(u32, u32)> {
+        None
+    }
+
+    pub fn identity(&self) -> Option<MonitorIdentity> {
+        None
+    }
+
+    pub fn orientation(&self) -> Orientation {
+        Orientation::Landscape
+    }
+
+    pub fn icc_profile(&self) -> Option<Vec<u8>> {
+        None
+    }
+
     pub fn current_video_mode(&self) -> Option<VideoModeHandle> {
         Some(run_on_main(|mtm| {
             VideoModeHandle::new(
@@ -197,6 +216,35 @@ impl MonitorHandle {
         })
     }
 
+    pub fn color_capabilities(&self) -> Result<ColorCapabilities, RequestError> {
+        Err(NotSupportedError::new("color_capabilities is not supported").into())
+    }
+
+    pub fn brightness(&self) -> Result<f64, RequestError> {
+        run_on_main(|mtm| {
+            let ui_screen = self.main_ui_screen(mtm)?;
+            Ok(unsafe { ui_screen.brightness() } as f64)
+        })
+    }
+
+    pub fn set_brightness(&self, brightness: f64) -> Result<(), RequestError> {
+        run_on_main(|mtm| {
+            let ui_screen = self.main_ui_screen(mtm)?;
+            unsafe { ui_screen.setBrightness(brightness.clamp(0.0, 1.0) as _) };
+            Ok(())
+        })
+    }
+
+    /// The screen, if it's the main screen, which is the only one with a brightness.
+    fn main_ui_screen(&self, mtm: MainThreadMarker) -> Result<&Retained<UIScreen>, RequestError> {
+        #[allow(deprecated)]
+        if *self.ui_screen(mtm) == UIScreen::mainScreen(mtm) {
+            Ok(self.ui_screen(mtm))
+        } else {
+            Err(NotSupportedError::new("brightness is only supported for the main screen").into())
+        }
+    }
+
     pub(crate) fn ui_screen(&self, mtm: MainThreadMarker) -> &Retained<UIScreen> {
         self.ui_screen.get(mtm)
     }
@@ -7,8 +7,9 @@ use objc2::{declare_class, msg_send, msg_send_id, mutability, sel, ClassType, De
 use objc2_foundation::{CGFloat, CGPoint, CGRect, MainThreadMarker, NSObject, NSSet, NSString};
 use objc2_ui_kit::{
     UICoordinateSpace, UIEvent, UIForceTouchCapability, UIGestureRecognizer,
-    UIGestureRecognizerDelegate, UIGestureRecognizerState, UIKeyInput, UIPanGestureRecognizer,
-    UIPinchGestureRecognizer, UIResponder, UIRotationGestureRecognizer, UITapGestureRecognizer,
+    UIGestureRecognizerDelegate, UIGestureRecognizerState, UIKeyInput, UIKeyboardType,
+    UIPanGestureRecognizer, UIPinchGestureRecognizer, UIResponder, UIRotationGestureRecognizer,
+    UITapGestureRecognizer, UITextAutocapitalizationType, UITextAutocorrectionType,
     UITextInputTraits, UITouch, UITouchPhase, UITouchType, UITraitEnvironment, UIView,
 };
 
@@ -18,11 +19,11 @@ use super::FingerId;
 use crate::dpi::PhysicalPosition;
 use crate::event::{
     ButtonSource, ElementState, Event, FingerId as RootFingerId, Force, KeyEvent, PointerKind,
-    PointerSource, TouchPhase, WindowEvent,
+    PointerSource, TouchContact, TouchPhase, WindowEvent,
 };
 use crate::keyboard::{Key, KeyCode, KeyLocation, NamedKey, NativeKeyCode, PhysicalKey};
 use crate::platform_impl::KeyEventExtra;
-use crate::window::WindowAttributes;
+use crate::window::{ImePurpose, WindowAttributes};
 
 pub struct WinitViewState {
     pinch_gesture_recognizer: RefCell<Option<Retained<UIPinchGestureRecognizer>>>,
@@ -34,6 +35,8 @@ pub struct WinitViewState {
     rotation_last_delta: Cell<CGFloat>,
     pinch_last_delta: Cell<CGFloat>,
     pan_last_delta: Cell<CGPoint>,
+
+    ime_purpose: Cell<ImePurpose>,
 }
 
 declare_class!(
@@ -336,6 +339,37 @@ declare_class!(
     }
 
     unsafe impl UITextInputTraits for WinitView {
+        #[method(keyboardType)]
+        fn keyboard_type(&self) -> UIKeyboardType {
+            match self.ivars().ime_purpose.get() {
+                ImePurpose::Normal | ImePurpose::Password => UIKeyboardType::Default,
+                ImePurpose::Terminal => UIKeyboardType::ASCIICapable,
+                ImePurpose::Digits => UIKeyboardType::NumberPad,
+                ImePurpose::Email => UIKeyboardType::EmailAddress,
+                ImePurpose::Url => UIKeyboardType::URL,
+            }
+        }
+
+        #[method(isSecureTextEntry)]
+        fn is_secure_text_entry(&self) -> bool {
+            self.ivars().ime_purpose.get() == ImePurpose::Password
+        }
+
+        #[method(autocorrectionType)]
+        fn autocorrection_type(&self) -> UITextAutocorrectionType {
+            match self.ivars().ime_purpose.get() {
+                ImePurpose::Normal => UITextAutocorrectionType::Default,
+                _ => UITextAutocorrectionType::No,
+            }
+        }
+
+        #[method(autocapitalizationType)]
+        fn autocapitalization_type(&self) -> UITextAutocapitalizationType {
+            match self.ivars().ime_purpose.get() {
+                ImePurpose::Normal => UITextAutocapitalizationType::Sentences,
+                _ => UITextAutocapitalizationType::None,
+            }
+        }
     }
 
     unsafe impl UIKeyInput for WinitView {
@@ -371,6 +405,8 @@ impl WinitView {
             rotation_last_delta: Cell::new(0.0),
             pinch_last_delta: Cell::new(0.0),
             pan_last_delta: Cell::new(CGPoint { x: 0.0, y: 0.0 }),
+
+            ime_purpose: Cell::new(ImePurpose::Normal),
         });
         let this: Retained<Self> = unsafe { msg_send_id![super(this), initWithFrame: frame] };
 
@@ -477,6 +513,14 @@ impl WinitView {
         }
     }
 
+    pub(crate) fn set_ime_purpose(&self, purpose: ImePurpose) {
+        self.ivars().ime_purpose.set(purpose);
+        // The keyboard reads the traits again when it's shown.
+        if unsafe { self.isFirstResponder() } {
+            unsafe { self.reloadInputViews() };
+        }
+    }
+
     fn handle_touches(&self, touches: &NSSet<UITouch>) {
         let window = self.window().unwrap();
         let mut touch_events = Vec::new();
@@ -505,13 +549,14 @@ impl WinitView {
             };
             let touch_id = touch as *const UITouch as usize;
             let phase = touch.phase();
-            let position = {
-                let scale_factor = self.contentScaleFactor();
-                PhysicalPosition::from_logical::<(f64, f64), f64>(
-                    (logical_location.x as _, logical_location.y as _),
-                    scale_factor as f64,
-                )
-            };
+            let scale_factor = self.contentScaleFactor() as f64;
+            let position = PhysicalPosition::from_logical::<(f64, f64), f64>(
+                (logical_location.x as _, logical_location.y as _),
+                scale_factor,
+            );
+            // The radius is in points.
+            let diameter = 2.0 * unsafe { touch.majorRadius() } as f64 * scale_factor;
+            let contact = Some(TouchContact::new(diameter, diameter));
             let window_id = window.id();
             let finger_id = RootFingerId(FingerId(touch_id));
 
@@ -538,7 +583,7 @@ impl WinitView {
                             button: if let UITouchType::Pencil = touch_type {
                                 ButtonSource::Unknown(0)
                             } else {
-                                ButtonSource::Touch { finger_id, force }
+                                ButtonSource::Touch { finger_id, force, contact }
                             },
                         },
                     }));
@@ -552,7 +597,7 @@ impl WinitView {
                             source: if let UITouchType::Pencil = touch_type {
                                 PointerSource::Unknown
                             } else {
-                                PointerSource::Touch { finger_id, force }
+                                PointerSource::Touch { finger_id, force, contact }
                             },
                         },
                     }));
@@ -569,7 +614,7 @@ impl WinitView {
                                 button: if let UITouchType::Pencil = touch_type {
                                     ButtonSource::Unknown(0)
                                 } else {
-                                    ButtonSource::Touch { finger_id, force }
+                                    ButtonSource::Touch { finger_id, force, contact }
                                 },
                             },
                         }));
@@ -1,11 +1,14 @@
 #![allow(clippy::unnecessary_cast)]
 
 use std::collections::VecDeque;
+use std::time::Instant;
 
 use objc2::rc::Retained;
+use objc2::runtime::AnyObject;
 use objc2::{class, declare_class, msg_send, msg_send_id, mutability, ClassType, DeclaredClass};
 use objc2_foundation::{
-    CGFloat, CGPoint, CGRect, CGSize, MainThreadBound, MainThreadMarker, NSObject, NSObjectProtocol,
+    CGFloat, CGPoint, CGRect, CGSize, MainThreadBound, MainThreadMarker, NSObject,
+    NSObjectProtocol, NSString,
 };
 use objc2_ui_kit::{
     UIApplication, UICoordinateSpace, UIResponder, UIScreen, UIScreenOverscanCompensation,
@@ -20,15 +23,23 @@ use super::{app_state, monitor, ActiveEventLoop, Fullscreen, MonitorHandle};
 use crate::cursor::Cursor;
 use crate::dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize, Position, Size};
 use crate::error::{NotSupportedError, RequestError};
-use crate::event::{Event, WindowEvent};
+use crate::event::{DeviceId, Event, WindowEvent};
 use crate::icon::Icon;
-use crate::monitor::MonitorHandle as CoreMonitorHandle;
+use crate::monitor::{GammaRamp, HdrMetadata, MonitorHandle as CoreMonitorHandle};
 use crate::platform::ios::{ScreenEdge, StatusBarStyle, ValidOrientations};
 use crate::window::{
-    CursorGrabMode, ImePurpose, ResizeDirection, Theme, UserAttentionType, Window as CoreWindow,
-    WindowAttributes, WindowButtons, WindowId, WindowLevel,
+    Coalescing, CursorGrabMode, ImePurpose, Politeness, ResizeDirection, Theme, UserAttentionType,
+    Window as CoreWindow, WindowAttributes, WindowButtons, WindowId, WindowLevel,
 };
 
+// UIAccessibility.h
+#[link(name = "UIKit", kind = "framework")]
+extern "C" {
+    static UIAccessibilityAnnouncementNotification: u32;
+
+    fn UIAccessibilityPostNotification(notification: u32, argument: *const AnyObject);
+}
+
 declare_class!(
     #[derive(Debug, PartialEq, Eq, Hash)]
     pub(crate) struct WinitUIWindow;
@@ -156,6 +167,11 @@ impl Inner {
         }
     }
 
+    pub fn request_redraw_at(&self, instant: Instant) {
+        let mtm = MainThreadMarker::new().unwrap();
+        app_state::schedule_redraw(mtm, self.window.id(), instant);
+    }
+
     pub fn pre_present_notify(&self) {}
 
     pub fn inner_position(&self) -> PhysicalPosition<i32> {
@@ -386,8 +402,8 @@ impl Inner {
         }
     }
 
-    pub fn set_ime_purpose(&self, _purpose: ImePurpose) {
-        warn!("`Window::set_ime_purpose` is ignored on iOS")
+    pub fn set_ime_purpose(&self, purpose: ImePurpose) {
+        self.view.set_ime_purpose(purpose);
     }
 
     pub fn focus_window(&self) {
@@ -398,6 +414,16 @@ impl Inner {
         warn!("`Window::request_user_attention` is ignored on iOS")
     }
 
+    pub fn announce(&self, message: &str) {
+        let message = NSString::from_str(message);
+        unsafe {
+            UIAccessibilityPostNotification(
+                UIAccessibilityAnnouncementNotification,
+                Retained::as_ptr(&message).cast(),
+            )
+        };
+    }
+
     // Allow directly accessing the current monitor internally without unwrapping.
     fn current_monitor_inner(&self) -> MonitorHandle {
         MonitorHandle::new(self.window.screen())
@@ -596,6 +622,10 @@ impl CoreWindow for Window {
         self.maybe_wait_on_main(|delegate| delegate.request_redraw());
     }
 
+    fn request_redraw_at(&self, instant: Instant) {
+        self.maybe_wait_on_main(|delegate| delegate.request_redraw_at(instant));
+    }
+
     fn pre_present_notify(&self) {
         self.maybe_wait_on_main(|delegate| delegate.pre_present_notify());
     }
@@ -704,6 +734,18 @@ impl CoreWindow for Window {
         self.maybe_wait_on_main(|delegate| delegate.fullscreen().map(Into::into))
     }
 
+    fn set_fullscreen_hdr(&self, _hdr: Option<HdrMetadata>) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_fullscreen_hdr is not supported").into())
+    }
+
+    fn fullscreen_hdr(&self) -> Option<HdrMetadata> {
+        None
+    }
+
+    fn set_gamma_ramp(&self, _ramp: Option<&GammaRamp>) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_gamma_ramp is not supported").into())
+    }
+
     fn set_decorations(&self, decorations: bool) {
         self.maybe_wait_on_main(|delegate| delegate.set_decorations(decorations));
     }
@@ -724,14 +766,29 @@ impl CoreWindow for Window {
         self.maybe_wait_on_main(|delegate| delegate.set_ime_cursor_area(position, size));
     }
 
+    fn set_ime_exclusion_area(&self, _area: Option<(Position, Size)>) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_ime_exclusion_area is not supported").into())
+    }
+
+    fn set_ime_candidates_drawn_by_app(&self, _drawn_by_app: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_ime_candidates_drawn_by_app is not supported").into())
+    }
+
     fn set_ime_allowed(&self, allowed: bool) {
         self.maybe_wait_on_main(|delegate| delegate.set_ime_allowed(allowed));
     }
 
+    fn set_virtual_keyboard_visible(&self, visible: bool) -> Result<(), RequestError> {
+        self.maybe_wait_on_main(|delegate| delegate.set_ime_allowed(visible));
+        Ok(())
+    }
+
     fn set_ime_purpose(&self, purpose: ImePurpose) {
         self.maybe_wait_on_main(|delegate| delegate.set_ime_purpose(purpose));
     }
 
+    fn set_ime_surrounding_text(&self, _text: &str, _cursor: usize, _anchor: usize) {}
+
     fn focus_window(&self) {
         self.maybe_wait_on_main(|delegate| delegate.focus_window());
     }
@@ -740,10 +797,19 @@ impl CoreWindow for Window {
         self.maybe_wait_on_main(|delegate| delegate.has_focus())
     }
 
+    fn set_keyboard_capture(&self, _capture: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_keyboard_capture is not supported").into())
+    }
+
     fn request_user_attention(&self, request_type: Option<UserAttentionType>) {
         self.maybe_wait_on_main(|delegate| delegate.request_user_attention(request_type));
     }
 
+    fn announce(&self, message: &str, _politeness: Politeness) -> Result<(), RequestError> {
+        self.maybe_wait_on_main(|delegate| delegate.announce(message));
+        Ok(())
+    }
+
     fn set_theme(&self, theme: Option<Theme>) {
         self.maybe_wait_on_main(|delegate| delegate.set_theme(theme));
     }
@@ -772,10 +838,20 @@ impl CoreWindow for Window {
         Ok(self.maybe_wait_on_main(|delegate| delegate.set_cursor_grab(mode))?)
     }
 
+    fn set_cursor_grab_policy(&self, _policy: crate::window::CursorGrabPolicy) {}
+
     fn set_cursor_visible(&self, visible: bool) {
         self.maybe_wait_on_main(|delegate| delegate.set_cursor_visible(visible))
     }
 
+    fn set_cursor_for_device(&self, _: DeviceId, _: Cursor) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_cursor_for_device is not supported").into())
+    }
+
+    fn set_cursor_visible_for_device(&self, _: DeviceId, _: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_cursor_visible_for_device is not supported").into())
+    }
+
     fn drag_window(&self) -> Result<(), RequestError> {
         Ok(self.maybe_wait_on_main(|delegate| delegate.drag_window())?)
     }
@@ -795,6 +871,10 @@ impl CoreWindow for Window {
         Ok(self.maybe_wait_on_main(|delegate| delegate.set_cursor_hittest(hittest))?)
     }
 
+    fn set_motion_coalescing(&self, _coalescing: Coalescing) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_motion_coalescing is not supported").into())
+    }
+
     fn current_monitor(&self) -> Option<CoreMonitorHandle> {
         self.maybe_wait_on_main(|delegate| {
             delegate.current_monitor().map(|inner| CoreMonitorHandle { inner })
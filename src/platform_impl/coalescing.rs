@@ -0,0 +1,124 @@
+//! Coalescing of the pointer motion events.
+
+use std::mem;
+
+use crate::event::{PointerKind, WindowEvent};
+use crate::window::{Coalescing, WindowId};
+
+/// Queue holding back the pointer motion events until they must be delivered.
+#[derive(Debug, Default)]
+pub struct MotionCoalescer {
+    pending: Vec<(WindowId, Coalescing, WindowEvent)>,
+}
+
+impl MotionCoalescer {
+    /// Queue the `event` according to the `coalescing` mode of the window.
+    ///
+    /// The event is returned back when it must be delivered right away. In that case, the events
+    /// which were held back for the window must be delivered before it, see
+    /// [`Self::drain_window`].
+    pub fn push(
+        &mut self,
+        window_id: WindowId,
+        event: WindowEvent,
+        coalescing: Coalescing,
+    ) -> Option<WindowEvent> {
+        if coalescing == Coalescing::None {
+            return Some(event);
+        }
+
+        let pointer = match &event {
+            WindowEvent::PointerMoved { device_id, source, .. } => {
+                (*device_id, PointerKind::from(source.clone()))
+            },
+            _ => return Some(event),
+        };
+
+        let pending = self.pending.iter_mut().find(|(id, _, pending)| match pending {
+            WindowEvent::PointerMoved { device_id, source, .. } => {
+                *id == window_id && (*device_id, PointerKind::from(source.clone())) == pointer
+            },
+            _ => false,
+        });
+
+        match pending {
+            Some((_, mode, pending)) => {
+                *mode = coalescing;
+                *pending = event;
+            },
+            None => self.pending.push((window_id, coalescing, event)),
+        }
+
+        None
+    }
+
+    /// Take the events held back for the given window.
+    pub fn drain_window(&mut self, window_id: WindowId) -> Vec<WindowEvent> {
+        self.take(|id, _| id == window_id).into_iter().map(|(_, event)| event).collect()
+    }
+
+    /// Take the events which are coalesced only within a batch of events.
+    #[cfg(any(x11_platform, wayland_platform, test))]
+    pub fn drain_batch(&mut self) -> Vec<(WindowId, WindowEvent)> {
+        self.take(|_, coalescing| coalescing == Coalescing::All)
+    }
+
+    /// Take all the events held back.
+    pub fn drain(&mut self) -> Vec<(WindowId, WindowEvent)> {
+        self.take(|_, _| true)
+    }
+
+    fn take(
+        &mut self,
+        filter: impl Fn(WindowId, Coalescing) -> bool,
+    ) -> Vec<(WindowId, WindowEvent)> {
+        let (events, pending): (Vec<_>, Vec<_>) = mem::take(&mut self.pending)
+            .into_iter()
+            .partition(|(window_id, coalescing, _)| filter(*window_id, *coalescing));
+        self.pending = pending;
+        events.into_iter().map(|(window_id, _, event)| (window_id, event)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dpi::PhysicalPosition;
+    use crate::event::PointerSource;
+
+    fn moved(x: f64) -> WindowEvent {
+        WindowEvent::PointerMoved {
+            device_id: None,
+            position: PhysicalPosition::new(x, 0.),
+            source: PointerSource::Mouse,
+        }
+    }
+
+    #[test]
+    fn merges_motion_per_window() {
+        let first = WindowId::from_raw(1);
+        let second = WindowId::from_raw(2);
+        let mut coalescer = MotionCoalescer::default();
+
+        assert_eq!(coalescer.push(first, moved(1.), Coalescing::All), None);
+        assert_eq!(coalescer.push(first, moved(2.), Coalescing::All), None);
+        assert_eq!(coalescer.push(second, moved(3.), Coalescing::PerFrame), None);
+        assert_eq!(
+            coalescer.push(first, WindowEvent::Focused(true), Coalescing::All),
+            Some(WindowEvent::Focused(true))
+        );
+
+        assert_eq!(coalescer.drain_window(first), vec![moved(2.)]);
+        assert_eq!(coalescer.drain_batch(), vec![]);
+        assert_eq!(coalescer.drain(), vec![(second, moved(3.))]);
+    }
+
+    #[test]
+    fn no_coalescing() {
+        let window = WindowId::from_raw(1);
+        let mut coalescer = MotionCoalescer::default();
+
+        assert_eq!(coalescer.push(window, moved(1.), Coalescing::None), Some(moved(1.)));
+        assert!(coalescer.drain().is_empty());
+    }
+}
@@ -0,0 +1,80 @@
+//! The AccessKit adapter of a window, exposing its accessibility tree over AT-SPI.
+
+use accesskit::{
+    ActionHandler, ActionRequest, ActivationHandler, DeactivationHandler, Rect, TreeUpdate,
+};
+use dpi::{PhysicalPosition, PhysicalSize};
+
+/// The source of the tree given by the application.
+struct Source(Box<dyn ActivationHandler + Send>);
+
+impl ActivationHandler for Source {
+    fn request_initial_tree(&mut self) -> Option<TreeUpdate> {
+        self.0.request_initial_tree()
+    }
+}
+
+/// Forwards the actions to the event loop.
+struct Actions<F>(F);
+
+impl<F: FnMut(ActionRequest)> ActionHandler for Actions<F> {
+    fn do_action(&mut self, request: ActionRequest) {
+        (self.0)(request)
+    }
+}
+
+/// The tree is requested again from the source on the next activation, so nothing is kept.
+struct Deactivation;
+
+impl DeactivationHandler for Deactivation {
+    fn deactivate_accessibility(&mut self) {}
+}
+
+/// The AccessKit adapter of a window, registered with AT-SPI until it's dropped.
+pub struct Adapter {
+    adapter: accesskit_unix::Adapter,
+}
+
+impl std::fmt::Debug for Adapter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Adapter").finish_non_exhaustive()
+    }
+}
+
+impl Adapter {
+    /// Register the window with AT-SPI.
+    ///
+    /// Both the source and `on_action` are called from the thread of the adapter.
+    pub fn new(
+        source: Box<dyn ActivationHandler + Send>,
+        on_action: impl FnMut(ActionRequest) + Send + 'static,
+    ) -> Self {
+        let adapter =
+            accesskit_unix::Adapter::new(Source(source), Actions(on_action), Deactivation);
+        Self { adapter }
+    }
+
+    /// Push a change of the tree, dropped while no assistive technology uses the window.
+    pub fn update(&mut self, update: TreeUpdate) {
+        self.adapter.update_if_active(|| update);
+    }
+
+    pub fn set_focused(&mut self, focused: bool) {
+        self.adapter.update_window_focus_state(focused);
+    }
+
+    /// Set the bounds of the window and of its surface, on the desktop.
+    pub fn set_bounds(
+        &mut self,
+        outer: (PhysicalPosition<i32>, PhysicalSize<u32>),
+        surface: (PhysicalPosition<i32>, PhysicalSize<u32>),
+    ) {
+        self.adapter.set_root_window_bounds(rect(outer), rect(surface));
+    }
+}
+
+fn rect((position, size): (PhysicalPosition<i32>, PhysicalSize<u32>)) -> Rect {
+    let position: (f64, f64) = position.cast::<f64>().into();
+    let size: (f64, f64) = size.cast::<f64>().into();
+    Rect::from_origin_size(position, size)
+}
@@ -0,0 +1,276 @@
+//! Brightness of the monitors, through the backlight of built-in panels and DDC/CI for external
+//! monitors.
+//!
+//! Monitors are found by the name of their connector in the `drm` class of sysfs, which is also
+//! the name of the outputs on Wayland, and mostly on X11.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::os::unix::io::AsRawFd;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+use super::logind;
+
+const DRM_PATH: &str = "/sys/class/drm";
+const BACKLIGHT_PATH: &str = "/sys/class/backlight";
+
+/// The `I2C_SLAVE` request of `ioctl`, selecting the address of the device on the bus.
+const I2C_SLAVE: u64 = 0x0703;
+/// The address of DDC/CI on the I2C bus of the monitor.
+const DDC_ADDRESS: u8 = 0x37;
+/// The VCP code of the brightness, from the Monitor Control Command Set.
+const VCP_BRIGHTNESS: u8 = 0x10;
+/// How long monitors take to reply to a DDC/CI request.
+const DDC_REPLY_DELAY: Duration = Duration::from_millis(40);
+
+/// The way the brightness of a monitor is controlled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BrightnessControl {
+    /// A backlight device of the kernel, with the sysfs path of the device.
+    Backlight(PathBuf),
+    /// DDC/CI, with the path of the I2C bus of the monitor.
+    Ddc(PathBuf),
+}
+
+impl BrightnessControl {
+    /// The brightness control of the monitor connected to `output`, if any.
+    pub fn find(output: &str) -> Option<Self> {
+        Self::find_in(Path::new(DRM_PATH), Path::new(BACKLIGHT_PATH), output)
+    }
+
+    fn find_in(drm: &Path, backlights: &Path, output: &str) -> Option<Self> {
+        for entry in fs::read_dir(drm).ok()?.flatten() {
+            let name = entry.file_name();
+            // Connectors are named after their card, like `card0-HDMI-A-1`.
+            let Some((card, connector)) = name.to_str().and_then(|name| name.split_once('-'))
+            else {
+                continue;
+            };
+            if !card.starts_with("card") || !connector_matches(connector, output) {
+                continue;
+            }
+
+            let path = entry.path();
+            if let Some(backlight) = child_backlight(&path) {
+                return Some(Self::Backlight(backlight));
+            }
+            if let Some(bus) = ddc_bus(&path) {
+                return Some(Self::Ddc(bus));
+            }
+            // The backlight of built-in panels isn't always attached to their connector.
+            if ["eDP", "LVDS", "DSI"].iter().any(|kind| connector.starts_with(kind)) {
+                let backlight = fs::read_dir(backlights).ok()?.flatten().next()?;
+                return Some(Self::Backlight(backlight.path()));
+            }
+        }
+        None
+    }
+
+    /// The brightness, from 0 to 1.
+    pub fn brightness(&self) -> io::Result<f64> {
+        match self {
+            Self::Backlight(path) => {
+                let (brightness, max) = read_backlight(path)?;
+                Ok(brightness as f64 / max as f64)
+            },
+            Self::Ddc(bus) => {
+                let (brightness, max) = get_vcp(&mut open_ddc(bus)?, VCP_BRIGHTNESS)?;
+                Ok(brightness as f64 / max as f64)
+            },
+        }
+    }
+
+    /// Set the brightness, from 0 to 1.
+    pub fn set_brightness(&self, brightness: f64) -> io::Result<()> {
+        let brightness = brightness.clamp(0.0, 1.0);
+        match self {
+            Self::Backlight(path) => {
+                let (_, max) = read_backlight(path)?;
+                let value = (brightness * max as f64).round() as u32;
+                match fs::write(path.join("brightness"), value.to_string()) {
+                    // Only root can write to sysfs, but the session can set the backlight.
+                    Err(error) if error.kind() == io::ErrorKind::PermissionDenied => {
+                        let name = path.file_name().and_then(|name| name.to_str()).unwrap_or("");
+                        logind::set_brightness("backlight", name, value)
+                    },
+                    result => result,
+                }
+            },
+            Self::Ddc(bus) => {
+                let mut device = open_ddc(bus)?;
+                let (_, max) = get_vcp(&mut device, VCP_BRIGHTNESS)?;
+                set_vcp(&mut device, VCP_BRIGHTNESS, (brightness * max as f64).round() as u16)
+            },
+        }
+    }
+}
+
+/// Whether the DRM `connector` is the one named `output` by the display server.
+///
+/// The X server names HDMI outputs `HDMI-1` rather than `HDMI-A-1`.
+fn connector_matches(connector: &str, output: &str) -> bool {
+    connector == output || connector.replacen("HDMI-A-", "HDMI-", 1) == output
+}
+
+/// A backlight device which is a child of the connector, like `intel_backlight`.
+fn child_backlight(connector: &Path) -> Option<PathBuf> {
+    fs::read_dir(connector)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .find(|path| path.join("max_brightness").is_file())
+}
+
+/// The device of the I2C bus the monitor is connected to, if it's exposed.
+fn ddc_bus(connector: &Path) -> Option<PathBuf> {
+    let bus = match fs::read_link(connector.join("ddc")) {
+        Ok(link) => link.file_name()?.to_owned(),
+        // Some drivers add the bus as a child of the connector instead.
+        Err(_) => fs::read_dir(connector)
+            .ok()?
+            .flatten()
+            .map(|entry| entry.file_name())
+            .find(|name| name.to_str().is_some_and(|name| name.starts_with("i2c-")))?,
+    };
+    Some(Path::new("/dev").join(bus))
+}
+
+fn read_backlight(path: &Path) -> io::Result<(u32, u32)> {
+    let read = |attribute: &str| -> io::Result<u32> {
+        fs::read_to_string(path.join(attribute))?
+            .trim()
+            .parse()
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    };
+    let max = read("max_brightness")?;
+    if max == 0 {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "the backlight has no range"));
+    }
+    Ok((read("brightness")?, max))
+}
+
+fn open_ddc(bus: &Path) -> io::Result<File> {
+    let device = OpenOptions::new().read(true).write(true).open(bus)?;
+    if unsafe { libc::ioctl(device.as_raw_fd(), I2C_SLAVE as _, DDC_ADDRESS as libc::c_ulong) } < 0
+    {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(device)
+}
+
+/// The current and maximum values of a VCP feature.
+fn get_vcp(device: &mut File, code: u8) -> io::Result<(u16, u16)> {
+    device.write_all(&ddc_packet(&[0x01, code]))?;
+    thread::sleep(DDC_REPLY_DELAY);
+    let mut reply = [0; 11];
+    device.read_exact(&mut reply)?;
+    parse_vcp_reply(&reply, code)
+}
+
+fn set_vcp(device: &mut File, code: u8, value: u16) -> io::Result<()> {
+    let [high, low] = value.to_be_bytes();
+    device.write_all(&ddc_packet(&[0x03, code, high, low]))?;
+    // Monitors ignore the requests sent before they handled the previous one.
+    thread::sleep(DDC_REPLY_DELAY);
+    Ok(())
+}
+
+/// A DDC/CI message from the host, with its length and checksum.
+fn ddc_packet(data: &[u8]) -> Vec<u8> {
+    let mut packet = vec![0x51, 0x80 | data.len() as u8];
+    packet.extend_from_slice(data);
+    // The checksum covers the address of the monitor as well.
+    packet.push(packet.iter().fold(DDC_ADDRESS << 1, |checksum, byte| checksum ^ byte));
+    packet
+}
+
+fn parse_vcp_reply(reply: &[u8; 11], code: u8) -> io::Result<(u16, u16)> {
+    // The checksum covers the address of the host, instead of the address of the monitor.
+    let checksum = reply[..10].iter().fold(0x50, |checksum, byte| checksum ^ byte);
+    if reply[1] != 0x88 || reply[2] != 0x02 || reply[4] != code || reply[10] != checksum {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "invalid DDC/CI reply"));
+    }
+    if reply[3] != 0 {
+        return Err(io::Error::new(io::ErrorKind::Unsupported, "unsupported VCP feature"));
+    }
+
+    let max = u16::from_be_bytes([reply[6], reply[7]]);
+    let current = u16::from_be_bytes([reply[8], reply[9]]);
+    if max == 0 {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "the VCP feature has no range"));
+    }
+    Ok((current.min(max), max))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_root(name: &str) -> PathBuf {
+        let root =
+            std::env::temp_dir().join(format!("winit-brightness-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        root
+    }
+
+    #[test]
+    fn find_control() {
+        let root = temp_root("find");
+        let (drm, backlights) = (root.join("drm"), root.join("backlight"));
+        fs::create_dir_all(drm.join("card0-eDP-1/intel_backlight")).unwrap();
+        fs::write(drm.join("card0-eDP-1/intel_backlight/max_brightness"), "100\n").unwrap();
+        fs::create_dir_all(drm.join("card0-HDMI-A-1")).unwrap();
+        std::os::unix::fs::symlink("../../i2c-4", drm.join("card0-HDMI-A-1/ddc")).unwrap();
+        fs::create_dir_all(drm.join("card0-DP-2/i2c-7")).unwrap();
+        fs::create_dir_all(drm.join("card1-LVDS-1")).unwrap();
+        fs::create_dir_all(backlights.join("acpi_video0")).unwrap();
+
+        let find = |output| BrightnessControl::find_in(&drm, &backlights, output);
+        assert_eq!(
+            find("eDP-1"),
+            Some(BrightnessControl::Backlight(drm.join("card0-eDP-1/intel_backlight")))
+        );
+        assert_eq!(find("HDMI-1"), Some(BrightnessControl::Ddc("/dev/i2c-4".into())));
+        assert_eq!(find("HDMI-A-1"), find("HDMI-1"));
+        assert_eq!(find("DP-2"), Some(BrightnessControl::Ddc("/dev/i2c-7".into())));
+        assert_eq!(
+            find("LVDS-1"),
+            Some(BrightnessControl::Backlight(backlights.join("acpi_video0")))
+        );
+        assert_eq!(find("DP-1"), None);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn backlight_brightness() {
+        let root = temp_root("backlight");
+        fs::write(root.join("max_brightness"), "1000\n").unwrap();
+        fs::write(root.join("brightness"), "250\n").unwrap();
+
+        let control = BrightnessControl::Backlight(root.clone());
+        assert_eq!(control.brightness().unwrap(), 0.25);
+        control.set_brightness(1.5).unwrap();
+        assert_eq!(fs::read_to_string(root.join("brightness")).unwrap(), "1000");
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn ddc_messages() {
+        assert_eq!(ddc_packet(&[0x01, VCP_BRIGHTNESS]), [0x51, 0x82, 0x01, 0x10, 0xac]);
+
+        let mut reply = [0x6e, 0x88, 0x02, 0x00, 0x10, 0x00, 0x00, 0x64, 0x00, 0x32, 0x00];
+        reply[10] = reply[..10].iter().fold(0x50, |checksum, byte| checksum ^ byte);
+        assert_eq!(parse_vcp_reply(&reply, VCP_BRIGHTNESS).unwrap(), (50, 100));
+        assert!(parse_vcp_reply(&reply, 0x12).is_err());
+
+        reply[3] = 0x01;
+        reply[10] ^= 0x01;
+        let error = parse_vcp_reply(&reply, VCP_BRIGHTNESS).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::Unsupported);
+    }
+}
@@ -0,0 +1,134 @@
+//! The MIME types of the clipboard formats, shared by X11 and Wayland.
+
+use std::ffi::OsStr;
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+
+use crate::clipboard::{ClipboardData, ClipboardFormat, ClipboardImage};
+
+/// The MIME types of the format, the preferred one first.
+///
+/// `UTF8_STRING` isn't a MIME type, but X11 applications use it for the text.
+pub fn mime_types(format: ClipboardFormat) -> Vec<&'static str> {
+    match format {
+        ClipboardFormat::Text => vec!["text/plain;charset=utf-8", "UTF8_STRING", "text/plain"],
+        ClipboardFormat::Html => vec!["text/html"],
+        ClipboardFormat::Image => vec!["image/png"],
+        ClipboardFormat::Files | ClipboardFormat::Uris => vec!["text/uri-list"],
+        ClipboardFormat::Custom(mime_type) => vec![mime_type],
+    }
+}
+
+/// Encode the data for any of the MIME types of its format.
+pub fn encode(data: &ClipboardData) -> Vec<u8> {
+    match data {
+        ClipboardData::Text(text) | ClipboardData::Html(text) => text.as_bytes().to_vec(),
+        ClipboardData::Image(image) => image.to_png().into_owned(),
+        ClipboardData::Files(paths) => {
+            let mut list = String::new();
+            for path in paths {
+                list.push_str("file://");
+                list.push_str(&percent_encode(path.as_os_str().as_bytes()));
+                list.push_str("\r\n");
+            }
+            list.into_bytes()
+        },
+        ClipboardData::Uris(uris) => {
+            uris.iter().flat_map(|uri| [uri.as_str(), "\r\n"]).collect::<String>().into_bytes()
+        },
+        ClipboardData::Custom { data, .. } => data.clone(),
+    }
+}
+
+/// Decode the data received for one of the MIME types of the format.
+pub fn decode(format: ClipboardFormat, bytes: Vec<u8>) -> Option<ClipboardData> {
+    let text = |bytes: Vec<u8>| {
+        String::from_utf8(bytes)
+            .unwrap_or_else(|err| String::from_utf8_lossy(err.as_bytes()).into_owned())
+    };
+    match format {
+        ClipboardFormat::Text => Some(ClipboardData::Text(text(bytes))),
+        ClipboardFormat::Html => Some(ClipboardData::Html(text(bytes))),
+        ClipboardFormat::Image => Some(ClipboardData::Image(ClipboardImage::Png(bytes))),
+        ClipboardFormat::Files => {
+            // Comments start with `#`, and only local files can be represented as paths.
+            let paths: Vec<PathBuf> = text(bytes)
+                .lines()
+                .filter_map(|uri| uri.strip_prefix("file://"))
+                .filter_map(|path| path.get(path.find('/')?..))
+                .map(|path| Path::new(OsStr::from_bytes(&percent_decode(path))).to_owned())
+                .collect();
+            (!paths.is_empty()).then_some(ClipboardData::Files(paths))
+        },
+        ClipboardFormat::Uris => {
+            let uris: Vec<String> = text(bytes)
+                .lines()
+                .filter(|uri| !uri.is_empty() && !uri.starts_with('#'))
+                .map(str::to_owned)
+                .collect();
+            (!uris.is_empty()).then_some(ClipboardData::Uris(uris))
+        },
+        ClipboardFormat::Custom(mime_type) => {
+            Some(ClipboardData::Custom { mime_type, data: bytes })
+        },
+    }
+}
+
+fn percent_encode(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len());
+    for &byte in bytes {
+        if byte.is_ascii_alphanumeric() || b"/-._~".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+fn percent_decode(encoded: &str) -> Vec<u8> {
+    let mut decoded = Vec::with_capacity(encoded.len());
+    let mut bytes = encoded.bytes();
+    while let Some(byte) = bytes.next() {
+        let escaped = (byte == b'%')
+            .then(|| {
+                let hex = [bytes.clone().next()?, bytes.clone().nth(1)?];
+                u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()
+            })
+            .flatten();
+        match escaped {
+            Some(escaped) => {
+                decoded.push(escaped);
+                bytes.nth(1);
+            },
+            None => decoded.push(byte),
+        }
+    }
+    decoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn files_round_trip() {
+        let paths = vec![PathBuf::from("/tmp/a file.txt"), PathBuf::from("/home/ü/100%.png")];
+        let data = ClipboardData::Files(paths);
+        let encoded = encode(&data);
+        assert_eq!(
+            std::str::from_utf8(&encoded).unwrap(),
+            "file:///tmp/a%20file.txt\r\nfile:///home/%C3%BC/100%25.png\r\n"
+        );
+        assert_eq!(decode(ClipboardFormat::Files, encoded), Some(data));
+    }
+
+    #[test]
+    fn files_with_host() {
+        let list = b"# copied\r\nfile://localhost/etc/hosts\r\nhttps://example.com/\r\n".to_vec();
+        let paths = vec![PathBuf::from("/etc/hosts")];
+        assert_eq!(decode(ClipboardFormat::Files, list.clone()), Some(ClipboardData::Files(paths)));
+        let uris = vec!["file://localhost/etc/hosts".to_owned(), "https://example.com/".to_owned()];
+        assert_eq!(decode(ClipboardFormat::Uris, list), Some(ClipboardData::Uris(uris)));
+    }
+}
@@ -0,0 +1,26 @@
+//! The cursor theme of the user, as configured for libXcursor.
+
+use std::env;
+
+#[cfg(wayland_platform)]
+use crate::window::CursorThemeInfo;
+
+/// The size of the cursors when none is configured, like libXcursor and libwayland-cursor.
+pub const DEFAULT_CURSOR_SIZE: u32 = 24;
+
+/// The cursor theme from the `XCURSOR_THEME` and `XCURSOR_SIZE` environment variables.
+#[cfg(wayland_platform)]
+pub fn cursor_theme_from_env() -> CursorThemeInfo {
+    CursorThemeInfo {
+        name: theme_name_from_env(),
+        size: cursor_size_from_env().unwrap_or(DEFAULT_CURSOR_SIZE),
+    }
+}
+
+pub fn theme_name_from_env() -> Option<String> {
+    env::var("XCURSOR_THEME").ok().filter(|name| !name.is_empty())
+}
+
+pub fn cursor_size_from_env() -> Option<u32> {
+    env::var("XCURSOR_SIZE").ok()?.parse().ok().filter(|&size| size != 0)
+}
@@ -0,0 +1,852 @@
+//! Minimal blocking client of the D-Bus message buses.
+//!
+//! Only what's needed to talk to the desktop portals and `systemd-logind` is implemented: method
+//! calls, their replies, and signals, over a unix socket authenticated with `EXTERNAL`. Every
+//! type of the D-Bus type system is encoded and decoded, but file descriptors aren't passed.
+
+use std::collections::VecDeque;
+use std::io::{self, Read, Write};
+use std::os::unix::net::UnixStream;
+use std::path::Path;
+use std::{env, fmt};
+
+const BUS_NAME: &str = "org.freedesktop.DBus";
+const BUS_PATH: &str = "/org/freedesktop/DBus";
+
+const METHOD_CALL: u8 = 1;
+const METHOD_RETURN: u8 = 2;
+const ERROR: u8 = 3;
+const SIGNAL: u8 = 4;
+
+// Header fields.
+const PATH: u8 = 1;
+const INTERFACE: u8 = 2;
+const MEMBER: u8 = 3;
+const ERROR_NAME: u8 = 4;
+const REPLY_SERIAL: u8 = 5;
+const DESTINATION: u8 = 6;
+const SIGNATURE: u8 = 8;
+
+/// The largest message allowed by the specification.
+const MAX_MESSAGE_LEN: usize = 1 << 27;
+
+/// The address of the system bus when `DBUS_SYSTEM_BUS_ADDRESS` isn't set.
+const DEFAULT_SYSTEM_BUS_ADDRESS: &str = "unix:path=/var/run/dbus/system_bus_socket";
+
+/// A value of the D-Bus type system.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Byte(u8),
+    Bool(bool),
+    I16(i16),
+    U16(u16),
+    I32(i32),
+    U32(u32),
+    I64(i64),
+    U64(u64),
+    Double(f64),
+    /// The index of a file descriptor sent along the message. The file descriptors themselves
+    /// aren't received, so only the index is available.
+    UnixFd(u32),
+    Str(String),
+    ObjectPath(String),
+    Signature(String),
+    /// The signature of the elements, and the elements.
+    Array(String, Vec<Value>),
+    Struct(Vec<Value>),
+    DictEntry(Box<Value>, Box<Value>),
+    Variant(Box<Value>),
+}
+
+impl Value {
+    /// A dictionary of type `a{sv}`.
+    #[cfg(any(feature = "dialogs", test))]
+    pub fn dict(entries: Vec<(&str, Value)>) -> Self {
+        let entries = entries
+            .into_iter()
+            .map(|(key, value)| {
+                Value::DictEntry(
+                    Box::new(Value::Str(key.into())),
+                    Box::new(Value::Variant(Box::new(value))),
+                )
+            })
+            .collect();
+        Value::Array("{sv}".into(), entries)
+    }
+
+    pub fn signature(&self) -> String {
+        match self {
+            Value::Byte(_) => "y".into(),
+            Value::Bool(_) => "b".into(),
+            Value::I16(_) => "n".into(),
+            Value::U16(_) => "q".into(),
+            Value::I32(_) => "i".into(),
+            Value::U32(_) => "u".into(),
+            Value::I64(_) => "x".into(),
+            Value::U64(_) => "t".into(),
+            Value::Double(_) => "d".into(),
+            Value::UnixFd(_) => "h".into(),
+            Value::Str(_) => "s".into(),
+            Value::ObjectPath(_) => "o".into(),
+            Value::Signature(_) => "g".into(),
+            Value::Array(element, _) => format!("a{element}"),
+            Value::Struct(fields) => {
+                format!("({})", fields.iter().map(Value::signature).collect::<String>())
+            },
+            Value::DictEntry(key, value) => format!("{{{}{}}}", key.signature(), value.signature()),
+            Value::Variant(_) => "v".into(),
+        }
+    }
+
+    pub fn as_u32(&self) -> Option<u32> {
+        match self {
+            Value::U32(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Double(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::Str(value) | Value::ObjectPath(value) | Value::Signature(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(_, elements) => Some(elements),
+            _ => None,
+        }
+    }
+
+    /// Look up `key` in a dictionary of type `a{sv}`, unwrapping the variant.
+    pub fn lookup(&self, key: &str) -> Option<&Value> {
+        self.as_array()?.iter().find_map(|entry| match entry {
+            Value::DictEntry(entry_key, value) if entry_key.as_str() == Some(key) => match &**value
+            {
+                Value::Variant(value) => Some(&**value),
+                value => Some(value),
+            },
+            _ => None,
+        })
+    }
+}
+
+/// A message received from the bus.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Message {
+    kind: u8,
+    pub reply_serial: Option<u32>,
+    pub path: Option<String>,
+    pub interface: Option<String>,
+    pub member: Option<String>,
+    pub error_name: Option<String>,
+    pub body: Vec<Value>,
+}
+
+impl Message {
+    pub fn is_signal(&self, interface: &str, member: &str) -> bool {
+        self.kind == SIGNAL
+            && self.interface.as_deref() == Some(interface)
+            && self.member.as_deref() == Some(member)
+    }
+}
+
+/// An error returned by a method call.
+#[derive(Debug)]
+pub struct MethodError {
+    pub name: String,
+    pub message: Option<String>,
+}
+
+impl fmt::Display for MethodError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.message {
+            Some(message) => write!(f, "{}: {message}", self.name),
+            None => f.write_str(&self.name),
+        }
+    }
+}
+
+impl std::error::Error for MethodError {}
+
+/// A connection to a message bus.
+#[derive(Debug)]
+pub struct Connection {
+    stream: UnixStream,
+    next_serial: u32,
+    unique_name: String,
+    /// Messages received while waiting for a method reply.
+    pending: VecDeque<Message>,
+}
+
+impl Connection {
+    /// Connect to the session bus of the user.
+    pub fn session() -> io::Result<Self> {
+        let stream = match env::var("DBUS_SESSION_BUS_ADDRESS") {
+            Ok(addresses) => connect_any(&addresses)?,
+            Err(_) => {
+                let runtime_dir = env::var_os("XDG_RUNTIME_DIR").ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::NotFound, "no session bus address")
+                })?;
+                UnixStream::connect(Path::new(&runtime_dir).join("bus"))?
+            },
+        };
+        Self::new(stream)
+    }
+
+    /// Connect to the system bus.
+    pub fn system() -> io::Result<Self> {
+        let addresses = env::var("DBUS_SYSTEM_BUS_ADDRESS");
+        Self::new(connect_any(addresses.as_deref().unwrap_or(DEFAULT_SYSTEM_BUS_ADDRESS))?)
+    }
+
+    fn new(stream: UnixStream) -> io::Result<Self> {
+        let mut connection =
+            Self { stream, next_serial: 1, unique_name: String::new(), pending: VecDeque::new() };
+        connection.authenticate()?;
+
+        let reply = connection.call(BUS_NAME, BUS_PATH, BUS_NAME, "Hello", vec![])?;
+        connection.unique_name = reply
+            .body
+            .first()
+            .and_then(Value::as_str)
+            .ok_or_else(|| invalid_data("invalid reply to Hello"))?
+            .to_owned();
+
+        Ok(connection)
+    }
+
+    /// The name assigned by the bus to this connection.
+    #[cfg(feature = "dialogs")]
+    pub fn unique_name(&self) -> &str {
+        &self.unique_name
+    }
+
+    /// A handle to the socket of the connection, to shut it down from another thread.
+    pub fn try_clone_stream(&self) -> io::Result<UnixStream> {
+        self.stream.try_clone()
+    }
+
+    /// Receive the signals matching the `rule`.
+    pub fn add_match(&mut self, rule: &str) -> io::Result<()> {
+        self.call(BUS_NAME, BUS_PATH, BUS_NAME, "AddMatch", vec![Value::Str(rule.into())]).map(drop)
+    }
+
+    /// Call a method and wait for its reply.
+    pub fn call(
+        &mut self,
+        destination: &str,
+        path: &str,
+        interface: &str,
+        member: &str,
+        body: Vec<Value>,
+    ) -> io::Result<Message> {
+        let serial = self.next_serial;
+        self.next_serial = self.next_serial.wrapping_add(1).max(1);
+
+        let mut fields = vec![
+            header_field(PATH, Value::ObjectPath(path.into())),
+            header_field(INTERFACE, Value::Str(interface.into())),
+            header_field(MEMBER, Value::Str(member.into())),
+            header_field(DESTINATION, Value::Str(destination.into())),
+        ];
+        let signature: String = body.iter().map(Value::signature).collect();
+        if !signature.is_empty() {
+            fields.push(header_field(SIGNATURE, Value::Signature(signature)));
+        }
+        self.stream.write_all(&encode_message(METHOD_CALL, serial, fields, &body))?;
+
+        loop {
+            let message = self.read_message()?;
+            if message.reply_serial != Some(serial) {
+                if message.kind == SIGNAL {
+                    self.pending.push_back(message);
+                }
+                continue;
+            }
+
+            return match message.kind {
+                METHOD_RETURN => Ok(message),
+                _ => {
+                    let error = MethodError {
+                        name: message.error_name.unwrap_or_default(),
+                        message: message.body.first().and_then(Value::as_str).map(str::to_owned),
+                    };
+                    Err(io::Error::new(io::ErrorKind::Other, error))
+                },
+            };
+        }
+    }
+
+    /// Wait for the next signal.
+    ///
+    /// The malformed messages are skipped, so an error means that the connection is unusable.
+    pub fn next_signal(&mut self) -> io::Result<Message> {
+        if let Some(message) = self.pending.pop_front() {
+            return Ok(message);
+        }
+
+        loop {
+            let message = self.read_message()?;
+            if message.kind == SIGNAL {
+                return Ok(message);
+            }
+        }
+    }
+
+    fn authenticate(&mut self) -> io::Result<()> {
+        let uid = unsafe { libc::getuid() }.to_string();
+        let hex_uid: String = uid.bytes().map(|byte| format!("{byte:02x}")).collect();
+        self.stream.write_all(format!("\0AUTH EXTERNAL {hex_uid}\r\n").as_bytes())?;
+
+        let mut line = Vec::new();
+        loop {
+            let mut byte = [0];
+            self.stream.read_exact(&mut byte)?;
+            line.push(byte[0]);
+            if line.ends_with(b"\r\n") {
+                break;
+            }
+        }
+
+        if !line.starts_with(b"OK ") {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                "the bus rejected the authentication",
+            ));
+        }
+
+        self.stream.write_all(b"BEGIN\r\n")
+    }
+
+    /// Read the next message, skipping the ones with a malformed header.
+    fn read_message(&mut self) -> io::Result<Message> {
+        loop {
+            let (data, header_len, big_endian) = self.read_message_data()?;
+            match decode_message(&data, header_len, big_endian) {
+                Ok(message) => return Ok(message),
+                Err(error) => tracing::debug!("skipped a malformed D-Bus message: {error}"),
+            }
+        }
+    }
+
+    /// Read the bytes of the next message, with the length of its header and its endianness.
+    fn read_message_data(&mut self) -> io::Result<(Vec<u8>, usize, bool)> {
+        let mut fixed = [0; 16];
+        self.stream.read_exact(&mut fixed)?;
+
+        let big_endian = match fixed[0] {
+            b'l' => false,
+            b'B' => true,
+            _ => return Err(invalid_data("invalid endianness")),
+        };
+        let read_u32 = |bytes: &[u8]| {
+            let bytes = bytes.try_into().unwrap();
+            if big_endian {
+                u32::from_be_bytes(bytes)
+            } else {
+                u32::from_le_bytes(bytes)
+            }
+        };
+        let body_len = read_u32(&fixed[4..8]) as usize;
+        let fields_len = read_u32(&fixed[12..16]) as usize;
+        let header_len = (16 + fields_len).next_multiple_of(8);
+        if header_len + body_len > MAX_MESSAGE_LEN {
+            return Err(invalid_data("message too long"));
+        }
+
+        let mut data = vec![0; header_len + body_len];
+        data[..16].copy_from_slice(&fixed);
+        self.stream.read_exact(&mut data[16..])?;
+
+        Ok((data, header_len, big_endian))
+    }
+}
+
+/// Encode a little-endian message with the given header fields.
+fn encode_message(kind: u8, serial: u32, fields: Vec<Value>, body: &[Value]) -> Vec<u8> {
+    let mut encoded_body = Writer::default();
+    for value in body {
+        encoded_body.write(value);
+    }
+
+    let mut message = Writer::default();
+    for value in [
+        Value::Byte(b'l'),
+        Value::Byte(kind),
+        Value::Byte(0),
+        Value::Byte(1),
+        Value::U32(encoded_body.data.len() as u32),
+        Value::U32(serial),
+        Value::Array("(yv)".into(), fields),
+    ] {
+        message.write(&value);
+    }
+    message.align(8);
+    message.data.extend_from_slice(&encoded_body.data);
+    message.data
+}
+
+/// Decode a message, whose header is `header_len` bytes long.
+///
+/// A body that can't be decoded is truncated to the values before the error, as the message itself
+/// may still be meaningful, like the reply to a method call.
+fn decode_message(data: &[u8], header_len: usize, big_endian: bool) -> io::Result<Message> {
+    let mut message = Message {
+        kind: data[1],
+        reply_serial: None,
+        path: None,
+        interface: None,
+        member: None,
+        error_name: None,
+        body: Vec::new(),
+    };
+    let mut header = Reader { data: &data[..header_len], pos: 12, big_endian };
+    let mut signature = String::new();
+    for field in header.read("a(yv)")?.as_array().unwrap_or_default() {
+        let Value::Struct(field) = field else { continue };
+        let (Some(Value::Byte(code)), Some(Value::Variant(value))) = (field.first(), field.get(1))
+        else {
+            continue;
+        };
+        let string = value.as_str().map(str::to_owned);
+        match *code {
+            PATH => message.path = string,
+            INTERFACE => message.interface = string,
+            MEMBER => message.member = string,
+            ERROR_NAME => message.error_name = string,
+            REPLY_SERIAL => message.reply_serial = value.as_u32(),
+            SIGNATURE => signature = string.unwrap_or_default(),
+            _ => (),
+        }
+    }
+
+    let mut body = Reader { data: &data[header_len..], pos: 0, big_endian };
+    let mut types = signature.as_str();
+    while !types.is_empty() {
+        let value = split_single_type(types).and_then(|(single, rest)| {
+            types = rest;
+            body.read(single)
+        });
+        match value {
+            Ok(value) => message.body.push(value),
+            Err(error) => {
+                tracing::debug!("truncated the body of a D-Bus message: {error}");
+                break;
+            },
+        }
+    }
+
+    if message.kind == ERROR && message.error_name.is_none() {
+        message.error_name = Some("unknown error".into());
+    }
+
+    Ok(message)
+}
+
+/// Connect to the first usable address of a `;` separated list.
+fn connect_any(addresses: &str) -> io::Result<UnixStream> {
+    addresses
+        .split(';')
+        .find_map(|address| connect(address).ok())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no usable bus address"))
+}
+
+fn connect(address: &str) -> io::Result<UnixStream> {
+    let (transport, parameters) =
+        address.split_once(':').ok_or_else(|| invalid_data("invalid bus address"))?;
+    if transport != "unix" {
+        return Err(io::Error::new(io::ErrorKind::Unsupported, "unsupported bus transport"));
+    }
+
+    for parameter in parameters.split(',') {
+        match parameter.split_once('=') {
+            Some(("path", path)) => return UnixStream::connect(unescape(path)?),
+            #[cfg(target_os = "linux")]
+            Some(("abstract", name)) => {
+                use std::os::linux::net::SocketAddrExt;
+                use std::os::unix::net::SocketAddr;
+
+                let address = SocketAddr::from_abstract_name(unescape(name)?.as_bytes())?;
+                return UnixStream::connect_addr(&address);
+            },
+            _ => (),
+        }
+    }
+
+    Err(io::Error::new(io::ErrorKind::Unsupported, "unsupported bus address"))
+}
+
+/// Decode the `%XX` escapes of the values in the bus addresses.
+fn unescape(value: &str) -> io::Result<String> {
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut chars = value.bytes();
+    while let Some(byte) = chars.next() {
+        if byte == b'%' {
+            let hex = [chars.next().unwrap_or(0), chars.next().unwrap_or(0)];
+            let hex = std::str::from_utf8(&hex).map_err(|_| invalid_data("invalid escape"))?;
+            bytes.push(u8::from_str_radix(hex, 16).map_err(|_| invalid_data("invalid escape"))?);
+        } else {
+            bytes.push(byte);
+        }
+    }
+    String::from_utf8(bytes).map_err(|_| invalid_data("invalid escape"))
+}
+
+fn header_field(code: u8, value: Value) -> Value {
+    Value::Struct(vec![Value::Byte(code), Value::Variant(Box::new(value))])
+}
+
+fn invalid_data(error: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+/// Split the first complete type off a signature.
+fn split_single_type(signature: &str) -> io::Result<(&str, &str)> {
+    let bytes = signature.as_bytes();
+    let mut end = 0;
+    // Skip the array markers, their element follows.
+    while bytes.get(end) == Some(&b'a') {
+        end += 1;
+    }
+
+    match bytes.get(end) {
+        Some(b'(' | b'{') => {
+            let mut depth = 0;
+            for (index, byte) in bytes.iter().enumerate().skip(end) {
+                match byte {
+                    b'(' | b'{' => depth += 1,
+                    b')' | b'}' => depth -= 1,
+                    _ => (),
+                }
+                if depth == 0 {
+                    return Ok(signature.split_at(index + 1));
+                }
+            }
+            Err(invalid_data("unbalanced signature"))
+        },
+        Some(_) => Ok(signature.split_at(end + 1)),
+        None => Err(invalid_data("incomplete signature")),
+    }
+}
+
+fn alignment(signature: &str) -> usize {
+    match signature.as_bytes().first() {
+        Some(b'y' | b'g' | b'v') => 1,
+        Some(b'n' | b'q') => 2,
+        Some(b'x' | b't' | b'd' | b'(' | b'{') => 8,
+        _ => 4,
+    }
+}
+
+#[derive(Debug, Default)]
+struct Writer {
+    data: Vec<u8>,
+}
+
+impl Writer {
+    fn align(&mut self, alignment: usize) {
+        let len = self.data.len().next_multiple_of(alignment);
+        self.data.resize(len, 0);
+    }
+
+    fn write_u16(&mut self, value: u16) {
+        self.align(2);
+        self.data.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn write_u32(&mut self, value: u32) {
+        self.align(4);
+        self.data.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        self.align(8);
+        self.data.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn write(&mut self, value: &Value) {
+        match value {
+            Value::Byte(byte) => self.data.push(*byte),
+            Value::Bool(value) => self.write_u32(*value as u32),
+            Value::I16(value) => self.write_u16(*value as u16),
+            Value::U16(value) => self.write_u16(*value),
+            Value::I32(value) => self.write_u32(*value as u32),
+            Value::U32(value) | Value::UnixFd(value) => self.write_u32(*value),
+            Value::I64(value) => self.write_u64(*value as u64),
+            Value::U64(value) => self.write_u64(*value),
+            Value::Double(value) => self.write_u64(value.to_bits()),
+            Value::Str(string) | Value::ObjectPath(string) => {
+                self.write_u32(string.len() as u32);
+                self.data.extend_from_slice(string.as_bytes());
+                self.data.push(0);
+            },
+            Value::Signature(signature) => {
+                self.data.push(signature.len() as u8);
+                self.data.extend_from_slice(signature.as_bytes());
+                self.data.push(0);
+            },
+            Value::Array(element, elements) => {
+                self.write_u32(0);
+                let len_pos = self.data.len() - 4;
+                // The padding before the first element isn't part of the length.
+                self.align(alignment(element));
+                let start = self.data.len();
+                for element in elements {
+                    self.write(element);
+                }
+                let len = (self.data.len() - start) as u32;
+                self.data[len_pos..len_pos + 4].copy_from_slice(&len.to_le_bytes());
+            },
+            Value::Struct(fields) => {
+                self.align(8);
+                for field in fields {
+                    self.write(field);
+                }
+            },
+            Value::DictEntry(key, value) => {
+                self.align(8);
+                self.write(key);
+                self.write(value);
+            },
+            Value::Variant(value) => {
+                self.write(&Value::Signature(value.signature()));
+                self.write(value);
+            },
+        }
+    }
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+    big_endian: bool,
+}
+
+impl Reader<'_> {
+    fn align(&mut self, alignment: usize) {
+        self.pos = self.pos.next_multiple_of(alignment);
+    }
+
+    fn take(&mut self, len: usize) -> io::Result<&[u8]> {
+        let bytes = self
+            .data
+            .get(self.pos..self.pos + len)
+            .ok_or_else(|| invalid_data("truncated message"))?;
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn read_u16(&mut self) -> io::Result<u16> {
+        self.align(2);
+        let bytes = self.take(2)?.try_into().unwrap();
+        Ok(if self.big_endian { u16::from_be_bytes(bytes) } else { u16::from_le_bytes(bytes) })
+    }
+
+    fn read_u32(&mut self) -> io::Result<u32> {
+        self.align(4);
+        let bytes = self.take(4)?.try_into().unwrap();
+        Ok(if self.big_endian { u32::from_be_bytes(bytes) } else { u32::from_le_bytes(bytes) })
+    }
+
+    fn read_u64(&mut self) -> io::Result<u64> {
+        self.align(8);
+        let bytes = self.take(8)?.try_into().unwrap();
+        Ok(if self.big_endian { u64::from_be_bytes(bytes) } else { u64::from_le_bytes(bytes) })
+    }
+
+    fn read_string(&mut self, len: usize) -> io::Result<String> {
+        let bytes = self.take(len + 1)?;
+        String::from_utf8(bytes[..len].to_vec()).map_err(|_| invalid_data("invalid string"))
+    }
+
+    /// Read a value of the single complete type `signature`.
+    fn read(&mut self, signature: &str) -> io::Result<Value> {
+        Ok(match signature.as_bytes()[0] {
+            b'y' => Value::Byte(self.take(1)?[0]),
+            b'b' => Value::Bool(self.read_u32()? != 0),
+            b'n' => Value::I16(self.read_u16()? as i16),
+            b'q' => Value::U16(self.read_u16()?),
+            b'i' => Value::I32(self.read_u32()? as i32),
+            b'u' => Value::U32(self.read_u32()?),
+            b'x' => Value::I64(self.read_u64()? as i64),
+            b't' => Value::U64(self.read_u64()?),
+            b'd' => Value::Double(f64::from_bits(self.read_u64()?)),
+            b'h' => Value::UnixFd(self.read_u32()?),
+            b's' => {
+                let len = self.read_u32()? as usize;
+                Value::Str(self.read_string(len)?)
+            },
+            b'o' => {
+                let len = self.read_u32()? as usize;
+                Value::ObjectPath(self.read_string(len)?)
+            },
+            b'g' => {
+                let len = self.take(1)?[0] as usize;
+                Value::Signature(self.read_string(len)?)
+            },
+            b'a' => {
+                let len = self.read_u32()? as usize;
+                let element = &signature[1..];
+                self.align(alignment(element));
+                let end = self.pos + len;
+                let mut elements = Vec::new();
+                while self.pos < end {
+                    elements.push(self.read(element)?);
+                }
+                Value::Array(element.into(), elements)
+            },
+            b'(' | b'{' => {
+                self.align(8);
+                let mut fields = Vec::new();
+                let mut types = &signature[1..signature.len() - 1];
+                while !types.is_empty() {
+                    let (single, rest) = split_single_type(types)?;
+                    fields.push(self.read(single)?);
+                    types = rest;
+                }
+                if signature.starts_with('{') && fields.len() == 2 {
+                    let value = fields.pop().unwrap();
+                    let key = fields.pop().unwrap();
+                    Value::DictEntry(Box::new(key), Box::new(value))
+                } else {
+                    Value::Struct(fields)
+                }
+            },
+            b'v' => {
+                let len = self.take(1)?[0] as usize;
+                let signature = self.read_string(len)?;
+                let (single, _) = split_single_type(&signature)?;
+                Value::Variant(Box::new(self.read(single)?))
+            },
+            _ => return Err(invalid_data("unsupported type")),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let values = [
+            Value::Str("winit".into()),
+            Value::Variant(Box::new(Value::Double(1.25))),
+            Value::dict(vec![
+                ("multiple", Value::Bool(true)),
+                (
+                    "current_folder",
+                    Value::Array("y".into(), vec![Value::Byte(b'/'), Value::Byte(0)]),
+                ),
+            ]),
+            Value::Array("(sa(us))".into(), vec![Value::Struct(vec![
+                Value::Str("Images".into()),
+                Value::Array("(us)".into(), vec![Value::Struct(vec![
+                    Value::U32(0),
+                    Value::Str("*.png".into()),
+                ])]),
+            ])]),
+        ];
+
+        let mut writer = Writer::default();
+        for value in &values {
+            writer.write(value);
+        }
+
+        let mut reader = Reader { data: &writer.data, pos: 0, big_endian: false };
+        for value in &values {
+            assert_eq!(&reader.read(&value.signature()).unwrap(), value);
+        }
+        assert_eq!(reader.pos, writer.data.len());
+    }
+
+    #[test]
+    fn basic_types_round_trip() {
+        let values = [
+            Value::Byte(0xfe),
+            Value::Bool(true),
+            Value::I16(-2),
+            Value::U16(0xfffe),
+            Value::I32(-3),
+            Value::U32(0xffff_fffd),
+            Value::I64(-4),
+            Value::U64(0xffff_ffff_ffff_fffc),
+            Value::Double(-0.5),
+            Value::UnixFd(1),
+            Value::Str("winit".into()),
+            Value::ObjectPath("/org/freedesktop/login1".into()),
+            Value::Signature("a{sv}".into()),
+        ];
+
+        for value in values {
+            // The byte before the value checks its alignment.
+            let pair = Value::Struct(vec![Value::Byte(1), value]);
+            let mut writer = Writer::default();
+            writer.write(&pair);
+            let mut reader = Reader { data: &writer.data, pos: 0, big_endian: false };
+            assert_eq!(reader.read(&pair.signature()).unwrap(), pair);
+            assert_eq!(reader.pos, writer.data.len());
+        }
+    }
+
+    #[test]
+    fn decode_properties_changed() {
+        let body = [
+            Value::Str("org.freedesktop.login1.Session".into()),
+            Value::dict(vec![
+                ("IdleHint", Value::Bool(false)),
+                ("IdleSinceHint", Value::U64(1_700_000_000_000_000)),
+                ("LockedHint", Value::Bool(true)),
+            ]),
+            Value::Array("s".into(), vec![]),
+        ];
+        let signature = body.iter().map(Value::signature).collect();
+        let fields = vec![
+            header_field(PATH, Value::ObjectPath("/org/freedesktop/login1/session/_32".into())),
+            header_field(INTERFACE, Value::Str("org.freedesktop.DBus.Properties".into())),
+            header_field(MEMBER, Value::Str("PropertiesChanged".into())),
+            header_field(SIGNATURE, Value::Signature(signature)),
+        ];
+        let data = encode_message(SIGNAL, 7, fields, &body);
+        let mut encoded_body = Writer::default();
+        for value in &body {
+            encoded_body.write(value);
+        }
+        let header_len = data.len() - encoded_body.data.len();
+
+        let message = decode_message(&data, header_len, false).unwrap();
+        assert!(message.is_signal("org.freedesktop.DBus.Properties", "PropertiesChanged"));
+        assert_eq!(message.body, body);
+    }
+
+    #[test]
+    fn big_endian() {
+        let data = [0, 0, 0, 0, 0, 0, 0, 1, 0xff, 0xfe];
+        let mut reader = Reader { data: &data, pos: 0, big_endian: true };
+        assert_eq!(reader.read("t").unwrap(), Value::U64(1));
+        assert_eq!(reader.read("n").unwrap(), Value::I16(-2));
+    }
+
+    #[test]
+    fn single_types() {
+        assert_eq!(split_single_type("ua{sv}").unwrap(), ("u", "a{sv}"));
+        assert_eq!(split_single_type("a{sv}").unwrap(), ("a{sv}", ""));
+        assert_eq!(split_single_type("a(sa(us))s").unwrap(), ("a(sa(us))", "s"));
+    }
+}
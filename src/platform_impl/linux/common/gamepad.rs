@@ -0,0 +1,652 @@
+//! Gamepads, read from the `evdev` devices of `/dev/input`.
+//!
+//! A thread waits for the input of the gamepads, and for the devices which are created and removed
+//! in `/dev/input`, watched with `inotify`.
+
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::os::unix::fs::OpenOptionsExt;
+use std::os::unix::io::{AsRawFd, FromRawFd, OwnedFd};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use std::{mem, slice, thread};
+
+use crate::error::{NotSupportedError, RequestError};
+use crate::event::{DeviceId, DeviceInfo, DeviceKind, ElementState};
+use crate::gamepad::{BatteryLevel, GamepadAxis, GamepadButton, GamepadEvent};
+
+const INPUT_PATH: &str = "/dev/input";
+const SYSFS_INPUT_PATH: &str = "/sys/class/input";
+/// How often the batteries of the gamepads are checked.
+const BATTERY_INTERVAL: Duration = Duration::from_secs(60);
+
+const EV_KEY: u16 = 0x01;
+const EV_ABS: u16 = 0x03;
+const EV_FF: u16 = 0x15;
+
+/// The first button of the gamepads, which tells them apart from the other devices.
+const BTN_GAMEPAD: u16 = 0x130;
+const BTN_SOUTH: u16 = 0x130;
+const BTN_EAST: u16 = 0x131;
+const BTN_NORTH: u16 = 0x133;
+const BTN_WEST: u16 = 0x134;
+const BTN_TL: u16 = 0x136;
+const BTN_TR: u16 = 0x137;
+const BTN_TL2: u16 = 0x138;
+const BTN_TR2: u16 = 0x139;
+const BTN_SELECT: u16 = 0x13a;
+const BTN_START: u16 = 0x13b;
+const BTN_MODE: u16 = 0x13c;
+const BTN_THUMBL: u16 = 0x13d;
+const BTN_THUMBR: u16 = 0x13e;
+const BTN_DPAD_UP: u16 = 0x220;
+const BTN_DPAD_DOWN: u16 = 0x221;
+const BTN_DPAD_LEFT: u16 = 0x222;
+const BTN_DPAD_RIGHT: u16 = 0x223;
+const KEY_CNT: usize = 0x300;
+
+const ABS_X: u16 = 0x00;
+const ABS_Y: u16 = 0x01;
+const ABS_Z: u16 = 0x02;
+const ABS_RX: u16 = 0x03;
+const ABS_RY: u16 = 0x04;
+const ABS_RZ: u16 = 0x05;
+const ABS_HAT0X: u16 = 0x10;
+const ABS_HAT0Y: u16 = 0x11;
+const ABS_CNT: usize = 0x40;
+
+const FF_RUMBLE: u16 = 0x50;
+const FF_CNT: usize = 0x80;
+
+const IOC_WRITE: u64 = 1;
+const IOC_READ: u64 = 2;
+
+/// A request of `ioctl` on an `evdev` device, like the `_IOC` macro of the kernel.
+const fn evdev_request(direction: u64, number: u64, size: usize) -> u64 {
+    (direction << 30) | ((size as u64) << 16) | ((b'E' as u64) << 8) | number
+}
+
+const EVIOCGID: u64 = evdev_request(IOC_READ, 0x02, mem::size_of::<libc::input_id>());
+const EVIOCSFF: u64 = evdev_request(IOC_WRITE, 0x80, mem::size_of::<libc::ff_effect>());
+
+const fn eviocgname(size: usize) -> u64 {
+    evdev_request(IOC_READ, 0x06, size)
+}
+
+const fn eviocgbit(event_type: u16, size: usize) -> u64 {
+    evdev_request(IOC_READ, 0x20 + event_type as u64, size)
+}
+
+const fn eviocgabs(axis: u16) -> u64 {
+    evdev_request(IOC_READ, 0x40 + axis as u64, mem::size_of::<libc::input_absinfo>())
+}
+
+fn ioctl<T>(device: &File, request: u64, arg: &mut T) -> io::Result<()> {
+    if unsafe { libc::ioctl(device.as_raw_fd(), request as _, arg as *mut T) } < 0 {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(())
+    }
+}
+
+fn has_bit(bits: &[u8], bit: usize) -> bool {
+    bits.get(bit / 8).is_some_and(|byte| byte & (1 << (bit % 8)) != 0)
+}
+
+/// The identifier of the gamepad of `/dev/input/event<number>`.
+///
+/// The identifiers are negative, so they don't collide with the ones of the display server.
+fn device_id(number: u32) -> DeviceId {
+    DeviceId::from_raw(-1 - number as i64)
+}
+
+/// A change of the gamepads, sent from the thread of the [`GamepadWatcher`].
+#[derive(Debug)]
+pub enum GamepadMessage {
+    Added(DeviceInfo),
+    Removed(DeviceId),
+    Event(DeviceId, GamepadEvent),
+}
+
+/// Watches the gamepads until dropped.
+#[derive(Debug)]
+pub struct GamepadWatcher {
+    gamepads: Arc<Mutex<HashMap<u32, Gamepad>>>,
+    // Closing the pipe unblocks the thread, which then exits.
+    _stop: OwnedFd,
+}
+
+impl GamepadWatcher {
+    /// Call `on_message` from another thread each time a gamepad is connected, disconnected, or
+    /// sends input.
+    ///
+    /// The gamepads already connected are not reported as added.
+    pub fn new(on_message: impl FnMut(GamepadMessage) + Send + 'static) -> io::Result<Self> {
+        let inotify = unsafe { libc::inotify_init1(libc::IN_CLOEXEC | libc::IN_NONBLOCK) };
+        if inotify < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let inotify = unsafe { File::from_raw_fd(inotify) };
+        let path = b"/dev/input\0".as_ptr().cast();
+        let mask = libc::IN_CREATE | libc::IN_ATTRIB | libc::IN_DELETE;
+        if unsafe { libc::inotify_add_watch(inotify.as_raw_fd(), path, mask) } < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let mut pipe = [0; 2];
+        if unsafe { libc::pipe2(pipe.as_mut_ptr(), libc::O_CLOEXEC) } < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let (stopped, stop) =
+            unsafe { (OwnedFd::from_raw_fd(pipe[0]), OwnedFd::from_raw_fd(pipe[1])) };
+
+        let mut gamepads = HashMap::new();
+        for entry in fs::read_dir(INPUT_PATH)?.flatten() {
+            if let Some(number) = event_number(&entry.file_name().to_string_lossy()) {
+                if let Some(gamepad) = Gamepad::open(number) {
+                    gamepads.insert(number, gamepad);
+                }
+            }
+        }
+        let gamepads = Arc::new(Mutex::new(gamepads));
+
+        let thread_gamepads = gamepads.clone();
+        thread::Builder::new().name("winit gamepads".into()).spawn(move || {
+            if let Err(error) = watch(&thread_gamepads, &inotify, &stopped, on_message) {
+                tracing::debug!("stopped watching the gamepads: {error}");
+            }
+        })?;
+
+        Ok(Self { gamepads, _stop: stop })
+    }
+
+    /// The gamepads which are currently connected.
+    pub fn gamepads(&self) -> Vec<DeviceInfo> {
+        self.gamepads.lock().unwrap().values().map(|gamepad| gamepad.info.clone()).collect()
+    }
+
+    /// Make the gamepad vibrate, or stop it when both magnitudes are `0.0`.
+    pub fn set_rumble(
+        &self,
+        device_id: DeviceId,
+        strong: f32,
+        weak: f32,
+    ) -> Result<(), RequestError> {
+        let mut gamepads = self.gamepads.lock().unwrap();
+        let gamepad = gamepads
+            .values_mut()
+            .find(|gamepad| gamepad.info.id == device_id)
+            .ok_or_else(|| NotSupportedError::new("the device is not a connected gamepad"))?;
+        if !gamepad.supports_rumble {
+            return Err(NotSupportedError::new("the gamepad doesn't support rumble").into());
+        }
+        gamepad.set_rumble(strong, weak).map_err(|error| os_error!(error).into())
+    }
+}
+
+fn watch(
+    gamepads: &Mutex<HashMap<u32, Gamepad>>,
+    mut inotify: &File,
+    stopped: &OwnedFd,
+    mut on_message: impl FnMut(GamepadMessage),
+) -> io::Result<()> {
+    let mut next_battery_check = Instant::now();
+    loop {
+        let now = Instant::now();
+        if now >= next_battery_check {
+            for gamepad in gamepads.lock().unwrap().values_mut() {
+                if let Some(event) = gamepad.update_battery() {
+                    on_message(GamepadMessage::Event(gamepad.info.id, event));
+                }
+            }
+            next_battery_check = now + BATTERY_INTERVAL;
+        }
+
+        let pollfd = |fd| libc::pollfd { fd, events: libc::POLLIN, revents: 0 };
+        let mut fds = vec![pollfd(stopped.as_raw_fd()), pollfd(inotify.as_raw_fd())];
+        let numbers: Vec<u32> = {
+            let gamepads = gamepads.lock().unwrap();
+            fds.extend(gamepads.values().map(|gamepad| pollfd(gamepad.device.as_raw_fd())));
+            gamepads.keys().copied().collect()
+        };
+
+        let timeout = next_battery_check.saturating_duration_since(now).as_millis() as libc::c_int;
+        if unsafe { libc::poll(fds.as_mut_ptr(), fds.len() as _, timeout) } < 0 {
+            let error = io::Error::last_os_error();
+            if error.kind() == io::ErrorKind::Interrupted {
+                continue;
+            }
+            return Err(error);
+        }
+
+        // The other end of the pipe was closed.
+        if fds[0].revents != 0 {
+            return Ok(());
+        }
+
+        if fds[1].revents != 0 {
+            let mut buffer = [0u8; 4096];
+            let len = match inotify.read(&mut buffer) {
+                Ok(len) => len,
+                Err(error) if error.kind() == io::ErrorKind::WouldBlock => 0,
+                Err(error) => return Err(error),
+            };
+            for (mask, name) in inotify_events(&buffer[..len]) {
+                let Some(number) = event_number(&name) else { continue };
+                let mut gamepads = gamepads.lock().unwrap();
+                if mask & libc::IN_DELETE != 0 {
+                    if gamepads.remove(&number).is_some() {
+                        on_message(GamepadMessage::Removed(device_id(number)));
+                    }
+                } else if let Entry::Vacant(entry) = gamepads.entry(number) {
+                    // The device can only be opened once udev changed its permissions, which is
+                    // reported by `IN_ATTRIB`.
+                    if let Some(mut gamepad) = Gamepad::open(number) {
+                        on_message(GamepadMessage::Added(gamepad.info.clone()));
+                        if let Some(event) = gamepad.update_battery() {
+                            on_message(GamepadMessage::Event(gamepad.info.id, event));
+                        }
+                        entry.insert(gamepad);
+                    }
+                }
+            }
+        }
+
+        for (fd, number) in fds[2..].iter().zip(numbers) {
+            if fd.revents == 0 {
+                continue;
+            }
+            let mut gamepads = gamepads.lock().unwrap();
+            let Some(gamepad) = gamepads.get_mut(&number) else { continue };
+            let id = gamepad.info.id;
+            match gamepad.read_events() {
+                Ok(events) => {
+                    for event in events {
+                        on_message(GamepadMessage::Event(id, event));
+                    }
+                },
+                Err(error) => {
+                    // The gamepad was most likely unplugged.
+                    tracing::debug!("stopped reading the gamepad {number}: {error}");
+                    gamepads.remove(&number);
+                    on_message(GamepadMessage::Removed(id));
+                },
+            }
+        }
+    }
+}
+
+/// The mask and the file name of the `inotify` events in `buffer`.
+fn inotify_events(mut buffer: &[u8]) -> Vec<(u32, String)> {
+    const HEADER: usize = mem::size_of::<libc::inotify_event>();
+
+    let mut events = Vec::new();
+    while buffer.len() >= HEADER {
+        let field = |offset: usize| {
+            u32::from_ne_bytes(buffer[offset..offset + 4].try_into().unwrap()) as usize
+        };
+        let mask = field(4) as u32;
+        let len = field(12);
+        let Some(name) = buffer.get(HEADER..HEADER + len) else { break };
+        let name = name.split(|&byte| byte == 0).next().unwrap_or_default();
+        events.push((mask, String::from_utf8_lossy(name).into_owned()));
+        buffer = &buffer[HEADER + len..];
+    }
+    events
+}
+
+/// The number of the `evdev` device named `event<number>`.
+fn event_number(name: &str) -> Option<u32> {
+    name.strip_prefix("event")?.parse().ok()
+}
+
+/// The range of an absolute axis.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct AxisRange {
+    minimum: i32,
+    maximum: i32,
+    /// The values around the center which are reported as the center.
+    flat: i32,
+}
+
+#[derive(Debug)]
+struct Gamepad {
+    device: File,
+    number: u32,
+    info: DeviceInfo,
+    axes: HashMap<u16, AxisRange>,
+    /// The position of the directional pad reported as axes.
+    hat: (i32, i32),
+    supports_rumble: bool,
+    /// The identifier of the rumble effect, once uploaded to the device.
+    rumble_effect: Option<i16>,
+    battery: Option<(BatteryLevel, bool)>,
+}
+
+impl Gamepad {
+    /// Open `/dev/input/event<number>`, if it's a gamepad.
+    fn open(number: u32) -> Option<Self> {
+        let path = Path::new(INPUT_PATH).join(format!("event{number}"));
+        // Writing is only needed for rumble.
+        let open = |write| {
+            OpenOptions::new()
+                .read(true)
+                .write(write)
+                .custom_flags(libc::O_NONBLOCK | libc::O_CLOEXEC)
+                .open(&path)
+        };
+        let device = open(true).or_else(|_| open(false)).ok()?;
+
+        let mut keys = [0u8; KEY_CNT / 8];
+        ioctl(&device, eviocgbit(EV_KEY, keys.len()), &mut keys).ok()?;
+        if !has_bit(&keys, BTN_GAMEPAD as usize) {
+            return None;
+        }
+
+        let mut abs = [0u8; ABS_CNT / 8];
+        let _ = ioctl(&device, eviocgbit(EV_ABS, abs.len()), &mut abs);
+        let axes = (0..ABS_CNT as u16)
+            .filter(|&axis| has_bit(&abs, axis as usize))
+            .filter_map(|axis| {
+                let mut info: libc::input_absinfo = unsafe { mem::zeroed() };
+                ioctl(&device, eviocgabs(axis), &mut info).ok()?;
+                Some((axis, AxisRange {
+                    minimum: info.minimum,
+                    maximum: info.maximum,
+                    flat: info.flat,
+                }))
+            })
+            .collect();
+
+        let mut ff = [0u8; FF_CNT / 8];
+        let supports_rumble = ioctl(&device, eviocgbit(EV_FF, ff.len()), &mut ff).is_ok()
+            && has_bit(&ff, FF_RUMBLE as usize);
+
+        let mut name = [0u8; 256];
+        let name = ioctl(&device, eviocgname(name.len()), &mut name).ok().map(|()| {
+            let name = name.split(|&byte| byte == 0).next().unwrap_or_default();
+            String::from_utf8_lossy(name).into_owned()
+        });
+
+        let mut input_id: libc::input_id = unsafe { mem::zeroed() };
+        let (vendor_id, product_id) = match ioctl(&device, EVIOCGID, &mut input_id) {
+            Ok(()) => (Some(input_id.vendor), Some(input_id.product)),
+            Err(_) => (None, None),
+        };
+
+        Some(Self {
+            device,
+            number,
+            info: DeviceInfo {
+                id: device_id(number),
+                kind: DeviceKind::Gamepad,
+                name,
+                vendor_id,
+                product_id,
+            },
+            axes,
+            hat: (0, 0),
+            supports_rumble,
+            rumble_effect: None,
+            battery: None,
+        })
+    }
+
+    /// Read the pending input of the gamepad.
+    fn read_events(&mut self) -> io::Result<Vec<GamepadEvent>> {
+        let mut events = Vec::new();
+        loop {
+            let mut buffer: [libc::input_event; 64] = unsafe { mem::zeroed() };
+            let bytes = unsafe {
+                slice::from_raw_parts_mut(
+                    buffer.as_mut_ptr().cast::<u8>(),
+                    mem::size_of_val(&buffer),
+                )
+            };
+            let len = match self.device.read(bytes) {
+                Ok(len) => len,
+                Err(error) if error.kind() == io::ErrorKind::WouldBlock => return Ok(events),
+                Err(error) => return Err(error),
+            };
+            if len == 0 {
+                return Err(io::ErrorKind::UnexpectedEof.into());
+            }
+
+            for event in &buffer[..len / mem::size_of::<libc::input_event>()] {
+                match event.type_ {
+                    EV_KEY if event.value != 2 => {
+                        let state = if event.value == 0 {
+                            ElementState::Released
+                        } else {
+                            ElementState::Pressed
+                        };
+                        events.push(GamepadEvent::Button { button: button(event.code), state });
+                    },
+                    EV_ABS if event.code == ABS_HAT0X || event.code == ABS_HAT0Y => {
+                        self.update_hat(event.code, event.value, &mut events);
+                    },
+                    EV_ABS => {
+                        events.push(GamepadEvent::Axis {
+                            axis: axis(event.code),
+                            value: self.normalize(event.code, event.value),
+                        });
+                    },
+                    // The synchronization events, including the ones reporting input dropped by the
+                    // kernel, which the next events correct.
+                    _ => (),
+                }
+            }
+        }
+    }
+
+    /// Translate the directional pad reported as axes to button presses.
+    fn update_hat(&mut self, code: u16, value: i32, events: &mut Vec<GamepadEvent>) {
+        let (previous, negative, positive) = if code == ABS_HAT0X {
+            (
+                mem::replace(&mut self.hat.0, value),
+                GamepadButton::DPadLeft,
+                GamepadButton::DPadRight,
+            )
+        } else {
+            (mem::replace(&mut self.hat.1, value), GamepadButton::DPadUp, GamepadButton::DPadDown)
+        };
+        if previous == value {
+            return;
+        }
+
+        let direction = |value: i32| match value.signum() {
+            -1 => Some(negative),
+            1 => Some(positive),
+            _ => None,
+        };
+        if let Some(button) = direction(previous) {
+            events.push(GamepadEvent::Button { button, state: ElementState::Released });
+        }
+        if let Some(button) = direction(value) {
+            events.push(GamepadEvent::Button { button, state: ElementState::Pressed });
+        }
+    }
+
+    fn normalize(&self, code: u16, value: i32) -> f64 {
+        match self.axes.get(&code) {
+            Some(&range) => normalize_axis(code, value, range),
+            None => value as f64,
+        }
+    }
+
+    fn set_rumble(&mut self, strong: f32, weak: f32) -> io::Result<()> {
+        let magnitude = |value: f32| (value.clamp(0.0, 1.0) * u16::MAX as f32) as u16;
+        let (strong, weak) = (magnitude(strong), magnitude(weak));
+        if strong == 0 && weak == 0 {
+            return match self.rumble_effect {
+                Some(effect) => self.play(effect, false),
+                None => Ok(()),
+            };
+        }
+
+        // Uploading the effect again with its identifier replaces it.
+        let mut effect: libc::ff_effect = unsafe { mem::zeroed() };
+        effect.type_ = FF_RUMBLE;
+        effect.id = self.rumble_effect.unwrap_or(-1);
+        // The magnitudes of `ff_rumble_effect` start the union of the effects.
+        let rumble = effect.u.as_mut_ptr().cast::<u16>();
+        unsafe {
+            rumble.write(strong);
+            rumble.add(1).write(weak);
+        }
+        ioctl(&self.device, EVIOCSFF, &mut effect)?;
+        self.rumble_effect = Some(effect.id);
+
+        self.play(effect.id, true)
+    }
+
+    fn play(&mut self, effect: i16, play: bool) -> io::Result<()> {
+        let mut event: libc::input_event = unsafe { mem::zeroed() };
+        event.type_ = EV_FF;
+        event.code = effect as u16;
+        event.value = play as i32;
+        let bytes = unsafe {
+            slice::from_raw_parts(
+                (&event as *const libc::input_event).cast::<u8>(),
+                mem::size_of_val(&event),
+            )
+        };
+        self.device.write_all(bytes)
+    }
+
+    /// Read the battery of the gamepad, returning an event when it changed.
+    fn update_battery(&mut self) -> Option<GamepadEvent> {
+        let supplies = Path::new(SYSFS_INPUT_PATH)
+            .join(format!("event{}", self.number))
+            .join("device/device/power_supply");
+        let battery = fs::read_dir(supplies)
+            .ok()?
+            .flatten()
+            .find_map(|supply| battery_from(&supply.path()))?;
+        if self.battery.replace(battery) == Some(battery) {
+            return None;
+        }
+        let (level, charging) = battery;
+        Some(GamepadEvent::Battery { level, charging })
+    }
+}
+
+/// The level of the battery, and whether it's charging, of the `power_supply` at `supply`.
+fn battery_from(supply: &Path) -> Option<(BatteryLevel, bool)> {
+    let read = |attribute: &str| {
+        fs::read_to_string(supply.join(attribute)).ok().map(|value| value.trim().to_owned())
+    };
+
+    let level = match read("capacity").and_then(|capacity| capacity.parse::<u8>().ok()) {
+        Some(percent) => BatteryLevel::from_percent(percent),
+        None => match read("capacity_level")?.as_str() {
+            "Critical" => BatteryLevel::Empty,
+            "Low" => BatteryLevel::Low,
+            "Normal" => BatteryLevel::Medium,
+            "High" | "Full" => BatteryLevel::Full,
+            _ => return None,
+        },
+    };
+    Some((level, read("status").as_deref() == Some("Charging")))
+}
+
+/// Scale the `value` of an axis to the range of [`GamepadEvent::Axis`].
+fn normalize_axis(code: u16, value: i32, range: AxisRange) -> f64 {
+    let (minimum, maximum) = (range.minimum as f64, range.maximum as f64);
+    if maximum <= minimum {
+        return 0.0;
+    }
+    let value = value as f64;
+
+    if code == ABS_Z || code == ABS_RZ {
+        return ((value - minimum) / (maximum - minimum)).clamp(0.0, 1.0);
+    }
+
+    let center = (minimum + maximum) / 2.0;
+    if (value - center).abs() <= range.flat as f64 {
+        return 0.0;
+    }
+    let value = ((value - minimum) / (maximum - minimum) * 2.0 - 1.0).clamp(-1.0, 1.0);
+    // `evdev` reports the vertical axes pointing down.
+    if code == ABS_Y || code == ABS_RY {
+        -value
+    } else {
+        value
+    }
+}
+
+fn button(code: u16) -> GamepadButton {
+    match code {
+        BTN_SOUTH => GamepadButton::South,
+        BTN_EAST => GamepadButton::East,
+        BTN_NORTH => GamepadButton::North,
+        BTN_WEST => GamepadButton::West,
+        BTN_TL => GamepadButton::LeftShoulder,
+        BTN_TR => GamepadButton::RightShoulder,
+        BTN_TL2 => GamepadButton::LeftTrigger,
+        BTN_TR2 => GamepadButton::RightTrigger,
+        BTN_SELECT => GamepadButton::Select,
+        BTN_START => GamepadButton::Start,
+        BTN_MODE => GamepadButton::Mode,
+        BTN_THUMBL => GamepadButton::LeftStick,
+        BTN_THUMBR => GamepadButton::RightStick,
+        BTN_DPAD_UP => GamepadButton::DPadUp,
+        BTN_DPAD_DOWN => GamepadButton::DPadDown,
+        BTN_DPAD_LEFT => GamepadButton::DPadLeft,
+        BTN_DPAD_RIGHT => GamepadButton::DPadRight,
+        code => GamepadButton::Other(code),
+    }
+}
+
+fn axis(code: u16) -> GamepadAxis {
+    match code {
+        ABS_X => GamepadAxis::LeftStickX,
+        ABS_Y => GamepadAxis::LeftStickY,
+        ABS_RX => GamepadAxis::RightStickX,
+        ABS_RY => GamepadAxis::RightStickY,
+        ABS_Z => GamepadAxis::LeftTrigger,
+        ABS_RZ => GamepadAxis::RightTrigger,
+        code => GamepadAxis::Other(code),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn axes() {
+        let stick = AxisRange { minimum: -32768, maximum: 32767, flat: 128 };
+        assert_eq!(normalize_axis(ABS_X, 100, stick), 0.0);
+        assert_eq!(normalize_axis(ABS_X, 32767, stick), 1.0);
+        assert_eq!(normalize_axis(ABS_X, -32768, stick), -1.0);
+        // Up is positive.
+        assert_eq!(normalize_axis(ABS_Y, -32768, stick), 1.0);
+
+        let trigger = AxisRange { minimum: 0, maximum: 1023, flat: 0 };
+        assert_eq!(normalize_axis(ABS_Z, 0, trigger), 0.0);
+        assert_eq!(normalize_axis(ABS_RZ, 1023, trigger), 1.0);
+    }
+
+    #[test]
+    fn inotify() {
+        let mut buffer = Vec::new();
+        for (mask, name) in [(libc::IN_CREATE, &b"event7\0\0"[..]), (libc::IN_DELETE, b"js0\0")] {
+            buffer.extend(1i32.to_ne_bytes());
+            buffer.extend(mask.to_ne_bytes());
+            buffer.extend(0u32.to_ne_bytes());
+            buffer.extend((name.len() as u32).to_ne_bytes());
+            buffer.extend(name);
+        }
+
+        assert_eq!(inotify_events(&buffer), vec![
+            (libc::IN_CREATE, "event7".to_owned()),
+            (libc::IN_DELETE, "js0".to_owned())
+        ]);
+        assert_eq!(event_number("event7"), Some(7));
+        assert_eq!(event_number("js0"), None);
+    }
+}
@@ -0,0 +1,159 @@
+//! The session of the process, managed by `systemd-logind` on the system bus: its lock state, the
+//! sleep of the system, and the brightness of the devices it can set.
+
+use std::net::Shutdown;
+use std::os::unix::net::UnixStream;
+use std::{io, thread};
+
+use super::dbus::{Connection, Message, Value};
+
+const LOGIND_NAME: &str = "org.freedesktop.login1";
+const LOGIND_PATH: &str = "/org/freedesktop/login1";
+const MANAGER_INTERFACE: &str = "org.freedesktop.login1.Manager";
+const SESSION_INTERFACE: &str = "org.freedesktop.login1.Session";
+const PROPERTIES_INTERFACE: &str = "org.freedesktop.DBus.Properties";
+
+/// A change of the session or of the system reported by `systemd-logind`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionEvent {
+    /// The session was locked, or unlocked.
+    LockChanged(bool),
+    /// The system is about to sleep, or woke up.
+    PrepareForSleep(bool),
+}
+
+/// Watches the lock state of the session of the process and the sleep of the system until
+/// dropped.
+#[derive(Debug)]
+pub struct SessionWatcher {
+    stream: UnixStream,
+}
+
+impl SessionWatcher {
+    /// Call `on_event` from another thread for each change.
+    pub fn new(mut on_event: impl FnMut(SessionEvent) + Send + 'static) -> io::Result<Self> {
+        let mut connection = Connection::system()?;
+        let stream = connection.try_clone_stream()?;
+
+        thread::Builder::new().name("winit session".into()).spawn(move || {
+            let mut locked = None;
+            let result = watch(&mut connection, |event| match event {
+                SessionEvent::LockChanged(now_locked) if locked != Some(now_locked) => {
+                    // Don't report the initial state, only the changes.
+                    if locked.is_some() {
+                        on_event(event);
+                    }
+                    locked = Some(now_locked);
+                },
+                SessionEvent::LockChanged(_) => (),
+                SessionEvent::PrepareForSleep(_) => on_event(event),
+            });
+            if let Err(error) = result {
+                tracing::debug!("stopped watching the session: {error}");
+            }
+        })?;
+
+        Ok(Self { stream })
+    }
+}
+
+impl Drop for SessionWatcher {
+    fn drop(&mut self) {
+        // Unblock the thread, which then exits.
+        let _ = self.stream.shutdown(Shutdown::Both);
+    }
+}
+
+fn watch(connection: &mut Connection, mut on_event: impl FnMut(SessionEvent)) -> io::Result<()> {
+    connection.add_match(&format!(
+        "type='signal',interface='{MANAGER_INTERFACE}',member='PrepareForSleep',path='\
+         {LOGIND_PATH}'"
+    ))?;
+
+    // The sleep of the system is still reported to processes outside of a session, like the ones
+    // started by a service manager.
+    let session = session_path(connection)
+        .map_err(|error| tracing::debug!("can't watch the session lock: {error}"))
+        .ok();
+    if let Some(session) = &session {
+        let properties_rule = format!(
+            "type='signal',interface='{PROPERTIES_INTERFACE}',member='PropertiesChanged',arg0='\
+             {SESSION_INTERFACE}',path='{session}'"
+        );
+        connection.add_match(&properties_rule)?;
+        connection.add_match(&format!(
+            "type='signal',interface='{SESSION_INTERFACE}',path='{session}'"
+        ))?;
+
+        let reply = connection.call(LOGIND_NAME, session, PROPERTIES_INTERFACE, "Get", vec![
+            Value::Str(SESSION_INTERFACE.into()),
+            Value::Str("LockedHint".into()),
+        ])?;
+        if let Some(Value::Variant(locked)) = reply.body.first() {
+            on_event(SessionEvent::LockChanged(locked.as_bool().unwrap_or(false)));
+        }
+    }
+
+    loop {
+        let signal = connection.next_signal()?;
+        if signal.is_signal(MANAGER_INTERFACE, "PrepareForSleep") {
+            if let Some(sleeping) = signal.body.first().and_then(Value::as_bool) {
+                on_event(SessionEvent::PrepareForSleep(sleeping));
+            }
+        } else if signal.path.is_some() && signal.path == session {
+            if let Some(locked) = lock_state(&signal) {
+                on_event(SessionEvent::LockChanged(locked));
+            }
+        }
+    }
+}
+
+/// Set the brightness of a device of the kernel, like a backlight, through the session of the
+/// process, which is allowed to even when the process can't write to sysfs.
+pub fn set_brightness(subsystem: &str, name: &str, brightness: u32) -> io::Result<()> {
+    let mut connection = Connection::system()?;
+    let session = session_path(&mut connection)?;
+    connection
+        .call(LOGIND_NAME, &session, SESSION_INTERFACE, "SetBrightness", vec![
+            Value::Str(subsystem.into()),
+            Value::Str(name.into()),
+            Value::U32(brightness),
+        ])
+        .map(drop)
+}
+
+/// The object path of the session the process belongs to.
+fn session_path(connection: &mut Connection) -> io::Result<String> {
+    // `auto` is only understood since systemd 243.
+    let reply = connection
+        .call(LOGIND_NAME, LOGIND_PATH, MANAGER_INTERFACE, "GetSession", vec![Value::Str(
+            "auto".into(),
+        )])
+        .or_else(|_| {
+            connection.call(LOGIND_NAME, LOGIND_PATH, MANAGER_INTERFACE, "GetSessionByPID", vec![
+                Value::U32(std::process::id()),
+            ])
+        })?;
+    reply
+        .body
+        .first()
+        .and_then(Value::as_str)
+        .map(str::to_owned)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid session path"))
+}
+
+/// The lock state announced by a signal of the session, if any.
+///
+/// Screen lockers set the `LockedHint` property, while the `Lock` and `Unlock` signals ask them to
+/// lock or unlock the session.
+fn lock_state(signal: &Message) -> Option<bool> {
+    if signal.is_signal(SESSION_INTERFACE, "Lock") {
+        Some(true)
+    } else if signal.is_signal(SESSION_INTERFACE, "Unlock") {
+        Some(false)
+    } else if signal.is_signal(PROPERTIES_INTERFACE, "PropertiesChanged") {
+        signal.body.get(1)?.lookup("LockedHint")?.as_bool()
+    } else {
+        None
+    }
+}
@@ -0,0 +1,139 @@
+//! Message boxes shown by running `zenity`, or `kdialog` when it's not installed.
+//!
+//! The user's text is always passed as `--option=value`, so text starting with `-` isn't parsed as
+//! an option. Neither tool can be parented to a Wayland surface, so the message box is only
+//! attached to its parent window on X11; the windows it blocks are handled by winit itself.
+
+use std::process::{Command, Output, Stdio};
+use std::{io, thread};
+
+use crate::dialogs::{
+    MessageBoxButton, MessageBoxButtons, MessageBoxConfig, MessageBoxLevel, MessageBoxResult,
+};
+use crate::error::RequestError;
+
+/// Show the message box from another thread, calling `done` with the button pressed by the user.
+///
+/// `attach` is the X11 window the message box is shown on top of.
+pub fn show_message_box(
+    config: MessageBoxConfig,
+    attach: Option<u32>,
+    done: impl FnOnce(MessageBoxResult) + Send + 'static,
+) -> Result<(), RequestError> {
+    thread::Builder::new()
+        .name("winit message box".into())
+        .spawn(move || {
+            let result = match run_zenity(&config, attach) {
+                Err(error) if error.kind() == io::ErrorKind::NotFound => {
+                    run_kdialog(&config, attach)
+                },
+                result => result,
+            };
+            done(match result {
+                Ok(button) => MessageBoxResult::Pressed(button),
+                Err(error) => MessageBoxResult::Failed(error.to_string()),
+            });
+        })
+        .map_err(|error| os_error!(error))?;
+    Ok(())
+}
+
+fn run_zenity(config: &MessageBoxConfig, attach: Option<u32>) -> io::Result<MessageBoxButton> {
+    let mut command = Command::new("zenity");
+    command.args([
+        "--no-markup".to_owned(),
+        format!("--title={}", config.title),
+        format!("--text={}", config.text),
+    ]);
+    if let Some(window) = attach {
+        command.arg(format!("--attach={window}"));
+    }
+    match config.buttons {
+        MessageBoxButtons::Ok => {
+            command.arg(match config.level {
+                MessageBoxLevel::Info => "--info",
+                MessageBoxLevel::Warning => "--warning",
+                MessageBoxLevel::Error => "--error",
+            });
+        },
+        buttons => {
+            let (ok, cancel) = match buttons {
+                MessageBoxButtons::OkCancel => ("OK", "Cancel"),
+                MessageBoxButtons::YesNo => ("Yes", "No"),
+                _ => ("Yes", "Cancel"),
+            };
+            command.args(["--question", "--ok-label", ok, "--cancel-label", cancel]);
+            // Questions have an icon of their own, which is kept for informative messages.
+            let icon = match config.level {
+                MessageBoxLevel::Info => None,
+                MessageBoxLevel::Warning => Some("dialog-warning"),
+                MessageBoxLevel::Error => Some("dialog-error"),
+            };
+            if let Some(icon) = icon {
+                command.args(["--icon-name", icon]);
+            }
+            if buttons == MessageBoxButtons::YesNoCancel {
+                // The label of the extra button is printed when it's pressed.
+                command.args(["--extra-button", "No"]);
+            }
+        },
+    }
+
+    let output = run(&mut command)?;
+    match (config.buttons, output.status.code()) {
+        (MessageBoxButtons::Ok | MessageBoxButtons::OkCancel, Some(0)) => Ok(MessageBoxButton::Ok),
+        (MessageBoxButtons::YesNo | MessageBoxButtons::YesNoCancel, Some(0)) => {
+            Ok(MessageBoxButton::Yes)
+        },
+        (MessageBoxButtons::YesNoCancel, Some(1)) if output.stdout.starts_with(b"No") => {
+            Ok(MessageBoxButton::No)
+        },
+        (buttons, Some(1)) => Ok(buttons.dismissed()),
+        _ => Err(failed("zenity", &output)),
+    }
+}
+
+fn run_kdialog(config: &MessageBoxConfig, attach: Option<u32>) -> io::Result<MessageBoxButton> {
+    let warning = config.level != MessageBoxLevel::Info;
+    let kind = match config.buttons {
+        MessageBoxButtons::Ok => match config.level {
+            MessageBoxLevel::Info => "--msgbox",
+            MessageBoxLevel::Warning => "--sorry",
+            MessageBoxLevel::Error => "--error",
+        },
+        MessageBoxButtons::OkCancel | MessageBoxButtons::YesNo if warning => "--warningyesno",
+        MessageBoxButtons::OkCancel | MessageBoxButtons::YesNo => "--yesno",
+        MessageBoxButtons::YesNoCancel if warning => "--warningyesnocancel",
+        MessageBoxButtons::YesNoCancel => "--yesnocancel",
+    };
+
+    let mut command = Command::new("kdialog");
+    command.args([format!("--title={}", config.title), format!("{kind}={}", config.text)]);
+    if let Some(window) = attach {
+        command.arg(format!("--attach={window}"));
+    }
+    if config.buttons == MessageBoxButtons::OkCancel {
+        command.args(["--yes-label", "OK", "--no-label", "Cancel"]);
+    }
+
+    let output = run(&mut command)?;
+    match (config.buttons, output.status.code()) {
+        (MessageBoxButtons::Ok | MessageBoxButtons::OkCancel, Some(0)) => Ok(MessageBoxButton::Ok),
+        (MessageBoxButtons::YesNo | MessageBoxButtons::YesNoCancel, Some(0)) => {
+            Ok(MessageBoxButton::Yes)
+        },
+        (MessageBoxButtons::YesNo | MessageBoxButtons::YesNoCancel, Some(1)) => {
+            Ok(MessageBoxButton::No)
+        },
+        (buttons, Some(1 | 2)) => Ok(buttons.dismissed()),
+        _ => Err(failed("kdialog", &output)),
+    }
+}
+
+fn run(command: &mut Command) -> io::Result<Output> {
+    command.stdin(Stdio::null()).stderr(Stdio::null()).output()
+}
+
+fn failed(program: &str, output: &Output) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, format!("{program} failed: {}", output.status))
+}
@@ -1 +1,18 @@
+#[cfg(feature = "accesskit")]
+pub mod accessibility;
+pub mod brightness;
+pub mod clipboard;
+pub mod cursor_theme;
+pub mod dbus;
+#[cfg(feature = "gamepad")]
+pub mod gamepad;
+pub mod logind;
+#[cfg(feature = "dialogs")]
+pub mod message_box;
+pub mod modifiers;
+#[cfg(feature = "dialogs")]
+pub mod portal;
+pub mod power;
+pub mod preferences;
+pub mod termination;
 pub mod xkb;
@@ -0,0 +1,172 @@
+//! Ordering of the `ModifiersChanged` events relative to the modifier key events.
+
+use crate::event::WindowEvent;
+use crate::event_loop::ModifiersOrder;
+use crate::keyboard::{KeyCode, PhysicalKey};
+use crate::window::WindowId;
+
+/// Holds back one event until it's known whether it must be swapped with the next one.
+///
+/// The key events and modifiers updates are reported separately by the compositor and the X
+/// server, in no guaranteed order.
+///
+/// The events can carry `data` of the backend along, which stays with them when reordered.
+#[derive(Debug)]
+pub struct ModifiersOrderer<T = ()> {
+    order: ModifiersOrder,
+    held: Option<(WindowId, WindowEvent, T)>,
+}
+
+#[cfg(any(x11_platform, test))]
+impl ModifiersOrderer {
+    /// Queue the `event`, returning the events which must be delivered right away, in order.
+    pub fn push(
+        &mut self,
+        window_id: WindowId,
+        event: WindowEvent,
+    ) -> [Option<(WindowId, WindowEvent)>; 2] {
+        self.push_with(window_id, event, ()).map(|event| event.map(|(id, event, ())| (id, event)))
+    }
+
+    /// Take the event held back, at the end of a batch of events.
+    pub fn flush(&mut self) -> Option<(WindowId, WindowEvent)> {
+        self.flush_with().map(|(window_id, event, ())| (window_id, event))
+    }
+}
+
+impl<T> ModifiersOrderer<T> {
+    pub fn new(order: ModifiersOrder) -> Self {
+        Self { order, held: None }
+    }
+
+    /// Queue the `event` with its `data`, see [`Self::push`].
+    pub fn push_with(
+        &mut self,
+        window_id: WindowId,
+        event: WindowEvent,
+        data: T,
+    ) -> [Option<(WindowId, WindowEvent, T)>; 2] {
+        if matches!(&self.held, Some((held_window_id, ..)) if *held_window_id == window_id)
+            && self.leads(&event)
+        {
+            return [Some((window_id, event, data)), self.held.take()];
+        }
+
+        let held = self.held.take();
+        if self.follows(&event) {
+            self.held = Some((window_id, event, data));
+            [held, None]
+        } else {
+            [held, Some((window_id, event, data))]
+        }
+    }
+
+    /// Take the event held back with its data, see [`Self::flush`].
+    pub fn flush_with(&mut self) -> Option<(WindowId, WindowEvent, T)> {
+        self.held.take()
+    }
+
+    /// Whether the event must be delivered before the key or modifiers event preceding it.
+    fn leads(&self, event: &WindowEvent) -> bool {
+        match self.order {
+            ModifiersOrder::BeforeKeyboardInput => {
+                matches!(event, WindowEvent::ModifiersChanged(_))
+            },
+            ModifiersOrder::AfterKeyboardInput => is_modifier_key_event(event),
+        }
+    }
+
+    /// Whether the event must be delivered after the key or modifiers event following it.
+    fn follows(&self, event: &WindowEvent) -> bool {
+        match self.order {
+            ModifiersOrder::BeforeKeyboardInput => is_modifier_key_event(event),
+            ModifiersOrder::AfterKeyboardInput => matches!(event, WindowEvent::ModifiersChanged(_)),
+        }
+    }
+}
+
+/// Whether the event is a press or release of a key tracked by the modifiers.
+fn is_modifier_key_event(event: &WindowEvent) -> bool {
+    match event {
+        WindowEvent::KeyboardInput { event, .. } => matches!(
+            event.physical_key,
+            PhysicalKey::Code(
+                KeyCode::ShiftLeft
+                    | KeyCode::ShiftRight
+                    | KeyCode::ControlLeft
+                    | KeyCode::ControlRight
+                    | KeyCode::AltLeft
+                    | KeyCode::AltRight
+                    | KeyCode::SuperLeft
+                    | KeyCode::SuperRight
+            )
+        ),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{ElementState, KeyEvent, Modifiers};
+    use crate::keyboard::{Key, KeyLocation, ModifiersState, NamedKey};
+    use crate::platform_impl::KeyEventExtra;
+
+    fn shift_pressed() -> WindowEvent {
+        WindowEvent::KeyboardInput {
+            device_id: None,
+            event: KeyEvent {
+                physical_key: PhysicalKey::Code(KeyCode::ShiftLeft),
+                logical_key: Key::Named(NamedKey::Shift),
+                text: None,
+                location: KeyLocation::Left,
+                state: ElementState::Pressed,
+                repeat: false,
+                platform_specific: KeyEventExtra {
+                    text_with_all_modifiers: None,
+                    key_without_modifiers: Key::Named(NamedKey::Shift),
+                },
+            },
+            is_synthetic: false,
+        }
+    }
+
+    fn shift_modifiers() -> WindowEvent {
+        WindowEvent::ModifiersChanged(Modifiers::from(ModifiersState::SHIFT))
+    }
+
+    fn deliver(
+        orderer: &mut ModifiersOrderer,
+        events: Vec<WindowEvent>,
+    ) -> Vec<(WindowId, WindowEvent)> {
+        let window = WindowId::from_raw(1);
+        let mut delivered: Vec<_> =
+            events.into_iter().flat_map(|event| orderer.push(window, event)).flatten().collect();
+        delivered.extend(orderer.flush());
+        delivered
+    }
+
+    #[test]
+    fn modifiers_after_key() {
+        let window = WindowId::from_raw(1);
+        let mut orderer = ModifiersOrderer::new(ModifiersOrder::AfterKeyboardInput);
+        let expected = vec![(window, shift_pressed()), (window, shift_modifiers())];
+
+        assert_eq!(deliver(&mut orderer, vec![shift_modifiers(), shift_pressed()]), expected);
+        assert_eq!(deliver(&mut orderer, vec![shift_pressed(), shift_modifiers()]), expected);
+    }
+
+    #[test]
+    fn modifiers_before_key() {
+        let window = WindowId::from_raw(1);
+        let mut orderer = ModifiersOrderer::new(ModifiersOrder::BeforeKeyboardInput);
+        let expected = vec![(window, shift_modifiers()), (window, shift_pressed())];
+
+        assert_eq!(deliver(&mut orderer, vec![shift_modifiers(), shift_pressed()]), expected);
+        assert_eq!(deliver(&mut orderer, vec![shift_pressed(), shift_modifiers()]), expected);
+        assert_eq!(deliver(&mut orderer, vec![WindowEvent::Focused(true)]), vec![(
+            window,
+            WindowEvent::Focused(true)
+        )]);
+    }
+}
@@ -0,0 +1,166 @@
+//! File dialogs shown through the `FileChooser` portal of `xdg-desktop-portal`.
+
+use std::ffi::OsString;
+use std::os::unix::ffi::{OsStrExt, OsStringExt};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::{io, thread};
+
+use percent_encoding::percent_decode_str;
+
+use super::dbus::{Connection, Value};
+use crate::dialogs::{FileDialogKind, FileDialogOptions, FileDialogResult, FileFilter};
+use crate::error::RequestError;
+
+const PORTAL_NAME: &str = "org.freedesktop.portal.Desktop";
+const PORTAL_PATH: &str = "/org/freedesktop/portal/desktop";
+const FILE_CHOOSER_INTERFACE: &str = "org.freedesktop.portal.FileChooser";
+const REQUEST_INTERFACE: &str = "org.freedesktop.portal.Request";
+
+/// Show the dialog from another thread, calling `done` with the choice of the user.
+///
+/// `parent_window` is the portal's identifier of the parent window, or an empty string.
+pub fn show_file_dialog(
+    kind: FileDialogKind,
+    options: FileDialogOptions,
+    parent_window: String,
+    done: impl FnOnce(FileDialogResult) + Send + 'static,
+) -> Result<(), RequestError> {
+    thread::Builder::new()
+        .name("winit file dialog".into())
+        .spawn(move || {
+            let result = match run_file_dialog(kind, &options, parent_window) {
+                Ok(Some(paths)) => FileDialogResult::Picked(paths),
+                Ok(None) => FileDialogResult::Cancelled,
+                Err(error) => FileDialogResult::Failed(error.to_string()),
+            };
+            done(result);
+        })
+        .map_err(|error| os_error!(error))?;
+    Ok(())
+}
+
+fn run_file_dialog(
+    kind: FileDialogKind,
+    options: &FileDialogOptions,
+    parent_window: String,
+) -> io::Result<Option<Vec<PathBuf>>> {
+    static NEXT_TOKEN: AtomicUsize = AtomicUsize::new(0);
+
+    let mut connection = Connection::session()?;
+
+    // Subscribe to the response before calling the portal so it can't be missed, using the path
+    // of the request object derived from our token.
+    let token = format!("winit{}", NEXT_TOKEN.fetch_add(1, Ordering::Relaxed));
+    let sender = connection.unique_name().trim_start_matches(':').replace('.', "_");
+    let handle = format!("{PORTAL_PATH}/request/{sender}/{token}");
+    connection.add_match(&response_rule(&handle))?;
+
+    let mut dict = vec![
+        ("handle_token", Value::Str(token)),
+        ("modal", Value::Bool(!parent_window.is_empty())),
+    ];
+    if kind != FileDialogKind::PickFolder && !options.filters.is_empty() {
+        let filters: Vec<_> = options.filters.iter().map(filter).collect();
+        dict.push(("current_filter", filters[0].clone()));
+        dict.push(("filters", Value::Array("(sa(us))".into(), filters)));
+    }
+    if let Some(directory) = &options.directory {
+        dict.push(("current_folder", path_bytes(directory)));
+    }
+
+    let (method, default_title) = match kind {
+        FileDialogKind::PickFile => {
+            dict.push(("multiple", Value::Bool(options.multiple)));
+            ("OpenFile", "Open File")
+        },
+        FileDialogKind::PickFolder => {
+            dict.push(("multiple", Value::Bool(options.multiple)));
+            dict.push(("directory", Value::Bool(true)));
+            ("OpenFile", "Open Folder")
+        },
+        FileDialogKind::SaveFile => {
+            if let Some(file_name) = &options.file_name {
+                dict.push(("current_name", Value::Str(file_name.clone())));
+            }
+            ("SaveFile", "Save File")
+        },
+    };
+    let title = options.title.clone().unwrap_or_else(|| default_title.into());
+
+    let reply = connection.call(PORTAL_NAME, PORTAL_PATH, FILE_CHOOSER_INTERFACE, method, vec![
+        Value::Str(parent_window),
+        Value::Str(title),
+        Value::dict(dict),
+    ])?;
+
+    // Old versions of the portal don't derive the path from the token.
+    let request = reply.body.first().and_then(Value::as_str).unwrap_or(&handle).to_owned();
+    if request != handle {
+        connection.add_match(&response_rule(&request))?;
+    }
+
+    loop {
+        let signal = connection.next_signal()?;
+        if !signal.is_signal(REQUEST_INTERFACE, "Response")
+            || signal.path.as_deref() != Some(request.as_str())
+        {
+            continue;
+        }
+
+        return match signal.body.first().and_then(Value::as_u32) {
+            Some(0) => {
+                let uris = signal.body.get(1).and_then(|results| results.lookup("uris"));
+                let paths = uris
+                    .and_then(Value::as_array)
+                    .unwrap_or_default()
+                    .iter()
+                    .filter_map(|uri| uri_to_path(uri.as_str()?))
+                    .collect();
+                Ok(Some(paths))
+            },
+            Some(1) => Ok(None),
+            _ => Err(io::Error::new(io::ErrorKind::Other, "the file dialog was aborted")),
+        };
+    }
+}
+
+fn response_rule(path: &str) -> String {
+    format!("type='signal',interface='{REQUEST_INTERFACE}',member='Response',path='{path}'")
+}
+
+/// A filter of type `(sa(us))`, matching glob patterns.
+fn filter(filter: &FileFilter) -> Value {
+    let patterns = filter
+        .extensions
+        .iter()
+        .map(|extension| Value::Struct(vec![Value::U32(0), Value::Str(format!("*.{extension}"))]))
+        .collect();
+    Value::Struct(vec![Value::Str(filter.name.clone()), Value::Array("(us)".into(), patterns)])
+}
+
+/// A path as a nul-terminated byte array.
+fn path_bytes(path: &Path) -> Value {
+    let bytes = path.as_os_str().as_bytes().iter().chain([&0]).map(|byte| Value::Byte(*byte));
+    Value::Array("y".into(), bytes.collect())
+}
+
+fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    let path = uri.strip_prefix("file://")?;
+    let bytes: Vec<u8> = percent_decode_str(path).collect();
+    Some(PathBuf::from(OsString::from_vec(bytes)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_uri() {
+        assert_eq!(
+            uri_to_path("file:///home/user/My%20Pictures/caf%C3%A9.png"),
+            Some(PathBuf::from("/home/user/My Pictures/café.png"))
+        );
+        assert_eq!(uri_to_path("https://example.com"), None);
+    }
+}
@@ -0,0 +1,124 @@
+//! Power state of the system, read from the `power_supply` class of sysfs.
+
+use std::fs;
+use std::path::Path;
+
+use crate::event::{PowerSource, PowerState};
+
+const POWER_SUPPLY_PATH: &str = "/sys/class/power_supply";
+
+/// The current power state, or `None` when neither a battery nor a mains supply is reported.
+pub fn power_state() -> Option<PowerState> {
+    power_state_from(Path::new(POWER_SUPPLY_PATH))
+}
+
+fn power_state_from(root: &Path) -> Option<PowerState> {
+    let mut mains_online = None;
+    let mut battery = None;
+
+    for entry in fs::read_dir(root).ok()?.flatten() {
+        let supply = entry.path();
+        let read = |attribute: &str| {
+            fs::read_to_string(supply.join(attribute)).ok().map(|value| value.trim().to_owned())
+        };
+
+        match read("type").as_deref() {
+            Some("Mains") => {
+                let online = read("online").as_deref() == Some("1");
+                mains_online = Some(mains_online.unwrap_or(false) || online);
+            },
+            // Only consider the first battery which belongs to the system, and not to a
+            // peripheral like a mouse.
+            Some("Battery") if battery.is_none() && read("scope").as_deref() != Some("Device") => {
+                let level = read("capacity").and_then(|capacity| capacity.parse::<u8>().ok());
+                let status = read("status");
+                battery = Some((level.map(|level| level.min(100)), status));
+            },
+            _ => (),
+        }
+    }
+
+    let (battery_level, status) = match battery {
+        Some((level, status)) => (level, status),
+        None => (None, None),
+    };
+    let charging = status.as_deref() == Some("Charging");
+
+    let source = match (mains_online, status.as_deref()) {
+        (Some(true), _) | (None, Some("Charging" | "Full" | "Not charging")) => {
+            PowerSource::External
+        },
+        (Some(false), _) | (None, Some(_)) => PowerSource::Battery,
+        (None, None) => return None,
+    };
+
+    Some(PowerState { source, charging, battery_level })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    fn supply(root: &Path, name: &str, attributes: &[(&str, &str)]) {
+        let path = root.join(name);
+        fs::create_dir_all(&path).unwrap();
+        for (attribute, value) in attributes {
+            fs::write(path.join(attribute), format!("{value}\n")).unwrap();
+        }
+    }
+
+    fn temp_root(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("winit-power-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        root
+    }
+
+    #[test]
+    fn laptop() {
+        let root = temp_root("laptop");
+        supply(&root, "AC", &[("type", "Mains"), ("online", "0")]);
+        supply(&root, "BAT0", &[
+            ("type", "Battery"),
+            ("capacity", "42"),
+            ("status", "Discharging"),
+        ]);
+        supply(&root, "hidpp_battery_0", &[
+            ("type", "Battery"),
+            ("scope", "Device"),
+            ("capacity", "90"),
+            ("status", "Charging"),
+        ]);
+
+        assert_eq!(
+            power_state_from(&root),
+            Some(PowerState {
+                source: PowerSource::Battery,
+                charging: false,
+                battery_level: Some(42)
+            })
+        );
+
+        supply(&root, "AC", &[("online", "1")]);
+        supply(&root, "BAT0", &[("status", "Charging")]);
+        assert_eq!(
+            power_state_from(&root),
+            Some(PowerState {
+                source: PowerSource::External,
+                charging: true,
+                battery_level: Some(42)
+            })
+        );
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn no_power_supply() {
+        let root = temp_root("desktop");
+        assert_eq!(power_state_from(&root), None);
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,167 @@
+//! The accessibility preferences and the text scaling of the desktop, read from the settings portal
+//! on the session bus.
+
+use std::net::Shutdown;
+use std::os::unix::net::UnixStream;
+use std::sync::{Arc, Mutex};
+use std::{io, thread};
+
+use super::dbus::{Connection, Value};
+use crate::event::SystemPreferences;
+
+const PORTAL_NAME: &str = "org.freedesktop.portal.Desktop";
+const PORTAL_PATH: &str = "/org/freedesktop/portal/desktop";
+const SETTINGS_INTERFACE: &str = "org.freedesktop.portal.Settings";
+
+const APPEARANCE_NAMESPACE: &str = "org.freedesktop.appearance";
+const GNOME_A11Y_NAMESPACE: &str = "org.gnome.desktop.a11y.interface";
+const GNOME_INTERFACE_NAMESPACE: &str = "org.gnome.desktop.interface";
+
+/// The settings the preferences are made of, with the desktop neutral ones first.
+const SETTINGS: [(&str, &str); 5] = [
+    (APPEARANCE_NAMESPACE, "contrast"),
+    (APPEARANCE_NAMESPACE, "reduced-motion"),
+    (GNOME_A11Y_NAMESPACE, "high-contrast"),
+    (GNOME_INTERFACE_NAMESPACE, "enable-animations"),
+    (GNOME_INTERFACE_NAMESPACE, "text-scaling-factor"),
+];
+
+/// A change reported by the [`PreferencesWatcher`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PreferencesChange {
+    System(SystemPreferences),
+    TextScaleFactor(f64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Preferences {
+    system: SystemPreferences,
+    text_scale_factor: Option<f64>,
+}
+
+/// Watches the accessibility preferences and the text scaling until dropped.
+#[derive(Debug)]
+pub struct PreferencesWatcher {
+    stream: UnixStream,
+    preferences: Arc<Mutex<Option<Preferences>>>,
+}
+
+impl PreferencesWatcher {
+    /// Call `on_change` from another thread each time the preferences change.
+    pub fn new(mut on_change: impl FnMut(PreferencesChange) + Send + 'static) -> io::Result<Self> {
+        let mut connection = Connection::session()?;
+        let stream = connection.try_clone_stream()?;
+        let preferences = Arc::new(Mutex::new(None));
+
+        let current = preferences.clone();
+        thread::Builder::new().name("winit preferences".into()).spawn(move || {
+            let result = watch(&mut connection, |new| {
+                // Don't report the initial state, only the changes.
+                let Some(previous) = current.lock().unwrap().replace(new) else { return };
+                if previous.system != new.system {
+                    on_change(PreferencesChange::System(new.system));
+                }
+                if let Some(factor) = new.text_scale_factor {
+                    if previous.text_scale_factor != Some(factor) {
+                        on_change(PreferencesChange::TextScaleFactor(factor));
+                    }
+                }
+            });
+            if let Err(error) = result {
+                tracing::debug!("stopped watching the preferences: {error}");
+            }
+        })?;
+
+        Ok(Self { stream, preferences })
+    }
+
+    /// The preferences, once they were read from the portal.
+    pub fn preferences(&self) -> Option<SystemPreferences> {
+        Some(self.preferences.lock().unwrap().as_ref()?.system)
+    }
+
+    /// The text scale factor, once it was read from the portal and if the desktop has one.
+    pub fn text_scale_factor(&self) -> Option<f64> {
+        self.preferences.lock().unwrap().as_ref()?.text_scale_factor
+    }
+}
+
+impl Drop for PreferencesWatcher {
+    fn drop(&mut self) {
+        // Unblock the thread, which then exits.
+        let _ = self.stream.shutdown(Shutdown::Both);
+    }
+}
+
+fn watch(
+    connection: &mut Connection,
+    mut on_preferences: impl FnMut(Preferences),
+) -> io::Result<()> {
+    // Only match the settings used, the other ones may have types that aren't supported.
+    for (namespace, key) in SETTINGS {
+        connection.add_match(&format!(
+            "type='signal',interface='{SETTINGS_INTERFACE}',member='SettingChanged',path='\
+             {PORTAL_PATH}',arg0='{namespace}',arg1='{key}'"
+        ))?;
+    }
+
+    let mut settings: [Option<Value>; SETTINGS.len()] = Default::default();
+    for ((namespace, key), setting) in SETTINGS.into_iter().zip(&mut settings) {
+        // The portal replies with an error to the settings it doesn't know.
+        match connection.call(PORTAL_NAME, PORTAL_PATH, SETTINGS_INTERFACE, "Read", vec![
+            Value::Str(namespace.into()),
+            Value::Str(key.into()),
+        ]) {
+            Ok(reply) => *setting = reply.body.first().map(setting_value),
+            Err(error) if error.kind() == io::ErrorKind::Other => (),
+            Err(error) => return Err(error),
+        }
+    }
+    on_preferences(preferences(&settings));
+
+    loop {
+        let signal = connection.next_signal()?;
+        if !signal.is_signal(SETTINGS_INTERFACE, "SettingChanged") {
+            continue;
+        }
+        let (Some(namespace), Some(key)) = (
+            signal.body.first().and_then(Value::as_str),
+            signal.body.get(1).and_then(Value::as_str),
+        ) else {
+            continue;
+        };
+        let Some(index) = SETTINGS.iter().position(|&setting| setting == (namespace, key)) else {
+            continue;
+        };
+        settings[index] = signal.body.get(2).map(setting_value);
+        on_preferences(preferences(&settings));
+    }
+}
+
+/// The value of a setting, unwrapped from its possibly nested variants.
+fn setting_value(mut value: &Value) -> Value {
+    while let Value::Variant(inner) = value {
+        value = inner;
+    }
+    value.clone()
+}
+
+/// The preferences from the values of the [`SETTINGS`].
+fn preferences(settings: &[Option<Value>; SETTINGS.len()]) -> Preferences {
+    let [contrast, reduced_motion, high_contrast, enable_animations, text_scaling_factor] =
+        settings;
+    let system = SystemPreferences {
+        // The portal uses `1` for more contrast and for reduced motion.
+        high_contrast: contrast.as_ref().and_then(Value::as_u32) == Some(1)
+            || high_contrast.as_ref().and_then(Value::as_bool) == Some(true),
+        reduced_motion: reduced_motion.as_ref().and_then(Value::as_u32) == Some(1)
+            || enable_animations.as_ref().and_then(Value::as_bool) == Some(false),
+        // There is no such setting.
+        reduced_transparency: false,
+    };
+    let text_scale_factor = text_scaling_factor
+        .as_ref()
+        .and_then(Value::as_f64)
+        .filter(|factor| factor.is_finite() && *factor > 0.0);
+    Preferences { system, text_scale_factor }
+}
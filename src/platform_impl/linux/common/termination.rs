@@ -0,0 +1,104 @@
+//! Delivery of the `SIGTERM` signal to the event loop.
+
+use std::io;
+use std::os::fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, OwnedFd, RawFd};
+use std::sync::atomic::{AtomicI32, Ordering};
+
+/// The write end of the pipe the signal handler writes to.
+static SIGNAL_PIPE: AtomicI32 = AtomicI32::new(-1);
+
+extern "C" fn handle_sigterm(_: libc::c_int) {
+    let fd = SIGNAL_PIPE.load(Ordering::Relaxed);
+    if fd >= 0 {
+        // SAFETY: `write` is async-signal-safe, and the pipe is only closed once the handler was
+        // uninstalled.
+        let _ = unsafe { libc::write(fd, [0u8].as_ptr().cast(), 1) };
+    }
+}
+
+/// The `SIGTERM` handler waking up the event loop, installed while this is alive.
+///
+/// Dropping it restores the previous disposition of the signal and closes the pipe.
+#[derive(Debug)]
+pub struct SigtermHandler {
+    read: OwnedFd,
+    write: OwnedFd,
+    previous: libc::sigaction,
+}
+
+impl SigtermHandler {
+    /// Install a `SIGTERM` handler waking up the event loop.
+    ///
+    /// Returns `None` when the signal is already handled by someone else, in which case it's left
+    /// untouched. The handler becomes readable when the signal was received.
+    pub fn install() -> Option<Self> {
+        // SAFETY: Only querying the current disposition.
+        let mut previous: libc::sigaction = unsafe { std::mem::zeroed() };
+        if unsafe { libc::sigaction(libc::SIGTERM, std::ptr::null(), &mut previous) } != 0 {
+            return None;
+        }
+
+        // Either the user installed their own handler, or another event loop did.
+        if previous.sa_sigaction != libc::SIG_DFL {
+            return None;
+        }
+
+        let mut fds: [RawFd; 2] = [-1; 2];
+        if unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_CLOEXEC | libc::O_NONBLOCK) } != 0 {
+            tracing::warn!("failed to create the SIGTERM pipe: {}", io::Error::last_os_error());
+            return None;
+        }
+
+        // SAFETY: Both ends were just created and are owned by nobody else.
+        let (read, write) = unsafe { (OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1])) };
+        SIGNAL_PIPE.store(write.as_raw_fd(), Ordering::Relaxed);
+
+        // SAFETY: The handler only performs async-signal-safe operations.
+        let mut action: libc::sigaction = unsafe { std::mem::zeroed() };
+        action.sa_sigaction = handle_sigterm as extern "C" fn(libc::c_int) as libc::sighandler_t;
+        action.sa_flags = libc::SA_RESTART;
+        if unsafe { libc::sigaction(libc::SIGTERM, &action, std::ptr::null_mut()) } != 0 {
+            tracing::warn!("failed to install the SIGTERM handler: {}", io::Error::last_os_error());
+            SIGNAL_PIPE.store(-1, Ordering::Relaxed);
+            return None;
+        }
+
+        Some(Self { read, write, previous })
+    }
+
+    /// Consume the pending signal notifications from the pipe.
+    pub fn drain(&self) {
+        let mut buf = [0u8; 16];
+        // SAFETY: The buffer is valid for writes of its length.
+        while unsafe { libc::read(self.read.as_raw_fd(), buf.as_mut_ptr().cast(), buf.len()) } > 0 {
+        }
+    }
+}
+
+impl AsFd for SigtermHandler {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.read.as_fd()
+    }
+}
+
+impl Drop for SigtermHandler {
+    fn drop(&mut self) {
+        // Only restore the previous disposition if nobody replaced our handler in the meantime.
+        let mut current: libc::sigaction = unsafe { std::mem::zeroed() };
+        let ours = handle_sigterm as extern "C" fn(libc::c_int) as libc::sighandler_t;
+        if unsafe { libc::sigaction(libc::SIGTERM, std::ptr::null(), &mut current) } == 0
+            && current.sa_sigaction == ours
+        {
+            // SAFETY: Restoring the disposition queried when installing the handler.
+            unsafe { libc::sigaction(libc::SIGTERM, &self.previous, std::ptr::null_mut()) };
+        }
+
+        // The handler can't be called anymore, the pipe is closed when the fields are dropped.
+        let _ = SIGNAL_PIPE.compare_exchange(
+            self.write.as_raw_fd(),
+            -1,
+            Ordering::Relaxed,
+            Ordering::Relaxed,
+        );
+    }
+}
@@ -1,6 +1,6 @@
 //! XKB keymap.
 
-use std::ffi::c_char;
+use std::ffi::{c_char, CStr};
 use std::ops::Deref;
 use std::ptr::{self, NonNull};
 
@@ -911,6 +911,7 @@ pub fn keysym_location(keysym: u32) -> KeyLocation {
 pub struct XkbKeymap {
     keymap: NonNull<xkb_keymap>,
     _mods_indices: ModsIndices,
+    layout_names: Vec<String>,
     pub _core_keyboard_id: i32,
 }
 
@@ -962,7 +963,9 @@ impl XkbKeymap {
             mod5: mod_index_for_name(keymap, b"Mod5\0"),
         };
 
-        Self { keymap, _mods_indices: mods_indices, _core_keyboard_id }
+        let layout_names = read_layout_names(keymap);
+
+        Self { keymap, _mods_indices: mods_indices, layout_names, _core_keyboard_id }
     }
 
     #[cfg(x11_platform)]
@@ -998,6 +1001,65 @@ impl XkbKeymap {
     pub fn key_repeats(&mut self, keycode: xkb_keycode_t) -> bool {
         unsafe { (XKBH.xkb_keymap_key_repeats)(self.keymap.as_ptr(), keycode) == 1 }
     }
+
+    /// The name of the given layout, like `English (US)`.
+    pub fn layout_name(&self, layout: xkb_layout_index_t) -> Option<&str> {
+        self.layout_names.get(layout as usize).map(String::as_str).filter(|name| !name.is_empty())
+    }
+}
+
+/// Read the names of the layouts of the keymap, by index.
+///
+/// `xkbcommon-dl` doesn't expose `xkb_keymap_layout_get_name()`, so the names are read from the
+/// keymap serialized as text instead.
+fn read_layout_names(keymap: NonNull<xkb_keymap>) -> Vec<String> {
+    let text = unsafe {
+        (XKBH.xkb_keymap_get_as_string)(
+            keymap.as_ptr(),
+            xkb::xkb_keymap_format::XKB_KEYMAP_FORMAT_TEXT_V1,
+        )
+    };
+    if text.is_null() {
+        return Vec::new();
+    }
+
+    let names = parse_layout_names(&unsafe { CStr::from_ptr(text) }.to_string_lossy());
+    unsafe { libc::free(text as *mut _) };
+    names
+}
+
+/// Parse the `name[GroupN]="..."` statements of the `xkb_symbols` section.
+fn parse_layout_names(keymap: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    for line in keymap.lines() {
+        let Some((group, value)) =
+            line.trim_start().strip_prefix("name[").and_then(|line| line.split_once(']'))
+        else {
+            continue;
+        };
+        let Some(index) = group
+            .get(..5)
+            .filter(|prefix| prefix.eq_ignore_ascii_case("group"))
+            .and_then(|_| group[5..].parse::<usize>().ok())
+            .filter(|&index| index > 0)
+        else {
+            continue;
+        };
+        let Some(name) = value
+            .trim_start()
+            .strip_prefix('=')
+            .and_then(|value| value.trim_start().strip_prefix('"'))
+            .and_then(|value| value.split_once('"'))
+        else {
+            continue;
+        };
+
+        if names.len() < index {
+            names.resize(index, String::new());
+        }
+        names[index - 1] = name.0.to_owned();
+    }
+    names
 }
 
 impl Drop for XkbKeymap {
@@ -1041,3 +1103,29 @@ fn mod_index_for_name(keymap: NonNull<xkb_keymap>, name: &[u8]) -> Option<xkb_mo
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layout_names() {
+        let keymap = r#"xkb_keymap {
+xkb_types "complete" {
+	type "ONE_LEVEL" {
+		modifiers= none;
+		level_name[1]="Any";
+	};
+};
+
+xkb_symbols "pc+us+ru:2+inet(evdev)" {
+	name[Group1]="English (US)";
+	name[group2]="Russian";
+
+	key <ESC> { [ Escape ] };
+};
+};"#;
+        assert_eq!(parse_layout_names(keymap), ["English (US)", "Russian"]);
+        assert_eq!(parse_layout_names("xkb_keymap {};"), Vec::<String>::new());
+    }
+}
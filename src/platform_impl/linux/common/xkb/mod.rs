@@ -14,8 +14,8 @@ use xkbcommon_dl::{
 #[cfg(x11_platform)]
 use {x11_dl::xlib_xcb::xcb_connection_t, xkbcommon_dl::x11::xkbcommon_x11_handle};
 
-use crate::event::{ElementState, KeyEvent};
-use crate::keyboard::{Key, KeyLocation};
+use crate::event::{Compose, ElementState, KeyEvent};
+use crate::keyboard::{Key, KeyCode, KeyLocation, KeyboardLayout, PhysicalKey};
 use crate::platform_impl::KeyEventExtra;
 use crate::utils::Lazy;
 
@@ -28,6 +28,8 @@ use compose::{ComposeStatus, XkbComposeState, XkbComposeTable};
 pub use keymap::raw_keycode_to_physicalkey;
 use keymap::XkbKeymap;
 pub use keymap::{physicalkey_to_scancode, scancode_to_physicalkey};
+#[cfg(wayland_platform)]
+pub use state::ModifiersState;
 pub use state::XkbState;
 
 // TODO: Wire this up without using a static `AtomicBool`.
@@ -59,6 +61,8 @@ pub struct Context {
     compose_state1: Option<XkbComposeState>,
     compose_state2: Option<XkbComposeState>,
     _compose_table: Option<XkbComposeTable>,
+    /// The characters of the compose sequence in progress.
+    compose_pending: Option<String>,
     context: XkbContext,
     scratch_buffer: Vec<u8>,
 }
@@ -89,6 +93,7 @@ impl Context {
             #[cfg(x11_platform)]
             core_keyboard_id: 0,
             _compose_table: compose_table,
+            compose_pending: None,
             context,
             scratch_buffer: Vec::with_capacity(8),
         })
@@ -149,14 +154,53 @@ impl Context {
         self.keymap = keymap;
     }
 
+    /// The keyboard layout currently active.
+    pub fn keyboard_layout(&mut self) -> Option<KeyboardLayout> {
+        let layout = self.state.as_mut()?.active_layout();
+        let name = self.keymap.as_ref()?.layout_name(layout)?.to_owned();
+        Some(KeyboardLayout { id: name.clone(), name })
+    }
+
+    /// The keys of the active layout pressed without modifiers.
+    pub fn layout_keys(&mut self) -> LayoutKeys {
+        let mut keys = LayoutKeys::default();
+        let Some(mut context) = self.key_context() else {
+            return keys;
+        };
+        let layout = context.state.active_layout();
+        for scancode in 0..248 {
+            let PhysicalKey::Code(key_code) = keymap::scancode_to_physicalkey(scancode) else {
+                continue;
+            };
+            // The level 0 ignores the modifiers.
+            let keysym = context.keymap.first_keysym_by_level(layout, scancode + 8);
+            let key = match keymap::keysym_to_key(keysym) {
+                Key::Unidentified(_) => context.keysym_to_utf8_raw(keysym).map(Key::Character),
+                key => Some(key),
+            };
+            if let Some(key) = key {
+                keys.0.push((key_code, key));
+            }
+        }
+        keys
+    }
+
     /// Key builder context with the user provided xkb state.
     pub fn key_context(&mut self) -> Option<KeyContext<'_>> {
         let state = self.state.as_mut()?;
         let keymap = self.keymap.as_mut()?;
         let compose_state1 = self.compose_state1.as_mut();
         let compose_state2 = self.compose_state2.as_mut();
+        let compose_pending = &mut self.compose_pending;
         let scratch_buffer = &mut self.scratch_buffer;
-        Some(KeyContext { state, keymap, compose_state1, compose_state2, scratch_buffer })
+        Some(KeyContext {
+            state,
+            keymap,
+            compose_state1,
+            compose_state2,
+            compose_pending,
+            scratch_buffer,
+        })
     }
 
     /// Key builder context with the user provided xkb state.
@@ -170,8 +214,32 @@ impl Context {
         let keymap = self.keymap.as_mut()?;
         let compose_state1 = self.compose_state1.as_mut();
         let compose_state2 = self.compose_state2.as_mut();
+        let compose_pending = &mut self.compose_pending;
         let scratch_buffer = &mut self.scratch_buffer;
-        Some(KeyContext { state, keymap, compose_state1, compose_state2, scratch_buffer })
+        Some(KeyContext {
+            state,
+            keymap,
+            compose_state1,
+            compose_state2,
+            compose_pending,
+            scratch_buffer,
+        })
+    }
+}
+
+/// The keys of a keyboard layout pressed without modifiers, in the order of their scancodes.
+#[derive(Debug, Default)]
+pub struct LayoutKeys(Vec<(KeyCode, Key)>);
+
+impl LayoutKeys {
+    /// The key produced by the physical key.
+    pub fn key(&self, key_code: KeyCode) -> Option<&Key> {
+        self.0.iter().find(|(code, _)| *code == key_code).map(|(_, key)| key)
+    }
+
+    /// The first physical key producing the key, so the main block wins over the numpad.
+    pub fn key_code(&self, key: &Key) -> Option<KeyCode> {
+        self.0.iter().find(|(_, unmodified)| key.is_produced_by(unmodified)).map(|(code, _)| *code)
     }
 }
 
@@ -180,16 +248,18 @@ pub struct KeyContext<'a> {
     pub keymap: &'a mut XkbKeymap,
     compose_state1: Option<&'a mut XkbComposeState>,
     compose_state2: Option<&'a mut XkbComposeState>,
+    compose_pending: &'a mut Option<String>,
     scratch_buffer: &'a mut Vec<u8>,
 }
 
 impl<'a> KeyContext<'a> {
+    /// Build the key event, with the change of the compose sequence caused by the key.
     pub fn process_key_event(
         &mut self,
         keycode: u32,
         state: ElementState,
         repeat: bool,
-    ) -> KeyEvent {
+    ) -> (KeyEvent, Option<Compose>) {
         let mut event =
             KeyEventResults::new(self, keycode, !repeat && state == ElementState::Pressed);
         let physical_key = keymap::raw_keycode_to_physicalkey(keycode);
@@ -197,10 +267,20 @@ impl<'a> KeyContext<'a> {
         let text = event.text();
         let (key_without_modifiers, _) = event.key_without_modifiers();
         let text_with_all_modifiers = event.text_with_all_modifiers();
+        let compose = event.compose_change(&logical_key, text.as_deref());
 
         let platform_specific = KeyEventExtra { text_with_all_modifiers, key_without_modifiers };
 
-        KeyEvent { physical_key, logical_key, text, location, state, repeat, platform_specific }
+        let event = KeyEvent {
+            physical_key,
+            logical_key,
+            text,
+            location,
+            state,
+            repeat,
+            platform_specific,
+        };
+        (event, compose)
     }
 
     fn keysym_to_utf8_raw(&mut self, keysym: u32) -> Option<SmolStr> {
@@ -245,6 +325,7 @@ impl<'a, 'b> KeyEventResults<'a, 'b> {
             if RESET_DEAD_KEYS.swap(false, Ordering::SeqCst) {
                 state.reset();
                 context.compose_state2.as_mut().unwrap().reset();
+                *context.compose_pending = None;
             }
             state.feed(keysym)
         } else {
@@ -327,6 +408,39 @@ impl<'a, 'b> KeyEventResults<'a, 'b> {
         }
     }
 
+    /// Update the pending characters of the compose sequence, and report how it changed.
+    fn compose_change(&mut self, key: &Key, text: Option<&str>) -> Option<Compose> {
+        let ComposeStatus::Accepted(status) = self.compose else { return None };
+        match status {
+            xkb_compose_status::XKB_COMPOSE_COMPOSING => {
+                // Dead keys show their non-combining variant, and the Compose key nothing.
+                let chars = match key {
+                    Key::Dead(Some(char)) => Some(char.to_string()),
+                    _ => self.context.keysym_to_utf8_raw(self.keysym).map(String::from),
+                };
+                let pending = &mut *self.context.compose_pending;
+                match pending {
+                    Some(pending) => {
+                        pending.extend(chars);
+                        Some(Compose::Updated(pending.clone()))
+                    },
+                    None => {
+                        let started = chars.unwrap_or_default();
+                        *pending = Some(started.clone());
+                        Some(Compose::Started(started))
+                    },
+                }
+            },
+            xkb_compose_status::XKB_COMPOSE_COMPOSED => {
+                self.context.compose_pending.take().map(|_| Compose::Ended(text.map(String::from)))
+            },
+            xkb_compose_status::XKB_COMPOSE_CANCELLED => {
+                self.context.compose_pending.take().map(|_| Compose::Ended(None))
+            },
+            xkb_compose_status::XKB_COMPOSE_NOTHING => None,
+        }
+    }
+
     fn composed_text(&mut self) -> Result<Option<SmolStr>, ()> {
         match self.compose {
             ComposeStatus::Accepted(status) => match status {
@@ -52,6 +52,16 @@ impl XkbState {
         unsafe { (XKBH.xkb_state_key_get_layout)(self.state.as_ptr(), key) }
     }
 
+    /// The layout currently active.
+    pub fn active_layout(&mut self) -> xkb_layout_index_t {
+        unsafe {
+            (XKBH.xkb_state_serialize_layout)(
+                self.state.as_ptr(),
+                xkb_state_component::XKB_STATE_LAYOUT_EFFECTIVE,
+            )
+        }
+    }
+
     #[cfg(x11_platform)]
     pub fn depressed_modifiers(&mut self) -> xkb::xkb_mod_mask_t {
         unsafe {
@@ -12,6 +12,7 @@ use std::{ffi::CStr, mem::MaybeUninit, os::raw::*, sync::Arc, sync::Mutex};
 
 use smol_str::SmolStr;
 
+use self::common::brightness::BrightnessControl;
 pub(crate) use self::common::xkb::{physicalkey_to_scancode, scancode_to_physicalkey};
 #[cfg(x11_platform)]
 use self::x11::{XConnection, XError, XNotSupported};
@@ -20,10 +21,11 @@ pub(crate) use crate::cursor::OnlyCursorImageSource as PlatformCustomCursorSourc
 #[cfg(x11_platform)]
 use crate::dpi::Size;
 use crate::dpi::{PhysicalPosition, PhysicalSize};
-use crate::error::{EventLoopError, NotSupportedError};
-use crate::event_loop::ActiveEventLoop;
-pub(crate) use crate::icon::RgbaIcon as PlatformIcon;
+use crate::error::{EventLoopError, NotSupportedError, RequestError};
+use crate::event_loop::{ActiveEventLoop, ModifiersOrder};
+pub(crate) use crate::icon::RgbaIcons as PlatformIcon;
 use crate::keyboard::Key;
+use crate::monitor::{ColorCapabilities, MonitorIdentity, Orientation};
 use crate::platform::pump_events::PumpStatus;
 #[cfg(x11_platform)]
 use crate::platform::x11::{WindowType as XWindowType, XlibErrorHook};
@@ -49,6 +51,7 @@ pub(crate) enum Backend {
 pub(crate) struct PlatformSpecificEventLoopAttributes {
     pub(crate) forced_backend: Option<Backend>,
     pub(crate) any_thread: bool,
+    pub(crate) modifiers_order: ModifiersOrder,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -107,6 +110,41 @@ impl Default for PlatformSpecificWindowAttributes {
 pub(crate) static X11_BACKEND: Lazy<Mutex<Result<Arc<XConnection>, XNotSupported>>> =
     Lazy::new(|| Mutex::new(XConnection::new(Some(x_error_callback)).map(Arc::new)));
 
+/// Release the grabs of the X server after a panic of the event loop thread.
+///
+/// The Wayland compositor releases the grabs by itself once the application is gone.
+pub(crate) fn restore_after_panic() {
+    #[cfg(x11_platform)]
+    if let Some(Ok(backend)) = X11_BACKEND.get().map(Mutex::try_lock) {
+        if let Ok(xconn) = &*backend {
+            use x11rb::connection::Connection;
+            use x11rb::protocol::xproto::ConnectionExt;
+
+            let conn = xconn.xcb_connection();
+            let _ = conn.ungrab_pointer(x11rb::CURRENT_TIME);
+            let _ = conn.ungrab_keyboard(x11rb::CURRENT_TIME);
+            let _ = conn.flush();
+        }
+    }
+}
+
+/// Show the message of a panic of the event loop thread with `zenity`, or `kdialog` when it's
+/// not installed, until the user closes it.
+///
+/// The dialog isn't attached to any window, as the state of the event loop can't be trusted
+/// anymore. The message is passed as `--option=value`, so it's never parsed as an option.
+pub(crate) fn show_panic_dialog(message: &str) {
+    let shown = std::process::Command::new("zenity")
+        .args(["--error", "--no-markup", "--title=Error", &format!("--text={message}")])
+        .status()
+        .is_ok();
+    if !shown {
+        let _ = std::process::Command::new("kdialog")
+            .args(["--title=Error", &format!("--error={message}")])
+            .status();
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum FingerId {
     #[cfg(x11_platform)]
@@ -182,6 +220,16 @@ impl MonitorHandle {
         x11_or_wayland!(match self; MonitorHandle(m) => m.scale_factor() as _)
     }
 
+    #[inline]
+    pub fn physical_size_mm(&self) -> Option<(u32, u32)> {
+        x11_or_wayland!(match self; MonitorHandle(m) => m.physical_size_mm())
+    }
+
+    #[inline]
+    pub fn orientation(&self) -> Orientation {
+        x11_or_wayland!(match self; MonitorHandle(m) => m.orientation())
+    }
+
     #[inline]
     pub fn current_video_mode(&self) -> Option<VideoModeHandle> {
         x11_or_wayland!(match self; MonitorHandle(m) => m.current_video_mode())
@@ -191,6 +239,57 @@ impl MonitorHandle {
     pub fn video_modes(&self) -> Box<dyn Iterator<Item = VideoModeHandle>> {
         x11_or_wayland!(match self; MonitorHandle(m) => Box::new(m.video_modes()))
     }
+
+    pub fn color_capabilities(&self) -> Result<ColorCapabilities, RequestError> {
+        match self {
+            #[cfg(x11_platform)]
+            MonitorHandle::X(_) => {
+                Err(NotSupportedError::new("color_capabilities is not supported").into())
+            },
+            #[cfg(wayland_platform)]
+            MonitorHandle::Wayland(m) => m.color_capabilities(),
+        }
+    }
+
+    pub fn icc_profile(&self) -> Option<Vec<u8>> {
+        match self {
+            #[cfg(x11_platform)]
+            MonitorHandle::X(m) => {
+                let xconn = X11_BACKEND.lock().unwrap_or_else(|e| e.into_inner());
+                xconn.as_ref().ok().and_then(|xconn| xconn.icc_profile(m))
+            },
+            #[cfg(wayland_platform)]
+            MonitorHandle::Wayland(_) => None,
+        }
+    }
+
+    pub fn identity(&self) -> Option<MonitorIdentity> {
+        match self {
+            #[cfg(x11_platform)]
+            MonitorHandle::X(m) => {
+                let xconn = X11_BACKEND.lock().unwrap_or_else(|e| e.into_inner());
+                xconn.as_ref().ok().and_then(|xconn| xconn.monitor_identity(m))
+            },
+            #[cfg(wayland_platform)]
+            MonitorHandle::Wayland(m) => m.identity(),
+        }
+    }
+
+    pub fn brightness(&self) -> Result<f64, RequestError> {
+        self.brightness_control()?.brightness().map_err(|error| os_error!(error).into())
+    }
+
+    pub fn set_brightness(&self, brightness: f64) -> Result<(), RequestError> {
+        self.brightness_control()?
+            .set_brightness(brightness)
+            .map_err(|error| os_error!(error).into())
+    }
+
+    fn brightness_control(&self) -> Result<BrightnessControl, NotSupportedError> {
+        self.name().and_then(|name| BrightnessControl::find(&name)).ok_or_else(|| {
+            NotSupportedError::new("no brightness control was found for the monitor")
+        })
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -355,25 +454,31 @@ impl EventLoop {
         // Create the display based on the backend.
         match backend {
             #[cfg(wayland_platform)]
-            Backend::Wayland => EventLoop::new_wayland_any_thread().map_err(Into::into),
+            Backend::Wayland => {
+                EventLoop::new_wayland_any_thread(attributes.modifiers_order).map_err(Into::into)
+            },
             #[cfg(x11_platform)]
-            Backend::X => EventLoop::new_x11_any_thread().map_err(Into::into),
+            Backend::X => {
+                EventLoop::new_x11_any_thread(attributes.modifiers_order).map_err(Into::into)
+            },
         }
     }
 
     #[cfg(wayland_platform)]
-    fn new_wayland_any_thread() -> Result<EventLoop, EventLoopError> {
-        wayland::EventLoop::new().map(|evlp| EventLoop::Wayland(Box::new(evlp)))
+    fn new_wayland_any_thread(
+        modifiers_order: ModifiersOrder,
+    ) -> Result<EventLoop, EventLoopError> {
+        wayland::EventLoop::new(modifiers_order).map(|evlp| EventLoop::Wayland(Box::new(evlp)))
     }
 
     #[cfg(x11_platform)]
-    fn new_x11_any_thread() -> Result<EventLoop, EventLoopError> {
+    fn new_x11_any_thread(modifiers_order: ModifiersOrder) -> Result<EventLoop, EventLoopError> {
         let xconn = match X11_BACKEND.lock().unwrap_or_else(|e| e.into_inner()).as_ref() {
             Ok(xconn) => xconn.clone(),
             Err(err) => return Err(os_error!(err.clone()).into()),
         };
 
-        Ok(EventLoop::X(x11::EventLoop::new(xconn)))
+        Ok(EventLoop::X(x11::EventLoop::new(xconn, modifiers_order)))
     }
 
     #[inline]
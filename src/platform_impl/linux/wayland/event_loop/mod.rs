@@ -5,22 +5,52 @@ use std::io::Result as IOResult;
 use std::mem;
 use std::os::unix::io::{AsFd, AsRawFd, BorrowedFd, RawFd};
 use std::sync::atomic::Ordering;
-use std::sync::{Arc, Mutex};
+use std::sync::{mpsc, Arc, Mutex};
 use std::time::{Duration, Instant};
 
 use sctk::reexports::calloop_wayland_source::WaylandSource;
 use sctk::reexports::client::{globals, Connection, QueueHandle};
 
 use crate::application::ApplicationHandler;
+use crate::clipboard::{Clipboard as RootClipboard, ClipboardData, ClipboardFormat, Selection};
 use crate::cursor::OnlyCursorImage;
+#[cfg(feature = "dialogs")]
+use crate::dialogs::{
+    FileDialogKind, FileDialogOptions, FileDialogResult, MessageBoxConfig, MessageBoxResult,
+    ModalBlocks,
+};
 use crate::dpi::LogicalSize;
-use crate::error::{EventLoopError, OsError, RequestError};
-use crate::event::{Event, StartCause, SurfaceSizeWriter, WindowEvent};
-use crate::event_loop::{ActiveEventLoop as RootActiveEventLoop, ControlFlow, DeviceEvents};
+use crate::drag::DragData;
+use crate::error::{EventLoopError, NotSupportedError, OsError, RequestError};
+#[cfg(any(feature = "gamepad", feature = "winit-test-harness"))]
+use crate::event::{DeviceEvent, DeviceId};
+use crate::event::{
+    DeviceInfo, Event, LanguageTag, PlatformData, PowerEvent, PowerState, StartCause,
+    SurfaceSizeWriter, SystemPreferences, WindowEvent,
+};
+use crate::event_loop::{
+    ActiveEventLoop as RootActiveEventLoop, AsyncRequestSerial, ControlFlow, DeviceClass,
+    DeviceEvents, ExitResponse, ModifiersOrder,
+};
+use crate::keyboard::{Key, KeyCode, KeyboardLayout, Led};
+use crate::monitor::MonitorHandle as RootMonitorHandle;
 use crate::platform::pump_events::PumpStatus;
+use crate::platform_impl::coalescing::MotionCoalescer;
+#[cfg(feature = "gamepad")]
+use crate::platform_impl::common::gamepad::{GamepadMessage, GamepadWatcher};
+use crate::platform_impl::common::logind::{SessionEvent, SessionWatcher};
+use crate::platform_impl::common::modifiers::ModifiersOrderer;
+use crate::platform_impl::common::preferences::{PreferencesChange, PreferencesWatcher};
+use crate::platform_impl::common::termination::SigtermHandler;
+use crate::platform_impl::common::{cursor_theme, power};
+#[cfg(feature = "dialogs")]
+use crate::platform_impl::common::{message_box, portal};
 use crate::platform_impl::platform::min_timeout;
-use crate::platform_impl::PlatformCustomCursor;
-use crate::window::{CustomCursor as RootCustomCursor, CustomCursorSource, Theme};
+use crate::platform_impl::{MonitorHandle as PlatformMonitorHandle, PlatformCustomCursor};
+use crate::window::{
+    Coalescing, Cursor, CursorThemeInfo, CustomCursor as RootCustomCursor, CustomCursorSource,
+    Rgba, Theme,
+};
 
 mod proxy;
 pub mod sink;
@@ -34,12 +64,23 @@ use super::{logical_to_physical_rounded, WindowId};
 
 type WaylandDispatcher = calloop::Dispatcher<'static, WaylandSource<WinitState>, WinitState>;
 
+/// The outcome of a dialog shown from another thread.
+#[cfg(feature = "dialogs")]
+enum DialogDone {
+    File(AsyncRequestSerial, FileDialogResult),
+    MessageBox(AsyncRequestSerial, MessageBoxResult),
+}
+
 /// The Wayland event loop.
 pub struct EventLoop {
     /// Has `run` or `run_on_demand` been called or a call to `pump_events` that starts the loop
     loop_running: bool,
 
     buffer_sink: EventSink,
+    motion_coalescer: MotionCoalescer,
+
+    /// Orders the modifiers updates relative to the modifier key events.
+    modifiers_orderer: ModifiersOrderer<Option<PlatformData>>,
     compositor_updates: Vec<WindowCompositorUpdate>,
     window_ids: Vec<WindowId>,
 
@@ -50,6 +91,21 @@ pub struct EventLoop {
     /// Connection to the wayland server.
     connection: Connection,
 
+    /// The results of the dialogs.
+    #[cfg(feature = "dialogs")]
+    dialog_receiver: mpsc::Receiver<DialogDone>,
+
+    /// The changes of the session lock state.
+    session_receiver: mpsc::Receiver<SessionEvent>,
+    _session_watcher: Option<SessionWatcher>,
+
+    /// The changes of the accessibility preferences.
+    preferences_receiver: mpsc::Receiver<PreferencesChange>,
+
+    /// The changes of the gamepads.
+    #[cfg(feature = "gamepad")]
+    gamepad_receiver: mpsc::Receiver<GamepadMessage>,
+
     /// Event loop window target.
     active_event_loop: ActiveEventLoop,
 
@@ -59,7 +115,7 @@ pub struct EventLoop {
 }
 
 impl EventLoop {
-    pub fn new() -> Result<EventLoop, EventLoopError> {
+    pub fn new(modifiers_order: ModifiersOrder) -> Result<EventLoop, EventLoopError> {
         let connection = Connection::connect_to_env().map_err(|err| os_error!(err))?;
 
         let (globals, mut event_queue) =
@@ -75,6 +131,11 @@ impl EventLoop {
         // races with the server.
         event_queue.roundtrip(&mut winit_state).map_err(|err| os_error!(err))?;
 
+        // The outputs announced during the initialization aren't connections.
+        winit_state.monitor_changes.clear();
+        winit_state.orientation_changes.clear();
+        winit_state.refresh_rate_changes.clear();
+
         // Register Wayland source.
         let wayland_source = WaylandSource::new(connection.clone(), event_queue);
         let wayland_dispatcher =
@@ -104,6 +165,24 @@ impl EventLoop {
             })
             .map_err(|err| os_error!(err))?;
 
+        // Listen for the termination requests.
+        if let Some(handler) = SigtermHandler::install() {
+            let source = calloop::generic::Generic::new(
+                handler,
+                calloop::Interest::READ,
+                calloop::Mode::Level,
+            );
+            event_loop
+                .handle()
+                .insert_source(source, |_, handler, winit_state: &mut WinitState| {
+                    handler.drain();
+                    winit_state.dispatched_events = true;
+                    winit_state.exit_requested = true;
+                    Ok(calloop::PostAction::Continue)
+                })
+                .map_err(|err| os_error!(err))?;
+        }
+
         // An event's loop awakener to wake up for window events from winit's windows.
         let (event_loop_awakener, event_loop_awakener_source) =
             calloop::ping::make_ping().map_err(|err| os_error!(err))?;
@@ -116,8 +195,52 @@ impl EventLoop {
             })
             .map_err(|err| os_error!(err))?;
 
+        #[cfg(feature = "dialogs")]
+        let (dialog_sender, dialog_receiver) = mpsc::channel();
+
+        let (session_sender, session_receiver) = mpsc::channel();
+        let awakener = event_loop_awakener.clone();
+        let session_watcher = SessionWatcher::new(move |event| {
+            if session_sender.send(event).is_ok() {
+                awakener.ping();
+            }
+        })
+        .map_err(|error| tracing::debug!("can't watch the session: {error}"))
+        .ok();
+
+        let (preferences_sender, preferences_receiver) = mpsc::channel();
+        let awakener = event_loop_awakener.clone();
+        let preferences_watcher = PreferencesWatcher::new(move |change| {
+            if preferences_sender.send(change).is_ok() {
+                awakener.ping();
+            }
+        })
+        .map_err(|error| tracing::debug!("can't watch the preferences: {error}"))
+        .ok();
+
+        #[cfg(feature = "gamepad")]
+        let (gamepad_sender, gamepad_receiver) = mpsc::channel();
+        #[cfg(feature = "gamepad")]
+        let gamepad_watcher = {
+            let awakener = event_loop_awakener.clone();
+            GamepadWatcher::new(move |message| {
+                if gamepad_sender.send(message).is_ok() {
+                    awakener.ping();
+                }
+            })
+            .map_err(|error| tracing::debug!("can't watch the gamepads: {error}"))
+            .ok()
+        };
+
         let active_event_loop = ActiveEventLoop {
             connection: connection.clone(),
+            #[cfg(feature = "dialogs")]
+            dialog_sender,
+            #[cfg(feature = "dialogs")]
+            modal_blocks: Default::default(),
+            #[cfg(feature = "gamepad")]
+            gamepad_watcher,
+            preferences_watcher,
             wayland_dispatcher: wayland_dispatcher.clone(),
             event_loop_awakener,
             event_loop_proxy: EventLoopProxy::new(ping),
@@ -125,16 +248,26 @@ impl EventLoop {
             control_flow: Cell::new(ControlFlow::default()),
             exit: Cell::new(None),
             state: RefCell::new(winit_state),
+            platform_data: RefCell::new(None),
         };
 
         let event_loop = Self {
             loop_running: false,
             compositor_updates: Vec::new(),
             buffer_sink: EventSink::default(),
+            motion_coalescer: MotionCoalescer::default(),
+            modifiers_orderer: ModifiersOrderer::new(modifiers_order),
             window_ids: Vec::new(),
             connection,
             wayland_dispatcher,
             event_loop,
+            #[cfg(feature = "dialogs")]
+            dialog_receiver,
+            session_receiver,
+            _session_watcher: session_watcher,
+            preferences_receiver,
+            #[cfg(feature = "gamepad")]
+            gamepad_receiver,
             active_event_loop,
         };
 
@@ -217,7 +350,10 @@ impl EventLoop {
                         Some(wait_deadline.saturating_duration_since(start))
                     },
                 };
-                min_timeout(control_flow_timeout, timeout)
+                let redraw_timeout = self
+                    .next_scheduled_redraw()
+                    .map(|deadline| deadline.saturating_duration_since(start));
+                min_timeout(min_timeout(control_flow_timeout, redraw_timeout), timeout)
             };
 
             // NOTE Ideally we should flush as the last thing we do before polling
@@ -260,7 +396,12 @@ impl EventLoop {
 
             // Reduce spurious wake-ups.
             let dispatched_events = self.with_state(|state| state.dispatched_events);
-            if matches!(cause, StartCause::WaitCancelled { .. }) && !dispatched_events {
+            let redraw_due =
+                self.next_scheduled_redraw().is_some_and(|deadline| deadline <= Instant::now());
+            if matches!(cause, StartCause::WaitCancelled { .. })
+                && !dispatched_events
+                && !redraw_due
+            {
                 continue;
             }
 
@@ -294,6 +435,111 @@ impl EventLoop {
             app.proxy_wake_up(&self.active_event_loop);
         }
 
+        // Deliver the data read from the clipboard.
+        let clipboard_done = self.with_state(|state| {
+            state.clipboard.as_mut().map(|clipboard| clipboard.take_done()).unwrap_or_default()
+        });
+        for (serial, data) in clipboard_done {
+            app.clipboard_data(&self.active_event_loop, serial, data);
+        }
+
+        if self.with_state(|state| mem::take(&mut state.clipboard_changed)) {
+            app.clipboard_changed(&self.active_event_loop);
+        }
+
+        // Deliver the monitors connected and disconnected.
+        let monitor_changes = self.with_state(|state| mem::take(&mut state.monitor_changes));
+        for (monitor, added) in monitor_changes {
+            let monitor = RootMonitorHandle { inner: PlatformMonitorHandle::Wayland(monitor) };
+            if added {
+                app.monitor_added(&self.active_event_loop, monitor);
+            } else {
+                app.monitor_removed(&self.active_event_loop, monitor);
+            }
+        }
+
+        let color_capabilities_changes =
+            self.with_state(|state| mem::take(&mut state.color_capabilities_changes));
+        for monitor in color_capabilities_changes {
+            let monitor = RootMonitorHandle { inner: PlatformMonitorHandle::Wayland(monitor) };
+            app.color_capabilities_changed(&self.active_event_loop, monitor);
+        }
+
+        let orientation_changes =
+            self.with_state(|state| mem::take(&mut state.orientation_changes));
+        for monitor in orientation_changes {
+            let monitor = RootMonitorHandle { inner: PlatformMonitorHandle::Wayland(monitor) };
+            app.monitor_orientation_changed(&self.active_event_loop, monitor);
+        }
+
+        let refresh_rate_changes =
+            self.with_state(|state| mem::take(&mut state.refresh_rate_changes));
+        for monitor in refresh_rate_changes {
+            let monitor = RootMonitorHandle { inner: PlatformMonitorHandle::Wayland(monitor) };
+            app.monitor_refresh_rate_changed(&self.active_event_loop, monitor);
+        }
+
+        // Deliver the results of the dialogs.
+        #[cfg(feature = "dialogs")]
+        while let Ok(done) = self.dialog_receiver.try_recv() {
+            match done {
+                DialogDone::File(serial, result) => {
+                    self.active_event_loop.modal_blocks.borrow_mut().unblock(serial);
+                    app.file_dialog_done(&self.active_event_loop, serial, result)
+                },
+                DialogDone::MessageBox(serial, result) => {
+                    self.active_event_loop.modal_blocks.borrow_mut().unblock(serial);
+                    app.message_box_done(&self.active_event_loop, serial, result)
+                },
+            }
+        }
+
+        while let Ok(event) = self.session_receiver.try_recv() {
+            let target = &self.active_event_loop;
+            match event {
+                SessionEvent::LockChanged(true) => app.session_locked(target),
+                SessionEvent::LockChanged(false) => app.session_unlocked(target),
+                SessionEvent::PrepareForSleep(true) => {
+                    app.power_event(target, PowerEvent::Suspending)
+                },
+                SessionEvent::PrepareForSleep(false) => {
+                    app.power_event(target, PowerEvent::Resumed)
+                },
+            }
+        }
+
+        while let Ok(change) = self.preferences_receiver.try_recv() {
+            match change {
+                PreferencesChange::System(preferences) => {
+                    app.system_preferences_changed(&self.active_event_loop, preferences)
+                },
+                PreferencesChange::TextScaleFactor(factor) => {
+                    app.text_scale_factor_changed(&self.active_event_loop, factor)
+                },
+            }
+        }
+
+        #[cfg(feature = "gamepad")]
+        while let Ok(message) = self.gamepad_receiver.try_recv() {
+            let event_loop = &self.active_event_loop;
+            match message {
+                GamepadMessage::Added(device) => app.input_device_added(event_loop, device),
+                GamepadMessage::Removed(device_id) => {
+                    app.input_device_removed(event_loop, device_id)
+                },
+                GamepadMessage::Event(device_id, event) => {
+                    app.device_event(event_loop, Some(device_id), DeviceEvent::Gamepad(event))
+                },
+            }
+        }
+
+        // Ask the user whether to exit on termination request.
+        if self.with_state(|state| mem::take(&mut state.exit_requested))
+            && app.exit_requested(&self.active_event_loop) == ExitResponse::Exit
+        {
+            self.active_event_loop.exit();
+        }
+
         // Drain the pending compositor updates.
         self.with_state(|state| compositor_updates.append(&mut state.window_compositor_updates));
 
@@ -360,6 +606,9 @@ impl EventLoop {
                     size
                 });
 
+                #[cfg(feature = "accesskit")]
+                self.update_accesskit_adapter(window_id);
+
                 let event = WindowEvent::SurfaceResized(physical_size);
                 app.window_event(&self.active_event_loop, window_id, event);
             }
@@ -373,10 +622,10 @@ impl EventLoop {
         self.with_state(|state| {
             buffer_sink.append(&mut state.window_events_sink.lock().unwrap());
         });
-        for event in buffer_sink.drain() {
+        for (event, data) in buffer_sink.drain() {
             match event {
                 Event::WindowEvent { window_id, event } => {
-                    app.window_event(&self.active_event_loop, window_id, event)
+                    self.dispatch_window_event(app, window_id, event, data)
                 },
                 Event::DeviceEvent { device_id, event } => {
                     app.device_event(&self.active_event_loop, device_id, event)
@@ -389,10 +638,10 @@ impl EventLoop {
         self.with_state(|state| {
             buffer_sink.append(&mut state.events_sink);
         });
-        for event in buffer_sink.drain() {
+        for (event, data) in buffer_sink.drain() {
             match event {
                 Event::WindowEvent { window_id, event } => {
-                    app.window_event(&self.active_event_loop, window_id, event)
+                    self.dispatch_window_event(app, window_id, event, data)
                 },
                 Event::DeviceEvent { device_id, event } => {
                     app.device_event(&self.active_event_loop, device_id, event)
@@ -401,6 +650,16 @@ impl EventLoop {
             }
         }
 
+        // Flush the event held back for the modifiers order.
+        if let Some((window_id, event, data)) = self.modifiers_orderer.flush_with() {
+            self.coalesce_window_event(app, window_id, event, data);
+        }
+
+        // Flush the motion which is coalesced only within a batch of events.
+        for (window_id, event) in self.motion_coalescer.drain_batch() {
+            app.window_event(&self.active_event_loop, window_id, event);
+        }
+
         // Collect the window ids
         self.with_state(|state| {
             window_ids.extend(state.window_requests.get_mut().keys());
@@ -415,6 +674,12 @@ impl EventLoop {
                     return Some(WindowEvent::Destroyed);
                 }
 
+                // Turn the due redraw into a request, which waits for the frame callback as well.
+                let requests = window_requests.get(window_id).unwrap();
+                if requests.take_scheduled_redraw(Instant::now()) {
+                    requests.redraw_requested.store(true, Ordering::Relaxed);
+                }
+
                 let mut window =
                     state.windows.get_mut().get_mut(window_id).unwrap().lock().unwrap();
 
@@ -434,10 +699,19 @@ impl EventLoop {
             });
 
             if let Some(event) = event {
+                for event in self.motion_coalescer.drain_window(*window_id) {
+                    app.window_event(&self.active_event_loop, *window_id, event);
+                }
+
                 app.window_event(&self.active_event_loop, *window_id, event);
             }
         }
 
+        // Deliver the motion of the windows which weren't redrawn.
+        for (window_id, event) in self.motion_coalescer.drain() {
+            app.window_event(&self.active_event_loop, window_id, event);
+        }
+
         // Reset the hint that we've dispatched events.
         self.with_state(|state| {
             state.dispatched_events = false;
@@ -481,11 +755,87 @@ impl EventLoop {
         std::mem::swap(&mut self.window_ids, &mut window_ids);
     }
 
+    fn dispatch_window_event<A: ApplicationHandler>(
+        &mut self,
+        app: &mut A,
+        window_id: WindowId,
+        event: WindowEvent,
+        data: Option<PlatformData>,
+    ) {
+        #[cfg(feature = "accesskit")]
+        if let WindowEvent::Focused(_) = event {
+            self.update_accesskit_adapter(window_id);
+        }
+
+        #[cfg(feature = "dialogs")]
+        let Some(event) = self.active_event_loop.modal_blocks.borrow().filter(window_id, event) else {
+            return;
+        };
+
+        for (window_id, event, data) in
+            self.modifiers_orderer.push_with(window_id, event, data).into_iter().flatten()
+        {
+            self.coalesce_window_event(app, window_id, event, data);
+        }
+    }
+
+    #[cfg(feature = "accesskit")]
+    fn update_accesskit_adapter(&mut self, window_id: WindowId) {
+        self.with_state(|state| {
+            if let Some(window) = state.windows.get_mut().get(&window_id) {
+                window.lock().unwrap().update_accesskit_adapter();
+            }
+        });
+    }
+
+    fn coalesce_window_event<A: ApplicationHandler>(
+        &mut self,
+        app: &mut A,
+        window_id: WindowId,
+        event: WindowEvent,
+        data: Option<PlatformData>,
+    ) {
+        let coalescing = self.with_state(|state| {
+            state
+                .windows
+                .get_mut()
+                .get(&window_id)
+                .map(|window| window.lock().unwrap().motion_coalescing())
+                .unwrap_or_default()
+        });
+
+        let Some(event) = self.motion_coalescer.push(window_id, event, coalescing) else {
+            return;
+        };
+
+        if coalescing != Coalescing::PerFrame {
+            for event in self.motion_coalescer.drain_window(window_id) {
+                app.window_event(&self.active_event_loop, window_id, event);
+            }
+        }
+
+        *self.active_event_loop.platform_data.borrow_mut() = data;
+        app.window_event(&self.active_event_loop, window_id, event);
+        self.active_event_loop.platform_data.borrow_mut().take();
+    }
+
     #[inline]
     pub fn window_target(&self) -> &dyn RootActiveEventLoop {
         &self.active_event_loop
     }
 
+    /// The time of the earliest redraw scheduled by the windows.
+    fn next_scheduled_redraw(&mut self) -> Option<Instant> {
+        self.with_state(|state| {
+            state
+                .window_requests
+                .get_mut()
+                .values()
+                .filter_map(|requests| *requests.redraw_at.lock().unwrap())
+                .min()
+        })
+    }
+
     fn with_state<'a, U: 'a, F: FnOnce(&'a mut WinitState) -> U>(&'a mut self, callback: F) -> U {
         let state = self.active_event_loop.state.get_mut();
         callback(state)
@@ -562,6 +912,53 @@ pub struct ActiveEventLoop {
 
     /// Connection to the wayland server.
     pub connection: Connection,
+
+    /// Sender of the results of the dialogs.
+    #[cfg(feature = "dialogs")]
+    dialog_sender: mpsc::Sender<DialogDone>,
+
+    /// The windows blocked by the dialogs.
+    #[cfg(feature = "dialogs")]
+    modal_blocks: RefCell<ModalBlocks>,
+
+    /// Watcher of the gamepads, when they can be read.
+    #[cfg(feature = "gamepad")]
+    gamepad_watcher: Option<GamepadWatcher>,
+
+    /// Watcher of the accessibility preferences, when the settings portal is reachable.
+    preferences_watcher: Option<PreferencesWatcher>,
+
+    /// The platform data of the event being delivered.
+    platform_data: RefCell<Option<PlatformData>>,
+}
+
+impl RootClipboard for ActiveEventLoop {
+    fn set(&self, data: Vec<ClipboardData>) -> Result<(), RequestError> {
+        self.set_selection(Selection::Clipboard, data)
+    }
+
+    fn get(&self, format: ClipboardFormat) -> Result<AsyncRequestSerial, RequestError> {
+        self.get_selection(Selection::Clipboard, format)
+    }
+
+    fn set_selection(
+        &self,
+        selection: Selection,
+        data: Vec<ClipboardData>,
+    ) -> Result<(), RequestError> {
+        self.state.borrow_mut().set_clipboard(&self.queue_handle, selection, data)
+    }
+
+    fn get_selection(
+        &self,
+        selection: Selection,
+        format: ClipboardFormat,
+    ) -> Result<AsyncRequestSerial, RequestError> {
+        let serial = self.state.borrow_mut().read_clipboard(selection, format)?;
+        // The data may already be there, don't wait for another event to deliver it.
+        self.event_loop_awakener.ping();
+        Ok(serial)
+    }
 }
 
 impl RootActiveEventLoop for ActiveEventLoop {
@@ -592,6 +989,94 @@ impl RootActiveEventLoop for ActiveEventLoop {
     #[inline]
     fn listen_device_events(&self, _allowed: DeviceEvents) {}
 
+    fn listen_device_classes(&self, _classes: DeviceClass) {}
+
+    fn input_devices(&self) -> Vec<DeviceInfo> {
+        #[cfg(feature = "gamepad")]
+        if let Some(watcher) = &self.gamepad_watcher {
+            return watcher.gamepads();
+        }
+        Vec::new()
+    }
+
+    #[cfg(feature = "gamepad")]
+    fn set_gamepad_rumble(
+        &self,
+        device_id: DeviceId,
+        strong: f32,
+        weak: f32,
+    ) -> Result<(), RequestError> {
+        match &self.gamepad_watcher {
+            Some(watcher) => watcher.set_rumble(device_id, strong, weak),
+            None => Err(NotSupportedError::new("the gamepads can't be read").into()),
+        }
+    }
+
+    fn windows(&self) -> Vec<WindowId> {
+        self.state.borrow().windows.borrow().keys().copied().collect()
+    }
+
+    fn with_native_modal_scope(&self, f: &mut dyn FnMut()) {
+        let windows: Vec<_> = self.state.borrow().windows.borrow().values().cloned().collect();
+
+        for window in &windows {
+            window.lock().unwrap().suspend_cursor_grab();
+        }
+        let _ = self.connection.flush();
+
+        f();
+
+        for window in &windows {
+            window.lock().unwrap().resume_cursor_grab();
+        }
+        let _ = self.connection.flush();
+    }
+
+    fn start_drag(&self, _source: WindowId, _data: DragData) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("start_drag is not supported").into())
+    }
+
+    fn set_drag_image(&self, _image: Option<Cursor>) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_drag_image is not supported").into())
+    }
+
+    fn clipboard(&self) -> &dyn RootClipboard {
+        self
+    }
+
+    #[cfg(feature = "dialogs")]
+    fn pick_file(&self, options: FileDialogOptions) -> Result<AsyncRequestSerial, RequestError> {
+        self.show_file_dialog(FileDialogKind::PickFile, options)
+    }
+
+    #[cfg(feature = "dialogs")]
+    fn pick_folder(&self, options: FileDialogOptions) -> Result<AsyncRequestSerial, RequestError> {
+        self.show_file_dialog(FileDialogKind::PickFolder, options)
+    }
+
+    #[cfg(feature = "dialogs")]
+    fn save_file(&self, options: FileDialogOptions) -> Result<AsyncRequestSerial, RequestError> {
+        self.show_file_dialog(FileDialogKind::SaveFile, options)
+    }
+
+    #[cfg(feature = "dialogs")]
+    fn show_message_box(
+        &self,
+        config: MessageBoxConfig,
+    ) -> Result<AsyncRequestSerial, RequestError> {
+        let serial = AsyncRequestSerial::get();
+        let (modality, parent) = (config.modality, config.parent);
+        let sender = self.dialog_sender.clone();
+        let awakener = self.event_loop_awakener.clone();
+        message_box::show_message_box(config, None, move |result| {
+            if sender.send(DialogDone::MessageBox(serial, result)).is_ok() {
+                awakener.ping();
+            }
+        })?;
+        self.modal_blocks.borrow_mut().block(serial, modality, parent);
+        Ok(serial)
+    }
+
     fn create_custom_cursor(
         &self,
         cursor: CustomCursorSource,
@@ -606,6 +1091,75 @@ impl RootActiveEventLoop for ActiveEventLoop {
         None
     }
 
+    fn system_accent_color(&self) -> Option<Rgba> {
+        None
+    }
+
+    fn cursor_theme(&self) -> Option<CursorThemeInfo> {
+        Some(cursor_theme::cursor_theme_from_env())
+    }
+
+    fn system_preferences(&self) -> Option<SystemPreferences> {
+        self.preferences_watcher.as_ref()?.preferences()
+    }
+
+    fn text_scale_factor(&self) -> Option<f64> {
+        self.preferences_watcher.as_ref()?.text_scale_factor()
+    }
+
+    fn power_state(&self) -> Option<PowerState> {
+        power::power_state()
+    }
+
+    fn locale(&self) -> LanguageTag {
+        LanguageTag::from_env()
+    }
+
+    fn keyboard_layout(&self) -> Option<KeyboardLayout> {
+        self.state.borrow().keyboard_layout.clone()
+    }
+
+    fn key_label(&self, key_code: KeyCode) -> Option<String> {
+        self.state.borrow().layout_keys.key(key_code)?.to_label()
+    }
+
+    fn key_for_keycode(&self, key_code: KeyCode) -> Option<Key> {
+        self.state.borrow().layout_keys.key(key_code).cloned()
+    }
+
+    fn keycode_for_key(&self, key: &Key) -> Option<KeyCode> {
+        self.state.borrow().layout_keys.key_code(key)
+    }
+
+    fn set_keyboard_led(&self, _led: Led, _on: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_keyboard_led is not supported").into())
+    }
+
+    fn keyboard_led(&self, led: Led) -> Option<bool> {
+        let modifiers = self.state.borrow().keyboard_modifiers?;
+        match led {
+            Led::CapsLock => Some(modifiers.caps_lock),
+            Led::NumLock => Some(modifiers.num_lock),
+            _ => None,
+        }
+    }
+
+    #[cfg(feature = "winit-test-harness")]
+    fn inject_device_event(
+        &self,
+        device_id: Option<DeviceId>,
+        event: DeviceEvent,
+    ) -> Result<(), RequestError> {
+        let event = Event::DeviceEvent { device_id, event };
+        self.state.borrow().window_events_sink.lock().unwrap().window_events.push((event, None));
+        self.event_loop_awakener.ping();
+        Ok(())
+    }
+
+    fn event_platform_data(&self) -> Option<PlatformData> {
+        self.platform_data.borrow().clone()
+    }
+
     fn create_window(
         &self,
         window_attributes: crate::window::WindowAttributes,
@@ -644,6 +1198,27 @@ impl RootActiveEventLoop for ActiveEventLoop {
 }
 
 impl ActiveEventLoop {
+    #[cfg(feature = "dialogs")]
+    fn show_file_dialog(
+        &self,
+        kind: FileDialogKind,
+        options: FileDialogOptions,
+    ) -> Result<AsyncRequestSerial, RequestError> {
+        let serial = AsyncRequestSerial::get();
+        let (modality, parent) = (options.modality, options.parent);
+        let sender = self.dialog_sender.clone();
+        let awakener = self.event_loop_awakener.clone();
+        // Parenting requires exporting the window with `xdg-foreign`, which isn't supported.
+        portal::show_file_dialog(kind, options, String::new(), move |result| {
+            if sender.send(DialogDone::File(serial, result)).is_ok() {
+                awakener.ping();
+            }
+        })?;
+        // The windows are still blocked by winit itself.
+        self.modal_blocks.borrow_mut().block(serial, modality, parent);
+        Ok(serial)
+    }
+
     fn clear_exit(&self) {
         self.exit.set(None)
     }
@@ -2,14 +2,18 @@
 
 use std::vec::Drain;
 
-use crate::event::{DeviceEvent, Event, WindowEvent};
+use crate::event::{
+    ButtonSource, DeviceEvent, Event, FingerId, PlatformData, PointerSource, TouchContact,
+    WindowEvent,
+};
+use crate::platform::wayland::EventData;
 use crate::window::WindowId;
 
 /// An event loop's sink to deliver events from the Wayland event callbacks
 /// to the winit's user.
 #[derive(Default)]
 pub struct EventSink {
-    pub(crate) window_events: Vec<Event>,
+    pub(crate) window_events: Vec<(Event, Option<PlatformData>)>,
 }
 
 impl EventSink {
@@ -26,13 +30,47 @@ impl EventSink {
     /// Add new device event to a queue.
     #[inline]
     pub fn push_device_event(&mut self, event: DeviceEvent) {
-        self.window_events.push(Event::DeviceEvent { event, device_id: None });
+        self.window_events.push((Event::DeviceEvent { event, device_id: None }, None));
     }
 
     /// Add new window event to a queue.
     #[inline]
     pub fn push_window_event(&mut self, event: WindowEvent, window_id: WindowId) {
-        self.window_events.push(Event::WindowEvent { event, window_id });
+        self.window_events.push((Event::WindowEvent { event, window_id }, None));
+    }
+
+    /// Add new window event to a queue, with the serial of the input event it comes from.
+    #[inline]
+    pub fn push_window_event_with_serial(
+        &mut self,
+        event: WindowEvent,
+        window_id: WindowId,
+        serial: u32,
+    ) {
+        let data = PlatformData::new(EventData { serial });
+        self.window_events.push((Event::WindowEvent { event, window_id }, Some(data)));
+    }
+
+    /// Set the contact of the last pending event of the touch point.
+    pub fn set_touch_contact(&mut self, finger_id: FingerId, contact: TouchContact) {
+        for (event, _) in self.window_events.iter_mut().rev() {
+            let Event::WindowEvent { event, .. } = event else { continue };
+            let (id, event_contact) = match event {
+                WindowEvent::PointerButton {
+                    button: ButtonSource::Touch { finger_id, contact, .. },
+                    ..
+                }
+                | WindowEvent::PointerMoved {
+                    source: PointerSource::Touch { finger_id, contact, .. },
+                    ..
+                } => (*finger_id, contact),
+                _ => continue,
+            };
+            if id == finger_id {
+                *event_contact = Some(contact);
+                return;
+            }
+        }
     }
 
     #[inline]
@@ -41,7 +79,7 @@ impl EventSink {
     }
 
     #[inline]
-    pub(crate) fn drain(&mut self) -> Drain<'_, Event> {
+    pub(crate) fn drain(&mut self) -> Drain<'_, (Event, Option<PlatformData>)> {
         self.window_events.drain(..)
     }
 }
@@ -1,10 +1,13 @@
 use std::num::{NonZeroU16, NonZeroU32};
 
 use sctk::output::{Mode, OutputData};
-use sctk::reexports::client::protocol::wl_output::WlOutput;
+use sctk::reexports::client::protocol::wl_output::{Transform, WlOutput};
 use sctk::reexports::client::Proxy;
 
+use super::types::wp_color_management::output_color_capabilities;
 use crate::dpi::{LogicalPosition, PhysicalPosition, PhysicalSize};
+use crate::error::{NotSupportedError, RequestError};
+use crate::monitor::{ColorCapabilities, MonitorIdentity, Orientation};
 use crate::platform_impl::platform::VideoModeHandle as PlatformVideoModeHandle;
 
 #[derive(Clone, Debug)]
@@ -54,6 +57,67 @@ impl MonitorHandle {
     }
 
     #[inline]
+    pub fn physical_size_mm(&self) -> Option<(u32, u32)> {
+        let output_data = self.proxy.data::<OutputData>().unwrap();
+        output_data.with_output_info(|info| {
+            let (width, height) = info.physical_size;
+            if width <= 0 || height <= 0 {
+                return None;
+            }
+
+            // The physical size is the one of the untransformed output.
+            match info.transform {
+                Transform::_90 | Transform::_270 | Transform::Flipped90 | Transform::Flipped270 => {
+                    Some((height as u32, width as u32))
+                },
+                _ => Some((width as u32, height as u32)),
+            }
+        })
+    }
+
+    pub fn identity(&self) -> Option<MonitorIdentity> {
+        let output_data = self.proxy.data::<OutputData>().unwrap();
+        output_data.with_output_info(|info| {
+            // Compositors send `unknown` when the display doesn't identify itself.
+            let known =
+                |value: &str| (!value.is_empty() && value != "unknown").then(|| value.to_owned());
+            Some(MonitorIdentity {
+                manufacturer: known(&info.make)?,
+                product_code: None,
+                model: known(&info.model),
+                serial: None,
+            })
+        })
+    }
+
+    #[inline]
+    pub fn orientation(&self) -> Orientation {
+        let output_data = self.proxy.data::<OutputData>().unwrap();
+        // The transforms rotate counterclockwise.
+        output_data.with_output_info(|info| match info.transform {
+            Transform::_90 | Transform::Flipped90 => Orientation::PortraitFlipped,
+            Transform::_180 | Transform::Flipped180 => Orientation::LandscapeFlipped,
+            Transform::_270 | Transform::Flipped270 => Orientation::Portrait,
+            _ => Orientation::Landscape,
+        })
+    }
+
+    pub fn color_capabilities(&self) -> Result<ColorCapabilities, RequestError> {
+        output_color_capabilities(&self.proxy).ok_or_else(|| {
+            NotSupportedError::new("the compositor didn't describe the colors of the output").into()
+        })
+    }
+
+    #[inline]
+    /// The refresh rate of the current mode of the output.
+    pub(crate) fn refresh_rate_millihertz(&self) -> Option<NonZeroU32> {
+        let output_data = self.proxy.data::<OutputData>().unwrap();
+        output_data.with_output_info(|info| {
+            let mode = info.modes.iter().find(|mode| mode.current)?;
+            NonZeroU32::new(mode.refresh_rate as u32)
+        })
+    }
+
     pub fn current_video_mode(&self) -> Option<PlatformVideoModeHandle> {
         let output_data = self.proxy.data::<OutputData>().unwrap();
         output_data.with_output_info(|info| {
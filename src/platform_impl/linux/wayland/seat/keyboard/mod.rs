@@ -13,8 +13,8 @@ use sctk::reexports::client::{Connection, Dispatch, Proxy, QueueHandle, WEnum};
 use tracing::warn;
 
 use crate::event::{ElementState, WindowEvent};
-use crate::keyboard::ModifiersState;
-use crate::platform_impl::common::xkb::Context;
+use crate::keyboard::{KeyboardLayout, ModifiersState};
+use crate::platform_impl::common::xkb::{Context, LayoutKeys};
 use crate::platform_impl::wayland::event_loop::sink::EventSink;
 use crate::platform_impl::wayland::state::WinitState;
 use crate::platform_impl::wayland::{self, WindowId};
@@ -28,6 +28,12 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
         _: &Connection,
         _: &QueueHandle<WinitState>,
     ) {
+        // Remember the latest input for the requests needing an input serial.
+        if let WlKeyboardEvent::Enter { serial, .. } | WlKeyboardEvent::Key { serial, .. } = &event
+        {
+            state.latest_input = Some((data.seat.id(), *serial));
+        }
+
         let seat_state = match state.seats.get_mut(&data.seat.id()) {
             Some(seat_state) => seat_state,
             None => {
@@ -52,6 +58,14 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
                     WlKeymapFormat::XkbV1 => {
                         let context = &mut keyboard_state.xkb_context;
                         context.set_keymap_from_fd(fd, size as usize);
+                        update_keyboard_layout(
+                            context,
+                            true,
+                            &mut state.keyboard_layout,
+                            &mut state.layout_keys,
+                            &mut state.events_sink,
+                            *data.window_id.lock().unwrap(),
+                        );
                     },
                     _ => unreachable!(),
                 },
@@ -59,16 +73,21 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
                     warn!("unknown keymap format 0x{:x}", value)
                 },
             },
-            WlKeyboardEvent::Enter { surface, .. } => {
+            WlKeyboardEvent::Enter { surface, serial, .. } => {
                 let window_id = wayland::make_wid(&surface);
 
                 // Mark the window as focused.
-                let was_unfocused = match state.windows.get_mut().get(&window_id) {
+                let (was_unfocused, grab_mode) = match state.windows.get_mut().get(&window_id) {
                     Some(window) => {
                         let mut window = window.lock().unwrap();
                         let was_unfocused = !window.has_focus();
-                        window.add_seat_focus(data.seat.id());
-                        was_unfocused
+                        window.add_seat_focus(&data.seat);
+                        let grab_mode = if was_unfocused {
+                            window.focus_changed_cursor_grab(true)
+                        } else {
+                            None
+                        };
+                        (was_unfocused, grab_mode)
                     },
                     None => return,
                 };
@@ -83,7 +102,17 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
 
                 // The keyboard focus is considered as general focus.
                 if was_unfocused {
-                    state.events_sink.push_window_event(WindowEvent::Focused(true), window_id);
+                    state.events_sink.push_window_event_with_serial(
+                        WindowEvent::Focused(true),
+                        window_id,
+                        serial,
+                    );
+                }
+
+                if let Some(mode) = grab_mode {
+                    state
+                        .events_sink
+                        .push_window_event(WindowEvent::CursorGrabChanged(mode), window_id);
                 }
 
                 // HACK: this is just for GNOME not fixing their ordering issue of modifiers.
@@ -106,11 +135,14 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
 
                 // NOTE: The check whether the window exists is essential as we might get a
                 // nil surface, regardless of what protocol says.
-                let focused = match state.windows.get_mut().get(&window_id) {
+                let (focused, grab_mode) = match state.windows.get_mut().get(&window_id) {
                     Some(window) => {
                         let mut window = window.lock().unwrap();
                         window.remove_seat_focus(&data.seat.id());
-                        window.has_focus()
+                        let focused = window.has_focus();
+                        let grab_mode =
+                            if focused { None } else { window.focus_changed_cursor_grab(false) };
+                        (focused, grab_mode)
                     },
                     None => return,
                 };
@@ -128,8 +160,16 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
 
                     state.events_sink.push_window_event(WindowEvent::Focused(false), window_id);
                 }
+
+                if let Some(mode) = grab_mode {
+                    state
+                        .events_sink
+                        .push_window_event(WindowEvent::CursorGrabChanged(mode), window_id);
+                }
             },
-            WlKeyboardEvent::Key { key, state: WEnum::Value(WlKeyState::Pressed), .. } => {
+            WlKeyboardEvent::Key {
+                key, serial, state: WEnum::Value(WlKeyState::Pressed), ..
+            } => {
                 let key = key + 8;
 
                 key_input(
@@ -139,6 +179,7 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
                     key,
                     ElementState::Pressed,
                     false,
+                    Some(serial),
                 );
 
                 let delay = match keyboard_state.repeat_info {
@@ -191,6 +232,7 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
                             repeat_keycode,
                             ElementState::Pressed,
                             true,
+                            None,
                         );
 
                         // NOTE: the gap could change dynamically while repeat is going.
@@ -201,7 +243,9 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
                     })
                     .ok();
             },
-            WlKeyboardEvent::Key { key, state: WEnum::Value(WlKeyState::Released), .. } => {
+            WlKeyboardEvent::Key {
+                key, serial, state: WEnum::Value(WlKeyState::Released), ..
+            } => {
                 let key = key + 8;
 
                 key_input(
@@ -211,6 +255,7 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
                     key,
                     ElementState::Released,
                     false,
+                    Some(serial),
                 );
 
                 if keyboard_state.repeat_info != RepeatInfo::Disable
@@ -234,6 +279,16 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
 
                 xkb_state.update_modifiers(mods_depressed, mods_latched, mods_locked, 0, 0, group);
                 seat_state.modifiers = xkb_state.modifiers().into();
+                state.keyboard_modifiers = Some(xkb_state.modifiers());
+
+                update_keyboard_layout(
+                    xkb_context,
+                    false,
+                    &mut state.keyboard_layout,
+                    &mut state.layout_keys,
+                    &mut state.events_sink,
+                    *data.window_id.lock().unwrap(),
+                );
 
                 // HACK: part of the workaround from `WlKeyboardEvent::Enter`.
                 let window_id = match *data.window_id.lock().unwrap() {
@@ -363,6 +418,7 @@ fn key_input(
     keycode: u32,
     state: ElementState,
     repeat: bool,
+    serial: Option<u32>,
 ) {
     let window_id = match *data.window_id.lock().unwrap() {
         Some(window_id) => window_id,
@@ -370,8 +426,41 @@ fn key_input(
     };
 
     if let Some(mut key_context) = keyboard_state.xkb_context.key_context() {
-        let event = key_context.process_key_event(keycode, state, repeat);
+        let (event, compose) = key_context.process_key_event(keycode, state, repeat);
+        if let Some(compose) = compose {
+            event_sink.push_window_event(WindowEvent::Compose(compose), window_id);
+        }
         let event = WindowEvent::KeyboardInput { device_id: None, event, is_synthetic: false };
-        event_sink.push_window_event(event, window_id);
+        match serial {
+            Some(serial) => event_sink.push_window_event_with_serial(event, window_id, serial),
+            None => event_sink.push_window_event(event, window_id),
+        }
+    }
+}
+
+/// Report a change of the keyboard layout to the focused window, and update the keys of the
+/// layout when it or the keymap changed.
+///
+/// The layout is shared by the seats, so the last one to change it wins.
+fn update_keyboard_layout(
+    xkb_context: &mut Context,
+    keymap_changed: bool,
+    current: &mut Option<KeyboardLayout>,
+    layout_keys: &mut LayoutKeys,
+    event_sink: &mut EventSink,
+    window_id: Option<WindowId>,
+) {
+    let layout = xkb_context.keyboard_layout();
+    let layout_changed = *current != layout;
+    if keymap_changed || layout_changed {
+        *layout_keys = xkb_context.layout_keys();
+    }
+    if !layout_changed {
+        return;
+    }
+    current.clone_from(&layout);
+
+    if let (Some(window_id), Some(layout)) = (window_id, layout) {
+        event_sink.push_window_event(WindowEvent::KeyboardLayoutChanged(layout), window_id);
     }
 }
@@ -7,7 +7,9 @@ use sctk::reexports::client::backend::ObjectId;
 use sctk::reexports::client::protocol::wl_seat::WlSeat;
 use sctk::reexports::client::protocol::wl_touch::WlTouch;
 use sctk::reexports::client::{Connection, Proxy, QueueHandle};
+use sctk::reexports::protocols::wp::pointer_gestures::zv1::client::zwp_pointer_gesture_pinch_v1::ZwpPointerGesturePinchV1;
 use sctk::reexports::protocols::wp::relative_pointer::zv1::client::zwp_relative_pointer_v1::ZwpRelativePointerV1;
+use sctk::reexports::protocols::wp::tablet::zv2::client::zwp_tablet_seat_v2::ZwpTabletSeatV2;
 use sctk::reexports::protocols::wp::text_input::zv3::client::zwp_text_input_v3::ZwpTextInputV3;
 use sctk::seat::pointer::{ThemeSpec, ThemedPointer};
 use sctk::seat::{Capability as SeatCapability, SeatHandler, SeatState};
@@ -19,12 +21,16 @@ use crate::platform_impl::wayland::state::WinitState;
 
 mod keyboard;
 mod pointer;
+mod tablet;
 mod text_input;
 mod touch;
 
 use keyboard::{KeyboardData, KeyboardState};
+use pointer::pointer_gesture::PinchGestureData;
+pub use pointer::pointer_gesture::PointerGesturesState;
 pub use pointer::relative_pointer::RelativePointerState;
 pub use pointer::{PointerConstraintsState, WinitPointerData, WinitPointerDataExt};
+pub use tablet::TabletState;
 use text_input::TextInputData;
 pub use text_input::{TextInputState, ZwpTextInputV3Ext};
 use touch::TouchPoint;
@@ -46,6 +52,12 @@ pub struct WinitSeatState {
     /// The relative pointer bound on the seat.
     relative_pointer: Option<ZwpRelativePointerV1>,
 
+    /// The pinch gesture bound on the seat.
+    pinch_gesture: Option<ZwpPointerGesturePinchV1>,
+
+    /// The tablet seat, reporting the pens.
+    pub tablet_seat: Option<ZwpTabletSeatV2>,
+
     /// The keyboard bound on the seat.
     keyboard_state: Option<KeyboardState>,
 
@@ -115,6 +127,14 @@ impl SeatHandler for WinitState {
                     )
                 });
 
+                seat_state.pinch_gesture = self.pointer_gestures.as_ref().map(|manager| {
+                    manager.get_pinch_gesture(
+                        themed_pointer.pointer(),
+                        queue_handle,
+                        PinchGestureData::default(),
+                    )
+                });
+
                 let themed_pointer = Arc::new(themed_pointer);
 
                 // Register cursor surface.
@@ -168,6 +188,10 @@ impl SeatHandler for WinitState {
                     relative_pointer.destroy();
                 }
 
+                if let Some(pinch_gesture) = seat_state.pinch_gesture.take() {
+                    pinch_gesture.destroy();
+                }
+
                 if let Some(pointer) = seat_state.pointer.take() {
                     let pointer_data = pointer.pointer().winit_data();
 
@@ -195,10 +219,19 @@ impl SeatHandler for WinitState {
     fn new_seat(
         &mut self,
         _connection: &Connection,
-        _queue_handle: &QueueHandle<Self>,
+        queue_handle: &QueueHandle<Self>,
         seat: WlSeat,
     ) {
-        self.seats.insert(seat.id(), WinitSeatState::new());
+        let mut seat_state = WinitSeatState::new();
+        seat_state.tablet_seat =
+            self.tablet_state.as_ref().map(|state| state.get_tablet_seat(&seat, queue_handle));
+        if let Some(clipboard) = self.clipboard.as_mut() {
+            clipboard.add_seat(&seat, queue_handle);
+        }
+        if let Some(data_control) = self.data_control.as_mut() {
+            data_control.add_seat(&seat, queue_handle);
+        }
+        self.seats.insert(seat.id(), seat_state);
     }
 
     fn remove_seat(
@@ -207,7 +240,17 @@ impl SeatHandler for WinitState {
         _queue_handle: &QueueHandle<Self>,
         seat: WlSeat,
     ) {
-        let _ = self.seats.remove(&seat.id());
+        if let Some(tablet_seat) =
+            self.seats.remove(&seat.id()).and_then(|seat_state| seat_state.tablet_seat)
+        {
+            tablet_seat.destroy();
+        }
+        if let Some(clipboard) = self.clipboard.as_mut() {
+            clipboard.remove_seat(&seat.id());
+        }
+        if let Some(data_control) = self.data_control.as_mut() {
+            data_control.remove_seat(&seat.id());
+        }
         self.on_keyboard_destroy(&seat.id());
     }
 }
@@ -220,6 +263,10 @@ impl WinitState {
             window.remove_seat_focus(seat);
             if had_focus != window.has_focus() {
                 self.events_sink.push_window_event(WindowEvent::Focused(false), *window_id);
+                if let Some(mode) = window.focus_changed_cursor_grab(false) {
+                    self.events_sink
+                        .push_window_event(WindowEvent::CursorGrabChanged(mode), *window_id);
+                }
             }
         }
     }
@@ -7,7 +7,7 @@ use std::time::Duration;
 use tracing::warn;
 
 use sctk::reexports::client::delegate_dispatch;
-use sctk::reexports::client::protocol::wl_pointer::WlPointer;
+use sctk::reexports::client::protocol::wl_pointer::{AxisSource, WlPointer};
 use sctk::reexports::client::protocol::wl_seat::WlSeat;
 use sctk::reexports::client::protocol::wl_surface::WlSurface;
 use sctk::reexports::client::{Connection, Proxy, QueueHandle, Dispatch};
@@ -32,6 +32,7 @@ use crate::event::{ElementState, MouseButton, MouseScrollDelta, PointerSource, P
 use crate::platform_impl::wayland::state::WinitState;
 use crate::platform_impl::wayland::{self, WindowId};
 
+pub mod pointer_gesture;
 pub mod relative_pointer;
 
 impl PointerHandler for WinitState {
@@ -43,6 +44,15 @@ impl PointerHandler for WinitState {
         events: &[PointerEvent],
     ) {
         let seat = pointer.winit_data().seat();
+
+        // Remember the latest press for the requests needing an input serial.
+        if let Some(serial) = events.iter().rev().find_map(|event| match event.kind {
+            PointerEventKind::Press { serial, .. } => Some(serial),
+            _ => None,
+        }) {
+            self.latest_input = Some((seat.id(), serial));
+        }
+
         let seat_state = match self.seats.get(&seat.id()) {
             Some(seat_state) => seat_state,
             None => {
@@ -121,14 +131,15 @@ impl PointerHandler for WinitState {
                     );
                 },
                 // Regular events on the main surface.
-                PointerEventKind::Enter { .. } => {
-                    self.events_sink.push_window_event(
+                PointerEventKind::Enter { serial } => {
+                    self.events_sink.push_window_event_with_serial(
                         WindowEvent::PointerEntered {
                             device_id: None,
                             position,
                             kind: PointerKind::Mouse,
                         },
                         window_id,
+                        serial,
                     );
 
                     window.pointer_entered(Arc::downgrade(themed_pointer));
@@ -172,7 +183,7 @@ impl PointerHandler for WinitState {
                     } else {
                         ElementState::Released
                     };
-                    self.events_sink.push_window_event(
+                    self.events_sink.push_window_event_with_serial(
                         WindowEvent::PointerButton {
                             device_id: None,
                             state,
@@ -180,13 +191,31 @@ impl PointerHandler for WinitState {
                             button: button.into(),
                         },
                         window_id,
+                        serial,
                     );
                 },
-                PointerEventKind::Axis { horizontal, vertical, .. } => {
+                PointerEventKind::Axis { horizontal, vertical, source, .. } => {
                     // Get the current phase.
                     let mut pointer_data = pointer.winit_data().inner.lock().unwrap();
 
-                    let has_discrete_scroll = horizontal.discrete != 0 || vertical.discrete != 0;
+                    if source.is_some() {
+                        pointer_data.axis_source = source;
+                    }
+
+                    // Touchpads and similar devices scroll continuously, and the compositor
+                    // terminates their scroll with a stop. Discrete values only come from wheels.
+                    let continuous = matches!(
+                        pointer_data.axis_source,
+                        Some(AxisSource::Finger | AxisSource::Continuous)
+                    );
+                    let has_discrete_scroll =
+                        !continuous && (horizontal.discrete != 0 || vertical.discrete != 0);
+
+                    // Both axes could stop in their own frames.
+                    if (horizontal.stop || vertical.stop) && pointer_data.phase == TouchPhase::Ended
+                    {
+                        continue;
+                    }
 
                     // Figure out what to do about start/ended phases here.
                     //
@@ -223,7 +252,7 @@ impl PointerHandler for WinitState {
                     };
 
                     self.events_sink.push_window_event(
-                        WindowEvent::MouseWheel { device_id: None, delta, phase },
+                        WindowEvent::MouseWheel { device_id: None, delta, phase, momentum: false },
                         window_id,
                     )
                 },
@@ -350,6 +379,9 @@ pub struct WinitPointerDataInner {
 
     /// Current axis phase.
     phase: TouchPhase,
+
+    /// The source of the latest axis events.
+    axis_source: Option<AxisSource>,
 }
 
 impl Drop for WinitPointerDataInner {
@@ -372,6 +404,7 @@ impl Default for WinitPointerDataInner {
             confined_pointer: None,
             latest_button_serial: 0,
             phase: TouchPhase::Ended,
+            axis_source: None,
         }
     }
 }
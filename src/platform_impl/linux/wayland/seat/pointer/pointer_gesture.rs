@@ -0,0 +1,118 @@
+//! Pointer gestures.
+
+use std::ops::Deref;
+use std::sync::Mutex;
+
+use sctk::compositor::SurfaceData;
+use sctk::globals::GlobalData;
+use sctk::reexports::client::globals::{BindError, GlobalList};
+use sctk::reexports::client::{delegate_dispatch, Connection, Dispatch, Proxy, QueueHandle};
+use sctk::reexports::protocols::wp::pointer_gestures::zv1::client::zwp_pointer_gesture_pinch_v1::{
+    self, ZwpPointerGesturePinchV1,
+};
+use sctk::reexports::protocols::wp::pointer_gestures::zv1::client::zwp_pointer_gestures_v1::ZwpPointerGesturesV1;
+
+use crate::event::{TouchPhase, WindowEvent};
+use crate::platform_impl::wayland::state::WinitState;
+use crate::platform_impl::wayland::{self, WindowId};
+
+/// Wrapper around the pointer gestures.
+pub struct PointerGesturesState {
+    manager: ZwpPointerGesturesV1,
+}
+
+impl PointerGesturesState {
+    /// Create new pointer gestures manager.
+    pub fn new(
+        globals: &GlobalList,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> Result<Self, BindError> {
+        let manager = globals.bind(queue_handle, 1..=1, GlobalData)?;
+        Ok(Self { manager })
+    }
+}
+
+impl Deref for PointerGesturesState {
+    type Target = ZwpPointerGesturesV1;
+
+    fn deref(&self) -> &Self::Target {
+        &self.manager
+    }
+}
+
+/// The state of the pinch gesture in progress.
+#[derive(Debug, Default)]
+pub struct PinchGestureData {
+    inner: Mutex<PinchGestureInner>,
+}
+
+#[derive(Debug, Default)]
+struct PinchGestureInner {
+    /// The window the gesture happens over.
+    window_id: Option<WindowId>,
+
+    /// The scale since the beginning of the gesture.
+    scale: f64,
+}
+
+impl Dispatch<ZwpPointerGesturesV1, GlobalData, WinitState> for PointerGesturesState {
+    fn event(
+        _state: &mut WinitState,
+        _proxy: &ZwpPointerGesturesV1,
+        _event: <ZwpPointerGesturesV1 as wayland_client::Proxy>::Event,
+        _data: &GlobalData,
+        _conn: &Connection,
+        _qhandle: &QueueHandle<WinitState>,
+    ) {
+    }
+}
+
+impl Dispatch<ZwpPointerGesturePinchV1, PinchGestureData, WinitState> for PointerGesturesState {
+    fn event(
+        state: &mut WinitState,
+        _proxy: &ZwpPointerGesturePinchV1,
+        event: <ZwpPointerGesturePinchV1 as wayland_client::Proxy>::Event,
+        data: &PinchGestureData,
+        _conn: &Connection,
+        _qhandle: &QueueHandle<WinitState>,
+    ) {
+        let mut inner = data.inner.lock().unwrap();
+        let (window_id, phase, pinch_delta, rotation_delta) = match event {
+            zwp_pointer_gesture_pinch_v1::Event::Begin { surface, .. } => {
+                let parent_surface = match surface.data::<SurfaceData>() {
+                    Some(data) => data.parent_surface().unwrap_or(&surface),
+                    None => return,
+                };
+                let window_id = wayland::make_wid(parent_surface);
+                inner.window_id = Some(window_id);
+                inner.scale = 1.0;
+                (window_id, TouchPhase::Started, 0.0, 0.0)
+            },
+            zwp_pointer_gesture_pinch_v1::Event::Update { scale, rotation, .. } => {
+                let Some(window_id) = inner.window_id else { return };
+                let pinch_delta = scale - inner.scale;
+                inner.scale = scale;
+                // The rotation is reported clockwise.
+                (window_id, TouchPhase::Moved, pinch_delta, -rotation as f32)
+            },
+            zwp_pointer_gesture_pinch_v1::Event::End { cancelled, .. } => {
+                let Some(window_id) = inner.window_id.take() else { return };
+                let phase = if cancelled == 0 { TouchPhase::Ended } else { TouchPhase::Cancelled };
+                (window_id, phase, 0.0, 0.0)
+            },
+            _ => return,
+        };
+
+        state.events_sink.push_window_event(
+            WindowEvent::PinchGesture { device_id: None, delta: pinch_delta, phase },
+            window_id,
+        );
+        state.events_sink.push_window_event(
+            WindowEvent::RotationGesture { device_id: None, delta: rotation_delta, phase },
+            window_id,
+        );
+    }
+}
+
+delegate_dispatch!(WinitState: [ZwpPointerGesturesV1: GlobalData] => PointerGesturesState);
+delegate_dispatch!(WinitState: [ZwpPointerGesturePinchV1: PinchGestureData] => PointerGesturesState);
@@ -0,0 +1,385 @@
+//! The pen events, from the tablet protocol.
+//!
+//! Once the tablets are bound, the compositor stops emulating the pointer with the pens, so the
+//! pointer events are emitted from the tools as well.
+
+use std::sync::Mutex;
+
+use sctk::compositor::SurfaceData;
+use sctk::globals::GlobalData;
+use sctk::reexports::client::globals::{BindError, GlobalList};
+use sctk::reexports::client::protocol::wl_seat::WlSeat;
+use sctk::reexports::client::{
+    delegate_dispatch, event_created_child, Connection, Dispatch, Proxy, QueueHandle, WEnum,
+};
+use sctk::reexports::protocols::wp::cursor_shape::v1::client::wp_cursor_shape_device_v1::{
+    Shape, WpCursorShapeDeviceV1,
+};
+use sctk::reexports::protocols::wp::cursor_shape::v1::client::wp_cursor_shape_manager_v1::WpCursorShapeManagerV1;
+use sctk::reexports::protocols::wp::tablet::zv2::client::zwp_tablet_manager_v2::ZwpTabletManagerV2;
+use sctk::reexports::protocols::wp::tablet::zv2::client::zwp_tablet_pad_group_v2::{
+    self, ZwpTabletPadGroupV2,
+};
+use sctk::reexports::protocols::wp::tablet::zv2::client::zwp_tablet_pad_ring_v2::ZwpTabletPadRingV2;
+use sctk::reexports::protocols::wp::tablet::zv2::client::zwp_tablet_pad_strip_v2::ZwpTabletPadStripV2;
+use sctk::reexports::protocols::wp::tablet::zv2::client::zwp_tablet_pad_v2::{
+    self, ZwpTabletPadV2,
+};
+use sctk::reexports::protocols::wp::tablet::zv2::client::zwp_tablet_seat_v2::{
+    self, ZwpTabletSeatV2,
+};
+use sctk::reexports::protocols::wp::tablet::zv2::client::zwp_tablet_tool_v2::{
+    ButtonState, Capability, Event as ToolEvent, Type as ToolType, ZwpTabletToolV2,
+};
+use sctk::reexports::protocols::wp::tablet::zv2::client::zwp_tablet_v2::ZwpTabletV2;
+
+use crate::dpi::{LogicalPosition, PhysicalPosition};
+use crate::event::{
+    ElementState, MouseButton, PenButtons, PenTilt, PointerKind, PointerSource, WindowEvent,
+};
+use crate::platform_impl::wayland::state::WinitState;
+use crate::platform_impl::wayland::{self, WindowId};
+
+// These values are coming from <linux/input-event-codes.h>.
+const BTN_STYLUS: u32 = 0x14b;
+const BTN_STYLUS2: u32 = 0x14c;
+
+/// The largest pressure reported by the tools.
+const MAX_PRESSURE: f32 = 65535.0;
+
+#[derive(Debug)]
+pub struct TabletState {
+    tablet_manager: ZwpTabletManagerV2,
+    cursor_shape_manager: Option<WpCursorShapeManagerV1>,
+}
+
+impl TabletState {
+    pub fn new(
+        globals: &GlobalList,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> Result<Self, BindError> {
+        let tablet_manager = globals.bind(queue_handle, 1..=1, GlobalData)?;
+        let cursor_shape_manager = globals.bind(queue_handle, 1..=1, GlobalData).ok();
+        Ok(Self { tablet_manager, cursor_shape_manager })
+    }
+
+    pub fn get_tablet_seat(
+        &self,
+        seat: &WlSeat,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> ZwpTabletSeatV2 {
+        self.tablet_manager.get_tablet_seat(seat, queue_handle, GlobalData)
+    }
+}
+
+/// The state of a tool, updated by its events and delivered on each frame.
+#[derive(Debug, Default)]
+pub struct TabletToolData {
+    inner: Mutex<TabletToolInner>,
+}
+
+#[derive(Debug, Default)]
+struct TabletToolInner {
+    /// The device showing the cursor while the tool is in proximity.
+    cursor_shape_device: Option<WpCursorShapeDeviceV1>,
+    has_pressure: bool,
+    has_tilt: bool,
+    has_rotation: bool,
+    inverted: bool,
+
+    /// The window the tool is over.
+    window_id: Option<WindowId>,
+    position: (f64, f64),
+    contact: bool,
+    pressure: f32,
+    tilt: PenTilt,
+    twist: f32,
+    barrel_buttons: PenButtons,
+
+    /// The changes since the last frame.
+    entered: bool,
+    left: bool,
+    moved: bool,
+    buttons: Vec<(MouseButton, ElementState)>,
+}
+
+impl Dispatch<ZwpTabletToolV2, TabletToolData, WinitState> for TabletState {
+    fn event(
+        state: &mut WinitState,
+        tool: &ZwpTabletToolV2,
+        event: ToolEvent,
+        data: &TabletToolData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        let mut inner = data.inner.lock().unwrap();
+        match event {
+            ToolEvent::Type { tool_type: WEnum::Value(tool_type) } => {
+                inner.inverted = tool_type == ToolType::Eraser;
+            },
+            ToolEvent::Capability { capability: WEnum::Value(capability) } => match capability {
+                Capability::Pressure => inner.has_pressure = true,
+                Capability::Tilt => inner.has_tilt = true,
+                Capability::Rotation => inner.has_rotation = true,
+                _ => (),
+            },
+            ToolEvent::ProximityIn { serial, surface, .. } => {
+                // Pens over the decorations are ignored.
+                if surface.data::<SurfaceData>().and_then(|data| data.parent_surface()).is_some() {
+                    return;
+                }
+
+                inner.window_id = Some(wayland::make_wid(&surface));
+                inner.entered = true;
+                if let Some(device) = &inner.cursor_shape_device {
+                    device.set_shape(serial, Shape::Default);
+                }
+            },
+            ToolEvent::ProximityOut => {
+                inner.left = inner.window_id.is_some();
+            },
+            ToolEvent::Down { .. } => {
+                inner.contact = true;
+                inner.buttons.push((MouseButton::Left, ElementState::Pressed));
+            },
+            ToolEvent::Up => {
+                inner.contact = false;
+                inner.buttons.push((MouseButton::Left, ElementState::Released));
+            },
+            ToolEvent::Motion { x, y } => {
+                inner.position = (x, y);
+                inner.moved = true;
+            },
+            ToolEvent::Pressure { pressure } => {
+                inner.pressure = pressure as f32 / MAX_PRESSURE;
+            },
+            ToolEvent::Tilt { tilt_x, tilt_y } => {
+                inner.tilt = PenTilt { x: tilt_x as f32, y: tilt_y as f32 };
+            },
+            ToolEvent::Rotation { degrees } => {
+                inner.twist = degrees.rem_euclid(360.0) as f32;
+            },
+            ToolEvent::Button { button, state: WEnum::Value(button_state), .. } => {
+                let (flag, button) = match button {
+                    BTN_STYLUS => (PenButtons::PRIMARY, MouseButton::Middle),
+                    BTN_STYLUS2 => (PenButtons::SECONDARY, MouseButton::Right),
+                    _ => return,
+                };
+                let pressed = button_state == ButtonState::Pressed;
+                inner.barrel_buttons.set(flag, pressed);
+                let state = if pressed { ElementState::Pressed } else { ElementState::Released };
+                inner.buttons.push((button, state));
+            },
+            ToolEvent::Frame { .. } => inner.frame(state),
+            ToolEvent::Removed => {
+                if let Some(device) = inner.cursor_shape_device.take() {
+                    device.destroy();
+                }
+                tool.destroy();
+            },
+            _ => (),
+        }
+    }
+}
+
+impl TabletToolInner {
+    /// Deliver the changes of the frame.
+    fn frame(&mut self, state: &mut WinitState) {
+        let (entered, left, moved) = (self.entered, self.left, self.moved);
+        let buttons = std::mem::take(&mut self.buttons);
+        self.entered = false;
+        self.left = false;
+        self.moved = false;
+
+        let Some(window_id) = self.window_id else { return };
+        if left {
+            self.window_id = None;
+            self.contact = false;
+            self.pressure = 0.0;
+        }
+
+        let scale_factor = match state.windows.get_mut().get(&window_id) {
+            Some(window) => window.lock().unwrap().scale_factor(),
+            None => return,
+        };
+        let position: PhysicalPosition<f64> =
+            LogicalPosition::new(self.position.0, self.position.1).to_physical(scale_factor);
+
+        let sink = &mut state.events_sink;
+        let kind = PointerKind::Unknown;
+        if entered {
+            sink.push_window_event(
+                WindowEvent::PointerEntered { device_id: None, position, kind },
+                window_id,
+            );
+        }
+        if moved {
+            sink.push_window_event(
+                WindowEvent::PointerMoved {
+                    device_id: None,
+                    position,
+                    source: PointerSource::Unknown,
+                },
+                window_id,
+            );
+        }
+        for (button, state) in buttons {
+            sink.push_window_event(
+                WindowEvent::PointerButton {
+                    device_id: None,
+                    state,
+                    position,
+                    button: button.into(),
+                },
+                window_id,
+            );
+        }
+
+        let pressure = match self.has_pressure {
+            true => self.pressure,
+            false => self.contact as u8 as f32,
+        };
+        sink.push_window_event(
+            WindowEvent::PenInput {
+                device_id: None,
+                position,
+                contact: self.contact,
+                pressure,
+                tilt: self.has_tilt.then_some(self.tilt),
+                twist: self.has_rotation.then_some(self.twist),
+                inverted: self.inverted,
+                barrel_buttons: self.barrel_buttons,
+            },
+            window_id,
+        );
+
+        if left {
+            sink.push_window_event(
+                WindowEvent::PointerLeft { device_id: None, position: Some(position), kind },
+                window_id,
+            );
+        }
+    }
+}
+
+impl Dispatch<ZwpTabletSeatV2, GlobalData, WinitState> for TabletState {
+    fn event(
+        state: &mut WinitState,
+        _: &ZwpTabletSeatV2,
+        event: zwp_tablet_seat_v2::Event,
+        _: &GlobalData,
+        _: &Connection,
+        queue_handle: &QueueHandle<WinitState>,
+    ) {
+        if let zwp_tablet_seat_v2::Event::ToolAdded { id } = event {
+            let cursor_shape_manager = state
+                .tablet_state
+                .as_ref()
+                .and_then(|tablet_state| tablet_state.cursor_shape_manager.as_ref());
+            if let Some(manager) = cursor_shape_manager {
+                let device = manager.get_tablet_tool_v2(&id, queue_handle, GlobalData);
+                let data = id.data::<TabletToolData>().unwrap();
+                data.inner.lock().unwrap().cursor_shape_device = Some(device);
+            }
+        }
+    }
+
+    event_created_child!(WinitState, ZwpTabletSeatV2, [
+        zwp_tablet_seat_v2::EVT_TABLET_ADDED_OPCODE => (ZwpTabletV2, GlobalData),
+        zwp_tablet_seat_v2::EVT_TOOL_ADDED_OPCODE => (ZwpTabletToolV2, TabletToolData::default()),
+        zwp_tablet_seat_v2::EVT_PAD_ADDED_OPCODE => (ZwpTabletPadV2, GlobalData),
+    ]);
+}
+
+impl Dispatch<ZwpTabletManagerV2, GlobalData, WinitState> for TabletState {
+    fn event(
+        _: &mut WinitState,
+        _: &ZwpTabletManagerV2,
+        _: <ZwpTabletManagerV2 as Proxy>::Event,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        unreachable!("zwp_tablet_manager_v2 has no events")
+    }
+}
+
+// The tablets and their pads aren't used, their objects are only kept alive until removed.
+
+impl Dispatch<ZwpTabletV2, GlobalData, WinitState> for TabletState {
+    fn event(
+        _: &mut WinitState,
+        _: &ZwpTabletV2,
+        _: <ZwpTabletV2 as Proxy>::Event,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+    }
+}
+
+impl Dispatch<ZwpTabletPadV2, GlobalData, WinitState> for TabletState {
+    fn event(
+        _: &mut WinitState,
+        _: &ZwpTabletPadV2,
+        _: zwp_tablet_pad_v2::Event,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+    }
+
+    event_created_child!(WinitState, ZwpTabletPadV2, [
+        zwp_tablet_pad_v2::EVT_GROUP_OPCODE => (ZwpTabletPadGroupV2, GlobalData),
+    ]);
+}
+
+impl Dispatch<ZwpTabletPadGroupV2, GlobalData, WinitState> for TabletState {
+    fn event(
+        _: &mut WinitState,
+        _: &ZwpTabletPadGroupV2,
+        _: zwp_tablet_pad_group_v2::Event,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+    }
+
+    event_created_child!(WinitState, ZwpTabletPadGroupV2, [
+        zwp_tablet_pad_group_v2::EVT_RING_OPCODE => (ZwpTabletPadRingV2, GlobalData),
+        zwp_tablet_pad_group_v2::EVT_STRIP_OPCODE => (ZwpTabletPadStripV2, GlobalData),
+    ]);
+}
+
+impl Dispatch<ZwpTabletPadRingV2, GlobalData, WinitState> for TabletState {
+    fn event(
+        _: &mut WinitState,
+        _: &ZwpTabletPadRingV2,
+        _: <ZwpTabletPadRingV2 as Proxy>::Event,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+    }
+}
+
+impl Dispatch<ZwpTabletPadStripV2, GlobalData, WinitState> for TabletState {
+    fn event(
+        _: &mut WinitState,
+        _: &ZwpTabletPadStripV2,
+        _: <ZwpTabletPadStripV2 as Proxy>::Event,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+    }
+}
+
+delegate_dispatch!(WinitState: [ZwpTabletManagerV2: GlobalData] => TabletState);
+delegate_dispatch!(WinitState: [ZwpTabletSeatV2: GlobalData] => TabletState);
+delegate_dispatch!(WinitState: [ZwpTabletToolV2: TabletToolData] => TabletState);
+delegate_dispatch!(WinitState: [ZwpTabletV2: GlobalData] => TabletState);
+delegate_dispatch!(WinitState: [ZwpTabletPadV2: GlobalData] => TabletState);
+delegate_dispatch!(WinitState: [ZwpTabletPadGroupV2: GlobalData] => TabletState);
+delegate_dispatch!(WinitState: [ZwpTabletPadRingV2: GlobalData] => TabletState);
+delegate_dispatch!(WinitState: [ZwpTabletPadStripV2: GlobalData] => TabletState);
@@ -69,11 +69,17 @@ impl Dispatch<ZwpTextInputV3, TextInputData, WinitState> for TextInputState {
                     None => return,
                 };
 
-                if window.ime_allowed() {
+                if window.text_input_enabled() {
                     text_input.enable();
                     text_input.set_content_type_by_purpose(window.ime_purpose());
                     text_input.commit();
+                }
+                if window.ime_allowed() {
                     state.events_sink.push_window_event(WindowEvent::Ime(Ime::Enabled), window_id);
+                    state.events_sink.push_window_event(
+                        WindowEvent::Ime(Ime::SurroundingTextRequest),
+                        window_id,
+                    );
                 }
 
                 window.text_input_entered(text_input);
@@ -125,11 +131,15 @@ impl Dispatch<ZwpTextInputV3, TextInputData, WinitState> for TextInputState {
                     window_id,
                 );
 
-                // Send `Commit`.
+                // Send `Commit`, which changes the surrounding text.
                 if let Some(text) = text_input_data.pending_commit.take() {
                     state
                         .events_sink
                         .push_window_event(WindowEvent::Ime(Ime::Commit(text)), window_id);
+                    state.events_sink.push_window_event(
+                        WindowEvent::Ime(Ime::SurroundingTextRequest),
+                        window_id,
+                    );
                 }
 
                 // Send preedit.
@@ -151,8 +161,14 @@ impl Dispatch<ZwpTextInputV3, TextInputData, WinitState> for TextInputState {
     }
 }
 
+/// The maximum length of the surrounding text in bytes, with its nul terminator.
+const MAX_SURROUNDING_TEXT_LEN: usize = 4000;
+
 pub trait ZwpTextInputV3Ext {
     fn set_content_type_by_purpose(&self, purpose: ImePurpose);
+
+    /// Set the surrounding text, shortened around the selection to the length allowed.
+    fn set_surrounding_text_around(&self, text: &str, cursor: usize, anchor: usize);
 }
 
 impl ZwpTextInputV3Ext for ZwpTextInputV3 {
@@ -161,9 +177,33 @@ impl ZwpTextInputV3Ext for ZwpTextInputV3 {
             ImePurpose::Normal => (ContentHint::None, ContentPurpose::Normal),
             ImePurpose::Password => (ContentHint::SensitiveData, ContentPurpose::Password),
             ImePurpose::Terminal => (ContentHint::None, ContentPurpose::Terminal),
+            ImePurpose::Digits => (ContentHint::None, ContentPurpose::Digits),
+            ImePurpose::Email => (ContentHint::None, ContentPurpose::Email),
+            ImePurpose::Url => (ContentHint::None, ContentPurpose::Url),
         };
         self.set_content_type(hint, purpose);
     }
+
+    fn set_surrounding_text_around(&self, text: &str, cursor: usize, anchor: usize) {
+        let len = MAX_SURROUNDING_TEXT_LEN - 1;
+        let (mut start, mut end) = (0, text.len());
+        if text.len() > len {
+            // Center the selection, moving the bounds inwards to char boundaries.
+            let selection = cursor.min(anchor)..cursor.max(anchor);
+            let margin = len.saturating_sub(selection.len()) / 2;
+            start = selection.start.saturating_sub(margin).min(text.len() - len);
+            while !text.is_char_boundary(start) {
+                start += 1;
+            }
+            end = (start + len).min(text.len());
+            while !text.is_char_boundary(end) {
+                end -= 1;
+            }
+        }
+
+        let offset = |index: usize| (index.clamp(start, end) - start) as i32;
+        self.set_surrounding_text(text[start..end].to_owned(), offset(cursor), offset(anchor));
+    }
 }
 
 /// The Data associated with the text input.
@@ -8,7 +8,9 @@ use sctk::seat::touch::{TouchData, TouchHandler};
 use tracing::warn;
 
 use crate::dpi::LogicalPosition;
-use crate::event::{ButtonSource, ElementState, PointerKind, PointerSource, WindowEvent};
+use crate::event::{
+    ButtonSource, ElementState, PointerKind, PointerSource, TouchContact, WindowEvent,
+};
 use crate::platform_impl::wayland::state::WinitState;
 use crate::platform_impl::wayland::{self, FingerId};
 
@@ -18,7 +20,7 @@ impl TouchHandler for WinitState {
         _: &Connection,
         _: &QueueHandle<Self>,
         touch: &WlTouch,
-        _: u32,
+        serial: u32,
         _: u32,
         surface: WlSurface,
         id: i32,
@@ -30,6 +32,8 @@ impl TouchHandler for WinitState {
             None => return,
         };
 
+        self.latest_input = Some((touch.seat().id(), serial));
+
         let seat_state = match self.seats.get_mut(&touch.seat().id()) {
             Some(seat_state) => seat_state,
             None => {
@@ -40,7 +44,9 @@ impl TouchHandler for WinitState {
 
         // Update the state of the point.
         let location = LogicalPosition::<f64>::from(position);
-        seat_state.touch_map.insert(id, TouchPoint { surface, location });
+        let touch_point = TouchPoint { surface, location, shape: None, orientation: 0.0 };
+        let contact = touch_point.contact(scale_factor);
+        seat_state.touch_map.insert(id, touch_point);
 
         let position = location.to_physical(scale_factor);
         let finger_id =
@@ -54,14 +60,15 @@ impl TouchHandler for WinitState {
             },
             window_id,
         );
-        self.events_sink.push_window_event(
+        self.events_sink.push_window_event_with_serial(
             WindowEvent::PointerButton {
                 device_id: None,
                 state: ElementState::Pressed,
                 position,
-                button: ButtonSource::Touch { finger_id, force: None },
+                button: ButtonSource::Touch { finger_id, force: None, contact },
             },
             window_id,
+            serial,
         );
     }
 
@@ -70,7 +77,7 @@ impl TouchHandler for WinitState {
         _: &Connection,
         _: &QueueHandle<Self>,
         touch: &WlTouch,
-        _: u32,
+        serial: u32,
         _: u32,
         id: i32,
     ) {
@@ -97,15 +104,17 @@ impl TouchHandler for WinitState {
         let position = touch_point.location.to_physical(scale_factor);
         let finger_id =
             crate::event::FingerId(crate::platform_impl::FingerId::Wayland(FingerId(id)));
+        let contact = touch_point.contact(scale_factor);
 
-        self.events_sink.push_window_event(
+        self.events_sink.push_window_event_with_serial(
             WindowEvent::PointerButton {
                 device_id: None,
                 state: ElementState::Released,
                 position,
-                button: ButtonSource::Touch { finger_id, force: None },
+                button: ButtonSource::Touch { finger_id, force: None, contact },
             },
             window_id,
+            serial,
         );
         self.events_sink.push_window_event(
             WindowEvent::PointerLeft {
@@ -157,6 +166,7 @@ impl TouchHandler for WinitState {
                         FingerId(id),
                     )),
                     force: None,
+                    contact: touch_point.contact(scale_factor),
                 },
             },
             window_id,
@@ -198,16 +208,54 @@ impl TouchHandler for WinitState {
         &mut self,
         _: &Connection,
         _: &QueueHandle<Self>,
-        _: &WlTouch,
-        _: i32,
-        _: f64,
-        _: f64,
+        touch: &WlTouch,
+        id: i32,
+        major: f64,
+        minor: f64,
+    ) {
+        self.update_touch_contact(touch, id, |touch_point| {
+            touch_point.shape = Some((major, minor));
+        });
+    }
+
+    fn orientation(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        touch: &WlTouch,
+        id: i32,
+        orientation: f64,
     ) {
-        // Blank.
+        self.update_touch_contact(touch, id, |touch_point| {
+            touch_point.orientation = orientation;
+        });
     }
+}
+
+impl WinitState {
+    /// Update the contact of the touch point, and of its last event.
+    ///
+    /// The shape and orientation of a touch point come after its down or motion events in the
+    /// same frame, and apply to them.
+    fn update_touch_contact(&mut self, touch: &WlTouch, id: i32, f: impl FnOnce(&mut TouchPoint)) {
+        let Some(touch_point) =
+            self.seats.get_mut(&touch.seat().id()).and_then(|seat| seat.touch_map.get_mut(&id))
+        else {
+            return;
+        };
+        f(touch_point);
 
-    fn orientation(&mut self, _: &Connection, _: &QueueHandle<Self>, _: &WlTouch, _: i32, _: f64) {
-        // Blank.
+        let window_id = wayland::make_wid(&touch_point.surface);
+        let scale_factor = match self.windows.get_mut().get(&window_id) {
+            Some(window) => window.lock().unwrap().scale_factor(),
+            None => return,
+        };
+
+        if let Some(contact) = touch_point.contact(scale_factor) {
+            let finger_id =
+                crate::event::FingerId(crate::platform_impl::FingerId::Wayland(FingerId(id)));
+            self.events_sink.set_touch_contact(finger_id, contact);
+        }
     }
 }
 
@@ -219,6 +267,23 @@ pub struct TouchPoint {
 
     /// The location of the point on the surface.
     pub location: LogicalPosition<f64>,
+
+    /// The lengths of the major and minor axes of the contact, in surface coordinates.
+    pub shape: Option<(f64, f64)>,
+
+    /// The angle of the major axis, in degrees clockwise.
+    pub orientation: f64,
+}
+
+impl TouchPoint {
+    fn contact(&self, scale_factor: f64) -> Option<TouchContact> {
+        let (major, minor) = self.shape?;
+        Some(TouchContact {
+            major: major * scale_factor,
+            minor: minor * scale_factor,
+            orientation: self.orientation.to_radians(),
+        })
+    }
 }
 
 pub trait TouchDataExt {
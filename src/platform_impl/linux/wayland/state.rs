@@ -1,4 +1,5 @@
 use std::cell::RefCell;
+use std::num::NonZeroU32;
 use std::sync::atomic::Ordering;
 use std::sync::{Arc, Mutex};
 
@@ -22,13 +23,20 @@ use sctk::shm::{Shm, ShmHandler};
 use sctk::subcompositor::SubcompositorState;
 
 use crate::error::OsError;
+use crate::keyboard::KeyboardLayout;
+use crate::monitor::Orientation;
+use crate::platform_impl::common::xkb::{LayoutKeys, ModifiersState as XkbModifiersState};
 use crate::platform_impl::wayland::event_loop::sink::EventSink;
 use crate::platform_impl::wayland::output::MonitorHandle;
 use crate::platform_impl::wayland::seat::{
-    PointerConstraintsState, RelativePointerState, TextInputState, WinitPointerData,
-    WinitPointerDataExt, WinitSeatState,
+    PointerConstraintsState, PointerGesturesState, RelativePointerState, TabletState,
+    TextInputState, WinitPointerData, WinitPointerDataExt, WinitSeatState,
 };
+use crate::platform_impl::wayland::types::clipboard::ClipboardState;
+use crate::platform_impl::wayland::types::data_control::DataControlManager;
+use crate::platform_impl::wayland::types::keyboard_shortcuts_inhibit::KeyboardShortcutsInhibitManager;
 use crate::platform_impl::wayland::types::kwin_blur::KWinBlurManager;
+use crate::platform_impl::wayland::types::wp_color_management::ColorManager;
 use crate::platform_impl::wayland::types::wp_fractional_scaling::FractionalScalingManager;
 use crate::platform_impl::wayland::types::wp_viewporter::ViewporterState;
 use crate::platform_impl::wayland::types::xdg_activation::XdgActivationState;
@@ -85,6 +93,21 @@ pub struct WinitState {
     /// Observed monitors.
     pub monitors: Arc<Mutex<Vec<MonitorHandle>>>,
 
+    /// The monitors connected and disconnected since the last iteration of the event loop.
+    pub monitor_changes: Vec<(MonitorHandle, bool)>,
+
+    /// The monitors whose color capabilities changed since the last iteration of the event loop.
+    pub color_capabilities_changes: Vec<MonitorHandle>,
+
+    /// The last known orientations and refresh rates of the monitors, to detect their changes.
+    pub monitor_properties: Vec<(MonitorHandle, Orientation, Option<NonZeroU32>)>,
+
+    /// The monitors whose orientation changed since the last iteration of the event loop.
+    pub orientation_changes: Vec<MonitorHandle>,
+
+    /// The monitors whose refresh rate changed since the last iteration of the event loop.
+    pub refresh_rate_changes: Vec<MonitorHandle>,
+
     /// Sink to accumulate window events from the compositor, which is latter dispatched in
     /// event loop run.
     pub events_sink: EventSink,
@@ -95,6 +118,12 @@ pub struct WinitState {
     /// Relative pointer.
     pub relative_pointer: Option<RelativePointerState>,
 
+    /// Pointer gestures, for the touchpad pinches.
+    pub pointer_gestures: Option<PointerGesturesState>,
+
+    /// Tablets, to handle the pens.
+    pub tablet_state: Option<TabletState>,
+
     /// Pointer constraints to handle pointer locking and confining.
     pub pointer_constraints: Option<Arc<PointerConstraintsState>>,
 
@@ -107,6 +136,24 @@ pub struct WinitState {
     /// KWin blur manager.
     pub kwin_blur_manager: Option<KWinBlurManager>,
 
+    /// Keyboard shortcuts inhibit manager, to capture the keyboard.
+    pub keyboard_shortcuts_inhibit_manager: Option<KeyboardShortcutsInhibitManager>,
+
+    /// Color manager.
+    pub color_manager: Option<ColorManager>,
+
+    /// The data devices and primary selection devices, for the clipboard and primary selection.
+    pub clipboard: Option<ClipboardState>,
+
+    /// The data control devices, for the changes of the clipboard.
+    pub data_control: Option<DataControlManager>,
+
+    /// Whether the clipboard changed since the last iteration of the event loop.
+    pub clipboard_changed: bool,
+
+    /// The seat and the serial of the latest input, for the requests needing one.
+    pub latest_input: Option<(ObjectId, u32)>,
+
     /// Loop handle to re-register event sources, such as keyboard repeat.
     pub loop_handle: LoopHandle<'static, Self>,
 
@@ -116,6 +163,18 @@ pub struct WinitState {
 
     /// Whether the user initiated a wake up.
     pub proxy_wake_up: bool,
+
+    /// Whether the process received `SIGTERM`.
+    pub exit_requested: bool,
+
+    /// The keyboard layout of the last keymap or layout change.
+    pub keyboard_layout: Option<KeyboardLayout>,
+
+    /// The keys of the keyboard layout pressed without modifiers.
+    pub layout_keys: LayoutKeys,
+
+    /// The modifiers of the last modifiers change, with the state of the locks.
+    pub keyboard_modifiers: Option<XkbModifiersState>,
 }
 
 impl WinitState {
@@ -144,9 +203,23 @@ impl WinitState {
 
         let seat_state = SeatState::new(globals, queue_handle);
 
+        let tablet_state = TabletState::new(globals, queue_handle).ok();
+
+        let mut clipboard = ClipboardState::bind(globals, queue_handle).ok();
+        let mut data_control = DataControlManager::new(globals, queue_handle).ok();
+
         let mut seats = AHashMap::default();
         for seat in seat_state.seats() {
-            seats.insert(seat.id(), WinitSeatState::new());
+            let mut seat_data = WinitSeatState::new();
+            seat_data.tablet_seat =
+                tablet_state.as_ref().map(|state| state.get_tablet_seat(&seat, queue_handle));
+            if let Some(clipboard) = clipboard.as_mut() {
+                clipboard.add_seat(&seat, queue_handle);
+            }
+            if let Some(data_control) = data_control.as_mut() {
+                data_control.add_seat(&seat, queue_handle);
+            }
+            seats.insert(seat.id(), seat_data);
         }
 
         let (viewporter_state, fractional_scaling_manager) =
@@ -156,6 +229,13 @@ impl WinitState {
                 (None, None)
             };
 
+        let mut color_manager = ColorManager::new(globals, queue_handle).ok();
+        if let Some(color_manager) = color_manager.as_mut() {
+            for output in output_state.outputs() {
+                color_manager.add_output(&output, queue_handle);
+            }
+        }
+
         let shm = Shm::bind(globals, queue_handle).map_err(|err| os_error!(err))?;
         let custom_cursor_pool = Arc::new(Mutex::new(SlotPool::new(2, &shm).unwrap()));
 
@@ -178,22 +258,43 @@ impl WinitState {
             viewporter_state,
             fractional_scaling_manager,
             kwin_blur_manager: KWinBlurManager::new(globals, queue_handle).ok(),
+            keyboard_shortcuts_inhibit_manager: KeyboardShortcutsInhibitManager::new(
+                globals,
+                queue_handle,
+            )
+            .ok(),
+            color_manager,
+            clipboard,
+            data_control,
+            clipboard_changed: false,
+            latest_input: None,
 
             seats,
             text_input_state: TextInputState::new(globals, queue_handle).ok(),
 
             relative_pointer: RelativePointerState::new(globals, queue_handle).ok(),
+            pointer_gestures: PointerGesturesState::new(globals, queue_handle).ok(),
+            tablet_state,
             pointer_constraints: PointerConstraintsState::new(globals, queue_handle)
                 .map(Arc::new)
                 .ok(),
             pointer_surfaces: Default::default(),
 
             monitors: Arc::new(Mutex::new(monitors)),
+            monitor_changes: Vec::new(),
+            color_capabilities_changes: Vec::new(),
+            monitor_properties: Vec::new(),
+            orientation_changes: Vec::new(),
+            refresh_rate_changes: Vec::new(),
             events_sink: EventSink::new(),
             loop_handle,
             // Make it true by default.
             dispatched_events: true,
             proxy_wake_up: false,
+            exit_requested: false,
+            keyboard_layout: None,
+            layout_keys: Default::default(),
+            keyboard_modifiers: None,
         })
     }
 
@@ -313,13 +414,39 @@ impl OutputHandler for WinitState {
         &mut self.output_state
     }
 
-    fn new_output(&mut self, _: &Connection, _: &QueueHandle<Self>, output: WlOutput) {
-        self.monitors.lock().unwrap().push(MonitorHandle::new(output));
+    fn new_output(&mut self, _: &Connection, queue_handle: &QueueHandle<Self>, output: WlOutput) {
+        if let Some(color_manager) = self.color_manager.as_mut() {
+            color_manager.add_output(&output, queue_handle);
+        }
+        let added = MonitorHandle::new(output);
+        self.monitors.lock().unwrap().push(added.clone());
+        self.monitor_properties.push((
+            added.clone(),
+            added.orientation(),
+            added.refresh_rate_millihertz(),
+        ));
+        self.monitor_changes.push((added, true));
     }
 
     fn update_output(&mut self, _: &Connection, _: &QueueHandle<Self>, updated: WlOutput) {
-        let mut monitors = self.monitors.lock().unwrap();
         let updated = MonitorHandle::new(updated);
+        let orientation = updated.orientation();
+        let refresh_rate = updated.refresh_rate_millihertz();
+        match self.monitor_properties.iter_mut().find(|(monitor, ..)| *monitor == updated) {
+            Some((_, known_orientation, known_refresh_rate)) => {
+                if *known_orientation != orientation {
+                    *known_orientation = orientation;
+                    self.orientation_changes.push(updated.clone());
+                }
+                if *known_refresh_rate != refresh_rate {
+                    *known_refresh_rate = refresh_rate;
+                    self.refresh_rate_changes.push(updated.clone());
+                }
+            },
+            None => self.monitor_properties.push((updated.clone(), orientation, refresh_rate)),
+        }
+
+        let mut monitors = self.monitors.lock().unwrap();
         if let Some(pos) = monitors.iter().position(|output| output == &updated) {
             monitors[pos] = updated
         } else {
@@ -328,11 +455,17 @@ impl OutputHandler for WinitState {
     }
 
     fn output_destroyed(&mut self, _: &Connection, _: &QueueHandle<Self>, removed: WlOutput) {
-        let mut monitors = self.monitors.lock().unwrap();
+        if let Some(color_manager) = self.color_manager.as_mut() {
+            color_manager.remove_output(&removed);
+        }
         let removed = MonitorHandle::new(removed);
+        self.monitor_properties.retain(|(monitor, ..)| *monitor != removed);
+
+        let mut monitors = self.monitors.lock().unwrap();
         if let Some(pos) = monitors.iter().position(|output| output == &removed) {
             monitors.remove(pos);
         }
+        self.monitor_changes.push((removed, false));
     }
 }
 
@@ -0,0 +1,388 @@
+//! Handling of the data devices and the primary selection devices, which are used for the
+//! clipboard and the primary selection.
+
+use std::io::{Read, Write};
+use std::mem;
+
+use ahash::AHashMap;
+use sctk::data_device_manager::data_device::{DataDevice, DataDeviceHandler};
+use sctk::data_device_manager::data_offer::{DataOfferHandler, DragOffer};
+use sctk::data_device_manager::data_source::{CopyPasteSource, DataSourceHandler};
+use sctk::data_device_manager::{DataDeviceManagerState, ReadPipe, WritePipe};
+use sctk::primary_selection::device::{PrimarySelectionDevice, PrimarySelectionDeviceHandler};
+use sctk::primary_selection::selection::{PrimarySelectionSource, PrimarySelectionSourceHandler};
+use sctk::primary_selection::PrimarySelectionManagerState;
+use sctk::reexports::calloop::PostAction;
+use sctk::reexports::client::backend::ObjectId;
+use sctk::reexports::client::globals::{BindError, GlobalList};
+use sctk::reexports::client::protocol::wl_data_device::WlDataDevice;
+use sctk::reexports::client::protocol::wl_data_device_manager::DndAction;
+use sctk::reexports::client::protocol::wl_data_source::WlDataSource;
+use sctk::reexports::client::protocol::wl_seat::WlSeat;
+use sctk::reexports::client::protocol::wl_surface::WlSurface;
+use sctk::reexports::client::{Connection, Proxy, QueueHandle};
+use sctk::reexports::protocols::wp::primary_selection::zv1::client::zwp_primary_selection_device_v1::ZwpPrimarySelectionDeviceV1;
+use sctk::reexports::protocols::wp::primary_selection::zv1::client::zwp_primary_selection_source_v1::ZwpPrimarySelectionSourceV1;
+
+use crate::clipboard::{ClipboardData, ClipboardFormat, Selection};
+use crate::error::{NotSupportedError, RequestError};
+use crate::event_loop::AsyncRequestSerial;
+use crate::platform_impl::common::clipboard as mime;
+use crate::platform_impl::wayland::state::WinitState;
+
+pub struct ClipboardState {
+    manager: DataDeviceManagerState,
+
+    /// The data devices of the seats.
+    devices: AHashMap<ObjectId, DataDevice>,
+
+    /// The source offering the data copied by the application, while it owns the clipboard.
+    source: Option<(CopyPasteSource, Vec<ClipboardData>)>,
+
+    /// The primary selection manager, if the compositor supports it.
+    primary_manager: Option<PrimarySelectionManagerState>,
+
+    /// The primary selection devices of the seats.
+    primary_devices: AHashMap<ObjectId, PrimarySelectionDevice>,
+
+    /// The source offering the data selected in the application, while it owns the primary
+    /// selection.
+    primary_source: Option<(PrimarySelectionSource, Vec<ClipboardData>)>,
+
+    /// The reads which are done, to deliver to the application.
+    done: Vec<(AsyncRequestSerial, Option<ClipboardData>)>,
+}
+
+impl ClipboardState {
+    pub fn bind(
+        globals: &GlobalList,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> Result<Self, BindError> {
+        let manager = DataDeviceManagerState::bind(globals, queue_handle)?;
+        let primary_manager = PrimarySelectionManagerState::bind(globals, queue_handle).ok();
+        Ok(Self {
+            manager,
+            devices: Default::default(),
+            source: None,
+            primary_manager,
+            primary_devices: Default::default(),
+            primary_source: None,
+            done: Vec::new(),
+        })
+    }
+
+    pub fn add_seat(&mut self, seat: &WlSeat, queue_handle: &QueueHandle<WinitState>) {
+        self.devices.insert(seat.id(), self.manager.get_data_device(queue_handle, seat));
+        if let Some(primary_manager) = self.primary_manager.as_ref() {
+            let device = primary_manager.get_selection_device(queue_handle, seat);
+            self.primary_devices.insert(seat.id(), device);
+        }
+    }
+
+    pub fn remove_seat(&mut self, seat: &ObjectId) {
+        self.devices.remove(seat);
+        self.primary_devices.remove(seat);
+    }
+
+    /// The data the application put in the `selection`, while it owns it.
+    fn content(&self, selection: Selection) -> Option<&[ClipboardData]> {
+        match selection {
+            Selection::Clipboard => self.source.as_ref().map(|(_, content)| content.as_slice()),
+            Selection::Primary => {
+                self.primary_source.as_ref().map(|(_, content)| content.as_slice())
+            },
+        }
+    }
+
+    /// Take the reads which are done.
+    pub fn take_done(&mut self) -> Vec<(AsyncRequestSerial, Option<ClipboardData>)> {
+        mem::take(&mut self.done)
+    }
+}
+
+impl WinitState {
+    /// Offer `data` in the `selection` of the seat of the latest input.
+    pub fn set_clipboard(
+        &mut self,
+        queue_handle: &QueueHandle<WinitState>,
+        selection: Selection,
+        data: Vec<ClipboardData>,
+    ) -> Result<(), RequestError> {
+        let clipboard = self
+            .clipboard
+            .as_mut()
+            .ok_or(NotSupportedError::new("wl_data_device_manager is not available"))?;
+        let (seat, serial) = self.latest_input.clone().ok_or(RequestError::Ignored)?;
+        let mime_types = data.iter().flat_map(|data| mime::mime_types(data.format()));
+
+        match selection {
+            Selection::Clipboard => {
+                let device = clipboard.devices.get(&seat).ok_or(RequestError::Ignored)?;
+                let source = clipboard.manager.create_copy_paste_source(queue_handle, mime_types);
+                source.set_selection(device, serial);
+                clipboard.source = Some((source, data));
+            },
+            Selection::Primary => {
+                let primary_manager =
+                    clipboard.primary_manager.as_ref().ok_or(NotSupportedError::new(
+                        "zwp_primary_selection_device_manager_v1 is not available",
+                    ))?;
+                let device = clipboard.primary_devices.get(&seat).ok_or(RequestError::Ignored)?;
+                let source = primary_manager.create_selection_source(queue_handle, mime_types);
+                source.set_selection(device, serial);
+                clipboard.primary_source = Some((source, data));
+            },
+        }
+        Ok(())
+    }
+
+    /// Start reading the `format` in the `selection` of the seat of the latest input.
+    pub fn read_clipboard(
+        &mut self,
+        selection: Selection,
+        format: ClipboardFormat,
+    ) -> Result<AsyncRequestSerial, RequestError> {
+        let clipboard = self
+            .clipboard
+            .as_mut()
+            .ok_or(NotSupportedError::new("wl_data_device_manager is not available"))?;
+        if selection == Selection::Primary && clipboard.primary_manager.is_none() {
+            return Err(NotSupportedError::new(
+                "zwp_primary_selection_device_manager_v1 is not available",
+            )
+            .into());
+        }
+        let serial = AsyncRequestSerial::get();
+
+        if let Some(content) = clipboard.content(selection) {
+            let data = content.iter().find(|data| data.format() == format).cloned();
+            clipboard.done.push((serial, data));
+            return Ok(serial);
+        }
+
+        // Pick the preferred MIME type of the format among the offered ones.
+        let pick = |offered: &[String]| {
+            mime::mime_types(format)
+                .into_iter()
+                .find(|mime_type| offered.iter().any(|m| m == mime_type))
+        };
+        let seat = self.latest_input.as_ref().map(|(seat, _)| seat);
+        let pipe = match selection {
+            Selection::Clipboard => {
+                let offer = seat
+                    .and_then(|seat| clipboard.devices.get(seat))
+                    .or_else(|| clipboard.devices.values().next())
+                    .and_then(|device| device.data().selection_offer());
+                offer.and_then(|offer| {
+                    let mime_type = offer.with_mime_types(pick)?;
+                    Some(offer.receive(mime_type.to_owned()).map_err(|err| os_error!(err)))
+                })
+            },
+            Selection::Primary => {
+                let offer = seat
+                    .and_then(|seat| clipboard.primary_devices.get(seat))
+                    .or_else(|| clipboard.primary_devices.values().next())
+                    .and_then(|device| device.data().selection_offer());
+                offer.and_then(|offer| {
+                    let mime_type = offer.with_mime_types(pick)?;
+                    Some(offer.receive(mime_type.to_owned()).map_err(|err| os_error!(err)))
+                })
+            },
+        };
+        let Some(pipe) = pipe.transpose()? else {
+            clipboard.done.push((serial, None));
+            return Ok(serial);
+        };
+
+        self.receive_clipboard(pipe, serial, format)?;
+        Ok(serial)
+    }
+
+    /// Read the data sent by the owner of a selection into the reads which are done.
+    fn receive_clipboard(
+        &self,
+        pipe: ReadPipe,
+        serial: AsyncRequestSerial,
+        format: ClipboardFormat,
+    ) -> Result<(), RequestError> {
+        let mut content = Vec::new();
+        self.loop_handle
+            .insert_source(pipe, move |_, file, state| {
+                let mut buffer = [0; 4096];
+                let done = match (&**file).read(&mut buffer) {
+                    Ok(0) => Some(mime::decode(format, mem::take(&mut content))),
+                    Ok(len) => {
+                        content.extend_from_slice(&buffer[..len]);
+                        None
+                    },
+                    Err(err) if err.kind() == std::io::ErrorKind::Interrupted => None,
+                    Err(err) => {
+                        tracing::warn!("Failed to read the clipboard: {err}");
+                        Some(None)
+                    },
+                };
+
+                match done {
+                    Some(data) => {
+                        if let Some(clipboard) = state.clipboard.as_mut() {
+                            clipboard.done.push((serial, data));
+                        }
+                        state.dispatched_events = true;
+                        PostAction::Remove
+                    },
+                    None => PostAction::Continue,
+                }
+            })
+            .map_err(|err| os_error!(err))?;
+        Ok(())
+    }
+}
+
+/// Write the data of the `mime_type` to the application pasting it.
+fn send_content(content: &[ClipboardData], mime_type: &str, mut pipe: WritePipe) {
+    let bytes =
+        match content.iter().find(|data| mime::mime_types(data.format()).contains(&mime_type)) {
+            Some(data) => mime::encode(data),
+            None => return,
+        };
+
+    // Don't block the event loop on the application pasting the data.
+    std::thread::spawn(move || {
+        if let Err(err) = pipe.write_all(&bytes) {
+            tracing::warn!("Failed to write the clipboard: {err}");
+        }
+    });
+}
+
+impl DataDeviceHandler for WinitState {
+    fn enter(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &WlDataDevice,
+        _: f64,
+        _: f64,
+        _: &WlSurface,
+    ) {
+    }
+
+    fn leave(&mut self, _: &Connection, _: &QueueHandle<Self>, _: &WlDataDevice) {}
+
+    fn motion(&mut self, _: &Connection, _: &QueueHandle<Self>, _: &WlDataDevice, _: f64, _: f64) {}
+
+    fn selection(&mut self, _: &Connection, _: &QueueHandle<Self>, _: &WlDataDevice) {}
+
+    fn drop_performed(&mut self, _: &Connection, _: &QueueHandle<Self>, _: &WlDataDevice) {}
+}
+
+impl DataOfferHandler for WinitState {
+    fn source_actions(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &mut DragOffer,
+        _: DndAction,
+    ) {
+    }
+
+    fn selected_action(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &mut DragOffer,
+        _: DndAction,
+    ) {
+    }
+}
+
+impl DataSourceHandler for WinitState {
+    fn accept_mime(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &WlDataSource,
+        _: Option<String>,
+    ) {
+    }
+
+    fn send_request(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        source: &WlDataSource,
+        mime_type: String,
+        pipe: WritePipe,
+    ) {
+        match self.clipboard.as_ref().and_then(|clipboard| clipboard.source.as_ref()) {
+            Some((copy_paste, content)) if copy_paste.inner() == source => {
+                send_content(content, &mime_type, pipe)
+            },
+            _ => (),
+        }
+    }
+
+    fn cancelled(&mut self, _: &Connection, _: &QueueHandle<Self>, source: &WlDataSource) {
+        // Another client owns the clipboard now.
+        if let Some(clipboard) = self.clipboard.as_mut() {
+            if clipboard.source.as_ref().is_some_and(|(copy_paste, _)| copy_paste.inner() == source)
+            {
+                clipboard.source = None;
+            }
+        }
+    }
+
+    fn dnd_dropped(&mut self, _: &Connection, _: &QueueHandle<Self>, _: &WlDataSource) {}
+
+    fn dnd_finished(&mut self, _: &Connection, _: &QueueHandle<Self>, _: &WlDataSource) {}
+
+    fn action(&mut self, _: &Connection, _: &QueueHandle<Self>, _: &WlDataSource, _: DndAction) {}
+}
+
+impl PrimarySelectionDeviceHandler for WinitState {
+    fn selection(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &ZwpPrimarySelectionDeviceV1,
+    ) {
+    }
+}
+
+impl PrimarySelectionSourceHandler for WinitState {
+    fn send_request(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        source: &ZwpPrimarySelectionSourceV1,
+        mime_type: String,
+        pipe: WritePipe,
+    ) {
+        match self.clipboard.as_ref().and_then(|clipboard| clipboard.primary_source.as_ref()) {
+            Some((primary, content)) if primary.inner() == source => {
+                send_content(content, &mime_type, pipe)
+            },
+            _ => (),
+        }
+    }
+
+    fn cancelled(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        source: &ZwpPrimarySelectionSourceV1,
+    ) {
+        // Another client owns the primary selection now.
+        if let Some(clipboard) = self.clipboard.as_mut() {
+            if clipboard
+                .primary_source
+                .as_ref()
+                .is_some_and(|(primary, _)| primary.inner() == source)
+            {
+                clipboard.primary_source = None;
+            }
+        }
+    }
+}
+
+sctk::delegate_data_device!(WinitState);
+sctk::delegate_primary_selection!(WinitState);
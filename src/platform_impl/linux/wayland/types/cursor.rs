@@ -7,7 +7,8 @@ use crate::cursor::CursorImage;
 #[derive(Debug)]
 pub enum SelectedCursor {
     Named(CursorIcon),
-    Custom(CustomCursor),
+    /// The cursor for each scale of the custom cursor, in increasing size.
+    Custom(Vec<CustomCursor>),
 }
 
 impl Default for SelectedCursor {
@@ -0,0 +1,120 @@
+//! Handling of the wlr data control, which reports the changes of the clipboard made while the
+//! application isn't focused.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use ahash::AHashMap;
+use sctk::globals::GlobalData;
+use sctk::reexports::client::backend::ObjectId;
+use sctk::reexports::client::globals::{BindError, GlobalList};
+use sctk::reexports::client::protocol::wl_seat::WlSeat;
+use sctk::reexports::client::{
+    delegate_dispatch, event_created_child, Connection, Dispatch, Proxy, QueueHandle,
+};
+use sctk::reexports::protocols_wlr::data_control::v1::client::zwlr_data_control_device_v1::{
+    self, ZwlrDataControlDeviceV1,
+};
+use sctk::reexports::protocols_wlr::data_control::v1::client::zwlr_data_control_manager_v1::ZwlrDataControlManagerV1;
+use sctk::reexports::protocols_wlr::data_control::v1::client::zwlr_data_control_offer_v1::ZwlrDataControlOfferV1;
+
+use crate::platform_impl::wayland::state::WinitState;
+
+/// The wlr data control manager.
+#[derive(Debug)]
+pub struct DataControlManager {
+    manager: ZwlrDataControlManagerV1,
+
+    /// The data control devices of the seats.
+    devices: AHashMap<ObjectId, ZwlrDataControlDeviceV1>,
+}
+
+impl DataControlManager {
+    pub fn new(
+        globals: &GlobalList,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> Result<Self, BindError> {
+        let manager = globals.bind(queue_handle, 1..=1, GlobalData)?;
+        Ok(Self { manager, devices: Default::default() })
+    }
+
+    pub fn add_seat(&mut self, seat: &WlSeat, queue_handle: &QueueHandle<WinitState>) {
+        let device =
+            self.manager.get_data_device(seat, queue_handle, DataControlDeviceData::default());
+        self.devices.insert(seat.id(), device);
+    }
+
+    pub fn remove_seat(&mut self, seat: &ObjectId) {
+        if let Some(device) = self.devices.remove(seat) {
+            device.destroy();
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct DataControlDeviceData {
+    /// Whether the current selection was announced, after which the selections are changes.
+    initialized: AtomicBool,
+}
+
+impl Dispatch<ZwlrDataControlManagerV1, GlobalData, WinitState> for DataControlManager {
+    fn event(
+        _: &mut WinitState,
+        _: &ZwlrDataControlManagerV1,
+        _: <ZwlrDataControlManagerV1 as Proxy>::Event,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        unreachable!("no events defined for zwlr_data_control_manager_v1");
+    }
+}
+
+impl Dispatch<ZwlrDataControlDeviceV1, DataControlDeviceData, WinitState> for DataControlManager {
+    fn event(
+        state: &mut WinitState,
+        device: &ZwlrDataControlDeviceV1,
+        event: <ZwlrDataControlDeviceV1 as Proxy>::Event,
+        data: &DataControlDeviceData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        match event {
+            zwlr_data_control_device_v1::Event::Selection { id } => {
+                // The content is read through the data device, not the data control.
+                if let Some(offer) = id {
+                    offer.destroy();
+                }
+                if data.initialized.swap(true, Ordering::Relaxed) {
+                    state.clipboard_changed = true;
+                }
+            },
+            zwlr_data_control_device_v1::Event::Finished => {
+                device.destroy();
+                if let Some(data_control) = state.data_control.as_mut() {
+                    data_control.devices.retain(|_, d| d != device);
+                }
+            },
+            _ => (),
+        }
+    }
+
+    event_created_child!(WinitState, ZwlrDataControlDeviceV1, [
+        zwlr_data_control_device_v1::EVT_DATA_OFFER_OPCODE => (ZwlrDataControlOfferV1, ()),
+    ]);
+}
+
+impl Dispatch<ZwlrDataControlOfferV1, (), WinitState> for DataControlManager {
+    fn event(
+        _: &mut WinitState,
+        _: &ZwlrDataControlOfferV1,
+        _: <ZwlrDataControlOfferV1 as Proxy>::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+    }
+}
+
+delegate_dispatch!(WinitState: [ZwlrDataControlManagerV1: GlobalData] => DataControlManager);
+delegate_dispatch!(WinitState: [ZwlrDataControlDeviceV1: DataControlDeviceData] => DataControlManager);
+delegate_dispatch!(WinitState: [ZwlrDataControlOfferV1: ()] => DataControlManager);
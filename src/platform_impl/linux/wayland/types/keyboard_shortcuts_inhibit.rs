@@ -0,0 +1,70 @@
+//! Handling of the keyboard-shortcuts-inhibit protocol.
+
+use sctk::globals::GlobalData;
+use sctk::reexports::client::globals::{BindError, GlobalList};
+use sctk::reexports::client::protocol::wl_seat::WlSeat;
+use sctk::reexports::client::protocol::wl_surface::WlSurface;
+use sctk::reexports::client::{delegate_dispatch, Connection, Dispatch, Proxy, QueueHandle};
+use sctk::reexports::protocols::wp::keyboard_shortcuts_inhibit::zv1::client::zwp_keyboard_shortcuts_inhibit_manager_v1::ZwpKeyboardShortcutsInhibitManagerV1;
+use sctk::reexports::protocols::wp::keyboard_shortcuts_inhibit::zv1::client::zwp_keyboard_shortcuts_inhibitor_v1::ZwpKeyboardShortcutsInhibitorV1;
+
+use crate::platform_impl::wayland::state::WinitState;
+
+/// Keyboard shortcuts inhibit manager.
+#[derive(Debug, Clone)]
+pub struct KeyboardShortcutsInhibitManager {
+    manager: ZwpKeyboardShortcutsInhibitManagerV1,
+}
+
+impl KeyboardShortcutsInhibitManager {
+    pub fn new(
+        globals: &GlobalList,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> Result<Self, BindError> {
+        let manager = globals.bind(queue_handle, 1..=1, GlobalData)?;
+        Ok(Self { manager })
+    }
+
+    /// Inhibit the shortcuts of the compositor while the seat focuses the surface.
+    pub fn inhibit(
+        &self,
+        surface: &WlSurface,
+        seat: &WlSeat,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> ZwpKeyboardShortcutsInhibitorV1 {
+        self.manager.inhibit_shortcuts(surface, seat, queue_handle, GlobalData)
+    }
+}
+
+impl Dispatch<ZwpKeyboardShortcutsInhibitManagerV1, GlobalData, WinitState>
+    for KeyboardShortcutsInhibitManager
+{
+    fn event(
+        _: &mut WinitState,
+        _: &ZwpKeyboardShortcutsInhibitManagerV1,
+        _: <ZwpKeyboardShortcutsInhibitManagerV1 as Proxy>::Event,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        unreachable!("no events defined for zwp_keyboard_shortcuts_inhibit_manager_v1");
+    }
+}
+
+impl Dispatch<ZwpKeyboardShortcutsInhibitorV1, GlobalData, WinitState>
+    for KeyboardShortcutsInhibitManager
+{
+    fn event(
+        _: &mut WinitState,
+        _: &ZwpKeyboardShortcutsInhibitorV1,
+        _: <ZwpKeyboardShortcutsInhibitorV1 as Proxy>::Event,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        // Whether the compositor honors the inhibitor isn't reported to the user.
+    }
+}
+
+delegate_dispatch!(WinitState: [ZwpKeyboardShortcutsInhibitManagerV1: GlobalData] => KeyboardShortcutsInhibitManager);
+delegate_dispatch!(WinitState: [ZwpKeyboardShortcutsInhibitorV1: GlobalData] => KeyboardShortcutsInhibitManager);
@@ -1,7 +1,11 @@
 //! Wayland protocol implementation boilerplate.
 
+pub mod clipboard;
 pub mod cursor;
+pub mod data_control;
+pub mod keyboard_shortcuts_inhibit;
 pub mod kwin_blur;
+pub mod wp_color_management;
 pub mod wp_fractional_scaling;
 pub mod wp_viewporter;
 pub mod xdg_activation;
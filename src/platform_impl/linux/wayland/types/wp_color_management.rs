@@ -0,0 +1,362 @@
+//! Handling of the wp-color-management.
+
+use sctk::globals::GlobalData;
+use sctk::reexports::client::globals::{BindError, GlobalList};
+use std::sync::Mutex;
+
+use sctk::reexports::client::backend::ObjectId;
+use sctk::reexports::client::protocol::wl_output::WlOutput;
+use sctk::reexports::client::protocol::wl_surface::WlSurface;
+use sctk::reexports::client::{delegate_dispatch, Connection, Dispatch, Proxy, QueueHandle, WEnum};
+use sctk::reexports::protocols::wp::color_management::v1::client::wp_color_management_output_v1::{
+    Event as OutputEvent, WpColorManagementOutputV1,
+};
+use sctk::reexports::protocols::wp::color_management::v1::client::wp_color_management_surface_v1::WpColorManagementSurfaceV1;
+use sctk::reexports::protocols::wp::color_management::v1::client::wp_color_manager_v1::{
+    Event as ManagerEvent, Feature, Primaries, RenderIntent, TransferFunction, WpColorManagerV1,
+};
+use sctk::reexports::protocols::wp::color_management::v1::client::wp_image_description_creator_params_v1::WpImageDescriptionCreatorParamsV1;
+use sctk::reexports::protocols::wp::color_management::v1::client::wp_image_description_info_v1::{
+    Event as InfoEvent, WpImageDescriptionInfoV1,
+};
+use sctk::reexports::protocols::wp::color_management::v1::client::wp_image_description_v1::{
+    Event as ImageDescriptionEvent, WpImageDescriptionV1,
+};
+use tracing::warn;
+
+use crate::monitor::ColorCapabilities;
+use crate::platform_impl::wayland::output::MonitorHandle;
+use crate::platform_impl::wayland::state::WinitState;
+use crate::window::{SurfaceColorSpace, SurfaceFormatHint};
+
+/// The color capabilities of the outputs, read by their monitor handles.
+static OUTPUT_COLOR_CAPABILITIES: Mutex<Vec<(ObjectId, ColorCapabilities)>> =
+    Mutex::new(Vec::new());
+
+/// The color capabilities of the output, once the compositor described it.
+pub fn output_color_capabilities(output: &WlOutput) -> Option<ColorCapabilities> {
+    let capabilities = OUTPUT_COLOR_CAPABILITIES.lock().unwrap();
+    capabilities.iter().find(|(id, _)| *id == output.id()).map(|(_, capabilities)| *capabilities)
+}
+
+/// Color manager.
+#[derive(Debug)]
+pub struct ColorManager {
+    manager: WpColorManagerV1,
+    features: Vec<Feature>,
+    transfer_functions: Vec<TransferFunction>,
+    primaries: Vec<Primaries>,
+    render_intents: Vec<RenderIntent>,
+    outputs: Vec<(WlOutput, WpColorManagementOutputV1)>,
+}
+
+impl ColorManager {
+    pub fn new(
+        globals: &GlobalList,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> Result<Self, BindError> {
+        let manager = globals.bind(queue_handle, 1..=1, GlobalData)?;
+        Ok(Self {
+            manager,
+            features: Vec::new(),
+            transfer_functions: Vec::new(),
+            primaries: Vec::new(),
+            render_intents: Vec::new(),
+            outputs: Vec::new(),
+        })
+    }
+
+    /// Follow the image description of the output, which tells its color capabilities.
+    pub fn add_output(&mut self, output: &WlOutput, queue_handle: &QueueHandle<WinitState>) {
+        let color_output = self.manager.get_output(output, queue_handle, output.clone());
+        color_output.get_image_description(queue_handle, output.clone());
+        self.outputs.push((output.clone(), color_output));
+    }
+
+    pub fn remove_output(&mut self, output: &WlOutput) {
+        if let Some(pos) = self.outputs.iter().position(|(known, _)| known == output) {
+            let (_, color_output) = self.outputs.remove(pos);
+            color_output.destroy();
+        }
+        OUTPUT_COLOR_CAPABILITIES.lock().unwrap().retain(|(id, _)| *id != output.id());
+    }
+
+    /// Set the image description matching the format hint on the surface.
+    ///
+    /// The description is applied once the compositor made it ready, and takes effect with the
+    /// next commit of the surface.
+    pub fn set_format_hint(
+        &self,
+        surface: &WlSurface,
+        hint: SurfaceFormatHint,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> Option<WpColorManagementSurfaceV1> {
+        let primaries = match hint.color_space {
+            SurfaceColorSpace::Srgb => Primaries::Srgb,
+            SurfaceColorSpace::DisplayP3 => Primaries::DisplayP3,
+            SurfaceColorSpace::Bt2020 => Primaries::Bt2020,
+        };
+        let transfer_function = if hint.hdr {
+            TransferFunction::ExtLinear
+        } else if self.transfer_functions.contains(&TransferFunction::Srgb) {
+            TransferFunction::Srgb
+        } else {
+            TransferFunction::Gamma22
+        };
+
+        if !self.features.contains(&Feature::Parametric)
+            || !self.render_intents.contains(&RenderIntent::Perceptual)
+            || !self.primaries.contains(&primaries)
+            || !self.transfer_functions.contains(&transfer_function)
+        {
+            warn!("The compositor doesn't support the surface format {hint:?}");
+            return None;
+        }
+
+        let color_surface = self.manager.get_surface(surface, queue_handle, GlobalData);
+        let creator = self.manager.create_parametric_creator(queue_handle, GlobalData);
+        creator.set_primaries_named(primaries);
+        creator.set_tf_named(transfer_function);
+        creator.create(queue_handle, color_surface.clone());
+
+        Some(color_surface)
+    }
+}
+
+impl Dispatch<WpColorManagerV1, GlobalData, WinitState> for ColorManager {
+    fn event(
+        state: &mut WinitState,
+        _: &WpColorManagerV1,
+        event: ManagerEvent,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        let Some(color_manager) = state.color_manager.as_mut() else { return };
+        match event {
+            ManagerEvent::SupportedFeature { feature: WEnum::Value(feature) } => {
+                color_manager.features.push(feature);
+            },
+            ManagerEvent::SupportedTfNamed { tf: WEnum::Value(tf) } => {
+                color_manager.transfer_functions.push(tf);
+            },
+            ManagerEvent::SupportedPrimariesNamed { primaries: WEnum::Value(primaries) } => {
+                color_manager.primaries.push(primaries);
+            },
+            ManagerEvent::SupportedIntent { render_intent: WEnum::Value(render_intent) } => {
+                color_manager.render_intents.push(render_intent);
+            },
+            _ => (),
+        }
+    }
+}
+
+impl Dispatch<WpColorManagementSurfaceV1, GlobalData, WinitState> for ColorManager {
+    fn event(
+        _: &mut WinitState,
+        _: &WpColorManagementSurfaceV1,
+        _: <WpColorManagementSurfaceV1 as Proxy>::Event,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        // No events.
+    }
+}
+
+impl Dispatch<WpImageDescriptionCreatorParamsV1, GlobalData, WinitState> for ColorManager {
+    fn event(
+        _: &mut WinitState,
+        _: &WpImageDescriptionCreatorParamsV1,
+        _: <WpImageDescriptionCreatorParamsV1 as Proxy>::Event,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        // No events.
+    }
+}
+
+impl Dispatch<WpImageDescriptionV1, WpColorManagementSurfaceV1, WinitState> for ColorManager {
+    fn event(
+        _: &mut WinitState,
+        image_description: &WpImageDescriptionV1,
+        event: ImageDescriptionEvent,
+        color_surface: &WpColorManagementSurfaceV1,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        match event {
+            ImageDescriptionEvent::Ready { .. } => {
+                // The surface is inert once the window is gone.
+                if color_surface.is_alive() {
+                    color_surface
+                        .set_image_description(image_description, RenderIntent::Perceptual);
+                }
+            },
+            ImageDescriptionEvent::Failed { msg, .. } => {
+                warn!("Failed to create the image description of the surface: {msg}");
+            },
+            _ => return,
+        }
+
+        image_description.destroy();
+    }
+}
+
+impl Dispatch<WpColorManagementOutputV1, WlOutput, WinitState> for ColorManager {
+    fn event(
+        _: &mut WinitState,
+        color_output: &WpColorManagementOutputV1,
+        event: OutputEvent,
+        output: &WlOutput,
+        _: &Connection,
+        queue_handle: &QueueHandle<WinitState>,
+    ) {
+        if let OutputEvent::ImageDescriptionChanged = event {
+            color_output.get_image_description(queue_handle, output.clone());
+        }
+    }
+}
+
+impl Dispatch<WpImageDescriptionV1, WlOutput, WinitState> for ColorManager {
+    fn event(
+        _: &mut WinitState,
+        image_description: &WpImageDescriptionV1,
+        event: ImageDescriptionEvent,
+        output: &WlOutput,
+        _: &Connection,
+        queue_handle: &QueueHandle<WinitState>,
+    ) {
+        match event {
+            ImageDescriptionEvent::Ready { .. } => {
+                let info = OutputColorInfo { output: output.clone(), info: Default::default() };
+                image_description.get_information(queue_handle, info);
+            },
+            ImageDescriptionEvent::Failed { msg, .. } => {
+                warn!("Failed to get the image description of the output: {msg}");
+            },
+            _ => return,
+        }
+
+        image_description.destroy();
+    }
+}
+
+/// The information about the image description of an output, gathered until it's done.
+#[derive(Debug)]
+pub struct OutputColorInfo {
+    output: WlOutput,
+    info: Mutex<ColorInfo>,
+}
+
+#[derive(Debug, Default)]
+struct ColorInfo {
+    transfer_function: Option<TransferFunction>,
+    primaries: Option<Chromaticities>,
+    target_primaries: Option<Chromaticities>,
+    luminances: Option<(f32, f32, f32)>,
+    target_luminance: Option<(f32, f32)>,
+}
+
+impl ColorInfo {
+    fn color_capabilities(&self) -> ColorCapabilities {
+        let hdr = matches!(
+            self.transfer_function,
+            Some(TransferFunction::St2084Pq | TransferFunction::Hlg)
+        );
+        let primaries = self.target_primaries.or(self.primaries);
+        let sdr_white_luminance = self.luminances.map(|(_, _, reference)| reference);
+        let (min_luminance, max_luminance) =
+            self.target_luminance.or(self.luminances.map(|(min, max, _)| (min, max))).unzip();
+
+        ColorCapabilities {
+            hdr,
+            headroom: max_luminance
+                .zip(sdr_white_luminance)
+                .filter(|&(_, white)| white > 0.0)
+                .map(|(max, white)| (max / white).max(1.0)),
+            primaries: primaries.map(|(primaries, _)| primaries),
+            white_point: primaries.map(|(_, white_point)| white_point),
+            min_luminance,
+            max_luminance,
+            max_full_frame_luminance: None,
+            sdr_white_luminance,
+        }
+    }
+}
+
+/// The chromaticity coordinates of the red, green and blue primaries, and of the white point.
+type Chromaticities = ([(f32, f32); 3], (f32, f32));
+
+/// The chromaticity coordinates, sent multiplied by a million.
+#[allow(clippy::too_many_arguments)]
+fn chromaticities(
+    r_x: i32,
+    r_y: i32,
+    g_x: i32,
+    g_y: i32,
+    b_x: i32,
+    b_y: i32,
+    w_x: i32,
+    w_y: i32,
+) -> Chromaticities {
+    let point = |x: i32, y: i32| (x as f32 / 1_000_000.0, y as f32 / 1_000_000.0);
+    ([point(r_x, r_y), point(g_x, g_y), point(b_x, b_y)], point(w_x, w_y))
+}
+
+impl Dispatch<WpImageDescriptionInfoV1, OutputColorInfo, WinitState> for ColorManager {
+    fn event(
+        state: &mut WinitState,
+        _: &WpImageDescriptionInfoV1,
+        event: InfoEvent,
+        data: &OutputColorInfo,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        let mut info = data.info.lock().unwrap();
+        match event {
+            InfoEvent::TfNamed { tf: WEnum::Value(tf) } => info.transfer_function = Some(tf),
+            InfoEvent::Primaries { r_x, r_y, g_x, g_y, b_x, b_y, w_x, w_y } => {
+                info.primaries = Some(chromaticities(r_x, r_y, g_x, g_y, b_x, b_y, w_x, w_y));
+            },
+            InfoEvent::TargetPrimaries { r_x, r_y, g_x, g_y, b_x, b_y, w_x, w_y } => {
+                info.target_primaries =
+                    Some(chromaticities(r_x, r_y, g_x, g_y, b_x, b_y, w_x, w_y));
+            },
+            // The minimum luminances are sent multiplied by 10000.
+            InfoEvent::Luminances { min_lum, max_lum, reference_lum } => {
+                info.luminances =
+                    Some((min_lum as f32 / 10000.0, max_lum as f32, reference_lum as f32));
+            },
+            InfoEvent::TargetLuminance { min_lum, max_lum } => {
+                info.target_luminance = Some((min_lum as f32 / 10000.0, max_lum as f32));
+            },
+            InfoEvent::Done => {
+                let capabilities = info.color_capabilities();
+                let mut outputs = OUTPUT_COLOR_CAPABILITIES.lock().unwrap();
+                let id = data.output.id();
+                match outputs.iter_mut().find(|(known, _)| *known == id) {
+                    // The first description isn't a change.
+                    None => outputs.push((id, capabilities)),
+                    Some((_, known)) if *known != capabilities => {
+                        *known = capabilities;
+                        state
+                            .color_capabilities_changes
+                            .push(MonitorHandle::new(data.output.clone()));
+                    },
+                    Some(_) => (),
+                }
+            },
+            _ => (),
+        }
+    }
+}
+
+delegate_dispatch!(WinitState: [WpColorManagerV1: GlobalData] => ColorManager);
+delegate_dispatch!(WinitState: [WpColorManagementOutputV1: WlOutput] => ColorManager);
+delegate_dispatch!(WinitState: [WpImageDescriptionV1: WlOutput] => ColorManager);
+delegate_dispatch!(WinitState: [WpImageDescriptionInfoV1: OutputColorInfo] => ColorManager);
+delegate_dispatch!(WinitState: [WpColorManagementSurfaceV1: GlobalData] => ColorManager);
+delegate_dispatch!(WinitState: [WpImageDescriptionCreatorParamsV1: GlobalData] => ColorManager);
+delegate_dispatch!(WinitState: [WpImageDescriptionV1: WpColorManagementSurfaceV1] => ColorManager);
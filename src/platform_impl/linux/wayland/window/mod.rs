@@ -2,6 +2,7 @@
 
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::Instant;
 
 use sctk::compositor::{CompositorState, Region, SurfaceData};
 use sctk::reexports::client::protocol::wl_display::WlDisplay;
@@ -19,14 +20,16 @@ use super::types::xdg_activation::XdgActivationTokenData;
 use super::ActiveEventLoop;
 use crate::dpi::{LogicalSize, PhysicalPosition, PhysicalSize, Position, Size};
 use crate::error::{NotSupportedError, RequestError};
-use crate::event::{Ime, WindowEvent};
+use crate::event::{DeviceId, Ime, WindowEvent};
 use crate::event_loop::AsyncRequestSerial;
-use crate::monitor::MonitorHandle as CoreMonitorHandle;
+use crate::monitor::{GammaRamp, HdrMetadata, MonitorHandle as CoreMonitorHandle};
+#[cfg(feature = "accesskit")]
+use crate::platform_impl::common::accessibility::Adapter as AccessKitAdapter;
 use crate::platform_impl::{Fullscreen, MonitorHandle as PlatformMonitorHandle};
 use crate::window::{
-    Cursor, CursorGrabMode, Fullscreen as CoreFullscreen, ImePurpose, ResizeDirection, Theme,
-    UserAttentionType, Window as CoreWindow, WindowAttributes, WindowButtons, WindowId,
-    WindowLevel,
+    Coalescing, Cursor, CursorGrabMode, CursorGrabPolicy, Fullscreen as CoreFullscreen, ImePurpose,
+    Politeness, ResizeDirection, Theme, UserAttentionType, Window as CoreWindow, WindowAttributes,
+    WindowButtons, WindowId, WindowLevel,
 };
 
 pub(crate) mod state;
@@ -112,10 +115,17 @@ impl Window {
         );
 
         // Set transparency hint.
-        window_state.set_transparent(attributes.transparent);
+        let format_hint = attributes.surface_format_hint;
+        window_state
+            .set_transparent(attributes.transparent || format_hint.is_some_and(|hint| hint.alpha));
+        if let Some(hint) = format_hint {
+            window_state.set_surface_format_hint(state.color_manager.as_ref(), hint);
+        }
 
         window_state.set_blur(attributes.blur);
 
+        window_state.set_motion_coalescing(attributes.motion_coalescing);
+
         // Set the decorations hint.
         window_state.set_decorate(attributes.decorations);
 
@@ -178,6 +188,7 @@ impl Window {
 
         let window_requests = WindowRequests {
             redraw_requested: AtomicBool::new(true),
+            redraw_at: Mutex::new(None),
             closed: AtomicBool::new(false),
         };
         let window_requests = Arc::new(window_requests);
@@ -292,6 +303,13 @@ impl CoreWindow for Window {
         }
     }
 
+    fn request_redraw_at(&self, instant: Instant) {
+        let mut redraw_at = self.window_requests.redraw_at.lock().unwrap();
+        *redraw_at = Some(redraw_at.map_or(instant, |deadline| deadline.min(instant)));
+        // Wake up the loop, so it waits for the new deadline.
+        self.event_loop_awakener.ping();
+    }
+
     #[inline]
     fn title(&self) -> String {
         self.window_state.lock().unwrap().title().to_owned()
@@ -472,6 +490,18 @@ impl CoreWindow for Window {
         }
     }
 
+    fn set_fullscreen_hdr(&self, _hdr: Option<HdrMetadata>) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_fullscreen_hdr is not supported").into())
+    }
+
+    fn fullscreen_hdr(&self) -> Option<HdrMetadata> {
+        None
+    }
+
+    fn set_gamma_ramp(&self, _ramp: Option<&GammaRamp>) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_gamma_ramp is not supported").into())
+    }
+
     #[inline]
     fn scale_factor(&self) -> f64 {
         self.window_state.lock().unwrap().scale_factor()
@@ -507,28 +537,58 @@ impl CoreWindow for Window {
         }
     }
 
+    fn set_ime_exclusion_area(&self, _area: Option<(Position, Size)>) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_ime_exclusion_area is not supported").into())
+    }
+
+    fn set_ime_candidates_drawn_by_app(&self, _drawn_by_app: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_ime_candidates_drawn_by_app is not supported").into())
+    }
+
     #[inline]
     fn set_ime_allowed(&self, allowed: bool) {
         let mut window_state = self.window_state.lock().unwrap();
 
         if window_state.ime_allowed() != allowed && window_state.set_ime_allowed(allowed) {
             let event = WindowEvent::Ime(if allowed { Ime::Enabled } else { Ime::Disabled });
-            self.window_events_sink.lock().unwrap().push_window_event(event, self.window_id);
+            let mut window_events_sink = self.window_events_sink.lock().unwrap();
+            window_events_sink.push_window_event(event, self.window_id);
+            if allowed {
+                let event = WindowEvent::Ime(Ime::SurroundingTextRequest);
+                window_events_sink.push_window_event(event, self.window_id);
+            }
             self.event_loop_awakener.ping();
         }
     }
 
+    fn set_virtual_keyboard_visible(&self, visible: bool) -> Result<(), RequestError> {
+        self.window_state.lock().unwrap().set_virtual_keyboard_visible(visible);
+        Ok(())
+    }
+
     #[inline]
     fn set_ime_purpose(&self, purpose: ImePurpose) {
         self.window_state.lock().unwrap().set_ime_purpose(purpose);
     }
 
+    #[inline]
+    fn set_ime_surrounding_text(&self, text: &str, cursor: usize, anchor: usize) {
+        let window_state = self.window_state.lock().unwrap();
+        if window_state.ime_allowed() {
+            window_state.set_ime_surrounding_text(text, cursor, anchor);
+        }
+    }
+
     fn focus_window(&self) {}
 
     fn has_focus(&self) -> bool {
         self.window_state.lock().unwrap().has_focus()
     }
 
+    fn set_keyboard_capture(&self, capture: bool) -> Result<(), RequestError> {
+        self.window_state.lock().unwrap().set_keyboard_capture(capture)
+    }
+
     fn request_user_attention(&self, request_type: Option<UserAttentionType>) {
         let xdg_activation = match self.xdg_activation.as_ref() {
             Some(xdg_activation) => xdg_activation,
@@ -555,6 +615,32 @@ impl CoreWindow for Window {
         xdg_activation_token.commit();
     }
 
+    fn announce(&self, _message: &str, _politeness: Politeness) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("announce is not supported").into())
+    }
+
+    #[cfg(feature = "accesskit")]
+    fn set_accesskit_tree_source(
+        &self,
+        source: Box<dyn accesskit::ActivationHandler + Send>,
+    ) -> Result<(), RequestError> {
+        let window_id = self.window_id;
+        let sink = self.window_events_sink.clone();
+        let awakener = self.event_loop_awakener.clone();
+        let adapter = AccessKitAdapter::new(source, move |request| {
+            let event = WindowEvent::AccessKitActionRequested(request);
+            sink.lock().unwrap().push_window_event(event, window_id);
+            awakener.ping();
+        });
+        self.window_state.lock().unwrap().set_accesskit_adapter(adapter);
+        Ok(())
+    }
+
+    #[cfg(feature = "accesskit")]
+    fn update_accesskit_tree(&self, update: accesskit::TreeUpdate) {
+        self.window_state.lock().unwrap().update_accesskit_tree(update);
+    }
+
     fn set_theme(&self, theme: Option<Theme>) {
         self.window_state.lock().unwrap().set_theme(theme)
     }
@@ -589,10 +675,22 @@ impl CoreWindow for Window {
         self.window_state.lock().unwrap().set_cursor_grab(mode)
     }
 
+    fn set_cursor_grab_policy(&self, policy: CursorGrabPolicy) {
+        self.window_state.lock().unwrap().set_cursor_grab_policy(policy);
+    }
+
     fn set_cursor_visible(&self, visible: bool) {
         self.window_state.lock().unwrap().set_cursor_visible(visible);
     }
 
+    fn set_cursor_for_device(&self, _: DeviceId, _: Cursor) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_cursor_for_device is not supported").into())
+    }
+
+    fn set_cursor_visible_for_device(&self, _: DeviceId, _: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_cursor_visible_for_device is not supported").into())
+    }
+
     fn drag_window(&self) -> Result<(), RequestError> {
         self.window_state.lock().unwrap().drag_window()
     }
@@ -621,6 +719,11 @@ impl CoreWindow for Window {
         }
     }
 
+    fn set_motion_coalescing(&self, coalescing: Coalescing) -> Result<(), RequestError> {
+        self.window_state.lock().unwrap().set_motion_coalescing(coalescing);
+        Ok(())
+    }
+
     fn current_monitor(&self) -> Option<CoreMonitorHandle> {
         let data = self.window.wl_surface().data::<SurfaceData>()?;
         data.outputs()
@@ -647,6 +750,13 @@ impl CoreWindow for Window {
         None
     }
 
+    #[cfg(feature = "winit-test-harness")]
+    fn inject_event(&self, event: WindowEvent) -> Result<(), RequestError> {
+        self.window_events_sink.lock().unwrap().push_window_event(event, self.window_id);
+        self.event_loop_awakener.ping();
+        Ok(())
+    }
+
     /// Get the raw-window-handle v0.6 display handle.
     #[cfg(feature = "rwh_06")]
     fn rwh_06_display_handle(&self) -> &dyn rwh_06::HasDisplayHandle {
@@ -668,6 +778,9 @@ pub struct WindowRequests {
 
     /// Redraw Requested.
     pub redraw_requested: AtomicBool,
+
+    /// The time of the scheduled redraw.
+    pub redraw_at: Mutex<Option<Instant>>,
 }
 
 impl WindowRequests {
@@ -678,6 +791,16 @@ impl WindowRequests {
     pub fn take_redraw_requested(&self) -> bool {
         self.redraw_requested.swap(false, Ordering::Relaxed)
     }
+
+    /// Whether the scheduled redraw is due at `now`, clearing it if it is.
+    pub fn take_scheduled_redraw(&self, now: Instant) -> bool {
+        let mut redraw_at = self.redraw_at.lock().unwrap();
+        let due = redraw_at.is_some_and(|deadline| deadline <= now);
+        if due {
+            *redraw_at = None;
+        }
+        due
+    }
 }
 
 impl TryFrom<&str> for Theme {
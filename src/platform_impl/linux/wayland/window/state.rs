@@ -4,7 +4,7 @@ use std::num::NonZeroU32;
 use std::sync::{Arc, Mutex, Weak};
 use std::time::Duration;
 
-use ahash::HashSet;
+use ahash::HashMap;
 use sctk::compositor::{CompositorState, Region, SurfaceData, SurfaceDataExt};
 use sctk::reexports::client::backend::ObjectId;
 use sctk::reexports::client::protocol::wl_seat::WlSeat;
@@ -14,7 +14,9 @@ use sctk::reexports::client::{Connection, Proxy, QueueHandle};
 use sctk::reexports::csd_frame::{
     DecorationsFrame, FrameAction, FrameClick, ResizeEdge, WindowState as XdgWindowState,
 };
+use sctk::reexports::protocols::wp::color_management::v1::client::wp_color_management_surface_v1::WpColorManagementSurfaceV1;
 use sctk::reexports::protocols::wp::fractional_scale::v1::client::wp_fractional_scale_v1::WpFractionalScaleV1;
+use sctk::reexports::protocols::wp::keyboard_shortcuts_inhibit::zv1::client::zwp_keyboard_shortcuts_inhibitor_v1::ZwpKeyboardShortcutsInhibitorV1;
 use sctk::reexports::protocols::wp::text_input::zv3::client::zwp_text_input_v3::ZwpTextInputV3;
 use sctk::reexports::protocols::wp::viewporter::client::wp_viewport::WpViewport;
 use sctk::reexports::protocols::xdg::shell::client::xdg_toplevel::ResizeEdge as XdgResizeEdge;
@@ -28,18 +30,27 @@ use sctk::subcompositor::SubcompositorState;
 use tracing::{info, warn};
 use wayland_protocols_plasma::blur::client::org_kde_kwin_blur::OrgKdeKwinBlur;
 
-use crate::cursor::CustomCursor as RootCustomCursor;
+use crate::cursor::{closest_to_scale_factor, CustomCursor as RootCustomCursor};
+#[cfg(feature = "accesskit")]
+use crate::dpi::PhysicalPosition;
 use crate::dpi::{LogicalPosition, LogicalSize, PhysicalSize, Size};
 use crate::error::{NotSupportedError, RequestError};
+#[cfg(feature = "accesskit")]
+use crate::platform_impl::common::accessibility::Adapter as AccessKitAdapter;
 use crate::platform_impl::wayland::logical_to_physical_rounded;
 use crate::platform_impl::wayland::seat::{
     PointerConstraintsState, WinitPointerData, WinitPointerDataExt, ZwpTextInputV3Ext,
 };
 use crate::platform_impl::wayland::state::{WindowCompositorUpdate, WinitState};
 use crate::platform_impl::wayland::types::cursor::{CustomCursor, SelectedCursor};
+use crate::platform_impl::wayland::types::keyboard_shortcuts_inhibit::KeyboardShortcutsInhibitManager;
 use crate::platform_impl::wayland::types::kwin_blur::KWinBlurManager;
+use crate::platform_impl::wayland::types::wp_color_management::ColorManager;
 use crate::platform_impl::PlatformCustomCursor;
-use crate::window::{CursorGrabMode, CursorIcon, ImePurpose, ResizeDirection, Theme, WindowId};
+use crate::window::{
+    Coalescing, CursorGrabMode, CursorGrabPolicy, CursorIcon, ImePurpose, ResizeDirection,
+    SurfaceFormatHint, Theme, WindowId,
+};
 
 #[cfg(feature = "sctk-adwaita")]
 pub type WinitFrame = sctk_adwaita::AdwaitaFrame<WinitState>;
@@ -89,7 +100,12 @@ pub struct WindowState {
     // NOTE: we can't use simple counter, since it's racy when seat getting destroyed and new
     // is created, since add/removed stuff could be delivered a bit out of order.
     /// Seats that has keyboard focus on that window.
-    seat_focus: HashSet<ObjectId>,
+    seat_focus: HashMap<ObjectId, WlSeat>,
+
+    /// Whether the shortcuts of the compositor are inhibited for the seats focusing the window.
+    keyboard_capture: bool,
+    keyboard_shortcuts_inhibit_manager: Option<KeyboardShortcutsInhibitManager>,
+    shortcuts_inhibitors: HashMap<ObjectId, ZwpKeyboardShortcutsInhibitorV1>,
 
     /// The scale factor of the window.
     scale_factor: f64,
@@ -103,9 +119,22 @@ pub struct WindowState {
     /// The current cursor grabbing mode.
     cursor_grab_mode: GrabState,
 
+    /// The coalescing of the pointer motion events.
+    motion_coalescing: Coalescing,
+
+    /// The AccessKit adapter, once the application gave the source of the tree.
+    #[cfg(feature = "accesskit")]
+    accesskit: Option<AccessKitAdapter>,
+
+    /// What happens to the cursor grab when the window loses focus.
+    cursor_grab_policy: CursorGrabPolicy,
+
     /// Whether the IME input is allowed for that window.
     ime_allowed: bool,
 
+    /// Whether the virtual keyboard was requested with the text input disabled for IME.
+    virtual_keyboard_visible: bool,
+
     /// The current IME purpose.
     ime_purpose: ImePurpose,
 
@@ -141,6 +170,7 @@ pub struct WindowState {
     fractional_scale: Option<WpFractionalScaleV1>,
     blur: Option<OrgKdeKwinBlur>,
     blur_manager: Option<KWinBlurManager>,
+    color_surface: Option<WpColorManagementSurfaceV1>,
 
     /// Whether the client side decorations have pending move operations.
     ///
@@ -182,10 +212,15 @@ impl WindowState {
         Self {
             blur: None,
             blur_manager: winit_state.kwin_blur_manager.clone(),
+            color_surface: None,
             compositor,
             connection,
             csd_fails: false,
             cursor_grab_mode: GrabState::new(),
+            motion_coalescing: Coalescing::None,
+            #[cfg(feature = "accesskit")]
+            accesskit: None,
+            cursor_grab_policy: CursorGrabPolicy::default(),
             selected_cursor: Default::default(),
             cursor_visible: true,
             decorate: true,
@@ -193,8 +228,14 @@ impl WindowState {
             frame: None,
             frame_callback_state: FrameCallbackState::None,
             seat_focus: Default::default(),
+            keyboard_capture: false,
+            keyboard_shortcuts_inhibit_manager: winit_state
+                .keyboard_shortcuts_inhibit_manager
+                .clone(),
+            shortcuts_inhibitors: Default::default(),
             has_pending_move: None,
             ime_allowed: false,
+            virtual_keyboard_visible: false,
             ime_purpose: ImePurpose::Normal,
             last_configure: None,
             max_surface_size: None,
@@ -531,6 +572,12 @@ impl WindowState {
         self.ime_allowed
     }
 
+    /// Whether the text input should be enabled for the window.
+    #[inline]
+    pub fn text_input_enabled(&self) -> bool {
+        self.ime_allowed || self.virtual_keyboard_visible
+    }
+
     /// Get the size of the window.
     #[inline]
     pub fn surface_size(&self) -> LogicalSize<u32> {
@@ -572,6 +619,11 @@ impl WindowState {
         self.pointers.push(added);
         self.reload_cursor_style();
 
+        // The grab is established again once the window is focused.
+        if self.cursor_grab_policy == CursorGrabPolicy::ReGrabOnFocus && !self.has_focus() {
+            return;
+        }
+
         let mode = self.cursor_grab_mode.user_grab_mode;
         let _ = self.set_cursor_grab_inner(mode);
     }
@@ -710,23 +762,24 @@ impl WindowState {
             },
         };
 
-        let cursor = {
+        let cursors: Vec<_> = {
             let mut pool = self.custom_cursor_pool.lock().unwrap();
-            CustomCursor::new(&mut pool, &cursor)
+            cursor.images().iter().map(|image| CustomCursor::new(&mut pool, image)).collect()
         };
 
         if self.cursor_visible {
-            self.apply_custom_cursor(&cursor);
+            self.apply_custom_cursor(&cursors);
         }
 
-        self.selected_cursor = SelectedCursor::Custom(cursor);
+        self.selected_cursor = SelectedCursor::Custom(cursors);
     }
 
-    fn apply_custom_cursor(&self, cursor: &CustomCursor) {
+    fn apply_custom_cursor(&self, cursors: &[CustomCursor]) {
         self.apply_on_pointer(|pointer, _| {
             let surface = pointer.surface();
 
             let scale = surface.data::<SurfaceData>().unwrap().surface_data().scale_factor();
+            let cursor = closest_to_scale_factor(cursors, |cursor| cursor.w as u16, scale.into());
 
             surface.set_buffer_scale(scale);
             surface.attach(Some(cursor.buffer.wl_buffer()), 0, 0);
@@ -810,6 +863,92 @@ impl WindowState {
         Ok(())
     }
 
+    /// Set the coalescing of the pointer motion events.
+    #[inline]
+    pub fn set_motion_coalescing(&mut self, coalescing: Coalescing) {
+        self.motion_coalescing = coalescing;
+    }
+
+    /// The coalescing of the pointer motion events.
+    #[inline]
+    pub fn motion_coalescing(&self) -> Coalescing {
+        self.motion_coalescing
+    }
+
+    /// Set the AccessKit adapter of the window.
+    #[cfg(feature = "accesskit")]
+    pub fn set_accesskit_adapter(&mut self, adapter: AccessKitAdapter) {
+        self.accesskit = Some(adapter);
+        self.update_accesskit_adapter();
+    }
+
+    /// Push a change of the accessibility tree.
+    #[cfg(feature = "accesskit")]
+    pub fn update_accesskit_tree(&mut self, update: accesskit::TreeUpdate) {
+        if let Some(adapter) = self.accesskit.as_mut() {
+            adapter.update(update);
+        }
+    }
+
+    /// Keep the AccessKit adapter in sync with the focus and the size of the window.
+    ///
+    /// The position of the window isn't known on Wayland, so the bounds are relative to it.
+    #[cfg(feature = "accesskit")]
+    pub fn update_accesskit_adapter(&mut self) {
+        let has_focus = self.has_focus();
+        let scale_factor = self.scale_factor();
+        let outer_size = logical_to_physical_rounded(self.outer_size(), scale_factor);
+        let surface_size = logical_to_physical_rounded(self.surface_size(), scale_factor);
+        if let Some(adapter) = self.accesskit.as_mut() {
+            adapter.set_focused(has_focus);
+            adapter.set_bounds(
+                (PhysicalPosition::default(), outer_size),
+                (PhysicalPosition::default(), surface_size),
+            );
+        }
+    }
+
+    /// Set what happens to the cursor grab when the window loses focus.
+    #[inline]
+    pub fn set_cursor_grab_policy(&mut self, policy: CursorGrabPolicy) {
+        self.cursor_grab_policy = policy;
+    }
+
+    /// Release or establish again the cursor grab following the focus of the window, when the
+    /// policy asks for it.
+    ///
+    /// Returns the grab mode now in effect, if it was changed.
+    pub fn focus_changed_cursor_grab(&mut self, focused: bool) -> Option<CursorGrabMode> {
+        let user_mode = self.cursor_grab_mode.user_grab_mode;
+        if self.cursor_grab_policy != CursorGrabPolicy::ReGrabOnFocus
+            || user_mode == CursorGrabMode::None
+        {
+            return None;
+        }
+
+        let mode = if focused { user_mode } else { CursorGrabMode::None };
+        match self.set_cursor_grab_inner(mode) {
+            Ok(()) => Some(mode),
+            Err(err) => {
+                warn!("Failed to change the cursor grab: {err}");
+                None
+            },
+        }
+    }
+
+    /// Release the cursor grab without forgetting the mode requested by the user.
+    pub fn suspend_cursor_grab(&mut self) {
+        let _ = self.set_cursor_grab_inner(CursorGrabMode::None);
+    }
+
+    /// Restore the cursor grab mode requested by the user.
+    pub fn resume_cursor_grab(&mut self) {
+        let mode = self.cursor_grab_mode.user_grab_mode;
+        if let Err(err) = self.set_cursor_grab_inner(mode) {
+            warn!("Failed to restore cursor grab: {err}");
+        }
+    }
+
     /// Reload the hints for minimum and maximum sizes.
     pub fn reload_min_max_hints(&mut self) {
         self.set_min_surface_size(Some(self.min_surface_size));
@@ -934,24 +1073,74 @@ impl WindowState {
 
     /// Add seat focus for the window.
     #[inline]
-    pub fn add_seat_focus(&mut self, seat: ObjectId) {
-        self.seat_focus.insert(seat);
+    pub fn add_seat_focus(&mut self, seat: &WlSeat) {
+        self.seat_focus.insert(seat.id(), seat.clone());
+        if self.keyboard_capture {
+            self.inhibit_shortcuts(seat);
+        }
     }
 
     /// Remove seat focus from the window.
     #[inline]
     pub fn remove_seat_focus(&mut self, seat: &ObjectId) {
         self.seat_focus.remove(seat);
+        if let Some(inhibitor) = self.shortcuts_inhibitors.remove(seat) {
+            inhibitor.destroy();
+        }
+    }
+
+    /// Inhibit the shortcuts of the compositor while the window has focus.
+    pub fn set_keyboard_capture(&mut self, capture: bool) -> Result<(), RequestError> {
+        if self.keyboard_shortcuts_inhibit_manager.is_none() {
+            return Err(NotSupportedError::new(
+                "zwp_keyboard_shortcuts_inhibit_manager_v1 is not available",
+            )
+            .into());
+        }
+
+        self.keyboard_capture = capture;
+        if capture {
+            let seats: Vec<_> = self.seat_focus.values().cloned().collect();
+            for seat in &seats {
+                self.inhibit_shortcuts(seat);
+            }
+        } else {
+            for (_, inhibitor) in self.shortcuts_inhibitors.drain() {
+                inhibitor.destroy();
+            }
+        }
+
+        Ok(())
+    }
+
+    fn inhibit_shortcuts(&mut self, seat: &WlSeat) {
+        let Some(manager) = self.keyboard_shortcuts_inhibit_manager.as_ref() else { return };
+        if !self.shortcuts_inhibitors.contains_key(&seat.id()) {
+            let inhibitor = manager.inhibit(self.window.wl_surface(), seat, &self.queue_handle);
+            self.shortcuts_inhibitors.insert(seat.id(), inhibitor);
+        }
     }
 
     /// Returns `true` if the requested state was applied.
     pub fn set_ime_allowed(&mut self, allowed: bool) -> bool {
         self.ime_allowed = allowed;
+        self.update_text_inputs()
+    }
+
+    /// Show the virtual keyboard by enabling the text input, which the compositors show their
+    /// on-screen keyboard for.
+    pub fn set_virtual_keyboard_visible(&mut self, visible: bool) {
+        self.virtual_keyboard_visible = visible;
+        self.update_text_inputs();
+    }
 
+    /// Returns `true` if there was a text input to update.
+    fn update_text_inputs(&self) -> bool {
+        let enabled = self.text_input_enabled();
         let mut applied = false;
         for text_input in &self.text_inputs {
             applied = true;
-            if allowed {
+            if enabled {
                 text_input.enable();
                 text_input.set_content_type_by_purpose(self.ime_purpose);
             } else {
@@ -986,6 +1175,19 @@ impl WindowState {
         }
     }
 
+    /// Set the text around the cursor for the IME.
+    pub fn set_ime_surrounding_text(&self, text: &str, cursor: usize, anchor: usize) {
+        if !text.is_char_boundary(cursor) || !text.is_char_boundary(anchor) {
+            warn!("IME surrounding text ignored, the selection isn't on char boundaries");
+            return;
+        }
+
+        for text_input in &self.text_inputs {
+            text_input.set_surrounding_text_around(text, cursor, anchor);
+            text_input.commit();
+        }
+    }
+
     /// Get the IME purpose.
     pub fn ime_purpose(&self) -> ImePurpose {
         self.ime_purpose
@@ -1023,6 +1225,20 @@ impl WindowState {
         }
     }
 
+    /// Describe the colors of the surface content to the compositor.
+    pub fn set_surface_format_hint(
+        &mut self,
+        color_manager: Option<&ColorManager>,
+        hint: SurfaceFormatHint,
+    ) {
+        if let Some(color_manager) = color_manager {
+            self.color_surface =
+                color_manager.set_format_hint(self.window.wl_surface(), hint, &self.queue_handle);
+        } else {
+            info!("Color manager unavailable, unable to set the surface format")
+        }
+    }
+
     /// Set the window title to a new value.
     ///
     /// This will automatically truncate the title to something meaningful.
@@ -1090,6 +1306,14 @@ impl Drop for WindowState {
             viewport.destroy();
         }
 
+        if let Some(color_surface) = self.color_surface.take() {
+            color_surface.destroy();
+        }
+
+        for (_, inhibitor) in self.shortcuts_inhibitors.drain() {
+            inhibitor.destroy();
+        }
+
         // NOTE: the wl_surface used by the window is being cleaned up when
         // dropping SCTK `Window`.
     }
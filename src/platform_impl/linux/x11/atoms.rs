@@ -87,15 +87,41 @@ atom_manager! {
     XdndDrop,
     XdndPosition,
     XdndStatus,
-    XdndActionPrivate,
+    XdndActionCopy,
+    XdndActionMove,
+    XdndActionLink,
     XdndSelection,
     XdndFinished,
     XdndTypeList,
     TextUriList: b"text/uri-list",
     None: b"None",
 
+    // Clipboard Atoms
+    ClipboardSelection: b"CLIPBOARD",
+    Targets: b"TARGETS",
+    _WINIT_CLIPBOARD,
+    _WINIT_DROP,
+
+    // Input Device Atoms
+    DeviceProductId: b"Device Product ID",
+    AbsPressure: b"Abs Pressure",
+    AbsTiltX: b"Abs Tilt X",
+    AbsTiltY: b"Abs Tilt Y",
+    AbsMtPressure: b"Abs MT Pressure",
+    AbsMtTouchMajor: b"Abs MT Touch Major",
+    AbsMtTouchMinor: b"Abs MT Touch Minor",
+    AbsMtOrientation: b"Abs MT Orientation",
+    AbsMtPositionX: b"Abs MT Position X",
+
+    // Keyboard Indicator Atoms
+    CapsLockIndicator: b"Caps Lock",
+    NumLockIndicator: b"Num Lock",
+    ScrollLockIndicator: b"Scroll Lock",
+
     // Miscellaneous Atoms
+    Edid: b"EDID",
     _GTK_THEME_VARIANT,
+    _ICC_PROFILE,
     _MOTIF_WM_HINTS,
     _NET_ACTIVE_WINDOW,
     _NET_CLIENT_LIST,
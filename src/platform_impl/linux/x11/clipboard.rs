@@ -0,0 +1,351 @@
+//! The `CLIPBOARD` and `PRIMARY` selections, owned by a hidden window.
+
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::ffi::c_int;
+use std::sync::Arc;
+
+use x11_dl::xlib::{XSelectionClearEvent, XSelectionEvent, XSelectionRequestEvent};
+use x11rb::connection::{Connection, RequestConnection};
+use x11rb::protocol::xfixes::{self, ConnectionExt as _};
+use x11rb::protocol::xproto::{self, ConnectionExt};
+use x11rb::wrapper::ConnectionExt as _;
+
+use super::atoms::*;
+use super::{X11Error, XConnection};
+use crate::clipboard::{Clipboard as RootClipboard, ClipboardData, ClipboardFormat, Selection};
+use crate::error::RequestError;
+use crate::event_loop::AsyncRequestSerial;
+use crate::platform_impl::common::clipboard as mime;
+
+#[derive(Debug)]
+pub struct Clipboard {
+    xconn: Arc<XConnection>,
+    /// The window owning the selections while the application does, and receiving their content.
+    window: xproto::Window,
+    /// The type of the XFIXES events reporting the new owners of the selection, if the extension
+    /// is available.
+    xfixes_event: Option<u8>,
+    /// The atoms of the MIME types, interned when they're first used.
+    mime_atoms: RefCell<HashMap<&'static str, xproto::Atom>>,
+    /// The data the application put in the clipboard, while it owns it.
+    content: RefCell<Option<Vec<ClipboardData>>>,
+    /// The data the application put in the primary selection, while it owns it.
+    primary_content: RefCell<Option<Vec<ClipboardData>>>,
+    /// The reads waiting for the owner of the selection to convert it, with the index of the
+    /// target tried. Only the first one is being converted.
+    reads: RefCell<VecDeque<(AsyncRequestSerial, Selection, ClipboardFormat, usize)>>,
+    /// The reads which are done, to deliver to the application.
+    done: RefCell<VecDeque<(AsyncRequestSerial, Option<ClipboardData>)>>,
+}
+
+impl Clipboard {
+    pub fn new(xconn: Arc<XConnection>, root: xproto::Window) -> Result<Self, X11Error> {
+        let conn = xconn.xcb_connection();
+        let window = conn.generate_id()?;
+        conn.create_window(
+            x11rb::COPY_DEPTH_FROM_PARENT,
+            window,
+            root,
+            0,
+            0,
+            1,
+            1,
+            0,
+            xproto::WindowClass::INPUT_ONLY,
+            x11rb::COPY_FROM_PARENT,
+            &xproto::CreateWindowAux::new(),
+        )?;
+
+        let xfixes_event = Self::select_owner_changes(&xconn, window)
+            .map_err(|error| tracing::warn!("Failed to listen to the clipboard changes: {error}"))
+            .ok()
+            .flatten();
+
+        Ok(Self {
+            xconn,
+            window,
+            xfixes_event,
+            mime_atoms: Default::default(),
+            content: Default::default(),
+            primary_content: Default::default(),
+            reads: Default::default(),
+            done: Default::default(),
+        })
+    }
+
+    /// Ask XFIXES to report the new owners of the selection, which own its new content.
+    fn select_owner_changes(
+        xconn: &XConnection,
+        window: xproto::Window,
+    ) -> Result<Option<u8>, X11Error> {
+        let conn = xconn.xcb_connection();
+        let Some(info) = conn.extension_information(xfixes::X11_EXTENSION_NAME)? else {
+            return Ok(None);
+        };
+        // The version has to be announced before using the extension.
+        conn.xfixes_query_version(1, 0)?.reply()?;
+        conn.xfixes_select_selection_input(
+            window,
+            xconn.atoms()[ClipboardSelection],
+            xfixes::SelectionEventMask::SET_SELECTION_OWNER
+                | xfixes::SelectionEventMask::SELECTION_WINDOW_DESTROY
+                | xfixes::SelectionEventMask::SELECTION_CLIENT_CLOSE,
+        )?;
+        Ok(Some(info.first_event + xfixes::SELECTION_NOTIFY_EVENT))
+    }
+
+    pub fn window(&self) -> xproto::Window {
+        self.window
+    }
+
+    /// Whether the event reports a change of the clipboard.
+    pub fn is_change(&self, event_type: c_int) -> bool {
+        self.xfixes_event.is_some_and(|xfixes_event| event_type == xfixes_event as c_int)
+    }
+
+    /// Take the next read which is done.
+    pub fn take_done(&self) -> Option<(AsyncRequestSerial, Option<ClipboardData>)> {
+        self.done.borrow_mut().pop_front()
+    }
+
+    pub fn has_done(&self) -> bool {
+        !self.done.borrow().is_empty()
+    }
+
+    /// The atom of the selection.
+    fn atom_of(&self, selection: Selection) -> xproto::Atom {
+        match selection {
+            Selection::Clipboard => self.xconn.atoms()[ClipboardSelection],
+            Selection::Primary => xproto::AtomEnum::PRIMARY.into(),
+        }
+    }
+
+    /// The selection of the atom, if it's one of the application's.
+    fn selection_of(&self, atom: xproto::Atom) -> Option<Selection> {
+        [Selection::Clipboard, Selection::Primary]
+            .into_iter()
+            .find(|&selection| self.atom_of(selection) == atom)
+    }
+
+    /// The data the application put in the selection.
+    fn content_of(&self, selection: Selection) -> &RefCell<Option<Vec<ClipboardData>>> {
+        match selection {
+            Selection::Clipboard => &self.content,
+            Selection::Primary => &self.primary_content,
+        }
+    }
+
+    /// The atoms of the MIME types of the format, the preferred one first.
+    pub fn atoms_of(&self, format: ClipboardFormat) -> Vec<xproto::Atom> {
+        let mut mime_atoms = self.mime_atoms.borrow_mut();
+        let mut atoms = Vec::new();
+        for mime_type in mime::mime_types(format) {
+            if let Some(atom) = mime_atoms.get(mime_type) {
+                atoms.push(*atom);
+                continue;
+            }
+
+            let reply = self
+                .xconn
+                .xcb_connection()
+                .intern_atom(false, mime_type.as_bytes())
+                .map_err(X11Error::from)
+                .and_then(|cookie| Ok(cookie.reply()?));
+            match reply {
+                Ok(reply) => {
+                    mime_atoms.insert(mime_type, reply.atom);
+                    atoms.push(reply.atom);
+                },
+                Err(error) => tracing::warn!("Failed to intern the atom of {mime_type}: {error}"),
+            }
+        }
+        atoms
+    }
+
+    /// Send the data to the application pasting it.
+    pub fn handle_request(&self, event: &XSelectionRequestEvent) {
+        let atoms = self.xconn.atoms();
+        let conn = self.xconn.xcb_connection();
+        let requestor = event.requestor as xproto::Window;
+        let target = event.target as xproto::Atom;
+        // Obsolete clients don't set the property, the target is used instead.
+        let property = match event.property as xproto::Atom {
+            x11rb::NONE => target,
+            property => property,
+        };
+
+        let content = self
+            .selection_of(event.selection as xproto::Atom)
+            .map(|selection| self.content_of(selection).borrow());
+        let converted = match content.as_deref().and_then(Option::as_deref) {
+            Some(content) if target == atoms[Targets] => {
+                let mut targets = vec![atoms[Targets]];
+                for data in content {
+                    targets.extend(self.atoms_of(data.format()));
+                }
+                self.xconn
+                    .change_property(
+                        requestor,
+                        property,
+                        xproto::AtomEnum::ATOM.into(),
+                        xproto::PropMode::REPLACE,
+                        &targets,
+                    )
+                    .is_ok()
+            },
+            Some(content) => {
+                // Large data isn't sent incrementally, it's limited by the maximum request size.
+                let data =
+                    content.iter().find(|data| self.atoms_of(data.format()).contains(&target));
+                data.is_some_and(|data| {
+                    conn.change_property8(
+                        xproto::PropMode::REPLACE,
+                        requestor,
+                        property,
+                        target,
+                        &mime::encode(data),
+                    )
+                    .is_ok()
+                })
+            },
+            None => false,
+        };
+
+        let notify = xproto::SelectionNotifyEvent {
+            response_type: xproto::SELECTION_NOTIFY_EVENT,
+            sequence: 0,
+            time: event.time as xproto::Timestamp,
+            requestor,
+            selection: event.selection as xproto::Atom,
+            target,
+            property: if converted { property } else { x11rb::NONE },
+        };
+        let _ = conn.send_event(false, requestor, xproto::EventMask::NO_EVENT, notify);
+        let _ = conn.flush();
+    }
+
+    /// Forget the data once another application owns the selection.
+    pub fn handle_clear(&self, event: &XSelectionClearEvent) {
+        if let Some(selection) = self.selection_of(event.selection as xproto::Atom) {
+            self.content_of(selection).borrow_mut().take();
+        }
+    }
+
+    /// Read the data converted by the owner of the selection.
+    pub fn handle_notify(&self, event: &XSelectionEvent) {
+        let Some((serial, selection, format, index)) = self.reads.borrow_mut().pop_front() else {
+            return;
+        };
+
+        let property = event.property as xproto::Atom;
+        if property == x11rb::NONE {
+            // The owner refused the target, try the next one of the format.
+            if index + 1 < self.atoms_of(format).len() {
+                self.reads.borrow_mut().push_front((serial, selection, format, index + 1));
+            } else {
+                self.done.borrow_mut().push_back((serial, None));
+            }
+        } else {
+            // Large data sent incrementally isn't supported, it's a type mismatch.
+            let data = self
+                .xconn
+                .get_property::<u8>(self.window, property, event.target as xproto::Atom)
+                .map_err(|error| tracing::warn!("Failed to read the clipboard: {error}"))
+                .ok()
+                .and_then(|bytes| mime::decode(format, bytes));
+            let _ = self.xconn.xcb_connection().delete_property(self.window, property);
+            self.done.borrow_mut().push_back((serial, data));
+        }
+
+        if let Err(error) = self.convert_next() {
+            tracing::warn!("Failed to read the clipboard: {error}");
+            let mut done = self.done.borrow_mut();
+            done.extend(self.reads.borrow_mut().drain(..).map(|(serial, ..)| (serial, None)));
+        }
+    }
+
+    /// Ask the owner of the selection to convert it for the first read.
+    fn convert_next(&self) -> Result<(), X11Error> {
+        let Some(&(_, selection, format, index)) = self.reads.borrow().front() else {
+            return Ok(());
+        };
+
+        let Some(&target) = self.atoms_of(format).get(index) else {
+            // The atom of the target couldn't be interned.
+            let (serial, ..) = self.reads.borrow_mut().pop_front().unwrap();
+            self.done.borrow_mut().push_back((serial, None));
+            return self.convert_next();
+        };
+
+        let atoms = self.xconn.atoms();
+        let conn = self.xconn.xcb_connection();
+        conn.convert_selection(
+            self.window,
+            self.atom_of(selection),
+            target,
+            atoms[_WINIT_CLIPBOARD],
+            self.xconn.timestamp(),
+        )?;
+        conn.flush()?;
+        Ok(())
+    }
+}
+
+impl RootClipboard for Clipboard {
+    fn set(&self, data: Vec<ClipboardData>) -> Result<(), RequestError> {
+        self.set_selection(Selection::Clipboard, data)
+    }
+
+    fn get(&self, format: ClipboardFormat) -> Result<AsyncRequestSerial, RequestError> {
+        self.get_selection(Selection::Clipboard, format)
+    }
+
+    fn set_selection(
+        &self,
+        selection: Selection,
+        data: Vec<ClipboardData>,
+    ) -> Result<(), RequestError> {
+        let atom = self.atom_of(selection);
+        let conn = self.xconn.xcb_connection();
+        conn.set_selection_owner(self.window, atom, self.xconn.timestamp())
+            .map_err(|error| os_error!(error))?;
+        let owner = conn
+            .get_selection_owner(atom)
+            .map_err(|error| os_error!(error))?
+            .reply()
+            .map_err(|error| os_error!(error))?
+            .owner;
+        if owner != self.window {
+            return Err(os_error!("Failed to take the ownership of the selection").into());
+        }
+
+        *self.content_of(selection).borrow_mut() = Some(data);
+        Ok(())
+    }
+
+    fn get_selection(
+        &self,
+        selection: Selection,
+        format: ClipboardFormat,
+    ) -> Result<AsyncRequestSerial, RequestError> {
+        let serial = AsyncRequestSerial::get();
+
+        if let Some(content) = self.content_of(selection).borrow().as_ref() {
+            let data = content.iter().find(|data| data.format() == format).cloned();
+            self.done.borrow_mut().push_back((serial, data));
+            return Ok(serial);
+        }
+
+        let idle = self.reads.borrow().is_empty();
+        self.reads.borrow_mut().push_back((serial, selection, format, 0));
+        if idle {
+            if let Err(error) = self.convert_next() {
+                self.reads.borrow_mut().clear();
+                return Err(os_error!(error).into());
+            }
+        }
+
+        Ok(serial)
+    }
+}
@@ -1,19 +1,29 @@
 use std::io;
+use std::mem::MaybeUninit;
 use std::os::raw::*;
 use std::path::{Path, PathBuf};
 use std::str::Utf8Error;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
+use dpi::PhysicalPosition;
 use percent_encoding::percent_decode;
+use x11rb::connection::Connection;
 use x11rb::protocol::xproto::{self, ConnectionExt};
 
 use super::atoms::AtomName::None as DndNone;
 use super::atoms::*;
-use super::{util, CookieResultExt, X11Error, XConnection};
+use super::{ffi, util, CookieResultExt, X11Error, XConnection};
+use crate::clipboard::ClipboardFormat;
+use crate::drag::{DragEffect, DropData};
+use crate::platform_impl::common::clipboard as mime;
+
+/// How long the source of a drop has to convert its data.
+const CONVERSION_TIMEOUT: Duration = Duration::from_secs(1);
 
 #[derive(Debug, Clone, Copy)]
 pub enum DndState {
-    Accepted,
+    Accepted(DragEffect),
     Rejected,
 }
 
@@ -45,22 +55,56 @@ pub struct Dnd {
     pub type_list: Option<Vec<xproto::Atom>>,
     // Populated by XdndPosition event handler
     pub source_window: Option<xproto::Window>,
+    pub position: Option<PhysicalPosition<f64>>,
+    pub effect: Option<DragEffect>,
     // Populated by SelectionNotify event handler (triggered by XdndPosition event handler)
     pub result: Option<Result<Vec<PathBuf>, DndDataParseError>>,
 }
 
 impl Dnd {
     pub fn new(xconn: Arc<XConnection>) -> Result<Self, X11Error> {
-        Ok(Dnd { xconn, version: None, type_list: None, source_window: None, result: None })
+        Ok(Dnd {
+            xconn,
+            version: None,
+            type_list: None,
+            source_window: None,
+            position: None,
+            effect: None,
+            result: None,
+        })
     }
 
     pub fn reset(&mut self) {
         self.version = None;
         self.type_list = None;
         self.source_window = None;
+        self.position = None;
+        self.effect = None;
         self.result = None;
     }
 
+    /// The effect of the XDND action, if it's one of the standard ones.
+    pub fn effect_of(&self, action: xproto::Atom) -> Option<DragEffect> {
+        let atoms = self.xconn.atoms();
+        [
+            (atoms[XdndActionCopy], DragEffect::Copy),
+            (atoms[XdndActionMove], DragEffect::Move),
+            (atoms[XdndActionLink], DragEffect::Link),
+        ]
+        .into_iter()
+        .find_map(|(atom, effect)| (atom == action).then_some(effect))
+    }
+
+    fn action_of(&self, state: DndState) -> xproto::Atom {
+        let atoms = self.xconn.atoms();
+        match state {
+            DndState::Accepted(DragEffect::Copy) => atoms[XdndActionCopy],
+            DndState::Accepted(DragEffect::Move) => atoms[XdndActionMove],
+            DndState::Accepted(DragEffect::Link) => atoms[XdndActionLink],
+            DndState::Rejected => atoms[DndNone],
+        }
+    }
+
     pub unsafe fn send_status(
         &self,
         this_window: xproto::Window,
@@ -68,10 +112,8 @@ impl Dnd {
         state: DndState,
     ) -> Result<(), X11Error> {
         let atoms = self.xconn.atoms();
-        let (accepted, action) = match state {
-            DndState::Accepted => (1, atoms[XdndActionPrivate]),
-            DndState::Rejected => (0, atoms[DndNone]),
-        };
+        let accepted = matches!(state, DndState::Accepted(_)) as u32;
+        let action = self.action_of(state);
         self.xconn
             .send_client_msg(target_window, target_window, atoms[XdndStatus] as _, None, [
                 this_window,
@@ -92,10 +134,8 @@ impl Dnd {
         state: DndState,
     ) -> Result<(), X11Error> {
         let atoms = self.xconn.atoms();
-        let (accepted, action) = match state {
-            DndState::Accepted => (1, atoms[XdndActionPrivate]),
-            DndState::Rejected => (0, atoms[DndNone]),
-        };
+        let accepted = matches!(state, DndState::Accepted(_)) as u32;
+        let action = self.action_of(state);
         self.xconn
             .send_client_msg(target_window, target_window, atoms[XdndFinished] as _, None, [
                 this_window,
@@ -143,6 +183,37 @@ impl Dnd {
         self.xconn.get_property(window, atoms[XdndSelection], atoms[TextUriList])
     }
 
+    /// The data of the drop on the `window`, converted by the source in the `targets` of each
+    /// format when it's read.
+    pub fn drop_data(
+        &self,
+        window: xproto::Window,
+        time: xproto::Timestamp,
+        targets: Vec<(ClipboardFormat, Vec<xproto::Atom>)>,
+    ) -> DropData {
+        let xconn = Arc::clone(&self.xconn);
+        let offered = self.type_list.clone().unwrap_or_default();
+        let formats = targets.iter().map(|(format, _)| *format).collect();
+        DropData::new(formats, move |format| {
+            let atoms = match format {
+                ClipboardFormat::Custom(mime_type) => {
+                    let atom = xconn
+                        .xcb_connection()
+                        .intern_atom(false, mime_type.as_bytes())
+                        .ok()?
+                        .reply()
+                        .ok()?
+                        .atom;
+                    offered.contains(&atom).then(|| vec![atom])?
+                },
+                _ => targets.iter().find(|(f, _)| *f == format)?.1.clone(),
+            };
+            let bytes =
+                atoms.into_iter().find_map(|target| convert_drop(&xconn, window, time, target))?;
+            mime::decode(format, bytes)
+        })
+    }
+
     pub fn parse_data(&self, data: &mut [c_uchar]) -> Result<Vec<PathBuf>, DndDataParseError> {
         if !data.is_empty() {
             let mut path_list = Vec::new();
@@ -172,3 +243,96 @@ impl Dnd {
         }
     }
 }
+
+/// Ask the source of the drop to convert it to the `target`, and wait for the data.
+fn convert_drop(
+    xconn: &XConnection,
+    window: xproto::Window,
+    time: xproto::Timestamp,
+    target: xproto::Atom,
+) -> Option<Vec<u8>> {
+    let atoms = xconn.atoms();
+    let conn = xconn.xcb_connection();
+    conn.convert_selection(window, atoms[XdndSelection], target, atoms[_WINIT_DROP], time).ok()?;
+    conn.flush().ok()?;
+
+    let event = wait_for_conversion(xconn, window, target)?;
+    if event.property == 0 {
+        // The source refused the target.
+        return None;
+    }
+    let data = xconn
+        .get_property::<u8>(window, atoms[_WINIT_DROP], event.target as xproto::Atom)
+        .map_err(|error| tracing::warn!("Failed to read the dropped data: {error}"))
+        .ok();
+    let _ = conn.delete_property(window, atoms[_WINIT_DROP]);
+    data
+}
+
+/// Wait for the `SelectionNotify` event of the conversion of the drop to the `target`, taking it
+/// out of the queue of the event loop.
+fn wait_for_conversion(
+    xconn: &XConnection,
+    window: xproto::Window,
+    target: xproto::Atom,
+) -> Option<ffi::XSelectionEvent> {
+    struct Conversion {
+        window: c_ulong,
+        selection: c_ulong,
+        target: c_ulong,
+        property: c_ulong,
+    }
+
+    unsafe extern "C" fn is_conversion(
+        _: *mut ffi::Display,
+        event: *mut ffi::XEvent,
+        arg: *mut c_char,
+    ) -> c_int {
+        let conversion = unsafe { &*(arg as *const Conversion) };
+        let event = unsafe { &*event };
+        if event.get_type() != ffi::SelectionNotify {
+            return ffi::False;
+        }
+        let event: &ffi::XSelectionEvent = event.as_ref();
+        let matches = event.requestor == conversion.window
+            && event.selection == conversion.selection
+            && event.target == conversion.target
+            && (event.property == conversion.property || event.property == 0);
+        matches as c_int
+    }
+
+    let atoms = xconn.atoms();
+    let mut conversion = Conversion {
+        window: window as c_ulong,
+        selection: atoms[XdndSelection] as c_ulong,
+        target: target as c_ulong,
+        property: atoms[_WINIT_DROP] as c_ulong,
+    };
+    let deadline = Instant::now() + CONVERSION_TIMEOUT;
+    loop {
+        let mut event = MaybeUninit::<ffi::XEvent>::uninit();
+        let found = unsafe {
+            (xconn.xlib.XCheckIfEvent)(
+                xconn.display,
+                event.as_mut_ptr(),
+                Some(is_conversion),
+                &mut conversion as *mut Conversion as *mut c_char,
+            )
+        };
+        if found == ffi::True {
+            let event = unsafe { event.assume_init() };
+            return Some(*AsRef::<ffi::XSelectionEvent>::as_ref(&event));
+        }
+
+        let Some(timeout) = deadline.checked_duration_since(Instant::now()) else {
+            tracing::warn!("The source of the drop didn't convert it in time");
+            return None;
+        };
+        let mut fd = libc::pollfd {
+            fd: unsafe { (xconn.xlib.XConnectionNumber)(xconn.display) },
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        unsafe { libc::poll(&mut fd, 1, timeout.as_millis().max(1) as c_int) };
+    }
+}
@@ -1,8 +1,8 @@
 use std::cell::{Cell, RefCell};
 use std::collections::{HashMap, VecDeque};
 use std::os::raw::{c_char, c_int, c_long, c_ulong};
-use std::slice;
 use std::sync::{Arc, Mutex};
+use std::{mem, slice};
 
 use x11_dl::xinput2::{
     self, XIDeviceEvent, XIEnterEvent, XIFocusInEvent, XIFocusOutEvent, XIHierarchyEvent,
@@ -13,19 +13,24 @@ use x11_dl::xlib::{
     XDestroyWindowEvent, XEvent, XExposeEvent, XKeyEvent, XMapEvent, XPropertyEvent,
     XReparentEvent, XSelectionEvent, XVisibilityEvent, XkbAnyEvent, XkbStateRec,
 };
+use x11_dl::xrandr::{self, XRROutputPropertyNotifyEvent};
 use x11rb::protocol::sync::{ConnectionExt, Int64};
-use x11rb::protocol::xinput;
 use x11rb::protocol::xkb::ID as XkbId;
 use x11rb::protocol::xproto::{self, ConnectionExt as _, ModMask};
+use x11rb::protocol::{randr, xinput};
 use x11rb::x11_utils::{ExtensionInformation, Serialize};
 use xkbcommon_dl::xkb_mod_mask_t;
 
+use crate::clipboard::ClipboardFormat;
 use crate::dpi::{PhysicalPosition, PhysicalSize};
+use crate::drag::{DragEffect, DropEffectWriter};
 use crate::event::{
-    ButtonSource, DeviceEvent, DeviceId, ElementState, Event, Ime, MouseButton, MouseScrollDelta,
-    PointerKind, PointerSource, RawKeyEvent, SurfaceSizeWriter, TouchPhase, WindowEvent,
+    ButtonSource, DeviceEvent, DeviceId, ElementState, Event, Force, Ime, MouseButton,
+    MouseScrollDelta, PenButtons, PointerKind, PointerSource, RawKeyEvent, SurfaceSizeWriter,
+    TouchContact, TouchPhase, WindowEvent,
 };
 use crate::keyboard::ModifiersState;
+use crate::monitor::MonitorHandle as RootMonitorHandle;
 use crate::platform_impl::common::xkb::{self, XkbState};
 use crate::platform_impl::platform::common::xkb::Context;
 use crate::platform_impl::platform::x11::ime::{ImeEvent, ImeEventReceiver, ImeRequest};
@@ -33,9 +38,11 @@ use crate::platform_impl::platform::x11::ActiveEventLoop;
 use crate::platform_impl::x11::atoms::*;
 use crate::platform_impl::x11::util::cookie::GenericEventCookie;
 use crate::platform_impl::x11::{
-    mkdid, mkfid, mkwid, util, CookieResultExt, Device, DeviceInfo, Dnd, DndState, ImeReceiver,
-    ScrollOrientation, UnownedWindow, WindowId,
+    ffi, mkdid, mkfid, mkwid, util, xinput_fp1616_to_float, CookieResultExt, Device, DeviceInfo,
+    Dnd, DndState, ImeReceiver, ScrollOrientation, UnownedWindow, WindowId,
 };
+use crate::platform_impl::MonitorHandle as PlatformMonitorHandle;
+use crate::window::{CursorGrabMode, CursorThemeInfo};
 
 /// The maximum amount of X modifiers to replay.
 pub const MAX_MOD_REPLAY_LEN: usize = 32;
@@ -61,6 +68,8 @@ pub struct EventProcessor {
     // Used to detect key repeats.
     pub held_key_press: Option<u32>,
     pub first_touch: Option<u32>,
+    // The scale of the pinch gesture in progress, since its beginning.
+    pub pinch_scale: f64,
     // Currently focused window belonging to this process
     pub active_window: Option<xproto::Window>,
     /// Latest modifiers we've sent for the user to trigger change in event.
@@ -68,6 +77,8 @@ pub struct EventProcessor {
     pub xfiltered_modifiers: VecDeque<c_ulong>,
     pub xmodmap: util::ModifierKeymap,
     pub is_composing: bool,
+    /// The last known cursor theme, to detect its changes.
+    pub cursor_theme: CursorThemeInfo,
 }
 
 impl EventProcessor {
@@ -173,6 +184,8 @@ impl EventProcessor {
         match event_type {
             xlib::ClientMessage => self.client_message(xev.as_ref(), &mut callback),
             xlib::SelectionNotify => self.selection_notify(xev.as_ref(), &mut callback),
+            xlib::SelectionRequest => self.target.clipboard.handle_request(xev.as_ref()),
+            xlib::SelectionClear => self.target.clipboard.handle_clear(xev.as_ref()),
             xlib::ConfigureNotify => self.configure_notify(xev.as_ref(), &mut callback),
             xlib::ReparentNotify => self.reparent_notify(xev.as_ref()),
             xlib::MapNotify => self.map_notify(xev.as_ref(), &mut callback),
@@ -215,6 +228,9 @@ impl EventProcessor {
                             &mut callback,
                         );
                         self.xinput2_button_input(xev, state, &mut callback);
+                        if state == ElementState::Released && !matches!(xev.detail, 4..=7) {
+                            self.update_drag(true, &mut callback);
+                        }
                     },
                     xinput2::XI_Motion => {
                         let xev: &XIDeviceEvent = unsafe { xev.as_event() };
@@ -225,6 +241,7 @@ impl EventProcessor {
                             &mut callback,
                         );
                         self.xinput2_mouse_motion(xev, &mut callback);
+                        self.update_drag(false, &mut callback);
                     },
                     xinput2::XI_Enter => {
                         let xev: &XIEnterEvent = unsafe { xev.as_event() };
@@ -252,6 +269,12 @@ impl EventProcessor {
                         let xev: &XIDeviceEvent = unsafe { xev.as_event() };
                         self.xinput2_touch(xev, evtype, &mut callback);
                     },
+                    ffi::XI_GesturePinchBegin
+                    | ffi::XI_GesturePinchUpdate
+                    | ffi::XI_GesturePinchEnd => {
+                        let xev: &ffi::XIGesturePinchEvent = unsafe { xev.as_event() };
+                        self.xinput2_gesture_pinch(xev, evtype, &mut callback);
+                    },
                     xinput2::XI_RawButtonPress | xinput2::XI_RawButtonRelease => {
                         let state = match evtype {
                             xinput2::XI_RawButtonPress => ElementState::Pressed,
@@ -279,7 +302,7 @@ impl EventProcessor {
 
                     xinput2::XI_HierarchyChanged => {
                         let xev: &XIHierarchyEvent = unsafe { xev.as_event() };
-                        self.xinput2_hierarchy_changed(xev);
+                        self.xinput2_hierarchy_changed(xev, &mut callback);
                     },
                     _ => {},
                 }
@@ -292,6 +315,14 @@ impl EventProcessor {
                 if event_type == self.randr_event_offset as c_int {
                     self.process_dpi_change(&mut callback);
                 }
+                if event_type == self.randr_event_offset as c_int + xrandr::RRNotify {
+                    let xev: &XRROutputPropertyNotifyEvent =
+                        unsafe { &*(xev as *const _ as *const XRROutputPropertyNotifyEvent) };
+                    self.xrandr_notify(xev, &mut callback);
+                }
+                if self.target.clipboard.is_change(event_type) {
+                    callback(&self.target, Event::ClipboardChanged);
+                }
             },
         }
     }
@@ -330,7 +361,8 @@ impl EventProcessor {
         let mut devices = self.devices.borrow_mut();
         if let Some(info) = DeviceInfo::get(&self.target.xconn, device as _) {
             for info in info.iter() {
-                devices.insert(mkdid(info.deviceid as xinput::DeviceId), Device::new(info));
+                let device = Device::new(info, self.target.xconn.atoms());
+                devices.insert(mkdid(info.deviceid as xinput::DeviceId), device);
             }
         }
     }
@@ -454,9 +486,8 @@ impl EventProcessor {
             // over our window. We emit HoveredFile in response; while the macOS backend
             // does that upon a drag entering, XDND doesn't have access to the actual drop
             // data until this event. For parity with other platforms, we only emit
-            // `HoveredFile` the first time, though if winit's API is later extended to
-            // supply position updates with `HoveredFile` or another event, implementing
-            // that here would be trivial.
+            // `HoveredFile` the first time, while `DataHovered` is emitted every time with the
+            // position, and its answer is sent back with `XdndStatus`.
 
             let source_window = xev.data.get_long(0) as xproto::Window;
 
@@ -464,24 +495,24 @@ impl EventProcessor {
             // where `shift = mem::size_of::<c_short>() * 8`
             // Note that coordinates are in "desktop space", not "window space"
             // (in X11 parlance, they're root window coordinates)
-            // let packed_coordinates = xev.data.get_long(2);
-            // let shift = mem::size_of::<libc::c_short>() * 8;
-            // let x = packed_coordinates >> shift;
-            // let y = packed_coordinates & !(x << shift);
+            let packed_coordinates = xev.data.get_long(2);
+            let root_x = (packed_coordinates >> 16) as i16;
+            let root_y = packed_coordinates as i16;
+            let position = self
+                .target
+                .xconn
+                .xcb_connection()
+                .translate_coordinates(self.target.root, window, root_x, root_y)
+                .ok()
+                .and_then(|cookie| cookie.reply().ok())
+                .map(|reply| PhysicalPosition::new(reply.dst_x as f64, reply.dst_y as f64));
 
             // By our own state flow, `version` should never be `None` at this point.
             let version = self.dnd.version.unwrap_or(5);
 
-            // Action is specified in versions 2 and up, though we don't need it anyway.
-            // let action = xev.data.get_long(4);
-
-            let accepted = if let Some(ref type_list) = self.dnd.type_list {
-                type_list.contains(&atoms[TextUriList])
-            } else {
-                false
-            };
-
-            if !accepted {
+            let formats: Vec<_> =
+                self.drop_targets().into_iter().map(|(format, _)| format).collect();
+            if formats.is_empty() {
                 unsafe {
                     self.dnd
                         .send_status(window, source_window, DndState::Rejected)
@@ -492,7 +523,10 @@ impl EventProcessor {
             }
 
             self.dnd.source_window = Some(source_window);
-            if self.dnd.result.is_none() {
+            self.dnd.position = position.or(self.dnd.position);
+            let has_files =
+                self.dnd.type_list.as_ref().is_some_and(|list| list.contains(&atoms[TextUriList]));
+            if has_files && self.dnd.result.is_none() {
                 let time = if version >= 1 {
                     xev.data.get_long(3) as xproto::Timestamp
                 } else {
@@ -509,16 +543,34 @@ impl EventProcessor {
                 }
             }
 
+            // The action is specified in versions 2 and up, the source picks it from the modifiers.
+            let action = if version >= 2 { xev.data.get_long(4) as xproto::Atom } else { 0 };
+            let default = self.dnd.effect_of(action).or(Some(DragEffect::Copy));
+            let modifiers = self
+                .target
+                .xconn
+                .query_pointer(self.target.root, util::VIRTUAL_CORE_POINTER)
+                .map(|pointer| modifiers_of_mask(pointer.mods.effective))
+                .unwrap_or_default();
+            let position = self.dnd.position.unwrap_or_default();
+            let effect = DropEffectWriter::negotiate(default, |effect| {
+                let event = WindowEvent::DataHovered { position, formats, modifiers, effect };
+                callback(&self.target, Event::WindowEvent { window_id, event });
+            });
+            self.dnd.effect = effect;
+
+            let state = effect.map_or(DndState::Rejected, DndState::Accepted);
             unsafe {
                 self.dnd
-                    .send_status(window, source_window, DndState::Accepted)
+                    .send_status(window, source_window, state)
                     .expect("Failed to send `XdndStatus` message.");
             }
             return;
         }
 
         if xev.message_type == atoms[XdndDrop] as c_ulong {
-            let (source_window, state) = if let Some(source_window) = self.dnd.source_window {
+            let accepted = self.dnd.source_window.zip(self.dnd.effect);
+            let (source_window, state) = if let Some((source_window, effect)) = accepted {
                 if let Some(Ok(ref path_list)) = self.dnd.result {
                     for path in path_list {
                         let event = Event::WindowEvent {
@@ -528,10 +580,27 @@ impl EventProcessor {
                         callback(&self.target, event);
                     }
                 }
-                (source_window, DndState::Accepted)
+
+                let version = self.dnd.version.unwrap_or(5);
+                let time = if version >= 1 {
+                    xev.data.get_long(2) as xproto::Timestamp
+                } else {
+                    x11rb::CURRENT_TIME
+                };
+                let data = self.dnd.drop_data(window, time, self.drop_targets());
+                let position = self.dnd.position.unwrap_or_default();
+                let event = Event::WindowEvent {
+                    window_id,
+                    event: WindowEvent::DataDropped { position, data: data.clone(), effect },
+                };
+                callback(&self.target, event);
+                // The source only provides the data until the drop is finished.
+                data.expire();
+
+                (source_window, DndState::Accepted(effect))
             } else {
                 // `source_window` won't be part of our DND state if we already rejected the drop in
-                // our `XdndPosition` handler.
+                // our `XdndPosition` handler, nor the effect if the window rejected it.
                 let source_window = xev.data.get_long(0) as xproto::Window;
                 (source_window, DndState::Rejected)
             };
@@ -547,12 +616,45 @@ impl EventProcessor {
         }
 
         if xev.message_type == atoms[XdndLeave] as c_ulong {
+            let hovered = self.dnd.source_window.is_some();
             self.dnd.reset();
             let event = Event::WindowEvent { window_id, event: WindowEvent::HoveredFileCancelled };
             callback(&self.target, event);
+            if hovered {
+                let event = WindowEvent::DataHoverCancelled;
+                callback(&self.target, Event::WindowEvent { window_id, event });
+            }
         }
     }
 
+    /// The formats the source of the drag offers, with their targets, its preferred ones first.
+    fn drop_targets(&self) -> Vec<(ClipboardFormat, Vec<xproto::Atom>)> {
+        let Some(type_list) = self.dnd.type_list.as_ref() else {
+            return Vec::new();
+        };
+        let preference = |atom: &xproto::Atom| type_list.iter().position(|offered| offered == atom);
+
+        let mut targets: Vec<_> = [
+            ClipboardFormat::Files,
+            ClipboardFormat::Uris,
+            ClipboardFormat::Text,
+            ClipboardFormat::Html,
+            ClipboardFormat::Image,
+        ]
+        .into_iter()
+        .map(|format| {
+            let atoms = self.target.clipboard.atoms_of(format);
+            (
+                format,
+                atoms.into_iter().filter(|atom| preference(atom).is_some()).collect::<Vec<_>>(),
+            )
+        })
+        .filter(|(_, atoms)| !atoms.is_empty())
+        .collect();
+        targets.sort_by_key(|(_, atoms)| atoms.iter().filter_map(preference).min());
+        targets
+    }
+
     fn selection_notify<F>(&mut self, xev: &XSelectionEvent, mut callback: F)
     where
         F: FnMut(&ActiveEventLoop, Event),
@@ -565,6 +667,11 @@ impl EventProcessor {
         // Set the timestamp.
         self.target.xconn.set_timestamp(xev.time as xproto::Timestamp);
 
+        if window == self.target.clipboard.window() {
+            self.target.clipboard.handle_notify(xev);
+            return;
+        }
+
         if xev.property != atoms[XdndSelection] as c_ulong {
             return;
         }
@@ -713,6 +820,7 @@ impl EventProcessor {
                         surface_size_writer: SurfaceSizeWriter::new(Arc::downgrade(&surface_size)),
                     },
                 });
+                window.reload_custom_cursor(new_scale_factor);
 
                 let new_surface_size = *surface_size.lock().unwrap();
                 drop(surface_size);
@@ -833,6 +941,12 @@ impl EventProcessor {
             || atom == atoms[_XSETTINGS_SETTINGS]
         {
             self.process_dpi_change(&mut callback);
+
+            let cursor_theme = self.target.xconn.cursor_theme();
+            if cursor_theme != self.cursor_theme {
+                self.cursor_theme = cursor_theme.clone();
+                callback(&self.target, Event::CursorThemeChanged(cursor_theme));
+            }
         }
     }
 
@@ -942,7 +1056,12 @@ impl EventProcessor {
             }
 
             if let Some(mut key_processor) = self.xkb_context.key_context() {
-                let event = key_processor.process_key_event(keycode, state, repeat);
+                let (event, compose) = key_processor.process_key_event(keycode, state, repeat);
+                if let Some(compose) = compose {
+                    let event =
+                        Event::WindowEvent { window_id, event: WindowEvent::Compose(compose) };
+                    callback(&self.target, event);
+                }
                 let event = Event::WindowEvent {
                     window_id,
                     event: WindowEvent::KeyboardInput {
@@ -1031,7 +1150,7 @@ impl EventProcessor {
 
         let position = PhysicalPosition::new(event.event_x, event.event_y);
 
-        let event = match event.detail as u32 {
+        let window_event = match event.detail as u32 {
             xlib::Button1 => WindowEvent::PointerButton {
                 device_id,
                 state,
@@ -1066,6 +1185,7 @@ impl EventProcessor {
                     _ => unreachable!(),
                 },
                 phase: TouchPhase::Moved,
+                momentum: false,
             },
             8 => WindowEvent::PointerButton {
                 device_id,
@@ -1088,8 +1208,9 @@ impl EventProcessor {
             },
         };
 
-        let event = Event::WindowEvent { window_id, event };
-        callback(&self.target, event);
+        callback(&self.target, Event::WindowEvent { window_id, event: window_event });
+
+        self.xinput2_pen_input(event, Some((event.detail as u32, state)), callback);
     }
 
     fn xinput2_mouse_motion<F>(&self, event: &XIDeviceEvent, mut callback: F)
@@ -1137,6 +1258,7 @@ impl EventProcessor {
 
         let mut events = Vec::new();
         let mut value = event.valuators.values;
+        let mut scrolled = false;
         for i in 0..event.valuators.mask_len * 8 {
             if !xinput2::XIMaskIsSet(mask, i) {
                 continue;
@@ -1147,26 +1269,120 @@ impl EventProcessor {
             if let Some(&mut (_, ref mut info)) =
                 physical_device.scroll_axes.iter_mut().find(|&&mut (axis, _)| axis == i as _)
             {
-                let delta = (x - info.position) / info.increment;
+                let delta = x - info.position;
                 info.position = x;
-                // X11 vertical scroll coordinates are opposite to winit's
-                let delta = match info.orientation {
-                    ScrollOrientation::Horizontal => {
-                        MouseScrollDelta::LineDelta(-delta as f32, 0.0)
-                    },
-                    ScrollOrientation::Vertical => MouseScrollDelta::LineDelta(0.0, -delta as f32),
+                scrolled = true;
+
+                // The valuators of touchpads are in pixels, while wheels have a valuator
+                // increment per notch. X11 vertical scroll coordinates are opposite to winit's.
+                let (delta, phase) = if physical_device.touchpad {
+                    let (x, y) = match info.orientation {
+                        ScrollOrientation::Horizontal => (-delta, 0.0),
+                        ScrollOrientation::Vertical => (0.0, -delta),
+                    };
+                    let phase = if mem::replace(&mut physical_device.scrolling, true) {
+                        TouchPhase::Moved
+                    } else {
+                        TouchPhase::Started
+                    };
+                    (MouseScrollDelta::PixelDelta(PhysicalPosition::new(x, y)), phase)
+                } else {
+                    let delta = delta / info.increment;
+                    let delta = match info.orientation {
+                        ScrollOrientation::Horizontal => {
+                            MouseScrollDelta::LineDelta(-delta as f32, 0.0)
+                        },
+                        ScrollOrientation::Vertical => {
+                            MouseScrollDelta::LineDelta(0.0, -delta as f32)
+                        },
+                    };
+                    (delta, TouchPhase::Moved)
                 };
 
-                let event = WindowEvent::MouseWheel { device_id, delta, phase: TouchPhase::Moved };
+                let event = WindowEvent::MouseWheel { device_id, delta, phase, momentum: false };
                 events.push(Event::WindowEvent { window_id, event });
             }
 
             value = unsafe { value.offset(1) };
         }
 
+        // X11 doesn't report the end of a scroll, so end it once the pointer moves instead.
+        if !scrolled && mem::take(&mut physical_device.scrolling) {
+            let event = WindowEvent::MouseWheel {
+                device_id,
+                delta: MouseScrollDelta::PixelDelta(PhysicalPosition::new(0.0, 0.0)),
+                phase: TouchPhase::Ended,
+                momentum: false,
+            };
+            events.push(Event::WindowEvent { window_id, event });
+        }
+
         for event in events {
             callback(&self.target, event);
         }
+
+        drop(devices);
+        self.xinput2_pen_input(event, None, callback);
+    }
+
+    /// Deliver the state of the pen which is the source of `event`, if it's a pen.
+    ///
+    /// The button mask of the button events doesn't contain the button which changed yet, so it's
+    /// passed as `button`.
+    fn xinput2_pen_input<F>(
+        &self,
+        event: &XIDeviceEvent,
+        button: Option<(u32, ElementState)>,
+        mut callback: F,
+    ) where
+        F: FnMut(&ActiveEventLoop, Event),
+    {
+        let mut devices = self.devices.borrow_mut();
+        let Some(pen) = devices
+            .get_mut(&mkdid(event.sourceid as xinput::DeviceId))
+            .and_then(|device| device.pen.as_mut())
+        else {
+            return;
+        };
+
+        let mask = unsafe {
+            slice::from_raw_parts(event.valuators.mask, event.valuators.mask_len as usize)
+        };
+        let mut value = event.valuators.values;
+        for i in 0..event.valuators.mask_len * 8 {
+            if !xinput2::XIMaskIsSet(mask, i) {
+                continue;
+            }
+            if let Some(axis) = pen.axes_mut().find(|axis| axis.number == i) {
+                axis.value = unsafe { *value };
+            }
+            value = unsafe { value.offset(1) };
+        }
+
+        let buttons =
+            unsafe { slice::from_raw_parts(event.buttons.mask, event.buttons.mask_len as usize) };
+        let is_pressed = |number: u32| match button {
+            Some((button, state)) if button == number => state == ElementState::Pressed,
+            _ => xinput2::XIMaskIsSet(buttons, number as i32),
+        };
+        let contact = is_pressed(xlib::Button1);
+        let mut barrel_buttons = PenButtons::empty();
+        barrel_buttons.set(PenButtons::PRIMARY, is_pressed(xlib::Button2));
+        barrel_buttons.set(PenButtons::SECONDARY, is_pressed(xlib::Button3));
+
+        let window_id = mkwid(event.event as xproto::Window);
+        let pen_event = WindowEvent::PenInput {
+            device_id: Some(mkdid(event.deviceid as xinput::DeviceId)),
+            position: PhysicalPosition::new(event.event_x, event.event_y),
+            contact,
+            pressure: pen.pressure(contact),
+            tilt: pen.tilt(),
+            twist: None,
+            inverted: pen.eraser,
+            barrel_buttons,
+        };
+        drop(devices);
+        callback(&self.target, Event::WindowEvent { window_id, event: pen_event });
     }
 
     fn xinput2_mouse_enter<F>(&self, event: &XIEnterEvent, mut callback: F)
@@ -1261,13 +1477,23 @@ impl EventProcessor {
         let window_id = mkwid(window);
         let position = PhysicalPosition::new(xev.event_x, xev.event_y);
 
-        if let Some(window) = self.with_window(window, Arc::clone) {
+        let window = self.with_window(window, Arc::clone);
+        if let Some(window) = window.as_ref() {
             window.shared_state_lock().has_focus = true;
+            if let Err(err) = window.update_gamma_ramp() {
+                tracing::warn!("Failed to apply the gamma ramp: {err}");
+            }
         }
 
         let event = Event::WindowEvent { window_id, event: WindowEvent::Focused(true) };
         callback(&self.target, event);
 
+        if let Some(mode) = window.and_then(|window| window.resume_cursor_grab()) {
+            let event =
+                Event::WindowEvent { window_id, event: WindowEvent::CursorGrabChanged(mode) };
+            callback(&self.target, event);
+        }
+
         // Issue key press events for all pressed keys
         Self::handle_pressed_keys(
             &self.target,
@@ -1336,15 +1562,73 @@ impl EventProcessor {
             // window regains focus.
             self.held_key_press = None;
 
+            let mut grab_released = false;
             if let Some(window) = self.with_window(window, Arc::clone) {
                 window.shared_state_lock().has_focus = false;
+                grab_released = window.suspend_cursor_grab();
+                if let Err(err) = window.update_gamma_ramp() {
+                    tracing::warn!("Failed to restore the gamma ramp: {err}");
+                }
             }
 
             let event = Event::WindowEvent { window_id, event: WindowEvent::Focused(false) };
-            callback(&self.target, event)
+            callback(&self.target, event);
+
+            if grab_released {
+                let event = Event::WindowEvent {
+                    window_id,
+                    event: WindowEvent::CursorGrabChanged(CursorGrabMode::None),
+                };
+                callback(&self.target, event);
+            }
         }
     }
 
+    fn xinput2_gesture_pinch<F>(
+        &mut self,
+        xev: &ffi::XIGesturePinchEvent,
+        evtype: i32,
+        mut callback: F,
+    ) where
+        F: FnMut(&ActiveEventLoop, Event),
+    {
+        // Set the timestamp.
+        self.target.xconn.set_timestamp(xev.time as xproto::Timestamp);
+
+        let window = xev.event as xproto::Window;
+        if !self.window_exists(window) {
+            return;
+        }
+
+        let (phase, pinch_delta, rotation_delta) = match evtype {
+            ffi::XI_GesturePinchBegin => {
+                self.pinch_scale = 1.0;
+                (TouchPhase::Started, 0.0, 0.0)
+            },
+            ffi::XI_GesturePinchUpdate => {
+                let pinch_delta = xev.scale - self.pinch_scale;
+                self.pinch_scale = xev.scale;
+                // The rotation is reported clockwise.
+                (TouchPhase::Moved, pinch_delta, -xev.delta_angle as f32)
+            },
+            _ if xev.flags & ffi::XIGesturePinchEventCancelled != 0 => {
+                (TouchPhase::Cancelled, 0.0, 0.0)
+            },
+            _ => (TouchPhase::Ended, 0.0, 0.0),
+        };
+
+        let window_id = mkwid(window);
+        let device_id = Some(mkdid(xev.deviceid as xinput::DeviceId));
+        callback(&self.target, Event::WindowEvent {
+            window_id,
+            event: WindowEvent::PinchGesture { device_id, delta: pinch_delta, phase },
+        });
+        callback(&self.target, Event::WindowEvent {
+            window_id,
+            event: WindowEvent::RotationGesture { device_id, delta: rotation_delta, phase },
+        });
+    }
+
     fn xinput2_touch<F>(&mut self, xev: &XIDeviceEvent, phase: i32, mut callback: F)
     where
         F: FnMut(&ActiveEventLoop, Event),
@@ -1374,6 +1658,7 @@ impl EventProcessor {
 
             let device_id = Some(mkdid(xev.deviceid as xinput::DeviceId));
             let finger_id = mkfid(id);
+            let (force, contact) = self.xinput2_touch_axes(xev);
 
             match phase {
                 xinput2::XI_TouchBegin => {
@@ -1392,7 +1677,7 @@ impl EventProcessor {
                             device_id,
                             state: ElementState::Pressed,
                             position,
-                            button: ButtonSource::Touch { finger_id, force: None },
+                            button: ButtonSource::Touch { finger_id, force, contact },
                         },
                     };
                     callback(&self.target, event);
@@ -1403,7 +1688,7 @@ impl EventProcessor {
                         event: WindowEvent::PointerMoved {
                             device_id,
                             position,
-                            source: PointerSource::Touch { finger_id, force: None },
+                            source: PointerSource::Touch { finger_id, force, contact },
                         },
                     };
                     callback(&self.target, event);
@@ -1415,7 +1700,7 @@ impl EventProcessor {
                             device_id,
                             state: ElementState::Released,
                             position,
-                            button: ButtonSource::Touch { finger_id, force: None },
+                            button: ButtonSource::Touch { finger_id, force, contact },
                         },
                     };
                     callback(&self.target, event);
@@ -1434,6 +1719,34 @@ impl EventProcessor {
         }
     }
 
+    /// The force and contact of a touch, from the valuators of the touchscreen.
+    fn xinput2_touch_axes(&self, xev: &XIDeviceEvent) -> (Option<Force>, Option<TouchContact>) {
+        let mut devices = self.devices.borrow_mut();
+        let Some(touch) = devices
+            .get_mut(&mkdid(xev.sourceid as xinput::DeviceId))
+            .and_then(|device| device.touch.as_mut())
+        else {
+            return (None, None);
+        };
+
+        // The server sends all the valuators of the touch in each of its events.
+        let mask =
+            unsafe { slice::from_raw_parts(xev.valuators.mask, xev.valuators.mask_len as usize) };
+        let mut value = xev.valuators.values;
+        for i in 0..xev.valuators.mask_len * 8 {
+            if !xinput2::XIMaskIsSet(mask, i) {
+                continue;
+            }
+            if let Some(axis) = touch.axes_mut().find(|axis| axis.number == i) {
+                axis.value = unsafe { *value };
+            }
+            value = unsafe { value.offset(1) };
+        }
+
+        let screen_width = self.target.xconn.default_root().width_in_pixels;
+        (touch.force(), touch.contact(screen_width))
+    }
+
     fn xinput2_raw_button_input<F>(&self, xev: &XIRawEvent, state: ElementState, mut callback: F)
     where
         F: FnMut(&ActiveEventLoop, Event),
@@ -1522,16 +1835,24 @@ impl EventProcessor {
         });
     }
 
-    fn xinput2_hierarchy_changed(&mut self, xev: &XIHierarchyEvent) {
+    fn xinput2_hierarchy_changed<F>(&mut self, xev: &XIHierarchyEvent, mut callback: F)
+    where
+        F: FnMut(&ActiveEventLoop, Event),
+    {
         // Set the timestamp.
         self.target.xconn.set_timestamp(xev.time as xproto::Timestamp);
         let infos = unsafe { slice::from_raw_parts(xev.info, xev.num_info as usize) };
         for info in infos {
+            let device_id = info.deviceid as xinput::DeviceId;
             if 0 != info.flags & (xinput2::XISlaveAdded | xinput2::XIMasterAdded) {
-                self.init_device(info.deviceid as xinput::DeviceId);
+                self.init_device(device_id);
+                let devices = self.target.xconn.input_devices(device_id).unwrap_or_default();
+                for device in devices {
+                    callback(&self.target, Event::InputDeviceAdded(device));
+                }
             } else if 0 != info.flags & (xinput2::XISlaveRemoved | xinput2::XIMasterRemoved) {
-                let mut devices = self.devices.borrow_mut();
-                devices.remove(&mkdid(info.deviceid as xinput::DeviceId));
+                self.devices.borrow_mut().remove(&mkdid(device_id));
+                callback(&self.target, Event::InputDeviceRemoved(mkdid(device_id)));
             }
         }
     }
@@ -1559,6 +1880,7 @@ impl EventProcessor {
                     let xcb = self.target.xconn.xcb_connection().get_raw_xcb_connection();
                     self.xkb_context.set_keymap_from_x11(xcb);
                     self.xmodmap.reload_from_x_connection(&self.target.xconn);
+                    self.update_keyboard_layout(true, &mut callback);
 
                     let window_id = match self.active_window.map(super::mkwid) {
                         Some(window_id) => window_id,
@@ -1575,6 +1897,7 @@ impl EventProcessor {
                 let xcb = self.target.xconn.xcb_connection().get_raw_xcb_connection();
                 self.xkb_context.set_keymap_from_x11(xcb);
                 self.xmodmap.reload_from_x_connection(&self.target.xconn);
+                self.update_keyboard_layout(true, &mut callback);
                 let window_id = match self.active_window.map(super::mkwid) {
                     Some(window_id) => window_id,
                     None => return,
@@ -1600,13 +1923,14 @@ impl EventProcessor {
                         xev.latched_group as u32,
                         xev.locked_group as u32,
                     );
+                    let mods = state.modifiers().into();
+                    self.update_keyboard_layout(false, &mut callback);
 
                     let window_id = match self.active_window.map(super::mkwid) {
                         Some(window_id) => window_id,
                         None => return,
                     };
 
-                    let mods = state.modifiers().into();
                     self.send_modifiers(window_id, mods, true, &mut callback);
                 }
             },
@@ -1774,6 +2098,30 @@ impl EventProcessor {
         }
     }
 
+    /// Report a change of the keyboard layout to the focused window, and update the keys of the
+    /// layout when it or the keymap changed.
+    fn update_keyboard_layout<F: FnMut(&ActiveEventLoop, Event)>(
+        &mut self,
+        keymap_changed: bool,
+        callback: &mut F,
+    ) {
+        let layout = self.xkb_context.keyboard_layout();
+        let layout_changed = *self.target.keyboard_layout.borrow() != layout;
+        if keymap_changed || layout_changed {
+            self.target.layout_keys.replace(self.xkb_context.layout_keys());
+        }
+        if !layout_changed {
+            return;
+        }
+        self.target.keyboard_layout.replace(layout.clone());
+
+        if let (Some(window_id), Some(layout)) = (self.active_window.map(super::mkwid), layout) {
+            let event =
+                Event::WindowEvent { window_id, event: WindowEvent::KeyboardLayoutChanged(layout) };
+            callback(&self.target, event);
+        }
+    }
+
     fn handle_pressed_keys<F>(
         target: &ActiveEventLoop,
         window_id: crate::window::WindowId,
@@ -1802,7 +2150,7 @@ impl EventProcessor {
         };
 
         for keycode in target.xconn.query_keymap().into_iter().filter(|k| *k >= KEYCODE_OFFSET) {
-            let event = key_processor.process_key_event(keycode as u32, state, false);
+            let (event, _) = key_processor.process_key_event(keycode as u32, state, false);
             let event = Event::WindowEvent {
                 window_id,
                 event: WindowEvent::KeyboardInput { device_id: None, event, is_synthetic: true },
@@ -1817,7 +2165,6 @@ impl EventProcessor {
     {
         self.target.xconn.reload_database().expect("failed to reload Xft database");
 
-        // In the future, it would be quite easy to emit monitor hotplug events.
         let prev_list = {
             let prev_list = self.target.xconn.invalidate_cached_monitor_list();
             match prev_list {
@@ -1827,6 +2174,27 @@ impl EventProcessor {
         };
 
         let new_list = self.target.xconn.available_monitors().expect("Failed to get monitor list");
+        let root_monitor = |inner| RootMonitorHandle { inner: PlatformMonitorHandle::X(inner) };
+        for monitor in prev_list.iter().filter(|monitor| !new_list.contains(monitor)) {
+            callback(&self.target, Event::MonitorRemoved(root_monitor(monitor.clone())));
+        }
+        for monitor in new_list.iter().filter(|monitor| !prev_list.contains(monitor)) {
+            callback(&self.target, Event::MonitorAdded(root_monitor(monitor.clone())));
+        }
+        for monitor in new_list.iter().filter(|monitor| {
+            prev_list.iter().any(|prev| prev == *monitor && prev.orientation != monitor.orientation)
+        }) {
+            callback(&self.target, Event::MonitorOrientationChanged(root_monitor(monitor.clone())));
+        }
+        for monitor in new_list.iter().filter(|monitor| {
+            prev_list.iter().any(|prev| {
+                prev == *monitor
+                    && prev.refresh_rate_millihertz() != monitor.refresh_rate_millihertz()
+            })
+        }) {
+            callback(&self.target, Event::MonitorRefreshRateChanged(root_monitor(monitor.clone())));
+        }
+
         for new_monitor in new_list {
             // Previous list may be empty, in case of disconnecting and
             // reconnecting the only one monitor. We still need to emit events in
@@ -1845,11 +2213,102 @@ impl EventProcessor {
         }
     }
 
+    fn xrandr_notify<F>(&self, xev: &XRROutputPropertyNotifyEvent, callback: &mut F)
+    where
+        F: FnMut(&ActiveEventLoop, Event),
+    {
+        let atoms = self.target.xconn.atoms();
+        if xev.subtype != xrandr::RRNotify_OutputProperty
+            || xev.property as xproto::Atom != atoms[_ICC_PROFILE]
+        {
+            return;
+        }
+
+        // Only the windows on the monitor of the output see a different profile.
+        let output = xev.output as randr::Output;
+        for window in self.target.windows.borrow().values().filter_map(|w| w.upgrade()) {
+            let on_output = window.current_monitor().is_some_and(|monitor| {
+                self.target
+                    .xconn
+                    .get_crtc_outputs(monitor.id)
+                    .is_ok_and(|outputs| outputs.contains(&output))
+            });
+            if on_output {
+                let event = Event::WindowEvent {
+                    window_id: window.id(),
+                    event: WindowEvent::ColorProfileChanged,
+                };
+                callback(&self.target, event);
+            }
+        }
+    }
+
+    /// Move the drag between the windows to the pointer, or drop it there when `released`.
+    fn update_drag<F>(&self, released: bool, callback: &mut F)
+    where
+        F: FnMut(&ActiveEventLoop, Event),
+    {
+        // The application could start another drag from the callback.
+        let Some(mut drag) = self.target.drag.take() else { return };
+
+        let target = self.window_under_pointer();
+        let mut events = Vec::new();
+        let emit = |window_id, event| events.push(Event::WindowEvent { window_id, event });
+        if released {
+            if drag.image().is_some() {
+                let source = drag.source().into_raw() as xproto::Window;
+                self.with_window(source, |window| window.set_drag_cursor(None));
+            }
+            drag.released(target, emit);
+        } else {
+            drag.moved(target, emit);
+            self.target.drag.replace(Some(drag));
+        }
+
+        for event in events {
+            callback(&self.target, event);
+        }
+    }
+
+    /// The window under the pointer, with the position of the pointer in it.
+    ///
+    /// Descends from the root window, as the pointer is grabbed by the window the drag started
+    /// from.
+    fn window_under_pointer(&self) -> Option<(WindowId, PhysicalPosition<f64>)> {
+        let mut window = self.target.root;
+        loop {
+            let pointer =
+                self.target.xconn.query_pointer(window, util::VIRTUAL_CORE_POINTER).ok()?;
+            if self.window_exists(window) {
+                let position = PhysicalPosition::new(
+                    xinput_fp1616_to_float(pointer.win_x),
+                    xinput_fp1616_to_float(pointer.win_y),
+                );
+                return Some((mkwid(window), position));
+            }
+            if pointer.child == x11rb::NONE {
+                return None;
+            }
+            window = pointer.child;
+        }
+    }
+
     fn window_exists(&self, window_id: xproto::Window) -> bool {
         self.with_window(window_id, |_| ()).is_some()
     }
 }
 
+/// The modifiers of a core modifier mask, with Alt and Super on their usual modifiers.
+fn modifiers_of_mask(mask: u32) -> ModifiersState {
+    let mask = mask as u16;
+    let mut modifiers = ModifiersState::empty();
+    modifiers.set(ModifiersState::SHIFT, ModMask::SHIFT.intersects(mask));
+    modifiers.set(ModifiersState::CONTROL, ModMask::CONTROL.intersects(mask));
+    modifiers.set(ModifiersState::ALT, ModMask::M1.intersects(mask));
+    modifiers.set(ModifiersState::SUPER, ModMask::M4.intersects(mask));
+    modifiers
+}
+
 fn is_first_touch(first: &mut Option<u32>, num: &mut u32, id: u32, phase: i32) -> bool {
     match phase {
         xinput2::XI_TouchBegin => {
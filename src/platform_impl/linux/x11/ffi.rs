@@ -1,4 +1,47 @@
+#![allow(non_upper_case_globals)]
+
+use std::ffi::c_ulong;
+
 pub use x11_dl::error::OpenError;
 pub use x11_dl::xinput2::*;
 pub use x11_dl::xlib::*;
 pub use x11_dl::xlib_xcb::*;
+
+// The gesture events of XInput 2.4, which aren't part of `x11_dl` yet.
+
+pub const XI_GesturePinchBegin: i32 = 27;
+pub const XI_GesturePinchUpdate: i32 = 28;
+pub const XI_GesturePinchEnd: i32 = 29;
+
+pub const XIGesturePinchEventCancelled: i32 = 1 << 0;
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct XIGesturePinchEvent {
+    pub _type: i32,
+    pub serial: c_ulong,
+    pub send_event: Bool,
+    pub display: *mut Display,
+    pub extension: i32,
+    pub evtype: i32,
+    pub time: Time,
+    pub deviceid: i32,
+    pub sourceid: i32,
+    pub detail: i32,
+    pub root: Window,
+    pub event: Window,
+    pub child: Window,
+    pub root_x: f64,
+    pub root_y: f64,
+    pub event_x: f64,
+    pub event_y: f64,
+    pub delta_x: f64,
+    pub delta_y: f64,
+    pub delta_unaccel_x: f64,
+    pub delta_unaccel_y: f64,
+    pub scale: f64,
+    pub delta_angle: f64,
+    pub flags: i32,
+    pub mods: XIModifierState,
+    pub group: XIGroupState,
+}
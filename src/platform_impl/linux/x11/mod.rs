@@ -23,24 +23,53 @@ use x11rb::x11_utils::X11Error as LogicalError;
 use x11rb::xcb_ffi::ReplyOrIdError;
 
 use crate::application::ApplicationHandler;
+use crate::clipboard::Clipboard as RootClipboard;
+#[cfg(feature = "dialogs")]
+use crate::dialogs::{
+    FileDialogKind, FileDialogOptions, FileDialogResult, MessageBoxConfig, MessageBoxResult,
+    ModalBlocks,
+};
+use crate::drag::{DragData, DragSession};
+#[cfg(feature = "gamepad")]
+use crate::error::NotSupportedError;
 use crate::error::{EventLoopError, RequestError};
-use crate::event::{DeviceId, Event, StartCause, WindowEvent};
+#[cfg(any(feature = "gamepad", feature = "winit-test-harness"))]
+use crate::event::DeviceEvent;
+use crate::event::{
+    DeviceId, DeviceInfo as RootDeviceInfo, Event, Force, LanguageTag, PenTilt, PlatformData,
+    PowerEvent, PowerState, StartCause, SystemPreferences, TouchContact, WindowEvent,
+};
+#[cfg(feature = "dialogs")]
+use crate::event_loop::AsyncRequestSerial;
 use crate::event_loop::{
-    ActiveEventLoop as RootActiveEventLoop, ControlFlow, DeviceEvents,
-    OwnedDisplayHandle as RootOwnedDisplayHandle,
+    ActiveEventLoop as RootActiveEventLoop, ControlFlow, DeviceClass, DeviceEvents, ExitResponse,
+    ModifiersOrder, OwnedDisplayHandle as RootOwnedDisplayHandle,
 };
+use crate::keyboard::{Key, KeyCode, KeyboardLayout, Led};
 use crate::platform::pump_events::PumpStatus;
-use crate::platform_impl::common::xkb::Context;
+use crate::platform_impl::coalescing::MotionCoalescer;
+#[cfg(feature = "gamepad")]
+use crate::platform_impl::common::gamepad::{GamepadMessage, GamepadWatcher};
+use crate::platform_impl::common::logind::{SessionEvent, SessionWatcher};
+use crate::platform_impl::common::modifiers::ModifiersOrderer;
+use crate::platform_impl::common::power;
+use crate::platform_impl::common::preferences::{PreferencesChange, PreferencesWatcher};
+use crate::platform_impl::common::termination::SigtermHandler;
+use crate::platform_impl::common::xkb::{Context, LayoutKeys};
+#[cfg(feature = "dialogs")]
+use crate::platform_impl::common::{message_box, portal};
 use crate::platform_impl::platform::min_timeout;
 use crate::platform_impl::x11::window::Window;
 use crate::platform_impl::{OwnedDisplayHandle, PlatformCustomCursor};
+use crate::utils::RedrawSchedule;
 use crate::window::{
-    CustomCursor as RootCustomCursor, CustomCursorSource, Theme, Window as CoreWindow,
-    WindowAttributes, WindowId,
+    Coalescing, Cursor, CursorGrabMode, CursorThemeInfo, CustomCursor as RootCustomCursor,
+    CustomCursorSource, Rgba, Theme, Window as CoreWindow, WindowAttributes, WindowId,
 };
 
 mod activation;
 mod atoms;
+mod clipboard;
 mod dnd;
 mod event_processor;
 pub mod ffi;
@@ -52,6 +81,7 @@ mod xdisplay;
 mod xsettings;
 
 use atoms::*;
+use clipboard::Clipboard;
 use dnd::{Dnd, DndState};
 use event_processor::{EventProcessor, MAX_MOD_REPLAY_LEN};
 use ime::{Ime, ImeCreationError, ImeReceiver, ImeRequest, ImeSender};
@@ -138,9 +168,31 @@ pub struct ActiveEventLoop {
     ime: Option<RefCell<Ime>>,
     windows: RefCell<HashMap<WindowId, Weak<UnownedWindow>>>,
     redraw_sender: WakeSender<WindowId>,
+    scheduled_redraw_sender: WakeSender<(WindowId, Instant)>,
     activation_sender: WakeSender<ActivationToken>,
+    /// Sender of the events injected by the tests.
+    #[cfg(feature = "winit-test-harness")]
+    injected_sender: WakeSender<Event>,
+    #[cfg(feature = "dialogs")]
+    dialog_sender: WakeSender<DialogDone>,
+    #[cfg(feature = "accesskit")]
+    accesskit_sender: WakeSender<(WindowId, accesskit::ActionRequest)>,
+    #[cfg(feature = "dialogs")]
+    modal_blocks: RefCell<ModalBlocks>,
     event_loop_proxy: EventLoopProxy,
     device_events: Cell<DeviceEvents>,
+    device_classes: Cell<DeviceClass>,
+    #[cfg(feature = "gamepad")]
+    gamepad_watcher: Option<GamepadWatcher>,
+    preferences_watcher: Option<PreferencesWatcher>,
+    /// The keyboard layout, kept up to date by the event processor.
+    keyboard_layout: RefCell<Option<KeyboardLayout>>,
+    layout_keys: RefCell<LayoutKeys>,
+    /// The drag between the windows, driven by the pointer events.
+    drag: RefCell<Option<DragSession>>,
+    /// Whether the server reports the touchpad gestures.
+    xi2_gestures: bool,
+    clipboard: Clipboard,
 }
 
 pub struct EventLoop {
@@ -148,7 +200,22 @@ pub struct EventLoop {
     event_loop: Loop<'static, EventLoopState>,
     event_processor: EventProcessor,
     redraw_receiver: PeekableReceiver<WindowId>,
+    scheduled_redraw_receiver: PeekableReceiver<(WindowId, Instant)>,
+    redraw_schedule: RedrawSchedule,
     activation_receiver: PeekableReceiver<ActivationToken>,
+    #[cfg(feature = "winit-test-harness")]
+    injected_receiver: PeekableReceiver<Event>,
+    #[cfg(feature = "dialogs")]
+    dialog_receiver: PeekableReceiver<DialogDone>,
+    #[cfg(feature = "accesskit")]
+    accesskit_receiver: PeekableReceiver<(WindowId, accesskit::ActionRequest)>,
+    session_receiver: PeekableReceiver<SessionEvent>,
+    _session_watcher: Option<SessionWatcher>,
+    preferences_receiver: PeekableReceiver<PreferencesChange>,
+    #[cfg(feature = "gamepad")]
+    gamepad_receiver: PeekableReceiver<GamepadMessage>,
+    motion_coalescer: MotionCoalescer,
+    modifiers_orderer: ModifiersOrderer,
 
     /// The current state of the event loop.
     state: EventLoopState,
@@ -156,16 +223,26 @@ pub struct EventLoop {
 
 type ActivationToken = (WindowId, crate::event_loop::AsyncRequestSerial);
 
+/// The outcome of a dialog shown from another thread.
+#[cfg(feature = "dialogs")]
+enum DialogDone {
+    File(crate::event_loop::AsyncRequestSerial, FileDialogResult),
+    MessageBox(crate::event_loop::AsyncRequestSerial, MessageBoxResult),
+}
+
 struct EventLoopState {
     /// The latest readiness state for the x11 file descriptor
     x11_readiness: Readiness,
 
     /// User requested a wake up.
     proxy_wake_up: bool,
+
+    /// The process received `SIGTERM`.
+    exit_requested: bool,
 }
 
 impl EventLoop {
-    pub(crate) fn new(xconn: Arc<XConnection>) -> EventLoop {
+    pub(crate) fn new(xconn: Arc<XConnection>, modifiers_order: ModifiersOrder) -> EventLoop {
         let root = xconn.default_root().root;
         let atoms = xconn.atoms();
 
@@ -176,6 +253,9 @@ impl EventLoop {
         let dnd = Dnd::new(Arc::clone(&xconn))
             .expect("Failed to call XInternAtoms when initializing drag and drop");
 
+        let clipboard = Clipboard::new(Arc::clone(&xconn), root)
+            .expect("Failed to create the window owning the clipboard");
+
         let (ime_sender, ime_receiver) = mpsc::channel();
         let (ime_event_sender, ime_event_receiver) = mpsc::channel();
         // Input methods will open successfully without setting the locale, but it won't be
@@ -213,6 +293,8 @@ impl EventLoop {
 
         let randr_event_offset =
             xconn.select_xrandr_input(root).expect("Failed to query XRandR extension");
+        // The monitors are compared with this list when they change.
+        let _ = xconn.available_monitors();
 
         let xi2ext = xconn
             .xcb_connection()
@@ -226,12 +308,14 @@ impl EventLoop {
             .expect("X server missing XKB extension");
 
         // Check for XInput2 support.
-        xconn
+        let xi2_version = xconn
             .xcb_connection()
-            .xinput_xi_query_version(2, 3)
+            .xinput_xi_query_version(2, 4)
             .expect("Failed to send XInput2 query version request")
             .reply()
             .expect("Error while checking for XInput2 query version reply");
+        // The touchpad gestures were introduced in XInput 2.4.
+        let xi2_gestures = (xi2_version.major_version, xi2_version.minor_version) >= (2, 4);
 
         xconn.update_cached_wm_info(root);
 
@@ -265,10 +349,50 @@ impl EventLoop {
 
         // Create a channel for handling redraw requests.
         let (redraw_sender, redraw_channel) = mpsc::channel();
+        let (scheduled_redraw_sender, scheduled_redraw_channel) = mpsc::channel();
 
         // Create a channel for sending activation tokens.
         let (activation_token_sender, activation_token_channel) = mpsc::channel();
 
+        // Create a channel for sending the events injected by the tests.
+        #[cfg(feature = "winit-test-harness")]
+        let (injected_sender, injected_channel) = mpsc::channel();
+
+        // Create a channel for sending the results of the dialogs.
+        #[cfg(feature = "dialogs")]
+        let (dialog_sender, dialog_channel) = mpsc::channel();
+
+        // Create a channel for sending the actions requested by the assistive technologies.
+        #[cfg(feature = "accesskit")]
+        let (accesskit_sender, accesskit_channel) = mpsc::channel();
+
+        // Create a channel for sending the changes of the session lock state and the sleep of the
+        // system.
+        let (session_sender, session_channel) = mpsc::channel();
+        let session_sender = WakeSender { sender: session_sender, waker: waker.clone() };
+        let session_watcher = SessionWatcher::new(move |event| session_sender.send(event))
+            .map_err(|error| tracing::debug!("can't watch the session: {error}"))
+            .ok();
+
+        // Create a channel for sending the changes of the accessibility preferences.
+        let (preferences_sender, preferences_channel) = mpsc::channel();
+        let preferences_sender = WakeSender { sender: preferences_sender, waker: waker.clone() };
+        let preferences_watcher =
+            PreferencesWatcher::new(move |change| preferences_sender.send(change))
+                .map_err(|error| tracing::debug!("can't watch the preferences: {error}"))
+                .ok();
+
+        // Create a channel for sending the input of the gamepads.
+        #[cfg(feature = "gamepad")]
+        let (gamepad_sender, gamepad_channel) = mpsc::channel();
+        #[cfg(feature = "gamepad")]
+        let gamepad_watcher = {
+            let gamepad_sender = WakeSender { sender: gamepad_sender, waker: waker.clone() };
+            GamepadWatcher::new(move |message| gamepad_sender.send(message))
+                .map_err(|error| tracing::debug!("can't watch the gamepads: {error}"))
+                .ok()
+        };
+
         // Create a channel for sending user events.
         let (user_waker, user_waker_source) =
             calloop::ping::make_ping().expect("Failed to create user event loop waker.");
@@ -281,7 +405,20 @@ impl EventLoop {
             .expect("Failed to register the event loop waker source");
         let event_loop_proxy = EventLoopProxy::new(user_waker);
 
-        let xkb_context =
+        // Listen for the termination requests.
+        if let Some(handler) = SigtermHandler::install() {
+            let source = Generic::new(handler, calloop::Interest::READ, calloop::Mode::Level);
+            event_loop
+                .handle()
+                .insert_source(source, |_, handler, state| {
+                    handler.drain();
+                    state.exit_requested = true;
+                    Ok(calloop::PostAction::Continue)
+                })
+                .expect("Failed to register the termination source");
+        }
+
+        let mut xkb_context =
             Context::from_x11_xkb(xconn.xcb_connection().get_raw_xcb_connection()).unwrap();
 
         let mut xmodmap = util::ModifierKeymap::new();
@@ -302,17 +439,48 @@ impl EventLoop {
                 sender: redraw_sender, // not used again so no clone
                 waker: waker.clone(),
             },
+            scheduled_redraw_sender: WakeSender {
+                sender: scheduled_redraw_sender, // not used again so no clone
+                waker: waker.clone(),
+            },
             activation_sender: WakeSender {
                 sender: activation_token_sender, // not used again so no clone
                 waker: waker.clone(),
             },
+            #[cfg(feature = "winit-test-harness")]
+            injected_sender: WakeSender {
+                sender: injected_sender, // not used again so no clone
+                waker: waker.clone(),
+            },
+            #[cfg(feature = "dialogs")]
+            dialog_sender: WakeSender {
+                sender: dialog_sender, // not used again so no clone
+                waker: waker.clone(),
+            },
+            #[cfg(feature = "accesskit")]
+            accesskit_sender: WakeSender {
+                sender: accesskit_sender, // not used again so no clone
+                waker: waker.clone(),
+            },
+            #[cfg(feature = "dialogs")]
+            modal_blocks: Default::default(),
             event_loop_proxy,
             device_events: Default::default(),
+            device_classes: Default::default(),
+            #[cfg(feature = "gamepad")]
+            gamepad_watcher,
+            preferences_watcher,
+            keyboard_layout: RefCell::new(xkb_context.keyboard_layout()),
+            layout_keys: RefCell::new(xkb_context.layout_keys()),
+            drag: RefCell::new(None),
+            xi2_gestures,
+            clipboard,
         };
 
         // Set initial device event filter.
         window_target.update_listen_device_events(true);
 
+        let cursor_theme = window_target.xconn.cursor_theme();
         let event_processor = EventProcessor {
             target: window_target,
             dnd,
@@ -328,9 +496,11 @@ impl EventLoop {
             num_touch: 0,
             held_key_press: None,
             first_touch: None,
+            pinch_scale: 1.0,
             active_window: None,
             modifiers: Default::default(),
             is_composing: false,
+            cursor_theme,
         };
 
         // Register for device hotplug events
@@ -363,8 +533,27 @@ impl EventLoop {
             event_loop,
             event_processor,
             redraw_receiver: PeekableReceiver::from_recv(redraw_channel),
+            scheduled_redraw_receiver: PeekableReceiver::from_recv(scheduled_redraw_channel),
+            redraw_schedule: RedrawSchedule::default(),
             activation_receiver: PeekableReceiver::from_recv(activation_token_channel),
-            state: EventLoopState { x11_readiness: Readiness::EMPTY, proxy_wake_up: false },
+            #[cfg(feature = "winit-test-harness")]
+            injected_receiver: PeekableReceiver::from_recv(injected_channel),
+            #[cfg(feature = "dialogs")]
+            dialog_receiver: PeekableReceiver::from_recv(dialog_channel),
+            #[cfg(feature = "accesskit")]
+            accesskit_receiver: PeekableReceiver::from_recv(accesskit_channel),
+            session_receiver: PeekableReceiver::from_recv(session_channel),
+            _session_watcher: session_watcher,
+            preferences_receiver: PeekableReceiver::from_recv(preferences_channel),
+            #[cfg(feature = "gamepad")]
+            gamepad_receiver: PeekableReceiver::from_recv(gamepad_channel),
+            motion_coalescer: MotionCoalescer::default(),
+            modifiers_orderer: ModifiersOrderer::new(modifiers_order),
+            state: EventLoopState {
+                x11_readiness: Readiness::EMPTY,
+                proxy_wake_up: false,
+                exit_requested: false,
+            },
         }
     }
 
@@ -437,9 +626,21 @@ impl EventLoop {
     }
 
     fn has_pending(&mut self) -> bool {
+        while let Ok((window_id, deadline)) = self.scheduled_redraw_receiver.try_recv() {
+            self.redraw_schedule.schedule(window_id, deadline);
+        }
+
+        #[cfg(feature = "winit-test-harness")]
+        if self.injected_receiver.has_incoming() {
+            return true;
+        }
+
         self.event_processor.poll()
             || self.state.proxy_wake_up
+            || self.state.exit_requested
             || self.redraw_receiver.has_incoming()
+            || self.redraw_schedule.is_due(Instant::now())
+            || self.event_processor.target.clipboard.has_done()
     }
 
     fn poll_events_with_timeout<A: ApplicationHandler>(
@@ -463,7 +664,12 @@ impl EventLoop {
                 },
             };
 
-            min_timeout(control_flow_timeout, timeout)
+            let redraw_timeout = self
+                .redraw_schedule
+                .next_deadline()
+                .map(|deadline| deadline.saturating_duration_since(start));
+
+            min_timeout(min_timeout(control_flow_timeout, redraw_timeout), timeout)
         };
 
         self.state.x11_readiness = Readiness::EMPTY;
@@ -542,11 +748,84 @@ impl EventLoop {
             }
         }
 
+        // Deliver the results of the dialogs.
+        #[cfg(feature = "dialogs")]
+        while let Ok(done) = self.dialog_receiver.try_recv() {
+            let target = &self.event_processor.target;
+            match done {
+                DialogDone::File(serial, result) => {
+                    target.modal_blocks.borrow_mut().unblock(serial);
+                    app.file_dialog_done(target, serial, result)
+                },
+                DialogDone::MessageBox(serial, result) => {
+                    target.modal_blocks.borrow_mut().unblock(serial);
+                    app.message_box_done(target, serial, result)
+                },
+            }
+        }
+
+        // Deliver the actions requested by the assistive technologies.
+        #[cfg(feature = "accesskit")]
+        while let Ok((window_id, request)) = self.accesskit_receiver.try_recv() {
+            let event = WindowEvent::AccessKitActionRequested(request);
+            app.window_event(&self.event_processor.target, window_id, event);
+        }
+
+        // Deliver the data read from the clipboard.
+        while let Some((serial, data)) = self.event_processor.target.clipboard.take_done() {
+            app.clipboard_data(&self.event_processor.target, serial, data);
+        }
+
+        while let Ok(event) = self.session_receiver.try_recv() {
+            let target = &self.event_processor.target;
+            match event {
+                SessionEvent::LockChanged(true) => app.session_locked(target),
+                SessionEvent::LockChanged(false) => app.session_unlocked(target),
+                SessionEvent::PrepareForSleep(true) => {
+                    app.power_event(target, PowerEvent::Suspending)
+                },
+                SessionEvent::PrepareForSleep(false) => {
+                    app.power_event(target, PowerEvent::Resumed)
+                },
+            }
+        }
+
+        while let Ok(change) = self.preferences_receiver.try_recv() {
+            let target = &self.event_processor.target;
+            match change {
+                PreferencesChange::System(preferences) => {
+                    app.system_preferences_changed(target, preferences)
+                },
+                PreferencesChange::TextScaleFactor(factor) => {
+                    app.text_scale_factor_changed(target, factor)
+                },
+            }
+        }
+
+        #[cfg(feature = "gamepad")]
+        while let Ok(message) = self.gamepad_receiver.try_recv() {
+            let target = &self.event_processor.target;
+            match message {
+                GamepadMessage::Added(device) => app.input_device_added(target, device),
+                GamepadMessage::Removed(device_id) => app.input_device_removed(target, device_id),
+                GamepadMessage::Event(device_id, event) if target.gamepad_events() => {
+                    app.device_event(target, Some(device_id), DeviceEvent::Gamepad(event))
+                },
+                GamepadMessage::Event(..) => (),
+            }
+        }
+
         // Empty the user event buffer
         if mem::take(&mut self.state.proxy_wake_up) {
             app.proxy_wake_up(&self.event_processor.target);
         }
 
+        if mem::take(&mut self.state.exit_requested)
+            && app.exit_requested(&self.event_processor.target) == ExitResponse::Exit
+        {
+            self.event_processor.target.exit();
+        }
+
         // Empty the redraw requests
         {
             let mut windows = HashSet::new();
@@ -554,8 +833,13 @@ impl EventLoop {
             while let Ok(window_id) = self.redraw_receiver.try_recv() {
                 windows.insert(window_id);
             }
+            windows.extend(self.redraw_schedule.take_due(Instant::now()));
 
             for window_id in windows {
+                for event in self.motion_coalescer.drain_window(window_id) {
+                    app.window_event(&self.event_processor.target, window_id, event);
+                }
+
                 app.window_event(
                     &self.event_processor.target,
                     window_id,
@@ -564,31 +848,106 @@ impl EventLoop {
             }
         }
 
+        // Deliver the motion of the windows which weren't redrawn.
+        for (window_id, event) in self.motion_coalescer.drain() {
+            app.window_event(&self.event_processor.target, window_id, event);
+        }
+
         // This is always the last event we dispatch before poll again
         app.about_to_wait(&self.event_processor.target);
     }
 
     fn drain_events<A: ApplicationHandler>(&mut self, app: &mut A) {
         let mut xev = MaybeUninit::uninit();
+        let motion_coalescer = &mut self.motion_coalescer;
+        let modifiers_orderer = &mut self.modifiers_orderer;
+
+        let mut dispatch = |window_target: &ActiveEventLoop, event: Event| match event {
+            Event::WindowEvent { window_id, event } => {
+                #[cfg(feature = "accesskit")]
+                window_target.update_accesskit_adapter(window_id, &event);
+
+                #[cfg(feature = "dialogs")]
+                let Some(event) = window_target.modal_blocks.borrow().filter(window_id, event) else {
+                    return;
+                };
+
+                let coalescing = window_target.motion_coalescing(window_id);
+                let Some(event) = motion_coalescer.push(window_id, event, coalescing) else {
+                    return;
+                };
+
+                if coalescing != Coalescing::PerFrame {
+                    for event in motion_coalescer.drain_window(window_id) {
+                        app.window_event(window_target, window_id, event);
+                    }
+                }
+
+                app.window_event(window_target, window_id, event)
+            },
+            Event::DeviceEvent { device_id, event } => {
+                app.device_event(window_target, device_id, event)
+            },
+            Event::InputDeviceAdded(device) => app.input_device_added(window_target, device),
+            Event::InputDeviceRemoved(device_id) => {
+                app.input_device_removed(window_target, device_id)
+            },
+            Event::ClipboardChanged => app.clipboard_changed(window_target),
+            Event::MonitorAdded(monitor) => app.monitor_added(window_target, monitor),
+            Event::MonitorRemoved(monitor) => app.monitor_removed(window_target, monitor),
+            Event::MonitorOrientationChanged(monitor) => {
+                app.monitor_orientation_changed(window_target, monitor)
+            },
+            Event::MonitorRefreshRateChanged(monitor) => {
+                app.monitor_refresh_rate_changed(window_target, monitor)
+            },
+            Event::CursorThemeChanged(theme) => app.cursor_theme_changed(window_target, theme),
+            Event::SystemPreferencesChanged(preferences) => {
+                app.system_preferences_changed(window_target, preferences)
+            },
+            Event::TextScaleFactorChanged(factor) => {
+                app.text_scale_factor_changed(window_target, factor)
+            },
+            _ => unreachable!("event which is neither device nor window event."),
+        };
+
+        let mut route = |window_target: &ActiveEventLoop, event: Event| match event {
+            Event::WindowEvent { window_id, event: WindowEvent::RedrawRequested } => {
+                window_target.redraw_sender.send(window_id);
+            },
+            Event::WindowEvent { window_id, event } => {
+                let events = modifiers_orderer.push(window_id, event);
+                for (window_id, event) in events.into_iter().flatten() {
+                    dispatch(window_target, Event::WindowEvent { window_id, event });
+                }
+            },
+            event => dispatch(window_target, event),
+        };
 
         while unsafe { self.event_processor.poll_one_event(xev.as_mut_ptr()) } {
             let mut xev = unsafe { xev.assume_init() };
-            self.event_processor.process_event(&mut xev, |window_target, event: Event| {
-                if let Event::WindowEvent { window_id, event: WindowEvent::RedrawRequested } = event
-                {
-                    window_target.redraw_sender.send(window_id);
-                } else {
-                    match event {
-                        Event::WindowEvent { window_id, event } => {
-                            app.window_event(window_target, window_id, event)
-                        },
-                        Event::DeviceEvent { device_id, event } => {
-                            app.device_event(window_target, device_id, event)
-                        },
-                        _ => unreachable!("event which is neither device nor window event."),
-                    }
-                }
-            });
+            self.event_processor.process_event(&mut xev, &mut route);
+        }
+
+        // The injected events follow the events of the server, as if they were received last.
+        #[cfg(feature = "winit-test-harness")]
+        while let Ok(event) = self.injected_receiver.try_recv() {
+            if let Event::WindowEvent { event: WindowEvent::ModifiersChanged(modifiers), .. } =
+                &event
+            {
+                self.event_processor.modifiers.set(modifiers.state());
+            }
+            route(&self.event_processor.target, event);
+        }
+
+        // Flush the event held back for the modifiers order.
+        if let Some((window_id, event)) = modifiers_orderer.flush() {
+            dispatch(&self.event_processor.target, Event::WindowEvent { window_id, event });
+        }
+
+        // Flush the motion which is coalesced only within a batch of events.
+        for (window_id, event) in motion_coalescer.drain_batch() {
+            app.window_event(&self.event_processor.target, window_id, event);
         }
     }
 
@@ -628,6 +987,30 @@ impl ActiveEventLoop {
         &self.xconn
     }
 
+    /// The motion coalescing of the given window.
+    fn motion_coalescing(&self, window_id: WindowId) -> Coalescing {
+        self.windows
+            .borrow()
+            .get(&window_id)
+            .and_then(Weak::upgrade)
+            .map(|window| window.motion_coalescing())
+            .unwrap_or_default()
+    }
+
+    #[cfg(feature = "accesskit")]
+    fn update_accesskit_adapter(&self, window_id: WindowId, event: &WindowEvent) {
+        if let Some(window) = self.windows.borrow().get(&window_id).and_then(Weak::upgrade) {
+            window.update_accesskit_adapter(event);
+        }
+    }
+
+    /// Whether the input of the gamepads is delivered.
+    #[cfg(feature = "gamepad")]
+    fn gamepad_events(&self) -> bool {
+        self.device_events.get() != DeviceEvents::Never
+            && self.device_classes.get().contains(DeviceClass::HID)
+    }
+
     /// Update the device event based on window focus.
     pub fn update_listen_device_events(&self, focus: bool) {
         let device_events = self.device_events.get() == DeviceEvents::Always
@@ -635,11 +1018,15 @@ impl ActiveEventLoop {
 
         let mut mask = xinput::XIEventMask::from(0u32);
         if device_events {
-            mask = xinput::XIEventMask::RAW_MOTION
-                | xinput::XIEventMask::RAW_BUTTON_PRESS
-                | xinput::XIEventMask::RAW_BUTTON_RELEASE
-                | xinput::XIEventMask::RAW_KEY_PRESS
-                | xinput::XIEventMask::RAW_KEY_RELEASE;
+            let classes = self.device_classes.get();
+            if classes.contains(DeviceClass::MOUSE) {
+                mask |= xinput::XIEventMask::RAW_MOTION
+                    | xinput::XIEventMask::RAW_BUTTON_PRESS
+                    | xinput::XIEventMask::RAW_BUTTON_RELEASE;
+            }
+            if classes.contains(DeviceClass::KEYBOARD) {
+                mask |= xinput::XIEventMask::RAW_KEY_PRESS | xinput::XIEventMask::RAW_KEY_RELEASE;
+            }
         }
 
         self.xconn
@@ -662,6 +1049,24 @@ impl ActiveEventLoop {
         Ok(display_handle.into())
     }
 
+    #[cfg(feature = "dialogs")]
+    fn show_file_dialog(
+        &self,
+        kind: FileDialogKind,
+        options: FileDialogOptions,
+    ) -> Result<AsyncRequestSerial, RequestError> {
+        let serial = AsyncRequestSerial::get();
+        let parent_window =
+            options.parent.map(|parent| format!("x11:{:x}", parent.into_raw())).unwrap_or_default();
+        let (modality, parent) = (options.modality, options.parent);
+        let sender = self.dialog_sender.clone();
+        portal::show_file_dialog(kind, options, parent_window, move |result| {
+            sender.send(DialogDone::File(serial, result))
+        })?;
+        self.modal_blocks.borrow_mut().block(serial, modality, parent);
+        Ok(serial)
+    }
+
     pub(crate) fn clear_exit(&self) {
         self.exit.set(None)
     }
@@ -723,10 +1128,208 @@ impl RootActiveEventLoop for ActiveEventLoop {
         None
     }
 
+    fn system_accent_color(&self) -> Option<Rgba> {
+        None
+    }
+
+    fn cursor_theme(&self) -> Option<CursorThemeInfo> {
+        Some(self.xconn.cursor_theme())
+    }
+
+    fn system_preferences(&self) -> Option<SystemPreferences> {
+        self.preferences_watcher.as_ref()?.preferences()
+    }
+
+    fn text_scale_factor(&self) -> Option<f64> {
+        self.preferences_watcher.as_ref()?.text_scale_factor()
+    }
+
+    fn power_state(&self) -> Option<PowerState> {
+        power::power_state()
+    }
+
+    fn locale(&self) -> LanguageTag {
+        LanguageTag::from_env()
+    }
+
+    fn keyboard_layout(&self) -> Option<KeyboardLayout> {
+        self.keyboard_layout.borrow().clone()
+    }
+
+    fn key_label(&self, key_code: KeyCode) -> Option<String> {
+        self.layout_keys.borrow().key(key_code)?.to_label()
+    }
+
+    fn key_for_keycode(&self, key_code: KeyCode) -> Option<Key> {
+        self.layout_keys.borrow().key(key_code).cloned()
+    }
+
+    fn keycode_for_key(&self, key: &Key) -> Option<KeyCode> {
+        self.layout_keys.borrow().key_code(key)
+    }
+
+    fn set_keyboard_led(&self, led: Led, on: bool) -> Result<(), RequestError> {
+        self.xconn.set_keyboard_led(led, on).map_err(|error| os_error!(error))?;
+        Ok(())
+    }
+
+    fn keyboard_led(&self, led: Led) -> Option<bool> {
+        self.xconn
+            .keyboard_led(led)
+            .map_err(|error| tracing::warn!("Failed to query the keyboard LED: {error}"))
+            .ok()
+            .flatten()
+    }
+
+    fn event_platform_data(&self) -> Option<PlatformData> {
+        None
+    }
+
+    #[cfg(feature = "winit-test-harness")]
+    fn inject_device_event(
+        &self,
+        device_id: Option<DeviceId>,
+        event: DeviceEvent,
+    ) -> Result<(), RequestError> {
+        self.injected_sender.send(Event::DeviceEvent { device_id, event });
+        Ok(())
+    }
+
     fn listen_device_events(&self, allowed: DeviceEvents) {
         self.device_events.set(allowed);
     }
 
+    fn listen_device_classes(&self, classes: DeviceClass) {
+        self.device_classes.set(classes);
+        let focus = self
+            .windows
+            .borrow()
+            .values()
+            .filter_map(Weak::upgrade)
+            .any(|window| window.has_focus());
+        self.update_listen_device_events(focus);
+    }
+
+    fn input_devices(&self) -> Vec<RootDeviceInfo> {
+        #[allow(unused_mut)]
+        let mut devices = self.xconn.input_devices(ALL_DEVICES).unwrap_or_else(|error| {
+            tracing::warn!("failed to query the input devices: {error}");
+            Vec::new()
+        });
+        #[cfg(feature = "gamepad")]
+        if let Some(watcher) = &self.gamepad_watcher {
+            devices.extend(watcher.gamepads());
+        }
+        devices
+    }
+
+    #[cfg(feature = "gamepad")]
+    fn set_gamepad_rumble(
+        &self,
+        device_id: DeviceId,
+        strong: f32,
+        weak: f32,
+    ) -> Result<(), RequestError> {
+        match &self.gamepad_watcher {
+            Some(watcher) => watcher.set_rumble(device_id, strong, weak),
+            None => Err(NotSupportedError::new("the gamepads can't be read").into()),
+        }
+    }
+
+    fn windows(&self) -> Vec<WindowId> {
+        self.windows
+            .borrow()
+            .iter()
+            .filter(|(_, window)| window.strong_count() > 0)
+            .map(|(&window_id, _)| window_id)
+            .collect()
+    }
+
+    fn with_native_modal_scope(&self, f: &mut dyn FnMut()) {
+        let windows: Vec<_> = self.windows.borrow().values().filter_map(Weak::upgrade).collect();
+
+        // Release the pointer grabs, so the dialog could be interacted with.
+        let grabs: Vec<_> = windows
+            .iter()
+            .map(|window| {
+                let mode = window.cursor_grab_mode();
+                let _ = window.set_cursor_grab(CursorGrabMode::None);
+                mode
+            })
+            .collect();
+
+        self.xconn
+            .select_xinput_events(self.root, ALL_MASTER_DEVICES, xinput::XIEventMask::from(0u32))
+            .expect_then_ignore_error("Failed to update device event filter");
+        let _ = self.xconn.flush_requests();
+
+        f();
+
+        for (window, mode) in windows.iter().zip(grabs) {
+            if let Err(err) = window.set_cursor_grab(mode) {
+                warn!("Failed to restore cursor grab: {err}");
+            }
+        }
+
+        let focus = windows.iter().any(|window| window.has_focus());
+        self.update_listen_device_events(focus);
+        let _ = self.xconn.flush_requests();
+    }
+
+    fn start_drag(&self, source: WindowId, data: DragData) -> Result<(), RequestError> {
+        if !self.windows.borrow().contains_key(&source) || self.drag.borrow().is_some() {
+            return Err(RequestError::Ignored);
+        }
+        self.drag.replace(Some(DragSession::new(source, data)));
+        Ok(())
+    }
+
+    fn set_drag_image(&self, image: Option<Cursor>) -> Result<(), RequestError> {
+        let mut drag = self.drag.borrow_mut();
+        let drag = drag.as_mut().ok_or(RequestError::Ignored)?;
+        if let Some(window) = self.windows.borrow().get(&drag.source()).and_then(Weak::upgrade) {
+            window.set_drag_cursor(image.as_ref());
+            let _ = self.xconn.flush_requests();
+        }
+        drag.set_image(image);
+        Ok(())
+    }
+
+    fn clipboard(&self) -> &dyn RootClipboard {
+        &self.clipboard
+    }
+
+    #[cfg(feature = "dialogs")]
+    fn pick_file(&self, options: FileDialogOptions) -> Result<AsyncRequestSerial, RequestError> {
+        self.show_file_dialog(FileDialogKind::PickFile, options)
+    }
+
+    #[cfg(feature = "dialogs")]
+    fn pick_folder(&self, options: FileDialogOptions) -> Result<AsyncRequestSerial, RequestError> {
+        self.show_file_dialog(FileDialogKind::PickFolder, options)
+    }
+
+    #[cfg(feature = "dialogs")]
+    fn save_file(&self, options: FileDialogOptions) -> Result<AsyncRequestSerial, RequestError> {
+        self.show_file_dialog(FileDialogKind::SaveFile, options)
+    }
+
+    #[cfg(feature = "dialogs")]
+    fn show_message_box(
+        &self,
+        config: MessageBoxConfig,
+    ) -> Result<AsyncRequestSerial, RequestError> {
+        let serial = AsyncRequestSerial::get();
+        let (modality, parent) = (config.modality, config.parent);
+        let sender = self.dialog_sender.clone();
+        let attach = parent.map(|parent| parent.into_raw() as u32);
+        message_box::show_message_box(config, attach, move |result| {
+            sender.send(DialogDone::MessageBox(serial, result))
+        })?;
+        self.modal_blocks.borrow_mut().block(serial, modality, parent);
+        Ok(serial)
+    }
+
     fn set_control_flow(&self, control_flow: ControlFlow) {
         self.control_flow.set(control_flow)
     }
@@ -1002,6 +1605,14 @@ fn mkfid(w: u32) -> crate::event::FingerId {
 pub struct Device {
     _name: String,
     scroll_axes: Vec<(i32, ScrollAxis)>,
+    // Touchpads scroll precisely, in phases.
+    touchpad: bool,
+    // Whether a scroll of the touchpad is in progress.
+    scrolling: bool,
+    // The valuators of the pens and styluses.
+    pen: Option<PenAxes>,
+    // The valuators of the touchscreens with multi-touch axes.
+    touch: Option<TouchAxes>,
     // For master devices, this is the paired device (pointer <-> keyboard).
     // For slave devices, this is the master.
     attachment: c_int,
@@ -1020,10 +1631,145 @@ enum ScrollOrientation {
     Horizontal,
 }
 
+#[derive(Debug, Default, Clone)]
+struct PenAxes {
+    pressure: Option<Valuator>,
+    tilt_x: Option<Valuator>,
+    tilt_y: Option<Valuator>,
+    // The `evdev` and `libinput` drivers expose the eraser end as a device of its own.
+    eraser: bool,
+}
+
+#[derive(Debug, Default, Clone)]
+struct TouchAxes {
+    pressure: Option<Valuator>,
+    major: Option<Valuator>,
+    minor: Option<Valuator>,
+    orientation: Option<Valuator>,
+    position_x: Option<Valuator>,
+}
+
+#[derive(Debug, Copy, Clone)]
+struct Valuator {
+    number: i32,
+    min: f64,
+    max: f64,
+    value: f64,
+}
+
+impl PenAxes {
+    fn axes_mut(&mut self) -> impl Iterator<Item = &mut Valuator> {
+        [&mut self.pressure, &mut self.tilt_x, &mut self.tilt_y].into_iter().flatten()
+    }
+
+    /// The pressure from `0.0` to `1.0`, or whether the pen touches the surface without a
+    /// pressure valuator.
+    fn pressure(&self, contact: bool) -> f32 {
+        match self.pressure {
+            Some(axis) if axis.max > axis.min => {
+                ((axis.value - axis.min) / (axis.max - axis.min)).clamp(0.0, 1.0) as f32
+            },
+            _ => contact as u8 as f32,
+        }
+    }
+
+    /// The tilt, whose valuators are in degrees with both drivers.
+    fn tilt(&self) -> Option<PenTilt> {
+        let (x, y) = (self.tilt_x?, self.tilt_y?);
+        Some(PenTilt { x: x.value.clamp(-90.0, 90.0) as f32, y: y.value.clamp(-90.0, 90.0) as f32 })
+    }
+}
+
+impl TouchAxes {
+    fn axes_mut(&mut self) -> impl Iterator<Item = &mut Valuator> {
+        [&mut self.pressure, &mut self.major, &mut self.minor, &mut self.orientation]
+            .into_iter()
+            .flatten()
+    }
+
+    fn force(&self) -> Option<Force> {
+        let axis = self.pressure.filter(|axis| axis.max > axis.min)?;
+        Some(Force::Normalized(((axis.value - axis.min) / (axis.max - axis.min)).clamp(0.0, 1.0)))
+    }
+
+    /// The contact, whose axes are in the units of the position valuators, which span the
+    /// `screen_width`.
+    fn contact(&self, screen_width: u16) -> Option<TouchContact> {
+        let major = self.major?.value;
+        let minor = self.minor.map_or(major, |axis| axis.value);
+        let scale = match self.position_x {
+            Some(axis) if axis.max > axis.min => screen_width as f64 / (axis.max - axis.min),
+            _ => 1.0,
+        };
+        // The maximum of the orientation is a quarter turn clockwise.
+        let orientation = match self.orientation {
+            Some(axis) if axis.max > 0.0 => axis.value / axis.max * std::f64::consts::FRAC_PI_2,
+            _ => 0.0,
+        };
+        Some(TouchContact { major: major * scale, minor: minor * scale, orientation })
+    }
+}
+
 impl Device {
-    fn new(info: &ffi::XIDeviceInfo) -> Self {
+    fn new(info: &ffi::XIDeviceInfo, atoms: &Atoms) -> Self {
         let name = unsafe { CStr::from_ptr(info.name).to_string_lossy() };
         let mut scroll_axes = Vec::new();
+        let lowercase = name.to_lowercase();
+        let mut pen = (Device::physical_device(info)
+            && ["stylus", "pen", "eraser"].iter().any(|word| lowercase.contains(word)))
+        .then(|| PenAxes { eraser: lowercase.contains("eraser"), ..Default::default() });
+
+        if let Some(pen) = &mut pen {
+            for &class_ptr in Device::classes(info) {
+                if unsafe { (*class_ptr)._type } != ffi::XIValuatorClass {
+                    continue;
+                }
+                let info = unsafe { &*(class_ptr as *const ffi::XIValuatorClassInfo) };
+                let axis = Valuator {
+                    number: info.number,
+                    min: info.min,
+                    max: info.max,
+                    value: info.value,
+                };
+                let label = info.label as xproto::Atom;
+                if label == atoms[AbsPressure] {
+                    pen.pressure = Some(axis);
+                } else if label == atoms[AbsTiltX] {
+                    pen.tilt_x = Some(axis);
+                } else if label == atoms[AbsTiltY] {
+                    pen.tilt_y = Some(axis);
+                }
+            }
+        }
+
+        let mut touch = TouchAxes::default();
+        if Device::physical_device(info) && pen.is_none() {
+            for &class_ptr in Device::classes(info) {
+                if unsafe { (*class_ptr)._type } != ffi::XIValuatorClass {
+                    continue;
+                }
+                let info = unsafe { &*(class_ptr as *const ffi::XIValuatorClassInfo) };
+                let axis = Valuator {
+                    number: info.number,
+                    min: info.min,
+                    max: info.max,
+                    value: info.value,
+                };
+                let label = info.label as xproto::Atom;
+                if label == atoms[AbsMtPressure] {
+                    touch.pressure = Some(axis);
+                } else if label == atoms[AbsMtTouchMajor] {
+                    touch.major = Some(axis);
+                } else if label == atoms[AbsMtTouchMinor] {
+                    touch.minor = Some(axis);
+                } else if label == atoms[AbsMtOrientation] {
+                    touch.orientation = Some(axis);
+                } else if label == atoms[AbsMtPositionX] {
+                    touch.position_x = Some(axis);
+                }
+            }
+        }
+        let touch = (touch.pressure.is_some() || touch.major.is_some()).then_some(touch);
 
         if Device::physical_device(info) {
             // Identify scroll axes
@@ -1044,8 +1790,15 @@ impl Device {
             }
         }
 
-        let mut device =
-            Device { _name: name.into_owned(), scroll_axes, attachment: info.attachment };
+        let mut device = Device {
+            _name: name.into_owned(),
+            scroll_axes,
+            touchpad: lowercase.contains("touchpad"),
+            scrolling: false,
+            pen,
+            touch,
+            attachment: info.attachment,
+        };
         device.reset_scroll_position(info);
         device
     }
@@ -4,8 +4,10 @@ use x11rb::connection::RequestConnection;
 use x11rb::protocol::randr::{self, ConnectionExt as _};
 use x11rb::protocol::xproto;
 
+use super::atoms::*;
 use super::{util, X11Error, XConnection};
 use crate::dpi::{PhysicalPosition, PhysicalSize};
+use crate::monitor::{MonitorIdentity, Orientation};
 use crate::platform_impl::VideoModeHandle as PlatformVideoModeHandle;
 
 // Used for testing. This should always be committed as false.
@@ -62,6 +64,10 @@ pub struct MonitorHandle {
     primary: bool,
     /// The DPI scale factor
     pub(crate) scale_factor: f64,
+    /// The physical size in millimeters, if known
+    physical_size_mm: Option<(u32, u32)>,
+    /// The rotation of the CRTC
+    pub(crate) orientation: Orientation,
     /// Used to determine which windows are on this monitor
     pub(crate) rect: util::AaRect,
     /// Supported video modes on this monitor
@@ -97,10 +103,17 @@ impl std::hash::Hash for MonitorHandle {
 #[inline]
 pub fn mode_refresh_rate_millihertz(mode: &randr::ModeInfo) -> Option<NonZeroU32> {
     if mode.dot_clock > 0 && mode.htotal > 0 && mode.vtotal > 0 {
+        // Like `xrandr`, account for the modes scanning lines twice or every other line.
         #[allow(clippy::unnecessary_cast)]
-        NonZeroU32::new(
-            (mode.dot_clock as u64 * 1000 / (mode.htotal as u64 * mode.vtotal as u64)) as u32,
-        )
+        let mut pixels = mode.htotal as u64 * mode.vtotal as u64;
+        if mode.mode_flags.contains(randr::ModeFlag::DOUBLE_SCAN) {
+            pixels *= 2;
+        }
+        if mode.mode_flags.contains(randr::ModeFlag::INTERLACE) {
+            pixels /= 2;
+        }
+        // Round to the nearest millihertz, so 59.94 Hz modes aren't reported as 59.939 Hz.
+        NonZeroU32::new(((mode.dot_clock as u64 * 1000 + pixels / 2) / pixels) as u32)
     } else {
         None
     }
@@ -114,13 +127,35 @@ impl MonitorHandle {
         crtc: &randr::GetCrtcInfoReply,
         primary: bool,
     ) -> Option<Self> {
-        let (name, scale_factor, video_modes) = xconn.get_output_info(resources, crtc)?;
+        let util::OutputInfo { name, scale_factor, physical_size_mm, video_modes } =
+            xconn.get_output_info(resources, crtc)?;
         let dimensions = (crtc.width as u32, crtc.height as u32);
         let position = (crtc.x as i32, crtc.y as i32);
 
         let rect = util::AaRect::new(position, dimensions);
 
-        Some(MonitorHandle { id, name, scale_factor, position, primary, rect, video_modes })
+        // RandR rotates counterclockwise.
+        let orientation = if crtc.rotation.contains(randr::Rotation::ROTATE90) {
+            Orientation::PortraitFlipped
+        } else if crtc.rotation.contains(randr::Rotation::ROTATE180) {
+            Orientation::LandscapeFlipped
+        } else if crtc.rotation.contains(randr::Rotation::ROTATE270) {
+            Orientation::Portrait
+        } else {
+            Orientation::Landscape
+        };
+
+        Some(MonitorHandle {
+            id,
+            name,
+            scale_factor,
+            physical_size_mm,
+            orientation,
+            position,
+            primary,
+            rect,
+            video_modes,
+        })
     }
 
     pub fn dummy() -> Self {
@@ -128,6 +163,8 @@ impl MonitorHandle {
             id: 0,
             name: "<dummy monitor>".into(),
             scale_factor: 1.0,
+            physical_size_mm: None,
+            orientation: Orientation::Landscape,
             position: (0, 0),
             primary: true,
             rect: util::AaRect::new((0, 0), (1, 1)),
@@ -158,6 +195,21 @@ impl MonitorHandle {
         self.scale_factor
     }
 
+    #[inline]
+    pub fn physical_size_mm(&self) -> Option<(u32, u32)> {
+        self.physical_size_mm
+    }
+
+    #[inline]
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    /// The refresh rate of the current video mode of the monitor.
+    pub(crate) fn refresh_rate_millihertz(&self) -> Option<NonZeroU32> {
+        self.video_modes.iter().find(|mode| mode.current)?.refresh_rate_millihertz
+    }
+
     #[inline]
     pub fn current_video_mode(&self) -> Option<PlatformVideoModeHandle> {
         self.video_modes.iter().find(|mode| mode.current).cloned().map(PlatformVideoModeHandle::X)
@@ -174,6 +226,43 @@ impl MonitorHandle {
 }
 
 impl XConnection {
+    /// Returns the ICC profile of the monitor, falling back to the profile of the root window for
+    /// the primary monitor, following the ICC Profiles in X Specification.
+    pub fn icc_profile(&self, monitor: &MonitorHandle) -> Option<Vec<u8>> {
+        if monitor.is_dummy() {
+            return None;
+        }
+
+        let outputs = self.get_crtc_outputs(monitor.id).ok()?;
+        for output in outputs {
+            if let Ok(Some(profile)) = self.get_output_icc_profile(output) {
+                return Some(profile);
+            }
+        }
+
+        if !monitor.primary {
+            return None;
+        }
+
+        let root = self.default_root().root;
+        let atom = self.atoms()[_ICC_PROFILE];
+        self.get_property::<u8>(root, atom, xproto::AtomEnum::CARDINAL.into())
+            .ok()
+            .filter(|profile| !profile.is_empty())
+    }
+
+    pub fn monitor_identity(&self, monitor: &MonitorHandle) -> Option<MonitorIdentity> {
+        if monitor.is_dummy() {
+            return None;
+        }
+
+        let outputs = self.get_crtc_outputs(monitor.id).ok()?;
+        outputs.into_iter().find_map(|output| {
+            let edid = self.get_output_edid(output).ok()??;
+            MonitorIdentity::from_edid(&edid)
+        })
+    }
+
     pub fn get_monitor_for_window(
         &self,
         window_rect: Option<util::AaRect>,
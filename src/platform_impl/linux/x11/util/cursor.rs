@@ -5,13 +5,16 @@ use std::sync::Arc;
 
 use x11rb::connection::Connection;
 use x11rb::protocol::render::{self, ConnectionExt as _};
+use x11rb::protocol::xinput::{self, ConnectionExt as _};
 use x11rb::protocol::xproto;
 
 use super::super::ActiveEventLoop;
 use super::*;
+use crate::cursor::closest_to_scale_factor;
 use crate::error::RequestError;
+use crate::platform_impl::common::cursor_theme;
 use crate::platform_impl::PlatformCustomCursorSource;
-use crate::window::CursorIcon;
+use crate::window::{CursorIcon, CursorThemeInfo};
 
 impl XConnection {
     pub fn set_cursor_icon(
@@ -19,24 +22,67 @@ impl XConnection {
         window: xproto::Window,
         cursor: Option<CursorIcon>,
     ) -> Result<(), X11Error> {
-        let cursor = {
-            let mut cache = self.cursor_cache.lock().unwrap_or_else(|e| e.into_inner());
-
-            match cache.entry(cursor) {
-                Entry::Occupied(o) => *o.get(),
-                Entry::Vacant(v) => *v.insert(self.get_cursor(cursor)?),
-            }
-        };
-
+        let cursor = self.cached_cursor(cursor)?;
         self.update_cursor(window, cursor)
     }
 
+    /// Set the image of the custom cursor closest to the scale factor of the window.
     pub(crate) fn set_custom_cursor(
         &self,
         window: xproto::Window,
         cursor: &CustomCursor,
+        scale_factor: f64,
     ) -> Result<(), X11Error> {
-        self.update_cursor(window, cursor.inner.cursor)
+        self.update_cursor(window, cursor.for_scale_factor(scale_factor))
+    }
+
+    /// Set the cursor of a master pointer in the window, `x11rb::NONE` to use the cursor of the
+    /// window again.
+    pub(crate) fn set_device_cursor(
+        &self,
+        window: xproto::Window,
+        device: xinput::DeviceId,
+        cursor: xproto::Cursor,
+    ) -> Result<(), X11Error> {
+        self.xcb_connection().xinput_xi_change_cursor(window, cursor, device)?.check()?;
+        Ok(())
+    }
+
+    /// The cursor for the icon, or the invisible cursor for `None`.
+    pub(crate) fn cached_cursor(
+        &self,
+        cursor: Option<CursorIcon>,
+    ) -> Result<xproto::Cursor, X11Error> {
+        let mut cache = self.cursor_cache.lock().unwrap_or_else(|e| e.into_inner());
+
+        match cache.entry(cursor) {
+            Entry::Occupied(o) => Ok(*o.get()),
+            Entry::Vacant(v) => Ok(*v.insert(self.get_cursor(cursor)?)),
+        }
+    }
+
+    /// The cursor theme from XSettings, then from the resources and the environment like
+    /// libXcursor.
+    pub fn cursor_theme(&self) -> CursorThemeInfo {
+        let (xsettings_name, xsettings_size) = self
+            .xsettings_screen()
+            .and_then(|screen| self.xsettings_cursor_theme(screen).ok())
+            .unwrap_or_default();
+
+        let database = self.database();
+        let name = xsettings_name
+            .or_else(|| database.get_string("Xcursor.theme", "").map(str::to_owned))
+            .filter(|name| !name.is_empty())
+            .or_else(cursor_theme::theme_name_from_env);
+        let size = xsettings_size
+            .or_else(|| {
+                let size = database.get_string("Xcursor.size", "")?;
+                size.parse().ok().filter(|&size| size != 0)
+            })
+            .or_else(cursor_theme::cursor_size_from_env)
+            .unwrap_or(cursor_theme::DEFAULT_CURSOR_SIZE);
+
+        CursorThemeInfo { name, size }
     }
 
     /// Create a cursor from an image.
@@ -192,47 +238,69 @@ impl Eq for CustomCursor {}
 impl CustomCursor {
     pub(crate) fn new(
         event_loop: &ActiveEventLoop,
-        mut cursor: PlatformCustomCursorSource,
+        cursor: PlatformCustomCursorSource,
     ) -> Result<CustomCursor, RequestError> {
-        // Reverse RGBA order to BGRA.
-        cursor.0.rgba.chunks_mut(4).for_each(|chunk| {
-            let chunk: &mut [u8; 4] = chunk.try_into().unwrap();
-            chunk[0..3].reverse();
-
-            // Byteswap if we need to.
-            if event_loop.xconn.needs_endian_swap() {
-                let value = u32::from_ne_bytes(*chunk).swap_bytes();
-                *chunk = value.to_ne_bytes();
-            }
-        });
+        // The cursors created so far are freed on errors.
+        let mut inner = CustomCursorInner { xconn: event_loop.xconn.clone(), cursors: Vec::new() };
+        for image in cursor.0.images() {
+            // Reverse RGBA order to BGRA.
+            let mut bgra = image.rgba.clone();
+            bgra.chunks_mut(4).for_each(|chunk| {
+                let chunk: &mut [u8; 4] = chunk.try_into().unwrap();
+                chunk[0..3].reverse();
+
+                // Byteswap if we need to.
+                if event_loop.xconn.needs_endian_swap() {
+                    let value = u32::from_ne_bytes(*chunk).swap_bytes();
+                    *chunk = value.to_ne_bytes();
+                }
+            });
+
+            let cursor = event_loop
+                .xconn
+                .create_cursor_from_image(
+                    image.width,
+                    image.height,
+                    image.hotspot_x,
+                    image.hotspot_y,
+                    &bgra,
+                )
+                .map_err(|err| os_error!(err))?;
+            inner.cursors.push((image.width, cursor));
+        }
 
-        let cursor = event_loop
-            .xconn
-            .create_cursor_from_image(
-                cursor.0.width,
-                cursor.0.height,
-                cursor.0.hotspot_x,
-                cursor.0.hotspot_y,
-                &cursor.0.rgba,
-            )
-            .map_err(|err| os_error!(err))?;
+        Ok(Self { inner: Arc::new(inner) })
+    }
 
-        Ok(Self { inner: Arc::new(CustomCursorInner { xconn: event_loop.xconn.clone(), cursor }) })
+    /// The cursor of the image closest to the scale factor of the window.
+    pub(crate) fn for_scale_factor(&self, scale_factor: f64) -> xproto::Cursor {
+        closest_to_scale_factor(&self.inner.cursors, |&(width, _)| width, scale_factor).1
     }
 }
 
 #[derive(Debug)]
 struct CustomCursorInner {
     xconn: Arc<XConnection>,
-    cursor: xproto::Cursor,
+    /// The cursor for each image, with its width, in increasing size.
+    cursors: Vec<(u16, xproto::Cursor)>,
 }
 
 impl Drop for CustomCursorInner {
     fn drop(&mut self) {
-        self.xconn.xcb_connection().free_cursor(self.cursor).map(|r| r.ignore_error()).ok();
+        for &(_, cursor) in &self.cursors {
+            self.xconn.xcb_connection().free_cursor(cursor).map(|r| r.ignore_error()).ok();
+        }
     }
 }
 
+/// The cursor of a master pointer in a window, overriding the cursor of the window.
+#[derive(Debug, Clone, Default)]
+pub struct DeviceCursor {
+    /// The cursor of the device, `None` for the cursor of the window.
+    pub cursor: Option<SelectedCursor>,
+    pub hidden: bool,
+}
+
 impl Default for SelectedCursor {
     fn default() -> Self {
         SelectedCursor::Named(Default::default())
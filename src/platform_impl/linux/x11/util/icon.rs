@@ -1,7 +1,7 @@
 #![allow(clippy::assertions_on_constants)]
 
 use super::*;
-use crate::icon::{Pixel, RgbaIcon, PIXEL_SIZE};
+use crate::icon::{Pixel, RgbaIcon, RgbaIcons, PIXEL_SIZE};
 
 impl Pixel {
     pub fn to_packed_argb(&self) -> Cardinal {
@@ -18,8 +18,15 @@ impl Pixel {
     }
 }
 
-impl RgbaIcon {
+impl RgbaIcons {
+    /// The content of `_NET_WM_ICON`, with every image after the other.
     pub(crate) fn to_cardinals(&self) -> Vec<Cardinal> {
+        self.0.iter().flat_map(RgbaIcon::to_cardinals).collect()
+    }
+}
+
+impl RgbaIcon {
+    fn to_cardinals(&self) -> Vec<Cardinal> {
         assert_eq!(self.rgba.len() % PIXEL_SIZE, 0);
         let pixel_count = self.rgba.len() / PIXEL_SIZE;
         assert_eq!(pixel_count, (self.width * self.height) as usize);
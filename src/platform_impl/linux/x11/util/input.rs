@@ -1,9 +1,12 @@
 use std::{slice, str};
 
 use x11rb::protocol::xinput::{self, ConnectionExt as _};
-use x11rb::protocol::xkb;
+use x11rb::protocol::xkb::{self, ConnectionExt as _};
 
 use super::*;
+use crate::event::{DeviceInfo, DeviceKind};
+use crate::keyboard::Led;
+use crate::platform_impl::x11::mkdid;
 
 pub const VIRTUAL_CORE_POINTER: u16 = 2;
 
@@ -56,6 +59,66 @@ impl XConnection {
             .map_err(Into::into)
     }
 
+    /// The input devices matching `device_id`, which can be `XIAllDevices` to query all of them.
+    pub fn input_devices(&self, device_id: u16) -> Result<Vec<DeviceInfo>, X11Error> {
+        let reply = self.xcb_connection().xinput_xi_query_device(device_id)?.reply()?;
+        Ok(reply.infos.iter().map(|info| self.input_device_info(info)).collect())
+    }
+
+    fn input_device_info(&self, info: &xinput::XIDeviceInfo) -> DeviceInfo {
+        let name = String::from_utf8_lossy(&info.name).into_owned();
+        let lowercase = name.to_lowercase();
+        let touch_mode = info.classes.iter().find_map(|class| match &class.data {
+            xinput::DeviceClassData::Touch(touch) => Some(touch.mode),
+            _ => None,
+        });
+
+        let kind = if matches!(
+            info.type_,
+            xinput::DeviceType::MASTER_KEYBOARD | xinput::DeviceType::SLAVE_KEYBOARD
+        ) {
+            DeviceKind::Keyboard
+        } else if touch_mode == Some(xinput::TouchMode::DIRECT) {
+            DeviceKind::Touchscreen
+        } else if touch_mode == Some(xinput::TouchMode::DEPENDENT)
+            || ["touchpad", "trackpad"].iter().any(|word| lowercase.contains(word))
+        {
+            DeviceKind::Touchpad
+        } else if ["stylus", "pen", "eraser"].iter().any(|word| lowercase.contains(word)) {
+            DeviceKind::Pen
+        } else {
+            DeviceKind::Mouse
+        };
+
+        // Set by the `libinput` and `evdev` drivers, as the vendor and product IDs.
+        let ids = self
+            .xcb_connection()
+            .xinput_xi_get_property(
+                info.deviceid,
+                false,
+                self.atoms()[DeviceProductId],
+                xproto::AtomEnum::ANY.into(),
+                0,
+                2,
+            )
+            .ok()
+            .and_then(|cookie| cookie.reply().ok())
+            .and_then(|reply| match reply.items {
+                xinput::XIGetPropertyItems::Data32(ids) if ids.len() == 2 => {
+                    Some((ids[0] as u16, ids[1] as u16))
+                },
+                _ => None,
+            });
+
+        DeviceInfo {
+            id: mkdid(info.deviceid),
+            kind,
+            name: Some(name),
+            vendor_id: ids.map(|(vendor_id, _)| vendor_id),
+            product_id: ids.map(|(_, product_id)| product_id),
+        }
+    }
+
     fn lookup_utf8_inner(
         &self,
         ic: ffi::XIC,
@@ -103,4 +166,74 @@ impl XConnection {
 
         str::from_utf8(bytes).unwrap_or("").to_string()
     }
+
+    fn led_indicator(&self, led: Led) -> xproto::Atom {
+        let atoms = self.atoms();
+        match led {
+            Led::CapsLock => atoms[CapsLockIndicator],
+            Led::NumLock => atoms[NumLockIndicator],
+            Led::ScrollLock => atoms[ScrollLockIndicator],
+        }
+    }
+
+    /// Whether the indicator of a lock of the core keyboard is on.
+    pub fn keyboard_led(&self, led: Led) -> Result<Option<bool>, X11Error> {
+        let reply = self
+            .xcb_connection()
+            .xkb_get_named_indicator(
+                xkb::ID::USE_CORE_KBD.into(),
+                xkb::LedClass::DFLT_XI_CLASS,
+                xkb::ID::DFLT_XI_ID,
+                self.led_indicator(led),
+            )?
+            .reply()?;
+        Ok(reply.found.then_some(reply.on))
+    }
+
+    /// Lock or unlock a lock of the core keyboard.
+    pub fn set_keyboard_led(&self, led: Led, on: bool) -> Result<(), X11Error> {
+        let device = xkb::ID::USE_CORE_KBD.into();
+        // The indicators of the modifier locks can't be changed explicitly, so the modifiers are
+        // locked instead and their indicators follow.
+        let mask = match led {
+            Led::CapsLock => xproto::ModMask::LOCK,
+            Led::NumLock => xproto::ModMask::M2,
+            Led::ScrollLock => {
+                self.xcb_connection()
+                    .xkb_set_named_indicator(
+                        device,
+                        xkb::LedClass::DFLT_XI_CLASS,
+                        xkb::ID::DFLT_XI_ID,
+                        self.led_indicator(led),
+                        true,
+                        on,
+                        false,
+                        false,
+                        0u8.into(),
+                        0u8.into(),
+                        0u8.into(),
+                        0u8.into(),
+                        0u16.into(),
+                        0u16.into(),
+                        0u32.into(),
+                    )?
+                    .check()?;
+                return Ok(());
+            },
+        };
+        let locks = if on { mask } else { 0u16.into() };
+        self.xcb_connection()
+            .xkb_latch_lock_state(
+                device,
+                mask,
+                locks,
+                false,
+                xkb::Group::M1,
+                0u16.into(),
+                false,
+                0,
+            )?
+            .check()?;
+        Ok(())
+    }
 }
@@ -27,6 +27,7 @@ pub use self::geometry::*;
 pub use self::hint::*;
 pub use self::input::*;
 pub use self::mouse::*;
+pub use self::randr::OutputInfo;
 pub use self::window_property::*;
 pub use self::wm::*;
 pub use self::xmodmap::ModifierKeymap;
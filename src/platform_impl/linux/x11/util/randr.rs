@@ -16,6 +16,15 @@ pub enum EnvVarDPI {
     NotSet,
 }
 
+/// The properties of a RandR output.
+pub struct OutputInfo {
+    pub name: String,
+    pub scale_factor: f64,
+    /// The physical size in millimeters, if known.
+    pub physical_size_mm: Option<(u32, u32)>,
+    pub video_modes: Vec<VideoModeHandle>,
+}
+
 pub fn calc_dpi_factor(
     (width_px, height_px): (u32, u32),
     (width_mm, height_mm): (u64, u64),
@@ -58,7 +67,7 @@ impl XConnection {
         &self,
         resources: &monitor::ScreenResources,
         crtc: &randr::GetCrtcInfoReply,
-    ) -> Option<(String, f64, Vec<VideoModeHandle>)> {
+    ) -> Option<OutputInfo> {
         let output_info = match self
             .xcb_connection()
             .randr_get_output_info(crtc.outputs[0], x11rb::CURRENT_TIME)
@@ -155,7 +164,18 @@ impl XConnection {
             },
         };
 
-        Some((name, scale_factor, modes))
+        // The physical size is the one of the unrotated output, and is zero when unknown.
+        let mut physical_size_mm = (output_info.mm_width, output_info.mm_height);
+        if u16::from(crtc.rotation)
+            & u16::from(randr::Rotation::ROTATE90 | randr::Rotation::ROTATE270)
+            != 0
+        {
+            physical_size_mm = (physical_size_mm.1, physical_size_mm.0);
+        }
+        let physical_size_mm =
+            Some(physical_size_mm).filter(|&(width, height)| width != 0 && height != 0);
+
+        Some(OutputInfo { name, scale_factor, physical_size_mm, video_modes: modes })
     }
 
     pub fn set_crtc_config(
@@ -185,4 +205,66 @@ impl XConnection {
     pub fn get_crtc_mode(&self, crtc_id: randr::Crtc) -> Result<randr::Mode, X11Error> {
         Ok(self.xcb_connection().randr_get_crtc_info(crtc_id, x11rb::CURRENT_TIME)?.reply()?.mode)
     }
+
+    pub fn get_crtc_outputs(&self, crtc_id: randr::Crtc) -> Result<Vec<randr::Output>, X11Error> {
+        Ok(self
+            .xcb_connection()
+            .randr_get_crtc_info(crtc_id, x11rb::CURRENT_TIME)?
+            .reply()?
+            .outputs)
+    }
+
+    pub fn get_crtc_gamma(&self, crtc_id: randr::Crtc) -> Result<[Vec<u16>; 3], X11Error> {
+        let reply = self.xcb_connection().randr_get_crtc_gamma(crtc_id)?.reply()?;
+        Ok([reply.red, reply.green, reply.blue])
+    }
+
+    pub fn get_crtc_gamma_size(&self, crtc_id: randr::Crtc) -> Result<usize, X11Error> {
+        Ok(self.xcb_connection().randr_get_crtc_gamma_size(crtc_id)?.reply()?.size as usize)
+    }
+
+    pub fn set_crtc_gamma(
+        &self,
+        crtc_id: randr::Crtc,
+        [red, green, blue]: &[Vec<u16>; 3],
+    ) -> Result<(), X11Error> {
+        self.xcb_connection()
+            .randr_set_crtc_gamma(crtc_id, red, green, blue)?
+            .check()
+            .map_err(Into::into)
+    }
+
+    /// Reads the ICC profile set on a RandR output, as done by color managers like colord.
+    pub fn get_output_icc_profile(
+        &self,
+        output: randr::Output,
+    ) -> Result<Option<Vec<u8>>, X11Error> {
+        self.get_output_bytes(output, self.atoms()[_ICC_PROFILE])
+    }
+
+    /// Reads the EDID of the display connected to a RandR output.
+    pub fn get_output_edid(&self, output: randr::Output) -> Result<Option<Vec<u8>>, X11Error> {
+        self.get_output_bytes(output, self.atoms()[Edid])
+    }
+
+    fn get_output_bytes(
+        &self,
+        output: randr::Output,
+        property: xproto::Atom,
+    ) -> Result<Option<Vec<u8>>, X11Error> {
+        let reply = self
+            .xcb_connection()
+            .randr_get_output_property(
+                output,
+                property,
+                xproto::AtomEnum::ANY,
+                0,
+                u32::MAX / 4,
+                false,
+                false,
+            )?
+            .reply()?;
+
+        Ok(Some(reply.data).filter(|data| reply.format == 8 && !data.is_empty()))
+    }
 }
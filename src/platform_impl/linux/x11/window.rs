@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::ffi::CString;
 use std::mem::replace;
 use std::num::NonZeroU32;
@@ -5,6 +6,7 @@ use std::ops::Deref;
 use std::os::raw::*;
 use std::path::Path;
 use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Instant;
 use std::{cmp, env};
 
 use tracing::{debug, info, warn};
@@ -16,16 +18,19 @@ use x11rb::protocol::xfixes::{ConnectionExt, RegionWrapper};
 use x11rb::protocol::xproto::{self, ConnectionExt as _, Rectangle};
 use x11rb::protocol::{randr, xinput};
 
-use super::util::{self, SelectedCursor};
+use super::util::{self, DeviceCursor, SelectedCursor};
 use super::{
     ffi, ActiveEventLoop, CookieResultExt, ImeRequest, ImeSender, VoidCookie, XConnection,
 };
 use crate::cursor::{Cursor, CustomCursor as RootCustomCursor};
 use crate::dpi::{PhysicalPosition, PhysicalSize, Position, Size};
 use crate::error::{NotSupportedError, RequestError};
-use crate::event::{Event, SurfaceSizeWriter, WindowEvent};
+use crate::event::{DeviceId, Event, SurfaceSizeWriter, WindowEvent};
 use crate::event_loop::AsyncRequestSerial;
+use crate::monitor::GammaRamp;
 use crate::platform::x11::WindowType;
+#[cfg(feature = "accesskit")]
+use crate::platform_impl::common::accessibility::Adapter as AccessKitAdapter;
 use crate::platform_impl::x11::atoms::*;
 use crate::platform_impl::x11::{
     xinput_fp1616_to_float, MonitorHandle as X11MonitorHandle, WakeSender, X11Error,
@@ -35,8 +40,9 @@ use crate::platform_impl::{
     VideoModeHandle as PlatformVideoModeHandle,
 };
 use crate::window::{
-    CursorGrabMode, ImePurpose, ResizeDirection, Theme, UserAttentionType, Window as CoreWindow,
-    WindowAttributes, WindowButtons, WindowId, WindowLevel,
+    Coalescing, CursorGrabMode, CursorGrabPolicy, ImePurpose, Politeness, ResizeDirection, Theme,
+    UserAttentionType, Window as CoreWindow, WindowAttributes, WindowButtons, WindowId,
+    WindowLevel,
 };
 
 pub(crate) struct Window(Arc<UnownedWindow>);
@@ -74,6 +80,10 @@ impl CoreWindow for Window {
         self.0.request_redraw()
     }
 
+    fn request_redraw_at(&self, instant: Instant) {
+        self.0.request_redraw_at(instant)
+    }
+
     fn pre_present_notify(&self) {
         self.0.pre_present_notify()
     }
@@ -182,6 +192,21 @@ impl CoreWindow for Window {
         self.0.fullscreen().map(Into::into)
     }
 
+    fn set_fullscreen_hdr(
+        &self,
+        _hdr: Option<crate::monitor::HdrMetadata>,
+    ) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_fullscreen_hdr is not supported").into())
+    }
+
+    fn fullscreen_hdr(&self) -> Option<crate::monitor::HdrMetadata> {
+        None
+    }
+
+    fn set_gamma_ramp(&self, ramp: Option<&GammaRamp>) -> Result<(), RequestError> {
+        self.0.set_gamma_ramp(ramp)
+    }
+
     fn set_decorations(&self, decorations: bool) {
         self.0.set_decorations(decorations);
     }
@@ -202,14 +227,28 @@ impl CoreWindow for Window {
         self.0.set_ime_cursor_area(position, size);
     }
 
+    fn set_ime_exclusion_area(&self, _area: Option<(Position, Size)>) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_ime_exclusion_area is not supported").into())
+    }
+
+    fn set_ime_candidates_drawn_by_app(&self, _drawn_by_app: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_ime_candidates_drawn_by_app is not supported").into())
+    }
+
     fn set_ime_allowed(&self, allowed: bool) {
         self.0.set_ime_allowed(allowed);
     }
 
+    fn set_virtual_keyboard_visible(&self, _visible: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_virtual_keyboard_visible is not supported").into())
+    }
+
     fn set_ime_purpose(&self, purpose: ImePurpose) {
         self.0.set_ime_purpose(purpose);
     }
 
+    fn set_ime_surrounding_text(&self, _text: &str, _cursor: usize, _anchor: usize) {}
+
     fn focus_window(&self) {
         self.0.focus_window();
     }
@@ -218,10 +257,32 @@ impl CoreWindow for Window {
         self.0.has_focus()
     }
 
+    fn set_keyboard_capture(&self, _capture: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_keyboard_capture is not supported").into())
+    }
+
     fn request_user_attention(&self, request_type: Option<UserAttentionType>) {
         self.0.request_user_attention(request_type);
     }
 
+    fn announce(&self, _message: &str, _politeness: Politeness) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("announce is not supported").into())
+    }
+
+    #[cfg(feature = "accesskit")]
+    fn set_accesskit_tree_source(
+        &self,
+        source: Box<dyn accesskit::ActivationHandler + Send>,
+    ) -> Result<(), RequestError> {
+        self.0.set_accesskit_tree_source(source);
+        Ok(())
+    }
+
+    #[cfg(feature = "accesskit")]
+    fn update_accesskit_tree(&self, update: accesskit::TreeUpdate) {
+        self.0.update_accesskit_tree(update);
+    }
+
     fn set_theme(&self, theme: Option<Theme>) {
         self.0.set_theme(theme);
     }
@@ -247,13 +308,34 @@ impl CoreWindow for Window {
     }
 
     fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), RequestError> {
+        self.0.shared_state_lock().suspended_cursor_grab = None;
         self.0.set_cursor_grab(mode)
     }
 
+    fn set_cursor_grab_policy(&self, policy: CursorGrabPolicy) {
+        self.0.shared_state_lock().cursor_grab_policy = policy;
+    }
+
     fn set_cursor_visible(&self, visible: bool) {
         self.0.set_cursor_visible(visible);
     }
 
+    fn set_cursor_for_device(
+        &self,
+        device_id: DeviceId,
+        cursor: Cursor,
+    ) -> Result<(), RequestError> {
+        self.0.set_cursor_for_device(device_id, cursor)
+    }
+
+    fn set_cursor_visible_for_device(
+        &self,
+        device_id: DeviceId,
+        visible: bool,
+    ) -> Result<(), RequestError> {
+        self.0.set_cursor_visible_for_device(device_id, visible)
+    }
+
     fn drag_window(&self) -> Result<(), RequestError> {
         self.0.drag_window()
     }
@@ -270,6 +352,11 @@ impl CoreWindow for Window {
         self.0.set_cursor_hittest(hittest)
     }
 
+    fn set_motion_coalescing(&self, coalescing: Coalescing) -> Result<(), RequestError> {
+        self.0.set_motion_coalescing(coalescing);
+        Ok(())
+    }
+
     fn current_monitor(&self) -> Option<crate::monitor::MonitorHandle> {
         self.0
             .current_monitor()
@@ -294,6 +381,12 @@ impl CoreWindow for Window {
             .map(|inner| crate::monitor::MonitorHandle { inner })
     }
 
+    #[cfg(feature = "winit-test-harness")]
+    fn inject_event(&self, event: WindowEvent) -> Result<(), RequestError> {
+        self.0.inject_event(event);
+        Ok(())
+    }
+
     #[cfg(feature = "rwh_06")]
     fn rwh_06_display_handle(&self) -> &dyn rwh_06::HasDisplayHandle {
         self
@@ -357,6 +450,10 @@ pub struct SharedState {
     pub restore_position: Option<(i32, i32)>,
     // Used to restore video mode after exiting fullscreen
     pub desktop_video_mode: Option<(randr::Crtc, randr::Mode)>,
+    // The gamma ramp applied in exclusive fullscreen
+    pub gamma_ramp: Option<GammaRamp>,
+    // Used to restore the gamma ramp of the monitor when the window loses focus
+    pub saved_gamma_ramp: Option<(randr::Crtc, [Vec<u16>; 3])>,
     pub frame_extents: Option<util::FrameExtentsHeuristic>,
     pub min_surface_size: Option<Size>,
     pub max_surface_size: Option<Size>,
@@ -366,6 +463,9 @@ pub struct SharedState {
     pub has_focus: bool,
     // Use `Option` to not apply hittest logic when it was never requested.
     pub cursor_hittest: Option<bool>,
+    pub cursor_grab_policy: CursorGrabPolicy,
+    // The grab released when the window lost focus, following `CursorGrabPolicy::ReGrabOnFocus`.
+    pub suspended_cursor_grab: Option<CursorGrabMode>,
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -397,6 +497,8 @@ impl SharedState {
             desired_fullscreen: None,
             restore_position: None,
             desktop_video_mode: None,
+            gamma_ramp: None,
+            saved_gamma_ramp: None,
             frame_extents: None,
             min_surface_size: None,
             max_surface_size: None,
@@ -404,6 +506,8 @@ impl SharedState {
             base_size: None,
             has_focus: false,
             cursor_hittest: None,
+            cursor_grab_policy: CursorGrabPolicy::default(),
+            suspended_cursor_grab: None,
         })
     }
 }
@@ -424,10 +528,19 @@ pub struct UnownedWindow {
     cursor_grabbed_mode: Mutex<CursorGrabMode>,
     #[allow(clippy::mutex_atomic)]
     cursor_visible: Mutex<bool>,
+    device_cursors: Mutex<HashMap<xinput::DeviceId, DeviceCursor>>,
+    motion_coalescing: Mutex<Coalescing>,
     ime_sender: Mutex<ImeSender>,
     pub shared_state: Mutex<SharedState>,
     redraw_sender: WakeSender<WindowId>,
+    scheduled_redraw_sender: WakeSender<(WindowId, Instant)>,
     activation_sender: WakeSender<super::ActivationToken>,
+    #[cfg(feature = "winit-test-harness")]
+    injected_sender: WakeSender<Event>,
+    #[cfg(feature = "accesskit")]
+    accesskit: Mutex<Option<AccessKitAdapter>>,
+    #[cfg(feature = "accesskit")]
+    accesskit_sender: WakeSender<(WindowId, accesskit::ActionRequest)>,
 }
 macro_rules! leap {
     ($e:expr) => {
@@ -520,6 +633,9 @@ impl UnownedWindow {
             .flat_map(|root| &root.allowed_depths)
             .flat_map(|depth| depth.visuals.iter().map(move |visual| (visual, depth.depth)));
 
+        let format_hint = window_attrs.surface_format_hint.unwrap_or_default();
+        let transparent = window_attrs.transparent || format_hint.alpha;
+
         // creating
         let (visualtype, depth, require_colormap) =
             match window_attrs.platform_specific.x11.visual_id {
@@ -531,7 +647,21 @@ impl UnownedWindow {
 
                     (Some(visualtype), depth, true)
                 },
-                None if window_attrs.transparent => {
+                None if format_hint.hdr && !transparent => {
+                    // Find a suitable visual, true color with 10 bits per channel.
+                    all_visuals
+                        .find_map(|(visual, depth)| {
+                            (depth == 30
+                                && visual.class == xproto::VisualClass::TRUE_COLOR
+                                && visual.bits_per_rgb_value == 10)
+                                .then_some((Some(visual), depth, true))
+                        })
+                        .unwrap_or_else(|| {
+                            debug!("Could not find a visual with 10 bits per channel for HDR");
+                            (None as _, x11rb::COPY_FROM_PARENT as _, false)
+                        })
+                },
+                None if transparent => {
                     // Find a suitable visual, true color with 32 bits of depth.
                     all_visuals
                         .find_map(|(visual, depth)| {
@@ -641,10 +771,19 @@ impl UnownedWindow {
             selected_cursor: Default::default(),
             cursor_grabbed_mode: Mutex::new(CursorGrabMode::None),
             cursor_visible: Mutex::new(true),
+            device_cursors: Default::default(),
+            motion_coalescing: Mutex::new(window_attrs.motion_coalescing),
             ime_sender: Mutex::new(event_loop.ime_sender.clone()),
             shared_state: SharedState::new(guessed_monitor, &window_attrs),
             redraw_sender: event_loop.redraw_sender.clone(),
+            scheduled_redraw_sender: event_loop.scheduled_redraw_sender.clone(),
             activation_sender: event_loop.activation_sender.clone(),
+            #[cfg(feature = "winit-test-harness")]
+            injected_sender: event_loop.injected_sender.clone(),
+            #[cfg(feature = "accesskit")]
+            accesskit: Mutex::new(None),
+            #[cfg(feature = "accesskit")]
+            accesskit_sender: event_loop.accesskit_sender.clone(),
         };
 
         // Title must be set before mapping. Some tiling window managers (i.e. i3) use the window
@@ -830,7 +969,7 @@ impl UnownedWindow {
             }
 
             // Select XInput2 events
-            let mask = xinput::XIEventMask::MOTION
+            let mut mask = xinput::XIEventMask::MOTION
                 | xinput::XIEventMask::BUTTON_PRESS
                 | xinput::XIEventMask::BUTTON_RELEASE
                 | xinput::XIEventMask::ENTER
@@ -840,6 +979,13 @@ impl UnownedWindow {
                 | xinput::XIEventMask::TOUCH_BEGIN
                 | xinput::XIEventMask::TOUCH_UPDATE
                 | xinput::XIEventMask::TOUCH_END;
+            if event_loop.xi2_gestures {
+                mask |= xinput::XIEventMask::from(
+                    1u32 << ffi::XI_GesturePinchBegin
+                        | 1 << ffi::XI_GesturePinchUpdate
+                        | 1 << ffi::XI_GesturePinchEnd,
+                );
+            }
             leap!(xconn.select_xinput_events(window.xwindow, super::ALL_MASTER_DEVICES, mask))
                 .ignore_error();
 
@@ -1025,6 +1171,18 @@ impl UnownedWindow {
         if old_fullscreen == fullscreen {
             return Ok(None);
         }
+
+        // The gamma ramp only applies in exclusive fullscreen.
+        if !matches!(fullscreen, Some(Fullscreen::Exclusive(_)))
+            && shared_state_lock.gamma_ramp.take().is_some()
+        {
+            drop(shared_state_lock);
+            if let Err(err) = self.update_gamma_ramp() {
+                warn!("Failed to restore the gamma ramp: {err}");
+            }
+            shared_state_lock = self.shared_state_lock();
+        }
+
         shared_state_lock.fullscreen.clone_from(&fullscreen);
 
         match (&old_fullscreen, &fullscreen) {
@@ -1145,6 +1303,49 @@ impl UnownedWindow {
         }
     }
 
+    pub(crate) fn set_gamma_ramp(&self, ramp: Option<&GammaRamp>) -> Result<(), RequestError> {
+        let mut shared_state = self.shared_state_lock();
+        if ramp.is_some() && !matches!(shared_state.fullscreen, Some(Fullscreen::Exclusive(_))) {
+            return Err(RequestError::Ignored);
+        }
+        shared_state.gamma_ramp = ramp.cloned();
+        drop(shared_state);
+
+        self.update_gamma_ramp().map_err(|err| os_error!(err).into())
+    }
+
+    /// Apply the gamma ramp to the monitor while the window has focus in exclusive fullscreen,
+    /// and restore the previous ramp of the monitor otherwise.
+    pub(crate) fn update_gamma_ramp(&self) -> Result<(), X11Error> {
+        let mut shared_state = self.shared_state_lock();
+        let target = match (&shared_state.fullscreen, &shared_state.gamma_ramp) {
+            (Some(Fullscreen::Exclusive(PlatformVideoModeHandle::X(video_mode))), Some(ramp))
+                if shared_state.has_focus =>
+            {
+                video_mode.monitor.as_ref().map(|monitor| (monitor.id, ramp.clone()))
+            },
+            _ => None,
+        };
+
+        if let Some((crtc, saved)) = shared_state.saved_gamma_ramp.take() {
+            if target.as_ref().is_some_and(|(target, _)| *target == crtc) {
+                shared_state.saved_gamma_ramp = Some((crtc, saved));
+            } else {
+                self.xconn.set_crtc_gamma(crtc, &saved)?;
+            }
+        }
+
+        if let Some((crtc, ramp)) = target {
+            if shared_state.saved_gamma_ramp.is_none() {
+                shared_state.saved_gamma_ramp = Some((crtc, self.xconn.get_crtc_gamma(crtc)?));
+            }
+            let size = self.xconn.get_crtc_gamma_size(crtc)?;
+            self.xconn.set_crtc_gamma(crtc, &ramp.resample(size))?;
+        }
+
+        Ok(())
+    }
+
     // Called by EventProcessor when a VisibilityNotify event is received
     pub(crate) fn visibility_notify(&self) {
         let mut shared_state = self.shared_state_lock();
@@ -1231,6 +1432,7 @@ impl UnownedWindow {
                     surface_size_writer: SurfaceSizeWriter::new(Arc::downgrade(&surface_size)),
                 },
             });
+            self.reload_custom_cursor(new_monitor.scale_factor);
 
             let new_surface_size = *surface_size.lock().unwrap();
             drop(surface_size);
@@ -1798,7 +2000,9 @@ impl UnownedWindow {
             Cursor::Custom(RootCustomCursor { inner: PlatformCustomCursor::X(cursor) }) => {
                 #[allow(clippy::mutex_atomic)]
                 if *self.cursor_visible.lock().unwrap() {
-                    if let Err(err) = self.xconn.set_custom_cursor(self.xwindow, &cursor) {
+                    if let Err(err) =
+                        self.xconn.set_custom_cursor(self.xwindow, &cursor, self.scale_factor())
+                    {
                         tracing::error!("failed to set window icon: {err}");
                     }
                 }
@@ -1812,6 +2016,11 @@ impl UnownedWindow {
         }
     }
 
+    #[inline]
+    pub fn cursor_grab_mode(&self) -> CursorGrabMode {
+        *self.cursor_grabbed_mode.lock().unwrap()
+    }
+
     #[inline]
     pub fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), RequestError> {
         let mut grabbed_lock = self.cursor_grabbed_mode.lock().unwrap();
@@ -1893,6 +2102,86 @@ impl UnownedWindow {
         result
     }
 
+    /// Set the image of the custom cursors for the new scale factor of the window.
+    pub(crate) fn reload_custom_cursor(&self, scale_factor: f64) {
+        let device_cursors = self.device_cursors.lock().unwrap().clone();
+        for (device, state) in device_cursors {
+            if let DeviceCursor { cursor: Some(SelectedCursor::Custom(_)), hidden: false } = state {
+                if let Err(err) = self.apply_device_cursor(device, &state, scale_factor) {
+                    tracing::error!("failed to set cursor icon: {err}");
+                }
+            }
+        }
+
+        #[allow(clippy::mutex_atomic)]
+        if !*self.cursor_visible.lock().unwrap() {
+            return;
+        }
+        let SelectedCursor::Custom(cursor) = self.selected_cursor.lock().unwrap().clone() else {
+            return;
+        };
+        if let Err(err) = self.xconn.set_custom_cursor(self.xwindow, &cursor, scale_factor) {
+            tracing::error!("failed to set cursor icon: {err}");
+        }
+    }
+
+    pub fn set_cursor_for_device(
+        &self,
+        device_id: DeviceId,
+        cursor: Cursor,
+    ) -> Result<(), RequestError> {
+        let cursor = match cursor {
+            Cursor::Icon(icon) => SelectedCursor::Named(icon),
+            Cursor::Custom(RootCustomCursor { inner: PlatformCustomCursor::X(cursor) }) => {
+                SelectedCursor::Custom(cursor)
+            },
+            #[cfg(wayland_platform)]
+            Cursor::Custom(RootCustomCursor { inner: PlatformCustomCursor::Wayland(_) }) => {
+                return Err(NotSupportedError::new("passed a Wayland cursor to X11 backend").into());
+            },
+        };
+        self.update_device_cursor(device_id, |state| state.cursor = Some(cursor))
+    }
+
+    pub fn set_cursor_visible_for_device(
+        &self,
+        device_id: DeviceId,
+        visible: bool,
+    ) -> Result<(), RequestError> {
+        self.update_device_cursor(device_id, |state| state.hidden = !visible)
+    }
+
+    fn update_device_cursor(
+        &self,
+        device_id: DeviceId,
+        update: impl FnOnce(&mut DeviceCursor),
+    ) -> Result<(), RequestError> {
+        let device = xinput::DeviceId::try_from(device_id.into_raw())
+            .map_err(|_| NotSupportedError::new("the device isn't an X11 pointer"))?;
+        let mut device_cursors = self.device_cursors.lock().unwrap();
+        let mut state = device_cursors.get(&device).cloned().unwrap_or_default();
+        update(&mut state);
+        self.apply_device_cursor(device, &state, self.scale_factor())
+            .map_err(|err| os_error!(err))?;
+        device_cursors.insert(device, state);
+        Ok(())
+    }
+
+    fn apply_device_cursor(
+        &self,
+        device: xinput::DeviceId,
+        state: &DeviceCursor,
+        scale_factor: f64,
+    ) -> Result<(), X11Error> {
+        let cursor = match &state.cursor {
+            _ if state.hidden => self.xconn.cached_cursor(None)?,
+            Some(SelectedCursor::Named(icon)) => self.xconn.cached_cursor(Some(*icon))?,
+            Some(SelectedCursor::Custom(cursor)) => cursor.for_scale_factor(scale_factor),
+            None => x11rb::NONE,
+        };
+        self.xconn.set_device_cursor(self.xwindow, device, cursor)
+    }
+
     #[inline]
     pub fn set_cursor_visible(&self, visible: bool) {
         #[allow(clippy::mutex_atomic)]
@@ -1906,7 +2195,7 @@ impl UnownedWindow {
         drop(visible_lock);
         let result = match cursor {
             Some(SelectedCursor::Custom(cursor)) => {
-                self.xconn.set_custom_cursor(self.xwindow, &cursor)
+                self.xconn.set_custom_cursor(self.xwindow, &cursor, self.scale_factor())
             },
             Some(SelectedCursor::Named(cursor)) => {
                 self.xconn.set_cursor_icon(self.xwindow, Some(cursor))
@@ -1919,6 +2208,41 @@ impl UnownedWindow {
         }
     }
 
+    /// Show the image of a drag started from the window, or the selected cursor again.
+    ///
+    /// The pointer is grabbed by the window during the drag, so X shows its cursor wherever the
+    /// pointer is.
+    pub(crate) fn set_drag_cursor(&self, image: Option<&Cursor>) {
+        #[allow(clippy::mutex_atomic)]
+        let cursor = match image {
+            Some(Cursor::Icon(icon)) => Some(SelectedCursor::Named(*icon)),
+            Some(Cursor::Custom(RootCustomCursor { inner: PlatformCustomCursor::X(cursor) })) => {
+                Some(SelectedCursor::Custom(cursor.clone()))
+            },
+            #[cfg(wayland_platform)]
+            Some(Cursor::Custom(RootCustomCursor { inner: PlatformCustomCursor::Wayland(_) })) => {
+                tracing::error!("passed a Wayland cursor to X11 backend");
+                return;
+            },
+            None if *self.cursor_visible.lock().unwrap() => {
+                Some(self.selected_cursor.lock().unwrap().clone())
+            },
+            None => None,
+        };
+        let result = match cursor {
+            Some(SelectedCursor::Custom(cursor)) => {
+                self.xconn.set_custom_cursor(self.xwindow, &cursor, self.scale_factor())
+            },
+            Some(SelectedCursor::Named(cursor)) => {
+                self.xconn.set_cursor_icon(self.xwindow, Some(cursor))
+            },
+            None => self.xconn.set_cursor_icon(self.xwindow, None),
+        };
+        if let Err(err) = result {
+            tracing::error!("failed to set the drag cursor: {err}");
+        }
+    }
+
     #[inline]
     pub fn scale_factor(&self) -> f64 {
         self.shared_state_lock().last_monitor.scale_factor
@@ -1961,6 +2285,98 @@ impl UnownedWindow {
         Ok(())
     }
 
+    /// Release the cursor grab when the window loses focus, if the policy asks for it.
+    ///
+    /// Returns whether the grab was released.
+    pub(crate) fn suspend_cursor_grab(&self) -> bool {
+        let mode = self.cursor_grab_mode();
+        let mut shared_state = self.shared_state_lock();
+        if shared_state.cursor_grab_policy != CursorGrabPolicy::ReGrabOnFocus
+            || mode == CursorGrabMode::None
+        {
+            return false;
+        }
+
+        shared_state.suspended_cursor_grab = Some(mode);
+        drop(shared_state);
+        if let Err(err) = self.set_cursor_grab(CursorGrabMode::None) {
+            warn!("Failed to release the cursor grab: {err}");
+        }
+
+        true
+    }
+
+    /// Establish the grab released by [`Self::suspend_cursor_grab`] again.
+    ///
+    /// Returns the grab mode which was established.
+    pub(crate) fn resume_cursor_grab(&self) -> Option<CursorGrabMode> {
+        let mode = self.shared_state_lock().suspended_cursor_grab.take()?;
+        match self.set_cursor_grab(mode) {
+            Ok(()) => Some(mode),
+            Err(err) => {
+                warn!("Failed to establish the cursor grab again: {err}");
+                None
+            },
+        }
+    }
+
+    #[inline]
+    pub fn set_motion_coalescing(&self, coalescing: Coalescing) {
+        *self.motion_coalescing.lock().unwrap() = coalescing;
+    }
+
+    #[inline]
+    pub fn motion_coalescing(&self) -> Coalescing {
+        *self.motion_coalescing.lock().unwrap()
+    }
+
+    #[cfg(feature = "accesskit")]
+    pub fn set_accesskit_tree_source(&self, source: Box<dyn accesskit::ActivationHandler + Send>) {
+        let window_id = self.id();
+        let sender = self.accesskit_sender.clone();
+        let mut adapter =
+            AccessKitAdapter::new(source, move |request| sender.send((window_id, request)));
+        adapter.set_focused(self.has_focus());
+        adapter.set_bounds(self.accesskit_bounds(), self.accesskit_surface_bounds());
+        *self.accesskit.lock().unwrap() = Some(adapter);
+    }
+
+    #[cfg(feature = "accesskit")]
+    pub fn update_accesskit_tree(&self, update: accesskit::TreeUpdate) {
+        if let Some(adapter) = self.accesskit.lock().unwrap().as_mut() {
+            adapter.update(update);
+        }
+    }
+
+    /// Keep the AccessKit adapter in sync with the focus and the bounds of the window.
+    #[cfg(feature = "accesskit")]
+    pub(super) fn update_accesskit_adapter(&self, event: &WindowEvent) {
+        match event {
+            WindowEvent::Focused(focused) => {
+                if let Some(adapter) = self.accesskit.lock().unwrap().as_mut() {
+                    adapter.set_focused(*focused);
+                }
+            },
+            WindowEvent::Moved(_) | WindowEvent::SurfaceResized(_) => {
+                let (outer, surface) = (self.accesskit_bounds(), self.accesskit_surface_bounds());
+                if let Some(adapter) = self.accesskit.lock().unwrap().as_mut() {
+                    adapter.set_bounds(outer, surface);
+                }
+            },
+            _ => (),
+        }
+    }
+
+    #[cfg(feature = "accesskit")]
+    fn accesskit_bounds(&self) -> (PhysicalPosition<i32>, PhysicalSize<u32>) {
+        (self.outer_position().unwrap_or_default(), self.outer_size())
+    }
+
+    #[cfg(feature = "accesskit")]
+    fn accesskit_surface_bounds(&self) -> (PhysicalPosition<i32>, PhysicalSize<u32>) {
+        (self.inner_position().unwrap_or_default(), self.surface_size())
+    }
+
     /// Moves the window while it is being dragged.
     pub fn drag_window(&self) -> Result<(), RequestError> {
         self.drag_initiate(util::MOVERESIZE_MOVE)
@@ -2147,6 +2563,17 @@ impl UnownedWindow {
         self.redraw_sender.send(WindowId::from_raw(self.xwindow as _));
     }
 
+    #[cfg(feature = "winit-test-harness")]
+    pub fn inject_event(&self, event: WindowEvent) {
+        let window_id = WindowId::from_raw(self.xwindow as _);
+        self.injected_sender.send(Event::WindowEvent { window_id, event });
+    }
+
+    #[inline]
+    pub fn request_redraw_at(&self, instant: Instant) {
+        self.scheduled_redraw_sender.send((WindowId::from_raw(self.xwindow as _), instant));
+    }
+
     #[inline]
     pub fn pre_present_notify(&self) {
         // TODO timer
@@ -15,6 +15,8 @@ use super::XConnection;
 type Result<T> = core::result::Result<T, ParserError>;
 
 const DPI_NAME: &[u8] = b"Xft/DPI";
+const CURSOR_THEME_NAME: &[u8] = b"Gtk/CursorThemeName";
+const CURSOR_THEME_SIZE: &[u8] = b"Gtk/CursorThemeSize";
 const DPI_MULTIPLIER: f64 = 1024.0;
 const LITTLE_ENDIAN: u8 = b'l';
 const BIG_ENDIAN: u8 = b'B';
@@ -25,14 +27,7 @@ impl XConnection {
         &self,
         xsettings_screen: xproto::Atom,
     ) -> core::result::Result<Option<f64>, super::X11Error> {
-        let atoms = self.atoms();
-
-        // Get the current owner of the screen's settings.
-        let owner = self.xcb_connection().get_selection_owner(xsettings_screen)?.reply()?;
-
-        // Read the _XSETTINGS_SETTINGS property.
-        let data: Vec<u8> =
-            self.get_property(owner.owner, atoms[_XSETTINGS_SETTINGS], atoms[_XSETTINGS_SETTINGS])?;
+        let data = self.xsettings_data(xsettings_screen)?;
 
         // Parse the property.
         let dpi_setting = read_settings(&data)?
@@ -54,6 +49,46 @@ impl XConnection {
             Ok(None)
         }
     }
+
+    /// Get the name and size of the cursor theme from XSettings.
+    pub(crate) fn xsettings_cursor_theme(
+        &self,
+        xsettings_screen: xproto::Atom,
+    ) -> core::result::Result<(Option<String>, Option<u32>), super::X11Error> {
+        let data = self.xsettings_data(xsettings_screen)?;
+
+        let (mut name, mut size) = (None, None);
+        for setting in read_settings(&data)? {
+            let setting = setting?;
+            match (setting.name, setting.data) {
+                (CURSOR_THEME_NAME, SettingData::String(value)) if !value.is_empty() => {
+                    name = Some(String::from_utf8_lossy(value).into_owned());
+                },
+                (CURSOR_THEME_SIZE, SettingData::Integer(value)) if value > 0 => {
+                    size = Some(value as u32);
+                },
+                _ => (),
+            }
+        }
+
+        Ok((name, size))
+    }
+
+    /// Read the settings of the screen, from the property of the owner of its selection.
+    fn xsettings_data(
+        &self,
+        xsettings_screen: xproto::Atom,
+    ) -> core::result::Result<Vec<u8>, super::X11Error> {
+        let atoms = self.atoms();
+
+        // Get the current owner of the screen's settings.
+        let owner = self.xcb_connection().get_selection_owner(xsettings_screen)?.reply()?;
+
+        // Read the _XSETTINGS_SETTINGS property.
+        let data =
+            self.get_property(owner.owner, atoms[_XSETTINGS_SETTINGS], atoms[_XSETTINGS_SETTINGS])?;
+        Ok(data)
+    }
 }
 
 /// Read over the settings in the block of data.
@@ -81,7 +116,7 @@ struct Setting<'a> {
 /// The data contained in a setting.
 enum SettingData<'a> {
     Integer(i32),
-    String(#[allow(dead_code)] &'a [u8]),
+    String(&'a [u8]),
     Color(#[allow(dead_code)] [i16; 4]),
 }
 
@@ -5,6 +5,8 @@ use crate::window::Fullscreen as RootFullscreen;
 mod android;
 #[cfg(target_vendor = "apple")]
 mod apple;
+#[cfg(any(windows_platform, macos_platform, x11_platform, wayland_platform))]
+mod coalescing;
 #[cfg(any(x11_platform, wayland_platform))]
 mod linux;
 #[cfg(orbital_platform)]
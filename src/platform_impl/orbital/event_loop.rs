@@ -16,16 +16,28 @@ use super::{
     WindowProperties,
 };
 use crate::application::ApplicationHandler;
+use crate::clipboard::{Clipboard as RootClipboard, ClipboardData, ClipboardFormat};
+#[cfg(feature = "dialogs")]
+use crate::dialogs::{FileDialogOptions, MessageBoxConfig};
+use crate::drag::DragData;
 use crate::error::{EventLoopError, NotSupportedError, RequestError};
-use crate::event::{self, Ime, Modifiers, StartCause};
-use crate::event_loop::{self, ActiveEventLoop as RootActiveEventLoop, ControlFlow, DeviceEvents};
+use crate::event::{
+    self, DeviceInfo, Ime, LanguageTag, Modifiers, PlatformData, PowerState, StartCause,
+    SystemPreferences,
+};
+use crate::event_loop::{
+    self, ActiveEventLoop as RootActiveEventLoop, AsyncRequestSerial, ControlFlow, DeviceClass,
+    DeviceEvents, ModifiersOrder,
+};
 use crate::keyboard::{
-    Key, KeyCode, KeyLocation, ModifiersKeys, ModifiersState, NamedKey, NativeKey, NativeKeyCode,
-    PhysicalKey,
+    Key, KeyCode, KeyLocation, KeyboardLayout, Led, ModifiersKeys, ModifiersState, NamedKey,
+    NativeKey, NativeKeyCode, PhysicalKey,
 };
 use crate::platform_impl::Window;
+use crate::utils::RedrawSchedule;
 use crate::window::{
-    CustomCursor as RootCustomCursor, CustomCursorSource, Theme, Window as CoreWindow, WindowId,
+    Cursor, CursorThemeInfo, CustomCursor as RootCustomCursor, CustomCursorSource, Rgba, Theme,
+    Window as CoreWindow, WindowId,
 };
 
 fn convert_scancode(scancode: u8) -> (PhysicalKey, Option<NamedKey>) {
@@ -278,7 +290,9 @@ pub struct EventLoop {
 }
 
 impl EventLoop {
-    pub(crate) fn new(_: &PlatformSpecificEventLoopAttributes) -> Result<Self, EventLoopError> {
+    pub(crate) fn new(
+        attributes: &PlatformSpecificEventLoopAttributes,
+    ) -> Result<Self, EventLoopError> {
         // NOTE: Create a channel which can hold only one event to automatically _squash_ user
         // events.
         let (user_events_sender, user_events_receiver) = mpsc::sync_channel(1);
@@ -304,10 +318,12 @@ impl EventLoop {
                 exit: Cell::new(false),
                 creates: Mutex::new(VecDeque::new()),
                 redraws: Arc::new(Mutex::new(VecDeque::new())),
+                redraw_schedule: Arc::new(Mutex::new(RedrawSchedule::default())),
                 destroys: Arc::new(Mutex::new(VecDeque::new())),
                 event_socket,
                 wake_socket,
                 user_events_sender,
+                modifiers_order: attributes.modifiers_order,
             },
             user_events_receiver,
         })
@@ -378,15 +394,15 @@ impl EventLoop {
                     is_synthetic: false,
                 };
 
-                app.window_event(window_target, window_id, event);
-
-                // If the state of the modifiers has changed, send the event.
-                if modifiers_before != event_state.keyboard {
-                    app.window_event(
-                        window_target,
-                        window_id,
-                        event::WindowEvent::ModifiersChanged(event_state.modifiers()),
-                    );
+                // If the state of the modifiers has changed, send the event in the chosen order.
+                let modifiers_changed = (modifiers_before != event_state.keyboard)
+                    .then(|| event::WindowEvent::ModifiersChanged(event_state.modifiers()));
+                let events = match window_target.modifiers_order {
+                    ModifiersOrder::BeforeKeyboardInput => [modifiers_changed, Some(event)],
+                    ModifiersOrder::AfterKeyboardInput => [Some(event), modifiers_changed],
+                };
+                for event in events.into_iter().flatten() {
+                    app.window_event(window_target, window_id, event);
                 }
             },
             EventOption::TextInput(TextInputEvent { character }) => {
@@ -428,6 +444,7 @@ impl EventLoop {
                     device_id: None,
                     delta: event::MouseScrollDelta::LineDelta(x as f32, y as f32),
                     phase: event::TouchPhase::Moved,
+                    momentum: false,
                 });
             },
             EventOption::Quit(QuitEvent {}) => {
@@ -573,6 +590,15 @@ impl EventLoop {
                 app.proxy_wake_up(&self.window_target);
             }
 
+            // Queue the scheduled redraws which are due.
+            let due = self.window_target.redraw_schedule.lock().unwrap().take_due(Instant::now());
+            for window_id in due {
+                let mut redraws = self.window_target.redraws.lock().unwrap();
+                if !redraws.contains(&window_id) {
+                    redraws.push_back(window_id);
+                }
+            }
+
             // To avoid deadlocks the redraws lock is not held during event processing.
             while let Some(window_id) = {
                 let mut redraws = self.window_target.redraws.lock().unwrap();
@@ -613,8 +639,18 @@ impl EventLoop {
                 })
                 .unwrap();
 
+            // Wake up for the next scheduled redraw as well.
+            let redraw_deadline =
+                self.window_target.redraw_schedule.lock().unwrap().next_deadline();
+            let timeout = match (requested_resume, redraw_deadline) {
+                (Some(requested_resume), Some(redraw_deadline)) => {
+                    Some(requested_resume.min(redraw_deadline))
+                },
+                (requested_resume, redraw_deadline) => requested_resume.or(redraw_deadline),
+            };
+
             let start = Instant::now();
-            if let Some(instant) = requested_resume {
+            if let Some(instant) = timeout {
                 let mut time = timeout_socket.current_time().unwrap();
 
                 if let Some(duration) = instant.checked_duration_since(start) {
@@ -636,7 +672,9 @@ impl EventLoop {
 
             // TODO: handle spurious wakeups (redraw caused wakeup but redraw already handled)
             match requested_resume {
-                Some(requested_resume) if event.id == timeout_socket.0.fd => {
+                Some(requested_resume)
+                    if event.id == timeout_socket.0.fd && Instant::now() >= requested_resume =>
+                {
                     // If the event is from the special timeout socket, report that resume
                     // time was reached.
                     start_cause = StartCause::ResumeTimeReached { start, requested_resume };
@@ -689,10 +727,22 @@ pub struct ActiveEventLoop {
     exit: Cell<bool>,
     pub(super) creates: Mutex<VecDeque<Arc<RedoxSocket>>>,
     pub(super) redraws: Arc<Mutex<VecDeque<WindowId>>>,
+    pub(super) redraw_schedule: Arc<Mutex<RedrawSchedule>>,
     pub(super) destroys: Arc<Mutex<VecDeque<WindowId>>>,
     pub(super) event_socket: Arc<RedoxSocket>,
     pub(super) wake_socket: Arc<TimeSocket>,
     user_events_sender: mpsc::SyncSender<()>,
+    modifiers_order: ModifiersOrder,
+}
+
+impl RootClipboard for ActiveEventLoop {
+    fn set(&self, _data: Vec<ClipboardData>) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set is not supported").into())
+    }
+
+    fn get(&self, _format: ClipboardFormat) -> Result<AsyncRequestSerial, RequestError> {
+        Err(NotSupportedError::new("get is not supported").into())
+    }
 }
 
 impl RootActiveEventLoop for ActiveEventLoop {
@@ -729,12 +779,123 @@ impl RootActiveEventLoop for ActiveEventLoop {
         None
     }
 
+    fn system_accent_color(&self) -> Option<Rgba> {
+        None
+    }
+
+    fn cursor_theme(&self) -> Option<CursorThemeInfo> {
+        None
+    }
+
+    fn system_preferences(&self) -> Option<SystemPreferences> {
+        None
+    }
+
+    fn text_scale_factor(&self) -> Option<f64> {
+        None
+    }
+
+    fn power_state(&self) -> Option<PowerState> {
+        None
+    }
+
+    fn locale(&self) -> LanguageTag {
+        LanguageTag::from_env()
+    }
+
+    fn keyboard_layout(&self) -> Option<KeyboardLayout> {
+        None
+    }
+
+    fn key_label(&self, _key_code: KeyCode) -> Option<String> {
+        None
+    }
+
+    fn key_for_keycode(&self, _key_code: KeyCode) -> Option<Key> {
+        None
+    }
+
+    fn keycode_for_key(&self, _key: &Key) -> Option<KeyCode> {
+        None
+    }
+
+    fn set_keyboard_led(&self, _led: Led, _on: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_keyboard_led is not supported").into())
+    }
+
+    fn keyboard_led(&self, _led: Led) -> Option<bool> {
+        None
+    }
+
+    fn event_platform_data(&self) -> Option<PlatformData> {
+        None
+    }
+
     fn primary_monitor(&self) -> Option<crate::monitor::MonitorHandle> {
         Some(crate::monitor::MonitorHandle { inner: MonitorHandle })
     }
 
     fn listen_device_events(&self, _allowed: DeviceEvents) {}
 
+    fn listen_device_classes(&self, _classes: DeviceClass) {}
+
+    fn input_devices(&self) -> Vec<DeviceInfo> {
+        Vec::new()
+    }
+
+    #[cfg(feature = "gamepad")]
+    fn set_gamepad_rumble(
+        &self,
+        _device_id: crate::event::DeviceId,
+        _strong: f32,
+        _weak: f32,
+    ) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("gamepads are not supported").into())
+    }
+
+    fn windows(&self) -> Vec<WindowId> {
+        Vec::new()
+    }
+
+    fn with_native_modal_scope(&self, f: &mut dyn FnMut()) {
+        f()
+    }
+
+    fn start_drag(&self, _source: WindowId, _data: DragData) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("start_drag is not supported").into())
+    }
+
+    fn set_drag_image(&self, _image: Option<Cursor>) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_drag_image is not supported").into())
+    }
+
+    fn clipboard(&self) -> &dyn RootClipboard {
+        self
+    }
+
+    #[cfg(feature = "dialogs")]
+    fn pick_file(&self, _options: FileDialogOptions) -> Result<AsyncRequestSerial, RequestError> {
+        Err(NotSupportedError::new("pick_file is not supported").into())
+    }
+
+    #[cfg(feature = "dialogs")]
+    fn pick_folder(&self, _options: FileDialogOptions) -> Result<AsyncRequestSerial, RequestError> {
+        Err(NotSupportedError::new("pick_folder is not supported").into())
+    }
+
+    #[cfg(feature = "dialogs")]
+    fn save_file(&self, _options: FileDialogOptions) -> Result<AsyncRequestSerial, RequestError> {
+        Err(NotSupportedError::new("save_file is not supported").into())
+    }
+
+    #[cfg(feature = "dialogs")]
+    fn show_message_box(
+        &self,
+        _config: MessageBoxConfig,
+    ) -> Result<AsyncRequestSerial, RequestError> {
+        Err(NotSupportedError::new("show_message_box is not supported").into())
+    }
+
     fn set_control_flow(&self, control_flow: ControlFlow) {
         self.control_flow.set(control_flow)
     }
@@ -7,7 +7,10 @@ use smol_str::SmolStr;
 
 pub(crate) use self::event_loop::{ActiveEventLoop, EventLoop, EventLoopProxy, OwnedDisplayHandle};
 use crate::dpi::{PhysicalPosition, PhysicalSize};
+use crate::error::{NotSupportedError, RequestError};
+use crate::event_loop::ModifiersOrder;
 use crate::keyboard::Key;
+use crate::monitor::{ColorCapabilities, MonitorIdentity, Orientation};
 mod event_loop;
 
 pub use self::window::Window;
@@ -18,6 +21,12 @@ pub(crate) use crate::cursor::{
 };
 pub(crate) use crate::icon::NoIcon as PlatformIcon;
 
+/// Nothing is grabbed by the application that would outlive a panic.
+pub(crate) fn restore_after_panic() {}
+
+/// There is no dialog that can be shown from the panic hook.
+pub(crate) fn show_panic_dialog(_message: &str) {}
+
 struct RedoxSocket {
     fd: usize,
 }
@@ -97,7 +106,9 @@ impl TimeSocket {
 }
 
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
-pub(crate) struct PlatformSpecificEventLoopAttributes {}
+pub(crate) struct PlatformSpecificEventLoopAttributes {
+    pub(crate) modifiers_order: ModifiersOrder,
+}
 
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct FingerId;
@@ -161,6 +172,22 @@ impl MonitorHandle {
         1.0 // TODO
     }
 
+    pub fn physical_size_mm(&self) -> Option<(u32, u32)> {
+        None
+    }
+
+    pub fn identity(&self) -> Option<MonitorIdentity> {
+        None
+    }
+
+    pub fn orientation(&self) -> Orientation {
+        Orientation::Landscape
+    }
+
+    pub fn icc_profile(&self) -> Option<Vec<u8>> {
+        None
+    }
+
     pub fn current_video_mode(&self) -> Option<VideoModeHandle> {
         // (it is guaranteed to support 32 bit color though)
         Some(VideoModeHandle { monitor: self.clone() })
@@ -169,6 +196,18 @@ impl MonitorHandle {
     pub fn video_modes(&self) -> impl Iterator<Item = VideoModeHandle> {
         self.current_video_mode().into_iter()
     }
+
+    pub fn color_capabilities(&self) -> Result<ColorCapabilities, RequestError> {
+        Err(NotSupportedError::new("color_capabilities is not supported").into())
+    }
+
+    pub fn brightness(&self) -> Result<f64, RequestError> {
+        Err(NotSupportedError::new("brightness is not supported").into())
+    }
+
+    pub fn set_brightness(&self, _brightness: f64) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_brightness is not supported").into())
+    }
 }
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
@@ -1,12 +1,15 @@
 use std::collections::VecDeque;
 use std::sync::{Arc, Mutex};
+use std::time::Instant;
 
 use super::{ActiveEventLoop, MonitorHandle, RedoxSocket, TimeSocket, WindowProperties};
 use crate::cursor::Cursor;
 use crate::dpi::{PhysicalPosition, PhysicalSize, Position, Size};
 use crate::error::{NotSupportedError, RequestError};
-use crate::monitor::MonitorHandle as CoreMonitorHandle;
-use crate::window::{self, Fullscreen, ImePurpose, Window as CoreWindow, WindowId};
+use crate::event::DeviceId;
+use crate::monitor::{GammaRamp, HdrMetadata, MonitorHandle as CoreMonitorHandle};
+use crate::utils::RedrawSchedule;
+use crate::window::{self, Coalescing, Fullscreen, ImePurpose, Window as CoreWindow, WindowId};
 
 // These values match the values uses in the `window_new` function in orbital:
 // https://gitlab.redox-os.org/redox-os/orbital/-/blob/master/src/scheme.rs
@@ -22,6 +25,7 @@ const ORBITAL_FLAG_TRANSPARENT: char = 't';
 pub struct Window {
     window_socket: Arc<RedoxSocket>,
     redraws: Arc<Mutex<VecDeque<WindowId>>>,
+    redraw_schedule: Arc<Mutex<RedrawSchedule>>,
     destroys: Arc<Mutex<VecDeque<WindowId>>>,
     wake_socket: Arc<TimeSocket>,
 }
@@ -118,6 +122,7 @@ impl Window {
         Ok(Self {
             window_socket,
             redraws: el.redraws.clone(),
+            redraw_schedule: el.redraw_schedule.clone(),
             destroys: el.destroys.clone(),
             wake_socket: el.wake_socket.clone(),
         })
@@ -190,6 +195,12 @@ impl CoreWindow for Window {
         }
     }
 
+    fn request_redraw_at(&self, instant: Instant) {
+        self.redraw_schedule.lock().unwrap().schedule(self.id(), instant);
+        // Wake up the event loop, so it waits for the new deadline.
+        self.wake_socket.wake().unwrap();
+    }
+
     #[inline]
     fn pre_present_notify(&self) {}
 
@@ -319,6 +330,18 @@ impl CoreWindow for Window {
         None
     }
 
+    fn set_fullscreen_hdr(&self, _hdr: Option<HdrMetadata>) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_fullscreen_hdr is not supported").into())
+    }
+
+    fn fullscreen_hdr(&self) -> Option<HdrMetadata> {
+        None
+    }
+
+    fn set_gamma_ramp(&self, _ramp: Option<&GammaRamp>) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_gamma_ramp is not supported").into())
+    }
+
     #[inline]
     fn set_decorations(&self, decorations: bool) {
         let _ = self.set_flag(ORBITAL_FLAG_BORDERLESS, !decorations);
@@ -351,18 +374,41 @@ impl CoreWindow for Window {
     #[inline]
     fn set_ime_cursor_area(&self, _position: Position, _size: Size) {}
 
+    fn set_ime_exclusion_area(&self, _area: Option<(Position, Size)>) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_ime_exclusion_area is not supported").into())
+    }
+
+    fn set_ime_candidates_drawn_by_app(&self, _drawn_by_app: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_ime_candidates_drawn_by_app is not supported").into())
+    }
+
     #[inline]
     fn set_ime_allowed(&self, _allowed: bool) {}
 
+    fn set_virtual_keyboard_visible(&self, _visible: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_virtual_keyboard_visible is not supported").into())
+    }
+
     #[inline]
     fn set_ime_purpose(&self, _purpose: ImePurpose) {}
 
+    #[inline]
+    fn set_ime_surrounding_text(&self, _text: &str, _cursor: usize, _anchor: usize) {}
+
     #[inline]
     fn focus_window(&self) {}
 
     #[inline]
     fn request_user_attention(&self, _request_type: Option<window::UserAttentionType>) {}
 
+    fn announce(
+        &self,
+        _message: &str,
+        _politeness: window::Politeness,
+    ) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("announce is not supported").into())
+    }
+
     #[inline]
     fn set_cursor(&self, _: Cursor) {}
 
@@ -387,11 +433,21 @@ impl CoreWindow for Window {
         Ok(())
     }
 
+    fn set_cursor_grab_policy(&self, _policy: window::CursorGrabPolicy) {}
+
     #[inline]
     fn set_cursor_visible(&self, visible: bool) {
         let _ = self.window_socket.write(format!("M,C,{}", if visible { 1 } else { 0 }).as_bytes());
     }
 
+    fn set_cursor_for_device(&self, _: DeviceId, _: Cursor) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_cursor_for_device is not supported").into())
+    }
+
+    fn set_cursor_visible_for_device(&self, _: DeviceId, _: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_cursor_visible_for_device is not supported").into())
+    }
+
     #[inline]
     fn drag_window(&self) -> Result<(), RequestError> {
         self.window_socket.write(b"D").map_err(|err| os_error!(format!("{err}")))?;
@@ -424,6 +480,10 @@ impl CoreWindow for Window {
         Err(NotSupportedError::new("set_cursor_hittest is not supported").into())
     }
 
+    fn set_motion_coalescing(&self, _coalescing: Coalescing) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_motion_coalescing is not supported").into())
+    }
+
     #[inline]
     fn set_enabled_buttons(&self, _buttons: window::WindowButtons) {}
 
@@ -442,6 +502,10 @@ impl CoreWindow for Window {
         false
     }
 
+    fn set_keyboard_capture(&self, _capture: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_keyboard_capture is not supported").into())
+    }
+
     #[inline]
     fn set_theme(&self, _theme: Option<window::Theme>) {}
 
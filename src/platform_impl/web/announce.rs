@@ -0,0 +1,39 @@
+//! Screen reader announcements with hidden ARIA live regions.
+
+use web_sys::{Document, Element};
+
+use crate::window::Politeness;
+
+/// Speak the message by replacing the text of the live region of the politeness.
+pub(crate) fn announce(document: &Document, message: &str, politeness: Politeness) {
+    let Some(region) = live_region(document, politeness) else { return };
+    // Clearing the text first makes the same message be announced again.
+    region.set_text_content(None);
+    region.set_text_content(Some(message));
+}
+
+/// The live region of the politeness, created in the body of the document on first use.
+fn live_region(document: &Document, politeness: Politeness) -> Option<Element> {
+    let (id, live) = match politeness {
+        Politeness::Polite => ("winit-announcer-polite", "polite"),
+        Politeness::Assertive => ("winit-announcer-assertive", "assertive"),
+    };
+    if let Some(region) = document.get_element_by_id(id) {
+        return Some(region);
+    }
+
+    let region = document.create_element("div").ok()?;
+    region.set_id(id);
+    region.set_attribute("aria-live", live).ok()?;
+    region.set_attribute("aria-atomic", "true").ok()?;
+    // Visually hidden, but still read by the screen readers.
+    region
+        .set_attribute(
+            "style",
+            "position: absolute; width: 1px; height: 1px; margin: -1px; padding: 0; border: 0; \
+             overflow: hidden; clip: rect(0 0 0 0); white-space: nowrap;",
+        )
+        .ok()?;
+    document.body()?.append_child(&region).ok()?;
+    Some(region)
+}
@@ -1,4 +1,5 @@
 use std::cell::RefCell;
+use std::collections::HashSet;
 use std::future::{self, Future};
 use std::hash::{Hash, Hasher};
 use std::mem;
@@ -24,12 +25,15 @@ use super::backend::Style;
 use super::main_thread::{MainThreadMarker, MainThreadSafe};
 use super::r#async::{AbortHandle, Abortable, DropAbortHandle, Notified, Notifier};
 use super::ActiveEventLoop;
-use crate::cursor::{BadImage, Cursor, CursorImage, CustomCursor as RootCustomCursor};
+use crate::cursor::{
+    BadImage, Cursor, CursorImage, CursorImages, CustomCursor as RootCustomCursor,
+};
+use crate::event::DeviceId;
 use crate::platform::web::CustomCursorError;
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub(crate) enum CustomCursorSource {
-    Image(CursorImage),
+    Image(CursorImages),
     Url { url: String, hotspot_x: u16, hotspot_y: u16 },
     Animation { duration: Duration, cursors: Vec<RootCustomCursor> },
 }
@@ -42,9 +46,21 @@ impl CustomCursorSource {
         hotspot_x: u16,
         hotspot_y: u16,
     ) -> Result<CustomCursorSource, BadImage> {
-        Ok(CustomCursorSource::Image(CursorImage::from_rgba(
+        Ok(CustomCursorSource::Image(CursorImages::new(CursorImage::from_rgba(
             rgba, width, height, hotspot_x, hotspot_y,
-        )?))
+        )?)))
+    }
+
+    pub fn push_scaled_rgba(
+        &mut self,
+        rgba: Vec<u8>,
+        width: u16,
+        height: u16,
+        hotspot_x: u16,
+        hotspot_y: u16,
+    ) -> Result<(), BadImage> {
+        let CustomCursorSource::Image(images) = self else { return Err(BadImage::NotAnImage) };
+        images.push_scaled(CursorImage::from_rgba(rgba, width, height, hotspot_x, hotspot_y)?)
     }
 }
 
@@ -71,9 +87,14 @@ impl Eq for CustomCursor {}
 impl CustomCursor {
     pub(crate) fn new(event_loop: &ActiveEventLoop, source: CustomCursorSource) -> Self {
         match source {
-            CustomCursorSource::Image(image) => Self::build_spawn(
+            // Cursor images are sized in CSS pixels, so the browser already scales them.
+            CustomCursorSource::Image(images) => Self::build_spawn(
                 event_loop,
-                from_rgba(event_loop.runner.window(), event_loop.runner.document().clone(), &image),
+                from_rgba(
+                    event_loop.runner.window(),
+                    event_loop.runner.document().clone(),
+                    images.base(),
+                ),
                 false,
             ),
             CustomCursorSource::Url { url, hotspot_x, hotspot_y } => Self::build_spawn(
@@ -194,6 +215,10 @@ struct Inner {
     canvas: HtmlCanvasElement,
     style: Style,
     visible: bool,
+    /// The devices for which the cursor is hidden.
+    hidden_devices: HashSet<DeviceId>,
+    /// The last device to enter or move over the canvas.
+    active_device: Option<DeviceId>,
     cursor: SelectedCursor,
 }
 
@@ -208,6 +233,8 @@ impl CursorHandler {
             canvas,
             style,
             visible: true,
+            hidden_devices: HashSet::new(),
+            active_device: None,
             cursor: SelectedCursor::default(),
         })))
     }
@@ -286,7 +313,7 @@ impl CursorHandler {
                         );
                         drop(state);
 
-                        if !this.visible {
+                        if !this.is_visible() {
                             animation.cancel();
                         }
 
@@ -303,24 +330,55 @@ impl CursorHandler {
 
     pub fn set_cursor_visible(&self, visible: bool) {
         let mut this = self.0.borrow_mut();
+        let was_visible = this.is_visible();
+        this.visible = visible;
+        this.update_visibility(was_visible);
+    }
+
+    pub fn set_cursor_visible_for_device(&self, device_id: DeviceId, visible: bool) {
+        let mut this = self.0.borrow_mut();
+        let was_visible = this.is_visible();
+        if visible {
+            this.hidden_devices.remove(&device_id);
+        } else {
+            this.hidden_devices.insert(device_id);
+        }
+        this.update_visibility(was_visible);
+    }
+
+    /// A pointer entered or moved over the canvas, which shows or hides the cursor for it.
+    pub fn pointer_active(&self, device_id: Option<DeviceId>) {
+        let mut this = self.0.borrow_mut();
+        if this.active_device == device_id {
+            return;
+        }
+        let was_visible = this.is_visible();
+        this.active_device = device_id;
+        this.update_visibility(was_visible);
+    }
+}
+
+impl Inner {
+    fn is_visible(&self) -> bool {
+        self.visible
+            && !self.active_device.is_some_and(|device| self.hidden_devices.contains(&device))
+    }
 
-        if !visible && this.visible {
-            this.visible = false;
-            this.style.set("cursor", "none");
+    fn update_visibility(&self, was_visible: bool) {
+        let visible = self.is_visible();
+        if !visible && was_visible {
+            self.style.set("cursor", "none");
 
-            if let SelectedCursor::Animation { animation, .. } = &this.cursor {
+            if let SelectedCursor::Animation { animation, .. } = &self.cursor {
                 animation.0.cancel();
             }
-        } else if visible && !this.visible {
-            this.visible = true;
-            this.set_style();
+        } else if visible && !was_visible {
+            self.set_style();
         }
     }
-}
 
-impl Inner {
     fn set_style(&self) {
-        if self.visible {
+        if self.is_visible() {
             match &self.cursor {
                 SelectedCursor::Icon(icon)
                 | SelectedCursor::Loading { previous: Previous::Icon(icon), .. } => {
@@ -368,7 +426,7 @@ impl Inner {
                 );
                 drop(state);
 
-                if !self.visible {
+                if !self.is_visible() {
                     animation.cancel();
                 }
 
@@ -2,7 +2,7 @@ use super::{backend, HasMonitorPermissionFuture, MonitorPermissionFuture};
 use crate::application::ApplicationHandler;
 use crate::error::{EventLoopError, NotSupportedError};
 use crate::event::Event;
-use crate::event_loop::ActiveEventLoop as RootActiveEventLoop;
+use crate::event_loop::{ActiveEventLoop as RootActiveEventLoop, ExitResponse, ModifiersOrder};
 use crate::platform::web::{PollStrategy, WaitUntilStrategy};
 
 mod proxy;
@@ -18,11 +18,15 @@ pub struct EventLoop {
 }
 
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
-pub(crate) struct PlatformSpecificEventLoopAttributes {}
+pub(crate) struct PlatformSpecificEventLoopAttributes {
+    pub(crate) modifiers_order: ModifiersOrder,
+}
 
 impl EventLoop {
-    pub(crate) fn new(_: &PlatformSpecificEventLoopAttributes) -> Result<Self, EventLoopError> {
-        Ok(EventLoop { elw: ActiveEventLoop::new() })
+    pub(crate) fn new(
+        attributes: &PlatformSpecificEventLoopAttributes,
+    ) -> Result<Self, EventLoopError> {
+        Ok(EventLoop { elw: ActiveEventLoop::new(attributes.modifiers_order) })
     }
 
     pub fn run_app<A: ApplicationHandler>(self, mut app: A) -> ! {
@@ -97,7 +101,41 @@ fn handle_event<A: ApplicationHandler>(app: &mut A, target: &ActiveEventLoop, ev
         Event::Resumed => app.resumed(target),
         Event::CreateSurfaces => app.can_create_surfaces(target),
         Event::AboutToWait => app.about_to_wait(target),
+        Event::ExitRequested => {
+            if app.exit_requested(target) == ExitResponse::Exit {
+                target.exit();
+            }
+        },
         Event::LoopExiting => app.exiting(target),
         Event::MemoryWarning => app.memory_warning(target),
+        Event::PowerEvent(event) => app.power_event(target, event),
+        Event::ReopenRequested => app.reopen_requested(target),
+        Event::OpenFiles(paths) => app.open_files(target, paths),
+        Event::OpenUrls(urls) => app.open_urls(target, urls),
+        Event::SessionLocked => app.session_locked(target),
+        Event::SessionUnlocked => app.session_unlocked(target),
+        Event::ClipboardData { serial, data } => app.clipboard_data(target, serial, data),
+        Event::ClipboardChanged => app.clipboard_changed(target),
+        Event::InputDeviceAdded(device) => app.input_device_added(target, device),
+        Event::InputDeviceRemoved(device_id) => app.input_device_removed(target, device_id),
+        Event::LocaleChanged(locale) => app.locale_changed(target, locale),
+        Event::CursorThemeChanged(theme) => app.cursor_theme_changed(target, theme),
+        Event::SystemPreferencesChanged(preferences) => {
+            app.system_preferences_changed(target, preferences)
+        },
+        Event::TextScaleFactorChanged(factor) => app.text_scale_factor_changed(target, factor),
+        Event::MonitorAdded(monitor) => app.monitor_added(target, monitor),
+        Event::MonitorRemoved(monitor) => app.monitor_removed(target, monitor),
+        Event::ColorCapabilitiesChanged(monitor) => app.color_capabilities_changed(target, monitor),
+        Event::MonitorOrientationChanged(monitor) => {
+            app.monitor_orientation_changed(target, monitor)
+        },
+        Event::MonitorRefreshRateChanged(monitor) => {
+            app.monitor_refresh_rate_changed(target, monitor)
+        },
+        #[cfg(feature = "dialogs")]
+        Event::FileDialogDone { serial, result } => app.file_dialog_done(target, serial, result),
+        #[cfg(feature = "dialogs")]
+        Event::MessageBoxDone { serial, result } => app.message_box_done(target, serial, result),
     }
 }
@@ -1,5 +1,5 @@
 use std::cell::{Cell, RefCell};
-use std::collections::{HashSet, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::iter;
 use std::ops::Deref;
 use std::rc::{Rc, Weak};
@@ -16,7 +16,7 @@ use super::backend;
 use super::state::State;
 use crate::dpi::PhysicalSize;
 use crate::event::{DeviceEvent, ElementState, Event, RawKeyEvent, StartCause, WindowEvent};
-use crate::event_loop::{ControlFlow, DeviceEvents};
+use crate::event_loop::{ControlFlow, DeviceClass, DeviceEvents};
 use crate::platform::web::{PollStrategy, WaitUntilStrategy};
 use crate::platform_impl::platform::backend::EventListenerHandle;
 use crate::platform_impl::platform::r#async::{DispatchRunner, Waker, WakerSpawner};
@@ -53,10 +53,13 @@ struct Execution {
     #[allow(clippy::type_complexity)]
     all_canvases: RefCell<Vec<(WindowId, Weak<backend::Canvas>, DispatchRunner<Inner>)>>,
     redraw_pending: RefCell<HashSet<WindowId>>,
+    /// The timers of the redraws scheduled by the windows, with their deadline.
+    redraw_timers: RefCell<HashMap<WindowId, (Instant, backend::Schedule)>>,
     destroy_pending: RefCell<VecDeque<WindowId>>,
     pub(crate) monitor: Rc<MonitorHandler>,
     page_transition_event_handle: RefCell<Option<backend::PageTransitionEventHandle>>,
     device_events: Cell<DeviceEvents>,
+    device_classes: Cell<DeviceClass>,
     on_mouse_move: OnEventHandle<PointerEvent>,
     on_wheel: OnEventHandle<WheelEvent>,
     on_mouse_press: OnEventHandle<PointerEvent>,
@@ -64,6 +67,10 @@ struct Execution {
     on_key_press: OnEventHandle<KeyboardEvent>,
     on_key_release: OnEventHandle<KeyboardEvent>,
     on_visibility_change: OnEventHandle<web_sys::Event>,
+    on_language_change: OnEventHandle<web_sys::Event>,
+    on_preferences_change: RefCell<Vec<backend::MediaQueryListHandle>>,
+    #[cfg(feature = "gamepad")]
+    gamepad_poller: RefCell<Option<backend::gamepad::GamepadPoller>>,
 }
 
 enum RunnerEnum {
@@ -171,10 +178,12 @@ impl Shared {
                 id: Cell::new(0),
                 all_canvases: RefCell::new(Vec::new()),
                 redraw_pending: RefCell::new(HashSet::new()),
+                redraw_timers: RefCell::new(HashMap::new()),
                 destroy_pending: RefCell::new(VecDeque::new()),
                 monitor: Rc::new(monitor),
                 page_transition_event_handle: RefCell::new(None),
                 device_events: Cell::default(),
+                device_classes: Cell::default(),
                 on_mouse_move: RefCell::new(None),
                 on_wheel: RefCell::new(None),
                 on_mouse_press: RefCell::new(None),
@@ -182,6 +191,10 @@ impl Shared {
                 on_key_press: RefCell::new(None),
                 on_key_release: RefCell::new(None),
                 on_visibility_change: RefCell::new(None),
+                on_language_change: RefCell::new(None),
+                on_preferences_change: RefCell::new(Vec::new()),
+                #[cfg(feature = "gamepad")]
+                gamepad_poller: RefCell::new(None),
             }
         }))
     }
@@ -202,6 +215,10 @@ impl Shared {
         &self.0.document
     }
 
+    pub fn windows(&self) -> Vec<WindowId> {
+        self.0.all_canvases.borrow().iter().map(|&(id, ..)| id).collect()
+    }
+
     pub fn add_canvas(
         &self,
         id: WindowId,
@@ -278,7 +295,7 @@ impl Shared {
             self.window().clone(),
             "pointermove",
             Closure::new(move |event: PointerEvent| {
-                if !runner.device_events() {
+                if !runner.device_events(DeviceClass::MOUSE) {
                     return;
                 }
 
@@ -318,7 +335,7 @@ impl Shared {
             self.window().clone(),
             "wheel",
             Closure::new(move |event: WheelEvent| {
-                if !runner.device_events() {
+                if !runner.device_events(DeviceClass::MOUSE) {
                     return;
                 }
 
@@ -335,7 +352,7 @@ impl Shared {
             self.window().clone(),
             "pointerdown",
             Closure::new(move |event: PointerEvent| {
-                if !runner.device_events() {
+                if !runner.device_events(DeviceClass::MOUSE) {
                     return;
                 }
 
@@ -354,7 +371,7 @@ impl Shared {
             self.window().clone(),
             "pointerup",
             Closure::new(move |event: PointerEvent| {
-                if !runner.device_events() {
+                if !runner.device_events(DeviceClass::MOUSE) {
                     return;
                 }
 
@@ -373,7 +390,7 @@ impl Shared {
             self.window().clone(),
             "keydown",
             Closure::new(move |event: KeyboardEvent| {
-                if !runner.device_events() {
+                if !runner.device_events(DeviceClass::KEYBOARD) {
                     return;
                 }
 
@@ -391,7 +408,7 @@ impl Shared {
             self.window().clone(),
             "keyup",
             Closure::new(move |event: KeyboardEvent| {
-                if !runner.device_events() {
+                if !runner.device_events(DeviceClass::KEYBOARD) {
                     return;
                 }
 
@@ -431,6 +448,43 @@ impl Shared {
                 }
             }),
         ));
+        let runner = self.clone();
+        *self.0.on_language_change.borrow_mut() = Some(EventListenerHandle::new(
+            self.window().clone(),
+            "languagechange",
+            Closure::new(move |_| {
+                let locale = backend::locale(runner.navigator());
+                runner.send_event(Event::LocaleChanged(locale));
+            }),
+        ));
+        // Several queries can change at once, e.g. with forced colors, so only report real changes.
+        let preferences = Rc::new(Cell::new(backend::system_preferences(self.window())));
+        *self.0.on_preferences_change.borrow_mut() = backend::SYSTEM_PREFERENCES_QUERIES
+            .iter()
+            .map(|query| {
+                let runner = self.clone();
+                let preferences = preferences.clone();
+                backend::MediaQueryListHandle::new(self.window(), query, move |_| {
+                    let new = backend::system_preferences(runner.window());
+                    if preferences.replace(new) != new {
+                        runner.send_event(Event::SystemPreferencesChanged(new));
+                    }
+                })
+            })
+            .collect();
+        #[cfg(feature = "gamepad")]
+        {
+            let runner = self.clone();
+            *self.0.gamepad_poller.borrow_mut() =
+                Some(backend::gamepad::GamepadPoller::new(self.window().clone(), move |event| {
+                    if matches!(event, Event::DeviceEvent { .. })
+                        && !runner.device_events(DeviceClass::HID)
+                    {
+                        return;
+                    }
+                    runner.send_event(event);
+                }));
+        }
     }
 
     // Generate a strictly increasing ID
@@ -447,6 +501,38 @@ impl Shared {
         self.send_events::<EventWrapper>(iter::empty());
     }
 
+    /// Request an animation frame for the window at `instant`, unless it's already scheduled
+    /// earlier.
+    pub fn request_redraw_at(&self, id: WindowId, instant: Instant) {
+        let now = Instant::now();
+        let mut timers = self.0.redraw_timers.borrow_mut();
+        if timers.get(&id).is_some_and(|&(deadline, _)| now < deadline && deadline <= instant) {
+            return;
+        }
+
+        let runner = self.weak();
+        let timer = backend::Schedule::new_with_duration(
+            self.wait_until_strategy(),
+            self.window(),
+            move || {
+                // The timer is kept until it's replaced, since it can't be dropped while it runs.
+                let Some(runner) = runner.upgrade() else { return };
+                let canvas = runner
+                    .0
+                    .all_canvases
+                    .borrow()
+                    .iter()
+                    .find(|&&(item_id, ..)| item_id == id)
+                    .and_then(|(_, canvas, _)| canvas.upgrade());
+                if let Some(canvas) = canvas {
+                    canvas.request_animation_frame();
+                }
+            },
+            instant.saturating_duration_since(now),
+        );
+        timers.insert(id, (instant, timer));
+    }
+
     fn init(&self) {
         // NB: For consistency all platforms must call `can_create_surfaces` even though Web
         // applications don't themselves have a formal surface destroy/create lifecycle.
@@ -566,6 +652,7 @@ impl Shared {
     fn process_destroy_pending_windows(&self) {
         while let Some(id) = self.0.destroy_pending.borrow_mut().pop_front() {
             self.0.all_canvases.borrow_mut().retain(|&(item_id, ..)| item_id != id);
+            self.0.redraw_timers.borrow_mut().remove(&id);
             self.handle_event(Event::WindowEvent {
                 window_id: id,
                 event: crate::event::WindowEvent::Destroyed,
@@ -722,6 +809,12 @@ impl Shared {
         *self.0.on_key_press.borrow_mut() = None;
         *self.0.on_key_release.borrow_mut() = None;
         *self.0.on_visibility_change.borrow_mut() = None;
+        *self.0.on_language_change.borrow_mut() = None;
+        self.0.on_preferences_change.borrow_mut().clear();
+        #[cfg(feature = "gamepad")]
+        {
+            *self.0.gamepad_poller.borrow_mut() = None;
+        }
         // Dropping the `Runner` drops the event handler closure, which will in
         // turn drop all `Window`s moved into the closure.
         *self.0.runner.borrow_mut() = RunnerEnum::Destroyed;
@@ -766,7 +859,15 @@ impl Shared {
         self.0.device_events.set(allowed)
     }
 
-    fn device_events(&self) -> bool {
+    pub fn listen_device_classes(&self, classes: DeviceClass) {
+        self.0.device_classes.set(classes)
+    }
+
+    fn device_events(&self, class: DeviceClass) -> bool {
+        if !self.0.device_classes.get().contains(class) {
+            return false;
+        }
+
         match self.0.device_events.get() {
             DeviceEvents::Always => true,
             DeviceEvents::WhenFocused => {
@@ -9,19 +9,34 @@ use super::super::monitor::MonitorPermissionFuture;
 use super::super::{lock, KeyEventExtra};
 use super::runner::{EventWrapper, WeakShared};
 use super::{backend, runner, EventLoopProxy};
+use crate::clipboard::{
+    Clipboard as RootClipboard, ClipboardData, ClipboardFormat, ClipboardImage,
+};
+#[cfg(feature = "dialogs")]
+use crate::dialogs::{FileDialogOptions, MessageBoxConfig};
+use crate::drag::DragData;
 use crate::error::{NotSupportedError, RequestError};
-use crate::event::{ElementState, Event, KeyEvent, TouchPhase, WindowEvent};
+#[cfg(feature = "gamepad")]
+use crate::event::DeviceId;
+use crate::event::{
+    DeviceInfo, ElementState, Event, KeyEvent, LanguageTag, PlatformData, PowerState,
+    SystemPreferences, TouchPhase, WindowEvent,
+};
 use crate::event_loop::{
-    ActiveEventLoop as RootActiveEventLoop, ControlFlow, DeviceEvents,
-    EventLoopProxy as RootEventLoopProxy, OwnedDisplayHandle as RootOwnedDisplayHandle,
+    ActiveEventLoop as RootActiveEventLoop, AsyncRequestSerial, ControlFlow, DeviceClass,
+    DeviceEvents, EventLoopProxy as RootEventLoopProxy, ModifiersOrder,
+    OwnedDisplayHandle as RootOwnedDisplayHandle,
 };
-use crate::keyboard::ModifiersState;
+use crate::keyboard::{Key, KeyCode, KeyboardLayout, Led, ModifiersState};
 use crate::monitor::MonitorHandle as RootMonitorHandle;
 use crate::platform::web::{CustomCursorFuture, PollStrategy, WaitUntilStrategy};
 use crate::platform_impl::platform::cursor::CustomCursor;
 use crate::platform_impl::platform::r#async::Waker;
 use crate::platform_impl::Window;
-use crate::window::{CustomCursor as RootCustomCursor, CustomCursorSource, Theme, WindowId};
+use crate::window::{
+    Cursor, CursorThemeInfo, CustomCursor as RootCustomCursor, CustomCursorSource, Rgba, Theme,
+    WindowId,
+};
 
 #[derive(Default)]
 struct ModifiersShared(Rc<Cell<ModifiersState>>);
@@ -46,11 +61,16 @@ impl Clone for ModifiersShared {
 pub struct ActiveEventLoop {
     pub(crate) runner: runner::Shared,
     modifiers: ModifiersShared,
+    modifiers_order: ModifiersOrder,
 }
 
 impl ActiveEventLoop {
-    pub fn new() -> Self {
-        Self { runner: runner::Shared::new(), modifiers: ModifiersShared::default() }
+    pub fn new(modifiers_order: ModifiersOrder) -> Self {
+        Self {
+            runner: runner::Shared::new(),
+            modifiers: ModifiersShared::default(),
+            modifiers_order,
+        }
     }
 
     pub(crate) fn run(
@@ -125,6 +145,7 @@ impl ActiveEventLoop {
 
         let runner = self.runner.clone();
         let modifiers = self.modifiers.clone();
+        let modifiers_order = self.modifiers_order;
         canvas.on_keyboard_press(
             move |physical_key, logical_key, text, location, repeat, active_modifiers| {
                 let modifiers_changed = (modifiers.get() != active_modifiers).then(|| {
@@ -135,30 +156,34 @@ impl ActiveEventLoop {
                     }
                 });
 
-                runner.send_events(
-                    iter::once(Event::WindowEvent {
-                        window_id,
-                        event: WindowEvent::KeyboardInput {
-                            device_id: None,
-                            event: KeyEvent {
-                                physical_key,
-                                logical_key,
-                                text,
-                                location,
-                                state: ElementState::Pressed,
-                                repeat,
-                                platform_specific: KeyEventExtra,
-                            },
-                            is_synthetic: false,
+                let keyboard_input = Some(Event::WindowEvent {
+                    window_id,
+                    event: WindowEvent::KeyboardInput {
+                        device_id: None,
+                        event: KeyEvent {
+                            physical_key,
+                            logical_key,
+                            text,
+                            location,
+                            state: ElementState::Pressed,
+                            repeat,
+                            platform_specific: KeyEventExtra,
                         },
-                    })
-                    .chain(modifiers_changed),
-                );
+                        is_synthetic: false,
+                    },
+                });
+
+                let events = match modifiers_order {
+                    ModifiersOrder::BeforeKeyboardInput => [modifiers_changed, keyboard_input],
+                    ModifiersOrder::AfterKeyboardInput => [keyboard_input, modifiers_changed],
+                };
+                runner.send_events(events.into_iter().flatten());
             },
         );
 
         let runner = self.runner.clone();
         let modifiers = self.modifiers.clone();
+        let modifiers_order = self.modifiers_order;
         canvas.on_keyboard_release(
             move |physical_key, logical_key, text, location, repeat, active_modifiers| {
                 let modifiers_changed = (modifiers.get() != active_modifiers).then(|| {
@@ -169,25 +194,28 @@ impl ActiveEventLoop {
                     }
                 });
 
-                runner.send_events(
-                    iter::once(Event::WindowEvent {
-                        window_id,
-                        event: WindowEvent::KeyboardInput {
-                            device_id: None,
-                            event: KeyEvent {
-                                physical_key,
-                                logical_key,
-                                text,
-                                location,
-                                state: ElementState::Released,
-                                repeat,
-                                platform_specific: KeyEventExtra,
-                            },
-                            is_synthetic: false,
+                let keyboard_input = Some(Event::WindowEvent {
+                    window_id,
+                    event: WindowEvent::KeyboardInput {
+                        device_id: None,
+                        event: KeyEvent {
+                            physical_key,
+                            logical_key,
+                            text,
+                            location,
+                            state: ElementState::Released,
+                            repeat,
+                            platform_specific: KeyEventExtra,
                         },
-                    })
-                    .chain(modifiers_changed),
-                )
+                        is_synthetic: false,
+                    },
+                });
+
+                let events = match modifiers_order {
+                    ModifiersOrder::BeforeKeyboardInput => [modifiers_changed, keyboard_input],
+                    ModifiersOrder::AfterKeyboardInput => [keyboard_input, modifiers_changed],
+                };
+                runner.send_events(events.into_iter().flatten())
             },
         );
 
@@ -217,8 +245,11 @@ impl ActiveEventLoop {
             let runner = self.runner.clone();
             let has_focus = has_focus.clone();
             let modifiers = self.modifiers.clone();
+            let canvas = canvas_clone.clone();
 
             move |active_modifiers, device_id, position, kind| {
+                canvas.cursor.pointer_active(device_id);
+
                 let focus = (has_focus.get() && modifiers.get() != active_modifiers).then(|| {
                     modifiers.set(active_modifiers);
                     Event::WindowEvent {
@@ -239,8 +270,11 @@ impl ActiveEventLoop {
                 let runner = self.runner.clone();
                 let has_focus = has_focus.clone();
                 let modifiers = self.modifiers.clone();
+                let canvas = canvas_clone.clone();
 
                 move |device_id, events| {
+                    canvas.cursor.pointer_active(device_id);
+
                     runner.send_events(events.flat_map(|(active_modifiers, position, source)| {
                         let modifiers = (has_focus.get() && modifiers.get() != active_modifiers)
                             .then(|| {
@@ -352,6 +386,7 @@ impl ActiveEventLoop {
                         device_id: None,
                         delta,
                         phase: TouchPhase::Moved,
+                        momentum: false,
                     },
                 },
             )));
@@ -458,6 +493,91 @@ impl ActiveEventLoop {
     }
 }
 
+impl RootClipboard for ActiveEventLoop {
+    fn set(&self, data: Vec<ClipboardData>) -> Result<(), RequestError> {
+        let navigator = self.runner.navigator().clone();
+        if !backend::clipboard::has_clipboard(&navigator) {
+            return Err(NotSupportedError::new("the clipboard requires a secure context").into());
+        }
+
+        let mut items: Vec<(String, Vec<u8>)> = Vec::new();
+        for data in &data {
+            let (mime_type, bytes) = match data {
+                ClipboardData::Text(text) => ("text/plain".into(), text.as_bytes().to_vec()),
+                ClipboardData::Html(html) => ("text/html".into(), html.as_bytes().to_vec()),
+                ClipboardData::Image(image) => ("image/png".into(), image.to_png().into_owned()),
+                ClipboardData::Files(_) | ClipboardData::Uris(_) => {
+                    return Err(
+                        NotSupportedError::new("files and URIs are not supported on Web").into()
+                    )
+                },
+                // Browsers only accept the custom formats with this prefix.
+                ClipboardData::Custom { mime_type, data } => {
+                    (format!("web {mime_type}"), data.clone())
+                },
+            };
+            if !items.iter().any(|(m, _)| *m == mime_type) {
+                items.push((mime_type, bytes));
+            }
+        }
+
+        wasm_bindgen_futures::spawn_local(async move {
+            // Writing text alone is supported by more browsers than writing items.
+            let result = match data.as_slice() {
+                [ClipboardData::Text(text)] => {
+                    backend::clipboard::write_text(&navigator, text).await
+                },
+                _ => backend::clipboard::write(&navigator, items).await,
+            };
+            if let Err(error) = result {
+                tracing::warn!("Failed to write the clipboard: {error:?}");
+            }
+        });
+        Ok(())
+    }
+
+    fn get(&self, format: ClipboardFormat) -> Result<AsyncRequestSerial, RequestError> {
+        let navigator = self.runner.navigator().clone();
+        if !backend::clipboard::has_clipboard(&navigator) {
+            return Err(NotSupportedError::new("the clipboard requires a secure context").into());
+        }
+        if matches!(format, ClipboardFormat::Files | ClipboardFormat::Uris) {
+            return Err(NotSupportedError::new("files and URIs are not supported on Web").into());
+        }
+
+        let serial = AsyncRequestSerial::get();
+        let runner = self.runner.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            let result = match format {
+                ClipboardFormat::Text => backend::clipboard::read_text(&navigator)
+                    .await
+                    .map(|text| Some(ClipboardData::Text(text))),
+                ClipboardFormat::Html => {
+                    backend::clipboard::read(&navigator, "text/html").await.map(|html| {
+                        html.map(|html| {
+                            ClipboardData::Html(String::from_utf8_lossy(&html).into_owned())
+                        })
+                    })
+                },
+                ClipboardFormat::Custom(mime_type) => {
+                    backend::clipboard::read(&navigator, &format!("web {mime_type}"))
+                        .await
+                        .map(|data| data.map(|data| ClipboardData::Custom { mime_type, data }))
+                },
+                _ => backend::clipboard::read(&navigator, "image/png")
+                    .await
+                    .map(|png| png.map(|png| ClipboardData::Image(ClipboardImage::Png(png)))),
+            };
+            let data = result
+                .map_err(|error| tracing::warn!("Failed to read the clipboard: {error:?}"))
+                .ok()
+                .flatten();
+            runner.send_event(Event::ClipboardData { serial, data });
+        });
+        Ok(serial)
+    }
+}
+
 impl RootActiveEventLoop for ActiveEventLoop {
     fn create_proxy(&self) -> RootEventLoopProxy {
         let event_loop_proxy = EventLoopProxy::new(self.waker());
@@ -497,6 +617,70 @@ impl RootActiveEventLoop for ActiveEventLoop {
         self.runner.listen_device_events(allowed)
     }
 
+    fn listen_device_classes(&self, classes: DeviceClass) {
+        self.runner.listen_device_classes(classes)
+    }
+
+    fn input_devices(&self) -> Vec<DeviceInfo> {
+        #[cfg(feature = "gamepad")]
+        return backend::gamepad::gamepads(self.runner.navigator());
+        #[cfg(not(feature = "gamepad"))]
+        Vec::new()
+    }
+
+    #[cfg(feature = "gamepad")]
+    fn set_gamepad_rumble(
+        &self,
+        device_id: DeviceId,
+        strong: f32,
+        weak: f32,
+    ) -> Result<(), RequestError> {
+        backend::gamepad::set_rumble(self.runner.navigator(), device_id, strong, weak)
+    }
+
+    fn windows(&self) -> Vec<WindowId> {
+        self.runner.windows()
+    }
+
+    fn with_native_modal_scope(&self, f: &mut dyn FnMut()) {
+        f()
+    }
+
+    fn start_drag(&self, _source: WindowId, _data: DragData) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("start_drag is not supported").into())
+    }
+
+    fn set_drag_image(&self, _image: Option<Cursor>) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_drag_image is not supported").into())
+    }
+
+    fn clipboard(&self) -> &dyn RootClipboard {
+        self
+    }
+
+    #[cfg(feature = "dialogs")]
+    fn pick_file(&self, _options: FileDialogOptions) -> Result<AsyncRequestSerial, RequestError> {
+        Err(NotSupportedError::new("pick_file is not supported").into())
+    }
+
+    #[cfg(feature = "dialogs")]
+    fn pick_folder(&self, _options: FileDialogOptions) -> Result<AsyncRequestSerial, RequestError> {
+        Err(NotSupportedError::new("pick_folder is not supported").into())
+    }
+
+    #[cfg(feature = "dialogs")]
+    fn save_file(&self, _options: FileDialogOptions) -> Result<AsyncRequestSerial, RequestError> {
+        Err(NotSupportedError::new("save_file is not supported").into())
+    }
+
+    #[cfg(feature = "dialogs")]
+    fn show_message_box(
+        &self,
+        _config: MessageBoxConfig,
+    ) -> Result<AsyncRequestSerial, RequestError> {
+        Err(NotSupportedError::new("show_message_box is not supported").into())
+    }
+
     fn system_theme(&self) -> Option<Theme> {
         backend::is_dark_mode(self.runner.window()).map(|is_dark_mode| {
             if is_dark_mode {
@@ -507,6 +691,58 @@ impl RootActiveEventLoop for ActiveEventLoop {
         })
     }
 
+    fn system_accent_color(&self) -> Option<Rgba> {
+        None
+    }
+
+    fn cursor_theme(&self) -> Option<CursorThemeInfo> {
+        None
+    }
+
+    fn system_preferences(&self) -> Option<SystemPreferences> {
+        Some(backend::system_preferences(self.runner.window()))
+    }
+
+    fn text_scale_factor(&self) -> Option<f64> {
+        None
+    }
+
+    fn power_state(&self) -> Option<PowerState> {
+        None
+    }
+
+    fn locale(&self) -> LanguageTag {
+        backend::locale(self.runner.navigator())
+    }
+
+    fn keyboard_layout(&self) -> Option<KeyboardLayout> {
+        None
+    }
+
+    fn key_label(&self, _key_code: KeyCode) -> Option<String> {
+        None
+    }
+
+    fn key_for_keycode(&self, _key_code: KeyCode) -> Option<Key> {
+        None
+    }
+
+    fn keycode_for_key(&self, _key: &Key) -> Option<KeyCode> {
+        None
+    }
+
+    fn set_keyboard_led(&self, _led: Led, _on: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_keyboard_led is not supported").into())
+    }
+
+    fn keyboard_led(&self, _led: Led) -> Option<bool> {
+        None
+    }
+
+    fn event_platform_data(&self) -> Option<PlatformData> {
+        None
+    }
+
     fn set_control_flow(&self, control_flow: ControlFlow) {
         self.runner.set_control_flow(control_flow)
     }
@@ -7,6 +7,8 @@ use wasm_bindgen::prelude::wasm_bindgen;
 use wasm_bindgen::{JsCast, JsValue};
 use web_sys::{console, Document, DomException, Element, Navigator};
 
+use crate::error::NotSupportedError;
+
 pub(crate) fn is_cursor_lock_raw(navigator: &Navigator, document: &Document) -> bool {
     thread_local! {
         static IS_CURSOR_LOCK_RAW: OnceCell<bool> = const { OnceCell::new() };
@@ -62,6 +64,38 @@ pub(crate) fn request_pointer_lock(navigator: &Navigator, document: &Document, e
     }
 }
 
+/// Capture the keys of the system with the Keyboard Lock API, which only applies in fullscreen.
+pub(crate) fn set_keyboard_lock(
+    navigator: &Navigator,
+    lock: bool,
+) -> Result<(), NotSupportedError> {
+    let navigator: &NavigatorExt = navigator.unchecked_ref();
+    let keyboard = navigator.keyboard();
+    if keyboard.is_undefined() {
+        return Err(NotSupportedError::new("the Keyboard Lock API is not supported"));
+    }
+
+    let keyboard: Keyboard = keyboard.unchecked_into();
+    if lock {
+        thread_local! {
+            static REJECT_HANDLER: Closure<dyn FnMut(JsValue)> = Closure::new(|error: JsValue| {
+                if let Some(error) = error.dyn_ref::<DomException>() {
+                    error!("Failed to lock keyboard. {}: {}", error.name(), error.message());
+                } else {
+                    console::error_1(&error);
+                    error!("Failed to lock keyboard");
+                }
+            });
+        }
+
+        let _ = REJECT_HANDLER.with(|handler| keyboard.lock().catch(handler));
+    } else {
+        keyboard.unlock();
+    }
+
+    Ok(())
+}
+
 #[wasm_bindgen]
 extern "C" {
     type ElementExt;
@@ -79,4 +113,17 @@ extern "C" {
 
     #[wasm_bindgen(method, setter, js_name = unadjustedMovement)]
     fn set_unadjusted_movement(this: &PointerLockOptions, value: bool);
+
+    type NavigatorExt;
+
+    #[wasm_bindgen(method, getter)]
+    fn keyboard(this: &NavigatorExt) -> JsValue;
+
+    type Keyboard;
+
+    #[wasm_bindgen(method)]
+    fn lock(this: &Keyboard) -> Promise;
+
+    #[wasm_bindgen(method)]
+    fn unlock(this: &Keyboard);
 }
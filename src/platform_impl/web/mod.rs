@@ -20,6 +20,7 @@
 // TODO: FP, remove when <https://github.com/rust-lang/rust-clippy/issues/12377> is fixed.
 #![allow(clippy::empty_docs)]
 
+mod announce;
 mod r#async;
 mod cursor;
 mod error;
@@ -50,3 +51,22 @@ pub(crate) use self::monitor::{
 use self::web_sys as backend;
 pub use self::window::{PlatformSpecificWindowAttributes, Window};
 pub(crate) use crate::icon::NoIcon as PlatformIcon;
+
+/// Release the pointer lock and leave fullscreen after a panic.
+///
+/// The event loop may be gone by then, so this doesn't go through the cached JS objects.
+#[allow(clippy::disallowed_methods)]
+pub(crate) fn restore_after_panic() {
+    if let Some(document) = ::web_sys::window().and_then(|window| window.document()) {
+        document.exit_pointer_lock();
+        backend::exit_any_fullscreen(&document);
+    }
+}
+
+/// Show the message of a panic with an alert, until the user closes it.
+#[allow(clippy::disallowed_methods)]
+pub(crate) fn show_panic_dialog(message: &str) {
+    if let Some(window) = ::web_sys::window() {
+        let _ = window.alert_with_message(message);
+    }
+}
@@ -29,7 +29,11 @@ use super::main_thread::MainThreadMarker;
 use super::r#async::{Dispatcher, Notified, Notifier};
 use super::web_sys::{Engine, EventListenerHandle};
 use crate::dpi::{PhysicalPosition, PhysicalSize};
-use crate::monitor::MonitorHandle as RootMonitorHandle;
+use crate::error::{NotSupportedError, RequestError};
+use crate::monitor::{
+    ColorCapabilities, MonitorHandle as RootMonitorHandle, MonitorIdentity,
+    Orientation as RootOrientation,
+};
 use crate::platform::web::{
     MonitorPermissionError, Orientation, OrientationData, OrientationLock, OrientationLockError,
 };
@@ -59,6 +63,24 @@ impl MonitorHandle {
         self.inner.queue(|inner| inner.name())
     }
 
+    pub fn physical_size_mm(&self) -> Option<(u32, u32)> {
+        None
+    }
+
+    pub fn identity(&self) -> Option<MonitorIdentity> {
+        None
+    }
+
+    pub fn orientation(&self) -> RootOrientation {
+        self.inner.queue(|inner| {
+            RootOrientation::from_degrees(inner.orientation_raw().angle().unwrap().into())
+        })
+    }
+
+    pub fn icc_profile(&self) -> Option<Vec<u8>> {
+        None
+    }
+
     pub fn current_video_mode(&self) -> Option<VideoModeHandle> {
         Some(VideoModeHandle(self.clone()))
     }
@@ -67,8 +89,20 @@ impl MonitorHandle {
         iter::once(VideoModeHandle(self.clone()))
     }
 
-    pub fn orientation(&self) -> OrientationData {
-        self.inner.queue(|inner| inner.orientation())
+    pub fn color_capabilities(&self) -> Result<ColorCapabilities, RequestError> {
+        Err(NotSupportedError::new("color_capabilities is not supported").into())
+    }
+
+    pub fn brightness(&self) -> Result<f64, RequestError> {
+        Err(NotSupportedError::new("brightness is not supported").into())
+    }
+
+    pub fn set_brightness(&self, _brightness: f64) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_brightness is not supported").into())
+    }
+
+    pub fn orientation_data(&self) -> OrientationData {
+        self.inner.queue(|inner| inner.orientation_data())
     }
 
     pub fn request_lock(&self, orientation_lock: OrientationLock) -> OrientationLockFuture {
@@ -148,7 +182,7 @@ impl Debug for MonitorHandle {
                     this.name(),
                     this.position(),
                     this.scale_factor(),
-                    this.orientation(),
+                    this.orientation_data(),
                     this.is_internal(),
                     this.is_detailed(),
                 )
@@ -320,7 +354,7 @@ impl Inner {
         self.orientation.get_or_init(|| self.screen.orientation().unchecked_into())
     }
 
-    fn orientation(&self) -> OrientationData {
+    fn orientation_data(&self) -> OrientationData {
         let orientation = self.orientation_raw();
 
         let angle = orientation.angle().unwrap();
@@ -0,0 +1,88 @@
+use js_sys::{Array, Object, Promise, Reflect, Uint8Array};
+use wasm_bindgen::prelude::wasm_bindgen;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{Blob, BlobPropertyBag, Navigator};
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(extends = Navigator)]
+    type NavigatorExt;
+
+    #[wasm_bindgen(method, getter)]
+    fn clipboard(this: &NavigatorExt) -> Option<ClipboardExt>;
+
+    type ClipboardExt;
+
+    #[wasm_bindgen(method, js_name = writeText)]
+    fn write_text(this: &ClipboardExt, text: &str) -> Promise;
+
+    #[wasm_bindgen(method, js_name = readText)]
+    fn read_text(this: &ClipboardExt) -> Promise;
+
+    #[wasm_bindgen(method)]
+    fn write(this: &ClipboardExt, items: &Array) -> Promise;
+
+    #[wasm_bindgen(method)]
+    fn read(this: &ClipboardExt) -> Promise;
+
+    type ClipboardItem;
+
+    #[wasm_bindgen(constructor)]
+    fn new(items: &Object) -> ClipboardItem;
+
+    #[wasm_bindgen(method, getter)]
+    fn types(this: &ClipboardItem) -> Array;
+
+    #[wasm_bindgen(method, js_name = getType)]
+    fn get_type(this: &ClipboardItem, mime_type: &str) -> Promise;
+}
+
+/// The clipboard is only available in secure contexts.
+fn clipboard(navigator: &Navigator) -> Option<ClipboardExt> {
+    navigator.unchecked_ref::<NavigatorExt>().clipboard()
+}
+
+pub fn has_clipboard(navigator: &Navigator) -> bool {
+    clipboard(navigator).is_some()
+}
+
+pub async fn write_text(navigator: &Navigator, text: &str) -> Result<(), JsValue> {
+    let clipboard = clipboard(navigator).ok_or(JsValue::UNDEFINED)?;
+    JsFuture::from(clipboard.write_text(text)).await.map(|_| ())
+}
+
+pub async fn read_text(navigator: &Navigator) -> Result<String, JsValue> {
+    let clipboard = clipboard(navigator).ok_or(JsValue::UNDEFINED)?;
+    let text = JsFuture::from(clipboard.read_text()).await?;
+    text.as_string().ok_or(JsValue::UNDEFINED)
+}
+
+/// Write a single item holding the data of each MIME type.
+pub async fn write(navigator: &Navigator, data: Vec<(String, Vec<u8>)>) -> Result<(), JsValue> {
+    let clipboard = clipboard(navigator).ok_or(JsValue::UNDEFINED)?;
+    let item = Object::new();
+    for (mime_type, bytes) in data {
+        let options = BlobPropertyBag::new();
+        options.set_type(&mime_type);
+        let parts = Array::of1(&Uint8Array::from(bytes.as_slice()));
+        let blob = Blob::new_with_u8_array_sequence_and_options(&parts, &options)?;
+        Reflect::set(&item, &mime_type.as_str().into(), &blob)?;
+    }
+    JsFuture::from(clipboard.write(&Array::of1(&ClipboardItem::new(&item)))).await.map(|_| ())
+}
+
+/// Read the data of the first item offering the MIME type.
+pub async fn read(navigator: &Navigator, mime_type: &str) -> Result<Option<Vec<u8>>, JsValue> {
+    let clipboard = clipboard(navigator).ok_or(JsValue::UNDEFINED)?;
+    let items: Array = JsFuture::from(clipboard.read()).await?.unchecked_into();
+    for item in items.iter() {
+        let item: ClipboardItem = item.unchecked_into();
+        if item.types().includes(&mime_type.into(), 0) {
+            let blob: Blob = JsFuture::from(item.get_type(mime_type)).await?.unchecked_into();
+            let buffer = JsFuture::from(blob.array_buffer()).await?;
+            return Ok(Some(Uint8Array::new(&buffer).to_vec()));
+        }
+    }
+    Ok(None)
+}
@@ -8,7 +8,7 @@ use web_sys::{KeyboardEvent, MouseEvent, Navigator, PointerEvent, WheelEvent};
 
 use super::super::FingerId;
 use super::Engine;
-use crate::event::{MouseButton, MouseScrollDelta, PointerKind};
+use crate::event::{MouseButton, MouseScrollDelta, PointerKind, TouchContact};
 use crate::keyboard::{Key, KeyLocation, ModifiersState, NamedKey, PhysicalKey};
 
 bitflags::bitflags! {
@@ -161,6 +161,13 @@ pub fn mouse_scroll_delta(
     }
 }
 
+/// The contact of a touch, from the size of the pointer which is in CSS pixels.
+pub fn touch_contact(event: &PointerEvent, scale_factor: f64) -> Option<TouchContact> {
+    let width = event.width() as f64 * scale_factor;
+    let height = event.height() as f64 * scale_factor;
+    Some(TouchContact::new(width.max(height), width.min(height)))
+}
+
 pub fn pointer_type(event: &PointerEvent, pointer_id: i32) -> PointerKind {
     match event.pointer_type().as_str() {
         "mouse" => PointerKind::Mouse,
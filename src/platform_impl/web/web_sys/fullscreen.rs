@@ -1,6 +1,6 @@
 use std::cell::OnceCell;
 
-use js_sys::{Object, Promise};
+use js_sys::{Object, Promise, Reflect};
 use tracing::error;
 use wasm_bindgen::closure::Closure;
 use wasm_bindgen::prelude::wasm_bindgen;
@@ -115,15 +115,15 @@ pub fn is_fullscreen(document: &Document, canvas: &HtmlCanvasElement) -> bool {
     }
 }
 
-pub fn exit_fullscreen(document: &Document, canvas: &HtmlCanvasElement) {
-    #[wasm_bindgen]
-    extern "C" {
-        type ExitFullscreen;
+#[wasm_bindgen]
+extern "C" {
+    type ExitFullscreen;
 
-        #[wasm_bindgen(method, js_name = webkitExitFullscreen)]
-        fn webkit_exit_fullscreen(this: &ExitFullscreen);
-    }
+    #[wasm_bindgen(method, js_name = webkitExitFullscreen)]
+    fn webkit_exit_fullscreen(this: &ExitFullscreen);
+}
 
+pub fn exit_fullscreen(document: &Document, canvas: &HtmlCanvasElement) {
     if has_fullscreen_api_support(canvas) {
         #[allow(clippy::disallowed_methods)]
         document.exit_fullscreen()
@@ -133,6 +133,17 @@ pub fn exit_fullscreen(document: &Document, canvas: &HtmlCanvasElement) {
     }
 }
 
+/// Like [`exit_fullscreen()`], for when no canvas is around to check the API support with.
+pub fn exit_any_fullscreen(document: &Document) {
+    if Reflect::has(document, &"exitFullscreen".into()).unwrap_or(false) {
+        #[allow(clippy::disallowed_methods)]
+        document.exit_fullscreen()
+    } else {
+        let document: &ExitFullscreen = document.unchecked_ref();
+        document.webkit_exit_fullscreen()
+    }
+}
+
 fn has_fullscreen_api_support(canvas: &HtmlCanvasElement) -> bool {
     thread_local! {
         static FULLSCREEN_API_SUPPORT: OnceCell<bool> = const { OnceCell::new() };
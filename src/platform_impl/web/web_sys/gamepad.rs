@@ -0,0 +1,277 @@
+//! Gamepads, read with the Gamepad API.
+//!
+//! The Gamepad API doesn't send events for the input, so the gamepads are polled with an interval.
+
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
+use js_sys::{Function, Object, Reflect};
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsCast;
+use web_sys::{Gamepad, GamepadButton as WebGamepadButton, GamepadMappingType, Navigator};
+
+use crate::error::{NotSupportedError, RequestError};
+use crate::event::{DeviceEvent, DeviceId, DeviceInfo, DeviceKind, ElementState, Event};
+use crate::gamepad::{GamepadAxis, GamepadButton, GamepadEvent};
+
+/// How often the gamepads are polled, in milliseconds.
+const POLL_INTERVAL: i32 = 8;
+/// How long the gamepads vibrate at most, in milliseconds.
+const RUMBLE_DURATION: f64 = 5000.0;
+
+/// The buttons of the standard mapping, by their index.
+const BUTTONS: [GamepadButton; 17] = [
+    GamepadButton::South,
+    GamepadButton::East,
+    GamepadButton::West,
+    GamepadButton::North,
+    GamepadButton::LeftShoulder,
+    GamepadButton::RightShoulder,
+    GamepadButton::LeftTrigger,
+    GamepadButton::RightTrigger,
+    GamepadButton::Select,
+    GamepadButton::Start,
+    GamepadButton::LeftStick,
+    GamepadButton::RightStick,
+    GamepadButton::DPadUp,
+    GamepadButton::DPadDown,
+    GamepadButton::DPadLeft,
+    GamepadButton::DPadRight,
+    GamepadButton::Mode,
+];
+
+/// The axes of the standard mapping, by their index.
+const AXES: [GamepadAxis; 4] = [
+    GamepadAxis::LeftStickX,
+    GamepadAxis::LeftStickY,
+    GamepadAxis::RightStickX,
+    GamepadAxis::RightStickY,
+];
+
+/// The identifier of the gamepad at `index`.
+///
+/// The identifiers are negative, so they don't collide with the ones of the pointers.
+fn device_id(index: u32) -> DeviceId {
+    DeviceId::from_raw(-1 - index as i64)
+}
+
+/// Polls the gamepads until dropped.
+pub struct GamepadPoller {
+    window: web_sys::Window,
+    handle: i32,
+    _closure: Closure<dyn FnMut()>,
+}
+
+impl GamepadPoller {
+    pub fn new(window: web_sys::Window, mut send_event: impl FnMut(Event) + 'static) -> Self {
+        #[allow(clippy::disallowed_methods)]
+        let navigator = window.navigator();
+        let mut gamepads = HashMap::new();
+        let closure = Closure::<dyn FnMut()>::new(move || {
+            poll(&navigator, &mut gamepads, &mut send_event);
+        });
+        let handle = window
+            .set_interval_with_callback_and_timeout_and_arguments_0(
+                closure.as_ref().unchecked_ref(),
+                POLL_INTERVAL,
+            )
+            .expect("Failed to set an interval");
+
+        Self { window, handle, _closure: closure }
+    }
+}
+
+impl Drop for GamepadPoller {
+    fn drop(&mut self) {
+        self.window.clear_interval_with_handle(self.handle);
+    }
+}
+
+/// The gamepads which are currently connected.
+pub fn gamepads(navigator: &Navigator) -> Vec<DeviceInfo> {
+    connected(navigator).iter().map(info).collect()
+}
+
+pub fn set_rumble(
+    navigator: &Navigator,
+    device_id: DeviceId,
+    strong: f32,
+    weak: f32,
+) -> Result<(), RequestError> {
+    let gamepad = connected(navigator)
+        .into_iter()
+        .find(|gamepad| self::device_id(gamepad.index()) == device_id)
+        .ok_or_else(|| NotSupportedError::new("the device is not a connected gamepad"))?;
+    // The `vibrationActuator` isn't supported by every browser.
+    let actuator = Reflect::get(&gamepad, &"vibrationActuator".into())
+        .ok()
+        .filter(|actuator| actuator.is_object())
+        .ok_or_else(|| NotSupportedError::new("the gamepad doesn't support rumble"))?;
+    let method = |name: &str| {
+        Reflect::get(&actuator, &name.into())
+            .ok()
+            .and_then(|method| method.dyn_into::<Function>().ok())
+            .ok_or_else(|| NotSupportedError::new("the gamepad doesn't support rumble"))
+    };
+
+    let result = if strong <= 0.0 && weak <= 0.0 {
+        method("reset")?.call0(&actuator)
+    } else {
+        let params = Object::new();
+        let set = |key: &str, value: f64| Reflect::set(&params, &key.into(), &value.into());
+        let _ = set("duration", RUMBLE_DURATION);
+        let _ = set("strongMagnitude", strong.clamp(0.0, 1.0) as f64);
+        let _ = set("weakMagnitude", weak.clamp(0.0, 1.0) as f64);
+        method("playEffect")?.call2(&actuator, &"dual-rumble".into(), &params)
+    };
+    result.map(drop).map_err(|_| os_error!("Failed to start the rumble of the gamepad").into())
+}
+
+fn connected(navigator: &Navigator) -> Vec<Gamepad> {
+    let Ok(gamepads) = navigator.get_gamepads() else { return Vec::new() };
+    gamepads
+        .iter()
+        .filter_map(|gamepad| gamepad.dyn_into::<Gamepad>().ok())
+        .filter(Gamepad::connected)
+        .collect()
+}
+
+fn info(gamepad: &Gamepad) -> DeviceInfo {
+    let name = gamepad.id();
+    let (vendor_id, product_id) = usb_ids(&name);
+    DeviceInfo {
+        id: device_id(gamepad.index()),
+        kind: DeviceKind::Gamepad,
+        name: Some(name),
+        vendor_id,
+        product_id,
+    }
+}
+
+/// The USB IDs in the identifier of a gamepad, whose format depends on the browser.
+fn usb_ids(id: &str) -> (Option<u16>, Option<u16>) {
+    let hex = |value: &str| u16::from_str_radix(value.get(..4)?, 16).ok();
+
+    // Chromium, like `Xbox 360 Controller (STANDARD GAMEPAD Vendor: 045e Product: 028e)`.
+    if let (Some(vendor), Some(product)) = (id.find("Vendor: "), id.find("Product: ")) {
+        return (hex(&id[vendor + 8..]), hex(&id[product + 9..]));
+    }
+
+    // Firefox, like `45e-28e-Microsoft X-Box 360 pad`.
+    let mut parts = id.splitn(3, '-');
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(vendor), Some(product), Some(_)) => {
+            (u16::from_str_radix(vendor, 16).ok(), u16::from_str_radix(product, 16).ok())
+        },
+        _ => (None, None),
+    }
+}
+
+fn poll(
+    navigator: &Navigator,
+    gamepads: &mut HashMap<u32, State>,
+    send_event: &mut impl FnMut(Event),
+) {
+    let connected = connected(navigator);
+    for gamepad in &connected {
+        let index = gamepad.index();
+        let new = State::new(gamepad);
+        let old = match gamepads.entry(index) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                send_event(Event::InputDeviceAdded(info(gamepad)));
+                entry.insert(State::released(&new))
+            },
+        };
+        old.diff(&new, |event| {
+            send_event(Event::DeviceEvent {
+                device_id: Some(device_id(index)),
+                event: DeviceEvent::Gamepad(event),
+            })
+        });
+        *old = new;
+    }
+
+    gamepads.retain(|&index, _| {
+        let is_connected = connected.iter().any(|gamepad| gamepad.index() == index);
+        if !is_connected {
+            send_event(Event::InputDeviceRemoved(device_id(index)));
+        }
+        is_connected
+    });
+}
+
+/// The state of the buttons and axes of a gamepad.
+struct State {
+    standard: bool,
+    /// Whether the buttons are pressed, and their analog value.
+    buttons: Vec<(bool, f64)>,
+    axes: Vec<f64>,
+}
+
+impl State {
+    fn new(gamepad: &Gamepad) -> Self {
+        let buttons = gamepad
+            .buttons()
+            .iter()
+            .map(|button| match button.dyn_into::<WebGamepadButton>() {
+                Ok(button) => (button.pressed(), button.value()),
+                Err(_) => (false, 0.0),
+            })
+            .collect();
+        let axes = gamepad.axes().iter().map(|axis| axis.as_f64().unwrap_or_default()).collect();
+        Self { standard: gamepad.mapping() == GamepadMappingType::Standard, buttons, axes }
+    }
+
+    /// The state with the same buttons and axes as `state`, at rest.
+    fn released(state: &Self) -> Self {
+        Self {
+            standard: state.standard,
+            buttons: vec![(false, 0.0); state.buttons.len()],
+            axes: vec![0.0; state.axes.len()],
+        }
+    }
+
+    /// Send the changes from `self` to `new`.
+    fn diff(&self, new: &Self, mut send_event: impl FnMut(GamepadEvent)) {
+        for (index, (&(was_pressed, old), &(is_pressed, value))) in
+            self.buttons.iter().zip(&new.buttons).enumerate()
+        {
+            let button = match BUTTONS.get(index) {
+                Some(&button) if new.standard => button,
+                _ => GamepadButton::Other(index as u16),
+            };
+            if was_pressed != is_pressed {
+                let state = if is_pressed { ElementState::Pressed } else { ElementState::Released };
+                send_event(GamepadEvent::Button { button, state });
+            }
+
+            // The standard mapping reports the analog triggers as buttons.
+            let axis = match button {
+                GamepadButton::LeftTrigger => GamepadAxis::LeftTrigger,
+                GamepadButton::RightTrigger => GamepadAxis::RightTrigger,
+                _ => continue,
+            };
+            if old != value {
+                send_event(GamepadEvent::Axis { axis, value });
+            }
+        }
+
+        for (index, (&old, &value)) in self.axes.iter().zip(&new.axes).enumerate() {
+            if old == value {
+                continue;
+            }
+            let event = match AXES.get(index) {
+                // The vertical axes of the standard mapping point down.
+                Some(&axis @ (GamepadAxis::LeftStickY | GamepadAxis::RightStickY))
+                    if new.standard =>
+                {
+                    GamepadEvent::Axis { axis, value: -value }
+                },
+                Some(&axis) if new.standard => GamepadEvent::Axis { axis, value },
+                _ => GamepadEvent::Axis { axis: GamepadAxis::Other(index as u16), value },
+            };
+            send_event(event);
+        }
+    }
+}
@@ -2,7 +2,7 @@ use wasm_bindgen::prelude::Closure;
 use wasm_bindgen::JsCast;
 use web_sys::MediaQueryList;
 
-pub(super) struct MediaQueryListHandle {
+pub struct MediaQueryListHandle {
     mql: MediaQueryList,
     closure: Closure<dyn FnMut()>,
 }
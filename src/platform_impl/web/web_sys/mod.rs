@@ -1,8 +1,11 @@
 mod animation_frame;
 mod canvas;
+pub mod clipboard;
 pub mod event;
 mod event_handle;
 mod fullscreen;
+#[cfg(feature = "gamepad")]
+pub mod gamepad;
 mod intersection_handle;
 mod media_query_handle;
 mod pointer;
@@ -19,9 +22,12 @@ use web_sys::{Document, HtmlCanvasElement, Navigator, PageTransitionEvent, Visib
 
 pub use self::canvas::{Canvas, Style};
 pub use self::event_handle::EventListenerHandle;
+pub use self::fullscreen::exit_any_fullscreen;
+pub use self::media_query_handle::MediaQueryListHandle;
 pub use self::resize_scaling::ResizeScaleHandle;
 pub use self::schedule::Schedule;
 use crate::dpi::{LogicalPosition, LogicalSize};
+use crate::event::{LanguageTag, SystemPreferences};
 
 pub fn throw(msg: &str) {
     wasm_bindgen::throw_str(msg);
@@ -157,10 +163,35 @@ pub fn is_dark_mode(window: &web_sys::Window) -> Option<bool> {
     window.match_media("(prefers-color-scheme: dark)").ok().flatten().map(|media| media.matches())
 }
 
+/// The media queries whose changes can change the [`SystemPreferences`].
+pub const SYSTEM_PREFERENCES_QUERIES: [&str; 4] = [
+    "(prefers-reduced-motion: reduce)",
+    "(prefers-contrast: more)",
+    "(forced-colors: active)",
+    "(prefers-reduced-transparency: reduce)",
+];
+
+pub fn system_preferences(window: &web_sys::Window) -> SystemPreferences {
+    let matches =
+        |query| window.match_media(query).ok().flatten().is_some_and(|media| media.matches());
+    SystemPreferences {
+        reduced_motion: matches(SYSTEM_PREFERENCES_QUERIES[0]),
+        // Forced colors are used by the high contrast themes of Windows.
+        high_contrast: matches(SYSTEM_PREFERENCES_QUERIES[1])
+            || matches(SYSTEM_PREFERENCES_QUERIES[2]),
+        // Unsupported by most browsers, in which case this doesn't match.
+        reduced_transparency: matches(SYSTEM_PREFERENCES_QUERIES[3]),
+    }
+}
+
 pub fn is_visible(document: &Document) -> bool {
     document.visibility_state() == VisibilityState::Visible
 }
 
+pub fn locale(navigator: &Navigator) -> LanguageTag {
+    navigator.language().map(LanguageTag::new).unwrap_or_else(LanguageTag::undetermined)
+}
+
 pub type RawCanvasType = HtmlCanvasElement;
 
 #[derive(Clone, Copy)]
@@ -85,6 +85,7 @@ impl PointerHandler {
                     PointerKind::Touch(finger_id) => ButtonSource::Touch {
                         finger_id,
                         force: Some(Force::Normalized(event.pressure().into())),
+                        contact: event::touch_contact(&event, super::scale_factor(&window)),
                     },
                     PointerKind::Unknown => ButtonSource::Unknown(button.to_id()),
                 };
@@ -136,6 +137,7 @@ impl PointerHandler {
                     PointerKind::Touch(finger_id) => ButtonSource::Touch {
                         finger_id,
                         force: Some(Force::Normalized(event.pressure().into())),
+                        contact: event::touch_contact(&event, super::scale_factor(&window)),
                     },
                     PointerKind::Unknown => ButtonSource::Unknown(button.to_id()),
                 };
@@ -205,6 +207,7 @@ impl PointerHandler {
                             ButtonSource::Touch {
                                 finger_id,
                                 force: Some(Force::Normalized(event.pressure().into())),
+                                contact: event::touch_contact(&event, super::scale_factor(&window)),
                             }
                         },
                         PointerKind::Unknown => todo!(),
@@ -235,6 +238,7 @@ impl PointerHandler {
                                 PointerKind::Touch(finger_id) => PointerSource::Touch {
                                     finger_id,
                                     force: Some(Force::Normalized(event.pressure().into())),
+                                    contact: event::touch_contact(&event, scale),
                                 },
                                 PointerKind::Unknown => PointerSource::Unknown,
                             },
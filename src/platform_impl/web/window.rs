@@ -3,19 +3,22 @@ use std::rc::Rc;
 use std::sync::Arc;
 
 use web_sys::HtmlCanvasElement;
+use web_time::Instant;
 
+use super::event_loop::runner::WeakShared;
 use super::main_thread::{MainThreadMarker, MainThreadSafe};
 use super::monitor::MonitorHandler;
 use super::r#async::Dispatcher;
-use super::{backend, lock, ActiveEventLoop};
+use super::{announce, backend, lock, ActiveEventLoop};
 use crate::dpi::{PhysicalPosition, PhysicalSize, Position, Size};
 use crate::error::{NotSupportedError, RequestError};
+use crate::event::DeviceId;
 use crate::icon::Icon;
-use crate::monitor::MonitorHandle as RootMonitorHandle;
+use crate::monitor::{GammaRamp, HdrMetadata, MonitorHandle as RootMonitorHandle};
 use crate::window::{
-    Cursor, CursorGrabMode, Fullscreen as RootFullscreen, ImePurpose, ResizeDirection, Theme,
-    UserAttentionType, Window as RootWindow, WindowAttributes, WindowButtons, WindowId,
-    WindowLevel,
+    Coalescing, Cursor, CursorGrabMode, CursorGrabPolicy, Fullscreen as RootFullscreen, ImePurpose,
+    Politeness, ResizeDirection, Theme, UserAttentionType, Window as RootWindow, WindowAttributes,
+    WindowButtons, WindowId, WindowLevel,
 };
 
 pub struct Window {
@@ -27,6 +30,7 @@ pub struct Inner {
     pub window: web_sys::Window,
     monitor: Rc<MonitorHandler>,
     canvas: Rc<backend::Canvas>,
+    runner: WeakShared,
     destroy_fn: Option<Box<dyn FnOnce()>>,
 }
 
@@ -60,6 +64,7 @@ impl Window {
             window: window.clone(),
             monitor: Rc::clone(target.runner.monitor()),
             canvas,
+            runner: target.runner.weak(),
             destroy_fn: Some(destroy_fn),
         };
 
@@ -103,6 +108,14 @@ impl RootWindow for Window {
         self.inner.dispatch(|inner| inner.canvas.request_animation_frame())
     }
 
+    fn request_redraw_at(&self, instant: Instant) {
+        self.inner.dispatch(move |inner| {
+            if let Some(runner) = inner.runner.upgrade() {
+                runner.request_redraw_at(inner.id, instant)
+            }
+        })
+    }
+
     fn pre_present_notify(&self) {}
 
     fn reset_dead_keys(&self) {
@@ -252,6 +265,18 @@ impl RootWindow for Window {
         })
     }
 
+    fn set_fullscreen_hdr(&self, _hdr: Option<HdrMetadata>) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_fullscreen_hdr is not supported").into())
+    }
+
+    fn fullscreen_hdr(&self) -> Option<HdrMetadata> {
+        None
+    }
+
+    fn set_gamma_ramp(&self, _ramp: Option<&GammaRamp>) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_gamma_ramp is not supported").into())
+    }
+
     fn set_decorations(&self, _: bool) {
         // Intentionally a no-op, no canvas decorations
     }
@@ -272,14 +297,30 @@ impl RootWindow for Window {
         // Currently not implemented
     }
 
+    fn set_ime_exclusion_area(&self, _area: Option<(Position, Size)>) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_ime_exclusion_area is not supported").into())
+    }
+
+    fn set_ime_candidates_drawn_by_app(&self, _drawn_by_app: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_ime_candidates_drawn_by_app is not supported").into())
+    }
+
     fn set_ime_allowed(&self, _: bool) {
         // Currently not implemented
     }
 
+    fn set_virtual_keyboard_visible(&self, _visible: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_virtual_keyboard_visible is not supported").into())
+    }
+
     fn set_ime_purpose(&self, _: ImePurpose) {
         // Currently not implemented
     }
 
+    fn set_ime_surrounding_text(&self, _: &str, _: usize, _: usize) {
+        // Currently not implemented
+    }
+
     fn focus_window(&self) {
         self.inner.dispatch(|inner| {
             let _ = inner.canvas.raw().focus();
@@ -290,10 +331,19 @@ impl RootWindow for Window {
         self.inner.queue(|inner| inner.canvas.has_focus.get())
     }
 
+    fn set_keyboard_capture(&self, capture: bool) -> Result<(), RequestError> {
+        Ok(self.inner.queue(|inner| lock::set_keyboard_lock(inner.canvas.navigator(), capture))?)
+    }
+
     fn request_user_attention(&self, _: Option<UserAttentionType>) {
         // Currently an intentional no-op
     }
 
+    fn announce(&self, message: &str, politeness: Politeness) -> Result<(), RequestError> {
+        self.inner.queue(|inner| announce::announce(inner.canvas.document(), message, politeness));
+        Ok(())
+    }
+
     fn set_theme(&self, _: Option<Theme>) {}
 
     fn theme(&self) -> Option<Theme> {
@@ -340,10 +390,27 @@ impl RootWindow for Window {
         })?)
     }
 
+    fn set_cursor_grab_policy(&self, _policy: CursorGrabPolicy) {}
+
     fn set_cursor_visible(&self, visible: bool) {
         self.inner.dispatch(move |inner| inner.canvas.cursor.set_cursor_visible(visible))
     }
 
+    fn set_cursor_for_device(&self, _: DeviceId, _: Cursor) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_cursor_for_device is not supported").into())
+    }
+
+    fn set_cursor_visible_for_device(
+        &self,
+        device_id: DeviceId,
+        visible: bool,
+    ) -> Result<(), RequestError> {
+        self.inner.dispatch(move |inner| {
+            inner.canvas.cursor.set_cursor_visible_for_device(device_id, visible)
+        });
+        Ok(())
+    }
+
     fn drag_window(&self) -> Result<(), RequestError> {
         Err(NotSupportedError::new("drag_window is not supported").into())
     }
@@ -358,6 +425,10 @@ impl RootWindow for Window {
         Err(NotSupportedError::new("set_cursor_hittest is not supported").into())
     }
 
+    fn set_motion_coalescing(&self, _coalescing: Coalescing) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_motion_coalescing is not supported").into())
+    }
+
     fn current_monitor(&self) -> Option<RootMonitorHandle> {
         Some(self.inner.queue(|inner| inner.monitor.current_monitor()).into())
     }
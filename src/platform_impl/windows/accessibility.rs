@@ -0,0 +1,78 @@
+//! The AccessKit adapter of a window, answering the `WM_GETOBJECT` messages with UI Automation.
+
+use std::cell::RefCell;
+
+use accesskit::{ActionHandler, ActionRequest, ActivationHandler, TreeUpdate};
+use windows_sys::Win32::Foundation::{HWND, LPARAM, LRESULT, WPARAM};
+
+/// The adapter of a window, with the source of its tree.
+pub(crate) struct AccessKit {
+    adapter: accesskit_windows::Adapter,
+    source: Box<dyn ActivationHandler + Send>,
+}
+
+/// Forwards the actions to the thread of the event loop.
+struct Actions<F>(F);
+
+impl<F: FnMut(ActionRequest)> ActionHandler for Actions<F> {
+    fn do_action(&mut self, request: ActionRequest) {
+        (self.0)(request)
+    }
+}
+
+impl AccessKit {
+    /// Create the adapter of the window, which mustn't happen while handling `WM_GETOBJECT`.
+    ///
+    /// `on_action` may be called from any thread.
+    pub(crate) fn new(
+        window: HWND,
+        is_focused: bool,
+        source: Box<dyn ActivationHandler + Send>,
+        on_action: impl FnMut(ActionRequest) + Send + 'static,
+    ) -> Self {
+        let window = accesskit_windows::HWND(window as _);
+        let adapter = accesskit_windows::Adapter::new(window, is_focused, Actions(on_action));
+        Self { adapter, source }
+    }
+}
+
+/// Answer `WM_GETOBJECT`, or return `None` to let `DefWindowProc` handle it.
+pub(crate) fn handle_wm_getobject(
+    accesskit: &RefCell<Option<AccessKit>>,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> Option<LRESULT> {
+    // UI Automation may send a nested `WM_GETOBJECT` when the result is converted, so it's done
+    // once the adapter isn't borrowed anymore.
+    let result = {
+        let mut accesskit = accesskit.borrow_mut();
+        let AccessKit { adapter, source } = accesskit.as_mut()?;
+        let (wparam, lparam) =
+            (accesskit_windows::WPARAM(wparam), accesskit_windows::LPARAM(lparam));
+        adapter.handle_wm_getobject(wparam, lparam, &mut **source)?
+    };
+    Some(result.into().0)
+}
+
+/// Tell the adapter whether the window is focused.
+pub(crate) fn set_focused(accesskit: &RefCell<Option<AccessKit>>, is_focused: bool) {
+    let events = accesskit
+        .borrow_mut()
+        .as_mut()
+        .and_then(|accesskit| accesskit.adapter.update_window_focus_state(is_focused));
+    // Raising the events may call back into the window procedure.
+    if let Some(events) = events {
+        events.raise();
+    }
+}
+
+/// Push a change of the tree, dropped while no assistive technology uses the window.
+pub(crate) fn update(accesskit: &RefCell<Option<AccessKit>>, update: TreeUpdate) {
+    let events = accesskit
+        .borrow_mut()
+        .as_mut()
+        .and_then(|accesskit| accesskit.adapter.update_if_active(|| update));
+    if let Some(events) = events {
+        events.raise();
+    }
+}
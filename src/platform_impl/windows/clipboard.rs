@@ -0,0 +1,331 @@
+//! The clipboard, owned by the window of the event loop thread while it's open.
+
+use std::ffi::OsString;
+use std::os::windows::ffi::OsStringExt;
+use std::path::PathBuf;
+use std::{mem, ptr, slice};
+
+use windows_sys::Win32::Foundation::{GlobalFree, HWND};
+use windows_sys::Win32::Graphics::Gdi::{BITMAPINFOHEADER, BI_BITFIELDS, BI_RGB};
+use windows_sys::Win32::System::DataExchange::{
+    CloseClipboard, EmptyClipboard, GetClipboardData, IsClipboardFormatAvailable, OpenClipboard,
+    RegisterClipboardFormatW, SetClipboardData,
+};
+use windows_sys::Win32::System::Memory::{
+    GlobalAlloc, GlobalLock, GlobalSize, GlobalUnlock, GMEM_MOVEABLE,
+};
+use windows_sys::Win32::System::Ole::{CF_DIB, CF_HDROP, CF_UNICODETEXT};
+use windows_sys::Win32::UI::Shell::DragQueryFileW;
+
+use super::drag_source::file_list;
+use super::util::{decode_wide, encode_wide};
+use crate::clipboard::{ClipboardData, ClipboardFormat, ClipboardImage};
+use crate::error::RequestError;
+
+/// Closes the clipboard when dropped.
+struct OpenedClipboard;
+
+impl OpenedClipboard {
+    fn open(owner: HWND) -> Result<Self, RequestError> {
+        // Another application may have it open, in which case this fails.
+        if unsafe { OpenClipboard(owner) } == 0 {
+            return Err(os_error!(std::io::Error::last_os_error()).into());
+        }
+        Ok(Self)
+    }
+
+    /// A copy of the data in the `format`, if the clipboard contains it.
+    fn read(&self, format: u32) -> Option<Vec<u8>> {
+        let data = unsafe { GetClipboardData(format) };
+        if data == 0 as _ {
+            return None;
+        }
+        let memory = unsafe { GlobalLock(data as _) };
+        if memory.is_null() {
+            return None;
+        }
+        let len = unsafe { GlobalSize(data as _) };
+        let bytes = unsafe { slice::from_raw_parts(memory.cast::<u8>(), len) }.to_vec();
+        unsafe { GlobalUnlock(data as _) };
+        Some(bytes)
+    }
+}
+
+impl Drop for OpenedClipboard {
+    fn drop(&mut self) {
+        unsafe { CloseClipboard() };
+    }
+}
+
+/// The formats registered by applications, such as the ones of HTML and PNG images.
+pub(crate) fn registered_format(name: &str) -> u32 {
+    unsafe { RegisterClipboardFormatW(encode_wide(name).as_ptr()) }
+}
+
+/// The text in UTF-16 with its null terminator, as the system stores it.
+fn wide_bytes(text: &str) -> Vec<u8> {
+    encode_wide(text).iter().flat_map(|unit| unit.to_ne_bytes()).collect()
+}
+
+/// The text of bytes in UTF-16, up to its null terminator.
+fn wide_text(bytes: &[u8]) -> String {
+    let wide: Vec<u16> =
+        bytes.chunks_exact(2).map(|unit| u16::from_ne_bytes([unit[0], unit[1]])).collect();
+    decode_wide(&wide).to_string_lossy().into_owned()
+}
+
+/// Replace the content of the clipboard with `data`.
+pub(crate) fn set(owner: HWND, data: &[ClipboardData]) -> Result<(), RequestError> {
+    let mut formats: Vec<(u32, Vec<u8>)> = Vec::new();
+    for data in data {
+        let encoded = match data {
+            ClipboardData::Text(text) => vec![(CF_UNICODETEXT as u32, wide_bytes(text))],
+            ClipboardData::Html(html) => {
+                vec![(registered_format("HTML Format"), encode_html(html))]
+            },
+            ClipboardData::Image(image) => {
+                let mut encoded = vec![(registered_format("PNG"), image.to_png().into_owned())];
+                // Applications which don't know PNG images read the pixels.
+                if let ClipboardImage::Rgba { width, height, pixels } = image {
+                    encoded.push((CF_DIB as u32, encode_dib(*width, *height, pixels)));
+                }
+                encoded
+            },
+            ClipboardData::Files(paths) => vec![(CF_HDROP as u32, file_list(paths))],
+            // The format holds a single URL.
+            ClipboardData::Uris(uris) => uris
+                .first()
+                .map(|uri| (registered_format("UniformResourceLocatorW"), wide_bytes(uri)))
+                .into_iter()
+                .collect(),
+            ClipboardData::Custom { mime_type, data } => {
+                vec![(registered_format(mime_type), data.clone())]
+            },
+        };
+        for (format, bytes) in encoded {
+            if !formats.iter().any(|(f, _)| *f == format) {
+                formats.push((format, bytes));
+            }
+        }
+    }
+
+    let _clipboard = OpenedClipboard::open(owner)?;
+    unsafe { EmptyClipboard() };
+    for (format, bytes) in formats {
+        let global = unsafe { GlobalAlloc(GMEM_MOVEABLE, bytes.len()) };
+        let memory = unsafe { GlobalLock(global) };
+        if memory.is_null() {
+            unsafe { GlobalFree(global) };
+            return Err(os_error!(std::io::Error::last_os_error()).into());
+        }
+        unsafe {
+            ptr::copy_nonoverlapping(bytes.as_ptr(), memory.cast(), bytes.len());
+            GlobalUnlock(global);
+        }
+
+        // The system owns the memory once it's in the clipboard.
+        if unsafe { SetClipboardData(format, global as _) } == 0 as _ {
+            unsafe { GlobalFree(global) };
+            return Err(os_error!(std::io::Error::last_os_error()).into());
+        }
+    }
+    Ok(())
+}
+
+/// The content of the clipboard in the `format`, if it contains it.
+pub(crate) fn get(
+    owner: HWND,
+    format: ClipboardFormat,
+) -> Result<Option<ClipboardData>, RequestError> {
+    let available = |format: u32| unsafe { IsClipboardFormatAvailable(format) } != 0;
+    let data = match format {
+        ClipboardFormat::Text if available(CF_UNICODETEXT as u32) => {
+            let clipboard = OpenedClipboard::open(owner)?;
+            clipboard
+                .read(CF_UNICODETEXT as u32)
+                .map(|bytes| ClipboardData::Text(wide_text(&bytes)))
+        },
+        ClipboardFormat::Html if available(registered_format("HTML Format")) => {
+            let clipboard = OpenedClipboard::open(owner)?;
+            clipboard.read(registered_format("HTML Format")).and_then(|bytes| decode_html(&bytes))
+        },
+        ClipboardFormat::Image if available(registered_format("PNG")) => {
+            let clipboard = OpenedClipboard::open(owner)?;
+            clipboard
+                .read(registered_format("PNG"))
+                .map(|png| ClipboardData::Image(ClipboardImage::Png(png)))
+        },
+        ClipboardFormat::Image if available(CF_DIB as u32) => {
+            let clipboard = OpenedClipboard::open(owner)?;
+            clipboard.read(CF_DIB as u32).and_then(|dib| decode_dib(&dib))
+        },
+        ClipboardFormat::Files if available(CF_HDROP as u32) => {
+            let _clipboard = OpenedClipboard::open(owner)?;
+            let hdrop = unsafe { GetClipboardData(CF_HDROP as u32) };
+            (hdrop != 0 as _).then(|| ClipboardData::Files(drop_files(hdrop as _)))
+        },
+        ClipboardFormat::Uris if available(registered_format("UniformResourceLocatorW")) => {
+            let clipboard = OpenedClipboard::open(owner)?;
+            clipboard
+                .read(registered_format("UniformResourceLocatorW"))
+                .map(|bytes| ClipboardData::Uris(vec![wide_text(&bytes)]))
+        },
+        ClipboardFormat::Custom(mime_type) if available(registered_format(mime_type)) => {
+            let clipboard = OpenedClipboard::open(owner)?;
+            clipboard
+                .read(registered_format(mime_type))
+                .map(|data| ClipboardData::Custom { mime_type, data })
+        },
+        _ => None,
+    };
+    Ok(data)
+}
+
+/// The formats of the system holding the `format`, the preferred one first.
+pub(crate) fn system_formats(format: ClipboardFormat) -> Vec<u32> {
+    match format {
+        ClipboardFormat::Text => vec![CF_UNICODETEXT as u32],
+        ClipboardFormat::Html => vec![registered_format("HTML Format")],
+        ClipboardFormat::Image => vec![registered_format("PNG"), CF_DIB as u32],
+        ClipboardFormat::Files => vec![CF_HDROP as u32],
+        ClipboardFormat::Uris => vec![registered_format("UniformResourceLocatorW")],
+        ClipboardFormat::Custom(mime_type) => vec![registered_format(mime_type)],
+    }
+}
+
+/// Decode the bytes of the `system_format`, one of the formats of the `format`.
+///
+/// Files are held in a handle rather than in bytes, see [`drop_files`].
+pub(crate) fn decode(
+    format: ClipboardFormat,
+    system_format: u32,
+    bytes: Vec<u8>,
+) -> Option<ClipboardData> {
+    match format {
+        ClipboardFormat::Text => Some(ClipboardData::Text(wide_text(&bytes))),
+        ClipboardFormat::Html => decode_html(&bytes),
+        ClipboardFormat::Image if system_format == CF_DIB as u32 => decode_dib(&bytes),
+        ClipboardFormat::Image => Some(ClipboardData::Image(ClipboardImage::Png(bytes))),
+        ClipboardFormat::Files => None,
+        ClipboardFormat::Uris => Some(ClipboardData::Uris(vec![wide_text(&bytes)])),
+        ClipboardFormat::Custom(mime_type) => {
+            Some(ClipboardData::Custom { mime_type, data: bytes })
+        },
+    }
+}
+
+/// The paths of the files of a `CF_HDROP`, which the clipboard keeps.
+pub(crate) fn drop_files(hdrop: isize) -> Vec<PathBuf> {
+    let count = unsafe { DragQueryFileW(hdrop, u32::MAX, ptr::null_mut(), 0) };
+    (0..count)
+        .map(|i| {
+            let len = unsafe { DragQueryFileW(hdrop, i, ptr::null_mut(), 0) } as usize;
+            let mut path = vec![0; len + 1];
+            unsafe { DragQueryFileW(hdrop, i, path.as_mut_ptr(), path.len() as u32) };
+            OsString::from_wide(&path[..len]).into()
+        })
+        .collect()
+}
+
+/// The `HTML Format` of the fragment: a header with the byte offsets of the document and of the
+/// fragment in it, followed by the document.
+fn encode_html(fragment: &str) -> Vec<u8> {
+    const HEADER_LEN: usize = 105;
+    let prefix = "<html><body><!--StartFragment-->";
+    let suffix = "<!--EndFragment--></body></html>";
+    let start_fragment = HEADER_LEN + prefix.len();
+    let end_fragment = start_fragment + fragment.len();
+    let end_html = end_fragment + suffix.len();
+    let header = format!(
+        "Version:0.9\r\nStartHTML:{HEADER_LEN:010}\r\nEndHTML:{end_html:010}\r\nStartFragment:\
+         {start_fragment:010}\r\nEndFragment:{end_fragment:010}\r\n"
+    );
+    debug_assert_eq!(header.len(), HEADER_LEN);
+    format!("{header}{prefix}{fragment}{suffix}\0").into_bytes()
+}
+
+/// The fragment of the `HTML Format`.
+fn decode_html(bytes: &[u8]) -> Option<ClipboardData> {
+    let header_len = bytes.iter().position(|byte| *byte == b'<').unwrap_or(bytes.len());
+    let header = String::from_utf8_lossy(&bytes[..header_len]);
+    let offset = |name: &str| -> Option<usize> {
+        header.lines().find_map(|line| line.strip_prefix(name))?.trim().parse().ok()
+    };
+    let start = offset("StartFragment:")?;
+    let end = offset("EndFragment:")?.min(bytes.len());
+    let fragment = bytes.get(start..end)?;
+    Some(ClipboardData::Html(String::from_utf8_lossy(fragment).into_owned()))
+}
+
+/// A device independent bitmap of RGBA pixels, with 32 bits per pixel in BGRA order.
+fn encode_dib(width: u32, height: u32, pixels: &[u8]) -> Vec<u8> {
+    let header = BITMAPINFOHEADER {
+        biSize: mem::size_of::<BITMAPINFOHEADER>() as u32,
+        biWidth: width as i32,
+        // The rows are from bottom to top, which is understood by more applications.
+        biHeight: height as i32,
+        biPlanes: 1,
+        biBitCount: 32,
+        biCompression: BI_RGB,
+        biSizeImage: width * height * 4,
+        biXPelsPerMeter: 0,
+        biYPelsPerMeter: 0,
+        biClrUsed: 0,
+        biClrImportant: 0,
+    };
+    let mut bytes = unsafe {
+        slice::from_raw_parts(
+            &header as *const BITMAPINFOHEADER as *const u8,
+            mem::size_of::<BITMAPINFOHEADER>(),
+        )
+    }
+    .to_vec();
+    for row in pixels.chunks_exact((width as usize * 4).max(1)).take(height as usize).rev() {
+        bytes.extend(row.chunks_exact(4).flat_map(|rgba| [rgba[2], rgba[1], rgba[0], rgba[3]]));
+    }
+    bytes
+}
+
+/// The RGBA pixels of a device independent bitmap with 24 or 32 bits per pixel.
+fn decode_dib(bytes: &[u8]) -> Option<ClipboardData> {
+    if bytes.len() < mem::size_of::<BITMAPINFOHEADER>() {
+        return None;
+    }
+    let header = unsafe { ptr::read_unaligned(bytes.as_ptr().cast::<BITMAPINFOHEADER>()) };
+    let bytes_per_pixel = match (header.biBitCount, header.biCompression) {
+        (24, BI_RGB) => 3,
+        // The masks of the bit fields are assumed to be the ones of BGRA.
+        (32, BI_RGB | BI_BITFIELDS) => 4,
+        _ => return None,
+    };
+    let mut offset = header.biSize as usize;
+    if header.biCompression == BI_BITFIELDS && header.biSize == 40 {
+        offset += 12;
+    }
+
+    let width = header.biWidth.unsigned_abs();
+    let height = header.biHeight.unsigned_abs();
+    // Rows are aligned on 4 bytes.
+    let stride = (width as usize * bytes_per_pixel + 3) & !3;
+    let rows = bytes.get(offset..offset + stride * height as usize)?.chunks_exact(stride);
+    let mut pixels = Vec::with_capacity(width as usize * height as usize * 4);
+    let mut push_row = |row: &[u8]| {
+        for bgra in row[..width as usize * bytes_per_pixel].chunks_exact(bytes_per_pixel) {
+            let alpha = if bytes_per_pixel == 4 { bgra[3] } else { 255 };
+            pixels.extend_from_slice(&[bgra[2], bgra[1], bgra[0], alpha]);
+        }
+    };
+    // A positive height means the rows are from bottom to top.
+    if header.biHeight > 0 {
+        rows.rev().for_each(&mut push_row);
+    } else {
+        rows.for_each(&mut push_row);
+    }
+
+    // Most applications leave the alpha of 32 bits bitmaps empty, they are opaque.
+    if bytes_per_pixel == 4 && pixels.chunks_exact(4).all(|rgba| rgba[3] == 0) {
+        pixels.chunks_exact_mut(4).for_each(|rgba| rgba[3] = 255);
+    }
+
+    Some(ClipboardData::Image(ClipboardImage::Rgba { width, height, pixels }))
+}
@@ -4,6 +4,7 @@ use std::{ffi::c_void, ptr};
 
 use windows_sys::core::PCSTR;
 use windows_sys::Win32::Foundation::{BOOL, HWND, NTSTATUS, S_OK};
+use windows_sys::Win32::Graphics::Dwm::{DwmSetWindowAttribute, DWMWA_USE_IMMERSIVE_DARK_MODE};
 use windows_sys::Win32::System::LibraryLoader::{GetProcAddress, LoadLibraryA};
 use windows_sys::Win32::System::SystemInformation::OSVERSIONINFOW;
 use windows_sys::Win32::UI::Accessibility::{HCF_HIGHCONTRASTON, HIGHCONTRASTA};
@@ -71,6 +72,9 @@ pub fn try_theme(hwnd: HWND, preferred_theme: Option<Theme>) -> Theme {
 
         let status = unsafe { SetWindowTheme(hwnd, theme_name, ptr::null()) };
 
+        // Also makes the DWM draw the title bar and the backdrop materials with the theme.
+        set_immersive_dark_mode(hwnd, is_dark_mode);
+
         if status == S_OK && set_dark_mode_for_window(hwnd, is_dark_mode) {
             return theme;
         }
@@ -79,6 +83,24 @@ pub fn try_theme(hwnd: HWND, preferred_theme: Option<Theme>) -> Theme {
     Theme::Light
 }
 
+fn set_immersive_dark_mode(hwnd: HWND, is_dark_mode: bool) {
+    // The attribute had another value before Windows 10 20H1.
+    const DWMWA_USE_IMMERSIVE_DARK_MODE_BEFORE_20H1: u32 = 19;
+
+    let value = BOOL::from(is_dark_mode);
+    let set_attribute = |attribute: u32| unsafe {
+        DwmSetWindowAttribute(
+            hwnd,
+            attribute,
+            &value as *const BOOL as *const c_void,
+            std::mem::size_of::<BOOL>() as u32,
+        )
+    };
+    if set_attribute(DWMWA_USE_IMMERSIVE_DARK_MODE as u32) < 0 {
+        set_attribute(DWMWA_USE_IMMERSIVE_DARK_MODE_BEFORE_20H1);
+    }
+}
+
 fn set_dark_mode_for_window(hwnd: HWND, is_dark_mode: bool) -> bool {
     // Uses Windows undocumented API SetWindowCompositionAttribute,
     // as seen in win32-darkmode example linked at top of file.
@@ -4,10 +4,13 @@
 use std::ffi::c_void;
 
 use windows_sys::core::{IUnknown, GUID, HRESULT};
-use windows_sys::Win32::Foundation::{BOOL, HWND, POINTL};
+use windows_sys::Win32::Foundation::{BOOL, HMODULE, HWND, POINTL, RECT};
+use windows_sys::Win32::Graphics::Gdi::HMONITOR;
 use windows_sys::Win32::System::Com::{
     IAdviseSink, IDataObject, IEnumFORMATETC, IEnumSTATDATA, FORMATETC, STGMEDIUM,
 };
+use windows_sys::Win32::UI::Shell::THUMBBUTTON;
+use windows_sys::Win32::UI::WindowsAndMessaging::HICON;
 
 #[repr(C)]
 pub struct IUnknownVtbl {
@@ -33,7 +36,7 @@ pub struct IDataObjectVtbl {
         pformatetc: *const FORMATETC,
         pmedium: *mut STGMEDIUM,
     ) -> HRESULT,
-    QueryGetData:
+    pub QueryGetData:
         unsafe extern "system" fn(This: *mut IDataObject, pformatetc: *const FORMATETC) -> HRESULT,
     pub GetCanonicalFormatEtc: unsafe extern "system" fn(
         This: *mut IDataObject,
@@ -43,7 +46,7 @@ pub struct IDataObjectVtbl {
     pub SetData: unsafe extern "system" fn(
         This: *mut IDataObject,
         pformatetc: *const FORMATETC,
-        pformatetcOut: *const FORMATETC,
+        pmedium: *const STGMEDIUM,
         fRelease: BOOL,
     ) -> HRESULT,
     pub EnumFormatEtc: unsafe extern "system" fn(
@@ -96,6 +99,29 @@ pub struct IDropTarget {
     pub lpVtbl: *const IDropTargetVtbl,
 }
 
+#[repr(C)]
+pub struct ISequentialStreamVtbl {
+    pub parent: IUnknownVtbl,
+    pub Read: unsafe extern "system" fn(
+        This: *mut ISequentialStream,
+        pv: *mut c_void,
+        cb: u32,
+        pcbRead: *mut u32,
+    ) -> HRESULT,
+    pub Write: unsafe extern "system" fn(
+        This: *mut ISequentialStream,
+        pv: *const c_void,
+        cb: u32,
+        pcbWritten: *mut u32,
+    ) -> HRESULT,
+}
+
+/// The base of `IStream`, enough to read the streams of the data objects.
+#[repr(C)]
+pub struct ISequentialStream {
+    pub lpVtbl: *const ISequentialStreamVtbl,
+}
+
 #[repr(C)]
 pub struct ITaskbarListVtbl {
     pub parent: IUnknownVtbl,
@@ -126,6 +152,72 @@ pub struct ITaskbarList2 {
     pub lpVtbl: *const ITaskbarList2Vtbl,
 }
 
+#[repr(C)]
+pub struct ITaskbarList3Vtbl {
+    pub parent: ITaskbarList2Vtbl,
+    pub SetProgressValue: unsafe extern "system" fn(
+        This: *mut ITaskbarList3,
+        hwnd: HWND,
+        ullCompleted: u64,
+        ullTotal: u64,
+    ) -> HRESULT,
+    pub SetProgressState:
+        unsafe extern "system" fn(This: *mut ITaskbarList3, hwnd: HWND, tbpFlags: i32) -> HRESULT,
+    pub RegisterTab: unsafe extern "system" fn(
+        This: *mut ITaskbarList3,
+        hwndTab: HWND,
+        hwndMDI: HWND,
+    ) -> HRESULT,
+    pub UnregisterTab:
+        unsafe extern "system" fn(This: *mut ITaskbarList3, hwndTab: HWND) -> HRESULT,
+    pub SetTabOrder: unsafe extern "system" fn(
+        This: *mut ITaskbarList3,
+        hwndTab: HWND,
+        hwndInsertBefore: HWND,
+    ) -> HRESULT,
+    pub SetTabActive: unsafe extern "system" fn(
+        This: *mut ITaskbarList3,
+        hwndTab: HWND,
+        hwndMDI: HWND,
+        dwReserved: u32,
+    ) -> HRESULT,
+    pub ThumbBarAddButtons: unsafe extern "system" fn(
+        This: *mut ITaskbarList3,
+        hwnd: HWND,
+        cButtons: u32,
+        pButton: *const THUMBBUTTON,
+    ) -> HRESULT,
+    pub ThumbBarUpdateButtons: unsafe extern "system" fn(
+        This: *mut ITaskbarList3,
+        hwnd: HWND,
+        cButtons: u32,
+        pButton: *const THUMBBUTTON,
+    ) -> HRESULT,
+    pub ThumbBarSetImageList:
+        unsafe extern "system" fn(This: *mut ITaskbarList3, hwnd: HWND, himl: isize) -> HRESULT,
+    pub SetOverlayIcon: unsafe extern "system" fn(
+        This: *mut ITaskbarList3,
+        hwnd: HWND,
+        hIcon: HICON,
+        pszDescription: *const u16,
+    ) -> HRESULT,
+    pub SetThumbnailTooltip: unsafe extern "system" fn(
+        This: *mut ITaskbarList3,
+        hwnd: HWND,
+        pszTip: *const u16,
+    ) -> HRESULT,
+    pub SetThumbnailClip: unsafe extern "system" fn(
+        This: *mut ITaskbarList3,
+        hwnd: HWND,
+        prcClip: *const RECT,
+    ) -> HRESULT,
+}
+
+#[repr(C)]
+pub struct ITaskbarList3 {
+    pub lpVtbl: *const ITaskbarList3Vtbl,
+}
+
 pub const CLSID_TaskbarList: GUID = GUID {
     data1: 0x56fdf344,
     data2: 0xfd6d,
@@ -146,3 +238,800 @@ pub const IID_ITaskbarList2: GUID = GUID {
     data3: 0x429b,
     data4: [0xa6, 0x6e, 0x19, 0x35, 0xe4, 0x4f, 0x43, 0x17],
 };
+
+pub const IID_ITaskbarList3: GUID = GUID {
+    data1: 0xea1afb91,
+    data2: 0x9e28,
+    data3: 0x4b86,
+    data4: [0x90, 0xe9, 0x9e, 0x9f, 0x8a, 0x5e, 0xef, 0xaf],
+};
+
+#[repr(C)]
+pub struct ITipInvocationVtbl {
+    pub parent: IUnknownVtbl,
+    pub Toggle: unsafe extern "system" fn(This: *mut ITipInvocation, hwnd: HWND) -> HRESULT,
+}
+
+#[repr(C)]
+pub struct ITipInvocation {
+    pub lpVtbl: *const ITipInvocationVtbl,
+}
+
+#[repr(C)]
+pub struct IFrameworkInputPaneVtbl {
+    pub parent: IUnknownVtbl,
+    pub Advise: unsafe extern "system" fn(
+        This: *mut IFrameworkInputPane,
+        pWindow: *mut IUnknown,
+        pHandler: *mut IUnknown,
+        pdwCookie: *mut u32,
+    ) -> HRESULT,
+    pub AdviseWithHWND: unsafe extern "system" fn(
+        This: *mut IFrameworkInputPane,
+        hwnd: HWND,
+        pHandler: *mut IUnknown,
+        pdwCookie: *mut u32,
+    ) -> HRESULT,
+    pub Unadvise:
+        unsafe extern "system" fn(This: *mut IFrameworkInputPane, dwCookie: u32) -> HRESULT,
+    pub Location: unsafe extern "system" fn(
+        This: *mut IFrameworkInputPane,
+        prcInputPaneScreenLocation: *mut RECT,
+    ) -> HRESULT,
+}
+
+#[repr(C)]
+pub struct IFrameworkInputPane {
+    pub lpVtbl: *const IFrameworkInputPaneVtbl,
+}
+
+pub const CLSID_UIHostNoLaunch: GUID = GUID {
+    data1: 0x4ce576fa,
+    data2: 0x83dc,
+    data3: 0x4f88,
+    data4: [0x95, 0x1c, 0x9d, 0x07, 0x82, 0xb4, 0xe3, 0x76],
+};
+
+pub const IID_ITipInvocation: GUID = GUID {
+    data1: 0x37c994e7,
+    data2: 0x432b,
+    data3: 0x4834,
+    data4: [0xa2, 0xf7, 0xdc, 0xe1, 0xf1, 0x3b, 0x83, 0x4b],
+};
+
+pub const CLSID_FrameworkInputPane: GUID = GUID {
+    data1: 0xd5120aa3,
+    data2: 0x46ba,
+    data3: 0x44c5,
+    data4: [0x82, 0x2d, 0xca, 0x80, 0x92, 0xc1, 0xfc, 0x72],
+};
+
+pub const IID_IFrameworkInputPane: GUID = GUID {
+    data1: 0x5752238b,
+    data2: 0x24f0,
+    data3: 0x495a,
+    data4: [0x82, 0xf1, 0x2f, 0xd5, 0x93, 0x05, 0x67, 0x96],
+};
+
+#[cfg(feature = "dialogs")]
+#[repr(C)]
+pub struct COMDLG_FILTERSPEC {
+    pub pszName: *const u16,
+    pub pszSpec: *const u16,
+}
+
+#[cfg(feature = "dialogs")]
+#[repr(C)]
+pub struct IShellItemVtbl {
+    pub parent: IUnknownVtbl,
+    pub BindToHandler: unsafe extern "system" fn(
+        This: *mut IShellItem,
+        pbc: *mut c_void,
+        bhid: *const GUID,
+        riid: *const GUID,
+        ppv: *mut *mut c_void,
+    ) -> HRESULT,
+    pub GetParent:
+        unsafe extern "system" fn(This: *mut IShellItem, ppsi: *mut *mut IShellItem) -> HRESULT,
+    pub GetDisplayName: unsafe extern "system" fn(
+        This: *mut IShellItem,
+        sigdnName: i32,
+        ppszName: *mut *mut u16,
+    ) -> HRESULT,
+    pub GetAttributes: unsafe extern "system" fn(
+        This: *mut IShellItem,
+        sfgaoMask: u32,
+        psfgaoAttribs: *mut u32,
+    ) -> HRESULT,
+    pub Compare: unsafe extern "system" fn(
+        This: *mut IShellItem,
+        psi: *mut IShellItem,
+        hint: u32,
+        piOrder: *mut i32,
+    ) -> HRESULT,
+}
+
+#[cfg(feature = "dialogs")]
+#[repr(C)]
+pub struct IShellItem {
+    pub lpVtbl: *const IShellItemVtbl,
+}
+
+#[cfg(feature = "dialogs")]
+#[repr(C)]
+pub struct IShellItemArrayVtbl {
+    pub parent: IUnknownVtbl,
+    pub BindToHandler: unsafe extern "system" fn(
+        This: *mut IShellItemArray,
+        pbc: *mut c_void,
+        bhid: *const GUID,
+        riid: *const GUID,
+        ppvOut: *mut *mut c_void,
+    ) -> HRESULT,
+    pub GetPropertyStore: unsafe extern "system" fn(
+        This: *mut IShellItemArray,
+        flags: i32,
+        riid: *const GUID,
+        ppv: *mut *mut c_void,
+    ) -> HRESULT,
+    pub GetPropertyDescriptionList: unsafe extern "system" fn(
+        This: *mut IShellItemArray,
+        keyType: *const c_void,
+        riid: *const GUID,
+        ppv: *mut *mut c_void,
+    ) -> HRESULT,
+    pub GetAttributes: unsafe extern "system" fn(
+        This: *mut IShellItemArray,
+        AttribFlags: i32,
+        sfgaoMask: u32,
+        psfgaoAttribs: *mut u32,
+    ) -> HRESULT,
+    pub GetCount:
+        unsafe extern "system" fn(This: *mut IShellItemArray, pdwNumItems: *mut u32) -> HRESULT,
+    pub GetItemAt: unsafe extern "system" fn(
+        This: *mut IShellItemArray,
+        dwIndex: u32,
+        ppsi: *mut *mut IShellItem,
+    ) -> HRESULT,
+    pub EnumItems: unsafe extern "system" fn(
+        This: *mut IShellItemArray,
+        ppenumShellItems: *mut *mut c_void,
+    ) -> HRESULT,
+}
+
+#[cfg(feature = "dialogs")]
+#[repr(C)]
+pub struct IShellItemArray {
+    pub lpVtbl: *const IShellItemArrayVtbl,
+}
+
+#[cfg(feature = "dialogs")]
+#[repr(C)]
+pub struct IModalWindowVtbl {
+    pub parent: IUnknownVtbl,
+    pub Show: unsafe extern "system" fn(This: *mut IFileDialog, hwndOwner: HWND) -> HRESULT,
+}
+
+#[cfg(feature = "dialogs")]
+#[repr(C)]
+pub struct IFileDialogVtbl {
+    pub parent: IModalWindowVtbl,
+    pub SetFileTypes: unsafe extern "system" fn(
+        This: *mut IFileDialog,
+        cFileTypes: u32,
+        rgFilterSpec: *const COMDLG_FILTERSPEC,
+    ) -> HRESULT,
+    pub SetFileTypeIndex:
+        unsafe extern "system" fn(This: *mut IFileDialog, iFileType: u32) -> HRESULT,
+    pub GetFileTypeIndex:
+        unsafe extern "system" fn(This: *mut IFileDialog, piFileType: *mut u32) -> HRESULT,
+    pub Advise: unsafe extern "system" fn(
+        This: *mut IFileDialog,
+        pfde: *mut c_void,
+        pdwCookie: *mut u32,
+    ) -> HRESULT,
+    pub Unadvise: unsafe extern "system" fn(This: *mut IFileDialog, dwCookie: u32) -> HRESULT,
+    pub SetOptions: unsafe extern "system" fn(This: *mut IFileDialog, fos: u32) -> HRESULT,
+    pub GetOptions: unsafe extern "system" fn(This: *mut IFileDialog, pfos: *mut u32) -> HRESULT,
+    pub SetDefaultFolder:
+        unsafe extern "system" fn(This: *mut IFileDialog, psi: *mut IShellItem) -> HRESULT,
+    pub SetFolder:
+        unsafe extern "system" fn(This: *mut IFileDialog, psi: *mut IShellItem) -> HRESULT,
+    pub GetFolder:
+        unsafe extern "system" fn(This: *mut IFileDialog, ppsi: *mut *mut IShellItem) -> HRESULT,
+    pub GetCurrentSelection:
+        unsafe extern "system" fn(This: *mut IFileDialog, ppsi: *mut *mut IShellItem) -> HRESULT,
+    pub SetFileName:
+        unsafe extern "system" fn(This: *mut IFileDialog, pszName: *const u16) -> HRESULT,
+    pub GetFileName:
+        unsafe extern "system" fn(This: *mut IFileDialog, pszName: *mut *mut u16) -> HRESULT,
+    pub SetTitle:
+        unsafe extern "system" fn(This: *mut IFileDialog, pszTitle: *const u16) -> HRESULT,
+    pub SetOkButtonLabel:
+        unsafe extern "system" fn(This: *mut IFileDialog, pszText: *const u16) -> HRESULT,
+    pub SetFileNameLabel:
+        unsafe extern "system" fn(This: *mut IFileDialog, pszLabel: *const u16) -> HRESULT,
+    pub GetResult:
+        unsafe extern "system" fn(This: *mut IFileDialog, ppsi: *mut *mut IShellItem) -> HRESULT,
+    pub AddPlace: unsafe extern "system" fn(
+        This: *mut IFileDialog,
+        psi: *mut IShellItem,
+        fdap: i32,
+    ) -> HRESULT,
+    pub SetDefaultExtension: unsafe extern "system" fn(
+        This: *mut IFileDialog,
+        pszDefaultExtension: *const u16,
+    ) -> HRESULT,
+    pub Close: unsafe extern "system" fn(This: *mut IFileDialog, hr: HRESULT) -> HRESULT,
+    pub SetClientGuid:
+        unsafe extern "system" fn(This: *mut IFileDialog, guid: *const GUID) -> HRESULT,
+    pub ClearClientData: unsafe extern "system" fn(This: *mut IFileDialog) -> HRESULT,
+    pub SetFilter:
+        unsafe extern "system" fn(This: *mut IFileDialog, pFilter: *mut c_void) -> HRESULT,
+}
+
+#[cfg(feature = "dialogs")]
+#[repr(C)]
+pub struct IFileDialog {
+    pub lpVtbl: *const IFileDialogVtbl,
+}
+
+#[cfg(feature = "dialogs")]
+#[repr(C)]
+pub struct IFileOpenDialogVtbl {
+    pub parent: IFileDialogVtbl,
+    pub GetResults: unsafe extern "system" fn(
+        This: *mut IFileOpenDialog,
+        ppenum: *mut *mut IShellItemArray,
+    ) -> HRESULT,
+    pub GetSelectedItems: unsafe extern "system" fn(
+        This: *mut IFileOpenDialog,
+        ppsai: *mut *mut IShellItemArray,
+    ) -> HRESULT,
+}
+
+#[cfg(feature = "dialogs")]
+#[repr(C)]
+pub struct IFileOpenDialog {
+    pub lpVtbl: *const IFileOpenDialogVtbl,
+}
+
+#[cfg(feature = "dialogs")]
+pub const CLSID_FileOpenDialog: GUID = GUID {
+    data1: 0xdc1c5a9c,
+    data2: 0xe88a,
+    data3: 0x4dde,
+    data4: [0xa5, 0xa1, 0x60, 0xf8, 0x2a, 0x20, 0xae, 0xf7],
+};
+
+#[cfg(feature = "dialogs")]
+pub const CLSID_FileSaveDialog: GUID = GUID {
+    data1: 0xc0b4e2f3,
+    data2: 0xba21,
+    data3: 0x4773,
+    data4: [0x8d, 0xba, 0x33, 0x5e, 0xc9, 0x46, 0xeb, 0x8b],
+};
+
+#[cfg(feature = "dialogs")]
+pub const IID_IFileOpenDialog: GUID = GUID {
+    data1: 0xd57c7288,
+    data2: 0xd4ad,
+    data3: 0x4768,
+    data4: [0xbe, 0x02, 0x9d, 0x96, 0x95, 0x32, 0xd9, 0x60],
+};
+
+#[cfg(feature = "dialogs")]
+pub const IID_IFileSaveDialog: GUID = GUID {
+    data1: 0x84bccd23,
+    data2: 0x5fde,
+    data3: 0x4cdb,
+    data4: [0xae, 0xa4, 0xaf, 0x64, 0xb8, 0x3d, 0x78, 0xab],
+};
+
+#[cfg(feature = "dialogs")]
+pub const IID_IShellItem: GUID = GUID {
+    data1: 0x43826d1e,
+    data2: 0xe718,
+    data3: 0x42ee,
+    data4: [0xbc, 0x55, 0xa1, 0xe2, 0x61, 0xc3, 0x7b, 0xfe],
+};
+
+pub const IID_IDataObject: GUID = GUID {
+    data1: 0x0000010e,
+    data2: 0x0000,
+    data3: 0x0000,
+    data4: [0xc0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x46],
+};
+
+#[repr(C)]
+pub struct IDXGIObjectVtbl {
+    pub parent: IUnknownVtbl,
+    pub SetPrivateData: unsafe extern "system" fn(
+        This: *mut c_void,
+        Name: *const GUID,
+        DataSize: u32,
+        pData: *const c_void,
+    ) -> HRESULT,
+    pub SetPrivateDataInterface: unsafe extern "system" fn(
+        This: *mut c_void,
+        Name: *const GUID,
+        pUnknown: *mut IUnknown,
+    ) -> HRESULT,
+    pub GetPrivateData: unsafe extern "system" fn(
+        This: *mut c_void,
+        Name: *const GUID,
+        pDataSize: *mut u32,
+        pData: *mut c_void,
+    ) -> HRESULT,
+    pub GetParent: unsafe extern "system" fn(
+        This: *mut c_void,
+        riid: *const GUID,
+        ppParent: *mut *mut c_void,
+    ) -> HRESULT,
+}
+
+#[repr(C)]
+pub struct IDXGIFactoryVtbl {
+    pub parent: IDXGIObjectVtbl,
+    pub EnumAdapters: unsafe extern "system" fn(
+        This: *mut IDXGIFactory,
+        Adapter: u32,
+        ppAdapter: *mut *mut IDXGIAdapter,
+    ) -> HRESULT,
+    pub MakeWindowAssociation: unsafe extern "system" fn(
+        This: *mut IDXGIFactory,
+        WindowHandle: HWND,
+        Flags: u32,
+    ) -> HRESULT,
+    pub GetWindowAssociation:
+        unsafe extern "system" fn(This: *mut IDXGIFactory, pWindowHandle: *mut HWND) -> HRESULT,
+    pub CreateSwapChain: unsafe extern "system" fn(
+        This: *mut IDXGIFactory,
+        pDevice: *mut IUnknown,
+        pDesc: *mut c_void,
+        ppSwapChain: *mut *mut c_void,
+    ) -> HRESULT,
+    pub CreateSoftwareAdapter: unsafe extern "system" fn(
+        This: *mut IDXGIFactory,
+        Module: HMODULE,
+        ppAdapter: *mut *mut IDXGIAdapter,
+    ) -> HRESULT,
+}
+
+#[repr(C)]
+pub struct IDXGIFactory {
+    pub lpVtbl: *const IDXGIFactoryVtbl,
+}
+
+#[repr(C)]
+pub struct IDXGIAdapterVtbl {
+    pub parent: IDXGIObjectVtbl,
+    pub EnumOutputs: unsafe extern "system" fn(
+        This: *mut IDXGIAdapter,
+        Output: u32,
+        ppOutput: *mut *mut c_void,
+    ) -> HRESULT,
+    pub GetDesc: unsafe extern "system" fn(This: *mut IDXGIAdapter, pDesc: *mut c_void) -> HRESULT,
+    pub CheckInterfaceSupport: unsafe extern "system" fn(
+        This: *mut IDXGIAdapter,
+        InterfaceName: *const GUID,
+        pUMDVersion: *mut i64,
+    ) -> HRESULT,
+}
+
+#[repr(C)]
+pub struct IDXGIAdapter {
+    pub lpVtbl: *const IDXGIAdapterVtbl,
+}
+
+/// The methods of `IDXGIOutput` up to `IDXGIOutput5`, which `IDXGIOutput6` inherits.
+#[repr(C)]
+pub struct IDXGIOutputVtbl {
+    pub parent: IDXGIObjectVtbl,
+    pub GetDesc: unsafe extern "system" fn(This: *mut IDXGIOutput6, pDesc: *mut c_void) -> HRESULT,
+    pub GetDisplayModeList: unsafe extern "system" fn(
+        This: *mut IDXGIOutput6,
+        EnumFormat: u32,
+        Flags: u32,
+        pNumModes: *mut u32,
+        pDesc: *mut c_void,
+    ) -> HRESULT,
+    pub FindClosestMatchingMode: unsafe extern "system" fn(
+        This: *mut IDXGIOutput6,
+        pModeToMatch: *const c_void,
+        pClosestMatch: *mut c_void,
+        pConcernedDevice: *mut IUnknown,
+    ) -> HRESULT,
+    pub WaitForVBlank: unsafe extern "system" fn(This: *mut IDXGIOutput6) -> HRESULT,
+    pub TakeOwnership: unsafe extern "system" fn(
+        This: *mut IDXGIOutput6,
+        pDevice: *mut IUnknown,
+        Exclusive: BOOL,
+    ) -> HRESULT,
+    pub ReleaseOwnership: unsafe extern "system" fn(This: *mut IDXGIOutput6),
+    pub GetGammaControlCapabilities:
+        unsafe extern "system" fn(This: *mut IDXGIOutput6, pGammaCaps: *mut c_void) -> HRESULT,
+    pub SetGammaControl:
+        unsafe extern "system" fn(This: *mut IDXGIOutput6, pArray: *const c_void) -> HRESULT,
+    pub GetGammaControl:
+        unsafe extern "system" fn(This: *mut IDXGIOutput6, pArray: *mut c_void) -> HRESULT,
+    pub SetDisplaySurface:
+        unsafe extern "system" fn(This: *mut IDXGIOutput6, pScanoutSurface: *mut c_void) -> HRESULT,
+    pub GetDisplaySurfaceData:
+        unsafe extern "system" fn(This: *mut IDXGIOutput6, pDestination: *mut c_void) -> HRESULT,
+    pub GetFrameStatistics:
+        unsafe extern "system" fn(This: *mut IDXGIOutput6, pStats: *mut c_void) -> HRESULT,
+    // IDXGIOutput1
+    pub GetDisplayModeList1: unsafe extern "system" fn(
+        This: *mut IDXGIOutput6,
+        EnumFormat: u32,
+        Flags: u32,
+        pNumModes: *mut u32,
+        pDesc: *mut c_void,
+    ) -> HRESULT,
+    pub FindClosestMatchingMode1: unsafe extern "system" fn(
+        This: *mut IDXGIOutput6,
+        pModeToMatch: *const c_void,
+        pClosestMatch: *mut c_void,
+        pConcernedDevice: *mut IUnknown,
+    ) -> HRESULT,
+    pub GetDisplaySurfaceData1:
+        unsafe extern "system" fn(This: *mut IDXGIOutput6, pDestination: *mut c_void) -> HRESULT,
+    pub DuplicateOutput: unsafe extern "system" fn(
+        This: *mut IDXGIOutput6,
+        pDevice: *mut IUnknown,
+        ppOutputDuplication: *mut *mut c_void,
+    ) -> HRESULT,
+    // IDXGIOutput2
+    pub SupportsOverlays: unsafe extern "system" fn(This: *mut IDXGIOutput6) -> BOOL,
+    // IDXGIOutput3
+    pub CheckOverlaySupport: unsafe extern "system" fn(
+        This: *mut IDXGIOutput6,
+        EnumFormat: u32,
+        pConcernedDevice: *mut IUnknown,
+        pFlags: *mut u32,
+    ) -> HRESULT,
+    // IDXGIOutput4
+    pub CheckOverlayColorSpaceSupport: unsafe extern "system" fn(
+        This: *mut IDXGIOutput6,
+        Format: u32,
+        ColorSpace: i32,
+        pConcernedDevice: *mut IUnknown,
+        pFlags: *mut u32,
+    ) -> HRESULT,
+    // IDXGIOutput5
+    pub DuplicateOutput1: unsafe extern "system" fn(
+        This: *mut IDXGIOutput6,
+        pDevice: *mut IUnknown,
+        Flags: u32,
+        SupportedFormatsCount: u32,
+        pSupportedFormats: *const u32,
+        ppOutputDuplication: *mut *mut c_void,
+    ) -> HRESULT,
+}
+
+#[repr(C)]
+pub struct IDXGIOutput6Vtbl {
+    pub parent: IDXGIOutputVtbl,
+    pub GetDesc1: unsafe extern "system" fn(
+        This: *mut IDXGIOutput6,
+        pDesc: *mut DXGI_OUTPUT_DESC1,
+    ) -> HRESULT,
+    pub CheckHardwareCompositionSupport:
+        unsafe extern "system" fn(This: *mut IDXGIOutput6, pFlags: *mut u32) -> HRESULT,
+}
+
+#[repr(C)]
+pub struct IDXGIOutput6 {
+    pub lpVtbl: *const IDXGIOutput6Vtbl,
+}
+
+#[repr(C)]
+pub struct DXGI_OUTPUT_DESC1 {
+    pub DeviceName: [u16; 32],
+    pub DesktopCoordinates: RECT,
+    pub AttachedToDesktop: BOOL,
+    pub Rotation: i32,
+    pub Monitor: HMONITOR,
+    pub BitsPerColor: u32,
+    pub ColorSpace: i32,
+    pub RedPrimary: [f32; 2],
+    pub GreenPrimary: [f32; 2],
+    pub BluePrimary: [f32; 2],
+    pub WhitePoint: [f32; 2],
+    pub MinLuminance: f32,
+    pub MaxLuminance: f32,
+    pub MaxFullFrameLuminance: f32,
+}
+
+pub const DXGI_COLOR_SPACE_RGB_FULL_G2084_NONE_P2020: i32 = 12;
+
+pub const IID_IDXGIFactory1: GUID = GUID {
+    data1: 0x770aae78,
+    data2: 0xf26f,
+    data3: 0x4dba,
+    data4: [0xa8, 0x29, 0x25, 0x3c, 0x83, 0xd1, 0xb3, 0x87],
+};
+
+pub const IID_IDXGIOutput6: GUID = GUID {
+    data1: 0x068346e8,
+    data2: 0xaaec,
+    data3: 0x4b84,
+    data4: [0xad, 0xd7, 0x13, 0x7f, 0x51, 0x3f, 0x77, 0xa1],
+};
+
+#[cfg(feature = "dialogs")]
+pub const FOS_OVERWRITEPROMPT: u32 = 0x2;
+#[cfg(feature = "dialogs")]
+pub const FOS_PICKFOLDERS: u32 = 0x20;
+#[cfg(feature = "dialogs")]
+pub const FOS_FORCEFILESYSTEM: u32 = 0x40;
+#[cfg(feature = "dialogs")]
+pub const FOS_ALLOWMULTISELECT: u32 = 0x200;
+
+#[cfg(feature = "dialogs")]
+pub const SIGDN_FILESYSPATH: i32 = 0x80058000u32 as i32;
+
+#[repr(C)]
+pub struct IObjectArrayVtbl {
+    pub parent: IUnknownVtbl,
+    pub GetCount:
+        unsafe extern "system" fn(This: *mut IObjectArray, pcObjects: *mut u32) -> HRESULT,
+    pub GetAt: unsafe extern "system" fn(
+        This: *mut IObjectArray,
+        uiIndex: u32,
+        riid: *const GUID,
+        ppv: *mut *mut c_void,
+    ) -> HRESULT,
+}
+
+#[repr(C)]
+pub struct IObjectArray {
+    pub lpVtbl: *const IObjectArrayVtbl,
+}
+
+#[repr(C)]
+pub struct IObjectCollectionVtbl {
+    pub parent: IObjectArrayVtbl,
+    pub AddObject:
+        unsafe extern "system" fn(This: *mut IObjectCollection, punk: *mut IUnknown) -> HRESULT,
+    pub AddFromArray: unsafe extern "system" fn(
+        This: *mut IObjectCollection,
+        poaSource: *mut IObjectArray,
+    ) -> HRESULT,
+    pub RemoveObjectAt:
+        unsafe extern "system" fn(This: *mut IObjectCollection, uiIndex: u32) -> HRESULT,
+    pub Clear: unsafe extern "system" fn(This: *mut IObjectCollection) -> HRESULT,
+}
+
+#[repr(C)]
+pub struct IObjectCollection {
+    pub lpVtbl: *const IObjectCollectionVtbl,
+}
+
+#[repr(C)]
+pub struct ICustomDestinationListVtbl {
+    pub parent: IUnknownVtbl,
+    pub SetAppID: unsafe extern "system" fn(
+        This: *mut ICustomDestinationList,
+        pszAppID: *const u16,
+    ) -> HRESULT,
+    pub BeginList: unsafe extern "system" fn(
+        This: *mut ICustomDestinationList,
+        pcMinSlots: *mut u32,
+        riid: *const GUID,
+        ppv: *mut *mut c_void,
+    ) -> HRESULT,
+    pub AppendCategory: unsafe extern "system" fn(
+        This: *mut ICustomDestinationList,
+        pszCategory: *const u16,
+        poa: *mut IObjectArray,
+    ) -> HRESULT,
+    pub AppendKnownCategory:
+        unsafe extern "system" fn(This: *mut ICustomDestinationList, category: i32) -> HRESULT,
+    pub AddUserTasks: unsafe extern "system" fn(
+        This: *mut ICustomDestinationList,
+        poa: *mut IObjectArray,
+    ) -> HRESULT,
+    pub CommitList: unsafe extern "system" fn(This: *mut ICustomDestinationList) -> HRESULT,
+    pub GetRemovedDestinations: unsafe extern "system" fn(
+        This: *mut ICustomDestinationList,
+        riid: *const GUID,
+        ppv: *mut *mut c_void,
+    ) -> HRESULT,
+    pub DeleteList: unsafe extern "system" fn(
+        This: *mut ICustomDestinationList,
+        pszAppID: *const u16,
+    ) -> HRESULT,
+    pub AbortList: unsafe extern "system" fn(This: *mut ICustomDestinationList) -> HRESULT,
+}
+
+#[repr(C)]
+pub struct ICustomDestinationList {
+    pub lpVtbl: *const ICustomDestinationListVtbl,
+}
+
+#[repr(C)]
+pub struct IShellLinkWVtbl {
+    pub parent: IUnknownVtbl,
+    pub GetPath: unsafe extern "system" fn(
+        This: *mut IShellLinkW,
+        pszFile: *mut u16,
+        cch: i32,
+        pfd: *mut c_void,
+        fFlags: u32,
+    ) -> HRESULT,
+    pub GetIDList:
+        unsafe extern "system" fn(This: *mut IShellLinkW, ppidl: *mut *mut c_void) -> HRESULT,
+    pub SetIDList:
+        unsafe extern "system" fn(This: *mut IShellLinkW, pidl: *const c_void) -> HRESULT,
+    pub GetDescription:
+        unsafe extern "system" fn(This: *mut IShellLinkW, pszName: *mut u16, cch: i32) -> HRESULT,
+    pub SetDescription:
+        unsafe extern "system" fn(This: *mut IShellLinkW, pszName: *const u16) -> HRESULT,
+    pub GetWorkingDirectory:
+        unsafe extern "system" fn(This: *mut IShellLinkW, pszDir: *mut u16, cch: i32) -> HRESULT,
+    pub SetWorkingDirectory:
+        unsafe extern "system" fn(This: *mut IShellLinkW, pszDir: *const u16) -> HRESULT,
+    pub GetArguments:
+        unsafe extern "system" fn(This: *mut IShellLinkW, pszArgs: *mut u16, cch: i32) -> HRESULT,
+    pub SetArguments:
+        unsafe extern "system" fn(This: *mut IShellLinkW, pszArgs: *const u16) -> HRESULT,
+    pub GetHotkey: unsafe extern "system" fn(This: *mut IShellLinkW, pwHotkey: *mut u16) -> HRESULT,
+    pub SetHotkey: unsafe extern "system" fn(This: *mut IShellLinkW, wHotkey: u16) -> HRESULT,
+    pub GetShowCmd:
+        unsafe extern "system" fn(This: *mut IShellLinkW, piShowCmd: *mut i32) -> HRESULT,
+    pub SetShowCmd: unsafe extern "system" fn(This: *mut IShellLinkW, iShowCmd: i32) -> HRESULT,
+    pub GetIconLocation: unsafe extern "system" fn(
+        This: *mut IShellLinkW,
+        pszIconPath: *mut u16,
+        cch: i32,
+        piIcon: *mut i32,
+    ) -> HRESULT,
+    pub SetIconLocation: unsafe extern "system" fn(
+        This: *mut IShellLinkW,
+        pszIconPath: *const u16,
+        iIcon: i32,
+    ) -> HRESULT,
+    pub SetRelativePath: unsafe extern "system" fn(
+        This: *mut IShellLinkW,
+        pszPathRel: *const u16,
+        dwReserved: u32,
+    ) -> HRESULT,
+    pub Resolve:
+        unsafe extern "system" fn(This: *mut IShellLinkW, hwnd: HWND, fFlags: u32) -> HRESULT,
+    pub SetPath: unsafe extern "system" fn(This: *mut IShellLinkW, pszFile: *const u16) -> HRESULT,
+}
+
+#[repr(C)]
+pub struct IShellLinkW {
+    pub lpVtbl: *const IShellLinkWVtbl,
+}
+
+#[repr(C)]
+#[allow(clippy::upper_case_acronyms)]
+pub struct PROPERTYKEY {
+    pub fmtid: GUID,
+    pub pid: u32,
+}
+
+/// A `PROPVARIANT` holding a string or nothing, the only kinds of values used.
+#[repr(C)]
+pub struct PROPVARIANT_LPWSTR {
+    pub vt: u16,
+    pub wReserved1: u16,
+    pub wReserved2: u16,
+    pub wReserved3: u16,
+    pub pwszVal: *const u16,
+    /// The rest of the union, which is two pointers large.
+    pub padding: usize,
+}
+
+#[repr(C)]
+pub struct IPropertyStoreVtbl {
+    pub parent: IUnknownVtbl,
+    pub GetCount: unsafe extern "system" fn(This: *mut IPropertyStore, cProps: *mut u32) -> HRESULT,
+    pub GetAt: unsafe extern "system" fn(
+        This: *mut IPropertyStore,
+        iProp: u32,
+        pkey: *mut PROPERTYKEY,
+    ) -> HRESULT,
+    pub GetValue: unsafe extern "system" fn(
+        This: *mut IPropertyStore,
+        key: *const PROPERTYKEY,
+        pv: *mut c_void,
+    ) -> HRESULT,
+    pub SetValue: unsafe extern "system" fn(
+        This: *mut IPropertyStore,
+        key: *const PROPERTYKEY,
+        propvar: *const PROPVARIANT_LPWSTR,
+    ) -> HRESULT,
+    pub Commit: unsafe extern "system" fn(This: *mut IPropertyStore) -> HRESULT,
+}
+
+#[repr(C)]
+pub struct IPropertyStore {
+    pub lpVtbl: *const IPropertyStoreVtbl,
+}
+
+pub const CLSID_DestinationList: GUID = GUID {
+    data1: 0x77f10cf0,
+    data2: 0x3db5,
+    data3: 0x4966,
+    data4: [0xb5, 0x20, 0xb7, 0xc5, 0x4f, 0xd3, 0x5e, 0xd6],
+};
+
+pub const CLSID_EnumerableObjectCollection: GUID = GUID {
+    data1: 0x2d3468c1,
+    data2: 0x36a7,
+    data3: 0x43b6,
+    data4: [0xac, 0x24, 0xd3, 0xf0, 0x2f, 0xd9, 0x60, 0x7a],
+};
+
+pub const CLSID_ShellLink: GUID = GUID {
+    data1: 0x00021401,
+    data2: 0x0000,
+    data3: 0x0000,
+    data4: [0xc0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x46],
+};
+
+pub const IID_ICustomDestinationList: GUID = GUID {
+    data1: 0x6332debf,
+    data2: 0x87b5,
+    data3: 0x4670,
+    data4: [0x90, 0xc0, 0x5e, 0x57, 0xb4, 0x08, 0xa4, 0x9e],
+};
+
+pub const IID_IObjectArray: GUID = GUID {
+    data1: 0x92ca9dcd,
+    data2: 0x5622,
+    data3: 0x4bba,
+    data4: [0xa8, 0x05, 0x5e, 0x9f, 0x54, 0x1b, 0xd8, 0xc9],
+};
+
+pub const IID_IObjectCollection: GUID = GUID {
+    data1: 0x5632b1a4,
+    data2: 0xe38a,
+    data3: 0x400a,
+    data4: [0x92, 0x8a, 0xd4, 0xcd, 0x63, 0x23, 0x02, 0x95],
+};
+
+pub const IID_IShellLinkW: GUID = GUID {
+    data1: 0x000214f9,
+    data2: 0x0000,
+    data3: 0x0000,
+    data4: [0xc0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x46],
+};
+
+pub const IID_IPropertyStore: GUID = GUID {
+    data1: 0x886d8eeb,
+    data2: 0x8cf2,
+    data3: 0x4446,
+    data4: [0x8d, 0x02, 0xcd, 0xba, 0x1d, 0xbd, 0xcf, 0x99],
+};
+
+pub const PKEY_Title: PROPERTYKEY = PROPERTYKEY {
+    fmtid: GUID {
+        data1: 0xf29f85e0,
+        data2: 0x4ff9,
+        data3: 0x1068,
+        data4: [0xab, 0x91, 0x08, 0x00, 0x2b, 0x27, 0xb3, 0xd9],
+    },
+    pid: 2,
+};
+
+pub const PKEY_AppUserModel_ID: PROPERTYKEY = PROPERTYKEY {
+    fmtid: GUID {
+        data1: 0x9f4c2855,
+        data2: 0x9f79,
+        data3: 0x4b39,
+        data4: [0xa8, 0xd0, 0xe1, 0xd4, 0x2d, 0xe1, 0xd5, 0xf3],
+    },
+    pid: 5,
+};
+
+pub const VT_EMPTY: u16 = 0;
+pub const VT_LPWSTR: u16 = 31;
+
+pub const KDC_FREQUENT: i32 = 1;
+pub const KDC_RECENT: i32 = 2;
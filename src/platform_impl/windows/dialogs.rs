@@ -0,0 +1,230 @@
+//! File dialogs shown with `IFileDialog` and message boxes, from threads of their own.
+
+use std::ffi::{c_void, OsString};
+use std::os::windows::ffi::OsStringExt;
+use std::path::PathBuf;
+use std::{io, ptr, slice, thread};
+
+use windows_sys::core::{GUID, HRESULT};
+use windows_sys::Win32::Foundation::{HWND, S_OK};
+use windows_sys::Win32::System::Com::{
+    CoCreateInstance, CoInitializeEx, CoTaskMemFree, CoUninitialize, CLSCTX_INPROC_SERVER,
+    COINIT_APARTMENTTHREADED,
+};
+use windows_sys::Win32::UI::Shell::SHCreateItemFromParsingName;
+use windows_sys::Win32::UI::WindowsAndMessaging::{
+    MessageBoxW, IDCANCEL, IDNO, IDOK, IDYES, MB_ICONERROR, MB_ICONINFORMATION, MB_ICONWARNING,
+    MB_OK, MB_OKCANCEL, MB_SETFOREGROUND, MB_YESNO, MB_YESNOCANCEL,
+};
+
+use super::definitions::{
+    CLSID_FileOpenDialog, CLSID_FileSaveDialog, IFileDialog, IFileOpenDialog, IID_IFileOpenDialog,
+    IID_IFileSaveDialog, IID_IShellItem, IShellItem, IShellItemArray, COMDLG_FILTERSPEC,
+    FOS_ALLOWMULTISELECT, FOS_FORCEFILESYSTEM, FOS_OVERWRITEPROMPT, FOS_PICKFOLDERS,
+    SIGDN_FILESYSPATH,
+};
+use super::util::{check, encode_wide, ComPtr};
+use crate::dialogs::{
+    FileDialogKind, FileDialogOptions, FileDialogResult, MessageBoxButton, MessageBoxButtons,
+    MessageBoxConfig, MessageBoxLevel, MessageBoxResult,
+};
+use crate::error::RequestError;
+
+/// `HRESULT_FROM_WIN32(ERROR_CANCELLED)`, returned by `Show` when the user dismisses the dialog.
+const CANCELLED: HRESULT = 0x800704c7u32 as HRESULT;
+
+/// Show the dialog from another thread, calling `done` with the choice of the user.
+///
+/// Modal dialogs run a message loop of their own, so they're shown outside of the event loop
+/// thread to keep it responsive.
+pub(crate) fn show_file_dialog(
+    kind: FileDialogKind,
+    options: FileDialogOptions,
+    done: impl FnOnce(FileDialogResult) + Send + 'static,
+) -> Result<(), RequestError> {
+    thread::Builder::new()
+        .name("winit file dialog".into())
+        .spawn(move || {
+            let result = unsafe {
+                CoInitializeEx(ptr::null(), COINIT_APARTMENTTHREADED as u32);
+                let result = run_file_dialog(kind, &options);
+                CoUninitialize();
+                result
+            };
+            done(match result {
+                Ok(Some(paths)) => FileDialogResult::Picked(paths),
+                Ok(None) => FileDialogResult::Cancelled,
+                Err(error) => FileDialogResult::Failed(error.to_string()),
+            });
+        })
+        .map_err(|error| os_error!(error))?;
+    Ok(())
+}
+
+/// Show the message box from another thread, calling `done` with the button pressed by the user.
+pub(crate) fn show_message_box(
+    config: MessageBoxConfig,
+    done: impl FnOnce(MessageBoxResult) + Send + 'static,
+) -> Result<(), RequestError> {
+    thread::Builder::new()
+        .name("winit message box".into())
+        .spawn(move || {
+            let buttons = match config.buttons {
+                MessageBoxButtons::Ok => MB_OK,
+                MessageBoxButtons::OkCancel => MB_OKCANCEL,
+                MessageBoxButtons::YesNo => MB_YESNO,
+                MessageBoxButtons::YesNoCancel => MB_YESNOCANCEL,
+            };
+            let icon = match config.level {
+                MessageBoxLevel::Info => MB_ICONINFORMATION,
+                MessageBoxLevel::Warning => MB_ICONWARNING,
+                MessageBoxLevel::Error => MB_ICONERROR,
+            };
+            let owner = config.parent.map_or(0, |parent| parent.into_raw() as HWND);
+            let text = encode_wide(&config.text);
+            let title = encode_wide(&config.title);
+            let pressed = unsafe {
+                MessageBoxW(owner, text.as_ptr(), title.as_ptr(), buttons | icon | MB_SETFOREGROUND)
+            };
+            done(match pressed {
+                0 => MessageBoxResult::Failed(io::Error::last_os_error().to_string()),
+                IDOK => MessageBoxResult::Pressed(MessageBoxButton::Ok),
+                IDCANCEL => MessageBoxResult::Pressed(config.buttons.dismissed()),
+                IDYES => MessageBoxResult::Pressed(MessageBoxButton::Yes),
+                IDNO => MessageBoxResult::Pressed(MessageBoxButton::No),
+                _ => MessageBoxResult::Pressed(config.buttons.dismissed()),
+            });
+        })
+        .map_err(|error| os_error!(error))?;
+    Ok(())
+}
+
+unsafe fn create_dialog(clsid: &GUID, iid: &GUID) -> io::Result<ComPtr<IFileDialog>> {
+    unsafe {
+        ComPtr::from_out(|object| {
+            CoCreateInstance(clsid, ptr::null_mut(), CLSCTX_INPROC_SERVER, iid, object)
+        })
+    }
+}
+
+unsafe fn run_file_dialog(
+    kind: FileDialogKind,
+    options: &FileDialogOptions,
+) -> io::Result<Option<Vec<PathBuf>>> {
+    let dialog = unsafe {
+        match kind {
+            FileDialogKind::SaveFile => create_dialog(&CLSID_FileSaveDialog, &IID_IFileSaveDialog)?,
+            _ => create_dialog(&CLSID_FileOpenDialog, &IID_IFileOpenDialog)?,
+        }
+    };
+    let this = dialog.0;
+    let vtbl = unsafe { &*(*this).lpVtbl };
+
+    let mut flags = 0;
+    check(unsafe { (vtbl.GetOptions)(this, &mut flags) })?;
+    flags |= FOS_FORCEFILESYSTEM;
+    match kind {
+        FileDialogKind::PickFile if options.multiple => flags |= FOS_ALLOWMULTISELECT,
+        FileDialogKind::PickFile => (),
+        FileDialogKind::PickFolder => {
+            flags |= FOS_PICKFOLDERS;
+            if options.multiple {
+                flags |= FOS_ALLOWMULTISELECT;
+            }
+        },
+        FileDialogKind::SaveFile => flags |= FOS_OVERWRITEPROMPT,
+    }
+    check(unsafe { (vtbl.SetOptions)(this, flags) })?;
+
+    if let Some(title) = &options.title {
+        check(unsafe { (vtbl.SetTitle)(this, encode_wide(title).as_ptr()) })?;
+    }
+
+    if let (FileDialogKind::SaveFile, Some(file_name)) = (kind, &options.file_name) {
+        check(unsafe { (vtbl.SetFileName)(this, encode_wide(file_name).as_ptr()) })?;
+    }
+
+    if let Some(directory) = &options.directory {
+        // Ignore the directories which don't exist, like the other platforms.
+        let folder = unsafe {
+            ComPtr::<IShellItem>::from_out(|item| {
+                SHCreateItemFromParsingName(
+                    encode_wide(directory).as_ptr(),
+                    ptr::null_mut(),
+                    &IID_IShellItem,
+                    item,
+                )
+            })
+        };
+        if let Ok(folder) = folder {
+            check(unsafe { (vtbl.SetFolder)(this, folder.0) })?;
+        }
+    }
+
+    if kind != FileDialogKind::PickFolder && !options.filters.is_empty() {
+        // The strings must outlive the call.
+        let strings: Vec<_> = options
+            .filters
+            .iter()
+            .map(|filter| {
+                let spec: Vec<_> =
+                    filter.extensions.iter().map(|extension| format!("*.{extension}")).collect();
+                (encode_wide(&filter.name), encode_wide(spec.join(";")))
+            })
+            .collect();
+        let specs: Vec<_> = strings
+            .iter()
+            .map(|(name, spec)| COMDLG_FILTERSPEC {
+                pszName: name.as_ptr(),
+                pszSpec: spec.as_ptr(),
+            })
+            .collect();
+        check(unsafe { (vtbl.SetFileTypes)(this, specs.len() as u32, specs.as_ptr()) })?;
+    }
+
+    let owner = options.parent.map_or(0, |parent| parent.into_raw() as HWND);
+    match unsafe { (vtbl.parent.Show)(this, owner) } {
+        S_OK => (),
+        CANCELLED => return Ok(None),
+        error => return Err(io::Error::from_raw_os_error(error)),
+    }
+
+    let paths = if kind == FileDialogKind::SaveFile {
+        let item =
+            unsafe { ComPtr::<IShellItem>::from_out(|item| (vtbl.GetResult)(this, item.cast()))? };
+        vec![unsafe { item_path(&item)? }]
+    } else {
+        let this = this as *mut IFileOpenDialog;
+        let vtbl = unsafe { &*(*this).lpVtbl };
+        let items = unsafe {
+            ComPtr::<IShellItemArray>::from_out(|items| (vtbl.GetResults)(this, items.cast()))?
+        };
+        let items_vtbl = unsafe { &*(*items.0).lpVtbl };
+
+        let mut count = 0;
+        check(unsafe { (items_vtbl.GetCount)(items.0, &mut count) })?;
+        (0..count)
+            .map(|index| unsafe {
+                let item = ComPtr::<IShellItem>::from_out(|item| {
+                    (items_vtbl.GetItemAt)(items.0, index, item.cast())
+                })?;
+                item_path(&item)
+            })
+            .collect::<io::Result<_>>()?
+    };
+
+    Ok(Some(paths))
+}
+
+unsafe fn item_path(item: &ComPtr<IShellItem>) -> io::Result<PathBuf> {
+    let vtbl = unsafe { &*(*item.0).lpVtbl };
+    let mut name = ptr::null_mut();
+    check(unsafe { (vtbl.GetDisplayName)(item.0, SIGDN_FILESYSPATH, &mut name) })?;
+
+    let path = unsafe {
+        let len = (0..).take_while(|&i| *name.add(i) != 0).count();
+        OsString::from_wide(slice::from_raw_parts(name, len))
+    };
+    unsafe { CoTaskMemFree(name as *const c_void) };
+    Ok(PathBuf::from(path))
+}
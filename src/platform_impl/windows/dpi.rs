@@ -8,23 +8,38 @@ use windows_sys::Win32::Graphics::Gdi::{
 };
 use windows_sys::Win32::UI::HiDpi::{
     DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE, DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2,
-    MDT_EFFECTIVE_DPI, PROCESS_PER_MONITOR_DPI_AWARE,
+    DPI_AWARENESS_CONTEXT_SYSTEM_AWARE, DPI_AWARENESS_CONTEXT_UNAWARE,
+    DPI_AWARENESS_PER_MONITOR_AWARE, DPI_AWARENESS_SYSTEM_AWARE, MDT_EFFECTIVE_DPI,
+    PROCESS_DPI_UNAWARE, PROCESS_PER_MONITOR_DPI_AWARE, PROCESS_SYSTEM_DPI_AWARE,
 };
 use windows_sys::Win32::UI::WindowsAndMessaging::IsProcessDPIAware;
 
+use crate::platform::windows::DpiAwareness;
 use crate::platform_impl::platform::util::{
-    ENABLE_NON_CLIENT_DPI_SCALING, GET_DPI_FOR_MONITOR, GET_DPI_FOR_WINDOW, SET_PROCESS_DPI_AWARE,
+    ARE_DPI_AWARENESS_CONTEXTS_EQUAL, ENABLE_NON_CLIENT_DPI_SCALING,
+    GET_AWARENESS_FROM_DPI_AWARENESS_CONTEXT, GET_DPI_FOR_MONITOR, GET_DPI_FOR_WINDOW,
+    GET_PROCESS_DPI_AWARENESS, GET_THREAD_DPI_AWARENESS_CONTEXT, SET_PROCESS_DPI_AWARE,
     SET_PROCESS_DPI_AWARENESS, SET_PROCESS_DPI_AWARENESS_CONTEXT,
 };
 
-pub fn become_dpi_aware() {
+pub fn become_dpi_aware(awareness: DpiAwareness) {
     static ENABLE_DPI_AWARENESS: Once = Once::new();
     ENABLE_DPI_AWARENESS.call_once(|| {
+        let (context, process_awareness) = match awareness {
+            DpiAwareness::PerMonitorV2 => {
+                (DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2, PROCESS_PER_MONITOR_DPI_AWARE)
+            },
+            DpiAwareness::PerMonitor => {
+                (DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE, PROCESS_PER_MONITOR_DPI_AWARE)
+            },
+            DpiAwareness::System => (DPI_AWARENESS_CONTEXT_SYSTEM_AWARE, PROCESS_SYSTEM_DPI_AWARE),
+            DpiAwareness::Unaware => (DPI_AWARENESS_CONTEXT_UNAWARE, PROCESS_DPI_UNAWARE),
+        };
         unsafe {
             if let Some(SetProcessDpiAwarenessContext) = *SET_PROCESS_DPI_AWARENESS_CONTEXT {
                 // We are on Windows 10 Anniversary Update (1607) or later.
-                if SetProcessDpiAwarenessContext(DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2)
-                    == false.into()
+                if SetProcessDpiAwarenessContext(context) == false.into()
+                    && awareness == DpiAwareness::PerMonitorV2
                 {
                     // V2 only works with Windows 10 Creators Update (1703). Try using the older
                     // V1 if we can't set V2.
@@ -32,15 +47,63 @@ pub fn become_dpi_aware() {
                 }
             } else if let Some(SetProcessDpiAwareness) = *SET_PROCESS_DPI_AWARENESS {
                 // We are on Windows 8.1 or later.
-                SetProcessDpiAwareness(PROCESS_PER_MONITOR_DPI_AWARE);
+                SetProcessDpiAwareness(process_awareness);
             } else if let Some(SetProcessDPIAware) = *SET_PROCESS_DPI_AWARE {
-                // We are on Vista or later.
-                SetProcessDPIAware();
+                // We are on Vista or later, where processes are unaware by default.
+                if awareness != DpiAwareness::Unaware {
+                    SetProcessDPIAware();
+                }
             }
         }
     });
 }
 
+pub fn dpi_awareness() -> DpiAwareness {
+    unsafe {
+        if let (
+            Some(GetThreadDpiAwarenessContext),
+            Some(GetAwarenessFromDpiAwarenessContext),
+            Some(AreDpiAwarenessContextsEqual),
+        ) = (
+            *GET_THREAD_DPI_AWARENESS_CONTEXT,
+            *GET_AWARENESS_FROM_DPI_AWARENESS_CONTEXT,
+            *ARE_DPI_AWARENESS_CONTEXTS_EQUAL,
+        ) {
+            // We are on Windows 10 Anniversary Update (1607) or later.
+            let context = GetThreadDpiAwarenessContext();
+            return match GetAwarenessFromDpiAwarenessContext(context) {
+                DPI_AWARENESS_PER_MONITOR_AWARE
+                    if AreDpiAwarenessContextsEqual(
+                        context,
+                        DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2,
+                    ) != false.into() =>
+                {
+                    DpiAwareness::PerMonitorV2
+                },
+                DPI_AWARENESS_PER_MONITOR_AWARE => DpiAwareness::PerMonitor,
+                DPI_AWARENESS_SYSTEM_AWARE => DpiAwareness::System,
+                _ => DpiAwareness::Unaware,
+            };
+        }
+        if let Some(GetProcessDpiAwareness) = *GET_PROCESS_DPI_AWARENESS {
+            // We are on Windows 8.1 or later.
+            let mut awareness = PROCESS_DPI_UNAWARE;
+            if GetProcessDpiAwareness(0, &mut awareness) == S_OK {
+                return match awareness {
+                    PROCESS_PER_MONITOR_DPI_AWARE => DpiAwareness::PerMonitor,
+                    PROCESS_SYSTEM_DPI_AWARE => DpiAwareness::System,
+                    _ => DpiAwareness::Unaware,
+                };
+            }
+        }
+        if IsProcessDPIAware() != false.into() {
+            DpiAwareness::System
+        } else {
+            DpiAwareness::Unaware
+        }
+    }
+}
+
 pub fn enable_non_client_dpi_scaling(hwnd: HWND) {
     unsafe {
         if let Some(EnableNonClientDpiScaling) = *ENABLE_NON_CLIENT_DPI_SCALING {
@@ -0,0 +1,135 @@
+//! Hands the drags between the windows over to OLE drag and drop when they leave the application.
+
+use std::ffi::c_void;
+use std::os::windows::ffi::OsStrExt;
+use std::path::PathBuf;
+use std::{mem, ptr, slice};
+
+use windows_sys::Win32::Foundation::{GlobalFree, DRAGDROP_S_DROP, HWND, POINT, S_OK};
+use windows_sys::Win32::System::Com::{
+    DVASPECT_CONTENT, FORMATETC, STGMEDIUM, STGMEDIUM_0, TYMED_HGLOBAL,
+};
+use windows_sys::Win32::System::Memory::{GlobalAlloc, GlobalLock, GlobalUnlock, GMEM_MOVEABLE};
+use windows_sys::Win32::System::Ole::{
+    CF_HDROP, CF_UNICODETEXT, DROPEFFECT, DROPEFFECT_COPY, DROPEFFECT_LINK, DROPEFFECT_MOVE,
+    DROPEFFECT_NONE,
+};
+use windows_sys::Win32::UI::Shell::{SHDoDragDrop, DROPFILES};
+
+use super::definitions::{IDataObjectVtbl, IID_IDataObject};
+use super::util::{encode_wide, SH_CREATE_DATA_OBJECT};
+use crate::drag::{DragData, DragEffect};
+
+/// Drag the files or text of `data` from `window` until they're dropped, returning what another
+/// application did with them, or `None` if none accepted them.
+///
+/// OLE runs a modal loop while the data is dragged, so this only returns once the drag ended.
+pub(crate) unsafe fn do_drag_drop(window: HWND, data: &DragData) -> Option<DragEffect> {
+    let (format, bytes) = match data {
+        DragData::Files(paths) => (CF_HDROP, file_list(paths)),
+        DragData::Text(text) => {
+            (CF_UNICODETEXT, encode_wide(text).iter().flat_map(|unit| unit.to_ne_bytes()).collect())
+        },
+        DragData::Custom(_) => return None,
+    };
+
+    let sh_create_data_object = (*SH_CREATE_DATA_OBJECT)?;
+    let mut object: *mut c_void = ptr::null_mut();
+    let result = unsafe {
+        sh_create_data_object(
+            ptr::null(),
+            0,
+            ptr::null(),
+            ptr::null_mut(),
+            &IID_IDataObject,
+            &mut object,
+        )
+    };
+    if result != S_OK {
+        return None;
+    }
+    let vtbl = unsafe { &**(object as *const *const IDataObjectVtbl) };
+
+    let effect = if unsafe { set_data(object, vtbl, format, &bytes) } {
+        let allowed = DROPEFFECT_COPY | DROPEFFECT_MOVE | DROPEFFECT_LINK;
+        let mut effect = DROPEFFECT_NONE;
+        let result = unsafe { SHDoDragDrop(window, object, ptr::null_mut(), allowed, &mut effect) };
+        if result == DRAGDROP_S_DROP {
+            drag_effect(effect)
+        } else {
+            None
+        }
+    } else {
+        None
+    };
+
+    unsafe { (vtbl.parent.Release)(object.cast()) };
+    effect
+}
+
+/// The effect the target chose, the most significant one if it reported several.
+fn drag_effect(effect: DROPEFFECT) -> Option<DragEffect> {
+    if effect & DROPEFFECT_MOVE != 0 {
+        Some(DragEffect::Move)
+    } else if effect & DROPEFFECT_COPY != 0 {
+        Some(DragEffect::Copy)
+    } else if effect & DROPEFFECT_LINK != 0 {
+        Some(DragEffect::Link)
+    } else {
+        None
+    }
+}
+
+/// Store a copy of `bytes` in the data object, which takes ownership of it.
+unsafe fn set_data(object: *mut c_void, vtbl: &IDataObjectVtbl, format: u16, bytes: &[u8]) -> bool {
+    let global = unsafe { GlobalAlloc(GMEM_MOVEABLE, bytes.len()) };
+    let memory = unsafe { GlobalLock(global) };
+    if memory.is_null() {
+        unsafe { GlobalFree(global) };
+        return false;
+    }
+    unsafe {
+        ptr::copy_nonoverlapping(bytes.as_ptr(), memory.cast(), bytes.len());
+        GlobalUnlock(global);
+    }
+
+    let format = FORMATETC {
+        cfFormat: format,
+        ptd: ptr::null_mut(),
+        dwAspect: DVASPECT_CONTENT,
+        lindex: -1,
+        tymed: TYMED_HGLOBAL as u32,
+    };
+    let medium = STGMEDIUM {
+        tymed: TYMED_HGLOBAL as u32,
+        u: STGMEDIUM_0 { hGlobal: global },
+        pUnkForRelease: ptr::null_mut(),
+    };
+    let result = unsafe { (vtbl.SetData)(object.cast(), &format, &medium, true.into()) };
+    if result != S_OK {
+        unsafe { GlobalFree(global) };
+        return false;
+    }
+    true
+}
+
+/// The `CF_HDROP` representation of the paths: a `DROPFILES` header followed by the
+/// nul-terminated paths, ending with an empty one.
+pub(crate) fn file_list(paths: &[PathBuf]) -> Vec<u8> {
+    let header = DROPFILES {
+        pFiles: mem::size_of::<DROPFILES>() as u32,
+        pt: POINT { x: 0, y: 0 },
+        fNC: false.into(),
+        fWide: true.into(),
+    };
+    let mut bytes = unsafe {
+        slice::from_raw_parts(&header as *const DROPFILES as *const u8, mem::size_of::<DROPFILES>())
+    }
+    .to_vec();
+    for path in paths {
+        let units = path.as_os_str().encode_wide().chain([0]);
+        bytes.extend(units.flat_map(|unit| unit.to_ne_bytes()));
+    }
+    bytes.extend([0, 0]);
+    bytes
+}
@@ -1,29 +1,68 @@
 use std::ffi::{c_void, OsString};
 use std::os::windows::ffi::OsStringExt;
-use std::path::PathBuf;
-use std::ptr;
+use std::path::{Component, PathBuf};
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::{env, fs, mem, process, ptr, slice};
 
+use dpi::PhysicalPosition;
 use tracing::debug;
 use windows_sys::core::{IUnknown, GUID, HRESULT};
-use windows_sys::Win32::Foundation::{DV_E_FORMATETC, HWND, POINTL, S_OK};
-use windows_sys::Win32::System::Com::{IDataObject, DVASPECT_CONTENT, FORMATETC, TYMED_HGLOBAL};
-use windows_sys::Win32::System::Ole::{CF_HDROP, DROPEFFECT_COPY, DROPEFFECT_NONE};
-use windows_sys::Win32::UI::Shell::{DragFinish, DragQueryFileW, HDROP};
+use windows_sys::Win32::Foundation::{DV_E_FORMATETC, HGLOBAL, HWND, POINT, POINTL, S_OK};
+use windows_sys::Win32::Graphics::Gdi::ScreenToClient;
+use windows_sys::Win32::Storage::FileSystem::FILE_ATTRIBUTE_DIRECTORY;
+use windows_sys::Win32::System::Com::{
+    IDataObject, DVASPECT_CONTENT, FORMATETC, STGMEDIUM, TYMED_HGLOBAL, TYMED_ISTREAM,
+};
+use windows_sys::Win32::System::Memory::{GlobalLock, GlobalSize, GlobalUnlock};
+use windows_sys::Win32::System::Ole::{
+    ReleaseStgMedium, CF_HDROP, DROPEFFECT_COPY, DROPEFFECT_LINK, DROPEFFECT_MOVE, DROPEFFECT_NONE,
+};
+use windows_sys::Win32::System::SystemServices::{MK_CONTROL, MK_SHIFT};
+use windows_sys::Win32::UI::Input::KeyboardAndMouse::{GetAsyncKeyState, VK_LWIN, VK_RWIN};
+use windows_sys::Win32::UI::Shell::{
+    DragFinish, DragQueryFileW, FD_ATTRIBUTES, FD_FILESIZE, FILEDESCRIPTORW, HDROP,
+};
 
+use crate::clipboard::{ClipboardData, ClipboardFormat};
+use crate::drag::{DragEffect, DropData, DropEffectWriter};
 use crate::event::Event;
+use crate::keyboard::ModifiersState;
+use crate::platform::windows::DropFormatHandler;
+use crate::platform_impl::platform::clipboard;
 use crate::platform_impl::platform::definitions::{
-    IDataObjectVtbl, IDropTarget, IDropTargetVtbl, IUnknownVtbl,
+    IDataObjectVtbl, IDropTarget, IDropTargetVtbl, ISequentialStream, IUnknownVtbl,
 };
+use crate::platform_impl::platform::window_state::WindowState;
 use crate::window::WindowId;
 
+/// The formats read from the data objects of the drops, in this order.
+const DROP_FORMATS: [ClipboardFormat; 5] = [
+    ClipboardFormat::Files,
+    ClipboardFormat::Uris,
+    ClipboardFormat::Text,
+    ClipboardFormat::Html,
+    ClipboardFormat::Image,
+];
+
+/// The `grfKeyState` flag of the Alt key, which is only defined for drag and drop.
+const MK_ALT: u32 = 0x20;
+
+/// The formats of the files which only exist in the source, like the attachments of a mail.
+const FILE_DESCRIPTOR_FORMAT: &str = "FileGroupDescriptorW";
+const FILE_CONTENTS_FORMAT: &str = "FileContents";
+
 #[repr(C)]
 pub struct FileDropHandlerData {
     pub interface: IDropTarget,
     refcount: AtomicUsize,
     window: HWND,
+    window_state: Arc<Mutex<WindowState>>,
     send_event: Box<dyn Fn(Event)>,
-    cursor_effect: u32,
+    /// The formats of the hovered data object which can be read once it's dropped.
+    formats: Vec<ClipboardFormat>,
+    /// The effect of dropping the data where it's hovered, chosen by the application.
+    effect: Option<DragEffect>,
     hovered_is_valid: bool, /* If the currently hovered item is not valid there must not be any
                              * `HoveredFileCancelled` emitted */
 }
@@ -34,13 +73,19 @@ pub struct FileDropHandler {
 
 #[allow(non_snake_case)]
 impl FileDropHandler {
-    pub(crate) fn new(window: HWND, send_event: Box<dyn Fn(Event)>) -> FileDropHandler {
+    pub(crate) fn new(
+        window: HWND,
+        window_state: Arc<Mutex<WindowState>>,
+        send_event: Box<dyn Fn(Event)>,
+    ) -> FileDropHandler {
         let data = Box::new(FileDropHandlerData {
             interface: IDropTarget { lpVtbl: &DROP_TARGET_VTBL as *const IDropTargetVtbl },
             refcount: AtomicUsize::new(1),
             window,
+            window_state,
             send_event,
-            cursor_effect: DROPEFFECT_NONE,
+            formats: Vec::new(),
+            effect: None,
             hovered_is_valid: false,
         });
         FileDropHandler { data: Box::into_raw(data) }
@@ -76,8 +121,8 @@ impl FileDropHandler {
     pub unsafe extern "system" fn DragEnter(
         this: *mut IDropTarget,
         pDataObj: *const IDataObject,
-        _grfKeyState: u32,
-        _pt: *const POINTL,
+        grfKeyState: u32,
+        pt: *const POINTL,
         pdwEffect: *mut u32,
     ) -> HRESULT {
         use crate::event::WindowEvent::HoveredFile;
@@ -91,10 +136,10 @@ impl FileDropHandler {
             })
         };
         drop_handler.hovered_is_valid = hdrop.is_some();
-        drop_handler.cursor_effect =
-            if drop_handler.hovered_is_valid { DROPEFFECT_COPY } else { DROPEFFECT_NONE };
+        let data_object = unsafe { DataObject::new(pDataObj) };
+        drop_handler.formats = data_object.drop_formats(drop_handler.format_handler().as_ref());
         unsafe {
-            *pdwEffect = drop_handler.cursor_effect;
+            *pdwEffect = drop_handler.hover(grfKeyState, *pt, *pdwEffect);
         }
 
         S_OK
@@ -102,26 +147,27 @@ impl FileDropHandler {
 
     pub unsafe extern "system" fn DragOver(
         this: *mut IDropTarget,
-        _grfKeyState: u32,
-        _pt: *const POINTL,
+        grfKeyState: u32,
+        pt: *const POINTL,
         pdwEffect: *mut u32,
     ) -> HRESULT {
         let drop_handler = unsafe { Self::from_interface(this) };
         unsafe {
-            *pdwEffect = drop_handler.cursor_effect;
+            *pdwEffect = drop_handler.hover(grfKeyState, *pt, *pdwEffect);
         }
 
         S_OK
     }
 
     pub unsafe extern "system" fn DragLeave(this: *mut IDropTarget) -> HRESULT {
-        use crate::event::WindowEvent::HoveredFileCancelled;
+        use crate::event::WindowEvent::{DataHoverCancelled, HoveredFileCancelled};
         let drop_handler = unsafe { Self::from_interface(this) };
+        let window_id = WindowId::from_raw(drop_handler.window as usize);
         if drop_handler.hovered_is_valid {
-            drop_handler.send_event(Event::WindowEvent {
-                window_id: WindowId::from_raw(drop_handler.window as usize),
-                event: HoveredFileCancelled,
-            });
+            drop_handler.send_event(Event::WindowEvent { window_id, event: HoveredFileCancelled });
+        }
+        if !mem::take(&mut drop_handler.formats).is_empty() {
+            drop_handler.send_event(Event::WindowEvent { window_id, event: DataHoverCancelled });
         }
 
         S_OK
@@ -131,23 +177,40 @@ impl FileDropHandler {
         this: *mut IDropTarget,
         pDataObj: *const IDataObject,
         _grfKeyState: u32,
-        _pt: *const POINTL,
-        _pdwEffect: *mut u32,
+        pt: *const POINTL,
+        pdwEffect: *mut u32,
     ) -> HRESULT {
-        use crate::event::WindowEvent::DroppedFile;
+        use crate::event::WindowEvent::{DataDropped, DroppedFile};
         let drop_handler = unsafe { Self::from_interface(this) };
+        let window_id = WindowId::from_raw(drop_handler.window as usize);
+        drop_handler.formats.clear();
+        let Some(effect) = drop_handler.effect.take() else {
+            // The application rejected the drop where it was released.
+            unsafe { *pdwEffect = DROPEFFECT_NONE };
+            return S_OK;
+        };
+
         let hdrop = unsafe {
             Self::iterate_filenames(pDataObj, |filename| {
-                drop_handler.send_event(Event::WindowEvent {
-                    window_id: WindowId::from_raw(drop_handler.window as usize),
-                    event: DroppedFile(filename),
-                });
+                drop_handler
+                    .send_event(Event::WindowEvent { window_id, event: DroppedFile(filename) });
             })
         };
         if let Some(hdrop) = hdrop {
             unsafe { DragFinish(hdrop) };
         }
 
+        let position = unsafe { drop_handler.client_position(*pt) };
+        let data =
+            unsafe { DataObject::new(pDataObj) }.into_drop_data(drop_handler.format_handler());
+        drop_handler.send_event(Event::WindowEvent {
+            window_id,
+            event: DataDropped { position, data: data.clone(), effect },
+        });
+        // The source only provides the data until the drop is done.
+        data.expire();
+        unsafe { *pdwEffect = drop_effect(effect) };
+
         S_OK
     }
 
@@ -207,10 +270,296 @@ impl FileDropHandler {
     }
 }
 
+/// A reference to the data object of a drop.
+struct DataObject(*const IDataObject);
+
+// SAFETY: `DropData` only reads the data object on the thread of the event loop, and releases it
+// there when the drop is done.
+unsafe impl Send for DataObject {}
+
+impl DataObject {
+    unsafe fn new(data_object: *const IDataObject) -> Self {
+        unsafe { (Self::vtbl(data_object).parent.AddRef)(data_object as *mut IUnknown) };
+        Self(data_object)
+    }
+
+    unsafe fn vtbl<'a>(data_object: *const IDataObject) -> &'a IDataObjectVtbl {
+        unsafe { &*(*data_object).cast::<IDataObjectVtbl>() }
+    }
+
+    fn format_etc(format: u32) -> FORMATETC {
+        FORMATETC {
+            cfFormat: format as u16,
+            ptd: ptr::null_mut(),
+            dwAspect: DVASPECT_CONTENT,
+            lindex: -1,
+            tymed: TYMED_HGLOBAL as u32,
+        }
+    }
+
+    /// The formats of the system holding the `format` which the data object offers.
+    fn formats(&self, format: ClipboardFormat) -> Vec<u32> {
+        let query_get_data = unsafe { Self::vtbl(self.0).QueryGetData };
+        clipboard::system_formats(format)
+            .into_iter()
+            .filter(|&format| {
+                let result = unsafe { query_get_data(self.0 as *mut _, &Self::format_etc(format)) };
+                result == S_OK
+            })
+            .collect()
+    }
+
+    /// The formats the data can be read in, including the ones added by the `handler`.
+    fn drop_formats(&self, handler: Option<&DropFormatHandler>) -> Vec<ClipboardFormat> {
+        let mut formats: Vec<_> =
+            DROP_FORMATS.into_iter().filter(|&format| !self.formats(format).is_empty()).collect();
+        let Some(handler) = handler else { return formats };
+
+        if handler.virtual_files
+            && !formats.contains(&ClipboardFormat::Files)
+            && self.has_virtual_files()
+        {
+            formats.insert(0, ClipboardFormat::Files);
+        }
+        formats.extend(
+            handler
+                .formats
+                .iter()
+                .map(|&name| ClipboardFormat::Custom(name))
+                .filter(|&format| !self.formats(format).is_empty()),
+        );
+        formats
+    }
+
+    /// Read the data in the `format` of the system.
+    fn get(&self, format: ClipboardFormat, system_format: u32) -> Option<ClipboardData> {
+        let get_data = unsafe { Self::vtbl(self.0).GetData };
+        let mut medium: STGMEDIUM = unsafe { mem::zeroed() };
+        if unsafe { get_data(self.0 as *mut _, &Self::format_etc(system_format), &mut medium) } < 0
+        {
+            return None;
+        }
+
+        let global = unsafe { medium.u.hGlobal };
+        let data = if format == ClipboardFormat::Files {
+            Some(ClipboardData::Files(clipboard::drop_files(global as _)))
+        } else {
+            unsafe { global_bytes(global) }
+                .and_then(|bytes| clipboard::decode(format, system_format, bytes))
+        };
+        unsafe { ReleaseStgMedium(&mut medium) };
+        data
+    }
+
+    /// Read the bytes of the item at the `index` in the `format` of the system, held in memory or
+    /// in a stream.
+    fn read(&self, system_format: u32, index: i32) -> Option<Vec<u8>> {
+        let get_data = unsafe { Self::vtbl(self.0).GetData };
+        let format_etc = FORMATETC {
+            lindex: index,
+            tymed: (TYMED_HGLOBAL | TYMED_ISTREAM) as u32,
+            ..Self::format_etc(system_format)
+        };
+        let mut medium: STGMEDIUM = unsafe { mem::zeroed() };
+        if unsafe { get_data(self.0 as *mut _, &format_etc, &mut medium) } < 0 {
+            return None;
+        }
+
+        let bytes = if medium.tymed == TYMED_ISTREAM as u32 {
+            unsafe { read_stream(medium.u.pstm as *mut ISequentialStream) }
+        } else if medium.tymed == TYMED_HGLOBAL as u32 {
+            unsafe { global_bytes(medium.u.hGlobal) }
+        } else {
+            None
+        };
+        unsafe { ReleaseStgMedium(&mut medium) };
+        bytes
+    }
+
+    fn has_virtual_files(&self) -> bool {
+        let query_get_data = unsafe { Self::vtbl(self.0).QueryGetData };
+        let format = clipboard::registered_format(FILE_DESCRIPTOR_FORMAT);
+        unsafe { query_get_data(self.0 as *mut _, &Self::format_etc(format)) == S_OK }
+    }
+
+    /// Save the files which only exist in the source in a new temporary directory, returning the
+    /// paths of the top-level ones.
+    fn save_virtual_files(&self) -> Option<Vec<PathBuf>> {
+        static DROP_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+        let descriptor = self.read(clipboard::registered_format(FILE_DESCRIPTOR_FORMAT), -1)?;
+        let contents_format = clipboard::registered_format(FILE_CONTENTS_FORMAT);
+        // A `FILEGROUPDESCRIPTORW`: the count of the files, followed by their descriptors.
+        let count = u32::from_ne_bytes(descriptor.get(..4)?.try_into().ok()?) as usize;
+        let descriptor_size = mem::size_of::<FILEDESCRIPTORW>();
+
+        let drop_count = DROP_COUNT.fetch_add(1, Ordering::Relaxed);
+        let directory = env::temp_dir().join(format!("winit-drop-{}-{drop_count}", process::id()));
+        let mut paths = Vec::new();
+        for index in 0..count {
+            let start = 4 + index * descriptor_size;
+            let bytes = descriptor.get(start..start + descriptor_size)?;
+            let file: FILEDESCRIPTORW = unsafe { ptr::read_unaligned(bytes.as_ptr().cast()) };
+
+            let file_name = file.cFileName;
+            let len = file_name.iter().position(|&unit| unit == 0).unwrap_or(file_name.len());
+            let name = PathBuf::from(OsString::from_wide(&file_name[..len]));
+            // The names are relative, the files of folders being named after them.
+            if !name.components().all(|component| matches!(component, Component::Normal(_))) {
+                continue;
+            }
+
+            let path = directory.join(&name);
+            let flags = file.dwFlags;
+            if flags & FD_ATTRIBUTES as u32 != 0
+                && file.dwFileAttributes & FILE_ATTRIBUTE_DIRECTORY != 0
+            {
+                fs::create_dir_all(&path).ok()?;
+            } else {
+                let mut contents = self.read(contents_format, index as i32)?;
+                if flags & FD_FILESIZE as u32 != 0 {
+                    let size = ((file.nFileSizeHigh as u64) << 32) | file.nFileSizeLow as u64;
+                    contents.truncate(size as usize);
+                }
+                fs::create_dir_all(path.parent()?).ok()?;
+                fs::write(&path, contents).ok()?;
+            }
+            if name.components().count() == 1 {
+                paths.push(path);
+            }
+        }
+        Some(paths)
+    }
+
+    fn into_drop_data(self, handler: Option<DropFormatHandler>) -> DropData {
+        let formats = self.drop_formats(handler.as_ref());
+        let virtual_files = handler.is_some_and(|handler| handler.virtual_files);
+        // The files are only saved once, however many times they're read.
+        let mut saved_files = None;
+        DropData::new(formats, move |format| {
+            if format == ClipboardFormat::Files && virtual_files && self.formats(format).is_empty()
+            {
+                if saved_files.is_none() {
+                    saved_files = self.save_virtual_files();
+                }
+                return saved_files.clone().map(ClipboardData::Files);
+            }
+            self.formats(format)
+                .into_iter()
+                .find_map(|system_format| self.get(format, system_format))
+        })
+    }
+}
+
+impl Drop for DataObject {
+    fn drop(&mut self) {
+        unsafe { (Self::vtbl(self.0).parent.Release)(self.0 as *mut IUnknown) };
+    }
+}
+
+/// The bytes of the memory, which may be larger than its content.
+unsafe fn global_bytes(global: HGLOBAL) -> Option<Vec<u8>> {
+    let memory = unsafe { GlobalLock(global) };
+    if memory.is_null() {
+        return None;
+    }
+    let len = unsafe { GlobalSize(global) };
+    let bytes = unsafe { slice::from_raw_parts(memory.cast::<u8>(), len) }.to_vec();
+    unsafe { GlobalUnlock(global) };
+    Some(bytes)
+}
+
+/// Read the stream until its end.
+unsafe fn read_stream(stream: *mut ISequentialStream) -> Option<Vec<u8>> {
+    let read = unsafe { (*(*stream).lpVtbl).Read };
+    let mut bytes = Vec::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+    loop {
+        let mut count = 0;
+        let result =
+            unsafe { read(stream, buffer.as_mut_ptr().cast(), buffer.len() as u32, &mut count) };
+        if result < 0 {
+            return None;
+        }
+        bytes.extend_from_slice(&buffer[..count as usize]);
+        // `S_FALSE` at the end of the stream.
+        if result != S_OK || count == 0 {
+            return Some(bytes);
+        }
+    }
+}
+
 impl FileDropHandlerData {
     fn send_event(&self, event: Event) {
         (self.send_event)(event);
     }
+
+    /// The additional formats accepted by the window.
+    fn format_handler(&self) -> Option<DropFormatHandler> {
+        self.window_state.lock().unwrap().drop_format_handler.clone()
+    }
+
+    /// The position of the point of the screen in the window.
+    unsafe fn client_position(&self, point: POINTL) -> PhysicalPosition<f64> {
+        let mut point = POINT { x: point.x, y: point.y };
+        unsafe { ScreenToClient(self.window, &mut point) };
+        PhysicalPosition::new(point.x as f64, point.y as f64)
+    }
+
+    /// Ask the application for the effect of dropping the hovered data at the point of the
+    /// screen, returning it if the source allows it.
+    unsafe fn hover(&mut self, key_state: u32, point: POINTL, allowed: u32) -> u32 {
+        use crate::event::WindowEvent::DataHovered;
+        if self.formats.is_empty() {
+            self.effect = None;
+            return DROPEFFECT_NONE;
+        }
+
+        let window_id = WindowId::from_raw(self.window as usize);
+        let position = unsafe { self.client_position(point) };
+        let formats = self.formats.clone();
+        let modifiers = modifiers_of_key_state(key_state);
+        let default = default_effect(key_state, allowed);
+        let effect = DropEffectWriter::negotiate(default, |effect| {
+            let event = DataHovered { position, formats, modifiers, effect };
+            self.send_event(Event::WindowEvent { window_id, event });
+        });
+        self.effect = effect.filter(|&effect| allowed & drop_effect(effect) != 0);
+        self.effect.map_or(DROPEFFECT_NONE, drop_effect)
+    }
+}
+
+/// The effect the modifiers ask for by convention, among the `allowed` ones.
+fn default_effect(key_state: u32, allowed: u32) -> Option<DragEffect> {
+    let requested = match (key_state & MK_CONTROL != 0, key_state & MK_SHIFT != 0) {
+        (true, true) => Some(DragEffect::Link),
+        (true, false) => Some(DragEffect::Copy),
+        (false, true) => Some(DragEffect::Move),
+        (false, false) if key_state & MK_ALT != 0 => Some(DragEffect::Link),
+        (false, false) => None,
+    };
+    let is_allowed = |effect: &DragEffect| allowed & drop_effect(*effect) != 0;
+    requested.filter(is_allowed).or_else(|| {
+        [DragEffect::Copy, DragEffect::Move, DragEffect::Link].into_iter().find(is_allowed)
+    })
+}
+
+fn drop_effect(effect: DragEffect) -> u32 {
+    match effect {
+        DragEffect::Copy => DROPEFFECT_COPY,
+        DragEffect::Move => DROPEFFECT_MOVE,
+        DragEffect::Link => DROPEFFECT_LINK,
+    }
+}
+
+fn modifiers_of_key_state(key_state: u32) -> ModifiersState {
+    let mut modifiers = ModifiersState::empty();
+    modifiers.set(ModifiersState::SHIFT, key_state & MK_SHIFT != 0);
+    modifiers.set(ModifiersState::CONTROL, key_state & MK_CONTROL != 0);
+    modifiers.set(ModifiersState::ALT, key_state & MK_ALT != 0);
+    let key_pressed = |key| unsafe { GetAsyncKeyState(key as i32) } < 0;
+    modifiers.set(ModifiersState::SUPER, key_pressed(VK_LWIN) || key_pressed(VK_RWIN));
+    modifiers
 }
 
 impl Drop for FileDropHandler {
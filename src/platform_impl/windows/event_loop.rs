@@ -2,9 +2,11 @@
 
 mod runner;
 
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 use std::ffi::c_void;
+use std::num::NonZeroU32;
 use std::os::windows::io::{AsRawHandle as _, FromRawHandle as _, OwnedHandle, RawHandle};
+use std::path::PathBuf;
 use std::rc::Rc;
 use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::{Arc, Mutex, MutexGuard};
@@ -14,84 +16,133 @@ use std::{mem, panic, ptr};
 use runner::EventLoopRunner;
 use windows_sys::Win32::Devices::HumanInterfaceDevice::MOUSE_MOVE_RELATIVE;
 use windows_sys::Win32::Foundation::{
-    GetLastError, FALSE, HANDLE, HWND, LPARAM, LRESULT, POINT, RECT, WAIT_FAILED, WPARAM,
+    GetLastError, BOOL, FALSE, HANDLE, HWND, LPARAM, LRESULT, POINT, RECT, TRUE, WAIT_FAILED,
+    WPARAM,
 };
 use windows_sys::Win32::Graphics::Gdi::{
     GetMonitorInfoW, MonitorFromRect, MonitorFromWindow, RedrawWindow, ScreenToClient,
-    ValidateRect, MONITORINFO, MONITOR_DEFAULTTONULL, RDW_INTERNALPAINT, SC_SCREENSAVE,
+    ValidateRect, MONITORINFO, MONITOR_DEFAULTTONEAREST, MONITOR_DEFAULTTONULL, RDW_INTERNALPAINT,
+    SC_SCREENSAVE,
+};
+use windows_sys::Win32::System::DataExchange::{
+    AddClipboardFormatListener, RemoveClipboardFormatListener, COPYDATASTRUCT,
 };
 use windows_sys::Win32::System::Ole::RevokeDragDrop;
+use windows_sys::Win32::System::RemoteDesktop::{
+    WTSRegisterSessionNotification, WTSUnRegisterSessionNotification, NOTIFY_FOR_THIS_SESSION,
+};
+use windows_sys::Win32::System::SystemServices::MK_LBUTTON;
 use windows_sys::Win32::System::Threading::{
     CreateWaitableTimerExW, GetCurrentThreadId, SetWaitableTimer,
     CREATE_WAITABLE_TIMER_HIGH_RESOLUTION, INFINITE, TIMER_ALL_ACCESS,
 };
 use windows_sys::Win32::UI::Controls::{HOVER_DEFAULT, WM_MOUSELEAVE};
-use windows_sys::Win32::UI::Input::Ime::{GCS_COMPSTR, GCS_RESULTSTR, ISC_SHOWUICOMPOSITIONWINDOW};
+use windows_sys::Win32::UI::Input::Ime::{
+    GCS_COMPSTR, GCS_RESULTSTR, IMN_CHANGECANDIDATE, IMN_CLOSECANDIDATE, IMN_OPENCANDIDATE,
+    IMR_CONFIRMRECONVERTSTRING, IMR_RECONVERTSTRING, ISC_SHOWUIALLCANDIDATEWINDOW,
+    ISC_SHOWUICOMPOSITIONWINDOW, RECONVERTSTRING,
+};
+#[cfg(feature = "dialogs")]
+use windows_sys::Win32::UI::Input::KeyboardAndMouse::{EnableWindow, IsWindowEnabled};
 use windows_sys::Win32::UI::Input::KeyboardAndMouse::{
     ReleaseCapture, SetCapture, TrackMouseEvent, TME_LEAVE, TRACKMOUSEEVENT,
 };
 use windows_sys::Win32::UI::Input::Pointer::{
-    POINTER_FLAG_DOWN, POINTER_FLAG_PRIMARY, POINTER_FLAG_UP, POINTER_FLAG_UPDATE,
+    POINTER_FLAG_CANCELED, POINTER_FLAG_DOWN, POINTER_FLAG_INCONTACT, POINTER_FLAG_PRIMARY,
+    POINTER_FLAG_UP, POINTER_FLAG_UPDATE, POINTER_PEN_INFO,
 };
 use windows_sys::Win32::UI::Input::Touch::{
     CloseTouchInputHandle, GetTouchInputInfo, TOUCHEVENTF_DOWN, TOUCHEVENTF_MOVE,
-    TOUCHEVENTF_PRIMARY, TOUCHEVENTF_UP, TOUCHINPUT,
+    TOUCHEVENTF_PRIMARY, TOUCHEVENTF_UP, TOUCHINPUT, TOUCHINPUTMASKF_CONTACTAREA,
 };
 use windows_sys::Win32::UI::Input::{RAWINPUT, RIM_TYPEKEYBOARD, RIM_TYPEMOUSE};
+use windows_sys::Win32::UI::Shell::{SetCurrentProcessExplicitAppUserModelID, THBN_CLICKED};
+#[cfg(feature = "dialogs")]
+use windows_sys::Win32::UI::WindowsAndMessaging::HTERROR;
+#[cfg(feature = "accesskit")]
+use windows_sys::Win32::UI::WindowsAndMessaging::WM_GETOBJECT;
 use windows_sys::Win32::UI::WindowsAndMessaging::{
-    CreateWindowExW, DefWindowProcW, DestroyWindow, DispatchMessageW, GetClientRect, GetCursorPos,
-    GetMenu, LoadCursorW, MsgWaitForMultipleObjectsEx, PeekMessageW, PostMessageW,
-    RegisterClassExW, RegisterWindowMessageA, SetCursor, SetWindowPos, TranslateMessage,
-    CREATESTRUCTW, GWL_STYLE, GWL_USERDATA, HTCAPTION, HTCLIENT, MINMAXINFO, MNC_CLOSE, MSG,
-    MWMO_INPUTAVAILABLE, NCCALCSIZE_PARAMS, PM_REMOVE, PT_TOUCH, QS_ALLEVENTS, RI_MOUSE_HWHEEL,
-    RI_MOUSE_WHEEL, SC_MINIMIZE, SC_RESTORE, SIZE_MAXIMIZED, SWP_NOACTIVATE, SWP_NOMOVE,
-    SWP_NOSIZE, SWP_NOZORDER, WHEEL_DELTA, WINDOWPOS, WMSZ_BOTTOM, WMSZ_BOTTOMLEFT,
-    WMSZ_BOTTOMRIGHT, WMSZ_LEFT, WMSZ_RIGHT, WMSZ_TOP, WMSZ_TOPLEFT, WMSZ_TOPRIGHT,
-    WM_CAPTURECHANGED, WM_CLOSE, WM_CREATE, WM_DESTROY, WM_DPICHANGED, WM_ENTERSIZEMOVE,
-    WM_EXITSIZEMOVE, WM_GETMINMAXINFO, WM_IME_COMPOSITION, WM_IME_ENDCOMPOSITION,
-    WM_IME_SETCONTEXT, WM_IME_STARTCOMPOSITION, WM_INPUT, WM_KEYDOWN, WM_KEYUP, WM_KILLFOCUS,
-    WM_LBUTTONDOWN, WM_LBUTTONUP, WM_MBUTTONDOWN, WM_MBUTTONUP, WM_MENUCHAR, WM_MOUSEHWHEEL,
-    WM_MOUSEMOVE, WM_MOUSEWHEEL, WM_NCACTIVATE, WM_NCCALCSIZE, WM_NCCREATE, WM_NCDESTROY,
-    WM_NCLBUTTONDOWN, WM_PAINT, WM_POINTERDOWN, WM_POINTERUP, WM_POINTERUPDATE, WM_RBUTTONDOWN,
-    WM_RBUTTONUP, WM_SETCURSOR, WM_SETFOCUS, WM_SETTINGCHANGE, WM_SIZE, WM_SIZING, WM_SYSCOMMAND,
-    WM_SYSKEYDOWN, WM_SYSKEYUP, WM_TOUCH, WM_WINDOWPOSCHANGED, WM_WINDOWPOSCHANGING,
-    WM_XBUTTONDOWN, WM_XBUTTONUP, WNDCLASSEXW, WS_EX_LAYERED, WS_EX_NOACTIVATE, WS_EX_TOOLWINDOW,
-    WS_EX_TRANSPARENT, WS_OVERLAPPED, WS_POPUP, WS_VISIBLE,
+    CreateWindowExW, DefWindowProcW, DestroyWindow, DispatchMessageW, EnumChildWindows,
+    EnumThreadWindows, GetClientRect, GetCursorPos, GetMenu, GetParent, KillTimer,
+    MsgWaitForMultipleObjectsEx, PeekMessageW, PostMessageW, RegisterClassExW,
+    RegisterWindowMessageA, SetCursor, SetWindowPos, TranslateMessage, WindowFromPoint,
+    CREATESTRUCTW, GIDC_ARRIVAL, GIDC_REMOVAL, GWLP_WNDPROC, GWL_STYLE, GWL_USERDATA, HTCAPTION,
+    HTCLIENT, HTMAXBUTTON, MINMAXINFO, MNC_CLOSE, MSG, MWMO_INPUTAVAILABLE, NCCALCSIZE_PARAMS,
+    PBT_APMPOWERSTATUSCHANGE, PBT_APMRESUMEAUTOMATIC, PBT_APMSUSPEND, PEN_FLAG_BARREL,
+    PEN_FLAG_ERASER, PEN_FLAG_INVERTED, PEN_MASK_PRESSURE, PEN_MASK_ROTATION, PEN_MASK_TILT_X,
+    PEN_MASK_TILT_Y, PM_REMOVE, PT_MOUSE, PT_PEN, PT_TOUCH, QS_ALLEVENTS, RI_MOUSE_HWHEEL,
+    RI_MOUSE_WHEEL, SC_MINIMIZE, SC_RESTORE, SIZE_MAXIMIZED, SIZE_MINIMIZED,
+    SPI_SETCLIENTAREAANIMATION, SPI_SETCURSORS, SPI_SETHIGHCONTRAST, SWP_NOACTIVATE, SWP_NOMOVE,
+    SWP_NOSIZE, SWP_NOZORDER, TOUCH_MASK_CONTACTAREA, WHEEL_DELTA, WINDOWPOS, WMSZ_BOTTOM,
+    WMSZ_BOTTOMLEFT, WMSZ_BOTTOMRIGHT, WMSZ_LEFT, WMSZ_RIGHT, WMSZ_TOP, WMSZ_TOPLEFT,
+    WMSZ_TOPRIGHT, WM_CAPTURECHANGED, WM_CLIPBOARDUPDATE, WM_CLOSE, WM_COMMAND, WM_COPYDATA,
+    WM_CREATE, WM_DESTROY, WM_DISPLAYCHANGE, WM_DPICHANGED, WM_DWMCOLORIZATIONCOLORCHANGED,
+    WM_DWMSENDICONICLIVEPREVIEWBITMAP, WM_DWMSENDICONICTHUMBNAIL, WM_ENTERSIZEMOVE,
+    WM_EXITSIZEMOVE, WM_GETMINMAXINFO, WM_IME_COMPOSITION, WM_IME_ENDCOMPOSITION, WM_IME_NOTIFY,
+    WM_IME_REQUEST, WM_IME_SETCONTEXT, WM_IME_STARTCOMPOSITION, WM_INPUT, WM_INPUTLANGCHANGE,
+    WM_INPUT_DEVICE_CHANGE, WM_KEYDOWN, WM_KEYUP, WM_KILLFOCUS, WM_LBUTTONDOWN, WM_LBUTTONUP,
+    WM_MBUTTONDOWN, WM_MBUTTONUP, WM_MENUCHAR, WM_MOUSEHWHEEL, WM_MOUSEMOVE, WM_MOUSEWHEEL,
+    WM_NCACTIVATE, WM_NCCALCSIZE, WM_NCCREATE, WM_NCDESTROY, WM_NCHITTEST, WM_NCLBUTTONDOWN,
+    WM_NCLBUTTONUP, WM_NCMOUSEMOVE, WM_PAINT, WM_POINTERDOWN, WM_POINTERUP, WM_POINTERUPDATE,
+    WM_POWERBROADCAST, WM_QUERYENDSESSION, WM_RBUTTONDOWN, WM_RBUTTONUP, WM_SETCURSOR, WM_SETFOCUS,
+    WM_SETTINGCHANGE, WM_SIZE, WM_SIZING, WM_SYSCOMMAND, WM_SYSKEYDOWN, WM_SYSKEYUP, WM_TIMER,
+    WM_TOUCH, WM_WINDOWPOSCHANGED, WM_WINDOWPOSCHANGING, WM_WTSSESSION_CHANGE, WM_XBUTTONDOWN,
+    WM_XBUTTONUP, WNDCLASSEXW, WS_EX_LAYERED, WS_EX_NOACTIVATE, WS_EX_TOOLWINDOW,
+    WS_EX_TRANSPARENT, WS_OVERLAPPED, WS_POPUP, WS_VISIBLE, WTS_SESSION_LOCK, WTS_SESSION_UNLOCK,
 };
 
+#[cfg(feature = "accesskit")]
+use super::accessibility::{self, AccessKit};
 use super::window::set_skip_taskbar;
 use super::SelectedCursor;
 use crate::application::ApplicationHandler;
-use crate::dpi::{PhysicalPosition, PhysicalSize};
+use crate::clipboard::{Clipboard as RootClipboard, ClipboardData, ClipboardFormat};
+#[cfg(feature = "dialogs")]
+use crate::dialogs::{FileDialogKind, FileDialogOptions, MessageBoxConfig, Modality};
+use crate::dpi::{LogicalPosition, PhysicalPosition, PhysicalSize};
+use crate::drag::{DragData, DragSession};
 use crate::error::{EventLoopError, RequestError};
+#[cfg(feature = "gamepad")]
+use crate::event::DeviceId;
 use crate::event::{
-    Event, FingerId as RootFingerId, Force, Ime, RawKeyEvent, SurfaceSizeWriter, TouchPhase,
-    WindowEvent,
+    DeviceInfo, Event, FingerId as RootFingerId, Force, Ime, LanguageTag, MouseScrollDelta,
+    PenButtons, PenTilt, PlatformData, PowerEvent, PowerState, RawKeyEvent, SurfaceSizeWriter,
+    SystemPreferences, TouchContact, TouchPhase, WindowEvent,
 };
 use crate::event_loop::{
-    ActiveEventLoop as RootActiveEventLoop, ControlFlow, DeviceEvents,
-    EventLoopProxy as RootEventLoopProxy, OwnedDisplayHandle as RootOwnedDisplayHandle,
+    ActiveEventLoop as RootActiveEventLoop, AsyncRequestSerial, ControlFlow, DeviceClass,
+    DeviceEvents, EventLoopProxy as RootEventLoopProxy, ExitResponse, ModifiersOrder,
+    OwnedDisplayHandle as RootOwnedDisplayHandle,
 };
-use crate::keyboard::ModifiersState;
-use crate::monitor::MonitorHandle as RootMonitorHandle;
+use crate::keyboard::{Key, KeyCode, KeyboardLayout, Led, ModifiersState, PhysicalKey};
+use crate::monitor::{ColorCapabilities, MonitorHandle as RootMonitorHandle, Orientation};
 use crate::platform::pump_events::PumpStatus;
+use crate::platform::windows::{DpiAwareness, EcoQos, JumpList, MessageData, RawInputConfig};
 use crate::platform_impl::platform::dark_mode::try_theme;
-use crate::platform_impl::platform::dpi::{become_dpi_aware, dpi_to_scale_factor};
+#[cfg(feature = "dialogs")]
+use crate::platform_impl::platform::dialogs;
+use crate::platform_impl::platform::dpi::{become_dpi_aware, dpi_awareness, dpi_to_scale_factor};
 use crate::platform_impl::platform::drop_handler::FileDropHandler;
-use crate::platform_impl::platform::icon::WinCursor;
+#[cfg(feature = "gamepad")]
+use crate::platform_impl::platform::gamepad::{self, Gamepads};
+use crate::platform_impl::platform::icon::{IconType, WinCursor};
 use crate::platform_impl::platform::ime::ImeContext;
-use crate::platform_impl::platform::keyboard::KeyEventBuilder;
-use crate::platform_impl::platform::keyboard_layout::LAYOUT_CACHE;
+use crate::platform_impl::platform::keyboard::{physicalkey_to_scancode, KeyEventBuilder};
+use crate::platform_impl::platform::keyboard_layout::{WindowsModifiers, LAYOUT_CACHE};
 use crate::platform_impl::platform::monitor::{self, MonitorHandle};
 use crate::platform_impl::platform::window::InitData;
 use crate::platform_impl::platform::window_state::{
     CursorFlags, ImeState, WindowFlags, WindowState,
 };
-use crate::platform_impl::platform::{raw_input, util, wrap_device_id, FingerId, Fullscreen};
+use crate::platform_impl::platform::{
+    clipboard, drag_source, instance, jump_list, keyboard_capture, power_throttling, raw_input,
+    taskbar, util, wrap_device_id, FingerId, Fullscreen,
+};
 use crate::platform_impl::Window;
 use crate::utils::Lazy;
 use crate::window::{
-    CustomCursor as RootCustomCursor, CustomCursorSource, Theme, Window as CoreWindow,
+    Coalescing, Cursor, CursorGrabMode, CursorGrabPolicy, CursorThemeInfo,
+    CustomCursor as RootCustomCursor, CustomCursorSource, Rgba, Theme, Window as CoreWindow,
     WindowAttributes, WindowId,
 };
 
@@ -102,8 +153,20 @@ pub(crate) struct WindowData {
     pub _file_drop_handler: Option<FileDropHandler>,
     pub userdata_removed: Cell<bool>,
     pub recurse_depth: Cell<u32>,
+    /// The hook set with `WindowExtWindows::set_wndproc_hook`.
+    pub wndproc_hook: RefCell<Option<Rc<RefCell<WndProcHook>>>>,
+    /// How the pointer motion is coalesced, read by the runner when dispatching the events.
+    pub motion_coalescing: Cell<Coalescing>,
+    /// The AccessKit adapter, set with `Window::set_accesskit_tree_source`.
+    #[cfg(feature = "accesskit")]
+    pub accesskit: RefCell<Option<AccessKit>>,
 }
 
+/// A hook receiving the messages of a window before winit, and handling them when it returns a
+/// result.
+pub(crate) type WndProcHook =
+    Box<dyn FnMut(HWND, u32, WPARAM, LPARAM) -> Option<LRESULT> + Send + 'static>;
+
 impl WindowData {
     fn send_event(&self, event: Event) {
         self.event_loop_runner.send_event(event);
@@ -116,6 +179,14 @@ impl WindowData {
 
 struct ThreadMsgTargetData {
     event_loop_runner: Rc<EventLoopRunner>,
+    /// The monitors known when the display configuration last changed.
+    monitors: RefCell<Vec<MonitorState>>,
+    /// The accessibility preferences last reported.
+    system_preferences: Cell<SystemPreferences>,
+    /// The text scale factor last reported.
+    text_scale_factor: Cell<f64>,
+    #[cfg(feature = "gamepad")]
+    gamepads: RefCell<Gamepads>,
 }
 
 impl ThreadMsgTargetData {
@@ -124,6 +195,29 @@ impl ThreadMsgTargetData {
     }
 }
 
+/// A monitor with the properties whose changes are reported.
+#[derive(Clone)]
+struct MonitorState {
+    monitor: MonitorHandle,
+    color_capabilities: Option<ColorCapabilities>,
+    orientation: Orientation,
+    refresh_rate_millihertz: Option<NonZeroU32>,
+}
+
+fn monitor_states() -> Vec<MonitorState> {
+    monitor::available_monitors()
+        .into_iter()
+        .map(|monitor| MonitorState {
+            color_capabilities: monitor.color_capabilities().ok(),
+            orientation: monitor.orientation(),
+            refresh_rate_millihertz: monitor
+                .current_video_mode()
+                .and_then(|mode| mode.refresh_rate_millihertz()),
+            monitor,
+        })
+        .collect()
+}
+
 /// The result of a subclass procedure (the message handling callback)
 #[derive(Clone, Copy)]
 pub(crate) enum ProcResult {
@@ -142,20 +236,33 @@ pub struct EventLoop {
 
 pub(crate) struct PlatformSpecificEventLoopAttributes {
     pub(crate) any_thread: bool,
-    pub(crate) dpi_aware: bool,
+    pub(crate) dpi_awareness: Option<DpiAwareness>,
     pub(crate) msg_hook: Option<Box<dyn FnMut(*const c_void) -> bool + 'static>>,
+    pub(crate) modifiers_order: ModifiersOrder,
+    pub(crate) app_user_model_id: Option<String>,
+    pub(crate) single_instance: bool,
 }
 
 impl Default for PlatformSpecificEventLoopAttributes {
     fn default() -> Self {
-        Self { any_thread: false, dpi_aware: true, msg_hook: None }
+        Self {
+            any_thread: false,
+            dpi_awareness: Some(DpiAwareness::PerMonitorV2),
+            msg_hook: None,
+            modifiers_order: ModifiersOrder::default(),
+            app_user_model_id: None,
+            single_instance: false,
+        }
     }
 }
 
 impl PartialEq for PlatformSpecificEventLoopAttributes {
     fn eq(&self, other: &Self) -> bool {
         self.any_thread.eq(&other.any_thread)
-            && self.dpi_aware.eq(&other.dpi_aware)
+            && self.dpi_awareness.eq(&other.dpi_awareness)
+            && self.modifiers_order.eq(&other.modifiers_order)
+            && self.app_user_model_id.eq(&other.app_user_model_id)
+            && self.single_instance.eq(&other.single_instance)
             && match (&self.msg_hook, &other.msg_hook) {
                 (Some(this), Some(other)) => std::ptr::eq(&this, &other),
                 (None, None) => true,
@@ -169,7 +276,9 @@ impl Eq for PlatformSpecificEventLoopAttributes {}
 impl std::hash::Hash for PlatformSpecificEventLoopAttributes {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         self.any_thread.hash(state);
-        self.dpi_aware.hash(state);
+        self.dpi_awareness.hash(state);
+        self.app_user_model_id.hash(state);
+        self.single_instance.hash(state);
         std::ptr::hash(&self.msg_hook, state);
     }
 }
@@ -177,6 +286,9 @@ impl std::hash::Hash for PlatformSpecificEventLoopAttributes {
 pub struct ActiveEventLoop {
     thread_id: u32,
     thread_msg_target: HWND,
+    device_events: Cell<DeviceEvents>,
+    device_classes: Cell<DeviceClass>,
+    raw_input_config: RefCell<RawInputConfig>,
     pub(crate) runner_shared: Rc<EventLoopRunner>,
 }
 
@@ -195,22 +307,72 @@ impl EventLoop {
             );
         }
 
-        if attributes.dpi_aware {
-            become_dpi_aware();
+        if let Some(awareness) = attributes.dpi_awareness {
+            become_dpi_aware(awareness);
+        }
+
+        if let Some(id) = &attributes.app_user_model_id {
+            let id = util::encode_wide(id);
+            let hr = unsafe { SetCurrentProcessExplicitAppUserModelID(id.as_ptr()) };
+            if hr < 0 {
+                tracing::warn!("Setting the AppUserModelID failed. HRESULT Code: 0x{:X}", hr);
+            }
+        }
+
+        // Hand the task of the jump list this process was launched from to the running instance.
+        let jump_list_task = jump_list::launch_task_arguments();
+        if let Some(arguments) = &jump_list_task {
+            if instance::send_to_running_instance(jump_list::TASK_COPYDATA, arguments) {
+                std::process::exit(0);
+            }
+        }
+
+        // Hand the files this process was launched with to the running instance.
+        let launch_files = instance::launch_files();
+        if attributes.single_instance
+            && instance::send_to_running_instance(instance::LAUNCH_COPYDATA, &launch_files)
+        {
+            std::process::exit(0);
         }
 
         let thread_msg_target = create_event_target_window();
+        if attributes.single_instance {
+            instance::register(thread_msg_target);
+        }
 
-        let runner_shared = Rc::new(EventLoopRunner::new(thread_msg_target));
+        let runner_shared =
+            Rc::new(EventLoopRunner::new(thread_msg_target, attributes.modifiers_order));
+        // Delivered after `can_create_surfaces`, like the buffered events.
+        if let Some(arguments) = jump_list_task {
+            runner_shared.send_event(Event::JumpListTask(arguments));
+        }
+        if !launch_files.is_empty() {
+            runner_shared.send_event(Event::OpenFiles(launch_files));
+        }
 
         insert_event_target_window_data(thread_msg_target, runner_shared.clone());
-        raw_input::register_all_mice_and_keyboards_for_raw_input(
+        raw_input::register_for_raw_input(
             thread_msg_target,
             Default::default(),
+            Default::default(),
+            &Default::default(),
         );
+        // Receive `WM_WTSSESSION_CHANGE` when the session is locked or unlocked.
+        unsafe { WTSRegisterSessionNotification(thread_msg_target, NOTIFY_FOR_THIS_SESSION) };
+        // Receive `WM_CLIPBOARDUPDATE` when the content of the clipboard changes.
+        unsafe { AddClipboardFormatListener(thread_msg_target) };
+        #[cfg(feature = "gamepad")]
+        gamepad::set_polling(thread_msg_target, Some(gamepad::POLL_INTERVAL));
 
         Ok(EventLoop {
-            window_target: ActiveEventLoop { thread_id, thread_msg_target, runner_shared },
+            window_target: ActiveEventLoop {
+                thread_id,
+                thread_msg_target,
+                device_events: Cell::new(Default::default()),
+                device_classes: Cell::new(Default::default()),
+                raw_input_config: RefCell::new(Default::default()),
+                runner_shared,
+            },
             msg_hook: attributes.msg_hook.take(),
             high_resolution_timer: None,
         })
@@ -220,6 +382,22 @@ impl EventLoop {
         &self.window_target
     }
 
+    pub(crate) fn set_jump_list(&self, jump_list: &JumpList) -> Result<(), RequestError> {
+        self.window_target.set_jump_list(jump_list)
+    }
+
+    pub(crate) fn set_raw_input_config(&self, config: RawInputConfig) {
+        self.window_target.set_raw_input_config(config)
+    }
+
+    pub(crate) fn set_process_power_throttling(&self, qos: EcoQos) -> Result<(), RequestError> {
+        self.window_target.set_process_power_throttling(qos)
+    }
+
+    pub(crate) fn dpi_awareness(&self) -> DpiAwareness {
+        self.window_target.dpi_awareness()
+    }
+
     pub fn run_app<A: ApplicationHandler>(mut self, app: A) -> Result<(), EventLoopError> {
         self.run_app_on_demand(app)
     }
@@ -250,8 +428,74 @@ impl EventLoop {
                     Event::Resumed => app.resumed(event_loop_windows_ref),
                     Event::CreateSurfaces => app.can_create_surfaces(event_loop_windows_ref),
                     Event::AboutToWait => app.about_to_wait(event_loop_windows_ref),
+                    Event::ExitRequested => {
+                        if app.exit_requested(event_loop_windows_ref) == ExitResponse::Exit {
+                            event_loop_windows_ref.exit();
+                        }
+                    },
                     Event::LoopExiting => app.exiting(event_loop_windows_ref),
                     Event::MemoryWarning => app.memory_warning(event_loop_windows_ref),
+                    Event::PowerEvent(event) => app.power_event(event_loop_windows_ref, event),
+                    Event::ReopenRequested => app.reopen_requested(event_loop_windows_ref),
+                    Event::OpenFiles(paths) => app.open_files(event_loop_windows_ref, paths),
+                    Event::OpenUrls(urls) => app.open_urls(event_loop_windows_ref, urls),
+                    Event::SessionLocked => app.session_locked(event_loop_windows_ref),
+                    Event::SessionUnlocked => app.session_unlocked(event_loop_windows_ref),
+                    Event::ClipboardData { serial, data } => {
+                        app.clipboard_data(event_loop_windows_ref, serial, data)
+                    },
+                    Event::ClipboardChanged => app.clipboard_changed(event_loop_windows_ref),
+                    Event::InputDeviceAdded(device) => {
+                        app.input_device_added(event_loop_windows_ref, device)
+                    },
+                    Event::InputDeviceRemoved(device_id) => {
+                        app.input_device_removed(event_loop_windows_ref, device_id)
+                    },
+                    Event::LocaleChanged(locale) => {
+                        app.locale_changed(event_loop_windows_ref, locale)
+                    },
+                    Event::CursorThemeChanged(theme) => {
+                        app.cursor_theme_changed(event_loop_windows_ref, theme)
+                    },
+                    Event::SystemPreferencesChanged(preferences) => {
+                        app.system_preferences_changed(event_loop_windows_ref, preferences)
+                    },
+                    Event::TextScaleFactorChanged(factor) => {
+                        app.text_scale_factor_changed(event_loop_windows_ref, factor)
+                    },
+                    Event::JumpListTask(arguments) => {
+                        if let Some(handler) = app.windows_handler() {
+                            handler.jump_list_task(event_loop_windows_ref, arguments)
+                        }
+                    },
+                    Event::ThumbbarButtonClicked { window_id, id } => {
+                        if let Some(handler) = app.windows_handler() {
+                            handler.thumbbar_button_clicked(event_loop_windows_ref, window_id, id)
+                        }
+                    },
+                    Event::MonitorAdded(monitor) => {
+                        app.monitor_added(event_loop_windows_ref, monitor)
+                    },
+                    Event::MonitorRemoved(monitor) => {
+                        app.monitor_removed(event_loop_windows_ref, monitor)
+                    },
+                    Event::ColorCapabilitiesChanged(monitor) => {
+                        app.color_capabilities_changed(event_loop_windows_ref, monitor)
+                    },
+                    Event::MonitorOrientationChanged(monitor) => {
+                        app.monitor_orientation_changed(event_loop_windows_ref, monitor)
+                    },
+                    Event::MonitorRefreshRateChanged(monitor) => {
+                        app.monitor_refresh_rate_changed(event_loop_windows_ref, monitor)
+                    },
+                    #[cfg(feature = "dialogs")]
+                    Event::FileDialogDone { serial, result } => {
+                        app.file_dialog_done(event_loop_windows_ref, serial, result)
+                    },
+                    #[cfg(feature = "dialogs")]
+                    Event::MessageBoxDone { serial, result } => {
+                        app.message_box_done(event_loop_windows_ref, serial, result)
+                    },
                 });
             }
         }
@@ -317,8 +561,74 @@ impl EventLoop {
                     Event::Resumed => app.resumed(event_loop_windows_ref),
                     Event::CreateSurfaces => app.can_create_surfaces(event_loop_windows_ref),
                     Event::AboutToWait => app.about_to_wait(event_loop_windows_ref),
+                    Event::ExitRequested => {
+                        if app.exit_requested(event_loop_windows_ref) == ExitResponse::Exit {
+                            event_loop_windows_ref.exit();
+                        }
+                    },
                     Event::LoopExiting => app.exiting(event_loop_windows_ref),
                     Event::MemoryWarning => app.memory_warning(event_loop_windows_ref),
+                    Event::PowerEvent(event) => app.power_event(event_loop_windows_ref, event),
+                    Event::ReopenRequested => app.reopen_requested(event_loop_windows_ref),
+                    Event::OpenFiles(paths) => app.open_files(event_loop_windows_ref, paths),
+                    Event::OpenUrls(urls) => app.open_urls(event_loop_windows_ref, urls),
+                    Event::SessionLocked => app.session_locked(event_loop_windows_ref),
+                    Event::SessionUnlocked => app.session_unlocked(event_loop_windows_ref),
+                    Event::ClipboardData { serial, data } => {
+                        app.clipboard_data(event_loop_windows_ref, serial, data)
+                    },
+                    Event::ClipboardChanged => app.clipboard_changed(event_loop_windows_ref),
+                    Event::InputDeviceAdded(device) => {
+                        app.input_device_added(event_loop_windows_ref, device)
+                    },
+                    Event::InputDeviceRemoved(device_id) => {
+                        app.input_device_removed(event_loop_windows_ref, device_id)
+                    },
+                    Event::LocaleChanged(locale) => {
+                        app.locale_changed(event_loop_windows_ref, locale)
+                    },
+                    Event::CursorThemeChanged(theme) => {
+                        app.cursor_theme_changed(event_loop_windows_ref, theme)
+                    },
+                    Event::SystemPreferencesChanged(preferences) => {
+                        app.system_preferences_changed(event_loop_windows_ref, preferences)
+                    },
+                    Event::TextScaleFactorChanged(factor) => {
+                        app.text_scale_factor_changed(event_loop_windows_ref, factor)
+                    },
+                    Event::JumpListTask(arguments) => {
+                        if let Some(handler) = app.windows_handler() {
+                            handler.jump_list_task(event_loop_windows_ref, arguments)
+                        }
+                    },
+                    Event::ThumbbarButtonClicked { window_id, id } => {
+                        if let Some(handler) = app.windows_handler() {
+                            handler.thumbbar_button_clicked(event_loop_windows_ref, window_id, id)
+                        }
+                    },
+                    Event::MonitorAdded(monitor) => {
+                        app.monitor_added(event_loop_windows_ref, monitor)
+                    },
+                    Event::MonitorRemoved(monitor) => {
+                        app.monitor_removed(event_loop_windows_ref, monitor)
+                    },
+                    Event::ColorCapabilitiesChanged(monitor) => {
+                        app.color_capabilities_changed(event_loop_windows_ref, monitor)
+                    },
+                    Event::MonitorOrientationChanged(monitor) => {
+                        app.monitor_orientation_changed(event_loop_windows_ref, monitor)
+                    },
+                    Event::MonitorRefreshRateChanged(monitor) => {
+                        app.monitor_refresh_rate_changed(event_loop_windows_ref, monitor)
+                    },
+                    #[cfg(feature = "dialogs")]
+                    Event::FileDialogDone { serial, result } => {
+                        app.file_dialog_done(event_loop_windows_ref, serial, result)
+                    },
+                    #[cfg(feature = "dialogs")]
+                    Event::MessageBoxDone { serial, result } => {
+                        app.message_box_done(event_loop_windows_ref, serial, result)
+                    },
                 });
 
                 runner.wakeup();
@@ -440,12 +750,22 @@ impl EventLoop {
 impl Drop for EventLoop {
     fn drop(&mut self) {
         unsafe {
+            WTSUnRegisterSessionNotification(self.window_target.thread_msg_target);
+            RemoveClipboardFormatListener(self.window_target.thread_msg_target);
             DestroyWindow(self.window_target.thread_msg_target);
         }
     }
 }
 
 impl ActiveEventLoop {
+    /// Poll the gamepads only while their device events are captured.
+    #[cfg(feature = "gamepad")]
+    fn update_gamepad_polling(&self) {
+        let enabled = self.device_events.get() != DeviceEvents::Never
+            && self.device_classes.get().contains(DeviceClass::HID);
+        gamepad::set_polling(self.thread_msg_target, enabled.then_some(gamepad::POLL_INTERVAL));
+    }
+
     #[inline(always)]
     pub(crate) fn create_thread_executor(&self) -> EventLoopThreadExecutor {
         EventLoopThreadExecutor { thread_id: self.thread_id, target_window: self.thread_msg_target }
@@ -458,6 +778,97 @@ impl ActiveEventLoop {
     fn exit_code(&self) -> Option<i32> {
         self.runner_shared.exit_code()
     }
+
+    pub(crate) fn set_jump_list(&self, jump_list: &JumpList) -> Result<(), RequestError> {
+        jump_list::set_jump_list(self.thread_msg_target, jump_list)
+    }
+
+    pub(crate) fn set_raw_input_config(&self, config: RawInputConfig) {
+        // Remove the usages of the previous configuration, which may not be in the new one.
+        raw_input::register_for_raw_input(
+            self.thread_msg_target,
+            DeviceEvents::Never,
+            DeviceClass::empty(),
+            &self.raw_input_config.borrow(),
+        );
+        *self.raw_input_config.borrow_mut() = config;
+        self.register_raw_input();
+    }
+
+    pub(crate) fn set_process_power_throttling(&self, qos: EcoQos) -> Result<(), RequestError> {
+        power_throttling::set_process_power_throttling(qos)
+    }
+
+    pub(crate) fn dpi_awareness(&self) -> DpiAwareness {
+        dpi_awareness()
+    }
+
+    fn register_raw_input(&self) {
+        raw_input::register_for_raw_input(
+            self.thread_msg_target,
+            self.device_events.get(),
+            self.device_classes.get(),
+            &self.raw_input_config.borrow(),
+        );
+    }
+
+    #[cfg(feature = "dialogs")]
+    fn show_file_dialog(
+        &self,
+        kind: FileDialogKind,
+        options: FileDialogOptions,
+    ) -> Result<AsyncRequestSerial, RequestError> {
+        let serial = AsyncRequestSerial::get();
+        let (modality, parent) = (options.modality, options.parent);
+        let target_window = self.thread_msg_target;
+        dialogs::show_file_dialog(kind, options, move |result| {
+            post_dialog_done(target_window, Event::FileDialogDone { serial, result })
+        })?;
+        self.block_windows(serial, modality, parent);
+        Ok(serial)
+    }
+
+    /// Disable the other windows for an application-modal dialog.
+    ///
+    /// The parent window is disabled by the dialog itself, for both modalities. The result of the
+    /// dialog is only handled once this returns, so the windows are enabled again afterwards.
+    #[cfg(feature = "dialogs")]
+    fn block_windows(
+        &self,
+        serial: AsyncRequestSerial,
+        modality: Modality,
+        parent: Option<WindowId>,
+    ) {
+        if modality != Modality::Application {
+            return;
+        }
+
+        let disabled: Vec<HWND> = self
+            .windows()
+            .into_iter()
+            .filter(|&window_id| Some(window_id) != parent)
+            .map(|window_id| window_id.into_raw() as HWND)
+            .filter(|&window| unsafe { IsWindowEnabled(window) } != false.into())
+            .collect();
+        for &window in &disabled {
+            unsafe { EnableWindow(window, false.into()) };
+        }
+        self.runner_shared.modal_windows.borrow_mut().push((serial, disabled));
+    }
+}
+
+impl RootClipboard for ActiveEventLoop {
+    fn set(&self, data: Vec<ClipboardData>) -> Result<(), RequestError> {
+        clipboard::set(self.thread_msg_target, &data)
+    }
+
+    fn get(&self, format: ClipboardFormat) -> Result<AsyncRequestSerial, RequestError> {
+        let serial = AsyncRequestSerial::get();
+        let data = clipboard::get(self.thread_msg_target, format)?;
+        // Buffered until the current event is handled.
+        self.runner_shared.send_event(Event::ClipboardData { serial, data });
+        Ok(serial)
+    }
 }
 
 impl RootActiveEventLoop for ActiveEventLoop {
@@ -500,8 +911,202 @@ impl RootActiveEventLoop for ActiveEventLoop {
         Some(if super::dark_mode::should_use_dark_mode() { Theme::Dark } else { Theme::Light })
     }
 
+    fn system_accent_color(&self) -> Option<Rgba> {
+        util::accent_color()
+    }
+
+    fn cursor_theme(&self) -> Option<CursorThemeInfo> {
+        util::cursor_theme()
+    }
+
+    fn system_preferences(&self) -> Option<SystemPreferences> {
+        Some(util::system_preferences())
+    }
+
+    fn text_scale_factor(&self) -> Option<f64> {
+        Some(util::text_scale_factor())
+    }
+
+    fn power_state(&self) -> Option<PowerState> {
+        util::power_state()
+    }
+
+    fn locale(&self) -> LanguageTag {
+        util::user_locale()
+    }
+
+    fn keyboard_layout(&self) -> Option<KeyboardLayout> {
+        util::keyboard_layout()
+    }
+
+    fn key_label(&self, key_code: KeyCode) -> Option<String> {
+        self.key_for_keycode(key_code)?.to_label()
+    }
+
+    fn key_for_keycode(&self, key_code: KeyCode) -> Option<Key> {
+        let mut layouts = LAYOUT_CACHE.lock().unwrap();
+        let (_, layout) = layouts.get_current_layout();
+        let key = layout.keys.get(&WindowsModifiers::empty())?.get(&key_code)?;
+        (!matches!(key, Key::Unidentified(_))).then(|| key.clone())
+    }
+
+    fn keycode_for_key(&self, key: &Key) -> Option<KeyCode> {
+        let mut layouts = LAYOUT_CACHE.lock().unwrap();
+        let (_, layout) = layouts.get_current_layout();
+        // Prefer the main block over the numpad, which comes after it.
+        layout
+            .keys
+            .get(&WindowsModifiers::empty())?
+            .iter()
+            .filter(|(_, unmodified)| key.is_produced_by(unmodified))
+            .map(|(key_code, _)| *key_code)
+            .min_by_key(|key_code| physicalkey_to_scancode(PhysicalKey::Code(*key_code)))
+    }
+
+    fn set_keyboard_led(&self, led: Led, on: bool) -> Result<(), RequestError> {
+        util::set_keyboard_led(led, on).map_err(|err| os_error!(err).into())
+    }
+
+    fn keyboard_led(&self, led: Led) -> Option<bool> {
+        Some(util::keyboard_led(led))
+    }
+
+    fn event_platform_data(&self) -> Option<PlatformData> {
+        self.runner_shared.current_message.get().map(PlatformData::new)
+    }
+
     fn listen_device_events(&self, allowed: DeviceEvents) {
-        raw_input::register_all_mice_and_keyboards_for_raw_input(self.thread_msg_target, allowed);
+        self.device_events.set(allowed);
+        self.register_raw_input();
+        #[cfg(feature = "gamepad")]
+        self.update_gamepad_polling();
+    }
+
+    fn listen_device_classes(&self, classes: DeviceClass) {
+        self.device_classes.set(classes);
+        self.register_raw_input();
+        #[cfg(feature = "gamepad")]
+        self.update_gamepad_polling();
+    }
+
+    fn input_devices(&self) -> Vec<DeviceInfo> {
+        #[allow(unused_mut)]
+        let mut devices = raw_input::get_input_devices();
+        #[cfg(feature = "gamepad")]
+        devices.extend(gamepad::gamepads());
+        devices
+    }
+
+    #[cfg(feature = "gamepad")]
+    fn set_gamepad_rumble(
+        &self,
+        device_id: DeviceId,
+        strong: f32,
+        weak: f32,
+    ) -> Result<(), RequestError> {
+        gamepad::set_rumble(device_id, strong, weak)
+    }
+
+    fn windows(&self) -> Vec<WindowId> {
+        unsafe extern "system" fn push_window(window: HWND, windows: LPARAM) -> BOOL {
+            let windows = unsafe { &mut *(windows as *mut Vec<WindowId>) };
+            if unsafe { is_winit_window(window) } {
+                windows.push(WindowId::from_raw(window as usize));
+            }
+            TRUE
+        }
+
+        unsafe extern "system" fn push_windows(window: HWND, windows: LPARAM) -> BOOL {
+            unsafe {
+                push_window(window, windows);
+                // Child windows are only enumerated from their top-level window.
+                EnumChildWindows(window, Some(push_window), windows);
+            }
+            TRUE
+        }
+
+        let mut windows = Vec::new();
+        unsafe {
+            EnumThreadWindows(
+                GetCurrentThreadId(),
+                Some(push_windows),
+                &mut windows as *mut Vec<WindowId> as LPARAM,
+            )
+        };
+        windows
+    }
+
+    fn with_native_modal_scope(&self, f: &mut dyn FnMut()) {
+        let desktop_rect = util::get_desktop_rect();
+        let cursor_clip = util::get_cursor_clip().ok().filter(|clip| {
+            (clip.left, clip.top, clip.right, clip.bottom)
+                != (desktop_rect.left, desktop_rect.top, desktop_rect.right, desktop_rect.bottom)
+        });
+
+        // Release the cursor and stop the raw input, so the dialog could be interacted with.
+        let _ = util::set_cursor_clip(None);
+        raw_input::register_for_raw_input(
+            self.thread_msg_target,
+            DeviceEvents::Never,
+            DeviceClass::empty(),
+            &self.raw_input_config.borrow(),
+        );
+
+        f();
+
+        self.register_raw_input();
+        if cursor_clip.is_some() {
+            let _ = util::set_cursor_clip(cursor_clip);
+        }
+    }
+
+    fn start_drag(&self, source: WindowId, data: DragData) -> Result<(), RequestError> {
+        let window = source.into_raw() as HWND;
+        if !unsafe { is_winit_window(window) } || self.runner_shared.drag.borrow().is_some() {
+            return Err(RequestError::Ignored);
+        }
+        self.runner_shared.drag.replace(Some(DragSession::new(source, data)));
+        Ok(())
+    }
+
+    fn set_drag_image(&self, image: Option<Cursor>) -> Result<(), RequestError> {
+        let mut drag = self.runner_shared.drag.borrow_mut();
+        drag.as_mut().ok_or(RequestError::Ignored)?.set_image(image);
+        Ok(())
+    }
+
+    fn clipboard(&self) -> &dyn RootClipboard {
+        self
+    }
+
+    #[cfg(feature = "dialogs")]
+    fn pick_file(&self, options: FileDialogOptions) -> Result<AsyncRequestSerial, RequestError> {
+        self.show_file_dialog(FileDialogKind::PickFile, options)
+    }
+
+    #[cfg(feature = "dialogs")]
+    fn pick_folder(&self, options: FileDialogOptions) -> Result<AsyncRequestSerial, RequestError> {
+        self.show_file_dialog(FileDialogKind::PickFolder, options)
+    }
+
+    #[cfg(feature = "dialogs")]
+    fn save_file(&self, options: FileDialogOptions) -> Result<AsyncRequestSerial, RequestError> {
+        self.show_file_dialog(FileDialogKind::SaveFile, options)
+    }
+
+    #[cfg(feature = "dialogs")]
+    fn show_message_box(
+        &self,
+        config: MessageBoxConfig,
+    ) -> Result<AsyncRequestSerial, RequestError> {
+        let serial = AsyncRequestSerial::get();
+        let (modality, parent) = (config.modality, config.parent);
+        let target_window = self.thread_msg_target;
+        dialogs::show_message_box(config, move |result| {
+            post_dialog_done(target_window, Event::MessageBoxDone { serial, result })
+        })?;
+        self.block_windows(serial, modality, parent);
+        Ok(serial)
     }
 
     fn set_control_flow(&self, control_flow: ControlFlow) {
@@ -882,11 +1487,32 @@ pub(crate) static DESTROY_MSG_ID: LazyMessageId = LazyMessageId::new("Winit::Des
 // documentation in the `window_state` module for more information.
 pub(crate) static SET_RETAIN_STATE_ON_SIZE_MSG_ID: LazyMessageId =
     LazyMessageId::new("Winit::SetRetainMaximized\0");
-static THREAD_EVENT_TARGET_WINDOW_CLASS: Lazy<Vec<u16>> =
+// Message sent by the thread of a dialog when the user made their choice.
+// WPARAM contains a Box<Event> that must be retrieved with `Box::from_raw`, and LPARAM is unused.
+#[cfg(feature = "dialogs")]
+static DIALOG_DONE_MSG_ID: LazyMessageId = LazyMessageId::new("Winit::DialogDone\0");
+// Message sent by the AccessKit adapter of a window when an assistive technology requested an
+// action, from any thread.
+// WPARAM contains a Box<Event> that must be retrieved with `Box::from_raw`, and LPARAM is unused.
+#[cfg(feature = "accesskit")]
+static ACCESSKIT_ACTION_MSG_ID: LazyMessageId = LazyMessageId::new("Winit::AccessKitAction\0");
+pub(crate) static THREAD_EVENT_TARGET_WINDOW_CLASS: Lazy<Vec<u16>> =
     Lazy::new(|| util::encode_wide("Winit Thread Event Target"));
+/// The identifier of the timer of the redraws scheduled with `Window::request_redraw_at`.
+pub(crate) const REDRAW_TIMER_ID: usize = 1;
 /// When the taskbar is created, it registers a message with the "TaskbarCreated" string and then
 /// broadcasts this message to all top-level windows <https://docs.microsoft.com/en-us/windows/win32/shell/taskbar#taskbar-creation-notification>
 pub(crate) static TASKBAR_CREATED: LazyMessageId = LazyMessageId::new("TaskbarCreated\0");
+/// Sent to a window when its taskbar button is created, after which its thumbnail toolbar can be
+/// added.
+static TASKBAR_BUTTON_CREATED: LazyMessageId = LazyMessageId::new("TaskbarButtonCreated\0");
+
+/// Hand the outcome of a dialog over to the thread of the event loop.
+#[cfg(feature = "dialogs")]
+fn post_dialog_done(target_window: HWND, event: Event) {
+    let event = Box::into_raw(Box::new(event));
+    unsafe { PostMessageW(target_window, DIALOG_DONE_MSG_ID.get(), event as usize, 0) };
+}
 
 fn create_event_target_window() -> HWND {
     use windows_sys::Win32::UI::WindowsAndMessaging::{CS_HREDRAW, CS_VREDRAW};
@@ -948,7 +1574,14 @@ fn insert_event_target_window_data(
     thread_msg_target: HWND,
     event_loop_runner: Rc<EventLoopRunner>,
 ) {
-    let userdata = ThreadMsgTargetData { event_loop_runner };
+    let userdata = ThreadMsgTargetData {
+        event_loop_runner,
+        monitors: RefCell::new(monitor_states()),
+        system_preferences: Cell::new(util::system_preferences()),
+        text_scale_factor: Cell::new(util::text_scale_factor()),
+        #[cfg(feature = "gamepad")]
+        gamepads: Default::default(),
+    };
     let input_ptr = Box::into_raw(Box::new(userdata));
 
     unsafe { super::set_window_long(thread_msg_target, GWL_USERDATA, input_ptr as isize) };
@@ -979,6 +1612,58 @@ fn normalize_pointer_pressure(pressure: u32) -> Option<Force> {
     }
 }
 
+/// Retrieve the pen information of the frames of a `WM_POINTER` message.
+unsafe fn get_pointer_pen_info_history(pointer_id: u32) -> Vec<POINTER_PEN_INFO> {
+    let Some(GetPointerPenInfoHistory) = *util::GET_POINTER_PEN_INFO_HISTORY else {
+        return Vec::new();
+    };
+
+    let mut entries_count = 0u32;
+    if unsafe { GetPointerPenInfoHistory(pointer_id, &mut entries_count, ptr::null_mut()) }
+        == false.into()
+    {
+        return Vec::new();
+    }
+
+    let mut pen_infos = Vec::with_capacity(entries_count as usize);
+    if unsafe { GetPointerPenInfoHistory(pointer_id, &mut entries_count, pen_infos.as_mut_ptr()) }
+        == false.into()
+    {
+        return Vec::new();
+    }
+    unsafe { pen_infos.set_len(entries_count as usize) };
+    pen_infos
+}
+
+fn pen_input(position: PhysicalPosition<f64>, pen_info: &POINTER_PEN_INFO) -> WindowEvent {
+    let contact = util::has_flag(pen_info.pointerInfo.pointerFlags, POINTER_FLAG_INCONTACT);
+    let pressure = if util::has_flag(pen_info.penMask, PEN_MASK_PRESSURE) {
+        pen_info.pressure.min(1024) as f32 / 1024.0
+    } else {
+        contact as u8 as f32
+    };
+    let tilt = (util::has_flag(pen_info.penMask, PEN_MASK_TILT_X)
+        || util::has_flag(pen_info.penMask, PEN_MASK_TILT_Y))
+    .then_some(PenTilt { x: pen_info.tiltX as f32, y: pen_info.tiltY as f32 });
+    let twist =
+        util::has_flag(pen_info.penMask, PEN_MASK_ROTATION).then_some(pen_info.rotation as f32);
+
+    let mut barrel_buttons = PenButtons::empty();
+    barrel_buttons.set(PenButtons::PRIMARY, util::has_flag(pen_info.penFlags, PEN_FLAG_BARREL));
+
+    WindowEvent::PenInput {
+        device_id: None,
+        position,
+        contact,
+        pressure,
+        tilt,
+        twist,
+        inverted: util::has_flag(pen_info.penFlags, PEN_FLAG_INVERTED)
+            || util::has_flag(pen_info.penFlags, PEN_FLAG_ERASER),
+        barrel_buttons,
+    }
+}
+
 /// Emit a `ModifiersChanged` event whenever modifiers have changed.
 /// Returns the current modifier state
 fn update_modifiers(window: HWND, userdata: &WindowData) {
@@ -1003,21 +1688,170 @@ fn update_modifiers(window: HWND, userdata: &WindowData) {
     }
 }
 
+/// Report `ColorProfileChanged` when the window moved to a monitor with another profile, or when
+/// `reload` and the profile of its monitor changed.
+fn update_color_profile(window: HWND, userdata: &WindowData, reload: bool) {
+    let hmonitor = unsafe { MonitorFromWindow(window, MONITOR_DEFAULTTONEAREST) };
+    let previous = match &userdata.window_state_lock().color_profile {
+        Some((monitor, _)) if *monitor == hmonitor && !reload => return,
+        previous => previous.clone(),
+    };
+
+    let profile = MonitorHandle::new(hmonitor).icc_profile();
+    let changed = previous.is_some_and(|(_, previous)| previous != profile);
+    userdata.window_state_lock().color_profile = Some((hmonitor, profile));
+
+    // The first profile is only a baseline.
+    if changed {
+        userdata.send_event(Event::WindowEvent {
+            window_id: WindowId::from_raw(window as usize),
+            event: WindowEvent::ColorProfileChanged,
+        });
+    }
+}
+
+/// The wheel event for a delta in units of `WHEEL_DELTA` per notch.
+///
+/// Precision touchpads report fractions of a notch, which are delivered as pixels, with one notch
+/// scrolling as many logical pixels as its delta, until the pointer moves again.
+fn mouse_wheel(userdata: &WindowData, x: i32, y: i32) -> WindowEvent {
+    let notch = WHEEL_DELTA as i32;
+    let mut w = userdata.window_state_lock();
+    if x % notch == 0 && y % notch == 0 && !w.precise_scroll {
+        return WindowEvent::MouseWheel {
+            device_id: None,
+            delta: MouseScrollDelta::LineDelta(x as f32 / notch as f32, y as f32 / notch as f32),
+            phase: TouchPhase::Moved,
+            momentum: false,
+        };
+    }
+
+    let phase = if mem::replace(&mut w.precise_scroll, true) {
+        TouchPhase::Moved
+    } else {
+        TouchPhase::Started
+    };
+    let delta = LogicalPosition::new(x as f64, y as f64).to_physical(w.scale_factor);
+    WindowEvent::MouseWheel {
+        device_id: None,
+        delta: MouseScrollDelta::PixelDelta(delta),
+        phase,
+        momentum: false,
+    }
+}
+
+/// Move the drag between the windows to the cursor, or drop it there when `released`.
+///
+/// `window` has the mouse capture, so it receives the mouse messages wherever the cursor is.
+unsafe fn update_drag(window: HWND, userdata: &WindowData, released: bool) {
+    let runner = &userdata.event_loop_runner;
+    // The application could start another drag while handling the events.
+    let Some(mut drag) = runner.drag.take() else { return };
+
+    // The events are sent once the drag is back in place, so it can be changed while handling them.
+    let events = RefCell::new(Vec::new());
+    let emit = |window_id, event| events.borrow_mut().push(Event::WindowEvent { window_id, event });
+    let send_events = || {
+        for event in events.take() {
+            userdata.send_event(event);
+        }
+    };
+    // The window doesn't receive `WM_SETCURSOR` while it has the mouse capture.
+    let selected_cursor = || userdata.window_state_lock().mouse.selected_cursor.clone();
+    let scale_factor = userdata.window_state_lock().scale_factor;
+
+    let target = unsafe { window_under_cursor() };
+    if released {
+        drag.released(target, emit);
+        unsafe { SetCursor(selected_cursor().handle(scale_factor)) };
+        send_events();
+    } else if target.is_none() && drag.data().is_shareable() {
+        drag.moved(None, emit);
+        send_events();
+        // OLE drag and drop runs a modal loop until the data is dropped.
+        let effect = unsafe { drag_source::do_drag_drop(window, drag.data()) };
+        drag.ended(effect, emit);
+        send_events();
+    } else {
+        drag.moved(target, emit);
+        let cursor = drag.image().map_or_else(selected_cursor, SelectedCursor::from);
+        unsafe { SetCursor(cursor.handle(scale_factor)) };
+        runner.drag.replace(Some(drag));
+        send_events();
+    }
+}
+
+/// The window of the event loop under the cursor, with the position of the cursor in it.
+unsafe fn window_under_cursor() -> Option<(WindowId, PhysicalPosition<f64>)> {
+    let mut point = POINT { x: 0, y: 0 };
+    if unsafe { GetCursorPos(&mut point) } == FALSE {
+        return None;
+    }
+
+    // Skip the child windows which weren't created by winit, like the ones of a web view.
+    let mut window = unsafe { WindowFromPoint(point) };
+    while window != 0 && !unsafe { is_winit_window(window) } {
+        window = unsafe { GetParent(window) };
+    }
+    if window == 0 {
+        return None;
+    }
+
+    unsafe { ScreenToClient(window, &mut point) };
+    let position = PhysicalPosition::new(point.x as f64, point.y as f64);
+    Some((WindowId::from_raw(window as usize), position))
+}
+
+/// Whether the window was created by this event loop.
+unsafe fn is_winit_window(window: HWND) -> bool {
+    let window_proc = unsafe { super::get_window_long(window, GWLP_WNDPROC) };
+    window_proc == public_window_callback as *const () as isize
+}
+
 unsafe fn gain_active_focus(window: HWND, userdata: &WindowData) {
-    use crate::event::WindowEvent::Focused;
+    use crate::event::WindowEvent::{CursorGrabChanged, Focused};
 
     update_modifiers(window, userdata);
 
+    if let Err(err) = userdata.window_state_lock().update_gamma_ramp() {
+        tracing::warn!("Failed to apply the gamma ramp: {err}");
+    }
+
     userdata.send_event(Event::WindowEvent {
         window_id: WindowId::from_raw(window as usize),
         event: Focused(true),
     });
+
+    #[cfg(feature = "accesskit")]
+    accessibility::set_focused(&userdata.accesskit, true);
+
+    // Confine the cursor again, following `CursorGrabPolicy::ReGrabOnFocus`.
+    let mut window_state = userdata.window_state_lock();
+    if window_state.cursor_grab_policy == CursorGrabPolicy::ReGrabOnFocus
+        && window_state.mouse.cursor_flags().contains(CursorFlags::GRABBED)
+    {
+        match window_state.mouse.set_cursor_flags(window, |_| ()) {
+            Ok(()) => {
+                drop(window_state);
+                userdata.send_event(Event::WindowEvent {
+                    window_id: WindowId::from_raw(window as usize),
+                    event: CursorGrabChanged(CursorGrabMode::Confined),
+                });
+            },
+            Err(err) => tracing::warn!("Failed to confine the cursor again: {err}"),
+        }
+    }
 }
 
 unsafe fn lose_active_focus(window: HWND, userdata: &WindowData) {
-    use crate::event::WindowEvent::{Focused, ModifiersChanged};
+    use crate::event::WindowEvent::{CursorGrabChanged, Focused, ModifiersChanged};
 
     userdata.window_state_lock().modifiers_state = ModifiersState::empty();
+
+    if let Err(err) = userdata.window_state_lock().update_gamma_ramp() {
+        tracing::warn!("Failed to restore the gamma ramp: {err}");
+    }
+
     userdata.send_event(Event::WindowEvent {
         window_id: WindowId::from_raw(window as usize),
         event: ModifiersChanged(ModifiersState::empty().into()),
@@ -1027,6 +1861,21 @@ unsafe fn lose_active_focus(window: HWND, userdata: &WindowData) {
         window_id: WindowId::from_raw(window as usize),
         event: Focused(false),
     });
+
+    #[cfg(feature = "accesskit")]
+    accessibility::set_focused(&userdata.accesskit, false);
+
+    // The cursor clip only applies while focused, so only the event is needed.
+    let window_state = userdata.window_state_lock();
+    if window_state.cursor_grab_policy == CursorGrabPolicy::ReGrabOnFocus
+        && window_state.mouse.cursor_flags().contains(CursorFlags::GRABBED)
+    {
+        drop(window_state);
+        userdata.send_event(Event::WindowEvent {
+            window_id: WindowId::from_raw(window as usize),
+            event: CursorGrabChanged(CursorGrabMode::None),
+        });
+    }
 }
 
 /// Any window whose callback is configured to this function will have its events propagated
@@ -1078,7 +1927,14 @@ pub(super) unsafe extern "system" fn public_window_callback(
 
         userdata.recurse_depth.set(userdata.recurse_depth.get() + 1);
 
-        let result = unsafe { public_window_callback_inner(window, msg, wparam, lparam, userdata) };
+        let runner = &userdata.event_loop_runner;
+        let message = MessageData { hwnd: window, msg, wparam, lparam };
+        let previous_message = runner.current_message.replace(Some(message));
+        let result = match call_wndproc_hook(window, msg, wparam, lparam, userdata) {
+            Some(result) => result,
+            None => unsafe { public_window_callback_inner(window, msg, wparam, lparam, userdata) },
+        };
+        runner.current_message.set(previous_message);
 
         let userdata_removed = userdata.userdata_removed.get();
         let recurse_depth = userdata.recurse_depth.get() - 1;
@@ -1094,6 +1950,76 @@ pub(super) unsafe extern "system" fn public_window_callback(
     result
 }
 
+/// Set the hook of a window, which must be called on the thread of the window.
+pub(super) fn set_wndproc_hook(window: HWND, hook: Option<WndProcHook>) {
+    let userdata = unsafe { super::get_window_long(window, GWL_USERDATA) } as *const WindowData;
+    // The window may have been destroyed before this ran.
+    if let Some(userdata) = unsafe { userdata.as_ref() } {
+        *userdata.wndproc_hook.borrow_mut() = hook.map(|hook| Rc::new(RefCell::new(hook)));
+    }
+}
+
+#[cfg(feature = "accesskit")]
+pub(super) fn set_accesskit_tree_source(
+    window: HWND,
+    source: Box<dyn accesskit::ActivationHandler + Send>,
+) {
+    let userdata = unsafe { super::get_window_long(window, GWL_USERDATA) } as *const WindowData;
+    // The window may have been destroyed before this ran.
+    if let Some(userdata) = unsafe { userdata.as_ref() } {
+        let is_focused = userdata.window_state_lock().has_active_focus();
+        let target_window = userdata.event_loop_runner.thread_msg_target();
+        let window_id = WindowId::from_raw(window as usize);
+        let accesskit = AccessKit::new(window, is_focused, source, move |request| {
+            let event = WindowEvent::AccessKitActionRequested(request);
+            let event = Box::into_raw(Box::new(Event::WindowEvent { window_id, event }));
+            unsafe {
+                PostMessageW(target_window, ACCESSKIT_ACTION_MSG_ID.get(), event as usize, 0)
+            };
+        });
+        *userdata.accesskit.borrow_mut() = Some(accesskit);
+    }
+}
+
+#[cfg(feature = "accesskit")]
+pub(super) fn update_accesskit_tree(window: HWND, update: accesskit::TreeUpdate) {
+    let userdata = unsafe { super::get_window_long(window, GWL_USERDATA) } as *const WindowData;
+    // The window may have been destroyed before this ran.
+    if let Some(userdata) = unsafe { userdata.as_ref() } {
+        accessibility::update(&userdata.accesskit, update);
+    }
+}
+
+pub(super) fn set_motion_coalescing(window: HWND, coalescing: Coalescing) {
+    let userdata = unsafe { super::get_window_long(window, GWL_USERDATA) } as *const WindowData;
+    // The window may have been destroyed before this ran.
+    if let Some(userdata) = unsafe { userdata.as_ref() } {
+        userdata.motion_coalescing.set(coalescing);
+    }
+}
+
+/// The motion coalescing of the window, read when dispatching its events.
+fn motion_coalescing(window: HWND) -> Coalescing {
+    let userdata = unsafe { super::get_window_long(window, GWL_USERDATA) } as *const WindowData;
+    // The events can be dispatched after the window was destroyed.
+    unsafe { userdata.as_ref() }
+        .map_or(Coalescing::None, |userdata| userdata.motion_coalescing.get())
+}
+
+/// Pass a message to the hook of the window, returning its result if it handled the message.
+fn call_wndproc_hook(
+    window: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+    userdata: &WindowData,
+) -> Option<LRESULT> {
+    let hook = userdata.wndproc_hook.borrow().clone()?;
+    // The messages sent by the hook itself to the window aren't passed to it again.
+    let mut hook = hook.try_borrow_mut().ok()?;
+    hook(window, msg, wparam, lparam)
+}
+
 unsafe fn public_window_callback_inner(
     window: HWND,
     msg: u32,
@@ -1103,13 +2029,17 @@ unsafe fn public_window_callback_inner(
 ) -> LRESULT {
     let mut result = ProcResult::DefWindowProc(wparam);
 
-    // Send new modifiers before sending key events.
-    let mods_changed_callback = || match msg {
-        WM_KEYDOWN | WM_SYSKEYDOWN | WM_KEYUP | WM_SYSKEYUP => {
-            update_modifiers(window, userdata);
+    let is_key_msg = matches!(msg, WM_KEYDOWN | WM_SYSKEYDOWN | WM_KEYUP | WM_SYSKEYUP);
+    let modifiers_order = userdata.event_loop_runner.modifiers_order;
+
+    // Send new modifiers before sending key events, if requested.
+    let mods_changed_callback = || {
+        if is_key_msg {
+            if modifiers_order == ModifiersOrder::BeforeKeyboardInput {
+                update_modifiers(window, userdata);
+            }
             result = ProcResult::Value(0);
-        },
-        _ => (),
+        }
     };
     userdata
         .event_loop_runner
@@ -1121,6 +2051,12 @@ unsafe fn public_window_callback_inner(
         let events =
             userdata.key_event_builder.process_message(window, msg, wparam, lparam, &mut result);
         for event in events {
+            if let Some(compose) = event.compose {
+                userdata.send_event(Event::WindowEvent {
+                    window_id: WindowId::from_raw(window as usize),
+                    event: WindowEvent::Compose(compose),
+                });
+            }
             userdata.send_event(Event::WindowEvent {
                 window_id: WindowId::from_raw(window as usize),
                 event: KeyboardInput {
@@ -1136,6 +2072,14 @@ unsafe fn public_window_callback_inner(
         .catch_unwind(keyboard_callback)
         .unwrap_or_else(|| result = ProcResult::Value(-1));
 
+    // Otherwise, send them once the key events are delivered.
+    if is_key_msg && modifiers_order == ModifiersOrder::AfterKeyboardInput {
+        userdata
+            .event_loop_runner
+            .catch_unwind(|| update_modifiers(window, userdata))
+            .unwrap_or_else(|| result = ProcResult::Value(-1));
+    }
+
     // I decided to bind the closure to `callback` and pass it to catch_unwind rather than passing
     // the closure to catch_unwind directly so that the match body indentation wouldn't change and
     // the git blame and history would be preserved.
@@ -1197,6 +2141,45 @@ unsafe fn public_window_callback_inner(
             result = ProcResult::Value(0);
         },
 
+        // The maximize button of the custom title bar, for the snap layouts flyout.
+        WM_NCHITTEST => {
+            let screen_point = POINT {
+                x: super::get_x_lparam(lparam as u32) as i32,
+                y: super::get_y_lparam(lparam as u32) as i32,
+            };
+            let window_state = userdata.window_state_lock();
+            result = match window_state.snap_layout_button_point(window, screen_point) {
+                Some(_) => ProcResult::Value(HTMAXBUTTON as isize),
+                None => ProcResult::DefWindowProc(wparam),
+            };
+        },
+
+        // Deliver the mouse in the maximize button of the custom title bar as in the client area,
+        // instead of letting the system draw and handle a button of its own.
+        WM_NCMOUSEMOVE | WM_NCLBUTTONDOWN | WM_NCLBUTTONUP if wparam == HTMAXBUTTON as usize => {
+            let screen_point = POINT {
+                x: super::get_x_lparam(lparam as u32) as i32,
+                y: super::get_y_lparam(lparam as u32) as i32,
+            };
+            let client_point =
+                userdata.window_state_lock().snap_layout_button_point(window, screen_point);
+            result = match client_point {
+                Some(point) => {
+                    let (client_msg, buttons) = match msg {
+                        WM_NCMOUSEMOVE => (WM_MOUSEMOVE, 0),
+                        WM_NCLBUTTONDOWN => (WM_LBUTTONDOWN, MK_LBUTTON),
+                        _ => (WM_LBUTTONUP, 0),
+                    };
+                    let client_lparam = ((point.y as u16 as u32) << 16) | point.x as u16 as u32;
+                    unsafe {
+                        PostMessageW(window, client_msg, buttons as WPARAM, client_lparam as LPARAM)
+                    };
+                    ProcResult::Value(0)
+                },
+                None => ProcResult::DefWindowProc(wparam),
+            };
+        },
+
         WM_NCLBUTTONDOWN => {
             if wparam == HTCAPTION as _ {
                 unsafe { PostMessageW(window, WM_MOUSEMOVE, 0, lparam) };
@@ -1204,6 +2187,13 @@ unsafe fn public_window_callback_inner(
             result = ProcResult::DefWindowProc(wparam);
         },
 
+        WM_TIMER if wparam == REDRAW_TIMER_ID => {
+            unsafe { KillTimer(window, REDRAW_TIMER_ID) };
+            userdata.window_state_lock().redraw_at = None;
+            unsafe { RedrawWindow(window, ptr::null(), 0, RDW_INTERNALPAINT) };
+            result = ProcResult::Value(0);
+        },
+
         WM_CLOSE => {
             use crate::event::WindowEvent::CloseRequested;
             userdata.send_event(Event::WindowEvent {
@@ -1213,9 +2203,48 @@ unsafe fn public_window_callback_inner(
             result = ProcResult::Value(0);
         },
 
+        // A button of the thumbnail toolbar was clicked, its identifier being its index.
+        WM_COMMAND if super::hiword(wparam as u32) == THBN_CLICKED as u16 => {
+            let index = super::loword(wparam as u32) as usize;
+            let button = userdata.window_state_lock().thumbbar_buttons.get(index).map(|b| b.id);
+            if let Some(id) = button {
+                userdata.send_event(Event::ThumbbarButtonClicked {
+                    window_id: WindowId::from_raw(window as usize),
+                    id,
+                });
+            }
+            result = ProcResult::Value(0);
+        },
+
+        WM_DWMSENDICONICTHUMBNAIL => {
+            let window_state = userdata.window_state_lock();
+            if let Some(preview) = &window_state.taskbar_preview {
+                let max_width = super::hiword(lparam as u32) as u32;
+                let max_height = super::loword(lparam as u32) as u32;
+                taskbar::set_iconic_thumbnail(window, &preview.image, max_width, max_height);
+            }
+            result = ProcResult::Value(0);
+        },
+
+        WM_DWMSENDICONICLIVEPREVIEWBITMAP => {
+            let window_state = userdata.window_state_lock();
+            if let Some(preview) = &window_state.taskbar_preview {
+                let mut rect: RECT = unsafe { mem::zeroed() };
+                unsafe { GetClientRect(window, &mut rect) };
+                let width = (rect.right - rect.left).max(1) as u32;
+                let height = (rect.bottom - rect.top).max(1) as u32;
+                taskbar::set_iconic_live_preview(window, &preview.image, width, height);
+            }
+            result = ProcResult::Value(0);
+        },
+
         WM_DESTROY => {
             use crate::event::WindowEvent::Destroyed;
             unsafe { RevokeDragDrop(window) };
+            if userdata.window_state_lock().app_user_model_id {
+                let _ = util::set_app_user_model_id(window, None);
+            }
+            power_throttling::remove_window(window);
             userdata.send_event(Event::WindowEvent {
                 window_id: WindowId::from_raw(window as usize),
                 event: Destroyed,
@@ -1224,6 +2253,7 @@ unsafe fn public_window_callback_inner(
         },
 
         WM_NCDESTROY => {
+            let _ = keyboard_capture::set_keyboard_capture(window, false);
             unsafe { super::set_window_long(window, GWL_USERDATA, 0) };
             userdata.userdata_removed.set(true);
             result = ProcResult::Value(0);
@@ -1346,6 +2376,8 @@ unsafe fn public_window_callback_inner(
                 });
             }
 
+            update_color_profile(window, userdata, false);
+
             // This is necessary for us to still get sent WM_SIZE.
             result = ProcResult::DefWindowProc(wparam);
         },
@@ -1370,6 +2402,7 @@ unsafe fn public_window_callback_inner(
                     w.set_window_flags_in_place(|f| f.set(WindowFlags::MAXIMIZED, maximized));
                 }
             }
+            power_throttling::set_occluded(window, wparam == SIZE_MINIMIZED as usize);
             userdata.send_event(event);
             result = ProcResult::Value(0);
         },
@@ -1475,6 +2508,10 @@ unsafe fn public_window_callback_inner(
                     window_id: WindowId::from_raw(window as usize),
                     event: WindowEvent::Ime(Ime::Enabled),
                 });
+                userdata.send_event(Event::WindowEvent {
+                    window_id: WindowId::from_raw(window as usize),
+                    event: WindowEvent::Ime(Ime::SurroundingTextRequest),
+                });
             }
 
             result = ProcResult::DefWindowProc(wparam);
@@ -1511,6 +2548,10 @@ unsafe fn public_window_callback_inner(
                             window_id: WindowId::from_raw(window as usize),
                             event: WindowEvent::Ime(Ime::Commit(text)),
                         });
+                        userdata.send_event(Event::WindowEvent {
+                            window_id: WindowId::from_raw(window as usize),
+                            event: WindowEvent::Ime(Ime::SurroundingTextRequest),
+                        });
                     }
                 }
 
@@ -1553,6 +2594,10 @@ unsafe fn public_window_callback_inner(
                             window_id: WindowId::from_raw(window as usize),
                             event: WindowEvent::Ime(Ime::Commit(text)),
                         });
+                        userdata.send_event(Event::WindowEvent {
+                            window_id: WindowId::from_raw(window as usize),
+                            event: WindowEvent::Ime(Ime::SurroundingTextRequest),
+                        });
                     }
                 }
 
@@ -1567,10 +2612,60 @@ unsafe fn public_window_callback_inner(
             result = ProcResult::DefWindowProc(wparam);
         },
 
+        WM_IME_REQUEST => {
+            let window_state = userdata.window_state_lock();
+            let surrounding_text =
+                window_state.ime_surrounding_text.as_ref().filter(|_| window_state.ime_allowed);
+            result = match (wparam as u32, surrounding_text) {
+                (IMR_RECONVERTSTRING, Some(text)) => ProcResult::Value(unsafe {
+                    text.reconvert_string(lparam as *mut RECONVERTSTRING)
+                } as isize),
+                (IMR_CONFIRMRECONVERTSTRING, Some(text)) => ProcResult::Value(unsafe {
+                    text.confirm_reconvert_string(lparam as *const RECONVERTSTRING)
+                }
+                    as isize),
+                _ => ProcResult::DefWindowProc(wparam),
+            };
+        },
+
         WM_IME_SETCONTEXT => {
             // Hide composing text drawn by IME.
             let wparam = wparam & (!ISC_SHOWUICOMPOSITIONWINDOW as usize);
-            result = ProcResult::DefWindowProc(wparam);
+            if userdata.window_state_lock().ime_candidates_drawn_by_app {
+                // Hide the candidate window too, its candidates are sent with `WM_IME_NOTIFY`.
+                let lparam = lparam & !(ISC_SHOWUIALLCANDIDATEWINDOW as isize);
+                result = ProcResult::Value(unsafe { DefWindowProcW(window, msg, wparam, lparam) });
+            } else {
+                result = ProcResult::DefWindowProc(wparam);
+            }
+        },
+
+        WM_IME_NOTIFY
+            if matches!(
+                wparam as u32,
+                IMN_OPENCANDIDATE | IMN_CHANGECANDIDATE | IMN_CLOSECANDIDATE
+            ) =>
+        {
+            let drawn_by_app = {
+                let w = userdata.window_state_lock();
+                w.ime_allowed && w.ime_candidates_drawn_by_app
+            };
+            if drawn_by_app {
+                let (candidates, selected, page) = if wparam as u32 == IMN_CLOSECANDIDATE {
+                    Default::default()
+                } else {
+                    unsafe { ImeContext::current(window).get_candidates() }.unwrap_or_default()
+                };
+                userdata.send_event(Event::WindowEvent {
+                    window_id: WindowId::from_raw(window as usize),
+                    event: WindowEvent::Ime(Ime::Candidates { candidates, selected, page }),
+                });
+
+                // Not calling DefWindowProc to hide the candidate window.
+                result = ProcResult::Value(0);
+            } else {
+                result = ProcResult::DefWindowProc(wparam);
+            }
         },
 
         // this is necessary for us to maintain minimize/restore state
@@ -1665,10 +2760,27 @@ unsafe fn public_window_callback_inner(
             if cursor_moved {
                 update_modifiers(window, userdata);
 
+                // The system doesn't report the end of a precise scroll, so end it once the
+                // pointer moves instead.
+                let scrolling = mem::take(&mut userdata.window_state_lock().precise_scroll);
+                if scrolling {
+                    userdata.send_event(Event::WindowEvent {
+                        window_id: WindowId::from_raw(window as usize),
+                        event: WindowEvent::MouseWheel {
+                            device_id: None,
+                            delta: MouseScrollDelta::PixelDelta(PhysicalPosition::new(0.0, 0.0)),
+                            phase: TouchPhase::Ended,
+                            momentum: false,
+                        },
+                    });
+                }
+
                 userdata.send_event(Event::WindowEvent {
                     window_id: WindowId::from_raw(window as usize),
                     event: PointerMoved { device_id: None, position, source: PointerSource::Mouse },
                 });
+
+                unsafe { update_drag(window, userdata, false) };
             }
 
             result = ProcResult::Value(0);
@@ -1692,40 +2804,27 @@ unsafe fn public_window_callback_inner(
         },
 
         WM_MOUSEWHEEL => {
-            use crate::event::MouseScrollDelta::LineDelta;
-
             let value = (wparam >> 16) as i16;
-            let value = value as f32 / WHEEL_DELTA as f32;
 
             update_modifiers(window, userdata);
 
             userdata.send_event(Event::WindowEvent {
                 window_id: WindowId::from_raw(window as usize),
-                event: WindowEvent::MouseWheel {
-                    device_id: None,
-                    delta: LineDelta(0.0, value),
-                    phase: TouchPhase::Moved,
-                },
+                event: mouse_wheel(userdata, 0, value as i32),
             });
 
             result = ProcResult::Value(0);
         },
 
         WM_MOUSEHWHEEL => {
-            use crate::event::MouseScrollDelta::LineDelta;
-
             let value = (wparam >> 16) as i16;
-            let value = -value as f32 / WHEEL_DELTA as f32; // NOTE: inverted! See https://github.com/rust-windowing/winit/pull/2105/
+            let value = -(value as i32); // NOTE: inverted! See https://github.com/rust-windowing/winit/pull/2105/
 
             update_modifiers(window, userdata);
 
             userdata.send_event(Event::WindowEvent {
                 window_id: WindowId::from_raw(window as usize),
-                event: WindowEvent::MouseWheel {
-                    device_id: None,
-                    delta: LineDelta(value, 0.0),
-                    phase: TouchPhase::Moved,
-                },
+                event: mouse_wheel(userdata, value, 0),
             });
 
             result = ProcResult::Value(0);
@@ -1792,6 +2891,7 @@ unsafe fn public_window_callback_inner(
                     button: Left.into(),
                 },
             });
+            unsafe { update_drag(window, userdata, true) };
             result = ProcResult::Value(0);
         },
 
@@ -1842,6 +2942,7 @@ unsafe fn public_window_callback_inner(
                     button: Right.into(),
                 },
             });
+            unsafe { update_drag(window, userdata, true) };
             result = ProcResult::Value(0);
         },
 
@@ -1892,6 +2993,7 @@ unsafe fn public_window_callback_inner(
                     button: Middle.into(),
                 },
             });
+            unsafe { update_drag(window, userdata, true) };
             result = ProcResult::Value(0);
         },
 
@@ -1954,6 +3056,7 @@ unsafe fn public_window_callback_inner(
                     .into(),
                 },
             });
+            unsafe { update_drag(window, userdata, true) };
             result = ProcResult::Value(0);
         },
 
@@ -2001,6 +3104,13 @@ unsafe fn public_window_callback_inner(
                         id: input.dwID,
                         primary: util::has_flag(input.dwFlags, TOUCHEVENTF_PRIMARY),
                     });
+                    // The contact area is in hundredths of a pixel.
+                    let contact =
+                        util::has_flag(input.dwMask, TOUCHINPUTMASKF_CONTACTAREA).then(|| {
+                            let (width, height) =
+                                (input.cxContact as f64 / 100.0, input.cyContact as f64 / 100.0);
+                            TouchContact::new(width.max(height), width.min(height))
+                        });
 
                     if util::has_flag(input.dwFlags, TOUCHEVENTF_DOWN) {
                         userdata.send_event(Event::WindowEvent {
@@ -2017,7 +3127,7 @@ unsafe fn public_window_callback_inner(
                                 device_id: None,
                                 state: Pressed,
                                 position,
-                                button: Touch { finger_id, force: None },
+                                button: Touch { finger_id, force: None, contact },
                             },
                         });
                     } else if util::has_flag(input.dwFlags, TOUCHEVENTF_UP) {
@@ -2027,7 +3137,7 @@ unsafe fn public_window_callback_inner(
                                 device_id: None,
                                 state: Released,
                                 position,
-                                button: Touch { finger_id, force: None },
+                                button: Touch { finger_id, force: None, contact },
                             },
                         });
                         userdata.send_event(Event::WindowEvent {
@@ -2044,7 +3154,7 @@ unsafe fn public_window_callback_inner(
                             event: WindowEvent::PointerMoved {
                                 device_id: None,
                                 position,
-                                source: PointerSource::Touch { finger_id, force: None },
+                                source: PointerSource::Touch { finger_id, force: None, contact },
                             },
                         });
                     } else {
@@ -2101,6 +3211,23 @@ unsafe fn public_window_callback_inner(
                 }
                 unsafe { pointer_infos.set_len(pointer_info_count) };
 
+                // With the mouse in the pointer messages, the default procedure turns them back
+                // into the mouse messages.
+                if pointer_infos
+                    .first()
+                    .is_some_and(|pointer_info| pointer_info.pointerType == PT_MOUSE)
+                {
+                    result = ProcResult::DefWindowProc(wparam);
+                    return;
+                }
+
+                let pen_infos = match pointer_infos.first() {
+                    Some(pointer_info) if pointer_info.pointerType == PT_PEN => unsafe {
+                        get_pointer_pen_info_history(pointer_id)
+                    },
+                    _ => Vec::new(),
+                };
+
                 // https://docs.microsoft.com/en-us/windows/desktop/api/winuser/nf-winuser-getpointerframeinfohistory
                 // The information retrieved appears in reverse chronological order, with the most
                 // recent entry in the first row of the returned array
@@ -2144,21 +3271,31 @@ unsafe fn public_window_callback_inner(
                         continue;
                     }
 
-                    let force = if let PT_TOUCH = pointer_info.pointerType {
+                    let touch_info = if let PT_TOUCH = pointer_info.pointerType {
                         let mut touch_info = mem::MaybeUninit::uninit();
                         util::GET_POINTER_TOUCH_INFO.and_then(|GetPointerTouchInfo| {
                             match unsafe {
                                 GetPointerTouchInfo(pointer_info.pointerId, touch_info.as_mut_ptr())
                             } {
                                 0 => None,
-                                _ => normalize_pointer_pressure(unsafe {
-                                    touch_info.assume_init().pressure
-                                }),
+                                _ => Some(unsafe { touch_info.assume_init() }),
                             }
                         })
                     } else {
                         None
                     };
+                    let force = touch_info
+                        .and_then(|touch_info| normalize_pointer_pressure(touch_info.pressure));
+                    let contact = touch_info
+                        .filter(|touch_info| {
+                            util::has_flag(touch_info.touchMask, TOUCH_MASK_CONTACTAREA)
+                        })
+                        .map(|touch_info| {
+                            let rect = touch_info.rcContact;
+                            let (width, height) =
+                                ((rect.right - rect.left) as f64, (rect.bottom - rect.top) as f64);
+                            TouchContact::new(width.max(height), width.min(height))
+                        });
 
                     let x = location.x as f64 + x.fract();
                     let y = location.y as f64 + y.fract();
@@ -2190,7 +3327,7 @@ unsafe fn public_window_callback_inner(
                                 state: Pressed,
                                 position,
                                 button: if let PT_TOUCH = pointer_info.pointerType {
-                                    ButtonSource::Touch { finger_id, force }
+                                    ButtonSource::Touch { finger_id, force, contact }
                                 } else {
                                     ButtonSource::Unknown(0)
                                 },
@@ -2204,7 +3341,7 @@ unsafe fn public_window_callback_inner(
                                 state: Released,
                                 position,
                                 button: if let PT_TOUCH = pointer_info.pointerType {
-                                    ButtonSource::Touch { finger_id, force }
+                                    ButtonSource::Touch { finger_id, force, contact }
                                 } else {
                                     ButtonSource::Unknown(0)
                                 },
@@ -2229,7 +3366,7 @@ unsafe fn public_window_callback_inner(
                                 device_id: None,
                                 position,
                                 source: if let PT_TOUCH = pointer_info.pointerType {
-                                    PointerSource::Touch { finger_id, force }
+                                    PointerSource::Touch { finger_id, force, contact }
                                 } else {
                                     PointerSource::Unknown
                                 },
@@ -2238,6 +3375,52 @@ unsafe fn public_window_callback_inner(
                     } else {
                         continue;
                     }
+
+                    if let PT_TOUCH = pointer_info.pointerType {
+                        let phase = if util::has_flag(pointer_info.pointerFlags, POINTER_FLAG_DOWN)
+                        {
+                            TouchPhase::Started
+                        } else if util::has_flag(pointer_info.pointerFlags, POINTER_FLAG_CANCELED) {
+                            TouchPhase::Cancelled
+                        } else if util::has_flag(pointer_info.pointerFlags, POINTER_FLAG_UP) {
+                            TouchPhase::Ended
+                        } else {
+                            TouchPhase::Moved
+                        };
+                        let gesture = userdata.window_state_lock().touch_gesture.update(
+                            pointer_info.pointerId,
+                            position,
+                            phase,
+                        );
+                        if let Some((phase, pinch_delta, rotation_delta)) = gesture {
+                            userdata.send_event(Event::WindowEvent {
+                                window_id,
+                                event: WindowEvent::PinchGesture {
+                                    device_id: None,
+                                    delta: pinch_delta,
+                                    phase,
+                                },
+                            });
+                            userdata.send_event(Event::WindowEvent {
+                                window_id,
+                                event: WindowEvent::RotationGesture {
+                                    device_id: None,
+                                    delta: rotation_delta,
+                                    phase,
+                                },
+                            });
+                        }
+                    }
+
+                    if let Some(pen_info) = pen_infos
+                        .iter()
+                        .find(|pen_info| pen_info.pointerInfo.frameId == pointer_info.frameId)
+                    {
+                        userdata.send_event(Event::WindowEvent {
+                            window_id,
+                            event: pen_input(position, pen_info),
+                        });
+                    }
                 }
 
                 unsafe { SkipPointerFrameMessages(pointer_id) };
@@ -2258,6 +3441,14 @@ unsafe fn public_window_callback_inner(
             result = ProcResult::DefWindowProc(wparam);
         },
 
+        #[cfg(feature = "accesskit")]
+        WM_GETOBJECT => {
+            result = match accessibility::handle_wm_getobject(&userdata.accesskit, wparam, lparam) {
+                Some(lresult) => ProcResult::Value(lresult),
+                None => ProcResult::DefWindowProc(wparam),
+            };
+        },
+
         WM_SETFOCUS => {
             let active_focus_changed = userdata.window_state_lock().set_focused(true);
             if active_focus_changed {
@@ -2274,7 +3465,35 @@ unsafe fn public_window_callback_inner(
             result = ProcResult::Value(0);
         },
 
+        WM_INPUTLANGCHANGE => {
+            use crate::event::WindowEvent::KeyboardLayoutChanged;
+
+            if let Some(layout) = util::keyboard_layout() {
+                userdata.send_event(Event::WindowEvent {
+                    window_id: WindowId::from_raw(window as usize),
+                    event: KeyboardLayoutChanged(layout),
+                });
+            }
+
+            // Let the child windows know about the change.
+            result = ProcResult::DefWindowProc(wparam);
+        },
+
         WM_SETCURSOR => {
+            // Clicking a window disabled by a modal dialog sets the cursor with `HTERROR`.
+            #[cfg(feature = "dialogs")]
+            if super::loword(lparam as u32) == HTERROR as u16
+                && matches!(
+                    super::hiword(lparam as u32) as u32,
+                    WM_LBUTTONDOWN | WM_RBUTTONDOWN | WM_MBUTTONDOWN | WM_XBUTTONDOWN
+                )
+            {
+                userdata.send_event(Event::WindowEvent {
+                    window_id: WindowId::from_raw(window as usize),
+                    event: WindowEvent::ModalInputRejected,
+                });
+            }
+
             let set_cursor_to = {
                 let window_state = userdata.window_state_lock();
                 // The return value for the preceding `WM_NCHITTEST` message is conveniently
@@ -2282,21 +3501,15 @@ unsafe fn public_window_callback_inner(
                 // `WM_MOUSEMOVE` seems to come after `WM_SETCURSOR` for a given cursor movement.
                 let in_client_area = super::loword(lparam as u32) as u32 == HTCLIENT;
                 if in_client_area {
-                    Some(window_state.mouse.selected_cursor.clone())
+                    Some((window_state.mouse.selected_cursor.clone(), window_state.scale_factor))
                 } else {
                     None
                 }
             };
 
             match set_cursor_to {
-                Some(selected_cursor) => {
-                    let hcursor = match selected_cursor {
-                        SelectedCursor::Named(cursor_icon) => unsafe {
-                            LoadCursorW(0, util::to_windows_cursor(cursor_icon))
-                        },
-                        SelectedCursor::Custom(cursor) => cursor.as_raw_handle(),
-                    };
-                    unsafe { SetCursor(hcursor) };
+                Some((selected_cursor, scale_factor)) => {
+                    unsafe { SetCursor(selected_cursor.handle(scale_factor)) };
                     result = ProcResult::Value(0);
                 },
                 None => result = ProcResult::DefWindowProc(wparam),
@@ -2340,7 +3553,7 @@ unsafe fn public_window_callback_inner(
             let new_scale_factor = dpi_to_scale_factor(new_dpi_x);
             let old_scale_factor: f64;
 
-            let (allow_resize, window_flags) = {
+            let (allow_resize, window_flags, window_icon, taskbar_icon) = {
                 let mut window_state = userdata.window_state_lock();
                 old_scale_factor = window_state.scale_factor;
                 window_state.scale_factor = new_scale_factor;
@@ -2353,9 +3566,22 @@ unsafe fn public_window_callback_inner(
                 let allow_resize = window_state.fullscreen.is_none()
                     && !window_state.window_flags().contains(WindowFlags::MAXIMIZED);
 
-                (allow_resize, window_state.window_flags)
+                (
+                    allow_resize,
+                    window_state.window_flags,
+                    window_state.window_icon.clone(),
+                    window_state.taskbar_icon.clone(),
+                )
             };
 
+            // Use the images of the icons for the new DPI.
+            if let Some(window_icon) = window_icon {
+                window_icon.inner.set_for_window_at_dpi(window, IconType::Small, new_dpi_x);
+            }
+            if let Some(taskbar_icon) = taskbar_icon {
+                taskbar_icon.inner.set_for_window_at_dpi(window, IconType::Big, new_dpi_x);
+            }
+
             // New size as suggested by Windows.
             let suggested_rect = unsafe { *(lparam as *const RECT) };
 
@@ -2536,6 +3762,12 @@ unsafe fn public_window_callback_inner(
             result = ProcResult::Value(0);
         },
 
+        // The resolution or the color settings of a monitor changed.
+        WM_DISPLAYCHANGE => {
+            update_color_profile(window, userdata, true);
+            result = ProcResult::DefWindowProc(wparam);
+        },
+
         WM_SETTINGCHANGE => {
             use crate::event::WindowEvent::ThemeChanged;
 
@@ -2557,6 +3789,17 @@ unsafe fn public_window_callback_inner(
             result = ProcResult::DefWindowProc(wparam);
         },
 
+        WM_DWMCOLORIZATIONCOLORCHANGED => {
+            use crate::event::WindowEvent::AccentColorChanged;
+
+            // The new color is provided as `0xAARRGGBB`.
+            userdata.send_event(Event::WindowEvent {
+                window_id: WindowId::from_raw(window as usize),
+                event: AccentColorChanged(util::argb_to_rgba(wparam as u32)),
+            });
+            result = ProcResult::DefWindowProc(wparam);
+        },
+
         _ => {
             if msg == DESTROY_MSG_ID.get() {
                 unsafe { DestroyWindow(window) };
@@ -2571,6 +3814,12 @@ unsafe fn public_window_callback_inner(
                 let window_state = userdata.window_state_lock();
                 unsafe { set_skip_taskbar(window, window_state.skip_taskbar) };
                 result = ProcResult::DefWindowProc(wparam);
+            } else if msg == TASKBAR_BUTTON_CREATED.get() {
+                // The button is new, and so is its toolbar.
+                let mut window_state = userdata.window_state_lock();
+                window_state.thumbbar_added = false;
+                taskbar::update_thumbbar(window, &mut window_state);
+                result = ProcResult::DefWindowProc(wparam);
             } else {
                 result = ProcResult::DefWindowProc(wparam);
             }
@@ -2632,6 +3881,153 @@ unsafe extern "system" fn thread_event_target_callback(
             unsafe { DefWindowProcW(window, msg, wparam, lparam) }
         },
 
+        WM_INPUT_DEVICE_CHANGE => {
+            let handle = lparam as HANDLE;
+            match wparam as u32 {
+                GIDC_ARRIVAL => {
+                    if let Some(device) = raw_input::get_input_device(handle) {
+                        userdata.send_event(Event::InputDeviceAdded(device));
+                    }
+                },
+                GIDC_REMOVAL => {
+                    userdata.send_event(Event::InputDeviceRemoved(wrap_device_id(handle as _)))
+                },
+                _ => (),
+            }
+            0
+        },
+
+        WM_QUERYENDSESSION => {
+            // The session is allowed to end only if the application agreed to exit.
+            userdata.send_event(Event::ExitRequested);
+            userdata.event_loop_runner.exit_code().is_some() as LRESULT
+        },
+
+        WM_POWERBROADCAST => {
+            let event = match wparam as u32 {
+                PBT_APMSUSPEND => Some(PowerEvent::Suspending),
+                PBT_APMRESUMEAUTOMATIC => Some(PowerEvent::Resumed),
+                PBT_APMPOWERSTATUSCHANGE => util::power_state().map(PowerEvent::PowerStateChanged),
+                _ => None,
+            };
+            if let Some(event) = event {
+                userdata.send_event(Event::PowerEvent(event));
+            }
+            TRUE as LRESULT
+        },
+
+        // The regional settings changed, which is also where the locale is chosen.
+        WM_SETTINGCHANGE
+            if lparam != 0 && unsafe { util::wide_eq(lparam as *const u16, "intl") } =>
+        {
+            userdata.send_event(Event::LocaleChanged(util::user_locale()));
+            0
+        },
+
+        // The cursor scheme or the cursor size changed.
+        WM_SETTINGCHANGE if wparam == SPI_SETCURSORS as WPARAM => {
+            if let Some(cursor_theme) = util::cursor_theme() {
+                userdata.send_event(Event::CursorThemeChanged(cursor_theme));
+            }
+            0
+        },
+
+        // The animation, high contrast or transparency settings may have changed. The transparency
+        // setting is only signaled by the broadcast of `ImmersiveColorSet`.
+        WM_SETTINGCHANGE
+            if wparam == SPI_SETCLIENTAREAANIMATION as WPARAM
+                || wparam == SPI_SETHIGHCONTRAST as WPARAM
+                || (lparam != 0
+                    && unsafe { util::wide_eq(lparam as *const u16, "ImmersiveColorSet") }) =>
+        {
+            let preferences = util::system_preferences();
+            if userdata.system_preferences.replace(preferences) != preferences {
+                userdata.send_event(Event::SystemPreferencesChanged(preferences));
+            }
+            0
+        },
+
+        // The text scale factor is changed without a specific parameter, so check it for the other
+        // settings.
+        WM_SETTINGCHANGE => {
+            let factor = util::text_scale_factor();
+            if userdata.text_scale_factor.replace(factor) != factor {
+                userdata.send_event(Event::TextScaleFactorChanged(factor));
+            }
+            0
+        },
+
+        WM_CLIPBOARDUPDATE => {
+            userdata.send_event(Event::ClipboardChanged);
+            0
+        },
+
+        // An instance launched from a task of the jump list sent its arguments.
+        WM_COPYDATA
+            if unsafe { (*(lparam as *const COPYDATASTRUCT)).dwData }
+                == jump_list::TASK_COPYDATA =>
+        {
+            let arguments = unsafe { instance::received_arguments(&*(lparam as *const _)) };
+            userdata.send_event(Event::JumpListTask(arguments));
+            1
+        },
+
+        // A new instance was launched while this one is running, see `with_single_instance`.
+        WM_COPYDATA
+            if unsafe { (*(lparam as *const COPYDATASTRUCT)).dwData }
+                == instance::LAUNCH_COPYDATA =>
+        {
+            // Like on macOS, a launch without files asks to reopen the application.
+            let arguments = unsafe { instance::received_arguments(&*(lparam as *const _)) };
+            if arguments.is_empty() {
+                userdata.send_event(Event::ReopenRequested);
+            } else {
+                userdata.send_event(Event::OpenFiles(
+                    arguments.into_iter().map(PathBuf::from).collect(),
+                ));
+            }
+            1
+        },
+
+        // Monitors were connected, disconnected, rotated, or their resolution or color changed.
+        WM_DISPLAYCHANGE => {
+            let monitors = monitor_states();
+            let previous = userdata.monitors.replace(monitors.clone());
+            for state in &previous {
+                if !monitors.iter().any(|known| known.monitor == state.monitor) {
+                    let monitor = RootMonitorHandle { inner: state.monitor.clone() };
+                    userdata.send_event(Event::MonitorRemoved(monitor));
+                }
+            }
+            for state in &monitors {
+                let monitor = || RootMonitorHandle { inner: state.monitor.clone() };
+                let Some(known) = previous.iter().find(|known| known.monitor == state.monitor)
+                else {
+                    userdata.send_event(Event::MonitorAdded(monitor()));
+                    continue;
+                };
+                if known.color_capabilities != state.color_capabilities {
+                    userdata.send_event(Event::ColorCapabilitiesChanged(monitor()));
+                }
+                if known.orientation != state.orientation {
+                    userdata.send_event(Event::MonitorOrientationChanged(monitor()));
+                }
+                if known.refresh_rate_millihertz != state.refresh_rate_millihertz {
+                    userdata.send_event(Event::MonitorRefreshRateChanged(monitor()));
+                }
+            }
+            unsafe { DefWindowProcW(window, msg, wparam, lparam) }
+        },
+
+        WM_WTSSESSION_CHANGE => {
+            match wparam as u32 {
+                WTS_SESSION_LOCK => userdata.send_event(Event::SessionLocked),
+                WTS_SESSION_UNLOCK => userdata.send_event(Event::SessionUnlocked),
+                _ => (),
+            }
+            0
+        },
+
         _ if msg == USER_EVENT_MSG_ID.get() => {
             // synthesis a placeholder UserEvent, so that if the callback is
             // re-entered it can be buffered for later delivery. the real
@@ -2646,6 +4042,41 @@ unsafe extern "system" fn thread_event_target_callback(
             function();
             0
         },
+        #[cfg(feature = "gamepad")]
+        WM_TIMER if wparam == gamepad::GAMEPAD_TIMER_ID => {
+            let mut events = Vec::new();
+            let interval = userdata.gamepads.borrow_mut().poll(|event| events.push(event));
+            // Rearm the timer before sending the events, since the application may stop the
+            // polling in response to them.
+            gamepad::set_polling(window, Some(interval));
+            for event in events {
+                userdata.send_event(event);
+            }
+            0
+        },
+        #[cfg(feature = "dialogs")]
+        _ if msg == DIALOG_DONE_MSG_ID.get() => {
+            let event: Box<Event> = unsafe { Box::from_raw(wparam as *mut _) };
+            if let Event::FileDialogDone { serial, .. } | Event::MessageBoxDone { serial, .. } =
+                &*event
+            {
+                // Enable the windows disabled for the dialog, before the application handles it.
+                let mut modal_windows = userdata.event_loop_runner.modal_windows.borrow_mut();
+                if let Some(index) = modal_windows.iter().position(|(dialog, _)| dialog == serial) {
+                    for window in modal_windows.remove(index).1 {
+                        unsafe { EnableWindow(window, true.into()) };
+                    }
+                }
+            }
+            userdata.send_event(*event);
+            0
+        },
+        #[cfg(feature = "accesskit")]
+        _ if msg == ACCESSKIT_ACTION_MSG_ID.get() => {
+            let event: Box<Event> = unsafe { Box::from_raw(wparam as *mut _) };
+            userdata.send_event(*event);
+            0
+        },
         _ => unsafe { DefWindowProcW(window, msg, wparam, lparam) },
     };
 
@@ -9,10 +9,16 @@ use windows_sys::Win32::Foundation::HWND;
 
 use super::ControlFlow;
 use crate::dpi::PhysicalSize;
+use crate::drag::DragSession;
 use crate::event::{Event, StartCause, SurfaceSizeWriter, WindowEvent};
+#[cfg(feature = "dialogs")]
+use crate::event_loop::AsyncRequestSerial;
+use crate::event_loop::ModifiersOrder;
+use crate::platform::windows::MessageData;
+use crate::platform_impl::coalescing::MotionCoalescer;
 use crate::platform_impl::platform::event_loop::{WindowData, GWL_USERDATA};
 use crate::platform_impl::platform::get_window_long;
-use crate::window::WindowId;
+use crate::window::{Coalescing, WindowId};
 
 type EventHandler = Cell<Option<Box<dyn FnMut(Event)>>>;
 
@@ -25,12 +31,29 @@ pub(crate) struct EventLoopRunner {
     // can't stall an external loop beyond a frame
     pub(super) interrupt_msg_dispatch: Cell<bool>,
 
+    // The order of `ModifiersChanged` relative to the key events
+    pub(super) modifiers_order: ModifiersOrder,
+
     control_flow: Cell<ControlFlow>,
     exit: Cell<Option<i32>>,
     runner_state: Cell<RunnerState>,
     last_events_cleared: Cell<Instant>,
     event_handler: EventHandler,
-    event_buffer: RefCell<VecDeque<BufferedEvent>>,
+    // The events sent while the handler was busy, with the message they were created from
+    event_buffer: RefCell<VecDeque<(BufferedEvent, Option<MessageData>)>>,
+
+    // The window message being processed, exposed as the platform data of its events
+    pub(super) current_message: Cell<Option<MessageData>>,
+
+    // The drag between the windows, driven by the mouse messages
+    pub(super) drag: RefCell<Option<DragSession>>,
+
+    // The pointer motion held back according to the coalescing of the windows
+    motion_coalescer: RefCell<MotionCoalescer>,
+
+    // The windows disabled by the application-modal dialogs, until they're dismissed
+    #[cfg(feature = "dialogs")]
+    pub(super) modal_windows: RefCell<Vec<(AsyncRequestSerial, Vec<HWND>)>>,
 
     panic_error: Cell<Option<PanicError>>,
 }
@@ -57,10 +80,11 @@ enum BufferedEvent {
 }
 
 impl EventLoopRunner {
-    pub(crate) fn new(thread_msg_target: HWND) -> EventLoopRunner {
+    pub(crate) fn new(thread_msg_target: HWND, modifiers_order: ModifiersOrder) -> EventLoopRunner {
         EventLoopRunner {
             thread_msg_target,
             interrupt_msg_dispatch: Cell::new(false),
+            modifiers_order,
             runner_state: Cell::new(RunnerState::Uninitialized),
             control_flow: Cell::new(ControlFlow::default()),
             exit: Cell::new(None),
@@ -68,6 +92,11 @@ impl EventLoopRunner {
             last_events_cleared: Cell::new(Instant::now()),
             event_handler: Cell::new(None),
             event_buffer: RefCell::new(VecDeque::new()),
+            current_message: Cell::new(None),
+            drag: RefCell::new(None),
+            motion_coalescer: RefCell::new(MotionCoalescer::default()),
+            #[cfg(feature = "dialogs")]
+            modal_windows: RefCell::new(Vec::new()),
         }
     }
 
@@ -102,6 +131,7 @@ impl EventLoopRunner {
         let EventLoopRunner {
             thread_msg_target: _,
             interrupt_msg_dispatch,
+            modifiers_order: _,
             runner_state,
             panic_error,
             control_flow: _,
@@ -109,8 +139,16 @@ impl EventLoopRunner {
             last_events_cleared: _,
             event_handler,
             event_buffer: _,
+            current_message,
+            drag,
+            motion_coalescer,
+            #[cfg(feature = "dialogs")]
+            modal_windows: _,
         } = self;
         interrupt_msg_dispatch.set(false);
+        drag.replace(None);
+        motion_coalescer.take();
+        current_message.set(None);
         runner_state.set(RunnerState::Uninitialized);
         panic_error.set(None);
         exit.set(None);
@@ -210,7 +248,8 @@ impl EventLoopRunner {
         } else if self.should_buffer() {
             // If the runner is already borrowed, we're in the middle of an event loop invocation.
             // Add the event to a buffer to be processed later.
-            self.event_buffer.borrow_mut().push_back(BufferedEvent::from_event(event))
+            let message = self.current_message.get();
+            self.event_buffer.borrow_mut().push_back((BufferedEvent::from_event(event), message))
         } else {
             self.call_event_handler(event);
             self.dispatch_buffered_events();
@@ -222,6 +261,42 @@ impl EventLoopRunner {
     }
 
     fn call_event_handler(&self, event: Event) {
+        let event = match event {
+            Event::WindowEvent { window_id, event } => {
+                let coalescing = super::motion_coalescing(window_id.into_raw() as HWND);
+                let event = self.motion_coalescer.borrow_mut().push(window_id, event, coalescing);
+                let Some(event) = event else {
+                    return;
+                };
+
+                // The motion coalesced per frame is only delivered before the window is redrawn.
+                if coalescing != Coalescing::PerFrame
+                    || matches!(event, WindowEvent::RedrawRequested)
+                {
+                    let pending = self.motion_coalescer.borrow_mut().drain_window(window_id);
+                    for pending in pending {
+                        self.call_handler(Event::WindowEvent { window_id, event: pending });
+                    }
+                }
+
+                Event::WindowEvent { window_id, event }
+            },
+            Event::AboutToWait | Event::LoopExiting => {
+                // Deliver the motion of the windows which weren't redrawn.
+                let pending = self.motion_coalescer.borrow_mut().drain();
+                for (window_id, event) in pending {
+                    self.call_handler(Event::WindowEvent { window_id, event });
+                }
+
+                event
+            },
+            event => event,
+        };
+
+        self.call_handler(event);
+    }
+
+    fn call_handler(&self, event: Event) {
         self.catch_unwind(|| {
             let mut event_handler = self.event_handler.take().expect(
                 "either event handler is re-entrant (likely), or no event handler is registered \
@@ -242,7 +317,11 @@ impl EventLoopRunner {
             // `process_event` will fail.
             let buffered_event_opt = self.event_buffer.borrow_mut().pop_front();
             match buffered_event_opt {
-                Some(e) => e.dispatch_event(|e| self.call_event_handler(e)),
+                Some((e, message)) => {
+                    let current_message = self.current_message.replace(message);
+                    e.dispatch_event(|e| self.call_event_handler(e));
+                    self.current_message.set(current_message);
+                },
                 None => break,
             }
         }
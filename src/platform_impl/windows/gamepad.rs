@@ -0,0 +1,284 @@
+//! Gamepads, read with XInput.
+//!
+//! XInput doesn't send events, so the gamepads are polled with a timer of the thread event target
+//! window.
+
+use std::time::{Duration, Instant};
+use std::{io, mem};
+
+use windows_sys::Win32::Foundation::{ERROR_SUCCESS, HWND};
+use windows_sys::Win32::UI::Input::XboxController::{
+    XInputGetBatteryInformation, XInputGetState, XInputSetState, BATTERY_DEVTYPE_GAMEPAD,
+    BATTERY_LEVEL_EMPTY, BATTERY_LEVEL_FULL, BATTERY_LEVEL_LOW, BATTERY_TYPE_DISCONNECTED,
+    BATTERY_TYPE_UNKNOWN, BATTERY_TYPE_WIRED, XINPUT_BATTERY_INFORMATION, XINPUT_GAMEPAD_A,
+    XINPUT_GAMEPAD_B, XINPUT_GAMEPAD_BACK, XINPUT_GAMEPAD_DPAD_DOWN, XINPUT_GAMEPAD_DPAD_LEFT,
+    XINPUT_GAMEPAD_DPAD_RIGHT, XINPUT_GAMEPAD_DPAD_UP, XINPUT_GAMEPAD_LEFT_SHOULDER,
+    XINPUT_GAMEPAD_LEFT_THUMB, XINPUT_GAMEPAD_RIGHT_SHOULDER, XINPUT_GAMEPAD_RIGHT_THUMB,
+    XINPUT_GAMEPAD_START, XINPUT_GAMEPAD_X, XINPUT_GAMEPAD_Y, XINPUT_STATE, XINPUT_VIBRATION,
+};
+use windows_sys::Win32::UI::WindowsAndMessaging::{KillTimer, SetTimer};
+
+use crate::error::{NotSupportedError, RequestError};
+use crate::event::{DeviceEvent, DeviceId, DeviceInfo, DeviceKind, ElementState, Event};
+use crate::gamepad::{BatteryLevel, GamepadAxis, GamepadButton, GamepadEvent};
+
+/// The identifier of the timer polling the gamepads, on the thread event target window.
+pub(crate) const GAMEPAD_TIMER_ID: usize = 2;
+/// How often the gamepads are polled while one is connected, in milliseconds.
+pub(crate) const POLL_INTERVAL: u32 = 8;
+/// How often the gamepads are polled while none is connected, in milliseconds.
+const IDLE_POLL_INTERVAL: u32 = 1000;
+/// How often the empty slots are checked for a new gamepad, since polling them is slow.
+const CONNECTION_INTERVAL: Duration = Duration::from_secs(1);
+/// How often the batteries of the gamepads are checked.
+const BATTERY_INTERVAL: Duration = Duration::from_secs(60);
+/// The number of gamepads supported by XInput.
+const SLOTS: u32 = 4;
+/// The position from which the triggers are reported as pressed buttons.
+const TRIGGER_THRESHOLD: u8 = 30;
+
+const BUTTONS: [(u16, GamepadButton); 14] = [
+    (XINPUT_GAMEPAD_A, GamepadButton::South),
+    (XINPUT_GAMEPAD_B, GamepadButton::East),
+    (XINPUT_GAMEPAD_X, GamepadButton::West),
+    (XINPUT_GAMEPAD_Y, GamepadButton::North),
+    (XINPUT_GAMEPAD_LEFT_SHOULDER, GamepadButton::LeftShoulder),
+    (XINPUT_GAMEPAD_RIGHT_SHOULDER, GamepadButton::RightShoulder),
+    (XINPUT_GAMEPAD_BACK, GamepadButton::Select),
+    (XINPUT_GAMEPAD_START, GamepadButton::Start),
+    (XINPUT_GAMEPAD_LEFT_THUMB, GamepadButton::LeftStick),
+    (XINPUT_GAMEPAD_RIGHT_THUMB, GamepadButton::RightStick),
+    (XINPUT_GAMEPAD_DPAD_UP, GamepadButton::DPadUp),
+    (XINPUT_GAMEPAD_DPAD_DOWN, GamepadButton::DPadDown),
+    (XINPUT_GAMEPAD_DPAD_LEFT, GamepadButton::DPadLeft),
+    (XINPUT_GAMEPAD_DPAD_RIGHT, GamepadButton::DPadRight),
+];
+
+/// The identifier of the gamepad of an XInput slot.
+///
+/// The identifiers are negative, so they don't collide with the raw input devices.
+fn device_id(slot: u32) -> DeviceId {
+    DeviceId::from_raw(-1 - slot as i64)
+}
+
+fn slot(device_id: DeviceId) -> Option<u32> {
+    let slot = -1 - device_id.into_raw();
+    (0..SLOTS as i64).contains(&slot).then_some(slot as u32)
+}
+
+fn info(slot: u32) -> DeviceInfo {
+    DeviceInfo {
+        id: device_id(slot),
+        kind: DeviceKind::Gamepad,
+        // XInput doesn't tell the names and the USB IDs of the gamepads.
+        name: None,
+        vendor_id: None,
+        product_id: None,
+    }
+}
+
+/// Poll the gamepads every `interval` milliseconds, or stop polling them with `None`.
+pub(crate) fn set_polling(thread_msg_target: HWND, interval: Option<u32>) {
+    if let Some(interval) = interval {
+        unsafe { SetTimer(thread_msg_target, GAMEPAD_TIMER_ID, interval, None) };
+    } else {
+        unsafe { KillTimer(thread_msg_target, GAMEPAD_TIMER_ID) };
+    }
+}
+
+/// The gamepads which are currently connected.
+pub(crate) fn gamepads() -> Vec<DeviceInfo> {
+    (0..SLOTS).filter(|&slot| State::get(slot).is_some()).map(info).collect()
+}
+
+pub(crate) fn set_rumble(device_id: DeviceId, strong: f32, weak: f32) -> Result<(), RequestError> {
+    let slot = slot(device_id)
+        .ok_or_else(|| NotSupportedError::new("the device is not an XInput gamepad"))?;
+    let speed = |magnitude: f32| (magnitude.clamp(0.0, 1.0) * u16::MAX as f32) as u16;
+    let vibration =
+        XINPUT_VIBRATION { wLeftMotorSpeed: speed(strong), wRightMotorSpeed: speed(weak) };
+    match unsafe { XInputSetState(slot, &vibration) } {
+        ERROR_SUCCESS => Ok(()),
+        error => Err(os_error!(io::Error::from_raw_os_error(error as i32)).into()),
+    }
+}
+
+/// The state of the gamepads, compared to the new one each time they're polled.
+#[derive(Debug, Default)]
+pub(crate) struct Gamepads {
+    slots: [Option<Gamepad>; SLOTS as usize],
+    last_connection_check: Option<Instant>,
+    last_battery_check: Option<Instant>,
+}
+
+#[derive(Debug)]
+struct Gamepad {
+    state: State,
+    battery: Option<(BatteryLevel, bool)>,
+}
+
+impl Gamepads {
+    /// Poll the gamepads, sending the changes to `send_event`.
+    ///
+    /// Returns the interval until the next poll, in milliseconds.
+    pub(crate) fn poll(&mut self, mut send_event: impl FnMut(Event)) -> u32 {
+        let now = Instant::now();
+        let due = |last: &mut Option<Instant>, interval| {
+            let due = last.map_or(true, |last| now.duration_since(last) >= interval);
+            if due {
+                *last = Some(now);
+            }
+            due
+        };
+        let check_connections = due(&mut self.last_connection_check, CONNECTION_INTERVAL);
+        let check_batteries = due(&mut self.last_battery_check, BATTERY_INTERVAL);
+
+        for (slot, gamepad) in (0..SLOTS).zip(&mut self.slots) {
+            if gamepad.is_none() && !check_connections {
+                continue;
+            }
+            let device_id = device_id(slot);
+            let gamepad_event = |event| Event::DeviceEvent {
+                device_id: Some(device_id),
+                event: DeviceEvent::Gamepad(event),
+            };
+
+            match (State::get(slot), gamepad.as_mut()) {
+                (Some(state), Some(gamepad)) => {
+                    let mut send_gamepad_event = |event| send_event(gamepad_event(event));
+                    gamepad.state.diff(&state, &mut send_gamepad_event);
+                    gamepad.state = state;
+                    if check_batteries {
+                        gamepad.update_battery(slot, &mut send_gamepad_event);
+                    }
+                },
+                (Some(state), None) => {
+                    send_event(Event::InputDeviceAdded(info(slot)));
+                    let mut send_gamepad_event = |event| send_event(gamepad_event(event));
+                    let mut added = Gamepad { state: State::default(), battery: None };
+                    added.state.diff(&state, &mut send_gamepad_event);
+                    added.state = state;
+                    added.update_battery(slot, &mut send_gamepad_event);
+                    *gamepad = Some(added);
+                },
+                (None, Some(_)) => {
+                    *gamepad = None;
+                    send_event(Event::InputDeviceRemoved(device_id));
+                },
+                (None, None) => (),
+            }
+        }
+
+        if self.slots.iter().any(Option::is_some) {
+            POLL_INTERVAL
+        } else {
+            IDLE_POLL_INTERVAL
+        }
+    }
+}
+
+impl Gamepad {
+    fn update_battery(&mut self, slot: u32, send_event: &mut impl FnMut(GamepadEvent)) {
+        let mut info: XINPUT_BATTERY_INFORMATION = unsafe { mem::zeroed() };
+        if unsafe { XInputGetBatteryInformation(slot, BATTERY_DEVTYPE_GAMEPAD, &mut info) }
+            != ERROR_SUCCESS
+        {
+            return;
+        }
+
+        let level = match info.BatteryType {
+            // The wired gamepads have no battery.
+            BATTERY_TYPE_WIRED | BATTERY_TYPE_DISCONNECTED | BATTERY_TYPE_UNKNOWN => return,
+            _ => match info.BatteryLevel {
+                BATTERY_LEVEL_EMPTY => BatteryLevel::Empty,
+                BATTERY_LEVEL_LOW => BatteryLevel::Low,
+                BATTERY_LEVEL_FULL => BatteryLevel::Full,
+                _ => BatteryLevel::Medium,
+            },
+        };
+        // XInput doesn't tell whether the battery is charging.
+        let battery = (level, false);
+        if self.battery.replace(battery) != Some(battery) {
+            send_event(GamepadEvent::Battery { level, charging: false });
+        }
+    }
+}
+
+/// The state of the buttons and axes of a gamepad.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct State {
+    buttons: u16,
+    left_trigger: u8,
+    right_trigger: u8,
+    /// The left and right sticks, horizontally and vertically.
+    sticks: [i16; 4],
+}
+
+impl State {
+    /// The state of the gamepad in `slot`, if one is connected.
+    fn get(slot: u32) -> Option<Self> {
+        let mut state: XINPUT_STATE = unsafe { mem::zeroed() };
+        if unsafe { XInputGetState(slot, &mut state) } != ERROR_SUCCESS {
+            return None;
+        }
+        let gamepad = state.Gamepad;
+        Some(Self {
+            buttons: gamepad.wButtons,
+            left_trigger: gamepad.bLeftTrigger,
+            right_trigger: gamepad.bRightTrigger,
+            sticks: [gamepad.sThumbLX, gamepad.sThumbLY, gamepad.sThumbRX, gamepad.sThumbRY],
+        })
+    }
+
+    /// Send the changes from `self` to `new`.
+    fn diff(&self, new: &Self, send_event: &mut impl FnMut(GamepadEvent)) {
+        let pressed =
+            |pressed| if pressed { ElementState::Pressed } else { ElementState::Released };
+
+        for (mask, button) in BUTTONS {
+            let was_pressed = self.buttons & mask != 0;
+            let is_pressed = new.buttons & mask != 0;
+            if was_pressed != is_pressed {
+                send_event(GamepadEvent::Button { button, state: pressed(is_pressed) });
+            }
+        }
+
+        let triggers = [
+            (
+                GamepadAxis::LeftTrigger,
+                GamepadButton::LeftTrigger,
+                self.left_trigger,
+                new.left_trigger,
+            ),
+            (
+                GamepadAxis::RightTrigger,
+                GamepadButton::RightTrigger,
+                self.right_trigger,
+                new.right_trigger,
+            ),
+        ];
+        for (axis, button, old, new) in triggers {
+            if old != new {
+                send_event(GamepadEvent::Axis { axis, value: new as f64 / u8::MAX as f64 });
+            }
+            let was_pressed = old > TRIGGER_THRESHOLD;
+            let is_pressed = new > TRIGGER_THRESHOLD;
+            if was_pressed != is_pressed {
+                send_event(GamepadEvent::Button { button, state: pressed(is_pressed) });
+            }
+        }
+
+        let axes = [
+            GamepadAxis::LeftStickX,
+            GamepadAxis::LeftStickY,
+            GamepadAxis::RightStickX,
+            GamepadAxis::RightStickY,
+        ];
+        for ((axis, old), new) in axes.into_iter().zip(self.sticks).zip(new.sticks) {
+            if old != new {
+                let value = (new as f64 / i16::MAX as f64).max(-1.0);
+                send_event(GamepadEvent::Axis { axis, value });
+            }
+        }
+    }
+}
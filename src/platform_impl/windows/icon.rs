@@ -1,6 +1,10 @@
 use std::ffi::c_void;
+#[cfg(feature = "svg")]
+use std::hash::{Hash, Hasher};
 use std::path::Path;
 use std::sync::Arc;
+#[cfg(feature = "svg")]
+use std::sync::Mutex;
 use std::{fmt, io, mem};
 
 use cursor_icon::CursorIcon;
@@ -10,12 +14,14 @@ use windows_sys::Win32::Graphics::Gdi::{
     CreateBitmap, CreateCompatibleBitmap, DeleteObject, GetDC, ReleaseDC, SetBitmapBits,
 };
 use windows_sys::Win32::UI::WindowsAndMessaging::{
-    CreateIcon, CreateIconIndirect, DestroyCursor, DestroyIcon, LoadImageW, SendMessageW, HCURSOR,
-    HICON, ICONINFO, ICON_BIG, ICON_SMALL, IMAGE_ICON, LR_DEFAULTSIZE, LR_LOADFROMFILE, WM_SETICON,
+    CreateIcon, CreateIconIndirect, DestroyCursor, DestroyIcon, LoadCursorW, LoadImageW,
+    SendMessageW, HCURSOR, HICON, ICONINFO, ICON_BIG, ICON_SMALL, IMAGE_ICON, LR_DEFAULTSIZE,
+    LR_LOADFROMFILE, WM_SETICON,
 };
 
+use super::dpi::{hwnd_dpi, BASE_DPI};
 use super::util;
-use crate::cursor::CursorImage;
+use crate::cursor::{closest_to_scale_factor, Cursor, CursorImage, CursorImages};
 use crate::dpi::PhysicalSize;
 use crate::error::RequestError;
 use crate::icon::*;
@@ -27,7 +33,7 @@ impl Pixel {
 }
 
 impl RgbaIcon {
-    fn into_windows_icon(self) -> Result<WinIcon, BadIcon> {
+    fn into_windows_icon(self) -> Result<RaiiIcon, BadIcon> {
         let rgba = self.rgba;
         let pixel_count = rgba.len() / PIXEL_SIZE;
         let mut and_mask = Vec::with_capacity(pixel_count);
@@ -50,34 +56,105 @@ impl RgbaIcon {
             )
         };
         if handle != 0 {
-            Ok(WinIcon::from_handle(handle))
+            Ok(RaiiIcon { handle, width: self.width })
         } else {
             Err(BadIcon::OsError(io::Error::last_os_error()))
         }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub enum IconType {
     Small = ICON_SMALL as isize,
     Big = ICON_BIG as isize,
 }
 
+impl IconType {
+    /// The size of the icon at a scale factor of 1.
+    fn base_size(self) -> u32 {
+        match self {
+            IconType::Small => 16,
+            IconType::Big => 32,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Hash)]
 struct RaiiIcon {
     handle: HICON,
+    /// The width of the image, or 0 for the icons loaded by the system.
+    width: u32,
 }
 
+/// An icon for each image of the icon, in increasing size.
 #[derive(Clone, PartialEq, Eq, Hash)]
 pub struct WinIcon {
-    inner: Arc<RaiiIcon>,
+    inner: Arc<[RaiiIcon]>,
+    /// The SVG icon to rasterize at the size the windows need, with `inner` as fallback.
+    #[cfg(feature = "svg")]
+    svg: Option<WinSvgIcon>,
+}
+
+/// The icons rasterized from an SVG icon, for each size the windows needed.
+#[cfg(feature = "svg")]
+#[derive(Clone, Debug)]
+struct WinSvgIcon {
+    svg: SvgIcon,
+    rasterized: Arc<Mutex<Vec<RaiiIcon>>>,
+}
+
+#[cfg(feature = "svg")]
+impl WinSvgIcon {
+    fn handle(&self, size: u32) -> Option<HICON> {
+        let mut rasterized = self.rasterized.lock().unwrap();
+        if let Some(icon) = rasterized.iter().find(|icon| icon.width == size) {
+            return Some(icon.handle);
+        }
+        let icon = self.svg.rasterize(size).into_windows_icon().ok()?;
+        let handle = icon.handle;
+        rasterized.push(icon);
+        Some(handle)
+    }
+}
+
+#[cfg(feature = "svg")]
+impl PartialEq for WinSvgIcon {
+    fn eq(&self, other: &Self) -> bool {
+        self.svg == other.svg
+    }
+}
+
+#[cfg(feature = "svg")]
+impl Eq for WinSvgIcon {}
+
+#[cfg(feature = "svg")]
+impl Hash for WinSvgIcon {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.svg.hash(state);
+    }
 }
 
 unsafe impl Send for WinIcon {}
 
 impl WinIcon {
+    /// The handle of the largest image.
     pub fn as_raw_handle(&self) -> HICON {
-        self.inner.handle
+        self.inner[self.inner.len() - 1].handle
+    }
+
+    /// The handle of the smallest image at least as large as the icon at the given DPI, or of the
+    /// largest image.
+    fn handle_for_dpi(&self, icon_type: IconType, dpi: u32) -> HICON {
+        let size = icon_type.base_size() * dpi / BASE_DPI;
+        #[cfg(feature = "svg")]
+        if let Some(handle) = self.svg.as_ref().and_then(|svg| svg.handle(size)) {
+            return handle;
+        }
+        self.inner
+            .iter()
+            .find(|icon| icon.width >= size)
+            .unwrap_or(&self.inner[self.inner.len() - 1])
+            .handle
     }
 
     pub fn from_path<P: AsRef<Path>>(
@@ -131,17 +208,45 @@ impl WinIcon {
 
     pub fn from_rgba(rgba: Vec<u8>, width: u32, height: u32) -> Result<Self, BadIcon> {
         let rgba_icon = RgbaIcon::from_rgba(rgba, width, height)?;
-        rgba_icon.into_windows_icon()
+        Ok(Self::from_icons(vec![rgba_icon.into_windows_icon()?]))
+    }
+
+    pub fn from_rgba_set(images: Vec<RgbaIcon>) -> Result<Self, BadIcon> {
+        let icons =
+            images.into_iter().map(RgbaIcon::into_windows_icon).collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::from_icons(icons))
+    }
+
+    #[cfg(feature = "svg")]
+    pub fn from_svg(svg: SvgIcon) -> Result<Self, BadIcon> {
+        // The fallback when rasterizing at the size of a window fails.
+        let fallback = svg.rasterize(IconType::Big.base_size()).into_windows_icon()?;
+        let svg = WinSvgIcon { svg, rasterized: Default::default() };
+        Ok(Self { svg: Some(svg), ..Self::from_icons(vec![fallback]) })
     }
 
     pub fn set_for_window(&self, hwnd: HWND, icon_type: IconType) {
+        self.set_for_window_at_dpi(hwnd, icon_type, unsafe { hwnd_dpi(hwnd) })
+    }
+
+    /// Set the icon with the image for the given DPI, when it's about to change.
+    pub fn set_for_window_at_dpi(&self, hwnd: HWND, icon_type: IconType, dpi: u32) {
+        let handle = self.handle_for_dpi(icon_type, dpi);
         unsafe {
-            SendMessageW(hwnd, WM_SETICON, icon_type as usize, self.as_raw_handle());
+            SendMessageW(hwnd, WM_SETICON, icon_type as usize, handle);
         }
     }
 
     fn from_handle(handle: HICON) -> Self {
-        Self { inner: Arc::new(RaiiIcon { handle }) }
+        Self::from_icons(vec![RaiiIcon { handle, width: 0 }])
+    }
+
+    fn from_icons(icons: Vec<RaiiIcon>) -> Self {
+        Self {
+            inner: icons.into(),
+            #[cfg(feature = "svg")]
+            svg: None,
+        }
     }
 }
 
@@ -153,7 +258,7 @@ impl Drop for RaiiIcon {
 
 impl fmt::Debug for WinIcon {
     fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
-        (*self.inner).fmt(formatter)
+        self.inner.fmt(formatter)
     }
 }
 
@@ -166,7 +271,28 @@ pub fn unset_for_window(hwnd: HWND, icon_type: IconType) {
 #[derive(Debug, Clone)]
 pub enum SelectedCursor {
     Named(CursorIcon),
-    Custom(Arc<RaiiCursor>),
+    Custom(WinCursor),
+}
+
+impl SelectedCursor {
+    /// The cursor to show in a window with the given scale factor.
+    pub fn handle(&self, scale_factor: f64) -> HCURSOR {
+        match self {
+            Self::Named(cursor_icon) => unsafe {
+                LoadCursorW(0, util::to_windows_cursor(*cursor_icon))
+            },
+            Self::Custom(cursor) => cursor.for_scale_factor(scale_factor).as_raw_handle(),
+        }
+    }
+}
+
+impl From<&Cursor> for SelectedCursor {
+    fn from(cursor: &Cursor) -> Self {
+        match cursor {
+            Cursor::Icon(icon) => Self::Named(*icon),
+            Cursor::Custom(cursor) => Self::Custom(cursor.inner.clone()),
+        }
+    }
 }
 
 impl Default for SelectedCursor {
@@ -175,11 +301,22 @@ impl Default for SelectedCursor {
     }
 }
 
+/// A cursor for each image of a custom cursor, in increasing size.
 #[derive(Clone, Debug, Hash, Eq, PartialEq)]
-pub struct WinCursor(pub(super) Arc<RaiiCursor>);
+pub struct WinCursor(pub(super) Arc<[RaiiCursor]>);
 
 impl WinCursor {
-    pub(crate) fn new(image: &CursorImage) -> Result<Self, RequestError> {
+    pub(crate) fn new(images: &CursorImages) -> Result<Self, RequestError> {
+        let cursors = images.images().iter().map(Self::create).collect::<Result<Vec<_>, _>>()?;
+        Ok(Self(cursors.into()))
+    }
+
+    /// The cursor closest to the scale factor of the window.
+    pub(crate) fn for_scale_factor(&self, scale_factor: f64) -> &RaiiCursor {
+        closest_to_scale_factor(&self.0, |cursor| cursor.width, scale_factor)
+    }
+
+    fn create(image: &CursorImage) -> Result<RaiiCursor, RequestError> {
         let mut bgra = image.rgba.clone();
         bgra.chunks_exact_mut(4).for_each(|chunk| chunk.swap(0, 2));
 
@@ -224,7 +361,7 @@ impl WinCursor {
                 return Err(os_error!(io::Error::last_os_error()).into());
             }
 
-            Ok(Self(Arc::new(RaiiCursor { handle })))
+            Ok(RaiiCursor { handle, width: image.width })
         }
     }
 }
@@ -232,6 +369,7 @@ impl WinCursor {
 #[derive(Debug, Hash, Eq, PartialEq)]
 pub struct RaiiCursor {
     handle: HCURSOR,
+    width: u16,
 }
 
 impl Drop for RaiiCursor {
@@ -1,19 +1,27 @@
 use std::ffi::{c_void, OsString};
+use std::mem;
+use std::ops::Range;
 use std::os::windows::prelude::OsStringExt;
 use std::ptr::null_mut;
 
 use windows_sys::Win32::Foundation::{POINT, RECT};
 use windows_sys::Win32::Globalization::HIMC;
 use windows_sys::Win32::UI::Input::Ime::{
-    ImmAssociateContextEx, ImmGetCompositionStringW, ImmGetContext, ImmReleaseContext,
-    ImmSetCandidateWindow, ImmSetCompositionWindow, ATTR_TARGET_CONVERTED,
-    ATTR_TARGET_NOTCONVERTED, CANDIDATEFORM, CFS_EXCLUDE, CFS_POINT, COMPOSITIONFORM, GCS_COMPATTR,
-    GCS_COMPSTR, GCS_CURSORPOS, GCS_RESULTSTR, IACE_CHILDREN, IACE_DEFAULT,
+    ImmAssociateContextEx, ImmGetCandidateListW, ImmGetCompositionStringW, ImmGetContext,
+    ImmReleaseContext, ImmSetCandidateWindow, ImmSetCompositionWindow, ATTR_TARGET_CONVERTED,
+    ATTR_TARGET_NOTCONVERTED, CANDIDATEFORM, CANDIDATELIST, CFS_EXCLUDE, CFS_POINT,
+    COMPOSITIONFORM, GCS_COMPATTR, GCS_COMPSTR, GCS_CURSORPOS, GCS_RESULTSTR, IACE_CHILDREN,
+    IACE_DEFAULT, RECONVERTSTRING,
+};
+use windows_sys::Win32::UI::TextServices::{
+    IS_DEFAULT, IS_DIGITS, IS_EMAIL_SMTPEMAILADDRESS, IS_PASSWORD, IS_URL,
 };
 use windows_sys::Win32::UI::WindowsAndMessaging::{GetSystemMetrics, SM_IMMENABLED};
 
+use super::util::SET_INPUT_SCOPE;
 use crate::dpi::{Position, Size};
 use crate::platform::windows::HWND;
+use crate::window::ImePurpose;
 
 pub struct ImeContext {
     hwnd: HWND,
@@ -106,24 +114,45 @@ impl ImeContext {
         }
     }
 
-    pub unsafe fn set_ime_cursor_area(&self, spot: Position, size: Size, scale_factor: f64) {
+    /// Place the composition and candidate windows at the cursor area, with the candidate window
+    /// outside of the exclusion area.
+    pub unsafe fn set_ime_cursor_area(
+        &self,
+        spot: Position,
+        size: Size,
+        exclusion: Option<(Position, Size)>,
+        scale_factor: f64,
+    ) {
         if !unsafe { ImeContext::system_has_ime() } {
             return;
         }
 
-        let (x, y) = spot.to_physical::<i32>(scale_factor).into();
-        let (width, height): (i32, i32) = size.to_physical::<i32>(scale_factor).into();
-        let rc_area = RECT { left: x, top: y, right: x + width, bottom: y + height };
+        let to_rect = |position: Position, size: Size| {
+            let (x, y): (i32, i32) = position.to_physical::<i32>(scale_factor).into();
+            let (width, height): (i32, i32) = size.to_physical::<i32>(scale_factor).into();
+            RECT { left: x, top: y, right: x + width, bottom: y + height }
+        };
+        let cursor_area = to_rect(spot, size);
+        let exclusion_area = match exclusion.map(|(position, size)| to_rect(position, size)) {
+            Some(area) => RECT {
+                left: area.left.min(cursor_area.left),
+                top: area.top.min(cursor_area.top),
+                right: area.right.max(cursor_area.right),
+                bottom: area.bottom.max(cursor_area.bottom),
+            },
+            None => cursor_area,
+        };
+        let (x, y) = (cursor_area.left, cursor_area.top);
         let candidate_form = CANDIDATEFORM {
             dwIndex: 0,
             dwStyle: CFS_EXCLUDE,
             ptCurrentPos: POINT { x, y },
-            rcArea: rc_area,
+            rcArea: exclusion_area,
         };
         let composition_form = COMPOSITIONFORM {
             dwStyle: CFS_POINT,
-            ptCurrentPos: POINT { x, y: y + height },
-            rcArea: rc_area,
+            ptCurrentPos: POINT { x, y: cursor_area.bottom },
+            rcArea: cursor_area,
         };
 
         unsafe {
@@ -132,6 +161,45 @@ impl ImeContext {
         }
     }
 
+    /// The candidates of the first candidate list, with the selected candidate and the current
+    /// page.
+    pub unsafe fn get_candidates(&self) -> Option<(Vec<String>, Option<usize>, Range<usize>)> {
+        let size = unsafe { ImmGetCandidateListW(self.himc, 0, null_mut(), 0) };
+        if (size as usize) < mem::size_of::<CANDIDATELIST>() {
+            return None;
+        }
+
+        // The list is followed by the offsets of the strings and the strings.
+        let mut buf = vec![0u32; (size as usize).div_ceil(4)];
+        let list = buf.as_mut_ptr() as *mut CANDIDATELIST;
+        if unsafe { ImmGetCandidateListW(self.himc, 0, list, size) } == 0 {
+            return None;
+        }
+        let list = unsafe { &*list };
+        let bytes = unsafe { std::slice::from_raw_parts(buf.as_ptr() as *const u8, size as usize) };
+        // The offsets follow the six other fields of the list.
+        let offsets = 6 * mem::size_of::<u32>();
+
+        let mut candidates = Vec::with_capacity(list.dwCount as usize);
+        for index in 0..list.dwCount as usize {
+            let offset = bytes.get(offsets + index * 4..offsets + index * 4 + 4)?;
+            let start = u32::from_ne_bytes([offset[0], offset[1], offset[2], offset[3]]) as usize;
+            let text: Vec<u16> = bytes
+                .get(start..)?
+                .chunks_exact(2)
+                .map(|unit| u16::from_ne_bytes([unit[0], unit[1]]))
+                .take_while(|&unit| unit != 0)
+                .collect();
+            candidates.push(String::from_utf16_lossy(&text));
+        }
+
+        let count = candidates.len();
+        let selected = (list.dwSelection as usize) < count;
+        let page_start = (list.dwPageStart as usize).min(count);
+        let page_end = (page_start + list.dwPageSize as usize).min(count);
+        Some((candidates, selected.then_some(list.dwSelection as usize), page_start..page_end))
+    }
+
     pub unsafe fn set_ime_allowed(hwnd: HWND, allowed: bool) {
         if !unsafe { ImeContext::system_has_ime() } {
             return;
@@ -144,6 +212,19 @@ impl ImeContext {
         }
     }
 
+    pub unsafe fn set_ime_purpose(hwnd: HWND, purpose: ImePurpose) {
+        let scope = match purpose {
+            ImePurpose::Normal | ImePurpose::Terminal => IS_DEFAULT,
+            ImePurpose::Password => IS_PASSWORD,
+            ImePurpose::Digits => IS_DIGITS,
+            ImePurpose::Email => IS_EMAIL_SMTPEMAILADDRESS,
+            ImePurpose::Url => IS_URL,
+        };
+        if let Some(set_input_scope) = *SET_INPUT_SCOPE {
+            unsafe { set_input_scope(hwnd, scope) };
+        }
+    }
+
     unsafe fn system_has_ime() -> bool {
         unsafe { GetSystemMetrics(SM_IMMENABLED) != 0 }
     }
@@ -154,3 +235,60 @@ impl Drop for ImeContext {
         unsafe { ImmReleaseContext(self.hwnd, self.himc) };
     }
 }
+
+/// The text around the cursor set by the application, used to reconvert the selection.
+#[derive(Clone)]
+pub struct SurroundingText {
+    text: Vec<u16>,
+    /// The selection, in UTF-16 code units.
+    selection: Range<u32>,
+}
+
+impl SurroundingText {
+    /// Returns `None` when the selection isn't on char boundaries.
+    pub fn new(text: &str, cursor: usize, anchor: usize) -> Option<Self> {
+        let utf16_index = |index| text.get(..index).map(|text| text.encode_utf16().count() as u32);
+        let (cursor, anchor) = (utf16_index(cursor)?, utf16_index(anchor)?);
+        let selection = cursor.min(anchor)..cursor.max(anchor);
+        Some(Self { text: text.encode_utf16().collect(), selection })
+    }
+
+    /// Answer `IMR_RECONVERTSTRING` with the `RECONVERTSTRING` followed by the text, returning its
+    /// size, or only the size when `reconvert` is null.
+    ///
+    /// Only a selection is reconverted, so this returns 0 when nothing is selected.
+    pub unsafe fn reconvert_string(&self, reconvert: *mut RECONVERTSTRING) -> usize {
+        if self.selection.is_empty() {
+            return 0;
+        }
+
+        let header_size = mem::size_of::<RECONVERTSTRING>();
+        let size = header_size + self.text.len() * mem::size_of::<u16>();
+        if !reconvert.is_null() {
+            let selection_len = self.selection.end - self.selection.start;
+            unsafe {
+                reconvert.write(RECONVERTSTRING {
+                    dwSize: size as u32,
+                    dwVersion: 0,
+                    dwStrLen: self.text.len() as u32,
+                    dwStrOffset: header_size as u32,
+                    dwCompStrLen: selection_len,
+                    dwCompStrOffset: self.selection.start * mem::size_of::<u16>() as u32,
+                    dwTargetStrLen: selection_len,
+                    dwTargetStrOffset: self.selection.start * mem::size_of::<u16>() as u32,
+                });
+                let text = reconvert.cast::<u8>().add(header_size).cast::<u16>();
+                text.copy_from_nonoverlapping(self.text.as_ptr(), self.text.len());
+            }
+        }
+        size
+    }
+
+    /// Answer `IMR_CONFIRMRECONVERTSTRING`, accepting the reconversion only when the IME kept the
+    /// selection, since the committed text replaces it.
+    pub unsafe fn confirm_reconvert_string(&self, reconvert: *const RECONVERTSTRING) -> bool {
+        let reconvert = unsafe { &*reconvert };
+        reconvert.dwCompStrOffset == self.selection.start * mem::size_of::<u16>() as u32
+            && reconvert.dwCompStrLen == self.selection.end - self.selection.start
+    }
+}
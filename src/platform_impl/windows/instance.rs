@@ -0,0 +1,77 @@
+//! The delivery of the arguments of a launch to the running instance of the application with
+//! `WM_COPYDATA`, used by the tasks of the jump list and by single instance applications.
+
+use std::ffi::{c_void, OsStr};
+use std::path::PathBuf;
+use std::{env, slice};
+
+use windows_sys::Win32::Foundation::{HWND, LPARAM};
+use windows_sys::Win32::System::DataExchange::COPYDATASTRUCT;
+use windows_sys::Win32::UI::WindowsAndMessaging::{
+    AllowSetForegroundWindow, FindWindowW, GetWindowThreadProcessId, SendMessageW, SetWindowTextW,
+    WM_COPYDATA,
+};
+
+use super::event_loop::THREAD_EVENT_TARGET_WINDOW_CLASS;
+use super::util::encode_wide;
+
+/// The `dwData` of the `WM_COPYDATA` messages carrying the files a new instance was launched with.
+pub(crate) const LAUNCH_COPYDATA: usize = 0x7769_6e6c;
+
+/// Let the instances launched later find this one by its executable.
+pub(crate) fn register(thread_msg_target: HWND) {
+    let Ok(exe) = env::current_exe() else { return };
+    let title = encode_wide(&exe);
+    unsafe { SetWindowTextW(thread_msg_target, title.as_ptr()) };
+}
+
+/// Send the arguments to the running instance, returning whether it received them.
+pub(crate) fn send_to_running_instance(kind: usize, arguments: &[impl AsRef<OsStr>]) -> bool {
+    let Ok(exe) = env::current_exe() else { return false };
+    let title = encode_wide(&exe);
+    let target = unsafe { FindWindowW(THREAD_EVENT_TARGET_WINDOW_CLASS.as_ptr(), title.as_ptr()) };
+    if target == 0 {
+        return false;
+    }
+
+    // Let the running instance bring its windows to the foreground.
+    let mut process_id = 0;
+    unsafe { GetWindowThreadProcessId(target, &mut process_id) };
+    unsafe { AllowSetForegroundWindow(process_id) };
+
+    let data: Vec<u16> = arguments.iter().flat_map(encode_wide).collect();
+    let copy_data = COPYDATASTRUCT {
+        dwData: kind,
+        cbData: (data.len() * 2) as u32,
+        lpData: data.as_ptr() as *mut c_void,
+    };
+    unsafe { SendMessageW(target, WM_COPYDATA, 0, &copy_data as *const _ as LPARAM) != 0 }
+}
+
+/// The arguments sent by [`send_to_running_instance`].
+pub(crate) unsafe fn received_arguments(copy_data: &COPYDATASTRUCT) -> Vec<String> {
+    if copy_data.cbData == 0 {
+        return Vec::new();
+    }
+    let data = unsafe {
+        slice::from_raw_parts(copy_data.lpData as *const u16, copy_data.cbData as usize / 2)
+    };
+    let data = data.strip_suffix(&[0u16]).unwrap_or(data);
+    data.split(|&unit| unit == 0).map(String::from_utf16_lossy).collect()
+}
+
+/// The files the process was launched with, made absolute.
+///
+/// The shell passes the files opened with the application as its arguments, so the command line
+/// is only taken as a list of files when all the arguments are existing paths.
+pub(crate) fn launch_files() -> Vec<PathBuf> {
+    let current_dir = env::current_dir().unwrap_or_default();
+    let files: Option<Vec<_>> = env::args_os()
+        .skip(1)
+        .map(|argument| {
+            let path = current_dir.join(argument);
+            path.exists().then_some(path)
+        })
+        .collect();
+    files.unwrap_or_default()
+}
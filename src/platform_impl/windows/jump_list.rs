@@ -0,0 +1,181 @@
+//! The jump list of the taskbar button set with `ICustomDestinationList`. The arguments of its
+//! tasks are delivered to the running instance by the `instance` module.
+
+use std::path::Path;
+use std::{env, io, ptr};
+
+use windows_sys::core::GUID;
+use windows_sys::Win32::Foundation::HWND;
+use windows_sys::Win32::System::Com::{CoCreateInstance, CLSCTX_INPROC_SERVER};
+
+use super::definitions::{
+    CLSID_DestinationList, CLSID_EnumerableObjectCollection, CLSID_ShellLink,
+    ICustomDestinationList, IID_ICustomDestinationList, IID_IObjectArray, IID_IObjectCollection,
+    IID_IPropertyStore, IID_IShellLinkW, IObjectArray, IObjectCollection, IPropertyStore,
+    IShellLinkW, PKEY_Title, KDC_FREQUENT, KDC_RECENT, PROPVARIANT_LPWSTR, VT_LPWSTR,
+};
+use super::instance;
+use super::util::{check, encode_wide, ComPtr};
+use super::window::com_initialized;
+use crate::error::RequestError;
+use crate::platform::windows::{JumpList, JumpListCategory, JumpListTask};
+
+/// The argument preceding the ones of the task in the command line of the launched instance.
+const TASK_MARKER: &str = "--winit-jump-list-task";
+
+/// The `dwData` of the `WM_COPYDATA` messages carrying the arguments of a task.
+pub(crate) const TASK_COPYDATA: usize = 0x7769_6e6a;
+
+pub(crate) fn set_jump_list(
+    thread_msg_target: HWND,
+    jump_list: &JumpList,
+) -> Result<(), RequestError> {
+    com_initialized();
+    let exe = env::current_exe().map_err(|error| os_error!(error))?;
+    unsafe { commit_jump_list(&exe, jump_list) }.map_err(|error| os_error!(error))?;
+
+    // The instances launched from the tasks find this one by its executable.
+    instance::register(thread_msg_target);
+    Ok(())
+}
+
+/// The arguments of the task the process was launched from, if any.
+pub(crate) fn launch_task_arguments() -> Option<Vec<String>> {
+    let mut arguments = env::args_os().skip(1);
+    if arguments.next()? != TASK_MARKER {
+        return None;
+    }
+    Some(arguments.map(|argument| argument.to_string_lossy().into_owned()).collect())
+}
+
+unsafe fn create<T>(clsid: &GUID, iid: &GUID) -> io::Result<ComPtr<T>> {
+    unsafe {
+        ComPtr::from_out(|object| {
+            CoCreateInstance(clsid, ptr::null_mut(), CLSCTX_INPROC_SERVER, iid, object)
+        })
+    }
+}
+
+unsafe fn commit_jump_list(exe: &Path, jump_list: &JumpList) -> io::Result<()> {
+    let list: ComPtr<ICustomDestinationList> =
+        unsafe { create(&CLSID_DestinationList, &IID_ICustomDestinationList)? };
+    let vtbl = unsafe { &*(*list.0).lpVtbl };
+
+    let mut min_slots = 0;
+    let _removed: ComPtr<IObjectArray> = unsafe {
+        ComPtr::from_out(|removed| {
+            (vtbl.BeginList)(list.0, &mut min_slots, &IID_IObjectArray, removed)
+        })?
+    };
+
+    let result = unsafe { append_items(list.0, exe, jump_list) }
+        .and_then(|()| check(unsafe { (vtbl.CommitList)(list.0) }));
+    if result.is_err() {
+        unsafe { (vtbl.AbortList)(list.0) };
+    }
+    result
+}
+
+unsafe fn append_items(
+    list: *mut ICustomDestinationList,
+    exe: &Path,
+    jump_list: &JumpList,
+) -> io::Result<()> {
+    let vtbl = unsafe { &*(*list).lpVtbl };
+
+    if let Some(category) = jump_list.category {
+        let category = match category {
+            JumpListCategory::Recent => KDC_RECENT,
+            JumpListCategory::Frequent => KDC_FREQUENT,
+        };
+        // This fails when the application has no registered file type, which shouldn't prevent
+        // showing the tasks.
+        if let Err(error) = check(unsafe { (vtbl.AppendKnownCategory)(list, category) }) {
+            tracing::warn!("failed to add the jump list category: {error}");
+        }
+    }
+
+    if !jump_list.tasks.is_empty() {
+        let collection: ComPtr<IObjectCollection> =
+            unsafe { create(&CLSID_EnumerableObjectCollection, &IID_IObjectCollection)? };
+        let collection_vtbl = unsafe { &*(*collection.0).lpVtbl };
+        for task in &jump_list.tasks {
+            let link = unsafe { create_task_link(exe, task)? };
+            check(unsafe { (collection_vtbl.AddObject)(collection.0, link.0.cast()) })?;
+        }
+        check(unsafe { (vtbl.AddUserTasks)(list, collection.0.cast()) })?;
+    }
+
+    Ok(())
+}
+
+unsafe fn create_task_link(exe: &Path, task: &JumpListTask) -> io::Result<ComPtr<IShellLinkW>> {
+    let link: ComPtr<IShellLinkW> = unsafe { create(&CLSID_ShellLink, &IID_IShellLinkW)? };
+    let vtbl = unsafe { &*(*link.0).lpVtbl };
+
+    let path = encode_wide(exe);
+    let arguments = encode_wide(command_line(&task.arguments));
+    unsafe {
+        check((vtbl.SetPath)(link.0, path.as_ptr()))?;
+        check((vtbl.SetArguments)(link.0, arguments.as_ptr()))?;
+        check((vtbl.SetIconLocation)(link.0, path.as_ptr(), 0))?;
+    }
+    if let Some(description) = &task.description {
+        let description = encode_wide(description);
+        check(unsafe { (vtbl.SetDescription)(link.0, description.as_ptr()) })?;
+    }
+
+    // The title of the task is a property of the link.
+    let store: ComPtr<IPropertyStore> = unsafe {
+        ComPtr::from_out(|store| {
+            (vtbl.parent.QueryInterface)(link.0.cast(), &IID_IPropertyStore, store)
+        })?
+    };
+    let store_vtbl = unsafe { &*(*store.0).lpVtbl };
+    let title = encode_wide(&task.title);
+    let value = PROPVARIANT_LPWSTR {
+        vt: VT_LPWSTR,
+        wReserved1: 0,
+        wReserved2: 0,
+        wReserved3: 0,
+        pwszVal: title.as_ptr(),
+        padding: 0,
+    };
+    unsafe {
+        // The value is copied by the store.
+        check((store_vtbl.SetValue)(store.0, &PKEY_Title, &value))?;
+        check((store_vtbl.Commit)(store.0))?;
+    }
+
+    Ok(link)
+}
+
+/// The command line of the arguments after the marker, quoted to be parsed back by
+/// `CommandLineToArgvW`.
+fn command_line(arguments: &[String]) -> String {
+    let mut command_line = String::from(TASK_MARKER);
+    for argument in arguments {
+        command_line.push(' ');
+        if !argument.is_empty() && !argument.contains([' ', '\t', '\n', '\u{b}', '"']) {
+            command_line.push_str(argument);
+            continue;
+        }
+
+        command_line.push('"');
+        let mut backslashes = 0;
+        for c in argument.chars() {
+            if c == '\\' {
+                backslashes += 1;
+                continue;
+            }
+            // The backslashes are only escaped before a quote.
+            let escaped = if c == '"' { backslashes * 2 + 1 } else { backslashes };
+            command_line.extend(std::iter::repeat('\\').take(escaped));
+            command_line.push(c);
+            backslashes = 0;
+        }
+        command_line.extend(std::iter::repeat('\\').take(backslashes * 2));
+        command_line.push('"');
+    }
+    command_line
+}
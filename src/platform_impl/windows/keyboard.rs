@@ -26,7 +26,7 @@ use windows_sys::Win32::UI::WindowsAndMessaging::{
     WM_KEYUP, WM_KILLFOCUS, WM_SETFOCUS, WM_SYSCHAR, WM_SYSDEADCHAR, WM_SYSKEYDOWN, WM_SYSKEYUP,
 };
 
-use crate::event::{ElementState, KeyEvent};
+use crate::event::{Compose, ElementState, KeyEvent};
 use crate::keyboard::{Key, KeyCode, KeyLocation, NamedKey, NativeKey, NativeKeyCode, PhysicalKey};
 use crate::platform_impl::platform::event_loop::ProcResult;
 use crate::platform_impl::platform::keyboard_layout::{
@@ -39,6 +39,8 @@ pub type ExScancode = u16;
 pub struct MessageAsKeyEvent {
     pub event: KeyEvent,
     pub is_synthetic: bool,
+    /// The change of the dead key sequence, delivered before the key event.
+    pub compose: Option<Compose>,
 }
 
 /// Stores information required to make `KeyEvent`s.
@@ -63,11 +65,17 @@ pub struct MessageAsKeyEvent {
 /// text input. The "sequence" only consists of one WM_KEYUP / WM_SYSKEYUP event.
 pub struct KeyEventBuilder {
     event_info: Mutex<Option<PartialKeyEventInfo>>,
+    /// The characters of the dead keys waiting for the next character.
+    dead_chars: Mutex<Option<String>>,
     pending: PendingEventQueue<MessageAsKeyEvent>,
 }
 impl Default for KeyEventBuilder {
     fn default() -> Self {
-        KeyEventBuilder { event_info: Mutex::new(None), pending: Default::default() }
+        KeyEventBuilder {
+            event_info: Mutex::new(None),
+            dead_chars: Mutex::new(None),
+            pending: Default::default(),
+        }
     }
 }
 impl KeyEventBuilder {
@@ -147,7 +155,7 @@ impl KeyEventBuilder {
                         let ev = event_info.finalize();
                         return MatchResult::MessagesToDispatch(self.pending.complete_pending(
                             pending_token,
-                            MessageAsKeyEvent { event: ev, is_synthetic: false },
+                            MessageAsKeyEvent { event: ev, is_synthetic: false, compose: None },
                         ));
                     }
                     MatchResult::TokenToRemove(pending_token)
@@ -159,9 +167,26 @@ impl KeyEventBuilder {
                     // to this key press
                     let event_info = self.event_info.lock().unwrap().take().unwrap();
                     let ev = event_info.finalize();
+                    // The dead keys can be chained by some layouts.
+                    let dead_char = char::from_u32(wparam as u32);
+                    let compose = match &mut *self.dead_chars.lock().unwrap() {
+                        Some(dead_chars) => {
+                            dead_chars.extend(dead_char);
+                            Compose::Updated(dead_chars.clone())
+                        },
+                        dead_chars => {
+                            let started: String = dead_char.into_iter().collect();
+                            *dead_chars = Some(started.clone());
+                            Compose::Started(started)
+                        },
+                    };
                     MatchResult::MessagesToDispatch(self.pending.complete_pending(
                         pending_token,
-                        MessageAsKeyEvent { event: ev, is_synthetic: false },
+                        MessageAsKeyEvent {
+                            event: ev,
+                            is_synthetic: false,
+                            compose: Some(compose),
+                        },
                     ))
                 },
                 WM_CHAR | WM_SYSCHAR => {
@@ -252,9 +277,15 @@ impl KeyEventBuilder {
                             event_info.text = PartialText::Text(key.to_text().map(SmolStr::new));
                         }
                         let ev = event_info.finalize();
+                        // The character ends the dead key sequence, even when it couldn't be
+                        // combined with the dead keys and the text has all their characters.
+                        let compose =
+                            self.dead_chars.lock().unwrap().take().map(|_| {
+                                Compose::Ended(ev.text.as_ref().map(|text| text.to_string()))
+                            });
                         MatchResult::MessagesToDispatch(self.pending.complete_pending(
                             pending_token,
-                            MessageAsKeyEvent { event: ev, is_synthetic: false },
+                            MessageAsKeyEvent { event: ev, is_synthetic: false, compose },
                         ))
                     }
                 },
@@ -291,7 +322,7 @@ impl KeyEventBuilder {
                         let event = event_info.finalize();
                         return MatchResult::MessagesToDispatch(self.pending.complete_pending(
                             pending_token,
-                            MessageAsKeyEvent { event, is_synthetic: false },
+                            MessageAsKeyEvent { event, is_synthetic: false, compose: None },
                         ));
                     }
                     MatchResult::TokenToRemove(pending_token)
@@ -453,7 +484,7 @@ impl KeyEventBuilder {
         let mut event = event_info.finalize();
         event.logical_key = logical_key;
         event.platform_specific.text_with_all_modifiers = text;
-        Some(MessageAsKeyEvent { event, is_synthetic: true })
+        Some(MessageAsKeyEvent { event, is_synthetic: true, compose: None })
     }
 }
 
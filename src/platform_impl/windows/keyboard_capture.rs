@@ -0,0 +1,94 @@
+//! Capture of the keyboard shortcuts of the system with a low-level keyboard hook.
+//!
+//! The hook sees the keys before the system handles its shortcuts. The keys of the shortcuts are
+//! sent to the captured window as the messages it would have received, and hidden from the
+//! system.
+
+use std::sync::Mutex;
+use std::{io, ptr};
+
+use windows_sys::Win32::Foundation::{HWND, LPARAM, LRESULT, WPARAM};
+use windows_sys::Win32::System::LibraryLoader::GetModuleHandleW;
+use windows_sys::Win32::UI::Input::KeyboardAndMouse::{
+    GetAsyncKeyState, VK_APPS, VK_BROWSER_BACK, VK_CONTROL, VK_ESCAPE, VK_F4, VK_LAUNCH_APP2,
+    VK_LWIN, VK_RWIN, VK_SPACE, VK_TAB,
+};
+use windows_sys::Win32::UI::WindowsAndMessaging::{
+    CallNextHookEx, GetForegroundWindow, SendMessageW, SetWindowsHookExW, UnhookWindowsHookEx,
+    HC_ACTION, HHOOK, KBDLLHOOKSTRUCT, LLKHF_ALTDOWN, LLKHF_EXTENDED, LLKHF_UP, WH_KEYBOARD_LL,
+};
+
+struct Capture {
+    hook: HHOOK,
+    windows: Vec<HWND>,
+}
+
+static CAPTURE: Mutex<Capture> = Mutex::new(Capture { hook: 0, windows: Vec::new() });
+
+/// Capture the keyboard shortcuts while the window is in the foreground.
+///
+/// Must be called from the thread running the event loop, which runs the hook.
+pub(crate) fn set_keyboard_capture(window: HWND, capture: bool) -> Result<(), io::Error> {
+    let mut state = CAPTURE.lock().unwrap();
+    state.windows.retain(|&captured| captured != window);
+    if capture {
+        state.windows.push(window);
+    }
+
+    if !state.windows.is_empty() && state.hook == 0 {
+        let module = unsafe { GetModuleHandleW(ptr::null()) };
+        let hook = unsafe { SetWindowsHookExW(WH_KEYBOARD_LL, Some(hook_proc), module, 0) };
+        if hook == 0 {
+            state.windows.clear();
+            return Err(io::Error::last_os_error());
+        }
+        state.hook = hook;
+    } else if state.windows.is_empty() && state.hook != 0 {
+        unsafe { UnhookWindowsHookEx(state.hook) };
+        state.hook = 0;
+    }
+
+    Ok(())
+}
+
+/// Whether the key is part of a shortcut handled by the system.
+fn is_system_shortcut(key: &KBDLLHOOKSTRUCT) -> bool {
+    let alt = key.flags & LLKHF_ALTDOWN != 0;
+    let control = unsafe { GetAsyncKeyState(VK_CONTROL as i32) } < 0;
+    match key.vkCode as u16 {
+        VK_LWIN | VK_RWIN | VK_APPS => true,
+        VK_TAB | VK_F4 | VK_SPACE => alt,
+        VK_ESCAPE => alt || control,
+        // The browser, volume, media and application launching keys.
+        vk => (VK_BROWSER_BACK..=VK_LAUNCH_APP2).contains(&vk),
+    }
+}
+
+unsafe extern "system" fn hook_proc(code: i32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+    if code == HC_ACTION as i32 {
+        let key = unsafe { &*(lparam as *const KBDLLHOOKSTRUCT) };
+        let foreground = unsafe { GetForegroundWindow() };
+        let captured = CAPTURE.lock().is_ok_and(|state| state.windows.contains(&foreground));
+        if captured && is_system_shortcut(key) {
+            // The `lParam` of the keyboard messages, with a repeat count of 1.
+            let mut key_lparam = 1 | (key.scanCode & 0xff) << 16;
+            if key.flags & LLKHF_EXTENDED != 0 {
+                key_lparam |= 1 << 24;
+            }
+            if key.flags & LLKHF_ALTDOWN != 0 {
+                key_lparam |= 1 << 29;
+            }
+            if key.flags & LLKHF_UP != 0 {
+                key_lparam |= (1 << 30) | (1 << 31);
+            }
+
+            // The message is the one the window would have received.
+            unsafe {
+                SendMessageW(foreground, wparam as u32, key.vkCode as WPARAM, key_lparam as LPARAM)
+            };
+            return 1;
+        }
+    }
+
+    unsafe { CallNextHookEx(0, code, wparam, lparam) }
+}
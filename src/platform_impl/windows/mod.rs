@@ -3,12 +3,14 @@ use windows_sys::Win32::Foundation::HWND;
 use windows_sys::Win32::UI::WindowsAndMessaging::{HMENU, WINDOW_LONG_PTR_INDEX};
 
 pub(crate) use self::event_loop::{
-    EventLoop, EventLoopProxy, OwnedDisplayHandle, PlatformSpecificEventLoopAttributes,
+    ActiveEventLoop, EventLoop, EventLoopProxy, OwnedDisplayHandle,
+    PlatformSpecificEventLoopAttributes,
 };
 pub use self::icon::WinIcon as PlatformIcon;
 pub(crate) use self::icon::{SelectedCursor, WinCursor as PlatformCustomCursor, WinIcon};
 pub(crate) use self::keyboard::{physicalkey_to_scancode, scancode_to_physicalkey};
 pub(crate) use self::monitor::{MonitorHandle, VideoModeHandle};
+pub(crate) use self::util::{restore_after_panic, show_panic_dialog};
 pub(crate) use self::window::Window;
 pub(crate) use crate::cursor::OnlyCursorImageSource as PlatformCustomCursorSource;
 use crate::event::DeviceId;
@@ -33,6 +35,7 @@ pub struct PlatformSpecificWindowAttributes {
     pub title_background_color: Option<Color>,
     pub title_text_color: Option<Color>,
     pub corner_preference: Option<CornerPreference>,
+    pub app_user_model_id: Option<String>,
 }
 
 impl Default for PlatformSpecificWindowAttributes {
@@ -52,6 +55,7 @@ impl Default for PlatformSpecificWindowAttributes {
             title_background_color: None,
             title_text_color: None,
             corner_preference: None,
+            app_user_model_id: None,
         }
     }
 }
@@ -143,16 +147,30 @@ unsafe fn set_window_long(hwnd: HWND, nindex: WINDOW_LONG_PTR_INDEX, dwnewlong:
 
 #[macro_use]
 mod util;
+#[cfg(feature = "accesskit")]
+mod accessibility;
+mod clipboard;
 mod dark_mode;
 mod definitions;
+#[cfg(feature = "dialogs")]
+mod dialogs;
 mod dpi;
+mod drag_source;
 mod drop_handler;
 mod event_loop;
+#[cfg(feature = "gamepad")]
+mod gamepad;
 mod icon;
 mod ime;
+mod instance;
+mod jump_list;
 mod keyboard;
+mod keyboard_capture;
 mod keyboard_layout;
 mod monitor;
+mod power_throttling;
 pub(crate) mod raw_input;
+mod taskbar;
+mod touch_keyboard;
 mod window;
 mod window_state;
@@ -1,19 +1,44 @@
 use std::collections::{BTreeSet, VecDeque};
+use std::ffi::c_void;
 use std::hash::Hash;
 use std::num::{NonZeroU16, NonZeroU32};
 use std::{io, mem, ptr};
 
-use windows_sys::Win32::Foundation::{BOOL, HWND, LPARAM, POINT, RECT};
+use windows_sys::Win32::Devices::Display::{
+    DestroyPhysicalMonitors, DisplayConfigGetDeviceInfo, DisplayConfigSetDeviceInfo,
+    GetDisplayConfigBufferSizes, GetMonitorBrightness, GetNumberOfPhysicalMonitorsFromHMONITOR,
+    GetPhysicalMonitorsFromHMONITOR, QueryDisplayConfig, SetMonitorBrightness,
+    DISPLAYCONFIG_DEVICE_INFO_GET_SDR_WHITE_LEVEL, DISPLAYCONFIG_DEVICE_INFO_GET_SOURCE_NAME,
+    DISPLAYCONFIG_DEVICE_INFO_GET_TARGET_NAME, DISPLAYCONFIG_DEVICE_INFO_SET_ADVANCED_COLOR_STATE,
+    DISPLAYCONFIG_MODE_INFO, DISPLAYCONFIG_PATH_INFO, DISPLAYCONFIG_SDR_WHITE_LEVEL,
+    DISPLAYCONFIG_SET_ADVANCED_COLOR_STATE, DISPLAYCONFIG_SOURCE_DEVICE_NAME,
+    DISPLAYCONFIG_TARGET_DEVICE_NAME, PHYSICAL_MONITOR, QDC_ONLY_ACTIVE_PATHS,
+};
+use windows_sys::Win32::Foundation::{
+    BOOL, ERROR_SUCCESS, HANDLE, HWND, LPARAM, MAX_PATH, POINT, RECT,
+};
 use windows_sys::Win32::Graphics::Gdi::{
-    EnumDisplayMonitors, EnumDisplaySettingsExW, GetMonitorInfoW, MonitorFromPoint,
-    MonitorFromWindow, DEVMODEW, DM_BITSPERPEL, DM_DISPLAYFREQUENCY, DM_PELSHEIGHT, DM_PELSWIDTH,
-    ENUM_CURRENT_SETTINGS, HDC, HMONITOR, MONITORINFO, MONITORINFOEXW, MONITOR_DEFAULTTONEAREST,
-    MONITOR_DEFAULTTOPRIMARY,
+    CreateDCW, DeleteDC, EnumDisplayMonitors, EnumDisplaySettingsExW, GetDeviceCaps,
+    GetMonitorInfoW, MonitorFromPoint, MonitorFromWindow, DEVMODEW, DMDO_180, DMDO_270, DMDO_90,
+    DM_BITSPERPEL, DM_DISPLAYFREQUENCY, DM_PELSHEIGHT, DM_PELSWIDTH, ENUM_CURRENT_SETTINGS, HDC,
+    HMONITOR, HORZSIZE, MONITORINFO, MONITORINFOEXW, MONITOR_DEFAULTTONEAREST,
+    MONITOR_DEFAULTTOPRIMARY, VERTSIZE,
 };
 
-use super::util::decode_wide;
+use super::definitions::{
+    IDXGIAdapter, IDXGIFactory, IDXGIOutput6, IID_IDXGIFactory1, IID_IDXGIOutput6, IUnknownVtbl,
+    DXGI_COLOR_SPACE_RGB_FULL_G2084_NONE_P2020, DXGI_OUTPUT_DESC1,
+};
+use super::util::{
+    check, decode_wide, registry_value, ComPtr, CREATE_DXGI_FACTORY_1, GET_DEVICE_GAMMA_RAMP,
+    GET_ICM_PROFILE_W, HKEY_LOCAL_MACHINE, RRF_RT_REG_BINARY, SET_DEVICE_GAMMA_RAMP,
+};
 use crate::dpi::{PhysicalPosition, PhysicalSize};
-use crate::monitor::VideoModeHandle as RootVideoModeHandle;
+use crate::error::{NotSupportedError, RequestError};
+use crate::monitor::{
+    pnp_id, ColorCapabilities, GammaRamp, MonitorIdentity, Orientation,
+    VideoModeHandle as RootVideoModeHandle,
+};
 use crate::platform_impl::platform::dpi::{dpi_to_scale_factor, get_monitor_dpi};
 use crate::platform_impl::platform::util::has_flag;
 
@@ -135,6 +160,217 @@ pub fn current_monitor(hwnd: HWND) -> MonitorHandle {
     MonitorHandle::new(hmonitor)
 }
 
+/// Enable or disable the advanced color (HDR) output of the monitor.
+pub(crate) fn set_advanced_color(hmonitor: HMONITOR, enable: bool) -> Result<(), io::Error> {
+    let path = display_path(hmonitor)?;
+
+    let mut color_state: DISPLAYCONFIG_SET_ADVANCED_COLOR_STATE = unsafe { mem::zeroed() };
+    color_state.header.r#type = DISPLAYCONFIG_DEVICE_INFO_SET_ADVANCED_COLOR_STATE;
+    color_state.header.size = mem::size_of::<DISPLAYCONFIG_SET_ADVANCED_COLOR_STATE>() as u32;
+    color_state.header.adapterId = path.targetInfo.adapterId;
+    color_state.header.id = path.targetInfo.id;
+    color_state.Anonymous.value = enable as u32;
+    let status = unsafe { DisplayConfigSetDeviceInfo(&color_state.header) };
+    if status != ERROR_SUCCESS as i32 {
+        return Err(io::Error::from_raw_os_error(status));
+    }
+
+    Ok(())
+}
+
+/// A GDI gamma ramp, with 256 entries for each of the red, green and blue channels.
+pub(crate) type DeviceGammaRamp = [[u16; 256]; 3];
+
+/// Resample the gamma ramp to the size of a GDI gamma ramp.
+pub(crate) fn device_gamma_ramp(ramp: &GammaRamp) -> Box<DeviceGammaRamp> {
+    let mut device_ramp = Box::new([[0; 256]; 3]);
+    for (device_channel, channel) in device_ramp.iter_mut().zip(ramp.resample(256)) {
+        device_channel.copy_from_slice(&channel);
+    }
+    device_ramp
+}
+
+/// Get the gamma ramp of the monitor.
+pub(crate) fn gamma_ramp(hmonitor: HMONITOR) -> Result<Box<DeviceGammaRamp>, io::Error> {
+    let get_device_gamma_ramp = (*GET_DEVICE_GAMMA_RAMP)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::Unsupported, "missing GetDeviceGammaRamp"))?;
+    let mut ramp = Box::new([[0; 256]; 3]);
+    with_monitor_dc(hmonitor, |hdc| unsafe {
+        get_device_gamma_ramp(hdc, ramp.as_mut_ptr().cast())
+    })?;
+    Ok(ramp)
+}
+
+/// Set the gamma ramp of the monitor.
+pub(crate) fn set_gamma_ramp(hmonitor: HMONITOR, ramp: &DeviceGammaRamp) -> Result<(), io::Error> {
+    let set_device_gamma_ramp = (*SET_DEVICE_GAMMA_RAMP)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::Unsupported, "missing SetDeviceGammaRamp"))?;
+    with_monitor_dc(hmonitor, |hdc| unsafe { set_device_gamma_ramp(hdc, ramp.as_ptr().cast()) })
+}
+
+/// Call a GDI function with a device context for the monitor.
+fn with_monitor_dc(hmonitor: HMONITOR, f: impl FnOnce(HDC) -> BOOL) -> Result<(), io::Error> {
+    let monitor_info = get_monitor_info(hmonitor)?;
+    let hdc =
+        unsafe { CreateDCW(ptr::null(), monitor_info.szDevice.as_ptr(), ptr::null(), ptr::null()) };
+    if hdc == 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let result = f(hdc);
+    let error = io::Error::last_os_error();
+    unsafe { DeleteDC(hdc) };
+    if result == false.into() {
+        return Err(error);
+    }
+    Ok(())
+}
+
+/// The luminance of SDR white on the monitor, in nits.
+fn sdr_white_level(hmonitor: HMONITOR) -> Result<f32, io::Error> {
+    let path = display_path(hmonitor)?;
+
+    let mut white_level: DISPLAYCONFIG_SDR_WHITE_LEVEL = unsafe { mem::zeroed() };
+    white_level.header.r#type = DISPLAYCONFIG_DEVICE_INFO_GET_SDR_WHITE_LEVEL;
+    white_level.header.size = mem::size_of::<DISPLAYCONFIG_SDR_WHITE_LEVEL>() as u32;
+    white_level.header.adapterId = path.targetInfo.adapterId;
+    white_level.header.id = path.targetInfo.id;
+    let status = unsafe { DisplayConfigGetDeviceInfo(&mut white_level.header) };
+    if status != ERROR_SUCCESS as i32 {
+        return Err(io::Error::from_raw_os_error(status));
+    }
+
+    // The level is a multiplier of 80 nits, scaled by 1000.
+    Ok(white_level.SDRWhiteLevel as f32 * 80.0 / 1000.0)
+}
+
+/// The active display path whose source is the GDI device of the monitor.
+fn display_path(hmonitor: HMONITOR) -> Result<DISPLAYCONFIG_PATH_INFO, io::Error> {
+    let monitor_info = get_monitor_info(hmonitor)?;
+
+    let mut path_count = 0;
+    let mut mode_count = 0;
+    let status = unsafe {
+        GetDisplayConfigBufferSizes(QDC_ONLY_ACTIVE_PATHS, &mut path_count, &mut mode_count)
+    };
+    if status != ERROR_SUCCESS {
+        return Err(io::Error::from_raw_os_error(status as i32));
+    }
+
+    let mut paths: Vec<DISPLAYCONFIG_PATH_INFO> =
+        vec![unsafe { mem::zeroed() }; path_count as usize];
+    let mut modes: Vec<DISPLAYCONFIG_MODE_INFO> =
+        vec![unsafe { mem::zeroed() }; mode_count as usize];
+    let status = unsafe {
+        QueryDisplayConfig(
+            QDC_ONLY_ACTIVE_PATHS,
+            &mut path_count,
+            paths.as_mut_ptr(),
+            &mut mode_count,
+            modes.as_mut_ptr(),
+            ptr::null_mut(),
+        )
+    };
+    if status != ERROR_SUCCESS {
+        return Err(io::Error::from_raw_os_error(status as i32));
+    }
+
+    paths.truncate(path_count as usize);
+    paths
+        .into_iter()
+        .find(|path| {
+            let mut source_name: DISPLAYCONFIG_SOURCE_DEVICE_NAME = unsafe { mem::zeroed() };
+            source_name.header.r#type = DISPLAYCONFIG_DEVICE_INFO_GET_SOURCE_NAME;
+            source_name.header.size = mem::size_of::<DISPLAYCONFIG_SOURCE_DEVICE_NAME>() as u32;
+            source_name.header.adapterId = path.sourceInfo.adapterId;
+            source_name.header.id = path.sourceInfo.id;
+            let status = unsafe { DisplayConfigGetDeviceInfo(&mut source_name.header) };
+            status == ERROR_SUCCESS as i32 && source_name.viewGdiDeviceName == monitor_info.szDevice
+        })
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no display path for the monitor"))
+}
+
+/// The name and IDs of the display showing the monitor.
+fn target_name(hmonitor: HMONITOR) -> Option<DISPLAYCONFIG_TARGET_DEVICE_NAME> {
+    let path = display_path(hmonitor).ok()?;
+    let mut target_name: DISPLAYCONFIG_TARGET_DEVICE_NAME = unsafe { mem::zeroed() };
+    target_name.header.r#type = DISPLAYCONFIG_DEVICE_INFO_GET_TARGET_NAME;
+    target_name.header.size = mem::size_of::<DISPLAYCONFIG_TARGET_DEVICE_NAME>() as u32;
+    target_name.header.adapterId = path.targetInfo.adapterId;
+    target_name.header.id = path.targetInfo.id;
+    let status = unsafe { DisplayConfigGetDeviceInfo(&mut target_name.header) };
+    (status == ERROR_SUCCESS as i32).then_some(target_name)
+}
+
+/// The EDID of the display, stored by its driver in the registry key of the device.
+fn edid(target_name: &DISPLAYCONFIG_TARGET_DEVICE_NAME) -> Option<Vec<u8>> {
+    // The device path is like `\\?\DISPLAY#DEL40F4#5&1a2b3c&0&UID4353#{e6f07b5f-...}`, where the
+    // first three parts are the instance of the device, `DISPLAY\DEL40F4\5&1a2b3c&0&UID4353`.
+    let device_path = decode_wide(&target_name.monitorDevicePath).into_string().ok()?;
+    let instance: Vec<_> = device_path.strip_prefix(r"\\?\")?.split('#').take(3).collect();
+    if instance.len() != 3 {
+        return None;
+    }
+    let key = format!(r"SYSTEM\CurrentControlSet\Enum\{}\Device Parameters", instance.join(r"\"));
+    registry_value(HKEY_LOCAL_MACHINE, &key, "EDID", RRF_RT_REG_BINARY)
+}
+
+/// The exact refresh rate of the current mode of the monitor, like 59.94 Hz.
+fn refresh_rate_millihertz(hmonitor: HMONITOR) -> Option<NonZeroU32> {
+    let rate = display_path(hmonitor).ok()?.targetInfo.refreshRate;
+    if rate.Denominator == 0 {
+        return None;
+    }
+    let (numerator, denominator) = (rate.Numerator as u64, rate.Denominator as u64);
+    NonZeroU32::new(((numerator * 1000 + denominator / 2) / denominator) as u32)
+}
+
+/// The description of the DXGI output showing the monitor, with its color capabilities.
+fn output_desc(hmonitor: HMONITOR) -> Result<DXGI_OUTPUT_DESC1, io::Error> {
+    let Some(create_factory) = *CREATE_DXGI_FACTORY_1 else {
+        return Err(io::Error::new(io::ErrorKind::Unsupported, "DXGI 1.1 is not available"));
+    };
+    let factory = unsafe {
+        ComPtr::<IDXGIFactory>::from_out(|factory| create_factory(&IID_IDXGIFactory1, factory))?
+    };
+    let factory_vtbl = unsafe { &*(*factory.0).lpVtbl };
+
+    // The enumerations fail with `DXGI_ERROR_NOT_FOUND` past the last adapter or output.
+    for adapter_index in 0.. {
+        let Ok(adapter) = (unsafe {
+            ComPtr::<IDXGIAdapter>::from_out(|adapter| {
+                (factory_vtbl.EnumAdapters)(factory.0, adapter_index, adapter.cast())
+            })
+        }) else {
+            break;
+        };
+        let adapter_vtbl = unsafe { &*(*adapter.0).lpVtbl };
+
+        for output_index in 0.. {
+            let Ok(output) = (unsafe {
+                ComPtr::<c_void>::from_out(|output| {
+                    (adapter_vtbl.EnumOutputs)(adapter.0, output_index, output)
+                })
+            }) else {
+                break;
+            };
+            let output = unsafe {
+                ComPtr::<IDXGIOutput6>::from_out(|output6| {
+                    let vtbl = *(output.0 as *mut *const IUnknownVtbl);
+                    ((*vtbl).QueryInterface)(output.0.cast(), &IID_IDXGIOutput6, output6)
+                })?
+            };
+
+            let mut desc: DXGI_OUTPUT_DESC1 = unsafe { mem::zeroed() };
+            check(unsafe { ((*(*output.0).lpVtbl).GetDesc1)(output.0, &mut desc) })?;
+            if desc.Monitor == hmonitor {
+                return Ok(desc);
+            }
+        }
+    }
+
+    Err(io::Error::new(io::ErrorKind::NotFound, "no DXGI output for the monitor"))
+}
+
 pub(crate) fn get_monitor_info(hmonitor: HMONITOR) -> Result<MONITORINFOEXW, io::Error> {
     let mut monitor_info: MONITORINFOEXW = unsafe { mem::zeroed() };
     monitor_info.monitorInfo.cbSize = mem::size_of::<MONITORINFOEXW>() as u32;
@@ -192,6 +428,93 @@ impl MonitorHandle {
         dpi_to_scale_factor(get_monitor_dpi(self.0).unwrap_or(96))
     }
 
+    pub fn physical_size_mm(&self) -> Option<(u32, u32)> {
+        let monitor_info = get_monitor_info(self.0).ok()?;
+        let size = unsafe {
+            let hdc =
+                CreateDCW(ptr::null(), monitor_info.szDevice.as_ptr(), ptr::null(), ptr::null());
+            if hdc == 0 {
+                return None;
+            }
+            let size = (GetDeviceCaps(hdc, HORZSIZE as _), GetDeviceCaps(hdc, VERTSIZE as _));
+            DeleteDC(hdc);
+            size
+        };
+        // Zero when the monitor doesn't report its size.
+        (size.0 > 0 && size.1 > 0).then_some((size.0 as u32, size.1 as u32))
+    }
+
+    pub fn orientation(&self) -> Orientation {
+        let Ok(monitor_info) = get_monitor_info(self.0) else { return Orientation::Landscape };
+        let orientation = unsafe {
+            let mut mode: DEVMODEW = mem::zeroed();
+            mode.dmSize = mem::size_of_val(&mode) as u16;
+            if EnumDisplaySettingsExW(
+                monitor_info.szDevice.as_ptr(),
+                ENUM_CURRENT_SETTINGS,
+                &mut mode,
+                0,
+            ) == false.into()
+            {
+                return Orientation::Landscape;
+            }
+            mode.Anonymous1.Anonymous2.dmDisplayOrientation
+        };
+
+        // The display orientations rotate clockwise.
+        match orientation {
+            DMDO_90 => Orientation::Portrait,
+            DMDO_180 => Orientation::LandscapeFlipped,
+            DMDO_270 => Orientation::PortraitFlipped,
+            _ => Orientation::Landscape,
+        }
+    }
+
+    pub fn identity(&self) -> Option<MonitorIdentity> {
+        let target_name = target_name(self.0)?;
+        if let Some(identity) =
+            edid(&target_name).and_then(|edid| MonitorIdentity::from_edid(&edid))
+        {
+            return Some(identity);
+        }
+
+        // Without the EDID, the display configuration still has its IDs, but not the serial.
+        const EDID_IDS_VALID: u32 = 1 << 2;
+        if unsafe { target_name.flags.Anonymous.value } & EDID_IDS_VALID == 0 {
+            return None;
+        }
+        let model =
+            decode_wide(&target_name.monitorFriendlyDeviceName).to_string_lossy().into_owned();
+        Some(MonitorIdentity {
+            // The ID is stored with its bytes swapped.
+            manufacturer: pnp_id(target_name.edidManufactureId.swap_bytes())?,
+            product_code: Some(target_name.edidProductCodeId),
+            model: (!model.is_empty()).then_some(model),
+            serial: None,
+        })
+    }
+
+    pub fn icc_profile(&self) -> Option<Vec<u8>> {
+        let get_icm_profile = (*GET_ICM_PROFILE_W)?;
+        let monitor_info = get_monitor_info(self.0).ok()?;
+        let path = unsafe {
+            let hdc =
+                CreateDCW(ptr::null(), monitor_info.szDevice.as_ptr(), ptr::null(), ptr::null());
+            if hdc == 0 {
+                return None;
+            }
+            let mut path = [0u16; MAX_PATH as usize];
+            let mut len = path.len() as u32;
+            let result = get_icm_profile(hdc, &mut len, path.as_mut_ptr());
+            DeleteDC(hdc);
+            if result == false.into() {
+                return None;
+            }
+            decode_wide(&path)
+        };
+        std::fs::read(path).ok()
+    }
+
     #[inline]
     pub fn current_video_mode(&self) -> Option<VideoModeHandle> {
         let monitor_info = get_monitor_info(self.0).ok()?;
@@ -204,7 +527,12 @@ impl MonitorHandle {
             {
                 None
             } else {
-                Some(VideoModeHandle::new(self.clone(), mode))
+                let mut video_mode = VideoModeHandle::new(self.clone(), mode);
+                // `dmDisplayFrequency` is in whole hertz, the display path has the exact rate.
+                if let Some(refresh_rate_millihertz) = refresh_rate_millihertz(self.0) {
+                    video_mode.refresh_rate_millihertz = Some(refresh_rate_millihertz);
+                }
+                Some(video_mode)
             }
         }
     }
@@ -245,4 +573,89 @@ impl MonitorHandle {
 
         modes.into_iter().map(mod_map)
     }
+
+    pub fn color_capabilities(&self) -> Result<ColorCapabilities, RequestError> {
+        let desc = output_desc(self.0).map_err(|error| os_error!(error))?;
+        let hdr = desc.ColorSpace == DXGI_COLOR_SPACE_RGB_FULL_G2084_NONE_P2020;
+        let sdr_white_luminance = sdr_white_level(self.0).ok();
+
+        // The values the monitor doesn't report are zero.
+        let known = |value: f32| (value > 0.0).then_some(value);
+        let point = |[x, y]: [f32; 2]| (x > 0.0 && y > 0.0).then_some((x, y));
+        let max_luminance = known(desc.MaxLuminance);
+        let headroom = if hdr {
+            max_luminance.zip(sdr_white_luminance).map(|(max, white)| (max / white).max(1.0))
+        } else {
+            Some(1.0)
+        };
+
+        Ok(ColorCapabilities {
+            hdr,
+            headroom,
+            primaries: point(desc.RedPrimary)
+                .zip(point(desc.GreenPrimary))
+                .zip(point(desc.BluePrimary))
+                .map(|((red, green), blue)| [red, green, blue]),
+            white_point: point(desc.WhitePoint),
+            min_luminance: max_luminance.map(|_| desc.MinLuminance),
+            max_luminance,
+            max_full_frame_luminance: known(desc.MaxFullFrameLuminance),
+            sdr_white_luminance,
+        })
+    }
+
+    pub fn brightness(&self) -> Result<f64, RequestError> {
+        with_physical_monitor(self.0, |monitor| {
+            let (mut min, mut current, mut max) = (0, 0, 0);
+            if unsafe { GetMonitorBrightness(monitor, &mut min, &mut current, &mut max) }
+                == false.into()
+            {
+                return Err(io::Error::last_os_error());
+            }
+            if max <= min {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, "empty brightness range"));
+            }
+            Ok((current.clamp(min, max) - min) as f64 / (max - min) as f64)
+        })
+    }
+
+    pub fn set_brightness(&self, brightness: f64) -> Result<(), RequestError> {
+        with_physical_monitor(self.0, |monitor| {
+            let (mut min, mut current, mut max) = (0, 0, 0);
+            if unsafe { GetMonitorBrightness(monitor, &mut min, &mut current, &mut max) }
+                == false.into()
+            {
+                return Err(io::Error::last_os_error());
+            }
+            let value = min + (brightness.clamp(0.0, 1.0) * max.saturating_sub(min) as f64) as u32;
+            if unsafe { SetMonitorBrightness(monitor, value) } == false.into() {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(())
+        })
+    }
+}
+
+/// Call `f` with the first physical monitor of the monitor, through which it's controlled with
+/// DDC/CI.
+fn with_physical_monitor<T>(
+    hmonitor: HMONITOR,
+    f: impl FnOnce(HANDLE) -> Result<T, io::Error>,
+) -> Result<T, RequestError> {
+    let mut count = 0;
+    if unsafe { GetNumberOfPhysicalMonitorsFromHMONITOR(hmonitor, &mut count) } == false.into()
+        || count == 0
+    {
+        return Err(NotSupportedError::new("DDC/CI is not available for the monitor").into());
+    }
+
+    let mut monitors: Vec<PHYSICAL_MONITOR> = vec![unsafe { mem::zeroed() }; count as usize];
+    if unsafe { GetPhysicalMonitorsFromHMONITOR(hmonitor, count, monitors.as_mut_ptr()) }
+        == false.into()
+    {
+        return Err(os_error!(io::Error::last_os_error()).into());
+    }
+    let result = f(monitors[0].hPhysicalMonitor);
+    unsafe { DestroyPhysicalMonitors(count, monitors.as_ptr()) };
+    result.map_err(|error| os_error!(error).into())
 }
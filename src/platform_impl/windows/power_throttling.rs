@@ -0,0 +1,106 @@
+//! The power throttling of the process with `SetProcessInformation`, which is lowered to EcoQoS
+//! while all the windows throttled when occluded are minimized.
+
+use std::ffi::c_void;
+use std::sync::Mutex;
+use std::{io, mem};
+
+use windows_sys::Win32::Foundation::HWND;
+use windows_sys::Win32::System::Threading::{
+    GetCurrentProcess, ProcessPowerThrottling, PROCESS_POWER_THROTTLING_CURRENT_VERSION,
+    PROCESS_POWER_THROTTLING_EXECUTION_SPEED, PROCESS_POWER_THROTTLING_STATE,
+};
+
+use super::util;
+use crate::error::{NotSupportedError, RequestError};
+use crate::platform::windows::EcoQos;
+
+struct State {
+    /// The QoS set by the application.
+    qos: EcoQos,
+    /// The QoS of the process.
+    applied: EcoQos,
+    /// The windows throttled when occluded, with whether they are.
+    windows: Vec<(HWND, bool)>,
+}
+
+static STATE: Mutex<State> =
+    Mutex::new(State { qos: EcoQos::Default, applied: EcoQos::Default, windows: Vec::new() });
+
+impl State {
+    fn update(&mut self) -> Result<(), RequestError> {
+        let qos = if !self.windows.is_empty() && self.windows.iter().all(|(_, occluded)| *occluded)
+        {
+            EcoQos::Eco
+        } else {
+            self.qos
+        };
+        if qos != self.applied {
+            set_process_qos(qos)?;
+            self.applied = qos;
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn set_process_power_throttling(qos: EcoQos) -> Result<(), RequestError> {
+    let mut state = STATE.lock().unwrap();
+    state.qos = qos;
+    state.update()
+}
+
+pub(crate) fn set_throttle_when_occluded(window: HWND, enabled: bool) {
+    let mut state = STATE.lock().unwrap();
+    state.windows.retain(|(throttled, _)| *throttled != window);
+    if enabled {
+        state.windows.push((window, util::is_minimized(window)));
+    }
+    let _ = state.update();
+}
+
+/// Update the window, when it's minimized or restored.
+pub(crate) fn set_occluded(window: HWND, occluded: bool) {
+    let mut state = STATE.lock().unwrap();
+    let Some((_, throttled_occluded)) =
+        state.windows.iter_mut().find(|(throttled, _)| *throttled == window)
+    else {
+        return;
+    };
+    *throttled_occluded = occluded;
+    let _ = state.update();
+}
+
+pub(crate) fn remove_window(window: HWND) {
+    set_throttle_when_occluded(window, false);
+}
+
+fn set_process_qos(qos: EcoQos) -> Result<(), RequestError> {
+    let Some(set_process_information) = *util::SET_PROCESS_INFORMATION else {
+        return Err(NotSupportedError::new("power throttling is not supported").into());
+    };
+
+    let (control_mask, state_mask) = match qos {
+        EcoQos::Default => (0, 0),
+        EcoQos::Eco => {
+            (PROCESS_POWER_THROTTLING_EXECUTION_SPEED, PROCESS_POWER_THROTTLING_EXECUTION_SPEED)
+        },
+        EcoQos::HighQos => (PROCESS_POWER_THROTTLING_EXECUTION_SPEED, 0),
+    };
+    let state = PROCESS_POWER_THROTTLING_STATE {
+        Version: PROCESS_POWER_THROTTLING_CURRENT_VERSION,
+        ControlMask: control_mask,
+        StateMask: state_mask,
+    };
+    let result = unsafe {
+        set_process_information(
+            GetCurrentProcess(),
+            ProcessPowerThrottling,
+            &state as *const PROCESS_POWER_THROTTLING_STATE as *const c_void,
+            mem::size_of::<PROCESS_POWER_THROTTLING_STATE>() as u32,
+        )
+    };
+    if result == false.into() {
+        return Err(os_error!(io::Error::last_os_error()).into());
+    }
+    Ok(())
+}
@@ -2,18 +2,24 @@ use std::mem::{self, size_of};
 use std::ptr;
 
 use windows_sys::Win32::Devices::HumanInterfaceDevice::{
-    HID_USAGE_GENERIC_KEYBOARD, HID_USAGE_GENERIC_MOUSE, HID_USAGE_PAGE_GENERIC,
+    HidD_GetProductString, HID_USAGE_DIGITIZER_PEN, HID_USAGE_DIGITIZER_TOUCH_PAD,
+    HID_USAGE_DIGITIZER_TOUCH_SCREEN, HID_USAGE_GENERIC_GAMEPAD, HID_USAGE_GENERIC_JOYSTICK,
+    HID_USAGE_GENERIC_KEYBOARD, HID_USAGE_GENERIC_MOUSE, HID_USAGE_PAGE_DIGITIZER,
+    HID_USAGE_PAGE_GENERIC,
+};
+use windows_sys::Win32::Foundation::{CloseHandle, HANDLE, HWND, INVALID_HANDLE_VALUE};
+use windows_sys::Win32::Storage::FileSystem::{
+    CreateFileW, FILE_SHARE_READ, FILE_SHARE_WRITE, OPEN_EXISTING,
 };
-use windows_sys::Win32::Foundation::{HANDLE, HWND};
 use windows_sys::Win32::UI::Input::KeyboardAndMouse::{
     MapVirtualKeyW, MAPVK_VK_TO_VSC_EX, VK_NUMLOCK, VK_SHIFT,
 };
 use windows_sys::Win32::UI::Input::{
     GetRawInputData, GetRawInputDeviceInfoW, GetRawInputDeviceList, RegisterRawInputDevices,
     HRAWINPUT, RAWINPUT, RAWINPUTDEVICE, RAWINPUTDEVICELIST, RAWINPUTHEADER, RAWKEYBOARD,
-    RIDEV_DEVNOTIFY, RIDEV_INPUTSINK, RIDEV_REMOVE, RIDI_DEVICEINFO, RIDI_DEVICENAME,
-    RID_DEVICE_INFO, RID_DEVICE_INFO_HID, RID_DEVICE_INFO_KEYBOARD, RID_DEVICE_INFO_MOUSE,
-    RID_INPUT, RIM_TYPEHID, RIM_TYPEKEYBOARD, RIM_TYPEMOUSE,
+    RIDEV_DEVNOTIFY, RIDEV_INPUTSINK, RIDEV_NOLEGACY, RIDEV_REMOVE, RIDI_DEVICEINFO,
+    RIDI_DEVICENAME, RID_DEVICE_INFO, RID_DEVICE_INFO_HID, RID_DEVICE_INFO_KEYBOARD,
+    RID_DEVICE_INFO_MOUSE, RID_INPUT, RIM_TYPEHID, RIM_TYPEKEYBOARD, RIM_TYPEMOUSE,
 };
 use windows_sys::Win32::UI::WindowsAndMessaging::{
     RI_KEY_E0, RI_KEY_E1, RI_MOUSE_BUTTON_1_DOWN, RI_MOUSE_BUTTON_1_UP, RI_MOUSE_BUTTON_2_DOWN,
@@ -21,13 +27,13 @@ use windows_sys::Win32::UI::WindowsAndMessaging::{
     RI_MOUSE_BUTTON_4_UP, RI_MOUSE_BUTTON_5_DOWN, RI_MOUSE_BUTTON_5_UP,
 };
 
-use super::scancode_to_physicalkey;
-use crate::event::ElementState;
-use crate::event_loop::DeviceEvents;
+use super::{scancode_to_physicalkey, wrap_device_id};
+use crate::event::{DeviceInfo, DeviceKind, ElementState};
+use crate::event_loop::{DeviceClass, DeviceEvents};
 use crate::keyboard::{KeyCode, PhysicalKey};
+use crate::platform::windows::{RawInputConfig, RawInputUsage};
 use crate::platform_impl::platform::util;
 
-#[allow(dead_code)]
 pub fn get_raw_input_device_list() -> Option<Vec<RAWINPUTDEVICELIST>> {
     let list_size = size_of::<RAWINPUTDEVICELIST>() as u32;
 
@@ -74,7 +80,6 @@ impl From<RID_DEVICE_INFO> for RawDeviceInfo {
     }
 }
 
-#[allow(dead_code)]
 pub fn get_raw_input_device_info(handle: HANDLE) -> Option<RawDeviceInfo> {
     let mut info: RID_DEVICE_INFO = unsafe { mem::zeroed() };
     let info_size = size_of::<RID_DEVICE_INFO>() as u32;
@@ -122,6 +127,88 @@ pub fn get_raw_input_device_name(handle: HANDLE) -> Option<String> {
     util::decode_wide(&name).into_string().ok()
 }
 
+pub fn get_input_devices() -> Vec<DeviceInfo> {
+    get_raw_input_device_list()
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|device| get_input_device(device.hDevice))
+        .collect()
+}
+
+pub fn get_input_device(handle: HANDLE) -> Option<DeviceInfo> {
+    let path = get_raw_input_device_name(handle);
+    let (kind, mut ids) = match get_raw_input_device_info(handle)? {
+        RawDeviceInfo::Mouse(_) => (DeviceKind::Mouse, None),
+        RawDeviceInfo::Keyboard(_) => (DeviceKind::Keyboard, None),
+        RawDeviceInfo::Hid(hid) => {
+            let kind = match (hid.usUsagePage, hid.usUsage) {
+                (
+                    HID_USAGE_PAGE_GENERIC,
+                    HID_USAGE_GENERIC_JOYSTICK | HID_USAGE_GENERIC_GAMEPAD,
+                ) => DeviceKind::Gamepad,
+                (HID_USAGE_PAGE_DIGITIZER, HID_USAGE_DIGITIZER_PEN) => DeviceKind::Pen,
+                (HID_USAGE_PAGE_DIGITIZER, HID_USAGE_DIGITIZER_TOUCH_SCREEN) => {
+                    DeviceKind::Touchscreen
+                },
+                (HID_USAGE_PAGE_DIGITIZER, HID_USAGE_DIGITIZER_TOUCH_PAD) => DeviceKind::Touchpad,
+                _ => DeviceKind::Other,
+            };
+            (kind, Some((hid.dwVendorId as u16, hid.dwProductId as u16)))
+        },
+    };
+
+    // Mice and keyboards only have their IDs in the path, like `\\?\HID#VID_046D&PID_C52B&...`.
+    if ids.is_none() {
+        ids = path.as_deref().and_then(|path| {
+            let path = path.to_uppercase();
+            let id = |prefix: &str| {
+                let start = path.find(prefix)? + prefix.len();
+                u16::from_str_radix(path.get(start..start + 4)?, 16).ok()
+            };
+            Some((id("VID_")?, id("PID_")?))
+        });
+    }
+
+    Some(DeviceInfo {
+        id: wrap_device_id(handle as _),
+        kind,
+        name: path.as_deref().and_then(get_hid_product_string),
+        vendor_id: ids.map(|(vendor_id, _)| vendor_id),
+        product_id: ids.map(|(_, product_id)| product_id),
+    })
+}
+
+/// The product name in the HID descriptor of the device at `path`.
+fn get_hid_product_string(path: &str) -> Option<String> {
+    let path = util::encode_wide(path);
+    // No access is needed to query the attributes of the device, which can then be opened even when
+    // the system uses it exclusively, like keyboards and mice.
+    let file = unsafe {
+        CreateFileW(
+            path.as_ptr(),
+            0,
+            FILE_SHARE_READ | FILE_SHARE_WRITE,
+            ptr::null(),
+            OPEN_EXISTING,
+            0,
+            0,
+        )
+    };
+    if file == INVALID_HANDLE_VALUE {
+        return None;
+    }
+
+    // The maximum length of a string descriptor of USB, in bytes.
+    let mut buffer = [0u16; 127];
+    let status = unsafe {
+        HidD_GetProductString(file, buffer.as_mut_ptr().cast(), mem::size_of_val(&buffer) as u32)
+    };
+    unsafe { CloseHandle(file) };
+
+    let name = util::decode_wide(&buffer).into_string().ok()?;
+    (status != 0 && !name.is_empty()).then_some(name)
+}
+
 pub fn register_raw_input_devices(devices: &[RAWINPUTDEVICE]) -> bool {
     let device_size = size_of::<RAWINPUTDEVICE>() as u32;
 
@@ -130,36 +217,49 @@ pub fn register_raw_input_devices(devices: &[RAWINPUTDEVICE]) -> bool {
     }
 }
 
-pub fn register_all_mice_and_keyboards_for_raw_input(
-    mut window_handle: HWND,
+pub fn register_for_raw_input(
+    window_handle: HWND,
     filter: DeviceEvents,
+    classes: DeviceClass,
+    config: &RawInputConfig,
 ) -> bool {
     // RIDEV_DEVNOTIFY: receive hotplug events
     // RIDEV_INPUTSINK: receive events even if we're not in the foreground
     // RIDEV_REMOVE: don't receive device events (requires NULL hwndTarget)
-    let flags = match filter {
-        DeviceEvents::Never => {
-            window_handle = 0;
-            RIDEV_REMOVE
-        },
+    let mut flags = match filter {
+        DeviceEvents::Never => RIDEV_REMOVE,
         DeviceEvents::WhenFocused => RIDEV_DEVNOTIFY,
         DeviceEvents::Always => RIDEV_DEVNOTIFY | RIDEV_INPUTSINK,
     };
+    if flags != RIDEV_REMOVE && config.input_sink {
+        flags |= RIDEV_INPUTSINK;
+    }
+    let device = |usage: &RawInputUsage| {
+        let class = match (usage.page, usage.usage) {
+            (HID_USAGE_PAGE_GENERIC, HID_USAGE_GENERIC_MOUSE) => DeviceClass::MOUSE,
+            (HID_USAGE_PAGE_GENERIC, HID_USAGE_GENERIC_KEYBOARD) => DeviceClass::KEYBOARD,
+            _ => DeviceClass::HID,
+        };
+        if flags == RIDEV_REMOVE || !classes.contains(class) {
+            RAWINPUTDEVICE {
+                usUsagePage: usage.page,
+                usUsage: usage.usage,
+                dwFlags: RIDEV_REMOVE,
+                hwndTarget: 0,
+            }
+        } else {
+            // RIDEV_NOLEGACY: don't generate the legacy mouse messages
+            let no_legacy = config.no_legacy_mouse && class == DeviceClass::MOUSE;
+            RAWINPUTDEVICE {
+                usUsagePage: usage.page,
+                usUsage: usage.usage,
+                dwFlags: if no_legacy { flags | RIDEV_NOLEGACY } else { flags },
+                hwndTarget: window_handle,
+            }
+        }
+    };
 
-    let devices: [RAWINPUTDEVICE; 2] = [
-        RAWINPUTDEVICE {
-            usUsagePage: HID_USAGE_PAGE_GENERIC,
-            usUsage: HID_USAGE_GENERIC_MOUSE,
-            dwFlags: flags,
-            hwndTarget: window_handle,
-        },
-        RAWINPUTDEVICE {
-            usUsagePage: HID_USAGE_PAGE_GENERIC,
-            usUsage: HID_USAGE_GENERIC_KEYBOARD,
-            dwFlags: flags,
-            hwndTarget: window_handle,
-        },
-    ];
+    let devices: Vec<RAWINPUTDEVICE> = config.usages.iter().map(device).collect();
 
     register_raw_input_devices(&devices)
 }
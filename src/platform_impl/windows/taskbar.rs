@@ -0,0 +1,200 @@
+//! The toolbar of the taskbar thumbnail of the windows with `ITaskbarList3`, and the images shown
+//! in the thumbnail and when peeking at the windows with the iconic bitmaps of the DWM.
+
+use std::cell::Cell;
+use std::ffi::c_void;
+use std::{mem, ptr, slice};
+
+use windows_sys::Win32::Foundation::{BOOL, HWND, S_OK};
+use windows_sys::Win32::Graphics::Dwm::{
+    DwmInvalidateIconicBitmaps, DwmSetIconicLivePreviewBitmap, DwmSetIconicThumbnail,
+    DwmSetWindowAttribute, DWMWA_FORCE_ICONIC_REPRESENTATION, DWMWA_HAS_ICONIC_BITMAP,
+};
+use windows_sys::Win32::Graphics::Gdi::{
+    CreateDIBSection, DeleteObject, BITMAPINFO, BITMAPINFOHEADER, BI_RGB, DIB_RGB_COLORS, HBITMAP,
+};
+use windows_sys::Win32::System::Com::{CoCreateInstance, CLSCTX_ALL};
+use windows_sys::Win32::UI::Shell::{
+    THBF_DISABLED, THBF_DISMISSONCLICK, THBF_ENABLED, THBF_HIDDEN, THB_FLAGS, THB_ICON,
+    THB_TOOLTIP, THUMBBUTTON,
+};
+
+use super::definitions::{CLSID_TaskbarList, IID_ITaskbarList3, ITaskbarList3};
+use super::window::com_initialized;
+use super::window_state::WindowState;
+use crate::icon::RgbaIcon;
+use crate::platform::windows::ThumbbarButton;
+
+/// The most buttons the toolbar can have.
+const MAX_THUMBBAR_BUTTONS: usize = 7;
+
+thread_local! {
+    static TASKBAR_LIST3: Cell<*mut ITaskbarList3> = const { Cell::new(ptr::null_mut()) };
+}
+
+fn taskbar_list3() -> Option<*mut ITaskbarList3> {
+    com_initialized();
+    TASKBAR_LIST3.with(|task_bar_list3_ptr| {
+        if task_bar_list3_ptr.get().is_null() {
+            let mut task_bar_list3: *mut ITaskbarList3 = ptr::null_mut();
+            let hr = unsafe {
+                CoCreateInstance(
+                    &CLSID_TaskbarList,
+                    ptr::null_mut(),
+                    CLSCTX_ALL,
+                    &IID_ITaskbarList3,
+                    &mut task_bar_list3 as *mut _ as *mut _,
+                )
+            };
+            if hr != S_OK {
+                return None;
+            }
+
+            let hr_init = unsafe { (*(*task_bar_list3).lpVtbl).parent.parent.HrInit };
+            if unsafe { hr_init(task_bar_list3.cast()) } != S_OK {
+                return None;
+            }
+            task_bar_list3_ptr.set(task_bar_list3);
+        }
+        Some(task_bar_list3_ptr.get())
+    })
+}
+
+/// Add or update the toolbar with the buttons of the window state.
+///
+/// The toolbar can only be added once the taskbar button of the window was created, and only
+/// once, so it always has all the buttons, hiding the unused ones. The identifiers of the buttons
+/// are their index in the state.
+pub(crate) fn update_thumbbar(hwnd: HWND, window_state: &mut WindowState) {
+    if window_state.thumbbar_buttons.is_empty() && !window_state.thumbbar_added {
+        return;
+    }
+    let Some(task_bar_list3) = taskbar_list3() else { return };
+    let vtbl = unsafe { &*(*task_bar_list3).lpVtbl };
+
+    let buttons: Vec<THUMBBUTTON> = (0..MAX_THUMBBAR_BUTTONS)
+        .map(|index| thumb_button(index, window_state.thumbbar_buttons.get(index)))
+        .collect();
+    if window_state.thumbbar_added {
+        unsafe {
+            (vtbl.ThumbBarUpdateButtons)(
+                task_bar_list3,
+                hwnd,
+                buttons.len() as u32,
+                buttons.as_ptr(),
+            )
+        };
+    } else {
+        // This fails until the taskbar button is created, when it's called again.
+        let hr = unsafe {
+            (vtbl.ThumbBarAddButtons)(task_bar_list3, hwnd, buttons.len() as u32, buttons.as_ptr())
+        };
+        window_state.thumbbar_added = hr == S_OK;
+    }
+}
+
+fn thumb_button(index: usize, button: Option<&ThumbbarButton>) -> THUMBBUTTON {
+    let mut thumb_button: THUMBBUTTON = unsafe { mem::zeroed() };
+    thumb_button.dwMask = THB_ICON | THB_TOOLTIP | THB_FLAGS;
+    thumb_button.iId = index as u32;
+    let Some(button) = button else {
+        thumb_button.dwFlags = THBF_HIDDEN;
+        return thumb_button;
+    };
+
+    thumb_button.hIcon = button.icon.inner.as_raw_handle();
+    // Truncated to fit with the terminating null.
+    let max_len = thumb_button.szTip.len() - 1;
+    for (unit, tip) in button.tooltip.encode_utf16().take(max_len).zip(&mut thumb_button.szTip) {
+        *tip = unit;
+    }
+    thumb_button.dwFlags = if button.enabled { THBF_ENABLED } else { THBF_DISABLED };
+    if button.dismiss_on_click {
+        thumb_button.dwFlags |= THBF_DISMISSONCLICK;
+    }
+    thumb_button
+}
+
+/// Make the DWM ask for the images of the window instead of showing its content.
+pub(crate) fn set_iconic_representation(hwnd: HWND, enabled: bool) {
+    let value = BOOL::from(enabled);
+    for attribute in [DWMWA_FORCE_ICONIC_REPRESENTATION, DWMWA_HAS_ICONIC_BITMAP] {
+        unsafe {
+            DwmSetWindowAttribute(
+                hwnd,
+                attribute as u32,
+                &value as *const BOOL as *const c_void,
+                mem::size_of::<BOOL>() as u32,
+            )
+        };
+    }
+    if enabled {
+        // Replace the images previously given.
+        unsafe { DwmInvalidateIconicBitmaps(hwnd) };
+    }
+}
+
+/// Answer `WM_DWMSENDICONICTHUMBNAIL`, the image having to fit in the size.
+pub(crate) fn set_iconic_thumbnail(hwnd: HWND, image: &RgbaIcon, max_width: u32, max_height: u32) {
+    let bitmap = create_bitmap(image, max_width, max_height);
+    if bitmap != 0 {
+        unsafe { DwmSetIconicThumbnail(hwnd, bitmap, 0) };
+        unsafe { DeleteObject(bitmap) };
+    }
+}
+
+/// Answer `WM_DWMSENDICONICLIVEPREVIEWBITMAP`, the image being shown over the window.
+pub(crate) fn set_iconic_live_preview(hwnd: HWND, image: &RgbaIcon, width: u32, height: u32) {
+    let bitmap = create_bitmap(image, width, height);
+    if bitmap != 0 {
+        unsafe { DwmSetIconicLivePreviewBitmap(hwnd, bitmap, ptr::null(), 0) };
+        unsafe { DeleteObject(bitmap) };
+    }
+}
+
+/// A top-down 32bpp bitmap of the image with premultiplied alpha, as the DWM expects, scaled down
+/// to fit in the size.
+fn create_bitmap(image: &RgbaIcon, max_width: u32, max_height: u32) -> HBITMAP {
+    if image.width == 0 || image.height == 0 {
+        return 0;
+    }
+    let scale = f64::min(
+        1.0,
+        f64::min(max_width as f64 / image.width as f64, max_height as f64 / image.height as f64),
+    );
+    let width = ((image.width as f64 * scale) as u32).max(1);
+    let height = ((image.height as f64 * scale) as u32).max(1);
+
+    let mut info: BITMAPINFO = unsafe { mem::zeroed() };
+    info.bmiHeader = BITMAPINFOHEADER {
+        biSize: mem::size_of::<BITMAPINFOHEADER>() as u32,
+        biWidth: width as i32,
+        // The rows are from top to bottom.
+        biHeight: -(height as i32),
+        biPlanes: 1,
+        biBitCount: 32,
+        biCompression: BI_RGB,
+        biSizeImage: 0,
+        biXPelsPerMeter: 0,
+        biYPelsPerMeter: 0,
+        biClrUsed: 0,
+        biClrImportant: 0,
+    };
+    let mut bits = ptr::null_mut();
+    let bitmap = unsafe { CreateDIBSection(0, &info, DIB_RGB_COLORS, &mut bits, 0, 0) };
+    if bitmap == 0 || bits.is_null() {
+        return 0;
+    }
+
+    let (width, height) = (width as usize, height as usize);
+    let (image_width, image_height) = (image.width as usize, image.height as usize);
+    let pixels = unsafe { slice::from_raw_parts_mut(bits as *mut u8, width * height * 4) };
+    for (index, pixel) in pixels.chunks_exact_mut(4).enumerate() {
+        let (x, y) = (index % width, index / width);
+        let source = ((y * image_height / height) * image_width + x * image_width / width) * 4;
+        let [r, g, b, a] = [0, 1, 2, 3].map(|channel| image.rgba[source + channel] as u32);
+        let premultiply = |channel: u32| (channel * a / 255) as u8;
+        pixel.copy_from_slice(&[premultiply(b), premultiply(g), premultiply(r), a as u8]);
+    }
+    bitmap
+}
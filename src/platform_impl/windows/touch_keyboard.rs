@@ -0,0 +1,60 @@
+//! Control of the touch keyboard with the COM interfaces of the shell.
+//!
+//! `ITipInvocation` only toggles the keyboard, so its visibility is read from the location of the
+//! input pane first.
+
+use std::{io, ptr};
+
+use windows_sys::Win32::Foundation::RECT;
+use windows_sys::Win32::System::Com::{
+    CoCreateInstance, CLSCTX_INPROC_SERVER, CLSCTX_LOCAL_SERVER,
+};
+use windows_sys::Win32::UI::WindowsAndMessaging::GetDesktopWindow;
+
+use super::definitions::{
+    CLSID_FrameworkInputPane, CLSID_UIHostNoLaunch, IFrameworkInputPane, IID_IFrameworkInputPane,
+    IID_ITipInvocation, ITipInvocation,
+};
+use super::util::{check, ComPtr};
+use super::window::com_initialized;
+
+/// Whether the touch keyboard is on screen.
+unsafe fn is_visible() -> io::Result<bool> {
+    let input_pane: ComPtr<IFrameworkInputPane> = unsafe {
+        ComPtr::from_out(|object| {
+            CoCreateInstance(
+                &CLSID_FrameworkInputPane,
+                ptr::null_mut(),
+                CLSCTX_INPROC_SERVER,
+                &IID_IFrameworkInputPane,
+                object,
+            )
+        })
+    }?;
+    let mut rect = RECT { left: 0, top: 0, right: 0, bottom: 0 };
+    check(unsafe { ((*(*input_pane.0).lpVtbl).Location)(input_pane.0, &mut rect) })?;
+    // The location is empty while the keyboard is hidden.
+    Ok(rect.right > rect.left && rect.bottom > rect.top)
+}
+
+/// Show or hide the touch keyboard.
+pub(crate) fn set_visible(visible: bool) -> io::Result<()> {
+    com_initialized();
+
+    if unsafe { is_visible() }? == visible {
+        return Ok(());
+    }
+
+    let tip_invocation: ComPtr<ITipInvocation> = unsafe {
+        ComPtr::from_out(|object| {
+            CoCreateInstance(
+                &CLSID_UIHostNoLaunch,
+                ptr::null_mut(),
+                CLSCTX_INPROC_SERVER | CLSCTX_LOCAL_SERVER,
+                &IID_ITipInvocation,
+                object,
+            )
+        })
+    }?;
+    check(unsafe { ((*(*tip_invocation.0).lpVtbl).Toggle)(tip_invocation.0, GetDesktopWindow()) })
+}
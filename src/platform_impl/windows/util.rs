@@ -5,31 +5,294 @@ use std::os::windows::prelude::{OsStrExt, OsStringExt};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::{io, mem, ptr};
 
-use windows_sys::core::{HRESULT, PCWSTR};
-use windows_sys::Win32::Foundation::{BOOL, HANDLE, HMODULE, HWND, RECT};
-use windows_sys::Win32::Graphics::Gdi::{ClientToScreen, HMONITOR};
+use windows_sys::core::{GUID, HRESULT, PCWSTR, PWSTR};
+use windows_sys::Win32::Foundation::{BOOL, ERROR_SUCCESS, HANDLE, HMODULE, HWND, RECT, S_OK};
+use windows_sys::Win32::Globalization::{
+    GetLocaleInfoEx, GetUserDefaultLocaleName, LCIDToLocaleName, LOCALE_SLOCALIZEDDISPLAYNAME,
+};
+use windows_sys::Win32::Graphics::Dwm::DwmGetColorizationColor;
+use windows_sys::Win32::Graphics::Gdi::{ChangeDisplaySettingsExW, ClientToScreen, HDC, HMONITOR};
 use windows_sys::Win32::System::LibraryLoader::{GetProcAddress, LoadLibraryA};
+use windows_sys::Win32::System::Power::{GetSystemPowerStatus, SYSTEM_POWER_STATUS};
 use windows_sys::Win32::System::SystemServices::IMAGE_DOS_HEADER;
+use windows_sys::Win32::System::Threading::PROCESS_INFORMATION_CLASS;
+use windows_sys::Win32::UI::Accessibility::{HCF_HIGHCONTRASTON, HIGHCONTRASTW};
 use windows_sys::Win32::UI::HiDpi::{
-    DPI_AWARENESS_CONTEXT, MONITOR_DPI_TYPE, PROCESS_DPI_AWARENESS,
+    DPI_AWARENESS, DPI_AWARENESS_CONTEXT, MONITOR_DPI_TYPE, PROCESS_DPI_AWARENESS,
+};
+use windows_sys::Win32::UI::Input::KeyboardAndMouse::{
+    GetActiveWindow, GetKeyState, GetKeyboardLayout, GetKeyboardLayoutNameW, ReleaseCapture,
+    SendInput, INPUT, INPUT_0, INPUT_KEYBOARD, KEYBDINPUT, KEYEVENTF_KEYUP, VIRTUAL_KEY,
+    VK_CAPITAL, VK_NUMLOCK, VK_SCROLL,
 };
-use windows_sys::Win32::UI::Input::KeyboardAndMouse::GetActiveWindow;
-use windows_sys::Win32::UI::Input::Pointer::{POINTER_INFO, POINTER_TOUCH_INFO};
+use windows_sys::Win32::UI::Input::Pointer::{POINTER_INFO, POINTER_PEN_INFO, POINTER_TOUCH_INFO};
+use windows_sys::Win32::UI::Shell::PropertiesSystem::SHGetPropertyStoreForWindow;
+use windows_sys::Win32::UI::TextServices::InputScope;
 use windows_sys::Win32::UI::WindowsAndMessaging::{
     ClipCursor, GetClientRect, GetClipCursor, GetSystemMetrics, GetWindowPlacement, GetWindowRect,
-    IsIconic, ShowCursor, IDC_APPSTARTING, IDC_ARROW, IDC_CROSS, IDC_HAND, IDC_HELP, IDC_IBEAM,
-    IDC_NO, IDC_SIZEALL, IDC_SIZENESW, IDC_SIZENS, IDC_SIZENWSE, IDC_SIZEWE, IDC_WAIT,
-    SM_CXVIRTUALSCREEN, SM_CYVIRTUALSCREEN, SM_XVIRTUALSCREEN, SM_YVIRTUALSCREEN, SW_MAXIMIZE,
-    WINDOWPLACEMENT,
+    IsIconic, MessageBoxW, ShowCursor, SystemParametersInfoW, IDC_APPSTARTING, IDC_ARROW,
+    IDC_CROSS, IDC_HAND, IDC_HELP, IDC_IBEAM, IDC_NO, IDC_SIZEALL, IDC_SIZENESW, IDC_SIZENS,
+    IDC_SIZENWSE, IDC_SIZEWE, IDC_WAIT, KL_NAMELENGTH, MB_ICONERROR, MB_OK, MB_TASKMODAL,
+    SM_CXVIRTUALSCREEN, SM_CYVIRTUALSCREEN, SM_XVIRTUALSCREEN, SM_YVIRTUALSCREEN,
+    SPI_GETCLIENTAREAANIMATION, SPI_GETHIGHCONTRAST, SW_MAXIMIZE, WINDOWPLACEMENT,
 };
 
+use super::definitions::{
+    IID_IPropertyStore, IPropertyStore, IUnknownVtbl, PKEY_AppUserModel_ID, PROPVARIANT_LPWSTR,
+    VT_EMPTY, VT_LPWSTR,
+};
+use crate::event::{LanguageTag, PowerSource, PowerState, SystemPreferences};
+use crate::keyboard::{KeyboardLayout, Led};
 use crate::utils::Lazy;
-use crate::window::CursorIcon;
+use crate::window::{CursorIcon, CursorThemeInfo, Rgba};
 
 pub fn encode_wide(string: impl AsRef<OsStr>) -> Vec<u16> {
     string.as_ref().encode_wide().chain(once(0)).collect()
 }
 
+/// Convert a `0xAARRGGBB` color, as used by DWM.
+pub fn argb_to_rgba(argb: u32) -> Rgba {
+    let [alpha, red, green, blue] = argb.to_be_bytes();
+    Rgba::new(red, green, blue, alpha)
+}
+
+pub fn accent_color() -> Option<Rgba> {
+    let mut color = 0;
+    let mut opaque_blend = false.into();
+    let result = unsafe { DwmGetColorizationColor(&mut color, &mut opaque_blend) };
+    (result >= 0).then(|| argb_to_rgba(color))
+}
+
+/// `LOCALE_NAME_MAX_LENGTH`, the size of a locale name buffer, which `windows-sys` doesn't define.
+const LOCALE_NAME_MAX_LENGTH: usize = 85;
+
+pub fn user_locale() -> LanguageTag {
+    let mut name = [0u16; LOCALE_NAME_MAX_LENGTH];
+    let len = unsafe { GetUserDefaultLocaleName(name.as_mut_ptr(), name.len() as i32) };
+    if len <= 1 {
+        return LanguageTag::undetermined();
+    }
+    // The length includes the terminating nul.
+    LanguageTag::new(String::from_utf16_lossy(&name[..len as usize - 1]))
+}
+
+/// The cursor scheme and the cursor size chosen in the accessibility settings.
+pub fn cursor_theme() -> Option<CursorThemeInfo> {
+    let key = r"Control Panel\Cursors";
+    let name = registry_value(HKEY_CURRENT_USER, key, "", RRF_RT_REG_SZ)?;
+    let name: Vec<u16> = name
+        .chunks_exact(2)
+        .map(|c| u16::from_ne_bytes([c[0], c[1]]))
+        .take_while(|&c| c != 0)
+        .collect();
+    let name = String::from_utf16_lossy(&name);
+
+    // The size is only stored once it was changed from the default.
+    let size = registry_value(HKEY_CURRENT_USER, key, "CursorBaseSize", RRF_RT_REG_DWORD)
+        .and_then(|size| Some(u32::from_ne_bytes(size.get(..4)?.try_into().ok()?)))
+        .filter(|&size| size != 0)
+        .unwrap_or(32);
+
+    Some(CursorThemeInfo { name: (!name.is_empty()).then_some(name), size })
+}
+
+/// The animation, contrast and transparency settings of the accessibility settings.
+pub fn system_preferences() -> SystemPreferences {
+    let mut animation: BOOL = true.into();
+    unsafe {
+        SystemParametersInfoW(
+            SPI_GETCLIENTAREAANIMATION,
+            0,
+            &mut animation as *mut BOOL as *mut c_void,
+            0,
+        )
+    };
+
+    let mut high_contrast: HIGHCONTRASTW = unsafe { mem::zeroed() };
+    high_contrast.cbSize = mem::size_of::<HIGHCONTRASTW>() as u32;
+    let result = unsafe {
+        SystemParametersInfoW(
+            SPI_GETHIGHCONTRAST,
+            high_contrast.cbSize,
+            &mut high_contrast as *mut HIGHCONTRASTW as *mut c_void,
+            0,
+        )
+    };
+    let high_contrast = result != 0 && high_contrast.dwFlags & HCF_HIGHCONTRASTON != 0;
+
+    let key = r"Software\Microsoft\Windows\CurrentVersion\Themes\Personalize";
+    let reduced_transparency =
+        registry_value(HKEY_CURRENT_USER, key, "EnableTransparency", RRF_RT_REG_DWORD)
+            .and_then(|value| Some(u32::from_ne_bytes(value.get(..4)?.try_into().ok()?)))
+            .is_some_and(|value| value == 0);
+
+    SystemPreferences { reduced_motion: animation == 0, high_contrast, reduced_transparency }
+}
+
+/// The "Make text bigger" setting of the accessibility settings.
+pub fn text_scale_factor() -> f64 {
+    // The percentage is only stored once it was changed from the default.
+    let percent = registry_value(
+        HKEY_CURRENT_USER,
+        r"Software\Microsoft\Accessibility",
+        "TextScaleFactor",
+        RRF_RT_REG_DWORD,
+    )
+    .and_then(|value| Some(u32::from_ne_bytes(value.get(..4)?.try_into().ok()?)))
+    .filter(|&percent| percent != 0)
+    .unwrap_or(100);
+    percent as f64 / 100.0
+}
+
+/// Read a value of the registry, restricted to the types in `flags`.
+pub fn registry_value(hkey: isize, key: &str, value: &str, flags: u32) -> Option<Vec<u8>> {
+    let reg_get_value = (*REG_GET_VALUE_W)?;
+    let key = encode_wide(key);
+    let value = encode_wide(value);
+
+    let mut size = 0;
+    let status = unsafe {
+        reg_get_value(
+            hkey,
+            key.as_ptr(),
+            value.as_ptr(),
+            flags,
+            ptr::null_mut(),
+            ptr::null_mut(),
+            &mut size,
+        )
+    };
+    if status != ERROR_SUCCESS {
+        return None;
+    }
+    let mut data = vec![0u8; size as usize];
+    let status = unsafe {
+        reg_get_value(
+            hkey,
+            key.as_ptr(),
+            value.as_ptr(),
+            flags,
+            ptr::null_mut(),
+            data.as_mut_ptr().cast(),
+            &mut size,
+        )
+    };
+    if status != ERROR_SUCCESS {
+        return None;
+    }
+    data.truncate(size as usize);
+    Some(data)
+}
+
+/// The keyboard layout of the calling thread.
+pub fn keyboard_layout() -> Option<KeyboardLayout> {
+    let mut id = [0u16; KL_NAMELENGTH as usize];
+    if unsafe { GetKeyboardLayoutNameW(id.as_mut_ptr()) } == false.into() {
+        return None;
+    }
+    let len = id.iter().position(|&c| c == 0).unwrap_or(id.len());
+    let id = String::from_utf16_lossy(&id[..len]);
+
+    // The low word of the layout handle is the language identifier.
+    let language = unsafe { GetKeyboardLayout(0) } as usize as u32 & 0xffff;
+    let mut locale = [0u16; LOCALE_NAME_MAX_LENGTH];
+    let mut name = [0u16; 128];
+    let len = unsafe {
+        if LCIDToLocaleName(language, locale.as_mut_ptr(), locale.len() as i32, 0) == 0 {
+            0
+        } else {
+            GetLocaleInfoEx(
+                locale.as_ptr(),
+                LOCALE_SLOCALIZEDDISPLAYNAME,
+                name.as_mut_ptr(),
+                name.len() as i32,
+            )
+        }
+    };
+    // The length includes the terminating nul.
+    let name =
+        if len > 1 { String::from_utf16_lossy(&name[..len as usize - 1]) } else { id.clone() };
+
+    Some(KeyboardLayout { id, name })
+}
+
+fn led_virtual_key(led: Led) -> VIRTUAL_KEY {
+    match led {
+        Led::CapsLock => VK_CAPITAL,
+        Led::NumLock => VK_NUMLOCK,
+        Led::ScrollLock => VK_SCROLL,
+    }
+}
+
+/// Whether the lock key of the LED is toggled on.
+pub fn keyboard_led(led: Led) -> bool {
+    (unsafe { GetKeyState(led_virtual_key(led) as i32) } & 1) != 0
+}
+
+/// Toggle the lock key of the LED by simulating a press and release of the key.
+pub fn set_keyboard_led(led: Led, on: bool) -> Result<(), io::Error> {
+    if keyboard_led(led) == on {
+        return Ok(());
+    }
+
+    let input = |flags| INPUT {
+        r#type: INPUT_KEYBOARD,
+        Anonymous: INPUT_0 {
+            ki: KEYBDINPUT {
+                wVk: led_virtual_key(led),
+                wScan: 0,
+                dwFlags: flags,
+                dwExtraInfo: 0,
+                time: 0,
+            },
+        },
+    };
+    let inputs = [input(0), input(KEYEVENTF_KEYUP)];
+    let sent =
+        unsafe { SendInput(inputs.len() as u32, inputs.as_ptr(), mem::size_of::<INPUT>() as i32) };
+    if sent == 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+pub fn power_state() -> Option<PowerState> {
+    let mut status: SYSTEM_POWER_STATUS = unsafe { mem::zeroed() };
+    if unsafe { GetSystemPowerStatus(&mut status) } == false.into() {
+        return None;
+    }
+
+    // See https://learn.microsoft.com/en-us/windows/win32/api/winbase/ns-winbase-system_power_status
+    let has_battery = status.BatteryFlag != 128 && status.BatteryFlag != 255;
+    let source = match status.ACLineStatus {
+        0 => PowerSource::Battery,
+        1 => PowerSource::External,
+        _ => return None,
+    };
+
+    Some(PowerState {
+        source,
+        charging: has_battery && has_flag(status.BatteryFlag, 8),
+        battery_level: (has_battery && status.BatteryLifePercent <= 100)
+            .then_some(status.BatteryLifePercent),
+    })
+}
+
+/// Whether the nul-terminated `wide` string is equal to `string`.
+///
+/// # Safety
+///
+/// `wide` must point to a valid nul-terminated string.
+pub unsafe fn wide_eq(mut wide: *const u16, string: &str) -> bool {
+    for unit in string.encode_utf16().chain(once(0)) {
+        if unsafe { *wide } != unit {
+            return false;
+        }
+        wide = unsafe { wide.add(1) };
+    }
+    true
+}
+
 pub fn decode_wide(mut wide_c_string: &[u16]) -> OsString {
     if let Some(null_pos) = wide_c_string.iter().position(|c| *c == 0) {
         wide_c_string = &wide_c_string[..null_pos];
@@ -53,6 +316,57 @@ pub(crate) fn win_to_err(result: BOOL) -> Result<(), io::Error> {
     }
 }
 
+/// A COM object, released when dropped.
+pub(crate) struct ComPtr<T>(pub(crate) *mut T);
+
+impl<T> ComPtr<T> {
+    /// Creates an object, or takes ownership of one returned by an out pointer.
+    pub(crate) unsafe fn from_out(f: impl FnOnce(*mut *mut c_void) -> HRESULT) -> io::Result<Self> {
+        let mut object = ptr::null_mut();
+        check(f(&mut object))?;
+        Ok(Self(object.cast()))
+    }
+}
+
+impl<T> Drop for ComPtr<T> {
+    fn drop(&mut self) {
+        unsafe {
+            let vtbl = *(self.0 as *mut *const IUnknownVtbl);
+            ((*vtbl).Release)(self.0.cast());
+        }
+    }
+}
+
+/// Sets the `System.AppUserModel.ID` of the window, or clears it, which must be done before the
+/// window is destroyed.
+pub(crate) fn set_app_user_model_id(hwnd: HWND, id: Option<&str>) -> io::Result<()> {
+    let store: ComPtr<IPropertyStore> = unsafe {
+        ComPtr::from_out(|store| SHGetPropertyStoreForWindow(hwnd, &IID_IPropertyStore, store))?
+    };
+    let vtbl = unsafe { &*(*store.0).lpVtbl };
+    let id = id.map(encode_wide);
+    let value = PROPVARIANT_LPWSTR {
+        vt: if id.is_some() { VT_LPWSTR } else { VT_EMPTY },
+        wReserved1: 0,
+        wReserved2: 0,
+        wReserved3: 0,
+        pwszVal: id.as_ref().map_or(ptr::null(), |id| id.as_ptr()),
+        padding: 0,
+    };
+    unsafe {
+        check((vtbl.SetValue)(store.0, &PKEY_AppUserModel_ID, &value))?;
+        check((vtbl.Commit)(store.0))
+    }
+}
+
+pub(crate) fn check(result: HRESULT) -> io::Result<()> {
+    if result == S_OK {
+        Ok(())
+    } else {
+        Err(io::Error::from_raw_os_error(result))
+    }
+}
+
 pub enum WindowArea {
     Outer,
     Inner,
@@ -99,6 +413,24 @@ pub fn set_cursor_hidden(hidden: bool) {
     }
 }
 
+/// Release the cursor and restore the display modes after a panic of the event loop thread.
+pub(crate) fn restore_after_panic() {
+    set_cursor_hidden(false);
+    let _ = set_cursor_clip(None);
+    unsafe {
+        ReleaseCapture();
+        // The display settings of the registry don't have the modes of the exclusive fullscreen.
+        ChangeDisplaySettingsExW(ptr::null(), ptr::null(), 0, 0, ptr::null());
+    }
+}
+
+/// Show the message of a panic of the event loop thread, until the user closes it.
+pub(crate) fn show_panic_dialog(message: &str) {
+    let text = encode_wide(message);
+    let caption = encode_wide("Error");
+    unsafe { MessageBoxW(0, text.as_ptr(), caption.as_ptr(), MB_OK | MB_ICONERROR | MB_TASKMODAL) };
+}
+
 pub fn get_cursor_clip() -> Result<RECT, io::Error> {
     unsafe {
         let mut rect: RECT = mem::zeroed();
@@ -212,6 +544,13 @@ pub type SetProcessDpiAwareness =
 pub type SetProcessDpiAwarenessContext =
     unsafe extern "system" fn(value: DPI_AWARENESS_CONTEXT) -> BOOL;
 pub type GetDpiForWindow = unsafe extern "system" fn(hwnd: HWND) -> u32;
+pub type GetProcessDpiAwareness =
+    unsafe extern "system" fn(hprocess: HANDLE, value: *mut PROCESS_DPI_AWARENESS) -> HRESULT;
+pub type GetThreadDpiAwarenessContext = unsafe extern "system" fn() -> DPI_AWARENESS_CONTEXT;
+pub type GetAwarenessFromDpiAwarenessContext =
+    unsafe extern "system" fn(value: DPI_AWARENESS_CONTEXT) -> DPI_AWARENESS;
+pub type AreDpiAwarenessContextsEqual =
+    unsafe extern "system" fn(a: DPI_AWARENESS_CONTEXT, b: DPI_AWARENESS_CONTEXT) -> BOOL;
 pub type GetDpiForMonitor = unsafe extern "system" fn(
     hmonitor: HMONITOR,
     dpi_type: MONITOR_DPI_TYPE,
@@ -244,6 +583,65 @@ pub type GetPointerDeviceRects = unsafe extern "system" fn(
 pub type GetPointerTouchInfo =
     unsafe extern "system" fn(pointerId: u32, touchInfo: *mut POINTER_TOUCH_INFO) -> BOOL;
 
+pub type GetPointerPenInfoHistory = unsafe extern "system" fn(
+    pointerId: u32,
+    entriesCount: *mut u32,
+    penInfo: *mut POINTER_PEN_INFO,
+) -> BOOL;
+
+pub type SetProcessInformation = unsafe extern "system" fn(
+    hProcess: HANDLE,
+    ProcessInformationClass: PROCESS_INFORMATION_CLASS,
+    ProcessInformation: *const c_void,
+    ProcessInformationSize: u32,
+) -> BOOL;
+
+pub type EnableMouseInPointer = unsafe extern "system" fn(fEnable: BOOL) -> BOOL;
+
+pub type CreateDXGIFactory1 =
+    unsafe extern "system" fn(riid: *const GUID, ppFactory: *mut *mut c_void) -> HRESULT;
+
+pub type GetICMProfileW =
+    unsafe extern "system" fn(hdc: HDC, pBufSize: *mut u32, pszFilename: PWSTR) -> BOOL;
+
+pub type GetDeviceGammaRamp = unsafe extern "system" fn(hdc: HDC, lpRamp: *mut c_void) -> BOOL;
+
+pub type SetDeviceGammaRamp = unsafe extern "system" fn(hdc: HDC, lpRamp: *const c_void) -> BOOL;
+
+pub type SetInputScope = unsafe extern "system" fn(hwnd: HWND, inputscope: InputScope) -> HRESULT;
+
+// `HKEY` is a handle, `isize` like the other handles.
+pub type RegGetValueW = unsafe extern "system" fn(
+    hkey: isize,
+    lpSubKey: PCWSTR,
+    lpValue: PCWSTR,
+    dwFlags: u32,
+    pdwType: *mut u32,
+    pvData: *mut c_void,
+    pcbData: *mut u32,
+) -> u32;
+
+// Not bound by `windows-sys`, as it gates it on a feature it doesn't define.
+pub type SHCreateDataObject = unsafe extern "system" fn(
+    pidlFolder: *const c_void,
+    cidl: u32,
+    apidl: *const *const c_void,
+    pdtInner: *mut c_void,
+    riid: *const GUID,
+    ppv: *mut *mut c_void,
+) -> HRESULT;
+
+/// `HKEY_CURRENT_USER`, a sign-extended predefined key.
+pub const HKEY_CURRENT_USER: isize = 0x8000_0001_u32 as i32 as isize;
+/// `HKEY_LOCAL_MACHINE`, a sign-extended predefined key.
+pub const HKEY_LOCAL_MACHINE: isize = 0x8000_0002_u32 as i32 as isize;
+/// `RRF_RT_REG_SZ`, restricting `RegGetValueW` to string values.
+pub const RRF_RT_REG_SZ: u32 = 0x0000_0002;
+/// `RRF_RT_REG_BINARY`, restricting `RegGetValueW` to binary values.
+pub const RRF_RT_REG_BINARY: u32 = 0x0000_0008;
+/// `RRF_RT_REG_DWORD`, restricting `RegGetValueW` to 32-bit values.
+pub const RRF_RT_REG_DWORD: u32 = 0x0000_0010;
+
 pub(crate) static GET_DPI_FOR_WINDOW: Lazy<Option<GetDpiForWindow>> =
     Lazy::new(|| get_function!("user32.dll", GetDpiForWindow));
 pub(crate) static ADJUST_WINDOW_RECT_EX_FOR_DPI: Lazy<Option<AdjustWindowRectExForDpi>> =
@@ -256,6 +654,15 @@ pub(crate) static SET_PROCESS_DPI_AWARENESS_CONTEXT: Lazy<Option<SetProcessDpiAw
     Lazy::new(|| get_function!("user32.dll", SetProcessDpiAwarenessContext));
 pub(crate) static SET_PROCESS_DPI_AWARENESS: Lazy<Option<SetProcessDpiAwareness>> =
     Lazy::new(|| get_function!("shcore.dll", SetProcessDpiAwareness));
+pub(crate) static GET_PROCESS_DPI_AWARENESS: Lazy<Option<GetProcessDpiAwareness>> =
+    Lazy::new(|| get_function!("shcore.dll", GetProcessDpiAwareness));
+pub(crate) static GET_THREAD_DPI_AWARENESS_CONTEXT: Lazy<Option<GetThreadDpiAwarenessContext>> =
+    Lazy::new(|| get_function!("user32.dll", GetThreadDpiAwarenessContext));
+pub(crate) static GET_AWARENESS_FROM_DPI_AWARENESS_CONTEXT: Lazy<
+    Option<GetAwarenessFromDpiAwarenessContext>,
+> = Lazy::new(|| get_function!("user32.dll", GetAwarenessFromDpiAwarenessContext));
+pub(crate) static ARE_DPI_AWARENESS_CONTEXTS_EQUAL: Lazy<Option<AreDpiAwarenessContextsEqual>> =
+    Lazy::new(|| get_function!("user32.dll", AreDpiAwarenessContextsEqual));
 pub(crate) static SET_PROCESS_DPI_AWARE: Lazy<Option<SetProcessDPIAware>> =
     Lazy::new(|| get_function!("user32.dll", SetProcessDPIAware));
 pub(crate) static GET_POINTER_FRAME_INFO_HISTORY: Lazy<Option<GetPointerFrameInfoHistory>> =
@@ -266,3 +673,23 @@ pub(crate) static GET_POINTER_DEVICE_RECTS: Lazy<Option<GetPointerDeviceRects>>
     Lazy::new(|| get_function!("user32.dll", GetPointerDeviceRects));
 pub(crate) static GET_POINTER_TOUCH_INFO: Lazy<Option<GetPointerTouchInfo>> =
     Lazy::new(|| get_function!("user32.dll", GetPointerTouchInfo));
+pub(crate) static GET_POINTER_PEN_INFO_HISTORY: Lazy<Option<GetPointerPenInfoHistory>> =
+    Lazy::new(|| get_function!("user32.dll", GetPointerPenInfoHistory));
+pub(crate) static SET_PROCESS_INFORMATION: Lazy<Option<SetProcessInformation>> =
+    Lazy::new(|| get_function!("kernel32.dll", SetProcessInformation));
+pub(crate) static ENABLE_MOUSE_IN_POINTER: Lazy<Option<EnableMouseInPointer>> =
+    Lazy::new(|| get_function!("user32.dll", EnableMouseInPointer));
+pub(crate) static CREATE_DXGI_FACTORY_1: Lazy<Option<CreateDXGIFactory1>> =
+    Lazy::new(|| get_function!("dxgi.dll", CreateDXGIFactory1));
+pub(crate) static GET_ICM_PROFILE_W: Lazy<Option<GetICMProfileW>> =
+    Lazy::new(|| get_function!("gdi32.dll", GetICMProfileW));
+pub(crate) static GET_DEVICE_GAMMA_RAMP: Lazy<Option<GetDeviceGammaRamp>> =
+    Lazy::new(|| get_function!("gdi32.dll", GetDeviceGammaRamp));
+pub(crate) static SET_DEVICE_GAMMA_RAMP: Lazy<Option<SetDeviceGammaRamp>> =
+    Lazy::new(|| get_function!("gdi32.dll", SetDeviceGammaRamp));
+pub(crate) static SH_CREATE_DATA_OBJECT: Lazy<Option<SHCreateDataObject>> =
+    Lazy::new(|| get_function!("shell32.dll", SHCreateDataObject));
+pub(crate) static SET_INPUT_SCOPE: Lazy<Option<SetInputScope>> =
+    Lazy::new(|| get_function!("msctf.dll", SetInputScope));
+pub(crate) static REG_GET_VALUE_W: Lazy<Option<RegGetValueW>> =
+    Lazy::new(|| get_function!("advapi32.dll", RegGetValueW));
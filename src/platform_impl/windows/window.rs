@@ -1,20 +1,23 @@
 #![cfg(windows_platform)]
 
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 use std::ffi::c_void;
 use std::mem::{self, MaybeUninit};
 use std::sync::mpsc::channel;
-use std::sync::{Arc, Mutex, MutexGuard};
+use std::sync::{Arc, Mutex, MutexGuard, Once};
+use std::time::Instant;
 use std::{io, panic, ptr};
 
 use tracing::warn;
 use windows_sys::Win32::Foundation::{
-    HWND, LPARAM, OLE_E_WRONGCOMPOBJ, POINT, POINTS, RECT, RPC_E_CHANGED_MODE, S_OK, WPARAM,
+    SysAllocString, SysFreeString, BOOL, HWND, LPARAM, OLE_E_WRONGCOMPOBJ, POINT, POINTS, RECT,
+    RPC_E_CHANGED_MODE, S_OK, WPARAM,
 };
 use windows_sys::Win32::Graphics::Dwm::{
-    DwmEnableBlurBehindWindow, DwmSetWindowAttribute, DWMWA_BORDER_COLOR, DWMWA_CAPTION_COLOR,
-    DWMWA_SYSTEMBACKDROP_TYPE, DWMWA_TEXT_COLOR, DWMWA_WINDOW_CORNER_PREFERENCE, DWM_BB_BLURREGION,
-    DWM_BB_ENABLE, DWM_BLURBEHIND, DWM_SYSTEMBACKDROP_TYPE, DWM_WINDOW_CORNER_PREFERENCE,
+    DwmEnableBlurBehindWindow, DwmExtendFrameIntoClientArea, DwmSetWindowAttribute,
+    DWMWA_CAPTION_COLOR, DWMWA_SYSTEMBACKDROP_TYPE, DWMWA_TEXT_COLOR,
+    DWMWA_WINDOW_CORNER_PREFERENCE, DWM_BB_BLURREGION, DWM_BB_ENABLE, DWM_BLURBEHIND,
+    DWM_SYSTEMBACKDROP_TYPE, DWM_WINDOW_CORNER_PREFERENCE,
 };
 use windows_sys::Win32::Graphics::Gdi::{
     ChangeDisplaySettingsExW, ClientToScreen, CreateRectRgn, DeleteObject, InvalidateRgn,
@@ -25,32 +28,44 @@ use windows_sys::Win32::System::Com::{
     CoCreateInstance, CoInitializeEx, CoUninitialize, CLSCTX_ALL, COINIT_APARTMENTTHREADED,
 };
 use windows_sys::Win32::System::Ole::{OleInitialize, RegisterDragDrop};
+use windows_sys::Win32::UI::Accessibility::{
+    NotificationKind_Other, NotificationProcessing_All, NotificationProcessing_ImportantMostRecent,
+    UiaHostProviderFromHwnd, UiaRaiseNotificationEvent,
+};
+use windows_sys::Win32::UI::Controls::MARGINS;
 use windows_sys::Win32::UI::Input::KeyboardAndMouse::{
     EnableWindow, GetActiveWindow, MapVirtualKeyW, ReleaseCapture, SendInput, ToUnicode, INPUT,
     INPUT_0, INPUT_KEYBOARD, KEYBDINPUT, KEYEVENTF_EXTENDEDKEY, KEYEVENTF_KEYUP, MAPVK_VK_TO_VSC,
     VIRTUAL_KEY, VK_LMENU, VK_MENU, VK_SPACE,
 };
-use windows_sys::Win32::UI::Input::Touch::{RegisterTouchWindow, TWF_WANTPALM};
+use windows_sys::Win32::UI::Input::Touch::{
+    RegisterTouchWindow, UnregisterTouchWindow, TWF_WANTPALM,
+};
 use windows_sys::Win32::UI::WindowsAndMessaging::{
     CreateWindowExW, EnableMenuItem, FlashWindowEx, GetClientRect, GetCursorPos,
     GetForegroundWindow, GetSystemMenu, GetSystemMetrics, GetWindowPlacement, GetWindowTextLengthW,
     GetWindowTextW, IsWindowVisible, LoadCursorW, PeekMessageW, PostMessageW, RegisterClassExW,
-    SetCursor, SetCursorPos, SetForegroundWindow, SetMenuDefaultItem, SetWindowDisplayAffinity,
-    SetWindowPlacement, SetWindowPos, SetWindowTextW, TrackPopupMenu, CS_HREDRAW, CS_VREDRAW,
-    CW_USEDEFAULT, FLASHWINFO, FLASHW_ALL, FLASHW_STOP, FLASHW_TIMERNOFG, FLASHW_TRAY,
-    GWLP_HINSTANCE, HTBOTTOM, HTBOTTOMLEFT, HTBOTTOMRIGHT, HTCAPTION, HTLEFT, HTRIGHT, HTTOP,
-    HTTOPLEFT, HTTOPRIGHT, MENU_ITEM_STATE, MFS_DISABLED, MFS_ENABLED, MF_BYCOMMAND, NID_READY,
-    PM_NOREMOVE, SC_CLOSE, SC_MAXIMIZE, SC_MINIMIZE, SC_MOVE, SC_RESTORE, SC_SIZE, SM_DIGITIZER,
-    SWP_ASYNCWINDOWPOS, SWP_NOACTIVATE, SWP_NOSIZE, SWP_NOZORDER, TPM_LEFTALIGN, TPM_RETURNCMD,
-    WDA_EXCLUDEFROMCAPTURE, WDA_NONE, WM_NCLBUTTONDOWN, WM_SYSCOMMAND, WNDCLASSEXW,
+    SetCursor, SetCursorPos, SetForegroundWindow, SetMenuDefaultItem, SetTimer,
+    SetWindowDisplayAffinity, SetWindowPlacement, SetWindowPos, SetWindowTextW, TrackPopupMenu,
+    CS_HREDRAW, CS_VREDRAW, CW_USEDEFAULT, FLASHWINFO, FLASHW_ALL, FLASHW_STOP, FLASHW_TIMERNOFG,
+    FLASHW_TRAY, GWLP_HINSTANCE, HTBOTTOM, HTBOTTOMLEFT, HTBOTTOMRIGHT, HTCAPTION, HTLEFT, HTRIGHT,
+    HTTOP, HTTOPLEFT, HTTOPRIGHT, MENU_ITEM_STATE, MFS_DISABLED, MFS_ENABLED, MF_BYCOMMAND,
+    NID_READY, PM_NOREMOVE, SC_CLOSE, SC_MAXIMIZE, SC_MINIMIZE, SC_MOVE, SC_RESTORE, SC_SIZE,
+    SM_DIGITIZER, SWP_ASYNCWINDOWPOS, SWP_NOACTIVATE, SWP_NOSIZE, SWP_NOZORDER, TPM_LEFTALIGN,
+    TPM_RETURNCMD, USER_TIMER_MAXIMUM, WDA_EXCLUDEFROMCAPTURE, WDA_NONE, WM_NCLBUTTONDOWN,
+    WM_SYSCOMMAND, WNDCLASSEXW,
 };
 
 use crate::cursor::Cursor;
 use crate::dpi::{PhysicalPosition, PhysicalSize, Position, Size};
 use crate::error::{NotSupportedError, RequestError};
+use crate::event::DeviceId;
 use crate::icon::Icon;
-use crate::monitor::MonitorHandle as CoreMonitorHandle;
-use crate::platform::windows::{BackdropType, Color, CornerPreference};
+use crate::monitor::{GammaRamp, HdrMetadata, MonitorHandle as CoreMonitorHandle};
+use crate::platform::windows::{
+    BackdropType, Color, CornerPreference, DropFormatHandler, PointerApi, TaskbarPreview,
+    ThumbbarButton,
+};
 use crate::platform_impl::platform::dark_mode::try_theme;
 use crate::platform_impl::platform::definitions::{
     CLSID_TaskbarList, IID_ITaskbarList, IID_ITaskbarList2, ITaskbarList, ITaskbarList2,
@@ -59,18 +74,23 @@ use crate::platform_impl::platform::dpi::{
     dpi_to_scale_factor, enable_non_client_dpi_scaling, hwnd_dpi,
 };
 use crate::platform_impl::platform::drop_handler::FileDropHandler;
-use crate::platform_impl::platform::event_loop::{self, ActiveEventLoop, DESTROY_MSG_ID};
+use crate::platform_impl::platform::event_loop::{
+    self, ActiveEventLoop, WndProcHook, DESTROY_MSG_ID, REDRAW_TIMER_ID,
+};
 use crate::platform_impl::platform::icon::{self, IconType};
-use crate::platform_impl::platform::ime::ImeContext;
+use crate::platform_impl::platform::ime::{ImeContext, SurroundingText};
 use crate::platform_impl::platform::keyboard::KeyEventBuilder;
 use crate::platform_impl::platform::window_state::{
-    CursorFlags, SavedWindow, WindowFlags, WindowState,
+    apply_border_color, CursorFlags, SavedWindow, WindowFlags, WindowState,
+};
+use crate::platform_impl::platform::{
+    keyboard_capture, monitor, power_throttling, taskbar, touch_keyboard, util, Fullscreen,
+    SelectedCursor,
 };
-use crate::platform_impl::platform::{monitor, util, Fullscreen, SelectedCursor};
 use crate::window::{
-    CursorGrabMode, Fullscreen as CoreFullscreen, ImePurpose, ResizeDirection, Theme,
-    UserAttentionType, Window as CoreWindow, WindowAttributes, WindowButtons, WindowId,
-    WindowLevel,
+    Coalescing, CursorGrabMode, CursorGrabPolicy, Fullscreen as CoreFullscreen, ImePurpose,
+    Politeness, ResizeDirection, Theme, UserAttentionType, Window as CoreWindow, WindowAttributes,
+    WindowButtons, WindowId, WindowLevel,
 };
 
 /// The Win32 implementation of the main `Window` object.
@@ -161,16 +181,85 @@ impl Window {
     }
 
     pub fn set_system_backdrop(&self, backdrop_type: BackdropType) {
-        unsafe {
+        // The undocumented attribute enabling Mica before Windows 11 build 22523.
+        const DWMWA_MICA_EFFECT: u32 = 1029;
+
+        let hr = unsafe {
             DwmSetWindowAttribute(
                 self.hwnd(),
                 DWMWA_SYSTEMBACKDROP_TYPE as u32,
                 &(backdrop_type as i32) as *const _ as _,
                 mem::size_of::<DWM_SYSTEMBACKDROP_TYPE>() as _,
-            );
+            )
+        };
+        if hr < 0 {
+            let mica = BOOL::from(backdrop_type == BackdropType::MainWindow);
+            unsafe {
+                DwmSetWindowAttribute(
+                    self.hwnd(),
+                    DWMWA_MICA_EFFECT,
+                    &mica as *const _ as _,
+                    mem::size_of::<BOOL>() as _,
+                )
+            };
+        }
+
+        let mut window_state = self.window_state_lock();
+        let previous = mem::replace(&mut window_state.backdrop_type, backdrop_type);
+        let transparent = window_state.window_flags.contains(WindowFlags::TRANSPARENT);
+        drop(window_state);
+        if is_material(previous) || is_material(backdrop_type) {
+            extend_frame_for_backdrop(self.hwnd(), backdrop_type, transparent);
         }
     }
 
+    pub fn set_thumbbar_buttons(&self, buttons: Vec<ThumbbarButton>) {
+        let window = self.window;
+        let window_state = self.window_state.clone();
+        let mut buttons = Some(buttons);
+        self.thread_executor.execute_in_thread(move || {
+            let mut window_state = window_state.lock().unwrap();
+            window_state.thumbbar_buttons = buttons.take().unwrap_or_default();
+            taskbar::update_thumbbar(window, &mut window_state);
+        });
+    }
+
+    pub fn set_taskbar_preview(&self, preview: Option<TaskbarPreview>) {
+        let enabled = preview.is_some();
+        self.window_state_lock().taskbar_preview = preview;
+        taskbar::set_iconic_representation(self.hwnd(), enabled);
+    }
+
+    pub fn set_snap_layout_button_rect(&self, rect: Option<(Position, Size)>) {
+        self.window_state_lock().snap_layout_button = rect;
+    }
+
+    pub fn set_pointer_api(&self, api: PointerApi) {
+        let window = self.window;
+        self.thread_executor.execute_in_thread(move || match api {
+            PointerApi::Legacy => register_touch_window(window),
+            PointerApi::WmPointer => {
+                // The touch input is only received with `WM_POINTER` once the window is no
+                // longer registered for `WM_TOUCH`.
+                unsafe { UnregisterTouchWindow(window) };
+                static ENABLE_MOUSE_IN_POINTER: Once = Once::new();
+                ENABLE_MOUSE_IN_POINTER.call_once(|| {
+                    if let Some(enable_mouse_in_pointer) = *util::ENABLE_MOUSE_IN_POINTER {
+                        unsafe { enable_mouse_in_pointer(true.into()) };
+                    }
+                });
+            },
+        });
+    }
+
+    pub fn set_throttle_when_occluded(&self, enabled: bool) {
+        power_throttling::set_throttle_when_occluded(self.hwnd(), enabled);
+    }
+
+    pub fn set_drop_format_handler(&self, handler: Option<DropFormatHandler>) {
+        self.window_state_lock().drop_format_handler = handler;
+    }
+
     pub fn set_taskbar_icon(&self, taskbar_icon: Option<Icon>) {
         if let Some(ref taskbar_icon) = taskbar_icon {
             taskbar_icon.inner.set_for_window(self.hwnd(), IconType::Big);
@@ -287,14 +376,8 @@ impl Window {
 
     #[inline]
     pub fn set_border_color(&self, color: Color) {
-        unsafe {
-            DwmSetWindowAttribute(
-                self.hwnd(),
-                DWMWA_BORDER_COLOR as u32,
-                &color as *const _ as _,
-                mem::size_of::<Color>() as _,
-            );
-        }
+        self.window_state_lock().border_color = Some(color);
+        apply_border_color(self.hwnd(), color);
     }
 
     #[inline]
@@ -321,6 +404,18 @@ impl Window {
         }
     }
 
+    #[inline]
+    pub fn set_wndproc_hook(&self, hook: Option<WndProcHook>) {
+        let window = self.window;
+        // The function is only called once.
+        let mut hook = Some(hook);
+        self.thread_executor.execute_in_thread(move || {
+            if let Some(hook) = hook.take() {
+                event_loop::set_wndproc_hook(window, hook);
+            }
+        });
+    }
+
     #[inline]
     pub fn set_corner_preference(&self, preference: CornerPreference) {
         unsafe {
@@ -378,9 +473,13 @@ impl CoreWindow for Window {
         let window_state = Arc::clone(&self.window_state);
         self.thread_executor.execute_in_thread(move || {
             let _ = &window;
+            let backdrop_type = window_state.lock().unwrap().backdrop_type;
             WindowState::set_window_flags(window_state.lock().unwrap(), window, |f| {
                 f.set(WindowFlags::TRANSPARENT, transparent)
             });
+            if is_material(backdrop_type) {
+                extend_frame_for_backdrop(window, backdrop_type, transparent);
+            }
         });
     }
 
@@ -409,6 +508,24 @@ impl CoreWindow for Window {
         }
     }
 
+    fn request_redraw_at(&self, instant: Instant) {
+        let window = self.window;
+        let window_state = Arc::clone(&self.window_state);
+
+        // Timers can only be set by the thread of the window.
+        self.thread_executor.execute_in_thread(move || {
+            let mut window_state = window_state.lock().unwrap();
+            if window_state.redraw_at.is_some_and(|deadline| deadline <= instant) {
+                return;
+            }
+            window_state.redraw_at = Some(instant);
+
+            let delay = instant.saturating_duration_since(Instant::now()).as_millis();
+            let delay = delay.min(USER_TIMER_MAXIMUM as u128) as u32;
+            unsafe { SetTimer(window, REDRAW_TIMER_ID, delay, None) };
+        });
+    }
+
     fn pre_present_notify(&self) {}
 
     fn outer_position(&self) -> Result<PhysicalPosition<i32>, RequestError> {
@@ -579,10 +696,12 @@ impl CoreWindow for Window {
                 });
             },
             Cursor::Custom(cursor) => {
-                self.window_state_lock().mouse.selected_cursor =
-                    SelectedCursor::Custom(cursor.inner.0.clone());
+                let mut window_state = self.window_state_lock();
+                window_state.mouse.selected_cursor = SelectedCursor::Custom(cursor.inner.clone());
+                let scale_factor = window_state.scale_factor;
+                drop(window_state);
                 self.thread_executor.execute_in_thread(move || unsafe {
-                    SetCursor(cursor.inner.0.as_raw_handle());
+                    SetCursor(cursor.inner.for_scale_factor(scale_factor).as_raw_handle());
                 });
             },
         }
@@ -615,6 +734,10 @@ impl CoreWindow for Window {
         rx.recv().unwrap()
     }
 
+    fn set_cursor_grab_policy(&self, policy: CursorGrabPolicy) {
+        self.window_state_lock().cursor_grab_policy = policy;
+    }
+
     fn set_cursor_visible(&self, visible: bool) {
         let window = self.window;
         let window_state = Arc::clone(&self.window_state);
@@ -633,6 +756,14 @@ impl CoreWindow for Window {
         rx.recv().unwrap().ok();
     }
 
+    fn set_cursor_for_device(&self, _: DeviceId, _: Cursor) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_cursor_for_device is not supported").into())
+    }
+
+    fn set_cursor_visible_for_device(&self, _: DeviceId, _: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_cursor_visible_for_device is not supported").into())
+    }
+
     fn scale_factor(&self) -> f64 {
         self.window_state_lock().scale_factor
     }
@@ -696,6 +827,43 @@ impl CoreWindow for Window {
         Ok(())
     }
 
+    #[cfg(feature = "accesskit")]
+    fn set_accesskit_tree_source(
+        &self,
+        source: Box<dyn accesskit::ActivationHandler + Send>,
+    ) -> Result<(), RequestError> {
+        let window = self.window;
+        // The function is only called once.
+        let mut source = Some(source);
+        self.thread_executor.execute_in_thread(move || {
+            if let Some(source) = source.take() {
+                event_loop::set_accesskit_tree_source(window, source);
+            }
+        });
+
+        Ok(())
+    }
+
+    #[cfg(feature = "accesskit")]
+    fn update_accesskit_tree(&self, update: accesskit::TreeUpdate) {
+        let window = self.window;
+        // The function is only called once.
+        let mut update = Some(update);
+        self.thread_executor.execute_in_thread(move || {
+            if let Some(update) = update.take() {
+                event_loop::update_accesskit_tree(window, update);
+            }
+        });
+    }
+
+    fn set_motion_coalescing(&self, coalescing: Coalescing) -> Result<(), RequestError> {
+        let window = self.window;
+        self.thread_executor
+            .execute_in_thread(move || event_loop::set_motion_coalescing(window, coalescing));
+
+        Ok(())
+    }
+
     fn id(&self) -> WindowId {
         WindowId::from_raw(self.hwnd() as usize)
     }
@@ -764,7 +932,17 @@ impl CoreWindow for Window {
             _ => {},
         }
 
+        // The gamma ramp only applies in exclusive fullscreen.
+        if !matches!(fullscreen, Some(Fullscreen::Exclusive(_)))
+            && window_state_lock.gamma_ramp.take().is_some()
+        {
+            if let Err(err) = window_state_lock.update_gamma_ramp() {
+                warn!("Failed to restore the gamma ramp: {err}");
+            }
+        }
+
         window_state_lock.fullscreen.clone_from(&fullscreen);
+        let hdr = window_state_lock.fullscreen_hdr.is_some();
         drop(window_state_lock);
 
         self.thread_executor.execute_in_thread(move || {
@@ -791,8 +969,21 @@ impl CoreWindow for Window {
                     debug_assert!(res != DISP_CHANGE_BADPARAM);
                     debug_assert!(res != DISP_CHANGE_FAILED);
                     assert_eq!(res, DISP_CHANGE_SUCCESSFUL);
+
+                    if hdr {
+                        if let Err(err) = monitor::set_advanced_color(monitor.hmonitor(), true) {
+                            warn!("Failed to enable HDR output: {err}");
+                        }
+                    }
                 },
-                (Some(Fullscreen::Exclusive(_)), _) => {
+                (Some(Fullscreen::Exclusive(video_mode)), _) => {
+                    if hdr {
+                        let hmonitor = video_mode.monitor().hmonitor();
+                        if let Err(err) = monitor::set_advanced_color(hmonitor, false) {
+                            warn!("Failed to disable HDR output: {err}");
+                        }
+                    }
+
                     let res = unsafe {
                         ChangeDisplaySettingsExW(
                             ptr::null(),
@@ -894,6 +1085,36 @@ impl CoreWindow for Window {
         });
     }
 
+    fn set_fullscreen_hdr(&self, hdr: Option<HdrMetadata>) -> Result<(), RequestError> {
+        let mut window_state = self.window_state_lock();
+        let enabled = hdr.is_some();
+        let was_enabled = mem::replace(&mut window_state.fullscreen_hdr, hdr).is_some();
+
+        // Otherwise, it's applied when entering exclusive fullscreen.
+        if let Some(Fullscreen::Exclusive(video_mode)) = &window_state.fullscreen {
+            if enabled != was_enabled {
+                let hmonitor = video_mode.monitor().hmonitor();
+                drop(window_state);
+                monitor::set_advanced_color(hmonitor, enabled).map_err(|err| os_error!(err))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn fullscreen_hdr(&self) -> Option<HdrMetadata> {
+        self.window_state_lock().fullscreen_hdr
+    }
+
+    fn set_gamma_ramp(&self, ramp: Option<&GammaRamp>) -> Result<(), RequestError> {
+        let mut window_state = self.window_state_lock();
+        if ramp.is_some() && !matches!(window_state.fullscreen, Some(Fullscreen::Exclusive(_))) {
+            return Err(RequestError::Ignored);
+        }
+        window_state.gamma_ramp = ramp.cloned();
+        window_state.update_gamma_ramp().map_err(|err| os_error!(err).into())
+    }
+
     fn set_decorations(&self, decorations: bool) {
         let window = self.window;
         let window_state = Arc::clone(&self.window_state);
@@ -949,9 +1170,37 @@ impl CoreWindow for Window {
         let window = self.window;
         let state = self.window_state.clone();
         self.thread_executor.execute_in_thread(move || unsafe {
-            let scale_factor = state.lock().unwrap().scale_factor;
-            ImeContext::current(window).set_ime_cursor_area(spot, size, scale_factor);
+            let (exclusion, scale_factor) = {
+                let mut state = state.lock().unwrap();
+                state.ime_cursor_area = Some((spot, size));
+                (state.ime_exclusion_area, state.scale_factor)
+            };
+            ImeContext::current(window).set_ime_cursor_area(spot, size, exclusion, scale_factor);
+        });
+    }
+
+    fn set_ime_exclusion_area(&self, area: Option<(Position, Size)>) -> Result<(), RequestError> {
+        let window = self.window;
+        let state = self.window_state.clone();
+        self.thread_executor.execute_in_thread(move || unsafe {
+            let (cursor_area, scale_factor) = {
+                let mut state = state.lock().unwrap();
+                state.ime_exclusion_area = area;
+                (state.ime_cursor_area, state.scale_factor)
+            };
+            if let Some((spot, size)) = cursor_area {
+                ImeContext::current(window).set_ime_cursor_area(spot, size, area, scale_factor);
+            }
+        });
+        Ok(())
+    }
+
+    fn set_ime_candidates_drawn_by_app(&self, drawn_by_app: bool) -> Result<(), RequestError> {
+        let state = self.window_state.clone();
+        self.thread_executor.execute_in_thread(move || {
+            state.lock().unwrap().ime_candidates_drawn_by_app = drawn_by_app;
         });
+        Ok(())
     }
 
     fn set_ime_allowed(&self, allowed: bool) {
@@ -963,7 +1212,27 @@ impl CoreWindow for Window {
         })
     }
 
-    fn set_ime_purpose(&self, _purpose: ImePurpose) {}
+    fn set_virtual_keyboard_visible(&self, visible: bool) -> Result<(), RequestError> {
+        touch_keyboard::set_visible(visible).map_err(|err| os_error!(err).into())
+    }
+
+    fn set_ime_purpose(&self, purpose: ImePurpose) {
+        let window = self.window;
+        self.thread_executor.execute_in_thread(move || unsafe {
+            ImeContext::set_ime_purpose(window, purpose);
+        })
+    }
+
+    fn set_ime_surrounding_text(&self, text: &str, cursor: usize, anchor: usize) {
+        let Some(surrounding_text) = SurroundingText::new(text, cursor, anchor) else {
+            warn!("IME surrounding text ignored, the selection isn't on char boundaries");
+            return;
+        };
+        let state = self.window_state.clone();
+        self.thread_executor.execute_in_thread(move || {
+            state.lock().unwrap().ime_surrounding_text = Some(surrounding_text.clone());
+        })
+    }
 
     fn request_user_attention(&self, request_type: Option<UserAttentionType>) {
         let window = self.window;
@@ -991,6 +1260,33 @@ impl CoreWindow for Window {
         });
     }
 
+    fn announce(&self, message: &str, politeness: Politeness) -> Result<(), RequestError> {
+        com_initialized();
+
+        let processing = match politeness {
+            Politeness::Polite => NotificationProcessing_All,
+            Politeness::Assertive => NotificationProcessing_ImportantMostRecent,
+        };
+        unsafe {
+            // The host provider is the UI Automation element of the window itself.
+            let provider: util::ComPtr<c_void> =
+                util::ComPtr::from_out(|provider| UiaHostProviderFromHwnd(self.window, provider))
+                    .map_err(|err| os_error!(err))?;
+            let message = SysAllocString(util::encode_wide(message).as_ptr());
+            let activity_id = SysAllocString(util::encode_wide("winit.announce").as_ptr());
+            let result = UiaRaiseNotificationEvent(
+                provider.0,
+                NotificationKind_Other,
+                processing,
+                message,
+                activity_id,
+            );
+            SysFreeString(message);
+            SysFreeString(activity_id);
+            util::check(result).map_err(|err| os_error!(err).into())
+        }
+    }
+
     fn set_theme(&self, theme: Option<Theme>) {
         try_theme(self.window, theme);
     }
@@ -1004,6 +1300,21 @@ impl CoreWindow for Window {
         window_state.has_active_focus()
     }
 
+    fn set_keyboard_capture(&self, capture: bool) -> Result<(), RequestError> {
+        let window = self.window;
+        let (tx, rx) = channel();
+
+        // The hook is run by the thread which installed it.
+        self.thread_executor.execute_in_thread(move || {
+            let _ = &window;
+            let result = keyboard_capture::set_keyboard_capture(window, capture)
+                .map_err(|err| os_error!(err).into());
+            let _ = tx.send(result);
+        });
+
+        rx.recv().unwrap()
+    }
+
     fn title(&self) -> String {
         let len = unsafe { GetWindowTextLengthW(self.window) } + 1;
         let mut buf = vec![0; len as usize];
@@ -1076,13 +1387,7 @@ pub(super) struct InitData<'a> {
 
 impl<'a> InitData<'a> {
     unsafe fn create_window(&self, window: HWND) -> Window {
-        // Register for touch events if applicable
-        {
-            let digitizer = unsafe { GetSystemMetrics(SM_DIGITIZER) as u32 };
-            if digitizer & NID_READY != 0 {
-                unsafe { RegisterTouchWindow(window, TWF_WANTPALM) };
-            }
-        }
+        register_touch_window(window);
 
         let dpi = unsafe { hwnd_dpi(window) };
         let scale_factor = dpi_to_scale_factor(dpi);
@@ -1131,6 +1436,7 @@ impl<'a> InitData<'a> {
             let file_drop_runner = self.event_loop.runner_shared.clone();
             let file_drop_handler = FileDropHandler::new(
                 win.window,
+                win.window_state.clone(),
                 Box::new(move |event| file_drop_runner.send_event(event)),
             );
 
@@ -1150,6 +1456,10 @@ impl<'a> InitData<'a> {
             _file_drop_handler: file_drop_handler,
             userdata_removed: Cell::new(false),
             recurse_depth: Cell::new(0),
+            wndproc_hook: RefCell::new(None),
+            motion_coalescing: Cell::new(self.attributes.motion_coalescing),
+            #[cfg(feature = "accesskit")]
+            accesskit: RefCell::new(None),
         }
     }
 
@@ -1175,7 +1485,9 @@ impl<'a> InitData<'a> {
         let win = self.window.as_mut().expect("failed window creation");
 
         // making the window transparent
-        if self.attributes.transparent && !self.attributes.platform_specific.no_redirection_bitmap {
+        let no_redirection_bitmap = self.attributes.platform_specific.no_redirection_bitmap
+            || self.attributes.surface_format_hint.is_some_and(|hint| hint.alpha);
+        if self.attributes.transparent && !no_redirection_bitmap {
             // Empty region for the blur effect, so the window is fully transparent
             let region = unsafe { CreateRectRgn(0, 0, -1, -1) };
 
@@ -1196,6 +1508,14 @@ impl<'a> InitData<'a> {
         win.set_window_icon(self.attributes.window_icon.clone());
         win.set_taskbar_icon(self.attributes.platform_specific.taskbar_icon.clone());
 
+        // Set before the window is shown, which is when the taskbar groups it.
+        if let Some(id) = &self.attributes.platform_specific.app_user_model_id {
+            match util::set_app_user_model_id(win.hwnd(), Some(id)) {
+                Ok(()) => win.window_state_lock().app_user_model_id = true,
+                Err(error) => warn!("Setting the AppUserModelID failed: {error}"),
+            }
+        }
+
         let attributes = self.attributes.clone();
 
         if attributes.content_protected {
@@ -1266,8 +1586,13 @@ unsafe fn init(
         .set(WindowFlags::ALWAYS_ON_TOP, attributes.window_level == WindowLevel::AlwaysOnTop);
     window_flags
         .set(WindowFlags::ALWAYS_ON_BOTTOM, attributes.window_level == WindowLevel::AlwaysOnBottom);
-    window_flags
-        .set(WindowFlags::NO_BACK_BUFFER, attributes.platform_specific.no_redirection_bitmap);
+    // Surfaces with alpha are composited by DirectComposition, which requires the window not to
+    // have a redirection bitmap.
+    window_flags.set(
+        WindowFlags::NO_BACK_BUFFER,
+        attributes.platform_specific.no_redirection_bitmap
+            || attributes.surface_format_hint.is_some_and(|hint| hint.alpha),
+    );
     window_flags.set(WindowFlags::MARKER_ACTIVATE, attributes.active);
     window_flags.set(WindowFlags::TRANSPARENT, attributes.transparent);
     // WindowFlags::VISIBLE and MAXIMIZED are set down below after the window has been configured.
@@ -1353,6 +1678,34 @@ unsafe fn init(
     Ok(win)
 }
 
+fn is_material(backdrop_type: BackdropType) -> bool {
+    matches!(
+        backdrop_type,
+        BackdropType::MainWindow | BackdropType::TransientWindow | BackdropType::TabbedWindow
+    )
+}
+
+/// Extend the frame over the whole client area of the transparent windows with a backdrop
+/// material, which then shows through their transparent content.
+fn extend_frame_for_backdrop(window: HWND, backdrop_type: BackdropType, transparent: bool) {
+    let inset = if transparent && is_material(backdrop_type) { -1 } else { 0 };
+    let margins = MARGINS {
+        cxLeftWidth: inset,
+        cxRightWidth: inset,
+        cyTopHeight: inset,
+        cyBottomHeight: inset,
+    };
+    unsafe { DwmExtendFrameIntoClientArea(window, &margins) };
+}
+
+/// Receive the touch input with `WM_TOUCH`, if there's a touch digitizer.
+fn register_touch_window(window: HWND) {
+    let digitizer = unsafe { GetSystemMetrics(SM_DIGITIZER) as u32 };
+    if digitizer & NID_READY != 0 {
+        unsafe { RegisterTouchWindow(window, TWF_WANTPALM) };
+    }
+}
+
 unsafe fn register_window_class(class_name: &[u16]) {
     let class = WNDCLASSEXW {
         cbSize: mem::size_of::<WNDCLASSEXW>() as u32,
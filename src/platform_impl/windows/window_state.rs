@@ -1,9 +1,12 @@
-use std::io;
+use std::ffi::c_void;
 use std::sync::MutexGuard;
+use std::time::Instant;
+use std::{io, mem};
 
 use bitflags::bitflags;
-use windows_sys::Win32::Foundation::{HWND, RECT};
-use windows_sys::Win32::Graphics::Gdi::InvalidateRgn;
+use windows_sys::Win32::Foundation::{HWND, POINT, RECT};
+use windows_sys::Win32::Graphics::Dwm::{DwmSetWindowAttribute, DWMWA_BORDER_COLOR};
+use windows_sys::Win32::Graphics::Gdi::{InvalidateRgn, ScreenToClient, HMONITOR};
 use windows_sys::Win32::UI::WindowsAndMessaging::{
     AdjustWindowRectEx, EnableMenuItem, GetMenu, GetSystemMenu, GetWindowLongW, SendMessageW,
     SetWindowLongW, SetWindowPos, ShowWindow, GWL_EXSTYLE, GWL_STYLE, HWND_BOTTOM, HWND_NOTOPMOST,
@@ -16,11 +19,18 @@ use windows_sys::Win32::UI::WindowsAndMessaging::{
     WS_MINIMIZEBOX, WS_OVERLAPPEDWINDOW, WS_POPUP, WS_SIZEBOX, WS_SYSMENU, WS_VISIBLE,
 };
 
-use crate::dpi::{PhysicalPosition, PhysicalSize, Size};
+use crate::dpi::{PhysicalPosition, PhysicalSize, Position, Size};
+use crate::event::TouchPhase;
 use crate::icon::Icon;
 use crate::keyboard::ModifiersState;
+use crate::monitor::{GammaRamp, HdrMetadata};
+use crate::platform::windows::{
+    BackdropType, Color, DropFormatHandler, TaskbarPreview, ThumbbarButton,
+};
+use crate::platform_impl::platform::ime::SurroundingText;
+use crate::platform_impl::platform::monitor::{self, DeviceGammaRamp};
 use crate::platform_impl::platform::{event_loop, util, Fullscreen, SelectedCursor};
-use crate::window::{Theme, WindowAttributes};
+use crate::window::{CursorGrabPolicy, Theme, WindowAttributes};
 
 /// Contains information about states and the window that the callback is going to use.
 pub(crate) struct WindowState {
@@ -40,6 +50,11 @@ pub(crate) struct WindowState {
 
     pub modifiers_state: ModifiersState,
     pub fullscreen: Option<Fullscreen>,
+    pub fullscreen_hdr: Option<HdrMetadata>,
+    pub gamma_ramp: Option<GammaRamp>,
+    /// Used to restore the gamma ramp of the monitor when the window loses focus.
+    pub saved_gamma_ramp: Option<(HMONITOR, Box<DeviceGammaRamp>)>,
+    pub cursor_grab_policy: CursorGrabPolicy,
     pub current_theme: Theme,
     pub preferred_theme: Option<Theme>,
 
@@ -47,6 +62,13 @@ pub(crate) struct WindowState {
 
     pub ime_state: ImeState,
     pub ime_allowed: bool,
+    /// Used by `WM_IME_REQUEST` to reconvert the selection.
+    pub ime_surrounding_text: Option<SurroundingText>,
+    pub ime_cursor_area: Option<(Position, Size)>,
+    /// The area the candidate window must not cover, in addition to the cursor area.
+    pub ime_exclusion_area: Option<(Position, Size)>,
+    /// Whether the candidate window is hidden and its candidates are sent as events.
+    pub ime_candidates_drawn_by_app: bool,
 
     // Used by WM_NCACTIVATE, WM_SETFOCUS and WM_KILLFOCUS
     pub is_active: bool,
@@ -54,10 +76,101 @@ pub(crate) struct WindowState {
 
     // Flag whether redraw was requested.
     pub redraw_requested: bool,
+    /// The time of the redraw scheduled with the redraw timer.
+    pub redraw_at: Option<Instant>,
 
     pub dragging: bool,
 
     pub skip_taskbar: bool,
+
+    pub touch_gesture: TouchGesture,
+
+    /// Whether a scroll of a precision touchpad is in progress.
+    pub precise_scroll: bool,
+
+    /// The monitor the window is on, with its ICC profile, to report `ColorProfileChanged`.
+    pub color_profile: Option<(HMONITOR, Option<Vec<u8>>)>,
+
+    /// The backdrop, to extend the frame for the materials when the window is transparent.
+    pub backdrop_type: BackdropType,
+
+    /// Whether the window has an AppUserModelID of its own, cleared when it's destroyed.
+    pub app_user_model_id: bool,
+
+    /// The buttons of the toolbar in the taskbar thumbnail.
+    pub thumbbar_buttons: Vec<ThumbbarButton>,
+    /// Whether the toolbar was added, after which it can only be updated.
+    pub thumbbar_added: bool,
+    /// The image shown in the taskbar thumbnail instead of the content of the window.
+    pub taskbar_preview: Option<TaskbarPreview>,
+
+    /// The border color set by the application, applied again when the decorations change.
+    pub border_color: Option<Color>,
+
+    /// The area of the maximize button of the custom title bar, for the snap layouts.
+    pub snap_layout_button: Option<(Position, Size)>,
+
+    /// The additional formats accepted by the drop target.
+    pub drop_format_handler: Option<DropFormatHandler>,
+}
+
+/// The pinch and rotation gesture of two fingers, recognized from the touch contacts.
+#[derive(Debug, Default)]
+pub struct TouchGesture {
+    /// The contacts on the window, with their latest position.
+    contacts: Vec<(u32, PhysicalPosition<f64>)>,
+
+    /// The distance and the angle between the fingers, while exactly two touch the window.
+    span: Option<(f64, f64)>,
+}
+
+impl TouchGesture {
+    /// Update a contact, returning the phase of the gesture with the change of its scale and its
+    /// counterclockwise rotation in degrees.
+    pub fn update(
+        &mut self,
+        id: u32,
+        position: PhysicalPosition<f64>,
+        phase: TouchPhase,
+    ) -> Option<(TouchPhase, f64, f32)> {
+        match phase {
+            TouchPhase::Started => self.contacts.push((id, position)),
+            TouchPhase::Moved => {
+                if let Some(contact) = self.contacts.iter_mut().find(|contact| contact.0 == id) {
+                    contact.1 = position;
+                }
+            },
+            TouchPhase::Ended | TouchPhase::Cancelled => {
+                self.contacts.retain(|contact| contact.0 != id)
+            },
+        }
+
+        let span = match self.contacts.as_slice() {
+            [(_, a), (_, b)] => Some(((b.x - a.x).hypot(b.y - a.y), (b.y - a.y).atan2(b.x - a.x))),
+            _ => None,
+        };
+        let update = match (self.span, span) {
+            (None, Some(_)) => Some((TouchPhase::Started, 0.0, 0.0)),
+            (Some(_), None) if phase == TouchPhase::Cancelled => {
+                Some((TouchPhase::Cancelled, 0.0, 0.0))
+            },
+            (Some(_), None) => Some((TouchPhase::Ended, 0.0, 0.0)),
+            (Some((old_distance, old_angle)), Some((distance, angle))) => {
+                let pinch = if old_distance > 0.0 { distance / old_distance - 1.0 } else { 0.0 };
+                // The y axis points down, so the angle grows clockwise.
+                let mut rotation = (old_angle - angle).to_degrees();
+                if rotation > 180.0 {
+                    rotation -= 360.0;
+                } else if rotation < -180.0 {
+                    rotation += 360.0;
+                }
+                Some((TouchPhase::Moved, pinch, rotation as f32))
+            },
+            (None, None) => None,
+        };
+        self.span = span;
+        update
+    }
 }
 
 #[derive(Clone)]
@@ -164,20 +277,47 @@ impl WindowState {
 
             modifiers_state: ModifiersState::default(),
             fullscreen: None,
+            fullscreen_hdr: None,
+            gamma_ramp: None,
+            saved_gamma_ramp: None,
+            cursor_grab_policy: CursorGrabPolicy::default(),
             current_theme,
             preferred_theme,
             window_flags: WindowFlags::empty(),
 
             ime_state: ImeState::Disabled,
             ime_allowed: false,
+            ime_surrounding_text: None,
+            ime_cursor_area: None,
+            ime_exclusion_area: None,
+            ime_candidates_drawn_by_app: false,
 
             is_active: false,
             is_focused: false,
             redraw_requested: false,
+            redraw_at: None,
 
             dragging: false,
 
             skip_taskbar: false,
+
+            touch_gesture: TouchGesture::default(),
+            precise_scroll: false,
+
+            color_profile: None,
+
+            backdrop_type: BackdropType::default(),
+            app_user_model_id: false,
+
+            thumbbar_buttons: Vec::new(),
+            thumbbar_added: false,
+            taskbar_preview: None,
+
+            border_color: None,
+
+            snap_layout_button: None,
+
+            drop_format_handler: None,
         }
     }
 
@@ -192,9 +332,18 @@ impl WindowState {
         let old_flags = this.window_flags;
         f(&mut this.window_flags);
         let new_flags = this.window_flags;
+        let border_color = this.border_color;
 
         drop(this);
         old_flags.apply_diff(window, new_flags);
+
+        // The frame is recomputed with the decorations, keep the border color with the new one.
+        let frame_flags = WindowFlags::MARKER_DECORATIONS | WindowFlags::MARKER_UNDECORATED_SHADOW;
+        if let Some(color) = border_color {
+            if (old_flags ^ new_flags).intersects(frame_flags) {
+                apply_border_color(window, color);
+            }
+        }
     }
 
     pub fn set_window_flags_in_place<F>(&mut self, f: F)
@@ -204,6 +353,22 @@ impl WindowState {
         f(&mut self.window_flags);
     }
 
+    /// The point in the client area, if the point on the screen is in the maximize button of the
+    /// custom title bar.
+    pub fn snap_layout_button_point(&self, window: HWND, screen_point: POINT) -> Option<POINT> {
+        let (position, size) = self.snap_layout_button?;
+        let position = position.to_physical::<i32>(self.scale_factor);
+        let size = size.to_physical::<u32>(self.scale_factor);
+
+        let mut point = screen_point;
+        unsafe { ScreenToClient(window, &mut point) };
+        let inside = point.x >= position.x
+            && point.y >= position.y
+            && point.x < position.x + size.width as i32
+            && point.y < position.y + size.height as i32;
+        inside.then_some(point)
+    }
+
     pub fn has_active_focus(&self) -> bool {
         self.is_active && self.is_focused
     }
@@ -221,6 +386,34 @@ impl WindowState {
         self.is_focused = is_focused;
         old != self.has_active_focus()
     }
+
+    /// Apply the gamma ramp to the monitor while the window has focus in exclusive fullscreen,
+    /// and restore the previous ramp of the monitor otherwise.
+    pub fn update_gamma_ramp(&mut self) -> Result<(), io::Error> {
+        let target = match (&self.fullscreen, &self.gamma_ramp) {
+            (Some(Fullscreen::Exclusive(video_mode)), Some(ramp)) if self.has_active_focus() => {
+                Some((video_mode.monitor().hmonitor(), monitor::device_gamma_ramp(ramp)))
+            },
+            _ => None,
+        };
+
+        if let Some((hmonitor, saved)) = self.saved_gamma_ramp.take() {
+            if target.as_ref().is_some_and(|(target, _)| *target == hmonitor) {
+                self.saved_gamma_ramp = Some((hmonitor, saved));
+            } else {
+                monitor::set_gamma_ramp(hmonitor, &saved)?;
+            }
+        }
+
+        if let Some((hmonitor, ramp)) = target {
+            if self.saved_gamma_ramp.is_none() {
+                self.saved_gamma_ramp = Some((hmonitor, monitor::gamma_ramp(hmonitor)?));
+            }
+            monitor::set_gamma_ramp(hmonitor, &ramp)?;
+        }
+
+        Ok(())
+    }
 }
 
 impl MouseProperties {
@@ -529,3 +722,15 @@ impl CursorFlags {
         Ok(())
     }
 }
+
+/// Sets `DWMWA_BORDER_COLOR`, which is also drawn around the undecorated windows.
+pub(crate) fn apply_border_color(window: HWND, color: Color) {
+    unsafe {
+        DwmSetWindowAttribute(
+            window,
+            DWMWA_BORDER_COLOR as u32,
+            &color as *const Color as *const c_void,
+            mem::size_of::<Color>() as u32,
+        );
+    }
+}
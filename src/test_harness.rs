@@ -0,0 +1,572 @@
+//! Running the event loop against virtual displays, to test applications with the real backends.
+//!
+//! A [`VirtualDisplay`] spawns and manages a headless display server: `Xvfb` for X11, and
+//! `weston` with its headless backend for Wayland. Both must be installed, e.g. with the `xvfb`
+//! and `weston` packages on Debian and Ubuntu. The event loop is then created against it with
+//! [`VirtualDisplay::event_loop()`].
+//!
+//! A [`Scenario`] scripts the interaction with the application: creating windows, acting on them,
+//! and expecting the resulting events within a timeout.
+//!
+//! Since the event loop can only be created once per process, each scenario should be run in its
+//! own test binary, with `harness = false` to run it on the main thread:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! winit = { version = "...", features = ["winit-test-harness"] }
+//!
+//! [[test]]
+//! name = "resize"
+//! harness = false
+//! ```
+//!
+//! ```no_run
+//! use winit::dpi::PhysicalSize;
+//! use winit::event::WindowEvent;
+//! use winit::test_harness::{Backend, Scenario, VirtualDisplay};
+//! use winit::window::WindowAttributes;
+//!
+//! fn main() {
+//!     let display = VirtualDisplay::spawn(Backend::X11).unwrap();
+//!     let event_loop = display.event_loop().unwrap();
+//!
+//!     Scenario::new()
+//!         .create_window(WindowAttributes::default().with_title("test"))
+//!         .expect("redraw requested", |_, event| matches!(event, WindowEvent::RedrawRequested))
+//!         .act(0, |window| {
+//!             let _ = window.request_surface_size(PhysicalSize::new(200, 100).into());
+//!         })
+//!         .expect("resized", |_, event| {
+//!             matches!(event, WindowEvent::SurfaceResized(size) if size.width == 200)
+//!         })
+//!         .run(event_loop)
+//!         .unwrap();
+//! }
+//! ```
+//!
+//! The `DISPLAY` or `WAYLAND_DISPLAY` environment variables of the process are set when creating
+//! the event loop, so that libraries loaded later connect to the same display.
+
+use std::collections::VecDeque;
+use std::error::Error;
+use std::ffi::OsString;
+use std::path::PathBuf;
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+use std::{env, fmt, fs, io};
+
+use crate::application::ApplicationHandler;
+use crate::dpi::PhysicalSize;
+use crate::error::{EventLoopError, RequestError};
+use crate::event::{DeviceEvent, WindowEvent};
+use crate::event_loop::{ActiveEventLoop, ControlFlow, EventLoop};
+#[cfg(wayland_platform)]
+use crate::platform::wayland::EventLoopBuilderExtWayland;
+#[cfg(x11_platform)]
+use crate::platform::x11::EventLoopBuilderExtX11;
+use crate::window::{Window, WindowAttributes, WindowId};
+
+/// How long to wait for `weston` to accept connections.
+#[cfg(wayland_platform)]
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The name of the `weston` socket in its runtime directory.
+#[cfg(wayland_platform)]
+const WAYLAND_SOCKET: &str = "wayland-0";
+
+/// The backend of a [`VirtualDisplay`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Backend {
+    /// An X11 display served by `Xvfb`.
+    #[cfg(x11_platform)]
+    X11,
+    /// A Wayland display served by the headless backend of `weston`.
+    #[cfg(wayland_platform)]
+    Wayland,
+}
+
+/// A headless display server, killed when dropped.
+#[derive(Debug)]
+pub struct VirtualDisplay {
+    backend: Backend,
+    size: PhysicalSize<u32>,
+    server: Child,
+    /// The value of `DISPLAY` or `WAYLAND_DISPLAY` to connect to the server.
+    display: OsString,
+    /// The runtime directory of `weston`, removed when dropped.
+    runtime_dir: Option<PathBuf>,
+}
+
+impl VirtualDisplay {
+    /// Spawn a display server with a single 1024x768 output.
+    pub fn spawn(backend: Backend) -> io::Result<Self> {
+        Self::spawn_with_size(backend, PhysicalSize::new(1024, 768))
+    }
+
+    /// Spawn a display server with a single output of the given size.
+    ///
+    /// Returns once the server accepts connections.
+    pub fn spawn_with_size(backend: Backend, size: PhysicalSize<u32>) -> io::Result<Self> {
+        match backend {
+            #[cfg(x11_platform)]
+            Backend::X11 => {
+                let (server, display) = spawn_xvfb(size)?;
+                Ok(Self { backend, size, server, display, runtime_dir: None })
+            },
+            #[cfg(wayland_platform)]
+            Backend::Wayland => {
+                let (server, runtime_dir) = spawn_weston(size)?;
+                let display = runtime_dir.join(WAYLAND_SOCKET).into_os_string();
+                Ok(Self { backend, size, server, display, runtime_dir: Some(runtime_dir) })
+            },
+        }
+    }
+
+    /// The backend of the display server.
+    pub fn backend(&self) -> Backend {
+        self.backend
+    }
+
+    /// The size of the output of the display server.
+    pub fn size(&self) -> PhysicalSize<u32> {
+        self.size
+    }
+
+    /// The environment variable and its value to connect to the display server.
+    ///
+    /// This is useful to run other processes against the display.
+    pub fn env(&self) -> (&'static str, &OsString) {
+        match self.backend {
+            #[cfg(x11_platform)]
+            Backend::X11 => ("DISPLAY", &self.display),
+            #[cfg(wayland_platform)]
+            Backend::Wayland => ("WAYLAND_DISPLAY", &self.display),
+        }
+    }
+
+    /// Create the event loop connected to the display server.
+    ///
+    /// The environment of the process is updated to point to this display only, see
+    /// [`VirtualDisplay::env()`].
+    pub fn event_loop(&self) -> Result<EventLoop, EventLoopError> {
+        let mut builder = EventLoop::builder();
+        match self.backend {
+            #[cfg(x11_platform)]
+            Backend::X11 => {
+                env::remove_var("WAYLAND_DISPLAY");
+                env::set_var("DISPLAY", &self.display);
+                builder.with_x11();
+            },
+            #[cfg(wayland_platform)]
+            Backend::Wayland => {
+                env::remove_var("DISPLAY");
+                env::set_var("WAYLAND_DISPLAY", &self.display);
+                builder.with_wayland();
+            },
+        }
+
+        builder.build()
+    }
+}
+
+impl Drop for VirtualDisplay {
+    fn drop(&mut self) {
+        let _ = self.server.kill();
+        let _ = self.server.wait();
+        if let Some(runtime_dir) = &self.runtime_dir {
+            let _ = fs::remove_dir_all(runtime_dir);
+        }
+    }
+}
+
+/// Spawn `Xvfb` on the first free display, which it reports on `-displayfd`.
+#[cfg(x11_platform)]
+fn spawn_xvfb(size: PhysicalSize<u32>) -> io::Result<(Child, OsString)> {
+    use std::fs::File;
+    use std::io::{BufRead, BufReader};
+    use std::os::fd::{FromRawFd, OwnedFd, RawFd};
+
+    let mut fds: [RawFd; 2] = [-1; 2];
+    if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    // SAFETY: The pipe was just created and is owned by nobody else.
+    let (reader, writer) = unsafe { (OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1])) };
+
+    // Only the write end must be inherited by the server.
+    if unsafe { libc::fcntl(fds[0], libc::F_SETFD, libc::FD_CLOEXEC) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    let mut server = Command::new("Xvfb")
+        .arg("-displayfd")
+        .arg(fds[1].to_string())
+        .arg("-screen")
+        .arg("0")
+        .arg(format!("{}x{}x24", size.width, size.height))
+        .args(["-nolisten", "tcp", "-noreset"])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+
+    // Reading reaches the end of file if the server exits before reporting the display.
+    drop(writer);
+    let mut line = String::new();
+    BufReader::new(File::from(reader)).read_line(&mut line)?;
+
+    match line.trim().parse::<u32>() {
+        Ok(display) => Ok((server, format!(":{display}").into())),
+        Err(_) => {
+            let _ = server.kill();
+            let _ = server.wait();
+            Err(io::Error::new(io::ErrorKind::Other, "Xvfb exited without reporting its display"))
+        },
+    }
+}
+
+/// Spawn `weston` in its own runtime directory, and wait for its socket.
+#[cfg(wayland_platform)]
+fn spawn_weston(size: PhysicalSize<u32>) -> io::Result<(Child, PathBuf)> {
+    use std::os::unix::fs::DirBuilderExt;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::{process, thread};
+
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    let runtime_dir = env::temp_dir().join(format!(
+        "winit-test-harness-{}-{}",
+        process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    fs::DirBuilder::new().mode(0o700).create(&runtime_dir)?;
+
+    let spawned = Command::new("weston")
+        .arg("--backend=headless-backend.so")
+        .arg(format!("--socket={WAYLAND_SOCKET}"))
+        .arg(format!("--width={}", size.width))
+        .arg(format!("--height={}", size.height))
+        .arg("--idle-time=0")
+        .env("XDG_RUNTIME_DIR", &runtime_dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+    let mut server = match spawned {
+        Ok(server) => server,
+        Err(err) => {
+            let _ = fs::remove_dir_all(&runtime_dir);
+            return Err(err);
+        },
+    };
+
+    let socket = runtime_dir.join(WAYLAND_SOCKET);
+    let deadline = Instant::now() + STARTUP_TIMEOUT;
+    let error = loop {
+        if socket.exists() {
+            return Ok((server, runtime_dir));
+        }
+
+        match server.try_wait() {
+            Ok(Some(status)) => {
+                break io::Error::new(io::ErrorKind::Other, format!("weston exited with {status}"))
+            },
+            Ok(None) if Instant::now() >= deadline => {
+                break io::Error::new(io::ErrorKind::TimedOut, "weston didn't create its socket")
+            },
+            Ok(None) => thread::sleep(Duration::from_millis(10)),
+            Err(err) => break err,
+        }
+    };
+
+    let _ = server.kill();
+    let _ = server.wait();
+    let _ = fs::remove_dir_all(&runtime_dir);
+    Err(error)
+}
+
+/// A scripted interaction with the application, run step by step.
+///
+/// Windows are referred to by their index, in the order they are created by the scenario.
+pub struct Scenario {
+    steps: VecDeque<Step>,
+    timeout: Duration,
+}
+
+type Action = Box<dyn FnOnce(&dyn Window)>;
+type Predicate = Box<dyn FnMut(usize, &WindowEvent) -> bool>;
+
+enum Step {
+    CreateWindow(Box<WindowAttributes>),
+    Act(usize, Action),
+    Inject(usize, WindowEvent),
+    InjectDevice(DeviceEvent),
+    Expect(String, Predicate),
+    Wait(Duration),
+}
+
+impl fmt::Debug for Scenario {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Scenario")
+            .field("steps", &self.steps.len())
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+impl Default for Scenario {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scenario {
+    /// Create an empty scenario, with expectations timing out after 5 seconds.
+    pub fn new() -> Self {
+        Self { steps: VecDeque::new(), timeout: Duration::from_secs(5) }
+    }
+
+    /// Set how long the following expectations wait for their event.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Create a window, given the next index.
+    pub fn create_window(mut self, attributes: WindowAttributes) -> Self {
+        self.steps.push_back(Step::CreateWindow(Box::new(attributes)));
+        self
+    }
+
+    /// Act on the window with the given index.
+    ///
+    /// # Panics
+    ///
+    /// Running the step panics if no window was created with that index.
+    pub fn act(mut self, window: usize, action: impl FnOnce(&dyn Window) + 'static) -> Self {
+        self.steps.push_back(Step::Act(window, Box::new(action)));
+        self
+    }
+
+    /// Deliver an event to the window with the given index, as if it came from the system.
+    ///
+    /// See [`Window::inject_event()`].
+    ///
+    /// # Panics
+    ///
+    /// Running the step panics if no window was created with that index.
+    pub fn inject(mut self, window: usize, event: WindowEvent) -> Self {
+        self.steps.push_back(Step::Inject(window, event));
+        self
+    }
+
+    /// Deliver a device event as if it came from the system.
+    ///
+    /// See [`ActiveEventLoop::inject_device_event()`].
+    pub fn inject_device_event(mut self, event: DeviceEvent) -> Self {
+        self.steps.push_back(Step::InjectDevice(event));
+        self
+    }
+
+    /// Wait for an event delivered to one of the windows, given its index and the event.
+    ///
+    /// The events delivered since the previous expectation was met are considered, so an event
+    /// caused by the preceding steps isn't missed when it's delivered before the expectation is
+    /// reached. The scenario fails when no event matches within the timeout.
+    pub fn expect(
+        mut self,
+        description: impl Into<String>,
+        predicate: impl FnMut(usize, &WindowEvent) -> bool + 'static,
+    ) -> Self {
+        self.steps.push_back(Step::Expect(description.into(), Box::new(predicate)));
+        self
+    }
+
+    /// Keep processing events for the given duration.
+    pub fn wait(mut self, duration: Duration) -> Self {
+        self.steps.push_back(Step::Wait(duration));
+        self
+    }
+
+    /// Run the scenario on the event loop, exiting once all the steps completed.
+    pub fn run(self, event_loop: EventLoop) -> Result<(), ScenarioError> {
+        let mut runner = Runner {
+            steps: self.steps,
+            timeout: self.timeout,
+            step: 0,
+            deadline: None,
+            windows: Vec::new(),
+            events: Vec::new(),
+            result: Ok(()),
+        };
+        event_loop.run_app(&mut runner)?;
+        runner.result
+    }
+}
+
+/// An error which made a [`Scenario`] fail.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ScenarioError {
+    /// The event loop failed.
+    EventLoop(EventLoopError),
+    /// Creating a window failed at the given step.
+    CreateWindow { step: usize, error: RequestError },
+    /// Injecting an event failed at the given step.
+    Inject { step: usize, error: RequestError },
+    /// The expected event wasn't delivered in time at the given step.
+    Timeout { step: usize, description: String },
+}
+
+impl fmt::Display for ScenarioError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EventLoop(err) => err.fmt(f),
+            Self::CreateWindow { step, error } => {
+                write!(f, "step {step}: failed to create the window: {error}")
+            },
+            Self::Inject { step, error } => {
+                write!(f, "step {step}: failed to inject the event: {error}")
+            },
+            Self::Timeout { step, description } => {
+                write!(f, "step {step}: timed out waiting for {description}")
+            },
+        }
+    }
+}
+
+impl Error for ScenarioError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::EventLoop(err) => Some(err),
+            Self::CreateWindow { error, .. } | Self::Inject { error, .. } => Some(error),
+            Self::Timeout { .. } => None,
+        }
+    }
+}
+
+impl From<EventLoopError> for ScenarioError {
+    fn from(value: EventLoopError) -> Self {
+        Self::EventLoop(value)
+    }
+}
+
+struct Runner {
+    steps: VecDeque<Step>,
+    timeout: Duration,
+    /// The index of the current step.
+    step: usize,
+    /// When the current waiting step completes or times out.
+    deadline: Option<Instant>,
+    windows: Vec<Box<dyn Window>>,
+    /// The events not yet matched by an expectation.
+    events: Vec<(usize, WindowEvent)>,
+    result: Result<(), ScenarioError>,
+}
+
+impl Runner {
+    /// Run the steps until one has to wait for events.
+    fn advance(&mut self, event_loop: &dyn ActiveEventLoop) {
+        while let Some(step) = self.steps.front_mut() {
+            let now = Instant::now();
+            match step {
+                Step::CreateWindow(_) => {
+                    let Some(Step::CreateWindow(attributes)) = self.steps.pop_front() else {
+                        unreachable!()
+                    };
+                    match event_loop.create_window(*attributes) {
+                        Ok(window) => self.windows.push(window),
+                        Err(error) => {
+                            return self.fail(event_loop, ScenarioError::CreateWindow {
+                                step: self.step,
+                                error,
+                            })
+                        },
+                    }
+                },
+                Step::Act(..) => {
+                    let Some(Step::Act(window, action)) = self.steps.pop_front() else {
+                        unreachable!()
+                    };
+                    action(self.windows[window].as_ref());
+                },
+                Step::Inject(..) | Step::InjectDevice(_) => {
+                    let result = match self.steps.pop_front() {
+                        Some(Step::Inject(window, event)) => {
+                            self.windows[window].inject_event(event)
+                        },
+                        Some(Step::InjectDevice(event)) => {
+                            event_loop.inject_device_event(None, event)
+                        },
+                        _ => unreachable!(),
+                    };
+                    if let Err(error) = result {
+                        return self
+                            .fail(event_loop, ScenarioError::Inject { step: self.step, error });
+                    }
+                },
+                Step::Expect(description, predicate) => {
+                    if let Some(index) = self.events.iter().position(|(w, e)| predicate(*w, e)) {
+                        self.events.drain(..=index);
+                        self.deadline = None;
+                        self.steps.pop_front();
+                    } else {
+                        let deadline = *self.deadline.get_or_insert(now + self.timeout);
+                        if now < deadline {
+                            return event_loop.set_control_flow(ControlFlow::WaitUntil(deadline));
+                        }
+
+                        let description = description.clone();
+                        return self.fail(event_loop, ScenarioError::Timeout {
+                            step: self.step,
+                            description,
+                        });
+                    }
+                },
+                Step::Wait(duration) => {
+                    let deadline = *self.deadline.get_or_insert(now + *duration);
+                    if now < deadline {
+                        return event_loop.set_control_flow(ControlFlow::WaitUntil(deadline));
+                    }
+
+                    self.deadline = None;
+                    self.steps.pop_front();
+                },
+            }
+
+            self.step += 1;
+        }
+
+        event_loop.exit();
+    }
+
+    fn fail(&mut self, event_loop: &dyn ActiveEventLoop, error: ScenarioError) {
+        self.steps.clear();
+        self.result = Err(error);
+        event_loop.exit();
+    }
+}
+
+impl ApplicationHandler for Runner {
+    fn can_create_surfaces(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.advance(event_loop);
+    }
+
+    fn window_event(
+        &mut self,
+        _event_loop: &dyn ActiveEventLoop,
+        window_id: WindowId,
+        event: WindowEvent,
+    ) {
+        if let Some(index) = self.windows.iter().position(|window| window.id() == window_id) {
+            self.events.push((index, event));
+        }
+    }
+
+    fn about_to_wait(&mut self, event_loop: &dyn ActiveEventLoop) {
+        if !self.steps.is_empty() {
+            self.advance(event_loop);
+        }
+    }
+}
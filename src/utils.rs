@@ -7,6 +7,13 @@
 use std::any::Any;
 use std::ops::Deref;
 use std::sync::OnceLock;
+#[cfg(not(web_platform))]
+use std::time::Instant;
+
+#[cfg(web_platform)]
+use web_time::Instant;
+
+use crate::window::WindowId;
 
 pub(crate) struct Lazy<T> {
     cell: OnceLock<T>,
@@ -17,6 +24,11 @@ impl<T> Lazy<T> {
     pub const fn new(f: fn() -> T) -> Self {
         Self { cell: OnceLock::new(), init: f }
     }
+
+    /// The value, without initializing it.
+    pub fn get(&self) -> Option<&T> {
+        self.cell.get()
+    }
 }
 
 impl<T> Deref for Lazy<T> {
@@ -44,3 +56,75 @@ impl<T: Any> AsAny for T {
         self
     }
 }
+
+/// The redraws scheduled with [`Window::request_redraw_at()`], for the backends which wait for
+/// them in their event loop.
+///
+/// Only the earliest redraw of each window is kept, since the application usually schedules the
+/// next one when handling [`WindowEvent::RedrawRequested`].
+///
+/// [`Window::request_redraw_at()`]: crate::window::Window::request_redraw_at()
+/// [`WindowEvent::RedrawRequested`]: crate::event::WindowEvent::RedrawRequested
+#[derive(Debug, Default)]
+pub(crate) struct RedrawSchedule {
+    redraws: Vec<(WindowId, Instant)>,
+}
+
+impl RedrawSchedule {
+    /// Schedule a redraw of the window at `deadline`, unless an earlier one is already scheduled.
+    pub fn schedule(&mut self, window_id: WindowId, deadline: Instant) {
+        match self.redraws.iter_mut().find(|(id, _)| *id == window_id) {
+            Some((_, scheduled)) => *scheduled = deadline.min(*scheduled),
+            None => self.redraws.push((window_id, deadline)),
+        }
+    }
+
+    /// The deadline of the next redraw.
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.redraws.iter().map(|&(_, deadline)| deadline).min()
+    }
+
+    /// Whether a redraw is due at `now`.
+    pub fn is_due(&self, now: Instant) -> bool {
+        self.next_deadline().is_some_and(|deadline| deadline <= now)
+    }
+
+    /// Remove the redraws which are due at `now`, returning their windows.
+    pub fn take_due(&mut self, now: Instant) -> Vec<WindowId> {
+        let mut due = Vec::new();
+        self.redraws.retain(|&(window_id, deadline)| {
+            if deadline <= now {
+                due.push(window_id);
+            }
+            deadline > now
+        });
+        due
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn redraw_schedule() {
+        let (a, b) = (WindowId::from_raw(1), WindowId::from_raw(2));
+        let now = Instant::now();
+        let mut schedule = RedrawSchedule::default();
+        assert_eq!(schedule.next_deadline(), None);
+
+        schedule.schedule(a, now + Duration::from_millis(20));
+        schedule.schedule(a, now + Duration::from_millis(10));
+        schedule.schedule(a, now + Duration::from_millis(30));
+        schedule.schedule(b, now + Duration::from_millis(15));
+        assert_eq!(schedule.next_deadline(), Some(now + Duration::from_millis(10)));
+        assert!(!schedule.is_due(now));
+
+        assert_eq!(schedule.take_due(now + Duration::from_millis(12)), vec![a]);
+        assert_eq!(schedule.take_due(now + Duration::from_millis(12)), vec![]);
+        assert_eq!(schedule.take_due(now + Duration::from_millis(15)), vec![b]);
+        assert_eq!(schedule.next_deadline(), None);
+    }
+}
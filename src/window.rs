@@ -1,16 +1,26 @@
 //! The [`Window`] struct and associated types.
 use std::fmt;
+#[cfg(not(web_platform))]
+use std::time::{Duration, Instant};
 
 #[doc(inline)]
 pub use cursor_icon::{CursorIcon, ParseError as CursorIconParseError};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+#[cfg(web_platform)]
+use web_time::{Duration, Instant};
 
-pub use crate::cursor::{BadImage, Cursor, CustomCursor, CustomCursorSource, MAX_CURSOR_SIZE};
+pub use crate::cursor::{
+    BadCursorFile, BadImage, Cursor, CursorThemeInfo, CustomCursor, CustomCursorSource,
+    MAX_CURSOR_SIZE,
+};
 use crate::dpi::{PhysicalPosition, PhysicalSize, Position, Size};
-use crate::error::RequestError;
-pub use crate::icon::{BadIcon, Icon};
-use crate::monitor::{MonitorHandle, VideoModeHandle};
+use crate::error::{NotSupportedError, RequestError};
+use crate::event::DeviceId;
+#[cfg(feature = "winit-test-harness")]
+use crate::event::WindowEvent;
+pub use crate::icon::{BadIcon, Icon, IconImage};
+use crate::monitor::{self, GammaRamp, HdrMetadata, MonitorHandle, VideoModeHandle};
 use crate::platform_impl::PlatformSpecificWindowAttributes;
 use crate::utils::AsAny;
 
@@ -67,6 +77,8 @@ pub struct WindowAttributes {
     pub window_level: WindowLevel,
     pub active: bool,
     pub cursor: Cursor,
+    pub motion_coalescing: Coalescing,
+    pub surface_format_hint: Option<SurfaceFormatHint>,
     #[cfg(feature = "rwh_06")]
     pub(crate) parent_window: Option<SendSyncRawWindowHandle>,
     pub fullscreen: Option<Fullscreen>,
@@ -98,6 +110,8 @@ impl Default for WindowAttributes {
             preferred_theme: None,
             content_protected: false,
             cursor: Cursor::default(),
+            motion_coalescing: Coalescing::default(),
+            surface_format_hint: None,
             #[cfg(feature = "rwh_06")]
             parent_window: None,
             active: true,
@@ -394,6 +408,43 @@ impl WindowAttributes {
         self
     }
 
+    /// Sets how the pointer motion is coalesced before being delivered to the window.
+    ///
+    /// The default is [`Coalescing::None`]. It's ignored on the platforms where
+    /// [`Window::set_motion_coalescing()`] isn't supported.
+    ///
+    /// See [`Window::set_motion_coalescing()`] for more details.
+    #[inline]
+    pub fn with_motion_coalescing(mut self, coalescing: Coalescing) -> Self {
+        self.motion_coalescing = coalescing;
+        self
+    }
+
+    /// Requests the color format of the surface the window is presented with.
+    ///
+    /// The surface is configured when the window is created, so renderers get the requested
+    /// format without reconfiguring the window afterwards. It's only a hint, renderers must still
+    /// pick a matching format for their swap chain.
+    ///
+    /// The default is `None`, which leaves the format of the system.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** The view is backed by a `CAMetalLayer` with the requested color space, and
+    ///   extended dynamic range for HDR.
+    /// - **Wayland:** The image description is set with the color management protocol, when the
+    ///   compositor supports it.
+    /// - **Windows:** Only `alpha` is applied, the color space of the swap chain is chosen by the
+    ///   renderer.
+    /// - **X11:** HDR picks a visual with 10 bits per channel, `alpha` a visual with 32 bits of
+    ///   depth. The color space isn't applied.
+    /// - **iOS / Android / Web / Orbital:** Unsupported.
+    #[inline]
+    pub fn with_surface_format_hint(mut self, hint: SurfaceFormatHint) -> Self {
+        self.surface_format_hint = Some(hint);
+        self
+    }
+
     /// Build window with parent window.
     ///
     /// The default is `None`.
@@ -533,6 +584,35 @@ pub trait Window: AsAny + Send + Sync {
     /// [`WindowEvent::RedrawRequested`]: crate::event::WindowEvent::RedrawRequested
     fn request_redraw(&self);
 
+    /// Schedules a [`WindowEvent::RedrawRequested`] event to be emitted at `instant`, as with
+    /// [`Window::request_redraw()`] once it's reached.
+    ///
+    /// This lets animations whose next change is known ahead, like a blinking cursor, redraw at
+    /// the right time without waking up the whole event loop with [`ControlFlow::WaitUntil`].
+    /// Only the earliest scheduled redraw of the window is kept, so it's usually scheduled again
+    /// when handling the event.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** The deadline is rounded to the resolution of the system timer, which is
+    ///   usually around 15ms.
+    ///
+    /// [`WindowEvent::RedrawRequested`]: crate::event::WindowEvent::RedrawRequested
+    /// [`ControlFlow::WaitUntil`]: crate::event_loop::ControlFlow::WaitUntil
+    fn request_redraw_at(&self, instant: Instant);
+
+    /// Schedules a [`WindowEvent::RedrawRequested`] event to be emitted after `delay`.
+    ///
+    /// See [`Window::request_redraw_at()`] for details.
+    ///
+    /// [`WindowEvent::RedrawRequested`]: crate::event::WindowEvent::RedrawRequested
+    fn request_redraw_after(&self, delay: Duration) {
+        // Delays too large to be represented are never reached.
+        if let Some(instant) = Instant::now().checked_add(delay) {
+            self.request_redraw_at(instant);
+        }
+    }
+
     /// Notify the windowing system before presenting to the window.
     ///
     /// You should call this event after your drawing operations, but before you submit
@@ -615,6 +695,66 @@ pub trait Window: AsAny + Send + Sync {
     /// - **Android / Wayland:** Always returns [`RequestError::NotSupported`].
     fn outer_position(&self) -> Result<PhysicalPosition<i32>, RequestError>;
 
+    /// Converts a position in the surface of the window to a position relative to the top-left
+    /// hand corner of `monitor`.
+    ///
+    /// The resulting position is in the physical pixels of `monitor`, which accounts for the
+    /// scale factors of the window and the monitor when they differ. The position doesn't need
+    /// to lie on the monitor.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Android / Wayland:** Always returns [`RequestError::NotSupported`], like
+    ///   [`Window::inner_position`].
+    /// - **Web:** Returns [`RequestError::NotSupported`] when the position of the monitor isn't
+    ///   available, see [`MonitorHandle::position`].
+    fn position_to_monitor(
+        &self,
+        position: PhysicalPosition<f64>,
+        monitor: &MonitorHandle,
+    ) -> Result<PhysicalPosition<f64>, RequestError> {
+        let desktop = surface_to_desktop(self.inner_position()?, self.scale_factor(), position);
+        let origin = monitor_origin(monitor)?;
+        Ok(desktop_to_surface(origin, monitor.scale_factor(), desktop))
+    }
+
+    /// Converts a position relative to the top-left hand corner of `monitor` to a position in
+    /// the surface of the window.
+    ///
+    /// This is the inverse of [`Window::position_to_monitor`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// See [`Window::position_to_monitor`].
+    fn position_from_monitor(
+        &self,
+        position: PhysicalPosition<f64>,
+        monitor: &MonitorHandle,
+    ) -> Result<PhysicalPosition<f64>, RequestError> {
+        let desktop =
+            surface_to_desktop(monitor_origin(monitor)?, monitor.scale_factor(), position);
+        Ok(desktop_to_surface(self.inner_position()?, self.scale_factor(), desktop))
+    }
+
+    /// Converts a position in the surface of the window to a position in the surface of `other`.
+    ///
+    /// This accounts for the scale factors of both windows, so positions can be exchanged
+    /// between windows on monitors with different scale factors, e.g. while dragging content
+    /// between them.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Android / Wayland:** Always returns [`RequestError::NotSupported`], like
+    ///   [`Window::inner_position`].
+    fn position_to_window(
+        &self,
+        position: PhysicalPosition<f64>,
+        other: &dyn Window,
+    ) -> Result<PhysicalPosition<f64>, RequestError> {
+        let desktop = surface_to_desktop(self.inner_position()?, self.scale_factor(), position);
+        Ok(desktop_to_surface(other.inner_position()?, other.scale_factor(), desktop))
+    }
+
     /// Modifies the position of the window.
     ///
     /// See [`Window::outer_position`] for more information about the coordinates.
@@ -933,6 +1073,42 @@ pub trait Window: AsAny + Send + Sync {
     /// - **Web:** Can only return `None` or `Borderless(None)`.
     fn fullscreen(&self) -> Option<Fullscreen>;
 
+    /// Request HDR10 output while the window is in [`Fullscreen::Exclusive`].
+    ///
+    /// Passing `Some` switches the monitor to the BT.2020 color space with the PQ transfer function
+    /// when entering exclusive fullscreen, or right away when the window is already in it. The
+    /// metadata describes the content, and should also be given to the graphics API presenting
+    /// it. Passing `None` restores SDR output.
+    ///
+    /// The monitor is switched back to its previous state when leaving exclusive fullscreen.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Toggles the advanced color state of the monitor, which must support HDR.
+    /// - **iOS / Android / Web / macOS / Orbital / Wayland / X11:** Unsupported.
+    fn set_fullscreen_hdr(&self, hdr: Option<HdrMetadata>) -> Result<(), RequestError>;
+
+    /// Gets the HDR metadata requested with [`Window::set_fullscreen_hdr`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / Android / Web / macOS / Orbital / Wayland / X11:** Always returns `None`.
+    fn fullscreen_hdr(&self) -> Option<HdrMetadata>;
+
+    /// Set the gamma ramp of the monitor while the window is in [`Fullscreen::Exclusive`].
+    ///
+    /// The ramp is applied while the window has focus. The previous ramp of the monitor is
+    /// restored when the window loses focus, and the ramp is applied again when it regains it.
+    /// Passing `None`, or leaving exclusive fullscreen, restores the previous ramp for good.
+    ///
+    /// Returns [`RequestError::Ignored`] when passing `Some` outside of exclusive fullscreen.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** The system rejects ramps that deviate too much from the identity.
+    /// - **iOS / Android / Web / Orbital / Wayland:** Unsupported.
+    fn set_gamma_ramp(&self, ramp: Option<&GammaRamp>) -> Result<(), RequestError>;
+
     /// Turn window decorations on or off.
     ///
     /// Enable/disable window decorations provided by the server or Winit.
@@ -1019,6 +1195,34 @@ pub trait Window: AsAny + Send + Sync {
     /// [japanese]: https://support.apple.com/guide/japanese-input-method/use-the-candidate-window-jpim10262/6.3/mac/12.0
     fn set_ime_cursor_area(&self, position: Position, size: Size);
 
+    /// Sets the area the IME candidate window must not cover, like the whole text field or the
+    /// line being edited, and anchors the candidate window outside of it.
+    ///
+    /// The candidate window is still placed at the area set with
+    /// [`Window::set_ime_cursor_area`], but moved when it would cover the exclusion area. Passing
+    /// `None` restores the default, which only excludes the cursor area.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Only applies to the IMEs using the candidate window of IMM32.
+    /// - **macOS / Wayland / X11 / iOS / Android / Web / Orbital:** Unsupported.
+    fn set_ime_exclusion_area(&self, area: Option<(Position, Size)>) -> Result<(), RequestError>;
+
+    /// Hides the candidate window of the IME, and delivers its candidates to the application
+    /// instead.
+    ///
+    /// This allows the applications drawing their own interface, like games in exclusive
+    /// fullscreen where the candidate window can't be shown, to draw the candidates themselves.
+    /// The candidates are delivered with [`Ime::Candidates`], while the IME is enabled.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** The IMEs not supporting the candidate list of IMM32 still show their window.
+    /// - **macOS / Wayland / X11 / iOS / Android / Web / Orbital:** Unsupported.
+    ///
+    /// [`Ime::Candidates`]: crate::event::Ime::Candidates
+    fn set_ime_candidates_drawn_by_app(&self, drawn_by_app: bool) -> Result<(), RequestError>;
+
     /// Sets whether the window should get IME events
     ///
     /// When IME is allowed, the window will receive [`Ime`] events, and during the
@@ -1043,13 +1247,55 @@ pub trait Window: AsAny + Send + Sync {
     /// [`KeyboardInput`]: crate::event::WindowEvent::KeyboardInput
     fn set_ime_allowed(&self, allowed: bool);
 
+    /// Shows or hides the on-screen keyboard of the system.
+    ///
+    /// This allows applications on touchscreens without a physical keyboard, like kiosks, to ask
+    /// for the keyboard when a text field is focused. The area covered by the keyboard is reported
+    /// with [`WindowEvent::VirtualKeyboardGeometryChanged`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Toggles the touch keyboard when it isn't already in the requested state.
+    /// - **Wayland:** Enables the text input of the window, which makes the compositors with an
+    ///   on-screen keyboard show it. The typed text is delivered with [`Ime`] events.
+    /// - **iOS:** Same as [`Window::set_ime_allowed`].
+    /// - **Android:** Shows or hides the soft input.
+    /// - **Web / macOS / X11 / Orbital:** Unsupported.
+    ///
+    /// [`WindowEvent::VirtualKeyboardGeometryChanged`]: crate::event::WindowEvent::VirtualKeyboardGeometryChanged
+    /// [`Ime`]: crate::event::WindowEvent::Ime
+    fn set_virtual_keyboard_visible(&self, visible: bool) -> Result<(), RequestError>;
+
     /// Sets the IME purpose for the window using [`ImePurpose`].
     ///
+    /// The on-screen keyboards and the IMEs present the layout for the purpose, and disable the
+    /// prediction for passwords.
+    ///
     /// ## Platform-specific
     ///
-    /// - **iOS / Android / Web / Windows / X11 / macOS / Orbital:** Unsupported.
+    /// - **Android / Web / X11 / Orbital:** Unsupported.
     fn set_ime_purpose(&self, purpose: ImePurpose);
 
+    /// Sets the text around the cursor for the IME, in response to
+    /// [`Ime::SurroundingTextRequest`].
+    ///
+    /// The text is usually the paragraph of the cursor, without the preedit. The `cursor` and
+    /// `anchor` are the byte offsets of the ends of the selection in the text, and are equal when
+    /// nothing is selected. The text is ignored when they aren't on char boundaries. It should
+    /// also be set when the text or the selection changes.
+    ///
+    /// The IME reconverts the selected text by replacing it with a preedit, and the committed text
+    /// then replaces the selection.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** The text is shortened around the selection to the 4000 bytes allowed by the
+    ///   protocol.
+    /// - **iOS / Android / Web / X11 / Orbital:** Unsupported.
+    ///
+    /// [`Ime::SurroundingTextRequest`]: crate::event::Ime::SurroundingTextRequest
+    fn set_ime_surrounding_text(&self, text: &str, cursor: usize, anchor: usize);
+
     /// Brings the window to the front and sets input focus. Has no effect if the window is
     /// already in focus, minimized, or not visible.
     ///
@@ -1069,6 +1315,26 @@ pub trait Window: AsAny + Send + Sync {
     /// [`WindowEvent::Focused`]: crate::event::WindowEvent::Focused
     fn has_focus(&self) -> bool;
 
+    /// Capture the keyboard shortcuts of the system while the window has focus.
+    ///
+    /// The keys normally handled by the system, like <kbd>Alt</kbd>+<kbd>Tab</kbd>,
+    /// <kbd>Cmd</kbd>+<kbd>Tab</kbd>, the <kbd>Windows</kbd> key and the media keys, are then
+    /// delivered to the window as [`WindowEvent::KeyboardInput`]. This is meant for remote desktop
+    /// clients and virtual machines, which forward them to the remote system.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Uses a low-level keyboard hook. <kbd>Ctrl</kbd>+<kbd>Alt</kbd>+<kbd>Del</kbd>
+    ///   can't be captured.
+    /// - **macOS:** Uses an event tap, which requires the accessibility permission.
+    /// - **Wayland:** Requires the `keyboard-shortcuts-inhibit` protocol, and the compositor may
+    ///   ask the user to allow it.
+    /// - **Web:** Uses the Keyboard Lock API, which only applies in fullscreen.
+    /// - **X11 / iOS / Android / Orbital:** Always returns an [`RequestError::NotSupported`].
+    ///
+    /// [`WindowEvent::KeyboardInput`]: crate::event::WindowEvent::KeyboardInput
+    fn set_keyboard_capture(&self, capture: bool) -> Result<(), RequestError>;
+
     /// Requests user attention to the window, this has no effect if the application
     /// is already focused. How requesting for user attention manifests is platform dependent,
     /// see [`UserAttentionType`] for details.
@@ -1084,6 +1350,61 @@ pub trait Window: AsAny + Send + Sync {
     /// - **Wayland:** Requires `xdg_activation_v1` protocol, `None` has no effect.
     fn request_user_attention(&self, request_type: Option<UserAttentionType>);
 
+    /// Asks the screen reader to speak a message, like a transient status such as "File saved".
+    ///
+    /// This doesn't require the application to expose an accessibility tree. How the message
+    /// interrupts the other speech is chosen with the [`Politeness`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Raises a UI Automation notification for the window.
+    /// - **macOS:** Posts an announcement request with the priority matching the politeness.
+    /// - **iOS:** Posts an announcement, the politeness is ignored.
+    /// - **Web:** Sets the text of a hidden ARIA live region of the canvas.
+    /// - **X11 / Wayland / Android / Orbital:** Always returns an [`RequestError::NotSupported`].
+    fn announce(&self, message: &str, politeness: Politeness) -> Result<(), RequestError>;
+
+    /// Exposes the content of the window to the assistive technologies with AccessKit.
+    ///
+    /// The window creates the platform adapter of AccessKit and owns it until it's dropped. The
+    /// `source` is asked for the initial tree once an assistive technology starts using the
+    /// window, the changes are then pushed with [`Window::update_accesskit_tree`], and the actions
+    /// are delivered as [`WindowEvent::AccessKitActionRequested`]. See the [`accessibility`]
+    /// module for an example.
+    ///
+    /// This should be called before the window is first shown, so create the window with
+    /// [`WindowAttributes::with_visible(false)`] and make it visible afterwards. Calling it again
+    /// replaces the adapter.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / Android / Web / Orbital:** Always returns an [`RequestError::NotSupported`].
+    ///
+    /// [`WindowEvent::AccessKitActionRequested`]: crate::event::WindowEvent::AccessKitActionRequested
+    /// [`accessibility`]: crate::accessibility
+    /// [`WindowAttributes::with_visible(false)`]: WindowAttributes::with_visible
+    #[cfg(feature = "accesskit")]
+    fn set_accesskit_tree_source(
+        &self,
+        source: Box<dyn accesskit::ActivationHandler + Send>,
+    ) -> Result<(), RequestError> {
+        let _ = source;
+        Err(NotSupportedError::new("set_accesskit_tree_source is not supported").into())
+    }
+
+    /// Pushes a change of the accessibility tree to the assistive technologies.
+    ///
+    /// The update is dropped while no assistive technology uses the window, the initial tree
+    /// being requested from the source given to [`Window::set_accesskit_tree_source`] instead.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / Android / Web / Orbital:** Unsupported.
+    #[cfg(feature = "accesskit")]
+    fn update_accesskit_tree(&self, update: accesskit::TreeUpdate) {
+        let _ = update;
+    }
+
     /// Set or override the window theme.
     ///
     /// Specify `None` to reset the theme to the system default.
@@ -1171,6 +1492,15 @@ pub trait Window: AsAny + Send + Sync {
     /// ```
     fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), RequestError>;
 
+    /// Sets what happens to the cursor grab when the window loses focus.
+    ///
+    /// See [`CursorGrabPolicy`] for details.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / Android / Web / Orbital:** Unsupported.
+    fn set_cursor_grab_policy(&self, policy: CursorGrabPolicy);
+
     /// Modifies the cursor's visibility.
     ///
     /// If `false`, this will hide the cursor. If `true`, this will show the cursor.
@@ -1185,6 +1515,44 @@ pub trait Window: AsAny + Send + Sync {
     /// - **iOS / Android:** Unsupported.
     fn set_cursor_visible(&self, visible: bool);
 
+    /// Modifies the cursor icon of the window for a single pointing device, taking precedence over
+    /// [`Window::set_cursor()`] and [`Window::set_cursor_visible()`] for that device.
+    ///
+    /// `device_id` is the one of its pointer events, like [`WindowEvent::PointerMoved`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** The device is a master pointer, the cursor is shared by the physical devices
+    ///   attached to it.
+    /// - **Windows / macOS / Wayland / Web / iOS / Android / Orbital:** Always returns an
+    ///   [`RequestError::NotSupported`], their pointer events have no device.
+    ///
+    /// [`WindowEvent::PointerMoved`]: crate::event::WindowEvent::PointerMoved
+    fn set_cursor_for_device(
+        &self,
+        device_id: DeviceId,
+        cursor: Cursor,
+    ) -> Result<(), RequestError>;
+
+    /// Modifies the cursor's visibility for a single pointing device, like hiding it for a pen
+    /// while keeping it for the mouse.
+    ///
+    /// See [`Window::set_cursor_for_device()`] for the device.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** The device is a master pointer, the cursor is shared by the physical devices
+    ///   attached to it.
+    /// - **Web:** The cursor is hidden while the device is the last one to enter or move over the
+    ///   window.
+    /// - **Windows / macOS / Wayland / iOS / Android / Orbital:** Always returns an
+    ///   [`RequestError::NotSupported`], their pointer events have no device.
+    fn set_cursor_visible_for_device(
+        &self,
+        device_id: DeviceId,
+        visible: bool,
+    ) -> Result<(), RequestError>;
+
     /// Moves the window with the left mouse button until the button is released.
     ///
     /// There's no guarantee that this will work unless the left mouse button was pressed
@@ -1231,11 +1599,61 @@ pub trait Window: AsAny + Send + Sync {
     /// - **iOS / Android / Web / Orbital:** Always returns an [`RequestError::NotSupported`].
     fn set_cursor_hittest(&self, hittest: bool) -> Result<(), RequestError>;
 
+    /// Sets how the [`WindowEvent::PointerMoved`] events are coalesced before being delivered.
+    ///
+    /// Painting applications usually want every sample reported by the system, while games only
+    /// care about the latest position once per frame. See [`Coalescing`] for the available modes.
+    ///
+    /// The motion of every pointer source is coalesced, each finger of a touch screen separately.
+    /// Other events are never coalesced, including the raw motion of
+    /// [`DeviceEvent::PointerMotion`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows / macOS:** The system already merges the mouse motion, [`Coalescing::None`]
+    ///   delivers it as the system reports it.
+    /// - **iOS / Android / Web / Orbital:** Always returns an [`RequestError::NotSupported`], the
+    ///   motion is delivered as coalesced by the system.
+    ///
+    /// [`WindowEvent::PointerMoved`]: crate::event::WindowEvent::PointerMoved
+    /// [`DeviceEvent::PointerMotion`]: crate::event::DeviceEvent::PointerMotion
+    fn set_motion_coalescing(&self, coalescing: Coalescing) -> Result<(), RequestError>;
+
     /// Returns the monitor on which the window currently resides.
     ///
     /// Returns `None` if current monitor can't be detected.
     fn current_monitor(&self) -> Option<MonitorHandle>;
 
+    /// Returns the monitors the window overlaps, with the fraction of the window covered by each
+    /// of them, from the most to the least covered.
+    ///
+    /// The coverage is between `0.0` and `1.0`, and the coverages sum to less than `1.0` when the
+    /// window is partly outside of every monitor.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** The position of the window is unknown, so this returns
+    ///   [`Window::current_monitor()`] with a coverage of `1.0`.
+    fn intersecting_monitors(&self) -> Vec<(MonitorHandle, f64)> {
+        let Ok(position) = self.outer_position() else {
+            return self.current_monitor().map(|monitor| (monitor, 1.0)).into_iter().collect();
+        };
+        let window = (position, self.outer_size());
+        let area = window.1.width as f64 * window.1.height as f64;
+        if area == 0.0 {
+            return Vec::new();
+        }
+        let mut monitors: Vec<_> = self
+            .available_monitors()
+            .filter_map(|monitor| {
+                let covered = monitor::intersection_area(monitor.bounds()?, window);
+                (covered != 0).then(|| (monitor, covered as f64 / area))
+            })
+            .collect();
+        monitors.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+        monitors
+    }
+
     /// Returns the list of all the monitors available on the system.
     ///
     /// This is the same as [`ActiveEventLoop::available_monitors`], and is provided for
@@ -1275,6 +1693,24 @@ pub trait Window: AsAny + Send + Sync {
     /// [`ActiveEventLoop::primary_monitor`]: crate::event_loop::ActiveEventLoop::primary_monitor
     fn primary_monitor(&self) -> Option<MonitorHandle>;
 
+    /// Deliver an event to this window as if it came from the system, to test applications
+    /// end-to-end.
+    ///
+    /// The event is delivered on the next iteration of the event loop, through the same
+    /// processing as the events of the system: it's held back while a modal dialog blocks the
+    /// window, ordered with the modifiers changes, and coalesced with the pointer motion. An
+    /// injected [`WindowEvent::ModifiersChanged`] also updates the modifiers the backend tracks.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** The modifiers tracked for the seat aren't updated.
+    /// - **Windows / macOS / iOS / Android / Web / Orbital:** Unsupported.
+    #[cfg(feature = "winit-test-harness")]
+    fn inject_event(&self, event: WindowEvent) -> Result<(), RequestError> {
+        let _ = event;
+        Err(NotSupportedError::new("inject_event is not supported").into())
+    }
+
     /// Get the raw-window-handle v0.6 display handle.
     #[cfg(feature = "rwh_06")]
     fn rwh_06_display_handle(&self) -> &dyn rwh_06::HasDisplayHandle;
@@ -1284,6 +1720,41 @@ pub trait Window: AsAny + Send + Sync {
     fn rwh_06_window_handle(&self) -> &dyn rwh_06::HasWindowHandle;
 }
 
+/// Whether the desktop is laid out in logical units, in which case the physical positions of the
+/// windows and the monitors are each scaled by their own scale factor.
+const LOGICAL_DESKTOP: bool = cfg!(any(macos_platform, ios_platform, web_platform));
+
+/// The position of the top-left hand corner of the monitor on the desktop.
+fn monitor_origin(monitor: &MonitorHandle) -> Result<PhysicalPosition<i32>, RequestError> {
+    monitor
+        .position()
+        .ok_or_else(|| NotSupportedError::new("the position of the monitor is unknown").into())
+}
+
+/// Converts a physical position relative to `origin` to a position in the desktop units.
+fn surface_to_desktop(
+    origin: PhysicalPosition<i32>,
+    scale_factor: f64,
+    position: PhysicalPosition<f64>,
+) -> (f64, f64) {
+    let (x, y) = (origin.x as f64 + position.x, origin.y as f64 + position.y);
+    if LOGICAL_DESKTOP {
+        (x / scale_factor, y / scale_factor)
+    } else {
+        (x, y)
+    }
+}
+
+/// Converts a position in the desktop units to a physical position relative to `origin`.
+fn desktop_to_surface(
+    origin: PhysicalPosition<i32>,
+    scale_factor: f64,
+    (x, y): (f64, f64),
+) -> PhysicalPosition<f64> {
+    let (x, y) = if LOGICAL_DESKTOP { (x * scale_factor, y * scale_factor) } else { (x, y) };
+    PhysicalPosition::new(x - origin.x as f64, y - origin.y as f64)
+}
+
 impl dyn Window {
     /// Create a new [`WindowAttributes`] which allows modifying the window's attributes before
     /// creation.
@@ -1353,6 +1824,89 @@ pub enum CursorGrabMode {
     Locked,
 }
 
+/// What happens to the cursor grab when the window loses focus.
+///
+/// Use this enum with [`Window::set_cursor_grab_policy`].
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CursorGrabPolicy {
+    /// The grab is left as is, the system could release it while the window isn't focused, e.g.
+    /// when switching to another application.
+    #[default]
+    Manual,
+
+    /// The grab is released when the window loses focus, and established again when it regains
+    /// focus.
+    ///
+    /// Each transition is reported with a [`WindowEvent::CursorGrabChanged`].
+    ///
+    /// [`WindowEvent::CursorGrabChanged`]: crate::event::WindowEvent::CursorGrabChanged
+    ReGrabOnFocus,
+}
+
+/// The coalescing of the pointer motion events.
+///
+/// Use this enum with [`Window::set_motion_coalescing`] to control how often
+/// [`WindowEvent::PointerMoved`] is delivered. Only the events of the same pointer are merged
+/// together, and the merged event always carries the latest position.
+///
+/// [`WindowEvent::PointerMoved`]: crate::event::WindowEvent::PointerMoved
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Coalescing {
+    /// Every motion sample reported by the system is delivered.
+    #[default]
+    None,
+
+    /// Consecutive motion events are merged together, until any other event is delivered to the
+    /// window.
+    ///
+    /// The order of the motion events relative to the other events of the window is preserved.
+    All,
+
+    /// At most one motion event per pointer is delivered per event loop iteration, right before
+    /// [`WindowEvent::RedrawRequested`], or before [`about_to_wait`] when the window isn't
+    /// redrawn.
+    ///
+    /// [`WindowEvent::RedrawRequested`]: crate::event::WindowEvent::RedrawRequested
+    /// [`about_to_wait`]: crate::application::ApplicationHandler::about_to_wait
+    PerFrame,
+}
+
+/// The color format requested for the surface of a window.
+///
+/// See [`WindowAttributes::with_surface_format_hint`].
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SurfaceFormatHint {
+    /// Whether the content is in high dynamic range, with values above `1.0` brighter than the
+    /// SDR white.
+    ///
+    /// HDR content is expected in linear light, like from a floating point swap chain.
+    pub hdr: bool,
+
+    /// The color primaries of the content.
+    pub color_space: SurfaceColorSpace,
+
+    /// Whether the alpha channel of the content is composited with what's behind the window.
+    pub alpha: bool,
+}
+
+/// The color primaries of a surface.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SurfaceColorSpace {
+    /// The sRGB primaries, which are also used by BT.709.
+    #[default]
+    Srgb,
+
+    /// The Display P3 primaries.
+    DisplayP3,
+
+    /// The BT.2020 primaries.
+    Bt2020,
+}
+
 /// Defines the orientation that a window resize will be performed.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -1403,6 +1957,23 @@ pub enum Theme {
     Dark,
 }
 
+/// A color with 8-bit red, green, blue and alpha channels, in the sRGB color space.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Rgba {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+    pub alpha: u8,
+}
+
+impl Rgba {
+    /// Create a color from its channels.
+    pub const fn new(red: u8, green: u8, blue: u8, alpha: u8) -> Self {
+        Self { red, green, blue, alpha }
+    }
+}
+
 /// ## Platform-specific
 ///
 /// - **X11:** Sets the WM's `XUrgencyHint`. No distinction between [`Critical`] and
@@ -1428,6 +1999,18 @@ pub enum UserAttentionType {
     Informational,
 }
 
+/// How an announcement of [`Window::announce`] interrupts the screen reader.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Politeness {
+    /// The message is spoken once the screen reader is idle.
+    #[default]
+    Polite,
+
+    /// The message interrupts the current speech.
+    Assertive,
+}
+
 bitflags::bitflags! {
     #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
     pub struct WindowButtons: u32 {
@@ -1468,7 +2051,18 @@ pub enum WindowLevel {
 ///
 /// ## Platform-specific
 ///
-/// - **iOS / Android / Web / Windows / X11 / macOS / Orbital:** Unsupported.
+/// - **Windows:** Sets the input scope of the window for the Text Services Framework.
+/// - **macOS:** Only the Roman input sources are allowed for [`Password`], [`Digits`], [`Email`]
+///   and [`Url`].
+/// - **iOS:** Changes the type of the on-screen keyboard, and disables the autocorrection for the
+///   purposes other than [`Normal`].
+/// - **Android / Web / X11 / Orbital:** Unsupported.
+///
+/// [`Normal`]: Self::Normal
+/// [`Password`]: Self::Password
+/// [`Digits`]: Self::Digits
+/// [`Email`]: Self::Email
+/// [`Url`]: Self::Url
 #[non_exhaustive]
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -1481,6 +2075,12 @@ pub enum ImePurpose {
     ///
     /// For example, that could alter OSK on Wayland to show extra buttons.
     Terminal,
+    /// The IME is used to input digits.
+    Digits,
+    /// The IME is used to input an email address.
+    Email,
+    /// The IME is used to input a URL.
+    Url,
 }
 
 impl Default for ImePurpose {
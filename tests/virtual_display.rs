@@ -0,0 +1,45 @@
+//! Run a scenario against a virtual display, see the `test_harness` module.
+
+#[cfg(any(x11_platform, wayland_platform))]
+fn main() {
+    use std::io;
+
+    use winit::dpi::PhysicalSize;
+    use winit::event::WindowEvent;
+    use winit::test_harness::{Backend, Scenario, VirtualDisplay};
+    use winit::window::WindowAttributes;
+
+    #[cfg(x11_platform)]
+    let backend = Backend::X11;
+    #[cfg(not(x11_platform))]
+    let backend = Backend::Wayland;
+
+    let display = match VirtualDisplay::spawn(backend) {
+        Ok(display) => display,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            eprintln!("skipping, the display server of {backend:?} isn't installed");
+            return;
+        },
+        Err(err) => panic!("failed to spawn the display server: {err}"),
+    };
+    let event_loop = display.event_loop().unwrap();
+
+    Scenario::new()
+        .create_window(WindowAttributes::default().with_title("virtual display"))
+        .expect("the window to be redrawn", |_, event| {
+            matches!(event, WindowEvent::RedrawRequested)
+        })
+        .act(0, |window| {
+            let _ = window.request_surface_size(PhysicalSize::new(320, 240).into());
+        })
+        .expect("the window to be resized", |_, event| {
+            matches!(event, WindowEvent::SurfaceResized(size) if *size == PhysicalSize::new(320, 240))
+        })
+        .inject(0, WindowEvent::Occluded(true))
+        .expect("the injected event", |_, event| matches!(event, WindowEvent::Occluded(true)))
+        .run(event_loop)
+        .unwrap();
+}
+
+#[cfg(not(any(x11_platform, wayland_platform)))]
+fn main() {}